@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * High-level actions that can be performed in the editor
+ */
+export type Action = { "InsertChar": string } | "InsertNewline" | "InsertTab" | "MoveLeft" | "MoveRight" | "MoveUp" | "MoveDown" | "MoveWordLeft" | "MoveWordRight" | "MoveLineStart" | "MoveLineEnd" | "MovePageUp" | "MovePageDown" | "MoveDocumentStart" | "MoveDocumentEnd" | "SelectLeft" | "SelectRight" | "SelectUp" | "SelectDown" | "SelectWordLeft" | "SelectWordRight" | "SelectLineStart" | "SelectLineEnd" | "SelectDocumentStart" | "SelectDocumentEnd" | "SelectPageUp" | "SelectPageDown" | "SelectAll" | "SelectWord" | "SelectLine" | "ExpandSelection" | "BlockSelectLeft" | "BlockSelectRight" | "BlockSelectUp" | "BlockSelectDown" | "DeleteBackward" | "DeleteForward" | "DeleteWordBackward" | "DeleteWordForward" | "DeleteLine" | "DeleteToLineEnd" | "DeleteToLineStart" | "TransposeChars" | "OpenLine" | "Recenter" | "SetMark" | "Copy" | { "CopyWithTheme": string } | "Cut" | "Paste" | "YankWordForward" | "YankWordBackward" | "YankToLineEnd" | "YankToLineStart" | "AddCursorAbove" | "AddCursorBelow" | "AddCursorNextMatch" | "RemoveSecondaryCursors" | "Save" | "SaveAs" | "Open" | "SwitchProject" | "New" | "Close" | "CloseTab" | "Quit" | "Revert" | "ToggleAutoRevert" | "FormatBuffer" | "GotoLine" | "GoToMatchingBracket" | "JumpToNextError" | "JumpToPreviousError" | "SmartHome" | "DedentSelection" | "ToggleComment" | { "SetBookmark": string } | { "JumpToBookmark": string } | { "ClearBookmark": string } | "ListBookmarks" | "ToggleSearchCaseSensitive" | "ToggleSearchWholeWord" | "ToggleSearchRegex" | "ToggleSearchConfirmEach" | "StartMacroRecording" | "StopMacroRecording" | { "PlayMacro": string } | { "ToggleMacroRecording": string } | { "ShowMacro": string } | "ListMacros" | "PromptRecordMacro" | "PromptPlayMacro" | "PlayLastMacro" | "PromptSetBookmark" | "PromptJumpToBookmark" | "Undo" | "Redo" | "ScrollUp" | "ScrollDown" | "ShowHelp" | "ShowKeyboardShortcuts" | "ShowWarnings" | "ShowLspStatus" | "ClearWarnings" | "CommandPalette" | "ToggleLineWrap" | "ToggleComposeMode" | "SetComposeWidth" | "SelectTheme" | "SelectKeybindingMap" | "SelectCursorStyle" | "SelectLocale" | "NextBuffer" | "PrevBuffer" | "SwitchToPreviousTab" | "SwitchToTabByName" | "ScrollTabsLeft" | "ScrollTabsRight" | "NavigateBack" | "NavigateForward" | "SplitHorizontal" | "SplitVertical" | "CloseSplit" | "NextSplit" | "PrevSplit" | "IncreaseSplitSize" | "DecreaseSplitSize" | "ToggleMaximizeSplit" | "PromptConfirm" | { "PromptConfirmWithText": string } | "PromptCancel" | "PromptBackspace" | "PromptDelete" | "PromptMoveLeft" | "PromptMoveRight" | "PromptMoveStart" | "PromptMoveEnd" | "PromptSelectPrev" | "PromptSelectNext" | "PromptPageUp" | "PromptPageDown" | "PromptAcceptSuggestion" | "PromptMoveWordLeft" | "PromptMoveWordRight" | "PromptDeleteWordForward" | "PromptDeleteWordBackward" | "PromptDeleteToLineEnd" | "PromptCopy" | "PromptCut" | "PromptPaste" | "PromptMoveLeftSelecting" | "PromptMoveRightSelecting" | "PromptMoveHomeSelecting" | "PromptMoveEndSelecting" | "PromptSelectWordLeft" | "PromptSelectWordRight" | "PromptSelectAll" | "FileBrowserToggleHidden" | "PopupSelectNext" | "PopupSelectPrev" | "PopupPageUp" | "PopupPageDown" | "PopupConfirm" | "PopupCancel" | "ToggleFileExplorer" | "ToggleMenuBar" | "ToggleTabBar" | "FocusFileExplorer" | "FocusEditor" | "FileExplorerUp" | "FileExplorerDown" | "FileExplorerPageUp" | "FileExplorerPageDown" | "FileExplorerExpand" | "FileExplorerCollapse" | "FileExplorerOpen" | "FileExplorerRefresh" | "FileExplorerNewFile" | "FileExplorerNewDirectory" | "FileExplorerDelete" | "FileExplorerRename" | "FileExplorerToggleHidden" | "FileExplorerToggleGitignored" | "LspCompletion" | "LspGotoDefinition" | "LspReferences" | "LspRename" | "LspHover" | "LspSignatureHelp" | "LspCodeActions" | "LspRestart" | "LspStop" | "ToggleInlayHints" | "ToggleMouseHover" | "ToggleLineNumbers" | "ToggleScrollSync" | "ToggleMouseCapture" | "ToggleDebugHighlights" | "SetBackground" | "SetBackgroundBlend" | "SetTabSize" | "SetLineEnding" | "ToggleIndentationStyle" | "ToggleTabIndicators" | "ResetBufferSettings" | "DumpConfig" | "Search" | "FindInSelection" | "FindNext" | "FindPrevious" | "FindSelectionNext" | "FindSelectionPrevious" | "Replace" | "QueryReplace" | "MenuActivate" | "MenuClose" | "MenuLeft" | "MenuRight" | "MenuUp" | "MenuDown" | "MenuExecute" | { "MenuOpen": string } | { "SwitchKeybindingMap": string } | { "PluginAction": string } | "OpenSettings" | "CloseSettings" | "SettingsSave" | "SettingsReset" | "SettingsToggleFocus" | "SettingsActivate" | "SettingsSearch" | "SettingsHelp" | "SettingsIncrement" | "SettingsDecrement" | "OpenTerminal" | "CloseTerminal" | "FocusTerminal" | "TerminalEscape" | "ToggleKeyboardCapture" | "TerminalPaste" | "ShellCommand" | "ShellCommandReplace" | "ToUpperCase" | "ToLowerCase" | "CalibrateInput" | "None";
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TsActionPopupAction } from "./TsActionPopupAction";
+
+/**
+ * Options for showActionPopup
+ */
+export type ActionPopupOptions = { 
+/**
+ * Unique identifier for the popup (used in ActionPopupResult)
+ */
+id: string, 
+/**
+ * Title text for the popup
+ */
+title: string, 
+/**
+ * Body message (supports basic formatting)
+ */
+message: string, 
+/**
+ * Action buttons to display
+ */
+actions: Array<TsActionPopupAction>, };
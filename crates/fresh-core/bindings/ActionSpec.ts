@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Specification for an action to execute, with optional repeat count
+ */
+export type ActionSpec = { 
+/**
+ * Action name (e.g., "move_word_right", "delete_line")
+ */
+action: string, 
+/**
+ * Number of times to repeat the action (default 1)
+ */
+count: number, };
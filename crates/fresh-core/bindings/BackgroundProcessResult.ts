@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result from spawning a background process
+ */
+export type BackgroundProcessResult = { 
+/**
+ * Unique process ID for later reference
+ */
+process_id: number, 
+/**
+ * Process exit code (0 usually means success, -1 if killed)
+ * Only present when the process has exited
+ */
+exit_code: number, };
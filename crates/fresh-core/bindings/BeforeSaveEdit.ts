@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Replacement of a byte range, relative to the buffer when the save started
+ */
+export type BeforeSaveEdit = { 
+/**
+ * Start byte offset (inclusive)
+ */
+start: number, 
+/**
+ * End byte offset (exclusive)
+ */
+end: number, 
+/**
+ * Text to put in place of the range
+ */
+text: string, };
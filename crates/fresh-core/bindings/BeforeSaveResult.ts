@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BeforeSaveEdit } from "./BeforeSaveEdit";
+
+/**
+ * Value a `before_file_save` handler returns (or resolves to)
+ */
+export type BeforeSaveResult = { 
+/**
+ * Edits to make before the file is written
+ */
+edits?: Array<BeforeSaveEdit>, 
+/**
+ * Cancel the save; the reason is shown in the status bar
+ */
+veto?: string, };
@@ -0,0 +1,41 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Information about a buffer
+ */
+export type BufferInfo = { 
+/**
+ * Buffer ID
+ */
+id: number, 
+/**
+ * File path (if any)
+ */
+path: string, 
+/**
+ * Whether the buffer has been modified
+ */
+modified: boolean, 
+/**
+ * Length of buffer in bytes
+ */
+length: number, 
+/**
+ * Whether this is a virtual buffer (not backed by a file)
+ */
+is_virtual: boolean, 
+/**
+ * Current view mode of the active split: "source" or "compose"
+ */
+view_mode: string, 
+/**
+ * True if any split showing this buffer has compose mode enabled.
+ * Plugins should use this (not `view_mode`) to decide whether to maintain
+ * decorations, since decorations live on the buffer and are filtered
+ * per-split at render time.
+ */
+is_composing_in_any_split: boolean, 
+/**
+ * Compose width (if set), from the active split's view state
+ */
+compose_width: number | null, };
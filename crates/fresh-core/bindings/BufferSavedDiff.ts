@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Diff between current buffer content and last saved snapshot
+ */
+export type BufferSavedDiff = { equal: boolean, byte_ranges: Array<[number, number]>, line_ranges: Array<[number, number]> | null, };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GrammarPackConfig } from "./GrammarPackConfig";
+import type { LanguagePackConfig } from "./LanguagePackConfig";
+import type { LspServerPackConfig } from "./LspServerPackConfig";
+
+/**
+ * A language in a bundle
+ */
+export type BundleLanguage = { 
+/**
+ * Language identifier (e.g., 'elixir', 'heex')
+ */
+id: string, 
+/**
+ * Grammar configuration
+ */
+grammar?: GrammarPackConfig, 
+/**
+ * Language configuration
+ */
+language?: LanguagePackConfig, 
+/**
+ * LSP server configuration
+ */
+lsp?: LspServerPackConfig, };
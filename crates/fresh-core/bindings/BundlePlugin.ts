@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A plugin in a bundle
+ */
+export type BundlePlugin = { 
+/**
+ * Plugin entry point file relative to package
+ */
+entry: string, };
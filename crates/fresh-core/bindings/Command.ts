@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A command registered by a plugin via the service bridge.
+ * This is a simplified version that the editor converts to its internal Command type.
+ */
+export type Command = { 
+/**
+ * Command name (e.g., "Open File")
+ */
+name: string, 
+/**
+ * Command description
+ */
+description: string, 
+/**
+ * The action name to trigger (for plugin commands, this is the function name)
+ */
+action_name: string, 
+/**
+ * Plugin that registered this command
+ */
+plugin_name: string, 
+/**
+ * Custom contexts required for this command (plugin-defined contexts like "vi-mode")
+ */
+custom_contexts: Array<string>, };
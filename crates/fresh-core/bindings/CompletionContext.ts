@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where completions were requested, passed to completion provider callbacks
+ */
+export type CompletionContext = { bufferId: number, language: string, 
+/**
+ * Byte offset of the cursor
+ */
+position: number, 
+/**
+ * The partial word before the cursor
+ */
+prefix: string, };
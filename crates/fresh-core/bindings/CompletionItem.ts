@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A completion offered by a plugin completion provider
+ */
+export type CompletionItem = { 
+/**
+ * Text shown in the popup; inserted unless `insertText` is set
+ */
+label: string, 
+/**
+ * Text to insert instead of the label
+ */
+insertText?: string, 
+/**
+ * Text matched against what was typed instead of the label
+ */
+filterText?: string, 
+/**
+ * Short description shown next to the label
+ */
+detail?: string, 
+/**
+ * "function", "method", "variable", "class", "struct", "constant", "keyword", ...
+ */
+kind?: string, };
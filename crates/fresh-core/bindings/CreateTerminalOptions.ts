@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options for createTerminal
+ */
+export type CreateTerminalOptions = { 
+/**
+ * Working directory for the terminal (defaults to editor cwd)
+ */
+cwd?: string, 
+/**
+ * Split direction: "horizontal" or "vertical" (default: "vertical")
+ */
+direction?: string, 
+/**
+ * Split ratio 0.0-1.0 (default: 0.5)
+ */
+ratio?: number, 
+/**
+ * Whether to focus the new terminal split (default: true)
+ */
+focus?: boolean, };
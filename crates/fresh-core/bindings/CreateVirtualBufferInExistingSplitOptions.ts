@@ -0,0 +1,43 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TextPropertyEntry } from "./TextPropertyEntry";
+
+/**
+ * Options for createVirtualBufferInExistingSplit
+ */
+export type CreateVirtualBufferInExistingSplitOptions = { 
+/**
+ * Buffer name (displayed in tabs/title)
+ */
+name: string, 
+/**
+ * Target split ID (required)
+ */
+splitId: number, 
+/**
+ * Mode for keybindings (e.g., "git-log", "search-results")
+ */
+mode?: string, 
+/**
+ * Whether buffer is read-only (default: false)
+ */
+readOnly?: boolean, 
+/**
+ * Show line numbers in gutter (default: true)
+ */
+showLineNumbers?: boolean, 
+/**
+ * Show cursor (default: true)
+ */
+showCursors?: boolean, 
+/**
+ * Disable text editing (default: false)
+ */
+editingDisabled?: boolean, 
+/**
+ * Enable line wrapping
+ */
+lineWrap?: boolean, 
+/**
+ * Initial content entries with optional properties
+ */
+entries?: Array<TextPropertyEntry>, };
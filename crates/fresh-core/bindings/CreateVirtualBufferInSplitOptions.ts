@@ -0,0 +1,55 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TextPropertyEntry } from "./TextPropertyEntry";
+
+/**
+ * Options for createVirtualBufferInSplit
+ */
+export type CreateVirtualBufferInSplitOptions = { 
+/**
+ * Buffer name (displayed in tabs/title)
+ */
+name: string, 
+/**
+ * Mode for keybindings (e.g., "git-log", "search-results")
+ */
+mode?: string, 
+/**
+ * Whether buffer is read-only (default: false)
+ */
+readOnly?: boolean, 
+/**
+ * Split ratio 0.0-1.0 (default: 0.5)
+ */
+ratio?: number, 
+/**
+ * Split direction: "horizontal" or "vertical"
+ */
+direction?: string, 
+/**
+ * Panel ID to split from
+ */
+panelId?: string, 
+/**
+ * Show line numbers in gutter (default: true)
+ */
+showLineNumbers?: boolean, 
+/**
+ * Show cursor (default: true)
+ */
+showCursors?: boolean, 
+/**
+ * Disable text editing (default: false)
+ */
+editingDisabled?: boolean, 
+/**
+ * Enable line wrapping
+ */
+lineWrap?: boolean, 
+/**
+ * Place the new buffer before (left/top of) the existing content (default: false)
+ */
+before?: boolean, 
+/**
+ * Initial content entries with optional properties
+ */
+entries?: Array<TextPropertyEntry>, };
@@ -0,0 +1,39 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TextPropertyEntry } from "./TextPropertyEntry";
+
+/**
+ * Options for createVirtualBuffer
+ */
+export type CreateVirtualBufferOptions = { 
+/**
+ * Buffer name (displayed in tabs/title)
+ */
+name: string, 
+/**
+ * Mode for keybindings (e.g., "git-log", "search-results")
+ */
+mode?: string, 
+/**
+ * Whether buffer is read-only (default: false)
+ */
+readOnly?: boolean, 
+/**
+ * Show line numbers in gutter (default: false)
+ */
+showLineNumbers?: boolean, 
+/**
+ * Show cursor (default: true)
+ */
+showCursors?: boolean, 
+/**
+ * Disable text editing (default: false)
+ */
+editingDisabled?: boolean, 
+/**
+ * Hide from tab bar (default: false)
+ */
+hiddenFromTabs?: boolean, 
+/**
+ * Initial content entries with optional properties
+ */
+entries?: Array<TextPropertyEntry>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Information about a cursor in the editor
+ */
+export type CursorInfo = { 
+/**
+ * Byte position of the cursor
+ */
+position: number, 
+/**
+ * Selection range (if any)
+ */
+selection: { start: number; end: number } | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Directory entry returned by readDir
+ */
+export type DirEntry = { 
+/**
+ * File/directory name
+ */
+name: string, 
+/**
+ * True if this is a file
+ */
+is_file: boolean, 
+/**
+ * True if this is a directory
+ */
+is_dir: boolean, };
@@ -0,0 +1,88 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BufferId } from "./BufferId";
+import type { BufferInfo } from "./BufferInfo";
+import type { BufferSavedDiff } from "./BufferSavedDiff";
+import type { CursorInfo } from "./CursorInfo";
+import type { TextProperty } from "./TextProperty";
+import type { ViewportInfo } from "./ViewportInfo";
+
+/**
+ * Snapshot of editor state for plugin queries
+ * This is updated by the editor on each loop iteration
+ */
+export type EditorStateSnapshot = { 
+/**
+ * Currently active buffer ID
+ */
+active_buffer_id: BufferId, 
+/**
+ * Currently active split ID
+ */
+active_split_id: number, 
+/**
+ * Information about all open buffers
+ */
+buffers: { [key in BufferId]?: BufferInfo }, 
+/**
+ * Diff vs last saved snapshot for each buffer (line counts may be unknown)
+ */
+buffer_saved_diffs: { [key in BufferId]?: BufferSavedDiff }, 
+/**
+ * Primary cursor position for the active buffer
+ */
+primary_cursor: CursorInfo | null, 
+/**
+ * All cursor positions for the active buffer
+ */
+all_cursors: Array<CursorInfo>, 
+/**
+ * Viewport information for the active buffer
+ */
+viewport: ViewportInfo | null, 
+/**
+ * Cursor positions per buffer (for buffers other than active)
+ */
+buffer_cursor_positions: { [key in BufferId]?: number }, 
+/**
+ * Text properties per buffer (for virtual buffers with properties)
+ */
+buffer_text_properties: { [key in BufferId]?: Array<TextProperty> }, 
+/**
+ * Selected text from the primary cursor (if any selection exists)
+ * This is populated on each update to avoid needing full buffer access
+ */
+selected_text: string | null, 
+/**
+ * Internal clipboard content (for plugins that need clipboard access)
+ */
+clipboard: string, 
+/**
+ * Editor's working directory (for file operations and spawning processes)
+ */
+working_dir: string, 
+/**
+ * LSP diagnostics per file URI
+ * Maps file URI string to Vec of diagnostics for that file
+ */
+diagnostics: any, 
+/**
+ * Runtime config as serde_json::Value (merged user config + defaults)
+ * This is the runtime config, not just the user's config file
+ */
+config: any, 
+/**
+ * User config as serde_json::Value (only what's in the user's config file)
+ * Fields not present here are using default values
+ */
+user_config: any, 
+/**
+ * Global editor mode for modal editing (e.g., "vi-normal", "vi-insert")
+ * When set, this mode's keybindings take precedence over normal key handling
+ */
+editor_mode: string | null, 
+/**
+ * Plugin-managed per-buffer view state for the active split.
+ * Updated from BufferViewState.plugin_state during snapshot updates.
+ * Also written directly by JS plugins via setViewState for immediate read-back.
+ */
+plugin_view_states: any, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Decoration metadata for a file explorer entry.
+ */
+export type FileExplorerDecoration = { 
+/**
+ * File path to decorate
+ */
+path: string, 
+/**
+ * Symbol to display (e.g., "●", "M", "A")
+ */
+symbol: string, 
+/**
+ * Color as RGB array (rquickjs_serde requires array, not tuple)
+ */
+color: [number, number, number], 
+/**
+ * Priority for display when multiple decorations exist (higher wins)
+ */
+priority: number, };
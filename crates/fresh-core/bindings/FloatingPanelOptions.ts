@@ -0,0 +1,47 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options for showFloatingPanel
+ */
+export type FloatingPanelOptions = { 
+/**
+ * Unique identifier for the panel (used by hideFloatingPanel)
+ */
+id: string, 
+/**
+ * Lines of text to display
+ */
+lines: Array<string>, 
+/**
+ * Title shown in the top border
+ */
+title?: string, 
+/**
+ * Anchor: "cursor" (below the cursor), "aboveCursor", "center" or "bottomRight"
+ * (default: "cursor"). Ignored when x and y are given.
+ */
+anchor?: string, 
+/**
+ * Screen column of the top-left corner
+ */
+x?: number, 
+/**
+ * Screen row of the top-left corner
+ */
+y?: number, 
+/**
+ * Width in columns including the border (default: fits the content)
+ */
+width?: number, 
+/**
+ * Maximum height in rows including the border (default: fits the content)
+ */
+height?: number, 
+/**
+ * Whether to draw a border (default: true)
+ */
+border?: boolean, 
+/**
+ * Stacking order relative to other popups; higher draws on top (default: 0)
+ */
+zIndex?: number, };
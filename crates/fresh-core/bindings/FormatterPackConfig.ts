@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Formatter configuration for language packs
+ */
+export type FormatterPackConfig = { 
+/**
+ * Command to run (e.g., "prettier", "rustfmt")
+ */
+command: string, 
+/**
+ * Arguments to pass to the formatter
+ */
+args?: Array<string>, };
@@ -0,0 +1,66 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BundleLanguage } from "./BundleLanguage";
+import type { BundlePlugin } from "./BundlePlugin";
+import type { GrammarPackConfig } from "./GrammarPackConfig";
+import type { LanguagePackConfig } from "./LanguagePackConfig";
+import type { LspServerPackConfig } from "./LspServerPackConfig";
+import type { PackageTheme } from "./PackageTheme";
+
+/**
+ * The `fresh` section of a package manifest
+ */
+export type FreshPackageConfig = { 
+/**
+ * Minimum required Fresh version
+ */
+min_version?: string, 
+/**
+ * Minimum required plugin API version (integer, e.g. 2)
+ */
+min_api_version?: number, 
+/**
+ * Plugin entry point file (for plugins)
+ */
+entry?: string, 
+/**
+ * Plugin entry point file (alias for entry)
+ */
+main?: string, 
+/**
+ * Events that load the plugin: "onLanguage:<language>" when a file of
+ * the language is opened, "onCommand:<action>" when the action is run,
+ * "*" at startup. Without any, the plugin is loaded at startup.
+ */
+activation_events?: Array<string>, 
+/**
+ * Theme JSON file path (for single theme packages)
+ */
+theme?: string, 
+/**
+ * Theme definitions (for theme packs and bundles)
+ */
+themes?: Array<PackageTheme>, 
+/**
+ * JSON Schema for plugin configuration options
+ */
+config_schema?: Record<string, unknown>, 
+/**
+ * Grammar configuration (for language packs)
+ */
+grammar?: GrammarPackConfig, 
+/**
+ * Language configuration (for language packs)
+ */
+language?: LanguagePackConfig, 
+/**
+ * LSP server configuration (for language packs)
+ */
+lsp?: LspServerPackConfig, 
+/**
+ * Language definitions (for bundles)
+ */
+languages?: Array<BundleLanguage>, 
+/**
+ * Plugin definitions (for bundles)
+ */
+plugins?: Array<BundlePlugin>, };
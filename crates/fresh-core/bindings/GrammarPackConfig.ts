@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Grammar configuration for language packs
+ */
+export type GrammarPackConfig = { 
+/**
+ * Path to grammar file (.sublime-syntax recommended, or .tmLanguage)
+ */
+file: string, 
+/**
+ * File extensions this grammar handles (e.g., ["rs", "rust"])
+ */
+extensions?: Array<string>, 
+/**
+ * Regex pattern for shebang detection
+ */
+firstLine?: string, };
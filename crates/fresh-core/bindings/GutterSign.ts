@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverlayColorSpec } from "./OverlayColorSpec";
+
+/**
+ * A plugin's icon in a line's gutter indicator column
+ */
+export type GutterSign = { 
+/**
+ * Line number (0-indexed)
+ */
+line: number, 
+/**
+ * Symbol to display (e.g., "✓", "✗", "★")
+ */
+symbol: string, 
+/**
+ * Color - RGB array or theme key string (default: "editor.line_number_fg")
+ */
+color?: OverlayColorSpec, 
+/**
+ * Priority against other indicators on the line; higher wins (default: 0)
+ */
+priority?: number, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A callback ID for JavaScript promises in the plugin runtime.
+ *
+ * This newtype distinguishes JS promise callbacks (resolved via `resolve_callback`)
+ * from Rust oneshot channel IDs (resolved via `send_plugin_response`).
+ * Using a newtype prevents accidentally mixing up these two callback mechanisms.
+ */
+export type JsCallbackId = bigint;
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsRange } from "./JsRange";
+
+/**
+ * Diagnostic from LSP
+ */
+export type JsDiagnostic = { 
+/**
+ * Document URI
+ */
+uri: string, 
+/**
+ * Diagnostic message
+ */
+message: string, 
+/**
+ * Severity: 1=Error, 2=Warning, 3=Info, 4=Hint, null=unknown
+ */
+severity: number | null, 
+/**
+ * Range in the document
+ */
+range: JsRange, 
+/**
+ * Source of the diagnostic (e.g., "typescript", "eslint")
+ */
+source?: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Position in a document (line and character)
+ */
+export type JsPosition = { 
+/**
+ * Zero-indexed line number
+ */
+line: number, 
+/**
+ * Zero-indexed character offset
+ */
+character: number, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsPosition } from "./JsPosition";
+
+/**
+ * Range in a document (start and end positions)
+ */
+export type JsRange = { 
+/**
+ * Start position
+ */
+start: JsPosition, 
+/**
+ * End position
+ */
+end: JsPosition, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Context in which a keybinding is active
+ */
+export type KeyContext = "Global" | "Normal" | "Prompt" | "Popup" | "FileExplorer" | "Menu" | "Terminal" | "Settings";
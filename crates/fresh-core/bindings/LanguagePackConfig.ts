@@ -0,0 +1,43 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FormatterPackConfig } from "./FormatterPackConfig";
+
+/**
+ * Language configuration for language packs
+ *
+ * This is a simplified version of the full LanguageConfig, containing only
+ * the fields that can be set via the plugin API.
+ */
+export type LanguagePackConfig = { 
+/**
+ * Comment prefix for line comments (e.g., "//" or "#")
+ */
+commentPrefix?: string, 
+/**
+ * Block comment start marker (e.g., slash-star)
+ */
+blockCommentStart?: string, 
+/**
+ * Block comment end marker (e.g., star-slash)
+ */
+blockCommentEnd?: string, 
+/**
+ * Whether to use tabs instead of spaces for indentation
+ */
+useTabs?: boolean, 
+/**
+ * Tab size (number of spaces per tab level)
+ */
+tabSize?: number, 
+/**
+ * Whether auto-indent is enabled
+ */
+autoIndent?: boolean, 
+/**
+ * Whether to show whitespace tab indicators (→) for this language
+ * Defaults to true. Set to false for languages like Go/Hare that use tabs for indentation.
+ */
+showWhitespaceTabs?: boolean, 
+/**
+ * Formatter configuration
+ */
+formatter?: FormatterPackConfig, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Layout hints supplied by plugins (e.g., Compose mode)
+ */
+export type LayoutHints = { 
+/**
+ * Optional compose width for centering/wrapping
+ */
+composeWidth: number | null, 
+/**
+ * Optional column guides for aligned tables
+ */
+columnGuides: Array<number> | null, };
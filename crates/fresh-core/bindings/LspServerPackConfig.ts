@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * LSP server configuration for language packs
+ */
+export type LspServerPackConfig = { 
+/**
+ * Command to start the LSP server
+ */
+command: string, 
+/**
+ * Arguments to pass to the command
+ */
+args?: Array<string>, 
+/**
+ * Whether to auto-start the server when a matching file is opened
+ */
+autoStart?: boolean, 
+/**
+ * LSP initialization options
+ */
+initializationOptions: Record<string, unknown>, };
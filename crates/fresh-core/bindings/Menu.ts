@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MenuItem } from "./MenuItem";
+
+/**
+ * A top-level menu in the menu bar
+ */
+export type Menu = { 
+/**
+ * Internal identifier for the menu (used for keybinding matching).
+ * This should NOT be translated - use English names like "File", "Edit".
+ * If not set, the label is used for matching (for backward compatibility).
+ */
+id?: string | null, 
+/**
+ * Display label for the menu (can be translated)
+ */
+label: string, 
+/**
+ * Menu items (actions, separators, or submenus)
+ */
+items: Array<MenuItem>, 
+/**
+ * Context condition for menu visibility (e.g., "file_explorer_focused")
+ * If set, the menu is only shown when this condition evaluates to true
+ */
+when?: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A menu item (action, separator, or submenu)
+ */
+export type MenuItem = { separator: boolean, } | { label: string, action: string, args: Record<string, any>, when: string | null, 
+/**
+ * Checkbox state condition (e.g., "line_numbers", "line_wrap")
+ */
+checkbox: string | null, } | { label: string, items: Array<MenuItem>, } | { label: string, source: string, } | { info: string, };
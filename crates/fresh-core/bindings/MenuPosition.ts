@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Position for inserting menu items or menus
+ */
+export type MenuPosition = "Top" | "Bottom" | { "Before": string } | { "After": string };
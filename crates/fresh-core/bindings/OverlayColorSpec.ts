@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Color specification that can be either RGB values or a theme key.
+ *
+ * Theme keys reference colors from the current theme, e.g.:
+ * - "ui.status_bar_bg" - UI status bar background
+ * - "editor.selection_bg" - Editor selection background
+ * - "syntax.keyword" - Syntax highlighting for keywords
+ * - "diagnostic.error" - Error diagnostic color
+ *
+ * When a theme key is used, the color is resolved at render time,
+ * so overlays automatically update when the theme changes.
+ */
+export type OverlayColorSpec = [number, number, number] | string;
@@ -0,0 +1,44 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverlayColorSpec } from "./OverlayColorSpec";
+
+/**
+ * Options for adding an overlay with theme support.
+ *
+ * This struct provides a type-safe way to specify overlay styling
+ * with optional theme key references for colors.
+ */
+export type OverlayOptions = { 
+/**
+ * Foreground color - RGB array or theme key string
+ */
+fg?: OverlayColorSpec | null, 
+/**
+ * Background color - RGB array or theme key string
+ */
+bg?: OverlayColorSpec | null, 
+/**
+ * Whether to render with underline
+ */
+underline: boolean, 
+/**
+ * Whether to render in bold
+ */
+bold: boolean, 
+/**
+ * Whether to render in italic
+ */
+italic: boolean, 
+/**
+ * Whether to render with strikethrough
+ */
+strikethrough: boolean, 
+/**
+ * Whether to extend background color to end of line
+ */
+extendToLineEnd: boolean, 
+/**
+ * Optional URL for OSC 8 terminal hyperlinks.
+ * When set, the overlay text becomes a clickable hyperlink in terminals
+ * that support OSC 8 escape sequences.
+ */
+url?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of package, which decides where it is installed
+ */
+export type PackageKind = "plugin" | "theme" | "theme-pack" | "language" | "bundle";
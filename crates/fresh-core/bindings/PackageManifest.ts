@@ -0,0 +1,48 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FreshPackageConfig } from "./FreshPackageConfig";
+import type { PackageKind } from "./PackageKind";
+
+/**
+ * Schema for Fresh plugin and theme package.json files
+ */
+export type PackageManifest = { 
+/**
+ * Package name (lowercase, hyphens allowed)
+ */
+name: string, 
+/**
+ * Semantic version (e.g., 1.0.0)
+ */
+version: string, 
+/**
+ * Short package description
+ */
+description: string, 
+/**
+ * Package type
+ */
+type: PackageKind, 
+/**
+ * Author name and optional email (e.g., 'Name <email>')
+ */
+author?: string, 
+/**
+ * SPDX license identifier
+ */
+license?: string, 
+/**
+ * Git repository URL
+ */
+repository?: string, 
+/**
+ * Search keywords
+ */
+keywords?: Array<string>, 
+/**
+ * Fresh-specific configuration
+ */
+fresh?: FreshPackageConfig, 
+/**
+ * Package dependencies (reserved for future use)
+ */
+dependencies?: { [key in string]?: string }, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThemeVariant } from "./ThemeVariant";
+
+/**
+ * A theme shipped in a package
+ */
+export type PackageTheme = { 
+/**
+ * Theme JSON file path relative to package
+ */
+file: string, 
+/**
+ * Display name for the theme
+ */
+name: string, 
+/**
+ * Theme variant (dark or light)
+ */
+variant?: ThemeVariant, };
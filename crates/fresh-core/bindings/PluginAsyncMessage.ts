@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PluginResponse } from "./PluginResponse";
+
+/**
+ * Messages sent from async plugin tasks to the synchronous main loop
+ */
+export type PluginAsyncMessage = { "ProcessOutput": { 
+/**
+ * Unique ID for this process
+ */
+process_id: bigint, 
+/**
+ * Standard output
+ */
+stdout: string, 
+/**
+ * Standard error
+ */
+stderr: string, 
+/**
+ * Exit code
+ */
+exit_code: number, } } | { "DelayComplete": { 
+/**
+ * Callback ID to resolve
+ */
+callback_id: bigint, } } | { "ProcessStdout": { process_id: bigint, data: string, } } | { "ProcessStderr": { process_id: bigint, data: string, } } | { "ProcessExit": { process_id: bigint, callback_id: bigint, exit_code: number, } } | { "LspResponse": { language: string, request_id: bigint, result: any, } } | { "PluginResponse": PluginResponse };
@@ -0,0 +1,661 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActionSpec } from "./ActionSpec";
+import type { BeforeSaveResult } from "./BeforeSaveResult";
+import type { BufferId } from "./BufferId";
+import type { Command } from "./Command";
+import type { CompletionItem } from "./CompletionItem";
+import type { FileExplorerDecoration } from "./FileExplorerDecoration";
+import type { FloatingPanelOptions } from "./FloatingPanelOptions";
+import type { GutterSign } from "./GutterSign";
+import type { JsCallbackId } from "./JsCallbackId";
+import type { LanguagePackConfig } from "./LanguagePackConfig";
+import type { LayoutHints } from "./LayoutHints";
+import type { LspServerPackConfig } from "./LspServerPackConfig";
+import type { Menu } from "./Menu";
+import type { MenuItem } from "./MenuItem";
+import type { MenuPosition } from "./MenuPosition";
+import type { OverlayHandle } from "./OverlayHandle";
+import type { OverlayNamespace } from "./OverlayNamespace";
+import type { OverlayOptions } from "./OverlayOptions";
+import type { PluginLogLevel } from "./PluginLogLevel";
+import type { PromptSuggestion } from "./PromptSuggestion";
+import type { ReviewHunk } from "./ReviewHunk";
+import type { SplitId } from "./SplitId";
+import type { StatusSegmentOptions } from "./StatusSegmentOptions";
+import type { TerminalId } from "./TerminalId";
+import type { TextPropertyEntry } from "./TextPropertyEntry";
+import type { TsActionPopupAction } from "./TsActionPopupAction";
+import type { TsCompositeHunk } from "./TsCompositeHunk";
+import type { TsCompositeLayoutConfig } from "./TsCompositeLayoutConfig";
+import type { TsCompositeSourceConfig } from "./TsCompositeSourceConfig";
+import type { ViewTransformPayload } from "./ViewTransformPayload";
+
+/**
+ * Plugin command - allows plugins to send commands to the editor
+ */
+export type PluginCommand = { "InsertText": { buffer_id: BufferId, position: number, text: string, } } | { "DeleteRange": { buffer_id: BufferId, range: { start: number, end: number, }, } } | { "AddOverlay": { buffer_id: BufferId, namespace: OverlayNamespace | null, range: { start: number, end: number, }, 
+/**
+ * Overlay styling options (colors, modifiers, etc.)
+ */
+options: OverlayOptions, } } | { "RemoveOverlay": { buffer_id: BufferId, handle: OverlayHandle, } } | { "SetStatus": { message: string, } } | { "SetStatusSegment": { plugin_name: string, 
+/**
+ * Identifies the segment among the plugin's segments
+ */
+segment_id: string, options: StatusSegmentOptions, } } | { "RemoveStatusSegment": { plugin_name: string, segment_id: string, } } | { "ApplyTheme": { theme_name: string, } } | "ReloadConfig" | { "RegisterCommand": { command: Command, } } | { "UnregisterCommand": { name: string, } } | { "OpenFileInBackground": { path: string, } } | { "InsertAtCursor": { text: string, } } | { "SpawnProcess": { command: string, args: Array<string>, cwd: string | null, callback_id: JsCallbackId, } } | { "GrepProject": { pattern: string, 
+/**
+ * Treat the pattern as a regular expression instead of literal text
+ */
+regex: boolean, 
+/**
+ * Match case; None matches case only when the pattern has an uppercase letter
+ */
+case_sensitive: boolean | null, whole_word: boolean, 
+/**
+ * Stop after this many matching lines
+ */
+max_results: number, callback_id: JsCallbackId, } } | { "Delay": { callback_id: JsCallbackId, duration_ms: bigint, } } | { "StartTimer": { plugin_name: string, 
+/**
+ * Timer ID (generated by plugin runtime)
+ */
+timer_id: bigint, 
+/**
+ * Milliseconds until the timer fires
+ */
+delay_ms: bigint, 
+/**
+ * Fire every `delay_ms` until cleared (setInterval)
+ */
+repeat: boolean, } } | { "ClearTimer": { plugin_name: string, timer_id: bigint, } } | { "BeforeSaveResponse": { plugin_name: string, 
+/**
+ * Save request the handler was called for
+ */
+request_id: bigint, result: BeforeSaveResult, } } | { "SpawnBackgroundProcess": { 
+/**
+ * Unique ID for this process (generated by plugin runtime)
+ */
+process_id: bigint, 
+/**
+ * Command to execute
+ */
+command: string, 
+/**
+ * Arguments to pass
+ */
+args: Array<string>, 
+/**
+ * Working directory (optional)
+ */
+cwd: string | null, 
+/**
+ * Callback ID to call when process exits
+ */
+callback_id: JsCallbackId, } } | { "KillBackgroundProcess": { process_id: bigint, } } | { "SpawnProcessWait": { 
+/**
+ * Process ID to wait for
+ */
+process_id: bigint, 
+/**
+ * Callback ID for async response
+ */
+callback_id: JsCallbackId, } } | { "SetLayoutHints": { buffer_id: BufferId, split_id: SplitId | null, range: { start: number, end: number, }, hints: LayoutHints, } } | { "SetLineNumbers": { buffer_id: BufferId, enabled: boolean, } } | { "SetViewMode": { buffer_id: BufferId, mode: string, } } | { "SetLineWrap": { buffer_id: BufferId, split_id: SplitId | null, enabled: boolean, } } | { "SubmitViewTransform": { buffer_id: BufferId, split_id: SplitId | null, payload: ViewTransformPayload, } } | { "ClearViewTransform": { buffer_id: BufferId, split_id: SplitId | null, } } | { "SetViewState": { buffer_id: BufferId, key: string, value: any, } } | { "ClearAllOverlays": { buffer_id: BufferId, } } | { "ClearNamespace": { buffer_id: BufferId, namespace: OverlayNamespace, } } | { "ClearOverlaysInRange": { buffer_id: BufferId, start: number, end: number, } } | { "AddVirtualText": { buffer_id: BufferId, virtual_text_id: string, position: number, text: string, color: [number, number, number], use_bg: boolean, before: boolean, } } | { "RemoveVirtualText": { buffer_id: BufferId, virtual_text_id: string, } } | { "RemoveVirtualTextsByPrefix": { buffer_id: BufferId, prefix: string, } } | { "ClearVirtualTexts": { buffer_id: BufferId, } } | { "AddVirtualLine": { buffer_id: BufferId, 
+/**
+ * Byte position to anchor the line to
+ */
+position: number, 
+/**
+ * Full line content to display
+ */
+text: string, 
+/**
+ * Foreground color (RGB)
+ */
+fg_color: [number, number, number], 
+/**
+ * Background color (RGB), None = transparent
+ */
+bg_color: [number, number, number] | null, 
+/**
+ * true = above the line containing position, false = below
+ */
+above: boolean, 
+/**
+ * Namespace for bulk removal (e.g., "git-blame")
+ */
+namespace: string, 
+/**
+ * Priority for ordering multiple lines at same position (higher = later)
+ */
+priority: number, } } | { "ClearVirtualTextNamespace": { buffer_id: BufferId, namespace: string, } } | { "AddConceal": { buffer_id: BufferId, 
+/**
+ * Namespace for bulk removal (shared with overlay namespace system)
+ */
+namespace: OverlayNamespace, 
+/**
+ * Byte range to conceal
+ */
+start: number, end: number, 
+/**
+ * Optional replacement text to show instead. None = hide completely.
+ */
+replacement: string | null, } } | { "ClearConcealNamespace": { buffer_id: BufferId, namespace: OverlayNamespace, } } | { "ClearConcealsInRange": { buffer_id: BufferId, start: number, end: number, } } | { "AddFold": { buffer_id: BufferId, 
+/**
+ * Namespace for bulk removal (shared with overlay namespace system)
+ */
+namespace: OverlayNamespace, 
+/**
+ * Byte range to fold
+ */
+start: number, end: number, 
+/**
+ * Text shown in place of the folded range
+ */
+placeholder: string, } } | { "ClearFoldNamespace": { buffer_id: BufferId, namespace: OverlayNamespace, } } | { "AddSoftBreak": { buffer_id: BufferId, 
+/**
+ * Namespace for bulk removal (shared with overlay namespace system)
+ */
+namespace: OverlayNamespace, 
+/**
+ * Byte offset where the break should be injected
+ */
+position: number, 
+/**
+ * Number of hanging indent spaces after the break
+ */
+indent: number, } } | { "ClearSoftBreakNamespace": { buffer_id: BufferId, namespace: OverlayNamespace, } } | { "ClearSoftBreaksInRange": { buffer_id: BufferId, start: number, end: number, } } | { "RefreshLines": { buffer_id: BufferId, } } | "RefreshAllLines" | { "HookCompleted": { hook_name: string, } } | { "SetLineIndicator": { buffer_id: BufferId, 
+/**
+ * Line number (0-indexed)
+ */
+line: number, 
+/**
+ * Namespace for grouping (e.g., "git-gutter", "breakpoints")
+ */
+namespace: string, 
+/**
+ * Symbol to display (e.g., "│", "●", "★")
+ */
+symbol: string, 
+/**
+ * Color as RGB tuple
+ */
+color: [number, number, number], 
+/**
+ * Priority for display when multiple indicators exist (higher wins)
+ */
+priority: number, } } | { "ClearLineIndicators": { buffer_id: BufferId, 
+/**
+ * Namespace to clear (e.g., "git-gutter")
+ */
+namespace: string, } } | { "SetGutterSigns": { plugin_name: string, buffer_id: BufferId, signs: Array<GutterSign>, } } | { "SetFileExplorerDecorations": { 
+/**
+ * Namespace for grouping (e.g., "git-status")
+ */
+namespace: string, 
+/**
+ * Decorations to apply
+ */
+decorations: Array<FileExplorerDecoration>, } } | { "ClearFileExplorerDecorations": { 
+/**
+ * Namespace to clear (e.g., "git-status")
+ */
+namespace: string, } } | { "OpenFileAtLocation": { path: string, line: number | null, column: number | null, } } | { "OpenFileInSplit": { split_id: number, path: string, line: number | null, column: number | null, } } | { "StartPrompt": { label: string, prompt_type: string, 
+/**
+ * Tab marks suggestions and confirming reports every marked one
+ */
+multi_select: boolean, } } | { "StartPromptWithInitial": { label: string, prompt_type: string, initial_value: string, } } | { "StartPromptAsync": { label: string, initial_value: string, callback_id: JsCallbackId, } } | { "SetPromptSuggestions": { suggestions: Array<PromptSuggestion>, } } | { "SetPromptInputSync": { sync: boolean, } } | { "AddMenuItem": { menu_label: string, item: MenuItem, position: MenuPosition, } } | { "AddMenu": { menu: Menu, position: MenuPosition, } } | { "RemoveMenuItem": { menu_label: string, item_label: string, } } | { "RemoveMenu": { menu_label: string, } } | { "CreateVirtualBuffer": { 
+/**
+ * Display name (e.g., "*Diagnostics*")
+ */
+name: string, 
+/**
+ * Mode name for buffer-local keybindings (e.g., "diagnostics-list")
+ */
+mode: string, 
+/**
+ * Whether the buffer is read-only
+ */
+read_only: boolean, } } | { "CreateVirtualBufferWithContent": { 
+/**
+ * Display name (e.g., "*Diagnostics*")
+ */
+name: string, 
+/**
+ * Mode name for buffer-local keybindings (e.g., "diagnostics-list")
+ */
+mode: string, 
+/**
+ * Whether the buffer is read-only
+ */
+read_only: boolean, 
+/**
+ * Entries with text and embedded properties
+ */
+entries: Array<TextPropertyEntry>, 
+/**
+ * Whether to show line numbers in the gutter
+ */
+show_line_numbers: boolean, 
+/**
+ * Whether to show cursors in the buffer
+ */
+show_cursors: boolean, 
+/**
+ * Whether editing is disabled (blocks editing commands)
+ */
+editing_disabled: boolean, 
+/**
+ * Whether this buffer should be hidden from tabs (for composite source buffers)
+ */
+hidden_from_tabs: boolean, 
+/**
+ * Optional request ID for async response
+ */
+request_id: bigint | null, } } | { "CreateVirtualBufferInSplit": { 
+/**
+ * Display name (e.g., "*Diagnostics*")
+ */
+name: string, 
+/**
+ * Mode name for buffer-local keybindings (e.g., "diagnostics-list")
+ */
+mode: string, 
+/**
+ * Whether the buffer is read-only
+ */
+read_only: boolean, 
+/**
+ * Entries with text and embedded properties
+ */
+entries: Array<TextPropertyEntry>, 
+/**
+ * Split ratio (0.0 to 1.0, where 0.5 = equal split)
+ */
+ratio: number, 
+/**
+ * Split direction ("horizontal" or "vertical"), default horizontal
+ */
+direction: string | null, 
+/**
+ * Optional panel ID for idempotent operations (if panel exists, update content)
+ */
+panel_id: string | null, 
+/**
+ * Whether to show line numbers in the buffer (default true)
+ */
+show_line_numbers: boolean, 
+/**
+ * Whether to show cursors in the buffer (default true)
+ */
+show_cursors: boolean, 
+/**
+ * Whether editing is disabled for this buffer (default false)
+ */
+editing_disabled: boolean, 
+/**
+ * Whether line wrapping is enabled for this split (None = use global setting)
+ */
+line_wrap: boolean | null, 
+/**
+ * Place the new buffer before (left/top of) the existing content (default: false/after)
+ */
+before: boolean, 
+/**
+ * Optional request ID for async response (if set, editor will send back buffer ID)
+ */
+request_id: bigint | null, } } | { "SetVirtualBufferContent": { buffer_id: BufferId, 
+/**
+ * Entries with text and embedded properties
+ */
+entries: Array<TextPropertyEntry>, } } | { "SetImagePreview": { buffer_id: BufferId, path: string | null, } } | { "GetTextPropertiesAtCursor": { buffer_id: BufferId, } } | { "DefineMode": { name: string, parent: string | null, bindings: Array<[string, string]>, read_only: boolean, } } | { "RegisterKeybinding": { plugin_name: string, 
+/**
+ * Keys in mode binding syntax (e.g., "C-k C-g")
+ */
+keys: string, 
+/**
+ * Built-in action name or plugin handler name
+ */
+command: string, 
+/**
+ * Key context ("normal", "prompt", ...) or a custom context set with SetContext;
+ * None means "normal"
+ */
+context: string | null, } } | { "UnregisterKeybinding": { plugin_name: string, keys: string, context: string | null, } } | { "RegisterCompletionProvider": { plugin_name: string, 
+/**
+ * Provider ID (generated by plugin runtime)
+ */
+provider_id: bigint, 
+/**
+ * Languages to complete in; empty means every language
+ */
+languages: Array<string>, 
+/**
+ * Characters that open the completion popup right away
+ */
+trigger_chars: Array<string>, 
+/**
+ * Source label shown next to the provider's items
+ */
+label: string, 
+/**
+ * Rank against other sources; language servers rank 0, higher goes first
+ */
+priority: number, } } | { "UnregisterCompletionProvider": { plugin_name: string, provider_id: bigint, } } | { "CompletionItems": { request_id: bigint, items: Array<CompletionItem>, } } | { "PluginUnloaded": { plugin_name: string, } } | { "PluginLog": { plugin_name: string, level: PluginLogLevel, message: string, 
+/**
+ * JS stack trace of an uncaught exception
+ */
+stack: string | null, } } | { "ShowBuffer": { buffer_id: BufferId, } } | { "CreateVirtualBufferInExistingSplit": { 
+/**
+ * Display name (e.g., "*Commit Details*")
+ */
+name: string, 
+/**
+ * Mode name for buffer-local keybindings
+ */
+mode: string, 
+/**
+ * Whether the buffer is read-only
+ */
+read_only: boolean, 
+/**
+ * Entries with text and embedded properties
+ */
+entries: Array<TextPropertyEntry>, 
+/**
+ * Target split ID where the buffer should be displayed
+ */
+split_id: SplitId, 
+/**
+ * Whether to show line numbers in the buffer (default true)
+ */
+show_line_numbers: boolean, 
+/**
+ * Whether to show cursors in the buffer (default true)
+ */
+show_cursors: boolean, 
+/**
+ * Whether editing is disabled for this buffer (default false)
+ */
+editing_disabled: boolean, 
+/**
+ * Whether line wrapping is enabled for this split (None = use global setting)
+ */
+line_wrap: boolean | null, 
+/**
+ * Optional request ID for async response
+ */
+request_id: bigint | null, } } | { "CloseBuffer": { buffer_id: BufferId, } } | { "CreateCompositeBuffer": { 
+/**
+ * Display name (shown in tab bar)
+ */
+name: string, 
+/**
+ * Mode name for keybindings (e.g., "diff-view")
+ */
+mode: string, 
+/**
+ * Layout configuration
+ */
+layout: TsCompositeLayoutConfig, 
+/**
+ * Source pane configurations
+ */
+sources: Array<TsCompositeSourceConfig>, 
+/**
+ * Diff hunks for line alignment (optional)
+ */
+hunks: Array<TsCompositeHunk> | null, 
+/**
+ * Request ID for async response
+ */
+request_id: bigint | null, } } | { "UpdateCompositeAlignment": { buffer_id: BufferId, hunks: Array<TsCompositeHunk>, } } | { "CloseCompositeBuffer": { buffer_id: BufferId, } } | { "FocusSplit": { split_id: SplitId, } } | { "SetSplitBuffer": { split_id: SplitId, buffer_id: BufferId, } } | { "SetSplitScroll": { split_id: SplitId, top_byte: number, } } | { "RequestHighlights": { buffer_id: BufferId, range: { start: number, end: number, }, request_id: bigint, } } | { "CloseSplit": { split_id: SplitId, } } | { "SetSplitRatio": { split_id: SplitId, 
+/**
+ * Ratio between 0.0 and 1.0 (0.5 = equal split)
+ */
+ratio: number, } } | { "SetSplitLabel": { split_id: SplitId, label: string, } } | { "ClearSplitLabel": { split_id: SplitId, } } | { "GetSplitByLabel": { label: string, request_id: bigint, } } | { "DistributeSplitsEvenly": { 
+/**
+ * Split IDs to distribute evenly
+ */
+split_ids: Array<SplitId>, } } | { "SetBufferCursor": { buffer_id: BufferId, 
+/**
+ * Byte offset position for the cursor
+ */
+position: number, } } | { "SendLspRequest": { language: string, method: string, params: any, request_id: bigint, } } | { "SetClipboard": { text: string, } } | "DeleteSelection" | { "SetContext": { 
+/**
+ * Context name (e.g., "config-editor")
+ */
+name: string, 
+/**
+ * Whether the context is active
+ */
+active: boolean, } } | { "SetReviewDiffHunks": { hunks: Array<ReviewHunk>, } } | { "ExecuteAction": { 
+/**
+ * Action name (e.g., "move_word_right", "move_line_end")
+ */
+action_name: string, } } | { "ExecuteActions": { 
+/**
+ * List of actions to execute in sequence
+ */
+actions: Array<ActionSpec>, } } | { "GetBufferText": { 
+/**
+ * Buffer ID
+ */
+buffer_id: BufferId, 
+/**
+ * Start byte offset
+ */
+start: number, 
+/**
+ * End byte offset
+ */
+end: number, 
+/**
+ * Request ID for async response
+ */
+request_id: bigint, } } | { "GetLineStartPosition": { 
+/**
+ * Buffer ID (0 for active buffer)
+ */
+buffer_id: BufferId, 
+/**
+ * Line number (0-indexed)
+ */
+line: number, 
+/**
+ * Request ID for async response
+ */
+request_id: bigint, } } | { "GetLineEndPosition": { 
+/**
+ * Buffer ID (0 for active buffer)
+ */
+buffer_id: BufferId, 
+/**
+ * Line number (0-indexed)
+ */
+line: number, 
+/**
+ * Request ID for async response
+ */
+request_id: bigint, } } | { "GetBufferLineCount": { 
+/**
+ * Buffer ID (0 for active buffer)
+ */
+buffer_id: BufferId, 
+/**
+ * Request ID for async response
+ */
+request_id: bigint, } } | { "ScrollToLineCenter": { 
+/**
+ * Split ID to scroll
+ */
+split_id: SplitId, 
+/**
+ * Buffer ID containing the line
+ */
+buffer_id: BufferId, 
+/**
+ * Line number to center (0-indexed)
+ */
+line: number, } } | { "SetEditorMode": { 
+/**
+ * Mode name (e.g., "vi-normal", "vi-insert") or None to clear
+ */
+mode: string | null, } } | { "ShowActionPopup": { 
+/**
+ * Unique identifier for the popup (used in ActionPopupResult)
+ */
+popup_id: string, 
+/**
+ * Title text for the popup
+ */
+title: string, 
+/**
+ * Body message (supports basic formatting)
+ */
+message: string, 
+/**
+ * Action buttons to display
+ */
+actions: Array<TsActionPopupAction>, } } | { "ShowFloatingPanel": { options: FloatingPanelOptions, } } | { "HideFloatingPanel": { 
+/**
+ * Identifier the panel was shown with
+ */
+panel_id: string, } } | { "DisableLspForLanguage": { 
+/**
+ * The language to disable LSP for (e.g., "python", "rust")
+ */
+language: string, } } | { "SetLspRootUri": { 
+/**
+ * The language to set root URI for (e.g., "csharp", "rust")
+ */
+language: string, 
+/**
+ * The root URI (file:// URL format)
+ */
+uri: string, } } | { "CreateScrollSyncGroup": { 
+/**
+ * Plugin-assigned group ID
+ */
+group_id: number, 
+/**
+ * The left (primary) split - scroll position is tracked in this split's line space
+ */
+left_split: SplitId, 
+/**
+ * The right (secondary) split - position is derived from anchors
+ */
+right_split: SplitId, } } | { "SetScrollSyncAnchors": { 
+/**
+ * The group ID returned by CreateScrollSyncGroup
+ */
+group_id: number, 
+/**
+ * List of (left_line, right_line) pairs marking corresponding positions
+ */
+anchors: Array<[number, number]>, } } | { "RemoveScrollSyncGroup": { 
+/**
+ * The group ID returned by CreateScrollSyncGroup
+ */
+group_id: number, } } | { "SaveBufferToPath": { 
+/**
+ * Buffer ID to save
+ */
+buffer_id: BufferId, 
+/**
+ * Path to save to
+ */
+path: string, } } | { "LoadPlugin": { 
+/**
+ * Path to the plugin file (.ts or .js)
+ */
+path: string, 
+/**
+ * Callback ID for async response (success/failure)
+ */
+callback_id: JsCallbackId, } } | { "UnloadPlugin": { 
+/**
+ * Plugin name (as registered)
+ */
+name: string, 
+/**
+ * Callback ID for async response (success/failure)
+ */
+callback_id: JsCallbackId, } } | { "ReloadPlugin": { 
+/**
+ * Plugin name (as registered)
+ */
+name: string, 
+/**
+ * Callback ID for async response (success/failure)
+ */
+callback_id: JsCallbackId, } } | { "ListPlugins": { 
+/**
+ * Callback ID for async response (JSON array of plugin info)
+ */
+callback_id: JsCallbackId, } } | { "SetPluginEnabled": { 
+/**
+ * Plugin name (as registered)
+ */
+name: string, enabled: boolean, 
+/**
+ * Callback ID for async response (success/failure)
+ */
+callback_id: JsCallbackId, } } | "ReloadThemes" | { "RegisterGrammar": { 
+/**
+ * Language identifier (e.g., "elixir", "zig")
+ */
+language: string, 
+/**
+ * Path to the grammar file (.sublime-syntax or .tmLanguage)
+ */
+grammar_path: string, 
+/**
+ * File extensions to associate with this grammar (e.g., ["ex", "exs"])
+ */
+extensions: Array<string>, } } | { "RegisterLanguageConfig": { 
+/**
+ * Language identifier (e.g., "elixir")
+ */
+language: string, 
+/**
+ * Language configuration
+ */
+config: LanguagePackConfig, } } | { "RegisterLspServer": { 
+/**
+ * Language identifier (e.g., "elixir")
+ */
+language: string, 
+/**
+ * LSP server configuration
+ */
+config: LspServerPackConfig, } } | "ReloadGrammars" | { "CreateTerminal": { 
+/**
+ * Working directory for the terminal (defaults to editor cwd)
+ */
+cwd: string | null, 
+/**
+ * Split direction ("horizontal" or "vertical"), default vertical
+ */
+direction: string | null, 
+/**
+ * Split ratio (0.0 to 1.0), default 0.5
+ */
+ratio: number | null, 
+/**
+ * Whether to focus the new terminal split (default true)
+ */
+focus: boolean | null, 
+/**
+ * Callback ID for async response
+ */
+request_id: bigint, } } | { "SendTerminalInput": { 
+/**
+ * The terminal ID (from TerminalResult)
+ */
+terminal_id: TerminalId, 
+/**
+ * Data to write to the terminal PTY (UTF-8 string, may include escape sequences)
+ */
+data: string, } } | { "CloseTerminal": { 
+/**
+ * The terminal ID to close
+ */
+terminal_id: TerminalId, } };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Severity of a message in the plugin console
+ */
+export type PluginLogLevel = "error" | "warn" | "info" | "debug";
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BufferId } from "./BufferId";
+import type { SplitId } from "./SplitId";
+import type { TerminalId } from "./TerminalId";
+import type { TsHighlightSpan } from "./TsHighlightSpan";
+
+/**
+ * Response from the editor for async plugin operations
+ */
+export type PluginResponse = { "VirtualBufferCreated": { request_id: bigint, buffer_id: BufferId, split_id: SplitId | null, } } | { "TerminalCreated": { request_id: bigint, buffer_id: BufferId, terminal_id: TerminalId, split_id: SplitId | null, } } | { "LspRequest": { request_id: bigint, result: any, } } | { "HighlightsComputed": { request_id: bigint, spans: Array<TsHighlightSpan>, } } | { "BufferText": { request_id: bigint, text: { Ok : string } | { Err : string }, } } | { "LineStartPosition": { request_id: bigint, 
+/**
+ * None if line is out of range, Some(offset) for valid line
+ */
+position: number | null, } } | { "LineEndPosition": { request_id: bigint, 
+/**
+ * None if line is out of range, Some(offset) for valid line
+ */
+position: number | null, } } | { "BufferLineCount": { request_id: bigint, 
+/**
+ * None if buffer not found, Some(count) for valid buffer
+ */
+count: number | null, } } | { "CompositeBufferCreated": { request_id: bigint, buffer_id: BufferId, } } | { "SplitByLabel": { request_id: bigint, split_id: SplitId | null, } };
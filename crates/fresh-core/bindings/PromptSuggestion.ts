@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A single suggestion item for autocomplete
+ */
+export type PromptSuggestion = { 
+/**
+ * The text to display
+ */
+text: string, 
+/**
+ * Optional description
+ */
+description?: string, 
+/**
+ * The value to use when selected (defaults to text if None)
+ */
+value?: string, 
+/**
+ * Whether this suggestion is disabled (greyed out, defaults to false)
+ */
+disabled?: boolean, 
+/**
+ * Optional keyboard shortcut
+ */
+keybinding?: string, };
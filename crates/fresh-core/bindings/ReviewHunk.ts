@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HunkStatus } from "./HunkStatus";
+
+/**
+ * A high-level hunk directive for the Review Diff tool
+ */
+export type ReviewHunk = { id: string, file: string, context_header: string, status: HunkStatus, 
+/**
+ * 0-indexed line range in the base (HEAD) version
+ */
+base_range: [number, number] | null, 
+/**
+ * 0-indexed line range in the modified (Working) version
+ */
+modified_range: [number, number] | null, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result from spawning a process with spawnProcess
+ */
+export type SpawnResult = { 
+/**
+ * Complete stdout as string
+ */
+stdout: string, 
+/**
+ * Complete stderr as string
+ */
+stderr: string, 
+/**
+ * Process exit code (0 usually means success, -1 if killed)
+ */
+exit_code: number, };
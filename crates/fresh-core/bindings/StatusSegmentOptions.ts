@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OverlayColorSpec } from "./OverlayColorSpec";
+
+/**
+ * Options for registerStatusSegment
+ */
+export type StatusSegmentOptions = { 
+/**
+ * Text shown in the status bar
+ */
+text: string, 
+/**
+ * Foreground color - RGB array or theme key string (default: the status bar's)
+ */
+fg?: OverlayColorSpec, 
+/**
+ * Background color - RGB array or theme key string (default: the status bar's)
+ */
+bg?: OverlayColorSpec, 
+/**
+ * Order among segments; higher is further left (default: 0)
+ */
+priority?: number, 
+/**
+ * Command or action run when the segment is clicked
+ */
+command?: string, };
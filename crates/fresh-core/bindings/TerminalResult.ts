@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result of creating a terminal
+ */
+export type TerminalResult = { 
+/**
+ * The created buffer ID (for use with setSplitBuffer, etc.)
+ */
+bufferId: number, 
+/**
+ * The terminal ID (for use with sendTerminalInput, closeTerminal)
+ */
+terminalId: number, 
+/**
+ * The split ID (if created in a new split)
+ */
+splitId: number | null, };
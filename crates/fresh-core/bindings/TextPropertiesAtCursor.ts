@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result of getTextPropertiesAtCursor - array of property objects
+ *
+ * Each element contains the properties from a text property span that overlaps
+ * with the cursor position. Properties are dynamic key-value pairs set by plugins.
+ */
+export type TextPropertiesAtCursor = Array<Record<string, unknown>>;
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A text property that associates metadata with a range of text
+ */
+export type TextProperty = { 
+/**
+ * Start byte offset (inclusive)
+ */
+start: number, 
+/**
+ * End byte offset (exclusive)
+ */
+end: number, 
+/**
+ * Arbitrary properties as key-value pairs
+ */
+properties: Record<string, any>, };
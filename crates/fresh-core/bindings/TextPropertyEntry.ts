@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Entry for virtual buffer content with optional text properties (JS API version)
+ */
+export type TextPropertyEntry = { 
+/**
+ * Text content for this entry
+ */
+text: string, 
+/**
+ * Optional properties attached to this text (e.g., file path, line number)
+ */
+properties?: Record<string, unknown>, };
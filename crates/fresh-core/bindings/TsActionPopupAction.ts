@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Action button for action popups
+ */
+export type TsActionPopupAction = { 
+/**
+ * Unique action identifier (returned in ActionPopupResult)
+ */
+id: string, 
+/**
+ * Display text for the button (can include command hints)
+ */
+label: string, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Diff hunk for composite buffer alignment
+ */
+export type TsCompositeHunk = { 
+/**
+ * Starting line in old buffer (0-indexed)
+ */
+oldStart: number, 
+/**
+ * Number of lines in old buffer
+ */
+oldCount: number, 
+/**
+ * Starting line in new buffer (0-indexed)
+ */
+newStart: number, 
+/**
+ * Number of lines in new buffer
+ */
+newCount: number, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Layout configuration for composite buffers
+ */
+export type TsCompositeLayoutConfig = { 
+/**
+ * Layout type: "side-by-side", "stacked", or "unified"
+ */
+type: string, 
+/**
+ * Width ratios for side-by-side (e.g., [0.5, 0.5])
+ */
+ratios: Array<number> | null, 
+/**
+ * Show separator between panes
+ */
+showSeparator: boolean, 
+/**
+ * Spacing for stacked layout
+ */
+spacing: number | null, };
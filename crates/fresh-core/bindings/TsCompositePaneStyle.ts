@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Style configuration for a composite pane
+ */
+export type TsCompositePaneStyle = { 
+/**
+ * Background color for added lines (RGB)
+ * Using [u8; 3] instead of (u8, u8, u8) for better rquickjs_serde compatibility
+ */
+addBg: [number, number, number] | null, 
+/**
+ * Background color for removed lines (RGB)
+ */
+removeBg: [number, number, number] | null, 
+/**
+ * Background color for modified lines (RGB)
+ */
+modifyBg: [number, number, number] | null, 
+/**
+ * Gutter style: "line-numbers", "diff-markers", "both", or "none"
+ */
+gutterStyle: string | null, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TsCompositePaneStyle } from "./TsCompositePaneStyle";
+
+/**
+ * Source pane configuration for composite buffers
+ */
+export type TsCompositeSourceConfig = { 
+/**
+ * Buffer ID of the source buffer (required)
+ */
+bufferId: number, 
+/**
+ * Label for this pane (e.g., "OLD", "NEW")
+ */
+label: string, 
+/**
+ * Whether this pane is editable
+ */
+editable: boolean, 
+/**
+ * Style configuration
+ */
+style: TsCompositePaneStyle | null, };
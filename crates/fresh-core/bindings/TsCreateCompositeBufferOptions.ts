@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TsCompositeHunk } from "./TsCompositeHunk";
+import type { TsCompositeLayoutConfig } from "./TsCompositeLayoutConfig";
+import type { TsCompositeSourceConfig } from "./TsCompositeSourceConfig";
+
+/**
+ * Options for creating a composite buffer (used by plugin API)
+ */
+export type TsCreateCompositeBufferOptions = { 
+/**
+ * Buffer name (displayed in tabs/title)
+ */
+name: string, 
+/**
+ * Mode for keybindings
+ */
+mode: string, 
+/**
+ * Layout configuration
+ */
+layout: TsCompositeLayoutConfig, 
+/**
+ * Source pane configurations
+ */
+sources: Array<TsCompositeSourceConfig>, 
+/**
+ * Diff hunks for alignment (optional)
+ */
+hunks: Array<TsCompositeHunk> | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Syntax highlight span for a buffer range
+ */
+export type TsHighlightSpan = { start: number, end: number, color: [number, number, number], bold: boolean, italic: boolean, };
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Styling for view tokens (used for injected annotations)
+ *
+ * This allows plugins to specify styling for tokens that don't have a source
+ * mapping (sourceOffset: None), such as annotation headers in git blame.
+ * For tokens with sourceOffset: Some(_), syntax highlighting is applied instead.
+ */
+export type ViewTokenStyle = { 
+/**
+ * Foreground color as RGB tuple
+ */
+fg: [number, number, number] | null, 
+/**
+ * Background color as RGB tuple
+ */
+bg: [number, number, number] | null, 
+/**
+ * Whether to render in bold
+ */
+bold: boolean, 
+/**
+ * Whether to render in italic
+ */
+italic: boolean, };
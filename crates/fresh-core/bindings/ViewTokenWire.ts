@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ViewTokenStyle } from "./ViewTokenStyle";
+import type { ViewTokenWireKind } from "./ViewTokenWireKind";
+
+/**
+ * Wire-format view token with optional source mapping and styling
+ */
+export type ViewTokenWire = { 
+/**
+ * Source byte offset in the buffer. None for injected content (annotations).
+ */
+source_offset: number | null, 
+/**
+ * The token content
+ */
+kind: ViewTokenWireKind, 
+/**
+ * Optional styling for injected content (only used when source_offset is None)
+ */
+style?: ViewTokenStyle, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Wire-format view token kind (serialized for plugin transforms)
+ */
+export type ViewTokenWireKind = { "Text": string } | "Newline" | "Space" | "Break" | { "BinaryByte": number };
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LayoutHints } from "./LayoutHints";
+import type { ViewTokenWire } from "./ViewTokenWire";
+
+/**
+ * Transformed view stream payload (plugin-provided)
+ */
+export type ViewTransformPayload = { 
+/**
+ * Byte range this transform applies to (viewport)
+ */
+range: { start: number, end: number, }, 
+/**
+ * Tokens in wire format
+ */
+tokens: Array<ViewTokenWire>, 
+/**
+ * Layout hints
+ */
+layout_hints: LayoutHints | null, };
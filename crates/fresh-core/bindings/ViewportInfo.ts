@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Information about the viewport
+ */
+export type ViewportInfo = { 
+/**
+ * Byte position of the first visible line
+ */
+topByte: number, 
+/**
+ * Left column offset (horizontal scroll)
+ */
+leftColumn: number, 
+/**
+ * Viewport width
+ */
+width: number, 
+/**
+ * Viewport height
+ */
+height: number, };
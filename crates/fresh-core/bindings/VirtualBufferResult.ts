@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result of creating a virtual buffer
+ */
+export type VirtualBufferResult = { 
+/**
+ * The created buffer ID
+ */
+bufferId: number, 
+/**
+ * The split ID (if created in a new split)
+ */
+splitId: number | null, };
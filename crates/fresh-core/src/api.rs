@@ -53,6 +53,7 @@ use crate::BufferId;
 use crate::SplitId;
 use crate::TerminalId;
 use lsp_types;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
@@ -422,7 +423,7 @@ pub struct LayoutHints {
 ///
 /// When a theme key is used, the color is resolved at render time,
 /// so overlays automatically update when the theme changes.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[serde(untagged)]
 #[ts(export)]
 pub enum OverlayColorSpec {
@@ -807,6 +808,28 @@ pub enum MenuPosition {
     After(String),
 }
 
+/// Severity of a message in the plugin console
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum PluginLogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl PluginLogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PluginLogLevel::Error => "error",
+            PluginLogLevel::Warn => "warn",
+            PluginLogLevel::Info => "info",
+            PluginLogLevel::Debug => "debug",
+        }
+    }
+}
+
 /// Plugin command - allows plugins to send commands to the editor
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -845,6 +868,20 @@ pub enum PluginCommand {
     /// Set status message
     SetStatus { message: String },
 
+    /// Add or update a plugin's status bar segment
+    SetStatusSegment {
+        plugin_name: String,
+        /// Identifies the segment among the plugin's segments
+        segment_id: String,
+        options: StatusSegmentOptions,
+    },
+
+    /// Remove a plugin's status bar segment
+    RemoveStatusSegment {
+        plugin_name: String,
+        segment_id: String,
+    },
+
     /// Apply a theme by name
     ApplyTheme { theme_name: String },
 
@@ -872,12 +909,48 @@ pub enum PluginCommand {
         callback_id: JsCallbackId,
     },
 
+    /// Search the files of the working directory for a pattern (async,
+    /// resolves callback with the matching lines)
+    GrepProject {
+        pattern: String,
+        /// Treat the pattern as a regular expression instead of literal text
+        regex: bool,
+        /// Match case; None matches case only when the pattern has an uppercase letter
+        case_sensitive: Option<bool>,
+        whole_word: bool,
+        /// Stop after this many matching lines
+        max_results: usize,
+        callback_id: JsCallbackId,
+    },
+
     /// Delay/sleep for a duration (async, resolves callback when done)
     Delay {
         callback_id: JsCallbackId,
         duration_ms: u64,
     },
 
+    /// Start a plugin timer; the editor fires it from its main loop
+    StartTimer {
+        plugin_name: String,
+        /// Timer ID (generated by plugin runtime)
+        timer_id: u64,
+        /// Milliseconds until the timer fires
+        delay_ms: u64,
+        /// Fire every `delay_ms` until cleared (setInterval)
+        repeat: bool,
+    },
+
+    /// Cancel a plugin timer
+    ClearTimer { plugin_name: String, timer_id: u64 },
+
+    /// Answer of a plugin's `before_file_save` handler to a pending save
+    BeforeSaveResponse {
+        plugin_name: String,
+        /// Save request the handler was called for
+        request_id: u64,
+        result: BeforeSaveResult,
+    },
+
     /// Spawn a long-running background process
     /// Unlike SpawnProcess, this returns immediately with a process handle
     /// and provides streaming output via hooks
@@ -1045,6 +1118,24 @@ pub enum PluginCommand {
         end: usize,
     },
 
+    /// Fold a byte range: hide it in every view mode, showing a placeholder instead
+    AddFold {
+        buffer_id: BufferId,
+        /// Namespace for bulk removal (shared with overlay namespace system)
+        namespace: OverlayNamespace,
+        /// Byte range to fold
+        start: usize,
+        end: usize,
+        /// Text shown in place of the folded range
+        placeholder: String,
+    },
+
+    /// Remove all folds in a namespace
+    ClearFoldNamespace {
+        buffer_id: BufferId,
+        namespace: OverlayNamespace,
+    },
+
     /// Add a soft break point for marker-based line wrapping.
     /// The break is stored as a marker that auto-adjusts on buffer edits,
     /// eliminating the flicker caused by async view_transform round-trips.
@@ -1107,6 +1198,13 @@ pub enum PluginCommand {
         namespace: String,
     },
 
+    /// Replace a plugin's gutter signs in a buffer
+    SetGutterSigns {
+        plugin_name: String,
+        buffer_id: BufferId,
+        signs: Vec<GutterSign>,
+    },
+
     /// Set file explorer decorations for a namespace
     SetFileExplorerDecorations {
         /// Namespace for grouping (e.g., "git-status")
@@ -1143,6 +1241,8 @@ pub enum PluginCommand {
     StartPrompt {
         label: String,
         prompt_type: String, // e.g., "git-grep", "git-find-file"
+        /// Tab marks suggestions and confirming reports every marked one
+        multi_select: bool,
     },
 
     /// Start a prompt with pre-filled initial value
@@ -1259,6 +1359,13 @@ pub enum PluginCommand {
         entries: Vec<TextPropertyEntry>,
     },
 
+    /// Show an image file in place of a buffer's contents, or the contents
+    /// again when `path` is None
+    SetImagePreview {
+        buffer_id: BufferId,
+        path: Option<PathBuf>,
+    },
+
     /// Get text properties at the cursor position in a buffer
     GetTextPropertiesAtCursor { buffer_id: BufferId },
 
@@ -1270,6 +1377,64 @@ pub enum PluginCommand {
         read_only: bool,
     },
 
+    /// Bind a key sequence to a plugin command or built-in action
+    RegisterKeybinding {
+        plugin_name: String,
+        /// Keys in mode binding syntax (e.g., "C-k C-g")
+        keys: String,
+        /// Built-in action name or plugin handler name
+        command: String,
+        /// Key context ("normal", "prompt", ...) or a custom context set with SetContext;
+        /// None means "normal"
+        context: Option<String>,
+    },
+
+    /// Remove a key binding previously registered by the plugin
+    UnregisterKeybinding {
+        plugin_name: String,
+        keys: String,
+        context: Option<String>,
+    },
+
+    /// Offer completions from a plugin callback alongside the language servers'
+    RegisterCompletionProvider {
+        plugin_name: String,
+        /// Provider ID (generated by plugin runtime)
+        provider_id: u64,
+        /// Languages to complete in; empty means every language
+        languages: Vec<String>,
+        /// Characters that open the completion popup right away
+        trigger_chars: Vec<char>,
+        /// Source label shown next to the provider's items
+        label: String,
+        /// Rank against other sources; language servers rank 0, higher goes first
+        priority: i32,
+    },
+
+    /// Remove a completion provider registered by the plugin
+    UnregisterCompletionProvider {
+        plugin_name: String,
+        provider_id: u64,
+    },
+
+    /// Items a completion provider returned for a completion request
+    CompletionItems {
+        request_id: u64,
+        items: Vec<PluginCompletionItem>,
+    },
+
+    /// A plugin was unloaded; the editor drops what it registered (e.g., key bindings)
+    PluginUnloaded { plugin_name: String },
+
+    /// A plugin log message or uncaught exception for the plugin console
+    PluginLog {
+        plugin_name: String,
+        level: PluginLogLevel,
+        message: String,
+        /// JS stack trace of an uncaught exception
+        stack: Option<String>,
+    },
+
     /// Switch the current split to display a buffer
     ShowBuffer { buffer_id: BufferId },
 
@@ -1495,6 +1660,16 @@ pub enum PluginCommand {
         actions: Vec<ActionPopupAction>,
     },
 
+    /// Show a read-only floating panel over the editor content
+    /// Showing a panel with an id that is already visible replaces it
+    ShowFloatingPanel { options: FloatingPanelOptions },
+
+    /// Hide a floating panel previously shown with ShowFloatingPanel
+    HideFloatingPanel {
+        /// Identifier the panel was shown with
+        panel_id: String,
+    },
+
     /// Disable LSP for a specific language and persist to config
     DisableLspForLanguage {
         /// The language to disable LSP for (e.g., "python", "rust")
@@ -1581,6 +1756,16 @@ pub enum PluginCommand {
         callback_id: JsCallbackId,
     },
 
+    /// Enable or disable a plugin and save the choice to the user config
+    /// Disabling unloads the plugin, enabling loads it again
+    SetPluginEnabled {
+        /// Plugin name (as registered)
+        name: String,
+        enabled: bool,
+        /// Callback ID for async response (success/failure)
+        callback_id: JsCallbackId,
+    },
+
     /// Reload the theme registry from disk
     /// Call this after installing a theme package or saving a new theme
     ReloadThemes,
@@ -1668,9 +1853,9 @@ impl PluginCommand {
 ///
 /// This is a simplified version of the full LanguageConfig, containing only
 /// the fields that can be set via the plugin API.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
-#[ts(export)]
+#[ts(export, optional_fields)]
 pub struct LanguagePackConfig {
     /// Comment prefix for line comments (e.g., "//" or "#")
     #[serde(default)]
@@ -1690,6 +1875,7 @@ pub struct LanguagePackConfig {
 
     /// Tab size (number of spaces per tab level)
     #[serde(default)]
+    #[schemars(range(min = 1))]
     pub tab_size: Option<usize>,
 
     /// Whether auto-indent is enabled
@@ -1707,28 +1893,30 @@ pub struct LanguagePackConfig {
 }
 
 /// Formatter configuration for language packs
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
-#[ts(export)]
+#[ts(export, optional_fields)]
 pub struct FormatterPackConfig {
     /// Command to run (e.g., "prettier", "rustfmt")
     pub command: String,
 
     /// Arguments to pass to the formatter
     #[serde(default)]
+    #[ts(as = "Option<Vec<String>>")]
     pub args: Vec<String>,
 }
 
 /// LSP server configuration for language packs
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
-#[ts(export)]
+#[ts(export, optional_fields)]
 pub struct LspServerPackConfig {
     /// Command to start the LSP server
     pub command: String,
 
     /// Arguments to pass to the command
     #[serde(default)]
+    #[ts(as = "Option<Vec<String>>")]
     pub args: Vec<String>,
 
     /// Whether to auto-start the server when a matching file is opened
@@ -1737,7 +1925,8 @@ pub struct LspServerPackConfig {
 
     /// LSP initialization options
     #[serde(default)]
-    #[ts(type = "Record<string, unknown> | null")]
+    #[schemars(with = "Option<HashMap<String, JsonValue>>")]
+    #[ts(type = "Record<string, unknown>")]
     pub initialization_options: Option<JsonValue>,
 }
 
@@ -1790,6 +1979,50 @@ pub struct ActionPopupOptions {
     pub actions: Vec<ActionPopupAction>,
 }
 
+/// Options for showFloatingPanel
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct FloatingPanelOptions {
+    /// Unique identifier for the panel (used by hideFloatingPanel)
+    pub id: String,
+    /// Lines of text to display
+    pub lines: Vec<String>,
+    /// Title shown in the top border
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Anchor: "cursor" (below the cursor), "aboveCursor", "center" or "bottomRight"
+    /// (default: "cursor"). Ignored when x and y are given.
+    #[serde(default)]
+    #[ts(optional)]
+    pub anchor: Option<String>,
+    /// Screen column of the top-left corner
+    #[serde(default)]
+    #[ts(optional)]
+    pub x: Option<u16>,
+    /// Screen row of the top-left corner
+    #[serde(default)]
+    #[ts(optional)]
+    pub y: Option<u16>,
+    /// Width in columns including the border (default: fits the content)
+    #[serde(default)]
+    #[ts(optional)]
+    pub width: Option<u16>,
+    /// Maximum height in rows including the border (default: fits the content)
+    #[serde(default)]
+    #[ts(optional)]
+    pub height: Option<u16>,
+    /// Whether to draw a border (default: true)
+    #[serde(default)]
+    #[ts(optional)]
+    pub border: Option<bool>,
+    /// Stacking order relative to other popups; higher draws on top (default: 0)
+    #[serde(default)]
+    #[ts(optional)]
+    pub z_index: Option<i32>,
+}
+
 /// Syntax highlight span for a buffer range
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -1826,6 +2059,115 @@ pub struct BackgroundProcessResult {
     pub exit_code: i32,
 }
 
+/// A completion offered by a plugin completion provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename = "CompletionItem", rename_all = "camelCase")]
+pub struct PluginCompletionItem {
+    /// Text shown in the popup; inserted unless `insertText` is set
+    pub label: String,
+    /// Text to insert instead of the label
+    #[serde(default)]
+    #[ts(optional)]
+    pub insert_text: Option<String>,
+    /// Text matched against what was typed instead of the label
+    #[serde(default)]
+    #[ts(optional)]
+    pub filter_text: Option<String>,
+    /// Short description shown next to the label
+    #[serde(default)]
+    #[ts(optional)]
+    pub detail: Option<String>,
+    /// "function", "method", "variable", "class", "struct", "constant", "keyword", ...
+    #[serde(default)]
+    #[ts(optional)]
+    pub kind: Option<String>,
+}
+
+/// Where completions were requested, passed to completion provider callbacks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct CompletionContext {
+    #[ts(type = "number")]
+    pub buffer_id: BufferId,
+    pub language: String,
+    /// Byte offset of the cursor
+    pub position: usize,
+    /// The partial word before the cursor
+    pub prefix: String,
+}
+
+/// Value a `before_file_save` handler returns (or resolves to)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BeforeSaveResult {
+    /// Edits to make before the file is written
+    #[serde(default)]
+    #[ts(optional)]
+    pub edits: Option<Vec<BeforeSaveEdit>>,
+    /// Cancel the save; the reason is shown in the status bar
+    #[serde(default)]
+    #[ts(optional)]
+    pub veto: Option<String>,
+}
+
+/// Replacement of a byte range, relative to the buffer when the save started
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BeforeSaveEdit {
+    /// Start byte offset (inclusive)
+    pub start: usize,
+    /// End byte offset (exclusive)
+    pub end: usize,
+    /// Text to put in place of the range
+    pub text: String,
+}
+
+/// Options for registerStatusSegment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct StatusSegmentOptions {
+    /// Text shown in the status bar
+    pub text: String,
+    /// Foreground color - RGB array or theme key string (default: the status bar's)
+    #[serde(default)]
+    #[ts(optional)]
+    pub fg: Option<OverlayColorSpec>,
+    /// Background color - RGB array or theme key string (default: the status bar's)
+    #[serde(default)]
+    #[ts(optional)]
+    pub bg: Option<OverlayColorSpec>,
+    /// Order among segments; higher is further left (default: 0)
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<i32>,
+    /// Command or action run when the segment is clicked
+    #[serde(default)]
+    #[ts(optional)]
+    pub command: Option<String>,
+}
+
+/// A plugin's icon in a line's gutter indicator column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct GutterSign {
+    /// Line number (0-indexed)
+    pub line: usize,
+    /// Symbol to display (e.g., "✓", "✗", "★")
+    pub symbol: String,
+    /// Color - RGB array or theme key string (default: "editor.line_number_fg")
+    #[serde(default)]
+    #[ts(optional)]
+    pub color: Option<OverlayColorSpec>,
+    /// Priority against other indicators on the line; higher wins (default: 0)
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<i32>,
+}
+
 /// Entry for virtual buffer content with optional text properties (JS API version)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -2135,6 +2477,36 @@ mod fromjs_impls {
         }
     }
 
+    impl<'js> FromJs<'js> for FloatingPanelOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "FloatingPanelOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for StatusSegmentOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "StatusSegmentOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for GutterSign {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "GutterSign",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
     impl<'js> FromJs<'js> for ViewTokenWire {
         fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
             rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
@@ -2395,7 +2767,11 @@ impl PluginApi {
     /// Start a prompt (minibuffer) with a custom type identifier
     /// The prompt_type is used to filter hooks in plugin code
     pub fn start_prompt(&self, label: String, prompt_type: String) -> Result<(), String> {
-        self.send_command(PluginCommand::StartPrompt { label, prompt_type })
+        self.send_command(PluginCommand::StartPrompt {
+            label,
+            prompt_type,
+            multi_select: false,
+        })
     }
 
     /// Set the suggestions for the current prompt
@@ -76,6 +76,16 @@ pub enum HookArgs {
         lines_removed: usize,
     },
 
+    /// Buffer content changed
+    ///
+    /// Carries every edit of one editor event (a keystroke, paste, undo,
+    /// replace-all, ...) in the order it was applied; each change is relative
+    /// to the buffer after the changes before it.
+    BufferChanged {
+        buffer_id: BufferId,
+        changes: Vec<TextChange>,
+    },
+
     /// Cursor moved to a new position
     CursorMoved {
         buffer_id: BufferId,
@@ -138,6 +148,8 @@ pub enum HookArgs {
         prompt_type: String,
         input: String,
         selected_index: Option<usize>,
+        /// Values of the suggestions marked in a multi-select prompt, in marking order
+        selected_values: Vec<String>,
     },
 
     /// Prompt was cancelled (user pressed Escape/Ctrl+G)
@@ -260,6 +272,18 @@ pub enum HookArgs {
     },
 }
 
+/// One edit of a BufferChanged hook: `removed_len` bytes at `start` were
+/// replaced by `text`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TextChange {
+    /// Byte offset where the change starts
+    pub start: usize,
+    /// Number of bytes removed at `start`
+    pub removed_len: usize,
+    /// Text inserted at `start`
+    pub text: String,
+}
+
 /// Information about a single line for the LinesChanged hook
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LineInfo {
@@ -500,11 +524,13 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
             prompt_type,
             input,
             selected_index,
+            selected_values,
         } => {
             serde_json::json!({
                 "prompt_type": prompt_type,
                 "input": input,
                 "selected_index": selected_index,
+                "selected_values": selected_values,
             })
         }
         HookArgs::PromptCancelled { prompt_type, input } => {
@@ -687,6 +713,12 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "data": data,
             })
         }
+        HookArgs::BufferChanged { buffer_id, changes } => {
+            serde_json::json!({
+                "buffer_id": buffer_id.0,
+                "changes": changes,
+            })
+        }
     };
 
     serde_json::to_string(&json_value)
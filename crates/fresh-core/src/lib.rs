@@ -51,6 +51,8 @@ impl std::fmt::Display for TerminalId {
 pub mod config;
 pub mod file_explorer;
 pub mod menu;
+pub mod native;
 pub mod overlay;
+pub mod package;
 pub mod services;
 pub mod text_property;
@@ -0,0 +1,290 @@
+//! Native plugin ABI
+//!
+//! Performance-critical extensions (fuzzy matchers, parsers, ...) can be
+//! shipped as dynamically loaded libraries instead of TypeScript. A native
+//! plugin exports a single symbol, [`DESCRIPTOR_SYMBOL`], returning a pointer
+//! to a [`NativePluginDescriptor`]. Everything crossing the boundary is
+//! `#[repr(C)]` plus UTF-8 byte slices, so the plugin and the editor do not
+//! need to be built with the same compiler version.
+//!
+//! The API surface is the same one TypeScript plugins use: hooks arrive as the
+//! JSON serialization of [`HookArgs`](crate::hooks::HookArgs), and the plugin
+//! talks back by sending JSON-serialized [`PluginCommand`]s through
+//! [`NativeHost::send_command`].
+//!
+//! Plugin authors normally don't touch the raw types: implement
+//! [`NativePlugin`] and invoke [`export_native_plugin!`](crate::export_native_plugin).
+//! The generated entry points catch panics so a misbehaving plugin reports
+//! [`NATIVE_PANIC`] instead of unwinding into the editor.
+
+use crate::api::PluginCommand;
+use std::ffi::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Version of the native plugin ABI.
+///
+/// Bumped whenever the layout of [`NativeHost`] or [`NativePluginDescriptor`]
+/// changes. The editor refuses to load plugins built for another version.
+pub const NATIVE_ABI_VERSION: u32 = 1;
+
+/// Name of the symbol every native plugin library must export.
+pub const DESCRIPTOR_SYMBOL: &[u8] = b"fresh_native_plugin";
+
+/// The call succeeded.
+pub const NATIVE_OK: i32 = 0;
+/// The plugin does not handle this action.
+pub const NATIVE_UNHANDLED: i32 = 1;
+/// The call failed; the plugin logged the reason at [`LOG_ERROR`].
+pub const NATIVE_ERROR: i32 = -1;
+/// The plugin panicked. The editor unloads it.
+pub const NATIVE_PANIC: i32 = -2;
+
+pub const LOG_ERROR: u32 = 1;
+pub const LOG_WARN: u32 = 2;
+pub const LOG_INFO: u32 = 3;
+pub const LOG_DEBUG: u32 = 4;
+
+/// Callbacks the editor hands to a plugin in `init`.
+///
+/// The pointer passed to `init` stays valid until `shutdown` returns. The
+/// callbacks are thread-safe, so a plugin may copy the struct and use it from
+/// worker threads of its own.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NativeHost {
+    pub abi_version: u32,
+    /// Opaque editor state, passed back to every callback.
+    pub ctx: *mut c_void,
+    /// Send a JSON-serialized `PluginCommand`. Returns 0 on success.
+    pub send_command: unsafe extern "C" fn(ctx: *mut c_void, json: *const u8, len: usize) -> i32,
+    /// Write a UTF-8 message to the editor log at one of the `LOG_*` levels.
+    pub log: unsafe extern "C" fn(ctx: *mut c_void, level: u32, msg: *const u8, len: usize),
+}
+
+/// Entry points of a native plugin, returned by [`DESCRIPTOR_SYMBOL`].
+///
+/// `abi_version` must stay the first field: the editor reads it before
+/// anything else to reject incompatible libraries.
+#[repr(C)]
+pub struct NativePluginDescriptor {
+    pub abi_version: u32,
+    /// Plugin name (UTF-8, not NUL-terminated).
+    pub name: *const u8,
+    pub name_len: usize,
+    /// Comma-separated hook names the plugin wants, or `*` for all of them.
+    pub hooks: *const u8,
+    pub hooks_len: usize,
+    /// Create the plugin instance. Returns null on failure.
+    pub init: unsafe extern "C" fn(host: *const NativeHost) -> *mut c_void,
+    /// Deliver a hook with its JSON payload.
+    pub handle_hook: unsafe extern "C" fn(
+        instance: *mut c_void,
+        hook: *const u8,
+        hook_len: usize,
+        payload: *const u8,
+        payload_len: usize,
+    ) -> i32,
+    /// Run a registered action. Returns [`NATIVE_UNHANDLED`] for unknown names.
+    pub run_action:
+        unsafe extern "C" fn(instance: *mut c_void, action: *const u8, action_len: usize) -> i32,
+    /// Destroy the plugin instance.
+    pub shutdown: unsafe extern "C" fn(instance: *mut c_void),
+}
+
+// The descriptor only holds pointers to immutable statics and functions.
+unsafe impl Sync for NativePluginDescriptor {}
+unsafe impl Send for NativePluginDescriptor {}
+
+impl NativePluginDescriptor {
+    /// Plugin name, or `None` if it is not valid UTF-8.
+    ///
+    /// # Safety
+    /// `name`/`name_len` must describe a readable byte slice.
+    pub unsafe fn name(&self) -> Option<&str> {
+        std::str::from_utf8(raw_slice(self.name, self.name_len)).ok()
+    }
+
+    /// Hook names the plugin subscribed to (`*` meaning all hooks).
+    ///
+    /// # Safety
+    /// `hooks`/`hooks_len` must describe a readable byte slice.
+    pub unsafe fn hooks(&self) -> Vec<String> {
+        std::str::from_utf8(raw_slice(self.hooks, self.hooks_len))
+            .unwrap_or_default()
+            .split(',')
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+            .collect()
+    }
+}
+
+/// Plugin-side handle to the editor.
+#[derive(Clone, Copy)]
+pub struct HostHandle {
+    raw: NativeHost,
+}
+
+// The editor guarantees the callbacks are thread-safe (see `NativeHost`).
+unsafe impl Send for HostHandle {}
+unsafe impl Sync for HostHandle {}
+
+impl HostHandle {
+    /// Send a command to the editor. Returns false if the editor rejected it.
+    pub fn send(&self, command: &PluginCommand) -> bool {
+        let Ok(json) = serde_json::to_vec(command) else {
+            return false;
+        };
+        unsafe { (self.raw.send_command)(self.raw.ctx, json.as_ptr(), json.len()) == 0 }
+    }
+
+    /// Write a message to the editor log.
+    pub fn log(&self, level: u32, message: &str) {
+        unsafe { (self.raw.log)(self.raw.ctx, level, message.as_ptr(), message.len()) }
+    }
+}
+
+/// A native plugin implementation.
+///
+/// All methods run on a dedicated editor thread, one call at a time.
+pub trait NativePlugin: Sized + 'static {
+    /// Plugin name, used for config and error messages.
+    const NAME: &'static str;
+    /// Comma-separated hook names to subscribe to, or `*` for all.
+    const HOOKS: &'static str = "";
+
+    fn new(host: HostHandle) -> Self;
+
+    /// Called for every subscribed hook with the JSON-serialized `HookArgs`.
+    fn on_hook(&mut self, _hook: &str, _payload: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Run an action registered through `PluginCommand::RegisterCommand`.
+    /// Returns `None` if the action is not handled by this plugin.
+    fn run_action(&mut self, _action: &str) -> Option<Result<(), String>> {
+        None
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+struct Instance<P> {
+    host: HostHandle,
+    plugin: P,
+}
+
+/// Build the descriptor for `P`. Used by [`export_native_plugin!`](crate::export_native_plugin).
+pub fn descriptor_for<P: NativePlugin>() -> NativePluginDescriptor {
+    NativePluginDescriptor {
+        abi_version: NATIVE_ABI_VERSION,
+        name: P::NAME.as_ptr(),
+        name_len: P::NAME.len(),
+        hooks: P::HOOKS.as_ptr(),
+        hooks_len: P::HOOKS.len(),
+        init: init::<P>,
+        handle_hook: handle_hook::<P>,
+        run_action: run_action::<P>,
+        shutdown: shutdown::<P>,
+    }
+}
+
+unsafe fn raw_slice<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Run `f` against the instance, turning errors and panics into status codes.
+unsafe fn guarded<P: NativePlugin>(
+    instance: *mut c_void,
+    f: impl FnOnce(&mut P) -> Option<Result<(), String>>,
+) -> i32 {
+    let Some(instance) = (instance as *mut Instance<P>).as_mut() else {
+        return NATIVE_ERROR;
+    };
+    let host = instance.host;
+    match catch_unwind(AssertUnwindSafe(|| f(&mut instance.plugin))) {
+        Ok(None) => NATIVE_UNHANDLED,
+        Ok(Some(Ok(()))) => NATIVE_OK,
+        Ok(Some(Err(e))) => {
+            host.log(LOG_ERROR, &e);
+            NATIVE_ERROR
+        }
+        Err(payload) => {
+            host.log(LOG_ERROR, &panic_message(payload.as_ref()));
+            NATIVE_PANIC
+        }
+    }
+}
+
+unsafe extern "C" fn init<P: NativePlugin>(host: *const NativeHost) -> *mut c_void {
+    let Some(raw) = host.as_ref() else {
+        return std::ptr::null_mut();
+    };
+    let host = HostHandle { raw: *raw };
+    match catch_unwind(|| P::new(host)) {
+        Ok(plugin) => Box::into_raw(Box::new(Instance { host, plugin })) as *mut c_void,
+        Err(payload) => {
+            host.log(LOG_ERROR, &panic_message(payload.as_ref()));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn handle_hook<P: NativePlugin>(
+    instance: *mut c_void,
+    hook: *const u8,
+    hook_len: usize,
+    payload: *const u8,
+    payload_len: usize,
+) -> i32 {
+    let hook = String::from_utf8_lossy(raw_slice(hook, hook_len));
+    let payload = String::from_utf8_lossy(raw_slice(payload, payload_len));
+    guarded::<P>(instance, |p| Some(p.on_hook(&hook, &payload)))
+}
+
+unsafe extern "C" fn run_action<P: NativePlugin>(
+    instance: *mut c_void,
+    action: *const u8,
+    action_len: usize,
+) -> i32 {
+    let action = String::from_utf8_lossy(raw_slice(action, action_len));
+    guarded::<P>(instance, |p| p.run_action(&action))
+}
+
+unsafe extern "C" fn shutdown<P: NativePlugin>(instance: *mut c_void) {
+    if instance.is_null() {
+        return;
+    }
+    let mut instance = Box::from_raw(instance as *mut Instance<P>);
+    let _ = catch_unwind(AssertUnwindSafe(|| instance.plugin.shutdown()));
+}
+
+/// Export a [`NativePlugin`] implementation from a `cdylib`.
+///
+/// ```ignore
+/// struct Fuzzy { host: HostHandle }
+/// impl NativePlugin for Fuzzy { /* ... */ }
+/// fresh_core::export_native_plugin!(Fuzzy);
+/// ```
+#[macro_export]
+macro_rules! export_native_plugin {
+    ($plugin:ty) => {
+        #[no_mangle]
+        pub extern "C" fn fresh_native_plugin() -> *const $crate::native::NativePluginDescriptor {
+            static DESCRIPTOR: ::std::sync::OnceLock<$crate::native::NativePluginDescriptor> =
+                ::std::sync::OnceLock::new();
+            DESCRIPTOR.get_or_init($crate::native::descriptor_for::<$plugin>)
+        }
+    };
+}
@@ -0,0 +1,297 @@
+//! Package manifest (`package.json`) format
+//!
+//! Plugins, themes, language packs and bundles describe themselves in a
+//! `package.json`. These types are the source of
+//! `plugins/schemas/package.schema.json` (`generate_schema package`) and of the
+//! manifest types in `fresh.d.ts`, so editors can validate pack configuration
+//! files against the same definitions the package manager reads.
+
+use crate::api::{LanguagePackConfig, LspServerPackConfig};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+/// Schema for Fresh plugin and theme package.json files
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[schemars(title = "Fresh Package Manifest")]
+#[ts(export, optional_fields)]
+pub struct PackageManifest {
+    /// Package name (lowercase, hyphens allowed)
+    #[schemars(regex(pattern = r"^[a-z0-9-]+$"))]
+    pub name: String,
+
+    /// Semantic version (e.g., 1.0.0)
+    #[schemars(regex(pattern = r"^\d+\.\d+\.\d+"))]
+    pub version: String,
+
+    /// Short package description
+    pub description: String,
+
+    /// Package type
+    #[serde(rename = "type")]
+    pub kind: PackageKind,
+
+    /// Author name and optional email (e.g., 'Name <email>')
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// SPDX license identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+
+    /// Git repository URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(url)]
+    pub repository: Option<String>,
+
+    /// Search keywords
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<String>>")]
+    pub keywords: Vec<String>,
+
+    /// Fresh-specific configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fresh: Option<FreshPackageConfig>,
+
+    /// Package dependencies (reserved for future use)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[ts(as = "Option<BTreeMap<String, String>>")]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// Kind of package, which decides where it is installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum PackageKind {
+    Plugin,
+    Theme,
+    ThemePack,
+    Language,
+    Bundle,
+}
+
+/// The `fresh` section of a package manifest
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(export, optional_fields)]
+pub struct FreshPackageConfig {
+    /// Minimum required Fresh version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(regex(pattern = r"^\d+\.\d+\.\d+"))]
+    pub min_version: Option<String>,
+
+    /// Minimum required plugin API version (integer, e.g. 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1))]
+    pub min_api_version: Option<u32>,
+
+    /// Plugin entry point file (for plugins)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+
+    /// Plugin entry point file (alias for entry)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main: Option<String>,
+
+    /// Events that load the plugin: "onLanguage:<language>" when a file of
+    /// the language is opened, "onCommand:<action>" when the action is run,
+    /// "*" at startup. Without any, the plugin is loaded at startup.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<String>>")]
+    pub activation_events: Vec<String>,
+
+    /// Theme JSON file path (for single theme packages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+
+    /// Theme definitions (for theme packs and bundles)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<PackageTheme>>")]
+    pub themes: Vec<PackageTheme>,
+
+    /// JSON Schema for plugin configuration options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<BTreeMap<String, JsonValue>>")]
+    #[ts(type = "Record<string, unknown>")]
+    pub config_schema: Option<JsonValue>,
+
+    /// Grammar configuration (for language packs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grammar: Option<GrammarPackConfig>,
+
+    /// Language configuration (for language packs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<LanguagePackConfig>,
+
+    /// LSP server configuration (for language packs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lsp: Option<LspServerPackConfig>,
+
+    /// Language definitions (for bundles)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<BundleLanguage>>")]
+    pub languages: Vec<BundleLanguage>,
+
+    /// Plugin definitions (for bundles)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<BundlePlugin>>")]
+    pub plugins: Vec<BundlePlugin>,
+}
+
+/// When a plugin is loaded, parsed from `activation_events`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivationEvent {
+    /// While the editor starts (`*`)
+    Startup,
+    /// When a file of the language is opened (`onLanguage:<language>`)
+    Language(String),
+    /// When the plugin action is run (`onCommand:<action>`)
+    Command(String),
+}
+
+impl ActivationEvent {
+    /// Parse an activation event; None if it is not one Fresh knows
+    pub fn parse(event: &str) -> Option<Self> {
+        if event == "*" {
+            return Some(Self::Startup);
+        }
+        let (kind, name) = event.split_once(':')?;
+        if name.is_empty() {
+            return None;
+        }
+        match kind {
+            "onLanguage" => Some(Self::Language(name.to_string())),
+            "onCommand" => Some(Self::Command(name.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// A theme shipped in a package
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(export, optional_fields)]
+pub struct PackageTheme {
+    /// Theme JSON file path relative to package
+    pub file: String,
+
+    /// Display name for the theme
+    pub name: String,
+
+    /// Theme variant (dark or light)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<ThemeVariant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ThemeVariant {
+    Dark,
+    Light,
+}
+
+/// Grammar configuration for language packs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, optional_fields)]
+pub struct GrammarPackConfig {
+    /// Path to grammar file (.sublime-syntax recommended, or .tmLanguage)
+    pub file: String,
+
+    /// File extensions this grammar handles (e.g., ["rs", "rust"])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<String>>")]
+    pub extensions: Vec<String>,
+
+    /// Regex pattern for shebang detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_line: Option<String>,
+}
+
+/// A language in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(export, optional_fields)]
+pub struct BundleLanguage {
+    /// Language identifier (e.g., 'elixir', 'heex')
+    pub id: String,
+
+    /// Grammar configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grammar: Option<GrammarPackConfig>,
+
+    /// Language configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<LanguagePackConfig>,
+
+    /// LSP server configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lsp: Option<LspServerPackConfig>,
+}
+
+/// A plugin in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, TS)]
+#[ts(export)]
+pub struct BundlePlugin {
+    /// Plugin entry point file relative to package
+    pub entry: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_language_pack_manifest() {
+        let manifest: PackageManifest = serde_json::from_str(
+            r#"{
+                "$schema": "https://example.com/package.schema.json",
+                "name": "hare",
+                "version": "0.1.0",
+                "description": "Hare language support",
+                "type": "language",
+                "fresh": {
+                    "grammar": { "file": "grammars/Hare.sublime-syntax", "extensions": ["ha"] },
+                    "language": { "commentPrefix": "//", "useTabs": true, "tabSize": 8 },
+                    "lsp": { "command": "hare-lsp", "autoStart": true }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.kind, PackageKind::Language);
+        let fresh = manifest.fresh.unwrap();
+        assert_eq!(fresh.grammar.unwrap().extensions, vec!["ha"]);
+        assert_eq!(fresh.language.unwrap().tab_size, Some(8));
+        assert_eq!(fresh.lsp.unwrap().auto_start, Some(true));
+    }
+
+    #[test]
+    fn test_parse_activation_events() {
+        assert_eq!(ActivationEvent::parse("*"), Some(ActivationEvent::Startup));
+        assert_eq!(
+            ActivationEvent::parse("onLanguage:rust"),
+            Some(ActivationEvent::Language("rust".to_string()))
+        );
+        assert_eq!(
+            ActivationEvent::parse("onCommand:pkg_sync"),
+            Some(ActivationEvent::Command("pkg_sync".to_string()))
+        );
+        assert_eq!(ActivationEvent::parse("onLanguage:"), None);
+        assert_eq!(ActivationEvent::parse("onView:explorer"), None);
+    }
+
+    #[test]
+    fn test_schema_uses_manifest_field_names() {
+        let schema = serde_json::to_value(schemars::schema_for!(PackageManifest)).unwrap();
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&"type".into()));
+        assert!(!required.contains(&"author".into()));
+
+        let grammar = &schema["$defs"]["GrammarPackConfig"]["properties"];
+        assert!(grammar.get("firstLine").is_some());
+        let language = &schema["$defs"]["LanguagePackConfig"]["properties"];
+        assert!(language.get("commentPrefix").is_some());
+    }
+}
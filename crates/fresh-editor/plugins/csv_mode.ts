@@ -0,0 +1,521 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * CSV Mode Plugin
+ *
+ * Table view for CSV and TSV files.
+ *
+ * Key features:
+ * - Columns line up using virtual padding (`csv.align_columns`)
+ * - Each column gets its own color (`csv.rainbow_columns`)
+ * - The header row can stay pinned above the viewport (`csv.pin_header`)
+ * - Tab / Shift+Tab move between cells in CSV files
+ * - Commands to move up and down a column and to insert or delete columns
+ *
+ * Padding, colors and the pinned header are decorations only: the file's
+ * bytes change only through the column editing commands.
+ */
+
+const editor = getEditor();
+
+const COLOR_NAMESPACE = "csv-columns";
+const HEADER_NAMESPACE = "csv-header";
+const PAD_PREFIX = "csv-pad:";
+const CONTEXT = "csv-mode";
+// Tab moves between cells only where Tab is not the delimiter
+const CELL_KEYS_CONTEXT = "csv-cell-keys";
+
+// Column widths are measured over the start of large files only
+const MAX_SCAN_BYTES = 4 * 1024 * 1024;
+const MAX_COLUMN_WIDTH = 40;
+const REFRESH_DELAY_MS = 150;
+
+const COLUMN_COLORS = [
+  "syntax.keyword",
+  "syntax.string",
+  "syntax.function",
+  "syntax.type",
+  "syntax.constant",
+  "syntax.variable",
+  "syntax.operator",
+];
+const HEADER_FG: [number, number, number] = [220, 220, 220];
+const HEADER_BG: [number, number, number] = [50, 55, 65];
+
+interface Cell {
+  start: number; // UTF-16 index of the first character
+  end: number; // UTF-16 index of the delimiter (or the end of the line)
+}
+
+interface Row {
+  line: number;
+  start: number; // Byte offset of the line
+  text: string;
+}
+
+interface TableState {
+  delimiter: string;
+  widths: number[];
+  header: string[];
+  headerEnd: number; // Byte offset where the first line ends
+  refreshTimer: number | null;
+}
+
+interface CsvSettings {
+  align_columns: boolean;
+  rainbow_columns: boolean;
+  pin_header: boolean;
+}
+
+const tables = new Map<number, TableState>();
+
+function readSettings(): CsvSettings {
+  const config = editor.getConfig() as Record<string, unknown>;
+  const csv = (config?.csv ?? {}) as Partial<CsvSettings>;
+  return {
+    align_columns: csv.align_columns ?? true,
+    rainbow_columns: csv.rainbow_columns ?? true,
+    pin_header: csv.pin_header ?? false,
+  };
+}
+
+// Toggle commands change these for the session
+const settings = readSettings();
+
+function delimiterFor(path: string): string | null {
+  const lower = path.toLowerCase();
+  if (lower.endsWith(".csv")) return ",";
+  if (lower.endsWith(".tsv") || lower.endsWith(".tab")) return "\t";
+  return null;
+}
+
+/**
+ * Split a line into cells, keeping delimiters inside quoted fields
+ */
+function splitCells(text: string, delimiter: string): Cell[] {
+  const cells: Cell[] = [];
+  let start = 0;
+  let quoted = false;
+  for (let i = 0; i < text.length; i++) {
+    const ch = text[i];
+    if (ch === '"') {
+      if (!quoted && i === start) {
+        quoted = true;
+      } else if (quoted && text[i + 1] === '"') {
+        i++; // Escaped quote
+      } else if (quoted) {
+        quoted = false;
+      }
+    } else if (ch === delimiter && !quoted) {
+      cells.push({ start, end: i });
+      start = i + 1;
+    }
+  }
+  cells.push({ start, end: text.length });
+  return cells;
+}
+
+/** Byte offset of every UTF-16 index of `text`, plus one for its end */
+function byteOffsets(text: string): number[] {
+  const offsets: number[] = new Array(text.length + 1);
+  let bytes = 0;
+  for (let i = 0; i < text.length; i++) {
+    offsets[i] = bytes;
+    const code = text.charCodeAt(i);
+    if (code < 0x80) bytes += 1;
+    else if (code < 0x800) bytes += 2;
+    else if (code >= 0xd800 && code <= 0xdbff) {
+      // Surrogate pair: four bytes for the whole code point
+      offsets[i + 1] = bytes;
+      bytes += 4;
+      i++;
+    } else bytes += 3;
+  }
+  offsets[text.length] = bytes;
+  return offsets;
+}
+
+function displayWidth(text: string): number {
+  let width = 0;
+  for (const _ of text) width++;
+  return width;
+}
+
+function cellIndexAt(cells: Cell[], index: number): number {
+  for (let i = 0; i < cells.length; i++) {
+    if (index <= cells[i].end) return i;
+  }
+  return cells.length - 1;
+}
+
+function stripCr(text: string): string {
+  return text.endsWith("\r") ? text.slice(0, -1) : text;
+}
+
+async function readAllRows(bufferId: number, limit: number): Promise<Row[]> {
+  const length = Math.min(editor.getBufferLength(bufferId), limit);
+  const text = await editor.getBufferText(bufferId, 0, length);
+  const rows: Row[] = [];
+  let start = 0;
+  const lines = text.split("\n");
+  for (let line = 0; line < lines.length; line++) {
+    rows.push({ line, start, text: stripCr(lines[line]) });
+    start += byteOffsets(lines[line])[lines[line].length] + 1;
+  }
+  return rows;
+}
+
+async function readRow(bufferId: number, line: number): Promise<Row | null> {
+  if (line < 0) return null;
+  const start = await editor.getLineStartPosition(line);
+  const end = await editor.getLineEndPosition(line);
+  if (start === null || end === null) return null;
+  const text = await editor.getBufferText(bufferId, start, end);
+  return { line, start, text: stripCr(text) };
+}
+
+// =============================================================================
+// Decorations
+// =============================================================================
+
+async function measureTable(bufferId: number, table: TableState): Promise<void> {
+  const rows = await readAllRows(bufferId, MAX_SCAN_BYTES);
+  const widths: number[] = [];
+  for (const row of rows) {
+    if (row.text.length === 0) continue;
+    splitCells(row.text, table.delimiter).forEach((cell, i) => {
+      const width = Math.min(displayWidth(row.text.slice(cell.start, cell.end)), MAX_COLUMN_WIDTH);
+      widths[i] = Math.max(widths[i] ?? 0, width);
+    });
+  }
+  const first = rows[0]?.text ?? "";
+  table.widths = widths;
+  table.header = splitCells(first, table.delimiter).map((cell) => first.slice(cell.start, cell.end));
+  table.headerEnd = byteOffsets(first)[first.length];
+}
+
+function clearDecorations(bufferId: number): void {
+  editor.clearNamespace(bufferId, COLOR_NAMESPACE);
+  editor.removeVirtualTextsByPrefix(bufferId, PAD_PREFIX);
+  editor.clearVirtualTextNamespace(bufferId, HEADER_NAMESPACE);
+}
+
+// Re-measure the columns and redecorate the visible lines
+async function refreshTable(bufferId: number): Promise<void> {
+  const table = tables.get(bufferId);
+  if (!table) return;
+  await measureTable(bufferId, table);
+  clearDecorations(bufferId);
+  const viewport = editor.getViewport();
+  if (viewport && editor.getActiveBufferId() === bufferId) {
+    pinHeader(bufferId, viewport.topByte);
+  }
+  editor.refreshLines(bufferId);
+}
+
+function scheduleRefresh(bufferId: number): void {
+  const table = tables.get(bufferId);
+  if (!table) return;
+  if (table.refreshTimer !== null) editor.clearTimer(table.refreshTimer);
+  table.refreshTimer = editor.setTimeout(() => {
+    table.refreshTimer = null;
+    refreshTable(bufferId);
+  }, REFRESH_DELAY_MS);
+}
+
+function decorateLine(bufferId: number, table: TableState, byteStart: number, content: string): void {
+  const text = stripCr(content.replace(/\n$/, ""));
+  if (text.length === 0) return;
+  const cells = splitCells(text, table.delimiter);
+  const offsets = byteOffsets(text);
+  cells.forEach((cell, i) => {
+    if (settings.rainbow_columns && cell.end > cell.start) {
+      editor.addOverlay(bufferId, COLOR_NAMESPACE, byteStart + offsets[cell.start], byteStart + offsets[cell.end], {
+        fg: COLUMN_COLORS[i % COLUMN_COLORS.length],
+      });
+    }
+    if (settings.align_columns && i < cells.length - 1) {
+      const pad = (table.widths[i] ?? 0) - displayWidth(text.slice(cell.start, cell.end));
+      if (pad > 0) {
+        // Inline virtual text is rendered with a trailing space, like inlay hints
+        const position = byteStart + offsets[cell.end];
+        editor.addVirtualText(bufferId, `${PAD_PREFIX}${position}`, position, " ".repeat(pad - 1), 0, 0, 0, true, false);
+      }
+    }
+  });
+}
+
+// Tab stops of the pinned header match those the delimiter gets in the buffer
+function tabSize(): number {
+  const config = editor.getConfig() as { editor?: { tab_size?: number } };
+  return config?.editor?.tab_size ?? 4;
+}
+
+function headerText(table: TableState): string {
+  let text = "";
+  table.header.forEach((name, i) => {
+    text += name;
+    if (i === table.header.length - 1) return;
+    if (settings.align_columns) {
+      text += " ".repeat(Math.max((table.widths[i] ?? 0) - displayWidth(name), 0));
+    }
+    if (table.delimiter === "\t") {
+      const size = tabSize();
+      const width = displayWidth(text);
+      text += " ".repeat(size - (width % size));
+    } else {
+      text += table.delimiter;
+    }
+  });
+  return text;
+}
+
+// Show the header above the first visible line once it scrolls out of view
+function pinHeader(bufferId: number, topByte: number): void {
+  const table = tables.get(bufferId);
+  if (!table) return;
+  editor.clearVirtualTextNamespace(bufferId, HEADER_NAMESPACE);
+  if (!settings.pin_header || topByte <= table.headerEnd || table.header.length === 0) return;
+  editor.addVirtualLine(
+    bufferId,
+    topByte,
+    headerText(table),
+    HEADER_FG[0],
+    HEADER_FG[1],
+    HEADER_FG[2],
+    HEADER_BG[0],
+    HEADER_BG[1],
+    HEADER_BG[2],
+    true,
+    HEADER_NAMESPACE,
+    0
+  );
+}
+
+function updateContext(bufferId: number): void {
+  const table = tables.get(bufferId);
+  editor.setContext(CONTEXT, table !== undefined);
+  editor.setContext(CELL_KEYS_CONTEXT, table !== undefined && table.delimiter !== "\t");
+}
+
+function enableTable(bufferId: number, path: string): void {
+  const delimiter = delimiterFor(path);
+  if (delimiter === null || tables.has(bufferId)) return;
+  tables.set(bufferId, { delimiter, widths: [], header: [], headerEnd: 0, refreshTimer: null });
+  if (editor.getActiveBufferId() === bufferId) updateContext(bufferId);
+  refreshTable(bufferId);
+}
+
+// =============================================================================
+// Cell Navigation
+// =============================================================================
+
+interface CursorCell {
+  bufferId: number;
+  table: TableState;
+  row: Row;
+  cells: Cell[];
+  column: number;
+}
+
+async function cursorCell(): Promise<CursorCell | null> {
+  const bufferId = editor.getActiveBufferId();
+  const table = tables.get(bufferId);
+  if (!table) {
+    editor.setStatus(editor.t("status.not_table"));
+    return null;
+  }
+  const position = editor.getCursorPosition();
+  const before = await editor.getBufferText(bufferId, 0, position);
+  const row = await readRow(bufferId, before.split("\n").length - 1);
+  if (!row) return null;
+  const cells = splitCells(row.text, table.delimiter);
+  const offsets = byteOffsets(row.text);
+  const cursor = position - row.start;
+  let index = offsets.findIndex((offset) => offset >= cursor);
+  if (index < 0) index = row.text.length;
+  return { bufferId, table, row, cells, column: cellIndexAt(cells, index) };
+}
+
+function moveToCell(bufferId: number, table: TableState, row: Row, column: number): void {
+  const cells = splitCells(row.text, table.delimiter);
+  const cell = cells[Math.min(column, cells.length - 1)];
+  editor.setBufferCursor(bufferId, row.start + byteOffsets(row.text)[cell.start]);
+  const index = Math.min(column, cells.length - 1);
+  const name = table.header[index] ?? "";
+  editor.setStatus(editor.t("status.cell", { column: String(index + 1), header: name }));
+}
+
+globalThis.csv_next_cell = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  if (at.column < at.cells.length - 1) {
+    moveToCell(at.bufferId, at.table, at.row, at.column + 1);
+    return;
+  }
+  const next = await readRow(at.bufferId, at.row.line + 1);
+  if (next) moveToCell(at.bufferId, at.table, next, 0);
+};
+
+globalThis.csv_prev_cell = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  if (at.column > 0) {
+    moveToCell(at.bufferId, at.table, at.row, at.column - 1);
+    return;
+  }
+  const previous = await readRow(at.bufferId, at.row.line - 1);
+  if (previous) moveToCell(at.bufferId, at.table, previous, splitCells(previous.text, at.table.delimiter).length - 1);
+};
+
+globalThis.csv_cell_down = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  const next = await readRow(at.bufferId, at.row.line + 1);
+  if (next) moveToCell(at.bufferId, at.table, next, at.column);
+};
+
+globalThis.csv_cell_up = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  const previous = await readRow(at.bufferId, at.row.line - 1);
+  if (previous) moveToCell(at.bufferId, at.table, previous, at.column);
+};
+
+// =============================================================================
+// Column Editing
+// =============================================================================
+
+// Edits run from the last row up so earlier byte offsets stay valid
+globalThis.csv_insert_column = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  const { bufferId, table, column } = at;
+  const rows = await readAllRows(bufferId, editor.getBufferLength(bufferId));
+  let shift = 0;
+  let cursor = 0;
+  for (let r = rows.length - 1; r >= 0; r--) {
+    const row = rows[r];
+    if (row.text.length === 0) continue;
+    const cells = splitCells(row.text, table.delimiter);
+    if (column >= cells.length) continue;
+    const position = row.start + byteOffsets(row.text)[cells[column].end];
+    editor.insertText(bufferId, position, table.delimiter);
+    if (row.line < at.row.line) shift++;
+    if (row.line === at.row.line) cursor = position + 1;
+  }
+  editor.setBufferCursor(bufferId, cursor + shift);
+  editor.setStatus(editor.t("status.column_inserted", { column: String(column + 2) }));
+};
+
+globalThis.csv_delete_column = async function (): Promise<void> {
+  const at = await cursorCell();
+  if (!at) return;
+  const { bufferId, table, column } = at;
+  const rows = await readAllRows(bufferId, editor.getBufferLength(bufferId));
+  for (let r = rows.length - 1; r >= 0; r--) {
+    const row = rows[r];
+    if (row.text.length === 0) continue;
+    const cells = splitCells(row.text, table.delimiter);
+    if (column >= cells.length || cells.length === 1) continue;
+    // Take the following delimiter, or the preceding one for the last cell
+    const from = column < cells.length - 1 ? cells[column].start : cells[column - 1].end;
+    const to = column < cells.length - 1 ? cells[column + 1].start : cells[column].end;
+    const offsets = byteOffsets(row.text);
+    editor.deleteRange(bufferId, row.start + offsets[from], row.start + offsets[to]);
+  }
+  const row = await readRow(bufferId, at.row.line);
+  if (row) moveToCell(bufferId, table, row, Math.max(Math.min(column, at.cells.length - 2), 0));
+  editor.setStatus(editor.t("status.column_deleted", { column: String(column + 1) }));
+};
+
+// =============================================================================
+// Toggles
+// =============================================================================
+
+function refreshAllTables(): void {
+  for (const bufferId of tables.keys()) {
+    refreshTable(bufferId);
+  }
+}
+
+globalThis.csv_toggle_alignment = function (): void {
+  settings.align_columns = !settings.align_columns;
+  refreshAllTables();
+  editor.setStatus(editor.t(settings.align_columns ? "status.alignment_on" : "status.alignment_off"));
+};
+
+globalThis.csv_toggle_pin_header = function (): void {
+  settings.pin_header = !settings.pin_header;
+  refreshAllTables();
+  editor.setStatus(editor.t(settings.pin_header ? "status.pin_header_on" : "status.pin_header_off"));
+};
+
+// =============================================================================
+// Event Handlers
+// =============================================================================
+
+globalThis.onCsvAfterFileOpen = function (args: { buffer_id: number; path: string }): boolean {
+  if (args.path) enableTable(args.buffer_id, args.path);
+  return true;
+};
+
+globalThis.onCsvBufferActivated = function (args: { buffer_id: number }): void {
+  updateContext(args.buffer_id);
+};
+
+globalThis.onCsvBufferClosed = function (args: { buffer_id: number }): void {
+  const table = tables.get(args.buffer_id);
+  if (table?.refreshTimer != null) editor.clearTimer(table.refreshTimer);
+  tables.delete(args.buffer_id);
+};
+
+globalThis.onCsvLinesChanged = function (data: {
+  buffer_id: number;
+  lines: Array<{ line_number: number; byte_start: number; byte_end: number; content: string }>;
+}): void {
+  const table = tables.get(data.buffer_id);
+  if (!table || table.widths.length === 0) return;
+  for (const line of data.lines) {
+    decorateLine(data.buffer_id, table, line.byte_start, line.content);
+  }
+};
+
+globalThis.onCsvBufferEdited = function (args: { buffer_id: number }): boolean {
+  scheduleRefresh(args.buffer_id);
+  return true;
+};
+
+globalThis.onCsvViewportChanged = function (data: { buffer_id: number; top_byte: number }): void {
+  pinHeader(data.buffer_id, data.top_byte);
+};
+
+editor.on("after_file_open", "onCsvAfterFileOpen");
+editor.on("buffer_activated", "onCsvBufferActivated");
+editor.on("buffer_closed", "onCsvBufferClosed");
+editor.on("lines_changed", "onCsvLinesChanged");
+editor.on("after_insert", "onCsvBufferEdited");
+editor.on("after_delete", "onCsvBufferEdited");
+editor.on("viewport_changed", "onCsvViewportChanged");
+
+// Command Registration
+editor.registerCommand("%cmd.csv_next_cell", "%cmd.csv_next_cell_desc", "csv_next_cell", CONTEXT);
+editor.registerCommand("%cmd.csv_prev_cell", "%cmd.csv_prev_cell_desc", "csv_prev_cell", CONTEXT);
+editor.registerCommand("%cmd.csv_cell_down", "%cmd.csv_cell_down_desc", "csv_cell_down", CONTEXT);
+editor.registerCommand("%cmd.csv_cell_up", "%cmd.csv_cell_up_desc", "csv_cell_up", CONTEXT);
+editor.registerCommand("%cmd.csv_insert_column", "%cmd.csv_insert_column_desc", "csv_insert_column", CONTEXT);
+editor.registerCommand("%cmd.csv_delete_column", "%cmd.csv_delete_column_desc", "csv_delete_column", CONTEXT);
+editor.registerCommand("%cmd.csv_toggle_alignment", "%cmd.csv_toggle_alignment_desc", "csv_toggle_alignment", CONTEXT);
+editor.registerCommand("%cmd.csv_toggle_pin_header", "%cmd.csv_toggle_pin_header_desc", "csv_toggle_pin_header", CONTEXT);
+
+editor.registerKeybinding("TAB", "csv_next_cell", CELL_KEYS_CONTEXT);
+editor.registerKeybinding("BACKTAB", "csv_prev_cell", CELL_KEYS_CONTEXT);
+editor.registerKeybinding("S-BACKTAB", "csv_prev_cell", CELL_KEYS_CONTEXT);
+
+// Tables opened before the plugin loaded
+for (const buffer of editor.listBuffers()) {
+  if (buffer.path) enableTable(buffer.id, buffer.path);
+}
+updateContext(editor.getActiveBufferId());
+
+editor.debug("CSV mode plugin loaded");
@@ -9,6 +9,7 @@
  * Key features:
  * - livePanel mode for reactive data updates
  * - Toggle between current file and all files (press 'a')
+ * - Cycle the minimum severity shown (press 's')
  * - groupBy: "file" for organized display
  * - syncWithEditor for bidirectional cursor sync
  */
@@ -30,6 +31,8 @@ interface DiagnosticItem {
 
 // State
 let showAllFiles = false;
+// Least severe level shown: 4 shows everything, 1 shows only errors
+let maxSeverity = 4;
 let sourceBufferId: number | null = null;
 let isOpen = false;
 
@@ -72,9 +75,11 @@ function getDiagnostics(): DiagnosticItem[] {
 
   // Filter diagnostics
   const filterUri = showAllFiles ? null : activeUri;
-  const filtered = filterUri
-    ? diagnostics.filter((d) => d.uri === filterUri)
-    : diagnostics;
+  const filtered = diagnostics.filter(
+    (d) =>
+      (filterUri === null || d.uri === filterUri) &&
+      (d.severity ?? 3) <= maxSeverity
+  );
 
   // Sort by file, then line, then severity
   filtered.sort((a, b) => {
@@ -135,11 +140,28 @@ const finder = new Finder<DiagnosticItem>(editor, {
   },
 });
 
+// Label for the current severity filter
+function severityLabel(): string {
+  switch (maxSeverity) {
+    case 1:
+      return editor.t("panel.severity_errors");
+    case 2:
+      return editor.t("panel.severity_warnings");
+    case 3:
+      return editor.t("panel.severity_info");
+    default:
+      return editor.t("panel.severity_all");
+  }
+}
+
 // Get title based on current filter state
 function getTitle(): string {
-  const filterLabel = showAllFiles
+  let filterLabel = showAllFiles
     ? editor.t("panel.all_files")
     : editor.t("panel.current_file");
+  if (maxSeverity < 4) {
+    filterLabel += ", " + severityLabel();
+  }
   return editor.t("panel.header", { filter: filterLabel });
 }
 
@@ -192,6 +214,18 @@ globalThis.diagnostics_toggle_all = function (): void {
   editor.setStatus(editor.t("status.showing", { label }));
 };
 
+globalThis.diagnostics_cycle_severity = function (): void {
+  if (!isOpen) return;
+
+  // All -> errors -> errors and warnings -> everything but hints -> all
+  maxSeverity = maxSeverity === 4 ? 1 : maxSeverity + 1;
+
+  finder.updateTitle(getTitle());
+  provider.notify();
+
+  editor.setStatus(editor.t("status.showing", { label: severityLabel() }));
+};
+
 globalThis.diagnostics_refresh = function (): void {
   if (!isOpen) return;
 
@@ -245,6 +279,7 @@ editor.defineMode(
   "diagnostics-results",
   [
     ["a", "diagnostics_toggle_all"],
+    ["s", "diagnostics_cycle_severity"],
     ["r", "diagnostics_refresh"],
   ],
   true
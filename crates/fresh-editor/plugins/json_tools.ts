@@ -0,0 +1,809 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * JSON Tools Plugin
+ *
+ * Built-in tooling for JSON and JSONC files that needs no external formatter.
+ *
+ * Key features:
+ * - Format and minify the document (Format keeps comments, Minify drops them)
+ * - The status bar shows the path of the value at the cursor, e.g. `.items[2].name`
+ * - Fold the document to a nesting depth; the cursor steps over folded values
+ * - Run a jq-style query and open the results in a scratch buffer
+ */
+
+const editor = getEditor();
+
+const FOLD_NAMESPACE = "json-folds";
+const STATUS_SEGMENT = "json-path";
+const CONTEXT = "json-mode";
+const FOLD_PLACEHOLDER = "…";
+const RESULTS_BUFFER_NAME = "*JSON Query*";
+const JSON_EXTENSIONS = [".json", ".jsonc"];
+
+// The breadcrumb and folding skip larger documents
+const MAX_SCAN_BYTES = 8 * 1024 * 1024;
+
+// =============================================================================
+// Tokenizer
+// =============================================================================
+
+type TokenKind = "punct" | "string" | "literal" | "comment";
+
+interface Token {
+  kind: TokenKind;
+  text: string;
+  start: number; // Byte offset
+  end: number; // Byte offset
+  newlineBefore: boolean;
+}
+
+function utf8Length(code: number): number {
+  if (code < 0x80) return 1;
+  if (code < 0x800) return 2;
+  // A surrogate pair is 4 bytes: count them on the high surrogate
+  if (code >= 0xd800 && code <= 0xdbff) return 4;
+  if (code >= 0xdc00 && code <= 0xdfff) return 0;
+  return 3;
+}
+
+function tokenize(text: string): Token[] {
+  const tokens: Token[] = [];
+  let i = 0;
+  let byte = 0;
+  let newlineBefore = false;
+  const advance = (to: number): void => {
+    for (; i < to; i++) byte += utf8Length(text.charCodeAt(i));
+  };
+
+  while (i < text.length) {
+    const ch = text[i];
+    if (ch === "\n") {
+      newlineBefore = true;
+      advance(i + 1);
+      continue;
+    }
+    if (ch === " " || ch === "\t" || ch === "\r") {
+      advance(i + 1);
+      continue;
+    }
+
+    const startIndex = i;
+    const start = byte;
+    let end: number;
+    let kind: TokenKind;
+    if ("{}[]:,".includes(ch)) {
+      kind = "punct";
+      end = i + 1;
+    } else if (ch === '"') {
+      kind = "string";
+      end = i + 1;
+      while (end < text.length && text[end] !== '"' && text[end] !== "\n") {
+        end += text[end] === "\\" ? 2 : 1;
+      }
+      if (text[end] === '"') end++;
+    } else if (ch === "/" && text[i + 1] === "/") {
+      kind = "comment";
+      end = text.indexOf("\n", i);
+      if (end < 0) end = text.length;
+    } else if (ch === "/" && text[i + 1] === "*") {
+      kind = "comment";
+      end = text.indexOf("*/", i + 2);
+      end = end < 0 ? text.length : end + 2;
+    } else {
+      kind = "literal";
+      end = i + 1;
+      while (end < text.length && !/[\s{}[\]:,"/]/.test(text[end])) end++;
+    }
+    end = Math.min(end, text.length);
+    advance(end);
+    tokens.push({ kind, text: text.slice(startIndex, end).trimEnd(), start, end: byte, newlineBefore });
+    newlineBefore = false;
+  }
+  return tokens;
+}
+
+function isOpen(token: Token): boolean {
+  return token.kind === "punct" && (token.text === "{" || token.text === "[");
+}
+
+function isClose(token: Token): boolean {
+  return token.kind === "punct" && (token.text === "}" || token.text === "]");
+}
+
+// =============================================================================
+// Format / Minify
+// =============================================================================
+
+function formatTokens(tokens: Token[], indent: string): string {
+  const lines: string[] = [];
+  let line = "";
+  let depth = 0;
+  let pendingBreak = false;
+  const breakLine = (): void => {
+    lines.push(line);
+    line = indent.repeat(depth);
+    pendingBreak = false;
+  };
+
+  for (let i = 0; i < tokens.length; i++) {
+    const token = tokens[i];
+    if (token.kind === "comment") {
+      if (token.newlineBefore && line.trim() !== "") breakLine();
+      line += line.trim() === "" ? token.text : ` ${token.text}`;
+      // Only a line comment forces a break: a block comment can share the line
+      if (token.text.startsWith("//") || token.newlineBefore) pendingBreak = true;
+      continue;
+    }
+    if (isClose(token)) {
+      depth = Math.max(depth - 1, 0);
+      if (line.trim() !== "") lines.push(line);
+      line = indent.repeat(depth) + token.text;
+      pendingBreak = depth === 0;
+      continue;
+    }
+    if (pendingBreak) breakLine();
+    if (isOpen(token)) {
+      const next = tokens[i + 1];
+      // Empty containers stay on one line
+      if (next && isClose(next)) {
+        line += token.text + next.text;
+        i++;
+        pendingBreak = depth === 0;
+        continue;
+      }
+      line += token.text;
+      depth++;
+      pendingBreak = true;
+    } else if (token.text === ",") {
+      line += ",";
+      pendingBreak = true;
+    } else if (token.text === ":") {
+      line += ": ";
+    } else {
+      line += token.text;
+      pendingBreak = depth === 0;
+    }
+  }
+  if (line.trim() !== "") lines.push(line);
+  return lines.join("\n") + "\n";
+}
+
+function minifyTokens(tokens: Token[]): string {
+  let text = "";
+  let depth = 0;
+  for (const token of tokens) {
+    if (token.kind === "comment") continue;
+    if (isOpen(token)) depth++;
+    if (isClose(token)) depth--;
+    text += token.text;
+    // Top-level values (JSON Lines) each keep their own line
+    if (depth === 0 && token.kind !== "punct") text += "\n";
+    if (depth === 0 && isClose(token)) text += "\n";
+  }
+  return text;
+}
+
+// JSONC to JSON: drop comments and trailing commas
+function parseJsonc(tokens: Token[]): unknown {
+  const kept = tokens.filter((token) => token.kind !== "comment");
+  const parts = kept.filter((token, i) => !(token.text === "," && kept[i + 1] && isClose(kept[i + 1])));
+  return JSON.parse(parts.map((token) => token.text).join(" "));
+}
+
+function indentUnit(): string {
+  const config = editor.getConfig() as {
+    editor?: { tab_size?: number };
+    languages?: { json?: { use_tabs?: boolean; tab_size?: number | null } };
+  };
+  const json = config?.languages?.json;
+  if (json?.use_tabs) return "\t";
+  return " ".repeat(json?.tab_size ?? config?.editor?.tab_size ?? 4);
+}
+
+// =============================================================================
+// Path at Cursor
+// =============================================================================
+
+type PathPart = string | number;
+
+interface Frame {
+  open: string;
+  key: string | null;
+  index: number;
+  expectKey: boolean;
+}
+
+function stringValue(token: Token): string {
+  try {
+    return JSON.parse(token.text) as string;
+  } catch {
+    return token.text.replace(/^"|"$/g, "");
+  }
+}
+
+function pathAt(tokens: Token[], offset: number): PathPart[] {
+  const stack: Frame[] = [];
+  for (const token of tokens) {
+    if (token.start >= offset) break;
+    if (token.kind === "comment") continue;
+    const top = stack[stack.length - 1];
+    if (isOpen(token)) {
+      stack.push({ open: token.text, key: null, index: 0, expectKey: token.text === "{" });
+    } else if (isClose(token)) {
+      stack.pop();
+    } else if (token.text === "," && top) {
+      top.index++;
+      top.key = null;
+      top.expectKey = top.open === "{";
+    } else if (token.text === ":" && top) {
+      top.expectKey = false;
+    } else if (token.kind === "string" && top?.open === "{" && top.expectKey) {
+      top.key = stringValue(token);
+    }
+  }
+
+  const path: PathPart[] = [];
+  for (const frame of stack) {
+    if (frame.open === "[") {
+      path.push(frame.index);
+    } else if (frame.key !== null) {
+      path.push(frame.key);
+    } else {
+      break;
+    }
+  }
+  return path;
+}
+
+function formatPath(path: PathPart[]): string {
+  let text = "";
+  for (const part of path) {
+    if (typeof part === "number") {
+      text += `[${part}]`;
+    } else if (/^[A-Za-z_][A-Za-z0-9_]*$/.test(part)) {
+      text += `.${part}`;
+    } else {
+      text += `[${JSON.stringify(part)}]`;
+    }
+  }
+  if (text === "" || text.startsWith("[")) text = `.${text}`;
+  return text;
+}
+
+// =============================================================================
+// Document State
+// =============================================================================
+
+interface Fold {
+  start: number; // Byte offset just after the opening bracket
+  end: number; // Byte offset of the closing bracket
+}
+
+interface JsonDocument {
+  tokens: Token[] | null; // null when stale
+  length: number; // Buffer length the tokens were read at
+  folds: Fold[];
+}
+
+const documents = new Map<number, JsonDocument>();
+
+function isJsonPath(path: string): boolean {
+  const lower = path.toLowerCase();
+  return JSON_EXTENSIONS.some((extension) => lower.endsWith(extension));
+}
+
+function enableDocument(bufferId: number, path: string): void {
+  if (!path || !isJsonPath(path) || documents.has(bufferId)) return;
+  documents.set(bufferId, { tokens: null, length: 0, folds: [] });
+}
+
+// Whole text of a buffer with its length in bytes, or null if it can't be read
+//
+// The length plugins see can lag behind an edit, so a rejected read is
+// retried once with the length read again.
+async function readDocument(bufferId: number): Promise<{ text: string; length: number } | null> {
+  for (let attempt = 0; attempt < 2; attempt++) {
+    const length = editor.getBufferLength(bufferId);
+    try {
+      return { text: await editor.getBufferText(bufferId, 0, length), length };
+    } catch {
+      // Retry with a fresh length
+    }
+  }
+  return null;
+}
+
+async function documentTokens(bufferId: number): Promise<Token[] | null> {
+  const doc = documents.get(bufferId);
+  if (!doc) return null;
+  const length = editor.getBufferLength(bufferId);
+  // The length seen right after opening a file can still be 0
+  if (doc.tokens && doc.length === length) return doc.tokens;
+  if (length > MAX_SCAN_BYTES) return null;
+  const current = await readDocument(bufferId);
+  if (!current) return null;
+  doc.tokens = tokenize(current.text);
+  doc.length = current.length;
+  return doc.tokens;
+}
+
+async function updateBreadcrumb(bufferId: number, position: number): Promise<void> {
+  const tokens = await documentTokens(bufferId);
+  if (!tokens || editor.getActiveBufferId() !== bufferId) return;
+  editor.registerStatusSegment(STATUS_SEGMENT, { text: formatPath(pathAt(tokens, position)), priority: 1 });
+}
+
+function updateContext(bufferId: number): void {
+  const active = documents.has(bufferId);
+  editor.setContext(CONTEXT, active);
+  if (active) {
+    updateBreadcrumb(bufferId, editor.getCursorPosition());
+  } else {
+    editor.removeStatusSegment(STATUS_SEGMENT);
+  }
+}
+
+function activeDocument(): number | null {
+  const bufferId = editor.getActiveBufferId();
+  if (!documents.has(bufferId)) {
+    editor.setStatus(editor.t("status.not_json"));
+    return null;
+  }
+  return bufferId;
+}
+
+async function replaceDocument(bufferId: number, text: string): Promise<void> {
+  const current = await readDocument(bufferId);
+  if (!current || current.text === text) return;
+  const cursor = editor.getCursorPosition();
+  unfoldAll(bufferId);
+  editor.deleteRange(bufferId, 0, current.length);
+  editor.insertText(bufferId, 0, text);
+  editor.setBufferCursor(bufferId, Math.min(cursor, text.length));
+}
+
+// =============================================================================
+// Folding
+// =============================================================================
+
+// Multi-line containers nested exactly `depth` levels deep (the top-level value is depth 0)
+function foldsAtDepth(tokens: Token[], depth: number): Fold[] {
+  const folds: Fold[] = [];
+  const stack: Array<{ open: Token; multiline: boolean }> = [];
+  for (const token of tokens) {
+    if (token.newlineBefore) {
+      for (const frame of stack) frame.multiline = true;
+    }
+    if (isOpen(token)) {
+      stack.push({ open: token, multiline: false });
+    } else if (isClose(token)) {
+      const frame = stack.pop();
+      if (frame && frame.multiline && stack.length === depth) {
+        folds.push({ start: frame.open.end, end: token.start });
+      }
+    }
+  }
+  return folds.sort((a, b) => a.start - b.start);
+}
+
+function applyFolds(bufferId: number, folds: Fold[]): void {
+  const doc = documents.get(bufferId);
+  if (!doc) return;
+  doc.folds = folds;
+  editor.clearFoldNamespace(bufferId, FOLD_NAMESPACE);
+  for (const fold of folds) {
+    editor.addFold(bufferId, FOLD_NAMESPACE, fold.start, fold.end, FOLD_PLACEHOLDER);
+  }
+}
+
+function unfoldAll(bufferId: number): void {
+  const doc = documents.get(bufferId);
+  if (!doc || doc.folds.length === 0) return;
+  applyFolds(bufferId, []);
+}
+
+// =============================================================================
+// Query
+// =============================================================================
+
+type QueryNode =
+  | { type: "identity" }
+  | { type: "literal"; value: unknown }
+  | { type: "field"; target: QueryNode; name: string }
+  | { type: "index"; target: QueryNode; index: number }
+  | { type: "slice"; target: QueryNode; from: number | null; to: number | null }
+  | { type: "iterate"; target: QueryNode }
+  | { type: "pipe"; left: QueryNode; right: QueryNode }
+  | { type: "compare"; op: string; left: QueryNode; right: QueryNode }
+  | { type: "call"; name: string; arg: QueryNode | null };
+
+const QUERY_FUNCTIONS = ["keys", "length", "type", "select", "map"];
+
+function lexQuery(query: string): string[] {
+  const pattern = /\s*(==|!=|<=|>=|"(?:[^"\\]|\\.)*"|-?\d+(?:\.\d+)?|[A-Za-z_][A-Za-z0-9_]*|[.[\]():|<>])/y;
+  const tokens: string[] = [];
+  let index = 0;
+  while (index < query.length) {
+    if (/^\s*$/.test(query.slice(index))) break;
+    pattern.lastIndex = index;
+    const match = pattern.exec(query);
+    if (!match) throw new Error(editor.t("error.unexpected", { text: query.slice(index).trim() }));
+    tokens.push(match[1]);
+    index = pattern.lastIndex;
+  }
+  return tokens;
+}
+
+function parseQuery(query: string): QueryNode {
+  const tokens = lexQuery(query);
+  let pos = 0;
+  const peek = (): string | undefined => tokens[pos];
+  const expect = (token: string): void => {
+    if (tokens[pos] !== token) {
+      throw new Error(editor.t("error.expected", { expected: token, found: tokens[pos] ?? "" }));
+    }
+    pos++;
+  };
+
+  const parseSuffixes = (target: QueryNode): QueryNode => {
+    for (;;) {
+      const token = peek();
+      if (token === "." && /^[A-Za-z_"]/.test(tokens[pos + 1] ?? "")) {
+        pos++;
+        const name = tokens[pos++];
+        target = { type: "field", target, name: name.startsWith('"') ? JSON.parse(name) : name };
+      } else if (token === "[") {
+        pos++;
+        if (peek() === "]") {
+          pos++;
+          target = { type: "iterate", target };
+        } else if (peek()?.startsWith('"')) {
+          target = { type: "field", target, name: JSON.parse(tokens[pos++]) };
+          expect("]");
+        } else {
+          const from = peek() === ":" ? null : Number(tokens[pos++]);
+          if (peek() === ":") {
+            pos++;
+            const to = peek() === "]" ? null : Number(tokens[pos++]);
+            target = { type: "slice", target, from, to };
+          } else {
+            target = { type: "index", target, index: from as number };
+          }
+          expect("]");
+        }
+      } else {
+        return target;
+      }
+    }
+  };
+
+  const parsePrimary = (): QueryNode => {
+    const token = peek();
+    if (token === undefined) throw new Error(editor.t("error.unexpected_end"));
+    if (token === ".") {
+      // `.` alone, or the start of a path like `.foo` / `.[0]`
+      if (/^[A-Za-z_"]/.test(tokens[pos + 1] ?? "")) return parseSuffixes({ type: "identity" });
+      pos++;
+      return parseSuffixes({ type: "identity" });
+    }
+    pos++;
+    if (token === "(") {
+      const inner = parsePipe();
+      expect(")");
+      return parseSuffixes(inner);
+    }
+    if (token.startsWith('"')) return { type: "literal", value: JSON.parse(token) };
+    if (/^-?\d/.test(token)) return { type: "literal", value: Number(token) };
+    if (token === "true" || token === "false" || token === "null") {
+      return { type: "literal", value: JSON.parse(token) };
+    }
+    if (QUERY_FUNCTIONS.includes(token)) {
+      let arg: QueryNode | null = null;
+      if (peek() === "(") {
+        pos++;
+        arg = parsePipe();
+        expect(")");
+      }
+      return parseSuffixes({ type: "call", name: token, arg });
+    }
+    throw new Error(editor.t("error.unexpected", { text: token }));
+  };
+
+  const parseComparison = (): QueryNode => {
+    const left = parsePrimary();
+    const op = peek();
+    if (op && ["==", "!=", "<", "<=", ">", ">="].includes(op)) {
+      pos++;
+      return { type: "compare", op, left, right: parsePrimary() };
+    }
+    return left;
+  };
+
+  const parsePipe = (): QueryNode => {
+    let node = parseComparison();
+    while (peek() === "|") {
+      pos++;
+      node = { type: "pipe", left: node, right: parseComparison() };
+    }
+    return node;
+  };
+
+  const node = parsePipe();
+  if (pos < tokens.length) throw new Error(editor.t("error.unexpected", { text: tokens[pos] }));
+  return node;
+}
+
+function typeOf(value: unknown): string {
+  if (value === null) return "null";
+  if (Array.isArray(value)) return "array";
+  return typeof value;
+}
+
+function compare(op: string, a: unknown, b: unknown): boolean {
+  if (op === "==") return JSON.stringify(a) === JSON.stringify(b);
+  if (op === "!=") return JSON.stringify(a) !== JSON.stringify(b);
+  const x = a as number | string;
+  const y = b as number | string;
+  if (op === "<") return x < y;
+  if (op === "<=") return x <= y;
+  if (op === ">") return x > y;
+  return x >= y;
+}
+
+function evaluate(node: QueryNode, input: unknown): unknown[] {
+  switch (node.type) {
+    case "identity":
+      return [input];
+    case "literal":
+      return [node.value];
+    case "field":
+      return evaluate(node.target, input).map((value) => {
+        if (value === null) return null;
+        if (typeOf(value) !== "object") {
+          throw new Error(editor.t("error.cannot_index", { type: typeOf(value), key: node.name }));
+        }
+        return (value as Record<string, unknown>)[node.name] ?? null;
+      });
+    case "index":
+      return evaluate(node.target, input).map((value) => {
+        if (value === null) return null;
+        if (!Array.isArray(value)) {
+          throw new Error(editor.t("error.cannot_index", { type: typeOf(value), key: String(node.index) }));
+        }
+        return value[node.index < 0 ? value.length + node.index : node.index] ?? null;
+      });
+    case "slice":
+      return evaluate(node.target, input).map((value) => {
+        if (!Array.isArray(value) && typeof value !== "string") {
+          throw new Error(editor.t("error.cannot_iterate", { type: typeOf(value) }));
+        }
+        return value.slice(node.from ?? 0, node.to ?? value.length);
+      });
+    case "iterate":
+      return evaluate(node.target, input).flatMap((value) => {
+        if (Array.isArray(value)) return value;
+        if (typeOf(value) === "object") return Object.values(value as Record<string, unknown>);
+        throw new Error(editor.t("error.cannot_iterate", { type: typeOf(value) }));
+      });
+    case "pipe":
+      return evaluate(node.left, input).flatMap((value) => evaluate(node.right, value));
+    case "compare":
+      return evaluate(node.left, input).flatMap((a) =>
+        evaluate(node.right, input).map((b) => compare(node.op, a, b))
+      );
+    case "call":
+      return evaluateCall(node.name, node.arg, input);
+  }
+}
+
+function evaluateCall(name: string, arg: QueryNode | null, input: unknown): unknown[] {
+  if ((name === "select" || name === "map") && !arg) {
+    throw new Error(editor.t("error.expected", { expected: "(", found: name }));
+  }
+  switch (name) {
+    case "keys":
+      if (Array.isArray(input)) return [input.map((_, i) => i)];
+      if (typeOf(input) === "object") return [Object.keys(input as object).sort()];
+      throw new Error(editor.t("error.no_keys", { type: typeOf(input) }));
+    case "length":
+      if (input === null) return [0];
+      if (Array.isArray(input) || typeof input === "string") return [input.length];
+      if (typeOf(input) === "object") return [Object.keys(input as object).length];
+      if (typeof input === "number") return [Math.abs(input)];
+      throw new Error(editor.t("error.no_length", { type: typeOf(input) }));
+    case "type":
+      return [typeOf(input)];
+    case "select":
+      return evaluate(arg!, input).some((value) => value !== false && value !== null) ? [input] : [];
+    default: {
+      // map(f) is [.[] | f]
+      const items = evaluate({ type: "iterate", target: { type: "identity" } }, input);
+      return [items.flatMap((item) => evaluate(arg!, item))];
+    }
+  }
+}
+
+let queryBufferId: number | null = null;
+let queryResultsId: number | null = null;
+
+async function showQueryResults(text: string): Promise<void> {
+  const entries = [{ text }];
+  const open = queryResultsId !== null && editor.listBuffers().some((buffer) => buffer.id === queryResultsId);
+  if (open && queryResultsId !== null) {
+    editor.setVirtualBufferContent(queryResultsId, entries);
+    editor.showBuffer(queryResultsId);
+    return;
+  }
+  const result = await editor.createVirtualBuffer({
+    name: RESULTS_BUFFER_NAME,
+    mode: "normal",
+    readOnly: false,
+    entries,
+    showLineNumbers: true,
+  });
+  queryResultsId = result.bufferId;
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+globalThis.json_format = async function (): Promise<void> {
+  const bufferId = activeDocument();
+  if (bufferId === null) return;
+  const current = await readDocument(bufferId);
+  if (!current) return;
+  await replaceDocument(bufferId, formatTokens(tokenize(current.text), indentUnit()));
+  editor.setStatus(editor.t("status.formatted"));
+};
+
+globalThis.json_minify = async function (): Promise<void> {
+  const bufferId = activeDocument();
+  if (bufferId === null) return;
+  const current = await readDocument(bufferId);
+  if (!current) return;
+  await replaceDocument(bufferId, minifyTokens(tokenize(current.text)));
+  editor.setStatus(editor.t("status.minified"));
+};
+
+globalThis.json_fold_to_depth = function (): void {
+  const bufferId = activeDocument();
+  if (bufferId === null) return;
+  editor.startPromptWithInitial(editor.t("prompt.fold_depth"), "json-fold-depth", "1");
+};
+
+globalThis.json_unfold_all = function (): void {
+  const bufferId = activeDocument();
+  if (bufferId === null) return;
+  unfoldAll(bufferId);
+  editor.setStatus(editor.t("status.unfolded"));
+};
+
+globalThis.json_query = function (): void {
+  const bufferId = activeDocument();
+  if (bufferId === null) return;
+  queryBufferId = bufferId;
+  editor.startPrompt(editor.t("prompt.query"), "json-query");
+};
+
+async function foldToDepth(input: string): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const depth = parseInt(input.trim(), 10);
+  if (!documents.has(bufferId)) return;
+  if (isNaN(depth) || depth < 0) {
+    editor.setStatus(editor.t("status.invalid_depth", { input }));
+    return;
+  }
+  const tokens = await documentTokens(bufferId);
+  if (!tokens) {
+    editor.setStatus(editor.t("status.too_large"));
+    return;
+  }
+  const folds = foldsAtDepth(tokens, depth);
+  applyFolds(bufferId, folds);
+  editor.setStatus(editor.t("status.folded", { count: String(folds.length), depth: String(depth) }));
+}
+
+async function runQuery(query: string): Promise<void> {
+  const bufferId = queryBufferId;
+  queryBufferId = null;
+  if (bufferId === null || !documents.has(bufferId)) return;
+  const current = await readDocument(bufferId);
+  if (!current) return;
+  const tokens = tokenize(current.text);
+  let results: unknown[];
+  try {
+    const node = parseQuery(query.trim() === "" ? "." : query);
+    let input: unknown;
+    try {
+      input = parseJsonc(tokens);
+    } catch (e) {
+      throw new Error(editor.t("error.invalid_json", { error: e instanceof Error ? e.message : String(e) }));
+    }
+    results = evaluate(node, input);
+  } catch (e) {
+    editor.setStatus(editor.t("status.query_failed", { error: e instanceof Error ? e.message : String(e) }));
+    return;
+  }
+  const indent = indentUnit();
+  const text = results.map((value) => JSON.stringify(value, null, indent) ?? "null").join("\n");
+  await showQueryResults(text + "\n");
+  editor.setStatus(editor.t("status.query_results", { count: String(results.length) }));
+}
+
+// =============================================================================
+// Event Handlers
+// =============================================================================
+
+globalThis.onJsonAfterFileOpen = function (args: { buffer_id: number; path: string }): boolean {
+  enableDocument(args.buffer_id, args.path);
+  if (editor.getActiveBufferId() === args.buffer_id) updateContext(args.buffer_id);
+  return true;
+};
+
+globalThis.onJsonBufferActivated = function (args: { buffer_id: number }): void {
+  updateContext(args.buffer_id);
+};
+
+globalThis.onJsonBufferClosed = function (args: { buffer_id: number }): void {
+  documents.delete(args.buffer_id);
+  if (args.buffer_id === queryResultsId) queryResultsId = null;
+};
+
+// Edits make the token cache stale and the recorded fold offsets unreliable
+globalThis.onJsonBufferEdited = function (args: { buffer_id: number }): boolean {
+  const doc = documents.get(args.buffer_id);
+  if (doc) {
+    doc.tokens = null;
+    unfoldAll(args.buffer_id);
+  }
+  return true;
+};
+
+globalThis.onJsonCursorMoved = function (data: {
+  buffer_id: number;
+  old_position: number;
+  new_position: number;
+}): void {
+  const doc = documents.get(data.buffer_id);
+  if (!doc) return;
+  // Step over a folded value instead of landing inside it
+  const fold = doc.folds.find((f) => data.new_position > f.start && data.new_position < f.end);
+  if (fold) {
+    editor.setBufferCursor(data.buffer_id, data.old_position <= fold.start ? fold.end : fold.start);
+    return;
+  }
+  updateBreadcrumb(data.buffer_id, data.new_position);
+};
+
+globalThis.onJsonPromptConfirmed = function (args: { prompt_type: string; input: string }): void {
+  if (args.prompt_type === "json-fold-depth") {
+    foldToDepth(args.input);
+  } else if (args.prompt_type === "json-query") {
+    runQuery(args.input);
+  }
+};
+
+editor.on("after_file_open", "onJsonAfterFileOpen");
+editor.on("buffer_activated", "onJsonBufferActivated");
+editor.on("buffer_closed", "onJsonBufferClosed");
+editor.on("after_insert", "onJsonBufferEdited");
+editor.on("after_delete", "onJsonBufferEdited");
+editor.on("cursor_moved", "onJsonCursorMoved");
+editor.on("prompt_confirmed", "onJsonPromptConfirmed");
+
+// Command Registration
+editor.registerCommand("%cmd.json_format", "%cmd.json_format_desc", "json_format", CONTEXT);
+editor.registerCommand("%cmd.json_minify", "%cmd.json_minify_desc", "json_minify", CONTEXT);
+editor.registerCommand("%cmd.json_fold_to_depth", "%cmd.json_fold_to_depth_desc", "json_fold_to_depth", CONTEXT);
+editor.registerCommand("%cmd.json_unfold_all", "%cmd.json_unfold_all_desc", "json_unfold_all", CONTEXT);
+editor.registerCommand("%cmd.json_query", "%cmd.json_query_desc", "json_query", CONTEXT);
+
+// Documents opened before the plugin loaded
+for (const buffer of editor.listBuffers()) {
+  enableDocument(buffer.id, buffer.path);
+}
+updateContext(editor.getActiveBufferId());
+
+editor.debug("JSON tools plugin loaded");
@@ -174,6 +174,9 @@ const colors = {
   separator: [100, 100, 100] as RGB,
 };
 
+/** Files previewed as images rather than text */
+const IMAGE_FILE_PATTERN = /\.(png|jpe?g|gif|bmp|webp)$/i;
+
 // ============================================================================
 // Fuzzy Filter
 // ============================================================================
@@ -330,6 +333,7 @@ interface PromptState<T> {
   searchVersion: number;
   currentSearch: ProcessHandle<SpawnResult> | null;
   pendingKill: Promise<boolean> | null;
+  debounceTimer: number | null;
   originalSplitId: number | null;
 }
 
@@ -369,6 +373,7 @@ export class Finder<T> {
     searchVersion: 0,
     currentSearch: null,
     pendingKill: null,
+    debounceTimer: null,
     originalSplitId: null,
   };
 
@@ -449,6 +454,7 @@ export class Finder<T> {
       searchVersion: 0,
       currentSearch: null,
       pendingKill: null,
+      debounceTimer: null,
       originalSplitId: this.editor.getActiveSplitId(),
     };
 
@@ -672,8 +678,15 @@ export class Finder<T> {
       return;
     }
 
-    // Debounce
-    await this.editor.delay(debounceMs);
+    // Debounce: a newer query restarts the timer, so superseded searches
+    // never wake up
+    if (this.promptState.debounceTimer !== null) {
+      this.editor.clearTimer(this.promptState.debounceTimer);
+    }
+    await new Promise<void>((resolve) => {
+      this.promptState.debounceTimer = this.editor.setTimeout(resolve, debounceMs);
+    });
+    this.promptState.debounceTimer = null;
 
     // Wait for pending kill
     if (this.promptState.pendingKill) {
@@ -878,12 +891,20 @@ export class Finder<T> {
     if (!entry.location) return;
 
     try {
-      const content = await this.editor.readFile(entry.location.file);
+      // Images are drawn by the editor instead of read as text
+      const isImage = IMAGE_FILE_PATTERN.test(entry.location.file);
+      const content = isImage
+        ? ""
+        : await this.editor.readFile(entry.location.file);
       const lines = content.split("\n");
 
       const contextLines = this.getContextLines();
-      const startLine = Math.max(0, entry.location.line - 1 - contextLines);
-      const endLine = Math.min(lines.length, entry.location.line + contextLines);
+      const startLine = isImage
+        ? 0
+        : Math.max(0, entry.location.line - 1 - contextLines);
+      const endLine = isImage
+        ? 0
+        : Math.min(lines.length, entry.location.line + contextLines);
 
       const entries: TextPropertyEntry[] = [];
 
@@ -947,6 +968,10 @@ export class Finder<T> {
         // Update existing preview
         this.editor.setVirtualBufferContent(this.previewState.bufferId, entries);
       }
+      this.editor.setImagePreview(
+        this.previewState.bufferId,
+        isImage ? entry.location.file : null
+      );
     } catch (e) {
       this.editor.debug(`[Finder] Failed to update preview: ${e}`);
     }
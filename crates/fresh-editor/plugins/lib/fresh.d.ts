@@ -27,300 +27,345 @@ interface ProcessHandle<T> extends PromiseLike<T> {
 type BufferId = number;
 /** Split identifier */
 type SplitId = number;
-type TextPropertyEntry = {
+type BufferInfo = {
 	/**
-	* Text content for this entry
+	* Buffer ID
 	*/
-	text: string;
+	id: number;
 	/**
-	* Optional properties attached to this text (e.g., file path, line number)
+	* File path (if any)
 	*/
-	properties?: Record<string, unknown>;
-};
-type TsCompositeLayoutConfig = {
+	path: string;
 	/**
-	* Layout type: "side-by-side", "stacked", or "unified"
+	* Whether the buffer has been modified
 	*/
-	type: string;
+	modified: boolean;
 	/**
-	* Width ratios for side-by-side (e.g., [0.5, 0.5])
+	* Length of buffer in bytes
 	*/
-	ratios: Array<number> | null;
+	length: number;
 	/**
-	* Show separator between panes
+	* Whether this is a virtual buffer (not backed by a file)
 	*/
-	showSeparator: boolean;
+	is_virtual: boolean;
 	/**
-	* Spacing for stacked layout
+	* Current view mode of the active split: "source" or "compose"
 	*/
-	spacing: number | null;
-};
-type TsCompositeSourceConfig = {
+	view_mode: string;
 	/**
-	* Buffer ID of the source buffer (required)
+	* True if any split showing this buffer has compose mode enabled.
+	* Plugins should use this (not `view_mode`) to decide whether to maintain
+	* decorations, since decorations live on the buffer and are filtered
+	* per-split at render time.
 	*/
-	bufferId: number;
+	is_composing_in_any_split: boolean;
 	/**
-	* Label for this pane (e.g., "OLD", "NEW")
+	* Compose width (if set), from the active split's view state
 	*/
-	label: string;
+	compose_width: number | null;
+};
+type CursorInfo = {
 	/**
-	* Whether this pane is editable
+	* Byte position of the cursor
 	*/
-	editable: boolean;
+	position: number;
 	/**
-	* Style configuration
+	* Selection range (if any)
 	*/
-	style: TsCompositePaneStyle | null;
+	selection: {
+		start: number;
+		end: number;
+	} | null;
 };
-type TsCompositePaneStyle = {
+type ViewportInfo = {
 	/**
-	* Background color for added lines (RGB)
-	* Using [u8; 3] instead of (u8, u8, u8) for better rquickjs_serde compatibility
+	* Byte position of the first visible line
 	*/
-	addBg: [number, number, number] | null;
+	topByte: number;
 	/**
-	* Background color for removed lines (RGB)
+	* Left column offset (horizontal scroll)
 	*/
-	removeBg: [number, number, number] | null;
+	leftColumn: number;
 	/**
-	* Background color for modified lines (RGB)
+	* Viewport width
 	*/
-	modifyBg: [number, number, number] | null;
+	width: number;
 	/**
-	* Gutter style: "line-numbers", "diff-markers", "both", or "none"
+	* Viewport height
 	*/
-	gutterStyle: string | null;
+	height: number;
 };
-type TsCompositeHunk = {
+type ActionSpec = {
 	/**
-	* Starting line in old buffer (0-indexed)
+	* Action name (e.g., "move_word_right", "delete_line")
 	*/
-	oldStart: number;
+	action: string;
 	/**
-	* Number of lines in old buffer
+	* Number of times to repeat the action (default 1)
 	*/
-	oldCount: number;
+	count: number;
+};
+type BufferSavedDiff = {
+	equal: boolean;
+	byte_ranges: Array<[number, number]>;
+	line_ranges: Array<[number, number]> | null;
+};
+type LayoutHints = {
 	/**
-	* Starting line in new buffer (0-indexed)
+	* Optional compose width for centering/wrapping
 	*/
-	newStart: number;
+	composeWidth: number | null;
 	/**
-	* Number of lines in new buffer
+	* Optional column guides for aligned tables
 	*/
-	newCount: number;
+	columnGuides: Array<number> | null;
 };
-type TsCreateCompositeBufferOptions = {
+type SpawnResult = {
 	/**
-	* Buffer name (displayed in tabs/title)
+	* Complete stdout as string
 	*/
-	name: string;
+	stdout: string;
 	/**
-	* Mode for keybindings
+	* Complete stderr as string
 	*/
-	mode: string;
+	stderr: string;
 	/**
-	* Layout configuration
+	* Process exit code (0 usually means success, -1 if killed)
 	*/
-	layout: TsCompositeLayoutConfig;
+	exit_code: number;
+};
+type BackgroundProcessResult = {
 	/**
-	* Source pane configurations
+	* Unique process ID for later reference
 	*/
-	sources: Array<TsCompositeSourceConfig>;
+	process_id: number;
 	/**
-	* Diff hunks for alignment (optional)
+	* Process exit code (0 usually means success, -1 if killed)
+	* Only present when the process has exited
 	*/
-	hunks: Array<TsCompositeHunk> | null;
+	exit_code: number;
 };
-type ViewportInfo = {
+type BeforeSaveEdit = {
 	/**
-	* Byte position of the first visible line
+	* Start byte offset (inclusive)
 	*/
-	topByte: number;
+	start: number;
 	/**
-	* Left column offset (horizontal scroll)
+	* End byte offset (exclusive)
 	*/
-	leftColumn: number;
+	end: number;
 	/**
-	* Viewport width
+	* Text to put in place of the range
 	*/
-	width: number;
+	text: string;
+};
+type BeforeSaveResult = {
 	/**
-	* Viewport height
+	* Edits to make before the file is written
 	*/
-	height: number;
+	edits?: Array<BeforeSaveEdit>;
+	/**
+	* Cancel the save; the reason is shown in the status bar
+	*/
+	veto?: string;
 };
-type LayoutHints = {
+type CompletionContext = {
+	bufferId: number;
+	language: string;
 	/**
-	* Optional compose width for centering/wrapping
+	* Byte offset of the cursor
 	*/
-	composeWidth: number | null;
+	position: number;
 	/**
-	* Optional column guides for aligned tables
+	* The partial word before the cursor
 	*/
-	columnGuides: Array<number> | null;
+	prefix: string;
 };
-type ViewTokenWire = {
+type CompletionItem = {
 	/**
-	* Source byte offset in the buffer. None for injected content (annotations).
+	* Text shown in the popup; inserted unless `insertText` is set
 	*/
-	source_offset: number | null;
+	label: string;
 	/**
-	* The token content
+	* Text to insert instead of the label
 	*/
-	kind: ViewTokenWireKind;
+	insertText?: string;
 	/**
-	* Optional styling for injected content (only used when source_offset is None)
+	* Text matched against what was typed instead of the label
 	*/
-	style?: ViewTokenStyle;
+	filterText?: string;
+	/**
+	* Short description shown next to the label
+	*/
+	detail?: string;
+	/**
+	* "function", "method", "variable", "class", "struct", "constant", "keyword", ...
+	*/
+	kind?: string;
 };
-type ViewTokenWireKind = {
-	"Text": string;
-} | "Newline" | "Space" | "Break" | {
-	"BinaryByte": number;
+type TerminalResult = {
+	/**
+	* The created buffer ID (for use with setSplitBuffer, etc.)
+	*/
+	bufferId: number;
+	/**
+	* The terminal ID (for use with sendTerminalInput, closeTerminal)
+	*/
+	terminalId: number;
+	/**
+	* The split ID (if created in a new split)
+	*/
+	splitId: number | null;
 };
-type ViewTokenStyle = {
+type CreateTerminalOptions = {
 	/**
-	* Foreground color as RGB tuple
+	* Working directory for the terminal (defaults to editor cwd)
 	*/
-	fg: [number, number, number] | null;
+	cwd?: string;
 	/**
-	* Background color as RGB tuple
+	* Split direction: "horizontal" or "vertical" (default: "vertical")
 	*/
-	bg: [number, number, number] | null;
+	direction?: string;
 	/**
-	* Whether to render in bold
+	* Split ratio 0.0-1.0 (default: 0.5)
 	*/
-	bold: boolean;
+	ratio?: number;
 	/**
-	* Whether to render in italic
+	* Whether to focus the new terminal split (default: true)
 	*/
-	italic: boolean;
+	focus?: boolean;
 };
-type PromptSuggestion = {
+type TsCompositeHunk = {
 	/**
-	* The text to display
+	* Starting line in old buffer (0-indexed)
 	*/
-	text: string;
+	oldStart: number;
 	/**
-	* Optional description
+	* Number of lines in old buffer
 	*/
-	description?: string;
+	oldCount: number;
 	/**
-	* The value to use when selected (defaults to text if None)
+	* Starting line in new buffer (0-indexed)
 	*/
-	value?: string;
+	newStart: number;
 	/**
-	* Whether this suggestion is disabled (greyed out, defaults to false)
+	* Number of lines in new buffer
 	*/
-	disabled?: boolean;
+	newCount: number;
+};
+type TsCompositeLayoutConfig = {
 	/**
-	* Optional keyboard shortcut
+	* Layout type: "side-by-side", "stacked", or "unified"
 	*/
-	keybinding?: string;
-};
-type DirEntry = {
+	type: string;
 	/**
-	* File/directory name
+	* Width ratios for side-by-side (e.g., [0.5, 0.5])
 	*/
-	name: string;
+	ratios: Array<number> | null;
 	/**
-	* True if this is a file
+	* Show separator between panes
 	*/
-	is_file: boolean;
+	showSeparator: boolean;
 	/**
-	* True if this is a directory
+	* Spacing for stacked layout
 	*/
-	is_dir: boolean;
+	spacing: number | null;
 };
-type BufferInfo = {
+type TsCompositePaneStyle = {
 	/**
-	* Buffer ID
+	* Background color for added lines (RGB)
+	* Using [u8; 3] instead of (u8, u8, u8) for better rquickjs_serde compatibility
 	*/
-	id: number;
+	addBg: [number, number, number] | null;
 	/**
-	* File path (if any)
+	* Background color for removed lines (RGB)
 	*/
-	path: string;
+	removeBg: [number, number, number] | null;
 	/**
-	* Whether the buffer has been modified
+	* Background color for modified lines (RGB)
 	*/
-	modified: boolean;
+	modifyBg: [number, number, number] | null;
 	/**
-	* Length of buffer in bytes
+	* Gutter style: "line-numbers", "diff-markers", "both", or "none"
 	*/
-	length: number;
+	gutterStyle: string | null;
+};
+type TsCompositeSourceConfig = {
 	/**
-	* Whether this is a virtual buffer (not backed by a file)
+	* Buffer ID of the source buffer (required)
 	*/
-	is_virtual: boolean;
+	bufferId: number;
 	/**
-	* Current view mode of the active split: "source" or "compose"
+	* Label for this pane (e.g., "OLD", "NEW")
 	*/
-	view_mode: string;
+	label: string;
 	/**
-	* True if any split showing this buffer has compose mode enabled.
-	* Plugins should use this (not view_mode) to decide whether to maintain
-	* decorations, since decorations live on the buffer and are filtered
-	* per-split at render time.
+	* Whether this pane is editable
 	*/
-	is_composing_in_any_split: boolean;
+	editable: boolean;
 	/**
-	* Compose width (if set), from the active split's view state
+	* Style configuration
 	*/
-	compose_width: number | null;
+	style: TsCompositePaneStyle | null;
 };
-type JsDiagnostic = {
+type TsCreateCompositeBufferOptions = {
 	/**
-	* Document URI
+	* Buffer name (displayed in tabs/title)
 	*/
-	uri: string;
+	name: string;
 	/**
-	* Diagnostic message
+	* Mode for keybindings
 	*/
-	message: string;
+	mode: string;
 	/**
-	* Severity: 1=Error, 2=Warning, 3=Info, 4=Hint, null=unknown
+	* Layout configuration
 	*/
-	severity: number | null;
+	layout: TsCompositeLayoutConfig;
 	/**
-	* Range in the document
+	* Source pane configurations
 	*/
-	range: JsRange;
+	sources: Array<TsCompositeSourceConfig>;
 	/**
-	* Source of the diagnostic (e.g., "typescript", "eslint")
+	* Diff hunks for alignment (optional)
 	*/
-	source?: string;
+	hunks: Array<TsCompositeHunk> | null;
 };
-type JsRange = {
+type ViewTokenWireKind = {
+	"Text": string;
+} | "Newline" | "Space" | "Break" | {
+	"BinaryByte": number;
+};
+type ViewTokenStyle = {
 	/**
-	* Start position
+	* Foreground color as RGB tuple
 	*/
-	start: JsPosition;
+	fg: [number, number, number] | null;
 	/**
-	* End position
+	* Background color as RGB tuple
 	*/
-	end: JsPosition;
-};
-type JsPosition = {
+	bg: [number, number, number] | null;
 	/**
-	* Zero-indexed line number
+	* Whether to render in bold
 	*/
-	line: number;
+	bold: boolean;
 	/**
-	* Zero-indexed character offset
+	* Whether to render in italic
 	*/
-	character: number;
+	italic: boolean;
 };
-type ActionSpec = {
+type ViewTokenWire = {
 	/**
-	* Action name (e.g., "move_word_right", "delete_line")
+	* Source byte offset in the buffer. None for injected content (annotations).
 	*/
-	action: string;
+	source_offset: number | null;
 	/**
-	* Number of times to repeat the action (default 1)
+	* The token content
 	*/
-	count: number;
+	kind: ViewTokenWireKind;
+	/**
+	* Optional styling for injected content (only used when source_offset is None)
+	*/
+	style?: ViewTokenStyle;
 };
 type TsActionPopupAction = {
 	/**
@@ -350,105 +395,131 @@ type ActionPopupOptions = {
 	*/
 	actions: Array<TsActionPopupAction>;
 };
-type FileExplorerDecoration = {
+type FloatingPanelOptions = {
 	/**
-	* File path to decorate
+	* Unique identifier for the panel (used by hideFloatingPanel)
 	*/
-	path: string;
+	id: string;
 	/**
-	* Symbol to display (e.g., "●", "M", "A")
+	* Lines of text to display
 	*/
-	symbol: string;
+	lines: Array<string>;
 	/**
-	* Color as RGB array (rquickjs_serde requires array, not tuple)
+	* Title shown in the top border
 	*/
-	color: [number, number, number];
+	title?: string;
 	/**
-	* Priority for display when multiple decorations exist (higher wins)
+	* Anchor: "cursor" (below the cursor), "aboveCursor", "center" or "bottomRight"
+	* (default: "cursor"). Ignored when x and y are given.
 	*/
-	priority: number;
-};
-type FormatterPackConfig = {
+	anchor?: string;
 	/**
-	* Command to run (e.g., "prettier", "rustfmt")
+	* Screen column of the top-left corner
 	*/
-	command: string;
+	x?: number;
 	/**
-	* Arguments to pass to the formatter
+	* Screen row of the top-left corner
+	*/
+	y?: number;
+	/**
+	* Width in columns including the border (default: fits the content)
+	*/
+	width?: number;
+	/**
+	* Maximum height in rows including the border (default: fits the content)
+	*/
+	height?: number;
+	/**
+	* Whether to draw a border (default: true)
+	*/
+	border?: boolean;
+	/**
+	* Stacking order relative to other popups; higher draws on top (default: 0)
 	*/
-	args: Array<string>;
+	zIndex?: number;
 };
-type TerminalResult = {
+type OverlayColorSpec = [number, number, number] | string;
+type StatusSegmentOptions = {
 	/**
-	* The created buffer ID (for use with setSplitBuffer, etc.)
+	* Text shown in the status bar
 	*/
-	bufferId: number;
+	text: string;
 	/**
-	* The terminal ID (for use with sendTerminalInput, closeTerminal)
+	* Foreground color - RGB array or theme key string (default: the status bar's)
 	*/
-	terminalId: number;
+	fg?: OverlayColorSpec;
 	/**
-	* The split ID (if created in a new split)
+	* Background color - RGB array or theme key string (default: the status bar's)
 	*/
-	splitId: number | null;
+	bg?: OverlayColorSpec;
+	/**
+	* Order among segments; higher is further left (default: 0)
+	*/
+	priority?: number;
+	/**
+	* Command or action run when the segment is clicked
+	*/
+	command?: string;
 };
-type CreateTerminalOptions = {
+type GutterSign = {
 	/**
-	* Working directory for the terminal (defaults to editor cwd)
+	* Line number (0-indexed)
 	*/
-	cwd?: string;
+	line: number;
 	/**
-	* Split direction: "horizontal" or "vertical" (default: "vertical")
+	* Symbol to display (e.g., "✓", "✗", "★")
 	*/
-	direction?: string;
+	symbol: string;
 	/**
-	* Split ratio 0.0-1.0 (default: 0.5)
+	* Color - RGB array or theme key string (default: "editor.line_number_fg")
 	*/
-	ratio?: number;
+	color?: OverlayColorSpec;
 	/**
-	* Whether to focus the new terminal split (default: true)
+	* Priority against other indicators on the line; higher wins (default: 0)
 	*/
-	focus?: boolean;
+	priority?: number;
 };
-type CursorInfo = {
+type TsHighlightSpan = {
+	start: number;
+	end: number;
+	color: [number, number, number];
+	bold: boolean;
+	italic: boolean;
+};
+type FileExplorerDecoration = {
 	/**
-	* Byte position of the cursor
+	* File path to decorate
 	*/
-	position: number;
+	path: string;
 	/**
-	* Selection range (if any)
+	* Symbol to display (e.g., "●", "M", "A")
 	*/
-	selection: {
-		start: number;
-		end: number;
-	} | null;
+	symbol: string;
+	/**
+	* Color as RGB array (rquickjs_serde requires array, not tuple)
+	*/
+	color: [number, number, number];
+	/**
+	* Priority for display when multiple decorations exist (higher wins)
+	*/
+	priority: number;
 };
-type BackgroundProcessResult = {
+type TextPropertyEntry = {
 	/**
-	* Unique process ID for later reference
+	* Text content for this entry
 	*/
-	process_id: number;
+	text: string;
 	/**
-	* Process exit code (0 usually means success, -1 if killed)
-	* Only present when the process has exited
+	* Optional properties attached to this text (e.g., file path, line number)
 	*/
-	exit_code: number;
-};
-type BufferSavedDiff = {
-	equal: boolean;
-	byte_ranges: Array<[number, number]>;
-	line_ranges: Array<[number, number]> | null;
+	properties?: Record<string, unknown>;
 };
-type CreateVirtualBufferInExistingSplitOptions = {
+type CreateVirtualBufferOptions = {
 	/**
 	* Buffer name (displayed in tabs/title)
 	*/
 	name: string;
 	/**
-	* Target split ID (required)
-	*/
-	splitId: number;
-	/**
 	* Mode for keybindings (e.g., "git-log", "search-results")
 	*/
 	mode?: string;
@@ -457,7 +528,7 @@ type CreateVirtualBufferInExistingSplitOptions = {
 	*/
 	readOnly?: boolean;
 	/**
-	* Show line numbers in gutter (default: true)
+	* Show line numbers in gutter (default: false)
 	*/
 	showLineNumbers?: boolean;
 	/**
@@ -469,9 +540,9 @@ type CreateVirtualBufferInExistingSplitOptions = {
 	*/
 	editingDisabled?: boolean;
 	/**
-	* Enable line wrapping
+	* Hide from tab bar (default: false)
 	*/
-	lineWrap?: boolean;
+	hiddenFromTabs?: boolean;
 	/**
 	* Initial content entries with optional properties
 	*/
@@ -527,12 +598,16 @@ type CreateVirtualBufferInSplitOptions = {
 	*/
 	entries?: Array<TextPropertyEntry>;
 };
-type CreateVirtualBufferOptions = {
+type CreateVirtualBufferInExistingSplitOptions = {
 	/**
 	* Buffer name (displayed in tabs/title)
 	*/
 	name: string;
 	/**
+	* Target split ID (required)
+	*/
+	splitId: number;
+	/**
 	* Mode for keybindings (e.g., "git-log", "search-results")
 	*/
 	mode?: string;
@@ -541,7 +616,7 @@ type CreateVirtualBufferOptions = {
 	*/
 	readOnly?: boolean;
 	/**
-	* Show line numbers in gutter (default: false)
+	* Show line numbers in gutter (default: true)
 	*/
 	showLineNumbers?: boolean;
 	/**
@@ -553,48 +628,147 @@ type CreateVirtualBufferOptions = {
 	*/
 	editingDisabled?: boolean;
 	/**
-	* Hide from tab bar (default: false)
+	* Enable line wrapping
 	*/
-	hiddenFromTabs?: boolean;
+	lineWrap?: boolean;
 	/**
 	* Initial content entries with optional properties
 	*/
 	entries?: Array<TextPropertyEntry>;
 };
+type TextPropertiesAtCursor = Array<Record<string, unknown>>;
+type VirtualBufferResult = {
+	/**
+	* The created buffer ID
+	*/
+	bufferId: number;
+	/**
+	* The split ID (if created in a new split)
+	*/
+	splitId: number | null;
+};
+type PromptSuggestion = {
+	/**
+	* The text to display
+	*/
+	text: string;
+	/**
+	* Optional description
+	*/
+	description?: string;
+	/**
+	* The value to use when selected (defaults to text if None)
+	*/
+	value?: string;
+	/**
+	* Whether this suggestion is disabled (greyed out, defaults to false)
+	*/
+	disabled?: boolean;
+	/**
+	* Optional keyboard shortcut
+	*/
+	keybinding?: string;
+};
+type DirEntry = {
+	/**
+	* File/directory name
+	*/
+	name: string;
+	/**
+	* True if this is a file
+	*/
+	is_file: boolean;
+	/**
+	* True if this is a directory
+	*/
+	is_dir: boolean;
+};
+type JsPosition = {
+	/**
+	* Zero-indexed line number
+	*/
+	line: number;
+	/**
+	* Zero-indexed character offset
+	*/
+	character: number;
+};
+type JsRange = {
+	/**
+	* Start position
+	*/
+	start: JsPosition;
+	/**
+	* End position
+	*/
+	end: JsPosition;
+};
+type JsDiagnostic = {
+	/**
+	* Document URI
+	*/
+	uri: string;
+	/**
+	* Diagnostic message
+	*/
+	message: string;
+	/**
+	* Severity: 1=Error, 2=Warning, 3=Info, 4=Hint, null=unknown
+	*/
+	severity: number | null;
+	/**
+	* Range in the document
+	*/
+	range: JsRange;
+	/**
+	* Source of the diagnostic (e.g., "typescript", "eslint")
+	*/
+	source?: string;
+};
+type FormatterPackConfig = {
+	/**
+	* Command to run (e.g., "prettier", "rustfmt")
+	*/
+	command: string;
+	/**
+	* Arguments to pass to the formatter
+	*/
+	args?: Array<string>;
+};
 type LanguagePackConfig = {
 	/**
 	* Comment prefix for line comments (e.g., "//" or "#")
 	*/
-	commentPrefix: string | null;
+	commentPrefix?: string;
 	/**
 	* Block comment start marker (e.g., slash-star)
 	*/
-	blockCommentStart: string | null;
+	blockCommentStart?: string;
 	/**
 	* Block comment end marker (e.g., star-slash)
 	*/
-	blockCommentEnd: string | null;
+	blockCommentEnd?: string;
 	/**
 	* Whether to use tabs instead of spaces for indentation
 	*/
-	useTabs: boolean | null;
+	useTabs?: boolean;
 	/**
 	* Tab size (number of spaces per tab level)
 	*/
-	tabSize: number | null;
+	tabSize?: number;
 	/**
 	* Whether auto-indent is enabled
 	*/
-	autoIndent: boolean | null;
+	autoIndent?: boolean;
 	/**
 	* Whether to show whitespace tab indicators (→) for this language
 	* Defaults to true. Set to false for languages like Go/Hare that use tabs for indentation.
 	*/
-	showWhitespaceTabs: boolean | null;
+	showWhitespaceTabs?: boolean;
 	/**
 	* Formatter configuration
 	*/
-	formatter: FormatterPackConfig | null;
+	formatter?: FormatterPackConfig;
 };
 type LspServerPackConfig = {
 	/**
@@ -604,51 +778,168 @@ type LspServerPackConfig = {
 	/**
 	* Arguments to pass to the command
 	*/
-	args: Array<string>;
+	args?: Array<string>;
 	/**
 	* Whether to auto-start the server when a matching file is opened
 	*/
-	autoStart: boolean | null;
+	autoStart?: boolean;
 	/**
 	* LSP initialization options
 	*/
-	initializationOptions: Record<string, unknown> | null;
+	initializationOptions: Record<string, unknown>;
 };
-type SpawnResult = {
+type PackageKind = "plugin" | "theme" | "theme-pack" | "language" | "bundle";
+type BundlePlugin = {
 	/**
-	* Complete stdout as string
+	* Plugin entry point file relative to package
 	*/
-	stdout: string;
+	entry: string;
+};
+type ThemeVariant = "dark" | "light";
+type PackageTheme = {
 	/**
-	* Complete stderr as string
+	* Theme JSON file path relative to package
 	*/
-	stderr: string;
+	file: string;
 	/**
-	* Process exit code (0 usually means success, -1 if killed)
+	* Display name for the theme
 	*/
-	exit_code: number;
+	name: string;
+	/**
+	* Theme variant (dark or light)
+	*/
+	variant?: ThemeVariant;
 };
-type TextPropertiesAtCursor = Array<Record<string, unknown>>;
-type TsHighlightSpan = {
-	start: number;
-	end: number;
-	color: [number, number, number];
-	bold: boolean;
-	italic: boolean;
+type GrammarPackConfig = {
+	/**
+	* Path to grammar file (.sublime-syntax recommended, or .tmLanguage)
+	*/
+	file: string;
+	/**
+	* File extensions this grammar handles (e.g., ["rs", "rust"])
+	*/
+	extensions?: Array<string>;
+	/**
+	* Regex pattern for shebang detection
+	*/
+	firstLine?: string;
 };
-type VirtualBufferResult = {
+type BundleLanguage = {
 	/**
-	* The created buffer ID
+	* Language identifier (e.g., 'elixir', 'heex')
 	*/
-	bufferId: number;
+	id: string;
 	/**
-	* The split ID (if created in a new split)
+	* Grammar configuration
 	*/
-	splitId: number | null;
+	grammar?: GrammarPackConfig;
+	/**
+	* Language configuration
+	*/
+	language?: LanguagePackConfig;
+	/**
+	* LSP server configuration
+	*/
+	lsp?: LspServerPackConfig;
+};
+type FreshPackageConfig = {
+	/**
+	* Minimum required Fresh version
+	*/
+	min_version?: string;
+	/**
+	* Minimum required plugin API version (integer, e.g. 2)
+	*/
+	min_api_version?: number;
+	/**
+	* Plugin entry point file (for plugins)
+	*/
+	entry?: string;
+	/**
+	* Plugin entry point file (alias for entry)
+	*/
+	main?: string;
+	/**
+	* Events that load the plugin: "onLanguage:<language>" when a file of
+	* the language is opened, "onCommand:<action>" when the action is run,
+	* "*" at startup. Without any, the plugin is loaded at startup.
+	*/
+	activation_events?: Array<string>;
+	/**
+	* Theme JSON file path (for single theme packages)
+	*/
+	theme?: string;
+	/**
+	* Theme definitions (for theme packs and bundles)
+	*/
+	themes?: Array<PackageTheme>;
+	/**
+	* JSON Schema for plugin configuration options
+	*/
+	config_schema?: Record<string, unknown>;
+	/**
+	* Grammar configuration (for language packs)
+	*/
+	grammar?: GrammarPackConfig;
+	/**
+	* Language configuration (for language packs)
+	*/
+	language?: LanguagePackConfig;
+	/**
+	* LSP server configuration (for language packs)
+	*/
+	lsp?: LspServerPackConfig;
+	/**
+	* Language definitions (for bundles)
+	*/
+	languages?: Array<BundleLanguage>;
+	/**
+	* Plugin definitions (for bundles)
+	*/
+	plugins?: Array<BundlePlugin>;
+};
+type PackageManifest = {
+	/**
+	* Package name (lowercase, hyphens allowed)
+	*/
+	name: string;
+	/**
+	* Semantic version (e.g., 1.0.0)
+	*/
+	version: string;
+	/**
+	* Short package description
+	*/
+	description: string;
+	/**
+	* Package type
+	*/
+	type: PackageKind;
+	/**
+	* Author name and optional email (e.g., 'Name <email>')
+	*/
+	author?: string;
+	/**
+	* SPDX license identifier
+	*/
+	license?: string;
+	/**
+	* Git repository URL
+	*/
+	repository?: string;
+	/**
+	* Search keywords
+	*/
+	keywords?: Array<string>;
+	/**
+	* Fresh-specific configuration
+	*/
+	fresh?: FreshPackageConfig;
+	/**
+	* Package dependencies (reserved for future use)
+	*/
+	dependencies?: { [key in string]? : string };
 };
-/**
-* Main editor API interface
-*/
 interface EditorAPI {
 	/**
 	* Get the plugin API version. Plugins can check this to verify
@@ -684,6 +975,17 @@ interface EditorAPI {
 	*/
 	unregisterCommand(name: string): boolean;
 	/**
+	* Bind keys to a command (a handler name or built-in action name)
+	* keys use mode binding syntax ("C-k C-g"); context is optional - a key
+	* context such as "normal" (the default) or "prompt", or a custom context
+	* set with setContext. Bindings are removed when the plugin unloads
+	*/
+	registerKeybinding(keys: string, command: string, context?: unknown): boolean;
+	/**
+	* Remove a key binding registered with registerKeybinding
+	*/
+	unregisterKeybinding(keys: string, context?: unknown): boolean;
+	/**
 	* Set a context (for keybinding conditions)
 	*/
 	setContext(name: string, active: boolean): boolean;
@@ -800,8 +1102,13 @@ interface EditorAPI {
 	closeBuffer(bufferId: number): boolean;
 	/**
 	* Subscribe to an editor event
+	* With `debounceMs`, the handler runs once the event has been quiet that long
+	* (per buffer); the `changes` of buffer_changed events in between are merged
+	* before_file_save handlers may return (or resolve to) a BeforeSaveResult
 	*/
-	on(eventName: string, handlerName: string): void;
+	on(eventName: string, handlerName: string, options?: {
+		debounceMs?: number;
+	}): void;
 	/**
 	* Unsubscribe from an event
 	*/
@@ -1008,6 +1315,14 @@ interface EditorAPI {
 	*/
 	clearConcealsInRange(bufferId: number, start: number, end: number): boolean;
 	/**
+	* Fold a byte range, showing `placeholder` in its place in every view mode
+	*/
+	addFold(bufferId: number, namespace: string, start: number, end: number, placeholder: string): boolean;
+	/**
+	* Remove all folds in a namespace
+	*/
+	clearFoldNamespace(bufferId: number, namespace: string): boolean;
+	/**
 	* Add a soft break point for marker-based line wrapping
 	*/
 	addSoftBreak(bufferId: number, namespace: string, position: number, indent: number): boolean;
@@ -1077,8 +1392,12 @@ interface EditorAPI {
 	prompt(label: string, initialValue: string): Promise<string | null>;
 	/**
 	* Start an interactive prompt
+	* With `multiSelect`, Tab marks suggestions and prompt_confirmed reports
+	* the marked values in `selected_values`
 	*/
-	startPrompt(label: string, promptType: string): boolean;
+	startPrompt(label: string, promptType: string, options?: {
+		multiSelect?: boolean;
+	}): boolean;
 	/**
 	* Start a prompt with initial value
 	*/
@@ -1151,6 +1470,11 @@ interface EditorAPI {
 	*/
 	clearLineIndicators(bufferId: number, namespace: string): boolean;
 	/**
+	* Replace this plugin's gutter signs in a buffer (an empty list clears them)
+	* Signs follow their line through edits and take theme keys for colors
+	*/
+	setGutterSigns(bufferId: number, signs: GutterSign[]): boolean;
+	/**
 	* Enable or disable line numbers for a buffer
 	*/
 	setLineNumbers(bufferId: number, enabled: boolean): boolean;
@@ -1195,6 +1519,24 @@ interface EditorAPI {
 	*/
 	showActionPopup(opts: ActionPopupOptions): boolean;
 	/**
+	* Show a read-only floating panel (replaces any visible panel with the same id)
+	* 
+	* Takes a typed FloatingPanelOptions struct - serde validates field names at runtime
+	*/
+	showFloatingPanel(opts: FloatingPanelOptions): boolean;
+	/**
+	* Hide a floating panel by id
+	*/
+	hideFloatingPanel(id: string): boolean;
+	/**
+	* Add a segment to the status bar, or update it if `id` is already registered
+	*/
+	registerStatusSegment(id: string, options: StatusSegmentOptions): boolean;
+	/**
+	* Remove a status bar segment added with registerStatusSegment
+	*/
+	removeStatusSegment(id: string): boolean;
+	/**
 	* Disable LSP for a specific language
 	*/
 	disableLspForLanguage(language: string): boolean;
@@ -1230,6 +1572,11 @@ interface EditorAPI {
 	*/
 	setVirtualBufferContent(bufferId: number, entriesArr: Record<string, unknown>[]): boolean;
 	/**
+	* Show an image file in place of a buffer's contents (null shows the
+	* contents again)
+	*/
+	setImagePreview(bufferId: number, path: string | null): boolean;
+	/**
 	* Get text properties at cursor position (returns JS array)
 	*/
 	getTextPropertiesAtCursor(bufferId: number): TextPropertiesAtCursor;
@@ -1250,6 +1597,46 @@ interface EditorAPI {
 	*/
 	delay(durationMs: number): Promise<void>;
 	/**
+	* Search the files of the working directory for a pattern, ripgrep style
+	* (async). Skips hidden, gitignored and binary files. Options: `regex`
+	* (default true), `caseSensitive` (default: only if the pattern has an
+	* uppercase letter), `wholeWord`, `maxResults` (default 100).
+	* Files are relative to the working directory; line and column are 1-indexed.
+	*/
+	grepProject(pattern: string, options?: {
+		regex?: boolean;
+		caseSensitive?: boolean;
+		wholeWord?: boolean;
+		maxResults?: number;
+	}): Promise<Array<{file: string, line: number, column: number, content: string}>>;
+	/**
+	* Call `callback` once after `delay_ms` milliseconds; returns a timer id for clearTimer
+	* Timers fire from the editor's main loop, so they run between frames
+	*/
+	setTimeout(callback: () => void, delayMs: number): number;
+	/**
+	* Call `callback` every `delay_ms` milliseconds until cleared; returns a timer id
+	*/
+	setInterval(callback: () => void, delayMs: number): number;
+	/**
+	* Cancel a timer started with setTimeout or setInterval
+	*/
+	clearTimer(timerId: number): boolean;
+	/**
+	* Offer completions from `callback` in the completion popup; returns a provider id
+	* An empty `languages` list means every language. Items are labelled with
+	* `options.label` (default: the plugin name) and ranked by `options.priority`
+	* (language servers rank 0, higher goes first)
+	*/
+	registerCompletionProvider(languages: string[], triggerChars: string[], callback: (context: CompletionContext) => CompletionItem[] | Promise<CompletionItem[]>, options?: {
+		label?: string;
+		priority?: number;
+	}): number;
+	/**
+	* Remove a completion provider registered with registerCompletionProvider
+	*/
+	unregisterCompletionProvider(providerId: number): boolean;
+	/**
 	* Send LSP request (async, returns request_id)
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
@@ -1302,4 +1689,9 @@ interface EditorAPI {
 		path: string;
 		enabled: boolean;
 	}>>;
+	/**
+	* Enable or disable a plugin by name and save it to the user config (async)
+	* Disabling unloads the plugin, enabling loads it again
+	*/
+	setPluginEnabled(name: string, enabled: boolean): Promise<boolean>;
 }
@@ -3,7 +3,9 @@
 /**
  * Live Grep Plugin
  *
- * Project-wide search with ripgrep and live preview.
+ * Project-wide search with live preview.
+ * Uses the editor's built-in project search, and ripgrep where that is not
+ * available (remote sessions).
  * Uses the Finder abstraction for unified search UX.
  *
  * - Type to search across all files
@@ -15,7 +17,7 @@ import { Finder, parseGrepOutput } from "./lib/finder.ts";
 
 const editor = getEditor();
 
-// Result type from ripgrep
+// A matching line
 interface GrepMatch {
   file: string;
   line: number;
@@ -42,6 +44,15 @@ const finder = new Finder<GrepMatch>(editor, {
   maxResults: 100,
 });
 
+// Search with the built-in project search, falling back to ripgrep
+async function searchProject(query: string): Promise<GrepMatch[]> {
+  try {
+    return await editor.grepProject(query, { maxResults: 100 });
+  } catch (_e) {
+    return searchWithRipgrep(query);
+  }
+}
+
 // Search function that parses ripgrep output
 async function searchWithRipgrep(query: string): Promise<GrepMatch[]> {
   const cwd = editor.getCwd();
@@ -80,7 +91,7 @@ globalThis.start_live_grep = function (): void {
     title: editor.t("prompt.live_grep"),
     source: {
       mode: "search",
-      search: searchWithRipgrep,
+      search: searchProject,
       debounceMs: 150,
       minQueryLength: 2,
     },
@@ -94,5 +105,6 @@ editor.registerCommand(
   "start_live_grep",
   null
 );
+editor.registerKeybinding("C-S-f", "start_live_grep");
 
 editor.debug("Live Grep plugin loaded (using Finder abstraction)");
@@ -52,118 +52,9 @@ const DEFAULT_REGISTRY = "https://github.com/sinelaw/fresh-plugins-registry";
 // Types
 // =============================================================================
 
-// TODO: Generate PackageManifest from the JSON schema (or vice versa) to ensure
-// pkg.ts types stay in sync with package.schema.json. Consider using json-schema-to-typescript
-// or ts-json-schema-generator to automate this.
-// Related files:
-//   - docs/internal/package-index-template/schemas/package.schema.json
-//   - crates/fresh-editor/plugins/schemas/package.schema.json
-
-// Bundle language definition (used in fresh.languages[])
-interface BundleLanguage {
-  /** Language identifier (e.g., 'elixir', 'heex') */
-  id: string;
-  /** Grammar configuration */
-  grammar?: {
-    file: string;
-    extensions?: string[];
-    firstLine?: string;
-  };
-  /** Language configuration */
-  language?: {
-    commentPrefix?: string;
-    blockCommentStart?: string;
-    blockCommentEnd?: string;
-    useTabs?: boolean;
-    tabSize?: number;
-    autoIndent?: boolean;
-    showWhitespaceTabs?: boolean;
-    formatter?: {
-      command: string;
-      args?: string[];
-    };
-  };
-  /** LSP server configuration */
-  lsp?: {
-    command: string;
-    args?: string[];
-    autoStart?: boolean;
-    initializationOptions?: Record<string, unknown>;
-  };
-}
-
-// Bundle plugin definition (used in fresh.plugins[])
-interface BundlePlugin {
-  /** Plugin entry point file relative to package */
-  entry: string;
-}
-
-// Bundle theme definition (used in fresh.themes[])
-interface BundleTheme {
-  /** Theme JSON file path relative to package */
-  file: string;
-  /** Display name for the theme */
-  name: string;
-  /** Theme variant (dark or light) */
-  variant?: "dark" | "light";
-}
-
-interface PackageManifest {
-  name: string;
-  version: string;
-  description: string;
-  type: "plugin" | "theme" | "theme-pack" | "language" | "bundle";
-  author?: string;
-  license?: string;
-  repository?: string;
-  fresh?: {
-    min_version?: string;
-    min_api_version?: number;
-    entry?: string;
-    themes?: Array<{
-      file: string;
-      name: string;
-      variant?: "dark" | "light";
-    }>;
-    config_schema?: Record<string, unknown>;
-
-    // Language pack fields
-    grammar?: {
-      /** Path to grammar file relative to package */
-      file: string;
-      /** File extensions (e.g., ["rs", "rust"]) */
-      extensions?: string[];
-      /** Shebang pattern for detection */
-      firstLine?: string;
-    };
-    language?: {
-      commentPrefix?: string;
-      blockCommentStart?: string;
-      blockCommentEnd?: string;
-      useTabs?: boolean;
-      tabSize?: number;
-      autoIndent?: boolean;
-      showWhitespaceTabs?: boolean;
-      formatter?: {
-        command: string;
-        args?: string[];
-      };
-    };
-    lsp?: {
-      command: string;
-      args?: string[];
-      autoStart?: boolean;
-      initializationOptions?: Record<string, unknown>;
-    };
-
-    // Bundle fields
-    /** Languages included in this bundle */
-    languages?: BundleLanguage[];
-    /** Plugins included in this bundle */
-    plugins?: BundlePlugin[];
-  };
-  keywords?: string[];
-}
+// PackageManifest and the types it references (FreshPackageConfig,
+// BundleLanguage, GrammarPackConfig, ...) are declared in fresh.d.ts, generated
+// from the same Rust structs as plugins/schemas/package.schema.json.
 
 interface RegistryEntry {
   description: string;
@@ -345,6 +236,22 @@ function extractPackageName(url: string): string {
   return parsePackageUrl(url).name;
 }
 
+/**
+ * Names of the plugins a package provides.
+ * Like any plugin directory, each top-level .ts/.js file is a plugin named
+ * after the file; these are the names listPlugins and the config use.
+ */
+function packagePluginNames(pkg: InstalledPackage): string[] {
+  if (pkg.type !== "plugin") return [];
+  try {
+    return editor.readDir(pkg.path)
+      .filter(e => e.is_file && /\.(ts|js)$/.test(e.name) && !e.name.includes(".i18n."))
+      .map(e => e.name.replace(/\.(ts|js)$/, ""));
+  } catch (e) {
+    return [];
+  }
+}
+
 /**
  * Get registry sources from config
  */
@@ -799,16 +706,21 @@ async function installPackage(
 
   editor.setStatus(`Installing ${packageName}...`);
 
+  let installed: boolean;
   if (parsed.isLocal) {
     // Local path installation: copy directly
-    return await installFromLocalPath(parsed, packageName);
+    installed = await installFromLocalPath(parsed, packageName);
   } else if (parsed.subpath) {
     // Remote monorepo installation: clone to temp, copy subdirectory
-    return await installFromMonorepo(parsed, packageName, version);
+    installed = await installFromMonorepo(parsed, packageName, version);
   } else {
     // Standard git installation: clone directly
-    return await installFromRepo(parsed.repoUrl, packageName, version);
+    installed = await installFromRepo(parsed.repoUrl, packageName, version);
+  }
+  if (installed) {
+    await writeLockfile();
   }
+  return installed;
 }
 
 /**
@@ -1361,6 +1273,8 @@ async function updatePackage(pkg: InstalledPackage): Promise<boolean> {
       } else if (pkg.type === "theme") {
         editor.reloadThemes();
       }
+      pkgState.updates.delete(pkg.name);
+      await writeLockfile();
       editor.setStatus(`Updated and reloaded ${pkg.name}`);
     }
     return true;
@@ -1451,6 +1365,7 @@ async function reinstallPackage(pkg: InstalledPackage): Promise<boolean> {
     editor.setStatus(`Reinstalled ${pkg.name}`);
   }
 
+  await writeLockfile();
   return true;
 }
 
@@ -1481,6 +1396,7 @@ async function removePackage(pkg: InstalledPackage): Promise<boolean> {
     if (pkg.type === "theme") {
       editor.reloadThemes();
     }
+    await writeLockfile();
     editor.setStatus(`Removed ${pkg.name}`);
     return true;
   } else {
@@ -1528,19 +1444,90 @@ async function updateAllPackages(): Promise<void> {
     }
   }
 
+  pkgState.updates.clear();
+  await writeLockfile();
   editor.setStatus(`Update complete: ${updated} updated, ${all.length - updated - failed} unchanged, ${failed} failed`);
 }
 
+/**
+ * Fetch a package and count the commits it is behind its upstream.
+ * Returns 0 for packages installed from a local path or a monorepo.
+ */
+async function commitsBehind(pkg: InstalledPackage): Promise<number> {
+  if (pkg.localSource || !editor.fileExists(editor.pathJoin(pkg.path, ".git"))) {
+    return 0;
+  }
+  await gitCommand(["-C", `${pkg.path}`, "fetch", "--quiet"]);
+  const result = await gitCommand([
+    "-C", `${pkg.path}`, "rev-list", "--count", "HEAD..@{upstream}"
+  ]);
+  const behind = parseInt(result.stdout.trim(), 10);
+  return isNaN(behind) ? 0 : behind;
+}
+
+/**
+ * Check every installed plugin and theme for updates
+ */
+async function checkForUpdates(): Promise<void> {
+  const all = [...getInstalledPackages("plugin"), ...getInstalledPackages("theme")];
+  for (const pkg of all) {
+    const behind = await commitsBehind(pkg);
+    if (behind > 0) {
+      pkgState.updates.set(pkg.name, behind);
+    } else {
+      pkgState.updates.delete(pkg.name);
+    }
+  }
+}
+
+/**
+ * Enable or disable every plugin of a package.
+ * The choice is saved to the user config, so it survives restarts.
+ */
+async function setPackageEnabled(pkg: InstalledPackage, enabled: boolean): Promise<boolean> {
+  const names = packagePluginNames(pkg);
+  try {
+    for (const name of names) {
+      await editor.setPluginEnabled(name, enabled);
+    }
+  } catch (e) {
+    editor.setStatus(`Failed to ${enabled ? "enable" : "disable"} ${pkg.name}: ${e}`);
+    return false;
+  }
+  if (enabled) {
+    pkgState.disabled.delete(pkg.name);
+  } else {
+    pkgState.disabled.add(pkg.name);
+  }
+  editor.setStatus(`${enabled ? "Enabled" : "Disabled"} ${pkg.name}`);
+  return true;
+}
+
+/**
+ * Packages whose plugins are all disabled in the config
+ */
+function loadDisabledPackages(): Set<string> {
+  const config = editor.getConfig() as Record<string, unknown>;
+  const plugins = (config?.plugins ?? {}) as Record<string, { enabled?: boolean }>;
+  const disabled = new Set<string>();
+  for (const pkg of getInstalledPackages("plugin")) {
+    const names = packagePluginNames(pkg);
+    if (names.length > 0 && names.every(name => plugins[name]?.enabled === false)) {
+      disabled.add(pkg.name);
+    }
+  }
+  return disabled;
+}
+
 // =============================================================================
 // Lockfile Operations
 // =============================================================================
 
 /**
- * Generate lockfile from current state
+ * Write the lockfile from the installed plugins and themes.
+ * Called after every install, update and removal so it stays current.
  */
-async function generateLockfile(): Promise<void> {
-  editor.setStatus("Generating lockfile...");
-
+async function writeLockfile(): Promise<number | null> {
   const plugins = getInstalledPackages("plugin");
   const themes = getInstalledPackages("theme");
   const all = [...plugins, ...themes];
@@ -1552,9 +1539,12 @@ async function generateLockfile(): Promise<void> {
   };
 
   for (const pkg of all) {
-    // Get current commit
-    const commitResult = await gitCommand(["-C", `${pkg.path}`, "rev-parse", "HEAD"]);
-    const commit = commitResult.stdout.trim();
+    // Get current commit (monorepo and local installs aren't checkouts)
+    let commit = "";
+    if (editor.fileExists(editor.pathJoin(pkg.path, ".git"))) {
+      const commitResult = await gitCommand(["-C", `${pkg.path}`, "rev-parse", "HEAD"]);
+      commit = commitResult.stdout.trim();
+    }
 
     lockfile.packages[pkg.name] = {
       source: pkg.source,
@@ -1563,8 +1553,18 @@ async function generateLockfile(): Promise<void> {
     };
   }
 
-  if (await writeJsonFile(LOCKFILE_PATH, lockfile)) {
-    editor.setStatus(`Lockfile generated with ${all.length} packages`);
+  return await writeJsonFile(LOCKFILE_PATH, lockfile) ? all.length : null;
+}
+
+/**
+ * Generate lockfile from current state
+ */
+async function generateLockfile(): Promise<void> {
+  editor.setStatus("Generating lockfile...");
+
+  const count = await writeLockfile();
+  if (count !== null) {
+    editor.setStatus(`Lockfile generated with ${count} packages`);
   } else {
     editor.setStatus("Failed to write lockfile");
   }
@@ -1631,6 +1631,10 @@ interface PackageListItem {
   version: string;
   installed: boolean;
   updateAvailable: boolean;
+  /** Commits behind upstream, for installed packages with updates */
+  behind?: number;
+  /** Whether the package's plugins are enabled, for installed plugin packages */
+  enabled?: boolean;
   latestVersion?: string;
   author?: string;
   license?: string;
@@ -1664,6 +1668,10 @@ interface PkgManagerState {
   selectedIndex: number;
   focus: FocusTarget;  // What element has Tab focus
   isLoading: boolean;
+  /** Installed packages with updates, and how many commits they are behind */
+  updates: Map<string, number>;
+  /** Installed packages whose plugins are disabled */
+  disabled: Set<string>;
 }
 
 const pkgState: PkgManagerState = {
@@ -1677,6 +1685,8 @@ const pkgState: PkgManagerState = {
   selectedIndex: 0,
   focus: { type: "list" },
   isLoading: false,
+  updates: new Map(),
+  disabled: new Set(),
 };
 
 // Theme-aware color configuration
@@ -1794,13 +1804,16 @@ function buildPackageList(): PackageListItem[] {
 
   for (const pkg of [...installedPlugins, ...installedThemes, ...installedLanguages, ...installedBundles]) {
     installedMap.set(pkg.name, pkg);
+    const behind = pkgState.updates.get(pkg.name);
     items.push({
       type: "installed",
       name: pkg.name,
       description: pkg.manifest?.description || "No description",
       version: pkg.version,
       installed: true,
-      updateAvailable: false, // TODO: Check for updates
+      updateAvailable: behind !== undefined,
+      behind,
+      enabled: packagePluginNames(pkg).length > 0 ? !pkgState.disabled.has(pkg.name) : undefined,
       author: pkg.manifest?.author,
       license: pkg.manifest?.license,
       repository: pkg.source,
@@ -1967,10 +1980,17 @@ function getActionButtons(): string[] {
   const item = items[pkgState.selectedIndex];
 
   if (item.installed) {
+    const actions: string[] = [];
     if (item.installedPackage?.localSource) {
-      return ["Reinstall", "Uninstall"];
+      actions.push("Reinstall");
+    } else if (item.updateAvailable) {
+      actions.push("Update");
     }
-    return item.updateAvailable ? ["Update", "Uninstall"] : ["Uninstall"];
+    actions.push("Uninstall");
+    if (item.enabled !== undefined) {
+      actions.push(item.enabled ? "Disable" : "Enable");
+    }
+    return actions;
   } else {
     return ["Install"];
   }
@@ -2101,7 +2121,7 @@ function buildListViewEntries(): TextPropertyEntry[] {
       const isSelected = idx === pkgState.selectedIndex;
       const listFocused = pkgState.focus.type === "list";
       const prefix = isSelected && listFocused ? "▸" : " ";
-      const status = item.updateAvailable ? "↑" : "✓";
+      const status = item.updateAvailable ? "↑" : item.enabled === false ? "-" : "✓";
       const ver = item.version.length > 7 ? item.version.slice(0, 6) + "…" : item.version;
       const name = item.name.length > 18 ? item.name.slice(0, 17) + "…" : item.name;
       const line = `${prefix} ${name.padEnd(18)} ${ver.padEnd(7)} ${status}`;
@@ -2155,6 +2175,13 @@ function buildListViewEntries(): TextPropertyEntry[] {
     if (metaLine.length > DETAIL_WIDTH - 2) metaLine = metaLine.slice(0, DETAIL_WIDTH - 5) + "...";
     rightLines.push({ text: metaLine, type: "detail-meta" });
 
+    if (selectedItem.updateAvailable) {
+      rightLines.push({ text: `Update available (${selectedItem.behind} commits behind)`, type: "detail-meta" });
+    }
+    if (selectedItem.enabled === false) {
+      rightLines.push({ text: "Disabled", type: "detail-meta" });
+    }
+
     rightLines.push({ text: "", type: "blank" });
 
     // Description (wrapped)
@@ -2455,6 +2482,7 @@ async function openPackageManager(): Promise<void> {
   pkgState.searchQuery = "";
   pkgState.selectedIndex = 0;
   pkgState.focus = { type: "list" };
+  pkgState.disabled = loadDisabledPackages();
 
   // Build package list immediately with installed packages and cached registry
   // This allows viewing/managing installed packages without waiting for network
@@ -2482,14 +2510,18 @@ async function openPackageManager(): Promise<void> {
   // Apply initial highlighting
   applyPkgManagerHighlighting();
 
-  // Sync registry in background and update view when done
-  // User can still interact with installed packages during sync
-  syncRegistry().then(() => {
+  // Sync registry and check for updates in background, updating the view
+  // after each. User can still interact with installed packages meanwhile
+  const refresh = () => {
     if (pkgState.isOpen) {
       pkgState.items = buildPackageList();
       updatePkgManagerView();
     }
-  });
+  };
+  syncRegistry().then(() => {
+    refresh();
+    return checkForUpdates();
+  }).then(refresh);
 }
 
 /**
@@ -2621,6 +2653,7 @@ globalThis.pkg_activate = async function(): Promise<void> {
   // Handle sync button
   if (focus.type === "sync") {
     await syncRegistry();
+    await checkForUpdates();
     pkgState.items = buildPackageList();
     updatePkgManagerView();
     return;
@@ -2666,6 +2699,10 @@ globalThis.pkg_activate = async function(): Promise<void> {
       await updatePackage(item.installedPackage);
       pkgState.items = buildPackageList();
       updatePkgManagerView();
+    } else if ((actionName === "Enable" || actionName === "Disable") && item.installedPackage) {
+      await setPackageEnabled(item.installedPackage, actionName === "Enable");
+      pkgState.items = buildPackageList();
+      updatePkgManagerView();
     } else if (actionName === "Uninstall" && item.installedPackage) {
       await removePackage(item.installedPackage);
       pkgState.items = buildPackageList();
@@ -2818,6 +2855,48 @@ globalThis.pkg_install_theme = async function(): Promise<void> {
   }
 };
 
+/**
+ * Install a plugin by registry name or git URL
+ */
+globalThis.pkg_plugins_install = async function(): Promise<void> {
+  if (!isRegistrySynced()) {
+    await syncRegistry();
+  }
+  const registry = loadRegistry("plugins");
+  editor.startPrompt(editor.t("prompt.plugins_install"), "pkg-plugins-install");
+  editor.setPromptSuggestions(
+    Object.entries(registry.packages).map(([name, entry]) => ({
+      text: name,
+      description: entry.description,
+      value: name,
+    }))
+  );
+};
+
+globalThis.onPkgPluginsInstallConfirmed = async function(args: {
+  prompt_type: string;
+  selected_index: number | null;
+  input: string;
+}): Promise<boolean> {
+  if (args.prompt_type !== "pkg-plugins-install") return true;
+
+  const input = args.input.trim();
+  if (!input) {
+    editor.setStatus("No plugin name or URL provided");
+    return true;
+  }
+  const entry = loadRegistry("plugins").packages[input];
+  if (entry) {
+    await installPackage(entry.repository, input, "plugin");
+  } else {
+    await installPackage(input);
+  }
+
+  return true;
+};
+
+editor.on("prompt_confirmed", "onPkgPluginsInstallConfirmed");
+
 /**
  * Install from git URL or local path
  */
@@ -2957,17 +3036,12 @@ globalThis.pkg_outdated = async function(): Promise<void> {
   const outdated: Array<{ pkg: InstalledPackage; behind: number }> = [];
 
   for (const pkg of all) {
-    // Fetch latest
-    await gitCommand(["-C", `${pkg.path}`, "fetch"]);
-
-    // Check how many commits behind
-    const result = await gitCommand([
-      "-C", `${pkg.path}`, "rev-list", "--count", "HEAD..origin/HEAD"
-    ]);
-
-    const behind = parseInt(result.stdout.trim(), 10);
+    const behind = await commitsBehind(pkg);
     if (behind > 0) {
+      pkgState.updates.set(pkg.name, behind);
       outdated.push({ pkg, behind });
+    } else {
+      pkgState.updates.delete(pkg.name);
     }
   }
 
@@ -3019,9 +3093,15 @@ globalThis.pkg_install_lock = async function(): Promise<void> {
 // Main entry point - opens the package manager UI
 editor.registerCommand("%cmd.list", "%cmd.list_desc", "pkg_list", null);
 
+// Install a plugin by registry name or git URL
+editor.registerCommand("%cmd.plugins_install", "%cmd.plugins_install_desc", "pkg_plugins_install", null);
+
 // Install from URL - for packages not in registry
 editor.registerCommand("%cmd.install_url", "%cmd.install_url_desc", "pkg_install_url", null);
 
+// Restore the package versions recorded in fresh.lock
+editor.registerCommand("%cmd.install_lock", "%cmd.install_lock_desc", "pkg_install_lock", null);
+
 // Note: Other commands (install_plugin, install_theme, update, remove, sync, etc.)
 // are available via the package manager UI and don't need global command palette entries.
 
@@ -0,0 +1,215 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * Task Runner Plugin
+ *
+ * Detects project tasks and runs them in an integrated terminal split.
+ * Uses the Finder abstraction with filter mode for the picker.
+ *
+ * Task sources (in the working directory):
+ * - Makefile targets
+ * - package.json scripts (run with npm, pnpm, yarn or bun, by lockfile)
+ * - Cargo aliases from .cargo/config.toml
+ * - justfile recipes
+ *
+ * The last task run is remembered; "Rerun Last Task" (Ctrl+Shift+B) runs it again.
+ */
+
+import { Finder } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+interface Task {
+  source: "make" | "npm" | "cargo" | "just";
+  name: string;
+  command: string;
+}
+
+// State
+let lastTask: Task | null = null;
+let taskTerminalId: number | null = null;
+
+function joinPath(dir: string, name: string): string {
+  return dir.endsWith("/") ? dir + name : `${dir}/${name}`;
+}
+
+// Read the first of `names` that exists in `dir`
+function readFirst(dir: string, names: string[]): string | null {
+  for (const name of names) {
+    const path = joinPath(dir, name);
+    if (editor.fileExists(path)) {
+      return editor.readFile(path);
+    }
+  }
+  return null;
+}
+
+function unique(names: string[]): string[] {
+  return names.filter((name, i) => names.indexOf(name) === i);
+}
+
+// Makefile targets, skipping special (.PHONY) and pattern (%.o) targets
+function parseMakefile(content: string): string[] {
+  const targets: string[] = [];
+  for (const line of content.split("\n")) {
+    const match = line.match(/^([^\s#:=][^:=]*?)\s*::?(?![:=])/);
+    if (!match) continue;
+    for (const target of match[1].split(/\s+/)) {
+      if (target && !target.startsWith(".") && !target.includes("%") && !target.includes("$")) {
+        targets.push(target);
+      }
+    }
+  }
+  return unique(targets);
+}
+
+// justfile recipes, skipping private ones (leading underscore) and settings
+function parseJustfile(content: string): string[] {
+  const recipes: string[] = [];
+  for (const line of content.split("\n")) {
+    const match = line.match(/^@?([A-Za-z][\w-]*)(?:\s+[^:]*)?:(?!=)/);
+    if (match) {
+      recipes.push(match[1]);
+    }
+  }
+  return unique(recipes);
+}
+
+// Aliases from the [alias] table of a Cargo config
+function parseCargoAliases(content: string): string[] {
+  const aliases: string[] = [];
+  let inAlias = false;
+  for (const raw of content.split("\n")) {
+    const line = raw.trim();
+    if (line.startsWith("[")) {
+      inAlias = line === "[alias]";
+      continue;
+    }
+    const match = inAlias ? line.match(/^([\w-]+)\s*=/) : null;
+    if (match) {
+      aliases.push(match[1]);
+    }
+  }
+  return aliases;
+}
+
+// Package manager for package.json scripts, picked by lockfile
+function scriptRunner(cwd: string): string {
+  if (editor.fileExists(joinPath(cwd, "pnpm-lock.yaml"))) return "pnpm run";
+  if (editor.fileExists(joinPath(cwd, "yarn.lock"))) return "yarn run";
+  if (editor.fileExists(joinPath(cwd, "bun.lockb")) || editor.fileExists(joinPath(cwd, "bun.lock"))) {
+    return "bun run";
+  }
+  return "npm run";
+}
+
+// Collect tasks from every source in the working directory
+function detectTasks(): Task[] {
+  const cwd = editor.getCwd();
+  const tasks: Task[] = [];
+
+  const makefile = readFirst(cwd, ["GNUmakefile", "makefile", "Makefile"]);
+  if (makefile !== null) {
+    for (const name of parseMakefile(makefile)) {
+      tasks.push({ source: "make", name, command: `make ${name}` });
+    }
+  }
+
+  const packageJson = readFirst(cwd, ["package.json"]);
+  if (packageJson !== null) {
+    try {
+      const scripts = (JSON.parse(packageJson) as { scripts?: Record<string, unknown> }).scripts ?? {};
+      const runner = scriptRunner(cwd);
+      for (const name of Object.keys(scripts)) {
+        tasks.push({ source: "npm", name, command: `${runner} ${name}` });
+      }
+    } catch (e) {
+      editor.debug(`[tasks] failed to parse package.json: ${e}`);
+    }
+  }
+
+  const cargoConfig = readFirst(cwd, [".cargo/config.toml", ".cargo/config"]);
+  if (cargoConfig !== null) {
+    for (const name of parseCargoAliases(cargoConfig)) {
+      tasks.push({ source: "cargo", name, command: `cargo ${name}` });
+    }
+  }
+
+  const justfile = readFirst(cwd, ["justfile", "Justfile", ".justfile"]);
+  if (justfile !== null) {
+    for (const name of parseJustfile(justfile)) {
+      tasks.push({ source: "just", name, command: `just ${name}` });
+    }
+  }
+
+  return tasks;
+}
+
+// Run a task in the task terminal, replacing the previous one
+async function runTask(task: Task): Promise<void> {
+  lastTask = task;
+
+  if (taskTerminalId !== null) {
+    editor.closeTerminal(taskTerminalId);
+    taskTerminalId = null;
+  }
+
+  try {
+    const terminal = await editor.createTerminal({
+      cwd: editor.getCwd(),
+      direction: "horizontal",
+      ratio: 0.7,
+      focus: true,
+    });
+    taskTerminalId = terminal.terminalId;
+    editor.sendTerminalInput(terminal.terminalId, `${task.command}\n`);
+    editor.setStatus(editor.t("status.running", { command: task.command }));
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed", { error: String(e) }));
+  }
+}
+
+const finder = new Finder<Task>(editor, {
+  id: "tasks",
+  format: (task) => ({
+    label: `${task.source}: ${task.name}`,
+    description: task.command,
+  }),
+  preview: false,
+  maxResults: 200,
+  onSelect: (task) => {
+    runTask(task);
+  },
+});
+
+// Commands
+globalThis.run_task = function (): void {
+  const tasks = detectTasks();
+  if (tasks.length === 0) {
+    editor.setStatus(editor.t("status.no_tasks"));
+    return;
+  }
+
+  finder.prompt({
+    title: editor.t("prompt.run_task"),
+    source: {
+      mode: "filter",
+      load: async () => tasks,
+    },
+  });
+};
+
+globalThis.rerun_last_task = function (): void {
+  if (lastTask === null) {
+    globalThis.run_task();
+    return;
+  }
+  runTask(lastTask);
+};
+
+// Command Registration
+editor.registerCommand("%cmd.run_task", "%cmd.run_task_desc", "run_task", null);
+editor.registerCommand("%cmd.rerun_last_task", "%cmd.rerun_last_task_desc", "rerun_last_task", null);
+editor.registerKeybinding("C-S-b", "rerun_last_task");
+
+editor.debug("Task Runner plugin loaded");
@@ -0,0 +1,432 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * Test Runner Plugin
+ *
+ * Finds tests in open buffers, marks them in the gutter, runs a single test or
+ * a whole file in the background and shows the outcome inline and in a results
+ * panel grouped by file.
+ *
+ * Supported frameworks:
+ * - Rust: #[test] / #[tokio::test] functions, run with cargo test
+ * - Python: test_* functions, run with pytest
+ * - Go: TestXxx functions, run with go test
+ * - JavaScript/TypeScript: it(...) / test(...) blocks, run with vitest or jest
+ */
+
+import { Finder, createLiveProvider, type FinderProvider } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+const NAMESPACE = "test-runner";
+const VTEXT_PREFIX = "test-runner:";
+const PRIORITY = 8; // Above git gutter, below diagnostics
+
+type Status = "pending" | "running" | "passed" | "failed" | "skipped";
+
+const STATUS_STYLE: Record<Status, { symbol: string; color: [number, number, number] }> = {
+  pending: { symbol: "▶", color: [140, 140, 140] },
+  running: { symbol: "◌", color: [241, 250, 140] },
+  passed: { symbol: "✓", color: [80, 250, 123] },
+  failed: { symbol: "✗", color: [255, 85, 85] },
+  skipped: { symbol: "○", color: [140, 140, 140] },
+};
+
+type Framework = "cargo" | "pytest" | "go" | "jest" | "vitest";
+
+interface TestCase {
+  file: string;
+  line: number; // 1-indexed
+  lineEnd: number; // byte offset of the end of the line
+  name: string;
+}
+
+interface TestResult extends TestCase {
+  status: Status;
+  message: string;
+}
+
+// State
+const results = new Map<string, TestResult>(); // keyed by file::name
+const bufferTests = new Map<number, TestCase[]>();
+let running = false;
+
+function resultKey(file: string, name: string): string {
+  return `${file}::${name}`;
+}
+
+function dirname(path: string): string {
+  const i = path.lastIndexOf("/");
+  return i > 0 ? path.substring(0, i) : "/";
+}
+
+// Walk up from `dir` to the first directory containing `marker`
+function findUp(dir: string, marker: string): string | null {
+  let current = dir;
+  for (;;) {
+    if (editor.fileExists(`${current}/${marker}`)) return current;
+    const parent = dirname(current);
+    if (parent === current) return null;
+    current = parent;
+  }
+}
+
+function frameworkFor(path: string): Framework | null {
+  if (path.endsWith(".rs")) return "cargo";
+  if (path.endsWith(".py")) return "pytest";
+  if (path.endsWith("_test.go")) return "go";
+  if (/\.(test|spec)\.[cm]?[jt]sx?$/.test(path)) {
+    const root = findUp(dirname(path), "package.json");
+    const pkg = root ? editor.readFile(`${root}/package.json`) : null;
+    return pkg !== null && pkg.includes('"vitest"') ? "vitest" : "jest";
+  }
+  return null;
+}
+
+// Find test definitions by language-specific patterns
+function detectTests(file: string, text: string): TestCase[] {
+  const framework = frameworkFor(file);
+  if (!framework) return [];
+
+  const lines = text.split("\n");
+  const lineStarts: number[] = [];
+  let offset = 0;
+  for (const line of lines) {
+    lineStarts.push(offset);
+    offset += editor.utf8ByteLength(line) + 1;
+  }
+
+  const tests: TestCase[] = [];
+  for (let i = 0; i < lines.length; i++) {
+    const line = lines[i];
+    let name: string | null = null;
+
+    if (framework === "cargo") {
+      if (!/^\s*#\[(?:[\w:]+::)?test\b/.test(line)) continue;
+      // The fn follows the attribute, possibly after more attributes
+      for (let j = i + 1; j < Math.min(i + 6, lines.length); j++) {
+        const fn = lines[j].match(/^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(\w+)/);
+        if (fn) {
+          name = fn[1];
+          i = j;
+          break;
+        }
+      }
+    } else if (framework === "pytest") {
+      name = line.match(/^\s*(?:async\s+)?def\s+(test\w*)\s*\(/)?.[1] ?? null;
+    } else if (framework === "go") {
+      name = line.match(/^func\s+(Test\w+)\s*\(\s*\w+\s+\*testing\.T\s*\)/)?.[1] ?? null;
+    } else {
+      name = line.match(/^\s*(?:it|test)(?:\.only)?\s*\(\s*(["'`])(.*?)\1/)?.[2] ?? null;
+    }
+
+    if (name) {
+      tests.push({ file, line: i + 1, lineEnd: lineStarts[i] + editor.utf8ByteLength(lines[i]), name });
+    }
+  }
+  return tests;
+}
+
+// Command line for running `names` from `file` (all tests in the file when empty)
+function buildCommand(framework: Framework, file: string, names: string[]): { cmd: string; args: string[]; cwd: string } {
+  const dir = dirname(file);
+  switch (framework) {
+    case "cargo": {
+      const cwd = findUp(dir, "Cargo.toml") ?? dir;
+      return { cmd: "cargo", args: ["test", "--", ...names], cwd };
+    }
+    case "pytest": {
+      const args = ["-m", "pytest", "-v", "--no-header", file];
+      if (names.length > 0) args.push("-k", names.join(" or "));
+      return { cmd: "python3", args, cwd: editor.getCwd() };
+    }
+    case "go": {
+      const args = ["test", "-v", "."];
+      if (names.length > 0) args.push("-run", `^(${names.join("|")})$`);
+      return { cmd: "go", args, cwd: dir };
+    }
+    case "jest":
+    case "vitest": {
+      const cwd = findUp(dir, "package.json") ?? dir;
+      const args = framework === "vitest"
+        ? ["vitest", "run", "--reporter=json", file]
+        : ["jest", "--json", file];
+      if (names.length === 1) args.push("-t", names[0]);
+      return { cmd: "npx", args, cwd };
+    }
+  }
+}
+
+// Parse runner output into name -> status (and failure message where available)
+function parseOutput(framework: Framework, output: string): Map<string, { status: Status; message: string }> {
+  const parsed = new Map<string, { status: Status; message: string }>();
+
+  if (framework === "cargo") {
+    for (const m of output.matchAll(/^test (\S+) \.\.\. (ok|FAILED|ignored)/gm)) {
+      const name = m[1].split("::").pop()!;
+      const status: Status = m[2] === "ok" ? "passed" : m[2] === "FAILED" ? "failed" : "skipped";
+      parsed.set(name, { status, message: "" });
+    }
+    // Panic messages from the failures section
+    for (const m of output.matchAll(/^---- (\S+) stdout ----\n(?:.*\n)*?.*panicked at [^\n]*\n([^\n]*)/gm)) {
+      const entry = parsed.get(m[1].split("::").pop()!);
+      if (entry) entry.message = m[2].trim();
+    }
+  } else if (framework === "pytest") {
+    for (const m of output.matchAll(/^\S+::(?:\w+::)*(\w+)(?:\[[^\]]*\])? (PASSED|FAILED|SKIPPED|ERROR)/gm)) {
+      const status: Status = m[2] === "PASSED" ? "passed" : m[2] === "SKIPPED" ? "skipped" : "failed";
+      parsed.set(m[1], { status, message: "" });
+    }
+    for (const m of output.matchAll(/^FAILED \S+::(\w+)(?:\[[^\]]*\])? - (.*)$/gm)) {
+      const entry = parsed.get(m[1]);
+      if (entry) entry.message = m[2].trim();
+    }
+  } else if (framework === "go") {
+    for (const m of output.matchAll(/^\s*--- (PASS|FAIL|SKIP): (\w+)/gm)) {
+      const status: Status = m[1] === "PASS" ? "passed" : m[1] === "FAIL" ? "failed" : "skipped";
+      parsed.set(m[2], { status, message: "" });
+    }
+  } else {
+    // jest --json and vitest --reporter=json share the same report shape
+    const start = output.indexOf("{");
+    if (start < 0) return parsed;
+    try {
+      const report = JSON.parse(output.substring(start)) as {
+        testResults?: { assertionResults?: { title: string; status: string; failureMessages?: string[] }[] }[];
+      };
+      for (const file of report.testResults ?? []) {
+        for (const a of file.assertionResults ?? []) {
+          const status: Status = a.status === "passed" ? "passed" : a.status === "failed" ? "failed" : "skipped";
+          const message = (a.failureMessages?.[0] ?? "").split("\n")[0].trim();
+          parsed.set(a.title, { status, message });
+        }
+      }
+    } catch (e) {
+      editor.debug(`[test_runner] failed to parse report: ${e}`);
+    }
+  }
+  return parsed;
+}
+
+// Gutter icons and inline status for one buffer
+function renderBuffer(bufferId: number): void {
+  const tests = bufferTests.get(bufferId) ?? [];
+  editor.clearLineIndicators(bufferId, NAMESPACE);
+  editor.removeVirtualTextsByPrefix(bufferId, VTEXT_PREFIX);
+
+  for (const test of tests) {
+    const result = results.get(resultKey(test.file, test.name));
+    const status = result?.status ?? "pending";
+    const style = STATUS_STYLE[status];
+    const [r, g, b] = style.color;
+    editor.setLineIndicator(bufferId, test.line - 1, NAMESPACE, style.symbol, r, g, b, PRIORITY);
+
+    if (result && status !== "pending") {
+      const label = editor.t(`result.${status}`);
+      const text = result.message ? `  ${label}: ${result.message}` : `  ${label}`;
+      editor.addVirtualText(bufferId, `${VTEXT_PREFIX}${test.line}`, test.lineEnd, text, r, g, b, false, false);
+    }
+  }
+}
+
+function renderFile(file: string): void {
+  for (const [bufferId] of bufferTests) {
+    if (editor.getBufferPath(bufferId) === file) {
+      renderBuffer(bufferId);
+    }
+  }
+}
+
+// Re-detect tests in a buffer from its current text
+async function scanBuffer(bufferId: number): Promise<TestCase[]> {
+  const path = editor.getBufferPath(bufferId);
+  if (!path || !frameworkFor(path)) {
+    bufferTests.delete(bufferId);
+    return [];
+  }
+  const text = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+  const tests = detectTests(path, text);
+  bufferTests.set(bufferId, tests);
+  renderBuffer(bufferId);
+  return tests;
+}
+
+const provider = createLiveProvider(() =>
+  Array.from(results.values()).sort((a, b) => (a.file !== b.file ? (a.file < b.file ? -1 : 1) : a.line - b.line))
+);
+
+const finder = new Finder<TestResult>(editor, {
+  id: "test-results",
+  format: (t) => ({
+    label: `${STATUS_STYLE[t.status].symbol} ${t.name}`,
+    description: t.message,
+    location: {
+      file: t.file,
+      line: t.line,
+      column: 1,
+    },
+  }),
+  groupBy: "file",
+  syncWithEditor: true,
+  onSelect: (t) => {
+    editor.openFile(t.file, t.line, 1);
+  },
+});
+
+function summary(): { passed: number; failed: number } {
+  let passed = 0;
+  let failed = 0;
+  for (const r of results.values()) {
+    if (r.status === "passed") passed++;
+    if (r.status === "failed") failed++;
+  }
+  return { passed, failed };
+}
+
+function getTitle(): string {
+  const { passed, failed } = summary();
+  return editor.t("panel.header", { passed: String(passed), failed: String(failed) });
+}
+
+function refreshPanel(): void {
+  if (finder.isOpen) {
+    finder.updateTitle(getTitle());
+    provider.notify();
+  }
+}
+
+// Run `tests` (all from one file) in the background and record their outcome
+async function runTests(file: string, tests: TestCase[], filterByName: boolean): Promise<void> {
+  const framework = frameworkFor(file);
+  if (!framework || tests.length === 0) {
+    editor.setStatus(editor.t("status.no_tests"));
+    return;
+  }
+  if (running) {
+    editor.setStatus(editor.t("status.busy"));
+    return;
+  }
+  running = true;
+
+  for (const test of tests) {
+    results.set(resultKey(file, test.name), { ...test, status: "running", message: "" });
+  }
+  renderFile(file);
+  refreshPanel();
+  editor.setStatus(editor.t("status.running", { count: String(tests.length) }));
+
+  const { cmd, args, cwd } = buildCommand(framework, file, filterByName ? tests.map((t) => t.name) : []);
+  let parsed = new Map<string, { status: Status; message: string }>();
+  try {
+    const result = await editor.spawnProcess(cmd, args, cwd);
+    // The JSON reporters write the report to stdout and progress to stderr
+    const json = framework === "jest" || framework === "vitest";
+    parsed = parseOutput(framework, json ? result.stdout : `${result.stdout}\n${result.stderr}`);
+    if (parsed.size === 0 && result.exit_code !== 0) {
+      editor.debug(`[test_runner] ${cmd} exited with ${result.exit_code}: ${result.stderr}`);
+    }
+  } catch (e) {
+    editor.debug(`[test_runner] failed to run ${cmd}: ${e}`);
+  } finally {
+    running = false;
+  }
+
+  let passed = 0;
+  let failed = 0;
+  for (const test of tests) {
+    const outcome = parsed.get(test.name);
+    // A test missing from the output did not run (e.g. a build failure)
+    const status = outcome?.status ?? "failed";
+    const message = outcome ? outcome.message : editor.t("status.no_result");
+    results.set(resultKey(file, test.name), { ...test, status, message });
+    if (status === "passed") passed++;
+    if (status === "failed") failed++;
+  }
+
+  renderFile(file);
+  refreshPanel();
+  editor.setStatus(editor.t("status.finished", { passed: String(passed), failed: String(failed) }));
+}
+
+// Commands
+globalThis.run_test_at_cursor = async function (): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const tests = await scanBuffer(bufferId);
+  const cursorLine = editor.getCursorLine() + 1;
+
+  // The nearest test starting at or above the cursor
+  let target: TestCase | null = null;
+  for (const test of tests) {
+    if (test.line <= cursorLine) target = test;
+  }
+  if (!target) {
+    editor.setStatus(editor.t("status.no_test_at_cursor"));
+    return;
+  }
+  await runTests(target.file, [target], true);
+};
+
+globalThis.run_tests_in_file = async function (): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  const tests = await scanBuffer(bufferId);
+  const path = editor.getBufferPath(bufferId);
+  // Filter by name for Rust, where running the file's module alone is not possible
+  await runTests(path, tests, frameworkFor(path) === "cargo");
+};
+
+globalThis.show_test_results = async function (): Promise<void> {
+  if (finder.isOpen) {
+    refreshPanel();
+    return;
+  }
+  await finder.livePanel({
+    title: getTitle(),
+    provider: provider as FinderProvider<TestResult>,
+    ratio: 0.3,
+  });
+};
+
+globalThis.clear_test_results = function (): void {
+  results.clear();
+  for (const [bufferId] of bufferTests) {
+    renderBuffer(bufferId);
+  }
+  refreshPanel();
+  editor.setStatus(editor.t("status.cleared"));
+};
+
+// Event Handlers
+
+globalThis.onTestRunnerBufferChanged = function (args: { buffer_id: number }): boolean {
+  scanBuffer(args.buffer_id);
+  return true;
+};
+
+globalThis.onTestRunnerBufferOpened = function (args: { buffer_id: number }): boolean {
+  scanBuffer(args.buffer_id);
+  return true;
+};
+
+globalThis.onTestRunnerBufferClosed = function (args: { buffer_id: number }): boolean {
+  bufferTests.delete(args.buffer_id);
+  return true;
+};
+
+editor.on("after_file_open", "onTestRunnerBufferOpened");
+editor.on("after_file_save", "onTestRunnerBufferChanged");
+editor.on("buffer_closed", "onTestRunnerBufferClosed");
+
+// Command Registration
+editor.registerCommand("%cmd.run_test_at_cursor", "%cmd.run_test_at_cursor_desc", "run_test_at_cursor", null);
+editor.registerCommand("%cmd.run_tests_in_file", "%cmd.run_tests_in_file_desc", "run_tests_in_file", null);
+editor.registerCommand("%cmd.show_test_results", "%cmd.show_test_results_desc", "show_test_results", null);
+editor.registerCommand("%cmd.clear_test_results", "%cmd.clear_test_results_desc", "clear_test_results", null);
+
+// Mark buffers that were opened before the plugin loaded
+for (const buffer of editor.listBuffers()) {
+  if (buffer.path) {
+    scanBuffer(buffer.id);
+  }
+}
+
+editor.debug("Test Runner plugin loaded");
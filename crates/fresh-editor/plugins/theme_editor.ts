@@ -8,6 +8,7 @@ const editor = getEditor();
  * Provides a visual interface for editing Fresh's color themes with:
  * - Organized display of all theme color fields by section
  * - Inline color swatches showing the actual colors
+ * - Live preview of sample code drawn with the colors being edited
  * - Color picker supporting both RGB values and named colors
  * - Copy from built-in themes to use as starting point
  * - Save as new theme name
@@ -626,6 +627,86 @@ function buildVisibleFields(): ThemeField[] {
   return fields;
 }
 
+// =============================================================================
+// Preview
+// =============================================================================
+
+/** A span of the preview sample and the theme field of its color (null: editor.fg) */
+type PreviewSpan = [string, string | null];
+
+/**
+ * Sample code drawn with the colors being edited, after a comment line. The
+ * line at PREVIEW_CURRENT_LINE is drawn as the cursor line and the span at
+ * PREVIEW_SELECTION as selected text (both counting the comment line).
+ */
+const PREVIEW_CODE: PreviewSpan[][] = [
+  [["fn", "syntax.keyword"], [" ", null], ["greet", "syntax.function"], ["(", null], ["name", "syntax.variable"], [": &", null], ["str", "syntax.type"], [") ", null], ["->", "syntax.operator"], [" ", null], ["usize", "syntax.type"], [" {", null]],
+  [["    ", null], ["let", "syntax.keyword"], [" ", null], ["count", "syntax.variable"], [" ", null], ["=", "syntax.operator"], [" ", null], ["42", "syntax.constant"], [";", null]],
+  [["    ", null], ["println!", "syntax.function"], ["(", null], ["\"Hello, {}!\"", "syntax.string"], [", ", null], ["name", "syntax.variable"], [");", null]],
+  [["    ", null], ["count", "syntax.variable"], [" ", null], ["+", "syntax.operator"], [" ", null], ["name", "syntax.variable"], [".", null], ["len", "syntax.function"], ["()", null]],
+  [["}", null]],
+];
+const PREVIEW_CURRENT_LINE = 2;
+const PREVIEW_SELECTION: [number, number] = [4, 5];
+
+/** Width of the line number gutter of the preview ("NNN  ") */
+const PREVIEW_GUTTER_WIDTH = 5;
+
+function previewLines(): PreviewSpan[][] {
+  return [[[`// ${editor.t("panel.preview_comment")}`, "syntax.comment"]], ...PREVIEW_CODE];
+}
+
+/**
+ * Build the preview lines, each with a line number gutter
+ */
+function buildPreviewEntries(): TextPropertyEntry[] {
+  return previewLines().map((spans, line) => ({
+    text: `${String(line + 1).padStart(PREVIEW_GUTTER_WIDTH - 2)}  ${spans.map(([text]) => text).join("")}\n`,
+    properties: { type: "preview", line },
+  }));
+}
+
+/**
+ * Color of a theme field being edited, if it is an RGB or named color
+ */
+function themeColor(path: string): RGB | null {
+  const value = getNestedValue(state.themeData, path);
+  return value === undefined ? null : parseColorToRgb(value as ColorValue);
+}
+
+/**
+ * Overlay options with the colors that are set
+ */
+function colorOptions(fg: RGB | null, bg: RGB | null, extendToLineEnd = false): Record<string, unknown> {
+  const options: Record<string, unknown> = { extendToLineEnd };
+  if (fg) options.fg = fg;
+  if (bg) options.bg = bg;
+  return options;
+}
+
+/**
+ * Draw a preview line with the colors being edited
+ */
+function highlightPreviewLine(bufferId: number, start: number, line: number): void {
+  const lineBg = themeColor(line === PREVIEW_CURRENT_LINE ? "editor.current_line_bg" : "editor.bg");
+  const textFg = themeColor("editor.fg");
+
+  editor.addOverlay(bufferId, "theme", start, start + PREVIEW_GUTTER_WIDTH,
+    colorOptions(themeColor("editor.line_number_fg"), themeColor("editor.line_number_bg")));
+
+  let offset = start + PREVIEW_GUTTER_WIDTH;
+  previewLines()[line].forEach(([text, colorPath], index) => {
+    const len = getUtf8ByteLength(text);
+    const selected = line === PREVIEW_SELECTION[0] && index === PREVIEW_SELECTION[1];
+    const fg = colorPath ? themeColor(colorPath) : textFg;
+    const bg = selected ? themeColor("editor.selection_bg") : lineBg;
+    editor.addOverlay(bufferId, "theme", offset, offset + len, colorOptions(fg, bg));
+    offset += len;
+  });
+  // The newline, so the line background reaches the end of the window
+  editor.addOverlay(bufferId, "theme", offset, offset + 1, colorOptions(null, lineBg, true));
+}
+
 // =============================================================================
 // UI Building
 // =============================================================================
@@ -675,6 +756,17 @@ function buildDisplayEntries(): TextPropertyEntry[] {
     properties: { type: "blank" },
   });
 
+  // Preview of the colors being edited
+  entries.push({
+    text: `${editor.t("panel.preview")}\n`,
+    properties: { type: "preview-title" },
+  });
+  entries.push(...buildPreviewEntries());
+  entries.push({
+    text: "\n",
+    properties: { type: "blank" },
+  });
+
   // Fields
   state.visibleFields = buildVisibleFields();
 
@@ -838,6 +930,10 @@ function applyHighlighting(): void {
         const valueStart = nameEnd + getUtf8ByteLength(": X  ");
         addColorOverlay(bufferId, valueStart, byteOffset + textLen, colors.customValue);
       }
+    } else if (entryType === "preview-title") {
+      addColorOverlay(bufferId, byteOffset, byteOffset + textLen, colors.sectionHeader, true);
+    } else if (entryType === "preview") {
+      highlightPreviewLine(bufferId, byteOffset, props.line as number);
     } else if (entryType === "separator" || entryType === "footer") {
       addColorOverlay(bufferId, byteOffset, byteOffset + textLen, colors.footer);
     }
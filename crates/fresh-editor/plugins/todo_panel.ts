@@ -0,0 +1,280 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * TODO Panel Plugin
+ *
+ * Collects TODO/FIXME/HACK comments across the workspace into a panel grouped
+ * by file, and marks them in the gutter of open buffers.
+ *
+ * Key features:
+ * - Tags are configurable with the `todo.tags` setting
+ * - Workspace scan runs in the background with git grep (or ripgrep outside git)
+ * - Saving a file rescans just that file
+ * - livePanel mode with groupBy: "file"
+ */
+
+import { Finder, createLiveProvider, getRelativePath, parseGrepOutput, type FinderProvider } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+const NAMESPACE = "todo";
+const PRIORITY = 5; // Lower than git gutter and diagnostics
+const MAX_RESULTS = 5000;
+
+// Gutter colors (RGB) per tag; other configured tags use the default
+const TAG_COLORS: Record<string, [number, number, number]> = {
+  TODO: [100, 200, 255],
+  FIXME: [255, 100, 100],
+  HACK: [255, 184, 108],
+};
+const DEFAULT_COLOR: [number, number, number] = [200, 200, 100];
+const SYMBOL = "●";
+
+interface TodoItem {
+  file: string;
+  line: number;
+  column: number;
+  tag: string;
+  text: string;
+}
+
+// State
+let items: TodoItem[] = [];
+let scanning = false;
+
+// Tags from config, falling back to the defaults
+function getTags(): string[] {
+  const config = editor.getConfig() as Record<string, unknown>;
+  const todo = config?.todo as { tags?: unknown } | undefined;
+  const tags = Array.isArray(todo?.tags)
+    ? (todo.tags as unknown[]).filter((t): t is string => typeof t === "string" && t.length > 0)
+    : [];
+  return tags.length > 0 ? tags : ["TODO", "FIXME", "HACK"];
+}
+
+function escapeRegex(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+// Pattern for the scanners (POSIX ERE, shared by git grep -E and ripgrep)
+function tagPattern(tags: string[]): string {
+  return `(${tags.map(escapeRegex).join("|")})`;
+}
+
+// Split "TODO(name): text" into tag and text, or null if no tag is at `column`
+function parseTodo(content: string, column: number, tags: string[]): { tag: string; text: string } | null {
+  const rest = content.substring(column - 1);
+  const match = rest.match(new RegExp(`^${tagPattern(tags)}\\b(?:\\([^)]*\\))?:?\\s*(.*)$`));
+  if (!match) return null;
+  return { tag: match[1], text: match[2].trim() };
+}
+
+// Find tagged comments in a block of text (used for open buffers)
+function scanText(file: string, text: string, tags: string[]): TodoItem[] {
+  const found: TodoItem[] = [];
+  const regex = new RegExp(`\\b${tagPattern(tags)}\\b`, "g");
+  const lines = text.split("\n");
+  for (let i = 0; i < lines.length; i++) {
+    regex.lastIndex = 0;
+    const match = regex.exec(lines[i]);
+    if (!match) continue;
+    const parsed = parseTodo(lines[i], match.index + 1, tags);
+    if (parsed) {
+      found.push({ file, line: i + 1, column: match.index + 1, ...parsed });
+    }
+  }
+  return found;
+}
+
+// Scan the workspace in the background
+async function scanWorkspace(): Promise<TodoItem[]> {
+  const cwd = editor.getCwd();
+  const tags = getTags();
+  const pattern = tagPattern(tags);
+
+  let result = await editor.spawnProcess(
+    "git",
+    ["grep", "-n", "--column", "-I", "-w", "--untracked", "-E", "-e", pattern],
+    cwd
+  );
+  // Not a git repository: fall back to ripgrep
+  if (result.exit_code !== 0 && result.exit_code !== 1) {
+    result = await editor.spawnProcess(
+      "rg",
+      ["--line-number", "--column", "--no-heading", "--color=never", "-w", "-e", pattern],
+      cwd
+    );
+  }
+  if (result.exit_code !== 0) {
+    return [];
+  }
+
+  const found: TodoItem[] = [];
+  for (const match of parseGrepOutput(result.stdout, MAX_RESULTS)) {
+    const parsed = parseTodo(match.content, match.column, tags);
+    if (parsed) {
+      const file = match.file.startsWith("/") ? match.file : `${cwd}/${match.file}`;
+      found.push({ file, line: match.line, column: match.column, ...parsed });
+    }
+  }
+  return found;
+}
+
+// Sort by file, then line
+function sortItems(list: TodoItem[]): TodoItem[] {
+  return list.sort((a, b) => (a.file !== b.file ? (a.file < b.file ? -1 : 1) : a.line - b.line));
+}
+
+const provider = createLiveProvider(() => items);
+
+const finder = new Finder<TodoItem>(editor, {
+  id: "todo",
+  format: (t) => ({
+    label: `${t.line}: ${t.tag}`,
+    description: t.text,
+    location: {
+      file: t.file,
+      line: t.line,
+      column: t.column,
+    },
+  }),
+  groupBy: "file",
+  syncWithEditor: true,
+  maxResults: MAX_RESULTS,
+  onSelect: (t) => {
+    editor.openFile(t.file, t.line, t.column);
+    editor.setStatus(
+      editor.t("status.jumped_to", {
+        file: getRelativePath(editor, t.file),
+        line: String(t.line),
+      })
+    );
+  },
+});
+
+function getTitle(): string {
+  return editor.t("panel.header", { count: String(items.length) });
+}
+
+// Rescan the workspace and refresh the panel
+async function refresh(): Promise<void> {
+  if (scanning) return;
+  scanning = true;
+  editor.setStatus(editor.t("status.scanning"));
+  try {
+    items = sortItems(await scanWorkspace());
+  } finally {
+    scanning = false;
+  }
+  if (finder.isOpen) {
+    provider.notify();
+    finder.updateTitle(getTitle());
+  }
+  editor.setStatus(editor.t("status.found", { count: String(items.length) }));
+}
+
+// Whole text of a buffer, or null if it can't be read
+//
+// The length plugins see can lag behind an edit made right after a hook
+// (whitespace trimmed on save), so a rejected read is retried once with the
+// length read again.
+async function readBuffer(bufferId: number): Promise<string | null> {
+  for (let attempt = 0; attempt < 2; attempt++) {
+    try {
+      return await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+    } catch {
+      // Retry with a fresh length
+    }
+  }
+  return null;
+}
+
+// Gutter markers for one open buffer, from its current text; null if the
+// buffer couldn't be read
+async function updateMarkers(bufferId: number): Promise<TodoItem[] | null> {
+  const path = editor.getBufferPath(bufferId);
+  if (!path) return [];
+
+  const text = await readBuffer(bufferId);
+  if (text === null) return null;
+  const found = scanText(path, text, getTags());
+
+  editor.clearLineIndicators(bufferId, NAMESPACE);
+  for (const t of found) {
+    const color = TAG_COLORS[t.tag] ?? DEFAULT_COLOR;
+    editor.setLineIndicator(bufferId, t.line - 1, NAMESPACE, SYMBOL, color[0], color[1], color[2], PRIORITY);
+  }
+  return found;
+}
+
+// Commands
+globalThis.show_todo_panel = async function (): Promise<void> {
+  if (finder.isOpen) {
+    await refresh();
+    return;
+  }
+
+  await finder.livePanel({
+    title: getTitle(),
+    provider: provider as FinderProvider<TodoItem>,
+    ratio: 0.3,
+  });
+
+  await refresh();
+};
+
+globalThis.todo_close = function (): void {
+  finder.close();
+  editor.setStatus(editor.t("status.closed"));
+};
+
+globalThis.toggle_todo_panel = function (): void {
+  if (finder.isOpen) {
+    globalThis.todo_close();
+  } else {
+    globalThis.show_todo_panel();
+  }
+};
+
+globalThis.todo_refresh = function (): void {
+  refresh();
+};
+
+// Event Handlers
+
+globalThis.onTodoAfterFileOpen = function (args: { buffer_id: number; path: string }): boolean {
+  if (args.path) {
+    updateMarkers(args.buffer_id);
+  }
+  return true;
+};
+
+// On save, rescan just the saved file and splice its entries into the panel
+globalThis.onTodoAfterSave = function (args: { buffer_id: number; path: string }): boolean {
+  updateMarkers(args.buffer_id).then((found) => {
+    if (!found || !finder.isOpen) return;
+    items = sortItems(items.filter((t) => t.file !== args.path).concat(found));
+    provider.notify();
+    finder.updateTitle(getTitle());
+  });
+  return true;
+};
+
+editor.on("after_file_open", "onTodoAfterFileOpen");
+editor.on("after_file_save", "onTodoAfterSave");
+
+// Mode Definition (for custom keybindings beyond Enter/Escape)
+editor.defineMode("todo-extra", "todo-results", [["r", "todo_refresh"]], true);
+
+// Command Registration
+editor.registerCommand("%cmd.show_todo_panel", "%cmd.show_todo_panel_desc", "show_todo_panel", null);
+editor.registerCommand("%cmd.toggle_todo_panel", "%cmd.toggle_todo_panel_desc", "toggle_todo_panel", null);
+
+// Mark buffers that were opened before the plugin loaded
+for (const buffer of editor.listBuffers()) {
+  if (buffer.path) {
+    updateMarkers(buffer.id);
+  }
+}
+
+editor.debug("TODO Panel plugin loaded");
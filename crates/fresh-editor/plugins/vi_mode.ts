@@ -1501,6 +1501,7 @@ editor.defineMode("vi-normal", null, [
   ["C-u", "vi_half_page_up"],
   ["%", "vi_matching_bracket"],
   ["z z", "vi_center_cursor"],
+  ["g f", "open_file_under_cursor"],
 
   // Search
   ["/", "vi_search_forward"],
@@ -1562,6 +1563,24 @@ editor.defineMode("vi-normal", null, [
   // Command mode
   [":", "vi_command_mode"],
 
+  // Window commands (Ctrl-W prefix)
+  ["C-w h", "focus_split_left"],
+  ["C-w j", "focus_split_down"],
+  ["C-w k", "focus_split_up"],
+  ["C-w l", "focus_split_right"],
+  ["C-w w", "next_split"],
+  ["C-w W", "prev_split"],
+  ["C-w s", "split_horizontal"],
+  ["C-w v", "split_vertical"],
+  ["C-w f", "open_file_under_cursor_in_split"],
+  ["C-w c", "close_split"],
+  ["C-w q", "close_split"],
+  ["C-w o", "close_other_splits"],
+  ["C-w r", "rotate_splits"],
+  ["C-w =", "equalize_splits"],
+  ["C-w +", "increase_split_size"],
+  ["C-w -", "decrease_split_size"],
+
   // Pass through to standard editor shortcuts
   ["C-p", "command_palette"],
   ["C-q", "quit"],
@@ -2064,6 +2083,7 @@ const commandTable: CommandDef[] = [
   { name: "vimgrep", minAbbrev: 3, allowBang: false, hasArgs: true },  // :vim, :vimgrep
   { name: "make", minAbbrev: 3, allowBang: true, hasArgs: true },      // :mak, :make
   { name: "ascii", minAbbrev: 2, allowBang: false, hasArgs: false },   // :as, :ascii
+  { name: "unicode", minAbbrev: 3, allowBang: false, hasArgs: false }, // :uni, :unicode (Fresh-specific)
   { name: "revert", minAbbrev: 3, allowBang: false, hasArgs: false },  // :rev, :revert (Fresh-specific)
 ];
 
@@ -2676,9 +2696,11 @@ async function executeCommand(
       return { error: editor.t("error.use_terminal") };
     }
 
-    case "ascii": {
-      // :ascii - show ASCII value of char under cursor
-      return { message: editor.t("info.status_bar_char") };
+    case "ascii":
+    case "unicode": {
+      // :ascii, :unicode - show the code points of the char under cursor
+      editor.executeAction("inspect_character");
+      return {};
     }
 
     default: {
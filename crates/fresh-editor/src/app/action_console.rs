@@ -0,0 +1,179 @@
+//! Action console: running any action by its config name
+//!
+//! The console lists every action, including those without a key binding and
+//! the user-defined commands, and runs the chosen one. Arguments follow the
+//! name as `key=value` pairs or as a JSON object, as in `increment count=10`
+//! or `menu_open {"name": "Edit"}`.
+
+use std::collections::HashMap;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::fuzzy_match;
+use crate::input::keybindings::{Action, KeybindingResolver};
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+type Arguments = HashMap<String, serde_json::Value>;
+
+/// The arguments an action needs, as typed in the console
+fn parameters_hint(name: &str) -> Option<&'static str> {
+    if Action::takes_char(name) {
+        return Some("char=<c>");
+    }
+    Some(match name {
+        "copy_with_theme" => "theme=<name>",
+        "menu_open" => "name=<menu>",
+        "switch_keybinding_map" => "map=<name>",
+        "increment" | "decrement" | "increment_sequence" | "decrement_sequence" => "count=<n>",
+        "user_command" => "name=<command>",
+        _ => return None,
+    })
+}
+
+/// Split console input into the action name and the arguments text
+fn split_invocation(input: &str) -> (&str, &str) {
+    let input = input.trim();
+    match input.split_once(char::is_whitespace) {
+        Some((name, arguments)) => (name, arguments.trim()),
+        None => (input, ""),
+    }
+}
+
+/// Parse `key=value` pairs or a JSON object. Values that aren't valid JSON,
+/// such as `name=Edit`, are strings.
+fn parse_arguments(text: &str) -> Option<Arguments> {
+    if text.starts_with('{') {
+        return serde_json::from_str(text).ok();
+    }
+    text.split_whitespace()
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+impl Editor {
+    /// Open the action console
+    pub fn start_action_console(&mut self) {
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("action_console.prompt").to_string(),
+            PromptType::ActionConsole,
+            Vec::new(),
+        ));
+        self.update_prompt_suggestions();
+    }
+
+    /// Actions whose name matches the name typed in `input`. Their values
+    /// keep the typed arguments.
+    pub(super) fn action_console_suggestions(&self, input: &str) -> Vec<Suggestion> {
+        let (query, arguments) = split_invocation(input);
+        let mut names = Action::all_action_names();
+        names.extend(self.config.commands.keys().cloned());
+
+        let mut matches: Vec<(i32, Suggestion)> = names
+            .into_iter()
+            .filter_map(|name| {
+                let matched = fuzzy_match(query, &name);
+                matched.matched.then(|| {
+                    (
+                        matched.score,
+                        self.action_console_suggestion(name, arguments),
+                    )
+                })
+            })
+            .collect();
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.cmp(a_score).then_with(|| a.text.cmp(&b.text))
+        });
+        matches
+            .into_iter()
+            .map(|(_, suggestion)| suggestion)
+            .collect()
+    }
+
+    fn action_console_suggestion(&self, name: String, arguments: &str) -> Suggestion {
+        let action = if self.config.commands.contains_key(&name) {
+            Some(Action::UserCommand(name.clone()))
+        } else {
+            Action::from_str(&name, &HashMap::new())
+        };
+        let mut description = match &action {
+            Some(action) => KeybindingResolver::format_action(action),
+            None => KeybindingResolver::format_action_from_str(&name),
+        };
+        if let Some(hint) = parameters_hint(&name) {
+            description = format!("{} ({})", description, hint);
+        }
+        let keybinding = action.and_then(|action| {
+            self.keybindings
+                .get_keybinding_for_action(&action, self.key_context)
+        });
+        let value = if arguments.is_empty() {
+            name.clone()
+        } else {
+            format!("{} {}", name, arguments)
+        };
+        Suggestion {
+            text: name,
+            description: Some(description),
+            value: Some(value),
+            disabled: false,
+            keybinding,
+            source: None,
+        }
+    }
+
+    /// The action typed in the console, or why it can't run
+    pub(super) fn parse_console_action(&self, input: &str) -> Result<Action, String> {
+        let (name, arguments) = split_invocation(input);
+        let Some(arguments) = parse_arguments(arguments) else {
+            return Err(t!("action_console.bad_arguments", arguments = arguments).to_string());
+        };
+        if let Some(action) = Action::from_str(name, &arguments) {
+            return Ok(action);
+        }
+        if self.config.commands.contains_key(name) {
+            return Ok(Action::UserCommand(name.to_string()));
+        }
+        match parameters_hint(name) {
+            Some(hint) => Err(t!(
+                "action_console.missing_arguments",
+                action = name,
+                hint = hint
+            )
+            .to_string()),
+            None => Err(t!("action_console.unknown", action = name).to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_arguments() {
+        assert_eq!(
+            split_invocation("  increment count=10 "),
+            ("increment", "count=10")
+        );
+        assert_eq!(split_invocation("save"), ("save", ""));
+
+        let arguments = parse_arguments("count=10 name=Edit flag=true").unwrap();
+        assert_eq!(arguments["count"], json!(10));
+        assert_eq!(arguments["name"], json!("Edit"));
+        assert_eq!(arguments["flag"], json!(true));
+
+        let arguments = parse_arguments(r#"{"name": "File"}"#).unwrap();
+        assert_eq!(arguments["name"], json!("File"));
+
+        assert_eq!(parse_arguments(""), Some(HashMap::new()));
+        assert_eq!(parse_arguments("count"), None);
+        assert_eq!(parse_arguments("{broken"), None);
+    }
+}
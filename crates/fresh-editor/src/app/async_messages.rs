@@ -12,6 +12,7 @@ use crate::model::event::BufferId;
 use crate::services::async_bridge::{
     LspMessageType, LspProgressValue, LspSemanticTokensResponse, LspServerStatus,
 };
+use crate::services::ignore_rules::IgnoreRules;
 use crate::state::{SemanticTokenSpan, SemanticTokenStore};
 use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{
@@ -56,8 +57,37 @@ impl Editor {
             diagnostics,
             &self.theme,
         );
+        crate::services::lsp::diagnostics::apply_diagnostic_lens(
+            state,
+            diagnostics,
+            &self.config.diagnostics,
+            &self.theme,
+        );
         Some(buffer_id)
     }
+
+    /// Re-apply inline diagnostics to every open buffer from stored diagnostics.
+    /// Called after the `[diagnostics]` config or the theme changes.
+    pub(crate) fn refresh_diagnostic_lens(&mut self) {
+        let uris: Vec<String> = self.stored_diagnostics.keys().cloned().collect();
+        for uri in uris {
+            let Some(buffer_id) = self.find_buffer_by_uri(&uri) else {
+                continue;
+            };
+            let (Some(state), Some(diagnostics)) = (
+                self.buffers.get_mut(&buffer_id),
+                self.stored_diagnostics.get(&uri),
+            ) else {
+                continue;
+            };
+            crate::services::lsp::diagnostics::apply_diagnostic_lens(
+                state,
+                diagnostics,
+                &self.config.diagnostics,
+                &self.theme,
+            );
+        }
+    }
 }
 
 // =============================================================================
@@ -66,7 +96,32 @@ impl Editor {
 
 impl Editor {
     /// Store and apply diagnostics, emit hook for plugins
-    fn store_and_apply_diagnostics(&mut self, uri: String, diagnostics: Vec<Diagnostic>) {
+    ///
+    /// `server` identifies the language server (or linter) that produced the
+    /// diagnostics; they replace its previous diagnostics for the file and are
+    /// merged with those of the other servers and linters attached to it.
+    pub(super) fn store_and_apply_diagnostics(
+        &mut self,
+        server: String,
+        uri: String,
+        diagnostics: Vec<Diagnostic>,
+    ) {
+        let per_server = self.server_diagnostics.entry(uri.clone()).or_default();
+        if diagnostics.is_empty() {
+            per_server.remove(&server);
+        } else {
+            per_server.insert(server, diagnostics);
+        }
+        let mut servers: Vec<&String> = per_server.keys().collect();
+        servers.sort();
+        let diagnostics: Vec<Diagnostic> = servers
+            .into_iter()
+            .flat_map(|server| per_server[server].iter().cloned())
+            .collect();
+        if per_server.is_empty() {
+            self.server_diagnostics.remove(&uri);
+        }
+
         // Store diagnostics for later retrieval by plugins
         if diagnostics.is_empty() {
             self.stored_diagnostics.remove(&uri);
@@ -96,18 +151,25 @@ impl Editor {
     }
 
     /// Handle LSP diagnostics (push model)
-    pub(super) fn handle_lsp_diagnostics(&mut self, uri: String, diagnostics: Vec<Diagnostic>) {
+    pub(super) fn handle_lsp_diagnostics(
+        &mut self,
+        server: String,
+        uri: String,
+        diagnostics: Vec<Diagnostic>,
+    ) {
         tracing::debug!(
-            "Processing {} LSP diagnostics for {}",
+            "Processing {} LSP diagnostics for {} from {}",
             diagnostics.len(),
-            uri
+            uri,
+            server
         );
-        self.store_and_apply_diagnostics(uri, diagnostics);
+        self.store_and_apply_diagnostics(server, uri, diagnostics);
     }
 
     /// Handle LSP pulled diagnostics (pull model - LSP 3.17+)
     pub(super) fn handle_lsp_pulled_diagnostics(
         &mut self,
+        server: String,
         uri: String,
         result_id: Option<String>,
         diagnostics: Vec<Diagnostic>,
@@ -134,7 +196,7 @@ impl Editor {
             self.diagnostic_result_ids.insert(uri.clone(), result_id);
         }
 
-        self.store_and_apply_diagnostics(uri, diagnostics);
+        self.store_and_apply_diagnostics(server, uri, diagnostics);
     }
 }
 
@@ -594,6 +656,7 @@ impl Editor {
                         title,
                         message,
                         percentage,
+                        started: self.time_source.now(),
                     },
                 );
                 self.update_lsp_status_from_progress();
@@ -634,6 +697,7 @@ impl Editor {
         if self.lsp_window_messages.len() > 100 {
             self.lsp_window_messages.remove(0);
         }
+        self.refresh_lsp_log_for(&language);
 
         // Show important messages in status bar
         match message_type {
@@ -654,7 +718,7 @@ impl Editor {
         message: String,
     ) {
         self.lsp_log_messages.push(LspMessageEntry {
-            language,
+            language: language.clone(),
             message_type,
             message,
             timestamp: Instant::now(),
@@ -664,6 +728,7 @@ impl Editor {
         if self.lsp_log_messages.len() > 500 {
             self.lsp_log_messages.remove(0);
         }
+        self.refresh_lsp_log_for(&language);
     }
 
     /// Handle LSP server status update
@@ -675,10 +740,17 @@ impl Editor {
 
         // Update server status
         self.lsp_server_statuses.insert(language.clone(), status);
-        self.update_lsp_status_from_server_statuses();
+
+        // A server that went away will never send the matching `end` notifications
+        if matches!(status, LspServerStatus::Error | LspServerStatus::Shutdown) {
+            self.lsp_progress
+                .retain(|_, info| info.language != language);
+        }
+        self.update_lsp_status_from_progress();
 
         // Update warning domain for LSP status indicator
         self.update_lsp_warning_domain();
+        self.refresh_lsp_info();
 
         // Handle server crash - trigger auto-restart
         if status == LspServerStatus::Error {
@@ -876,16 +948,25 @@ impl Editor {
     pub(super) fn handle_file_explorer_initialized(&mut self, mut view: FileTreeView) {
         tracing::info!("File explorer initialized");
 
-        // Load root .gitignore
+        // Load the project ignore rules, shared with Quick Open and project search
         let root_id = view.tree().root_id();
         let root_path = view.tree().get_node(root_id).map(|n| n.entry.path.clone());
 
         if let Some(root_path) = root_path {
-            if let Err(e) = view.load_gitignore_for_dir(&root_path) {
-                tracing::warn!("Failed to load root .gitignore from {:?}: {}", root_path, e);
-            } else {
-                tracing::debug!("Loaded root .gitignore from {:?}", root_path);
-            }
+            view.ignore_patterns_mut()
+                .set_rules(IgnoreRules::new(&root_path, &self.config.files.exclude));
+            tracing::debug!("Loaded ignore rules of {:?}", root_path);
+        }
+
+        // Apply configured filters; session settings below take precedence
+        let explorer_config = &self.config.file_explorer;
+        let ignore_patterns = view.ignore_patterns_mut();
+        ignore_patterns.set_show_hidden(explorer_config.show_hidden);
+        ignore_patterns.set_show_gitignored(
+            explorer_config.show_gitignored || !explorer_config.respect_gitignore,
+        );
+        for pattern in &explorer_config.custom_ignore_patterns {
+            ignore_patterns.add_custom_pattern(pattern.clone());
         }
 
         // Apply pending session restore settings (fixes #569)
@@ -907,6 +988,10 @@ impl Editor {
 
         self.file_explorer = Some(view);
         self.set_status_message(t!("status.file_explorer_ready").to_string());
+
+        if std::mem::take(&mut self.pending_file_explorer_reveal) {
+            self.sync_file_explorer_to_active_file();
+        }
     }
 
     /// Handle file explorer node toggle completed
@@ -968,6 +1053,8 @@ impl Editor {
     /// No-op sentinels like `HookCompleted` do not count.
     pub(super) fn process_plugin_commands(&mut self) -> bool {
         let commands = self.plugin_manager.process_commands();
+        #[cfg(feature = "runtime")]
+        self.report_native_plugin_failures();
         if commands.is_empty() {
             return false;
         }
@@ -1017,6 +1104,41 @@ impl Editor {
         has_visual_commands
     }
 
+    /// Show native plugin load errors and crashes in the status bar.
+    #[cfg(feature = "runtime")]
+    fn report_native_plugin_failures(&mut self) {
+        use crate::services::plugins::native::NativePluginFailure;
+
+        for failure in self.plugin_manager.take_native_failures() {
+            let message = match failure {
+                NativePluginFailure::LoadFailed { path, error } => t!(
+                    "plugin.native_load_failed",
+                    path = path.display().to_string(),
+                    error = error
+                ),
+                NativePluginFailure::AbiMismatch { path, found } => t!(
+                    "plugin.native_abi_mismatch",
+                    path = path.display().to_string(),
+                    found = found,
+                    expected = fresh_core::native::NATIVE_ABI_VERSION
+                ),
+                NativePluginFailure::CrashedLastRun { name, call, marker } => t!(
+                    "plugin.native_crashed_last_run",
+                    name = name,
+                    call = call,
+                    marker = marker.display().to_string()
+                ),
+                NativePluginFailure::Crashed { name, reason } => {
+                    t!("plugin.native_crashed", name = name, reason = reason)
+                }
+                NativePluginFailure::Error { name, message } => {
+                    t!("plugin.native_error", name = name, error = message)
+                }
+            };
+            self.set_status_message(message.to_string());
+        }
+    }
+
     /// Process pending plugin action completions
     #[cfg(feature = "plugins")]
     pub(super) fn process_pending_plugin_actions(&mut self) {
@@ -1069,7 +1191,16 @@ impl Editor {
     }
 
     /// Re-send didOpen notifications for all buffers of a given language
-    pub(super) fn resend_did_open_for_language(&mut self, language: &str) {
+    ///
+    /// `server` is a language or the key of one of its additional servers;
+    /// only that server receives the notifications.
+    pub(super) fn resend_did_open_for_language(&mut self, server: &str) {
+        let language = self
+            .lsp
+            .as_ref()
+            .map_or(server, |lsp| lsp.server_language(server))
+            .to_string();
+
         // Find all open buffers for this language using stored buffer language
         let buffers_for_language: Vec<_> = self
             .buffers
@@ -1100,7 +1231,7 @@ impl Editor {
                     let lang_id = state.language.clone();
                     if let Some(lsp) = self.lsp.as_mut() {
                         // LSP should already be running since we just restarted it
-                        if let Some(handle) = lsp.get_handle_mut(&lang_id) {
+                        if let Some(handle) = lsp.get_handle_mut(server) {
                             let handle_id = handle.id();
                             let _ = handle.did_open(uri, content, lang_id);
 
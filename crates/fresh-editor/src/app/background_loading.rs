@@ -0,0 +1,212 @@
+//! Loading grammars and plugins without holding up startup
+//!
+//! The editor draws its first frame before grammars and plugins are ready.
+//! Grammars are compiled on a background thread and swapped in when done,
+//! highlighting the files opened in the meantime again. Plugins are loaded
+//! by the plugin thread while the editor runs; hooks and actions sent to the
+//! plugin thread queue up behind the loads, so plugins still see the files
+//! opened at startup.
+//!
+//! A plugin package can put off loading until it is needed with
+//! `activation_events` in its `package.json`: `onLanguage:<language>` loads
+//! it when a file of the language is opened, `onCommand:<action>` when the
+//! action is run. Those actions are listed in the command palette until the
+//! plugin is loaded.
+
+use super::Editor;
+use crate::input::commands::{Command, CommandSource};
+use crate::input::keybindings::Action;
+use crate::primitives::grammar::GrammarRegistry;
+use crate::services::async_bridge::AsyncMessage;
+use fresh_core::api::PluginLogLevel;
+use fresh_core::config::PluginConfig;
+use fresh_core::package::{ActivationEvent, PackageManifest};
+use rust_i18n::t;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A plugin package waiting for one of its activation events
+pub(super) struct DeferredPlugin {
+    /// Directory of the package
+    dir: PathBuf,
+    /// Package name
+    name: String,
+    events: Vec<ActivationEvent>,
+}
+
+impl DeferredPlugin {
+    /// Package name
+    pub(super) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The package in `dir`, if its manifest defers loading it
+    ///
+    /// Packages without activation events, or with `*`, load at startup.
+    /// Events this version doesn't know are ignored.
+    pub(super) fn from_package_dir(dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(dir.join("package.json")).ok()?;
+        let manifest: PackageManifest = match serde_json::from_str(&content) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Invalid package.json in {:?}: {}", dir, e);
+                return None;
+            }
+        };
+        let events: Vec<ActivationEvent> = manifest
+            .fresh?
+            .activation_events
+            .iter()
+            .filter_map(|event| {
+                let parsed = ActivationEvent::parse(event);
+                if parsed.is_none() {
+                    tracing::warn!("Unknown activation event '{}' in {:?}", event, dir);
+                }
+                parsed
+            })
+            .collect();
+        if events.is_empty() || events.contains(&ActivationEvent::Startup) {
+            return None;
+        }
+        Some(Self {
+            dir: dir.to_path_buf(),
+            name: manifest.name,
+            events,
+        })
+    }
+
+    /// Actions whose running loads the plugin
+    fn commands(&self) -> impl Iterator<Item = &str> {
+        self.events.iter().filter_map(|event| match event {
+            ActivationEvent::Command(action) => Some(action.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Commands listed in the command palette until the plugin is loaded
+    pub(super) fn placeholder_commands(&self) -> Vec<Command> {
+        self.commands()
+            .map(|action| Command {
+                name: action.to_string(),
+                description: t!("plugin.deferred_command", plugin = &self.name).to_string(),
+                action: Action::PluginAction(action.to_string()),
+                contexts: vec![],
+                custom_contexts: vec![],
+                source: CommandSource::Plugin(self.name.clone()),
+            })
+            .collect()
+    }
+}
+
+impl Editor {
+    /// Compile the grammars on a background thread; files are highlighted
+    /// again once they are ready
+    pub(super) fn load_grammars_in_background(&mut self, config_dir: PathBuf) {
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            self.handle_grammars_loaded(GrammarRegistry::for_editor(config_dir));
+            return;
+        };
+        let thread_config_dir = config_dir.clone();
+        let spawned = std::thread::Builder::new()
+            .name("grammar-load".to_string())
+            .spawn(move || {
+                let registry = tracing::info_span!("load_grammars")
+                    .in_scope(|| GrammarRegistry::for_editor(thread_config_dir));
+                let _ = sender.send(AsyncMessage::GrammarsLoaded(registry));
+            });
+        if let Err(e) = spawned {
+            tracing::error!("Failed to start grammar loading thread: {}", e);
+            self.handle_grammars_loaded(GrammarRegistry::for_editor(config_dir));
+        }
+    }
+
+    /// Use the grammars loaded in the background
+    pub(super) fn handle_grammars_loaded(&mut self, registry: Arc<GrammarRegistry>) {
+        tracing::info!(
+            "Grammars loaded: {} syntaxes",
+            registry.available_syntaxes().len()
+        );
+        self.grammar_registry = registry;
+        if self.pending_grammars.is_empty() {
+            self.refresh_highlighters();
+        } else {
+            // Grammars registered by plugins while loading
+            self.handle_reload_grammars();
+        }
+    }
+
+    /// Load the plugins in `dirs` without waiting for them
+    pub(super) fn load_plugins_in_background(&mut self, dirs: &[PathBuf]) {
+        if dirs.is_empty() {
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            return;
+        };
+        self.plugin_manager.load_plugins_in_background(
+            dirs,
+            &self.config.plugins,
+            move |errors, discovered| {
+                let _ = sender.send(AsyncMessage::PluginsLoaded { errors, discovered });
+            },
+        );
+        self.plugin_loads_pending += 1;
+    }
+
+    /// Whether plugins are still being loaded in the background
+    pub fn plugins_loading(&self) -> bool {
+        self.plugin_loads_pending > 0
+    }
+
+    /// Note the plugins found by a background load and report its errors
+    pub(super) fn handle_plugins_loaded(
+        &mut self,
+        errors: Vec<String>,
+        discovered: HashMap<String, PluginConfig>,
+    ) {
+        self.plugin_loads_pending = self.plugin_loads_pending.saturating_sub(1);
+        // The discovered plugins already carry their saved enabled state
+        self.config.plugins.extend(discovered);
+        for err in errors {
+            tracing::error!("TypeScript plugin load error: {}", err);
+            self.plugin_console_log("plugins", PluginLogLevel::Error, err);
+        }
+    }
+
+    /// Load the plugins waiting for a file of `language` to be opened
+    pub(super) fn activate_plugins_for_language(&mut self, language: &str) {
+        self.activate_plugins(
+            |event| matches!(event, ActivationEvent::Language(l) if l.eq_ignore_ascii_case(language)),
+        );
+    }
+
+    /// Load the plugins waiting for `action` to be run
+    pub(super) fn activate_plugins_for_command(&mut self, action: &str) {
+        self.activate_plugins(|event| matches!(event, ActivationEvent::Command(a) if a == action));
+    }
+
+    fn activate_plugins(&mut self, wanted: impl Fn(&ActivationEvent) -> bool) {
+        if self.deferred_plugins.is_empty() {
+            return;
+        }
+        let (activated, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.deferred_plugins)
+            .into_iter()
+            .partition(|plugin| plugin.events.iter().any(&wanted));
+        self.deferred_plugins = waiting;
+        if activated.is_empty() {
+            return;
+        }
+
+        let registry = self.command_registry.read().unwrap();
+        for plugin in &activated {
+            tracing::info!("Activating plugin package '{}'", plugin.name);
+            for action in plugin.commands() {
+                registry.unregister(action);
+            }
+        }
+        drop(registry);
+        let dirs: Vec<PathBuf> = activated.into_iter().map(|plugin| plugin.dir).collect();
+        self.load_plugins_in_background(&dirs);
+    }
+}
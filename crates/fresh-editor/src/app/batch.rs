@@ -0,0 +1,116 @@
+//! Running batch scripts against files (`fresh --batch`).
+//!
+//! Each file is one transaction: the whole script runs against the buffer
+//! and the file is only written if every command succeeded. On failure the
+//! edits are undone, so the buffer and the file are left as they were.
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result as AnyhowResult};
+
+use crate::input::batch::{BatchCommand, BatchScript};
+use crate::input::keybindings::Action;
+
+use super::Editor;
+
+/// What a batch run did to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutcome {
+    /// The script changed the file and it was saved
+    Changed,
+    /// The script left the content as it was
+    Unchanged,
+}
+
+impl Editor {
+    /// Open `path`, run `script` on it and save the result
+    ///
+    /// The buffer is closed afterwards so files are processed independently.
+    pub fn run_batch_on_file(
+        &mut self,
+        path: &Path,
+        script: &BatchScript,
+    ) -> AnyhowResult<BatchOutcome> {
+        let buffer_id = self.open_file(path)?;
+        let result = if self.is_editing_disabled() {
+            Err(anyhow!("file is read-only"))
+        } else {
+            self.run_batch_script(script)
+        };
+        let result = result.and_then(|()| {
+            if !self.active_state().buffer.is_modified() {
+                return Ok(BatchOutcome::Unchanged);
+            }
+            self.save()?;
+            // A save that needs confirmation (e.g. sudo) opens a prompt instead
+            if self.prompt.is_some() || self.active_state().buffer.is_modified() {
+                self.cancel_prompt();
+                bail!("file could not be written");
+            }
+            Ok(BatchOutcome::Changed)
+        });
+        if let Err(e) = self.close_buffer(buffer_id) {
+            tracing::warn!("Failed to close batch buffer {:?}: {}", buffer_id, e);
+        }
+        result
+    }
+
+    /// Run `script` against the active buffer as one transaction
+    ///
+    /// If a command fails, every edit made by the script is undone.
+    pub fn run_batch_script(&mut self, script: &BatchScript) -> AnyhowResult<()> {
+        let start = self.active_event_log().current_index();
+        for step in &script.steps {
+            if let Err(e) = self.run_batch_command(&step.command) {
+                self.cancel_prompt();
+                while self.active_event_log().current_index() > start {
+                    let index = self.active_event_log().current_index();
+                    self.handle_undo();
+                    if self.active_event_log().current_index() == index {
+                        break;
+                    }
+                }
+                return Err(anyhow!("line {}: {}", step.line, e));
+            }
+        }
+        Ok(())
+    }
+
+    fn run_batch_command(&mut self, command: &BatchCommand) -> AnyhowResult<()> {
+        match command {
+            BatchCommand::Replace {
+                search,
+                replacement,
+                regex,
+                case_sensitive,
+                whole_word,
+            } => {
+                let saved = (
+                    self.search_use_regex,
+                    self.search_case_sensitive,
+                    self.search_whole_word,
+                );
+                self.search_use_regex = *regex;
+                self.search_case_sensitive = *case_sensitive;
+                self.search_whole_word = *whole_word;
+                self.perform_replace(search, replacement);
+                (
+                    self.search_use_regex,
+                    self.search_case_sensitive,
+                    self.search_whole_word,
+                ) = saved;
+            }
+            BatchCommand::Goto { line, column } => self.goto_line_col(*line, *column),
+            BatchCommand::Insert(text) => self.paste_text(text.clone()),
+            // Formatting reports failures through the status line; surface them here
+            BatchCommand::Action(Action::FormatBuffer) => {
+                self.format_buffer().map_err(|e| anyhow!(e))?
+            }
+            BatchCommand::Action(action) => self.handle_action(action.clone())?,
+        }
+        if self.prompt.is_some() {
+            bail!("command needs interactive input");
+        }
+        Ok(())
+    }
+}
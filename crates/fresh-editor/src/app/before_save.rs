@@ -0,0 +1,282 @@
+//! Plugin `before_file_save` handlers.
+//!
+//! Saving a file first calls the handlers and holds the write until each of
+//! them has answered. Their edits are applied together as one undo step, a
+//! veto cancels the save, and handlers still running after
+//! `editor.before_save_timeout_ms` are left behind so a hung plugin can't
+//! keep a file from being saved.
+
+use std::time::{Duration, Instant};
+
+use fresh_core::api::{BeforeSaveEdit, BeforeSaveResult};
+use rust_i18n::t;
+
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::services::plugins::hooks::HookArgs;
+
+use super::Editor;
+
+/// A save held until the plugins' `before_file_save` handlers answer
+#[derive(Debug)]
+pub(crate) struct PendingBeforeSave {
+    request_id: u64,
+    buffer_id: BufferId,
+    /// Buffer version the handlers saw; their edits are relative to it
+    version: u64,
+    deadline: Instant,
+    /// Plugins yet to answer, once per handler
+    waiting: Vec<String>,
+    /// Edits received so far, with the plugin that sent them
+    edits: Vec<(String, BeforeSaveEdit)>,
+    /// First veto received: plugin and reason
+    veto: Option<(String, String)>,
+}
+
+/// Order edits for applying and drop the ones that can't be applied
+///
+/// Edits are returned from the end of the buffer backwards so each one leaves
+/// the offsets of the rest valid. An edit outside the buffer, or overlapping
+/// an edit from earlier in `edits`, is dropped and its plugin reported.
+fn plan_edits(
+    edits: Vec<(String, BeforeSaveEdit)>,
+    buffer_len: usize,
+) -> (Vec<BeforeSaveEdit>, Vec<String>) {
+    let mut accepted: Vec<BeforeSaveEdit> = Vec::new();
+    let mut rejected = Vec::new();
+    for (plugin, edit) in edits {
+        let in_range = edit.start <= edit.end && edit.end <= buffer_len;
+        let overlaps = accepted
+            .iter()
+            .any(|other| (edit.start < other.end && other.start < edit.end) || edit == *other);
+        if in_range && !overlaps {
+            accepted.push(edit);
+        } else {
+            tracing::warn!(
+                "Dropping before_file_save edit {}..{} from plugin {}",
+                edit.start,
+                edit.end,
+                plugin
+            );
+            if !rejected.contains(&plugin) {
+                rejected.push(plugin);
+            }
+        }
+    }
+    // Insertions at the same offset end up in the order they were sent in
+    accepted.reverse();
+    accepted.sort_by(|a, b| (b.start, b.end).cmp(&(a.start, a.end)));
+    (accepted, rejected)
+}
+
+impl Editor {
+    /// Call the plugins' `before_file_save` handlers instead of saving right away
+    ///
+    /// Returns true if the save is held until they answer.
+    pub(crate) fn request_before_save(&mut self) -> bool {
+        let buffer_id = self.active_buffer();
+        if let Some(pending) = &self.pending_before_save {
+            // Saving again while waiting doesn't start another round
+            return pending.buffer_id == buffer_id;
+        }
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| p.to_path_buf())
+        else {
+            return false;
+        };
+
+        let request_id = self.next_before_save_id;
+        self.next_before_save_id += 1;
+        let waiting = self
+            .plugin_manager
+            .run_before_save(request_id, HookArgs::BeforeFileSave { buffer_id, path });
+        if waiting.is_empty() {
+            return false;
+        }
+
+        let timeout = Duration::from_millis(self.config.editor.before_save_timeout_ms);
+        self.pending_before_save = Some(PendingBeforeSave {
+            request_id,
+            buffer_id,
+            version: self.active_state().buffer.version(),
+            deadline: self.time_source.now() + timeout,
+            waiting,
+            edits: Vec::new(),
+            veto: None,
+        });
+        self.set_status_message(t!("file.before_save_running").to_string());
+        true
+    }
+
+    pub(super) fn handle_before_save_response(
+        &mut self,
+        plugin_name: String,
+        request_id: u64,
+        result: BeforeSaveResult,
+    ) {
+        let Some(pending) = self
+            .pending_before_save
+            .as_mut()
+            .filter(|p| p.request_id == request_id)
+        else {
+            tracing::debug!(
+                "Ignoring late before_file_save answer from plugin {}",
+                plugin_name
+            );
+            return;
+        };
+        let Some(index) = pending.waiting.iter().position(|p| *p == plugin_name) else {
+            return;
+        };
+        pending.waiting.remove(index);
+
+        if let Some(reason) = result.veto {
+            pending.veto.get_or_insert((plugin_name.clone(), reason));
+        }
+        for edit in result.edits.unwrap_or_default() {
+            pending.edits.push((plugin_name.clone(), edit));
+        }
+        if pending.waiting.is_empty() {
+            self.finish_before_save();
+        }
+    }
+
+    /// Stop waiting for `before_file_save` handlers that are past the timeout
+    ///
+    /// Returns true if a held save went ahead.
+    pub fn check_before_save_timeout(&mut self) -> bool {
+        let now = self.time_source.now();
+        if self
+            .pending_before_save
+            .as_ref()
+            .is_none_or(|p| p.deadline > now)
+        {
+            return false;
+        }
+        self.finish_before_save();
+        true
+    }
+
+    /// How long until a held save goes ahead without its handlers
+    pub(super) fn before_save_wait(&self) -> Option<Duration> {
+        let pending = self.pending_before_save.as_ref()?;
+        Some(
+            pending
+                .deadline
+                .saturating_duration_since(self.time_source.now()),
+        )
+    }
+
+    /// Apply the handlers' answers and write the file
+    fn finish_before_save(&mut self) {
+        let Some(pending) = self.pending_before_save.take() else {
+            return;
+        };
+        if let Some((plugin, reason)) = pending.veto {
+            self.set_status_message(
+                t!("file.save_vetoed", plugin = plugin, reason = reason).to_string(),
+            );
+            return;
+        }
+
+        let mut warning = None;
+        if !pending.waiting.is_empty() {
+            let mut plugins = pending.waiting.clone();
+            plugins.sort();
+            plugins.dedup();
+            tracing::warn!(
+                "before_file_save handlers timed out: {}",
+                plugins.join(", ")
+            );
+            warning = Some(t!("file.before_save_timeout", plugins = plugins.join(", ")));
+        }
+
+        let Some(state) = self.buffers.get_mut(&pending.buffer_id) else {
+            return;
+        };
+        // Edits are only meaningful against the text they were computed for
+        if state.buffer.version() != pending.version {
+            if !pending.edits.is_empty() {
+                warning = Some(t!("file.before_save_stale"));
+            }
+        } else {
+            let (edits, rejected) = plan_edits(pending.edits, state.buffer.len());
+            if !rejected.is_empty() && warning.is_none() {
+                warning = Some(t!(
+                    "file.before_save_edits_dropped",
+                    plugins = rejected.join(", ")
+                ));
+            }
+            let events: Vec<Event> = edits
+                .into_iter()
+                .flat_map(|edit| {
+                    let delete = (edit.start < edit.end).then(|| Event::Delete {
+                        range: edit.start..edit.end,
+                        deleted_text: state.get_text_range(edit.start, edit.end),
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    });
+                    let insert = (!edit.text.is_empty()).then_some(Event::Insert {
+                        position: edit.start,
+                        text: edit.text,
+                        cursor_id: CursorId::UNDO_SENTINEL,
+                    });
+                    delete.into_iter().chain(insert)
+                })
+                .collect();
+            if let Err(e) = self.apply_events_to_buffer_as_bulk_edit(
+                pending.buffer_id,
+                events,
+                "Before-save edits".to_string(),
+            ) {
+                self.set_status_message(t!("file.save_failed", error = e.to_string()).to_string());
+                return;
+            }
+        }
+
+        // Language server formatting still runs after the plugins' edits
+        if self.active_buffer() != pending.buffer_id || !self.request_format_on_save() {
+            self.save_formatted_buffer(pending.buffer_id);
+        }
+        if let Some(warning) = warning {
+            self.set_status_message(warning.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(start: usize, end: usize, text: &str) -> BeforeSaveEdit {
+        BeforeSaveEdit {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_edits_orders_back_to_front_and_drops_conflicts() {
+        let (edits, rejected) = plan_edits(
+            vec![
+                ("header".into(), edit(0, 0, "// license\n")),
+                ("fmt".into(), edit(4, 8, "x")),
+                ("fmt".into(), edit(10, 12, "")),
+                ("other".into(), edit(6, 9, "y")),
+                ("other".into(), edit(20, 30, "z")),
+            ],
+            20,
+        );
+        assert_eq!(
+            edits,
+            vec![
+                edit(10, 12, ""),
+                edit(4, 8, "x"),
+                edit(0, 0, "// license\n")
+            ]
+        );
+        assert_eq!(rejected, vec!["other".to_string()]);
+    }
+}
@@ -12,9 +12,10 @@ use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::app::warning_domains::WarningDomain;
-use crate::model::event::{BufferId, Event, SplitId};
+use crate::model::event::{BufferId, Event, SplitDirection, SplitId};
 use crate::state::EditorState;
 use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
@@ -22,6 +23,10 @@ use crate::view::split::SplitViewState;
 use super::help;
 use super::Editor;
 
+/// How long `open_file_in_background` waits for a file before showing a
+/// loading placeholder, so that small files open without flicker
+const OPEN_FILE_BLOCKING_WAIT: Duration = Duration::from_millis(50);
+
 impl Editor {
     /// Get the preferred split for opening a file.
     /// If the active split has no label, use it (normal case).
@@ -36,11 +41,29 @@ impl Editor {
 
     /// Open a file and return its buffer ID
     ///
-    /// If the file doesn't exist, creates an unsaved buffer with that filename.
-    /// Saving the buffer will create the file.
+    /// If the file doesn't exist, creates an unsaved buffer with that filename,
+    /// filled from its file template if there is one. Saving the buffer will
+    /// create the file.
     pub fn open_file(&mut self, path: &Path) -> anyhow::Result<BufferId> {
         let buffer_id = self.open_file_no_focus(path)?;
+        self.focus_opened_file(buffer_id, path);
 
+        // A file that doesn't exist yet starts from its template
+        let new_file = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .filter(|file| !self.filesystem.exists(file))
+            .map(Path::to_path_buf);
+        if let Some(file) = new_file {
+            self.apply_file_template(buffer_id, &file);
+        }
+        Ok(buffer_id)
+    }
+
+    /// Switch to a buffer just returned by `open_file_no_focus`, recording
+    /// position history and reporting the file in the status bar
+    fn focus_opened_file(&mut self, buffer_id: BufferId, path: &Path) {
         // Check if this was an already-open buffer or a new one
         // For already-open buffers, just switch to them
         // For new buffers, record position history before switching
@@ -68,21 +91,41 @@ impl Editor {
             .map(|m| m.display_name.clone())
             .unwrap_or_else(|| path.display().to_string());
 
-        // Check if buffer is binary for status message
-        let is_binary = self
+        if self.is_buffer_loading(buffer_id) {
+            self.status_message = Some(t!("buffer.loading", name = display_name).to_string());
+            return;
+        }
+
+        // Check if buffer is binary or large for status message
+        let (is_binary, is_large, size) = self
             .buffers
             .get(&buffer_id)
-            .map(|s| s.buffer.is_binary())
-            .unwrap_or(false);
+            .map(|s| {
+                (
+                    s.buffer.is_binary(),
+                    s.buffer.is_large_file(),
+                    s.buffer.len(),
+                )
+            })
+            .unwrap_or_default();
 
-        // Show appropriate status message for binary vs regular files
-        if is_binary {
+        // Show appropriate status message for images, binary, large and regular files
+        if self.is_image_preview(buffer_id) {
+            self.status_message = Some(t!("buffer.opened_image", name = display_name).to_string());
+        } else if is_binary {
             self.status_message = Some(t!("buffer.opened_binary", name = display_name).to_string());
+        } else if is_large {
+            self.status_message = Some(
+                t!(
+                    "buffer.opened_large",
+                    name = display_name,
+                    size = super::file_open::format_size(size as u64)
+                )
+                .to_string(),
+            );
         } else {
             self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
         }
-
-        Ok(buffer_id)
     }
 
     /// Open a file without switching focus to it
@@ -92,6 +135,226 @@ impl Editor {
     ///
     /// If the file doesn't exist, creates an unsaved buffer with that filename.
     pub fn open_file_no_focus(&mut self, path: &Path) -> anyhow::Result<BufferId> {
+        let (canonical_path, file_exists) = self.resolve_file_to_open(path);
+        let path = canonical_path.as_path();
+
+        // Check if the path is a directory (after following symlinks via canonicalize)
+        // Directories cannot be opened as files in the editor
+        // Use filesystem trait method to support remote files
+        if self.filesystem.is_dir(path).unwrap_or(false) {
+            anyhow::bail!(t!("buffer.cannot_open_directory"));
+        }
+
+        // Check if file is already open - return existing buffer without switching
+        let already_open = self
+            .buffers
+            .iter()
+            .find(|(_, state)| state.buffer.file_path() == Some(path))
+            .map(|(id, _)| *id);
+
+        if let Some(id) = already_open {
+            return Ok(id);
+        }
+
+        let buffer_id = self.buffer_id_for_opened_file();
+
+        // Create the editor state - either load from file or create empty buffer
+        tracing::info!(
+            "[SYNTAX DEBUG] open_file_no_focus: path={:?}, extension={:?}, registry_syntaxes={}, user_extensions={:?}",
+            path,
+            path.extension(),
+            self.grammar_registry.available_syntaxes().len(),
+            self.grammar_registry.user_extensions_debug()
+        );
+        let state = if file_exists {
+            EditorState::from_file_with_languages(
+                path,
+                self.terminal_width,
+                self.terminal_height,
+                self.config.editor.large_file_threshold_bytes as usize,
+                &self.grammar_registry,
+                &self.config.languages,
+                Arc::clone(&self.filesystem),
+            )?
+        } else {
+            // File doesn't exist - create empty buffer with the file path set
+            let mut new_state = EditorState::new(
+                self.terminal_width,
+                self.terminal_height,
+                self.config.editor.large_file_threshold_bytes as usize,
+                Arc::clone(&self.filesystem),
+            );
+            // Set the file path so saving will create the file
+            new_state.buffer.set_file_path(path.to_path_buf());
+            new_state
+        };
+        self.install_file_buffer(buffer_id, path, state);
+        Ok(buffer_id)
+    }
+
+    /// Open a file, reading it on a background thread
+    ///
+    /// Files that load within a few milliseconds open just like
+    /// [`Self::open_file`]. Slower files get a read-only placeholder tab marked
+    /// as loading, so the user can keep working in other buffers; the content
+    /// replaces it when `AsyncMessage::FileLoaded` arrives. Closing the tab
+    /// before then cancels the open.
+    pub fn open_file_in_background(&mut self, path: &Path) -> anyhow::Result<BufferId> {
+        let (canonical_path, file_exists) = self.resolve_file_to_open(path);
+        let already_open = self
+            .buffers
+            .values()
+            .any(|state| state.buffer.file_path() == Some(canonical_path.as_path()));
+        let (Some(runtime), Some(sender)) = (
+            self.tokio_runtime.as_ref().map(|r| r.handle().clone()),
+            self.async_bridge.as_ref().map(|b| b.sender()),
+        ) else {
+            return self.open_file(path);
+        };
+        if !file_exists || already_open || self.filesystem.is_dir(&canonical_path).unwrap_or(false)
+        {
+            return self.open_file(path);
+        }
+
+        let buffer_id = self.buffer_id_for_opened_file();
+        let (tx, rx) = std::sync::mpsc::channel();
+        {
+            let path = canonical_path.clone();
+            let threshold = self.config.editor.large_file_threshold_bytes as usize;
+            let filesystem = Arc::clone(&self.filesystem);
+            self.jobs.spawn_blocking(Some(buffer_id), move |token| {
+                let result =
+                    crate::model::buffer::Buffer::load_from_file(&path, threshold, filesystem);
+                if !token.is_cancelled() {
+                    let _ = tx.send(result);
+                }
+            });
+        }
+        let path = canonical_path.as_path();
+        match rx.recv_timeout(OPEN_FILE_BLOCKING_WAIT) {
+            Ok(result) => {
+                let buffer = result?;
+                let state = EditorState::from_loaded_file(
+                    buffer,
+                    path,
+                    &self.grammar_registry,
+                    &self.config.languages,
+                );
+                self.install_file_buffer(buffer_id, path, state);
+            }
+            Err(_) => {
+                let forwarded_path = canonical_path.clone();
+                runtime.spawn_blocking(move || {
+                    let result = rx
+                        .recv()
+                        .unwrap_or_else(|_| Err(anyhow::anyhow!("File loading was interrupted")));
+                    let _ = sender.send(crate::services::async_bridge::AsyncMessage::FileLoaded {
+                        buffer_id,
+                        path: forwarded_path,
+                        result,
+                    });
+                });
+                self.add_loading_placeholder(buffer_id, path);
+            }
+        }
+
+        self.focus_opened_file(buffer_id, path);
+        Ok(buffer_id)
+    }
+
+    /// Show an empty, read-only tab for a file that is still loading
+    fn add_loading_placeholder(&mut self, buffer_id: BufferId, path: &Path) {
+        let mut state = EditorState::new(
+            self.terminal_width,
+            self.terminal_height,
+            self.config.editor.large_file_threshold_bytes as usize,
+            Arc::clone(&self.filesystem),
+        );
+        state.buffer.set_file_path(path.to_path_buf());
+        state.editing_disabled = true;
+        state
+            .margins
+            .configure_for_line_numbers(self.config.editor.line_numbers);
+        self.buffers.insert(buffer_id, state);
+        self.event_logs
+            .insert(buffer_id, crate::model::event::EventLog::new());
+
+        let mut metadata =
+            super::types::BufferMetadata::with_file(path.to_path_buf(), &self.working_dir);
+        metadata.read_only = true;
+        metadata.loading = true;
+        self.buffer_metadata.insert(buffer_id, metadata);
+
+        let target_split = self.preferred_split_for_file();
+        if let Some(view_state) = self.split_view_states.get_mut(&target_split) {
+            view_state.add_buffer(buffer_id);
+            let buf_state = view_state.ensure_buffer_state(buffer_id);
+            buf_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+            buf_state.rulers = self.config.editor.rulers.clone();
+        }
+    }
+
+    /// Whether a buffer is a placeholder for a file still loading in the background
+    pub fn is_buffer_loading(&self, buffer_id: BufferId) -> bool {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.loading)
+    }
+
+    /// Replace a loading placeholder with the file read in the background
+    pub(super) fn handle_file_loaded(
+        &mut self,
+        buffer_id: BufferId,
+        path: PathBuf,
+        result: anyhow::Result<crate::model::buffer::Buffer>,
+    ) {
+        // The placeholder was closed (or reused) before the file finished loading
+        let still_waiting = self.is_buffer_loading(buffer_id)
+            && self
+                .buffer_metadata
+                .get(&buffer_id)
+                .and_then(|metadata| metadata.file_path())
+                == Some(&path);
+        if !still_waiting {
+            tracing::debug!("Discarding background load of {}", path.display());
+            return;
+        }
+
+        match result {
+            Ok(buffer) => {
+                let state = EditorState::from_loaded_file(
+                    buffer,
+                    &path,
+                    &self.grammar_registry,
+                    &self.config.languages,
+                );
+                self.install_file_buffer(buffer_id, &path, state);
+                if self.active_buffer() == buffer_id {
+                    let display_name = self
+                        .buffer_metadata
+                        .get(&buffer_id)
+                        .map(|m| m.display_name.clone())
+                        .unwrap_or_else(|| path.display().to_string());
+                    self.set_status_message(t!("buffer.opened", name = display_name).to_string());
+                }
+            }
+            Err(e) => {
+                let _ = self.force_close_buffer(buffer_id);
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    self.start_large_file_encoding_confirmation(confirmation);
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Absolute, canonical path of a file to open, and whether it exists
+    fn resolve_file_to_open(&self, path: &Path) -> (PathBuf, bool) {
         // Resolve relative paths against appropriate base directory
         // For remote mode, use the remote home directory; for local, use working_dir
         let base_dir = if self.filesystem.remote_connection_info().is_some() {
@@ -139,26 +402,12 @@ impl Editor {
                 resolved_path
             }
         };
-        let path = canonical_path.as_path();
-
-        // Check if the path is a directory (after following symlinks via canonicalize)
-        // Directories cannot be opened as files in the editor
-        // Use filesystem trait method to support remote files
-        if self.filesystem.is_dir(path).unwrap_or(false) {
-            anyhow::bail!(t!("buffer.cannot_open_directory"));
-        }
-
-        // Check if file is already open - return existing buffer without switching
-        let already_open = self
-            .buffers
-            .iter()
-            .find(|(_, state)| state.buffer.file_path() == Some(path))
-            .map(|(id, _)| *id);
-
-        if let Some(id) = already_open {
-            return Ok(id);
-        }
+        (canonical_path, file_exists)
+    }
 
+    /// Buffer ID for a file being opened: the current buffer when it is an
+    /// empty, unmodified scratch buffer, otherwise a new one
+    fn buffer_id_for_opened_file(&mut self) -> BufferId {
         // If the current buffer is empty and unmodified, replace it instead of creating a new one
         // Note: Don't replace composite buffers (they appear empty but are special views)
         let replace_current = {
@@ -169,7 +418,7 @@ impl Editor {
                 && current_state.buffer.file_path().is_none()
         };
 
-        let buffer_id = if replace_current {
+        if replace_current {
             // Reuse the current empty buffer
             self.active_buffer()
         } else {
@@ -177,38 +426,14 @@ impl Editor {
             let id = BufferId(self.next_buffer_id);
             self.next_buffer_id += 1;
             id
-        };
+        }
+    }
+
+    /// Add the state of a newly opened file under `buffer_id`, replacing any
+    /// loading placeholder, and set up LSP, watching and plugin hooks for it
+    fn install_file_buffer(&mut self, buffer_id: BufferId, path: &Path, mut state: EditorState) {
+        let was_loading = self.is_buffer_loading(buffer_id);
 
-        // Create the editor state - either load from file or create empty buffer
-        tracing::info!(
-            "[SYNTAX DEBUG] open_file_no_focus: path={:?}, extension={:?}, registry_syntaxes={}, user_extensions={:?}",
-            path,
-            path.extension(),
-            self.grammar_registry.available_syntaxes().len(),
-            self.grammar_registry.user_extensions_debug()
-        );
-        let mut state = if file_exists {
-            EditorState::from_file_with_languages(
-                path,
-                self.terminal_width,
-                self.terminal_height,
-                self.config.editor.large_file_threshold_bytes as usize,
-                &self.grammar_registry,
-                &self.config.languages,
-                Arc::clone(&self.filesystem),
-            )?
-        } else {
-            // File doesn't exist - create empty buffer with the file path set
-            let mut new_state = EditorState::new(
-                self.terminal_width,
-                self.terminal_height,
-                self.config.editor.large_file_threshold_bytes as usize,
-                Arc::clone(&self.filesystem),
-            );
-            // Set the file path so saving will create the file
-            new_state.buffer.set_file_path(path.to_path_buf());
-            new_state
-        };
         // Note: line_wrap_enabled is set on SplitViewState.viewport when the split is created
 
         // Check if the buffer contains binary content
@@ -219,6 +444,13 @@ impl Editor {
             tracing::info!("Detected binary file: {}", path.display());
         }
 
+        // Files we can't write to open read-only; toggle_read_only overrides it
+        state.read_only = self.open_read_only
+            || self
+                .filesystem
+                .metadata_if_exists(path)
+                .is_some_and(|meta| meta.is_readonly);
+
         // Set show_whitespace_tabs, use_tabs, and tab_size based on language config
         // with fallback to global editor config for tab_size
         // Use the buffer's stored language (already set by from_file_with_languages)
@@ -231,6 +463,8 @@ impl Editor {
         } else {
             state.buffer_settings.tab_size = self.config.editor.tab_size;
         }
+        self.apply_detected_indentation(&mut state);
+        self.apply_editorconfig(path, &mut state);
 
         // Apply line_numbers default from config
         state
@@ -241,6 +475,11 @@ impl Editor {
         self.event_logs
             .insert(buffer_id, crate::model::event::EventLog::new());
 
+        // Show images instead of their bytes
+        if is_binary {
+            self.show_image_preview(buffer_id, path);
+        }
+
         // Create metadata for this buffer
         let mut metadata =
             super::types::BufferMetadata::with_file(path.to_path_buf(), &self.working_dir);
@@ -260,9 +499,19 @@ impl Editor {
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
 
+        if !is_binary {
+            self.run_linters(buffer_id, false);
+        }
+
         // Add buffer to the preferred split's tabs (but don't switch to it)
         // Uses preferred_split_for_file() to avoid opening in labeled splits (e.g., sidebars)
-        let target_split = self.preferred_split_for_file();
+        // A loading placeholder stays in the split it was opened in
+        let placeholder_split = self
+            .split_view_states
+            .iter()
+            .find(|(_, view_state)| was_loading && view_state.has_buffer(buffer_id))
+            .map(|(split_id, _)| *split_id);
+        let target_split = placeholder_split.unwrap_or_else(|| self.preferred_split_for_file());
         if let Some(view_state) = self.split_view_states.get_mut(&target_split) {
             view_state.add_buffer(buffer_id);
             // Initialize per-buffer view state for the new buffer with config defaults
@@ -275,6 +524,9 @@ impl Editor {
         // This persists file positions across projects and editor instances
         self.restore_global_file_state(buffer_id, path, target_split);
 
+        // Show breakpoints set before the file was last closed
+        self.apply_breakpoint_indicators(buffer_id);
+
         // Emit control event
         self.emit_event(
             crate::model::control_event::events::FILE_OPENED.name,
@@ -287,7 +539,15 @@ impl Editor {
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
 
-        // Fire AfterFileOpen hook for plugins
+        // Plugins waiting for this language load before the hook reaches them
+        if let Some(language) = self.buffers.get(&buffer_id).map(|s| s.language.clone()) {
+            self.activate_plugins_for_language(&language);
+        }
+
+        // Fire AfterFileOpen hook for plugins, with the new buffer (and its
+        // length) already in the state snapshot they read
+        #[cfg(feature = "plugins")]
+        self.update_plugin_state_snapshot();
         self.plugin_manager.run_hook(
             "after_file_open",
             crate::services::plugins::hooks::HookArgs::AfterFileOpen {
@@ -295,8 +555,6 @@ impl Editor {
                 path: path.to_path_buf(),
             },
         );
-
-        Ok(buffer_id)
     }
 
     /// Open a local file (always uses local filesystem, not remote)
@@ -559,13 +817,9 @@ impl Editor {
             Arc::clone(&self.filesystem),
         )?;
 
-        // Create editor state with the buffer
-        let highlighter =
-            crate::primitives::highlight_engine::HighlightEngine::for_file_with_languages(
-                path,
-                &self.grammar_registry,
-                &self.config.languages,
-            );
+        // Create editor state with the buffer; large files are not highlighted
+        // (see EditorState::from_file)
+        let highlighter = crate::primitives::highlight_engine::HighlightEngine::None;
 
         let language = crate::primitives::highlighter::Language::from_path(path);
         let language_name = if let Some(lang) = &language {
@@ -897,6 +1151,7 @@ impl Editor {
         state.buffer.clear_file_path();
         // Clear modified flag - content is "fresh" from stdin (vim behavior)
         state.buffer.clear_modified();
+        state.read_only = self.open_read_only;
 
         // Set tab size from config
         state.buffer_settings.tab_size = self.config.editor.tab_size;
@@ -977,6 +1232,7 @@ impl Editor {
                 Some(t!("stdin.streaming_bytes", bytes = current_size).to_string());
             changed = true;
         }
+        let stream_buffer = stream_state.buffer_id;
 
         // Check if background thread has finished
         let thread_finished = stream_state
@@ -1007,6 +1263,10 @@ impl Editor {
             changed = true;
         }
 
+        if changed && self.pager_follow && self.active_buffer() == stream_buffer {
+            self.pager_follow_to_end();
+        }
+
         changed
     }
 
@@ -1202,77 +1462,6 @@ impl Editor {
         self.set_active_buffer(buffer_id);
     }
 
-    /// Open the keyboard shortcuts viewer in a read-only buffer
-    ///
-    /// If a keyboard shortcuts buffer already exists, switch to it instead of creating a new one.
-    /// The shortcuts are dynamically generated from the current keybindings configuration.
-    pub fn open_keyboard_shortcuts(&mut self) {
-        // Check if keyboard shortcuts buffer already exists
-        let existing_buffer = self
-            .buffer_metadata
-            .iter()
-            .find(|(_, m)| m.display_name == help::KEYBOARD_SHORTCUTS_BUFFER_NAME)
-            .map(|(id, _)| *id);
-
-        if let Some(buffer_id) = existing_buffer {
-            // Switch to existing buffer
-            self.set_active_buffer(buffer_id);
-            return;
-        }
-
-        // Get all keybindings
-        let bindings = self.keybindings.get_all_bindings();
-
-        // Format the keybindings as readable text
-        let mut content = String::from("Keyboard Shortcuts\n");
-        content.push_str("==================\n\n");
-        content.push_str("Press 'q' to close this buffer.\n\n");
-
-        // Group bindings by context (Normal, Prompt, etc.)
-        let mut current_context = String::new();
-        for (key, action) in &bindings {
-            // Check if action starts with a context prefix like "[Prompt] "
-            let (context, action_name) = if let Some(bracket_end) = action.find("] ") {
-                let ctx = &action[1..bracket_end];
-                let name = &action[bracket_end + 2..];
-                (ctx.to_string(), name.to_string())
-            } else {
-                ("Normal".to_string(), action.clone())
-            };
-
-            // Print context header when it changes
-            if context != current_context {
-                if !current_context.is_empty() {
-                    content.push('\n');
-                }
-                content.push_str(&format!("── {} Mode ──\n\n", context));
-                current_context = context;
-            }
-
-            // Format: "  Ctrl+S          Save"
-            content.push_str(&format!("  {:20} {}\n", key, action_name));
-        }
-
-        // Create new keyboard shortcuts buffer with "special" mode (has 'q' to close)
-        let buffer_id = self.create_virtual_buffer(
-            help::KEYBOARD_SHORTCUTS_BUFFER_NAME.to_string(),
-            "special".to_string(),
-            true,
-        );
-
-        // Set the content
-        if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            state.buffer.insert(0, &content);
-            state.buffer.clear_modified();
-            state.editing_disabled = true;
-
-            // Disable line numbers for cleaner display
-            state.margins.configure_for_line_numbers(false);
-        }
-
-        self.set_active_buffer(buffer_id);
-    }
-
     /// Show warnings by opening the warning log file directly
     ///
     /// If there are no warnings, shows a brief status message.
@@ -1375,8 +1564,16 @@ impl Editor {
 
     /// Internal helper to close a buffer (shared by close_buffer and force_close_buffer)
     fn close_buffer_internal(&mut self, id: BufferId) -> anyhow::Result<()> {
-        // Save file state before closing (for per-file session persistence)
-        self.save_file_state_on_close(id);
+        // Its background work (loading, searching) is no longer wanted
+        self.jobs.cancel_owned_by(id);
+
+        // A loading placeholder has no cursor or breakpoints of its own
+        if !self.is_buffer_loading(id) {
+            // Save file state before closing (for per-file session persistence)
+            self.save_file_state_on_close(id);
+            // Keep breakpoints where edits moved them
+            self.sync_breakpoints_from_buffer(id);
+        }
 
         // If closing a terminal buffer, clean up terminal-related data structures
         if let Some(terminal_id) = self.terminal_buffers.remove(&id) {
@@ -1463,6 +1660,7 @@ impl Editor {
                 .set_split_buffer(split_id, replacement_buffer);
         }
 
+        self.hide_image_preview(id);
         self.buffers.remove(&id);
         self.event_logs.remove(&id);
         self.seen_byte_ranges.remove(&id);
@@ -1477,6 +1675,7 @@ impl Editor {
         self.semantic_tokens_range_last_request.remove(&id);
         self.semantic_tokens_range_applied.remove(&id);
         self.semantic_tokens_full_debounce.remove(&id);
+        self.linting.forget(id);
 
         // Remove buffer from panel_ids mapping if it was a panel buffer
         // This prevents stale entries when the same panel_id is reused later
@@ -1488,11 +1687,31 @@ impl Editor {
             view_state.remove_from_history(id);
         }
 
+        // A built-in diff view owns its snapshot buffers
+        if let Some(sources) = self.git_diff_views.remove(&id) {
+            self.close_composite_buffer(id);
+            for source in sources {
+                let _ = self.close_buffer_internal(source);
+            }
+        }
+        self.unsaved_changes_views.remove(&id);
+        self.local_history_versions.remove(&id);
+        self.buffer_last_shown.remove(&id);
+        if self.is_diff_session(id) {
+            self.close_composite_buffer(id);
+            for source in self.end_diff_session(id) {
+                let _ = self.close_buffer_internal(source);
+            }
+        }
+
         // If this was the last visible buffer, focus file explorer
         if is_last_visible_buffer {
             self.focus_file_explorer();
         }
 
+        self.collab_buffer_closed(id);
+        self.quit_if_wait_files_closed();
+
         Ok(())
     }
 
@@ -2140,8 +2359,52 @@ impl Editor {
     /// code path as interactive file opens, providing consistent error handling
     /// (e.g., encoding confirmation prompts are shown in the UI instead of crashing).
     pub fn queue_file_open(&mut self, path: PathBuf, line: Option<usize>, column: Option<usize>) {
-        self.pending_file_opens
-            .push(super::PendingFileOpen { path, line, column });
+        self.pending_file_opens.push(super::PendingFileOpen {
+            path,
+            line,
+            column,
+            split: None,
+        });
+    }
+
+    /// Queue a file to be opened in a new split after the TUI starts (`-o`/`-O`).
+    pub fn queue_file_open_in_split(
+        &mut self,
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        direction: SplitDirection,
+    ) {
+        self.pending_file_opens.push(super::PendingFileOpen {
+            path,
+            line,
+            column,
+            split: Some(direction),
+        });
+    }
+
+    /// Quit once all of these files have been closed (`--wait`, for `$GIT_EDITOR`).
+    pub fn set_wait_for_files(&mut self, paths: &[PathBuf]) {
+        self.wait_for_files = paths
+            .iter()
+            .map(|path| self.resolve_file_to_open(path).0)
+            .collect();
+    }
+
+    /// Quit if every `--wait` file has been opened and closed again
+    fn quit_if_wait_files_closed(&mut self) {
+        if self.wait_for_files.is_empty() || !self.pending_file_opens.is_empty() {
+            return;
+        }
+        let still_open = self.buffer_metadata.values().any(|meta| {
+            meta.file_path()
+                .is_some_and(|path| self.wait_for_files.contains(path))
+        });
+        if !still_open {
+            tracing::info!("All --wait files closed, quitting");
+            self.wait_for_files.clear();
+            self.quit();
+        }
     }
 
     /// Process pending file opens (called from the event loop).
@@ -2156,6 +2419,7 @@ impl Editor {
         // Take all pending files to process
         let pending = std::mem::take(&mut self.pending_file_opens);
         let mut processed_any = false;
+        let opens_splits = pending.iter().any(|file| file.split.is_some());
 
         for pending_file in pending {
             tracing::info!(
@@ -2163,8 +2427,22 @@ impl Editor {
                 pending_file.path
             );
 
+            // The new split starts out showing the current buffer
+            let split_from = self.active_buffer();
+            match pending_file.split {
+                Some(SplitDirection::Horizontal) => self.split_pane_horizontal(),
+                Some(SplitDirection::Vertical) => self.split_pane_vertical(),
+                None => {}
+            }
+
             match self.open_file(&pending_file.path) {
-                Ok(_) => {
+                Ok(buffer_id) => {
+                    if pending_file.split.is_some() && buffer_id != split_from {
+                        let active_split = self.split_manager.active_split();
+                        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+                            view_state.remove_buffer(split_from);
+                        }
+                    }
                     // Navigate to line/column if specified
                     if let Some(line) = pending_file.line {
                         self.goto_line_col(line, pending_file.column);
@@ -2172,6 +2450,9 @@ impl Editor {
                     processed_any = true;
                 }
                 Err(e) => {
+                    if pending_file.split.is_some() {
+                        self.close_active_split();
+                    }
                     // Check if this is a large file encoding confirmation error
                     // Show prompt instead of crashing
                     if let Some(confirmation) =
@@ -2189,6 +2470,11 @@ impl Editor {
             }
         }
 
+        // Repeated splits halve the active pane; give each file an equal share
+        if opens_splits {
+            self.split_manager.distribute_splits_evenly();
+        }
+
         processed_any
     }
 }
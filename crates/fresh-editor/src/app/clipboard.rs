@@ -13,6 +13,7 @@ use crate::input::multi_cursor::{
 use crate::model::buffer::Buffer;
 use crate::model::cursor::Position2D;
 use crate::model::event::{CursorId, Event};
+use crate::primitives::indent_detection::reindent_block;
 use crate::primitives::word_navigation::{find_word_start_left, find_word_start_right};
 
 use super::Editor;
@@ -537,6 +538,8 @@ impl Editor {
             return;
         }
 
+        let normalized = self.reindent_paste(normalized);
+
         // Convert to buffer's line ending format
         let buffer_line_ending = self.active_state().buffer.line_ending();
         let paste_text = match buffer_line_ending {
@@ -545,6 +548,37 @@ impl Editor {
             crate::model::buffer::LineEnding::CR => normalized.replace('\n', "\r"),
         };
 
+        self.insert_text_at_cursors(paste_text, "Paste");
+        self.status_message = Some(t!("clipboard.pasted").to_string());
+    }
+
+    /// Shift multi-line pasted text to the indentation of the line the
+    /// single cursor is on, when `editor.reindent_on_paste` is set
+    fn reindent_paste(&mut self, text: String) -> String {
+        if !self.config.editor.reindent_on_paste
+            || !text.contains('\n')
+            || self.active_cursors().count() != 1
+        {
+            return text;
+        }
+        let cursor = *self.active_cursors().primary();
+        let position = cursor
+            .selection_range()
+            .map_or(cursor.position, |range| range.start);
+        let estimated_line_length = self.config.editor.estimated_line_length;
+        let state = self.active_state_mut();
+        let line_start = state
+            .buffer
+            .line_iterator(position, estimated_line_length)
+            .current_position();
+        let prefix = state.get_text_range(line_start, position);
+        let settings = &state.buffer_settings;
+        reindent_block(&text, &prefix, settings.use_tabs, settings.tab_size)
+    }
+
+    /// Insert text at every cursor, replacing selections, as one undo step
+    /// named `description`
+    pub(crate) fn insert_text_at_cursors(&mut self, insert_text: String, description: &str) {
         let mut events = Vec::new();
 
         // Collect cursor info sorted in reverse order by position
@@ -587,23 +621,22 @@ impl Editor {
             }
             events.push(Event::Insert {
                 position: insert_position,
-                text: paste_text.clone(),
+                text: insert_text.clone(),
                 cursor_id,
             });
         }
 
         // Apply events with atomic undo using bulk edit for O(n) performance
         if events.len() > 1 {
-            // Use optimized bulk edit for multi-cursor paste
-            if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Paste".to_string()) {
+            // Use optimized bulk edit for multi-cursor insertion
+            if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.to_string())
+            {
                 self.active_event_log_mut().append(bulk_edit);
             }
         } else if let Some(event) = events.into_iter().next() {
             self.active_event_log_mut().append(event.clone());
             self.apply_event_to_active_buffer(&event);
         }
-
-        self.status_message = Some(t!("clipboard.pasted").to_string());
     }
 
     /// Set clipboard content for testing purposes
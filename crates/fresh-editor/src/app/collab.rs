@@ -0,0 +1,1132 @@
+//! Collaborative editing sessions
+//!
+//! "Collab: Host Session" shares the active buffer over TCP and "Collab: Join
+//! Session" opens a host's buffer in a new one. Hosting listens on loopback
+//! unless another address is given, and guests must present the session's
+//! random token, which the host shares as `token@host:port`.
+//!
+//! Local edits are captured as the `Insert`/`Delete` events applied to the
+//! shared buffer and sent once per main-loop iteration; remote edits are
+//! transformed (see `services::collab::ot`) and applied without touching the
+//! event log.
+//!
+//! Each participant's cursor is drawn as an overlay in their color. Undo and
+//! redo in a shared buffer only revert the local participant's own edits:
+//! the inverse of every local edit is kept on a per-peer stack and
+//! transformed against everything applied after it.
+
+use super::Editor;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, Event, EventLog};
+use crate::services::collab::ot;
+use crate::services::collab::{
+    session_token, split_join_address, tokens_match, CollabEvent, CollabListener, CollabMessage,
+    ConnectionId, Participant, PeerId, PeerLink, HOST_PEER_ID,
+};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::prompt::PromptType;
+use ratatui::style::Color;
+use rust_i18n::t;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+/// Address offered when hosting; other machines can only join a session
+/// hosted on a non-loopback address
+const DEFAULT_HOST_ADDRESS: &str = "127.0.0.1:7878";
+
+/// Remote cursors are drawn above search and diagnostic highlights
+const CURSOR_PRIORITY: i32 = 90;
+const SELECTION_PRIORITY: i32 = 80;
+
+/// Accepted edits the host keeps for transforming late guest edits; a guest
+/// further behind than this is disconnected
+const MAX_HISTORY: usize = 1000;
+
+/// Participant colors, picked by peer id
+const PEER_COLORS: [(u8, u8, u8); 6] = [
+    (230, 126, 34),
+    (46, 204, 113),
+    (52, 152, 219),
+    (231, 76, 60),
+    (155, 89, 182),
+    (241, 196, 15),
+];
+
+fn peer_color(peer_id: PeerId) -> (u8, u8, u8) {
+    PEER_COLORS[peer_id as usize % PEER_COLORS.len()]
+}
+
+/// Selections are drawn in a darker shade of the participant color
+fn selection_color((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb(r / 3, g / 3, b / 3)
+}
+
+fn peer_namespace(peer_id: PeerId) -> OverlayNamespace {
+    OverlayNamespace::from_string(format!("collab-peer-{}", peer_id))
+}
+
+fn local_name() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "anonymous".to_string())
+}
+
+/// Collaboration state kept across sessions
+#[derive(Debug, Default)]
+pub(crate) struct CollabState {
+    session: Option<CollabSession>,
+    next_session_id: u64,
+}
+
+#[derive(Debug)]
+struct CollabSession {
+    id: u64,
+    /// The shared buffer (a guest has none until the host's welcome arrives)
+    buffer_id: Option<BufferId>,
+    peer_id: PeerId,
+    role: Role,
+    peers: BTreeMap<PeerId, Participant>,
+    /// Text of the shared buffer as of the last captured edit
+    shadow: String,
+    /// Buffer version matching `shadow`; a mismatch means an edit bypassed
+    /// event capture and is recovered by diffing
+    synced_version: u64,
+    /// Local edits captured since the last send
+    pending: Vec<Event>,
+    /// Inverses of local edits, transformed to apply to the current text
+    undo_stack: Vec<Vec<Event>>,
+    redo_stack: Vec<Vec<Event>>,
+    /// Last cursor sent to the other participants
+    sent_cursor: Option<(usize, Option<usize>)>,
+}
+
+#[derive(Debug)]
+enum Role {
+    Host {
+        _listener: CollabListener,
+        /// Secret guests must send in their `Hello`
+        token: String,
+        guests: HashMap<ConnectionId, Guest>,
+        history: History,
+        next_peer_id: PeerId,
+    },
+    Guest {
+        link: PeerLink,
+        address: String,
+        /// Revision of the last host edit applied here
+        revision: u64,
+        /// Sent edit the host has not acknowledged yet
+        outstanding: Option<Vec<Event>>,
+        /// Edits made while waiting for the acknowledgement
+        buffered: Vec<Event>,
+    },
+}
+
+/// The host's most recent accepted edits
+#[derive(Debug, Default)]
+struct History {
+    /// Revision the first kept edit applies to
+    start: u64,
+    /// `edits[i]` turns revision `start + i` into the next one
+    edits: Vec<Vec<Event>>,
+}
+
+impl History {
+    fn revision(&self) -> u64 {
+        self.start + self.edits.len() as u64
+    }
+
+    /// Record an accepted edit, returning the new revision
+    fn push(&mut self, events: Vec<Event>) -> u64 {
+        self.edits.push(events);
+        if self.edits.len() > MAX_HISTORY {
+            let dropped = self.edits.len() - MAX_HISTORY;
+            self.edits.drain(..dropped);
+            self.start += dropped as u64;
+        }
+        self.revision()
+    }
+
+    /// The edits made since `revision`, if they are still kept
+    fn since(&self, revision: u64) -> Option<&[Vec<Event>]> {
+        let index = usize::try_from(revision.checked_sub(self.start)?).ok()?;
+        self.edits.get(index..)
+    }
+}
+
+/// `text` as it was before `edits` were applied, newest last
+fn text_before<'a>(text: &'a str, edits: &[Vec<Event>]) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(text);
+    for events in edits.iter().rev() {
+        ot::apply_to_string(text.to_mut(), &ot::invert(events));
+    }
+    text
+}
+
+#[derive(Debug)]
+struct Guest {
+    link: PeerLink,
+    /// Set once the guest said hello
+    peer_id: Option<PeerId>,
+}
+
+/// What a local edit is, for the per-peer undo stacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LocalEdit {
+    Edit,
+    Undo,
+    Redo,
+}
+
+impl Role {
+    /// Send to every guest that has joined, except `skip`
+    fn broadcast(&self, message: &CollabMessage, skip: Option<ConnectionId>) {
+        if let Role::Host { guests, .. } = self {
+            for (connection, guest) in guests {
+                if Some(*connection) != skip && guest.peer_id.is_some() {
+                    guest.link.send(message.clone());
+                }
+            }
+        }
+    }
+}
+
+impl CollabSession {
+    fn transform_stacks(&mut self, applied: &[Event]) {
+        for stack in [&mut self.undo_stack, &mut self.redo_stack] {
+            for entry in stack.iter_mut() {
+                *entry = ot::transform(entry, applied, false).0;
+            }
+            stack.retain(|entry| !entry.is_empty());
+        }
+    }
+}
+
+impl Editor {
+    /// Whether a collaborative session is running
+    pub fn is_collaborating(&self) -> bool {
+        self.collab.session.is_some()
+    }
+
+    /// The buffer shared by the current session
+    pub fn collab_buffer(&self) -> Option<BufferId> {
+        self.collab.session.as_ref().and_then(|s| s.buffer_id)
+    }
+
+    /// Participants other than the local one
+    pub fn collab_participants(&self) -> Vec<Participant> {
+        self.collab
+            .session
+            .as_ref()
+            .map(|s| {
+                s.peers
+                    .values()
+                    .filter(|p| p.peer_id != s.peer_id)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Prompt for the address to host the active buffer on
+    pub fn collab_host_prompt(&mut self) {
+        if self.is_collaborating() {
+            self.set_status_message(t!("collab.already_active").to_string());
+            return;
+        }
+        self.start_prompt_with_initial_text(
+            t!("collab.host_prompt").to_string(),
+            PromptType::CollabHost,
+            DEFAULT_HOST_ADDRESS.to_string(),
+        );
+    }
+
+    /// Prompt for the address of a session to join
+    pub fn collab_join_prompt(&mut self) {
+        if self.is_collaborating() {
+            self.set_status_message(t!("collab.already_active").to_string());
+            return;
+        }
+        self.start_prompt(t!("collab.join_prompt").to_string(), PromptType::CollabJoin);
+    }
+
+    /// Share the active buffer, accepting guests on `address`
+    ///
+    /// Returns the address guests join with, `token@host:port`.
+    pub fn collab_host(&mut self, address: &str) -> Option<String> {
+        let address = address.trim();
+        let sender = self.async_bridge.as_ref().map(|b| b.sender())?;
+        let buffer_id = self.active_buffer();
+        let Some(shadow) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.to_string())
+        else {
+            self.set_status_message(t!("collab.buffer_not_loaded").to_string());
+            return None;
+        };
+
+        self.collab.next_session_id += 1;
+        let id = self.collab.next_session_id;
+        let listener = match crate::services::collab::transport::listen(address, id, sender) {
+            Ok(listener) => listener,
+            Err(e) => {
+                self.set_status_message(
+                    t!(
+                        "collab.host_failed",
+                        address = address,
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+                return None;
+            }
+        };
+        let token = session_token();
+        let join_address = format!("{}@{}", token, listener.local_addr());
+
+        let mut peers = BTreeMap::new();
+        peers.insert(
+            HOST_PEER_ID,
+            Participant {
+                peer_id: HOST_PEER_ID,
+                name: local_name(),
+            },
+        );
+        self.collab.session = Some(CollabSession {
+            id,
+            buffer_id: Some(buffer_id),
+            peer_id: HOST_PEER_ID,
+            role: Role::Host {
+                _listener: listener,
+                token,
+                guests: HashMap::new(),
+                history: History::default(),
+                next_peer_id: HOST_PEER_ID + 1,
+            },
+            peers,
+            shadow,
+            synced_version: self.collab_buffer_version(buffer_id),
+            pending: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            sent_cursor: None,
+        });
+
+        self.set_status_message(t!("collab.hosting", address = join_address.as_str()).to_string());
+        Some(join_address)
+    }
+
+    /// Join the session at `address`, given as `token@host:port`
+    pub fn collab_join(&mut self, address: &str) {
+        let (token, address) = split_join_address(address.trim());
+        if address.is_empty() {
+            return;
+        }
+        let Some(sender) = self.async_bridge.as_ref().map(|b| b.sender()) else {
+            return;
+        };
+        self.collab.next_session_id += 1;
+        let id = self.collab.next_session_id;
+        let link = match crate::services::collab::transport::connect(address, id, sender) {
+            Ok(link) => link,
+            Err(e) => {
+                self.set_status_message(
+                    t!(
+                        "collab.join_failed",
+                        address = address,
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+                return;
+            }
+        };
+        link.send(CollabMessage::Hello {
+            name: local_name(),
+            token: token.to_string(),
+        });
+        self.collab.session = Some(CollabSession {
+            id,
+            buffer_id: None,
+            peer_id: HOST_PEER_ID,
+            role: Role::Guest {
+                link,
+                address: address.to_string(),
+                revision: 0,
+                outstanding: None,
+                buffered: Vec::new(),
+            },
+            peers: BTreeMap::new(),
+            shadow: String::new(),
+            synced_version: 0,
+            pending: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            sent_cursor: None,
+        });
+        self.set_status_message(t!("collab.joining", address = address).to_string());
+    }
+
+    /// End the current session, keeping the buffer as a regular one
+    pub fn collab_leave(&mut self) {
+        if self.collab.session.is_none() {
+            self.set_status_message(t!("collab.not_active").to_string());
+            return;
+        }
+        self.collab_flush();
+        self.end_collab_session();
+        self.set_status_message(t!("collab.left").to_string());
+    }
+
+    fn end_collab_session(&mut self) {
+        let Some(session) = self.collab.session.take() else {
+            return;
+        };
+        // Dropping the session closes the listener and every connection
+        if let Some(buffer_id) = session.buffer_id {
+            for peer_id in session.peers.keys() {
+                self.clear_collab_cursor(buffer_id, *peer_id);
+            }
+            // Remote edits bypassed the event log, so its history no longer
+            // applies; a buffer that differs from disk stays modified
+            let mut event_log = EventLog::new();
+            if self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|s| s.buffer.is_modified())
+            {
+                event_log.clear_saved();
+            }
+            self.event_logs.insert(buffer_id, event_log);
+        }
+    }
+
+    /// Called when a buffer closes; closing the shared buffer ends the session
+    pub(super) fn collab_buffer_closed(&mut self, buffer_id: BufferId) {
+        if self.collab_buffer() == Some(buffer_id) {
+            self.end_collab_session();
+            self.set_status_message(t!("collab.left").to_string());
+        }
+    }
+
+    fn collab_buffer_version(&self, buffer_id: BufferId) -> u64 {
+        self.buffers
+            .get(&buffer_id)
+            .map(|s| s.buffer.version())
+            .unwrap_or(0)
+    }
+
+    fn collab_buffer_text(&self, buffer_id: BufferId) -> String {
+        self.buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Whether `event` is about to be applied to the shared buffer
+    fn collab_captures(&self, event: &Event) -> bool {
+        event.modifies_buffer()
+            && self
+                .collab
+                .session
+                .as_ref()
+                .is_some_and(|s| s.buffer_id == Some(self.active_buffer()))
+    }
+
+    /// Pick up edits that reached the shared buffer without going through
+    /// [`Self::collab_capture_local_edit`] (bulk edits, plugins, LSP)
+    fn collab_capture_uncaptured(&mut self) {
+        let Some(buffer_id) = self.collab_buffer() else {
+            return;
+        };
+        let version = self.collab_buffer_version(buffer_id);
+        if self.collab.session.as_ref().map(|s| s.synced_version) == Some(version) {
+            return;
+        }
+        let text = self.collab_buffer_text(buffer_id);
+        if let Some(session) = self.collab.session.as_mut() {
+            session.pending.extend(ot::diff(&session.shadow, &text));
+            session.shadow = text;
+            session.synced_version = version;
+        }
+    }
+
+    /// Called before an event is applied to the active buffer
+    pub(super) fn collab_before_local_edit(&mut self, event: &Event) {
+        if self.collab_captures(event) {
+            self.collab_capture_uncaptured();
+        }
+    }
+
+    /// Called after an event was applied to the active buffer
+    pub(super) fn collab_capture_local_edit(&mut self, event: &Event) {
+        if !self.collab_captures(event) {
+            return;
+        }
+        if matches!(event, Event::BulkEdit { .. }) {
+            // Snapshots carry no edit positions; diff instead
+            self.collab_capture_uncaptured();
+            return;
+        }
+        let version = self.collab_buffer_version(self.active_buffer());
+        if let Some(session) = self.collab.session.as_mut() {
+            let mut edits = Vec::new();
+            ot::edits_of(event, &mut edits);
+            ot::apply_to_string(&mut session.shadow, &edits);
+            session.pending.extend(edits);
+            session.synced_version = version;
+        }
+    }
+
+    /// Send captured local edits and the local cursor
+    ///
+    /// Runs once per main-loop iteration, before remote messages are handled.
+    pub(super) fn collab_flush(&mut self) {
+        let Some(buffer_id) = self.collab_buffer() else {
+            return;
+        };
+        self.collab_capture_uncaptured();
+        let pending = self
+            .collab
+            .session
+            .as_mut()
+            .map(|s| std::mem::take(&mut s.pending))
+            .unwrap_or_default();
+        if !pending.is_empty() {
+            self.collab_commit_local(pending, LocalEdit::Edit);
+        }
+
+        // Share the cursor when it moved in the shared buffer
+        if self.active_buffer() != buffer_id {
+            return;
+        }
+        let cursor = self.active_cursors().primary();
+        let current = (cursor.position, cursor.anchor);
+        let Some(session) = self.collab.session.as_mut() else {
+            return;
+        };
+        if session.sent_cursor == Some(current) {
+            return;
+        }
+        session.sent_cursor = Some(current);
+        let message = CollabMessage::Cursor {
+            peer_id: session.peer_id,
+            position: current.0,
+            anchor: current.1,
+        };
+        match &session.role {
+            Role::Host { .. } => session.role.broadcast(&message, None),
+            Role::Guest { link, .. } => {
+                link.send(message);
+            }
+        }
+    }
+
+    /// Record an applied local edit in the undo stacks and send it
+    fn collab_commit_local(&mut self, events: Vec<Event>, kind: LocalEdit) {
+        let Some(session) = self.collab.session.as_mut() else {
+            return;
+        };
+        session.transform_stacks(&events);
+        let inverse = ot::invert(&events);
+        match kind {
+            LocalEdit::Edit => {
+                session.undo_stack.push(inverse);
+                session.redo_stack.clear();
+            }
+            LocalEdit::Undo => session.redo_stack.push(inverse),
+            LocalEdit::Redo => session.undo_stack.push(inverse),
+        }
+
+        let peer_id = session.peer_id;
+        match &mut session.role {
+            Role::Host { history, .. } => {
+                let revision = history.push(events.clone());
+                let message = CollabMessage::Op {
+                    peer_id,
+                    revision,
+                    events,
+                };
+                session.role.broadcast(&message, None);
+            }
+            Role::Guest {
+                link,
+                revision,
+                outstanding,
+                buffered,
+                ..
+            } => {
+                if outstanding.is_some() {
+                    buffered.extend(events);
+                } else {
+                    link.send(CollabMessage::Op {
+                        peer_id,
+                        revision: *revision,
+                        events: events.clone(),
+                    });
+                    *outstanding = Some(events);
+                }
+            }
+        }
+    }
+
+    /// Whether undo/redo in the active buffer go through the session
+    pub(super) fn collab_owns_undo(&self) -> bool {
+        self.collab_buffer() == Some(self.active_buffer())
+    }
+
+    /// Revert the local participant's most recent edit
+    pub(super) fn collab_undo(&mut self) {
+        self.collab_undo_redo(LocalEdit::Undo);
+    }
+
+    /// Reapply the local participant's most recently undone edit
+    pub(super) fn collab_redo(&mut self) {
+        self.collab_undo_redo(LocalEdit::Redo);
+    }
+
+    fn collab_undo_redo(&mut self, kind: LocalEdit) {
+        self.collab_flush();
+        let Some(buffer_id) = self.collab_buffer() else {
+            return;
+        };
+        let Some(events) = self.collab.session.as_mut().and_then(|s| match kind {
+            LocalEdit::Undo => s.undo_stack.pop(),
+            _ => s.redo_stack.pop(),
+        }) else {
+            return;
+        };
+        self.apply_collab_events(buffer_id, &events);
+
+        // Leave the cursor where the change happened
+        let position = events.iter().rev().find_map(|e| match e {
+            Event::Insert { position, text, .. } => Some(position + text.len()),
+            Event::Delete { range, .. } => Some(range.start),
+            _ => None,
+        });
+        if let Some(position) = position {
+            let cursors = self.active_cursors_mut();
+            let primary = cursors.primary_mut();
+            primary.position = position;
+            primary.clear_selection();
+        }
+        self.collab_commit_local(events, kind);
+    }
+
+    /// Apply edits that did not originate from local input
+    ///
+    /// They are not recorded in the event log; cursors of every split showing
+    /// the buffer shift around them.
+    fn apply_collab_events(&mut self, buffer_id: BufferId, events: &[Event]) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let mut lsp_changes = Vec::new();
+        for event in events {
+            let (position, old_len, new_len, text) = match event {
+                Event::Insert { position, text, .. } => (*position, 0, text.len(), text.clone()),
+                Event::Delete { range, .. } => (range.start, range.len(), 0, String::new()),
+                _ => continue,
+            };
+            // Language servers get each edit in terms of the text before it
+            let (start_line, start_char) = state.buffer.position_to_lsp_position(position);
+            let (end_line, end_char) = state.buffer.position_to_lsp_position(position + old_len);
+            lsp_changes.push(lsp_types::TextDocumentContentChangeEvent {
+                range: Some(lsp_types::Range::new(
+                    lsp_types::Position::new(start_line as u32, start_char as u32),
+                    lsp_types::Position::new(end_line as u32, end_char as u32),
+                )),
+                range_length: None,
+                text,
+            });
+            state.apply(&mut Cursors::default(), event);
+            for split_id in self.split_manager.splits_for_buffer(buffer_id) {
+                if let Some(cursors) = self
+                    .split_view_states
+                    .get_mut(&split_id)
+                    .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+                    .map(|bvs| &mut bvs.cursors)
+                {
+                    cursors.adjust_for_edit(position, old_len, new_len);
+                }
+            }
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+        self.send_lsp_changes_for_buffer(buffer_id, lsp_changes);
+
+        let version = self.collab_buffer_version(buffer_id);
+        if let Some(session) = self.collab.session.as_mut() {
+            ot::apply_to_string(&mut session.shadow, events);
+            session.synced_version = version;
+        }
+    }
+
+    /// Apply an edit made by another participant
+    fn apply_remote_collab_edit(&mut self, events: &[Event]) {
+        let Some(buffer_id) = self.collab_buffer() else {
+            return;
+        };
+        self.apply_collab_events(buffer_id, events);
+        if let Some(session) = self.collab.session.as_mut() {
+            session.transform_stacks(events);
+        }
+    }
+
+    /// Handle a network event of a collaborative session
+    pub(super) fn handle_collab_event(&mut self, session_id: u64, event: CollabEvent) {
+        if self.collab.session.as_ref().map(|s| s.id) != Some(session_id) {
+            return;
+        }
+        let is_host = matches!(
+            self.collab.session.as_ref().map(|s| &s.role),
+            Some(Role::Host { .. })
+        );
+        if is_host {
+            self.handle_collab_host_event(event);
+        } else {
+            self.handle_collab_guest_event(event);
+        }
+    }
+
+    fn handle_collab_host_event(&mut self, event: CollabEvent) {
+        match event {
+            CollabEvent::Connected { connection, link } => {
+                if let Some(Role::Host { guests, .. }) =
+                    self.collab.session.as_mut().map(|s| &mut s.role)
+                {
+                    guests.insert(
+                        connection,
+                        Guest {
+                            link,
+                            peer_id: None,
+                        },
+                    );
+                }
+            }
+            CollabEvent::Message {
+                connection,
+                message,
+            } => self.handle_collab_guest_message(connection, message),
+            CollabEvent::Disconnected { connection, .. } => self.remove_collab_guest(connection),
+        }
+    }
+
+    /// Host: forget the guest on `connection`, closing the connection
+    fn remove_collab_guest(&mut self, connection: ConnectionId) {
+        let Some(session) = self.collab.session.as_mut() else {
+            return;
+        };
+        let Role::Host { guests, .. } = &mut session.role else {
+            return;
+        };
+        let Some(peer_id) = guests.remove(&connection).and_then(|g| g.peer_id) else {
+            return;
+        };
+        let name = session
+            .peers
+            .remove(&peer_id)
+            .map(|p| p.name)
+            .unwrap_or_default();
+        session
+            .role
+            .broadcast(&CollabMessage::PeerLeft { peer_id }, None);
+        if let Some(buffer_id) = session.buffer_id {
+            self.clear_collab_cursor(buffer_id, peer_id);
+        }
+        self.set_status_message(t!("collab.peer_left", name = name).to_string());
+    }
+
+    /// Host: handle a message from the guest on `connection`
+    fn handle_collab_guest_message(&mut self, connection: ConnectionId, message: CollabMessage) {
+        let guest_peer = match self.collab.session.as_ref().map(|s| &s.role) {
+            Some(Role::Host { guests, .. }) => match guests.get(&connection) {
+                Some(guest) => guest.peer_id,
+                None => return,
+            },
+            _ => return,
+        };
+
+        match message {
+            CollabMessage::Hello { name, token } => {
+                if guest_peer.is_some() {
+                    return;
+                }
+                if let Some(Role::Host {
+                    token: expected,
+                    guests,
+                    ..
+                }) = self.collab.session.as_mut().map(|s| &mut s.role)
+                {
+                    if !tokens_match(&token, expected) {
+                        tracing::warn!("Refusing collab guest {:?} with a wrong token", name);
+                        // Dropping the link closes the connection
+                        guests.remove(&connection);
+                        return;
+                    }
+                }
+                let Some(buffer_id) = self.collab_buffer() else {
+                    return;
+                };
+                let text = self.collab_buffer_text(buffer_id);
+                let Some(session) = self.collab.session.as_mut() else {
+                    return;
+                };
+                let Role::Host {
+                    guests,
+                    history,
+                    next_peer_id,
+                    ..
+                } = &mut session.role
+                else {
+                    return;
+                };
+                let peer_id = *next_peer_id;
+                *next_peer_id += 1;
+                let participant = Participant {
+                    peer_id,
+                    name: name.clone(),
+                };
+                if let Some(guest) = guests.get_mut(&connection) {
+                    guest.link.send(CollabMessage::Welcome {
+                        peer_id,
+                        revision: history.revision(),
+                        text,
+                        peers: session.peers.values().cloned().collect(),
+                    });
+                }
+                session.role.broadcast(
+                    &CollabMessage::PeerJoined {
+                        peer: participant.clone(),
+                    },
+                    Some(connection),
+                );
+                if let Role::Host { guests, .. } = &mut session.role {
+                    if let Some(guest) = guests.get_mut(&connection) {
+                        guest.peer_id = Some(peer_id);
+                    }
+                }
+                session.peers.insert(peer_id, participant);
+                // Show the newcomer where the host is
+                session.sent_cursor = None;
+                self.set_status_message(t!("collab.peer_joined", name = name).to_string());
+            }
+            CollabMessage::Op {
+                revision, events, ..
+            } => {
+                let Some(peer_id) = guest_peer else {
+                    return;
+                };
+                let Some(session) = self.collab.session.as_ref() else {
+                    return;
+                };
+                let Role::Host { history, .. } = &session.role else {
+                    return;
+                };
+                let Some(concurrent) = history.since(revision) else {
+                    tracing::warn!(
+                        "Disconnecting collab guest: edit against unknown revision {}",
+                        revision
+                    );
+                    self.remove_collab_guest(connection);
+                    return;
+                };
+                if !ot::is_valid(&text_before(&session.shadow, concurrent), &events) {
+                    tracing::warn!("Disconnecting collab guest: invalid edit {:?}", events);
+                    self.remove_collab_guest(connection);
+                    return;
+                }
+                // Host edits win ties, so guest edits go second
+                let events = concurrent
+                    .iter()
+                    .fold(events, |events, past| ot::transform(&events, past, false).0);
+
+                self.apply_remote_collab_edit(&events);
+
+                let Some(session) = self.collab.session.as_mut() else {
+                    return;
+                };
+                let Role::Host {
+                    guests, history, ..
+                } = &mut session.role
+                else {
+                    return;
+                };
+                let revision = history.push(events.clone());
+                if let Some(guest) = guests.get(&connection) {
+                    guest.link.send(CollabMessage::Ack { revision });
+                }
+                session.role.broadcast(
+                    &CollabMessage::Op {
+                        peer_id,
+                        revision,
+                        events,
+                    },
+                    Some(connection),
+                );
+            }
+            CollabMessage::Cursor {
+                position, anchor, ..
+            } => {
+                let Some(peer_id) = guest_peer else {
+                    return;
+                };
+                self.show_collab_cursor(peer_id, position, anchor);
+                if let Some(session) = self.collab.session.as_ref() {
+                    session.role.broadcast(
+                        &CollabMessage::Cursor {
+                            peer_id,
+                            position,
+                            anchor,
+                        },
+                        Some(connection),
+                    );
+                }
+            }
+            other => tracing::debug!("Unexpected collab message from guest: {:?}", other),
+        }
+    }
+
+    fn handle_collab_guest_event(&mut self, event: CollabEvent) {
+        match event {
+            CollabEvent::Connected { .. } => {}
+            CollabEvent::Message { message, .. } => self.handle_collab_host_message(message),
+            CollabEvent::Disconnected { error, .. } => {
+                if let Some(error) = error {
+                    tracing::warn!("Collab connection to host failed: {}", error);
+                }
+                let address = match self.collab.session.as_ref().map(|s| &s.role) {
+                    Some(Role::Guest { address, .. }) => address.clone(),
+                    _ => String::new(),
+                };
+                self.end_collab_session();
+                self.set_status_message(t!("collab.host_ended", address = address).to_string());
+            }
+        }
+    }
+
+    /// Guest: handle a message from the host
+    fn handle_collab_host_message(&mut self, message: CollabMessage) {
+        match message {
+            CollabMessage::Welcome {
+                peer_id,
+                revision: welcome_revision,
+                text,
+                peers,
+            } => {
+                let address = match self.collab.session.as_ref().map(|s| &s.role) {
+                    Some(Role::Guest { address, .. }) => address.clone(),
+                    _ => return,
+                };
+                let buffer_id = self.new_buffer();
+                if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    let event = Event::Insert {
+                        position: 0,
+                        text: text.clone(),
+                        cursor_id: crate::model::event::CursorId::UNDO_SENTINEL,
+                    };
+                    state.apply(&mut Cursors::default(), &event);
+                    state.buffer.set_modified(false);
+                }
+                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                    metadata.display_name = format!("[collab] {}", address);
+                }
+                self.invalidate_layouts_for_buffer(buffer_id);
+                let version = self.collab_buffer_version(buffer_id);
+                let host_name = peers
+                    .iter()
+                    .find(|p| p.peer_id == HOST_PEER_ID)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_default();
+                if let Some(session) = self.collab.session.as_mut() {
+                    session.buffer_id = Some(buffer_id);
+                    session.peer_id = peer_id;
+                    session.shadow = text;
+                    session.synced_version = version;
+                    session.peers = peers.into_iter().map(|p| (p.peer_id, p)).collect();
+                    session.peers.insert(
+                        peer_id,
+                        Participant {
+                            peer_id,
+                            name: local_name(),
+                        },
+                    );
+                    if let Role::Guest { revision, .. } = &mut session.role {
+                        *revision = welcome_revision;
+                    }
+                }
+                self.set_status_message(
+                    t!("collab.joined", name = host_name, address = address).to_string(),
+                );
+            }
+            CollabMessage::Op {
+                revision: op_revision,
+                events,
+                ..
+            } => {
+                let Some(session) = self.collab.session.as_mut() else {
+                    return;
+                };
+                let Role::Guest {
+                    address,
+                    revision,
+                    outstanding,
+                    buffered,
+                    ..
+                } = &mut session.role
+                else {
+                    return;
+                };
+                // The host's edit was made without our unacknowledged ones
+                let unacknowledged: Vec<Vec<Event>> = outstanding
+                    .iter()
+                    .cloned()
+                    .chain([buffered.clone()])
+                    .collect();
+                if !ot::is_valid(&text_before(&session.shadow, &unacknowledged), &events) {
+                    tracing::warn!(
+                        "Leaving collab session: invalid edit from host {:?}",
+                        events
+                    );
+                    let address = address.clone();
+                    self.end_collab_session();
+                    self.set_status_message(t!("collab.host_ended", address = address).to_string());
+                    return;
+                }
+                let mut events = events;
+                if let Some(sent) = outstanding.as_mut() {
+                    let (remote, local) = ot::transform(&events, sent, true);
+                    events = remote;
+                    *sent = local;
+                }
+                if !buffered.is_empty() {
+                    let (remote, local) = ot::transform(&events, buffered, true);
+                    events = remote;
+                    *buffered = local;
+                }
+                *revision = op_revision;
+                self.apply_remote_collab_edit(&events);
+            }
+            CollabMessage::Ack {
+                revision: ack_revision,
+            } => {
+                let Some(session) = self.collab.session.as_mut() else {
+                    return;
+                };
+                let peer_id = session.peer_id;
+                if let Role::Guest {
+                    link,
+                    revision,
+                    outstanding,
+                    buffered,
+                    ..
+                } = &mut session.role
+                {
+                    *revision = ack_revision;
+                    *outstanding = None;
+                    if !buffered.is_empty() {
+                        let events = std::mem::take(buffered);
+                        link.send(CollabMessage::Op {
+                            peer_id,
+                            revision: *revision,
+                            events: events.clone(),
+                        });
+                        *outstanding = Some(events);
+                    }
+                }
+            }
+            CollabMessage::Cursor {
+                peer_id,
+                position,
+                anchor,
+            } => self.show_collab_cursor(peer_id, position, anchor),
+            CollabMessage::PeerJoined { peer } => {
+                let name = peer.name.clone();
+                if let Some(session) = self.collab.session.as_mut() {
+                    session.peers.insert(peer.peer_id, peer);
+                }
+                self.set_status_message(t!("collab.peer_joined", name = name).to_string());
+            }
+            CollabMessage::PeerLeft { peer_id } => {
+                let name = self
+                    .collab
+                    .session
+                    .as_mut()
+                    .and_then(|s| s.peers.remove(&peer_id))
+                    .map(|p| p.name)
+                    .unwrap_or_default();
+                if let Some(buffer_id) = self.collab_buffer() {
+                    self.clear_collab_cursor(buffer_id, peer_id);
+                }
+                self.set_status_message(t!("collab.peer_left", name = name).to_string());
+            }
+            CollabMessage::Hello { .. } => {}
+        }
+    }
+
+    /// Draw a participant's cursor and selection in their color
+    fn show_collab_cursor(&mut self, peer_id: PeerId, position: usize, anchor: Option<usize>) {
+        let Some(buffer_id) = self.collab_buffer() else {
+            return;
+        };
+        let name = self
+            .collab
+            .session
+            .as_ref()
+            .and_then(|s| s.peers.get(&peer_id))
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+        self.clear_collab_cursor(buffer_id, peer_id);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let len = state.buffer.len();
+        let position = position.min(len);
+        let color = peer_color(peer_id);
+        let namespace = peer_namespace(peer_id);
+
+        if let Some(anchor) = anchor.map(|a| a.min(len)).filter(|a| *a != position) {
+            let range = anchor.min(position)..anchor.max(position);
+            let overlay = Overlay::with_namespace(
+                &mut state.marker_list,
+                range,
+                OverlayFace::Background {
+                    color: selection_color(color),
+                },
+                namespace.clone(),
+            )
+            .with_priority_value(SELECTION_PRIORITY)
+            .with_message(name.clone());
+            state.overlays.add(overlay);
+        }
+        // A one-character block; at the end of the buffer there is nothing to
+        // cover, so mark the last character instead
+        let range = if position < len {
+            position..position + 1
+        } else {
+            position.saturating_sub(1)..position
+        };
+        if range.is_empty() {
+            return;
+        }
+        let overlay = Overlay::with_namespace(
+            &mut state.marker_list,
+            range,
+            OverlayFace::Background {
+                color: Color::Rgb(color.0, color.1, color.2),
+            },
+            namespace,
+        )
+        .with_priority_value(CURSOR_PRIORITY)
+        .with_message(name);
+        state.overlays.add(overlay);
+    }
+
+    fn clear_collab_cursor(&mut self, buffer_id: BufferId, peer_id: PeerId) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .overlays
+                .clear_namespace(&peer_namespace(peer_id), &mut state.marker_list);
+        }
+    }
+}
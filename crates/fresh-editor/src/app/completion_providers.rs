@@ -0,0 +1,211 @@
+//! Plugin completion providers (`registerCompletionProvider`).
+//!
+//! Providers answer completion requests like additional language servers:
+//! each gets a request id from the LSP id space, and its items are merged
+//! into the same popup once every source has answered.
+
+use fresh_core::api::{CompletionContext, PluginCompletionItem};
+
+use crate::model::event::BufferId;
+use crate::primitives::word_navigation::find_completion_word_start;
+
+use super::Editor;
+
+/// A completion provider registered by a plugin
+#[derive(Debug, Clone)]
+pub(crate) struct CompletionProvider {
+    plugin_name: String,
+    provider_id: u64,
+    /// Languages to complete in; empty means every language
+    languages: Vec<String>,
+    trigger_chars: Vec<char>,
+    /// Source label shown next to the provider's items
+    label: String,
+    /// Rank against other sources; language servers rank 0
+    priority: i32,
+}
+
+impl CompletionProvider {
+    fn applies_to(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+/// Convert a plugin's item to an LSP item, labelled with its source
+fn to_lsp_item(item: PluginCompletionItem, source: &str) -> lsp_types::CompletionItem {
+    use lsp_types::CompletionItemKind;
+    let kind = item.kind.as_deref().and_then(|kind| match kind {
+        "function" => Some(CompletionItemKind::FUNCTION),
+        "method" => Some(CompletionItemKind::METHOD),
+        "variable" => Some(CompletionItemKind::VARIABLE),
+        "field" => Some(CompletionItemKind::FIELD),
+        "class" => Some(CompletionItemKind::CLASS),
+        "struct" => Some(CompletionItemKind::STRUCT),
+        "constant" => Some(CompletionItemKind::CONSTANT),
+        "keyword" => Some(CompletionItemKind::KEYWORD),
+        "snippet" => Some(CompletionItemKind::SNIPPET),
+        "text" => Some(CompletionItemKind::TEXT),
+        _ => None,
+    });
+    lsp_types::CompletionItem {
+        label: item.label,
+        insert_text: item.insert_text,
+        filter_text: item.filter_text,
+        detail: item.detail,
+        kind,
+        label_details: Some(lsp_types::CompletionItemLabelDetails {
+            detail: None,
+            description: Some(source.to_string()),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Detail shown for a completion item: its own detail and its label
+/// description, which for plugin items names the source they came from
+pub(crate) fn completion_item_detail(item: &lsp_types::CompletionItem) -> Option<String> {
+    let source = item
+        .label_details
+        .as_ref()
+        .and_then(|details| details.description.as_deref());
+    match (item.detail.as_deref(), source) {
+        (Some(detail), Some(source)) => Some(format!("{} · {}", detail, source)),
+        (Some(detail), None) => Some(detail.to_string()),
+        (None, Some(source)) => Some(source.to_string()),
+        (None, None) => None,
+    }
+}
+
+impl Editor {
+    pub(super) fn handle_register_completion_provider(
+        &mut self,
+        plugin_name: String,
+        provider_id: u64,
+        languages: Vec<String>,
+        trigger_chars: Vec<char>,
+        label: String,
+        priority: i32,
+    ) {
+        self.completion_providers.push(CompletionProvider {
+            plugin_name,
+            provider_id,
+            languages,
+            trigger_chars,
+            label,
+            priority,
+        });
+    }
+
+    pub(super) fn handle_unregister_completion_provider(
+        &mut self,
+        plugin_name: &str,
+        provider_id: u64,
+    ) {
+        self.completion_providers
+            .retain(|p| !(p.plugin_name == plugin_name && p.provider_id == provider_id));
+    }
+
+    /// Drop the completion providers of an unloaded plugin
+    pub(super) fn clear_plugin_completion_providers(&mut self, plugin_name: &str) {
+        self.completion_providers
+            .retain(|p| p.plugin_name != plugin_name);
+    }
+
+    /// Whether a plugin provider for `language` opens completion on `c`
+    pub(super) fn is_plugin_completion_trigger(&self, c: char, language: &str) -> bool {
+        self.completion_providers
+            .iter()
+            .any(|p| p.applies_to(language) && p.trigger_chars.contains(&c))
+    }
+
+    /// Ask the plugin providers for the active buffer's language for items
+    ///
+    /// Returns each request id with its provider's priority.
+    pub(super) fn request_plugin_completions(
+        &mut self,
+        buffer_id: BufferId,
+        position: usize,
+    ) -> Vec<(i32, u64)> {
+        let language = self.active_state().language.clone();
+        let providers: Vec<CompletionProvider> = self
+            .completion_providers
+            .iter()
+            .filter(|p| p.applies_to(&language))
+            .cloned()
+            .collect();
+        if providers.is_empty() {
+            return Vec::new();
+        }
+
+        let word_start = find_completion_word_start(&self.active_state().buffer, position);
+        let prefix = self.active_state_mut().get_text_range(word_start, position);
+        providers
+            .into_iter()
+            .map(|provider| {
+                let request_id = self.next_lsp_request_id;
+                self.next_lsp_request_id += 1;
+                self.completion_sources
+                    .insert(request_id, provider.label.clone());
+                self.plugin_manager.provide_completions(
+                    provider.plugin_name,
+                    provider.provider_id,
+                    request_id,
+                    CompletionContext {
+                        buffer_id,
+                        language: language.clone(),
+                        position,
+                        prefix: prefix.clone(),
+                    },
+                );
+                (provider.priority, request_id)
+            })
+            .collect()
+    }
+
+    pub(super) fn handle_plugin_completion_items(
+        &mut self,
+        request_id: u64,
+        items: Vec<PluginCompletionItem>,
+    ) {
+        let Some(source) = self.completion_sources.remove(&request_id) else {
+            return;
+        };
+        let items = items
+            .into_iter()
+            .map(|item| to_lsp_item(item, &source))
+            .collect();
+        if let Err(e) = self.handle_completion_response(request_id, items) {
+            tracing::warn!("Failed to show plugin completions: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_items_carry_their_source() {
+        let item = to_lsp_item(
+            PluginCompletionItem {
+                label: "println!".into(),
+                insert_text: Some("println!(\"{}\")".into()),
+                filter_text: None,
+                detail: Some("macro".into()),
+                kind: Some("function".into()),
+            },
+            "snippets",
+        );
+        assert_eq!(item.kind, Some(lsp_types::CompletionItemKind::FUNCTION));
+        assert_eq!(
+            completion_item_detail(&item).as_deref(),
+            Some("macro · snippets")
+        );
+
+        let lsp_item = lsp_types::CompletionItem {
+            label: "len".into(),
+            ..Default::default()
+        };
+        assert_eq!(completion_item_detail(&lsp_item), None);
+    }
+}
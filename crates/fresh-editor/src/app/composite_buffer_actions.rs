@@ -317,7 +317,7 @@ impl Editor {
 
     /// Get the effective viewport height for composite buffer scrolling.
     /// This accounts for the composite header row showing pane labels (e.g., "OLD (HEAD)" / "NEW (Working)")
-    fn get_composite_viewport_height(&self, split_id: SplitId) -> usize {
+    pub(super) fn get_composite_viewport_height(&self, split_id: SplitId) -> usize {
         const COMPOSITE_HEADER_HEIGHT: u16 = 1;
         const DEFAULT_VIEWPORT_HEIGHT: usize = 24;
 
@@ -685,7 +685,11 @@ impl Editor {
     }
 
     /// Sync the EditorState cursor with CompositeViewState (for status bar display)
-    fn sync_editor_cursor_from_composite(&mut self, split_id: SplitId, buffer_id: BufferId) {
+    pub(super) fn sync_editor_cursor_from_composite(
+        &mut self,
+        split_id: SplitId,
+        buffer_id: BufferId,
+    ) {
         let (cursor_row, cursor_column, focused_pane) = self
             .composite_view_states
             .get(&(split_id, buffer_id))
@@ -773,6 +777,17 @@ impl Editor {
                 Some(true)
             }
 
+            // Typing into the editable panes of a --diff/--merge session
+            // (DeleteBackward and InsertChar are routed from their own handlers)
+            Action::InsertNewline | Action::DeleteForward if self.is_diff_session(buffer_id) => {
+                use super::diff_mode::DiffEdit;
+                let edit = match action {
+                    Action::InsertNewline => DiffEdit::Insert("\n".to_string()),
+                    _ => DiffEdit::DeleteForward,
+                };
+                Some(self.diff_session_edit(buffer_id, edit))
+            }
+
             // Copy from the focused pane
             Action::Copy => {
                 self.handle_composite_copy(split_id, buffer_id);
@@ -795,7 +810,7 @@ impl Editor {
                 CursorMovement::Right,
                 false,
             ),
-            Action::MoveLineStart => self.handle_cursor_movement_action(
+            Action::MoveLineStart | Action::SmartHome => self.handle_cursor_movement_action(
                 split_id,
                 buffer_id,
                 CursorMovement::LineStart,
@@ -807,13 +822,13 @@ impl Editor {
                 CursorMovement::LineEnd,
                 false,
             ),
-            Action::MoveWordLeft => self.handle_cursor_movement_action(
+            Action::MoveWordLeft | Action::MoveSubWordLeft => self.handle_cursor_movement_action(
                 split_id,
                 buffer_id,
                 CursorMovement::WordLeft,
                 false,
             ),
-            Action::MoveWordRight => self.handle_cursor_movement_action(
+            Action::MoveWordRight | Action::MoveSubWordRight => self.handle_cursor_movement_action(
                 split_id,
                 buffer_id,
                 CursorMovement::WordRight,
@@ -839,30 +854,28 @@ impl Editor {
             Action::SelectRight => {
                 self.handle_cursor_movement_action(split_id, buffer_id, CursorMovement::Right, true)
             }
-            Action::SelectLineStart => self.handle_cursor_movement_action(
-                split_id,
-                buffer_id,
-                CursorMovement::LineStart,
-                true,
-            ),
+            Action::SelectLineStart | Action::SelectSmartHome => self
+                .handle_cursor_movement_action(
+                    split_id,
+                    buffer_id,
+                    CursorMovement::LineStart,
+                    true,
+                ),
             Action::SelectLineEnd => self.handle_cursor_movement_action(
                 split_id,
                 buffer_id,
                 CursorMovement::LineEnd,
                 true,
             ),
-            Action::SelectWordLeft => self.handle_cursor_movement_action(
-                split_id,
-                buffer_id,
-                CursorMovement::WordLeft,
-                true,
-            ),
-            Action::SelectWordRight => self.handle_cursor_movement_action(
-                split_id,
-                buffer_id,
-                CursorMovement::WordRight,
-                true,
-            ),
+            Action::SelectWordLeft | Action::SelectSubWordLeft => self
+                .handle_cursor_movement_action(split_id, buffer_id, CursorMovement::WordLeft, true),
+            Action::SelectWordRight | Action::SelectSubWordRight => self
+                .handle_cursor_movement_action(
+                    split_id,
+                    buffer_id,
+                    CursorMovement::WordRight,
+                    true,
+                ),
             Action::SelectWordEnd => self.handle_cursor_movement_action(
                 split_id,
                 buffer_id,
@@ -0,0 +1,142 @@
+//! Requests from the control socket (`fresh --listen`)
+//!
+//! See `services::control_socket` for the protocol. Requests are carried out
+//! the way the matching plugin API calls are.
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::services::control_socket::{self, RemoteError, RemoteRequest, RemoteResponse};
+use rust_i18n::t;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+impl Editor {
+    /// Listen for control requests on `socket`, or on the default socket for
+    /// the working directory
+    ///
+    /// Failures are reported in the status bar; the editor works without it.
+    pub fn start_control_socket(&mut self, socket: Option<&Path>) {
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            return;
+        };
+        let path = match socket {
+            Some(path) => Ok(path.to_path_buf()),
+            None => control_socket::default_socket_path(&self.working_dir),
+        };
+        match path.and_then(|path| control_socket::listen(&path, sender)) {
+            Ok(listener) => {
+                tracing::info!("Listening for control requests on {:?}", listener.path());
+                self.control_socket = Some(listener);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to start the control socket: {}", e);
+                self.set_status_message(
+                    t!("control_socket.listen_failed", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Path of the control socket, if the editor is listening
+    pub fn control_socket_path(&self) -> Option<&Path> {
+        self.control_socket.as_ref().map(|socket| socket.path())
+    }
+
+    /// Carry out a request from the control socket
+    pub(super) fn handle_remote_request(&mut self, request: RemoteRequest) -> RemoteResponse {
+        match request {
+            RemoteRequest::OpenFile { path, line, column } => {
+                let path = if path.is_relative() {
+                    self.working_dir.join(path)
+                } else {
+                    path
+                };
+                let buffer_id = self
+                    .open_file(&path)
+                    .map_err(|e| RemoteError::failed(e.to_string()))?;
+                if line.is_some() || column.is_some() {
+                    self.jump_to_line_column(line, column);
+                }
+                Ok(json!(buffer_id.0))
+            }
+            RemoteRequest::GotoPosition { line, column } => {
+                if line == 0 {
+                    return Err(RemoteError::invalid_params("lines start at 1"));
+                }
+                self.goto_line_col(line, column);
+                Ok(Value::Null)
+            }
+            RemoteRequest::InsertText {
+                text,
+                buffer_id,
+                position,
+            } => {
+                let buffer_id = buffer_id.map_or_else(|| self.active_buffer(), BufferId);
+                let state = self
+                    .buffers
+                    .get(&buffer_id)
+                    .ok_or_else(|| RemoteError::invalid_params("no such buffer"))?;
+                if state.read_only {
+                    return Err(RemoteError::failed("the buffer is read-only"));
+                }
+                match position {
+                    Some(position) if position > state.buffer.len() => {
+                        Err(RemoteError::invalid_params("position is past the end"))
+                    }
+                    Some(position) if state.buffer.snap_to_char_boundary(position) != position => {
+                        Err(RemoteError::invalid_params(
+                            "position is inside a character",
+                        ))
+                    }
+                    Some(position) => {
+                        self.handle_insert_text(buffer_id, position, text);
+                        Ok(Value::Null)
+                    }
+                    None if buffer_id != self.active_buffer() => Err(RemoteError::invalid_params(
+                        "position is needed for other buffers",
+                    )),
+                    None => {
+                        self.handle_insert_at_cursor(text);
+                        Ok(Value::Null)
+                    }
+                }
+            }
+            RemoteRequest::ListBuffers => {
+                let active = self.active_buffer();
+                let mut buffers: Vec<(&BufferId, _)> = self.buffers.iter().collect();
+                buffers.sort_by_key(|(id, _)| id.0);
+                Ok(buffers
+                    .into_iter()
+                    .map(|(id, state)| {
+                        json!({
+                            "id": id.0,
+                            "path": state.buffer.file_path(),
+                            "modified": state.buffer.is_modified(),
+                            "length": state.buffer.len(),
+                            "isVirtual": self
+                                .buffer_metadata
+                                .get(id)
+                                .is_some_and(|metadata| metadata.is_virtual()),
+                            "active": *id == active,
+                        })
+                    })
+                    .collect())
+            }
+            RemoteRequest::RunCommand { name } => {
+                let action = Action::from_str(&name, &HashMap::new())
+                    .or_else(|| {
+                        let registry = self.command_registry.read().ok()?;
+                        registry.find_by_name(&name).map(|command| command.action)
+                    })
+                    .ok_or_else(|| {
+                        RemoteError::invalid_params(format!("unknown command '{}'", name))
+                    })?;
+                self.handle_action(action)
+                    .map_err(|e| RemoteError::failed(e.to_string()))?;
+                Ok(Value::Null)
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Damage tracking for partial redraws.
+//!
+//! Most changes can affect anything on screen, so the whole frame is rendered
+//! again. Some frequent ones only change the status bar, such as language
+//! server progress and selection statistics being counted. For those, only the
+//! status bar is rendered, over a copy of the previous frame; the terminal
+//! backend then writes just the cells that differ.
+//!
+//! Damage isn't tracked per line: split panes are laid out and drawn as a
+//! whole, so edits, cursor moves and scrolling all render the full frame.
+//! Only the cells they changed reach the terminal.
+
+use ratatui::buffer::Buffer;
+use ratatui::Frame;
+
+use super::Editor;
+
+/// Parts of the screen that changed since the last frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Damage {
+    /// Nothing changed
+    #[default]
+    None,
+    /// Only the status bar changed
+    StatusBar,
+    /// Anything may have changed
+    Full,
+}
+
+impl Damage {
+    /// Add the parts damaged by `other`
+    pub fn add(&mut self, other: Damage) {
+        *self = (*self).max(other);
+    }
+
+    /// Whether anything needs to be drawn
+    pub fn is_damaged(self) -> bool {
+        self != Damage::None
+    }
+}
+
+impl Editor {
+    /// Damage done by the async messages handled by the last call to
+    /// `process_async_messages`
+    pub fn take_async_damage(&mut self) -> Damage {
+        std::mem::take(&mut self.async_damage)
+    }
+
+    /// Render only the status bar, over `previous`, the last frame drawn
+    ///
+    /// Returns false without rendering when the status bar can't be drawn on
+    /// its own: it is hidden, the screen was resized, or something drawn over
+    /// it (a prompt, popup, menu, modal or dimming) may have changed. The
+    /// whole frame must be rendered then.
+    pub fn render_status_bar_only(&mut self, frame: &mut Frame, previous: &Buffer) -> bool {
+        let size = frame.area();
+        if previous.area != size
+            || size.width != self.cached_layout.last_frame_width
+            || size.height != self.cached_layout.last_frame_height
+        {
+            return false;
+        }
+        let Some((y, x, width)) = self.cached_layout.status_bar_area else {
+            return false;
+        };
+        if self.status_bar_covered(y) {
+            return false;
+        }
+
+        frame.buffer_mut().clone_from(previous);
+        self.render_status_bar(frame, ratatui::layout::Rect::new(x, y, width, 1));
+        crate::primitives::display_width::skip_ambiguous_padding(frame.buffer_mut());
+        crate::view::color_support::convert_buffer_colors(
+            frame.buffer_mut(),
+            self.color_capability,
+        );
+        true
+    }
+
+    /// Whether anything may be drawn over the status bar on row `y`
+    fn status_bar_covered(&self, y: u16) -> bool {
+        self.prompt.is_some()
+            || self.active_state().popups.is_visible()
+            || self.menu_state.active_menu.is_some()
+            || self.tab_context_menu.is_some()
+            || self.settings_state.as_ref().is_some_and(|s| s.visible)
+            || self.calibration_wizard.is_some()
+            || self.keybinding_editor.is_some()
+            || self.event_debug.is_some()
+            || self.perf_hud.is_some()
+            || (self.keyboard_capture && self.terminal_mode)
+            || (self.gpm_active && self.mouse_cursor_position.is_some_and(|(_, row)| row == y))
+    }
+}
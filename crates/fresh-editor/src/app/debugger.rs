@@ -0,0 +1,1160 @@
+//! Debugger: breakpoints, debug sessions, variables panel and debug console
+//!
+//! Breakpoints are kept per file and drawn as gutter indicators, so they
+//! follow edits while the file is open. "Start Debugging" runs a
+//! configuration from `.fresh/launch.toml`: the adapter is initialized, the
+//! program launched (or attached to), and the breakpoints are sent once the
+//! adapter reports `initialized`. Every `stopped` event fetches the top stack
+//! frame, its scopes and their variables for the variables panel, and
+//! re-evaluates the watch expressions. Program output and evaluation results
+//! go to the debug console.
+
+use super::Editor;
+use crate::input::buffer_mode::{DEBUG_CONSOLE_MODE, DEBUG_VARIABLES_MODE};
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::dap::launch::{launch_file_path, parse_launch_configs};
+use crate::services::dap::{DapClient, DapMessage, LaunchConfig, LaunchRequest};
+use crate::view::margin::LineIndicator;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Name of the read-only buffer showing variables and watches
+pub const DEBUG_VARIABLES_BUFFER_NAME: &str = "*Debug Variables*";
+
+/// Name of the read-only buffer showing program output and evaluations
+pub const DEBUG_CONSOLE_BUFFER_NAME: &str = "*Debug Console*";
+
+/// Gutter indicator namespaces
+const BREAKPOINT_NAMESPACE: &str = "breakpoint";
+const EXECUTION_NAMESPACE: &str = "debug-execution";
+
+/// Above git (10) and modified-line (5) indicators
+const BREAKPOINT_PRIORITY: i32 = 30;
+const EXECUTION_PRIORITY: i32 = 40;
+
+/// Number of lines kept in the debug console
+const MAX_CONSOLE_LINES: usize = 5000;
+
+/// Nesting shown for expanded variables (guards against cyclic structures)
+const MAX_VARIABLE_DEPTH: usize = 8;
+
+/// Text property holding the `variablesReference` of an expandable variable
+const VARIABLE_PROPERTY: &str = "debug_variable";
+
+/// Text property holding the expression of a watch line
+const WATCH_PROPERTY: &str = "debug_watch";
+
+/// Debugger state kept across sessions
+#[derive(Debug, Default)]
+pub(crate) struct DebuggerState {
+    session: Option<DebugSession>,
+    /// 0-based breakpoint lines per file
+    breakpoints: BTreeMap<PathBuf, BTreeSet<usize>>,
+    watches: Vec<String>,
+    console: Vec<String>,
+    next_session_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionState {
+    /// Waiting for the adapter's `initialized` event
+    Starting,
+    Running,
+    Stopped,
+}
+
+/// Request whose response needs handling
+#[derive(Debug)]
+enum Pending {
+    Initialize,
+    Launch,
+    Threads,
+    StackTrace,
+    Scopes,
+    Variables(i64),
+    Watch(String),
+    Evaluate,
+}
+
+#[derive(Debug)]
+struct Frame {
+    id: i64,
+    name: String,
+    path: Option<PathBuf>,
+    /// 1-based
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug)]
+struct Scope {
+    name: String,
+    variables_reference: i64,
+}
+
+#[derive(Debug)]
+struct Variable {
+    name: String,
+    value: String,
+    variables_reference: i64,
+}
+
+#[derive(Debug)]
+struct DebugSession {
+    id: u64,
+    client: DapClient,
+    config: LaunchConfig,
+    state: SessionState,
+    supports_configuration_done: bool,
+    pending: HashMap<i64, Pending>,
+    thread_id: Option<i64>,
+    stop_reason: Option<String>,
+    frame: Option<Frame>,
+    scopes: Vec<Scope>,
+    /// Children of each fetched variables reference
+    variables: HashMap<i64, Vec<Variable>>,
+    expanded: HashSet<i64>,
+    watch_values: HashMap<String, Result<String, String>>,
+}
+
+impl DebugSession {
+    fn request(&mut self, command: &str, arguments: Value, pending: Option<Pending>) {
+        let seq = self.client.request(command, arguments);
+        if let Some(pending) = pending {
+            self.pending.insert(seq, pending);
+        }
+    }
+
+    /// Forget everything that is only valid while the program is stopped
+    fn resume(&mut self) {
+        self.state = SessionState::Running;
+        self.stop_reason = None;
+        self.frame = None;
+        self.scopes.clear();
+        self.variables.clear();
+        self.expanded.clear();
+        self.watch_values.clear();
+    }
+
+    fn evaluate_watch(&mut self, expression: &str) {
+        let Some(frame_id) = self.frame.as_ref().map(|f| f.id) else {
+            return;
+        };
+        self.request(
+            "evaluate",
+            json!({"expression": expression, "frameId": frame_id, "context": "watch"}),
+            Some(Pending::Watch(expression.to_string())),
+        );
+    }
+}
+
+fn parse_frame(body: &Value) -> Option<Frame> {
+    let frame = body.get("stackFrames")?.as_array()?.first()?;
+    Some(Frame {
+        id: frame.get("id")?.as_i64()?,
+        name: frame
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        path: frame
+            .pointer("/source/path")
+            .and_then(Value::as_str)
+            .map(PathBuf::from),
+        line: frame.get("line").and_then(Value::as_u64).unwrap_or(1) as usize,
+        column: frame.get("column").and_then(Value::as_u64).unwrap_or(1) as usize,
+    })
+}
+
+fn parse_scopes(body: &Value) -> Vec<(Scope, bool)> {
+    body.get("scopes")
+        .and_then(Value::as_array)
+        .map(|scopes| {
+            scopes
+                .iter()
+                .filter_map(|scope| {
+                    let expensive = scope
+                        .get("expensive")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    Some((
+                        Scope {
+                            name: scope.get("name")?.as_str()?.to_string(),
+                            variables_reference: scope.get("variablesReference")?.as_i64()?,
+                        },
+                        expensive,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_variables(body: &Value) -> Vec<Variable> {
+    body.get("variables")
+        .and_then(Value::as_array)
+        .map(|variables| {
+            variables
+                .iter()
+                .filter_map(|variable| {
+                    Some(Variable {
+                        name: variable.get("name")?.as_str()?.to_string(),
+                        value: variable
+                            .get("value")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        variables_reference: variable
+                            .get("variablesReference")
+                            .and_then(Value::as_i64)
+                            .unwrap_or(0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Error text of a failed response
+fn response_error(message: Option<String>, body: &Value) -> String {
+    body.pointer("/error/format")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .or(message)
+        .unwrap_or_else(|| "request failed".to_string())
+}
+
+impl Editor {
+    // ==================== Breakpoints ====================
+
+    /// Toggle a breakpoint on the cursor line
+    pub fn debug_toggle_breakpoint(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf)
+        else {
+            self.set_status_message(t!("debug.no_file").to_string());
+            return;
+        };
+        self.sync_breakpoints_from_buffer(buffer_id);
+
+        let position = self.active_cursors().primary().position;
+        let line = self.active_state().buffer.get_line_number(position);
+        let lines = self.debugger.breakpoints.entry(path.clone()).or_default();
+        if !lines.remove(&line) {
+            lines.insert(line);
+        }
+        if lines.is_empty() {
+            self.debugger.breakpoints.remove(&path);
+        }
+
+        self.apply_breakpoint_indicators(buffer_id);
+        self.send_breakpoints(&path);
+    }
+
+    /// 0-based breakpoint lines of a file
+    pub fn breakpoint_lines(&self, path: &Path) -> Vec<usize> {
+        self.debugger
+            .breakpoints
+            .get(path)
+            .map(|lines| lines.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Draw the breakpoints of a buffer's file in its gutter
+    pub(super) fn apply_breakpoint_indicators(&mut self, buffer_id: BufferId) {
+        let color = self.theme.diagnostic_error_fg;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state
+            .margins
+            .clear_line_indicators_for_namespace(BREAKPOINT_NAMESPACE);
+        let Some(lines) = state
+            .buffer
+            .file_path()
+            .and_then(|path| self.debugger.breakpoints.get(path))
+        else {
+            return;
+        };
+        for &line in lines {
+            if let Some(offset) = state.buffer.line_start_offset(line) {
+                state.margins.set_line_indicator(
+                    offset,
+                    BREAKPOINT_NAMESPACE.to_string(),
+                    LineIndicator::new("●", color, BREAKPOINT_PRIORITY),
+                );
+            }
+        }
+    }
+
+    /// Update a file's breakpoint lines from its gutter indicators, which
+    /// moved with any edits since they were set
+    pub(super) fn sync_breakpoints_from_buffer(&mut self, buffer_id: BufferId) {
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let Some(path) = state.buffer.file_path() else {
+            return;
+        };
+        let lines: BTreeSet<usize> = state
+            .margins
+            .line_indicator_positions(BREAKPOINT_NAMESPACE)
+            .into_iter()
+            .map(|offset| state.buffer.get_line_number(offset))
+            .collect();
+        if lines.is_empty() {
+            self.debugger.breakpoints.remove(path);
+        } else {
+            self.debugger.breakpoints.insert(path.to_path_buf(), lines);
+        }
+    }
+
+    /// Send a file's breakpoints to the adapter, if a session is configured
+    fn send_breakpoints(&mut self, path: &Path) {
+        let lines = self.breakpoint_lines(path);
+        let Some(session) = self.debugger.session.as_mut() else {
+            return;
+        };
+        if session.state == SessionState::Starting {
+            // Sent with the others once the adapter is initialized
+            return;
+        }
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        session.request(
+            "setBreakpoints",
+            json!({
+                "source": {"name": name, "path": path},
+                "breakpoints": lines.iter().map(|l| json!({"line": l + 1})).collect::<Vec<_>>(),
+                "lines": lines.iter().map(|l| l + 1).collect::<Vec<_>>(),
+            }),
+            None,
+        );
+    }
+
+    // ==================== Session control ====================
+
+    /// Whether a debug session is active
+    pub fn is_debugging(&self) -> bool {
+        self.debugger.session.is_some()
+    }
+
+    /// Start debugging, or continue when the program is paused
+    pub fn debug_start(&mut self) {
+        if let Some(session) = self.debugger.session.as_ref() {
+            if session.state == SessionState::Stopped {
+                self.debug_control("continue");
+            } else {
+                self.set_status_message(t!("debug.already_running").to_string());
+            }
+            return;
+        }
+
+        let Some(configs) = self.load_launch_configs() else {
+            return;
+        };
+        match configs.as_slice() {
+            [] => {
+                let path = launch_file_path(&self.working_dir);
+                self.set_status_message(
+                    t!("debug.no_configurations", path = path.display().to_string()).to_string(),
+                );
+            }
+            [config] => {
+                let config = config.clone();
+                self.start_debug_session(config);
+            }
+            _ => {
+                let suggestions = configs
+                    .iter()
+                    .map(|config| crate::input::commands::Suggestion {
+                        text: config.name.clone(),
+                        description: Some(format!(
+                            "{} ({})",
+                            config.adapter.command,
+                            config.request.as_str()
+                        )),
+                        value: Some(config.name.clone()),
+                        disabled: false,
+                        keybinding: None,
+                        source: None,
+                    })
+                    .collect();
+                self.start_prompt_with_suggestions(
+                    t!("debug.select_configuration").to_string(),
+                    PromptType::SelectDebugConfiguration,
+                    suggestions,
+                );
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.selected_suggestion = Some(0);
+                }
+            }
+        }
+    }
+
+    /// Start the configuration chosen in the prompt
+    pub(super) fn start_debug_configuration(&mut self, name: &str) {
+        let Some(configs) = self.load_launch_configs() else {
+            return;
+        };
+        match configs.into_iter().find(|config| config.name == name) {
+            Some(config) => self.start_debug_session(config),
+            None => {
+                self.set_status_message(t!("debug.unknown_configuration", name = name).to_string())
+            }
+        }
+    }
+
+    fn load_launch_configs(&mut self) -> Option<Vec<LaunchConfig>> {
+        let path = launch_file_path(&self.working_dir);
+        let text = match self.filesystem.read_file(&path) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(_) => {
+                self.set_status_message(
+                    t!("debug.no_configurations", path = path.display().to_string()).to_string(),
+                );
+                return None;
+            }
+        };
+        match parse_launch_configs(&text) {
+            Ok(configs) => Some(configs),
+            Err(e) => {
+                self.set_status_message(
+                    t!(
+                        "debug.invalid_configurations",
+                        path = path.display().to_string(),
+                        error = e
+                    )
+                    .to_string(),
+                );
+                None
+            }
+        }
+    }
+
+    fn start_debug_session(&mut self, config: LaunchConfig) {
+        let file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf);
+        let config = config.resolve(&self.working_dir, file.as_deref());
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            self.set_status_message(
+                t!("debug.start_failed", error = "no async runtime").to_string(),
+            );
+            return;
+        };
+
+        self.debugger.next_session_id += 1;
+        let id = self.debugger.next_session_id;
+        let client = match DapClient::spawn(
+            runtime.handle(),
+            id,
+            &config.adapter.command,
+            &config.adapter.args,
+            &self.working_dir,
+            bridge.sender(),
+        ) {
+            Ok(client) => client,
+            Err(e) => {
+                self.debug_console_line(e.clone());
+                self.set_status_message(t!("debug.start_failed", error = e).to_string());
+                return;
+            }
+        };
+
+        let adapter_id = Path::new(&config.adapter.command)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = config.name.clone();
+        let mut session = DebugSession {
+            id,
+            client,
+            config,
+            state: SessionState::Starting,
+            supports_configuration_done: false,
+            pending: HashMap::new(),
+            thread_id: None,
+            stop_reason: None,
+            frame: None,
+            scopes: Vec::new(),
+            variables: HashMap::new(),
+            expanded: HashSet::new(),
+            watch_values: HashMap::new(),
+        };
+        session.request(
+            "initialize",
+            json!({
+                "clientID": "fresh",
+                "clientName": "Fresh",
+                "adapterID": adapter_id,
+                "pathFormat": "path",
+                "linesStartAt1": true,
+                "columnsStartAt1": true,
+                "supportsVariableType": true,
+                "supportsRunInTerminalRequest": false,
+            }),
+            Some(Pending::Initialize),
+        );
+        self.debugger.session = Some(session);
+
+        self.debug_console_line(format!("── {} ──", name));
+        self.set_status_message(t!("debug.starting", name = name).to_string());
+        self.refresh_debug_variables();
+    }
+
+    /// Stop the debug session, terminating a launched program
+    pub fn debug_stop(&mut self) {
+        let Some(session) = self.debugger.session.as_mut() else {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        };
+        let terminate = session.config.request == LaunchRequest::Launch;
+        session.request("disconnect", json!({"terminateDebuggee": terminate}), None);
+        self.end_debug_session();
+    }
+
+    pub fn debug_continue(&mut self) {
+        self.debug_control("continue");
+    }
+
+    pub fn debug_step_over(&mut self) {
+        self.debug_control("next");
+    }
+
+    pub fn debug_step_into(&mut self) {
+        self.debug_control("stepIn");
+    }
+
+    pub fn debug_step_out(&mut self) {
+        self.debug_control("stepOut");
+    }
+
+    /// Resume the paused thread with `command`
+    fn debug_control(&mut self, command: &str) {
+        let Some(session) = self.debugger.session.as_mut() else {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        };
+        let (SessionState::Stopped, Some(thread_id)) = (session.state, session.thread_id) else {
+            self.set_status_message(t!("debug.not_paused").to_string());
+            return;
+        };
+        session.request(command, json!({"threadId": thread_id}), None);
+        session.resume();
+        self.clear_execution_indicator();
+        self.refresh_debug_variables();
+    }
+
+    fn end_debug_session(&mut self) {
+        // Dropping the client shuts the adapter down after pending writes
+        if self.debugger.session.take().is_none() {
+            return;
+        }
+        self.clear_execution_indicator();
+        self.debug_console_line("── session ended ──".to_string());
+        self.set_status_message(t!("debug.stopped").to_string());
+        self.refresh_debug_variables();
+    }
+
+    // ==================== Adapter messages ====================
+
+    pub(super) fn handle_dap_message(&mut self, session_id: u64, message: DapMessage) {
+        if self.debugger.session.as_ref().map(|s| s.id) != Some(session_id) {
+            // Late message from a session that already ended
+            return;
+        }
+        match message {
+            DapMessage::Response {
+                request_seq,
+                command,
+                success,
+                message,
+                body,
+            } => self.handle_dap_response(request_seq, &command, success, message, body),
+            DapMessage::Event { event, body } => self.handle_dap_event(&event, body),
+            DapMessage::Stderr(line) => self.debug_console_line(line),
+            DapMessage::Exited { error } => {
+                if let Some(error) = error {
+                    self.debug_console_line(error);
+                }
+                self.end_debug_session();
+            }
+        }
+    }
+
+    fn handle_dap_response(
+        &mut self,
+        request_seq: i64,
+        command: &str,
+        success: bool,
+        message: Option<String>,
+        body: Value,
+    ) {
+        let Some(session) = self.debugger.session.as_mut() else {
+            return;
+        };
+        let pending = session.pending.remove(&request_seq);
+
+        if !success {
+            let error = response_error(message, &body);
+            match pending {
+                Some(Pending::Watch(expression)) => {
+                    session.watch_values.insert(expression, Err(error));
+                    self.refresh_debug_variables();
+                }
+                Some(Pending::Initialize | Pending::Launch) => {
+                    self.debug_console_line(format!("{}: {}", command, error));
+                    self.set_status_message(t!("debug.start_failed", error = error).to_string());
+                    self.debug_stop();
+                }
+                _ => self.debug_console_line(format!("{}: {}", command, error)),
+            }
+            return;
+        }
+
+        match pending {
+            Some(Pending::Initialize) => {
+                session.supports_configuration_done = body
+                    .get("supportsConfigurationDoneRequest")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let request = session.config.request.as_str();
+                let arguments = session.config.arguments.clone();
+                session.request(request, arguments, Some(Pending::Launch));
+            }
+            Some(Pending::Threads) => {
+                session.thread_id = body.pointer("/threads/0/id").and_then(Value::as_i64);
+                if let Some(thread_id) = session.thread_id {
+                    session.request(
+                        "stackTrace",
+                        json!({"threadId": thread_id, "startFrame": 0, "levels": 1}),
+                        Some(Pending::StackTrace),
+                    );
+                }
+            }
+            Some(Pending::StackTrace) => {
+                session.frame = parse_frame(&body);
+                let Some(frame) = session.frame.as_ref() else {
+                    self.refresh_debug_variables();
+                    return;
+                };
+                let frame_id = frame.id;
+                let location = frame
+                    .path
+                    .clone()
+                    .map(|path| (path, frame.line, frame.column));
+                session.request(
+                    "scopes",
+                    json!({"frameId": frame_id}),
+                    Some(Pending::Scopes),
+                );
+                for expression in self.debugger.watches.clone() {
+                    if let Some(session) = self.debugger.session.as_mut() {
+                        session.evaluate_watch(&expression);
+                    }
+                }
+                if let Some((path, line, column)) = location {
+                    self.show_execution_point(&path, line, column);
+                }
+                self.refresh_debug_variables();
+            }
+            Some(Pending::Scopes) => {
+                for (scope, expensive) in parse_scopes(&body) {
+                    if !expensive && scope.variables_reference > 0 {
+                        session.expanded.insert(scope.variables_reference);
+                        session.request(
+                            "variables",
+                            json!({"variablesReference": scope.variables_reference}),
+                            Some(Pending::Variables(scope.variables_reference)),
+                        );
+                    }
+                    session.scopes.push(scope);
+                }
+                self.refresh_debug_variables();
+            }
+            Some(Pending::Variables(reference)) => {
+                session.variables.insert(reference, parse_variables(&body));
+                self.refresh_debug_variables();
+            }
+            Some(Pending::Watch(expression)) => {
+                let result = body
+                    .get("result")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                session.watch_values.insert(expression, Ok(result));
+                self.refresh_debug_variables();
+            }
+            Some(Pending::Evaluate) => {
+                let result = body
+                    .get("result")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                self.debug_console_line(result);
+            }
+            Some(Pending::Launch) | None => {}
+        }
+    }
+
+    fn handle_dap_event(&mut self, event: &str, body: Value) {
+        let Some(session) = self.debugger.session.as_mut() else {
+            return;
+        };
+        match event {
+            "initialized" => {
+                session.state = SessionState::Running;
+                for buffer_id in self.buffers.keys().copied().collect::<Vec<_>>() {
+                    self.sync_breakpoints_from_buffer(buffer_id);
+                }
+                for path in self
+                    .debugger
+                    .breakpoints
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                {
+                    self.send_breakpoints(&path);
+                }
+                if let Some(session) = self.debugger.session.as_mut() {
+                    if session.supports_configuration_done {
+                        session.request("configurationDone", json!({}), None);
+                    }
+                }
+                self.refresh_debug_variables();
+            }
+            "stopped" => {
+                session.resume();
+                session.state = SessionState::Stopped;
+                session.stop_reason = body.get("reason").and_then(Value::as_str).map(String::from);
+                if let Some(thread_id) = body.get("threadId").and_then(Value::as_i64) {
+                    session.thread_id = Some(thread_id);
+                }
+                match session.thread_id {
+                    Some(thread_id) => session.request(
+                        "stackTrace",
+                        json!({"threadId": thread_id, "startFrame": 0, "levels": 1}),
+                        Some(Pending::StackTrace),
+                    ),
+                    None => session.request("threads", json!({}), Some(Pending::Threads)),
+                }
+                let reason = session.stop_reason.clone().unwrap_or_default();
+                self.set_status_message(t!("debug.paused", reason = reason).to_string());
+                self.refresh_debug_variables();
+            }
+            "continued" => {
+                session.resume();
+                self.clear_execution_indicator();
+                self.refresh_debug_variables();
+            }
+            "output" => {
+                let category = body.get("category").and_then(Value::as_str);
+                if category == Some("telemetry") {
+                    return;
+                }
+                if let Some(output) = body.get("output").and_then(Value::as_str) {
+                    for line in output.strip_suffix('\n').unwrap_or(output).lines() {
+                        self.debug_console_line(line.to_string());
+                    }
+                }
+            }
+            "exited" => {
+                if let Some(code) = body.get("exitCode").and_then(Value::as_i64) {
+                    self.debug_console_line(format!("Program exited with code {}", code));
+                }
+            }
+            "terminated" => {
+                session.request("disconnect", json!({}), None);
+                self.end_debug_session();
+            }
+            _ => tracing::trace!("Ignoring debug adapter event {}", event),
+        }
+    }
+
+    // ==================== Execution point ====================
+
+    /// Open the paused location and mark it in the gutter
+    fn show_execution_point(&mut self, path: &Path, line: usize, column: usize) {
+        self.clear_execution_indicator();
+        if !self.filesystem.exists(path) {
+            return;
+        }
+        let buffer_id = match self.open_file(path) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                tracing::warn!("Failed to open {}: {}", path.display(), e);
+                return;
+            }
+        };
+        self.goto_line_col(line, Some(column));
+
+        let color = self.theme.diagnostic_warning_fg;
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            if let Some(offset) = state.buffer.line_start_offset(line.saturating_sub(1)) {
+                state.margins.set_line_indicator(
+                    offset,
+                    EXECUTION_NAMESPACE.to_string(),
+                    LineIndicator::new("▶", color, EXECUTION_PRIORITY),
+                );
+            }
+        }
+    }
+
+    fn clear_execution_indicator(&mut self) {
+        for state in self.buffers.values_mut() {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(EXECUTION_NAMESPACE);
+        }
+    }
+
+    // ==================== Variables panel ====================
+
+    /// Open the variables and watch panel
+    pub fn show_debug_variables(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(DEBUG_VARIABLES_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    DEBUG_VARIABLES_BUFFER_NAME.to_string(),
+                    DEBUG_VARIABLES_MODE.to_string(),
+                    true,
+                )
+            });
+        self.fill_debug_variables(buffer_id);
+        self.set_active_buffer(buffer_id);
+    }
+
+    fn refresh_debug_variables(&mut self) {
+        if let Some(buffer_id) = self.find_buffer_by_name(DEBUG_VARIABLES_BUFFER_NAME) {
+            self.fill_debug_variables(buffer_id);
+        }
+    }
+
+    fn fill_debug_variables(&mut self, buffer_id: BufferId) {
+        let mut entries = Vec::new();
+        let header = match self.debugger.session.as_ref() {
+            None => "Debugger: not running".to_string(),
+            Some(session) => {
+                let state = match session.state {
+                    SessionState::Starting => "starting".to_string(),
+                    SessionState::Running => "running".to_string(),
+                    SessionState::Stopped => match session.stop_reason.as_deref() {
+                        Some(reason) => format!("paused ({})", reason),
+                        None => "paused".to_string(),
+                    },
+                };
+                let mut header = format!("Debugger: {} - {}", session.config.name, state);
+                if let Some(frame) = session.frame.as_ref() {
+                    header.push_str(&format!(" in {}", frame.name));
+                    if let Some(path) = frame.path.as_ref() {
+                        let path = path.strip_prefix(&self.working_dir).unwrap_or(path);
+                        header.push_str(&format!(" ({}:{})", path.display(), frame.line));
+                    }
+                }
+                header
+            }
+        };
+        entries.push(TextPropertyEntry::text(format!(
+            "{}\nEnter: expand/collapse   a: add watch   d: remove watch   q: close\n\nWatch\n",
+            header
+        )));
+
+        let session = self.debugger.session.as_ref();
+        if self.debugger.watches.is_empty() {
+            entries.push(TextPropertyEntry::text("  (none)\n"));
+        }
+        for expression in &self.debugger.watches {
+            let value = match session.and_then(|s| s.watch_values.get(expression)) {
+                Some(Ok(value)) => value.clone(),
+                Some(Err(error)) => format!("<{}>", error),
+                None => "-".to_string(),
+            };
+            entries.push(
+                TextPropertyEntry::text(format!("  {} = {}\n", expression, value))
+                    .with_property(WATCH_PROPERTY, Value::String(expression.clone())),
+            );
+        }
+
+        if let Some(session) = session {
+            for scope in &session.scopes {
+                entries.push(TextPropertyEntry::text(format!("\n{}\n", scope.name)));
+                if session.expanded.contains(&scope.variables_reference) {
+                    push_variables(&mut entries, session, scope.variables_reference, 1);
+                }
+            }
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::error!("Failed to fill debug variables buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+    }
+
+    /// Expand or collapse the variable under the cursor
+    pub fn debug_toggle_variable(&mut self) {
+        let Some(reference) = self.debug_panel_property(VARIABLE_PROPERTY) else {
+            return;
+        };
+        let Some(reference) = reference.as_i64() else {
+            return;
+        };
+        let Some(session) = self.debugger.session.as_mut() else {
+            return;
+        };
+        if !session.expanded.remove(&reference) {
+            session.expanded.insert(reference);
+            if !session.variables.contains_key(&reference) {
+                session.request(
+                    "variables",
+                    json!({"variablesReference": reference}),
+                    Some(Pending::Variables(reference)),
+                );
+            }
+        }
+        self.refresh_debug_variables();
+    }
+
+    /// Prompt for a watch expression
+    pub fn debug_add_watch(&mut self) {
+        self.start_prompt(
+            t!("debug.add_watch_prompt").to_string(),
+            PromptType::DebugAddWatch,
+        );
+    }
+
+    pub(super) fn add_debug_watch(&mut self, expression: &str) {
+        let expression = expression.trim();
+        if expression.is_empty() || self.debugger.watches.iter().any(|w| w == expression) {
+            return;
+        }
+        self.debugger.watches.push(expression.to_string());
+        if let Some(session) = self.debugger.session.as_mut() {
+            session.evaluate_watch(expression);
+        }
+        self.refresh_debug_variables();
+    }
+
+    /// Remove the watch under the cursor in the variables panel
+    pub fn debug_remove_watch(&mut self) {
+        let Some(expression) = self.debug_panel_property(WATCH_PROPERTY) else {
+            return;
+        };
+        let Some(expression) = expression.as_str() else {
+            return;
+        };
+        self.debugger.watches.retain(|w| w != expression);
+        self.refresh_debug_variables();
+    }
+
+    fn debug_panel_property(&self, key: &str) -> Option<Value> {
+        if self.find_buffer_by_name(DEBUG_VARIABLES_BUFFER_NAME) != Some(self.active_buffer()) {
+            return None;
+        }
+        self.get_text_properties_at_cursor()?
+            .into_iter()
+            .find_map(|prop| prop.get(key).cloned())
+    }
+
+    // ==================== Debug console ====================
+
+    /// Open the debug console
+    pub fn show_debug_console(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(DEBUG_CONSOLE_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    DEBUG_CONSOLE_BUFFER_NAME.to_string(),
+                    DEBUG_CONSOLE_MODE.to_string(),
+                    true,
+                )
+            });
+        self.fill_debug_console(buffer_id, true);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Prompt for an expression to evaluate in the paused frame
+    pub fn debug_evaluate(&mut self) {
+        if self.debugger.session.is_none() {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        }
+        self.start_prompt(
+            t!("debug.evaluate_prompt").to_string(),
+            PromptType::DebugEvaluate,
+        );
+    }
+
+    pub(super) fn evaluate_debug_expression(&mut self, expression: &str) {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return;
+        }
+        let Some(session) = self.debugger.session.as_mut() else {
+            self.set_status_message(t!("debug.not_running").to_string());
+            return;
+        };
+        let mut arguments = json!({"expression": expression, "context": "repl"});
+        if let Some(frame) = session.frame.as_ref() {
+            arguments["frameId"] = json!(frame.id);
+        }
+        session.request("evaluate", arguments, Some(Pending::Evaluate));
+        self.debug_console_line(format!("> {}", expression));
+        if self.find_buffer_by_name(DEBUG_CONSOLE_BUFFER_NAME) != Some(self.active_buffer()) {
+            self.show_debug_console();
+        }
+    }
+
+    fn debug_console_line(&mut self, line: String) {
+        self.debugger.console.push(line);
+        let excess = self
+            .debugger
+            .console
+            .len()
+            .saturating_sub(MAX_CONSOLE_LINES);
+        self.debugger.console.drain(..excess);
+        if let Some(buffer_id) = self.find_buffer_by_name(DEBUG_CONSOLE_BUFFER_NAME) {
+            self.fill_debug_console(buffer_id, false);
+        }
+    }
+
+    /// Fill the console; the cursor follows new output when it was at the end
+    fn fill_debug_console(&mut self, buffer_id: BufferId, move_to_end: bool) {
+        let mut content = String::from("Debug console   e: evaluate   q: close\n\n");
+        for line in &self.debugger.console {
+            content.push_str(line);
+            content.push('\n');
+        }
+
+        let old_len = self.buffers.get(&buffer_id).map_or(0, |s| s.buffer.len());
+        let at_end = move_to_end || self.buffer_cursor_position(buffer_id) == Some(old_len);
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill debug console buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        if at_end {
+            self.move_cursor_to_buffer_end(buffer_id);
+        }
+    }
+}
+
+/// Variables of `reference`, with the expanded ones followed by their children
+fn push_variables(
+    entries: &mut Vec<TextPropertyEntry>,
+    session: &DebugSession,
+    reference: i64,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    let Some(variables) = session.variables.get(&reference) else {
+        entries.push(TextPropertyEntry::text(format!("{}...\n", indent)));
+        return;
+    };
+    for variable in variables {
+        let expandable = variable.variables_reference > 0 && depth < MAX_VARIABLE_DEPTH;
+        let expanded = expandable && session.expanded.contains(&variable.variables_reference);
+        let marker = match (expandable, expanded) {
+            (false, _) => " ",
+            (true, false) => "▸",
+            (true, true) => "▾",
+        };
+        let line = format!(
+            "{}{} {} = {}\n",
+            indent, marker, variable.name, variable.value
+        );
+        if expandable {
+            entries.push(
+                TextPropertyEntry::text(line)
+                    .with_property(VARIABLE_PROPERTY, Value::from(variable.variables_reference)),
+            );
+        } else {
+            entries.push(TextPropertyEntry::text(line));
+        }
+        if expanded {
+            push_variables(entries, session, variable.variables_reference, depth + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frame() {
+        let frame = parse_frame(&json!({
+            "stackFrames": [
+                {"id": 7, "name": "main", "line": 12, "column": 5,
+                 "source": {"name": "main.rs", "path": "/p/src/main.rs"}},
+                {"id": 8, "name": "start", "line": 1, "column": 1}
+            ],
+            "totalFrames": 2
+        }))
+        .unwrap();
+        assert_eq!(frame.id, 7);
+        assert_eq!(frame.name, "main");
+        assert_eq!(frame.path, Some(PathBuf::from("/p/src/main.rs")));
+        assert_eq!((frame.line, frame.column), (12, 5));
+
+        assert!(parse_frame(&json!({"stackFrames": []})).is_none());
+    }
+
+    #[test]
+    fn test_parse_scopes_and_variables() {
+        let scopes = parse_scopes(&json!({"scopes": [
+            {"name": "Locals", "variablesReference": 1, "expensive": false},
+            {"name": "Registers", "variablesReference": 2, "expensive": true}
+        ]}));
+        assert_eq!(scopes.len(), 2);
+        assert_eq!(scopes[0].0.name, "Locals");
+        assert!(!scopes[0].1);
+        assert!(scopes[1].1);
+
+        let variables = parse_variables(&json!({"variables": [
+            {"name": "x", "value": "1", "variablesReference": 0},
+            {"name": "point", "value": "Point", "variablesReference": 5},
+            {"value": "nameless"}
+        ]}));
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[1].name, "point");
+        assert_eq!(variables[1].variables_reference, 5);
+    }
+
+    #[test]
+    fn test_response_error_prefers_formatted_message() {
+        assert_eq!(
+            response_error(
+                Some("evaluate failed".to_string()),
+                &json!({"error": {"id": 1, "format": "name 'y' is not defined"}})
+            ),
+            "name 'y' is not defined"
+        );
+        assert_eq!(
+            response_error(Some("evaluate failed".to_string()), &Value::Null),
+            "evaluate failed"
+        );
+    }
+}
@@ -0,0 +1,585 @@
+//! Standalone diff and merge mode (`fresh --diff`, `fresh --merge`)
+//!
+//! Unlike the git diff view, the panes show the real file buffers rather than
+//! snapshots: the editable panes accept typing and Save writes them back to
+//! disk. The alignment is recomputed after every edit so the panes stay in
+//! step. The exit code tells `git mergetool` whether the merge was resolved.
+
+use super::Editor;
+use crate::model::composite_buffer::{
+    CompositeLayout, GutterStyle, LineAlignment, PaneStyle, RowType, SourcePane,
+};
+use crate::model::event::BufferId;
+use crate::model::line_diff::diff_hunks;
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// An open `--diff` or `--merge` comparison
+#[derive(Debug, Clone)]
+pub(super) struct DiffSession {
+    /// Source buffers, in pane order
+    panes: Vec<BufferId>,
+    /// Pane the other panes are diffed against
+    pivot: usize,
+}
+
+/// An edit typed into the focused pane of a diff session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum DiffEdit {
+    Insert(String),
+    DeleteBackward,
+    DeleteForward,
+}
+
+/// Whether `text` still contains git conflict markers
+fn has_conflict_markers(text: &str) -> bool {
+    text.lines().any(|line| {
+        line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> ") || line == "======="
+    })
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Byte offset of grapheme `column` within `line`
+fn column_to_byte(line: &str, column: usize) -> usize {
+    line.grapheme_indices(true)
+        .nth(column)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len())
+}
+
+impl Editor {
+    /// Compare two files side by side; both panes are editable
+    pub fn open_diff_files(&mut self, left: &Path, right: &Path) -> anyhow::Result<BufferId> {
+        let left_id = self.open_diff_source(left)?;
+        let right_id = self.open_diff_source(right)?;
+
+        let mut left_pane = SourcePane::new(left_id, file_label(left), true);
+        left_pane.style = PaneStyle {
+            remove_bg: Some((80, 40, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+        let mut right_pane = SourcePane::new(right_id, file_label(right), true);
+        right_pane.style = PaneStyle {
+            add_bg: Some((40, 80, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+
+        let composite = self.create_composite_buffer(
+            format!("*Diff: {} ↔ {}*", file_label(left), file_label(right)),
+            "diff-view".to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![left_pane, right_pane],
+        );
+        self.start_diff_session(composite, vec![left_id, right_id], 0);
+        Ok(composite)
+    }
+
+    /// Three-way merge: LOCAL | MERGED | REMOTE, with only MERGED editable
+    ///
+    /// `base` is accepted for `git mergetool` compatibility and seeds the
+    /// output when git did not write one.
+    pub fn open_merge_files(
+        &mut self,
+        local: &Path,
+        base: &Path,
+        remote: &Path,
+        output: &Path,
+    ) -> anyhow::Result<BufferId> {
+        let local_id = self.open_diff_source(local)?;
+        let remote_id = self.open_diff_source(remote)?;
+        let output_id = self.open_diff_source(output)?;
+
+        let output_empty = self
+            .buffers
+            .get(&output_id)
+            .is_some_and(|s| s.buffer.is_empty());
+        if output_empty {
+            if let Ok(text) = std::fs::read_to_string(base) {
+                if !text.is_empty() {
+                    self.handle_insert_text(output_id, 0, text);
+                }
+            }
+        }
+
+        let mut local_pane = SourcePane::new(local_id, t!("diff_mode.local_label"), false);
+        local_pane.style = PaneStyle {
+            remove_bg: Some((80, 40, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+        let mut output_pane = SourcePane::new(output_id, t!("diff_mode.merged_label"), true);
+        output_pane.style = PaneStyle {
+            modify_bg: Some((40, 40, 80)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+        let mut remote_pane = SourcePane::new(remote_id, t!("diff_mode.remote_label"), false);
+        remote_pane.style = PaneStyle {
+            add_bg: Some((40, 80, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+
+        let composite = self.create_composite_buffer(
+            format!("*Merge: {}*", file_label(output)),
+            "diff-view".to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.33, 0.34, 0.33],
+                show_separator: true,
+            },
+            vec![local_pane, output_pane, remote_pane],
+        );
+        self.start_diff_session(composite, vec![local_id, output_id, remote_id], 1);
+        self.merge_output = Some(output.to_path_buf());
+
+        let split_id = self.split_manager.active_split();
+        if let Some(view_state) = self.get_composite_view_state(split_id, composite) {
+            view_state.focused_pane = 1;
+        }
+        if let Some(composite) = self.composite_buffers.get_mut(&composite) {
+            composite.active_pane = 1;
+        }
+        Ok(composite)
+    }
+
+    /// Compare a buffer with the file on disk: the disk version is shown
+    /// read-only on the left, the buffer stays editable on the right
+    pub fn open_disk_diff(&mut self, buffer_id: BufferId) -> anyhow::Result<BufferId> {
+        let path = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!(t!("status.no_file_to_revert")))?;
+        let disk_content = self.filesystem.read_file(&path)?;
+        Ok(self.open_base_diff(
+            buffer_id,
+            &path,
+            t!("diff_mode.disk_label").to_string(),
+            disk_content,
+        ))
+    }
+
+    /// Compare a buffer of the file at `path` with an earlier version of it:
+    /// `base_content` is shown read-only on the left under `base_label`, the
+    /// buffer stays editable on the right
+    pub(super) fn open_base_diff(
+        &mut self,
+        buffer_id: BufferId,
+        path: &Path,
+        base_label: String,
+        base_content: Vec<u8>,
+    ) -> BufferId {
+        let disk_id = self.create_virtual_buffer(
+            format!("*{}: {}*", base_label, file_label(path)),
+            "diff-view".to_string(),
+            true,
+        );
+        if let Some(state) = self.buffers.get_mut(&disk_id) {
+            state.buffer = crate::model::buffer::Buffer::from_bytes(
+                base_content,
+                std::sync::Arc::clone(&self.filesystem),
+            );
+        }
+        if let Some(meta) = self.buffer_metadata.get_mut(&disk_id) {
+            meta.hidden_from_tabs = true;
+        }
+
+        let mut disk_pane = SourcePane::new(disk_id, base_label, false);
+        disk_pane.style = PaneStyle {
+            remove_bg: Some((80, 40, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+        let mut buffer_pane = SourcePane::new(buffer_id, t!("diff_mode.buffer_label"), true);
+        buffer_pane.style = PaneStyle {
+            add_bg: Some((40, 80, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+
+        let composite = self.create_composite_buffer(
+            format!("*Diff: {}*", file_label(path)),
+            "diff-view".to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![disk_pane, buffer_pane],
+        );
+        self.start_diff_session(composite, vec![disk_id, buffer_id], 0);
+
+        let split_id = self.split_manager.active_split();
+        if let Some(view_state) = self.get_composite_view_state(split_id, composite) {
+            view_state.focused_pane = 1;
+        }
+        if let Some(composite) = self.composite_buffers.get_mut(&composite) {
+            composite.active_pane = 1;
+        }
+        composite
+    }
+
+    /// Open a file for a diff pane, hidden from the tab bar
+    fn open_diff_source(&mut self, path: &Path) -> anyhow::Result<BufferId> {
+        let buffer_id = self.open_file(path)?;
+        if let Some(meta) = self.buffer_metadata.get_mut(&buffer_id) {
+            meta.hidden_from_tabs = true;
+        }
+        Ok(buffer_id)
+    }
+
+    fn start_diff_session(&mut self, composite: BufferId, panes: Vec<BufferId>, pivot: usize) {
+        self.diff_sessions
+            .insert(composite, DiffSession { panes, pivot });
+        self.realign_diff_session(composite);
+        self.set_active_buffer(composite);
+
+        let changes = self
+            .composite_buffers
+            .get(&composite)
+            .map(|c| {
+                c.alignment
+                    .rows
+                    .iter()
+                    .filter(|row| row.row_type == RowType::HunkHeader)
+                    .count()
+            })
+            .unwrap_or(0);
+        self.set_status_message(t!("diff_mode.opened", count = changes).to_string());
+    }
+
+    /// Whether `buffer_id` is the composite of a `--diff`/`--merge` session
+    pub fn is_diff_session(&self, buffer_id: BufferId) -> bool {
+        self.diff_sessions.contains_key(&buffer_id)
+    }
+
+    /// Recompute the alignment of a session from its current buffer contents
+    pub(super) fn realign_diff_session(&mut self, composite: BufferId) {
+        let Some(session) = self.diff_sessions.get(&composite) else {
+            return;
+        };
+        let texts: Option<Vec<String>> = session
+            .panes
+            .iter()
+            .map(|id| self.buffers.get(id).and_then(|s| s.buffer.to_string()))
+            .collect();
+        let Some(texts) = texts else {
+            return;
+        };
+        let line_counts: Vec<usize> = session
+            .panes
+            .iter()
+            .map(|id| {
+                self.buffers
+                    .get(id)
+                    .and_then(|s| s.buffer.line_count())
+                    .unwrap_or(0)
+            })
+            .collect();
+        let pivot_text = texts[session.pivot].as_bytes();
+        let hunks: Vec<_> = texts
+            .iter()
+            .map(|text| diff_hunks(pivot_text, text.as_bytes()))
+            .collect();
+
+        let alignment = LineAlignment::from_pivot_hunks(session.pivot, &line_counts, &hunks);
+        let max_row = alignment.rows.len().saturating_sub(1);
+        self.set_composite_alignment(composite, alignment);
+        for ((_, id), view_state) in self.composite_view_states.iter_mut() {
+            if *id == composite {
+                view_state.cursor_row = view_state.cursor_row.min(max_row);
+                view_state.scroll_row = view_state.scroll_row.min(max_row);
+            }
+        }
+    }
+
+    /// Apply a typed edit to the focused pane of a diff session
+    ///
+    /// Returns false when `composite` is not a diff session, so the caller can
+    /// fall back to its normal handling.
+    pub(super) fn diff_session_edit(&mut self, composite: BufferId, edit: DiffEdit) -> bool {
+        if !self.diff_sessions.contains_key(&composite) {
+            return false;
+        }
+        let split_id = self.split_manager.active_split();
+        let Some((row, column, pane)) = self
+            .get_composite_view_state(split_id, composite)
+            .map(|vs| (vs.cursor_row, vs.cursor_column, vs.focused_pane))
+        else {
+            return true;
+        };
+        let Some(composite_buffer) = self.composite_buffers.get(&composite) else {
+            return true;
+        };
+        let Some(source) = composite_buffer.sources.get(pane) else {
+            return true;
+        };
+        if !source.editable {
+            self.set_status_message(t!("diff_mode.read_only_pane").to_string());
+            return true;
+        }
+        let source_id = source.buffer_id;
+
+        // Padding rows edit at the start of the next real line in this pane
+        let rows = &composite_buffer.alignment.rows;
+        let (line, column) = match rows.get(row).and_then(|r| r.get_pane_line(pane)) {
+            Some(line_ref) => (line_ref.line, column),
+            None => match rows.iter().skip(row).find_map(|r| r.get_pane_line(pane)) {
+                Some(line_ref) => (line_ref.line, 0),
+                None => (
+                    self.buffers
+                        .get(&source_id)
+                        .and_then(|s| s.buffer.line_count())
+                        .unwrap_or(1)
+                        .saturating_sub(1),
+                    usize::MAX,
+                ),
+            },
+        };
+
+        let Some(state) = self.buffers.get(&source_id) else {
+            return true;
+        };
+        let line_start = state
+            .buffer
+            .line_start_offset(line)
+            .unwrap_or(state.buffer.len());
+        let raw_line = state
+            .buffer
+            .get_line(line)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        let content = raw_line.trim_end_matches(['\n', '\r']);
+        let newline_len = raw_line.len() - content.len();
+        let column = column.min(content.graphemes(true).count());
+        let position = line_start + column_to_byte(content, column);
+
+        let (new_line, new_column) = match edit {
+            DiffEdit::Insert(text) => {
+                let target = match text.rfind('\n') {
+                    Some(i) => (
+                        line + text.matches('\n').count(),
+                        text[i + 1..].graphemes(true).count(),
+                    ),
+                    None => (line, column + text.graphemes(true).count()),
+                };
+                self.handle_insert_text(source_id, position, text);
+                target
+            }
+            DiffEdit::DeleteBackward => {
+                if column > 0 {
+                    let start = line_start + column_to_byte(content, column - 1);
+                    self.handle_delete_range(source_id, start..position);
+                    (line, column - 1)
+                } else if line > 0 {
+                    // Join with the previous line
+                    let previous = state
+                        .buffer
+                        .get_line(line - 1)
+                        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                        .unwrap_or_default();
+                    let previous_content = previous.trim_end_matches(['\n', '\r']);
+                    let removed = previous.len() - previous_content.len();
+                    let previous_len = previous_content.graphemes(true).count();
+                    self.handle_delete_range(source_id, position - removed..position);
+                    (line - 1, previous_len)
+                } else {
+                    return true;
+                }
+            }
+            DiffEdit::DeleteForward => {
+                if column < content.graphemes(true).count() {
+                    let end = line_start + column_to_byte(content, column + 1);
+                    self.handle_delete_range(source_id, position..end);
+                } else if newline_len > 0 {
+                    self.handle_delete_range(source_id, position..position + newline_len);
+                } else {
+                    return true;
+                }
+                (line, column)
+            }
+        };
+
+        self.realign_diff_session(composite);
+        self.place_diff_cursor(composite, pane, new_line, new_column);
+        true
+    }
+
+    /// Move the composite cursor to `line`/`column` of `pane`, scrolling if needed
+    fn place_diff_cursor(&mut self, composite: BufferId, pane: usize, line: usize, column: usize) {
+        let split_id = self.split_manager.active_split();
+        let viewport_height = self.get_composite_viewport_height(split_id);
+        let Some(row) = self.composite_buffers.get(&composite).and_then(|c| {
+            c.alignment
+                .rows
+                .iter()
+                .position(|r| r.get_pane_line(pane).is_some_and(|l| l.line == line))
+        }) else {
+            return;
+        };
+        if let Some(view_state) = self.composite_view_states.get_mut(&(split_id, composite)) {
+            view_state.cursor_row = row;
+            view_state.cursor_column = column;
+            view_state.sticky_column = column;
+            if row < view_state.scroll_row {
+                view_state.scroll_row = row;
+            } else if row >= view_state.scroll_row + viewport_height {
+                view_state.scroll_row = row + 1 - viewport_height;
+            }
+        }
+        self.sync_editor_cursor_from_composite(split_id, composite);
+    }
+
+    /// Move the cursor to the next (or previous) change of the active composite view
+    pub fn diff_jump_hunk(&mut self, forward: bool) {
+        let buffer_id = self.active_buffer();
+        let split_id = self.split_manager.active_split();
+        let viewport_height = self.get_composite_viewport_height(split_id);
+        let Some(cursor_row) = self
+            .get_composite_view_state(split_id, buffer_id)
+            .map(|vs| vs.cursor_row)
+        else {
+            self.set_status_message(t!("diff_mode.not_a_diff").to_string());
+            return;
+        };
+        let Some(composite) = self.composite_buffers.get(&buffer_id) else {
+            return;
+        };
+
+        // The cursor sits on the first changed row, just below the hunk header
+        let header = if forward {
+            composite.alignment.next_hunk_row(cursor_row)
+        } else {
+            composite
+                .alignment
+                .prev_hunk_row(cursor_row.saturating_sub(1))
+        };
+        let Some(header) = header else {
+            self.set_status_message(t!("diff_mode.no_more_changes").to_string());
+            return;
+        };
+        let row = (header + 1).min(composite.row_count().saturating_sub(1));
+
+        if let Some(view_state) = self.composite_view_states.get_mut(&(split_id, buffer_id)) {
+            view_state.cursor_row = row;
+            view_state.cursor_column = 0;
+            view_state.sticky_column = 0;
+            if header < view_state.scroll_row || row >= view_state.scroll_row + viewport_height {
+                view_state.scroll_row = header;
+            }
+        }
+        self.sync_editor_cursor_from_composite(split_id, buffer_id);
+    }
+
+    /// Save the modified files of the active diff session
+    ///
+    /// Returns false when the active buffer is not a diff session.
+    pub(super) fn save_diff_session(&mut self) -> anyhow::Result<bool> {
+        let Some(session) = self.diff_sessions.get(&self.active_buffer()) else {
+            return Ok(false);
+        };
+        let modified: Vec<(BufferId, PathBuf)> = session
+            .panes
+            .iter()
+            .filter(|id| self.buffers.get(id).is_some_and(|s| s.buffer.is_modified()))
+            .filter_map(|id| {
+                let path = self.buffer_metadata.get(id)?.file_path()?.clone();
+                Some((*id, path))
+            })
+            .collect();
+
+        let options = self.save_options();
+        for (buffer_id, path) in &modified {
+            if let Some(state) = self.buffers.get_mut(buffer_id) {
+                state.buffer.set_save_options(options);
+                state.buffer.save()?;
+            }
+            self.finalize_save_buffer(*buffer_id, Some(path.clone()), true)?;
+        }
+        self.set_status_message(t!("diff_mode.saved", count = modified.len()).to_string());
+        Ok(true)
+    }
+
+    /// Forget a diff session whose composite is being closed
+    ///
+    /// Returns the unmodified file buffers the session opened, which close
+    /// with it. Files with unsaved edits stay open as regular tabs so the
+    /// edits are not lost, and buffers that already had a tab keep it.
+    pub(super) fn end_diff_session(&mut self, composite: BufferId) -> Vec<BufferId> {
+        let Some(session) = self.diff_sessions.remove(&composite) else {
+            return Vec::new();
+        };
+        let mut to_close = Vec::new();
+        for buffer_id in session.panes {
+            let modified = self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|s| s.buffer.is_modified());
+            let opened_for_session = self
+                .buffer_metadata
+                .get(&buffer_id)
+                .is_some_and(|meta| meta.hidden_from_tabs);
+            if modified {
+                if let Some(meta) = self.buffer_metadata.get_mut(&buffer_id) {
+                    meta.hidden_from_tabs = false;
+                }
+            } else if opened_for_session && !to_close.contains(&buffer_id) {
+                to_close.push(buffer_id);
+            }
+        }
+        to_close
+    }
+
+    /// Process exit code for diff/merge mode
+    ///
+    /// 1 when a `--merge` output is unsaved or still has conflict markers,
+    /// 0 otherwise, matching what `git mergetool` expects with
+    /// `trustExitCode = true`.
+    pub fn diff_mode_exit_code(&self) -> i32 {
+        let Some(output) = &self.merge_output else {
+            return 0;
+        };
+        let unsaved = self.buffer_metadata.iter().any(|(id, meta)| {
+            meta.file_path() == Some(output)
+                && self.buffers.get(id).is_some_and(|s| s.buffer.is_modified())
+        });
+        let unresolved = std::fs::read_to_string(output)
+            .map(|text| has_conflict_markers(&text))
+            .unwrap_or(true);
+        if unsaved || unresolved {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_conflict_markers() {
+        assert!(has_conflict_markers(
+            "a\n<<<<<<< HEAD\nb\n=======\nc\n>>>>>>> topic\n"
+        ));
+        assert!(!has_conflict_markers("a\n<<<<<<<<\n==\n"));
+    }
+
+    #[test]
+    fn test_column_to_byte() {
+        assert_eq!(column_to_byte("héllo", 2), 3);
+        assert_eq!(column_to_byte("abc", 10), 3);
+    }
+}
@@ -0,0 +1,64 @@
+//! EditorConfig support for opened files
+
+use super::Editor;
+use crate::primitives::editorconfig::{self, EditorConfigFile};
+use crate::state::EditorState;
+use std::path::Path;
+
+impl Editor {
+    /// Apply the `.editorconfig` files in the directories above `path` to
+    /// its newly opened buffer
+    pub(super) fn apply_editorconfig(&self, path: &Path, state: &mut EditorState) {
+        if !self.config.editor.read_editorconfig {
+            return;
+        }
+
+        // Nearest file first, up to one marked `root = true`
+        let mut files: Vec<(EditorConfigFile, String)> = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let Ok(bytes) = self
+                .filesystem
+                .read_file(&dir.join(editorconfig::FILE_NAME))
+            else {
+                continue;
+            };
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let relative: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            let file = editorconfig::parse(&String::from_utf8_lossy(&bytes));
+            let root = file.root;
+            files.push((file, relative.join("/")));
+            if root {
+                break;
+            }
+        }
+        if files.is_empty() {
+            return;
+        }
+
+        let outermost_first: Vec<(&EditorConfigFile, &str)> = files
+            .iter()
+            .rev()
+            .map(|(file, relative)| (file, relative.as_str()))
+            .collect();
+        let settings = editorconfig::settings_for(&outermost_first);
+        if settings.is_empty() {
+            return;
+        }
+        tracing::debug!("EditorConfig for {}: {:?}", path.display(), settings);
+
+        let buffer_settings = &mut state.buffer_settings;
+        if let Some(use_tabs) = settings.use_tabs {
+            buffer_settings.use_tabs = use_tabs;
+        }
+        if let Some(tab_size) = settings.tab_size() {
+            buffer_settings.tab_size = tab_size;
+        }
+        buffer_settings.trim_trailing_whitespace = settings.trim_trailing_whitespace;
+        buffer_settings.insert_final_newline = settings.insert_final_newline;
+    }
+}
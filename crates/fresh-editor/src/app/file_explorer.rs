@@ -3,7 +3,7 @@ use rust_i18n::t;
 
 use super::*;
 use crate::view::file_tree::TreeNode;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the parent directory path from a file tree node.
 /// If the node is a directory, returns its path. If it's a file, returns the parent directory.
@@ -43,6 +43,27 @@ fn get_parent_node_id(
     }
 }
 
+/// Copy a file, or a directory with everything in it
+fn copy_recursive(
+    fs: &dyn crate::model::filesystem::FileSystem,
+    from: &Path,
+    to: &Path,
+) -> std::io::Result<()> {
+    if !fs.is_dir(from)? {
+        return fs.copy(from, to).map(|_| ());
+    }
+    fs.create_dir(to)?;
+    for entry in fs.read_dir(from)? {
+        let target = to.join(&entry.name);
+        if entry.is_dir() && !entry.is_symlink() {
+            copy_recursive(fs, &entry.path, &target)?;
+        } else {
+            fs.copy(&entry.path, &target)?;
+        }
+    }
+    Ok(())
+}
+
 impl Editor {
     pub fn file_explorer_visible(&self) -> bool {
         self.file_explorer_visible
@@ -131,6 +152,32 @@ impl Editor {
         }
     }
 
+    /// Show the file explorer with the active buffer's file expanded and selected
+    pub fn reveal_in_file_explorer(&mut self) {
+        let Some(path) = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|metadata| metadata.file_path())
+            .cloned()
+        else {
+            self.set_status_message(t!("explorer.reveal_no_file").to_string());
+            return;
+        };
+        if !path.starts_with(&self.working_dir) {
+            self.set_status_message(t!("explorer.reveal_outside_project").to_string());
+            return;
+        }
+
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.close_filter();
+            explorer.search_clear();
+        } else {
+            // The explorer loads asynchronously; reveal once it's ready
+            self.pending_file_explorer_reveal = true;
+        }
+        self.focus_file_explorer();
+    }
+
     pub fn focus_editor(&mut self) {
         self.key_context = KeyContext::Normal;
         self.set_status_message(t!("editor.focused").to_string());
@@ -343,16 +390,27 @@ impl Editor {
             .and_then(|explorer| explorer.get_selected_entry())
             .map(|entry| (entry.is_dir(), entry.path.clone(), entry.name.clone()));
 
+        // Picking an entry from the filter results drops the filter and leaves
+        // the entry selected in the full tree
+        let was_filtering = self
+            .file_explorer
+            .as_mut()
+            .is_some_and(|explorer| explorer.close_filter());
+
         if let Some((is_dir, path, name)) = entry_type {
             if is_dir {
-                self.file_explorer_toggle_expand();
+                if !was_filtering {
+                    self.file_explorer_toggle_expand();
+                }
             } else {
                 tracing::info!("[SYNTAX DEBUG] file_explorer opening file: {:?}", path);
-                match self.open_file(&path) {
-                    Ok(_) => {
-                        self.set_status_message(
-                            t!("explorer.opened_file", name = &name).to_string(),
-                        );
+                match self.open_file_in_background(&path) {
+                    Ok(buffer_id) => {
+                        if !self.is_buffer_loading(buffer_id) {
+                            self.set_status_message(
+                                t!("explorer.opened_file", name = &name).to_string(),
+                            );
+                        }
                         self.focus_editor();
                     }
                     Err(e) => {
@@ -519,6 +577,19 @@ impl Editor {
 
     pub fn file_explorer_delete(&mut self) {
         if let Some(explorer) = &self.file_explorer {
+            if explorer.marked_count() > 0 {
+                let paths = explorer.operation_targets();
+                if paths.is_empty() {
+                    self.set_status_message(t!("explorer.cannot_delete_root").to_string());
+                    return;
+                }
+                self.start_prompt(
+                    t!("explorer.delete_many_confirm", count = paths.len()).to_string(),
+                    PromptType::ConfirmDeleteFiles { paths },
+                );
+                return;
+            }
+
             if let Some(selected_id) = explorer.get_selected() {
                 // Don't allow deleting the root directory
                 if selected_id == explorer.tree().root_id() {
@@ -551,15 +622,7 @@ impl Editor {
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        // For remote files, move to remote trash directory
-        // For local files, use system trash
-        let delete_result = if self.filesystem.remote_connection_info().is_some() {
-            self.move_to_remote_trash(&path)
-        } else {
-            trash::delete(&path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
-        };
-
-        match delete_result {
+        match self.move_to_trash(&path) {
             Ok(_) => {
                 // Refresh the parent directory in the file explorer
                 if let Some(explorer) = &mut self.file_explorer {
@@ -576,7 +639,7 @@ impl Editor {
 
                             // After refresh, select the next best node:
                             // Try to stay at the same index, or select the last visible item
-                            let visible = explorer.tree().get_visible_nodes();
+                            let visible = explorer.visible_nodes();
                             if !visible.is_empty() {
                                 let new_index = if let Some(idx) = deleted_index {
                                     idx.min(visible.len().saturating_sub(1))
@@ -604,6 +667,49 @@ impl Editor {
         }
     }
 
+    /// Move several files/directories to the trash (called after prompt confirmation)
+    pub fn perform_file_explorer_delete_many(&mut self, paths: Vec<PathBuf>) {
+        let mut deleted = 0;
+        let mut first_error = None;
+        let mut dirs = Vec::new();
+        for path in &paths {
+            match self.move_to_trash(path) {
+                Ok(()) => {
+                    deleted += 1;
+                    dirs.extend(path.parent().map(Path::to_path_buf));
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        self.refresh_file_explorer_dirs(dirs);
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.clear_marks();
+        }
+
+        match first_error {
+            None => self.set_status_message(
+                t!("explorer.moved_many_to_trash", count = deleted).to_string(),
+            ),
+            Some(e) => self
+                .set_status_message(t!("explorer.error_trash", error = e.to_string()).to_string()),
+        }
+        self.key_context = KeyContext::FileExplorer;
+    }
+
+    /// Move a file/directory to the trash
+    /// For local files: moves to system trash/recycle bin
+    /// For remote files: moves to ~/.local/share/fresh/trash/ on remote
+    fn move_to_trash(&self, path: &Path) -> std::io::Result<()> {
+        if self.filesystem.remote_connection_info().is_some() {
+            self.move_to_remote_trash(path)
+        } else {
+            trash::delete(path).map_err(std::io::Error::other)
+        }
+    }
+
     /// Move a file/directory to the remote trash directory (~/.local/share/fresh/trash/)
     fn move_to_remote_trash(&self, path: &std::path::Path) -> std::io::Result<()> {
         // Get remote home directory
@@ -694,44 +800,10 @@ impl Editor {
                         explorer.navigate_to_path(&new_path);
                     }
 
-                    // Update buffer metadata if this file is open in a buffer
-                    let buffer_to_update = self
-                        .buffers
-                        .iter()
-                        .find(|(_, state)| state.buffer.file_path() == Some(&original_path))
-                        .map(|(id, _)| *id);
-
-                    if let Some(buffer_id) = buffer_to_update {
-                        // Update the buffer's file path
-                        if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                            state.buffer.set_file_path(new_path.clone());
-                        }
-
-                        // Update the buffer metadata
-                        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
-                            // Compute new URI
-                            let file_uri = url::Url::from_file_path(&new_path)
-                                .ok()
-                                .and_then(|u| u.as_str().parse::<lsp_types::Uri>().ok());
-
-                            // Update kind with new path and URI
-                            metadata.kind = super::BufferKind::File {
-                                path: new_path.clone(),
-                                uri: file_uri,
-                            };
-
-                            // Update display name
-                            metadata.display_name = super::BufferMetadata::display_name_for_path(
-                                &new_path,
-                                &self.working_dir,
-                            );
-                        }
-
-                        // Only switch focus to the buffer if this is a new file being created
-                        // For renaming existing files from the explorer, keep focus in explorer.
-                        if is_new_file {
-                            self.key_context = KeyContext::Normal;
-                        }
+                    // Only switch focus to the buffer if this is a new file being created
+                    // For renaming existing files from the explorer, keep focus in explorer.
+                    if self.retarget_open_buffers(&original_path, &new_path) && is_new_file {
+                        self.key_context = KeyContext::Normal;
                     }
 
                     self.set_status_message(
@@ -747,6 +819,287 @@ impl Editor {
         }
     }
 
+    /// Point open buffers at `from` (or inside it, for directories) to the
+    /// matching path under `to`. Returns whether any buffer was updated.
+    pub(crate) fn retarget_open_buffers(&mut self, from: &Path, to: &Path) -> bool {
+        let moved: Vec<(BufferId, PathBuf)> = self
+            .buffers
+            .iter()
+            .filter_map(|(id, state)| {
+                let rel = state.buffer.file_path()?.strip_prefix(from).ok()?;
+                let new_path = if rel.as_os_str().is_empty() {
+                    to.to_path_buf()
+                } else {
+                    to.join(rel)
+                };
+                Some((*id, new_path))
+            })
+            .collect();
+
+        for (buffer_id, new_path) in &moved {
+            if let Some(state) = self.buffers.get_mut(buffer_id) {
+                state.buffer.set_file_path(new_path.clone());
+            }
+
+            if let Some(metadata) = self.buffer_metadata.get_mut(buffer_id) {
+                let file_uri = url::Url::from_file_path(new_path)
+                    .ok()
+                    .and_then(|u| u.as_str().parse::<lsp_types::Uri>().ok());
+                metadata.kind = super::BufferKind::File {
+                    path: new_path.clone(),
+                    uri: file_uri,
+                };
+                metadata.display_name =
+                    super::BufferMetadata::display_name_for_path(new_path, &self.working_dir);
+            }
+        }
+
+        !moved.is_empty()
+    }
+
+    /// Toggle the mark on the selected item and move to the next one
+    pub fn file_explorer_toggle_mark(&mut self) {
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.toggle_mark_selected();
+            explorer.select_next();
+            let count = explorer.marked_count();
+            self.set_status_message(t!("explorer.marked", count = count).to_string());
+        }
+    }
+
+    /// Mark the selected item and extend the selection up or down
+    pub fn file_explorer_mark_and_move(&mut self, down: bool) {
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.mark_selected();
+            if down {
+                explorer.select_next();
+            } else {
+                explorer.select_prev();
+            }
+            explorer.mark_selected();
+            let count = explorer.marked_count();
+            self.set_status_message(t!("explorer.marked", count = count).to_string());
+        }
+    }
+
+    /// Ask where to move the marked items (or the selected one)
+    pub fn file_explorer_move(&mut self) {
+        self.start_file_explorer_transfer(false);
+    }
+
+    /// Ask where to copy the marked items (or the selected one)
+    pub fn file_explorer_copy(&mut self) {
+        self.start_file_explorer_transfer(true);
+    }
+
+    fn start_file_explorer_transfer(&mut self, copy: bool) {
+        let Some(explorer) = &self.file_explorer else {
+            return;
+        };
+        let paths = explorer.operation_targets();
+        let Some(first) = paths.first() else {
+            self.set_status_message(t!("explorer.cannot_move_root").to_string());
+            return;
+        };
+
+        // Start from the directory the items are in, relative to the explorer root
+        let root = explorer.tree().root_path();
+        let parent = first.parent().unwrap_or(root);
+        let initial = match parent.strip_prefix(root) {
+            Ok(rel) if rel.as_os_str().is_empty() => String::new(),
+            Ok(rel) => format!("{}/", rel.display()),
+            Err(_) => format!("{}/", parent.display()),
+        };
+
+        let name = first
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let count = paths.len();
+        let (label, prompt_type) = match (copy, count) {
+            (false, 1) => (
+                t!("explorer.move_prompt", name = &name),
+                PromptType::FileExplorerMove { paths },
+            ),
+            (false, _) => (
+                t!("explorer.move_many_prompt", count = count),
+                PromptType::FileExplorerMove { paths },
+            ),
+            (true, 1) => (
+                t!("explorer.copy_prompt", name = &name),
+                PromptType::FileExplorerCopy { paths },
+            ),
+            (true, _) => (
+                t!("explorer.copy_many_prompt", count = count),
+                PromptType::FileExplorerCopy { paths },
+            ),
+        };
+        self.prompt = Some(crate::view::prompt::Prompt::with_initial_text(
+            label.to_string(),
+            prompt_type,
+            initial,
+        ));
+    }
+
+    /// Move or copy `paths` to `destination` (called after prompt confirmation)
+    ///
+    /// `destination` is relative to the explorer root unless absolute. Items
+    /// go inside it when it is an existing directory, ends with `/`, or more
+    /// than one item is being transferred; a single item is otherwise moved
+    /// (or copied) to that exact path, which also renames it. Open buffers
+    /// follow moved files.
+    pub fn perform_file_explorer_transfer(
+        &mut self,
+        paths: Vec<PathBuf>,
+        destination: String,
+        copy: bool,
+    ) {
+        let destination = destination.trim();
+        if destination.is_empty() || paths.is_empty() {
+            self.set_status_message(t!("explorer.move_cancelled").to_string());
+            return;
+        }
+
+        let root = self
+            .file_explorer
+            .as_ref()
+            .map(|explorer| explorer.tree().root_path().to_path_buf())
+            .unwrap_or_else(|| self.working_dir.clone());
+        let dest = root.join(destination);
+        let into_dir = paths.len() > 1
+            || destination.ends_with('/')
+            || self.filesystem.is_dir(&dest).unwrap_or(false);
+
+        let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+        let mut errors: Vec<String> = Vec::new();
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let target = if into_dir {
+                dest.join(&name)
+            } else {
+                dest.clone()
+            };
+
+            let result = if target == path {
+                continue;
+            } else if target.starts_with(&path) {
+                Err(t!("explorer.cannot_move_into_itself", name = &name).to_string())
+            } else if self.filesystem.exists(&target) {
+                Err(t!(
+                    "explorer.already_exists",
+                    name = target.display().to_string()
+                )
+                .to_string())
+            } else {
+                let parent = target.parent().unwrap_or(&root);
+                self.filesystem
+                    .create_dir_all(parent)
+                    .and_then(|_| {
+                        if copy {
+                            copy_recursive(self.filesystem.as_ref(), &path, &target)
+                        } else {
+                            self.filesystem.rename(&path, &target)
+                        }
+                    })
+                    .map_err(|e| format!("{}: {}", name, e))
+            };
+
+            match result {
+                Ok(()) => done.push((path, target)),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if !copy {
+            for (from, to) in &done {
+                self.retarget_open_buffers(from, to);
+            }
+        }
+
+        // Refresh the directories items left and arrived in
+        let mut dirs: Vec<PathBuf> = done
+            .iter()
+            .flat_map(|(from, to)| [from.parent(), to.parent()])
+            .flatten()
+            .map(Path::to_path_buf)
+            .collect();
+        if copy {
+            dirs.retain(|dir| done.iter().any(|(_, to)| to.parent() == Some(dir)));
+        }
+        self.refresh_file_explorer_dirs(dirs);
+
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.clear_marks();
+            if let Some((_, to)) = done.first() {
+                explorer.navigate_to_path(to);
+            }
+        }
+
+        let message = if let Some(error) = errors.first() {
+            if copy {
+                t!("explorer.error_copying", error = error)
+            } else {
+                t!("explorer.error_moving", error = error)
+            }
+        } else if let [(from, to)] = done.as_slice() {
+            let from = from
+                .strip_prefix(&root)
+                .unwrap_or(from)
+                .display()
+                .to_string();
+            let to = to.strip_prefix(&root).unwrap_or(to).display().to_string();
+            if copy {
+                t!("explorer.copied", from = &from, to = &to)
+            } else {
+                t!("explorer.moved", from = &from, to = &to)
+            }
+        } else {
+            let to = dest
+                .strip_prefix(&root)
+                .unwrap_or(&dest)
+                .display()
+                .to_string();
+            if copy {
+                t!("explorer.copied_many", count = done.len(), to = &to)
+            } else {
+                t!("explorer.moved_many", count = done.len(), to = &to)
+            }
+        };
+        self.set_status_message(message.to_string());
+        self.key_context = KeyContext::FileExplorer;
+    }
+
+    /// Reload the given directories in the file explorer, keeping the
+    /// selection on a valid row
+    fn refresh_file_explorer_dirs(&mut self, mut dirs: Vec<PathBuf>) {
+        dirs.sort();
+        dirs.dedup();
+        let (Some(runtime), Some(explorer)) = (&self.tokio_runtime, &mut self.file_explorer) else {
+            return;
+        };
+
+        let selected_index = explorer.get_selected_index();
+        for dir in dirs {
+            if let Some(node_id) = explorer.tree().get_node_by_path(&dir).map(|n| n.id) {
+                let _ = runtime.block_on(explorer.tree_mut().refresh_node(node_id));
+            }
+        }
+
+        let selection_valid = explorer
+            .get_selected()
+            .is_some_and(|id| explorer.tree().get_node(id).is_some());
+        if !selection_valid {
+            let visible = explorer.visible_nodes();
+            let index = selected_index
+                .unwrap_or(0)
+                .min(visible.len().saturating_sub(1));
+            explorer.set_selected(visible.get(index).copied());
+        }
+    }
+
     pub fn file_explorer_toggle_hidden(&mut self) {
         let show_hidden = if let Some(explorer) = &mut self.file_explorer {
             explorer.toggle_show_hidden();
@@ -793,17 +1146,82 @@ impl Editor {
         );
     }
 
+    /// Toggle showing only changed files (git status or unsaved edits)
+    pub fn file_explorer_toggle_changed_only(&mut self) {
+        let Some(explorer) = &self.file_explorer else {
+            return;
+        };
+        let show = !explorer.show_only_changed();
+
+        if show {
+            let changed = self.file_explorer_changed_paths();
+            let (Some(runtime), Some(explorer)) = (&self.tokio_runtime, &mut self.file_explorer)
+            else {
+                return;
+            };
+            // Expand the directories leading to changed files so they're reachable
+            for path in &changed {
+                runtime.block_on(explorer.tree_mut().expand_to_path(path));
+            }
+            explorer.set_changed_paths(changed);
+        }
+
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.set_show_only_changed(show);
+            explorer.update_scroll_for_selection();
+        }
+
+        let msg = if show {
+            t!("explorer.showing_changed_only")
+        } else {
+            t!("explorer.showing_all_files")
+        };
+        self.set_status_message(msg.to_string());
+    }
+
+    /// Files with a file explorer decoration (e.g. git status) or unsaved changes
+    pub(crate) fn file_explorer_changed_paths(&self) -> Vec<PathBuf> {
+        let unsaved = self.buffers.iter().filter_map(|(buffer_id, state)| {
+            if !state.buffer.is_modified() {
+                return None;
+            }
+            self.buffer_metadata.get(buffer_id)?.file_path().cloned()
+        });
+        self.file_explorer_decoration_cache
+            .decorated_paths()
+            .map(Path::to_path_buf)
+            .chain(unsaved)
+            .collect()
+    }
+
+    /// Open the file explorer filter box
+    pub fn file_explorer_open_filter(&mut self) {
+        if let Some(explorer) = &mut self.file_explorer {
+            explorer.open_filter();
+        }
+    }
+
     /// Clear the file explorer search
     pub fn file_explorer_search_clear(&mut self) {
         if let Some(explorer) = &mut self.file_explorer {
-            explorer.search_clear();
+            // Escape closes the filter, then clears the search, then any marks
+            if explorer.close_filter() {
+                return;
+            }
+            if explorer.is_search_active() || !explorer.clear_marks() {
+                explorer.search_clear();
+            }
         }
     }
 
     /// Add a character to the file explorer search
     pub fn file_explorer_search_push_char(&mut self, c: char) {
         if let Some(explorer) = &mut self.file_explorer {
-            explorer.search_push_char(c);
+            if explorer.is_filter_open() {
+                explorer.filter_push_char(c);
+            } else {
+                explorer.search_push_char(c);
+            }
             explorer.update_scroll_for_selection();
         }
     }
@@ -811,7 +1229,11 @@ impl Editor {
     /// Remove a character from the file explorer search (backspace)
     pub fn file_explorer_search_pop_char(&mut self) {
         if let Some(explorer) = &mut self.file_explorer {
-            explorer.search_pop_char();
+            if explorer.is_filter_open() {
+                explorer.filter_pop_char();
+            } else {
+                explorer.search_pop_char();
+            }
             explorer.update_scroll_for_selection();
         }
     }
@@ -267,8 +267,23 @@ impl Editor {
         // In folder mode, selecting a file does nothing
     }
 
+    /// Remember a chosen path in the history of the active file browser prompt
+    fn record_file_open_history(&mut self, path: &std::path::Path) {
+        let key = self
+            .prompt
+            .as_ref()
+            .and_then(|p| Self::prompt_type_to_history_key(&p.prompt_type));
+        if let Some(key) = key {
+            let history = self.get_or_create_prompt_history(&key);
+            history.push(path.display().to_string());
+            history.reset_navigation();
+        }
+    }
+
     /// Select a folder as the new project root (for SwitchProject mode)
     fn file_open_select_folder(&mut self, path: std::path::PathBuf) {
+        self.record_file_open_history(&path);
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
@@ -297,6 +312,7 @@ impl Editor {
             .as_ref()
             .map(|s| s.detect_encoding)
             .unwrap_or(true);
+        self.record_file_open_history(&path);
 
         // Close the file browser
         self.file_open_state = None;
@@ -314,40 +330,45 @@ impl Editor {
 
         // Open the file with auto-detected encoding
         tracing::info!("[SYNTAX DEBUG] file_open_dialog opening file: {:?}", path);
-        if let Err(e) = self.open_file(&path) {
-            // Check if this is a large file encoding confirmation error
-            if let Some(confirmation) =
-                e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
-            {
-                // Show confirmation prompt for large file with non-resynchronizable encoding
-                let size_mb = confirmation.file_size as f64 / (1024.0 * 1024.0);
-                let load_key = t!("file.large_encoding.key.load").to_string();
-                let encoding_key = t!("file.large_encoding.key.encoding").to_string();
-                let cancel_key = t!("file.large_encoding.key.cancel").to_string();
-                let prompt_msg = t!(
-                    "file.large_encoding_prompt",
-                    encoding = confirmation.encoding.display_name(),
-                    size = format!("{:.0}", size_mb),
-                    load_key = load_key,
-                    encoding_key = encoding_key,
-                    cancel_key = cancel_key
-                )
-                .to_string();
-                self.start_prompt(
-                    prompt_msg,
-                    PromptType::ConfirmLargeFileEncoding {
-                        path: confirmation.path.clone(),
-                    },
-                );
-            } else {
-                self.set_status_message(
-                    t!("file.error_opening", error = e.to_string()).to_string(),
-                );
+        match self.open_file_in_background(&path) {
+            Err(e) => {
+                // Check if this is a large file encoding confirmation error
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    // Show confirmation prompt for large file with non-resynchronizable encoding
+                    let size_mb = confirmation.file_size as f64 / (1024.0 * 1024.0);
+                    let load_key = t!("file.large_encoding.key.load").to_string();
+                    let encoding_key = t!("file.large_encoding.key.encoding").to_string();
+                    let cancel_key = t!("file.large_encoding.key.cancel").to_string();
+                    let prompt_msg = t!(
+                        "file.large_encoding_prompt",
+                        encoding = confirmation.encoding.display_name(),
+                        size = format!("{:.0}", size_mb),
+                        load_key = load_key,
+                        encoding_key = encoding_key,
+                        cancel_key = cancel_key
+                    )
+                    .to_string();
+                    self.start_prompt(
+                        prompt_msg,
+                        PromptType::ConfirmLargeFileEncoding {
+                            path: confirmation.path.clone(),
+                        },
+                    );
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+            Ok(buffer_id) => {
+                if !self.is_buffer_loading(buffer_id) {
+                    self.set_status_message(
+                        t!("file.opened", path = path.display().to_string()).to_string(),
+                    );
+                }
             }
-        } else {
-            self.set_status_message(
-                t!("file.opened", path = path.display().to_string()).to_string(),
-            );
         }
     }
 
@@ -425,6 +446,8 @@ impl Editor {
 
     /// Create a new file (opens an unsaved buffer that will create the file on save)
     fn file_open_create_new_file(&mut self, path: std::path::PathBuf) {
+        self.record_file_open_history(&path);
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
@@ -447,6 +470,8 @@ impl Editor {
     fn file_open_save_file(&mut self, path: std::path::PathBuf) {
         use crate::view::prompt::PromptType as PT;
 
+        self.record_file_open_history(&path);
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
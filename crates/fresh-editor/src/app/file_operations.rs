@@ -8,7 +8,7 @@
 //! - File modification time tracking
 //! - Save conflict detection
 
-use crate::model::buffer::SudoSaveRequired;
+use crate::model::buffer::{SaveOptions, SudoSaveRequired};
 use crate::view::prompt::PromptType;
 use std::path::{Path, PathBuf};
 
@@ -24,13 +24,23 @@ use super::{BufferMetadata, Editor};
 impl Editor {
     /// Save the active buffer
     pub fn save(&mut self) -> anyhow::Result<()> {
+        // Saving a placeholder would overwrite the file with nothing
+        let active = self.active_buffer();
+        if self.is_buffer_loading(active) {
+            let name = self.get_buffer_display_name(active);
+            anyhow::bail!(t!("buffer.still_loading", name = name));
+        }
+
         let path = self
             .active_state()
             .buffer
             .file_path()
             .map(|p| p.to_path_buf());
 
-        match self.active_state_mut().buffer.save() {
+        let options = self.save_options();
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_save_options(options);
+        match buffer.save() {
             Ok(()) => self.finalize_save(path),
             Err(e) => {
                 if let Some(sudo_info) = e.downcast_ref::<SudoSaveRequired>() {
@@ -47,6 +57,22 @@ impl Editor {
         }
     }
 
+    /// How buffers are written on save, from the editor config
+    pub(super) fn save_options(&self) -> SaveOptions {
+        SaveOptions {
+            atomic: self.config.editor.atomic_save,
+            backup_count: self.config.editor.save_backup_count,
+        }
+    }
+
+    /// Write the active buffer again after an on-save action changed it
+    pub(super) fn resave_active_buffer(&mut self) -> anyhow::Result<()> {
+        let options = self.save_options();
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_save_options(options);
+        buffer.save()
+    }
+
     /// Internal helper to finalize save state (mark as saved, notify LSP, etc.)
     pub(crate) fn finalize_save(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
         let buffer_id = self.active_buffer();
@@ -92,6 +118,11 @@ impl Editor {
             event_log.mark_saved();
         }
 
+        self.record_local_history(
+            buffer_id,
+            crate::services::local_history::SnapshotKind::Save,
+        );
+
         // Update file modification time after save
         if let Some(ref p) = path {
             if let Ok(metadata) = self.filesystem.metadata(p) {
@@ -103,6 +134,10 @@ impl Editor {
 
         // Notify LSP of save
         self.notify_lsp_save_buffer(buffer_id);
+        self.run_linters(buffer_id, false);
+
+        // The save may have changed the working tree's dirty state
+        self.refresh_git_status();
 
         // Delete recovery file (buffer is now saved)
         let _ = self.delete_buffer_recovery(buffer_id);
@@ -187,9 +222,12 @@ impl Editor {
             }
         }
 
+        let options = self.save_options();
         let mut count = 0;
         for (id, path) in to_save {
+            self.clean_up_whitespace_for_auto_save(id);
             if let Some(state) = self.buffers.get_mut(&id) {
+                state.buffer.set_save_options(options);
                 match state.buffer.save() {
                     Ok(()) => {
                         self.finalize_save_buffer(id, Some(path), true)?;
@@ -241,6 +279,7 @@ impl Editor {
 
         // Preserve user settings before reloading
         let old_buffer_settings = self.active_state().buffer_settings.clone();
+        let old_read_only = self.active_state().read_only;
 
         // Load the file content fresh from disk
         let mut new_state = EditorState::from_file_with_languages(
@@ -263,6 +302,7 @@ impl Editor {
         });
         // Restore user settings (tab size, indentation, etc.)
         new_state.buffer_settings = old_buffer_settings;
+        new_state.read_only = old_read_only;
         // Line number visibility is in per-split BufferViewState (survives buffer replacement)
 
         // Replace the current buffer with the new state
@@ -404,6 +444,12 @@ impl Editor {
             .tree()
             .all_nodes()
             .filter(|node| node.is_dir() && node.is_expanded())
+            // Directories left out by `files.exclude` aren't watched
+            .filter(|node| {
+                !explorer
+                    .ignore_patterns()
+                    .is_excluded(&node.entry.path, true)
+            })
             .map(|node| (node.id, node.entry.path.clone()))
             .collect();
 
@@ -452,6 +498,39 @@ impl Editor {
         true
     }
 
+    /// Poll the user themes directory (called from main loop)
+    ///
+    /// Reloads the theme registry when a theme file is added, removed or edited,
+    /// so changes to the active theme show up without restarting.
+    /// Returns true if themes were reloaded (requires re-render).
+    pub fn poll_theme_changes(&mut self) -> bool {
+        let poll_interval =
+            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        if self.time_source.elapsed_since(self.last_theme_poll) < poll_interval {
+            return false;
+        }
+        self.last_theme_poll = self.time_source.now();
+
+        // Reloading re-applies the configured theme, which would cancel a live
+        // preview in the theme picker; pick the change up once it closes
+        if self
+            .prompt
+            .as_ref()
+            .is_some_and(|p| matches!(p.prompt_type, PromptType::SelectTheme { .. }))
+        {
+            return false;
+        }
+
+        let loader = crate::view::theme::ThemeLoader::new(self.dir_context.themes_dir());
+        if loader.file_stamps() == self.theme_file_stamps {
+            return false;
+        }
+
+        self.reload_themes();
+        self.set_status_message(t!("view.themes_reloaded").to_string());
+        true
+    }
+
     /// Notify LSP server about a newly opened file
     /// Handles language detection, spawning LSP clients, and sending didOpen notifications
     pub(crate) fn notify_lsp_file_opened(
@@ -538,7 +617,7 @@ impl Editor {
                 if let Some(client) = lsp.get_handle_mut(&language) {
                     // Send didOpen
                     tracing::info!("Sending didOpen to LSP for: {}", uri.as_str());
-                    if let Err(e) = client.did_open(uri.clone(), text, language.clone()) {
+                    if let Err(e) = client.did_open(uri.clone(), text.clone(), language.clone()) {
                         tracing::warn!("Failed to send didOpen to LSP: {}", e);
                         return;
                     }
@@ -588,6 +667,17 @@ impl Editor {
                         }
                     }
                 }
+
+                // Open the file in the additional servers too, so they can
+                // publish diagnostics for it
+                for (key, handle) in lsp.additional_handles(&language) {
+                    match handle.did_open(uri.clone(), text.clone(), language.clone()) {
+                        Ok(()) => {
+                            metadata.lsp_opened_with.insert(handle.id());
+                        }
+                        Err(e) => tracing::warn!("Failed to send didOpen to {}: {}", key, e),
+                    }
+                }
             }
             LspSpawnResult::NotAutoStart => {
                 tracing::debug!(
@@ -702,9 +792,18 @@ impl Editor {
                     range_length: None,
                     text: content,
                 };
-                if let Err(e) = client.did_change(lsp_uri, vec![content_change]) {
+                if let Err(e) = client.did_change(lsp_uri, vec![content_change.clone()]) {
                     tracing::warn!("Failed to notify LSP of file change: {}", e);
                 }
+
+                self.with_additional_lsp_for_buffer(buffer_id, false, |_, handle, uri| {
+                    if let Err(e) = handle.did_change(uri.clone(), vec![content_change.clone()]) {
+                        tracing::warn!(
+                            "Failed to notify additional LSP server of file change: {}",
+                            e
+                        );
+                    }
+                });
             }
         }
     }
@@ -738,6 +837,7 @@ impl Editor {
             .get(&buffer_id)
             .map(|s| s.buffer_settings.clone())
             .unwrap_or_default();
+        let old_read_only = self.buffers.get(&buffer_id).is_some_and(|s| s.read_only);
 
         // Load the file content fresh from disk
         let mut new_state = EditorState::from_file_with_languages(
@@ -761,6 +861,7 @@ impl Editor {
         });
         // Restore user settings (tab size, indentation, etc.)
         new_state.buffer_settings = old_buffer_settings;
+        new_state.read_only = old_read_only;
         // Line number visibility is in per-split BufferViewState (survives buffer replacement)
 
         // Replace the buffer content
@@ -847,12 +948,17 @@ impl Editor {
                 continue;
             }
 
-            // If buffer has local modifications, show a warning (don't auto-revert)
+            // If buffer has local modifications, ask what to do (don't auto-revert)
             if state.buffer.is_modified() {
-                self.status_message = Some(format!(
-                    "File {} changed on disk (buffer has unsaved changes)",
-                    path.display()
-                ));
+                if self.prompt.is_none() {
+                    self.prompt_external_change(buffer_id, &path, current_mtime);
+                } else {
+                    // Asked again on the next poll, once the open prompt is done
+                    self.status_message = Some(format!(
+                        "File {} changed on disk (buffer has unsaved changes)",
+                        path.display()
+                    ));
+                }
                 continue;
             }
 
@@ -897,6 +1003,35 @@ impl Editor {
         }
     }
 
+    /// Ask whether to reload, keep or diff a modified buffer whose file
+    /// changed on disk
+    fn prompt_external_change(
+        &mut self,
+        buffer_id: BufferId,
+        path: &Path,
+        disk_mtime: std::time::SystemTime,
+    ) {
+        // Ask once per change on disk; choosing to keep the buffer means a
+        // later save overwrites this version without asking again
+        self.file_mod_times.insert(path.to_path_buf(), disk_mtime);
+
+        let name = self.get_buffer_display_name(buffer_id);
+        let reload_key = t!("prompt.key.reload").to_string();
+        let keep_key = t!("prompt.key.keep").to_string();
+        let diff_key = t!("prompt.key.diff").to_string();
+        self.start_prompt(
+            t!(
+                "prompt.external_change",
+                name = name,
+                reload_key = reload_key,
+                keep_key = keep_key,
+                diff_key = diff_key
+            )
+            .to_string(),
+            PromptType::ConfirmExternalChange { buffer_id },
+        );
+    }
+
     /// Check if saving would overwrite changes made by another process
     /// Returns Some(current_mtime) if there's a conflict, None otherwise
     pub fn check_save_conflict(&self) -> Option<std::time::SystemTime> {
@@ -0,0 +1,80 @@
+//! Filling new files from the user's file templates
+//!
+//! Opening a file that doesn't exist yet, or saving an empty unnamed buffer
+//! with Save As, inserts the template for the file's name or extension, see
+//! [`crate::primitives::file_template`]. The insertion is an ordinary edit,
+//! so it can be undone.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::file_template::{expand_template, template_for, ExpandedTemplate};
+
+impl Editor {
+    /// The template for a new file at `path`, with its variables substituted
+    fn file_template(&self, path: &Path) -> Option<ExpandedTemplate> {
+        let file_name = path.file_name()?.to_str()?;
+        let dir = self.dir_context.templates_dir();
+        let names: Vec<String> = std::fs::read_dir(&dir)
+            .ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                entry.file_type().ok()?.is_file().then_some(())?;
+                entry.file_name().into_string().ok()
+            })
+            .collect();
+        let name = template_for(names.iter().map(String::as_str), file_name)?;
+        let template = match std::fs::read_to_string(dir.join(name)) {
+            Ok(template) => template,
+            Err(e) => {
+                tracing::warn!("Failed to read file template {}: {}", name, e);
+                return None;
+            }
+        };
+
+        let today = self.time_source.today_date();
+        let basename = file_name
+            .rsplit_once('.')
+            .map_or(file_name, |(stem, _)| stem);
+        let variables = HashMap::from([
+            ("filename", file_name.to_string()),
+            ("basename", basename.to_string()),
+            ("date", today.format("%Y-%m-%d").to_string()),
+            ("year", today.format("%Y").to_string()),
+            ("author", self.config.editor.template_author.clone()),
+            ("license", self.config.editor.template_license.clone()),
+        ]);
+        Some(expand_template(&template, &variables))
+    }
+
+    /// Fill the empty active buffer `buffer_id` with the template for a new
+    /// file at `path`, placing the cursor where the template says
+    pub(super) fn apply_file_template(&mut self, buffer_id: BufferId, path: &Path) {
+        if !self.config.editor.file_templates || self.active_buffer() != buffer_id {
+            return;
+        }
+        let untouched = self.buffers.get(&buffer_id).is_some_and(|state| {
+            state.buffer.is_empty() && !state.read_only && !state.editing_disabled
+        }) && self
+            .event_logs
+            .get(&buffer_id)
+            .is_some_and(|log| log.is_empty());
+        if !untouched {
+            return;
+        }
+        let Some(template) = self.file_template(path) else {
+            return;
+        };
+        if template.text.is_empty() {
+            return;
+        }
+
+        let cursor_offset = template.cursor_offset.unwrap_or(0);
+        self.handle_insert_text(buffer_id, 0, template.text);
+        let cursor = self.active_cursors_mut().primary_mut();
+        cursor.position = cursor_offset;
+        cursor.anchor = None;
+    }
+}
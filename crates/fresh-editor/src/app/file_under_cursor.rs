@@ -0,0 +1,97 @@
+//! Opening the file path under the cursor, like Vim's `gf`
+//!
+//! The path-like token under the cursor may end in `:line` or `:line:col`,
+//! as in compiler output. A relative path is looked up in the buffer's
+//! directory, then the project root, then each of `editor.include_paths`.
+
+use super::Editor;
+use crate::primitives::file_reference::{file_reference_at, FileReference};
+use crate::primitives::path_utils::expand_tilde;
+use rust_i18n::t;
+use std::path::PathBuf;
+
+impl Editor {
+    /// Open the file path under the primary cursor, in a new split if
+    /// `in_split`
+    pub(super) fn open_file_under_cursor(&mut self, in_split: bool) {
+        let Some(reference) = self.file_reference_under_cursor() else {
+            self.set_status_message(t!("file.no_path_under_cursor").to_string());
+            return;
+        };
+        let Some(path) = self
+            .file_reference_candidates(&reference.path)
+            .into_iter()
+            .find(|path| self.filesystem.is_file(path).unwrap_or(false))
+        else {
+            self.set_status_message(
+                t!("file.path_not_found", path = reference.path.as_str()).to_string(),
+            );
+            return;
+        };
+
+        if in_split {
+            self.split_pane_vertical();
+        }
+        match self.open_file(&path) {
+            Ok(_) => {
+                if let Some(line) = reference.line {
+                    self.goto_line_col(line, reference.column);
+                }
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// The file reference on the primary cursor's line that contains it
+    fn file_reference_under_cursor(&self) -> Option<FileReference> {
+        let state = self.active_state();
+        let position = self.active_cursors().primary().position;
+        let line = state.buffer.get_line_number(position);
+        let line_start = state.buffer.line_start_offset(line)?;
+        let text = String::from_utf8_lossy(&state.buffer.get_line(line)?).into_owned();
+        file_reference_at(
+            text.trim_end_matches(['\r', '\n']),
+            position.saturating_sub(line_start),
+        )
+    }
+
+    /// Where a referenced path may be, in the order to try them
+    fn file_reference_candidates(&self, path: &str) -> Vec<PathBuf> {
+        let path = expand_tilde(path);
+        if path.is_absolute() {
+            return vec![path];
+        }
+
+        let buffer_dir = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(|file| file.parent())
+            .map(|dir| dir.to_path_buf());
+        let include_dirs = self.config.editor.include_paths.iter().map(|dir| {
+            let dir = expand_tilde(dir);
+            if dir.is_absolute() {
+                dir
+            } else {
+                self.working_dir.join(dir)
+            }
+        });
+
+        let mut candidates: Vec<PathBuf> = Vec::new();
+        for dir in buffer_dir
+            .into_iter()
+            .chain(std::iter::once(self.working_dir.clone()))
+            .chain(include_dirs)
+        {
+            let candidate = dir.join(&path);
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
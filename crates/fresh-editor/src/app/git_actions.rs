@@ -0,0 +1,194 @@
+//! Git branch/status integration
+//!
+//! Keeps the status bar's branch indicator up to date and implements the
+//! checkout, fetch/pull/push and log commands. Every git invocation goes
+//! through the background [`GitPool`](crate::services::git::GitPool); results
+//! arrive as [`GitEvent`]s via the async bridge.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::git::{GitBranch, GitEvent, GitOperation};
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+/// Name of the read-only buffer showing `git log` output
+pub const GIT_LOG_BUFFER_NAME: &str = "*Git History*";
+
+impl Editor {
+    /// Start a background status refresh when the poll interval has elapsed
+    pub(super) fn poll_git_status(&mut self) {
+        let interval_ms = self.config.editor.git_status_poll_interval_ms;
+        if interval_ms == 0 {
+            return;
+        }
+        if let Some(last) = self.last_git_status_poll {
+            let interval = std::time::Duration::from_millis(interval_ms);
+            if self.time_source.elapsed_since(last) < interval {
+                return;
+            }
+        }
+        self.refresh_git_status();
+    }
+
+    /// Refresh the branch indicator now (e.g. after saving or a git command)
+    pub(super) fn refresh_git_status(&mut self) {
+        if self.config.editor.git_status_poll_interval_ms == 0 {
+            return;
+        }
+        self.last_git_status_poll = Some(self.time_source.now());
+        if let Some(bridge) = &self.async_bridge {
+            self.git_pool
+                .refresh_status(&mut self.jobs, bridge.sender(), self.working_dir.clone());
+        }
+    }
+
+    /// Latest branch/dirty state, if the working directory is a repository
+    pub fn git_status(&self) -> Option<&crate::services::git::GitStatus> {
+        self.git_status.as_ref()
+    }
+
+    /// Load branches in the background, then open the checkout picker
+    pub fn start_git_checkout_branch(&mut self) {
+        if let Some(bridge) = &self.async_bridge {
+            self.git_pool
+                .list_branches(&mut self.jobs, bridge.sender(), self.working_dir.clone());
+            self.set_status_message(t!("git.loading_branches").to_string());
+        }
+    }
+
+    /// Check out `branch` (confirmed from the picker)
+    pub(super) fn git_checkout(&mut self, branch: &str) {
+        // Anything starting with '-' would be parsed by git as an option
+        if branch.is_empty() || branch.starts_with('-') {
+            self.set_status_message(t!("git.invalid_branch", branch = branch).to_string());
+            return;
+        }
+        self.run_git_operation(GitOperation::Checkout(branch.to_string()));
+    }
+
+    /// Run a fetch/pull/push/checkout in the background
+    pub fn run_git_operation(&mut self, operation: GitOperation) {
+        if let Some(bridge) = &self.async_bridge {
+            let label = operation.label();
+            self.git_pool.run(
+                &mut self.jobs,
+                bridge.sender(),
+                self.working_dir.clone(),
+                operation,
+            );
+            self.set_status_message(t!("git.running", operation = label).to_string());
+        }
+    }
+
+    /// Load `git log` in the background and show it in a read-only buffer
+    pub fn open_git_log(&mut self) {
+        if let Some(bridge) = &self.async_bridge {
+            self.git_pool
+                .log(&mut self.jobs, bridge.sender(), self.working_dir.clone());
+            self.set_status_message(t!("git.loading_log").to_string());
+        }
+    }
+
+    /// Handle a result or progress update from the git pool
+    pub(super) fn handle_git_event(&mut self, event: GitEvent) {
+        match event {
+            GitEvent::Status(status) => {
+                self.git_status = status;
+            }
+            GitEvent::Branches(Ok(branches)) => self.show_git_branch_picker(branches),
+            GitEvent::Branches(Err(error)) | GitEvent::Log(Err(error)) => {
+                self.set_status_message(t!("git.failed", error = error).to_string());
+            }
+            GitEvent::Progress { operation, message } => {
+                self.set_status_message(
+                    t!(
+                        "git.progress",
+                        operation = operation.label(),
+                        message = message
+                    )
+                    .to_string(),
+                );
+            }
+            GitEvent::Finished { operation, result } => {
+                let message = match result {
+                    Ok(()) => t!("git.finished", operation = operation.label()),
+                    Err(error) => t!(
+                        "git.operation_failed",
+                        operation = operation.label(),
+                        error = error
+                    ),
+                };
+                self.set_status_message(message.to_string());
+                self.refresh_git_status();
+            }
+            GitEvent::Log(Ok(log)) => self.show_git_log(log),
+        }
+    }
+
+    fn show_git_branch_picker(&mut self, branches: Vec<GitBranch>) {
+        if branches.is_empty() {
+            self.set_status_message(t!("git.no_branches").to_string());
+            return;
+        }
+        let current = self.git_status.as_ref().map(|s| s.branch.as_str());
+        let suggestions: Vec<Suggestion> = branches
+            .iter()
+            .map(|branch| {
+                let is_current = !branch.remote && Some(branch.name.as_str()) == current;
+                let description = if is_current {
+                    Some(t!("git.branch_current").to_string())
+                } else if branch.remote {
+                    Some(t!("git.branch_remote").to_string())
+                } else {
+                    None
+                };
+                Suggestion {
+                    text: branch.name.clone(),
+                    description,
+                    value: Some(branch.checkout_name().to_string()),
+                    disabled: is_current,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("git.checkout_prompt").to_string(),
+            PromptType::GitCheckoutBranch,
+            suggestions,
+        ));
+        self.status_message = None;
+    }
+
+    fn show_git_log(&mut self, log: String) {
+        if log.trim().is_empty() {
+            self.set_status_message(t!("git.no_commits").to_string());
+            return;
+        }
+
+        // Reuse an open log buffer so repeated commands just refresh it
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == GIT_LOG_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(GIT_LOG_BUFFER_NAME.to_string(), "special".to_string(), true)
+        });
+
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(log)])
+        {
+            tracing::error!("Failed to fill git log buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+        self.status_message = None;
+    }
+}
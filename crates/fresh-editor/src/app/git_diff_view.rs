@@ -0,0 +1,199 @@
+//! Built-in side-by-side git diff view
+//!
+//! Compares the active buffer against HEAD, the index, or an arbitrary
+//! revision. The base version and a snapshot of the buffer are loaded into
+//! hidden read-only buffers and shown as the two panes of a composite buffer,
+//! aligned by diff hunks so both sides scroll together.
+
+use crate::app::Editor;
+use crate::model::composite_buffer::{
+    CompositeLayout, GutterStyle, LineAlignment, PaneStyle, SourcePane,
+};
+use crate::model::event::BufferId;
+use crate::model::line_diff::diff_hunks;
+use crate::primitives::text_property::TextPropertyEntry;
+use rust_i18n::t;
+use std::path::Path;
+use std::process::Command;
+
+/// What the buffer is compared against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitDiffBase {
+    /// The last commit
+    Head,
+    /// The staged version
+    Index,
+    /// Any revision git understands (branch, tag, commit, `HEAD~2`, ...)
+    Revision(String),
+}
+
+impl GitDiffBase {
+    /// Object spec prefix for `git show <spec>:<path>`
+    fn object_prefix(&self) -> &str {
+        match self {
+            GitDiffBase::Head => "HEAD",
+            GitDiffBase::Index => "",
+            GitDiffBase::Revision(rev) => rev,
+        }
+    }
+
+    /// Pane label for the base side
+    fn label(&self) -> String {
+        match self {
+            GitDiffBase::Head => "HEAD".to_string(),
+            GitDiffBase::Index => t!("git_diff.index_label").to_string(),
+            GitDiffBase::Revision(rev) => rev.clone(),
+        }
+    }
+}
+
+/// Read `file` as of `base` with `git show`, run from the file's directory
+fn git_show(base: &GitDiffBase, file: &Path) -> Result<String, String> {
+    let dir = file
+        .parent()
+        .ok_or_else(|| "no parent directory".to_string())?;
+    let name = file
+        .file_name()
+        .ok_or_else(|| "no file name".to_string())?
+        .to_string_lossy();
+
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!("{}:./{}", base.object_prefix(), name))
+        .current_dir(dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or_default().trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl Editor {
+    /// Prompt for a revision to diff the active buffer against
+    pub fn start_git_diff_revision_prompt(&mut self) {
+        self.prompt = Some(crate::view::prompt::Prompt::new(
+            t!("git_diff.revision_prompt").to_string(),
+            crate::view::prompt::PromptType::GitDiffRevision,
+        ));
+    }
+
+    /// Open a side-by-side diff of the active buffer against `base`
+    pub fn open_git_diff(&mut self, base: GitDiffBase) {
+        if let GitDiffBase::Revision(rev) = &base {
+            // Anything starting with '-' would be parsed by git as an option
+            if rev.is_empty() || rev.starts_with('-') {
+                self.set_status_message(t!("git_diff.invalid_revision", rev = rev).to_string());
+                return;
+            }
+        }
+
+        let buffer_id = self.active_buffer();
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            self.set_status_message(t!("git_diff.not_a_file").to_string());
+            return;
+        };
+        // Compare what the user sees, including unsaved edits
+        let Some(current) = self.active_state().buffer.to_string() else {
+            self.set_status_message(t!("git_diff.not_loaded").to_string());
+            return;
+        };
+
+        let old = match git_show(&base, &path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.set_status_message(t!("git_diff.failed", error = e).to_string());
+                return;
+            }
+        };
+
+        let hunks = diff_hunks(old.as_bytes(), current.as_bytes());
+        let label = base.label();
+        if hunks.is_empty() {
+            self.set_status_message(t!("git_diff.no_changes", base = &label).to_string());
+            return;
+        }
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        // The snapshot names keep the file extension so they get syntax highlighting
+        let old_buffer = self.create_diff_snapshot(format!("*{}:{}*", label, file_name), old);
+        let new_buffer = self.create_diff_snapshot(format!("*Working:{}*", file_name), current);
+        let (Some(old_buffer), Some(new_buffer)) = (old_buffer, new_buffer) else {
+            return;
+        };
+
+        let mut old_pane = SourcePane::new(old_buffer, label.clone(), false);
+        old_pane.style = PaneStyle {
+            remove_bg: Some((80, 40, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+        let mut new_pane =
+            SourcePane::new(new_buffer, t!("git_diff.working_label").to_string(), false);
+        new_pane.style = PaneStyle {
+            add_bg: Some((40, 80, 40)),
+            gutter_style: GutterStyle::DiffMarkers,
+            ..Default::default()
+        };
+
+        let composite = self.create_composite_buffer(
+            format!("*Diff: {} ({})*", file_name, label),
+            "diff-view".to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![old_pane, new_pane],
+        );
+
+        let old_line_count = self
+            .buffers
+            .get(&old_buffer)
+            .and_then(|s| s.buffer.line_count())
+            .unwrap_or(0);
+        let new_line_count = self
+            .buffers
+            .get(&new_buffer)
+            .and_then(|s| s.buffer.line_count())
+            .unwrap_or(0);
+        let alignment = LineAlignment::from_hunks(&hunks, old_line_count, new_line_count);
+        self.set_composite_alignment(composite, alignment);
+
+        self.git_diff_views
+            .insert(composite, [old_buffer, new_buffer]);
+        self.set_active_buffer(composite);
+        self.set_status_message(
+            t!("git_diff.opened", count = hunks.len(), base = &label).to_string(),
+        );
+    }
+
+    /// Create a hidden, read-only buffer holding one side of a diff
+    fn create_diff_snapshot(&mut self, name: String, text: String) -> Option<BufferId> {
+        let buffer_id = self.create_virtual_buffer(name, "diff-view".to_string(), true);
+        if let Some(meta) = self.buffer_metadata.get_mut(&buffer_id) {
+            meta.hidden_from_tabs = true;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        match self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(text)]) {
+            Ok(()) => Some(buffer_id),
+            Err(e) => {
+                tracing::error!("Failed to fill diff snapshot buffer: {}", e);
+                let _ = self.close_buffer(buffer_id);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Global commands: a command applied to every line matching a pattern
+//!
+//! The command works on the selected lines, or on the whole buffer without a
+//! selection, and is one undo step. While it is typed, the prompt lists the
+//! lines it applies to.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::shell_command::run_shell_filter;
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::keybindings::Action;
+use crate::model::event::Event;
+use crate::primitives::global_command::{
+    parse_global_command, GlobalCommand, LineCommand, NormalStep, ParseError,
+};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// Most lines listed in the preview
+const MAX_PREVIEW_LINES: usize = 200;
+
+/// A line the command applies to
+struct TargetLine {
+    /// 0-based line number
+    index: usize,
+    /// Byte range of the line, without its line ending
+    range: Range<usize>,
+    /// Length of the line ending
+    ending_len: usize,
+    text: String,
+}
+
+fn parse_error_message(error: &ParseError) -> String {
+    match error {
+        ParseError::Syntax => t!("global.error_syntax").to_string(),
+        ParseError::InvalidRange => t!("global.error_range").to_string(),
+        ParseError::InvalidPattern(error) => t!("global.error_pattern", error = error).to_string(),
+        ParseError::UnknownCommand(command) => {
+            t!("global.error_command", command = command).to_string()
+        }
+    }
+}
+
+impl Editor {
+    /// Start the prompt for a global command
+    pub fn start_global_command_prompt(&mut self) {
+        self.start_prompt(t!("global.prompt").to_string(), PromptType::GlobalCommand);
+    }
+
+    /// Lines of the selection, or of the buffer, that `command` applies to
+    fn global_command_targets(&mut self, command: &GlobalCommand) -> Vec<TargetLine> {
+        let selection = self.active_cursors().primary().selection_range();
+        let text = self.active_state().buffer.to_string().unwrap_or_default();
+
+        let mut targets = Vec::new();
+        let mut start = 0;
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            let line_start = start;
+            start += line.len();
+            if let Some(selection) = &selection {
+                // A selection ending at a line start doesn't include that line
+                let in_selection =
+                    line_start < selection.end.max(selection.start + 1) && start > selection.start;
+                if !in_selection {
+                    continue;
+                }
+            }
+            let content = line.trim_end_matches(['\r', '\n']);
+            if command.applies_to(index, content) {
+                targets.push(TargetLine {
+                    index,
+                    range: line_start..line_start + content.len(),
+                    ending_len: line.len() - content.len(),
+                    text: content.to_string(),
+                });
+            }
+        }
+        targets
+    }
+
+    /// Show the lines a global command being typed applies to
+    pub(super) fn update_global_command_preview(&mut self, input: &str) {
+        let suggestions = if input.trim().is_empty() {
+            Vec::new()
+        } else {
+            match parse_global_command(input) {
+                Ok(command) => {
+                    let targets = self.global_command_targets(&command);
+                    let mut suggestions = vec![Suggestion {
+                        disabled: true,
+                        ..Suggestion::new(
+                            t!("global.preview_count", count = targets.len()).to_string(),
+                        )
+                    }];
+                    suggestions.extend(targets.into_iter().take(MAX_PREVIEW_LINES).map(|target| {
+                        Suggestion::with_description(
+                            target.text,
+                            t!("global.preview_line", line = target.index + 1).to_string(),
+                        )
+                    }));
+                    suggestions
+                }
+                Err(error) => vec![Suggestion {
+                    disabled: true,
+                    ..Suggestion::new(parse_error_message(&error))
+                }],
+            }
+        };
+        if let Some(prompt) = &mut self.prompt {
+            prompt.suggestions = suggestions;
+            prompt.selected_suggestion = None;
+        }
+    }
+
+    /// Run a global command confirmed in the prompt
+    pub fn handle_global_command(&mut self, input: &str) {
+        let command = match parse_global_command(input) {
+            Ok(command) => command,
+            Err(error) => {
+                self.set_status_message(parse_error_message(&error));
+                return;
+            }
+        };
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        let targets = self.global_command_targets(&command);
+        if targets.is_empty() {
+            self.set_status_message(t!("global.no_matches").to_string());
+            return;
+        }
+
+        // Actions the command runs belong to it, so macros and the last edit
+        // only record the command itself
+        let macro_playing = std::mem::replace(&mut self.macro_playing, true);
+        let repeating_edit = std::mem::replace(&mut self.repeating_edit, true);
+        if self.active_cursors().count() > 1 {
+            let _ = self.handle_action(Action::RemoveSecondaryCursors);
+        }
+        let applied = match &command.command {
+            LineCommand::Normal(steps) => self.run_normal_on_lines(steps, &targets),
+            line_command => self.edit_lines(line_command, &targets),
+        };
+        self.macro_playing = macro_playing;
+        self.repeating_edit = repeating_edit;
+
+        let count = targets.len();
+        if applied {
+            self.set_status_message(t!("global.applied", count = count).to_string());
+        }
+    }
+
+    /// Delete, indent, dedent or pipe the target lines as one bulk edit
+    fn edit_lines(&mut self, command: &LineCommand, targets: &[TargetLine]) -> bool {
+        let settings = &self.active_state().buffer_settings;
+        let tab_size = settings.tab_size.max(1);
+        let indent = if settings.use_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(tab_size)
+        };
+
+        // Replacements in document order, not overlapping
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        for target in targets {
+            match command {
+                LineCommand::Delete => {
+                    let mut range = target.range.start..target.range.end + target.ending_len;
+                    if target.ending_len == 0 && target.range.start > 0 {
+                        // Deleting the last line takes the line ending before it
+                        let before = self.active_state_mut().get_text_range(
+                            target.range.start.saturating_sub(2),
+                            target.range.start,
+                        );
+                        range.start -= if before == "\r\n" { 2 } else { 1 };
+                    }
+                    match edits.last_mut() {
+                        Some((last, _)) if last.end >= range.start => last.end = range.end,
+                        _ => edits.push((range, String::new())),
+                    }
+                }
+                LineCommand::Indent => {
+                    if !target.text.trim().is_empty() {
+                        let start = target.range.start;
+                        edits.push((start..start, indent.clone()));
+                    }
+                }
+                LineCommand::Dedent => {
+                    let mut width = 0;
+                    let removed = target
+                        .text
+                        .chars()
+                        .take_while(|&c| {
+                            let take = width < tab_size && (c == ' ' || c == '\t');
+                            width += if c == '\t' { tab_size } else { 1 };
+                            take
+                        })
+                        .count();
+                    if removed > 0 {
+                        let start = target.range.start;
+                        edits.push((start..start + removed, String::new()));
+                    }
+                }
+                LineCommand::Pipe(shell) => {
+                    match run_shell_filter(shell, &format!("{}\n", target.text)) {
+                        Ok(output) => {
+                            let output = output
+                                .strip_suffix('\n')
+                                .map(|o| o.strip_suffix('\r').unwrap_or(o))
+                                .unwrap_or(&output);
+                            edits.push((target.range.clone(), output.to_string()));
+                        }
+                        Err(error) => {
+                            self.set_status_message(error);
+                            return false;
+                        }
+                    }
+                }
+                LineCommand::Normal(_) => unreachable!("normal commands don't edit lines"),
+            }
+        }
+        if edits.is_empty() {
+            return true;
+        }
+
+        // Start from the first line, so that the edits don't shift the cursor
+        let cursor = self.active_cursors_mut().primary_mut();
+        cursor.position = targets[0].range.start;
+        cursor.anchor = None;
+        let cursor = *cursor;
+        let cursor_id = self.active_cursors().primary_id();
+        let state = self.active_state_mut();
+        let mut events = Vec::new();
+        for (range, text) in edits.iter().rev() {
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text: state.get_text_range(range.start, range.end),
+                    cursor_id,
+                });
+            }
+            if !text.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: text.clone(),
+                    cursor_id,
+                });
+            }
+        }
+        // The cursor goes to the first line changed
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: edits[0].0.start.min(targets[0].range.start),
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        });
+
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "Global command".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+        true
+    }
+
+    /// Run actions and type text with the cursor at the start of each target
+    /// line, recording the result as one undo step
+    fn run_normal_on_lines(&mut self, steps: &[NormalStep], targets: &[TargetLine]) -> bool {
+        let mut actions = Vec::new();
+        for step in steps {
+            match step {
+                NormalStep::Action(name) => match Action::from_str(name, &HashMap::new()) {
+                    Some(action) => actions.push(action),
+                    None => {
+                        self.set_status_message(
+                            t!("global.error_action", action = name).to_string(),
+                        );
+                        return false;
+                    }
+                },
+                NormalStep::Text(text) => {
+                    actions.extend(text.chars().map(|c| match c {
+                        '\n' => Action::InsertNewline,
+                        c => Action::InsertChar(c),
+                    }));
+                }
+            }
+        }
+
+        let old_cursors = self.bulk_edit_cursors();
+        let old_snapshot = self.active_state().buffer.snapshot_buffer_state();
+        let log_start = self.active_event_log().current_index();
+
+        let width = self.cached_layout.last_frame_width;
+        let height = self.cached_layout.last_frame_height;
+        // From the last line up, so earlier lines keep their positions
+        for target in targets.iter().rev() {
+            let cursor = self.active_cursors_mut().primary_mut();
+            cursor.position = target.range.start;
+            cursor.anchor = None;
+            for action in &actions {
+                let _ = self.handle_action(action.clone());
+                self.recompute_layout(width, height);
+            }
+        }
+
+        if self.active_event_log().current_index() > log_start {
+            let bulk_edit = Event::BulkEdit {
+                old_snapshot: Some(old_snapshot),
+                new_snapshot: Some(self.active_state().buffer.snapshot_buffer_state()),
+                old_cursors,
+                new_cursors: self.bulk_edit_cursors(),
+                description: "Global command".to_string(),
+            };
+            self.active_event_log_mut()
+                .replace_since(log_start, bulk_edit);
+        }
+        true
+    }
+
+    /// Cursors of the active buffer as recorded in a bulk edit
+    fn bulk_edit_cursors(&self) -> Vec<(crate::model::event::CursorId, usize, Option<usize>)> {
+        self.active_cursors()
+            .iter()
+            .map(|(id, cursor)| (id, cursor.position, cursor.anchor))
+            .collect()
+    }
+}
@@ -0,0 +1,394 @@
+//! Health check: what the editor can't do in this environment, and why
+//!
+//! `Check Health` opens the `*Health*` buffer and `fresh --health` prints the
+//! same report. It covers the terminal (colors, keyboard protocol,
+//! clipboard), the config files, the language servers and formatters the
+//! config refers to, and the loaded plugins. Anything not ok comes with a
+//! suggested fix.
+
+use super::Editor;
+use crate::config::{ColorMode, Keybinding};
+use crate::config_io::ConfigResolver;
+use crate::input::buffer_mode::HEALTH_MODE;
+use crate::input::keybindings::Action;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::health::{
+    find_on_path, HealthCheck, HealthReport, HealthSection, HealthStatus,
+};
+use crate::view::color_support::ColorCapability;
+use rust_i18n::t;
+
+/// Name of the read-only buffer showing the health check report
+pub const HEALTH_BUFFER_NAME: &str = "*Health*";
+
+/// Key of a binding as written in the config, e.g. `ctrl+k ctrl+d`
+fn binding_keys(binding: &Keybinding) -> String {
+    let key = |modifiers: &[String], key: &str| {
+        let mut parts: Vec<&str> = modifiers.iter().map(String::as_str).collect();
+        parts.push(key);
+        parts.join("+")
+    };
+    if binding.keys.is_empty() {
+        key(&binding.modifiers, &binding.key)
+    } else {
+        binding
+            .keys
+            .iter()
+            .map(|press| key(&press.modifiers, &press.key))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Editor {
+    /// Note whether the terminal accepted the keyboard enhancement protocol
+    pub fn set_keyboard_enhancement(&mut self, enabled: Option<bool>) {
+        self.keyboard_enhancement = enabled;
+    }
+
+    /// Open the `*Health*` buffer, running the checks again if it is open
+    pub fn show_health(&mut self) {
+        let report = self.health_report();
+        let buffer_id = self
+            .find_buffer_by_name(HEALTH_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    HEALTH_BUFFER_NAME.to_string(),
+                    HEALTH_MODE.to_string(),
+                    true,
+                )
+            });
+        if let Err(e) = self
+            .set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(report.to_text())])
+        {
+            tracing::error!("Failed to fill health buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+
+        let problems = report.count(HealthStatus::Error) + report.count(HealthStatus::Warning);
+        let report_status = if problems > 0 {
+            t!("health.problems_found", count = problems)
+        } else {
+            t!("health.all_ok")
+        };
+        self.set_status_message(report_status.to_string());
+    }
+
+    /// Run all checks
+    pub fn health_report(&self) -> HealthReport {
+        HealthReport {
+            sections: vec![
+                self.terminal_health(),
+                self.config_health(),
+                self.lsp_health(),
+                self.formatter_health(),
+                self.plugin_health(),
+            ],
+        }
+    }
+
+    fn terminal_health(&self) -> HealthSection {
+        let mut section = HealthSection::new("Terminal");
+
+        let forced = std::env::var("FRESH_COLOR_MODE").is_ok();
+        let source = if forced {
+            "set by FRESH_COLOR_MODE".to_string()
+        } else if self.config.editor.color_mode != ColorMode::Auto {
+            "set by editor.color_mode".to_string()
+        } else {
+            format!(
+                "detected from TERM={} COLORTERM={}",
+                std::env::var("TERM").unwrap_or_default(),
+                std::env::var("COLORTERM").unwrap_or_default()
+            )
+        };
+        section.push(match self.color_capability {
+            ColorCapability::TrueColor => {
+                HealthCheck::ok("Colors", format!("truecolor ({})", source))
+            }
+            ColorCapability::Color256 | ColorCapability::Color16 => {
+                let colors = if self.color_capability == ColorCapability::Color256 {
+                    "256"
+                } else {
+                    "16"
+                };
+                HealthCheck::new(
+                    HealthStatus::Info,
+                    "Colors",
+                    format!("{} colors, themes are approximated ({})", colors, source),
+                )
+                .with_fix(
+                    "If the terminal supports 24-bit color, set COLORTERM=truecolor \
+                     or editor.color_mode to \"truecolor\"",
+                )
+            }
+        });
+
+        let editor = &self.config.editor;
+        let requested = editor.keyboard_disambiguate_escape_codes
+            || editor.keyboard_report_event_types
+            || editor.keyboard_report_alternate_keys
+            || editor.keyboard_report_all_keys_as_escape_codes;
+        section.push(match (requested, self.keyboard_enhancement) {
+            (false, _) => HealthCheck::new(
+                HealthStatus::Info,
+                "Keyboard protocol",
+                "disabled by the editor.keyboard_* settings",
+            ),
+            (true, Some(true)) => HealthCheck::ok("Keyboard protocol", "enhanced keys enabled"),
+            (true, Some(false)) => HealthCheck::new(
+                HealthStatus::Warning,
+                "Keyboard protocol",
+                "not supported, some key combinations can't be told apart",
+            )
+            .with_fix(
+                "Use a terminal with the kitty keyboard protocol (kitty, WezTerm, foot, \
+                 Ghostty, Alacritty), or remap keys with Calibrate Keyboard",
+            ),
+            (true, None) => HealthCheck::new(
+                HealthStatus::Info,
+                "Keyboard protocol",
+                "unknown, not running in a terminal",
+            ),
+        });
+
+        let clipboard = &self.config.clipboard;
+        if clipboard.use_system_clipboard {
+            section.push(
+                match crate::services::clipboard::connect_system_clipboard() {
+                    Ok(()) => HealthCheck::ok("System clipboard", "available"),
+                    Err(e) if clipboard.use_osc52 => HealthCheck::new(
+                        HealthStatus::Info,
+                        "System clipboard",
+                        format!("unavailable ({}), copying with OSC 52 only", e),
+                    )
+                    .with_fix("Paste from other applications may need the terminal's paste key"),
+                    Err(e) => HealthCheck::new(
+                        HealthStatus::Warning,
+                        "System clipboard",
+                        format!("unavailable ({}), copies stay inside the editor", e),
+                    )
+                    .with_fix("Enable clipboard.use_osc52 to copy through the terminal"),
+                },
+            );
+        }
+        section.push(if clipboard.use_osc52 {
+            HealthCheck::ok("OSC 52", "enabled, copies go through the terminal")
+        } else {
+            HealthCheck::new(HealthStatus::Info, "OSC 52", "disabled")
+                .with_fix("Enable clipboard.use_osc52 to copy over SSH")
+        });
+        section
+    }
+
+    fn config_health(&self) -> HealthSection {
+        let mut section = HealthSection::new("Configuration");
+
+        let resolver = ConfigResolver::new(self.dir_context.clone(), self.working_dir.clone());
+        let layers = [
+            ("User config", Some(resolver.user_config_path())),
+            ("Platform config", resolver.user_platform_config_path()),
+            ("Project config", Some(resolver.project_config_path())),
+            ("Session config", Some(resolver.session_config_path())),
+        ];
+        for (label, path) in layers {
+            let Some(path) = path.filter(|path| path.exists()) else {
+                continue;
+            };
+            let loaded = match label {
+                "User config" => resolver.load_user_layer(),
+                "Platform config" => resolver.load_user_platform_layer(),
+                "Project config" => resolver.load_project_layer(),
+                _ => resolver.load_session_layer(),
+            };
+            section.push(match loaded {
+                Ok(_) => HealthCheck::ok(label, path.display().to_string()),
+                Err(e) => HealthCheck::new(
+                    HealthStatus::Error,
+                    label,
+                    format!("{}: {}", path.display(), e),
+                )
+                .with_fix("Fix the file; until then all settings are the defaults"),
+            });
+        }
+
+        section.push(match self.config.validate() {
+            Ok(()) => HealthCheck::ok("Settings", "valid"),
+            Err(e) => HealthCheck::new(HealthStatus::Error, "Settings", e.to_string())
+                .with_fix("Correct the setting in Settings or the config file"),
+        });
+
+        let theme: &str = &self.config.theme;
+        section.push(if self.theme_registry.contains(theme) {
+            HealthCheck::ok("Theme", theme)
+        } else {
+            HealthCheck::new(
+                HealthStatus::Warning,
+                "Theme",
+                format!("'{}' not found, using the default", theme),
+            )
+            .with_fix("Pick an installed theme with Select Theme")
+        });
+
+        let unknown: Vec<&Keybinding> = self
+            .config
+            .keybindings
+            .iter()
+            .filter(|binding| {
+                Action::from_str(&binding.action, &binding.args).is_none()
+                    && !self.config.commands.contains_key(&binding.action)
+            })
+            .collect();
+        if unknown.is_empty() {
+            section.push(HealthCheck::ok(
+                "Key bindings",
+                format!("{} custom", self.config.keybindings.len()),
+            ));
+        }
+        for binding in unknown {
+            section.push(
+                HealthCheck::new(
+                    HealthStatus::Warning,
+                    "Key binding",
+                    format!(
+                        "{}: unknown action '{}', the key does nothing",
+                        binding_keys(binding),
+                        binding.action
+                    ),
+                )
+                .with_fix("Check the action name with Keybinding Editor or fresh --dump-keys"),
+            );
+        }
+        section
+    }
+
+    fn lsp_health(&self) -> HealthSection {
+        let mut section = HealthSection::new("Language servers");
+        let mut servers: Vec<_> = self
+            .config
+            .lsp
+            .iter()
+            .filter(|(_, server)| server.enabled && !server.command.is_empty())
+            .collect();
+        servers.sort_by(|a, b| a.0.cmp(b.0));
+        for (language, server) in servers {
+            section.push(match find_on_path(&server.command) {
+                Some(path) => {
+                    HealthCheck::ok(language, format!("{} ({})", server.command, path.display()))
+                }
+                None => {
+                    let (status, when) = if server.auto_start {
+                        (HealthStatus::Warning, "")
+                    } else {
+                        (HealthStatus::Info, ", not started automatically")
+                    };
+                    HealthCheck::new(
+                        status,
+                        language,
+                        format!("{} not found on PATH{}", server.command, when),
+                    )
+                    .with_fix(super::warning_domains::lsp_install_hint(&server.command))
+                }
+            });
+        }
+        section
+    }
+
+    fn formatter_health(&self) -> HealthSection {
+        let mut section = HealthSection::new("Formatters");
+        let mut languages: Vec<_> = self
+            .config
+            .languages
+            .iter()
+            .filter_map(|(language, config)| Some((language, config.formatter.as_ref()?, config)))
+            .collect();
+        languages.sort_by(|a, b| a.0.cmp(b.0));
+        for (language, formatter, config) in languages {
+            section.push(match find_on_path(&formatter.command) {
+                Some(path) => HealthCheck::ok(
+                    language,
+                    format!("{} ({})", formatter.command, path.display()),
+                ),
+                None => {
+                    let status = if config.format_on_save {
+                        HealthStatus::Warning
+                    } else {
+                        HealthStatus::Info
+                    };
+                    HealthCheck::new(
+                        status,
+                        language,
+                        format!("{} not found on PATH", formatter.command),
+                    )
+                    .with_fix(format!(
+                        "Install '{}' or change languages.{}.formatter",
+                        formatter.command, language
+                    ))
+                }
+            });
+        }
+        section
+    }
+
+    fn plugin_health(&self) -> HealthSection {
+        let mut section = HealthSection::new("Plugins");
+        if !self.plugin_manager.is_active() {
+            section.push(HealthCheck::new(
+                HealthStatus::Info,
+                "Plugins",
+                "disabled (--no-plugins or built without plugins)",
+            ));
+            return section;
+        }
+        if self.plugins_loading() {
+            section.push(HealthCheck::new(
+                HealthStatus::Info,
+                "Plugins",
+                "still loading, run the check again",
+            ));
+        }
+
+        #[cfg(feature = "plugins")]
+        {
+            let mut plugins = self.plugin_manager.list_plugins();
+            plugins.sort_by(|a, b| a.name.cmp(&b.name));
+            let enabled = plugins.iter().filter(|plugin| plugin.enabled).count();
+            section.push(HealthCheck::ok(
+                "Loaded",
+                format!("{} plugins, {} enabled", plugins.len(), enabled),
+            ));
+            let deferred: Vec<&str> = self
+                .deferred_plugins
+                .iter()
+                .map(|plugin| plugin.name())
+                .collect();
+            if !deferred.is_empty() {
+                section.push(HealthCheck::new(
+                    HealthStatus::Info,
+                    "Not loaded yet",
+                    format!(
+                        "{} (waiting for their activation events)",
+                        deferred.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        for (plugin, message) in self.plugin_console.errors() {
+            section.push(
+                HealthCheck::new(
+                    HealthStatus::Error,
+                    plugin,
+                    message.lines().next().unwrap_or_default(),
+                )
+                .with_fix("See Show Plugin Console for the full error"),
+            );
+        }
+        section
+    }
+}
@@ -0,0 +1,165 @@
+//! History scrubber: slide through a buffer's edit history.
+//!
+//! The scrubber steps the active buffer's event log one edit at a time using
+//! the regular undo/redo machinery, so the buffer itself is the preview.
+//! Keeping a position leaves the later edits on the redo stack; the next edit
+//! forks from there, replacing them exactly as an edit after undo does.
+//! Cancelling returns to where the scrubber was opened.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use rust_i18n::t;
+
+use crate::model::event::BufferId;
+
+use super::Editor;
+
+/// Edits skipped by PageUp/PageDown
+const PAGE_STEPS: usize = 10;
+
+/// An open history scrubber
+#[derive(Debug)]
+pub(crate) struct HistoryScrubber {
+    buffer: BufferId,
+    /// Edit count when the scrubber was opened
+    origin: usize,
+}
+
+/// What the scrubber bar shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryScrubberView {
+    /// Number of edits applied at the previewed point
+    pub step: usize,
+    /// Number of edits in the history
+    pub total: usize,
+    /// Local time of the last applied edit (None at the start of the history)
+    pub time: Option<String>,
+}
+
+impl Editor {
+    /// Open the history scrubber on the active buffer
+    pub fn open_history_scrubber(&mut self) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        if self.collab_owns_undo() {
+            self.set_status_message(t!("history_scrubber.unavailable_collab").to_string());
+            return;
+        }
+        let (step, total) = self.history_position();
+        if total == 0 {
+            self.set_status_message(t!("history_scrubber.empty").to_string());
+            return;
+        }
+        self.history_scrubber = Some(HistoryScrubber {
+            buffer: self.active_buffer(),
+            origin: step,
+        });
+    }
+
+    /// Whether the history scrubber is open
+    pub fn is_history_scrubber_open(&self) -> bool {
+        self.history_scrubber.is_some()
+    }
+
+    /// State of the open scrubber, for rendering
+    pub fn history_scrubber_view(&self) -> Option<HistoryScrubberView> {
+        self.history_scrubber.as_ref()?;
+        let (step, total) = self.history_position();
+        let log = self.active_event_log();
+        let time = log.entries()[..log.current_index()]
+            .iter()
+            .rev()
+            .find(|entry| entry.event.is_write_action())
+            .and_then(|entry| chrono::DateTime::from_timestamp_millis(entry.timestamp as i64))
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            });
+        Some(HistoryScrubberView { step, total, time })
+    }
+
+    /// Move the preview to `step` edits into the history
+    pub fn scrub_history_to(&mut self, step: usize) {
+        loop {
+            let (current, _) = self.history_position();
+            let index = self.active_event_log().current_index();
+            if current > step {
+                self.handle_undo();
+            } else if current < step && self.active_event_log().can_redo() {
+                self.handle_redo();
+            } else {
+                break;
+            }
+            if self.active_event_log().current_index() == index {
+                break;
+            }
+        }
+    }
+
+    /// Close the scrubber, keeping the previewed point as the current state
+    pub fn keep_history_scrubber(&mut self) {
+        if self.history_scrubber.take().is_none() {
+            return;
+        }
+        let (step, total) = self.history_position();
+        self.set_status_message(
+            t!("history_scrubber.kept", step = step, total = total).to_string(),
+        );
+    }
+
+    /// Close the scrubber and return to where it was opened
+    pub fn cancel_history_scrubber(&mut self) {
+        if let Some(scrubber) = self.history_scrubber.take() {
+            if scrubber.buffer == self.active_buffer() {
+                self.scrub_history_to(scrubber.origin);
+            }
+        }
+    }
+
+    /// Handle a key while the scrubber is open; every key is consumed
+    pub(super) fn handle_history_scrubber_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        // The buffer may have changed underneath (e.g. a mouse click on a tab)
+        if self
+            .history_scrubber
+            .as_ref()
+            .is_some_and(|s| s.buffer != self.active_buffer())
+        {
+            self.history_scrubber = None;
+            return;
+        }
+        if !(modifiers - KeyModifiers::SHIFT).is_empty() {
+            return;
+        }
+        let (step, total) = self.history_position();
+        match code {
+            KeyCode::Left | KeyCode::Char('h') => self.scrub_history_to(step.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => self.scrub_history_to(step + 1),
+            KeyCode::PageUp => self.scrub_history_to(step.saturating_sub(PAGE_STEPS)),
+            KeyCode::PageDown => self.scrub_history_to(step + PAGE_STEPS),
+            KeyCode::Home => self.scrub_history_to(0),
+            KeyCode::End => self.scrub_history_to(total),
+            KeyCode::Enter => self.keep_history_scrubber(),
+            KeyCode::Esc | KeyCode::Char('q') => self.cancel_history_scrubber(),
+            _ => {}
+        }
+    }
+
+    /// (edits applied, edits in the history) for the active buffer
+    fn history_position(&self) -> (usize, usize) {
+        let log = self.active_event_log();
+        let entries = log.entries();
+        let is_edit = |entry: &&crate::model::event::LogEntry| entry.event.is_write_action();
+        let step = entries[..log.current_index()]
+            .iter()
+            .filter(is_edit)
+            .count();
+        let total = step
+            + entries[log.current_index()..]
+                .iter()
+                .filter(is_edit)
+                .count();
+        (step, total)
+    }
+}
@@ -0,0 +1,307 @@
+//! Image previews in editor splits
+//!
+//! Image files open as read-only buffers whose split shows the image instead
+//! of its bytes: a header with the format, dimensions and file size, then
+//! either blank cells that terminal graphics are drawn over once the frame is
+//! on screen, or ASCII art. Plugins can show an image in any buffer, which is
+//! how picker previews display image files.
+
+use super::{BufferId, Editor};
+use crate::config::ImagePreviewMode;
+use crate::model::event::SplitId;
+use crate::view::image_preview::{GraphicsProtocol, ImagePreview, KITTY_DELETE_ALL};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Paragraph};
+use ratatui::Frame;
+use rust_i18n::t;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Image files larger than this are described but not decoded
+const MAX_DECODED_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Cell size in pixels when the terminal doesn't report it
+const DEFAULT_CELL_SIZE: (u16, u16) = (10, 20);
+
+/// Rows above the image: the header line and a blank line
+const HEADER_ROWS: u16 = 2;
+
+/// An image shown in a buffer
+#[derive(Debug, Clone)]
+struct ShownImage {
+    path: PathBuf,
+    preview: Arc<ImagePreview>,
+}
+
+/// An image to draw over the frame with a graphics protocol
+#[derive(Debug, Clone, PartialEq)]
+struct ImagePlacement {
+    buffer_id: BufferId,
+    area: Rect,
+    protocol: GraphicsProtocol,
+    cell: (u16, u16),
+    /// Background of the blank cells left for the image
+    bg: Color,
+}
+
+/// Image previews and the graphics drawn for them
+#[derive(Debug)]
+pub(crate) struct ImagePreviews {
+    /// Images shown in place of buffer contents
+    shown: HashMap<BufferId, ShownImage>,
+    /// Protocol detected from the environment at startup
+    detected: Option<GraphicsProtocol>,
+    /// Images to draw over the frame being rendered
+    placements: Vec<ImagePlacement>,
+    /// Images currently on the terminal
+    drawn: Vec<ImagePlacement>,
+}
+
+impl Default for ImagePreviews {
+    fn default() -> Self {
+        Self {
+            shown: HashMap::new(),
+            detected: GraphicsProtocol::detect(|name| std::env::var(name).ok()),
+            placements: Vec::new(),
+            drawn: Vec::new(),
+        }
+    }
+}
+
+/// Pixel size of a terminal cell, when the terminal reports its size in pixels
+fn cell_size() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            (size.width / size.columns, size.height / size.rows)
+        }
+        _ => DEFAULT_CELL_SIZE,
+    }
+}
+
+impl Editor {
+    /// Show the image at `path` in place of the buffer's contents. Returns
+    /// false, leaving the buffer as it is, if the file isn't an image in a
+    /// recognized format.
+    pub(crate) fn show_image_preview(&mut self, buffer_id: BufferId, path: &Path) -> bool {
+        let too_large = self
+            .filesystem
+            .metadata_if_exists(path)
+            .is_some_and(|meta| meta.size > MAX_DECODED_FILE_SIZE);
+        let preview = if too_large {
+            self.filesystem
+                .read_range(path, 0, 64 * 1024)
+                .ok()
+                .and_then(ImagePreview::describe)
+        } else {
+            self.filesystem
+                .read_file(path)
+                .ok()
+                .and_then(ImagePreview::from_bytes)
+        };
+        let Some(mut preview) = preview else {
+            return false;
+        };
+        if let Some(meta) = self.filesystem.metadata_if_exists(path) {
+            preview.file_size = meta.size;
+        }
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return false;
+        };
+        state.show_cursors = false;
+        state.editing_disabled = true;
+        self.image_previews.shown.insert(
+            buffer_id,
+            ShownImage {
+                path: path.to_path_buf(),
+                preview: Arc::new(preview),
+            },
+        );
+        true
+    }
+
+    /// Show the buffer's own contents again
+    pub(crate) fn hide_image_preview(&mut self, buffer_id: BufferId) {
+        if self.image_previews.shown.remove(&buffer_id).is_some() {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.show_cursors = true;
+            }
+        }
+    }
+
+    /// Whether the buffer shows an image instead of its contents
+    pub fn is_image_preview(&self, buffer_id: BufferId) -> bool {
+        self.image_previews.shown.contains_key(&buffer_id)
+    }
+
+    /// Graphics protocol images are drawn with, per the config and terminal
+    fn graphics_protocol(&self) -> Option<GraphicsProtocol> {
+        match self.config.editor.image_preview {
+            ImagePreviewMode::Auto => self.image_previews.detected,
+            ImagePreviewMode::Kitty => Some(GraphicsProtocol::Kitty),
+            ImagePreviewMode::Sixel => Some(GraphicsProtocol::Sixel),
+            ImagePreviewMode::Ascii => None,
+        }
+    }
+
+    /// Draw image previews over the content area of the splits showing them
+    pub(super) fn render_image_previews(
+        &mut self,
+        frame: &mut Frame,
+        split_areas: &[(SplitId, BufferId, Rect, Rect, usize, usize)],
+    ) {
+        self.image_previews.placements.clear();
+        if self.image_previews.shown.is_empty() {
+            return;
+        }
+        let protocol = self.graphics_protocol();
+        let bg = if self.config.editor.use_terminal_bg {
+            Color::Reset
+        } else {
+            self.theme.editor_bg
+        };
+        let text_style = Style::default().fg(self.theme.editor_fg).bg(bg);
+        let dim_style = Style::default().fg(self.theme.line_number_fg).bg(bg);
+
+        for (_, buffer_id, area, _, _, _) in split_areas {
+            let Some(shown) = self.image_previews.shown.get(buffer_id) else {
+                continue;
+            };
+            let preview = &shown.preview;
+            frame.render_widget(Clear, *area);
+            frame.render_widget(Block::default().style(text_style), *area);
+
+            let name = shown
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let details = format!(
+                "  {}  {}×{}  {}",
+                preview.format.name(),
+                preview.width,
+                preview.height,
+                super::file_open::format_size(preview.file_size)
+            );
+            let header = Line::from(vec![
+                Span::styled(format!(" {}", name), text_style),
+                Span::styled(details, dim_style),
+            ]);
+            frame.render_widget(
+                Paragraph::new(header),
+                Rect::new(area.x, area.y, area.width, 1.min(area.height)),
+            );
+
+            let image_area = Rect::new(
+                area.x + 1,
+                area.y + HEADER_ROWS.min(area.height),
+                area.width.saturating_sub(2),
+                area.height.saturating_sub(HEADER_ROWS),
+            );
+            if image_area.width == 0 || image_area.height == 0 {
+                continue;
+            }
+            if !preview.can_draw() {
+                let message = t!("buffer.image_not_drawable", format = preview.format.name());
+                frame.render_widget(
+                    Paragraph::new(Span::styled(message.to_string(), dim_style)),
+                    Rect::new(image_area.x, image_area.y, image_area.width, 1),
+                );
+                continue;
+            }
+
+            match protocol {
+                Some(protocol) => {
+                    let cell = cell_size();
+                    let (cols, rows) =
+                        preview.fit(image_area.width, image_area.height, cell, false);
+                    let x = image_area.x + (image_area.width - cols) / 2;
+                    self.image_previews.placements.push(ImagePlacement {
+                        buffer_id: *buffer_id,
+                        area: Rect::new(x, image_area.y, cols, rows),
+                        protocol,
+                        cell,
+                        bg,
+                    });
+                }
+                None => {
+                    let (cols, rows) =
+                        preview.fit(image_area.width, image_area.height, DEFAULT_CELL_SIZE, true);
+                    let x = image_area.x + (image_area.width - cols) / 2;
+                    let lines: Vec<Line> = preview
+                        .ascii_art(cols, rows)
+                        .into_iter()
+                        .map(|line| Line::from(Span::styled(line, text_style)))
+                        .collect();
+                    frame.render_widget(
+                        Paragraph::new(lines),
+                        Rect::new(x, image_area.y, cols, rows),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Drop images whose cells were drawn over by popups, menus or prompts
+    /// after the splits were rendered, so the overlay stays visible
+    pub(super) fn drop_covered_images(&mut self, frame: &mut Frame) {
+        let buffer = frame.buffer_mut();
+        self.image_previews.placements.retain(|placement| {
+            placement.area.positions().all(|position| {
+                buffer
+                    .cell(position)
+                    .is_some_and(|cell| cell.symbol() == " " && cell.bg == placement.bg)
+            })
+        });
+    }
+
+    /// Escape sequences that bring the images on the terminal in line with
+    /// the last rendered frame. Write them right after the frame.
+    pub fn take_graphics_output(&mut self) -> Vec<u8> {
+        let images = &mut self.image_previews;
+        if images.placements == images.drawn {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        if images
+            .drawn
+            .iter()
+            .any(|p| p.protocol == GraphicsProtocol::Kitty)
+        {
+            out.extend_from_slice(KITTY_DELETE_ALL);
+        }
+        // Sixel pixels stay until their cells are written again, so clear
+        // the screen and draw the images on the next frame
+        if images
+            .drawn
+            .iter()
+            .any(|p| p.protocol == GraphicsProtocol::Sixel)
+        {
+            images.drawn.clear();
+            self.full_redraw_requested = true;
+            return out;
+        }
+        for placement in &images.placements {
+            if let Some(shown) = images.shown.get(&placement.buffer_id) {
+                out.extend(shown.preview.encode(
+                    placement.protocol,
+                    placement.area.x,
+                    placement.area.y,
+                    placement.area.width,
+                    placement.area.height,
+                    placement.cell,
+                ));
+            }
+        }
+        images.drawn = images.placements.clone();
+        out
+    }
+
+    /// Forget the images on the terminal after the screen was cleared, so
+    /// they are drawn again
+    pub(crate) fn forget_drawn_images(&mut self) {
+        self.image_previews.drawn.clear();
+    }
+}
@@ -0,0 +1,101 @@
+//! Incrementing and decrementing the values under the cursors
+
+use super::Editor;
+use crate::model::cursor::Cursor;
+use crate::model::event::{CursorId, Event};
+use crate::primitives::increment::increment_at;
+use rust_i18n::t;
+use std::ops::Range;
+
+impl Editor {
+    /// Add `step` to the number or cycle word under each cursor. With
+    /// `sequence`, cursors get `step`, `2 * step`, `3 * step`... in
+    /// document order.
+    pub(super) fn increment_values(&mut self, step: i64, sequence: bool) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        let cycles = self.config.editor.increment_cycles.clone();
+        let estimated_line_length = self.config.editor.estimated_line_length;
+
+        let mut cursors: Vec<_> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, cursor)| (id, *cursor))
+            .collect();
+        cursors.sort_by_key(|(_, cursor)| cursor.position);
+
+        // Edits in document order, at most one per value
+        let mut edits: Vec<(CursorId, Cursor, Range<usize>, String)> = Vec::new();
+        let state = self.active_state_mut();
+        for (index, (cursor_id, cursor)) in cursors.into_iter().enumerate() {
+            let delta = if sequence {
+                step.saturating_mul(index as i64 + 1)
+            } else {
+                step
+            };
+            let mut iter = state
+                .buffer
+                .line_iterator(cursor.position, estimated_line_length);
+            let line_start = iter.current_position();
+            let Some((_, content)) = iter.next_line() else {
+                continue;
+            };
+            let line = content.trim_end_matches(['\r', '\n']);
+            let Some(edit) = increment_at(line, cursor.position - line_start, delta, &cycles)
+            else {
+                continue;
+            };
+            let range = line_start + edit.range.start..line_start + edit.range.end;
+            if edits
+                .last()
+                .is_some_and(|(_, _, last, _)| last.end > range.start)
+            {
+                continue;
+            }
+            edits.push((cursor_id, cursor, range, edit.text));
+        }
+        if edits.is_empty() {
+            self.set_status_message(t!("increment.nothing").to_string());
+            return;
+        }
+
+        let state = self.active_state_mut();
+        let mut events = Vec::new();
+        for (cursor_id, _, range, text) in edits.iter().rev() {
+            events.push(Event::Delete {
+                range: range.clone(),
+                deleted_text: state.get_text_range(range.start, range.end),
+                cursor_id: *cursor_id,
+            });
+            events.push(Event::Insert {
+                position: range.start,
+                text: text.clone(),
+                cursor_id: *cursor_id,
+            });
+        }
+
+        // Leave each cursor on the last character of its new value, so
+        // repeating the action changes the same value again
+        let mut shift = 0isize;
+        for (cursor_id, cursor, range, text) in &edits {
+            let start = (range.start as isize + shift) as usize;
+            events.push(Event::MoveCursor {
+                cursor_id: *cursor_id,
+                old_position: cursor.position,
+                new_position: start + text.len().saturating_sub(1),
+                old_anchor: cursor.anchor,
+                new_anchor: None,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: 0,
+            });
+            shift += text.len() as isize - range.len() as isize;
+        }
+
+        let description = if step < 0 { "Decrement" } else { "Increment" };
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+    }
+}
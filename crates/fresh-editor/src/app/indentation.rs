@@ -0,0 +1,55 @@
+//! Indentation detection for opened files, and conversion of a buffer's
+//! indentation between tabs and spaces
+
+use super::Editor;
+use crate::primitives::indent_detection::{convert_indentation, detect_indentation};
+use crate::state::EditorState;
+use rust_i18n::t;
+
+/// Bytes from the start of a file examined when detecting its indentation
+const DETECTION_SAMPLE_BYTES: usize = 256 * 1024;
+
+impl Editor {
+    /// Use the indentation found in a newly opened file for its buffer
+    pub(super) fn apply_detected_indentation(&self, state: &mut EditorState) {
+        if !self.config.editor.detect_indentation || state.buffer.is_binary() {
+            return;
+        }
+        let end = state.buffer.len().min(DETECTION_SAMPLE_BYTES);
+        let sample = state.get_text_range(0, end);
+        let Some(detected) = detect_indentation(&sample) else {
+            return;
+        };
+        state.buffer_settings.use_tabs = detected.use_tabs;
+        if let Some(width) = detected.width {
+            state.buffer_settings.tab_size = width;
+        }
+    }
+
+    /// Rewrite the active buffer's leading whitespace as tabs or spaces
+    pub(super) fn convert_active_indentation(&mut self, use_tabs: bool) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        let tab_size = self.active_state().buffer_settings.tab_size;
+        let Some(content) = self.active_state().buffer.to_string() else {
+            return;
+        };
+        let converted = convert_indentation(&content, use_tabs, tab_size);
+        if converted != content {
+            if let Err(e) = self.replace_active_buffer_text(&converted, "Convert Indentation") {
+                tracing::warn!("Failed to convert indentation: {}", e);
+                return;
+            }
+        }
+        self.active_state_mut().buffer_settings.use_tabs = use_tabs;
+
+        let message = if use_tabs {
+            t!("indentation.converted_to_tabs", size = tab_size)
+        } else {
+            t!("indentation.converted_to_spaces", size = tab_size)
+        };
+        self.set_status_message(message.to_string());
+    }
+}
@@ -1,6 +1,10 @@
+use super::git_diff_view::GitDiffBase;
 use super::*;
 use crate::model::event::CursorId;
+use crate::services::git::GitOperation;
+use crate::services::lsp::async_handler::GotoTarget;
 use crate::services::plugins::hooks::HookArgs;
+use crate::view::split::FocusDirection;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 impl Editor {
@@ -33,6 +37,7 @@ impl Editor {
         use crate::input::keybindings::Action;
 
         let _t_total = std::time::Instant::now();
+        crate::services::perf::count(crate::services::perf::Counter::Keys, 1);
 
         tracing::trace!(
             "Editor.handle_key: code={:?}, modifiers={:?}",
@@ -60,6 +65,7 @@ impl Editor {
 
         // Special case: Hover and Signature Help popups should be dismissed on any key press
         // EXCEPT for Ctrl+C when the popup has a text selection (allow copy first)
+        self.signature_help_dismissed_by_key = false;
         if matches!(context, crate::input::keybindings::KeyContext::Popup) {
             // Check if the current popup is transient (hover, signature help)
             let (is_transient_popup, has_selection) = {
@@ -76,8 +82,22 @@ impl Editor {
                     .modifiers
                     .contains(crossterm::event::KeyModifiers::CONTROL);
 
+            // Alt+Up/Alt+Down cycle through signature help overloads
+            let is_signature_help = self.active_state().popups.is_signature_help_popup();
+            if is_signature_help
+                && key_event.modifiers == crossterm::event::KeyModifiers::ALT
+                && matches!(
+                    key_event.code,
+                    crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Down
+                )
+                && self.cycle_signature_help(key_event.code == crossterm::event::KeyCode::Down)
+            {
+                return Ok(());
+            }
+
             if is_transient_popup && !(has_selection && is_copy_key) {
                 // Dismiss the popup on any key press (except Ctrl+C with selection)
+                self.signature_help_dismissed_by_key = is_signature_help;
                 self.hide_popup();
                 tracing::debug!("Dismissed transient popup on key press");
                 // Recalculate context now that popup is gone
@@ -104,6 +124,21 @@ impl Editor {
                 | crate::input::keybindings::KeyContext::FileExplorer
         );
 
+        // The history scrubber is modal while no prompt, menu or popup is open
+        if self.history_scrubber.is_some()
+            && matches!(context, crate::input::keybindings::KeyContext::Normal)
+        {
+            self.handle_history_scrubber_key(code, modifiers);
+            return Ok(());
+        }
+
+        // Pager mode keys (q, /, G, F, ...) take precedence over regular bindings
+        if matches!(context, crate::input::keybindings::KeyContext::Normal) {
+            if let Some(action) = self.pager_action_for_key(code, modifiers) {
+                return self.handle_action(action);
+            }
+        }
+
         if should_check_mode_bindings {
             // If we're in a global editor mode, handle chords and keybindings
             if let Some(ref mode_name) = self.editor_mode {
@@ -173,9 +208,20 @@ impl Editor {
 
         // Check for chord sequence matches first
         let key_event = crossterm::event::KeyEvent::new(code, modifiers);
-        let chord_result = self
-            .keybindings
-            .resolve_chord(&self.chord_state, &key_event, context);
+        let mut chord_result = crate::input::keybindings::ChordResolution::NoMatch;
+        // Plugin bindings for active custom contexts come before the regular tables
+        if matches!(context, crate::input::keybindings::KeyContext::Normal) {
+            chord_result = self.keybindings.resolve_custom_context_chord(
+                &self.chord_state,
+                &key_event,
+                &self.active_custom_contexts,
+            );
+        }
+        if chord_result == crate::input::keybindings::ChordResolution::NoMatch {
+            chord_result = self
+                .keybindings
+                .resolve_chord(&self.chord_state, &key_event, context);
+        }
 
         match chord_result {
             crate::input::keybindings::ChordResolution::Complete(action) => {
@@ -209,6 +255,8 @@ impl Editor {
         match action {
             Action::LspCompletion
             | Action::LspGotoDefinition
+            | Action::LspGotoTypeDefinition
+            | Action::LspGotoImplementation
             | Action::LspReferences
             | Action::LspHover
             | Action::None => {
@@ -232,6 +280,7 @@ impl Editor {
 
         // Record action to macro if recording
         self.record_macro_action(&action);
+        self.record_repeatable_edit(&action);
 
         match action {
             Action::Quit => self.quit(),
@@ -242,8 +291,10 @@ impl Editor {
                 self.should_detach = true;
             }
             Action::Save => {
-                // Check if buffer has a file path - if not, redirect to SaveAs
-                if self.active_state().buffer.file_path().is_none() {
+                // A diff/merge view saves the files shown in its panes
+                if self.save_diff_session()? {
+                    // handled
+                } else if self.active_state().buffer.file_path().is_none() {
                     self.start_prompt_with_initial_text(
                         t!("file.save_as_prompt").to_string(),
                         PromptType::SaveFileAs,
@@ -256,6 +307,10 @@ impl Editor {
                         t!("file.file_changed_prompt").to_string(),
                         PromptType::ConfirmSaveConflict,
                     );
+                } else if self.request_before_save() {
+                    // Saved once the plugins' before_file_save handlers answer
+                } else if self.request_format_on_save() {
+                    // Saved once the language server's formatting edits arrive
                 } else {
                     self.save()?;
                 }
@@ -286,6 +341,9 @@ impl Editor {
                 self.prefill_open_file_prompt();
                 self.init_file_open_state();
             }
+            Action::OpenFileUnderCursor => self.open_file_under_cursor(false),
+            Action::OpenFileUnderCursorInSplit => self.open_file_under_cursor(true),
+            Action::OpenLink => self.open_link_under_cursor(),
             Action::SwitchProject => {
                 self.start_prompt(
                     t!("file.switch_project_prompt").to_string(),
@@ -390,14 +448,14 @@ impl Editor {
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
             Action::Cut => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 self.cut_selection()
             }
             Action::Paste => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 self.paste()
@@ -412,12 +470,16 @@ impl Editor {
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::HistoryScrubber => self.open_history_scrubber(),
             Action::ShowHelp => {
                 self.open_help_manual();
             }
             Action::ShowKeyboardShortcuts => {
                 self.open_keyboard_shortcuts();
             }
+            Action::ShowTutor => {
+                self.show_tutor();
+            }
             Action::ShowWarnings => {
                 self.show_warnings_popup();
             }
@@ -427,6 +489,12 @@ impl Editor {
             Action::ShowLspStatus => {
                 self.show_lsp_status_popup();
             }
+            Action::ShowLspInfo => {
+                self.show_lsp_info();
+            }
+            Action::ShowBuffers => {
+                self.show_buffers();
+            }
             Action::ClearWarnings => {
                 self.clear_warnings();
             }
@@ -530,7 +598,13 @@ impl Editor {
                 self.request_completion()?;
             }
             Action::LspGotoDefinition => {
-                self.request_goto_definition()?;
+                self.request_goto_definition(GotoTarget::Definition)?;
+            }
+            Action::LspGotoTypeDefinition => {
+                self.request_goto_definition(GotoTarget::TypeDefinition)?;
+            }
+            Action::LspGotoImplementation => {
+                self.request_goto_definition(GotoTarget::Implementation)?;
             }
             Action::LspRename => {
                 self.start_rename()?;
@@ -547,12 +621,108 @@ impl Editor {
             Action::LspCodeActions => {
                 self.request_code_actions()?;
             }
+            Action::LspFormatDocument => {
+                self.request_formatting(false, false);
+            }
+            Action::LspFormatRange => {
+                self.request_formatting(true, false);
+            }
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
             Action::LspStop => {
                 self.handle_lsp_stop();
             }
+            Action::LspInfoRestart => {
+                self.lsp_info_restart();
+            }
+            Action::LspInfoStop => {
+                self.lsp_info_stop();
+            }
+            Action::LspInfoOpenLog => {
+                self.lsp_info_open_log();
+            }
+            Action::LspLogCycleLevel => {
+                self.lsp_log_cycle_level();
+            }
+            Action::BuffersPanelOpen => {
+                self.buffers_panel_open();
+            }
+            Action::BuffersPanelUnload => {
+                self.buffers_panel_unload();
+            }
+            Action::FilterKeyboardShortcuts => {
+                self.start_keyboard_shortcuts_filter();
+            }
+            Action::DebugStart => {
+                self.debug_start();
+            }
+            Action::DebugStop => {
+                self.debug_stop();
+            }
+            Action::DebugContinue => {
+                self.debug_continue();
+            }
+            Action::DebugStepOver => {
+                self.debug_step_over();
+            }
+            Action::DebugStepInto => {
+                self.debug_step_into();
+            }
+            Action::DebugStepOut => {
+                self.debug_step_out();
+            }
+            Action::DebugToggleBreakpoint => {
+                self.debug_toggle_breakpoint();
+            }
+            Action::ShowDebugVariables => {
+                self.show_debug_variables();
+            }
+            Action::ShowDebugConsole => {
+                self.show_debug_console();
+            }
+            Action::ShowPluginConsole => {
+                self.show_plugin_console();
+            }
+            Action::PluginConsoleCycleLevel => {
+                self.plugin_console_cycle_level();
+            }
+            Action::PluginConsoleCyclePlugin => {
+                self.plugin_console_cycle_plugin();
+            }
+            Action::ShowLog => {
+                self.show_log();
+            }
+            Action::LogCycleLevel => {
+                self.log_cycle_level();
+            }
+            Action::LogCycleSource => {
+                self.log_cycle_source();
+            }
+            Action::CheckHealth => {
+                self.show_health();
+            }
+            Action::ShowKeybindingConflicts => {
+                self.show_keybinding_conflicts();
+            }
+            Action::KeybindingConflictsRebind => {
+                self.keybinding_conflicts_rebind();
+            }
+            Action::KeybindingConflictsDisable => {
+                self.keybinding_conflicts_disable();
+            }
+            Action::DebugAddWatch => {
+                self.debug_add_watch();
+            }
+            Action::DebugRemoveWatch => {
+                self.debug_remove_watch();
+            }
+            Action::DebugEvaluate => {
+                self.debug_evaluate();
+            }
+            Action::DebugToggleVariable => {
+                self.debug_toggle_variable();
+            }
             Action::LspToggleForBuffer => {
                 self.handle_lsp_toggle_for_buffer();
             }
@@ -638,6 +808,8 @@ impl Editor {
             Action::PrevBuffer => self.prev_buffer(),
             Action::SwitchToPreviousTab => self.switch_to_previous_tab(),
             Action::SwitchToTabByName => self.start_switch_to_tab_prompt(),
+            Action::InsertUnicodeCharacter => self.start_unicode_picker(),
+            Action::InspectCharacter => self.inspect_character(),
 
             // Tab scrolling (manual scroll - don't auto-adjust)
             Action::ScrollTabsLeft => {
@@ -664,16 +836,48 @@ impl Editor {
             Action::IncreaseSplitSize => self.adjust_split_size(0.05),
             Action::DecreaseSplitSize => self.adjust_split_size(-0.05),
             Action::ToggleMaximizeSplit => self.toggle_maximize_split(),
+            Action::FocusSplitLeft => self.focus_split_direction(FocusDirection::Left),
+            Action::FocusSplitRight => self.focus_split_direction(FocusDirection::Right),
+            Action::FocusSplitUp => self.focus_split_direction(FocusDirection::Up),
+            Action::FocusSplitDown => self.focus_split_direction(FocusDirection::Down),
+            Action::RotateSplits => self.rotate_splits(),
+            Action::CloseOtherSplits => self.close_other_splits(),
+            Action::EqualizeSplits => self.equalize_splits(),
+            Action::SaveLayout => self.start_save_layout_prompt(),
+            Action::RestoreLayout => self.start_layout_prompt(PromptType::RestoreLayout),
+            Action::DeleteLayout => self.start_layout_prompt(PromptType::DeleteLayout),
+            Action::GitDiffHead => self.open_git_diff(GitDiffBase::Head),
+            Action::GitDiffIndex => self.open_git_diff(GitDiffBase::Index),
+            Action::GitDiffRevision => self.start_git_diff_revision_prompt(),
+            Action::GitCheckoutBranch => self.start_git_checkout_branch(),
+            Action::GitFetch => self.run_git_operation(GitOperation::Fetch),
+            Action::GitPull => self.run_git_operation(GitOperation::Pull),
+            Action::GitPush => self.run_git_operation(GitOperation::Push),
+            Action::GitLog => self.open_git_log(),
+            Action::DiffNextHunk => self.diff_jump_hunk(true),
+            Action::DiffPrevHunk => self.diff_jump_hunk(false),
+            Action::ShowUnsavedChanges => self.show_unsaved_changes(false),
+            Action::ShowUnsavedChangesUnified => self.show_unsaved_changes(true),
+            Action::RevertHunk => self.revert_hunk(),
+            Action::LocalHistory => self.start_local_history_prompt(),
+            Action::ActionConsole => self.start_action_console(),
+            Action::RestoreSnapshot => self.restore_snapshot(),
             Action::ToggleFileExplorer => self.toggle_file_explorer(),
             Action::ToggleMenuBar => self.toggle_menu_bar(),
             Action::ToggleTabBar => self.toggle_tab_bar(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
+            Action::ToggleReadOnly => self.toggle_read_only(),
+            Action::TogglePagerFollow => self.toggle_pager_follow(),
+            Action::CollabHost => self.collab_host_prompt(),
+            Action::CollabJoin => self.collab_join_prompt(),
+            Action::CollabLeave => self.collab_leave(),
             Action::ToggleScrollSync => self.toggle_scroll_sync(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
             Action::ToggleDebugHighlights => self.toggle_debug_highlights(),
+            Action::TogglePerfHud => self.toggle_perf_hud(),
             // Rulers
             Action::AddRuler => {
                 self.start_prompt(t!("rulers.add_prompt").to_string(), PromptType::AddRuler);
@@ -717,6 +921,8 @@ impl Editor {
                     self.set_status_message(status.to_string());
                 }
             }
+            Action::ConvertIndentationToSpaces => self.convert_active_indentation(false),
+            Action::ConvertIndentationToTabs => self.convert_active_indentation(true),
             Action::ToggleTabIndicators => {
                 if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
                     state.buffer_settings.show_whitespace_tabs =
@@ -744,8 +950,16 @@ impl Editor {
             Action::FileExplorerNewDirectory => self.file_explorer_new_directory(),
             Action::FileExplorerDelete => self.file_explorer_delete(),
             Action::FileExplorerRename => self.file_explorer_rename(),
+            Action::FileExplorerToggleMark => self.file_explorer_toggle_mark(),
+            Action::FileExplorerMarkUp => self.file_explorer_mark_and_move(false),
+            Action::FileExplorerMarkDown => self.file_explorer_mark_and_move(true),
+            Action::FileExplorerMove => self.file_explorer_move(),
+            Action::FileExplorerCopy => self.file_explorer_copy(),
             Action::FileExplorerToggleHidden => self.file_explorer_toggle_hidden(),
             Action::FileExplorerToggleGitignored => self.file_explorer_toggle_gitignored(),
+            Action::FileExplorerToggleChangedOnly => self.file_explorer_toggle_changed_only(),
+            Action::FileExplorerFilter => self.file_explorer_open_filter(),
+            Action::RevealInFileExplorer => self.reveal_in_file_explorer(),
             Action::FileExplorerSearchClear => self.file_explorer_search_clear(),
             Action::FileExplorerSearchBackspace => self.file_explorer_search_pop_char(),
             Action::RemoveSecondaryCursors => {
@@ -811,8 +1025,7 @@ impl Editor {
                     self.config.active_keybinding_map = map_name.clone().into();
 
                     // Reload the keybinding resolver with the new map
-                    self.keybindings =
-                        crate::input::keybindings::KeybindingResolver::new(&self.config);
+                    self.keybindings.reset_from_config(&self.config);
 
                     self.set_status_message(
                         t!("view.keybindings_switched", map = map_name).to_string(),
@@ -824,21 +1037,36 @@ impl Editor {
                 }
             }
 
-            Action::SmartHome => {
-                self.smart_home();
-            }
             Action::ToggleComment => {
                 self.toggle_comment();
             }
+            Action::Increment(step) => {
+                self.increment_values(step, false);
+            }
+            Action::IncrementSequence(step) => {
+                self.increment_values(step, true);
+            }
             Action::GoToMatchingBracket => {
                 self.goto_matching_bracket();
             }
+            Action::GoToMatchingTag => {
+                self.goto_matching_tag();
+            }
+            Action::SelectElement => {
+                self.select_element();
+            }
             Action::JumpToNextError => {
                 self.jump_to_next_error();
             }
             Action::JumpToPreviousError => {
                 self.jump_to_previous_error();
             }
+            Action::NextDiagnostic => {
+                self.goto_diagnostic(true);
+            }
+            Action::PrevDiagnostic => {
+                self.goto_diagnostic(false);
+            }
             Action::SetBookmark(key) => {
                 self.set_bookmark(key);
             }
@@ -975,6 +1203,7 @@ impl Editor {
                     self.set_status_message(t!("status.no_macro_recorded").to_string());
                 }
             }
+            Action::RepeatLastEdit => self.repeat_last_edit(),
             Action::PromptSetBookmark => {
                 self.start_prompt("Set bookmark (0-9): ".to_string(), PromptType::SetBookmark);
             }
@@ -986,8 +1215,12 @@ impl Editor {
             }
             Action::None => {}
             Action::DeleteBackward => {
+                let buffer_id = self.active_buffer();
+                if self.diff_session_edit(buffer_id, super::diff_mode::DiffEdit::DeleteBackward) {
+                    return Ok(());
+                }
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 // Normal backspace handling
@@ -1009,6 +1242,12 @@ impl Editor {
             }
             Action::PluginAction(action_name) => {
                 tracing::debug!("handle_action: PluginAction('{}')", action_name);
+                // Actions registered by native plugins run on the plugin's own thread
+                if self.plugin_manager.run_native_action(&action_name) {
+                    return Ok(());
+                }
+                // A plugin package waiting for this action loads before it runs
+                self.activate_plugins_for_command(&action_name);
                 // Execute the plugin callback via TypeScript plugin thread
                 // Use non-blocking version to avoid deadlock with async plugin ops
                 #[cfg(feature = "plugins")]
@@ -1037,12 +1276,23 @@ impl Editor {
                     );
                 }
             }
+            Action::UserCommand(name) => {
+                self.run_user_command(&name);
+            }
             Action::OpenTerminal => {
                 self.open_terminal();
             }
             Action::CloseTerminal => {
                 self.close_terminal();
             }
+            Action::ToggleTerminalPanel => self.toggle_terminal_panel(),
+            Action::NewPanelTerminal => self.new_panel_terminal(),
+            Action::RenameTerminal => self.start_rename_terminal_prompt(),
+            Action::SendToRepl => self.send_to_repl(),
+            Action::SendBlockToRepl => self.send_block_to_repl(),
+            Action::TerminalPreviousCommand => self.terminal_previous_command(),
+            Action::TerminalNextCommand => self.terminal_next_command(),
+            Action::OpenTerminalOutputPath => self.open_terminal_output_path(),
             Action::FocusTerminal => {
                 // If viewing a terminal buffer, switch to terminal mode
                 if self.is_terminal_buffer(self.active_buffer()) {
@@ -1091,6 +1341,10 @@ impl Editor {
                 // Run shell command on buffer/selection, replace content
                 self.start_shell_command_prompt(true);
             }
+            Action::GlobalCommand => self.start_global_command_prompt(),
+            Action::ProjectSearch => self.start_project_search_prompt(),
+            Action::ProjectSearchOpen => self.project_search_open(),
+            Action::ProjectSearchRerun => self.project_search_again(),
             Action::OpenSettings => {
                 self.open_settings();
             }
@@ -1151,9 +1405,25 @@ impl Editor {
                 self.open_keybinding_editor();
             }
             Action::PromptConfirm => {
+                let marked = self
+                    .prompt
+                    .as_ref()
+                    .filter(|prompt| prompt.marking_enabled())
+                    .map(|prompt| prompt.marked.clone())
+                    .unwrap_or_default();
                 if let Some((input, prompt_type, selected_index)) = self.confirm_prompt() {
                     use super::prompt_actions::PromptResult;
-                    match self.handle_prompt_confirm_input(input, prompt_type, selected_index) {
+                    let result = if marked.is_empty() {
+                        self.handle_prompt_confirm_input(input, prompt_type, selected_index)
+                    } else {
+                        self.handle_prompt_confirm_marked(
+                            input,
+                            prompt_type,
+                            selected_index,
+                            marked,
+                        )
+                    };
+                    match result {
                         PromptResult::ExecuteAction(action) => {
                             return self.handle_action(action);
                         }
@@ -1271,7 +1541,7 @@ impl Editor {
             {
                 // Scroll the file explorer
                 if let Some(explorer) = &mut self.file_explorer {
-                    let visible = explorer.tree().get_visible_nodes();
+                    let visible = explorer.visible_nodes();
                     if visible.is_empty() {
                         return Ok(());
                     }
@@ -2217,6 +2487,14 @@ impl Editor {
                 return Ok(());
             }
 
+            // Ctrl+Click on a link opens it
+            if modifiers.contains(KeyModifiers::CONTROL) {
+                if let Some(url) = super::links::link_at(state, target_position) {
+                    self.open_link(&url);
+                    return Ok(());
+                }
+            }
+
             // Move the primary cursor to this position
             // If shift is held, extend selection; otherwise clear it
             let (primary_cursor_id, old_position, old_anchor) = self
@@ -2805,7 +3083,7 @@ impl Editor {
             self.config.active_keybinding_map = map_name.to_string().into();
 
             // Reload the keybinding resolver with the new map
-            self.keybindings = crate::input::keybindings::KeybindingResolver::new(&self.config);
+            self.keybindings.reset_from_config(&self.config);
 
             // Persist to config file
             self.save_keybinding_map_to_config();
@@ -3234,9 +3512,14 @@ impl Editor {
 
     /// Handle character insertion in normal editor mode.
     fn handle_insert_char_editor(&mut self, c: char) -> AnyhowResult<()> {
+        let buffer_id = self.active_buffer();
+        if self.diff_session_edit(buffer_id, super::diff_mode::DiffEdit::Insert(c.to_string())) {
+            return Ok(());
+        }
+
         // Check if editing is disabled (show_cursors = false)
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return Ok(());
         }
 
@@ -3260,8 +3543,14 @@ impl Editor {
             }
         }
 
-        // Auto-trigger signature help on '(' and ','
-        if c == '(' || c == ',' {
+        // Auto-trigger signature help on the server's trigger characters, and
+        // keep it up to date while typing arguments
+        let language = self.active_state().language.clone();
+        let is_signature_trigger = self
+            .lsp
+            .as_ref()
+            .is_some_and(|lsp| lsp.is_signature_help_trigger_char(c, &language));
+        if is_signature_trigger || self.signature_help_dismissed_by_key {
             let _ = self.request_signature_help();
         }
 
@@ -3305,11 +3594,15 @@ impl Editor {
         );
 
         if is_editing_action && self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return Ok(());
         }
 
         if let Some(events) = self.action_to_events(action) {
+            if self.active_state().read_only && events.iter().any(|e| e.modifies_buffer()) {
+                self.report_editing_disabled();
+                return Ok(());
+            }
             if events.len() > 1 {
                 // Check if this batch contains buffer modifications
                 let has_buffer_mods = events
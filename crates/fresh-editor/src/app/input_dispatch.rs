@@ -246,6 +246,12 @@ impl Editor {
             DeferredAction::PromptHistoryNext => {
                 self.prompt_history_next();
             }
+            DeferredAction::PromptHistorySearchPrev => {
+                self.prompt_history_search_prev();
+            }
+            DeferredAction::PromptHistorySearchNext => {
+                self.prompt_history_search_next();
+            }
             DeferredAction::PreviewThemeFromPrompt => {
                 if let Some(prompt) = &self.prompt {
                     if matches!(
@@ -469,42 +475,45 @@ impl Editor {
 
     /// Navigate to previous history entry in prompt.
     fn prompt_history_prev(&mut self) {
-        // Get the prompt type and current input
-        let prompt_info = self
-            .prompt
-            .as_ref()
-            .map(|p| (p.prompt_type.clone(), p.input.clone()));
-
-        if let Some((prompt_type, current_input)) = prompt_info {
-            // Get the history key for this prompt type
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt_type) {
-                if let Some(history) = self.prompt_histories.get_mut(&key) {
-                    if let Some(entry) = history.navigate_prev(&current_input) {
-                        if let Some(ref mut prompt) = self.prompt {
-                            prompt.set_input(entry);
-                        }
-                    }
-                }
-            }
-        }
+        self.prompt_history_step(|history, input| history.navigate_prev(input));
     }
 
     /// Navigate to next history entry in prompt.
     fn prompt_history_next(&mut self) {
-        let prompt_type = self.prompt.as_ref().map(|p| p.prompt_type.clone());
-
-        if let Some(prompt_type) = prompt_type {
-            // Get the history key for this prompt type
-            if let Some(key) = Self::prompt_type_to_history_key(&prompt_type) {
-                if let Some(history) = self.prompt_histories.get_mut(&key) {
-                    if let Some(entry) = history.navigate_next() {
-                        if let Some(ref mut prompt) = self.prompt {
-                            prompt.set_input(entry);
-                        }
-                    }
-                }
+        self.prompt_history_step(|history, _| history.navigate_next());
+    }
+
+    /// Search backwards through prompt history for entries containing the typed text.
+    fn prompt_history_search_prev(&mut self) {
+        self.prompt_history_step(|history, input| history.search_prev(input));
+    }
+
+    /// Search forwards through prompt history for entries containing the typed text.
+    fn prompt_history_search_next(&mut self) {
+        self.prompt_history_step(|history, _| history.search_next());
+    }
+
+    /// Move through the current prompt's history and show the resulting entry.
+    fn prompt_history_step(
+        &mut self,
+        step: impl FnOnce(&mut crate::input::input_history::InputHistory, &str) -> Option<String>,
+    ) {
+        let Some((key, current_input)) = self.prompt.as_ref().and_then(|p| {
+            Self::prompt_type_to_history_key(&p.prompt_type).map(|key| (key, p.input.clone()))
+        }) else {
+            return;
+        };
+
+        // Take the history out while refreshing suggestions, since a refresh
+        // resets navigation as if the user had typed
+        let mut history = std::mem::take(self.get_or_create_prompt_history(&key));
+        if let Some(entry) = step(&mut history, &current_input) {
+            if let Some(ref mut prompt) = self.prompt {
+                prompt.set_input(entry);
             }
+            self.update_prompt_suggestions();
         }
+        self.prompt_histories.insert(key, history);
     }
 }
 
@@ -0,0 +1,414 @@
+//! Keybinding conflicts: keys bound to different actions by the user's
+//! config, plugins and buffer modes
+//!
+//! Conflicts are checked whenever the bindings change (config reload, a
+//! plugin binding a key, a mode being defined) and new ones are reported in
+//! the status bar and the log. `Show Keybinding Conflicts` lists them in the
+//! `*Keybinding Conflicts*` buffer with the binding that wins first; `r`
+//! rebinds the key under the cursor in the keybinding editor and `d`
+//! disables the winning binding so the next one takes over.
+
+use super::keybinding_editor::{key_code_to_config_name, modifiers_to_config_names};
+use super::Editor;
+use crate::config::{KeyPress, Keybinding};
+use crate::input::buffer_mode::KEYBINDING_CONFLICTS_MODE;
+use crate::input::key_reference::format_keys;
+use crate::input::keybindings::{
+    Action, BindingOrigin, KeyContext, KeybindingConflict, KeybindingResolver,
+};
+use crate::primitives::text_property::TextPropertyEntry;
+use rust_i18n::t;
+
+/// Name of the buffer listing the conflicts
+pub const KEYBINDING_CONFLICTS_BUFFER_NAME: &str = "*Keybinding Conflicts*";
+
+/// Text property holding the index of the conflict a panel line belongs to
+const CONFLICT_PROPERTY: &str = "conflict";
+
+/// Conflicts found by the last check
+#[derive(Debug, Default)]
+pub(super) struct ConflictCheck {
+    /// Generations of the keybindings and the buffer modes last checked
+    checked: Option<(u64, u64)>,
+    /// In the order of the panel
+    conflicts: Vec<KeybindingConflict>,
+}
+
+fn origin_label(origin: &BindingOrigin) -> String {
+    match origin {
+        BindingOrigin::Keymap => "keymap".to_string(),
+        BindingOrigin::User => "your config".to_string(),
+        BindingOrigin::Plugin(plugin) => format!("plugin {}", plugin),
+        BindingOrigin::Mode(mode) => format!("mode {}", mode),
+    }
+}
+
+/// Where the conflict happens, e.g. `normal` or `markdown-preview buffers`
+fn conflict_scope(conflict: &KeybindingConflict) -> String {
+    match &conflict.winner().origin {
+        BindingOrigin::Mode(mode) => format!("{} buffers", mode),
+        _ => conflict.context.to_when_clause().to_string(),
+    }
+}
+
+/// Name to bind `action` to in the config, if it can be bound there
+fn config_action_name(action: &Action) -> Option<String> {
+    match action {
+        Action::PluginAction(_) => None,
+        Action::UserCommand(name) => Some(name.clone()),
+        _ => action
+            .name()
+            .filter(|name| !Action::takes_char(name))
+            .map(str::to_string),
+    }
+}
+
+/// Config binding of `keys` to `action` in `context`
+fn config_binding(
+    keys: &[(crossterm::event::KeyCode, crossterm::event::KeyModifiers)],
+    context: KeyContext,
+    action: String,
+) -> Keybinding {
+    let when = (context != KeyContext::Normal).then(|| context.to_when_clause().to_string());
+    match keys {
+        [(code, modifiers)] => Keybinding {
+            key: key_code_to_config_name(*code),
+            modifiers: modifiers_to_config_names(*modifiers),
+            keys: Vec::new(),
+            action,
+            args: Default::default(),
+            when,
+        },
+        _ => Keybinding {
+            key: String::new(),
+            modifiers: Vec::new(),
+            keys: keys
+                .iter()
+                .map(|(code, modifiers)| KeyPress {
+                    key: key_code_to_config_name(*code),
+                    modifiers: modifiers_to_config_names(*modifiers),
+                })
+                .collect(),
+            action,
+            args: Default::default(),
+            when,
+        },
+    }
+}
+
+/// Whether the config binding `binding` binds `keys` in `context`
+fn binds_keys(
+    binding: &Keybinding,
+    keys: &[(crossterm::event::KeyCode, crossterm::event::KeyModifiers)],
+    context: KeyContext,
+) -> bool {
+    let binding_context = binding
+        .when
+        .as_deref()
+        .and_then(KeyContext::from_when_clause)
+        .unwrap_or(KeyContext::Normal);
+    let parse = |key: &str, modifiers: &[String]| {
+        KeybindingResolver::parse_key_public(key)
+            .map(|code| (code, KeybindingResolver::parse_modifiers_public(modifiers)))
+    };
+    let binding_keys: Option<Vec<_>> = if binding.keys.is_empty() {
+        parse(&binding.key, &binding.modifiers).map(|key| vec![key])
+    } else {
+        binding
+            .keys
+            .iter()
+            .map(|press| parse(&press.key, &press.modifiers))
+            .collect()
+    };
+    binding_context == context && binding_keys.as_deref() == Some(keys)
+}
+
+/// Plain text layout of the conflicts panel, one entry per conflict
+fn panel_entries(conflicts: &[KeybindingConflict]) -> Vec<TextPropertyEntry> {
+    let mut entries = vec![TextPropertyEntry::text(
+        "Keybinding Conflicts\n====================\n\n\
+         r: rebind   d: disable the winning binding   g: refresh   q: close\n\n",
+    )];
+    if conflicts.is_empty() {
+        entries.push(TextPropertyEntry::text("No keybinding conflicts.\n"));
+        return entries;
+    }
+    let action_width = conflicts
+        .iter()
+        .flat_map(|conflict| &conflict.bindings)
+        .map(|binding| {
+            KeybindingResolver::format_action(&binding.action)
+                .chars()
+                .count()
+        })
+        .max()
+        .unwrap_or(0)
+        .min(40);
+    for (index, conflict) in conflicts.iter().enumerate() {
+        let mut text = format!(
+            "{}  ({})\n",
+            format_keys(&conflict.keys),
+            conflict_scope(conflict)
+        );
+        for (position, binding) in conflict.bindings.iter().enumerate() {
+            text.push_str(&format!(
+                "  {:8}  {:width$}  {}\n",
+                if position == 0 { "wins" } else { "shadowed" },
+                KeybindingResolver::format_action(&binding.action),
+                origin_label(&binding.origin),
+                width = action_width
+            ));
+        }
+        text.push('\n');
+        entries.push(
+            TextPropertyEntry::text(text)
+                .with_property(CONFLICT_PROPERTY, serde_json::Value::from(index)),
+        );
+    }
+    entries
+}
+
+impl Editor {
+    /// Check the bindings for conflicts if they changed since the last
+    /// check, reporting new conflicts. Returns true if anything changed on
+    /// screen.
+    pub fn check_keybinding_conflicts(&mut self) -> bool {
+        let generation = (
+            self.keybindings.generation(),
+            self.mode_registry.generation(),
+        );
+        if self.keybinding_conflicts.checked == Some(generation) {
+            return false;
+        }
+        self.keybinding_conflicts.checked = Some(generation);
+
+        let conflicts = self.keybindings.conflicts(&self.mode_registry);
+        // Where the user's own binding wins they already chose; list those
+        // in the panel without reporting them again on every start
+        let new: Vec<&KeybindingConflict> = conflicts
+            .iter()
+            .filter(|conflict| {
+                conflict.winner().origin != BindingOrigin::User
+                    && !self.keybinding_conflicts.conflicts.contains(conflict)
+            })
+            .collect();
+        for conflict in &new {
+            tracing::warn!(
+                "Keybinding conflict: {} ({}) runs {} from {}, shadowing {}",
+                format_keys(&conflict.keys),
+                conflict_scope(conflict),
+                KeybindingResolver::format_action(&conflict.winner().action),
+                origin_label(&conflict.winner().origin),
+                conflict.bindings[1..]
+                    .iter()
+                    .map(|binding| format!(
+                        "{} from {}",
+                        KeybindingResolver::format_action(&binding.action),
+                        origin_label(&binding.origin)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        let report = !new.is_empty();
+        self.keybinding_conflicts.conflicts = conflicts;
+        if report {
+            self.set_status_message(
+                t!(
+                    "keybinding_conflicts.found",
+                    count = self.keybinding_conflicts.conflicts.len()
+                )
+                .to_string(),
+            );
+        }
+
+        let panel_open = self
+            .find_buffer_by_name(KEYBINDING_CONFLICTS_BUFFER_NAME)
+            .is_some();
+        if panel_open {
+            self.refresh_keybinding_conflicts_panel();
+        }
+        report || panel_open
+    }
+
+    /// Conflicts found by the last check
+    pub fn keybinding_conflicts(&self) -> &[KeybindingConflict] {
+        &self.keybinding_conflicts.conflicts
+    }
+
+    /// Open (or refresh) the `*Keybinding Conflicts*` buffer
+    pub fn show_keybinding_conflicts(&mut self) {
+        self.keybinding_conflicts.checked = None;
+        self.check_keybinding_conflicts();
+        let buffer_id = self
+            .find_buffer_by_name(KEYBINDING_CONFLICTS_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    KEYBINDING_CONFLICTS_BUFFER_NAME.to_string(),
+                    KEYBINDING_CONFLICTS_MODE.to_string(),
+                    true,
+                )
+            });
+        self.refresh_keybinding_conflicts_panel();
+        self.set_active_buffer(buffer_id);
+
+        let count = self.keybinding_conflicts.conflicts.len();
+        let status = if count > 0 {
+            t!("keybinding_conflicts.found", count = count)
+        } else {
+            t!("keybinding_conflicts.none")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    fn refresh_keybinding_conflicts_panel(&mut self) {
+        let Some(buffer_id) = self.find_buffer_by_name(KEYBINDING_CONFLICTS_BUFFER_NAME) else {
+            return;
+        };
+        let entries = panel_entries(&self.keybinding_conflicts.conflicts);
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::error!("Failed to fill keybinding conflicts buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+    }
+
+    /// Conflict of the panel line under the cursor, telling the user if
+    /// there is none
+    fn keybinding_conflict_at_cursor(&mut self) -> Option<KeybindingConflict> {
+        let conflict = if self.find_buffer_by_name(KEYBINDING_CONFLICTS_BUFFER_NAME)
+            == Some(self.active_buffer())
+        {
+            self.get_text_properties_at_cursor().and_then(|props| {
+                props
+                    .into_iter()
+                    .find_map(|prop| prop.get(CONFLICT_PROPERTY)?.as_u64())
+            })
+        } else {
+            None
+        }
+        .and_then(|index| self.keybinding_conflicts.conflicts.get(index as usize))
+        .cloned();
+        if conflict.is_none() {
+            self.set_status_message(t!("keybinding_conflicts.no_conflict").to_string());
+        }
+        conflict
+    }
+
+    /// Open the keybinding editor on the keys of the conflict under the
+    /// cursor
+    pub fn keybinding_conflicts_rebind(&mut self) {
+        let Some(conflict) = self.keybinding_conflict_at_cursor() else {
+            return;
+        };
+        self.open_keybinding_editor();
+        if let Some(editor) = self.keybinding_editor.as_mut() {
+            editor.focus_keys(&conflict.keys, conflict.context.to_when_clause());
+        }
+    }
+
+    /// Disable the winning binding of the conflict under the cursor so the
+    /// next one takes over
+    ///
+    /// The user's own binding is removed from the config. A plugin's binding
+    /// is overridden in the user's config by the next binding in line, or,
+    /// when that one can't be written in the config, unbound until the
+    /// plugin binds it again. Mode bindings and global keymap bindings can
+    /// only be worked around by rebinding.
+    pub fn keybinding_conflicts_disable(&mut self) {
+        let Some(conflict) = self.keybinding_conflict_at_cursor() else {
+            return;
+        };
+        let keys = format_keys(&conflict.keys);
+        let winner = conflict.winner();
+        let status = match &winner.origin {
+            BindingOrigin::User => {
+                self.config
+                    .keybindings
+                    .retain(|binding| !binds_keys(binding, &conflict.keys, winner.context));
+                match self.save_keybindings_config() {
+                    Ok(()) => t!("keybinding_conflicts.user_removed", keys = &keys).to_string(),
+                    Err(e) => e,
+                }
+            }
+            BindingOrigin::Plugin(plugin) => {
+                let next = &conflict.bindings[1].action;
+                if let Some(action) = config_action_name(next) {
+                    self.config.keybindings.push(config_binding(
+                        &conflict.keys,
+                        winner.context,
+                        action,
+                    ));
+                    match self.save_keybindings_config() {
+                        Ok(()) => t!(
+                            "keybinding_conflicts.plugin_overridden",
+                            keys = &keys,
+                            plugin = plugin,
+                            action = KeybindingResolver::format_action(next)
+                        )
+                        .to_string(),
+                        Err(e) => e,
+                    }
+                } else {
+                    self.keybindings.remove_plugin_binding(
+                        plugin,
+                        &conflict.keys,
+                        winner.context,
+                        None,
+                    );
+                    t!(
+                        "keybinding_conflicts.plugin_unbound",
+                        keys = &keys,
+                        plugin = plugin
+                    )
+                    .to_string()
+                }
+            }
+            BindingOrigin::Mode(mode) => {
+                t!("keybinding_conflicts.mode_binding", mode = mode).to_string()
+            }
+            BindingOrigin::Keymap => {
+                t!("keybinding_conflicts.keymap_binding", keys = &keys).to_string()
+            }
+        };
+        self.check_keybinding_conflicts();
+        self.set_status_message(status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_config_binding_round_trip() {
+        let ctrl_k = (KeyCode::Char('k'), KeyModifiers::CONTROL);
+        let d = (KeyCode::Char('d'), KeyModifiers::NONE);
+        let single = config_binding(&[ctrl_k], KeyContext::Normal, "save".to_string());
+        assert_eq!(single.key, "k");
+        assert_eq!(single.modifiers, vec!["ctrl".to_string()]);
+        assert_eq!(single.when, None);
+        assert!(binds_keys(&single, &[ctrl_k], KeyContext::Normal));
+        assert!(!binds_keys(&single, &[ctrl_k], KeyContext::Prompt));
+
+        let chord = config_binding(&[ctrl_k, d], KeyContext::Prompt, "save".to_string());
+        assert_eq!(chord.keys.len(), 2);
+        assert_eq!(chord.when.as_deref(), Some("prompt"));
+        assert!(binds_keys(&chord, &[ctrl_k, d], KeyContext::Prompt));
+        assert!(!binds_keys(&chord, &[ctrl_k], KeyContext::Prompt));
+    }
+
+    #[test]
+    fn test_config_action_name() {
+        assert_eq!(config_action_name(&Action::Save).as_deref(), Some("save"));
+        assert_eq!(
+            config_action_name(&Action::PluginAction("live_grep".to_string())),
+            None
+        );
+        assert_eq!(config_action_name(&Action::InsertChar('x')), None);
+    }
+}
@@ -428,6 +428,32 @@ impl KeybindingEditor {
         self.apply_filters();
     }
 
+    /// Show only the bindings of `keys` and open the edit dialog on the one
+    /// of `context`, if there is one
+    pub fn focus_keys(&mut self, keys: &[(KeyCode, KeyModifiers)], context: &str) {
+        match keys {
+            [(code, modifiers)] => {
+                self.start_record_key_search();
+                self.record_search_key(&KeyEvent::new(*code, *modifiers));
+            }
+            _ => {
+                self.start_search();
+                self.search_query = crate::input::key_reference::format_keys(keys);
+                self.apply_filters();
+            }
+        }
+        self.search_focused = false;
+        if let Some(index) = self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.bindings[i].context == context)
+        {
+            self.selected = index;
+            self.ensure_visible();
+            self.open_edit_dialog();
+        }
+    }
+
     /// Cycle context filter
     pub fn cycle_context_filter(&mut self) {
         let contexts = vec![
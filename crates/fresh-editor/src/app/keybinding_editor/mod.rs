@@ -9,4 +9,5 @@ mod helpers;
 mod types;
 
 pub use editor::KeybindingEditor;
+pub use helpers::{key_code_to_config_name, modifiers_to_config_names};
 pub use types::*;
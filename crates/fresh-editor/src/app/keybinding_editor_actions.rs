@@ -74,17 +74,24 @@ impl Editor {
             self.config.keybindings.push(binding);
         }
 
-        // Rebuild the keybinding resolver
-        self.keybindings = crate::input::keybindings::KeybindingResolver::new(&self.config);
-
-        // Save to config file via the pending changes mechanism
-        let config_value = match serde_json::to_value(&self.config.keybindings) {
-            Ok(v) => v,
+        match self.save_keybindings_config() {
+            Ok(()) => {
+                self.set_status_message("Keybinding changes saved".to_string());
+            }
             Err(e) => {
-                self.set_status_message(format!("Failed to serialize keybindings: {}", e));
-                return;
+                self.set_status_message(e);
             }
-        };
+        }
+    }
+
+    /// Rebuild the keybinding resolver from `config.keybindings` and save
+    /// them to the user's config file
+    pub(super) fn save_keybindings_config(&mut self) -> Result<(), String> {
+        self.keybindings.reset_from_config(&self.config);
+
+        // Save to config file via the pending changes mechanism
+        let config_value = serde_json::to_value(&self.config.keybindings)
+            .map_err(|e| format!("Failed to serialize keybindings: {}", e))?;
 
         let mut changes = std::collections::HashMap::new();
         changes.insert("/keybindings".to_string(), config_value);
@@ -94,18 +101,13 @@ impl Editor {
             self.working_dir.clone(),
         );
 
-        match resolver.save_changes_to_layer(
-            &changes,
-            &std::collections::HashSet::new(),
-            crate::config_io::ConfigLayer::User,
-        ) {
-            Ok(()) => {
-                self.set_status_message("Keybinding changes saved".to_string());
-            }
-            Err(e) => {
-                self.set_status_message(format!("Failed to save keybindings: {}", e));
-            }
-        }
+        resolver
+            .save_changes_to_layer(
+                &changes,
+                &std::collections::HashSet::new(),
+                crate::config_io::ConfigLayer::User,
+            )
+            .map_err(|e| format!("Failed to save keybindings: {}", e))
     }
 
     /// Check if keybinding editor is active
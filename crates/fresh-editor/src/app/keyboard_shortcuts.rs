@@ -0,0 +1,88 @@
+//! Keyboard shortcuts page generated from the live keybinding tables.
+//!
+//! The page is rebuilt every time it is shown, so user remaps, plugin modes
+//! and keymap switches are reflected immediately. `/` narrows it down.
+
+use rust_i18n::t;
+
+use crate::input::buffer_mode::KEYBOARD_SHORTCUTS_MODE;
+use crate::input::key_reference::KeyReference;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::prompt::PromptType;
+
+use super::help;
+use super::Editor;
+
+impl Editor {
+    /// The key reference for the current bindings
+    pub fn key_reference(&self) -> KeyReference {
+        KeyReference::build(&self.keybindings, &self.mode_registry)
+    }
+
+    /// Open the keyboard shortcuts page in a read-only buffer
+    ///
+    /// If the page is already open, it is regenerated and switched to.
+    pub fn open_keyboard_shortcuts(&mut self) {
+        self.open_keyboard_shortcuts_filtered("");
+    }
+
+    /// Open the keyboard shortcuts page showing only bindings matching `query`
+    pub fn open_keyboard_shortcuts_filtered(&mut self, query: &str) {
+        let query = query.trim();
+        let reference = self.key_reference().filter(query);
+
+        let title = t!("keys.title").to_string();
+        let mut content = format!("{}\n{}\n\n", title, "=".repeat(title.chars().count()));
+        if query.is_empty() {
+            content.push_str(&t!("keys.hint"));
+        } else if reference.is_empty() {
+            content.push_str(&t!("keys.no_match", query = query));
+        } else {
+            content.push_str(&t!("keys.filtered", query = query));
+        }
+        content.push_str("\n\n");
+        content.push_str(&reference.to_text());
+
+        let buffer_id = self.keyboard_shortcuts_buffer().unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                help::KEYBOARD_SHORTCUTS_BUFFER_NAME.to_string(),
+                KEYBOARD_SHORTCUTS_MODE.to_string(),
+                true,
+            )
+        });
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill keyboard shortcuts buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            // Disable line numbers for cleaner display
+            state.margins.configure_for_line_numbers(false);
+        }
+        for view_state in self.split_view_states.values_mut() {
+            if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
+                buf_state.cursors.primary_mut().position = 0;
+                buf_state.cursors.primary_mut().anchor = None;
+            }
+        }
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Prompt for a filter on the keyboard shortcuts page
+    pub fn start_keyboard_shortcuts_filter(&mut self) {
+        self.start_prompt(
+            t!("keys.filter_prompt").to_string(),
+            PromptType::FilterKeyboardShortcuts,
+        );
+    }
+
+    fn keyboard_shortcuts_buffer(&self) -> Option<BufferId> {
+        self.buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == help::KEYBOARD_SHORTCUTS_BUFFER_NAME)
+            .map(|(id, _)| *id)
+    }
+}
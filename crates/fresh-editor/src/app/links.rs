@@ -0,0 +1,79 @@
+//! Opening links in the system browser
+//!
+//! A link is an overlay carrying a URL, such as the URLs found in the text
+//! (see `view::link_overlay`) and the links of markdown compose mode, or a URL
+//! in the text when link overlays are turned off.
+
+use super::Editor;
+use crate::state::EditorState;
+use crate::view::link_overlay::{is_openable_url, url_ranges};
+use rust_i18n::t;
+
+/// How far around a position to look for a URL in the text
+const URL_SEARCH_BYTES: usize = 2048;
+
+/// URL of the link at a buffer position
+pub(super) fn link_at(state: &EditorState, position: usize) -> Option<String> {
+    let overlay_url = state
+        .overlays
+        .at_position(position, &state.marker_list)
+        .into_iter()
+        .find_map(|overlay| overlay.url.clone());
+    if overlay_url.is_some() {
+        return overlay_url;
+    }
+
+    let start = position.saturating_sub(URL_SEARCH_BYTES);
+    let end = position
+        .saturating_add(URL_SEARCH_BYTES)
+        .min(state.buffer.len());
+    let text = state.buffer.slice_bytes(start..end);
+    url_ranges(&text, start)
+        .into_iter()
+        .find(|range| range.contains(&position))
+        .map(|range| String::from_utf8_lossy(&text[range.start - start..range.end - start]).into())
+}
+
+impl Editor {
+    /// Open the link under the primary cursor
+    pub(super) fn open_link_under_cursor(&mut self) {
+        let position = self.active_cursors().primary().position;
+        match link_at(self.active_state(), position) {
+            Some(url) => self.open_link(&url),
+            None => self.set_status_message(t!("link.none").to_string()),
+        }
+    }
+
+    /// Open a link in the system browser. Only http, https and mailto links
+    /// are opened, whether they come from the text or a plugin's overlay.
+    pub(super) fn open_link(&mut self, url: &str) {
+        if !is_openable_url(url) {
+            self.set_status_message(t!("link.not_opened", url = url).to_string());
+            return;
+        }
+        if let Some(opened) = &mut self.opened_links {
+            opened.push(url.to_string());
+            return;
+        }
+        match open::that(url) {
+            Ok(()) => self.set_status_message(t!("link.opening", url = url).to_string()),
+            Err(e) => {
+                tracing::warn!("Failed to open {}: {}", url, e);
+                self.set_status_message(t!("link.open_failed", error = e.to_string()).to_string());
+            }
+        }
+    }
+
+    /// Record the links that would be opened instead of starting a browser
+    /// (for testing)
+    #[doc(hidden)]
+    pub fn record_opened_links_for_test(&mut self) {
+        self.opened_links.get_or_insert_with(Vec::new);
+    }
+
+    /// Links opened since `record_opened_links_for_test`
+    #[doc(hidden)]
+    pub fn opened_links(&self) -> &[String] {
+        self.opened_links.as_deref().unwrap_or_default()
+    }
+}
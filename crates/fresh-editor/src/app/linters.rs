@@ -0,0 +1,201 @@
+//! Running the linters configured for a buffer's language
+//!
+//! Linters (see `services::linter`) run in the background when a file is
+//! opened or saved; `run_on: "idle"` linters also run after a pause in
+//! typing. Their findings are stored per linter next to the diagnostics of
+//! language servers and shown the same way.
+
+use super::Editor;
+use crate::config::{LintTrigger, LinterConfig};
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::linter::{run_linter, LintError};
+use lsp_types::Diagnostic;
+use rust_i18n::t;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Pause in typing after which idle linters run
+const IDLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Linter runs of the open buffers
+#[derive(Debug, Default)]
+pub(super) struct LintState {
+    /// Latest run of each linter on each buffer; results of older runs are dropped
+    runs: HashMap<(BufferId, String), u64>,
+    next_run: u64,
+    /// When buffers with idle linters are next due to be linted
+    idle_due: HashMap<BufferId, Instant>,
+    /// Commands already reported as missing
+    missing: HashSet<String>,
+}
+
+impl LintState {
+    /// Drop what is known about a closed buffer
+    pub(super) fn forget(&mut self, buffer_id: BufferId) {
+        self.runs.retain(|(id, _), _| *id != buffer_id);
+        self.idle_due.remove(&buffer_id);
+    }
+}
+
+/// Key of a linter's diagnostics, next to the language servers' keys
+fn linter_key(linter: &LinterConfig) -> String {
+    format!("linter:{}", linter.command)
+}
+
+/// Whether a linter can run on unsaved changes
+fn runs_when_idle(linter: &LinterConfig) -> bool {
+    linter.run_on == LintTrigger::Idle && linter.stdin
+}
+
+impl Editor {
+    /// Enabled linters of a buffer's language
+    fn buffer_linters(&self, buffer_id: BufferId) -> impl Iterator<Item = &LinterConfig> {
+        self.buffers
+            .get(&buffer_id)
+            .and_then(|state| self.config.languages.get(&state.language))
+            .into_iter()
+            .flat_map(|language| language.linters.iter())
+            .filter(|linter| linter.enabled)
+    }
+
+    /// Lint a buffer with the linters of its language
+    ///
+    /// With `idle`, only the linters that run while typing are started.
+    pub(crate) fn run_linters(&mut self, buffer_id: BufferId, idle: bool) {
+        let linters: Vec<LinterConfig> = self
+            .buffer_linters(buffer_id)
+            .filter(|linter| !idle || runs_when_idle(linter))
+            .cloned()
+            .collect();
+        if linters.is_empty() {
+            return;
+        }
+        let Some(path) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(|path| path.to_path_buf())
+        else {
+            return;
+        };
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            return;
+        };
+        let text = if linters.iter().any(|linter| linter.stdin) {
+            self.buffers
+                .get(&buffer_id)
+                .and_then(|state| state.buffer.to_string())
+        } else {
+            None
+        };
+
+        for linter in linters {
+            let stdin = if linter.stdin {
+                // Buffers that aren't fully loaded can't be passed on stdin
+                let Some(text) = text.clone() else {
+                    continue;
+                };
+                Some(text)
+            } else {
+                None
+            };
+            let key = linter_key(&linter);
+            let run = self.linting.next_run;
+            self.linting.next_run += 1;
+            self.linting.runs.insert((buffer_id, key.clone()), run);
+
+            let path = path.clone();
+            let working_dir = self.working_dir.clone();
+            let sender = sender.clone();
+            self.jobs.spawn_blocking(Some(buffer_id), move |token| {
+                let result = run_linter(&linter, &path, stdin.as_deref(), &working_dir, token);
+                if !token.is_cancelled() {
+                    let _ = sender.send(AsyncMessage::LinterFinished {
+                        buffer_id,
+                        run,
+                        linter: key,
+                        result,
+                    });
+                }
+            });
+        }
+    }
+
+    /// Schedule idle linting of a buffer that was just edited
+    pub(crate) fn schedule_idle_lint(&mut self, buffer_id: BufferId) {
+        if self.buffer_linters(buffer_id).any(runs_when_idle) {
+            let due = self.time_source.now() + IDLE_DELAY;
+            self.linting.idle_due.insert(buffer_id, due);
+        }
+    }
+
+    /// Start the idle linters of buffers whose typing pause has passed
+    pub fn check_lint_timer(&mut self) {
+        let now = self.time_source.now();
+        let due: Vec<BufferId> = self
+            .linting
+            .idle_due
+            .iter()
+            .filter(|(_, due)| **due <= now)
+            .map(|(buffer_id, _)| *buffer_id)
+            .collect();
+        for buffer_id in due {
+            self.linting.idle_due.remove(&buffer_id);
+            self.run_linters(buffer_id, true);
+        }
+    }
+
+    /// How long until the next idle lint is due
+    pub(super) fn lint_wait(&self) -> Option<Duration> {
+        let now = self.time_source.now();
+        self.linting
+            .idle_due
+            .values()
+            .min()
+            .map(|due| due.saturating_duration_since(now))
+    }
+
+    /// Show the findings of a finished linter run
+    pub(super) fn handle_linter_finished(
+        &mut self,
+        buffer_id: BufferId,
+        run: u64,
+        linter: String,
+        result: Result<Vec<Diagnostic>, LintError>,
+    ) {
+        if self.linting.runs.get(&(buffer_id, linter.clone())) != Some(&run) {
+            return;
+        }
+        let Some(uri) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|metadata| metadata.file_uri())
+            .map(|uri| uri.as_str().to_string())
+        else {
+            return;
+        };
+
+        match result {
+            Ok(diagnostics) => {
+                tracing::debug!(
+                    "{} reported {} findings for {}",
+                    linter,
+                    diagnostics.len(),
+                    uri
+                );
+                self.store_and_apply_diagnostics(linter, uri, diagnostics);
+            }
+            Err(LintError::NotFound(command)) => {
+                // Say it once, not on every save
+                if self.linting.missing.insert(command.clone()) {
+                    self.set_status_message(t!("linter.not_found", command = command).to_string());
+                }
+            }
+            Err(LintError::Failed(error)) => {
+                tracing::warn!("Linter failed: {}", error);
+                self.set_status_message(t!("linter.failed", error = error).to_string());
+            }
+        }
+    }
+}
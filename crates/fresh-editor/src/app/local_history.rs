@@ -0,0 +1,227 @@
+//! Local history: snapshots of files taken on save and periodically
+//!
+//! Snapshots are stored by [`crate::services::local_history`]. The Local
+//! History prompt lists the snapshots of the active file; choosing one
+//! compares it side by side with the buffer, where changes can be restored
+//! one at a time with Revert Hunk or all at once with Restore Snapshot.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use super::unsaved_changes::UnsavedChangesView;
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::services::local_history::{Retention, SnapshotKind};
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+impl Editor {
+    /// Snapshots which the configuration keeps
+    fn local_history_retention(&self) -> Retention {
+        let days = self.config.editor.local_history_max_age_days;
+        Retention {
+            max_snapshots: self.config.editor.local_history_max_snapshots,
+            max_age: (days > 0).then(|| Duration::from_secs(u64::from(days) * 86400)),
+        }
+    }
+
+    /// Store the content of `buffer_id` in its file's local history
+    pub(super) fn record_local_history(&mut self, buffer_id: BufferId, kind: SnapshotKind) {
+        if !self.config.editor.local_history_enabled {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let Some(path) = state.buffer.file_path().map(Path::to_path_buf) else {
+            return;
+        };
+        // Large files that aren't fully loaded are left out
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+        let version = state.buffer.version();
+        let retention = self.local_history_retention();
+        match self.local_history.record(
+            &path,
+            content.as_bytes(),
+            kind,
+            SystemTime::now(),
+            retention,
+        ) {
+            Ok(_) => {
+                self.local_history_versions.insert(buffer_id, version);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to record local history of {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Snapshot the buffers edited since their last snapshot, once per
+    /// configured interval. Returns the number of buffers snapshotted.
+    pub fn local_history_snapshot_buffers(&mut self) -> usize {
+        if !self.config.editor.local_history_enabled {
+            return 0;
+        }
+        let interval =
+            Duration::from_secs(u64::from(self.config.editor.local_history_interval_secs));
+        if self
+            .time_source
+            .elapsed_since(self.last_local_history_snapshot)
+            < interval
+        {
+            return 0;
+        }
+        self.last_local_history_snapshot = self.time_source.now();
+
+        let edited: Vec<BufferId> = self
+            .buffers
+            .iter()
+            .filter(|(id, state)| {
+                state.buffer.is_modified()
+                    && state.buffer.file_path().is_some()
+                    && self.local_history_versions.get(id) != Some(&state.buffer.version())
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for &id in &edited {
+            self.record_local_history(id, SnapshotKind::Auto);
+        }
+        edited.len()
+    }
+
+    /// List the local history of the active file to compare with a snapshot
+    pub fn start_local_history_prompt(&mut self) {
+        let active = self.active_buffer();
+        let buffer_id = self
+            .unsaved_changes_views
+            .get(&active)
+            .map_or(active, |view| view.source);
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            self.set_status_message(t!("local_history.not_a_file").to_string());
+            return;
+        };
+        let snapshots = match self.local_history.snapshots(&path) {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                self.set_status_message(
+                    t!("local_history.read_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        if snapshots.is_empty() {
+            self.set_status_message(t!("local_history.none").to_string());
+            return;
+        }
+
+        let suggestions = snapshots
+            .iter()
+            .map(|snapshot| {
+                let time: chrono::DateTime<chrono::Local> = snapshot.time.into();
+                let kind = match snapshot.kind {
+                    SnapshotKind::Save => t!("local_history.kind_save"),
+                    SnapshotKind::Auto => t!("local_history.kind_auto"),
+                };
+                Suggestion {
+                    text: time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    description: Some(kind.to_string()),
+                    value: Some(snapshot.path.to_string_lossy().into_owned()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        let mut prompt = Prompt::with_suggestions(
+            t!("local_history.prompt").to_string(),
+            PromptType::LocalHistory { buffer_id },
+            suggestions,
+        );
+        prompt.selected_suggestion = Some(0);
+        self.prompt = Some(prompt);
+    }
+
+    /// Compare the snapshot stored at `snapshot_path` with `buffer_id`
+    pub(super) fn open_local_history_snapshot(&mut self, buffer_id: BufferId, snapshot_path: &str) {
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            return;
+        };
+        let snapshot = self
+            .local_history
+            .snapshots(&path)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|s| s.path == Path::new(snapshot_path));
+        let Some(snapshot) = snapshot else {
+            self.set_status_message(t!("local_history.not_found").to_string());
+            return;
+        };
+        let content = match self.local_history.read(&snapshot) {
+            Ok(content) => content,
+            Err(e) => {
+                self.set_status_message(
+                    t!("local_history.read_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        let time: chrono::DateTime<chrono::Local> = snapshot.time.into();
+        let label = t!(
+            "local_history.snapshot_label",
+            time = time.format("%Y-%m-%d %H:%M").to_string()
+        )
+        .to_string();
+        let view = self.open_base_diff(buffer_id, &path, label, content);
+        self.unsaved_changes_views.insert(
+            view,
+            UnsavedChangesView::side_by_side(buffer_id, Some(snapshot)),
+        );
+    }
+
+    /// Replace the buffer compared in a local history view with the snapshot
+    pub fn restore_snapshot(&mut self) {
+        let view_id = self.active_buffer();
+        let Some((source, snapshot)) = self
+            .unsaved_changes_views
+            .get(&view_id)
+            .and_then(|view| Some((view.source, view.snapshot.clone()?)))
+        else {
+            self.set_status_message(t!("local_history.not_a_view").to_string());
+            return;
+        };
+        let content = match self.local_history.read(&snapshot) {
+            Ok(content) => String::from_utf8_lossy(&content).into_owned(),
+            Err(e) => {
+                self.set_status_message(
+                    t!("local_history.read_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let Some(len) = self.buffers.get(&source).map(|s| s.buffer.len()) else {
+            return;
+        };
+        if len > 0 {
+            self.handle_delete_range(source, 0..len);
+        }
+        if !content.is_empty() {
+            self.handle_insert_text(source, 0, content);
+        }
+        self.realign_diff_session(view_id);
+        self.set_status_message(t!("local_history.restored").to_string());
+    }
+}
@@ -0,0 +1,255 @@
+//! The `*Log*` buffer: the editor's log without leaving the TUI
+//!
+//! Log events are kept in memory by the log buffer layer of the tracing
+//! subscriber (see `services::log_buffer`). The `*Log*` buffer shows them,
+//! narrowed to a minimum severity and to one source (`lsp`, `plugins`, ...),
+//! and follows new events while it is open.
+
+use super::Editor;
+use crate::input::buffer_mode::LOG_MODE;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::log_buffer::{LogBuffer, LogEntry};
+use rust_i18n::t;
+use tracing::Level;
+
+/// Name of the read-only buffer showing the editor's log
+pub const LOG_BUFFER_NAME: &str = "*Log*";
+
+/// What the `*Log*` buffer shows
+#[derive(Debug)]
+pub(crate) struct LogViewState {
+    buffer: LogBuffer,
+    /// Least severe level shown
+    min_level: Level,
+    /// Only show events of this source
+    source_filter: Option<String>,
+    /// Number of the first event not shown yet
+    shown_seq: u64,
+}
+
+impl Default for LogViewState {
+    fn default() -> Self {
+        Self {
+            buffer: LogBuffer::new(),
+            min_level: Level::INFO,
+            source_filter: None,
+            shown_seq: 0,
+        }
+    }
+}
+
+impl LogViewState {
+    /// Events passing the level and source filters
+    fn visible(&self) -> Vec<LogEntry> {
+        let mut entries = self.buffer.entries(self.min_level);
+        if let Some(source) = &self.source_filter {
+            entries.retain(|entry| entry.source() == source);
+        }
+        entries
+    }
+
+    /// Next source filter: all sources, then each source that logged in turn
+    fn next_source_filter(&self) -> Option<String> {
+        let mut sources: Vec<String> = self
+            .buffer
+            .entries(Level::TRACE)
+            .iter()
+            .map(|entry| entry.source().to_string())
+            .collect();
+        sources.sort();
+        sources.dedup();
+        match &self.source_filter {
+            None => sources.into_iter().next(),
+            Some(current) => sources.into_iter().find(|s| s > current),
+        }
+    }
+}
+
+fn next_level(level: Level) -> Level {
+    match level {
+        Level::TRACE | Level::DEBUG => Level::INFO,
+        Level::INFO => Level::WARN,
+        Level::WARN => Level::ERROR,
+        Level::ERROR => Level::DEBUG,
+    }
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::TRACE => "trace",
+        Level::DEBUG => "debug",
+        Level::INFO => "info",
+        Level::WARN => "warn",
+        Level::ERROR => "error",
+    }
+}
+
+/// Log line(s) of an event; continuation lines are indented
+fn format_entry(entry: &LogEntry) -> String {
+    let mut text = format!(
+        "{} {:<5} {}: ",
+        entry.timestamp.format("%H:%M:%S%.3f"),
+        entry.level,
+        entry.target
+    );
+    let mut lines = entry.message.lines();
+    text.push_str(lines.next().unwrap_or_default());
+    text.push('\n');
+    for line in lines {
+        text.push_str("    ");
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
+
+impl Editor {
+    /// Show the events kept by `buffer` in the `*Log*` buffer
+    pub fn set_log_buffer(&mut self, buffer: LogBuffer) {
+        self.log_view.buffer = buffer;
+    }
+
+    /// The recent log events the `*Log*` buffer shows
+    pub fn log_buffer(&self) -> &LogBuffer {
+        &self.log_view.buffer
+    }
+
+    /// Open the `*Log*` buffer
+    pub fn show_log(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(LOG_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(LOG_BUFFER_NAME.to_string(), LOG_MODE.to_string(), true)
+            });
+        self.fill_log_view(buffer_id, true);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Show one more severity level in the log, wrapping to debug
+    pub fn log_cycle_level(&mut self) {
+        self.log_view.min_level = next_level(self.log_view.min_level);
+        let level = level_name(self.log_view.min_level);
+        self.refresh_log_view();
+        self.set_status_message(t!("log.level", level = level).to_string());
+    }
+
+    /// Restrict the log to the next source that logged, wrapping to all
+    pub fn log_cycle_source(&mut self) {
+        self.log_view.source_filter = self.log_view.next_source_filter();
+        self.refresh_log_view();
+        let status = match &self.log_view.source_filter {
+            Some(source) => t!("log.source", source = source),
+            None => t!("log.all_sources"),
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Add new events to the `*Log*` buffer if it is open (called from the
+    /// main loop)
+    ///
+    /// Returns true if the buffer changed (requires re-render).
+    pub fn check_log_view(&mut self) -> bool {
+        let Some(buffer_id) = self.find_buffer_by_name(LOG_BUFFER_NAME) else {
+            return false;
+        };
+        let view = &self.log_view;
+        if !view.buffer.has_new(view.shown_seq, view.min_level) {
+            return false;
+        }
+        self.fill_log_view(buffer_id, false);
+        true
+    }
+
+    fn refresh_log_view(&mut self) {
+        if let Some(buffer_id) = self.find_buffer_by_name(LOG_BUFFER_NAME) {
+            self.fill_log_view(buffer_id, true);
+        }
+    }
+
+    /// Fill the log buffer; the cursor follows new events when it was at the end
+    fn fill_log_view(&mut self, buffer_id: BufferId, move_to_end: bool) {
+        let view = &self.log_view;
+        // Events logged while filling are shown next time
+        let shown_seq = view.buffer.next_seq();
+        let mut content = format!(
+            "Log: {}   (showing {} and more severe, f: change, s: source, q: close)\n\n",
+            view.source_filter.as_deref().unwrap_or("all sources"),
+            level_name(view.min_level)
+        );
+        for entry in view.visible() {
+            content.push_str(&format_entry(&entry));
+        }
+        self.log_view.shown_seq = shown_seq;
+
+        let old_len = self.buffers.get(&buffer_id).map_or(0, |s| s.buffer.len());
+        let at_end = move_to_end || self.buffer_cursor_position(buffer_id) == Some(old_len);
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill log buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        if at_end {
+            self.move_cursor_to_buffer_end(buffer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_view_filters() {
+        let mut view = LogViewState::default();
+        view.buffer.push(
+            Level::DEBUG,
+            "fresh::services::lsp::manager",
+            "request".into(),
+        );
+        view.buffer.push(
+            Level::ERROR,
+            "fresh::services::lsp::manager",
+            "exited".into(),
+        );
+        view.buffer.push(
+            Level::WARN,
+            "fresh::services::plugins::thread",
+            "slow".into(),
+        );
+        let messages = |view: &LogViewState| -> Vec<String> {
+            view.visible().into_iter().map(|e| e.message).collect()
+        };
+        assert_eq!(messages(&view), vec!["exited", "slow"]);
+
+        view.min_level = next_level(Level::ERROR);
+        assert_eq!(view.visible().len(), 3);
+
+        view.source_filter = view.next_source_filter();
+        assert_eq!(view.source_filter.as_deref(), Some("lsp"));
+        assert_eq!(messages(&view), vec!["request", "exited"]);
+        view.source_filter = view.next_source_filter();
+        assert_eq!(view.source_filter.as_deref(), Some("plugins"));
+        view.source_filter = view.next_source_filter();
+        assert_eq!(view.source_filter, None);
+    }
+
+    #[test]
+    fn test_entry_format_indents_continuation_lines() {
+        let buffer = LogBuffer::new();
+        buffer.push(
+            Level::WARN,
+            "fresh::app",
+            "Config error\nline 3: expected }".into(),
+        );
+        let text = format_entry(&buffer.entries(Level::TRACE)[0]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].ends_with(" WARN  fresh::app: Config error"));
+        assert_eq!(lines[1], "    line 3: expected }");
+    }
+}
@@ -20,22 +20,27 @@ impl Editor {
             return;
         };
         let language = state.language.clone();
+        self.restart_lsp_server(&language);
+    }
 
-        // Attempt restart
+    /// Restart one LSP server (a language's main server or an additional one)
+    /// and re-send didOpen notifications for the buffers it serves.
+    pub(super) fn restart_lsp_server(&mut self, server: &str) {
         let Some(lsp) = self.lsp.as_mut() else {
             self.set_status_message(t!("lsp.no_manager").to_string());
             return;
         };
 
-        let (success, message) = lsp.manual_restart(&language);
+        let (success, message) = lsp.manual_restart(server);
         self.status_message = Some(message);
 
         if !success {
             return;
         }
 
-        // Re-send didOpen for all buffers of this language
-        self.reopen_buffers_for_language(&language);
+        // Re-send didOpen for all buffers of this language. Additional
+        // servers get theirs once they finish initializing.
+        self.reopen_buffers_for_language(server);
     }
 
     /// Re-send didOpen notifications for all buffers of a given language.
@@ -215,6 +220,7 @@ impl Editor {
 
         if let Some(uri_str) = uri {
             self.stored_diagnostics.remove(&uri_str);
+            self.server_diagnostics.remove(&uri_str);
             self.diagnostic_result_ids.remove(&uri_str);
         }
 
@@ -0,0 +1,470 @@
+//! LSP info view and server logs
+//!
+//! "Show LSP Info" lists every language server with its state, root URI,
+//! command and supported features. Keys in the view restart or stop the
+//! server under the cursor and open its log: the messages the server sent
+//! through `window/logMessage` followed by its stderr output, filtered by
+//! level and refreshed while the server keeps writing.
+
+use super::types::LspMessageEntry;
+use super::Editor;
+use crate::input::buffer_mode::{LSP_INFO_MODE, LSP_LOG_MODE};
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::{LspMessageType, LspServerStatus};
+use rust_i18n::t;
+use std::path::PathBuf;
+
+/// Name of the read-only buffer listing the language servers
+pub const LSP_INFO_BUFFER_NAME: &str = "*LSP Info*";
+
+/// Name of the read-only buffer showing a server's log
+pub const LSP_LOG_BUFFER_NAME: &str = "*LSP Log*";
+
+/// Text property naming the server a line of the info view belongs to
+const SERVER_PROPERTY: &str = "lsp_server";
+
+/// Number of stderr lines kept in the log view
+const MAX_STDERR_LINES: usize = 1000;
+
+/// State of the open LSP log view
+#[derive(Debug)]
+pub(crate) struct LspLogView {
+    buffer_id: BufferId,
+    server: String,
+    stderr_path: PathBuf,
+    /// Least severe level shown
+    min_level: LspMessageType,
+    /// Size of the stderr log when the view was last filled
+    stderr_len: u64,
+    last_poll: std::time::Instant,
+}
+
+/// Next level in the filter cycle: all messages, then info, warnings and
+/// errors only
+fn next_level(level: LspMessageType) -> LspMessageType {
+    match level {
+        LspMessageType::Log => LspMessageType::Info,
+        LspMessageType::Info => LspMessageType::Warning,
+        LspMessageType::Warning => LspMessageType::Error,
+        LspMessageType::Error => LspMessageType::Log,
+    }
+}
+
+fn level_name(level: LspMessageType) -> &'static str {
+    match level {
+        LspMessageType::Error => "error",
+        LspMessageType::Warning => "warning",
+        LspMessageType::Info => "info",
+        LspMessageType::Log => "log",
+    }
+}
+
+/// Level named by a word near the start of a stderr line, if any
+///
+/// Servers format their stderr differently (`ERROR`, `[Error - ...]`,
+/// `- WARNING -`), so this only looks for a capitalized level word.
+fn stderr_line_level(line: &str) -> Option<LspMessageType> {
+    let head: String = line.chars().take(64).collect();
+    head.split(|c: char| !c.is_ascii_alphabetic())
+        .find_map(|word| match word {
+            "ERROR" | "Error" | "FATAL" | "Fatal" | "panicked" => Some(LspMessageType::Error),
+            "WARN" | "Warn" | "WARNING" | "Warning" => Some(LspMessageType::Warning),
+            "INFO" | "Info" => Some(LspMessageType::Info),
+            "DEBUG" | "Debug" | "TRACE" | "Trace" => Some(LspMessageType::Log),
+            _ => None,
+        })
+}
+
+/// Keep the stderr lines at `min_level` or more severe
+///
+/// Lines without a level word (continuations, stack traces) belong to the
+/// line before them; lines before the first level word count as info.
+fn filter_stderr(text: &str, min_level: LspMessageType) -> Vec<&str> {
+    let mut level = LspMessageType::Info;
+    text.lines()
+        .filter(|line| {
+            level = stderr_line_level(line).unwrap_or(level);
+            level <= min_level
+        })
+        .collect()
+}
+
+/// State shown for a server; `running` tells whether it has a live handle,
+/// since a stopped server may not report a final status
+fn status_name(running: bool, status: Option<&LspServerStatus>) -> &'static str {
+    match (running, status) {
+        (_, Some(LspServerStatus::Error)) => "error",
+        (false, _) | (true, Some(LspServerStatus::Shutdown)) => "not running",
+        (true, Some(LspServerStatus::Starting) | None) => "starting",
+        (true, Some(LspServerStatus::Initializing)) => "initializing",
+        (true, Some(LspServerStatus::Running)) => "running",
+    }
+}
+
+impl Editor {
+    /// Open (or refresh) the LSP info view
+    pub fn show_lsp_info(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(LSP_INFO_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    LSP_INFO_BUFFER_NAME.to_string(),
+                    LSP_INFO_MODE.to_string(),
+                    true,
+                )
+            });
+        self.fill_lsp_info(buffer_id);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Rebuild the LSP info view if it is open (e.g. after a status change)
+    pub(super) fn refresh_lsp_info(&mut self) {
+        if let Some(buffer_id) = self.find_buffer_by_name(LSP_INFO_BUFFER_NAME) {
+            self.fill_lsp_info(buffer_id);
+        }
+    }
+
+    pub(super) fn find_buffer_by_name(&self, name: &str) -> Option<BufferId> {
+        self.buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// Servers shown in the info view: running ones, ones that reported a
+    /// status, and the configured servers of the open buffers' languages
+    fn lsp_info_servers(&self) -> Vec<String> {
+        let Some(lsp) = self.lsp.as_ref() else {
+            return Vec::new();
+        };
+        let mut servers = lsp.running_servers();
+        servers.extend(self.lsp_server_statuses.keys().cloned());
+        for state in self.buffers.values() {
+            if lsp.get_config(&state.language).is_some_and(|c| c.enabled) {
+                servers.push(state.language.clone());
+                servers.extend(lsp.additional_server_keys(&state.language).iter().cloned());
+            }
+        }
+        servers.sort();
+        servers.dedup();
+        servers
+    }
+
+    fn fill_lsp_info(&mut self, buffer_id: BufferId) {
+        let mut entries = vec![TextPropertyEntry::text(
+            "LSP Servers\n===========\n\nr: restart   s: stop   Enter/l: open log   q: close\n\n",
+        )];
+
+        let servers = self.lsp_info_servers();
+        if servers.is_empty() {
+            entries.push(TextPropertyEntry::text(
+                "No language server is configured for the open files.\n",
+            ));
+        }
+
+        if let Some(lsp) = self.lsp.as_ref() {
+            for server in servers {
+                let mut status = status_name(
+                    lsp.get_handle(&server).is_some(),
+                    self.lsp_server_statuses.get(&server),
+                )
+                .to_string();
+                if lsp.is_in_cooldown(&server) {
+                    status.push_str(", crashed too often, not restarting");
+                } else if lsp.has_pending_restart(&server) {
+                    status.push_str(", restart scheduled");
+                }
+
+                let mut section = format!("{} ({})\n", server, status);
+                if let Some(config) = lsp.get_config(&server) {
+                    let command = std::iter::once(config.command.as_str())
+                        .chain(config.args.iter().map(String::as_str))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    section.push_str(&format!("  Command:   {}\n", command));
+                }
+                if let Some(root) = lsp.get_effective_root_uri(&server) {
+                    section.push_str(&format!("  Root:      {}\n", root.as_str()));
+                }
+                let features = lsp.server_features(&server);
+                if lsp.get_handle(&server).is_some() && !features.is_empty() {
+                    section.push_str(&format!("  Features:  {}\n", features.join(", ")));
+                }
+                let restarts = lsp.restart_attempt_count(&server);
+                if restarts > 0 {
+                    section.push_str(&format!("  Restarts:  {}\n", restarts));
+                }
+                section.push_str(&format!(
+                    "  Log:       {}\n\n",
+                    crate::services::log_dirs::lsp_log_path(&server).display()
+                ));
+
+                entries.push(
+                    TextPropertyEntry::text(section)
+                        .with_property(SERVER_PROPERTY, serde_json::Value::String(server)),
+                );
+            }
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::error!("Failed to fill LSP info buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+    }
+
+    /// Server of the info view section under the cursor
+    fn lsp_info_server_at_cursor(&self) -> Option<String> {
+        if self.find_buffer_by_name(LSP_INFO_BUFFER_NAME) != Some(self.active_buffer()) {
+            return None;
+        }
+        self.get_text_properties_at_cursor()?
+            .into_iter()
+            .find_map(|prop| prop.get(SERVER_PROPERTY)?.as_str().map(String::from))
+    }
+
+    fn lsp_info_server_or_notify(&mut self) -> Option<String> {
+        let server = self.lsp_info_server_at_cursor();
+        if server.is_none() {
+            self.set_status_message(t!("lsp.info_no_server").to_string());
+        }
+        server
+    }
+
+    /// Restart the server under the cursor in the info view
+    pub fn lsp_info_restart(&mut self) {
+        if let Some(server) = self.lsp_info_server_or_notify() {
+            self.restart_lsp_server(&server);
+            self.refresh_lsp_info();
+        }
+    }
+
+    /// Stop the server under the cursor in the info view
+    pub fn lsp_info_stop(&mut self) {
+        if let Some(server) = self.lsp_info_server_or_notify() {
+            self.handle_stop_lsp_server(&server);
+            self.refresh_lsp_info();
+        }
+    }
+
+    /// Open the log of the server under the cursor in the info view
+    pub fn lsp_info_open_log(&mut self) {
+        let Some(server) = self.lsp_info_server_or_notify() else {
+            return;
+        };
+
+        let buffer_id = match self.lsp_log_view.as_ref() {
+            Some(view) if self.buffers.contains_key(&view.buffer_id) => view.buffer_id,
+            _ => self.create_virtual_buffer(
+                LSP_LOG_BUFFER_NAME.to_string(),
+                LSP_LOG_MODE.to_string(),
+                true,
+            ),
+        };
+        let min_level = self
+            .lsp_log_view
+            .as_ref()
+            .map_or(LspMessageType::Log, |view| view.min_level);
+        self.lsp_log_view = Some(LspLogView {
+            buffer_id,
+            stderr_path: crate::services::log_dirs::lsp_log_path(&server),
+            server,
+            min_level,
+            stderr_len: 0,
+            last_poll: self.time_source.now(),
+        });
+        self.fill_lsp_log(true);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Show fewer or more messages in the LSP log view
+    pub fn lsp_log_cycle_level(&mut self) {
+        let Some(view) = self.lsp_log_view.as_mut() else {
+            return;
+        };
+        view.min_level = next_level(view.min_level);
+        let level = level_name(view.min_level);
+        self.fill_lsp_log(false);
+        self.set_status_message(t!("lsp.log_level", level = level).to_string());
+    }
+
+    /// Refresh the LSP log view when `server` logged a message
+    pub(super) fn refresh_lsp_log_for(&mut self, server: &str) {
+        if self
+            .lsp_log_view
+            .as_ref()
+            .is_some_and(|view| view.server == server)
+        {
+            self.fill_lsp_log(false);
+        }
+    }
+
+    /// Reload the LSP log view when the server wrote to stderr
+    ///
+    /// Checked at the auto-revert poll interval. Returns true if the view
+    /// changed.
+    pub(super) fn poll_lsp_log(&mut self) -> bool {
+        let interval =
+            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        let now = self.time_source.now();
+        let Some(view) = self.lsp_log_view.as_mut() else {
+            return false;
+        };
+        if now.saturating_duration_since(view.last_poll) < interval {
+            return false;
+        }
+        view.last_poll = now;
+        let len = std::fs::metadata(&view.stderr_path).map_or(0, |m| m.len());
+        if len == view.stderr_len {
+            return false;
+        }
+        self.fill_lsp_log(false)
+    }
+
+    /// Fill the log view from the server's messages and stderr log
+    ///
+    /// The cursor follows new output when it was at the end. Returns false
+    /// (and forgets the view) once its buffer has been closed.
+    fn fill_lsp_log(&mut self, move_to_end: bool) -> bool {
+        let Some(view) = self.lsp_log_view.as_mut() else {
+            return false;
+        };
+        if !self.buffers.contains_key(&view.buffer_id) {
+            self.lsp_log_view = None;
+            return false;
+        }
+
+        let stderr = std::fs::read(&view.stderr_path).unwrap_or_default();
+        view.stderr_len = stderr.len() as u64;
+        let stderr = String::from_utf8_lossy(&stderr);
+        let stderr_lines = filter_stderr(&stderr, view.min_level);
+
+        let mut content = format!(
+            "LSP log: {}   (showing {} and more severe, f: change)\n\n",
+            view.server,
+            level_name(view.min_level)
+        );
+        let mut messages: Vec<&LspMessageEntry> = self
+            .lsp_window_messages
+            .iter()
+            .chain(&self.lsp_log_messages)
+            .filter(|entry| entry.language == view.server && entry.message_type <= view.min_level)
+            .collect();
+        messages.sort_by_key(|entry| entry.timestamp);
+        for entry in messages {
+            content.push_str(&format!(
+                "[{}] {}\n",
+                level_name(entry.message_type),
+                entry.message
+            ));
+        }
+        content.push_str(&format!("\n── stderr: {} ──\n", view.stderr_path.display()));
+        let skip = stderr_lines.len().saturating_sub(MAX_STDERR_LINES);
+        for line in &stderr_lines[skip..] {
+            content.push_str(line);
+            content.push('\n');
+        }
+
+        let buffer_id = view.buffer_id;
+        let old_len = self.buffers.get(&buffer_id).map_or(0, |s| s.buffer.len());
+        let at_end = move_to_end || self.buffer_cursor_position(buffer_id) == Some(old_len);
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill LSP log buffer: {}", e);
+            return false;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        if at_end {
+            self.move_cursor_to_buffer_end(buffer_id);
+        }
+        true
+    }
+
+    /// Put the cursor at the end of a buffer in every split showing it
+    pub(super) fn move_cursor_to_buffer_end(&mut self, buffer_id: BufferId) {
+        let Some(end) = self.buffers.get(&buffer_id).map(|s| s.buffer.len()) else {
+            return;
+        };
+        for view_state in self.split_view_states.values_mut() {
+            if let Some(buf_state) = view_state.keyed_states.get_mut(&buffer_id) {
+                buf_state.cursors.primary_mut().position = end;
+                buf_state.cursors.primary_mut().anchor = None;
+            }
+        }
+    }
+
+    pub(super) fn buffer_cursor_position(&self, buffer_id: BufferId) -> Option<usize> {
+        self.split_view_states
+            .values()
+            .find_map(|vs| vs.keyed_states.get(&buffer_id))
+            .map(|bs| bs.cursors.primary().position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stderr_line_level() {
+        assert_eq!(
+            stderr_line_level("2024-05-01T10:00:00Z ERROR rust_analyzer: failed"),
+            Some(LspMessageType::Error)
+        );
+        assert_eq!(
+            stderr_line_level("[Warn - 10:00:00] slow response"),
+            Some(LspMessageType::Warning)
+        );
+        assert_eq!(
+            stderr_line_level("2024-05-01 10:00:00 UTC - INFO - pylsp.config"),
+            Some(LspMessageType::Info)
+        );
+        assert_eq!(
+            stderr_line_level("DEBUG request received"),
+            Some(LspMessageType::Log)
+        );
+        // Lowercase words in the message are not levels
+        assert_eq!(stderr_line_level("could not find an error handler"), None);
+    }
+
+    #[test]
+    fn test_filter_stderr_keeps_continuation_lines() {
+        let log = "starting\nINFO ready\nERROR crashed\n  at main.rs:1\nINFO retrying\n";
+        assert_eq!(
+            filter_stderr(log, LspMessageType::Error),
+            vec!["ERROR crashed", "  at main.rs:1"]
+        );
+        assert_eq!(filter_stderr(log, LspMessageType::Log).len(), 5);
+        assert_eq!(
+            filter_stderr(log, LspMessageType::Info),
+            filter_stderr(log, LspMessageType::Log)
+        );
+    }
+
+    #[test]
+    fn test_level_cycle_returns_to_all() {
+        let mut level = LspMessageType::Log;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            level = next_level(level);
+            seen.push(level);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                LspMessageType::Info,
+                LspMessageType::Warning,
+                LspMessageType::Error,
+                LspMessageType::Log
+            ]
+        );
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module contains all methods related to LSP operations including:
 //! - Completion requests and response handling
-//! - Go-to-definition
+//! - Go-to-definition, type definition and implementation
 //! - Hover documentation
 //! - Find references
 //! - Signature help
@@ -12,16 +12,20 @@
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
+use std::collections::HashSet;
 use std::io;
+use std::ops::Range;
 use std::time::{Duration, Instant};
 
 use lsp_types::TextDocumentContentChangeEvent;
 
 use crate::model::event::{BufferId, Event};
 use crate::primitives::word_navigation::{find_word_end, find_word_start};
+use crate::services::lsp::async_handler::GotoTarget;
 use crate::view::prompt::{Prompt, PromptType};
 
-use super::{uri_to_path, Editor, SemanticTokenRangeRequest};
+use super::completion_providers::completion_item_detail;
+use super::{uri_to_path, Editor, FormattingRequest, SemanticTokenRangeRequest};
 
 const SEMANTIC_TOKENS_FULL_DEBOUNCE_MS: u64 = 500;
 const SEMANTIC_TOKENS_RANGE_DEBOUNCE_MS: u64 = 50;
@@ -34,18 +38,41 @@ impl Editor {
         request_id: u64,
         items: Vec<lsp_types::CompletionItem>,
     ) -> AnyhowResult<()> {
-        // Check if this is the pending completion request
-        if self.pending_completion_request != Some(request_id) {
+        // Check if this is one of the pending completion requests
+        let Some(response) = self
+            .completion_responses
+            .iter_mut()
+            .find(|(id, _)| *id == request_id)
+        else {
             tracing::debug!(
                 "Ignoring completion response for outdated request {}",
                 request_id
             );
             return Ok(());
+        };
+        response.1 = Some(items);
+
+        // Wait until every server has answered
+        if self.completion_responses.iter().any(|(_, r)| r.is_none()) {
+            return Ok(());
         }
 
         self.pending_completion_request = None;
         self.lsp_status.clear();
 
+        // Merge the results, main server first; an additional server's item
+        // is dropped when an earlier server already offered the same label
+        let mut items: Vec<lsp_types::CompletionItem> = Vec::new();
+        for (_, server_items) in std::mem::take(&mut self.completion_responses) {
+            let known: HashSet<String> = items.iter().map(|item| item.label.clone()).collect();
+            items.extend(
+                server_items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|item| !known.contains(&item.label)),
+            );
+        }
+
         if items.is_empty() {
             tracing::debug!("No completion items received");
             return Ok(());
@@ -98,7 +125,7 @@ impl Editor {
             .iter()
             .map(|item| {
                 let text = item.label.clone();
-                let detail = item.detail.clone();
+                let detail = completion_item_detail(item);
                 let icon = match item.kind {
                     Some(lsp_types::CompletionItemKind::FUNCTION)
                     | Some(lsp_types::CompletionItemKind::METHOD) => Some("λ".to_string()),
@@ -178,115 +205,225 @@ impl Editor {
         Ok(())
     }
 
-    /// Handle LSP go-to-definition response
+    /// Handle LSP go-to-definition (or type definition / implementation) response
+    ///
+    /// A single location is jumped to directly; several open a picker.
     pub(crate) fn handle_goto_definition_response(
         &mut self,
         request_id: u64,
-        locations: Vec<lsp_types::Location>,
+        mut locations: Vec<lsp_types::Location>,
     ) -> AnyhowResult<()> {
         // Check if this is the pending request
-        if self.pending_goto_definition_request != Some(request_id) {
-            tracing::debug!(
-                "Ignoring go-to-definition response for outdated request {}",
-                request_id
-            );
-            return Ok(());
-        }
+        let target = match self.pending_goto_definition_request {
+            Some((pending_id, target)) if pending_id == request_id => target,
+            _ => {
+                tracing::debug!(
+                    "Ignoring go-to-definition response for outdated request {}",
+                    request_id
+                );
+                return Ok(());
+            }
+        };
 
         self.pending_goto_definition_request = None;
 
-        if locations.is_empty() {
-            self.status_message = Some(t!("lsp.no_definition").to_string());
-            return Ok(());
-        }
-
-        // For now, just jump to the first location
-        let location = &locations[0];
+        // Servers sometimes report the same location more than once
+        locations.dedup_by(|a, b| a.uri == b.uri && a.range.start == b.range.start);
 
-        // Convert URI to file path
-        if let Ok(path) = uri_to_path(&location.uri) {
-            // Open the file
-            let buffer_id = match self.open_file(&path) {
-                Ok(id) => id,
-                Err(e) => {
-                    // Check if this is a large file encoding confirmation error
-                    if let Some(confirmation) =
-                        e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
-                    {
-                        self.start_large_file_encoding_confirmation(confirmation);
-                    } else {
-                        self.set_status_message(
-                            t!("file.error_opening", error = e.to_string()).to_string(),
-                        );
-                    }
-                    return Ok(());
-                }
-            };
+        let (none_message, picker_title) = match target {
+            GotoTarget::Definition => (t!("lsp.no_definition"), t!("lsp.pick_definition")),
+            GotoTarget::TypeDefinition => {
+                (t!("lsp.no_type_definition"), t!("lsp.pick_type_definition"))
+            }
+            GotoTarget::Implementation => {
+                (t!("lsp.no_implementation"), t!("lsp.pick_implementation"))
+            }
+        };
 
-            // Check if file is outside project root (library file)
-            let is_library_file = self.is_library_file(&path);
-            if is_library_file {
-                // Mark as read-only
-                if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                    state.editing_disabled = true;
-                }
-                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
-                    metadata.read_only = true;
+        match locations.len() {
+            0 => self.status_message = Some(none_message.to_string()),
+            1 => {
+                if let Some(path) = self.jump_to_lsp_location(&locations[0]) {
+                    let path = path.display().to_string();
+                    let line = locations[0].range.start.line as usize + 1;
+                    let message = match target {
+                        GotoTarget::Definition => {
+                            t!("lsp.jumped_to_definition", path = path, line = line)
+                        }
+                        GotoTarget::TypeDefinition => {
+                            t!("lsp.jumped_to_type_definition", path = path, line = line)
+                        }
+                        GotoTarget::Implementation => {
+                            t!("lsp.jumped_to_implementation", path = path, line = line)
+                        }
+                    };
+                    self.status_message = Some(message.to_string());
                 }
             }
+            _ => self.show_location_picker(picker_title.to_string(), locations),
+        }
 
-            // Move cursor to the definition position
-            let line = location.range.start.line as usize;
-            let character = location.range.start.character as usize;
+        Ok(())
+    }
 
-            // Calculate byte position from line and character
-            let position = self
-                .buffers
-                .get(&buffer_id)
-                .map(|state| state.buffer.line_col_to_position(line, character));
-
-            if let Some(position) = position {
-                // Move cursor - read cursor info from split view state
-                let (cursor_id, old_position, old_anchor, old_sticky_column) = {
-                    let cursors = self.active_cursors();
-                    let primary = cursors.primary();
-                    (
-                        cursors.primary_id(),
-                        primary.position,
-                        primary.anchor,
-                        primary.sticky_column,
-                    )
-                };
-                let event = crate::model::event::Event::MoveCursor {
-                    cursor_id,
-                    old_position,
-                    new_position: position,
-                    old_anchor,
-                    new_anchor: None,
-                    old_sticky_column,
-                    new_sticky_column: 0, // Reset sticky column for goto definition
-                };
+    /// Open the file of an LSP location and move the cursor there
+    ///
+    /// The position before the jump is pushed onto the position history so
+    /// Navigate Back returns to it. Returns the file path on success.
+    pub(crate) fn jump_to_lsp_location(
+        &mut self,
+        location: &lsp_types::Location,
+    ) -> Option<std::path::PathBuf> {
+        let Ok(path) = uri_to_path(&location.uri) else {
+            self.status_message = Some(t!("lsp.cannot_open_definition").to_string());
+            return None;
+        };
+
+        // Remember where we jumped from
+        self.position_history.commit_pending_movement();
+        let (position, anchor) = {
+            let primary = self.active_cursors().primary();
+            (primary.position, primary.anchor)
+        };
+        self.position_history
+            .record_movement(self.active_buffer(), position, anchor);
+        self.position_history.commit_pending_movement();
 
-                let split_id = self.split_manager.active_split();
-                if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                    let cursors = &mut self.split_view_states.get_mut(&split_id).unwrap().cursors;
-                    state.apply(cursors, &event);
+        // Open the file
+        let buffer_id = match self.open_file(&path) {
+            Ok(id) => id,
+            Err(e) => {
+                // Check if this is a large file encoding confirmation error
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    self.start_large_file_encoding_confirmation(confirmation);
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
                 }
+                return None;
             }
+        };
+
+        // Check if file is outside project root (library file)
+        let is_library_file = self.is_library_file(&path);
+        if is_library_file {
+            // Mark as read-only
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.editing_disabled = true;
+            }
+            if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+                metadata.read_only = true;
+            }
+        }
 
-            self.status_message = Some(
-                t!(
-                    "lsp.jumped_to_definition",
-                    path = path.display().to_string(),
-                    line = line + 1
+        // Move cursor to the location
+        let line = location.range.start.line as usize;
+        let character = location.range.start.character as usize;
+
+        // Calculate byte position from line and character
+        let position = self
+            .buffers
+            .get(&buffer_id)
+            .map(|state| state.buffer.line_col_to_position(line, character));
+
+        if let Some(position) = position {
+            // Move cursor - read cursor info from split view state
+            let (cursor_id, old_position, old_anchor, old_sticky_column) = {
+                let cursors = self.active_cursors();
+                let primary = cursors.primary();
+                (
+                    cursors.primary_id(),
+                    primary.position,
+                    primary.anchor,
+                    primary.sticky_column,
                 )
-                .to_string(),
-            );
-        } else {
-            self.status_message = Some(t!("lsp.cannot_open_definition").to_string());
+            };
+            let event = crate::model::event::Event::MoveCursor {
+                cursor_id,
+                old_position,
+                new_position: position,
+                old_anchor,
+                new_anchor: None,
+                old_sticky_column,
+                new_sticky_column: 0, // Reset sticky column for goto definition
+            };
+
+            let split_id = self.split_manager.active_split();
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                let cursors = &mut self.split_view_states.get_mut(&split_id).unwrap().cursors;
+                state.apply(cursors, &event);
+            }
         }
 
-        Ok(())
+        Some(path)
+    }
+
+    /// Let the user choose between several LSP locations
+    ///
+    /// Each entry shows `file:line` with the source line as a preview.
+    fn show_location_picker(&mut self, title: String, locations: Vec<lsp_types::Location>) {
+        let mut file_lines: std::collections::HashMap<std::path::PathBuf, Vec<String>> =
+            std::collections::HashMap::new();
+        let suggestions = locations
+            .iter()
+            .enumerate()
+            .map(|(i, location)| {
+                let line = location.range.start.line as usize;
+                let (label, preview) = match uri_to_path(&location.uri) {
+                    Ok(path) => {
+                        let lines = file_lines.entry(path.clone()).or_insert_with(|| {
+                            std::fs::read_to_string(&path)
+                                .map(|text| text.lines().map(str::to_string).collect())
+                                .unwrap_or_default()
+                        });
+                        let preview = lines.get(line).map(|l| l.trim().to_string());
+                        let display = path
+                            .strip_prefix(&self.working_dir)
+                            .unwrap_or(&path)
+                            .display()
+                            .to_string();
+                        (format!("{}:{}", display, line + 1), preview)
+                    }
+                    Err(_) => (format!("{}:{}", location.uri.as_str(), line + 1), None),
+                };
+                crate::input::commands::Suggestion {
+                    text: label,
+                    description: preview,
+                    value: Some(i.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.location_picker_choices = locations;
+        self.prompt = Some(Prompt::with_suggestions(
+            title,
+            PromptType::GotoLocation,
+            suggestions,
+        ));
+    }
+
+    /// Jump to the location picked from the location picker
+    pub(crate) fn confirm_location_choice(&mut self, choice: &str) {
+        let locations = std::mem::take(&mut self.location_picker_choices);
+        if let Some(location) = choice.parse::<usize>().ok().and_then(|i| locations.get(i)) {
+            if let Some(path) = self.jump_to_lsp_location(location) {
+                let line = location.range.start.line as usize + 1;
+                self.status_message = Some(
+                    t!(
+                        "lsp.jumped_to_location",
+                        path = path.display().to_string(),
+                        line = line
+                    )
+                    .to_string(),
+                );
+            }
+        }
     }
 
     /// Check if a file path is a library file (outside project root or in common library directories).
@@ -309,8 +446,24 @@ impl Editor {
             // Send cancellation to the LSP server
             self.send_lsp_cancel_request(request_id);
             self.lsp_status.clear();
+
+            // Requests to additional servers are cancelled too; each server
+            // ignores the IDs it does not know
+            let additional: Vec<u64> = std::mem::take(&mut self.completion_responses)
+                .into_iter()
+                .filter(|(id, response)| *id != request_id && response.is_none())
+                .map(|(id, _)| id)
+                .collect();
+            if !additional.is_empty() {
+                let buffer_id = self.active_buffer();
+                self.with_additional_lsp_for_buffer(buffer_id, false, |_, handle, _| {
+                    for id in &additional {
+                        let _ = handle.cancel_request(*id);
+                    }
+                });
+            }
         }
-        if let Some(request_id) = self.pending_goto_definition_request.take() {
+        if let Some((request_id, _)) = self.pending_goto_definition_request.take() {
             tracing::debug!(
                 "Canceling pending LSP goto-definition request {}",
                 request_id
@@ -415,6 +568,59 @@ impl Editor {
         Some(f(handle, &uri, &language))
     }
 
+    /// Execute a closure with each running additional server of a buffer's
+    /// language, ensuring didOpen was sent to each one first.
+    ///
+    /// Additional servers are started together with the main server, so this
+    /// never spawns anything. With `call_after_open` false, the closure is
+    /// skipped for servers that only received didOpen now (used for change
+    /// notifications, since didOpen already carries the current text).
+    /// Returns the closure results, along with the key of each server.
+    pub(crate) fn with_additional_lsp_for_buffer<F, R>(
+        &mut self,
+        buffer_id: BufferId,
+        call_after_open: bool,
+        mut f: F,
+    ) -> Vec<(String, R)>
+    where
+        F: FnMut(&str, &crate::services::lsp::async_handler::LspHandle, &lsp_types::Uri) -> R,
+    {
+        let mut results = Vec::new();
+        let (Some(metadata), Some(state), Some(lsp)) = (
+            self.buffer_metadata.get(&buffer_id),
+            self.buffers.get(&buffer_id),
+            self.lsp.as_ref(),
+        ) else {
+            return results;
+        };
+        let Some(uri) = metadata.file_uri().filter(|_| metadata.lsp_enabled) else {
+            return results;
+        };
+
+        let mut opened = Vec::new();
+        for (key, handle) in lsp.additional_handles(&state.language) {
+            if !metadata.lsp_opened_with.contains(&handle.id()) {
+                let Some(text) = state.buffer.to_string() else {
+                    return results;
+                };
+                if let Err(e) = handle.did_open(uri.clone(), text, state.language.clone()) {
+                    tracing::warn!("Failed to send didOpen to {}: {}", key, e);
+                    continue;
+                }
+                opened.push(handle.id());
+                if !call_after_open {
+                    continue;
+                }
+            }
+            results.push((key.to_string(), f(key, handle, uri)));
+        }
+
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.lsp_opened_with.extend(opened);
+        }
+        results
+    }
+
     /// Request LSP completion at current cursor position
     pub(crate) fn request_completion(&mut self) -> AnyhowResult<()> {
         // Get the current buffer and cursor position
@@ -443,12 +649,43 @@ impl Editor {
             })
             .unwrap_or(false);
 
+        // Request ids of every source, with its rank; language servers rank 0
+        let mut requests: Vec<(i32, u64)> = Vec::new();
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_completion_request = Some(request_id);
+            requests.push((0, request_id));
             self.lsp_status = "LSP: completion...".to_string();
+
+            // Ask the additional servers too; their items are merged in
+            let mut next_id = self.next_lsp_request_id;
+            let additional =
+                self.with_additional_lsp_for_buffer(buffer_id, true, |_, handle, uri| {
+                    let id = next_id;
+                    next_id += 1;
+                    handle
+                        .completion(id, uri.clone(), line as u32, character as u32)
+                        .ok()
+                        .map(|_| id)
+                });
+            self.next_lsp_request_id = next_id;
+            requests.extend(
+                additional
+                    .into_iter()
+                    .filter_map(|(_, id)| id.map(|id| (0, id))),
+            );
+        }
+
+        self.completion_sources.clear();
+        requests.extend(self.request_plugin_completions(buffer_id, cursor_pos));
+        if requests.is_empty() {
+            return Ok(());
         }
 
+        // Items are merged in this order; equal ranks keep servers first
+        requests.sort_by_key(|(priority, _)| std::cmp::Reverse(*priority));
+        self.pending_completion_request = Some(if sent { request_id } else { requests[0].1 });
+        self.completion_responses = requests.into_iter().map(|(_, id)| (id, None)).collect();
+
         Ok(())
     }
 
@@ -470,7 +707,8 @@ impl Editor {
             .lsp
             .as_ref()
             .map(|lsp| lsp.is_completion_trigger_char(c, &language))
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || self.is_plugin_completion_trigger(c, &language);
 
         // Check if quick suggestions is enabled and this is a word character
         let quick_suggestions_enabled = self.config.editor.quick_suggestions;
@@ -508,8 +746,8 @@ impl Editor {
         }
     }
 
-    /// Request LSP go-to-definition at current cursor position
-    pub(crate) fn request_goto_definition(&mut self) -> AnyhowResult<()> {
+    /// Request LSP go-to-definition (or type definition / implementation) at current cursor position
+    pub(crate) fn request_goto_definition(&mut self, target: GotoTarget) -> AnyhowResult<()> {
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let state = self.active_state();
@@ -522,11 +760,17 @@ impl Editor {
         // Use helper to ensure didOpen is sent before the request
         let sent = self
             .with_lsp_for_buffer(buffer_id, |handle, uri, _language| {
-                let result =
-                    handle.goto_definition(request_id, uri.clone(), line as u32, character as u32);
+                let result = handle.goto_definition(
+                    request_id,
+                    target,
+                    uri.clone(),
+                    line as u32,
+                    character as u32,
+                );
                 if result.is_ok() {
                     tracing::info!(
-                        "Requested go-to-definition at {}:{}:{}",
+                        "Requested {:?} at {}:{}:{}",
+                        target,
                         uri.as_str(),
                         line,
                         character
@@ -538,7 +782,7 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_goto_definition_request = Some(request_id);
+            self.pending_goto_definition_request = Some((request_id, target));
         }
 
         Ok(())
@@ -946,6 +1190,86 @@ impl Editor {
         Ok(())
     }
 
+    /// Request LSP document highlights after the reference highlight debounce
+    ///
+    /// The word-match highlights are already visible by the time this runs;
+    /// the server's answer replaces them when it arrives.
+    pub(crate) fn maybe_request_document_highlights(&mut self) {
+        let active_buffer = self.active_buffer();
+        let mut request = None;
+        for (buffer_id, state) in self.buffers.iter_mut() {
+            if let Some(cursor_pos) = state.reference_highlight_overlay.take_lsp_request() {
+                if *buffer_id == active_buffer {
+                    let (line, character) = state.buffer.position_to_lsp_position(cursor_pos);
+                    request = Some((line, character, state.buffer.version()));
+                }
+            }
+        }
+        let Some((line, character, version)) = request else {
+            return;
+        };
+
+        let request_id = self.next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(active_buffer, |handle, uri, _language| {
+                handle
+                    .document_highlight(request_id, uri.clone(), line as u32, character as u32)
+                    .is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.pending_document_highlight_request = Some((request_id, active_buffer, version));
+        }
+    }
+
+    /// Handle LSP document highlight response
+    pub(crate) fn handle_document_highlight_response(
+        &mut self,
+        request_id: u64,
+        highlights: Vec<lsp_types::DocumentHighlight>,
+    ) {
+        let Some((pending_id, buffer_id, version)) = self.pending_document_highlight_request else {
+            return;
+        };
+        if pending_id != request_id {
+            tracing::debug!("Ignoring stale document highlight response: {}", request_id);
+            return;
+        }
+        self.pending_document_highlight_request = None;
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        // Positions are only meaningful against the text they were computed for
+        if state.buffer.version() != version {
+            return;
+        }
+
+        let ranges = highlights
+            .iter()
+            .map(|h| {
+                let start = state.buffer.lsp_position_to_byte(
+                    h.range.start.line as usize,
+                    h.range.start.character as usize,
+                );
+                let end = state.buffer.lsp_position_to_byte(
+                    h.range.end.line as usize,
+                    h.range.end.character as usize,
+                );
+                start..end
+            })
+            .filter(|range| range.start < range.end)
+            .collect();
+
+        state.reference_highlight_overlay.apply_lsp_highlights(
+            &mut state.overlays,
+            &mut state.marker_list,
+            ranges,
+        );
+    }
+
     /// Request LSP signature help at current cursor position
     pub(crate) fn request_signature_help(&mut self) -> AnyhowResult<()> {
         // Get the current buffer and cursor position
@@ -1002,101 +1326,135 @@ impl Editor {
         let signature_help = match signature_help {
             Some(help) if !help.signatures.is_empty() => help,
             _ => {
+                // The cursor left the call (or the server has nothing to show)
                 tracing::debug!("No signature help available");
+                self.hide_signature_help();
                 return;
             }
         };
 
-        // Get the active signature
-        let active_signature_idx = signature_help.active_signature.unwrap_or(0) as usize;
-        let signature = match signature_help.signatures.get(active_signature_idx) {
-            Some(sig) => sig,
-            None => return,
-        };
+        let active_signature = (signature_help.active_signature.unwrap_or(0) as usize)
+            .min(signature_help.signatures.len() - 1);
+        tracing::info!(
+            "Showing signature help popup for {} signatures",
+            signature_help.signatures.len()
+        );
+        self.signature_help = Some((signature_help, active_signature));
+        self.show_signature_help_popup();
+    }
 
-        // Build the display content
-        let mut lines: Vec<String> = Vec::new();
+    /// Hide the signature help popup and forget its state
+    pub(crate) fn hide_signature_help(&mut self) {
+        self.signature_help = None;
+        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            if state.popups.is_signature_help_popup() {
+                state.popups.hide();
+            }
+        }
+    }
 
-        // Add the signature label (function signature)
-        lines.push(signature.label.clone());
+    /// Show another overload in the signature help popup.
+    /// Returns false if there is only one signature.
+    pub(crate) fn cycle_signature_help(&mut self, forward: bool) -> bool {
+        let Some((help, active)) = &mut self.signature_help else {
+            return false;
+        };
+        let count = help.signatures.len();
+        if count < 2 {
+            return false;
+        }
+        *active = if forward {
+            (*active + 1) % count
+        } else {
+            (*active + count - 1) % count
+        };
+        self.show_signature_help_popup();
+        true
+    }
 
-        // Add parameter highlighting info
-        let active_param = signature_help
-            .active_parameter
-            .or(signature.active_parameter)
-            .unwrap_or(0) as usize;
+    /// (Re)build the signature help popup from the stored signature help
+    fn show_signature_help_popup(&mut self) {
+        use crate::view::markdown::{parse_markdown, StyledLine};
+        use crate::view::popup::{Popup, PopupContent, PopupKind, PopupPosition};
+        use ratatui::style::{Modifier, Style};
 
-        // If there are parameters, highlight the active one
-        if let Some(params) = &signature.parameters {
-            if let Some(param) = params.get(active_param) {
-                // Get parameter label
-                let param_label = match &param.label {
-                    lsp_types::ParameterLabel::Simple(s) => s.clone(),
-                    lsp_types::ParameterLabel::LabelOffsets(offsets) => {
-                        // Extract substring from signature label
-                        let start = offsets[0] as usize;
-                        let end = offsets[1] as usize;
-                        if end <= signature.label.len() {
-                            signature.label[start..end].to_string()
-                        } else {
-                            String::new()
-                        }
-                    }
-                };
+        let Some((help, active_signature)) = &self.signature_help else {
+            return;
+        };
+        let Some(signature) = help.signatures.get(*active_signature) else {
+            return;
+        };
 
-                if !param_label.is_empty() {
-                    lines.push(format!("> {}", param_label));
-                }
+        let text_style = Style::default().fg(self.theme.popup_text_fg);
+        let active_style = text_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
 
-                // Add parameter documentation if available
-                if let Some(doc) = &param.documentation {
-                    let doc_text = match doc {
-                        lsp_types::Documentation::String(s) => s.clone(),
-                        lsp_types::Documentation::MarkupContent(m) => m.value.clone(),
-                    };
-                    if !doc_text.is_empty() {
-                        lines.push(String::new());
-                        lines.push(doc_text);
-                    }
-                }
+        // Signature label with the active parameter highlighted
+        let active_param = signature
+            .active_parameter
+            .or(help.active_parameter)
+            .unwrap_or(0) as usize;
+        let param = signature
+            .parameters
+            .as_ref()
+            .and_then(|params| params.get(active_param));
+        let param_range = param.and_then(|p| parameter_label_range(&signature.label, &p.label));
+
+        let mut label_line = StyledLine::new();
+        match param_range {
+            Some(range) => {
+                label_line.push(signature.label[..range.start].to_string(), text_style);
+                label_line.push(signature.label[range.clone()].to_string(), active_style);
+                label_line.push(signature.label[range.end..].to_string(), text_style);
             }
+            None => label_line.push(signature.label.clone(), text_style),
         }
+        let mut lines = vec![label_line];
 
-        // Add function documentation if available
-        if let Some(doc) = &signature.documentation {
-            let doc_text = match doc {
-                lsp_types::Documentation::String(s) => s.clone(),
-                lsp_types::Documentation::MarkupContent(m) => m.value.clone(),
-            };
-            if !doc_text.is_empty() {
-                if lines.len() > 1 {
-                    lines.push(String::new());
-                    lines.push("---".to_string());
+        let registry = Some(self.grammar_registry.as_ref());
+        let push_doc = |lines: &mut Vec<StyledLine>, doc: &lsp_types::Documentation| {
+            let doc_lines = match doc {
+                lsp_types::Documentation::String(s) => parse_markdown(s, &self.theme, None),
+                lsp_types::Documentation::MarkupContent(m) => {
+                    parse_markdown(&m.value, &self.theme, registry)
                 }
-                lines.push(doc_text);
+            };
+            if !doc_lines.is_empty() {
+                lines.push(StyledLine::new());
+                lines.extend(doc_lines);
             }
+        };
+        if let Some(doc) = param.and_then(|p| p.documentation.as_ref()) {
+            push_doc(&mut lines, doc);
+        }
+        if let Some(doc) = &signature.documentation {
+            push_doc(&mut lines, doc);
         }
 
-        // Create a popup with the signature help
-        use crate::view::popup::{Popup, PopupPosition};
-        use ratatui::style::Style;
+        let title = if help.signatures.len() > 1 {
+            t!(
+                "lsp.popup_signature_overload",
+                current = active_signature + 1,
+                total = help.signatures.len()
+            )
+            .to_string()
+        } else {
+            t!("lsp.popup_signature").to_string()
+        };
 
-        let mut popup = Popup::text(lines, &self.theme);
-        popup.title = Some(t!("lsp.popup_signature").to_string());
-        popup.transient = true;
-        popup.position = PopupPosition::BelowCursor;
-        popup.width = 60;
-        popup.max_height = 10;
-        popup.border_style = Style::default().fg(self.theme.popup_border_fg);
-        popup.background_style = Style::default().bg(self.theme.popup_bg);
+        let mut popup = Popup::text(Vec::new(), &self.theme)
+            .with_kind(PopupKind::SignatureHelp)
+            .with_title(title)
+            .with_transient(true)
+            .with_position(PopupPosition::BelowCursor)
+            .with_width(60)
+            .with_max_height(10);
+        popup.content = PopupContent::Markdown(lines);
 
-        // Show the popup
         if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            if state.popups.is_signature_help_popup() {
+                state.popups.hide();
+            }
             state.popups.show(popup);
-            tracing::info!(
-                "Showing signature help popup for {} signatures",
-                signature_help.signatures.len()
-            );
         }
     }
 
@@ -1248,6 +1606,33 @@ impl Editor {
             return Ok(());
         }
 
+        // A single reference is jumped to directly
+        if locations.len() == 1 {
+            self.pending_references_symbol.clear();
+            if let Some(path) = self.jump_to_lsp_location(&locations[0]) {
+                let line = locations[0].range.start.line as usize + 1;
+                self.set_status_message(
+                    t!(
+                        "lsp.jumped_to_location",
+                        path = path.display().to_string(),
+                        line = line
+                    )
+                    .to_string(),
+                );
+            }
+            return Ok(());
+        }
+
+        // Without a plugin to show the references panel, use the built-in picker
+        if !self.plugin_manager.has_hook_handlers("lsp_references") {
+            let symbol = std::mem::take(&mut self.pending_references_symbol);
+            self.show_location_picker(
+                t!("lsp.pick_reference", symbol = &symbol).to_string(),
+                locations,
+            );
+            return Ok(());
+        }
+
         // Convert locations to hook args format
         let lsp_locations: Vec<crate::services::plugins::hooks::LspLocation> = locations
             .iter()
@@ -1292,6 +1677,236 @@ impl Editor {
         Ok(())
     }
 
+    /// Request LSP formatting for the active buffer.
+    ///
+    /// With `range` set, only the primary selection is formatted. With
+    /// `save_after` set, the buffer is written once the edits are applied.
+    /// Returns true if a request was sent.
+    pub(crate) fn request_formatting(&mut self, range: bool, save_after: bool) -> bool {
+        let buffer_id = self.active_buffer();
+        let selection = self.active_cursors().primary().selection_range();
+        let state = self.active_state();
+        let lsp_range = match (range, selection) {
+            (false, _) => None,
+            (true, Some(selection)) if !selection.is_empty() => {
+                let (start_line, start_char) =
+                    state.buffer.position_to_lsp_position(selection.start);
+                let (end_line, end_char) = state.buffer.position_to_lsp_position(selection.end);
+                Some(lsp_types::Range {
+                    start: lsp_types::Position::new(start_line as u32, start_char as u32),
+                    end: lsp_types::Position::new(end_line as u32, end_char as u32),
+                })
+            }
+            (true, _) => {
+                self.set_status_message(t!("lsp.format_no_selection").to_string());
+                return false;
+            }
+        };
+        let options = lsp_types::FormattingOptions {
+            tab_size: state.buffer_settings.tab_size as u32,
+            insert_spaces: !state.buffer_settings.use_tabs,
+            ..Default::default()
+        };
+        let version = state.buffer.version();
+        let remaining_servers = self
+            .lsp
+            .as_ref()
+            .map(|lsp| lsp.additional_server_keys(&state.language).to_vec())
+            .unwrap_or_default();
+        let request_id = self.next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, |handle, uri, _language| {
+                handle
+                    .formatting(request_id, uri.clone(), lsp_range, options.clone())
+                    .is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.pending_formatting_request = Some(FormattingRequest {
+                request_id,
+                buffer_id,
+                version,
+                range,
+                save_after,
+                lsp_range,
+                options,
+                remaining_servers,
+            });
+            self.lsp_status = "LSP: formatting...".to_string();
+        } else if !save_after {
+            if !range && self.has_external_formatter() {
+                self.format_with_external_formatter();
+            } else {
+                self.set_status_message(t!("lsp.no_server_active").to_string());
+            }
+        }
+        sent
+    }
+
+    /// Whether the active buffer's language has a formatter command configured
+    fn has_external_formatter(&self) -> bool {
+        let language = &self.active_state().language;
+        self.config
+            .languages
+            .get(language)
+            .is_some_and(|lc| lc.formatter.is_some())
+    }
+
+    /// Format the active buffer with its configured formatter command, for
+    /// when the language server can't format it
+    fn format_with_external_formatter(&mut self) {
+        if let Err(e) = self.format_buffer() {
+            self.set_status_message(t!("error.format_failed", error = e.to_string()).to_string());
+        }
+    }
+
+    /// Send a formatting request instead of saving right away when the active
+    /// buffer's language formats on save through its language server.
+    ///
+    /// Returns true if the save was deferred until the formatting response.
+    pub(crate) fn request_format_on_save(&mut self) -> bool {
+        let language = &self.active_state().language;
+        let formats_with_lsp = self
+            .config
+            .languages
+            .get(language)
+            .is_some_and(|lc| lc.format_on_save && lc.formatter.is_none());
+        formats_with_lsp && self.request_formatting(false, true)
+    }
+
+    /// Handle LSP formatting response
+    pub(crate) fn handle_formatting_response(
+        &mut self,
+        request_id: u64,
+        result: Result<Vec<lsp_types::TextEdit>, String>,
+    ) {
+        let Some(pending) = self
+            .pending_formatting_request
+            .take_if(|p| p.request_id == request_id)
+        else {
+            tracing::debug!("Ignoring stale formatting response: {}", request_id);
+            return;
+        };
+        // The first server that supports formatting does the formatting
+        if result.as_ref().is_err_and(|e| e == "unsupported")
+            && self.send_formatting_to_next_server(pending.clone())
+        {
+            return;
+        }
+        let FormattingRequest {
+            buffer_id,
+            version,
+            range,
+            save_after,
+            ..
+        } = pending;
+        self.lsp_status.clear();
+
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        // Edits are only meaningful against the text they were computed for
+        let unchanged = state.buffer.version() == version;
+
+        match result {
+            Ok(edits) if unchanged => match self.apply_lsp_text_edits(buffer_id, edits) {
+                Ok(count) if !save_after => {
+                    self.set_status_message(t!("lsp.formatted", count = count).to_string());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.set_status_message(
+                        t!("lsp.format_failed", error = e.to_string()).to_string(),
+                    );
+                }
+            },
+            Ok(_) => {
+                if !save_after {
+                    self.set_status_message(t!("lsp.format_cancelled").to_string());
+                }
+            }
+            Err(e) => {
+                // Saving still goes ahead; only an explicit format reports why
+                // nothing changed
+                if !save_after {
+                    if e != "unsupported" {
+                        self.set_status_message(t!("lsp.format_failed", error = e).to_string());
+                    } else if !range
+                        && self.active_buffer() == buffer_id
+                        && self.has_external_formatter()
+                    {
+                        self.format_with_external_formatter();
+                    } else {
+                        self.set_status_message(t!("lsp.format_unsupported").to_string());
+                    }
+                }
+            }
+        }
+
+        if save_after {
+            self.save_formatted_buffer(buffer_id);
+        }
+    }
+
+    /// Send a formatting request on to the next additional server of the
+    /// buffer's language. Returns false if no server was left to ask.
+    fn send_formatting_to_next_server(&mut self, mut pending: FormattingRequest) -> bool {
+        while !pending.remaining_servers.is_empty() {
+            let server = pending.remaining_servers.remove(0);
+            let request_id = self.next_lsp_request_id;
+            let sent = self
+                .with_additional_lsp_for_buffer(pending.buffer_id, true, |key, handle, uri| {
+                    key == server
+                        && handle
+                            .formatting(
+                                request_id,
+                                uri.clone(),
+                                pending.lsp_range,
+                                pending.options.clone(),
+                            )
+                            .is_ok()
+                })
+                .into_iter()
+                .any(|(_, sent)| sent);
+            if sent {
+                tracing::debug!("Formatting with additional LSP server {}", server);
+                self.next_lsp_request_id += 1;
+                pending.request_id = request_id;
+                self.pending_formatting_request = Some(pending);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Finish a save that was waiting on format-on-save edits
+    pub(super) fn save_formatted_buffer(&mut self, buffer_id: BufferId) {
+        let result = if self.active_buffer() == buffer_id {
+            self.save()
+        } else {
+            // The user switched buffers while the server was formatting; on-save
+            // actions only run against the active buffer, so skip them
+            let options = self.save_options();
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                return;
+            };
+            let path = state.buffer.file_path().map(|p| p.to_path_buf());
+            state.buffer.set_save_options(options);
+            match state.buffer.save() {
+                Ok(()) => self
+                    .finalize_save_buffer(buffer_id, path, true)
+                    .map(|()| self.set_status_message(t!("status.file_saved").to_string())),
+                Err(e) => Err(e),
+            }
+        };
+        if let Err(e) = result {
+            self.set_status_message(t!("file.save_failed", error = e.to_string()).to_string());
+        }
+    }
+
     /// Apply LSP text edits to a buffer and return the number of changes made.
     /// Edits are sorted in reverse order and applied as a batch.
     pub(crate) fn apply_lsp_text_edits(
@@ -1421,106 +2036,26 @@ impl Editor {
 
                 // Apply the workspace edit
                 let mut total_changes = 0;
-
-                // Handle changes (map of URI -> Vec<TextEdit>)
-                if let Some(changes) = workspace_edit.changes {
-                    for (uri, edits) in changes {
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = match self.open_file(&path) {
-                                Ok(id) => id,
-                                Err(e) => {
-                                    // Check if this is a large file encoding confirmation error
-                                    if let Some(confirmation) = e.downcast_ref::<
-                                        crate::model::buffer::LargeFileEncodingConfirmation,
-                                    >() {
-                                        self.start_large_file_encoding_confirmation(confirmation);
-                                    } else {
-                                        self.set_status_message(
-                                            t!("file.error_opening", error = e.to_string())
-                                                .to_string(),
-                                        );
-                                    }
-                                    return Ok(());
-                                }
-                            };
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
-
-                // Handle document_changes (TextDocumentEdit[])
-                // This is what rust-analyzer sends instead of changes
-                if let Some(document_changes) = workspace_edit.document_changes {
-                    use lsp_types::DocumentChanges;
-
-                    let text_edits = match document_changes {
-                        DocumentChanges::Edits(edits) => edits,
-                        DocumentChanges::Operations(ops) => {
-                            // Extract TextDocumentEdit from operations
-                            ops.into_iter()
-                                .filter_map(|op| {
-                                    if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
-                                        Some(edit)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        }
-                    };
-
-                    for text_doc_edit in text_edits {
-                        let uri = text_doc_edit.text_document.uri;
-
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = match self.open_file(&path) {
-                                Ok(id) => id,
-                                Err(e) => {
-                                    // Check if this is a large file encoding confirmation error
-                                    if let Some(confirmation) = e.downcast_ref::<
-                                        crate::model::buffer::LargeFileEncodingConfirmation,
-                                    >() {
-                                        self.start_large_file_encoding_confirmation(confirmation);
-                                    } else {
-                                        self.set_status_message(
-                                            t!("file.error_opening", error = e.to_string())
-                                                .to_string(),
-                                        );
-                                    }
-                                    return Ok(());
+                for (uri, edits) in super::workspace_edit::text_document_edits(workspace_edit) {
+                    if let Ok(path) = uri_to_path(&uri) {
+                        let buffer_id = match self.open_file(&path) {
+                            Ok(id) => id,
+                            Err(e) => {
+                                // Check if this is a large file encoding confirmation error
+                                if let Some(confirmation) = e.downcast_ref::<
+                                    crate::model::buffer::LargeFileEncodingConfirmation,
+                                >() {
+                                    self.start_large_file_encoding_confirmation(confirmation);
+                                } else {
+                                    self.set_status_message(
+                                        t!("file.error_opening", error = e.to_string())
+                                            .to_string(),
+                                    );
                                 }
-                            };
-
-                            // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
-                            let edits: Vec<lsp_types::TextEdit> = text_doc_edit
-                                .edits
-                                .into_iter()
-                                .map(|one_of| match one_of {
-                                    lsp_types::OneOf::Left(text_edit) => text_edit,
-                                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
-                                })
-                                .collect();
-
-                            // Log the edits for debugging
-                            tracing::info!(
-                                "Applying {} edits from rust-analyzer for {:?}:",
-                                edits.len(),
-                                path
-                            );
-                            for (i, edit) in edits.iter().enumerate() {
-                                tracing::info!(
-                                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
-                                    i,
-                                    edit.range.start.line,
-                                    edit.range.start.character,
-                                    edit.range.end.line,
-                                    edit.range.end.character,
-                                    edit.new_text
-                                );
+                                return Ok(());
                             }
-
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
+                        };
+                        total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
                     }
                 }
 
@@ -1709,6 +2244,7 @@ impl Editor {
         if changes.is_empty() {
             return;
         }
+        self.schedule_idle_lint(buffer_id);
 
         // Check if LSP is enabled for this buffer
         let metadata = match self.buffer_metadata.get(&buffer_id) {
@@ -1771,7 +2307,15 @@ impl Editor {
             return;
         }
 
+        // Additional servers get the same changes (or didOpen with the current text)
+        self.with_additional_lsp_for_buffer(buffer_id, false, |_, handle, uri| {
+            if let Err(e) = handle.did_change(uri.clone(), changes.clone()) {
+                tracing::warn!("Failed to send didChange to additional LSP server: {}", e);
+            }
+        });
+
         // Get handle ID (handle exists since try_spawn succeeded)
+        let Some(lsp) = self.lsp.as_mut() else { return };
         let Some(handle) = lsp.get_handle_mut(&language) else {
             return;
         };
@@ -2287,6 +2831,32 @@ impl Editor {
     }
 }
 
+/// Byte range of a signature help parameter within its signature label.
+/// Label offsets are in UTF-16 code units, as the LSP spec requires.
+fn parameter_label_range(label: &str, param: &lsp_types::ParameterLabel) -> Option<Range<usize>> {
+    match param {
+        lsp_types::ParameterLabel::Simple(s) if !s.is_empty() => {
+            let start = label.find(s.as_str())?;
+            Some(start..start + s.len())
+        }
+        lsp_types::ParameterLabel::Simple(_) => None,
+        lsp_types::ParameterLabel::LabelOffsets([start, end]) => {
+            let to_byte = |utf16: u32| {
+                let mut units = 0u32;
+                for (byte, ch) in label.char_indices() {
+                    if units >= utf16 {
+                        return Some(byte);
+                    }
+                    units += ch.len_utf16() as u32;
+                }
+                (units >= utf16).then_some(label.len())
+            };
+            let (start, end) = (to_byte(*start)?, to_byte(*end)?);
+            (start < end).then_some(start..end)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::model::filesystem::StdFileSystem;
@@ -2381,4 +2951,31 @@ mod tests {
 
         assert!(state.virtual_texts.is_empty());
     }
+
+    #[test]
+    fn test_parameter_label_range() {
+        use super::parameter_label_range;
+        use lsp_types::ParameterLabel;
+
+        let label = "add(a: i32, b: i32)";
+        assert_eq!(
+            parameter_label_range(label, &ParameterLabel::Simple("b: i32".to_string())),
+            Some(12..18)
+        );
+        assert_eq!(
+            parameter_label_range(label, &ParameterLabel::LabelOffsets([4, 10])),
+            Some(4..10)
+        );
+        assert_eq!(
+            parameter_label_range(label, &ParameterLabel::LabelOffsets([4, 99])),
+            None
+        );
+
+        // Offsets are UTF-16 code units, not bytes
+        let label = "f(é: u8, x: u8)";
+        assert_eq!(
+            parameter_label_range(label, &ParameterLabel::LabelOffsets([9, 14])),
+            Some(10..15)
+        );
+    }
 }
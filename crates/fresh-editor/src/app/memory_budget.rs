@@ -0,0 +1,346 @@
+//! Memory budget for open files and the Buffers panel
+//!
+//! When the content of open files adds up to more than
+//! `editor.memory_budget_mb`, the content of unmodified files that were shown
+//! least recently is dropped from memory. Their cursors, undo history and line
+//! counts are kept, and the content is read back from disk when the buffer is
+//! shown or edited again. If the file changed on disk in the meantime, the
+//! buffer is reloaded as it would be after any external change.
+//!
+//! "Show Buffers" lists the open buffers with their size and the memory they
+//! use. Keys in the panel open the buffer under the cursor or unload it.
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::input::buffer_mode::BUFFERS_MODE;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use rust_i18n::t;
+use std::time::{Duration, Instant};
+
+/// How often the memory of open files is checked against the budget
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Name of the read-only buffer listing the open buffers
+pub const BUFFERS_BUFFER_NAME: &str = "*Buffers*";
+
+/// Text property holding the id of the buffer a line of the panel shows
+const BUFFER_PROPERTY: &str = "buffer_id";
+
+impl Editor {
+    /// The memory budget in bytes, or None when there is no budget
+    fn memory_budget(&self) -> Option<usize> {
+        let mb = self.config.editor.memory_budget_mb;
+        (mb > 0).then(|| (mb as usize).saturating_mul(1024 * 1024))
+    }
+
+    /// Memory used by the content of all open buffers
+    fn buffers_memory_usage(&self) -> usize {
+        self.buffers
+            .values()
+            .map(|state| state.buffer.memory_usage())
+            .sum()
+    }
+
+    /// Buffers shown in a split
+    fn shown_buffers(&self) -> Vec<BufferId> {
+        self.split_view_states
+            .keys()
+            .filter_map(|split| self.split_manager.get_buffer_id(*split))
+            .collect()
+    }
+
+    /// Whether the content of a buffer may be dropped from memory
+    ///
+    /// Terminals, buffers still loading and the sources of composite views
+    /// (which are drawn without being shown in a split) are kept.
+    fn can_unload_buffer(&self, buffer_id: BufferId) -> bool {
+        !self.is_terminal_buffer(buffer_id)
+            && self
+                .buffer_metadata
+                .get(&buffer_id)
+                .is_some_and(|m| !m.hidden_from_tabs && !m.loading)
+            && self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|state| state.buffer.can_unload())
+    }
+
+    /// Unload the least recently shown buffers while open files use more
+    /// memory than the budget
+    ///
+    /// Checked at most once a second. Returns true if anything was unloaded.
+    pub fn check_memory_budget(&mut self) -> bool {
+        let now = self.time_source.now();
+        if now.saturating_duration_since(self.last_memory_check) < MEMORY_CHECK_INTERVAL {
+            return false;
+        }
+        self.last_memory_check = now;
+        self.enforce_memory_budget() > 0
+    }
+
+    /// Unload buffers, least recently shown first, until open files use no
+    /// more memory than the budget. Returns the number of buffers unloaded.
+    pub fn enforce_memory_budget(&mut self) -> usize {
+        let Some(budget) = self.memory_budget() else {
+            return 0;
+        };
+        let mut used = self.buffers_memory_usage();
+        if used <= budget {
+            return 0;
+        }
+
+        let shown = self.shown_buffers();
+        let mut candidates: Vec<(Option<Instant>, BufferId)> = self
+            .buffers
+            .keys()
+            .filter(|id| !shown.contains(id) && self.can_unload_buffer(**id))
+            .map(|id| (self.buffer_last_shown.get(id).copied(), *id))
+            .collect();
+        // Buffers never shown (e.g. opened from the command line) go first
+        candidates.sort_by_key(|(last_shown, _)| *last_shown);
+
+        let mut unloaded = 0;
+        for (_, buffer_id) in candidates {
+            if used <= budget {
+                break;
+            }
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            let freed = state.buffer.memory_usage();
+            if state.buffer.unload() {
+                used = used.saturating_sub(freed);
+                unloaded += 1;
+            }
+        }
+        if unloaded > 0 {
+            tracing::info!(
+                "Unloaded {} buffer(s) over the memory budget, {} in use",
+                unloaded,
+                format_size(used as u64)
+            );
+            self.refresh_buffers_panel();
+        }
+        unloaded
+    }
+
+    /// Read back the unloaded content of the buffers about to be drawn, and
+    /// note that they were shown
+    pub(super) fn load_shown_buffers(&mut self) {
+        let now = self.time_source.now();
+        for buffer_id in self.shown_buffers() {
+            self.buffer_last_shown.insert(buffer_id, now);
+            self.ensure_buffer_loaded(buffer_id);
+        }
+    }
+
+    /// Read the content of a buffer back from its file if it was unloaded
+    pub(super) fn ensure_buffer_loaded(&mut self, buffer_id: BufferId) {
+        if !self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.buffer.is_unloaded())
+        {
+            return;
+        }
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            return;
+        };
+
+        // A file written since it was loaded may still have the same size
+        // and line count, so compare its modification time too
+        let changed_on_disk = match (
+            self.file_mod_times.get(&path),
+            self.filesystem
+                .metadata(&path)
+                .ok()
+                .and_then(|metadata| metadata.modified),
+        ) {
+            (Some(known), Some(current)) => *known != current,
+            _ => false,
+        };
+        let result = if changed_on_disk {
+            Ok(false)
+        } else {
+            self.buffers
+                .get_mut(&buffer_id)
+                .map_or(Ok(true), |state| state.buffer.reload())
+        };
+
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                if let Err(e) = self.revert_buffer_by_id(buffer_id, &path) {
+                    tracing::error!("Failed to reload {}: {}", path.display(), e);
+                }
+                let name = self
+                    .buffer_metadata
+                    .get(&buffer_id)
+                    .map(|m| m.display_name.clone())
+                    .unwrap_or_default();
+                self.set_status_message(t!("buffers.reloaded_changed", name = name).to_string());
+            }
+            Err(e) => {
+                tracing::error!("Failed to read unloaded {} back: {}", path.display(), e);
+                self.set_status_message(
+                    t!(
+                        "buffers.read_back_failed",
+                        path = path.display().to_string(),
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+            }
+        }
+    }
+
+    /// Open (or refresh) the Buffers panel
+    pub fn show_buffers(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(BUFFERS_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    BUFFERS_BUFFER_NAME.to_string(),
+                    BUFFERS_MODE.to_string(),
+                    true,
+                )
+            });
+        self.fill_buffers_panel(buffer_id);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Rebuild the Buffers panel if it is open
+    fn refresh_buffers_panel(&mut self) {
+        if let Some(buffer_id) = self.find_buffer_by_name(BUFFERS_BUFFER_NAME) {
+            self.fill_buffers_panel(buffer_id);
+        }
+    }
+
+    fn fill_buffers_panel(&mut self, panel: BufferId) {
+        let used = self.buffers_memory_usage() as u64;
+        let budget = match self.memory_budget() {
+            Some(budget) => format_size(budget as u64),
+            None => "none".to_string(),
+        };
+        let mut entries = vec![TextPropertyEntry::text(format!(
+            "Buffers\n=======\n\nEnter: open   u: unload   q: close\n\n\
+             Memory: {} in use, budget {}\n\n{:<10} {:<10} {:<9} Name\n",
+            format_size(used),
+            budget,
+            "Memory",
+            "Size",
+            "State"
+        ))];
+
+        let mut ids: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .copied()
+            .filter(|id| {
+                *id != panel
+                    && self
+                        .buffer_metadata
+                        .get(id)
+                        .is_some_and(|m| !m.hidden_from_tabs)
+            })
+            .collect();
+        ids.sort_by_key(|id| id.0);
+        for buffer_id in ids {
+            let (Some(state), Some(metadata)) = (
+                self.buffers.get(&buffer_id),
+                self.buffer_metadata.get(&buffer_id),
+            ) else {
+                continue;
+            };
+            let status = if metadata.loading {
+                "loading"
+            } else if state.buffer.is_modified() {
+                "modified"
+            } else if state.buffer.is_unloaded() {
+                "unloaded"
+            } else {
+                ""
+            };
+            let line = format!(
+                "{:<10} {:<10} {:<9} {}\n",
+                format_size(state.buffer.memory_usage() as u64),
+                format_size(state.buffer.len() as u64),
+                status,
+                metadata.display_name
+            );
+            entries.push(
+                TextPropertyEntry::text(line)
+                    .with_property(BUFFER_PROPERTY, serde_json::Value::from(buffer_id.0)),
+            );
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(panel, entries) {
+            tracing::error!("Failed to fill Buffers panel: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&panel) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+    }
+
+    /// Buffer of the panel line under the cursor, telling the user if there
+    /// is none
+    fn buffers_panel_buffer_at_cursor(&mut self) -> Option<BufferId> {
+        let buffer_id =
+            if self.find_buffer_by_name(BUFFERS_BUFFER_NAME) == Some(self.active_buffer()) {
+                self.get_text_properties_at_cursor().and_then(|props| {
+                    props.into_iter().find_map(|prop| {
+                        prop.get(BUFFER_PROPERTY)?
+                            .as_u64()
+                            .map(|id| BufferId(id as usize))
+                    })
+                })
+            } else {
+                None
+            }
+            .filter(|id| self.buffers.contains_key(id));
+        if buffer_id.is_none() {
+            self.set_status_message(t!("buffers.no_buffer").to_string());
+        }
+        buffer_id
+    }
+
+    /// Switch to the buffer under the cursor in the Buffers panel
+    pub fn buffers_panel_open(&mut self) {
+        if let Some(buffer_id) = self.buffers_panel_buffer_at_cursor() {
+            self.set_active_buffer(buffer_id);
+        }
+    }
+
+    /// Drop the content of the buffer under the cursor in the Buffers panel
+    /// from memory
+    pub fn buffers_panel_unload(&mut self) {
+        let Some(buffer_id) = self.buffers_panel_buffer_at_cursor() else {
+            return;
+        };
+        let name = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default();
+        let unloaded = self.can_unload_buffer(buffer_id)
+            && !self.shown_buffers().contains(&buffer_id)
+            && self
+                .buffers
+                .get_mut(&buffer_id)
+                .is_some_and(|state| state.buffer.unload());
+        if unloaded {
+            self.refresh_buffers_panel();
+            self.set_status_message(t!("buffers.unloaded", name = name).to_string());
+        } else {
+            self.set_status_message(t!("buffers.cannot_unload", name = name).to_string());
+        }
+    }
+}
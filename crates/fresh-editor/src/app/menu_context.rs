@@ -29,6 +29,10 @@ impl Editor {
         // File explorer state
         let show_hidden = self.is_file_explorer_showing_hidden();
         let show_gitignored = self.is_file_explorer_showing_gitignored();
+        let show_changed_only = self
+            .file_explorer
+            .as_ref()
+            .is_some_and(|fe| fe.show_only_changed());
 
         // Language-dependent context values
         let lsp_available = self.is_lsp_available();
@@ -55,6 +59,10 @@ impl Editor {
             .set(context_keys::LSP_AVAILABLE, lsp_available)
             .set(context_keys::FILE_EXPLORER_SHOW_HIDDEN, show_hidden)
             .set(context_keys::FILE_EXPLORER_SHOW_GITIGNORED, show_gitignored)
+            .set(
+                context_keys::FILE_EXPLORER_SHOW_CHANGED_ONLY,
+                show_changed_only,
+            )
             .set(context_keys::HAS_SELECTION, has_selection)
             .set(context_keys::MENU_BAR, menu_bar)
             .set(context_keys::FORMATTER_AVAILABLE, formatter_available)
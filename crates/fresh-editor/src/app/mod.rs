@@ -1,45 +1,92 @@
+mod action_console;
 mod async_messages;
+mod background_loading;
+mod batch;
+mod before_save;
 mod buffer_management;
 mod calibration_actions;
 pub mod calibration_wizard;
 mod clipboard;
+mod collab;
+mod completion_providers;
 mod composite_buffer_actions;
+mod control_socket;
+mod damage;
+mod debugger;
+mod diff_mode;
+mod editorconfig;
 pub mod event_debug;
 mod event_debug_actions;
 mod file_explorer;
 pub mod file_open;
 mod file_open_input;
 mod file_operations;
+mod file_templates;
+mod file_under_cursor;
+mod git_actions;
+mod git_diff_view;
+mod global_command;
+mod health;
 mod help;
+pub mod history_scrubber;
+mod image_preview;
+mod increment;
+mod indentation;
 mod input;
 mod input_dispatch;
+mod keybinding_conflicts;
 pub mod keybinding_editor;
 mod keybinding_editor_actions;
+mod keyboard_shortcuts;
+mod links;
+mod linters;
+mod local_history;
+mod log_view;
 mod lsp_actions;
+mod lsp_info;
 mod lsp_requests;
+mod memory_budget;
 mod menu_actions;
 mod menu_context;
 mod mouse_input;
 mod on_save_actions;
+mod pager;
+pub mod perf_hud;
 mod plugin_commands;
+mod plugin_console;
+mod plugin_timers;
 mod popup_actions;
+mod project_search;
 mod prompt_actions;
 mod recovery_actions;
 mod regex_replace;
 mod render;
+mod repeat_edit;
+mod repl;
+mod replay;
+mod selection_stats;
 mod settings_actions;
 mod shell_command;
 mod split_actions;
+mod status_segments;
 mod tab_drag;
 mod terminal;
+mod terminal_commands;
 mod terminal_input;
 mod terminal_mouse;
+mod terminal_panel;
 mod toggle_actions;
+mod tutor;
 pub mod types;
 mod undo_actions;
+mod unicode_picker;
+mod unsaved_changes;
+mod user_commands;
 mod view_actions;
+mod wakeups;
 pub mod warning_domains;
 pub mod workspace;
+mod workspace_edit;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -78,7 +125,7 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
 }
 
 use self::types::{
-    Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
+    Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LastEdit, LspMessageEntry,
     LspProgressInfo, MacroRecordingState, MouseState, SearchState, TabContextMenu,
     DEFAULT_BACKGROUND_FILE,
 };
@@ -88,6 +135,7 @@ use crate::input::actions::action_to_events as convert_action_to_events;
 use crate::input::buffer_mode::ModeRegistry;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Suggestion;
+use crate::input::frecency::Frecency;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::position_history::PositionHistory;
 use crate::input::quick_open::{
@@ -99,6 +147,8 @@ use crate::model::filesystem::FileSystem;
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
 use crate::services::fs::FsManager;
 use crate::services::lsp::manager::LspManager;
+use crate::services::perf;
+use crate::services::plugins::hooks::TextChange;
 use crate::services::plugins::PluginManager;
 use crate::services::recovery::{RecoveryConfig, RecoveryService};
 use crate::services::time_source::{RealTimeSource, SharedTimeSource};
@@ -111,12 +161,14 @@ use crate::view::split::{SplitManager, SplitViewState};
 use crate::view::ui::{
     FileExplorerRenderer, SplitRenderer, StatusBarRenderer, SuggestionsRenderer,
 };
+use background_loading::DeferredPlugin;
 use crossterm::event::{KeyCode, KeyModifiers};
 #[cfg(feature = "plugins")]
 use fresh_core::api::BufferSavedDiff;
 #[cfg(feature = "plugins")]
 use fresh_core::api::JsCallbackId;
 use fresh_core::api::PluginCommand;
+use fresh_core::api::PluginLogLevel;
 use lsp_types::{Position, Range as LspRange, TextDocumentContentChangeEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
@@ -128,6 +180,8 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
+pub use self::batch::BatchOutcome;
+pub use self::damage::Damage;
 // Re-export BufferId from event module for backward compatibility
 pub use self::types::{BufferKind, BufferMetadata, HoverTarget};
 pub use self::warning_domains::{
@@ -145,6 +199,14 @@ fn uri_to_path(uri: &lsp_types::Uri) -> Result<PathBuf, String> {
         .map_err(|_| "URI is not a file path".to_string())
 }
 
+/// Load saved frecency data, starting empty if the file can't be read
+fn load_frecency(path: &Path, name: &str) -> Frecency {
+    Frecency::load_from_file(path).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load {} frecency: {}", name, e);
+        Frecency::new()
+    })
+}
+
 /// A pending grammar registration waiting for reload_grammars() to apply
 #[derive(Clone, Debug)]
 pub struct PendingGrammar {
@@ -166,6 +228,22 @@ struct SemanticTokenRangeRequest {
     end_line: usize,
 }
 
+/// Track an in-flight LSP formatting request.
+#[derive(Clone, Debug)]
+struct FormattingRequest {
+    request_id: u64,
+    buffer_id: BufferId,
+    version: u64,
+    /// Only the selection was sent for formatting
+    range: bool,
+    /// Write the buffer once the edits are applied (format-on-save)
+    save_after: bool,
+    lsp_range: Option<lsp_types::Range>,
+    options: lsp_types::FormattingOptions,
+    /// Additional servers to ask, in order, if the current one can't format
+    remaining_servers: Vec<String>,
+}
+
 #[derive(Clone, Copy, Debug)]
 enum SemanticTokensFullRequestKind {
     Full,
@@ -208,12 +286,21 @@ pub struct Editor {
     /// Pending grammars registered by plugins, waiting for reload_grammars() to apply
     pending_grammars: Vec<PendingGrammar>,
 
+    /// Plugin packages waiting for one of their activation events
+    deferred_plugins: Vec<DeferredPlugin>,
+
+    /// Background plugin loads not finished yet
+    plugin_loads_pending: usize,
+
     /// Active theme
     theme: crate::view::theme::Theme,
 
     /// All loaded themes (embedded + user)
     theme_registry: crate::view::theme::ThemeRegistry,
 
+    /// Modification times of user theme files, used to hot-reload themes on change
+    theme_file_stamps: Vec<(PathBuf, std::time::SystemTime)>,
+
     /// Optional ANSI background image
     ansi_background: Option<crate::primitives::ansi_background::AnsiBackground>,
 
@@ -255,6 +342,9 @@ pub struct Editor {
     /// Plugin-provided status message (displayed alongside the core status)
     plugin_status_message: Option<String>,
 
+    /// Status bar segments registered by plugins, in display order
+    status_segments: Vec<status_segments::StatusSegment>,
+
     /// Accumulated plugin errors (for test assertions)
     /// These are collected when plugin error messages are received
     plugin_errors: Vec<String>,
@@ -275,6 +365,11 @@ pub struct Editor {
     /// Buffer mode registry (for buffer-local keybindings)
     mode_registry: ModeRegistry,
 
+    /// Background jobs (git, large searches and file loads, plugin processes).
+    /// Declared before the runtime so its jobs are cancelled before the
+    /// runtime waits for them on drop.
+    jobs: crate::services::jobs::JobPool,
+
     /// Tokio runtime for async I/O tasks
     tokio_runtime: Option<tokio::runtime::Runtime>,
 
@@ -331,6 +426,9 @@ pub struct Editor {
     /// Pending show_gitignored setting to apply when file explorer is initialized (from session restore)
     pending_file_explorer_show_gitignored: Option<bool>,
 
+    /// Reveal the active file once the file explorer finishes initializing
+    pending_file_explorer_reveal: bool,
+
     /// File explorer decorations by namespace
     file_explorer_decorations: HashMap<String, Vec<crate::view::file_tree::FileExplorerDecoration>>,
 
@@ -361,6 +459,33 @@ pub struct Editor {
     /// Whether GPM is being used for mouse input (requires software cursor)
     gpm_active: bool,
 
+    /// Open every file in read-only mode (`--readonly`)
+    open_read_only: bool,
+
+    /// Running as a pager (`--pager`): less-like keys in the editor area
+    pager_mode: bool,
+
+    /// Pager follow mode: keep the cursor at the end of growing stdin input
+    pager_follow: bool,
+
+    /// Writes inputs to the event log (`--event-log`)
+    input_recorder: Option<crate::input::replay::InputRecorder>,
+
+    /// Recorded session being replayed (`--replay`)
+    replay: Option<replay::ReplayState>,
+
+    /// History scrubber previewing the active buffer's edit history
+    history_scrubber: Option<history_scrubber::HistoryScrubber>,
+
+    /// Timers started by plugins (setTimeout/setInterval)
+    plugin_timers: plugin_timers::PluginTimers,
+
+    /// Save held until plugin before_file_save handlers answer
+    pending_before_save: Option<before_save::PendingBeforeSave>,
+
+    /// ID for the next before_file_save round
+    next_before_save_id: u64,
+
     /// Current keybinding context
     key_context: KeyContext,
 
@@ -385,6 +510,16 @@ pub struct Editor {
     /// Pending LSP completion request ID (if any)
     pending_completion_request: Option<u64>,
 
+    /// Requests of the pending completion, one per language server (main
+    /// server first), with the items each server returned so far
+    completion_responses: Vec<(u64, Option<Vec<lsp_types::CompletionItem>>)>,
+
+    /// Completion providers registered by plugins
+    completion_providers: Vec<completion_providers::CompletionProvider>,
+
+    /// Source labels of the pending completion's plugin requests
+    completion_sources: HashMap<u64, String>,
+
     /// Original LSP completion items (for type-to-filter)
     /// Stored when completion popup is shown, used for re-filtering as user types
     completion_items: Option<Vec<lsp_types::CompletionItem>>,
@@ -393,8 +528,11 @@ pub struct Editor {
     /// When Some, completion will be triggered when this instant is reached
     scheduled_completion_trigger: Option<Instant>,
 
-    /// Pending LSP go-to-definition request ID (if any)
-    pending_goto_definition_request: Option<u64>,
+    /// Pending LSP go-to-definition/type definition/implementation request (if any)
+    pending_goto_definition_request: Option<(u64, crate::services::lsp::async_handler::GotoTarget)>,
+
+    /// Locations offered by the open location picker (indexed by suggestion value)
+    location_picker_choices: Vec<lsp_types::Location>,
 
     /// Pending LSP hover request ID (if any)
     pending_hover_request: Option<u64>,
@@ -405,9 +543,22 @@ pub struct Editor {
     /// Symbol name for pending references request
     pending_references_symbol: String,
 
+    /// Pending LSP document highlight request: (request ID, buffer, buffer version)
+    pending_document_highlight_request: Option<(u64, BufferId, u64)>,
+
     /// Pending LSP signature help request ID (if any)
     pending_signature_help_request: Option<u64>,
 
+    /// Pending LSP formatting request
+    pending_formatting_request: Option<FormattingRequest>,
+
+    /// Signature help being displayed and the index of the shown signature
+    signature_help: Option<(lsp_types::SignatureHelp, usize)>,
+
+    /// Set when the current key press dismissed the signature help popup,
+    /// so typing inside the call re-requests it
+    signature_help_dismissed_by_key: bool,
+
     /// Pending LSP code actions request ID (if any)
     pending_code_actions_request: Option<u64>,
 
@@ -458,6 +609,9 @@ pub struct Editor {
     /// Pending search range that should be reused when the next search is confirmed
     pending_search_range: Option<Range<usize>>,
 
+    /// Search still running in the background, and the buffer it searches
+    search_job: Option<(crate::services::jobs::JobId, BufferId)>,
+
     /// Interactive replace state (if interactive replace is active)
     interactive_replace_state: Option<InteractiveReplaceState>,
 
@@ -496,14 +650,18 @@ pub struct Editor {
     /// Maps panel ID (e.g., "diagnostics") to buffer ID
     panel_ids: HashMap<String, BufferId>,
 
-    /// Background process abort handles for cancellation
-    /// Maps process_id to abort handle
-    background_process_handles: HashMap<u64, tokio::task::AbortHandle>,
+    /// Background process jobs for cancellation
+    /// Maps process_id to the job running the process
+    background_process_handles: HashMap<u64, crate::services::jobs::JobId>,
 
     /// Prompt histories keyed by prompt type name (e.g., "search", "replace", "goto_line", "plugin:custom_name")
     /// This provides a generic history system that works for all prompt types including plugin prompts.
     prompt_histories: HashMap<String, crate::input::input_history::InputHistory>,
 
+    /// How often and how recently each picker's entries were chosen
+    /// Key is the frecency name, e.g. "theme", "language", "plugin:custom_name"
+    picker_frecency: HashMap<String, Frecency>,
+
     /// Pending async prompt callback ID (for editor.prompt() API)
     /// When the prompt is confirmed, the callback is resolved with the input text.
     /// When cancelled, the callback is resolved with null.
@@ -512,6 +670,9 @@ pub struct Editor {
     /// LSP progress tracking (token -> progress info)
     lsp_progress: std::collections::HashMap<String, LspProgressInfo>,
 
+    /// Spinner frame last drawn for LSP progress
+    lsp_progress_spinner_frame: usize,
+
     /// LSP server statuses (language -> status)
     lsp_server_statuses:
         std::collections::HashMap<String, crate::services::async_bridge::LspServerStatus>,
@@ -522,6 +683,24 @@ pub struct Editor {
     /// LSP log messages (recent messages from window/logMessage)
     lsp_log_messages: Vec<LspMessageEntry>,
 
+    /// The open LSP server log view, if any
+    lsp_log_view: Option<lsp_info::LspLogView>,
+
+    /// Breakpoints, watches and the active debug session
+    debugger: debugger::DebuggerState,
+
+    /// Plugin console entries and plugin hot-reload state
+    plugin_console: plugin_console::PluginConsoleState,
+
+    /// Recent log events and the filters of the `*Log*` buffer
+    log_view: log_view::LogViewState,
+
+    /// Collaborative editing session
+    collab: collab::CollabState,
+
+    /// Control socket clients send JSON-RPC requests to (`fresh --listen`)
+    control_socket: Option<crate::services::control_socket::ControlSocket>,
+
     /// Diagnostic result IDs per URI (for incremental pull diagnostics)
     /// Maps URI string to last result_id received from server
     diagnostic_result_ids: HashMap<String, String>,
@@ -530,12 +709,34 @@ pub struct Editor {
     /// Maps file URI string to Vec of diagnostics for that file
     stored_diagnostics: HashMap<String, Vec<lsp_types::Diagnostic>>,
 
+    /// Diagnostics per URI from each language server that published them.
+    /// Merged into `stored_diagnostics` when a buffer has several servers.
+    server_diagnostics: HashMap<String, HashMap<String, Vec<lsp_types::Diagnostic>>>,
+
     /// Event broadcaster for control events (observable by external systems)
     event_broadcaster: crate::model::control_event::EventBroadcaster,
 
     /// Bookmarks (character key -> bookmark)
     bookmarks: HashMap<char, Bookmark>,
 
+    /// Named split layouts saved by the user (persisted with the workspace)
+    named_layouts: HashMap<String, crate::workspace::SerializedLayout>,
+
+    /// Bottom terminal panel tabs and height, kept while the panel is hidden
+    terminal_panel: terminal_panel::TerminalPanelState,
+
+    /// REPL terminal used by "Send to REPL", keyed by language ID
+    repl_terminals: HashMap<String, BufferId>,
+
+    /// Images shown in place of buffer contents, and the graphics drawn for them
+    image_previews: image_preview::ImagePreviews,
+
+    /// Hyperlinked cells of the last frame, shared with the terminal backend
+    screen_links: crate::view::hyperlinks::ScreenLinks,
+
+    /// Links opened while testing, instead of starting a browser
+    opened_links: Option<Vec<String>>,
+
     /// Global search options (persist across searches)
     search_case_sensitive: bool,
     search_whole_word: bool,
@@ -555,6 +756,15 @@ pub struct Editor {
     /// Flag to prevent recursive macro playback
     macro_playing: bool,
 
+    /// Last edit, for repeating it
+    last_edit: LastEdit,
+
+    /// Flag set while the last edit is being repeated
+    repeating_edit: bool,
+
+    /// Selection statistics for the status bar
+    selection_stats: selection_stats::SelectionStatsState,
+
     /// Pending plugin action receivers (for async action execution)
     #[cfg(feature = "plugins")]
     pending_plugin_actions: Vec<(
@@ -587,6 +797,13 @@ pub struct Editor {
     /// Last time we polled for directory changes (for file tree refresh)
     last_file_tree_poll: std::time::Instant,
 
+    /// Last time we polled the themes directory for changes (for theme hot-reload)
+    last_theme_poll: std::time::Instant,
+
+    /// Last time we polled plugin files for changes (for plugin hot-reload)
+    #[cfg(feature = "plugins")]
+    last_plugin_poll: std::time::Instant,
+
     /// Last known modification times for open files (for auto-revert)
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -620,6 +837,15 @@ pub struct Editor {
     /// Last persistent auto-save time for rate limiting (disk)
     last_persistent_auto_save: std::time::Instant,
 
+    /// Snapshots of files taken on save and periodically
+    local_history: crate::services::local_history::LocalHistory,
+
+    /// Last periodic local history snapshot time for rate limiting
+    last_local_history_snapshot: std::time::Instant,
+
+    /// Buffer version of each buffer's last local history snapshot
+    local_history_versions: HashMap<BufferId, u64>,
+
     /// Active custom contexts for command visibility
     /// Plugin-defined contexts like "config-editor" that control command availability
     active_custom_contexts: HashSet<String>,
@@ -634,6 +860,22 @@ pub struct Editor {
     /// Status message log path (for viewing full status history)
     status_log_path: Option<PathBuf>,
 
+    /// Startup profile to write when the first frame is drawn (`--profile-startup`)
+    startup_profile: Option<crate::services::startup_profile::StartupProfileHandle>,
+
+    /// Performance HUD, when shown
+    perf_hud: Option<perf_hud::PerfHud>,
+
+    /// Screen damage done by the async messages handled since it was taken
+    async_damage: Damage,
+
+    /// When each buffer was last shown, for unloading the least recently
+    /// shown ones when over the memory budget
+    buffer_last_shown: HashMap<BufferId, Instant>,
+
+    /// When buffer memory was last checked against the budget
+    last_memory_check: Instant,
+
     /// Warning domain registry for extensible warning indicators
     /// Contains LSP warnings, general warnings, and can be extended by plugins
     warning_domains: WarningDomainRegistry,
@@ -694,6 +936,22 @@ pub struct Editor {
     /// Terminal color capability (true color, 256, or 16 colors)
     color_capability: crate::view::color_support::ColorCapability,
 
+    /// Whether the terminal accepted the keyboard enhancement protocol
+    /// (None when not known, e.g. headless)
+    keyboard_enhancement: Option<bool>,
+
+    /// Interactive tutorial and its progress
+    tutor: tutor::TutorState,
+
+    /// Keybinding conflicts found by the last check
+    keybinding_conflicts: keybinding_conflicts::ConflictCheck,
+
+    /// Search listed in the `*Search Results*` buffer
+    project_search: project_search::ProjectSearchState,
+
+    /// Runs of the configured linters
+    linting: linters::LintState,
+
     /// Hunks for the Review Diff tool
     review_hunks: Vec<fresh_core::api::ReviewHunk>,
 
@@ -710,11 +968,36 @@ pub struct Editor {
     composite_view_states:
         HashMap<(SplitId, BufferId), crate::view::composite_view::CompositeViewState>,
 
+    /// Built-in git diff views: composite buffer -> the hidden snapshot buffers it shows
+    /// (closed together with the composite)
+    git_diff_views: HashMap<BufferId, [BufferId; 2]>,
+
+    /// `--diff`/`--merge` sessions: composite buffer -> the file buffers it shows
+    diff_sessions: HashMap<BufferId, diff_mode::DiffSession>,
+
+    /// Views of a buffer's unsaved changes: view buffer -> what it shows
+    unsaved_changes_views: HashMap<BufferId, unsaved_changes::UnsavedChangesView>,
+
+    /// Output file of a `--merge` session (decides the process exit code)
+    merge_output: Option<std::path::PathBuf>,
+
+    /// Background git process pool (status polling, fetch/pull/push, log)
+    git_pool: crate::services::git::GitPool,
+
+    /// Latest git branch/dirty state for the status bar (None outside a repository)
+    git_status: Option<crate::services::git::GitStatus>,
+
+    /// Last time we started a background `git status` refresh
+    last_git_status_poll: Option<std::time::Instant>,
+
     /// Pending file opens from CLI arguments (processed after TUI starts)
     /// This allows CLI files to go through the same code path as interactive file opens,
     /// ensuring consistent error handling (e.g., encoding confirmation prompts).
     pending_file_opens: Vec<PendingFileOpen>,
 
+    /// Files to wait on (`--wait`): quit once all of them have been closed
+    wait_for_files: Vec<PathBuf>,
+
     /// Stdin streaming state (if reading from stdin)
     stdin_streaming: Option<StdinStreamingState>,
 }
@@ -728,6 +1011,8 @@ pub struct PendingFileOpen {
     pub line: Option<usize>,
     /// Column number to navigate to (1-indexed, optional)
     pub column: Option<usize>,
+    /// Open in a new split in this direction instead of the active one (`-o`/`-O`)
+    pub split: Option<SplitDirection>,
 }
 
 /// State for tracking stdin streaming in background
@@ -780,9 +1065,10 @@ impl Editor {
         color_capability: crate::view::color_support::ColorCapability,
         filesystem: Arc<dyn FileSystem + Send + Sync>,
     ) -> AnyhowResult<Self> {
-        let grammar_registry =
-            crate::primitives::grammar::GrammarRegistry::for_editor(dir_context.config_dir.clone());
-        Self::with_options(
+        // Grammars and plugins load in the background, so the first frame
+        // doesn't wait for them
+        let config_dir = dir_context.config_dir.clone();
+        let mut editor = Self::with_options(
             config,
             width,
             height,
@@ -792,8 +1078,11 @@ impl Editor {
             dir_context,
             None,
             color_capability,
-            grammar_registry,
-        )
+            crate::primitives::grammar::GrammarRegistry::loading(),
+            true,
+        )?;
+        editor.load_grammars_in_background(config_dir);
+        Ok(editor)
     }
 
     /// Create a new editor for testing with custom backends
@@ -825,12 +1114,16 @@ impl Editor {
             time_source,
             color_capability,
             grammar_registry,
+            false,
         )
     }
 
     /// Create a new editor with custom options
     /// This is primarily used for testing with slow or mock backends
     /// to verify editor behavior under various I/O conditions
+    ///
+    /// With `load_plugins_in_background`, plugins are loaded while the editor
+    /// runs instead of before this returns.
     #[allow(clippy::too_many_arguments)]
     fn with_options(
         mut config: Config,
@@ -843,6 +1136,7 @@ impl Editor {
         time_source: Option<SharedTimeSource>,
         color_capability: crate::view::color_support::ColorCapability,
         grammar_registry: Arc<crate::primitives::grammar::GrammarRegistry>,
+        load_plugins_in_background: bool,
     ) -> AnyhowResult<Self> {
         // Use provided time_source or default to RealTimeSource
         let time_source = time_source.unwrap_or_else(RealTimeSource::shared);
@@ -856,9 +1150,15 @@ impl Editor {
         // This ensures consistent path comparisons throughout the editor
         let working_dir = working_dir.canonicalize().unwrap_or(working_dir);
 
+        crate::primitives::display_width::set_ambiguous_wide(
+            config.editor.ambiguous_width == crate::config::AmbiguousWidth::Wide,
+        );
+
         // Load all themes into registry
         let theme_loader = crate::view::theme::ThemeLoader::new(dir_context.themes_dir());
-        let theme_registry = theme_loader.load_all();
+        let theme_registry =
+            tracing::info_span!("load_themes").in_scope(|| theme_loader.load_all());
+        let theme_file_stamps = theme_loader.file_stamps();
 
         // Get active theme from registry, falling back to default if not found
         let theme = theme_registry.get_cloned(&config.theme).unwrap_or_else(|| {
@@ -960,10 +1260,19 @@ impl Editor {
         let fs_manager = Arc::new(FsManager::new(Arc::clone(&filesystem)));
 
         // Initialize command registry (always available, used by both plugins and core)
-        let command_registry = Arc::new(RwLock::new(CommandRegistry::new()));
+        let mut command_registry = CommandRegistry::new();
+        command_registry.set_user_commands(user_commands::user_palette_commands(&config.commands));
+        command_registry.set_usage(load_frecency(
+            &dir_context.frecency_path("commands"),
+            "commands",
+        ));
+        let command_registry = Arc::new(RwLock::new(command_registry));
 
         // Initialize file provider for Quick Open (stored separately for cache management)
-        let file_provider = Arc::new(FileProvider::new());
+        let file_provider = Arc::new(FileProvider::with_frecency(load_frecency(
+            &dir_context.frecency_path("files"),
+            "files",
+        )));
 
         // Initialize Quick Open registry with providers
         let mut quick_open_registry = QuickOpenRegistry::new();
@@ -972,7 +1281,8 @@ impl Editor {
         // We'll handle commands and buffers inline since they need App state
 
         // Initialize plugin manager (handles both enabled and disabled cases internally)
-        let plugin_manager = PluginManager::new(
+        #[allow(unused_mut)]
+        let mut plugin_manager = PluginManager::new(
             enable_plugins,
             Arc::clone(&command_registry),
             dir_context.clone(),
@@ -992,6 +1302,9 @@ impl Editor {
         // 3. From embedded plugins (for cargo-binstall, when embed-plugins feature is enabled)
         // 4. User plugins directory (~/.config/fresh/plugins)
         // 5. Package manager installed plugins (~/.config/fresh/plugins/packages/*)
+        let mut plugin_load_errors: Vec<String> = Vec::new();
+        let mut deferred_plugins: Vec<DeferredPlugin> = Vec::new();
+        let mut background_plugin_dirs: Vec<std::path::PathBuf> = Vec::new();
         if plugin_manager.is_active() {
             let mut plugin_dirs: Vec<std::path::PathBuf> = vec![];
 
@@ -1055,11 +1368,33 @@ impl Editor {
                 );
             }
 
-            // Load from all found plugin directories, respecting config
+            // Packages that wait for an activation event are loaded later; their
+            // commands are listed until then
+            let mut startup_plugin_dirs = Vec::new();
             for plugin_dir in plugin_dirs {
+                match DeferredPlugin::from_package_dir(&plugin_dir) {
+                    Some(plugin) => {
+                        tracing::info!("Deferring plugin package: {:?}", plugin_dir);
+                        for command in plugin.placeholder_commands() {
+                            command_registry.read().unwrap().register(command);
+                        }
+                        deferred_plugins.push(plugin);
+                    }
+                    None => startup_plugin_dirs.push(plugin_dir),
+                }
+            }
+            if load_plugins_in_background {
+                background_plugin_dirs = std::mem::take(&mut startup_plugin_dirs);
+            }
+
+            // Load from all found plugin directories, respecting config
+            for plugin_dir in startup_plugin_dirs {
                 tracing::info!("Loading TypeScript plugins from: {:?}", plugin_dir);
                 let (errors, discovered_plugins) =
-                    plugin_manager.load_plugins_from_dir_with_config(&plugin_dir, &config.plugins);
+                    tracing::info_span!("load_plugins").in_scope(|| {
+                        plugin_manager
+                            .load_plugins_from_dir_with_config(&plugin_dir, &config.plugins)
+                    });
 
                 // Merge discovered plugins into config
                 // discovered_plugins already contains the merged config (saved enabled state + discovered path)
@@ -1071,6 +1406,7 @@ impl Editor {
                     for err in &errors {
                         tracing::error!("TypeScript plugin load error: {}", err);
                     }
+                    plugin_load_errors.extend(errors.iter().cloned());
                     // In debug/test builds, panic to surface plugin loading errors
                     #[cfg(debug_assertions)]
                     panic!(
@@ -1082,6 +1418,14 @@ impl Editor {
             }
         }
 
+        // Load native plugins (~/.config/fresh/plugins/native/*.so), which run
+        // on their own threads independently of the TypeScript runtime
+        #[cfg(feature = "runtime")]
+        if enable_plugins {
+            let native_dir = dir_context.config_dir.join("plugins").join("native");
+            plugin_manager.load_native_plugins(&native_dir, &config.plugins);
+        }
+
         // Extract config values before moving config into the struct
         let file_explorer_width = config.file_explorer.width;
         let recovery_enabled = config.editor.recovery_enabled;
@@ -1116,8 +1460,11 @@ impl Editor {
             dir_context: dir_context.clone(),
             grammar_registry,
             pending_grammars: Vec::new(),
+            deferred_plugins,
+            plugin_loads_pending: 0,
             theme,
             theme_registry,
+            theme_file_stamps,
             ansi_background: None,
             ansi_background_path: None,
             background_fade: crate::primitives::ansi_background::DEFAULT_BACKGROUND_FADE,
@@ -1131,6 +1478,7 @@ impl Editor {
             restart_with_dir: None,
             status_message: None,
             plugin_status_message: None,
+            status_segments: Vec::new(),
             plugin_errors: Vec::new(),
             prompt: None,
             terminal_width: width,
@@ -1138,6 +1486,10 @@ impl Editor {
             lsp: Some(lsp),
             buffer_metadata,
             mode_registry: ModeRegistry::new(),
+            jobs: crate::services::jobs::JobPool::new(
+                tokio_runtime.as_ref().map(|r| r.handle().clone()),
+                crate::services::jobs::DEFAULT_MAX_BLOCKING_JOBS,
+            ),
             tokio_runtime,
             async_bridge: Some(async_bridge),
             split_manager,
@@ -1154,6 +1506,7 @@ impl Editor {
             file_explorer_width_percent: file_explorer_width,
             pending_file_explorer_show_hidden: None,
             pending_file_explorer_show_gitignored: None,
+            pending_file_explorer_reveal: false,
             menu_bar_visible: show_menu_bar,
             file_explorer_decorations: HashMap::new(),
             file_explorer_decoration_cache:
@@ -1164,6 +1517,15 @@ impl Editor {
             same_buffer_scroll_sync: false,
             mouse_cursor_position: None,
             gpm_active: false,
+            open_read_only: false,
+            pager_mode: false,
+            pager_follow: false,
+            input_recorder: None,
+            replay: None,
+            history_scrubber: None,
+            plugin_timers: Default::default(),
+            pending_before_save: None,
+            next_before_save_id: 0,
             key_context: KeyContext::Normal,
             menu_state: crate::view::ui::MenuState::new(dir_context.themes_dir()),
             menus: crate::config::MenuConfig::translated(),
@@ -1172,13 +1534,21 @@ impl Editor {
             in_navigation: false,
             next_lsp_request_id: 0,
             pending_completion_request: None,
+            completion_responses: Vec::new(),
+            completion_providers: Vec::new(),
+            completion_sources: HashMap::new(),
             completion_items: None,
             scheduled_completion_trigger: None,
             pending_goto_definition_request: None,
+            location_picker_choices: Vec::new(),
             pending_hover_request: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
+            pending_document_highlight_request: None,
             pending_signature_help_request: None,
+            pending_formatting_request: None,
+            signature_help: None,
+            signature_help_dismissed_by_key: false,
             pending_code_actions_request: None,
             pending_inlay_hints_request: None,
             pending_semantic_token_requests: HashMap::new(),
@@ -1199,6 +1569,7 @@ impl Editor {
                 "lsp-diagnostic".to_string(),
             ),
             pending_search_range: None,
+            search_job: None,
             interactive_replace_state: None,
             lsp_status: String::new(),
             mouse_state: MouseState::default(),
@@ -1225,15 +1596,30 @@ impl Editor {
                 }
                 histories
             },
+            picker_frecency: HashMap::new(),
             pending_async_prompt_callback: None,
             lsp_progress: std::collections::HashMap::new(),
+            lsp_progress_spinner_frame: 0,
             lsp_server_statuses: std::collections::HashMap::new(),
             lsp_window_messages: Vec::new(),
             lsp_log_messages: Vec::new(),
+            lsp_log_view: None,
+            debugger: debugger::DebuggerState::default(),
+            plugin_console: plugin_console::PluginConsoleState::default(),
+            log_view: log_view::LogViewState::default(),
+            collab: collab::CollabState::default(),
+            control_socket: None,
             diagnostic_result_ids: HashMap::new(),
             stored_diagnostics: HashMap::new(),
+            server_diagnostics: HashMap::new(),
             event_broadcaster: crate::model::control_event::EventBroadcaster::default(),
             bookmarks: HashMap::new(),
+            named_layouts: HashMap::new(),
+            terminal_panel: terminal_panel::TerminalPanelState::default(),
+            repl_terminals: HashMap::new(),
+            image_previews: image_preview::ImagePreviews::default(),
+            screen_links: crate::view::hyperlinks::ScreenLinks::default(),
+            opened_links: None,
             search_case_sensitive: true,
             search_whole_word: false,
             search_use_regex: false,
@@ -1242,6 +1628,9 @@ impl Editor {
             macro_recording: None,
             last_macro_register: None,
             macro_playing: false,
+            last_edit: LastEdit::default(),
+            repeating_edit: false,
+            selection_stats: Default::default(),
             #[cfg(feature = "plugins")]
             pending_plugin_actions: Vec::new(),
             #[cfg(feature = "plugins")]
@@ -1252,6 +1641,9 @@ impl Editor {
             auto_revert_enabled: true,
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
+            last_theme_poll: time_source.now(),
+            #[cfg(feature = "plugins")]
+            last_plugin_poll: time_source.now(),
             file_mod_times: HashMap::new(),
             dir_mod_times: HashMap::new(),
             file_rapid_change_counts: HashMap::new(),
@@ -1268,10 +1660,20 @@ impl Editor {
             time_source: time_source.clone(),
             last_auto_recovery_save: time_source.now(),
             last_persistent_auto_save: time_source.now(),
+            local_history: crate::services::local_history::LocalHistory::new(
+                dir_context.local_history_dir(),
+            ),
+            last_local_history_snapshot: time_source.now(),
+            local_history_versions: HashMap::new(),
             active_custom_contexts: HashSet::new(),
             editor_mode: None,
             warning_log: None,
             status_log_path: None,
+            startup_profile: None,
+            perf_hud: None,
+            async_damage: Damage::None,
+            buffer_last_shown: HashMap::new(),
+            last_memory_check: time_source.now(),
             warning_domains: WarningDomainRegistry::new(),
             update_checker,
             terminal_manager: crate::services::terminal::TerminalManager::new(),
@@ -1293,17 +1695,35 @@ impl Editor {
             )
             .unwrap_or_default(),
             color_capability,
+            keyboard_enhancement: None,
+            tutor: tutor::TutorState::default(),
+            keybinding_conflicts: keybinding_conflicts::ConflictCheck::default(),
+            project_search: project_search::ProjectSearchState::default(),
+            linting: linters::LintState::default(),
             pending_file_opens: Vec::new(),
+            wait_for_files: Vec::new(),
             stdin_streaming: None,
             review_hunks: Vec::new(),
             active_action_popup: None,
             composite_buffers: HashMap::new(),
             composite_view_states: HashMap::new(),
+            git_diff_views: HashMap::new(),
+            diff_sessions: HashMap::new(),
+            unsaved_changes_views: HashMap::new(),
+            merge_output: None,
+            git_pool: crate::services::git::GitPool::default(),
+            git_status: None,
+            last_git_status_poll: None,
         };
 
         // Apply clipboard configuration
         editor.clipboard.apply_config(&editor.config.clipboard);
 
+        for err in plugin_load_errors {
+            editor.plugin_console_log("plugins", PluginLogLevel::Error, err);
+        }
+        editor.load_plugins_in_background(&background_plugin_dirs);
+
         #[cfg(feature = "plugins")]
         {
             editor.update_plugin_state_snapshot();
@@ -1439,8 +1859,19 @@ impl Editor {
 
     /// Check if editing should be disabled for the active buffer
     /// This returns true when editing_disabled is true (e.g., for read-only virtual buffers)
+    /// or when the buffer is in read-only mode
     pub fn is_editing_disabled(&self) -> bool {
-        self.active_state().editing_disabled
+        let state = self.active_state();
+        state.editing_disabled || state.read_only
+    }
+
+    /// Tell the user why an edit to the active buffer was blocked
+    pub(crate) fn report_editing_disabled(&mut self) {
+        if self.active_state().read_only {
+            self.set_status_message(t!("buffer.read_only").to_string());
+        } else {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+        }
     }
 
     /// Resolve a keybinding for the current mode
@@ -1565,22 +1996,6 @@ impl Editor {
         }
     }
 
-    /// Enable event log streaming to a file
-    pub fn enable_event_streaming<P: AsRef<Path>>(&mut self, path: P) -> AnyhowResult<()> {
-        // Enable streaming for all existing event logs
-        for event_log in self.event_logs.values_mut() {
-            event_log.enable_streaming(&path)?;
-        }
-        Ok(())
-    }
-
-    /// Log keystroke for debugging
-    pub fn log_keystroke(&mut self, key_code: &str, modifiers: &str) {
-        if let Some(event_log) = self.event_logs.get_mut(&self.active_buffer()) {
-            event_log.log_keystroke(key_code, modifiers);
-        }
-    }
-
     /// Set up warning log monitoring
     ///
     /// When warnings/errors are logged, they will be written to the specified path
@@ -1603,6 +2018,17 @@ impl Editor {
         self.process_spawner = spawner;
     }
 
+    /// Share the hyperlinks of rendered frames with the terminal backend
+    /// that writes them
+    pub fn set_screen_links(&mut self, links: crate::view::hyperlinks::ScreenLinks) {
+        self.screen_links = links;
+    }
+
+    /// Hyperlinked cells of the last frame
+    pub fn screen_links(&self) -> &crate::view::hyperlinks::ScreenLinks {
+        &self.screen_links
+    }
+
     /// Get remote connection info if editing remote files
     ///
     /// Returns `Some("user@host")` for remote editing, `None` for local.
@@ -2072,6 +2498,9 @@ impl Editor {
             _ => {}
         }
 
+        let _timer = perf::timer(perf::Phase::EventApply);
+        perf::count(perf::Counter::Events, 1);
+
         // IMPORTANT: Calculate LSP changes and line info BEFORE applying to buffer!
         // The byte positions in the events are relative to the ORIGINAL buffer,
         // so we must convert them to LSP positions before modifying the buffer.
@@ -2079,6 +2508,9 @@ impl Editor {
 
         // Calculate line info for plugin hooks (using same pre-modification buffer state)
         let line_info = self.calculate_event_line_info(event);
+        let old_len = self.active_state().buffer.len();
+
+        self.collab_before_local_edit(event);
 
         // 1. Apply the event to the buffer
         // Borrow cursors from SplitViewState (sole source of truth) and state from buffers
@@ -2097,6 +2529,9 @@ impl Editor {
             state.apply(cursors, event);
         }
 
+        // 1b. Share the edit with collaborators
+        self.collab_capture_local_edit(event);
+
         // 1c. Invalidate layouts for all views of this buffer after content changes
         // Note: recovery_pending is set automatically by the buffer on edits
         match event {
@@ -2133,6 +2568,23 @@ impl Editor {
 
         // 3. Trigger plugin hooks for this event (with pre-calculated line info)
         self.trigger_plugin_hooks_for_event(event, line_info);
+        let changes = match event {
+            // Undo/redo of a bulk edit swaps snapshots, so report the whole buffer
+            Event::BulkEdit { .. } => self
+                .active_state()
+                .buffer
+                .to_string()
+                .map(|text| {
+                    vec![TextChange {
+                        start: 0,
+                        removed_len: old_len,
+                        text,
+                    }]
+                })
+                .unwrap_or_default(),
+            _ => crate::services::plugins::event_hooks::text_changes(event),
+        };
+        self.fire_buffer_changed(self.active_buffer(), changes);
 
         // 4. Notify LSP of the change using pre-calculated positions
         self.send_lsp_changes_for_buffer(self.active_buffer(), lsp_changes);
@@ -2168,6 +2620,11 @@ impl Editor {
             return None;
         }
 
+        // Bulk edits bypass EditorState::apply, so check read-only mode here
+        if self.active_state().read_only {
+            return None;
+        }
+
         let active_buf = self.active_buffer();
         let split_id = self.split_manager.active_split();
 
@@ -2379,9 +2836,34 @@ impl Editor {
             self.send_lsp_changes_for_buffer(buffer_id, full_content_change);
         }
 
+        // The edits were applied last-first, so in that order each one is
+        // relative to the buffer after the previous
+        let changes = edits
+            .into_iter()
+            .map(|(start, removed_len, text)| TextChange {
+                start,
+                removed_len,
+                text,
+            })
+            .collect();
+        #[cfg(feature = "plugins")]
+        self.update_plugin_state_snapshot();
+        self.fire_buffer_changed(buffer_id, changes);
+
         Some(bulk_edit)
     }
 
+    /// Tell plugins about edits to a buffer (the buffer_changed hook)
+    fn fire_buffer_changed(&mut self, buffer_id: BufferId, changes: Vec<TextChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        self.plugin_manager.run_hook(
+            "buffer_changed",
+            crate::services::plugins::hooks::HookArgs::BufferChanged { buffer_id, changes },
+        );
+    }
+
     /// Trigger plugin hooks for an event (if any)
     /// line_info contains pre-calculated line numbers from BEFORE buffer modification
     fn trigger_plugin_hooks_for_event(&mut self, event: &Event, line_info: EventLineInfo) {
@@ -2866,6 +3348,9 @@ impl Editor {
     pub fn take_full_redraw_request(&mut self) -> bool {
         let requested = self.full_redraw_requested;
         self.full_redraw_requested = false;
+        if requested {
+            self.forget_drawn_images();
+        }
         requested
     }
 
@@ -2940,6 +3425,9 @@ impl Editor {
 
         // Resize visible terminal PTYs to match new dimensions
         self.resize_visible_terminals();
+
+        // The terminal clears the screen, images included
+        self.forget_drawn_images();
     }
 
     // Prompt/Minibuffer control methods
@@ -3069,6 +3557,8 @@ impl Editor {
         let mut prompt = Prompt::with_suggestions(String::new(), PromptType::QuickOpen, vec![]);
         prompt.input = ">".to_string();
         prompt.cursor_pos = 1;
+        // Tab marks several files to open at once in file mode
+        prompt.multi_select = true;
         self.prompt = Some(prompt);
 
         // Load initial command suggestions
@@ -3204,12 +3694,22 @@ impl Editor {
         }]
     }
 
+    /// Ignore rules of the working directory, shared by the file explorer,
+    /// Quick Open and project search
+    pub(crate) fn project_ignore_rules(&self) -> crate::services::ignore_rules::IgnoreRules {
+        crate::services::ignore_rules::IgnoreRules::new(
+            &self.working_dir,
+            &self.config.files.exclude,
+        )
+    }
+
     /// Get file suggestions for Quick Open
     fn get_file_suggestions(&self, query: &str) -> Vec<Suggestion> {
         // Use the file provider's file loading mechanism
         let cwd = self.working_dir.display().to_string();
         let context = QuickOpenContext {
             cwd: cwd.clone(),
+            file_exclude: self.config.files.exclude.clone(),
             open_buffers: vec![], // Not needed for file suggestions
             active_buffer_id: self.active_buffer().0,
             active_buffer_path: self
@@ -3561,12 +4061,20 @@ impl Editor {
                     | PromptType::SwitchProject
                     | PromptType::SaveFileAs
                     | PromptType::StopLspServer
+                    | PromptType::SelectDebugConfiguration
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::RestoreLayout
+                    | PromptType::DeleteLayout
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
                     | PromptType::SetLineEnding
+                    | PromptType::GitCheckoutBranch
+                    | PromptType::GotoLocation
+                    | PromptType::InsertUnicodeCharacter
+                    | PromptType::ActionConsole
+                    | PromptType::LocalHistory { .. }
                     | PromptType::Plugin { .. }
             ) {
                 // Use the selected suggestion if any
@@ -3646,13 +4154,31 @@ impl Editor {
                 }
             }
 
-            // Add to appropriate history based on prompt type
+            // Add to appropriate history based on prompt type. Plugin prompts
+            // remember what was typed (e.g. a Live Grep query), not the result picked.
             if let Some(key) = Self::prompt_type_to_history_key(&prompt.prompt_type) {
+                let entry = if matches!(prompt.prompt_type, PromptType::Plugin { .. }) {
+                    prompt.input.clone()
+                } else {
+                    final_input.clone()
+                };
                 let history = self.get_or_create_prompt_history(&key);
-                history.push(final_input.clone());
+                history.push(entry);
                 history.reset_navigation();
             }
 
+            // Rank the chosen entry higher the next time this picker is filtered
+            if let Some(key) = Self::prompt_type_to_frecency_key(&prompt.prompt_type) {
+                if prompt
+                    .suggestions
+                    .iter()
+                    .any(|s| s.get_value() == final_input)
+                {
+                    self.get_or_create_picker_frecency(&key)
+                        .record(&final_input);
+                }
+            }
+
             Some((final_input, prompt.prompt_type, selected_index))
         } else {
             None
@@ -3665,11 +4191,24 @@ impl Editor {
     }
 
     /// Get or create a prompt history for the given key
+    ///
+    /// Histories that weren't loaded at startup are read from disk on first use,
+    /// so every prompt type (including plugin prompts) keeps its entries across sessions.
     fn get_or_create_prompt_history(
         &mut self,
         key: &str,
     ) -> &mut crate::input::input_history::InputHistory {
-        self.prompt_histories.entry(key.to_string()).or_default()
+        let path = self.dir_context.prompt_history_path(key);
+        self.prompt_histories
+            .entry(key.to_string())
+            .or_insert_with(|| {
+                crate::input::input_history::InputHistory::load_from_file(&path).unwrap_or_else(
+                    |e| {
+                        tracing::warn!("Failed to load {} history: {}", key, e);
+                        crate::input::input_history::InputHistory::new()
+                    },
+                )
+            })
     }
 
     /// Get a prompt history for the given key (immutable)
@@ -3681,18 +4220,76 @@ impl Editor {
     fn prompt_type_to_history_key(prompt_type: &crate::view::prompt::PromptType) -> Option<String> {
         use crate::view::prompt::PromptType;
         match prompt_type {
-            PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
-                Some("search".to_string())
-            }
+            PromptType::Search
+            | PromptType::ReplaceSearch
+            | PromptType::QueryReplaceSearch
+            | PromptType::ProjectSearch => Some("search".to_string()),
             PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
                 Some("replace".to_string())
             }
             PromptType::GotoLine => Some("goto_line".to_string()),
+            PromptType::Command => Some("command".to_string()),
+            PromptType::OpenFile | PromptType::SaveFileAs => Some("file_path".to_string()),
+            PromptType::SwitchProject => Some("project".to_string()),
+            PromptType::ActionConsole => Some("action_console".to_string()),
             PromptType::Plugin { custom_type } => Some(format!("plugin:{}", custom_type)),
             _ => None,
         }
     }
 
+    /// Get or create the frecency of a picker's entries for the given key
+    ///
+    /// Loaded from disk on first use, like prompt histories.
+    fn get_or_create_picker_frecency(&mut self, key: &str) -> &mut Frecency {
+        let path = self.dir_context.frecency_path(key);
+        self.picker_frecency
+            .entry(key.to_string())
+            .or_insert_with(|| load_frecency(&path, key))
+    }
+
+    /// Get the frecency key for a picker prompt type
+    ///
+    /// Only pickers whose suggestion values stay meaningful across sessions
+    /// (theme names, languages, branches, ...) are ranked by frecency.
+    fn prompt_type_to_frecency_key(
+        prompt_type: &crate::view::prompt::PromptType,
+    ) -> Option<String> {
+        use crate::view::prompt::PromptType;
+        match prompt_type {
+            PromptType::SelectTheme { .. } => Some("theme".to_string()),
+            PromptType::SelectLocale => Some("locale".to_string()),
+            PromptType::SetLanguage => Some("language".to_string()),
+            PromptType::SetEncoding => Some("encoding".to_string()),
+            PromptType::SetLineEnding => Some("line_ending".to_string()),
+            PromptType::RestoreLayout | PromptType::DeleteLayout => Some("layout".to_string()),
+            PromptType::StopLspServer => Some("lsp_server".to_string()),
+            PromptType::SelectDebugConfiguration => Some("debug_configuration".to_string()),
+            PromptType::GitCheckoutBranch => Some("git_branch".to_string()),
+            PromptType::Plugin { custom_type } => Some(format!("plugin:{}", custom_type)),
+            _ => None,
+        }
+    }
+
+    /// Fuzzy-filter the current prompt's suggestions, ranking the picker's
+    /// frequently and recently chosen entries first
+    fn filter_prompt_suggestions(&mut self, match_description: bool) {
+        let key = self
+            .prompt
+            .as_ref()
+            .and_then(|p| Self::prompt_type_to_frecency_key(&p.prompt_type));
+        if let Some(key) = &key {
+            self.get_or_create_picker_frecency(key);
+        }
+        let unranked = Frecency::new();
+        let frecency = key
+            .as_ref()
+            .and_then(|key| self.picker_frecency.get(key))
+            .unwrap_or(&unranked);
+        if let Some(prompt) = &mut self.prompt {
+            prompt.filter_suggestions(match_description, frecency);
+        }
+    }
+
     /// Get the current global editor mode (e.g., "vi-normal", "vi-insert")
     /// Returns None if no special mode is active
     pub fn editor_mode(&self) -> Option<String> {
@@ -3796,6 +4393,9 @@ impl Editor {
                 // Update Quick Open suggestions based on prefix
                 self.update_quick_open_suggestions(&input);
             }
+            PromptType::GlobalCommand => {
+                self.update_global_command_preview(&input);
+            }
             PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                 // Update incremental search highlights as user types
                 self.update_search_highlights(&input);
@@ -3839,26 +4439,43 @@ impl Editor {
                 // Note: filter_suggestions checks suggestions_set_for_input to skip
                 // filtering if the plugin has already provided filtered results for
                 // this input (handles the async race condition with run_hook).
-                if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(false);
-                }
+                self.filter_prompt_suggestions(false);
             }
             PromptType::SwitchToTab
+            | PromptType::RestoreLayout
+            | PromptType::DeleteLayout
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
+            | PromptType::SelectDebugConfiguration
             | PromptType::SetLanguage
             | PromptType::SetEncoding
-            | PromptType::SetLineEnding => {
+            | PromptType::SetLineEnding
+            | PromptType::GitCheckoutBranch
+            | PromptType::GotoLocation
+            | PromptType::LocalHistory { .. } => {
+                self.filter_prompt_suggestions(false);
+            }
+            PromptType::InsertUnicodeCharacter => {
                 if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(false);
+                    prompt.suggestions = unicode_picker::unicode_picker_suggestions(&input);
+                    prompt.selected_suggestion = if prompt.suggestions.is_empty() {
+                        None
+                    } else {
+                        Some(0)
+                    };
                 }
             }
-            PromptType::SelectLocale => {
-                // Locale selection also matches on description (language names)
+            PromptType::ActionConsole => {
+                let suggestions = self.action_console_suggestions(&input);
                 if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(true);
+                    prompt.selected_suggestion = (!suggestions.is_empty()).then_some(0);
+                    prompt.suggestions = suggestions;
                 }
             }
+            PromptType::SelectLocale => {
+                // Locale selection also matches on description (language names)
+                self.filter_prompt_suggestions(true);
+            }
             _ => {}
         }
     }
@@ -3875,25 +4492,40 @@ impl Editor {
         // This ensures plugin errors surface quickly instead of causing silent hangs
         self.plugin_manager.check_thread_health();
 
+        // Send local edits before transforming remote ones against them
+        self.collab_flush();
+
         let Some(bridge) = &self.async_bridge else {
             return false;
         };
 
         let messages = bridge.try_recv_all();
         let needs_render = !messages.is_empty();
+        perf::count(perf::Counter::AsyncMessages, messages.len() as u64);
 
         for message in messages {
+            // Progress only changes the spinner and message in the status bar
+            self.async_damage.add(match message {
+                AsyncMessage::LspProgress { .. } => Damage::StatusBar,
+                _ => Damage::Full,
+            });
             match message {
-                AsyncMessage::LspDiagnostics { uri, diagnostics } => {
-                    self.handle_lsp_diagnostics(uri, diagnostics);
+                AsyncMessage::LspDiagnostics {
+                    language,
+                    uri,
+                    diagnostics,
+                } => {
+                    self.handle_lsp_diagnostics(language, uri, diagnostics);
                 }
                 AsyncMessage::LspInitialized {
                     language,
                     completion_trigger_characters,
+                    signature_help_trigger_characters,
                     semantic_tokens_legend,
                     semantic_tokens_full,
                     semantic_tokens_full_delta,
                     semantic_tokens_range,
+                    features,
                 } => {
                     tracing::info!("LSP server initialized for language: {}", language);
                     tracing::debug!(
@@ -3909,6 +4541,10 @@ impl Editor {
                             &language,
                             completion_trigger_characters,
                         );
+                        lsp.set_signature_help_trigger_characters(
+                            &language,
+                            signature_help_trigger_characters,
+                        );
                         lsp.set_semantic_tokens_capabilities(
                             &language,
                             semantic_tokens_legend,
@@ -3916,6 +4552,7 @@ impl Editor {
                             semantic_tokens_full_delta,
                             semantic_tokens_range,
                         );
+                        lsp.set_server_features(&language, features);
                     }
 
                     // Send didOpen for all open buffers of this language
@@ -4023,6 +4660,27 @@ impl Editor {
                         tracing::error!("Error handling references response: {}", e);
                     }
                 }
+                AsyncMessage::LspDocumentHighlight {
+                    request_id,
+                    highlights,
+                } => {
+                    self.handle_document_highlight_response(request_id, highlights);
+                }
+                AsyncMessage::Git(event) => {
+                    self.handle_git_event(event);
+                }
+                AsyncMessage::Dap {
+                    session_id,
+                    message,
+                } => {
+                    self.handle_dap_message(session_id, message);
+                }
+                AsyncMessage::Collab { session_id, event } => {
+                    self.handle_collab_event(session_id, event);
+                }
+                AsyncMessage::LspFormatting { request_id, result } => {
+                    self.handle_formatting_response(request_id, result);
+                }
                 AsyncMessage::LspSignatureHelp {
                     request_id,
                     signature_help,
@@ -4037,12 +4695,19 @@ impl Editor {
                 }
                 AsyncMessage::LspPulledDiagnostics {
                     request_id: _,
+                    language,
                     uri,
                     result_id,
                     diagnostics,
                     unchanged,
                 } => {
-                    self.handle_lsp_pulled_diagnostics(uri, result_id, diagnostics, unchanged);
+                    self.handle_lsp_pulled_diagnostics(
+                        language,
+                        uri,
+                        result_id,
+                        diagnostics,
+                        unchanged,
+                    );
                 }
                 AsyncMessage::LspInlayHints {
                     request_id,
@@ -4181,6 +4846,46 @@ impl Editor {
                 AsyncMessage::FileOpenShortcutsLoaded(shortcuts) => {
                     self.handle_file_open_shortcuts_loaded(shortcuts);
                 }
+                AsyncMessage::FileLoaded {
+                    buffer_id,
+                    path,
+                    result,
+                } => {
+                    self.handle_file_loaded(buffer_id, path, result);
+                }
+                AsyncMessage::GrammarsLoaded(registry) => {
+                    self.handle_grammars_loaded(registry);
+                }
+                AsyncMessage::PluginsLoaded { errors, discovered } => {
+                    self.handle_plugins_loaded(errors, discovered);
+                }
+                AsyncMessage::SearchFinished {
+                    buffer_id,
+                    buffer_len,
+                    query,
+                    search_range,
+                    matches,
+                } => {
+                    self.handle_search_finished(
+                        buffer_id,
+                        buffer_len,
+                        query,
+                        search_range,
+                        matches,
+                    );
+                }
+                AsyncMessage::ProjectSearchMatches { search_id, files } => {
+                    self.handle_project_search_matches(search_id, files);
+                }
+                AsyncMessage::ProjectSearchFinished { search_id, summary } => {
+                    self.handle_project_search_finished(search_id, summary);
+                }
+                AsyncMessage::PluginGrepFinished { callback_id, files } => {
+                    self.handle_plugin_grep_finished(
+                        fresh_core::api::JsCallbackId::from(callback_id),
+                        files,
+                    );
+                }
                 AsyncMessage::TerminalOutput { terminal_id } => {
                     // Terminal output received - check if we should auto-jump back to terminal mode
                     tracing::trace!("Terminal output received for {:?}", terminal_id);
@@ -4266,6 +4971,25 @@ impl Editor {
                 } => {
                     self.handle_lsp_server_request(language, server_command, method, params);
                 }
+                AsyncMessage::LspApplyEdit {
+                    language,
+                    label,
+                    edit,
+                    response,
+                } => {
+                    let _ = response.send(self.handle_lsp_apply_edit(&language, label, edit));
+                }
+                AsyncMessage::RemoteRequest { request, response } => {
+                    let _ = response.send(self.handle_remote_request(request));
+                }
+                AsyncMessage::LinterFinished {
+                    buffer_id,
+                    run,
+                    linter,
+                    result,
+                } => {
+                    self.handle_linter_finished(buffer_id, run, linter, result);
+                }
                 AsyncMessage::PluginLspResponse {
                     language: _,
                     request_id,
@@ -4331,30 +5055,100 @@ impl Editor {
         // Poll for file changes (auto-revert) and file tree changes
         let file_changes = self.poll_file_changes();
         let tree_changes = self.poll_file_tree_changes();
+        let theme_changes = self.poll_theme_changes();
+        #[cfg(feature = "plugins")]
+        let plugin_changes = self.poll_plugin_changes();
+        #[cfg(not(feature = "plugins"))]
+        let plugin_changes = false;
+        let lsp_log_changes = self.poll_lsp_log();
+        let lsp_spinner_changes = self.tick_lsp_progress_spinner();
+        self.poll_git_status();
+
+        // The spinner only turns in the status bar; anything else may change the whole screen
+        if lsp_spinner_changes {
+            self.async_damage.add(Damage::StatusBar);
+        }
+        if processed_any_commands
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || theme_changes
+            || plugin_changes
+            || lsp_log_changes
+        {
+            self.async_damage.add(Damage::Full);
+        }
 
         // Trigger render if any async messages, plugin commands were processed, or plugin requested render
-        needs_render || processed_any_commands || plugin_render || file_changes || tree_changes
+        needs_render
+            || processed_any_commands
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || theme_changes
+            || plugin_changes
+            || lsp_log_changes
+            || lsp_spinner_changes
     }
 
     /// Update LSP status bar string from active progress operations
+    ///
+    /// Shows the most recently started operation with a spinner, and how
+    /// many others are still running.
     fn update_lsp_status_from_progress(&mut self) {
-        if self.lsp_progress.is_empty() {
+        let Some(info) = self
+            .lsp_progress
+            .iter()
+            .max_by(|(a_token, a), (b_token, b)| {
+                a.started.cmp(&b.started).then_with(|| b_token.cmp(a_token))
+            })
+            .map(|(_, info)| info)
+        else {
             // No active progress, update from server statuses
             self.update_lsp_status_from_server_statuses();
             return;
-        }
+        };
 
-        // Show the first active progress operation
-        if let Some((_, info)) = self.lsp_progress.iter().next() {
-            let mut status = format!("LSP ({}): {}", info.language, info.title);
-            if let Some(ref msg) = info.message {
-                status.push_str(&format!(" - {}", msg));
-            }
-            if let Some(pct) = info.percentage {
-                status.push_str(&format!(" ({}%)", pct));
-            }
-            self.lsp_status = status;
+        let frame = self.lsp_progress_frame();
+        self.lsp_progress_spinner_frame = frame;
+        self.lsp_status = format_lsp_progress(
+            LSP_PROGRESS_SPINNER[frame],
+            info,
+            self.lsp_progress.len() - 1,
+        );
+    }
+
+    /// Spinner frame for the current time, counted from the oldest active
+    /// progress operation
+    fn lsp_progress_frame(&self) -> usize {
+        let Some(oldest) = self.lsp_progress.values().map(|info| info.started).min() else {
+            return 0;
+        };
+        let elapsed = self.time_source.now().saturating_duration_since(oldest);
+        (elapsed.as_millis() / LSP_PROGRESS_FRAME_MS) as usize % LSP_PROGRESS_SPINNER.len()
+    }
+
+    /// How long until the LSP progress spinner turns, if it is shown
+    fn lsp_progress_spinner_wait(&self) -> Option<std::time::Duration> {
+        let oldest = self.lsp_progress.values().map(|info| info.started).min()?;
+        let elapsed = self.time_source.now().saturating_duration_since(oldest);
+        let into_frame = elapsed.as_millis() % LSP_PROGRESS_FRAME_MS;
+        Some(std::time::Duration::from_millis(
+            (LSP_PROGRESS_FRAME_MS - into_frame) as u64,
+        ))
+    }
+
+    /// Advance the LSP progress spinner
+    ///
+    /// Returns true if the status bar needs to be redrawn.
+    fn tick_lsp_progress_spinner(&mut self) -> bool {
+        if self.lsp_progress.is_empty()
+            || self.lsp_progress_frame() == self.lsp_progress_spinner_frame
+        {
+            return false;
         }
+        self.update_lsp_status_from_progress();
+        true
     }
 
     /// Update LSP status bar string from server statuses
@@ -4721,6 +5515,23 @@ impl Editor {
                 self.handle_clear_conceals_in_range(buffer_id, start, end);
             }
 
+            // ==================== Fold Commands ====================
+            PluginCommand::AddFold {
+                buffer_id,
+                namespace,
+                start,
+                end,
+                placeholder,
+            } => {
+                self.handle_add_fold(buffer_id, namespace, start, end, placeholder);
+            }
+            PluginCommand::ClearFoldNamespace {
+                buffer_id,
+                namespace,
+            } => {
+                self.handle_clear_fold_namespace(buffer_id, namespace);
+            }
+
             // ==================== Soft Break Commands ====================
             PluginCommand::AddSoftBreak {
                 buffer_id,
@@ -4881,6 +5692,13 @@ impl Editor {
             } => {
                 self.handle_clear_line_indicators(buffer_id, namespace);
             }
+            PluginCommand::SetGutterSigns {
+                plugin_name,
+                buffer_id,
+                signs,
+            } => {
+                self.handle_set_gutter_signs(&plugin_name, buffer_id, signs);
+            }
             PluginCommand::SetFileExplorerDecorations {
                 namespace,
                 decorations,
@@ -4895,6 +5713,19 @@ impl Editor {
             PluginCommand::SetStatus { message } => {
                 self.handle_set_status(message);
             }
+            PluginCommand::SetStatusSegment {
+                plugin_name,
+                segment_id,
+                options,
+            } => {
+                self.handle_set_status_segment(plugin_name, segment_id, options);
+            }
+            PluginCommand::RemoveStatusSegment {
+                plugin_name,
+                segment_id,
+            } => {
+                self.handle_remove_status_segment(&plugin_name, &segment_id);
+            }
             PluginCommand::ApplyTheme { theme_name } => {
                 self.apply_theme(&theme_name);
             }
@@ -4920,8 +5751,12 @@ impl Editor {
             PluginCommand::ReloadGrammars => {
                 self.handle_reload_grammars();
             }
-            PluginCommand::StartPrompt { label, prompt_type } => {
-                self.handle_start_prompt(label, prompt_type);
+            PluginCommand::StartPrompt {
+                label,
+                prompt_type,
+                multi_select,
+            } => {
+                self.handle_start_prompt(label, prompt_type, multi_select);
             }
             PluginCommand::StartPromptWithInitial {
                 label,
@@ -4961,6 +5796,79 @@ impl Editor {
             } => {
                 self.handle_define_mode(name, parent, bindings, read_only);
             }
+            PluginCommand::RegisterKeybinding {
+                plugin_name,
+                keys,
+                command,
+                context,
+            } => {
+                self.handle_register_keybinding(plugin_name, keys, command, context);
+            }
+            PluginCommand::UnregisterKeybinding {
+                plugin_name,
+                keys,
+                context,
+            } => {
+                self.handle_unregister_keybinding(plugin_name, keys, context);
+            }
+            PluginCommand::RegisterCompletionProvider {
+                plugin_name,
+                provider_id,
+                languages,
+                trigger_chars,
+                label,
+                priority,
+            } => {
+                self.handle_register_completion_provider(
+                    plugin_name,
+                    provider_id,
+                    languages,
+                    trigger_chars,
+                    label,
+                    priority,
+                );
+            }
+            PluginCommand::UnregisterCompletionProvider {
+                plugin_name,
+                provider_id,
+            } => {
+                self.handle_unregister_completion_provider(&plugin_name, provider_id);
+            }
+            PluginCommand::CompletionItems { request_id, items } => {
+                self.handle_plugin_completion_items(request_id, items);
+            }
+            PluginCommand::PluginUnloaded { plugin_name } => {
+                self.handle_plugin_unloaded(plugin_name);
+            }
+            PluginCommand::PluginLog {
+                plugin_name,
+                level,
+                message,
+                stack,
+            } => {
+                self.plugin_console_log_with_stack(&plugin_name, level, message, stack);
+            }
+            PluginCommand::StartTimer {
+                plugin_name,
+                timer_id,
+                delay_ms,
+                repeat,
+            } => {
+                self.handle_start_timer(plugin_name, timer_id, delay_ms, repeat);
+            }
+            PluginCommand::ClearTimer {
+                plugin_name,
+                timer_id,
+            } => {
+                self.handle_clear_timer(&plugin_name, timer_id);
+            }
+            PluginCommand::BeforeSaveResponse {
+                plugin_name,
+                request_id,
+                result,
+            } => {
+                self.handle_before_save_response(plugin_name, request_id, result);
+            }
 
             // ==================== File/Navigation Commands ====================
             PluginCommand::OpenFileInBackground { path } => {
@@ -5063,6 +5971,24 @@ impl Editor {
                 );
             }
 
+            PluginCommand::GrepProject {
+                pattern,
+                regex,
+                case_sensitive,
+                whole_word,
+                max_results,
+                callback_id,
+            } => {
+                let options = crate::services::project_search::SearchOptions {
+                    regex,
+                    case_sensitive,
+                    whole_word,
+                    context_lines: 0,
+                    max_matches: max_results,
+                };
+                self.handle_grep_project(pattern, options, callback_id);
+            }
+
             PluginCommand::Delay {
                 callback_id,
                 duration_ms,
@@ -5094,8 +6020,8 @@ impl Editor {
                 cwd,
                 callback_id,
             } => {
-                // Spawn background process with streaming output via tokio
-                if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                // Spawn background process with streaming output as a job
+                if let Some(bridge) = &self.async_bridge {
                     use tokio::io::{AsyncBufReadExt, BufReader};
                     use tokio::process::Command as TokioCommand;
 
@@ -5110,12 +6036,14 @@ impl Editor {
                     let sender_stderr = sender.clone();
                     let callback_id_u64 = callback_id.as_u64();
 
-                    let handle = runtime.spawn(async move {
+                    let job = self.jobs.spawn(None, |_| async move {
+                        // Cancelling the job drops the child, which kills it
                         let mut child = match TokioCommand::new(&command)
                             .args(&args)
                             .current_dir(&effective_cwd)
                             .stdout(std::process::Stdio::piped())
                             .stderr(std::process::Stdio::piped())
+                            .kill_on_drop(true)
                             .spawn()
                         {
                             Ok(child) => child,
@@ -5192,9 +6120,16 @@ impl Editor {
                         ));
                     });
 
-                    // Store abort handle for potential kill
-                    self.background_process_handles
-                        .insert(process_id, handle.abort_handle());
+                    // Keep the job for potential kill
+                    match job {
+                        Some(job) => {
+                            self.background_process_handles.insert(process_id, job);
+                        }
+                        None => self.plugin_manager.reject_callback(
+                            callback_id,
+                            "Async runtime not available".to_string(),
+                        ),
+                    }
                 } else {
                     // No runtime - reject immediately
                     self.plugin_manager
@@ -5203,8 +6138,8 @@ impl Editor {
             }
 
             PluginCommand::KillBackgroundProcess { process_id } => {
-                if let Some(handle) = self.background_process_handles.remove(&process_id) {
-                    handle.abort();
+                if let Some(job) = self.background_process_handles.remove(&process_id) {
+                    self.jobs.cancel(job);
                     tracing::debug!("Killed background process {}", process_id);
                 }
             }
@@ -5464,6 +6399,14 @@ impl Editor {
                     }
                 }
             }
+            PluginCommand::SetImagePreview { buffer_id, path } => match path {
+                Some(path) => {
+                    if !self.show_image_preview(buffer_id, &path) {
+                        tracing::debug!("Not an image preview: {}", path.display());
+                    }
+                }
+                None => self.hide_image_preview(buffer_id),
+            },
             PluginCommand::GetTextPropertiesAtCursor { buffer_id } => {
                 // Get text properties at cursor and fire a hook with the data
                 if let Some(state) = self.buffers.get(&buffer_id) {
@@ -5672,6 +6615,13 @@ impl Editor {
                 );
             }
 
+            PluginCommand::ShowFloatingPanel { options } => {
+                self.show_floating_panel(options);
+            }
+            PluginCommand::HideFloatingPanel { panel_id } => {
+                self.hide_floating_panel(&panel_id);
+            }
+
             PluginCommand::DisableLspForLanguage { language } => {
                 tracing::info!("Disabling LSP for language: {}", language);
 
@@ -5819,12 +6769,21 @@ impl Editor {
             PluginCommand::ListPlugins { callback_id } => {
                 self.handle_list_plugins(callback_id);
             }
+            #[cfg(feature = "plugins")]
+            PluginCommand::SetPluginEnabled {
+                name,
+                enabled,
+                callback_id,
+            } => {
+                self.handle_set_plugin_enabled(name, enabled, callback_id);
+            }
             // When plugins feature is disabled, these commands are no-ops
             #[cfg(not(feature = "plugins"))]
             PluginCommand::LoadPlugin { .. }
             | PluginCommand::UnloadPlugin { .. }
             | PluginCommand::ReloadPlugin { .. }
-            | PluginCommand::ListPlugins { .. } => {
+            | PluginCommand::ListPlugins { .. }
+            | PluginCommand::SetPluginEnabled { .. } => {
                 tracing::warn!("Plugin management commands require the 'plugins' feature");
             }
 
@@ -6006,8 +6965,10 @@ impl Editor {
 
     /// Save a buffer to a specific file path (for :w filename)
     fn handle_save_buffer_to_path(&mut self, buffer_id: BufferId, path: std::path::PathBuf) {
+        let options = self.save_options();
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             // Save to the specified path
+            state.buffer.set_save_options(options);
             match state.buffer.save_to_file(&path) {
                 Ok(()) => {
                     // Update the buffer's file path so future saves go to the same file
@@ -6038,6 +6999,11 @@ impl Editor {
             }
             Err(e) => {
                 tracing::error!("Failed to load plugin from {:?}: {}", path, e);
+                let name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.plugin_console_log(&name, PluginLogLevel::Error, e.to_string());
                 self.plugin_manager
                     .reject_callback(callback_id, format!("{}", e));
             }
@@ -6072,6 +7038,7 @@ impl Editor {
             }
             Err(e) => {
                 tracing::error!("Failed to reload plugin '{}': {}", name, e);
+                self.plugin_console_log(&name, PluginLogLevel::Error, e.to_string());
                 self.plugin_manager
                     .reject_callback(callback_id, format!("{}", e));
             }
@@ -6097,6 +7064,50 @@ impl Editor {
         self.plugin_manager.resolve_callback(callback_id, json_str);
     }
 
+    /// Enable or disable a plugin and persist it to the user config
+    #[cfg(feature = "plugins")]
+    fn handle_set_plugin_enabled(
+        &mut self,
+        name: String,
+        enabled: bool,
+        callback_id: JsCallbackId,
+    ) {
+        // Plugins loaded at runtime (e.g. just installed) have no config entry yet
+        let path = self
+            .config
+            .plugins
+            .get(&name)
+            .and_then(|c| c.path.clone())
+            .or_else(|| {
+                self.plugin_manager
+                    .list_plugins()
+                    .into_iter()
+                    .find(|p| p.name == name)
+                    .map(|p| p.path)
+            });
+        let Some(path) = path else {
+            self.plugin_manager
+                .reject_callback(callback_id, format!("Unknown plugin '{}'", name));
+            return;
+        };
+
+        let old_plugins = self.config.plugins.clone();
+        self.config.plugins.insert(
+            name.clone(),
+            crate::config::PluginConfig {
+                enabled,
+                path: Some(path),
+            },
+        );
+        self.persist_config_change(
+            &format!("/plugins/{}/enabled", name),
+            serde_json::Value::Bool(enabled),
+        );
+        self.apply_plugin_config_changes(&old_plugins);
+        self.plugin_manager
+            .resolve_callback(callback_id, "true".to_string());
+    }
+
     /// Execute an editor action by name (for vi mode plugin)
     fn handle_execute_action(&mut self, action_name: String) {
         use crate::input::keybindings::Action;
@@ -6357,12 +7368,34 @@ impl Editor {
     }
 }
 
+/// Frames of the spinner shown while a language server reports progress
+const LSP_PROGRESS_SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Time each spinner frame stays on screen
+const LSP_PROGRESS_FRAME_MS: u128 = 100;
+
+/// Status bar text for an LSP progress operation, e.g.
+/// `⠋ LSP (rust): Indexing - 12/40 (30%) +1`
+fn format_lsp_progress(spinner: &str, info: &LspProgressInfo, others: usize) -> String {
+    let mut status = format!("{} LSP ({}): {}", spinner, info.language, info.title);
+    if let Some(ref msg) = info.message {
+        status.push_str(&format!(" - {}", msg));
+    }
+    if let Some(pct) = info.percentage {
+        status.push_str(&format!(" ({}%)", pct.min(100)));
+    }
+    if others > 0 {
+        status.push_str(&format!(" +{}", others));
+    }
+    status
+}
+
 /// Parse a key string like "RET", "C-n", "M-x", "q" into KeyCode and KeyModifiers
 ///
 /// Supports:
 /// - Single characters: "a", "q", etc.
 /// - Function keys: "F1", "F2", etc.
-/// - Special keys: "RET", "TAB", "ESC", "SPC", "DEL", "BS"
+/// - Special keys: "RET", "TAB", "BACKTAB", "ESC", "SPC", "DEL", "BS"
 /// - Modifiers: "C-" (Control), "M-" (Alt/Meta), "S-" (Shift)
 /// - Combinations: "C-n", "M-x", "C-M-s", etc.
 fn parse_key_string(key_str: &str) -> Option<(KeyCode, KeyModifiers)> {
@@ -6393,6 +7426,7 @@ fn parse_key_string(key_str: &str) -> Option<(KeyCode, KeyModifiers)> {
     let code = match upper.as_str() {
         "RET" | "RETURN" | "ENTER" => KeyCode::Enter,
         "TAB" => KeyCode::Tab,
+        "BACKTAB" => KeyCode::BackTab,
         "ESC" | "ESCAPE" => KeyCode::Esc,
         "SPC" | "SPACE" => KeyCode::Char(' '),
         "DEL" | "DELETE" => KeyCode::Delete,
@@ -8133,4 +9167,30 @@ mod tests {
             .sum();
         assert!(view_state.tab_scroll_offset <= total_width);
     }
+
+    #[test]
+    fn test_format_lsp_progress() {
+        let mut info = LspProgressInfo {
+            language: "rust".to_string(),
+            title: "Indexing".to_string(),
+            message: None,
+            percentage: None,
+            started: std::time::Instant::now(),
+        };
+        assert_eq!(format_lsp_progress("⠋", &info, 0), "⠋ LSP (rust): Indexing");
+
+        info.message = Some("12/40".to_string());
+        info.percentage = Some(30);
+        assert_eq!(
+            format_lsp_progress("⠙", &info, 2),
+            "⠙ LSP (rust): Indexing - 12/40 (30%) +2"
+        );
+
+        // Out-of-range percentages from misbehaving servers are clamped
+        info.percentage = Some(250);
+        assert_eq!(
+            format_lsp_progress("⠙", &info, 0),
+            "⠙ LSP (rust): Indexing - 12/40 (100%)"
+        );
+    }
 }
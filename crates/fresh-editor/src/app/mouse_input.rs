@@ -879,6 +879,18 @@ impl Editor {
                     }
                 }
 
+                // Check plugin segments
+                if let Some(index) = self
+                    .cached_layout
+                    .status_bar_plugin_segment_areas
+                    .iter()
+                    .position(|&(seg_row, seg_start, seg_end)| {
+                        row == seg_row && col >= seg_start && col < seg_end
+                    })
+                {
+                    return Some(HoverTarget::StatusBarPluginSegment(index));
+                }
+
                 // Check LSP indicator area
                 if let Some((lsp_row, lsp_start, lsp_end)) = self.cached_layout.status_bar_lsp_area
                 {
@@ -1569,6 +1581,18 @@ impl Editor {
                     }
                 }
 
+                // Check plugin segments - click runs the segment's command
+                if let Some(index) = self
+                    .cached_layout
+                    .status_bar_plugin_segment_areas
+                    .iter()
+                    .position(|&(seg_row, seg_start, seg_end)| {
+                        row == seg_row && col >= seg_start && col < seg_end
+                    })
+                {
+                    return self.click_status_segment(index);
+                }
+
                 // Check LSP indicator - click opens LSP status popup
                 if let Some((lsp_row, lsp_start, lsp_end)) = self.cached_layout.status_bar_lsp_area
                 {
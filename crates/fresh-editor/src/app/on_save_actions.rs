@@ -3,6 +3,7 @@
 //! This module handles running configured actions when files are saved,
 //! such as formatters, linters, and other tools.
 
+use std::collections::HashSet;
 use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -10,7 +11,9 @@ use std::time::Duration;
 
 use super::Editor;
 use crate::config::{FormatterConfig, OnSaveAction};
-use crate::model::event::Event;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::model::line_diff::text_edits;
 use rust_i18n::t;
 
 /// Result of running a formatter or on-save action
@@ -37,21 +40,18 @@ impl Editor {
         let mut ran_any_action = false;
 
         // Run whitespace cleanup actions first (before formatter)
-        if self.config.editor.trim_trailing_whitespace_on_save {
-            if self.trim_trailing_whitespace()? {
-                ran_any_action = true;
-            }
+        let (trim, final_newline) = self.save_whitespace_cleanup(self.active_buffer());
+        if trim && self.trim_trailing_whitespace()? {
+            ran_any_action = true;
         }
 
-        if self.config.editor.ensure_final_newline_on_save {
-            if self.ensure_final_newline()? {
-                ran_any_action = true;
-            }
+        if final_newline && self.ensure_final_newline()? {
+            ran_any_action = true;
         }
 
         // If whitespace cleanup made changes, re-save
         if ran_any_action {
-            if let Err(e) = self.active_state_mut().buffer.save() {
+            if let Err(e) = self.resave_active_buffer() {
                 return Err(format!("Failed to re-save after whitespace cleanup: {}", e));
             }
             self.active_event_log_mut().mark_saved();
@@ -72,7 +72,7 @@ impl Editor {
                     ActionResult::Success(output) => {
                         self.replace_buffer_with_output(&output)?;
                         // Re-save after formatting
-                        if let Err(e) = self.active_state_mut().buffer.save() {
+                        if let Err(e) = self.resave_active_buffer() {
                             return Err(format!("Failed to re-save after format: {}", e));
                         }
                         self.active_event_log_mut().mark_saved();
@@ -405,59 +405,29 @@ impl Editor {
 
     /// Replace the active buffer's content with new output.
     fn replace_buffer_with_output(&mut self, output: &str) -> Result<(), String> {
-        let cursor_id = self.active_cursors().primary_id();
+        self.replace_active_buffer_text(output, "On-save format")
+    }
 
-        // Get current buffer content
+    /// Replace the active buffer's content with `output` as one undo step.
+    ///
+    /// Only the parts that differ are edited, so cursors, selections and
+    /// markers in unchanged text stay where they were.
+    pub(super) fn replace_active_buffer_text(
+        &mut self,
+        output: &str,
+        description: &str,
+    ) -> Result<(), String> {
         let buffer_content = self.active_state().buffer.to_string().unwrap_or_default();
 
-        // Only replace if content is different
-        if buffer_content == output {
+        let events = replacement_events(&buffer_content, output);
+        if events.is_empty() {
             return Ok(());
         }
 
-        let buffer_len = buffer_content.len();
-
-        // Capture cursor position and selection state before replacement
-        let old_cursor_pos = self.active_cursors().primary().position;
-        let old_anchor = self.active_cursors().primary().anchor;
-        let old_sticky_column = self.active_cursors().primary().sticky_column;
-
-        // Delete all content and insert new
-        let delete_event = Event::Delete {
-            range: 0..buffer_len,
-            deleted_text: buffer_content,
-            cursor_id,
-        };
-        let insert_event = Event::Insert {
-            position: 0,
-            text: output.to_string(),
-            cursor_id,
-        };
-
-        // After delete+insert, cursor will be at output.len()
-        // Restore cursor to original position (or clamp to new buffer length)
-        let new_buffer_len = output.len();
-        let new_cursor_pos = old_cursor_pos.min(new_buffer_len);
-
-        // Only add MoveCursor event if position actually changes
-        let mut events = vec![delete_event, insert_event];
-        if new_cursor_pos != new_buffer_len {
-            let move_cursor_event = Event::MoveCursor {
-                cursor_id,
-                old_position: new_buffer_len, // Where cursor is after insert
-                new_position: new_cursor_pos,
-                old_anchor: None,
-                new_anchor: old_anchor.map(|a| a.min(new_buffer_len)),
-                old_sticky_column: 0,
-                new_sticky_column: old_sticky_column,
-            };
-            events.push(move_cursor_event);
-        }
-
         // Apply as a batch for atomic undo
         let batch = Event::Batch {
             events,
-            description: "On-save format".to_string(),
+            description: description.to_string(),
         };
         self.active_event_log_mut().append(batch.clone());
         self.apply_event_to_active_buffer(&batch);
@@ -465,24 +435,120 @@ impl Editor {
         Ok(())
     }
 
+    /// Whether saving `buffer_id` trims trailing whitespace and adds a final
+    /// newline: its `.editorconfig` settings, or else the editor config
+    pub(super) fn save_whitespace_cleanup(&self, buffer_id: BufferId) -> (bool, bool) {
+        let editor = &self.config.editor;
+        match self.buffers.get(&buffer_id) {
+            Some(state) => (
+                state
+                    .buffer_settings
+                    .trim_trailing_whitespace
+                    .unwrap_or(editor.trim_trailing_whitespace_on_save),
+                state
+                    .buffer_settings
+                    .insert_final_newline
+                    .unwrap_or(editor.ensure_final_newline_on_save),
+            ),
+            None => (false, false),
+        }
+    }
+
+    /// Clean up whitespace in a buffer about to be auto-saved
+    ///
+    /// Lines with a cursor are left alone, since whitespace there may be
+    /// in the middle of being typed.
+    pub(super) fn clean_up_whitespace_for_auto_save(&mut self, buffer_id: BufferId) {
+        let (trim, final_newline) = self.save_whitespace_cleanup(buffer_id);
+        if !trim && !final_newline {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        if state.editing_disabled {
+            return;
+        }
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+        let cursor_lines: HashSet<usize> = self
+            .split_view_states
+            .values()
+            .filter_map(|view_state| view_state.keyed_states.get(&buffer_id))
+            .flat_map(|buffer_view| buffer_view.cursors.iter())
+            .map(|(_, cursor)| state.buffer.get_line_number(cursor.position))
+            .collect();
+        let cleaned = clean_up_whitespace(
+            &content,
+            trim,
+            final_newline.then(|| state.buffer.line_ending().as_str()),
+            &cursor_lines,
+        );
+        if cleaned != content {
+            self.replace_buffer_text(buffer_id, &content, &cleaned, "Auto-save cleanup");
+        }
+    }
+
+    /// Replace the text of a buffer that may not be the active one with
+    /// `output`, as one undo step
+    fn replace_buffer_text(
+        &mut self,
+        buffer_id: BufferId,
+        content: &str,
+        output: &str,
+        description: &str,
+    ) {
+        if buffer_id == self.active_buffer() {
+            if let Err(e) = self.replace_active_buffer_text(output, description) {
+                tracing::warn!("Failed to update buffer: {}", e);
+            }
+            return;
+        }
+
+        let events = replacement_events(content, output);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        for event in &events {
+            state.apply(&mut Cursors::default(), event);
+            let (position, old_len, new_len) = match event {
+                Event::Insert { position, text, .. } => (*position, 0, text.len()),
+                Event::Delete { range, .. } => (range.start, range.len(), 0),
+                _ => continue,
+            };
+            for view_state in self.split_view_states.values_mut() {
+                if let Some(buffer_view) = view_state.keyed_states.get_mut(&buffer_id) {
+                    buffer_view
+                        .cursors
+                        .adjust_for_edit(position, old_len, new_len);
+                }
+            }
+        }
+        if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+            event_log.append(Event::Batch {
+                events,
+                description: description.to_string(),
+            });
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+
+        // Language servers get the whole document, as for bulk edits
+        self.send_lsp_changes_for_buffer(
+            buffer_id,
+            vec![lsp_types::TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: output.to_string(),
+            }],
+        );
+    }
+
     /// Trim trailing whitespace from all lines in the active buffer.
     /// Returns Ok(true) if any changes were made, Ok(false) if buffer unchanged.
     pub fn trim_trailing_whitespace(&mut self) -> Result<bool, String> {
         let content = self.active_state().buffer.to_string().unwrap_or_default();
-
-        // Process each line and trim trailing whitespace
-        let trimmed: String = content
-            .lines()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        // Preserve original trailing newline if present
-        let trimmed = if content.ends_with('\n') && !trimmed.ends_with('\n') {
-            format!("{}\n", trimmed)
-        } else {
-            trimmed
-        };
+        let trimmed = clean_up_whitespace(&content, true, None, &HashSet::new());
 
         if trimmed == content {
             return Ok(false);
@@ -502,16 +568,73 @@ impl Editor {
             return Ok(false);
         }
 
-        if content.ends_with('\n') {
+        let newline = self.active_state().buffer.line_ending().as_str();
+        let with_newline = clean_up_whitespace(&content, false, Some(newline), &HashSet::new());
+        if with_newline == content {
             return Ok(false);
         }
 
-        let with_newline = format!("{}\n", content);
         self.replace_buffer_with_output(&with_newline)?;
         Ok(true)
     }
 }
 
+/// Events turning `content` into `output`, editing only the parts that differ
+///
+/// They apply from the end so earlier ranges stay valid; the sentinel cursor
+/// keeps the edits from moving the user's cursors to the edit points.
+fn replacement_events(content: &str, output: &str) -> Vec<Event> {
+    text_edits(content, output)
+        .into_iter()
+        .rev()
+        .flat_map(|(range, text)| {
+            let delete = (!range.is_empty()).then(|| Event::Delete {
+                deleted_text: content[range.clone()].to_string(),
+                range: range.clone(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            });
+            let insert = (!text.is_empty()).then_some(Event::Insert {
+                position: range.start,
+                text,
+                cursor_id: CursorId::UNDO_SENTINEL,
+            });
+            delete.into_iter().chain(insert)
+        })
+        .collect()
+}
+
+/// `text` with trailing whitespace removed from its lines if `trim`, and
+/// `final_newline` added if it doesn't end with a newline, leaving the lines
+/// numbered in `keep_lines` as they are
+fn clean_up_whitespace(
+    text: &str,
+    trim: bool,
+    final_newline: Option<&str>,
+    keep_lines: &HashSet<usize>,
+) -> String {
+    let mut cleaned = String::with_capacity(text.len() + 2);
+    let mut last_line = 0;
+    for (line_num, line) in text.split_inclusive('\n').enumerate() {
+        let content = line.trim_end_matches(['\r', '\n']);
+        if trim && !keep_lines.contains(&line_num) {
+            cleaned.push_str(content.trim_end());
+            cleaned.push_str(&line[content.len()..]);
+        } else {
+            cleaned.push_str(line);
+        }
+        last_line = line_num + usize::from(line.ends_with('\n'));
+    }
+    if let Some(newline) = final_newline {
+        if !cleaned.is_empty()
+            && !cleaned.ends_with(['\n', '\r'])
+            && !keep_lines.contains(&last_line)
+        {
+            cleaned.push_str(newline);
+        }
+    }
+    cleaned
+}
+
 /// Check if a command exists in the system PATH.
 fn command_exists(command: &str) -> bool {
     // Use 'which' on Unix or 'where' on Windows to check if command exists
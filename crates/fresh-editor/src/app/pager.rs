@@ -0,0 +1,76 @@
+//! Pager mode (`fresh --pager`).
+//!
+//! Buffers open read-only and plain keys in the editor area behave like
+//! `less`: `q` quits, `/` searches, `g`/`G` jump to the start/end, and `F`
+//! follows growing stdin input.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use rust_i18n::t;
+
+use crate::input::keybindings::Action;
+
+use super::Editor;
+
+impl Editor {
+    /// Enable pager mode; implies read-only buffers
+    pub fn set_pager_mode(&mut self, enabled: bool) {
+        self.pager_mode = enabled;
+        if enabled {
+            self.set_open_read_only(true);
+        }
+    }
+
+    /// Whether the editor is running as a pager
+    pub fn is_pager_mode(&self) -> bool {
+        self.pager_mode
+    }
+
+    /// Whether follow mode keeps the cursor at the end of growing input
+    pub fn is_pager_following(&self) -> bool {
+        self.pager_follow
+    }
+
+    /// Map a key to its less-like pager action, if it has one
+    pub(super) fn pager_action_for_key(
+        &self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Option<Action> {
+        if !self.pager_mode || !(modifiers - KeyModifiers::SHIFT).is_empty() {
+            return None;
+        }
+        let action = match code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('/') => Action::Search,
+            KeyCode::Char('n') => Action::FindNext,
+            KeyCode::Char('N') => Action::FindPrevious,
+            KeyCode::Char('g') | KeyCode::Char('<') => Action::MoveDocumentStart,
+            KeyCode::Char('G') | KeyCode::Char('>') => Action::MoveDocumentEnd,
+            KeyCode::Char(' ') | KeyCode::Char('f') => Action::MovePageDown,
+            KeyCode::Char('b') => Action::MovePageUp,
+            KeyCode::Char('j') | KeyCode::Enter => Action::MoveDown,
+            KeyCode::Char('k') => Action::MoveUp,
+            KeyCode::Char('F') => Action::TogglePagerFollow,
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    /// Toggle follow mode: jump to the end and stay there as input grows
+    pub fn toggle_pager_follow(&mut self) {
+        self.pager_follow = !self.pager_follow;
+        if self.pager_follow {
+            self.pager_follow_to_end();
+            self.set_status_message(t!("pager.follow_enabled").to_string());
+        } else {
+            self.set_status_message(t!("pager.follow_disabled").to_string());
+        }
+    }
+
+    /// Move the cursor to the end of the active buffer while following
+    pub(super) fn pager_follow_to_end(&mut self) {
+        if let Err(e) = self.handle_action(Action::MoveDocumentEnd) {
+            tracing::warn!("Pager follow failed to move to end: {}", e);
+        }
+    }
+}
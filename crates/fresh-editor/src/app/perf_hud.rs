@@ -0,0 +1,127 @@
+//! Performance HUD and startup profile.
+//!
+//! The HUD is an overlay with the timings of recent frames and counts of the
+//! work done since it was shown, so that slowness can be reported with
+//! numbers. Timings from deep in the call tree are collected by
+//! `services::perf`.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::services::perf::{self, Counter, Phase};
+use crate::services::startup_profile::StartupProfileHandle;
+
+/// Frames the HUD keeps timings for
+const FRAME_WINDOW: usize = 60;
+
+/// Timings of one drawn frame
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameTimes {
+    /// Whole frame: rendering, plus writing it to the terminal
+    pub frame: Duration,
+    /// Building the frame in `Editor::render`
+    pub render: Duration,
+    /// Applying edit events since the previous frame
+    pub event_apply: Duration,
+    /// Syntax highlighting since the previous frame
+    pub highlight: Duration,
+}
+
+/// State of the performance HUD while it is shown
+#[derive(Debug, Default)]
+pub struct PerfHud {
+    /// Recent frames, most recent last
+    pub frames: VecDeque<FrameTimes>,
+    /// Occurrences of each counter since the HUD was shown
+    pub counts: [u64; Counter::ALL.len()],
+    /// Render time of the frame being drawn
+    render: Duration,
+}
+
+impl PerfHud {
+    /// Most recent frame
+    pub fn last(&self) -> FrameTimes {
+        self.frames.back().copied().unwrap_or_default()
+    }
+
+    /// Slowest time of `field` over the recent frames
+    pub fn max(&self, field: impl Fn(&FrameTimes) -> Duration) -> Duration {
+        self.frames.iter().map(field).max().unwrap_or_default()
+    }
+}
+
+impl Editor {
+    /// Show or hide the performance HUD
+    pub fn toggle_perf_hud(&mut self) {
+        let show = self.perf_hud.is_none();
+        perf::set_enabled(show);
+        if show {
+            self.perf_hud = Some(PerfHud::default());
+            self.set_status_message(t!("perf_hud.shown").to_string());
+        } else {
+            self.perf_hud = None;
+            self.set_status_message(t!("perf_hud.hidden").to_string());
+        }
+    }
+
+    /// Record how long `Editor::render` took for the frame being drawn
+    pub(super) fn record_render_time(&mut self, render: Duration) {
+        if let Some(hud) = &mut self.perf_hud {
+            hud.render = render;
+        }
+    }
+
+    /// Record a drawn frame, with the time taken to render and write it
+    pub fn record_frame_time(&mut self, frame: Duration) {
+        let Some(hud) = &mut self.perf_hud else {
+            return;
+        };
+        if hud.frames.len() == FRAME_WINDOW {
+            hud.frames.pop_front();
+        }
+        hud.frames.push_back(FrameTimes {
+            frame,
+            render: hud.render,
+            event_apply: perf::take_time(Phase::EventApply),
+            highlight: perf::take_time(Phase::Highlight),
+        });
+        for counter in Counter::ALL {
+            hud.counts[counter as usize] += perf::take_count(counter);
+        }
+    }
+
+    /// Write the startup profile when the first frame has been drawn
+    pub fn set_startup_profile(&mut self, profile: StartupProfileHandle) {
+        self.startup_profile = Some(profile);
+    }
+
+    /// Write the startup profile, if one was requested and not yet written
+    pub fn finish_startup_profile(&mut self) {
+        let Some(profile) = self.startup_profile.take() else {
+            return;
+        };
+        match profile.finish() {
+            Ok(()) => self.set_status_message(
+                t!(
+                    "perf_hud.startup_profile_written",
+                    path = profile.path.display().to_string()
+                )
+                .to_string(),
+            ),
+            Err(e) => {
+                tracing::error!("Failed to write startup profile: {}", e);
+                self.set_status_message(
+                    t!(
+                        "perf_hud.startup_profile_failed",
+                        path = profile.path.display().to_string(),
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+            }
+        }
+    }
+}
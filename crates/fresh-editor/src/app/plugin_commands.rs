@@ -2,16 +2,22 @@
 //!
 //! This module groups plugin commands by domain for better maintainability.
 
+use crate::input::key_reference::format_keys;
+use crate::input::keybindings::KeyContext;
 use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, CursorId, Event, OverlayFace, SplitId};
+use crate::view::margin::LineIndicator;
 use crate::view::overlay::{OverlayHandle, OverlayNamespace};
 use crate::view::split::SplitViewState;
 use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyCode, KeyModifiers};
 use fresh_core::api::{
-    LayoutHints, MenuPosition, OverlayOptions, PluginResponse, ViewTransformPayload,
+    GutterSign, LayoutHints, MenuPosition, OverlayColorSpec, OverlayOptions, PluginResponse,
+    ViewTransformPayload,
 };
+use rust_i18n::t;
 
-use super::Editor;
+use super::{parse_key_string, Editor};
 
 impl Editor {
     // ==================== Menu Helpers ====================
@@ -304,6 +310,40 @@ impl Editor {
         }
     }
 
+    // ==================== Fold Commands ====================
+
+    /// Handle AddFold command - hide a byte range behind a placeholder
+    pub(super) fn handle_add_fold(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: OverlayNamespace,
+        start: usize,
+        end: usize,
+        placeholder: String,
+    ) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.folds.add(
+                &mut state.marker_list,
+                namespace,
+                start..end,
+                Some(placeholder),
+            );
+        }
+    }
+
+    /// Handle ClearFoldNamespace command
+    pub(super) fn handle_clear_fold_namespace(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: OverlayNamespace,
+    ) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .folds
+                .clear_namespace(&namespace, &mut state.marker_list);
+        }
+    }
+
     // ==================== Soft Break Commands ====================
 
     /// Handle AddSoftBreak command
@@ -720,17 +760,22 @@ impl Editor {
         text: String,
     ) {
         let text_len = text.len();
-        if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            let event = Event::Insert {
-                position,
-                text,
-                cursor_id: CursorId(0),
-            };
-            // Apply to buffer with dummy cursors (real cursors adjusted below)
-            state.apply(&mut Cursors::default(), &event);
-            if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                log.append(event);
-            }
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.read_only {
+            tracing::debug!("Plugin edit to read-only buffer {:?} ignored", buffer_id);
+            return;
+        }
+        let event = Event::Insert {
+            position,
+            text,
+            cursor_id: CursorId(0),
+        };
+        // Apply to buffer with dummy cursors (real cursors adjusted below)
+        state.apply(&mut Cursors::default(), &event);
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.append(event);
         }
         // Adjust cursors in all splits that display this buffer
         for split_id in self.split_manager.splits_for_buffer(buffer_id) {
@@ -748,18 +793,23 @@ impl Editor {
     ) {
         let delete_start = range.start;
         let delete_len = range.end.saturating_sub(range.start);
-        if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            let deleted_text = state.get_text_range(range.start, range.end);
-            let event = Event::Delete {
-                range,
-                deleted_text,
-                cursor_id: CursorId(0),
-            };
-            // Apply to buffer with dummy cursors (real cursors adjusted below)
-            state.apply(&mut Cursors::default(), &event);
-            if let Some(log) = self.event_logs.get_mut(&buffer_id) {
-                log.append(event);
-            }
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.read_only {
+            tracing::debug!("Plugin edit to read-only buffer {:?} ignored", buffer_id);
+            return;
+        }
+        let deleted_text = state.get_text_range(range.start, range.end);
+        let event = Event::Delete {
+            range,
+            deleted_text,
+            cursor_id: CursorId(0),
+        };
+        // Apply to buffer with dummy cursors (real cursors adjusted below)
+        state.apply(&mut Cursors::default(), &event);
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.append(event);
         }
         // Adjust cursors in all splits that display this buffer
         for split_id in self.split_manager.splits_for_buffer(buffer_id) {
@@ -1129,6 +1179,53 @@ impl Editor {
         }
     }
 
+    /// Handle SetGutterSigns command: replace the plugin's signs in a buffer
+    pub(super) fn handle_set_gutter_signs(
+        &mut self,
+        plugin_name: &str,
+        buffer_id: BufferId,
+        signs: Vec<GutterSign>,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let namespace = gutter_sign_namespace(plugin_name);
+        state
+            .margins
+            .clear_line_indicators_for_namespace(&namespace);
+        for sign in signs {
+            // Signs past the end of the buffer are dropped
+            let Some(byte_offset) = state.buffer.line_start_offset(sign.line) else {
+                continue;
+            };
+            let priority = sign.priority.unwrap_or(0);
+            let indicator = match sign.color {
+                Some(OverlayColorSpec::Rgb(r, g, b)) => {
+                    LineIndicator::new(sign.symbol, ratatui::style::Color::Rgb(r, g, b), priority)
+                }
+                Some(OverlayColorSpec::ThemeKey(key)) => {
+                    LineIndicator::new(sign.symbol, self.theme.line_number_fg, priority)
+                        .with_theme_color(key)
+                }
+                None => LineIndicator::new(sign.symbol, self.theme.line_number_fg, priority)
+                    .with_theme_color("editor.line_number_fg"),
+            };
+            state
+                .margins
+                .set_line_indicator(byte_offset, namespace.clone(), indicator);
+        }
+    }
+
+    /// Remove an unloaded plugin's gutter signs from every buffer
+    pub(super) fn clear_plugin_gutter_signs(&mut self, plugin_name: &str) {
+        let namespace = gutter_sign_namespace(plugin_name);
+        for state in self.buffers.values_mut() {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(&namespace);
+        }
+    }
+
     // ==================== Status/Prompt Commands ====================
 
     /// Handle SetStatus command
@@ -1155,15 +1252,22 @@ impl Editor {
     }
 
     /// Handle StartPrompt command
-    pub(super) fn handle_start_prompt(&mut self, label: String, prompt_type: String) {
+    pub(super) fn handle_start_prompt(
+        &mut self,
+        label: String,
+        prompt_type: String,
+        multi_select: bool,
+    ) {
         // Create a plugin-controlled prompt
         use crate::view::prompt::{Prompt, PromptType};
-        self.prompt = Some(Prompt::new(
+        let mut prompt = Prompt::new(
             label,
             PromptType::Plugin {
                 custom_type: prompt_type.clone(),
             },
-        ));
+        );
+        prompt.multi_select = multi_select;
+        self.prompt = Some(prompt);
 
         // Fire the prompt_changed hook immediately with empty input
         // This allows plugins to initialize the prompt state
@@ -1319,7 +1423,6 @@ impl Editor {
         bindings: Vec<(String, String)>,
         read_only: bool,
     ) {
-        use super::parse_key_string;
         use crate::input::buffer_mode::BufferMode;
 
         let mut mode = BufferMode::new(name.clone()).with_read_only(read_only);
@@ -1366,6 +1469,138 @@ impl Editor {
         tracing::info!("Registered buffer mode '{}'", name);
     }
 
+    /// Handle RegisterKeybinding command
+    pub(super) fn handle_register_keybinding(
+        &mut self,
+        plugin_name: String,
+        keys: String,
+        command: String,
+        context: Option<String>,
+    ) {
+        use crate::input::keybindings::{Action, KeybindingResolver, PluginKeybinding};
+
+        let Some(sequence) = parse_key_sequence(&keys) else {
+            tracing::warn!(
+                "Plugin '{}' registered an invalid key binding: {}",
+                plugin_name,
+                keys
+            );
+            self.set_status_message(
+                t!(
+                    "plugin.keybinding_invalid",
+                    plugin = &plugin_name,
+                    keys = &keys
+                )
+                .to_string(),
+            );
+            return;
+        };
+        let (context, custom_context) = split_binding_context(context);
+        let action = Action::from_str(&command, &std::collections::HashMap::new())
+            .unwrap_or_else(|| Action::PluginAction(command.clone()));
+        let keys_display = format_keys(&sequence);
+
+        // Report what the new binding displaces (or is shadowed by)
+        let conflict = if custom_context.is_some() {
+            None
+        } else {
+            self.keybindings
+                .config_binding(context, &sequence)
+                .filter(|(existing, _)| **existing != action)
+                .map(|(existing, custom)| {
+                    let existing = KeybindingResolver::format_action(existing);
+                    if custom {
+                        t!(
+                            "plugin.keybinding_shadowed",
+                            plugin = &plugin_name,
+                            keys = &keys_display,
+                            existing = existing
+                        )
+                    } else {
+                        t!(
+                            "plugin.keybinding_conflict",
+                            plugin = &plugin_name,
+                            keys = &keys_display,
+                            existing = existing
+                        )
+                    }
+                })
+        };
+        let replaced = self.keybindings.add_plugin_binding(PluginKeybinding {
+            plugin: plugin_name.clone(),
+            keys: sequence,
+            action: action.clone(),
+            context,
+            custom_context,
+        });
+        let conflict = conflict.or_else(|| {
+            replaced.filter(|old| old.plugin != plugin_name).map(|old| {
+                t!(
+                    "plugin.keybinding_conflict",
+                    plugin = &plugin_name,
+                    keys = &keys_display,
+                    existing = format!(
+                        "{} ({})",
+                        KeybindingResolver::format_action(&old.action),
+                        old.plugin
+                    )
+                )
+            })
+        });
+        if let Some(message) = conflict {
+            tracing::warn!("{}", message);
+            self.set_status_message(message.to_string());
+        }
+        tracing::debug!(
+            "Plugin '{}' bound {} to {:?} ({:?})",
+            plugin_name,
+            keys,
+            action,
+            context
+        );
+    }
+
+    /// Handle UnregisterKeybinding command
+    pub(super) fn handle_unregister_keybinding(
+        &mut self,
+        plugin_name: String,
+        keys: String,
+        context: Option<String>,
+    ) {
+        let Some(sequence) = parse_key_sequence(&keys) else {
+            return;
+        };
+        let (context, custom_context) = split_binding_context(context);
+        if !self.keybindings.remove_plugin_binding(
+            &plugin_name,
+            &sequence,
+            context,
+            custom_context.as_deref(),
+        ) {
+            tracing::debug!(
+                "Plugin '{}' unbound {}, which it had not bound",
+                plugin_name,
+                keys
+            );
+        }
+    }
+
+    /// Handle PluginUnloaded command: drop the plugin's key bindings
+    pub(super) fn handle_plugin_unloaded(&mut self, plugin_name: String) {
+        self.plugin_timers.clear_plugin(&plugin_name);
+        self.clear_plugin_completion_providers(&plugin_name);
+        self.clear_plugin_gutter_signs(&plugin_name);
+        self.clear_plugin_status_segments(&plugin_name);
+        let removed = self.keybindings.remove_plugin_bindings(&plugin_name);
+        if removed > 0 {
+            tracing::info!(
+                "Removed {} key binding(s) of unloaded plugin '{}'",
+                removed,
+                plugin_name
+            );
+        }
+    }
+
     // ==================== LSP Commands ====================
 
     /// Handle SendLspRequest command
@@ -1484,6 +1719,40 @@ impl Editor {
         tracing::info!("LSP server registered for '{}'", language);
     }
 
+    /// Re-detect syntax highlighting for file buffers after the grammar
+    /// registry changed
+    pub(super) fn refresh_highlighters(&mut self) {
+        // Collect buffer IDs and paths first to avoid borrow issues
+        let buffers_to_update: Vec<_> = self
+            .buffer_metadata
+            .iter()
+            .filter_map(|(id, meta)| meta.file_path().map(|p| (*id, p.to_path_buf())))
+            .collect();
+
+        for (buf_id, path) in buffers_to_update {
+            if let Some(state) = self.buffers.get_mut(&buf_id) {
+                // Large files stay unhighlighted
+                if state.buffer.is_large_file() {
+                    continue;
+                }
+                // Re-create the highlight engine with the new grammar registry
+                let new_engine =
+                    crate::primitives::highlight_engine::HighlightEngine::for_file_with_languages(
+                        &path,
+                        &self.grammar_registry,
+                        &self.config.languages,
+                    );
+
+                // Only update if the new engine has highlighting capability
+                // or if the current one doesn't (don't downgrade)
+                if new_engine.has_highlighting() || !state.highlighter.has_highlighting() {
+                    state.highlighter = new_engine;
+                    tracing::debug!("Updated syntax highlighting for {:?}", path.file_name());
+                }
+            }
+        }
+    }
+
     /// Handle ReloadGrammars command
     /// Rebuilds the grammar registry with pending grammars and invalidates highlight caches
     pub(super) fn handle_reload_grammars(&mut self) {
@@ -1499,6 +1768,10 @@ impl Editor {
             tracing::debug!("ReloadGrammars called but no pending grammars");
             return;
         }
+        if self.grammar_registry.is_loading() {
+            // Applied once the grammars loading in the background are ready
+            return;
+        }
 
         // Collect pending grammars
         let additional: Vec<_> = self
@@ -1558,35 +1831,7 @@ impl Editor {
                 );
                 self.grammar_registry = std::sync::Arc::new(new_registry);
 
-                // Re-detect syntax for all buffers that might now have highlighting
-                // Collect buffer IDs and paths first to avoid borrow issues
-                let buffers_to_update: Vec<_> = self
-                    .buffer_metadata
-                    .iter()
-                    .filter_map(|(id, meta)| meta.file_path().map(|p| (*id, p.to_path_buf())))
-                    .collect();
-
-                for (buf_id, path) in buffers_to_update {
-                    if let Some(state) = self.buffers.get_mut(&buf_id) {
-                        // Re-create the highlight engine with the new grammar registry
-                        let new_engine =
-                            crate::primitives::highlight_engine::HighlightEngine::for_file_with_languages(
-                                &path,
-                                &self.grammar_registry,
-                                &self.config.languages,
-                            );
-
-                        // Only update if the new engine has highlighting capability
-                        // or if the current one doesn't (don't downgrade)
-                        if new_engine.has_highlighting() || !state.highlighter.has_highlighting() {
-                            state.highlighter = new_engine;
-                            tracing::debug!(
-                                "Updated syntax highlighting for {:?}",
-                                path.file_name()
-                            );
-                        }
-                    }
-                }
+                self.refresh_highlighters();
 
                 // Emit event for plugins that might want to react
                 self.emit_event(
@@ -1602,3 +1847,26 @@ impl Editor {
         }
     }
 }
+
+/// Line indicator namespace holding a plugin's gutter signs
+fn gutter_sign_namespace(plugin_name: &str) -> String {
+    format!("plugin-signs:{}", plugin_name)
+}
+
+/// Parse a plugin key binding such as "C-k C-g" into a key sequence
+fn parse_key_sequence(keys: &str) -> Option<Vec<(KeyCode, KeyModifiers)>> {
+    let sequence: Option<Vec<_>> = keys.split_whitespace().map(parse_key_string).collect();
+    sequence.filter(|sequence| !sequence.is_empty())
+}
+
+/// Split a plugin binding context into the key context and an optional custom
+/// context; names that are not key contexts are custom contexts of the editor
+fn split_binding_context(context: Option<String>) -> (KeyContext, Option<String>) {
+    match context {
+        None => (KeyContext::Normal, None),
+        Some(name) => match KeyContext::from_when_clause(&name) {
+            Some(context) => (context, None),
+            None => (KeyContext::Normal, Some(name)),
+        },
+    }
+}
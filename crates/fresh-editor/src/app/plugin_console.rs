@@ -0,0 +1,412 @@
+//! Plugin development support: the plugin console and plugin hot-reload
+//!
+//! The `*Plugin Console*` buffer collects what plugins log through
+//! `editor.debug/info/warn/error` and `console.*`, uncaught JS exceptions with
+//! their stack traces, and load errors. It can be narrowed to one plugin and
+//! to a minimum severity.
+//!
+//! Loaded plugin files (and their `.i18n.json` translations) are polled for
+//! changes, like themes. An edited plugin is reloaded in place: its commands,
+//! handlers and key bindings are dropped and registered again by re-running
+//! it, while its JS context, and with it any state kept on `globalThis`, is
+//! preserved. New plugin files in the user plugins directory are loaded as
+//! they appear. Errors go to the console instead of requiring an editor
+//! restart.
+
+use super::Editor;
+use crate::input::buffer_mode::PLUGIN_CONSOLE_MODE;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use fresh_core::api::PluginLogLevel;
+use rust_i18n::t;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Name of the read-only buffer showing plugin errors and messages
+pub const PLUGIN_CONSOLE_BUFFER_NAME: &str = "*Plugin Console*";
+
+/// Number of entries kept in the plugin console
+const MAX_CONSOLE_ENTRIES: usize = 5000;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PluginConsoleEntry {
+    timestamp: chrono::DateTime<chrono::Local>,
+    plugin: String,
+    level: PluginLogLevel,
+    message: String,
+    stack: Option<String>,
+}
+
+impl PluginConsoleEntry {
+    /// Console line(s); continuation lines and the stack trace are indented
+    fn format(&self) -> String {
+        let mut text = format!(
+            "{} [{}] {}: ",
+            self.timestamp.format("%H:%M:%S"),
+            self.level.as_str(),
+            self.plugin
+        );
+        let mut lines = self.message.lines();
+        text.push_str(lines.next().unwrap_or_default());
+        text.push('\n');
+        for line in lines.chain(self.stack.iter().flat_map(|s| s.lines())) {
+            if !line.trim().is_empty() {
+                text.push_str("    ");
+                text.push_str(line.trim());
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// Plugin console entries and the file stamps used for hot-reload
+#[derive(Debug)]
+pub(crate) struct PluginConsoleState {
+    entries: VecDeque<PluginConsoleEntry>,
+    /// Least severe level shown
+    min_level: PluginLogLevel,
+    /// Only show entries of this plugin
+    plugin_filter: Option<String>,
+    /// Last seen modification time of each watched plugin file
+    file_stamps: HashMap<PathBuf, SystemTime>,
+    /// Whether the first poll recorded the initial stamps
+    watching: bool,
+}
+
+impl Default for PluginConsoleState {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            min_level: PluginLogLevel::Debug,
+            plugin_filter: None,
+            file_stamps: HashMap::new(),
+            watching: false,
+        }
+    }
+}
+
+impl PluginConsoleState {
+    fn push(&mut self, entry: PluginConsoleEntry) {
+        self.entries.push_back(entry);
+        while self.entries.len() > MAX_CONSOLE_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Entries passing the level and plugin filters
+    fn visible(&self) -> impl Iterator<Item = &PluginConsoleEntry> {
+        self.entries.iter().filter(|entry| {
+            entry.level <= self.min_level
+                && self
+                    .plugin_filter
+                    .as_ref()
+                    .is_none_or(|plugin| *plugin == entry.plugin)
+        })
+    }
+
+    /// Plugin and message of each error entry, oldest first
+    pub(super) fn errors(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.level == PluginLogLevel::Error)
+            .map(|entry| (entry.plugin.as_str(), entry.message.as_str()))
+    }
+
+    /// Plugins that logged something, sorted
+    fn plugins(&self) -> Vec<String> {
+        let mut plugins: Vec<String> = self.entries.iter().map(|e| e.plugin.clone()).collect();
+        plugins.sort();
+        plugins.dedup();
+        plugins
+    }
+
+    /// Next plugin filter: all plugins, then each plugin that logged in turn
+    fn next_plugin_filter(&self) -> Option<String> {
+        let plugins = self.plugins();
+        match &self.plugin_filter {
+            None => plugins.into_iter().next(),
+            Some(current) => plugins.into_iter().find(|p| p > current),
+        }
+    }
+}
+
+fn next_level(level: PluginLogLevel) -> PluginLogLevel {
+    match level {
+        PluginLogLevel::Debug => PluginLogLevel::Info,
+        PluginLogLevel::Info => PluginLogLevel::Warn,
+        PluginLogLevel::Warn => PluginLogLevel::Error,
+        PluginLogLevel::Error => PluginLogLevel::Debug,
+    }
+}
+
+impl Editor {
+    /// Open the plugin console
+    pub fn show_plugin_console(&mut self) {
+        let buffer_id = self
+            .find_buffer_by_name(PLUGIN_CONSOLE_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    PLUGIN_CONSOLE_BUFFER_NAME.to_string(),
+                    PLUGIN_CONSOLE_MODE.to_string(),
+                    true,
+                )
+            });
+        self.fill_plugin_console(buffer_id, true);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Add an entry to the plugin console
+    pub(crate) fn plugin_console_log(
+        &mut self,
+        plugin: &str,
+        level: PluginLogLevel,
+        message: impl Into<String>,
+    ) {
+        self.plugin_console_log_with_stack(plugin, level, message.into(), None);
+    }
+
+    /// Add an entry with an optional JS stack trace to the plugin console
+    pub(crate) fn plugin_console_log_with_stack(
+        &mut self,
+        plugin: &str,
+        level: PluginLogLevel,
+        message: String,
+        stack: Option<String>,
+    ) {
+        self.plugin_console.push(PluginConsoleEntry {
+            timestamp: chrono::Local::now(),
+            plugin: plugin.to_string(),
+            level,
+            message,
+            stack,
+        });
+        if let Some(buffer_id) = self.find_buffer_by_name(PLUGIN_CONSOLE_BUFFER_NAME) {
+            self.fill_plugin_console(buffer_id, false);
+        }
+    }
+
+    /// Show one more severity level in the plugin console, wrapping to all
+    pub fn plugin_console_cycle_level(&mut self) {
+        let console = &mut self.plugin_console;
+        console.min_level = next_level(console.min_level);
+        let level = console.min_level.as_str();
+        self.refresh_plugin_console();
+        self.set_status_message(t!("plugin.console_level", level = level).to_string());
+    }
+
+    /// Restrict the plugin console to the next plugin that logged, wrapping to all
+    pub fn plugin_console_cycle_plugin(&mut self) {
+        self.plugin_console.plugin_filter = self.plugin_console.next_plugin_filter();
+        self.refresh_plugin_console();
+        let status = match &self.plugin_console.plugin_filter {
+            Some(plugin) => t!("plugin.console_plugin", plugin = plugin),
+            None => t!("plugin.console_all_plugins"),
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    fn refresh_plugin_console(&mut self) {
+        if let Some(buffer_id) = self.find_buffer_by_name(PLUGIN_CONSOLE_BUFFER_NAME) {
+            self.fill_plugin_console(buffer_id, true);
+        }
+    }
+
+    /// Fill the console; the cursor follows new output when it was at the end
+    fn fill_plugin_console(&mut self, buffer_id: BufferId, move_to_end: bool) {
+        let console = &self.plugin_console;
+        let mut content = format!(
+            "Plugin console: {}   (showing {} and more severe, f: change, p: plugin, q: close)\n\n",
+            console.plugin_filter.as_deref().unwrap_or("all plugins"),
+            console.min_level.as_str()
+        );
+        for entry in console.visible() {
+            content.push_str(&entry.format());
+        }
+
+        let old_len = self.buffers.get(&buffer_id).map_or(0, |s| s.buffer.len());
+        let at_end = move_to_end || self.buffer_cursor_position(buffer_id) == Some(old_len);
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill plugin console buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.configure_for_line_numbers(false);
+        }
+        if at_end {
+            self.move_cursor_to_buffer_end(buffer_id);
+        }
+    }
+
+    /// Poll plugin files for changes (called from main loop)
+    ///
+    /// Reloads plugins whose file or translations changed and loads new plugin
+    /// files from the user plugins directory. Plugins that failed to load are
+    /// retried when their file changes again.
+    /// Returns true if any plugin was (re)loaded (requires re-render).
+    #[cfg(feature = "plugins")]
+    pub fn poll_plugin_changes(&mut self) -> bool {
+        if !self.plugin_manager.is_active() {
+            return false;
+        }
+        let poll_interval =
+            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        if self.time_source.elapsed_since(self.last_plugin_poll) < poll_interval {
+            return false;
+        }
+        self.last_plugin_poll = self.time_source.now();
+
+        let loaded: HashMap<PathBuf, String> = self
+            .plugin_manager
+            .list_plugins()
+            .into_iter()
+            .map(|p| (p.path, p.name))
+            .collect();
+        let user_dir = self.dir_context.config_dir.join("plugins");
+        let mut watched: Vec<PathBuf> = loaded.keys().cloned().collect();
+        watched.extend(plugin_files_in(&user_dir));
+        watched.extend(self.plugin_console.file_stamps.keys().cloned());
+        watched.sort();
+        watched.dedup();
+
+        let watching = self.plugin_console.watching;
+        self.plugin_console.watching = true;
+        let mut changed = Vec::new();
+        for path in watched {
+            let Some(stamp) = plugin_file_stamp(&path) else {
+                self.plugin_console.file_stamps.remove(&path);
+                continue;
+            };
+            let previous = self.plugin_console.file_stamps.insert(path.clone(), stamp);
+            if watching && previous != Some(stamp) {
+                changed.push(path);
+            }
+        }
+
+        let mut any_loaded = false;
+        for path in changed {
+            let name = plugin_name(&path);
+            if self.config.plugins.get(&name).is_some_and(|c| !c.enabled) {
+                continue;
+            }
+            let result = match loaded.get(&path) {
+                Some(name) => self.plugin_manager.reload_plugin(name),
+                None => self.plugin_manager.load_plugin(&path),
+            };
+            match result {
+                Ok(()) => {
+                    tracing::info!("Hot-reloaded plugin '{}' from {:?}", name, path);
+                    self.config.plugins.entry(name.clone()).or_default().path = Some(path);
+                    self.plugin_console_log(&name, PluginLogLevel::Info, "reloaded");
+                    self.set_status_message(t!("plugin.reloaded", name = name).to_string());
+                    any_loaded = true;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to hot-reload plugin '{}': {}", name, e);
+                    self.plugin_console_log(&name, PluginLogLevel::Error, e.to_string());
+                    self.set_status_message(t!("plugin.reload_failed", name = name).to_string());
+                }
+            }
+        }
+        any_loaded
+    }
+}
+
+/// Top-level plugin files in a directory
+fn plugin_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("ts") | Some("js")
+                )
+        })
+        .collect()
+}
+
+/// Plugin name of a plugin file (its file stem)
+fn plugin_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Latest modification time of a plugin file and its translations
+fn plugin_file_stamp(path: &Path) -> Option<SystemTime> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let i18n = path.with_extension("i18n.json");
+    let i18n_modified = std::fs::metadata(i18n).and_then(|m| m.modified()).ok();
+    Some(i18n_modified.map_or(modified, |t| t.max(modified)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_files_and_stamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugin = dir.path().join("hello.ts");
+        std::fs::write(&plugin, "editor.setStatus('hi');").unwrap();
+        std::fs::write(dir.path().join("hello.i18n.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+        std::fs::create_dir(dir.path().join("lib.ts")).unwrap();
+
+        assert_eq!(plugin_files_in(dir.path()), vec![plugin.clone()]);
+        assert_eq!(plugin_name(&plugin), "hello");
+        assert!(plugin_file_stamp(&plugin).is_some());
+        assert!(plugin_file_stamp(&dir.path().join("missing.ts")).is_none());
+    }
+
+    fn entry(plugin: &str, level: PluginLogLevel, message: &str) -> PluginConsoleEntry {
+        PluginConsoleEntry {
+            timestamp: chrono::Local::now(),
+            plugin: plugin.to_string(),
+            level,
+            message: message.to_string(),
+            stack: None,
+        }
+    }
+
+    #[test]
+    fn test_console_filters() {
+        let mut console = PluginConsoleState::default();
+        console.push(entry("git", PluginLogLevel::Debug, "polling"));
+        console.push(entry("git", PluginLogLevel::Error, "boom"));
+        console.push(entry("todo", PluginLogLevel::Warn, "slow"));
+        assert_eq!(console.visible().count(), 3);
+
+        console.min_level = PluginLogLevel::Warn;
+        let messages: Vec<&str> = console.visible().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["boom", "slow"]);
+
+        console.plugin_filter = console.next_plugin_filter();
+        assert_eq!(console.plugin_filter.as_deref(), Some("git"));
+        assert_eq!(console.visible().count(), 1);
+        console.plugin_filter = console.next_plugin_filter();
+        assert_eq!(console.plugin_filter.as_deref(), Some("todo"));
+        console.plugin_filter = console.next_plugin_filter();
+        assert_eq!(console.plugin_filter, None);
+    }
+
+    #[test]
+    fn test_entry_format_indents_stack() {
+        let mut e = entry("git", PluginLogLevel::Error, "TypeError: x is undefined");
+        e.stack = Some("    at run (git.ts:3)\n    at <eval> (git.ts:9)\n".to_string());
+        let text = e.format();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].ends_with("[error] git: TypeError: x is undefined"));
+        assert_eq!(lines[1], "    at run (git.ts:3)");
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,187 @@
+//! Plugin timers (`setTimeout` / `setInterval` in the plugin API).
+//!
+//! The plugin runtime keeps the callbacks; the editor only tracks deadlines.
+//! Due timers are fired from the main loop, which also shortens its idle
+//! poll so a timer is never late by more than a tick.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::Editor;
+
+/// A scheduled plugin timer
+#[derive(Debug)]
+struct PluginTimer {
+    plugin: String,
+    due: Instant,
+    /// Period of an interval timer; None for a one-shot timer
+    interval: Option<Duration>,
+}
+
+/// Timers started by plugins, keyed by timer id
+#[derive(Debug, Default)]
+pub(crate) struct PluginTimers {
+    timers: HashMap<u64, PluginTimer>,
+}
+
+impl PluginTimers {
+    /// Schedule a timer `delay` from `now`
+    pub fn start(
+        &mut self,
+        plugin: String,
+        timer_id: u64,
+        now: Instant,
+        delay: Duration,
+        repeat: bool,
+    ) {
+        // A zero period would fire on every tick without advancing
+        let interval = repeat.then(|| delay.max(Duration::from_millis(1)));
+        self.timers.insert(
+            timer_id,
+            PluginTimer {
+                plugin,
+                due: now + delay,
+                interval,
+            },
+        );
+    }
+
+    /// Cancel a timer; plugins can only cancel their own
+    pub fn clear(&mut self, plugin: &str, timer_id: u64) -> bool {
+        if self
+            .timers
+            .get(&timer_id)
+            .is_some_and(|timer| timer.plugin == plugin)
+        {
+            self.timers.remove(&timer_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cancel every timer of a plugin
+    pub fn clear_plugin(&mut self, plugin: &str) {
+        self.timers.retain(|_, timer| timer.plugin != plugin);
+    }
+
+    /// When the next timer is due
+    pub fn next_due(&self) -> Option<Instant> {
+        self.timers.values().map(|timer| timer.due).min()
+    }
+
+    /// Take the timers due at `now`, in the order they fell due
+    ///
+    /// One-shot timers are removed and interval timers rescheduled from
+    /// `now`, so a stalled loop fires an interval once rather than catching up.
+    pub fn take_due(&mut self, now: Instant) -> Vec<(String, u64)> {
+        let mut due: Vec<(Instant, u64)> = self
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.due <= now)
+            .map(|(id, timer)| (timer.due, *id))
+            .collect();
+        due.sort();
+        due.into_iter()
+            .filter_map(|(_, id)| {
+                let timer = self.timers.get_mut(&id)?;
+                let plugin = timer.plugin.clone();
+                match timer.interval {
+                    Some(interval) => timer.due = now + interval,
+                    None => {
+                        self.timers.remove(&id);
+                    }
+                }
+                Some((plugin, id))
+            })
+            .collect()
+    }
+}
+
+impl Editor {
+    pub(super) fn handle_start_timer(
+        &mut self,
+        plugin_name: String,
+        timer_id: u64,
+        delay_ms: u64,
+        repeat: bool,
+    ) {
+        let now = self.time_source.now();
+        self.plugin_timers.start(
+            plugin_name,
+            timer_id,
+            now,
+            Duration::from_millis(delay_ms),
+            repeat,
+        );
+    }
+
+    pub(super) fn handle_clear_timer(&mut self, plugin_name: &str, timer_id: u64) {
+        self.plugin_timers.clear(plugin_name, timer_id);
+    }
+
+    /// Fire plugin timers that are due
+    ///
+    /// Returns true if any timer fired.
+    pub fn check_plugin_timers(&mut self) -> bool {
+        let due = self.plugin_timers.take_due(self.time_source.now());
+        let fired = !due.is_empty();
+        for (plugin_name, timer_id) in due {
+            self.plugin_manager.fire_timer(plugin_name, timer_id);
+        }
+        fired
+    }
+
+    /// How long until the next plugin timer is due
+    pub fn plugin_timer_wait(&self) -> Option<Duration> {
+        let due = self.plugin_timers.next_due()?;
+        Some(due.saturating_duration_since(self.time_source.now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timers_fire_in_order_and_intervals_repeat() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut timers = PluginTimers::default();
+        timers.start("a".into(), 1, start, ms(30), false);
+        timers.start("b".into(), 2, start, ms(10), true);
+        timers.start("a".into(), 3, start, ms(20), false);
+
+        assert_eq!(timers.next_due(), Some(start + ms(10)));
+        assert!(timers.take_due(start + ms(5)).is_empty());
+        assert_eq!(
+            timers.take_due(start + ms(25)),
+            vec![("b".to_string(), 2), ("a".to_string(), 3)]
+        );
+        // The interval is rescheduled from when it fired
+        assert_eq!(timers.next_due(), Some(start + ms(30)));
+        assert_eq!(
+            timers.take_due(start + ms(40)),
+            vec![("a".to_string(), 1), ("b".to_string(), 2)]
+        );
+        assert_eq!(timers.next_due(), Some(start + ms(50)));
+    }
+
+    #[test]
+    fn test_clear_timers() {
+        let now = Instant::now();
+        let mut timers = PluginTimers::default();
+        timers.start("a".into(), 1, now, Duration::ZERO, true);
+        timers.start("b".into(), 2, now, Duration::ZERO, false);
+
+        // Plugins cannot cancel each other's timers
+        assert!(!timers.clear("b", 1));
+        assert!(timers.clear("a", 1));
+        assert!(!timers.clear("a", 1));
+
+        timers.start("a".into(), 3, now, Duration::ZERO, false);
+        timers.clear_plugin("a");
+        assert_eq!(timers.take_due(now), vec![("b".to_string(), 2)]);
+        assert_eq!(timers.next_due(), None);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module contains handlers for popup-related actions like confirmation and cancellation.
 
+use super::completion_providers::completion_item_detail;
 use super::Editor;
 use crate::model::event::Event;
 use crate::primitives::snippet::{expand_snippet, is_snippet};
@@ -324,7 +325,7 @@ impl Editor {
             .iter()
             .map(|item| {
                 let text = item.label.clone();
-                let detail = item.detail.clone();
+                let detail = completion_item_detail(item);
                 let icon = match item.kind {
                     Some(lsp_types::CompletionItemKind::FUNCTION)
                     | Some(lsp_types::CompletionItemKind::METHOD) => Some("λ".to_string()),
@@ -398,4 +399,62 @@ impl Editor {
             &crate::model::event::Event::ShowPopup { popup: popup_data },
         );
     }
+
+    /// Show a plugin floating panel on the active buffer.
+    ///
+    /// Panels are read-only hover-style popups addressed by id, so showing the
+    /// same id again updates the panel in place.
+    pub(crate) fn show_floating_panel(&mut self, options: fresh_core::api::FloatingPanelOptions) {
+        use crate::view::popup::{Popup, PopupKind, PopupPosition};
+        use unicode_width::UnicodeWidthStr;
+
+        let bordered = options.border.unwrap_or(true);
+        let chrome = if bordered { 2 } else { 0 };
+        let position = match (options.x, options.y) {
+            (Some(x), Some(y)) => PopupPosition::Fixed { x, y },
+            _ => match options.anchor.as_deref() {
+                Some("aboveCursor") => PopupPosition::AboveCursor,
+                Some("center") => PopupPosition::Centered,
+                Some("bottomRight") => PopupPosition::BottomRight,
+                _ => PopupPosition::BelowCursor,
+            },
+        };
+        let content_width = options
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .chain(options.title.as_deref().map(|title| title.width() + 2))
+            .max()
+            .unwrap_or(0) as u16;
+        // Text popups keep two columns free for the scrollbar
+        let width = options.width.unwrap_or_else(|| {
+            content_width
+                .saturating_add(chrome + 2)
+                .min(self.terminal_width)
+        });
+        let max_height = options
+            .height
+            .unwrap_or_else(|| (options.lines.len() as u16).saturating_add(chrome));
+
+        let mut popup = Popup::text(options.lines, &self.theme)
+            .with_id(options.id)
+            .with_kind(PopupKind::Hover)
+            .with_position(position)
+            .with_width(width)
+            .with_max_height(max_height)
+            .with_z_index(options.z_index.unwrap_or(0));
+        popup.title = options.title;
+        popup.bordered = bordered;
+
+        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            state.popups.show(popup);
+        }
+    }
+
+    /// Hide a plugin floating panel, whichever buffer it was shown on
+    pub(crate) fn hide_floating_panel(&mut self, panel_id: &str) {
+        for state in self.buffers.values_mut() {
+            state.popups.hide_by_id(panel_id);
+        }
+    }
 }
@@ -0,0 +1,562 @@
+//! Native project search: the `*Search Results*` buffer
+//!
+//! `Search in Project` searches the files of the working directory (see
+//! `services::project_search`) in the background and lists the matches in the
+//! `*Search Results*` buffer as they come in, grouped by file with a few lines
+//! of context around each. Enter on a line opens the file there and `g` runs
+//! the search again. Plugins search the same way with `editor.grepProject`.
+
+use super::Editor;
+use crate::input::buffer_mode::PROJECT_SEARCH_MODE;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, Event, OverlayFace};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::jobs::JobId;
+use crate::services::project_search::{
+    build_regex, search_project, FileMatches, SearchOptions, SearchSummary,
+};
+use crate::view::overlay::OverlayNamespace;
+use crate::view::prompt::PromptType;
+use fresh_core::api::{JsCallbackId, OverlayColorSpec, OverlayOptions};
+use rust_i18n::t;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Name of the buffer listing the results
+pub const PROJECT_SEARCH_BUFFER_NAME: &str = "*Search Results*";
+
+/// Lines shown before and after each matching line
+const CONTEXT_LINES: usize = 2;
+
+/// Text property holding the file, line and column a result line opens
+const LOCATION_PROPERTY: &str = "location";
+
+/// Overlay namespace of the highlighted matches
+const MATCH_NAMESPACE: &str = "project-search";
+
+/// Least time between refreshes of the results buffer while a search runs
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The search shown in the results buffer
+#[derive(Debug, Default)]
+pub(super) struct ProjectSearchState {
+    /// Bumped for every search; results of earlier searches are dropped
+    search_id: u64,
+    job: Option<JobId>,
+    query: String,
+    /// Directory searched
+    root: PathBuf,
+    /// Files with matches, sorted by path
+    files: Vec<FileMatches>,
+    /// None while the search is running
+    summary: Option<SearchSummary>,
+    last_refresh: Option<Instant>,
+}
+
+impl ProjectSearchState {
+    fn add_files(&mut self, files: Vec<FileMatches>) {
+        for file in files {
+            let index = self
+                .files
+                .binary_search_by(|f| f.path.cmp(&file.path))
+                .unwrap_or_else(|index| index);
+            self.files.insert(index, file);
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.files.iter().map(FileMatches::match_count).sum()
+    }
+}
+
+/// Path shown for a result: relative to the searched directory when inside it
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn location(path: &Path, line: usize, column: usize) -> serde_json::Value {
+    serde_json::json!({
+        "file": path.to_string_lossy(),
+        "line": line,
+        "column": column,
+    })
+}
+
+/// Text of the results buffer, and the byte ranges of the matches in it
+fn results_entries(state: &ProjectSearchState) -> (Vec<TextPropertyEntry>, Vec<Range<usize>>) {
+    let status = match &state.summary {
+        None => " (searching...)".to_string(),
+        Some(summary) if summary.truncated => {
+            format!(" (stopped after {} matches)", summary.matches)
+        }
+        Some(_) => String::new(),
+    };
+    let header = format!(
+        "Search: {}   {} matches in {} files{}\n\
+         Enter: open   g: search again   q: close\n\n",
+        state.query,
+        state.match_count(),
+        state.files.len(),
+        status
+    );
+    let mut offset = header.len();
+    let mut entries = vec![TextPropertyEntry::text(header)];
+    let mut highlights = Vec::new();
+    fn push(entries: &mut Vec<TextPropertyEntry>, offset: &mut usize, entry: TextPropertyEntry) {
+        *offset += entry.text.len();
+        entries.push(entry);
+    }
+
+    for file in &state.files {
+        let first_match = file.lines.iter().find(|line| line.is_match());
+        let first_line = first_match.map_or(1, |line| line.number);
+        let first_column = first_match
+            .and_then(|line| line.matches.first())
+            .map_or(1, |m| m.start + 1);
+        push(
+            &mut entries,
+            &mut offset,
+            TextPropertyEntry::text(format!("{}\n", display_path(&file.path, &state.root)))
+                .with_property(
+                    LOCATION_PROPERTY,
+                    location(&file.path, first_line, first_column),
+                ),
+        );
+
+        let width = file
+            .lines
+            .last()
+            .map_or(1, |line| line.number.to_string().len());
+        let mut previous: Option<usize> = None;
+        for line in &file.lines {
+            if previous.is_some_and(|previous| line.number > previous + 1) {
+                push(&mut entries, &mut offset, TextPropertyEntry::text("  --\n"));
+            }
+            previous = Some(line.number);
+
+            let prefix = format!(
+                "  {:>width$}{} ",
+                line.number,
+                if line.is_match() { ':' } else { '-' },
+                width = width
+            );
+            let text_start = offset + prefix.len();
+            highlights.extend(
+                line.matches
+                    .iter()
+                    .map(|m| text_start + m.start..text_start + m.end),
+            );
+            let column = line.matches.first().map_or(1, |m| m.start + 1);
+            push(
+                &mut entries,
+                &mut offset,
+                TextPropertyEntry::text(format!("{}{}\n", prefix, line.text))
+                    .with_property(LOCATION_PROPERTY, location(&file.path, line.number, column)),
+            );
+        }
+        push(&mut entries, &mut offset, TextPropertyEntry::text("\n"));
+    }
+    (entries, highlights)
+}
+
+/// Matching lines of `files` as the JSON array `grepProject` resolves with
+fn grep_results_json(files: &[FileMatches], root: &Path) -> serde_json::Value {
+    let matches: Vec<serde_json::Value> = files
+        .iter()
+        .flat_map(|file| {
+            file.lines
+                .iter()
+                .filter(|line| line.is_match())
+                .map(|line| {
+                    serde_json::json!({
+                        "file": display_path(&file.path, root),
+                        "line": line.number,
+                        "column": line.matches[0].start + 1,
+                        "content": line.text,
+                    })
+                })
+        })
+        .collect();
+    serde_json::Value::Array(matches)
+}
+
+impl Editor {
+    /// Ask for a pattern to search the project's files for, starting with
+    /// the selected text
+    pub fn start_project_search_prompt(&mut self) {
+        let selected = self
+            .active_cursors()
+            .primary()
+            .selection_range()
+            .map(|range| {
+                self.active_state_mut()
+                    .get_text_range(range.start, range.end)
+            })
+            .filter(|text| !text.contains('\n'))
+            .unwrap_or_default();
+        self.start_prompt_with_initial_text(
+            t!("project_search.prompt").to_string(),
+            PromptType::ProjectSearch,
+            selected,
+        );
+    }
+
+    /// Search the files of the working directory for `query`, listing the
+    /// results in the `*Search Results*` buffer as they are found
+    ///
+    /// Uses the regex, case and whole word options of the buffer search.
+    pub fn start_project_search(&mut self, query: &str) {
+        if query.is_empty() {
+            return;
+        }
+        if self.filesystem.remote_connection_info().is_some() {
+            self.set_status_message(t!("project_search.remote").to_string());
+            return;
+        }
+        let options = SearchOptions {
+            regex: self.search_use_regex,
+            case_sensitive: Some(self.search_case_sensitive),
+            whole_word: self.search_whole_word,
+            context_lines: CONTEXT_LINES,
+            ..Default::default()
+        };
+        let regex = match build_regex(query, &options) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        // A new search replaces one still running
+        if let Some(job) = self.project_search.job.take() {
+            self.jobs.cancel(job);
+        }
+        let buffer_id = self
+            .find_buffer_by_name(PROJECT_SEARCH_BUFFER_NAME)
+            .unwrap_or_else(|| {
+                self.create_virtual_buffer(
+                    PROJECT_SEARCH_BUFFER_NAME.to_string(),
+                    PROJECT_SEARCH_MODE.to_string(),
+                    true,
+                )
+            });
+        let search = &mut self.project_search;
+        search.search_id += 1;
+        search.query = query.to_string();
+        search.root = self.working_dir.clone();
+        search.files.clear();
+        search.summary = None;
+        let search_id = search.search_id;
+        let rules = self.project_ignore_rules();
+
+        let Some(sender) = self.async_bridge.as_ref().map(|bridge| bridge.sender()) else {
+            self.set_status_message(t!("project_search.unavailable").to_string());
+            return;
+        };
+        let job = self.jobs.spawn_blocking(Some(buffer_id), move |token| {
+            let summary = search_project(&rules, &regex, &options, token, |file| {
+                let _ = sender.send(AsyncMessage::ProjectSearchMatches {
+                    search_id,
+                    files: vec![file],
+                });
+            });
+            if !token.is_cancelled() {
+                let _ = sender.send(AsyncMessage::ProjectSearchFinished { search_id, summary });
+            }
+        });
+        let Some(job) = job else {
+            self.set_status_message(t!("project_search.unavailable").to_string());
+            return;
+        };
+        self.project_search.job = Some(job);
+
+        self.refresh_project_search_buffer();
+        self.set_active_buffer(buffer_id);
+        self.set_status_message(t!("project_search.searching", query = query).to_string());
+    }
+
+    /// Run the search shown in the results buffer again
+    pub fn project_search_again(&mut self) {
+        let query = self.project_search.query.clone();
+        if query.is_empty() {
+            self.start_project_search_prompt();
+        } else {
+            self.start_project_search(&query);
+        }
+    }
+
+    /// Add the files found by a running search to the results buffer
+    pub(super) fn handle_project_search_matches(
+        &mut self,
+        search_id: u64,
+        files: Vec<FileMatches>,
+    ) {
+        if search_id != self.project_search.search_id {
+            return;
+        }
+        self.project_search.add_files(files);
+        let now = self.time_source.now();
+        let due = self
+            .project_search
+            .last_refresh
+            .is_none_or(|last| now.duration_since(last) >= REFRESH_INTERVAL);
+        if due {
+            self.refresh_project_search_buffer();
+        }
+    }
+
+    /// Show the final results of a search
+    pub(super) fn handle_project_search_finished(
+        &mut self,
+        search_id: u64,
+        summary: SearchSummary,
+    ) {
+        if search_id != self.project_search.search_id {
+            return;
+        }
+        self.project_search.job = None;
+        self.project_search.summary = Some(summary);
+        self.refresh_project_search_buffer();
+
+        let files = self.project_search.files.len();
+        let status = if files == 0 {
+            t!(
+                "project_search.no_matches",
+                query = &self.project_search.query
+            )
+        } else if summary.truncated {
+            t!(
+                "project_search.truncated",
+                count = summary.matches,
+                files = files
+            )
+        } else {
+            t!(
+                "project_search.done",
+                count = summary.matches,
+                files = files
+            )
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Fill the results buffer, if it is open, with the results so far
+    fn refresh_project_search_buffer(&mut self) {
+        self.project_search.last_refresh = Some(self.time_source.now());
+        let Some(buffer_id) = self.find_buffer_by_name(PROJECT_SEARCH_BUFFER_NAME) else {
+            return;
+        };
+        let (entries, highlights) = results_entries(&self.project_search);
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::error!("Failed to fill search results buffer: {}", e);
+            return;
+        }
+        self.highlight_project_search_matches(buffer_id, highlights);
+    }
+
+    fn highlight_project_search_matches(&mut self, buffer_id: BufferId, ranges: Vec<Range<usize>>) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state.editing_disabled = true;
+        state.margins.configure_for_line_numbers(false);
+        let namespace = OverlayNamespace::from_string(MATCH_NAMESPACE.to_string());
+        state.apply(
+            &mut Cursors::default(),
+            &Event::ClearNamespace {
+                namespace: namespace.clone(),
+            },
+        );
+        for range in ranges {
+            let options = OverlayOptions {
+                fg: Some(OverlayColorSpec::ThemeKey("search.match_fg".to_string())),
+                bg: Some(OverlayColorSpec::ThemeKey("search.match_bg".to_string())),
+                ..Default::default()
+            };
+            state.apply(
+                &mut Cursors::default(),
+                &Event::AddOverlay {
+                    namespace: Some(namespace.clone()),
+                    range,
+                    face: OverlayFace::from_options(options),
+                    priority: 10,
+                    message: None,
+                    extend_to_line_end: false,
+                    url: None,
+                },
+            );
+        }
+    }
+
+    /// Open the file of the result under the cursor at its line
+    pub fn project_search_open(&mut self) {
+        let location =
+            if self.find_buffer_by_name(PROJECT_SEARCH_BUFFER_NAME) == Some(self.active_buffer()) {
+                self.get_text_properties_at_cursor().and_then(|props| {
+                    props
+                        .into_iter()
+                        .find_map(|prop| prop.get(LOCATION_PROPERTY).cloned())
+                })
+            } else {
+                None
+            };
+        let Some((path, line, column)) = location.and_then(|location| {
+            Some((
+                PathBuf::from(location.get("file")?.as_str()?),
+                location.get("line")?.as_u64()? as usize,
+                location.get("column")?.as_u64()? as usize,
+            ))
+        }) else {
+            self.set_status_message(t!("project_search.no_result").to_string());
+            return;
+        };
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            return;
+        }
+        self.jump_to_line_column(Some(line), Some(column));
+    }
+
+    /// Search the project for a plugin (`editor.grepProject`)
+    pub(super) fn handle_grep_project(
+        &mut self,
+        pattern: String,
+        options: SearchOptions,
+        callback_id: JsCallbackId,
+    ) {
+        if self.filesystem.remote_connection_info().is_some() {
+            self.plugin_manager
+                .reject_callback(callback_id, t!("project_search.remote").to_string());
+            return;
+        }
+        let regex = match build_regex(&pattern, &options) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.plugin_manager
+                    .reject_callback(callback_id, e.to_string());
+                return;
+            }
+        };
+        let rules = self.project_ignore_rules();
+        let job = self
+            .async_bridge
+            .as_ref()
+            .map(|bridge| bridge.sender())
+            .and_then(|sender| {
+                self.jobs.spawn_blocking(None, move |token| {
+                    let files = Mutex::new(Vec::new());
+                    search_project(&rules, &regex, &options, token, |file| {
+                        files.lock().unwrap().push(file)
+                    });
+                    let _ = sender.send(AsyncMessage::PluginGrepFinished {
+                        callback_id: callback_id.as_u64(),
+                        files: files.into_inner().unwrap(),
+                    });
+                })
+            });
+        if job.is_none() {
+            self.plugin_manager
+                .reject_callback(callback_id, t!("project_search.unavailable").to_string());
+        }
+    }
+
+    /// Resolve a plugin's `grepProject` call with the matching lines
+    pub(super) fn handle_plugin_grep_finished(
+        &mut self,
+        callback_id: JsCallbackId,
+        mut files: Vec<FileMatches>,
+    ) {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let result = grep_results_json(&files, &self.working_dir);
+        self.plugin_manager
+            .resolve_callback(callback_id, result.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::project_search::ResultLine;
+
+    /// A result line with at most one match
+    fn result_line(number: usize, text: &str, matched: Option<Range<usize>>) -> ResultLine {
+        ResultLine {
+            number,
+            text: text.to_string(),
+            matches: matched.into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_results_are_grouped_by_file() {
+        let mut state = ProjectSearchState {
+            query: "needle".to_string(),
+            root: PathBuf::from("/project"),
+            ..Default::default()
+        };
+        state.add_files(vec![
+            FileMatches {
+                path: PathBuf::from("/project/src/b.rs"),
+                lines: vec![result_line(7, "let needle = 1;", Some(4..10))],
+            },
+            FileMatches {
+                path: PathBuf::from("/project/src/a.rs"),
+                lines: vec![
+                    result_line(1, "needle", Some(0..6)),
+                    result_line(2, "after", None),
+                    result_line(9, "before", None),
+                    result_line(10, "needle", Some(0..6)),
+                ],
+            },
+        ]);
+
+        let (entries, highlights) = results_entries(&state);
+        let text: String = entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(
+            text,
+            "Search: needle   3 matches in 2 files (searching...)\n\
+             Enter: open   g: search again   q: close\n\n\
+             src/a.rs\n   1: needle\n   2- after\n  --\n   9- before\n  10: needle\n\n\
+             src/b.rs\n  7: let needle = 1;\n\n"
+        );
+        let highlighted: Vec<&str> = highlights.iter().map(|r| &text[r.clone()]).collect();
+        assert_eq!(highlighted, vec!["needle"; 3]);
+
+        // Each result line opens its own line
+        let line_ten = entries
+            .iter()
+            .find(|e| e.text.starts_with("  10:"))
+            .unwrap();
+        assert_eq!(
+            line_ten.properties[LOCATION_PROPERTY],
+            location(Path::new("/project/src/a.rs"), 10, 1)
+        );
+    }
+
+    #[test]
+    fn test_grep_results_skip_context_lines() {
+        let files = vec![FileMatches {
+            path: PathBuf::from("/project/main.rs"),
+            lines: vec![
+                result_line(1, "fn helper() {}", None),
+                result_line(2, "fn main() {}", Some(0..7)),
+            ],
+        }];
+        let json = grep_results_json(&files, Path::new("/project"));
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"file": "main.rs", "line": 2, "column": 1, "content": "fn main() {}"}
+            ])
+        );
+    }
+}
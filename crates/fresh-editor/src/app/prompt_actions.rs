@@ -4,6 +4,7 @@
 
 use rust_i18n::t;
 
+use super::git_diff_view::GitDiffBase;
 use super::normalize_path;
 use super::BufferId;
 use super::BufferMetadata;
@@ -44,14 +45,19 @@ impl Editor {
                     normalize_path(&self.working_dir.join(&expanded_path))
                 };
 
-                if let Err(e) = self.open_file(&resolved_path) {
-                    self.set_status_message(
-                        t!("file.error_opening", error = e.to_string()).to_string(),
-                    );
-                } else {
-                    self.set_status_message(
-                        t!("buffer.opened", name = resolved_path.display().to_string()).to_string(),
-                    );
+                match self.open_file_in_background(&resolved_path) {
+                    Err(e) => {
+                        self.set_status_message(
+                            t!("file.error_opening", error = e.to_string()).to_string(),
+                        );
+                    }
+                    Ok(buffer_id) if !self.is_buffer_loading(buffer_id) => {
+                        self.set_status_message(
+                            t!("buffer.opened", name = resolved_path.display().to_string())
+                                .to_string(),
+                        );
+                    }
+                    Ok(_) => {}
                 }
             }
             PromptType::OpenFileWithEncoding { path } => {
@@ -177,6 +183,9 @@ impl Editor {
             PromptType::SetComposeWidth => {
                 self.handle_set_compose_width(&input);
             }
+            PromptType::FilterKeyboardShortcuts => {
+                self.open_keyboard_shortcuts_filtered(&input);
+            }
             PromptType::RecordMacro => {
                 self.handle_register_input(
                     &input,
@@ -208,6 +217,7 @@ impl Editor {
                         prompt_type: custom_type.clone(),
                         input,
                         selected_index,
+                        selected_values: Vec::new(),
                     },
                 );
                 tracing::info!(
@@ -240,6 +250,12 @@ impl Editor {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmExternalChange { buffer_id } => {
+                self.handle_confirm_external_change(&input, buffer_id);
+            }
+            PromptType::ConfirmCrashRecovery => {
+                self.handle_confirm_crash_recovery(&input);
+            }
             PromptType::ConfirmSudoSave { info } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
@@ -249,6 +265,8 @@ impl Editor {
                     // Read temp file and write via sudo (works for both local and remote)
                     let result = (|| -> anyhow::Result<()> {
                         let data = self.filesystem.read_file(&info.temp_path)?;
+                        // sudo may ask for a password on the terminal
+                        let _input_hold = crate::services::event_bus::hold_terminal_input();
                         self.filesystem.sudo_write(
                             &info.dest_path,
                             &data,
@@ -341,6 +359,20 @@ impl Editor {
                     self.set_status_message(t!("explorer.delete_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmDeleteFiles { paths } => {
+                let input_lower = input.trim().to_lowercase();
+                if input_lower == "y" || input_lower == "yes" {
+                    self.perform_file_explorer_delete_many(paths);
+                } else {
+                    self.set_status_message(t!("explorer.delete_cancelled").to_string());
+                }
+            }
+            PromptType::FileExplorerMove { paths } => {
+                self.perform_file_explorer_transfer(paths, input, false);
+            }
+            PromptType::FileExplorerCopy { paths } => {
+                self.perform_file_explorer_transfer(paths, input, true);
+            }
             PromptType::ConfirmLargeFileEncoding { path } => {
                 let input_lower = input.trim().to_lowercase();
                 let load_key = t!("file.large_encoding.key.load")
@@ -376,6 +408,24 @@ impl Editor {
             PromptType::StopLspServer => {
                 self.handle_stop_lsp_server(&input);
             }
+            PromptType::LocalHistory { buffer_id } => {
+                self.open_local_history_snapshot(buffer_id, &input);
+            }
+            PromptType::SelectDebugConfiguration => {
+                self.start_debug_configuration(&input);
+            }
+            PromptType::DebugAddWatch => {
+                self.add_debug_watch(&input);
+            }
+            PromptType::DebugEvaluate => {
+                self.evaluate_debug_expression(&input);
+            }
+            PromptType::CollabHost => {
+                self.collab_host(&input);
+            }
+            PromptType::CollabJoin => {
+                self.collab_join(&input);
+            }
             PromptType::SelectTheme { .. } => {
                 self.apply_theme(input.trim());
             }
@@ -396,6 +446,36 @@ impl Editor {
                     self.switch_to_tab(BufferId(id));
                 }
             }
+            PromptType::SaveLayout => {
+                self.save_named_layout(input.trim());
+            }
+            PromptType::RestoreLayout => {
+                self.restore_named_layout(input.trim());
+            }
+            PromptType::DeleteLayout => {
+                self.delete_named_layout(input.trim());
+            }
+            PromptType::RenameTerminal { buffer_id } => {
+                self.rename_terminal(buffer_id, &input);
+            }
+            PromptType::GitDiffRevision => {
+                self.open_git_diff(GitDiffBase::Revision(input.trim().to_string()));
+            }
+            PromptType::GitCheckoutBranch => {
+                self.git_checkout(input.trim());
+            }
+            PromptType::GotoLocation => {
+                self.confirm_location_choice(input.trim());
+            }
+            PromptType::ActionConsole => match self.parse_console_action(&input) {
+                Ok(action) => return PromptResult::ExecuteAction(action),
+                Err(message) => self.set_status_message(message),
+            },
+            PromptType::InsertUnicodeCharacter => {
+                if selected_index.is_some() {
+                    self.insert_picked_character(input);
+                }
+            }
             PromptType::QueryReplaceConfirm => {
                 // This is handled by InsertChar, not PromptConfirm
                 // But if somehow Enter is pressed, treat it as skip (n)
@@ -424,6 +504,12 @@ impl Editor {
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
+            PromptType::GlobalCommand => {
+                self.handle_global_command(&input);
+            }
+            PromptType::ProjectSearch => {
+                self.start_project_search(&input);
+            }
             PromptType::AsyncPrompt => {
                 // Resolve the pending async prompt callback with the input text
                 if let Some(callback_id) = self.pending_async_prompt_callback.take() {
@@ -481,7 +567,15 @@ impl Editor {
             before_len
         );
 
-        match self.active_state_mut().buffer.save_to_file(&full_path) {
+        // An empty unnamed buffer starts from the template for its new name
+        if self.active_state().buffer.file_path().is_none() {
+            self.apply_file_template(self.active_buffer(), &full_path);
+        }
+
+        let options = self.save_options();
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_save_options(options);
+        match buffer.save_to_file(&full_path) {
             Ok(()) => {
                 let after_save_idx = self.active_event_log().current_index();
                 let after_save_len = self.active_event_log().len();
@@ -880,6 +974,7 @@ impl Editor {
                 }
                 self.set_status_message(format!("Language set to {}", trimmed));
             }
+            self.activate_plugins_for_language(trimmed);
         } else {
             self.set_status_message(format!("Unknown language: {}", input));
         }
@@ -904,6 +999,45 @@ impl Editor {
     }
 
     /// Handle ConfirmCloseBuffer prompt. Returns true if early return is needed.
+    /// Reload, keep or diff a modified buffer whose file changed on disk
+    fn handle_confirm_external_change(&mut self, input: &str, buffer_id: BufferId) {
+        let Some(path) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .cloned()
+        else {
+            return;
+        };
+        let first_char = input.trim().to_lowercase().chars().next();
+        let reload_first = t!("prompt.key.reload").to_lowercase().chars().next();
+        let diff_first = t!("prompt.key.diff").to_lowercase().chars().next();
+
+        if first_char.is_some() && first_char == reload_first {
+            let result = if buffer_id == self.active_buffer() {
+                // Keeps the viewport of the active split
+                self.revert_file().map(|_| ())
+            } else {
+                self.revert_buffer_by_id(buffer_id, &path)
+            };
+            match result {
+                Ok(()) => self.set_status_message(t!("status.reverted").to_string()),
+                Err(e) => self.set_status_message(
+                    t!("file.revert_failed", error = e.to_string()).to_string(),
+                ),
+            }
+        } else if first_char.is_some() && first_char == diff_first {
+            if let Err(e) = self.open_disk_diff(buffer_id) {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        } else {
+            let name = self.get_buffer_display_name(buffer_id);
+            self.set_status_message(t!("buffer.external_change_kept", name = name).to_string());
+        }
+    }
+
     fn handle_confirm_close_buffer(&mut self, input: &str, buffer_id: BufferId) -> bool {
         let input_lower = input.trim().to_lowercase();
         let save_key = t!("prompt.key.save").to_string().to_lowercase();
@@ -961,7 +1095,7 @@ impl Editor {
     }
 
     /// Handle StopLspServer prompt confirmation.
-    fn handle_stop_lsp_server(&mut self, input: &str) {
+    pub(super) fn handle_stop_lsp_server(&mut self, input: &str) {
         let language = input.trim();
         if language.is_empty() {
             return;
@@ -1131,36 +1265,7 @@ impl Editor {
         if let Some(idx) = selected_index {
             if let Some(suggestion) = suggestions.get(idx) {
                 if let Some(path_str) = &suggestion.value {
-                    let path = std::path::PathBuf::from(path_str);
-                    let full_path = if path.is_absolute() {
-                        path
-                    } else {
-                        self.working_dir.join(&path)
-                    };
-
-                    // Record file access for frecency
-                    self.file_provider.record_access(path_str);
-
-                    match self.open_file(&full_path) {
-                        Ok(_) => {
-                            self.set_status_message(
-                                t!("buffer.opened", name = full_path.display().to_string())
-                                    .to_string(),
-                            );
-                        }
-                        Err(e) => {
-                            // Check if this is a large file encoding confirmation error
-                            if let Some(confirmation) = e.downcast_ref::<
-                                crate::model::buffer::LargeFileEncodingConfirmation,
-                            >() {
-                                self.start_large_file_encoding_confirmation(confirmation);
-                            } else {
-                                self.set_status_message(
-                                    t!("file.error_opening", error = e.to_string()).to_string(),
-                                );
-                            }
-                        }
-                    }
+                    self.open_quick_open_path(path_str);
                     return PromptResult::Done;
                 }
             }
@@ -1169,4 +1274,86 @@ impl Editor {
         self.set_status_message(t!("status.no_selection").to_string());
         PromptResult::Done
     }
+
+    /// Open a file picked in Quick Open, given its (possibly relative) path
+    fn open_quick_open_path(&mut self, path_str: &str) {
+        let path = std::path::PathBuf::from(path_str);
+        let full_path = if path.is_absolute() {
+            path
+        } else {
+            self.working_dir.join(&path)
+        };
+
+        // Record file access for frecency
+        self.file_provider.record_access(&full_path);
+
+        match self.open_file_in_background(&full_path) {
+            Ok(buffer_id) => {
+                if !self.is_buffer_loading(buffer_id) {
+                    self.set_status_message(
+                        t!("buffer.opened", name = full_path.display().to_string()).to_string(),
+                    );
+                }
+            }
+            Err(e) => {
+                // Check if this is a large file encoding confirmation error
+                if let Some(confirmation) =
+                    e.downcast_ref::<crate::model::buffer::LargeFileEncodingConfirmation>()
+                {
+                    self.start_large_file_encoding_confirmation(confirmation);
+                } else {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Handle confirmation of a multi-select prompt with marked suggestions.
+    ///
+    /// Plugin prompts receive every marked value in one `prompt_confirmed` hook;
+    /// Quick Open opens each marked file. Other prompts confirm each value in turn.
+    pub fn handle_prompt_confirm_marked(
+        &mut self,
+        input: String,
+        prompt_type: PromptType,
+        selected_index: Option<usize>,
+        marked: Vec<String>,
+    ) -> PromptResult {
+        match prompt_type {
+            PromptType::Plugin { custom_type } => {
+                self.plugin_manager.run_hook(
+                    "prompt_confirmed",
+                    HookArgs::PromptConfirmed {
+                        prompt_type: custom_type,
+                        input,
+                        selected_index,
+                        selected_values: marked,
+                    },
+                );
+                PromptResult::Done
+            }
+            PromptType::QuickOpen => {
+                for path_str in &marked {
+                    self.open_quick_open_path(path_str);
+                }
+                if marked.len() > 1 {
+                    self.set_status_message(
+                        t!("buffer.opened_many", count = marked.len()).to_string(),
+                    );
+                }
+                PromptResult::Done
+            }
+            prompt_type => {
+                for value in marked {
+                    match self.handle_prompt_confirm_input(value, prompt_type.clone(), None) {
+                        PromptResult::Done => {}
+                        result => return result,
+                    }
+                }
+                PromptResult::Done
+            }
+        }
+    }
 }
@@ -5,11 +5,18 @@
 //! - Checking for and listing recoverable files
 //! - Recovering buffers from crash
 //! - Auto-saving modified buffers
+//! - Journaling edits made between auto-saves
 //! - Cleaning up recovery files
+//! - Keeping what a crash report describes up to date
 
 use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+use std::path::PathBuf;
 
+use crate::model::buffer::JournalEdits;
 use crate::model::event::BufferId;
+use crate::services::crash_report::{self, BufferSummary};
+use crate::view::prompt::PromptType;
 
 use super::Editor;
 
@@ -36,13 +43,139 @@ impl Editor {
         Ok(self.recovery_service.list_recoverable()?)
     }
 
+    /// Ask whether to recover the unsaved changes left by a crashed session
+    ///
+    /// Does nothing unless a crash left recovery files behind.
+    pub fn offer_crash_recovery(&mut self) {
+        if !self.has_recovery_files().unwrap_or(false) {
+            return;
+        }
+        let count = self.list_recoverable_files().map_or(0, |e| e.len());
+        let recover_key = t!("prompt.key.recover").to_string();
+        let discard_key = t!("prompt.key.discard").to_string();
+        let compare_key = t!("prompt.key.compare").to_string();
+        self.start_prompt(
+            t!(
+                "prompt.crash_recovery",
+                count = count,
+                recover_key = recover_key,
+                discard_key = discard_key,
+                compare_key = compare_key
+            )
+            .to_string(),
+            PromptType::ConfirmCrashRecovery,
+        );
+    }
+
+    /// Tell the user where the report on a crash since the last start is
+    ///
+    /// It also goes to the warning log, since the recovery prompt may hide
+    /// the status message.
+    pub fn report_previous_crash(&mut self) {
+        if let Some(report) = crash_report::take_last_crash() {
+            tracing::warn!(
+                "The last session crashed. Crash report: {}",
+                report.display()
+            );
+            self.set_status_message(
+                t!("recovery.crash_report", path = report.display().to_string()).to_string(),
+            );
+        }
+    }
+
+    /// Note the open buffers for a crash report
+    ///
+    /// Only names, sizes and state are kept, never content.
+    pub fn update_crash_context(&self) {
+        let active = self.active_buffer();
+        let mut buffers: Vec<BufferSummary> = self
+            .buffers
+            .iter()
+            .filter_map(|(id, state)| {
+                let metadata = self.buffer_metadata.get(id)?;
+                Some(BufferSummary {
+                    id: id.0,
+                    name: metadata.display_name.clone(),
+                    path: metadata.file_path().cloned(),
+                    language: state.language.clone(),
+                    size: state.buffer.len(),
+                    lines: state.buffer.line_count(),
+                    encoding: state.buffer.encoding().display_name().to_string(),
+                    line_ending: state.buffer.line_ending().display_name().to_string(),
+                    modified: state.buffer.is_modified(),
+                    unloaded: state.buffer.is_unloaded(),
+                    read_only: metadata.read_only,
+                    active: *id == active,
+                })
+            })
+            .collect();
+        buffers.sort_by_key(|buffer| buffer.id);
+        crash_report::set_editor_state((self.terminal_width, self.terminal_height), buffers);
+    }
+
+    /// Journal the edits made before a panic, so the next start can offer
+    /// to recover them
+    pub fn save_recovery_after_panic(&mut self) {
+        if let Err(e) = self.journal_unsaved_edits() {
+            tracing::error!("Failed to save unsaved changes after a crash: {}", e);
+        }
+    }
+
+    /// Handle the answer to [`Self::offer_crash_recovery`]
+    ///
+    /// Comparing recovers the buffers and opens a diff of each against the
+    /// file on disk.
+    pub(super) fn handle_confirm_crash_recovery(&mut self, input: &str) {
+        let first_char = input.trim().to_lowercase().chars().next();
+        let recover_first = t!("prompt.key.recover").to_lowercase().chars().next();
+        let compare_first = t!("prompt.key.compare").to_lowercase().chars().next();
+
+        if first_char.is_some() && (first_char == recover_first || first_char == compare_first) {
+            let recovered = match self.recover_buffers() {
+                Ok(recovered) => recovered,
+                Err(e) => {
+                    self.set_status_message(
+                        t!("recovery.failed", error = e.to_string()).to_string(),
+                    );
+                    return;
+                }
+            };
+            if first_char == compare_first {
+                for &buffer_id in &recovered {
+                    let on_disk = self
+                        .buffer_metadata
+                        .get(&buffer_id)
+                        .and_then(|m| m.file_path())
+                        .is_some_and(|p| self.filesystem.exists(p));
+                    if on_disk {
+                        if let Err(e) = self.open_disk_diff(buffer_id) {
+                            tracing::warn!("Failed to compare recovered buffer: {}", e);
+                        }
+                    }
+                }
+            }
+            self.set_status_message(t!("recovery.recovered", count = recovered.len()).to_string());
+        } else {
+            match self.discard_all_recovery() {
+                Ok(_) => self.set_status_message(t!("recovery.discarded").to_string()),
+                Err(e) => self
+                    .set_status_message(t!("recovery.failed", error = e.to_string()).to_string()),
+            }
+        }
+    }
+
     /// Recover all buffers from recovery files
     /// Returns the number of buffers recovered
     pub fn recover_all_buffers(&mut self) -> AnyhowResult<usize> {
-        use crate::services::recovery::RecoveryResult;
+        Ok(self.recover_buffers()?.len())
+    }
+
+    /// Recover all buffers from recovery files, returning the recovered buffers
+    fn recover_buffers(&mut self) -> AnyhowResult<Vec<BufferId>> {
+        use crate::services::recovery::{JournalEdit, RecoveryResult};
 
         let entries = self.recovery_service.list_recoverable()?;
-        let mut recovered_count = 0;
+        let mut recovered = Vec::new();
 
         for entry in entries {
             match self.recovery_service.accept_recovery(&entry) {
@@ -64,7 +197,7 @@ impl Editor {
                                 state.buffer.insert(0, &text);
                                 // Mark as modified since it differs from disk
                                 state.buffer.set_modified(true);
-                                recovered_count += 1;
+                                recovered.push(self.active_buffer());
                                 tracing::info!("Recovered buffer: {}", path.display());
                             }
                             Err(e) => {
@@ -84,13 +217,14 @@ impl Editor {
                         let state = self.active_state_mut();
                         state.buffer.insert(0, &text);
                         state.buffer.set_modified(true);
-                        recovered_count += 1;
+                        recovered.push(self.active_buffer());
                         tracing::info!("Recovered unsaved buffer");
                     }
                 }
                 Ok(RecoveryResult::RecoveredChunks {
                     original_path,
                     chunks,
+                    journal,
                 }) => {
                     // Chunked recovery for large files - apply chunks directly
                     if self.open_file(&original_path).is_ok() {
@@ -108,9 +242,25 @@ impl Editor {
                             state.buffer.insert(chunk.offset, &text);
                         }
 
+                        // Then replay the edits journaled after the chunks
+                        for edit in journal {
+                            let total = state.buffer.total_bytes();
+                            match edit {
+                                JournalEdit::Insert { offset, text } => {
+                                    state.buffer.insert(offset.min(total), &text);
+                                }
+                                JournalEdit::Delete { offset, len } => {
+                                    let start = offset.min(total);
+                                    state
+                                        .buffer
+                                        .delete(start..offset.saturating_add(len).min(total));
+                                }
+                            }
+                        }
+
                         // Mark as modified since it differs from disk
                         state.buffer.set_modified(true);
-                        recovered_count += 1;
+                        recovered.push(self.active_buffer());
                         tracing::info!("Recovered buffer with chunks: {}", original_path.display());
                     }
                 }
@@ -135,7 +285,7 @@ impl Editor {
             }
         }
 
-        Ok(recovered_count)
+        Ok(recovered)
     }
 
     /// Discard all recovery files (user decided not to recover)
@@ -165,6 +315,66 @@ impl Editor {
             return Ok(0);
         }
 
+        let buffer_info = self.recovery_targets();
+
+        // Early exit if nothing to save
+        if buffer_info.is_empty() {
+            // Still update the timer to avoid checking buffers too frequently
+            self.last_auto_recovery_save = self.time_source.now();
+            return Ok(0);
+        }
+
+        let mut saved_count = 0;
+        for (buffer_id, recovery_id, path) in buffer_info {
+            if self.write_recovery_snapshot(buffer_id, &recovery_id, path)? {
+                saved_count += 1;
+            }
+        }
+
+        self.last_auto_recovery_save = self.time_source.now();
+        Ok(saved_count)
+    }
+
+    /// Write edits made since the last auto-recovery-save to the journal
+    ///
+    /// Called every iteration of the main loop so a crash loses no edits. A
+    /// buffer's first edit (or one the journal can't describe) writes a full
+    /// recovery snapshot instead, which later journal entries build on.
+    pub fn journal_unsaved_edits(&mut self) -> AnyhowResult<usize> {
+        if !self.recovery_service.is_enabled() {
+            return Ok(0);
+        }
+
+        let mut journaled = 0;
+        for (buffer_id, recovery_id, path) in self.recovery_targets() {
+            let has_snapshot = self.recovery_service.has_snapshot(&recovery_id);
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            let edits = if has_snapshot {
+                state.buffer.take_journal_edits()
+            } else {
+                None
+            };
+            match edits {
+                Some(JournalEdits::Edits(edits)) => {
+                    if !edits.is_empty() {
+                        self.recovery_service.append_journal(&recovery_id, &edits)?;
+                        journaled += 1;
+                    }
+                }
+                Some(JournalEdits::NeedsSnapshot) | None => {
+                    if self.write_recovery_snapshot(buffer_id, &recovery_id, path)? {
+                        journaled += 1;
+                    }
+                }
+            }
+        }
+        Ok(journaled)
+    }
+
+    /// Modified buffers that take part in recovery, with their recovery IDs
+    fn recovery_targets(&mut self) -> Vec<(BufferId, String, Option<PathBuf>)> {
         // Collect buffer IDs that need recovery first (immutable pass)
         // Skip composite buffers and hidden buffers (they should not be saved for recovery)
         let buffers_needing_recovery: Vec<_> = self
@@ -212,7 +422,7 @@ impl Editor {
         }
 
         // Now collect full buffer info with stable recovery IDs
-        let buffer_info: Vec<_> = buffers_needing_recovery
+        buffers_needing_recovery
             .into_iter()
             .filter_map(|buffer_id| {
                 let state = self.buffers.get(&buffer_id)?;
@@ -238,100 +448,99 @@ impl Editor {
                     None
                 }
             })
-            .collect();
-
-        // Early exit if nothing to save
-        if buffer_info.is_empty() {
-            // Still update the timer to avoid checking buffers too frequently
-            self.last_auto_recovery_save = self.time_source.now();
-            return Ok(0);
-        }
-
-        let mut saved_count = 0;
-
-        for (buffer_id, recovery_id, path) in buffer_info {
-            if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                let line_count = state.buffer.line_count();
-
-                // For large files, use chunked recovery to avoid reading entire file
-                if state.buffer.is_large_file() {
-                    let chunks = state.buffer.get_recovery_chunks();
+            .collect()
+    }
 
-                    // If no modifications, skip saving (original file is recovery)
-                    if chunks.is_empty() {
-                        state.buffer.set_recovery_pending(false);
-                        continue;
-                    }
+    /// Save a buffer's full content for recovery and restart its journal
+    ///
+    /// Returns false if there was nothing to save.
+    fn write_recovery_snapshot(
+        &mut self,
+        buffer_id: BufferId,
+        recovery_id: &str,
+        path: Option<PathBuf>,
+    ) -> AnyhowResult<bool> {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return Ok(false);
+        };
+        let line_count = state.buffer.line_count();
 
-                    // Convert to RecoveryChunk format
-                    let recovery_chunks: Vec<_> = chunks
-                        .into_iter()
-                        .map(|(offset, content)| {
-                            crate::services::recovery::types::RecoveryChunk::new(
-                                offset, 0, // For insertions, original_len is 0
-                                content,
-                            )
-                        })
-                        .collect();
-
-                    let original_size = state.buffer.original_file_size().unwrap_or(0);
-                    let final_size = state.buffer.total_bytes();
-
-                    tracing::debug!(
-                        "auto_recovery_save_dirty_buffers: large file recovery - original_size={}, final_size={}, path={:?}",
-                        original_size,
-                        final_size,
-                        path
-                    );
+        // For large files, use chunked recovery to avoid reading entire file
+        if state.buffer.is_large_file() {
+            let chunks = state.buffer.get_recovery_chunks();
 
-                    self.recovery_service.save_buffer(
-                        &recovery_id,
-                        recovery_chunks,
-                        path.as_deref(),
-                        None,
-                        line_count,
-                        original_size,
-                        final_size,
-                    )?;
-
-                    tracing::debug!(
-                        "Saved chunked recovery for large file (original: {} bytes, final: {} bytes)",
-                        original_size,
-                        final_size
-                    );
-                } else {
-                    // For small files, save full content as a single chunk
-                    let total_bytes = state.buffer.total_bytes();
-                    let content = match state.buffer.get_text_range_mut(0, total_bytes) {
-                        Ok(bytes) => bytes,
-                        Err(e) => {
-                            tracing::warn!("Failed to get buffer content for recovery save: {}", e);
-                            continue;
-                        }
-                    };
-
-                    let chunks = vec![crate::services::recovery::types::RecoveryChunk::new(
-                        0, 0, content,
-                    )];
-                    self.recovery_service.save_buffer(
-                        &recovery_id,
-                        chunks,
-                        path.as_deref(),
-                        None,
-                        line_count,
-                        0,           // original_file_size = 0 for new/small files
-                        total_bytes, // final_size
-                    )?;
-                }
-
-                // Clear recovery_pending flag after successful save
+            // If no modifications, skip saving (original file is recovery)
+            if chunks.is_empty() {
                 state.buffer.set_recovery_pending(false);
-                saved_count += 1;
+                return Ok(false);
             }
+
+            // Convert to RecoveryChunk format
+            let recovery_chunks: Vec<_> = chunks
+                .into_iter()
+                .map(|(offset, content)| {
+                    crate::services::recovery::types::RecoveryChunk::new(
+                        offset, 0, // For insertions, original_len is 0
+                        content,
+                    )
+                })
+                .collect();
+
+            let original_size = state.buffer.original_file_size().unwrap_or(0);
+            let final_size = state.buffer.total_bytes();
+
+            tracing::debug!(
+                "auto_recovery_save_dirty_buffers: large file recovery - original_size={}, final_size={}, path={:?}",
+                original_size,
+                final_size,
+                path
+            );
+
+            self.recovery_service.save_buffer(
+                recovery_id,
+                recovery_chunks,
+                path.as_deref(),
+                None,
+                line_count,
+                original_size,
+                final_size,
+            )?;
+
+            tracing::debug!(
+                "Saved chunked recovery for large file (original: {} bytes, final: {} bytes)",
+                original_size,
+                final_size
+            );
+        } else {
+            // For small files, save full content as a single chunk
+            let total_bytes = state.buffer.total_bytes();
+            let content = match state.buffer.get_text_range_mut(0, total_bytes) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!("Failed to get buffer content for recovery save: {}", e);
+                    return Ok(false);
+                }
+            };
+
+            let chunks = vec![crate::services::recovery::types::RecoveryChunk::new(
+                0, 0, content,
+            )];
+            self.recovery_service.save_buffer(
+                recovery_id,
+                chunks,
+                path.as_deref(),
+                None,
+                line_count,
+                0,           // original_file_size = 0 for new/small files
+                total_bytes, // final_size
+            )?;
         }
 
-        self.last_auto_recovery_save = self.time_source.now();
-        Ok(saved_count)
+        // Clear recovery_pending flag after successful save; the journal
+        // now starts from the saved content
+        state.buffer.set_recovery_pending(false);
+        state.buffer.set_edit_journal(true);
+        Ok(true)
     }
 
     /// Check if the active buffer is marked dirty for auto-recovery-save
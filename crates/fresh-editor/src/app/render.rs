@@ -1,17 +1,65 @@
 use super::*;
+use crate::input::actions::smart_home_position;
+use crate::primitives::markup_tags::{self, Markup};
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
+use std::time::Duration;
+
+/// How long a search may run before the UI stops waiting for it and shows
+/// the matches when they arrive
+const SEARCH_BLOCKING_WAIT: Duration = Duration::from_millis(50);
+
+/// Positions and lengths of the matches of `regex` within `range` of
+/// `content`, or None if the search was cancelled
+fn find_matches(
+    regex: &regex::Regex,
+    content: &str,
+    range: Range<usize>,
+    token: &crate::services::jobs::CancellationToken,
+) -> Option<Vec<(usize, usize)>> {
+    let start = range.start;
+    let mut matches = Vec::new();
+    for m in regex.find_iter(&content[range]) {
+        if token.is_cancelled() {
+            return None;
+        }
+        matches.push((start + m.start(), m.end() - m.start()));
+    }
+    Some(matches)
+}
 
 impl Editor {
     /// Render the editor to the terminal
     pub fn render(&mut self, frame: &mut Frame) {
         let _span = tracing::trace_span!("render").entered();
+        let render_started = std::time::Instant::now();
         let size = frame.area();
 
         // Save frame dimensions for recompute_layout (used by macro replay)
         self.cached_layout.last_frame_width = size.width;
         self.cached_layout.last_frame_height = size.height;
 
+        // Column math follows the configured ambiguous width, which may have
+        // changed since the last frame
+        crate::primitives::display_width::set_ambiguous_wide(
+            self.config.editor.ambiguous_width == crate::config::AmbiguousWidth::Wide,
+        );
+
+        // Trailing whitespace only matters in text being edited, not in
+        // panels, terminals or read-only views
+        let highlight_trailing = self.config.editor.highlight_trailing_whitespace;
+        let hyperlinks = self.config.editor.hyperlinks;
+        for (id, state) in self.buffers.iter_mut() {
+            state.link_overlay.enabled = hyperlinks;
+            state.trailing_whitespace_overlay.enabled = highlight_trailing
+                && !state.editing_disabled
+                && !self.terminal_buffers.contains_key(id)
+                && !self
+                    .buffer_metadata
+                    .get(id)
+                    .is_some_and(|meta| meta.is_virtual());
+        }
+
         // For scroll sync groups, we need to update the active split's viewport position BEFORE
         // calling sync_scroll_groups, so that the sync reads the correct position.
         // Otherwise, cursor movements like 'G' (go to end) won't sync properly because
@@ -26,6 +74,9 @@ impl Editor {
         // NOTE: Viewport sync with cursor is handled by split_rendering.rs which knows the
         // correct content area dimensions. Don't sync here with incorrect EditorState viewport size.
 
+        // Read back buffers unloaded over the memory budget before drawing them
+        self.load_shown_buffers();
+
         // Prepare all buffers for rendering (pre-load viewport data for lazy loading)
         // Each split may have a different viewport position on the same buffer
         let mut semantic_ranges: std::collections::HashMap<BufferId, (usize, usize)> =
@@ -159,8 +210,18 @@ impl Editor {
             // Get remote connection info before mutable borrow of file_explorer
             let remote_connection = self.remote_connection_info().map(|s| s.to_string());
 
+            // Keep the changed-files filter current with git status and unsaved edits
+            let changed_paths = self
+                .file_explorer
+                .as_ref()
+                .filter(|explorer| explorer.show_only_changed())
+                .map(|_| self.file_explorer_changed_paths());
+
             // Render file explorer (only if we have it - during sync we just keep the area reserved)
             if let Some(ref mut explorer) = self.file_explorer {
+                if let Some(paths) = changed_paths {
+                    explorer.set_changed_paths(paths);
+                }
                 let is_focused = self.key_context == KeyContext::FileExplorer;
 
                 // Build set of files with unsaved changes
@@ -351,6 +412,12 @@ impl Editor {
             }
         }
 
+        // Keep highlighters in sync with the rainbow delimiters setting
+        let rainbow_delimiters = self.config.editor.rainbow_delimiters;
+        for state in self.buffers.values_mut() {
+            state.highlighter.set_rainbow_delimiters(rainbow_delimiters);
+        }
+
         // Render editor content (same for both layouts)
         let lsp_waiting = self.pending_completion_request.is_some()
             || self.pending_goto_definition_request.is_some();
@@ -398,6 +465,7 @@ impl Editor {
             maximize_split_areas,
             view_line_mappings,
             horizontal_scrollbar_areas,
+            mut screen_links,
         ) = SplitRenderer::render_content(
             frame,
             editor_content_area,
@@ -429,6 +497,8 @@ impl Editor {
             self.config.editor.show_horizontal_scrollbar,
         );
 
+        self.render_image_previews(frame, &split_areas);
+
         // Detect viewport changes and fire hooks
         // Compare against previous frame's viewport state (stored in self.previous_viewports)
         // This correctly detects changes from scroll events that happen before render()
@@ -579,93 +649,13 @@ impl Editor {
         }
 
         // Clone all immutable values before the mutable borrow
-        let display_name = self
-            .buffer_metadata
-            .get(&self.active_buffer())
-            .map(|m| m.display_name.clone())
-            .unwrap_or_else(|| "[No Name]".to_string());
-        let status_message = self.status_message.clone();
-        let plugin_status_message = self.plugin_status_message.clone();
         let prompt = self.prompt.clone();
-        let lsp_status = self.lsp_status.clone();
         let theme = self.theme.clone();
         let keybindings_cloned = self.keybindings.clone(); // Clone the keybindings
-        let chord_state_cloned = self.chord_state.clone(); // Clone the chord state
-
-        // Get update availability info
-        let update_available = self.latest_version().map(|v| v.to_string());
 
         // Render status bar (hidden when suggestions or file browser popup is shown)
         if !has_suggestions && !has_file_browser {
-            // Get warning level for colored indicator (respects config setting)
-            let (warning_level, general_warning_count) =
-                if self.config.warnings.show_status_indicator {
-                    (
-                        self.get_effective_warning_level(),
-                        self.get_general_warning_count(),
-                    )
-                } else {
-                    (WarningLevel::None, 0)
-                };
-
-            // Compute status bar hover state for styling
-            use crate::view::ui::status_bar::StatusBarHover;
-            let status_bar_hover = match &self.mouse_state.hover_target {
-                Some(HoverTarget::StatusBarLspIndicator) => StatusBarHover::LspIndicator,
-                Some(HoverTarget::StatusBarWarningBadge) => StatusBarHover::WarningBadge,
-                Some(HoverTarget::StatusBarLineEndingIndicator) => {
-                    StatusBarHover::LineEndingIndicator
-                }
-                Some(HoverTarget::StatusBarEncodingIndicator) => StatusBarHover::EncodingIndicator,
-                Some(HoverTarget::StatusBarLanguageIndicator) => StatusBarHover::LanguageIndicator,
-                _ => StatusBarHover::None,
-            };
-
-            // Get remote connection info if editing remote files
-            let remote_connection = self.remote_connection_info().map(|s| s.to_string());
-
-            // Get session name for display (only in session mode)
-            let session_name = self.session_name().map(|s| s.to_string());
-
-            let active_split = self.split_manager.active_split();
-            let active_buf = self.active_buffer();
-            let default_cursors = crate::model::cursor::Cursors::new();
-            let status_cursors = self
-                .split_view_states
-                .get(&active_split)
-                .map(|vs| &vs.cursors)
-                .unwrap_or(&default_cursors);
-            let status_bar_layout = StatusBarRenderer::render_status_bar(
-                frame,
-                main_chunks[status_bar_idx],
-                self.buffers.get_mut(&active_buf).unwrap(),
-                status_cursors,
-                &status_message,
-                &plugin_status_message,
-                &lsp_status,
-                &theme,
-                &display_name,
-                &keybindings_cloned,          // Pass the cloned keybindings
-                &chord_state_cloned,          // Pass the cloned chord state
-                update_available.as_deref(),  // Pass update availability
-                warning_level,                // Pass warning level for colored indicator
-                general_warning_count,        // Pass general warning count for badge
-                status_bar_hover,             // Pass hover state for indicator styling
-                remote_connection.as_deref(), // Pass remote connection info
-                session_name.as_deref(),      // Pass session name for status bar display
-            );
-
-            // Store status bar layout for click detection
-            let status_bar_area = main_chunks[status_bar_idx];
-            self.cached_layout.status_bar_area =
-                Some((status_bar_area.y, status_bar_area.x, status_bar_area.width));
-            self.cached_layout.status_bar_lsp_area = status_bar_layout.lsp_indicator;
-            self.cached_layout.status_bar_warning_area = status_bar_layout.warning_badge;
-            self.cached_layout.status_bar_line_ending_area =
-                status_bar_layout.line_ending_indicator;
-            self.cached_layout.status_bar_encoding_area = status_bar_layout.encoding_indicator;
-            self.cached_layout.status_bar_language_area = status_bar_layout.language_indicator;
-            self.cached_layout.status_bar_message_area = status_bar_layout.message_area;
+            self.render_status_bar(frame, main_chunks[status_bar_idx]);
         }
 
         // Render search options bar when in search prompt
@@ -745,6 +735,16 @@ impl Editor {
             }
         }
 
+        // The history scrubber takes the prompt line while open
+        if let Some(view) = self.history_scrubber_view() {
+            StatusBarRenderer::render_history_scrubber(
+                frame,
+                main_chunks[prompt_line_idx],
+                &view,
+                &theme,
+            );
+        }
+
         // Render popups from the active buffer state
         // Clone theme to avoid borrow checker issues with active_state_mut()
         let theme_clone = self.theme.clone();
@@ -956,6 +956,12 @@ impl Editor {
             }
         }
 
+        // Performance HUD goes over everything else
+        self.record_render_time(render_started.elapsed());
+        if let Some(ref hud) = self.perf_hud {
+            crate::view::perf_hud::render_perf_hud(frame, size, hud, &self.theme);
+        }
+
         // Render software mouse cursor when GPM is active
         // GPM can't draw its cursor on the alternate screen buffer used by TUI apps,
         // so we draw our own cursor at the tracked mouse position.
@@ -993,6 +999,22 @@ impl Editor {
             }
         }
 
+        // Reference highlights refreshed during this frame may want LSP results
+        self.maybe_request_document_highlights();
+
+        // Images are only drawn where nothing was rendered over them
+        self.drop_covered_images(frame);
+
+        // The same goes for links
+        if !hyperlinks {
+            screen_links.clear();
+        }
+        screen_links.retain(|link| link.is_shown(frame.buffer_mut()));
+        self.screen_links.set(screen_links);
+
+        // Let wide ambiguous characters cover the space after them
+        crate::primitives::display_width::skip_ambiguous_padding(frame.buffer_mut());
+
         // Convert all colors for terminal capability (256/16 color fallback)
         crate::view::color_support::convert_buffer_colors(
             frame.buffer_mut(),
@@ -1000,6 +1022,103 @@ impl Editor {
         );
     }
 
+    /// Render the status bar into `area`
+    pub(super) fn render_status_bar(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        // Clone all immutable values before the mutable borrow
+        let display_name = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .map(|m| m.display_name.clone())
+            .unwrap_or_else(|| "[No Name]".to_string());
+        let status_message = self.status_message.clone();
+        let plugin_status_message = self.plugin_status_message.clone();
+        let lsp_status = self.lsp_status.clone();
+        let theme = self.theme.clone();
+        let keybindings_cloned = self.keybindings.clone();
+        let chord_state_cloned = self.chord_state.clone();
+
+        // Get update availability info
+        let update_available = self.latest_version().map(|v| v.to_string());
+
+        // Get warning level for colored indicator (respects config setting)
+        let (warning_level, general_warning_count) = if self.config.warnings.show_status_indicator {
+            (
+                self.get_effective_warning_level(),
+                self.get_general_warning_count(),
+            )
+        } else {
+            (WarningLevel::None, 0)
+        };
+
+        // Compute status bar hover state for styling
+        use crate::view::ui::status_bar::StatusBarHover;
+        let status_bar_hover = match &self.mouse_state.hover_target {
+            Some(HoverTarget::StatusBarLspIndicator) => StatusBarHover::LspIndicator,
+            Some(HoverTarget::StatusBarWarningBadge) => StatusBarHover::WarningBadge,
+            Some(HoverTarget::StatusBarLineEndingIndicator) => StatusBarHover::LineEndingIndicator,
+            Some(HoverTarget::StatusBarEncodingIndicator) => StatusBarHover::EncodingIndicator,
+            Some(HoverTarget::StatusBarLanguageIndicator) => StatusBarHover::LanguageIndicator,
+            Some(HoverTarget::StatusBarPluginSegment(index)) => {
+                StatusBarHover::PluginSegment(*index)
+            }
+            _ => StatusBarHover::None,
+        };
+
+        // Get remote connection info if editing remote files
+        let remote_connection = self.remote_connection_info().map(|s| s.to_string());
+
+        // Get session name for display (only in session mode)
+        let session_name = self.session_name().map(|s| s.to_string());
+
+        let selection_stats = self.selection_stats_text();
+
+        let active_split = self.split_manager.active_split();
+        let active_buf = self.active_buffer();
+        let default_cursors = crate::model::cursor::Cursors::new();
+        let status_cursors = self
+            .split_view_states
+            .get(&active_split)
+            .map(|vs| &vs.cursors)
+            .unwrap_or(&default_cursors);
+        let status_segments: Vec<_> = self
+            .status_segments
+            .iter()
+            .map(|segment| segment.options.clone())
+            .collect();
+        let status_bar_layout = StatusBarRenderer::render_status_bar(
+            frame,
+            area,
+            self.buffers.get_mut(&active_buf).unwrap(),
+            status_cursors,
+            &status_message,
+            &plugin_status_message,
+            &lsp_status,
+            &theme,
+            &display_name,
+            &keybindings_cloned,          // Pass the cloned keybindings
+            &chord_state_cloned,          // Pass the cloned chord state
+            update_available.as_deref(),  // Pass update availability
+            warning_level,                // Pass warning level for colored indicator
+            general_warning_count,        // Pass general warning count for badge
+            status_bar_hover,             // Pass hover state for indicator styling
+            remote_connection.as_deref(), // Pass remote connection info
+            session_name.as_deref(),      // Pass session name for status bar display
+            self.git_status.as_ref(),     // Pass git branch/dirty state
+            &status_segments,             // Pass plugin segments
+            selection_stats.as_deref(),   // Pass selection statistics
+        );
+
+        // Store status bar layout for click detection
+        self.cached_layout.status_bar_area = Some((area.y, area.x, area.width));
+        self.cached_layout.status_bar_lsp_area = status_bar_layout.lsp_indicator;
+        self.cached_layout.status_bar_warning_area = status_bar_layout.warning_badge;
+        self.cached_layout.status_bar_line_ending_area = status_bar_layout.line_ending_indicator;
+        self.cached_layout.status_bar_encoding_area = status_bar_layout.encoding_indicator;
+        self.cached_layout.status_bar_language_area = status_bar_layout.language_indicator;
+        self.cached_layout.status_bar_message_area = status_bar_layout.message_area;
+        self.cached_layout.status_bar_plugin_segment_areas = status_bar_layout.plugin_segments;
+    }
+
     /// Render the Quick Open hints line showing available mode prefixes
     fn render_quick_open_hints(
         frame: &mut Frame,
@@ -1989,7 +2108,7 @@ impl Editor {
             }
             if let Some(client) = lsp.get_handle_mut(&language) {
                 // Send didSave with the full text content
-                if let Err(e) = client.did_save(uri, Some(full_text)) {
+                if let Err(e) = client.did_save(uri, Some(full_text.clone())) {
                     tracing::warn!("Failed to send didSave to LSP: {}", e);
                 } else {
                     tracing::info!("Successfully sent didSave to LSP");
@@ -1999,13 +2118,35 @@ impl Editor {
             }
         } else {
             tracing::debug!("notify_lsp_save: no LSP manager available");
+            return;
         }
+
+        self.with_additional_lsp_for_buffer(buffer_id, true, |_, handle, uri| {
+            if let Err(e) = handle.did_save(uri.clone(), Some(full_text.clone())) {
+                tracing::warn!("Failed to send didSave to additional LSP server: {}", e);
+            }
+        });
     }
 
     /// Convert an action into a list of events to apply to the active buffer
     /// Returns None for actions that don't generate events (like Quit)
     pub fn action_to_events(&mut self, action: Action) -> Option<Vec<Event>> {
-        let tab_size = self.config.editor.tab_size;
+        // Word motions stop inside words with `editor.subword_movement`
+        let action = if self.config.editor.subword_movement {
+            match action {
+                Action::MoveWordLeft => Action::MoveSubWordLeft,
+                Action::MoveWordRight | Action::MoveWordEnd => Action::MoveSubWordRight,
+                Action::SelectWordLeft => Action::SelectSubWordLeft,
+                Action::SelectWordRight | Action::SelectWordEnd => Action::SelectSubWordRight,
+                action => action,
+            }
+        } else {
+            action
+        };
+
+        // Indent with the buffer's width, which may come from its language or
+        // from the file's detected indentation
+        let tab_size = self.active_state().buffer_settings.tab_size;
         let auto_indent = self.config.editor.auto_indent;
         let estimated_line_length = self.config.editor.estimated_line_length;
 
@@ -2050,13 +2191,13 @@ impl Editor {
         &mut self,
         action: &Action,
         split_id: SplitId,
-        _estimated_line_length: usize,
+        estimated_line_length: usize,
     ) -> Option<Vec<Event>> {
         // Classify the action
         enum VisualAction {
             UpDown { direction: i8, is_select: bool },
             LineEnd { is_select: bool },
-            LineStart { is_select: bool },
+            LineStart { is_select: bool, smart: bool },
         }
 
         // Note: We don't intercept BlockSelectUp/Down because block selection has
@@ -2087,12 +2228,22 @@ impl Editor {
             Action::SelectLineEnd if self.config.editor.line_wrap => {
                 VisualAction::LineEnd { is_select: true }
             }
-            Action::MoveLineStart if self.config.editor.line_wrap => {
-                VisualAction::LineStart { is_select: false }
-            }
-            Action::SelectLineStart if self.config.editor.line_wrap => {
-                VisualAction::LineStart { is_select: true }
-            }
+            Action::MoveLineStart if self.config.editor.line_wrap => VisualAction::LineStart {
+                is_select: false,
+                smart: false,
+            },
+            Action::SelectLineStart if self.config.editor.line_wrap => VisualAction::LineStart {
+                is_select: true,
+                smart: false,
+            },
+            Action::SmartHome if self.config.editor.line_wrap => VisualAction::LineStart {
+                is_select: false,
+                smart: true,
+            },
+            Action::SelectSmartHome if self.config.editor.line_wrap => VisualAction::LineStart {
+                is_select: true,
+                smart: true,
+            },
             _ => return None, // Not a visual line action
         };
 
@@ -2185,6 +2336,18 @@ impl Editor {
                         None => return None,
                     }
                 }
+                VisualAction::LineStart { smart: true, .. }
+                    if self.is_first_visual_row(split_id, position) =>
+                {
+                    // On the first row of a line, smart Home toggles between the
+                    // indentation and the line start like it does without wrapping
+                    let buffer_id = self.split_manager.active_buffer_id()?;
+                    let state = self.buffers.get_mut(&buffer_id)?;
+                    match smart_home_position(&mut state.buffer, position, estimated_line_length) {
+                        Some(pos) => (pos, 0),
+                        None => return None,
+                    }
+                }
                 VisualAction::LineStart { .. } => {
                     // Allow advancing to previous visual segment only if not at a physical line start
                     let allow_advance = !at_line_start;
@@ -2201,7 +2364,7 @@ impl Editor {
             let is_select = match &visual_action {
                 VisualAction::UpDown { is_select, .. } => *is_select,
                 VisualAction::LineEnd { is_select } => *is_select,
-                VisualAction::LineStart { is_select } => *is_select,
+                VisualAction::LineStart { is_select, .. } => *is_select,
             };
 
             let new_anchor = if is_select {
@@ -2230,6 +2393,22 @@ impl Editor {
         }
     }
 
+    /// Whether `position` is on the first visual row of its line
+    fn is_first_visual_row(&self, split_id: SplitId, position: usize) -> bool {
+        let Some(row_start) = self
+            .cached_layout
+            .visual_line_start(split_id, position, false)
+        else {
+            return false;
+        };
+        row_start == 0
+            || self
+                .active_state()
+                .buffer
+                .slice_bytes(row_start - 1..row_start)
+                == b"\n"
+    }
+
     // === Search and Replace Methods ===
 
     /// Clear all search highlights from the active buffer and reset search state
@@ -2427,13 +2606,87 @@ impl Editor {
             }
         };
 
+        // A new search replaces one still running in the background
+        if let Some((job, _)) = self.search_job.take() {
+            self.jobs.cancel(job);
+        }
+
         // Find all matches within the search range (store position and length for overlays)
-        let search_slice = &buffer_content[search_start..search_end];
-        let match_ranges: Vec<(usize, usize)> = regex
-            .find_iter(search_slice)
-            .map(|m| (search_start + m.start(), m.end() - m.start()))
-            .collect();
+        let buffer_id = self.active_buffer();
+        let buffer_len = buffer_content.len();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let job = self.jobs.spawn_blocking(Some(buffer_id), move |token| {
+            let _ = tx.send(find_matches(
+                &regex,
+                &buffer_content,
+                search_start..search_end,
+                token,
+            ));
+        });
+        let Some(job) = job else {
+            self.set_status_message(t!("error.buffer_not_loaded").to_string());
+            return;
+        };
+        let match_ranges = match rx.recv_timeout(SEARCH_BLOCKING_WAIT) {
+            Ok(Some(match_ranges)) => match_ranges,
+            Ok(None) => return,
+            Err(_) => {
+                // Slow search: keep the UI responsive and show the matches
+                // when `AsyncMessage::SearchFinished` arrives
+                if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                    let sender = bridge.sender();
+                    let query = query.to_string();
+                    let search_range = search_range.clone();
+                    runtime.spawn_blocking(move || {
+                        if let Ok(Some(matches)) = rx.recv() {
+                            let _ = sender.send(AsyncMessage::SearchFinished {
+                                buffer_id,
+                                buffer_len,
+                                query,
+                                search_range,
+                                matches,
+                            });
+                        }
+                    });
+                }
+                self.search_job = Some((job, buffer_id));
+                self.set_status_message(t!("search.searching", query = query).to_string());
+                return;
+            }
+        };
+
+        self.show_search_matches(query, search_range, match_ranges);
+    }
+
+    /// Show the results of a search that finished in the background, unless
+    /// it was replaced by another search or the buffer changed meanwhile
+    pub(super) fn handle_search_finished(
+        &mut self,
+        buffer_id: BufferId,
+        buffer_len: usize,
+        query: String,
+        search_range: Option<Range<usize>>,
+        matches: Vec<(usize, usize)>,
+    ) {
+        if self.search_job.map(|(_, id)| id) != Some(buffer_id) {
+            return;
+        }
+        self.search_job = None;
+        if self.active_buffer() != buffer_id || self.active_state().buffer.len() != buffer_len {
+            self.set_status_message(t!("search.cancelled").to_string());
+            return;
+        }
+        self.show_search_matches(&query, search_range, matches);
+    }
 
+    /// Highlight the matches of a finished search and move to the first one
+    /// after the cursor
+    fn show_search_matches(
+        &mut self,
+        query: &str,
+        search_range: Option<Range<usize>>,
+        match_ranges: Vec<(usize, usize)>,
+    ) {
         if match_ranges.is_empty() {
             self.search_state = None;
             let msg = if search_range.is_some() {
@@ -3411,48 +3664,6 @@ impl Editor {
         self.set_status_message(t!("search.replaced_count", count = replacements_made).to_string());
     }
 
-    /// Smart home: toggle between line start and first non-whitespace character
-    pub(super) fn smart_home(&mut self) {
-        let estimated_line_length = self.config.editor.estimated_line_length;
-        let cursor = *self.active_cursors().primary();
-        let cursor_id = self.active_cursors().primary_id();
-        let state = self.active_state_mut();
-
-        // Get line information
-        let mut iter = state
-            .buffer
-            .line_iterator(cursor.position, estimated_line_length);
-        if let Some((line_start, line_content)) = iter.next_line() {
-            // Find first non-whitespace character
-            let first_non_ws = line_content
-                .chars()
-                .take_while(|c| *c != '\n')
-                .position(|c| !c.is_whitespace())
-                .map(|offset| line_start + offset)
-                .unwrap_or(line_start);
-
-            // Toggle: if at first non-ws, go to line start; otherwise go to first non-ws
-            let new_pos = if cursor.position == first_non_ws {
-                line_start
-            } else {
-                first_non_ws
-            };
-
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
-            };
-
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
-        }
-    }
-
     /// Toggle comment on the current line or selection
     pub(super) fn toggle_comment(&mut self) {
         // Determine comment prefix from language config
@@ -3741,68 +3952,103 @@ impl Editor {
         }
     }
 
-    /// Jump to next error/diagnostic
-    pub(super) fn jump_to_next_error(&mut self) {
-        let diagnostic_ns = self.lsp_diagnostic_namespace.clone();
-        let cursor_pos = self.active_cursors().primary().position;
-        let cursor_id = self.active_cursors().primary_id();
-        let cursor = *self.active_cursors().primary();
+    /// Markup elements of the active buffer, if it is a markup language
+    fn active_markup_elements(&mut self) -> Option<Vec<markup_tags::Element>> {
         let state = self.active_state_mut();
+        if state.buffer.is_large_file() {
+            return None;
+        }
+        let markup = Markup::detect(&state.language, state.highlighter.syntax_name())?;
+        let language = state.highlighter.language().cloned();
+        let text = state.get_text_range(0, state.buffer.len());
+        Some(markup_tags::elements(&text, markup, language.as_ref()))
+    }
 
-        // Get all diagnostic overlay positions
-        let mut diagnostic_positions: Vec<usize> = state
-            .overlays
-            .all()
+    /// Jump between the opening and closing tags of the element at the cursor.
+    /// From inside an element's content, jump to its opening tag.
+    pub(super) fn goto_matching_tag(&mut self) {
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let pos = cursor.position;
+        let Some(elements) = self.active_markup_elements() else {
+            self.set_status_message(t!("diagnostics.tag_none").to_string());
+            return;
+        };
+        let Some(element) = elements
             .iter()
-            .filter_map(|overlay| {
-                // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    Some(overlay.range(&state.marker_list).start)
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        if diagnostic_positions.is_empty() {
-            self.set_status_message(t!("diagnostics.none").to_string());
+            .filter(|e| e.range().start <= pos && pos < e.range().end)
+            .min_by_key(|e| e.range().len())
+        else {
+            self.set_status_message(t!("diagnostics.tag_none").to_string());
             return;
-        }
+        };
 
-        // Sort positions
-        diagnostic_positions.sort_unstable();
-        diagnostic_positions.dedup();
+        let target = if element.open.contains(&pos) {
+            element.close.as_ref().map(|close| close.start)
+        } else {
+            Some(element.open.start)
+        };
+        let Some(new_pos) = target else {
+            self.set_status_message(t!("diagnostics.tag_no_match").to_string());
+            return;
+        };
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: new_pos,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+    }
 
-        // Find next diagnostic after cursor position
-        let next_pos = diagnostic_positions
+    /// Select the element around the cursor, tags included. With an element
+    /// already selected, select its parent.
+    pub(super) fn select_element(&mut self) {
+        let cursor = *self.active_cursors().primary();
+        let cursor_id = self.active_cursors().primary_id();
+        let selected = cursor
+            .selection_range()
+            .unwrap_or(cursor.position..cursor.position);
+        let Some(elements) = self.active_markup_elements() else {
+            self.set_status_message(t!("diagnostics.tag_none").to_string());
+            return;
+        };
+        let Some(range) = elements
             .iter()
-            .find(|&&pos| pos > cursor_pos)
-            .or_else(|| diagnostic_positions.first()) // Wrap around
-            .copied();
-
-        if let Some(new_pos) = next_pos {
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
-            };
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
+            .map(|e| e.range())
+            .filter(|r| {
+                r.start <= selected.start
+                    && selected.end <= r.end
+                    && selected.start < r.end
+                    && r.len() > selected.len()
+            })
+            .min_by_key(|r| r.len())
+        else {
+            self.set_status_message(t!("diagnostics.tag_none").to_string());
+            return;
+        };
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: range.end,
+            old_anchor: cursor.anchor,
+            new_anchor: Some(range.start),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+    }
 
+    /// Jump to next error/diagnostic
+    pub(super) fn jump_to_next_error(&mut self) {
+        if let Some(pos) = self.move_to_diagnostic(true) {
             // Show diagnostic message in status bar
-            let state = self.active_state();
-            if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
-                let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    overlay.message.clone()
-                } else {
-                    None
-                }
-            }) {
+            if let Some(msg) = self.diagnostic_message_at(pos) {
                 self.set_status_message(msg);
             }
         }
@@ -3810,70 +4056,168 @@ impl Editor {
 
     /// Jump to previous error/diagnostic
     pub(super) fn jump_to_previous_error(&mut self) {
-        let diagnostic_ns = self.lsp_diagnostic_namespace.clone();
-        let cursor_pos = self.active_cursors().primary().position;
-        let cursor_id = self.active_cursors().primary_id();
-        let cursor = *self.active_cursors().primary();
-        let state = self.active_state_mut();
+        if let Some(pos) = self.move_to_diagnostic(false) {
+            // Show diagnostic message in status bar
+            if let Some(msg) = self.diagnostic_message_at(pos) {
+                self.set_status_message(msg);
+            }
+        }
+    }
+
+    /// Jump to the next/previous diagnostic and show its details in a popup
+    pub(super) fn goto_diagnostic(&mut self, forward: bool) {
+        use crate::view::popup::{Popup, PopupPosition};
+
+        let Some(pos) = self.move_to_diagnostic(forward) else {
+            return;
+        };
+        let Some(message) = self.diagnostic_message_at(pos) else {
+            return;
+        };
+
+        let positions = self.diagnostic_positions();
+        let current = positions.iter().position(|&p| p == pos).unwrap_or(0) + 1;
+
+        let mut popup = Popup::text(self.diagnostic_detail_lines(&message), &self.theme);
+        popup.title = Some(
+            t!(
+                "diagnostics.popup_title",
+                current = current,
+                total = positions.len()
+            )
+            .to_string(),
+        );
+        popup.transient = true;
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 80;
+        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            state.popups.show(popup);
+        }
+    }
 
-        // Get all diagnostic overlay positions
-        let mut diagnostic_positions: Vec<usize> = state
+    /// Sorted, deduplicated start positions of the LSP diagnostics in the active buffer
+    fn diagnostic_positions(&self) -> Vec<usize> {
+        let diagnostic_ns = &self.lsp_diagnostic_namespace;
+        let state = self.active_state();
+        let mut positions: Vec<usize> = state
             .overlays
             .all()
             .iter()
             .filter_map(|overlay| {
                 // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                if overlay.namespace.as_ref() == Some(diagnostic_ns) {
                     Some(overlay.range(&state.marker_list).start)
                 } else {
                     None
                 }
             })
             .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
 
+    /// Move the primary cursor to the next (or previous) diagnostic, wrapping
+    /// around at either end. Returns the new cursor position.
+    fn move_to_diagnostic(&mut self, forward: bool) -> Option<usize> {
+        let diagnostic_positions = self.diagnostic_positions();
         if diagnostic_positions.is_empty() {
             self.set_status_message(t!("diagnostics.none").to_string());
-            return;
+            return None;
         }
 
-        // Sort positions
-        diagnostic_positions.sort_unstable();
-        diagnostic_positions.dedup();
-
-        // Find previous diagnostic before cursor position
-        let prev_pos = diagnostic_positions
-            .iter()
-            .rev()
-            .find(|&&pos| pos < cursor_pos)
-            .or_else(|| diagnostic_positions.last()) // Wrap around
-            .copied();
+        let cursor_id = self.active_cursors().primary_id();
+        let cursor = *self.active_cursors().primary();
+        let new_pos = if forward {
+            diagnostic_positions
+                .iter()
+                .find(|&&pos| pos > cursor.position)
+                .or_else(|| diagnostic_positions.first()) // Wrap around
+        } else {
+            diagnostic_positions
+                .iter()
+                .rev()
+                .find(|&&pos| pos < cursor.position)
+                .or_else(|| diagnostic_positions.last()) // Wrap around
+        }
+        .copied()?;
 
-        if let Some(new_pos) = prev_pos {
-            let event = Event::MoveCursor {
-                cursor_id,
-                old_position: cursor.position,
-                new_position: new_pos,
-                old_anchor: cursor.anchor,
-                new_anchor: None,
-                old_sticky_column: cursor.sticky_column,
-                new_sticky_column: 0,
-            };
-            self.active_event_log_mut().append(event.clone());
-            self.apply_event_to_active_buffer(&event);
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: new_pos,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+        Some(new_pos)
+    }
 
-            // Show diagnostic message in status bar
-            let state = self.active_state();
-            if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
-                let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                    overlay.message.clone()
-                } else {
-                    None
-                }
-            }) {
-                self.set_status_message(msg);
+    /// Message of the LSP diagnostic starting at `pos` in the active buffer
+    fn diagnostic_message_at(&self, pos: usize) -> Option<String> {
+        let diagnostic_ns = &self.lsp_diagnostic_namespace;
+        let state = self.active_state();
+        state.overlays.all().iter().find_map(|overlay| {
+            let range = overlay.range(&state.marker_list);
+            if range.start == pos && overlay.namespace.as_ref() == Some(diagnostic_ns) {
+                overlay.message.clone()
+            } else {
+                None
             }
+        })
+    }
+
+    /// Lines for the diagnostic detail popup: severity, source and code, the
+    /// full message, then any related locations the server reported
+    fn diagnostic_detail_lines(&self, message: &str) -> Vec<String> {
+        use lsp_types::{DiagnosticSeverity, NumberOrString};
+
+        let diagnostic = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|meta| meta.file_uri())
+            .and_then(|uri| self.stored_diagnostics.get(uri.as_str()))
+            .and_then(|diagnostics| diagnostics.iter().find(|d| d.message == message));
+
+        let Some(diagnostic) = diagnostic else {
+            return message.lines().map(String::from).collect();
+        };
+
+        let mut header = match diagnostic.severity {
+            Some(DiagnosticSeverity::ERROR) => t!("diagnostics.severity_error"),
+            Some(DiagnosticSeverity::WARNING) => t!("diagnostics.severity_warning"),
+            Some(DiagnosticSeverity::HINT) => t!("diagnostics.severity_hint"),
+            _ => t!("diagnostics.severity_info"),
+        }
+        .to_string();
+        if let Some(source) = &diagnostic.source {
+            header.push_str(&format!(" ({source})"));
+        }
+        match &diagnostic.code {
+            Some(NumberOrString::String(code)) => header.push_str(&format!(" [{code}]")),
+            Some(NumberOrString::Number(code)) => header.push_str(&format!(" [{code}]")),
+            None => {}
         }
+
+        let mut lines = vec![header];
+        lines.extend(diagnostic.message.lines().map(String::from));
+        for related in diagnostic.related_information.iter().flatten() {
+            let path = related.location.uri.path().as_str().to_string();
+            let path = std::path::Path::new(&path)
+                .strip_prefix(&self.working_dir)
+                .map(|p| p.display().to_string())
+                .unwrap_or(path.clone());
+            lines.push(format!(
+                "  {}:{}: {}",
+                path,
+                related.location.range.start.line + 1,
+                related.message
+            ));
+        }
+        lines
     }
 
     /// Toggle macro recording for the given register
@@ -4180,6 +4524,7 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4255,6 +4600,7 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4376,6 +4722,28 @@ impl Editor {
                 tracing::debug!("Saved {} history to {:?}", key, path);
             }
         }
+
+        // Save how often and how recently commands, files and picker entries were chosen
+        let path = self.dir_context.frecency_path("commands");
+        if let Err(e) = self
+            .command_registry
+            .read()
+            .unwrap()
+            .usage()
+            .save_to_file(&path)
+        {
+            tracing::warn!("Failed to save commands frecency: {}", e);
+        }
+        let path = self.dir_context.frecency_path("files");
+        if let Err(e) = self.file_provider.save_frecency(&path) {
+            tracing::warn!("Failed to save files frecency: {}", e);
+        }
+        for (key, frecency) in &self.picker_frecency {
+            let path = self.dir_context.frecency_path(key);
+            if let Err(e) = frecency.save_to_file(&path) {
+                tracing::warn!("Failed to save {} frecency: {}", key, e);
+            }
+        }
     }
 
     /// Ensure the active tab in a split is visible by adjusting its scroll offset.
@@ -0,0 +1,96 @@
+//! Repeating the last edit
+//!
+//! The last editing operation is kept as the actions that made it: a run of
+//! typing (including backspaces that correct it), or a single edit such as a
+//! line deletion or case change. When the edit replaced a selection that a
+//! single action created (a word, a line or a text object), that action is
+//! kept too, so the repeat changes the matching text at the cursor.
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use rust_i18n::t;
+
+/// Actions that make up a run of typing
+fn is_typing(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::InsertChar(_) | Action::InsertNewline | Action::InsertTab | Action::DeleteBackward
+    )
+}
+
+/// Actions that are an edit of their own
+fn is_single_edit(action: &Action) -> bool {
+    action.is_editing()
+        || matches!(
+            action,
+            Action::ToUpperCase
+                | Action::ToLowerCase
+                | Action::SortLines
+                | Action::ToggleComment
+                | Action::DedentSelection
+                | Action::TrimTrailingWhitespace
+        )
+}
+
+/// Actions that create a selection an edit can start from
+fn is_selection(action: &Action) -> bool {
+    action
+        .name()
+        .is_some_and(|name| name.starts_with("select_"))
+}
+
+impl Editor {
+    /// Note an action handled in the editor, extending or replacing the
+    /// last edit
+    pub(super) fn record_repeatable_edit(&mut self, action: &Action) {
+        if self.repeating_edit || self.prompt.is_some() {
+            return;
+        }
+        let selection = self.last_edit.selection.take();
+
+        if is_typing(action) && self.last_edit.open {
+            self.last_edit.actions.push(action.clone());
+            return;
+        }
+        if is_typing(action) || is_single_edit(action) {
+            let has_selection = self.active_cursors().primary().selection_range().is_some();
+            self.last_edit.actions = selection
+                .filter(|_| has_selection)
+                .into_iter()
+                .chain(std::iter::once(action.clone()))
+                .collect();
+            self.last_edit.open = is_typing(action);
+            return;
+        }
+        if matches!(action, Action::RepeatLastEdit) {
+            return;
+        }
+        self.last_edit.open = false;
+        if is_selection(action) {
+            self.last_edit.selection = Some(action.clone());
+        }
+    }
+
+    /// Apply the last edit again at every cursor
+    pub(super) fn repeat_last_edit(&mut self) {
+        if self.repeating_edit {
+            return;
+        }
+        if self.last_edit.actions.is_empty() {
+            self.set_status_message(t!("repeat_edit.nothing").to_string());
+            return;
+        }
+
+        let actions = self.last_edit.actions.clone();
+        self.repeating_edit = true;
+        let width = self.cached_layout.last_frame_width;
+        let height = self.cached_layout.last_frame_height;
+        for action in actions {
+            let _ = self.handle_action(action);
+            self.recompute_layout(width, height);
+        }
+        self.repeating_edit = false;
+        // Typing after a repeat starts a new edit
+        self.last_edit.open = false;
+    }
+}
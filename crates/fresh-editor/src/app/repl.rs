@@ -0,0 +1,222 @@
+//! Sending code to a REPL
+//!
+//! Each language gets its own REPL terminal in the terminal panel. The first
+//! send starts it by typing the language's `repl` command into a new panel
+//! terminal; languages without a `repl` command send to the terminal shown in
+//! the panel instead. Code is wrapped in bracketed paste when the program in
+//! the terminal has enabled it, so multi-line blocks arrive as one unit.
+
+use super::{BufferId, Editor};
+use crate::model::event::Event;
+use crate::primitives::code_block;
+use rust_i18n::t;
+
+/// Bytes that start and end a bracketed paste
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Bytes to write to a REPL to evaluate `code`.
+///
+/// Lines are submitted with carriage returns like typed input. Multi-line
+/// code gets an extra blank line so REPLs such as Python close open blocks.
+fn repl_payload(code: &str, bracketed: bool) -> Vec<u8> {
+    let code = code.trim_end().replace("\r\n", "\n").replace('\n', "\r");
+    let mut payload = Vec::with_capacity(code.len() + 16);
+    if bracketed {
+        payload.extend_from_slice(PASTE_START);
+        payload.extend_from_slice(code.as_bytes());
+        payload.extend_from_slice(PASTE_END);
+    } else {
+        payload.extend_from_slice(code.as_bytes());
+    }
+    payload.push(b'\r');
+    if code.contains('\r') {
+        payload.push(b'\r');
+    }
+    payload
+}
+
+impl Editor {
+    /// Send the selection, or the current line, to the language's REPL
+    pub fn send_to_repl(&mut self) {
+        self.send_code_to_repl(false);
+    }
+
+    /// Send the selection, or the cell or block under the cursor, to the REPL
+    pub fn send_block_to_repl(&mut self) {
+        self.send_code_to_repl(true);
+    }
+
+    fn send_code_to_repl(&mut self, block: bool) {
+        let buffer_id = self.active_buffer();
+        if self.is_terminal_buffer(buffer_id) {
+            return;
+        }
+        let primary = *self.active_cursors().primary();
+        let language = self.active_state().language.clone();
+        let comment_prefix = self
+            .config
+            .languages
+            .get(&language)
+            .and_then(|lang| lang.comment_prefix.clone());
+
+        // Selections are sent as-is; otherwise the cursor moves past what was
+        // sent so repeated sends step through the file
+        let (code, next_position) = if let Some(range) = primary.selection_range() {
+            let code = self
+                .active_state_mut()
+                .get_text_range(range.start, range.end);
+            (code, None)
+        } else {
+            let state = self.active_state();
+            let Some(text) = state.buffer.to_string() else {
+                self.set_status_message(t!("repl.buffer_not_loaded").to_string());
+                return;
+            };
+            let range = if block {
+                code_block::block_at(
+                    &text,
+                    primary.position,
+                    state.highlighter.language(),
+                    comment_prefix.as_deref(),
+                )
+            } else {
+                code_block::line_at(&text, primary.position)
+            };
+            let next = next_code_line(&text, range.end);
+            (text[range].to_string(), Some(next))
+        };
+
+        if code.trim().is_empty() {
+            self.set_status_message(t!("repl.nothing_to_send").to_string());
+            return;
+        }
+
+        let Some((terminal_buffer, started)) = self.repl_terminal(&language) else {
+            return;
+        };
+        let Some(terminal_id) = self.get_terminal_id(terminal_buffer) else {
+            return;
+        };
+        // A REPL that was just started has not enabled bracketed paste yet,
+        // and the shell that has would pass the markers on to it verbatim
+        let bracketed = !started
+            && self
+                .terminal_manager
+                .get(terminal_id)
+                .and_then(|handle| handle.state.lock().ok().map(|s| s.uses_bracketed_paste()))
+                .unwrap_or(false);
+        if let Some(handle) = self.terminal_manager.get(terminal_id) {
+            handle.write(&repl_payload(&code, bracketed));
+        }
+        self.reveal_panel_terminal(terminal_buffer);
+
+        if let Some(new_position) = next_position {
+            let event = Event::MoveCursor {
+                cursor_id: self.active_cursors().primary_id(),
+                old_position: primary.position,
+                new_position,
+                old_anchor: primary.anchor,
+                new_anchor: None,
+                old_sticky_column: primary.sticky_column,
+                new_sticky_column: 0,
+            };
+            self.active_event_log_mut().append(event.clone());
+            self.apply_event_to_active_buffer(&event);
+        }
+
+        let lines = code.trim_end().lines().count();
+        self.set_status_message(t!("repl.sent", count = lines).to_string());
+    }
+
+    /// The REPL terminal for `language`, starting one if needed.
+    /// Also returns whether the terminal was just started.
+    fn repl_terminal(&mut self, language: &str) -> Option<(BufferId, bool)> {
+        if let Some(&buffer_id) = self.repl_terminals.get(language) {
+            let alive = self
+                .get_terminal_id(buffer_id)
+                .and_then(|id| self.terminal_manager.get(id))
+                .is_some_and(|handle| handle.is_alive());
+            if alive && self.buffers.contains_key(&buffer_id) {
+                return Some((buffer_id, false));
+            }
+            self.repl_terminals.remove(language);
+        }
+
+        let command = self
+            .config
+            .languages
+            .get(language)
+            .and_then(|lang| lang.repl.clone())
+            .filter(|command| !command.trim().is_empty());
+        let Some(command) = command else {
+            // No REPL configured: attach to the terminal shown in the panel
+            let Some(buffer_id) = self.panel_active_terminal() else {
+                self.set_status_message(t!("repl.not_configured", language = language).to_string());
+                return None;
+            };
+            self.repl_terminals.insert(language.to_string(), buffer_id);
+            return Some((buffer_id, false));
+        };
+
+        let buffer_id = self.add_panel_terminal()?;
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.display_name = format!("*{}*", command);
+        }
+        if let Some(handle) = self
+            .get_terminal_id(buffer_id)
+            .and_then(|id| self.terminal_manager.get(id))
+        {
+            handle.write(format!("{}\r", command).as_bytes());
+        }
+        self.repl_terminals.insert(language.to_string(), buffer_id);
+        Some((buffer_id, true))
+    }
+}
+
+/// Start of the first non-blank line after `offset`, or the end of the text
+fn next_code_line(text: &str, offset: usize) -> usize {
+    let mut start = match text[offset..].find('\n') {
+        Some(i) => offset + i + 1,
+        None => return text.len(),
+    };
+    while start < text.len() {
+        let end = text[start..].find('\n').map_or(text.len(), |i| start + i);
+        if !text[start..end].trim().is_empty() {
+            return start;
+        }
+        start = end + 1;
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repl_payload_single_line() {
+        assert_eq!(repl_payload("x = 1\n", false), b"x = 1\r");
+        assert_eq!(repl_payload("x = 1", true), b"\x1b[200~x = 1\x1b[201~\r");
+    }
+
+    #[test]
+    fn test_repl_payload_block_ends_with_blank_line() {
+        assert_eq!(
+            repl_payload("def f():\r\n    return 1\n", false),
+            b"def f():\r    return 1\r\r"
+        );
+        assert_eq!(
+            repl_payload("def f():\n    return 1", true),
+            b"\x1b[200~def f():\r    return 1\x1b[201~\r\r"
+        );
+    }
+
+    #[test]
+    fn test_next_code_line_skips_blank_lines() {
+        let text = "a = 1\n\n  \nb = 2\n";
+        assert_eq!(next_code_line(text, 5), text.find('b').unwrap());
+        assert_eq!(next_code_line(text, text.len() - 1), text.len());
+        assert_eq!(next_code_line("a = 1", 5), 5);
+    }
+}
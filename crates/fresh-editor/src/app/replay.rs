@@ -0,0 +1,112 @@
+//! Recording inputs to the event log and replaying them (`fresh --replay`).
+//!
+//! Replay feeds one recorded input per main-loop iteration, waiting until its
+//! recorded time unless running as fast as possible. The loop renders after
+//! every replayed input, so mouse events land on the same layout they were
+//! recorded against.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+
+use crate::input::replay::{InputRecorder, ReplayInput, ReplayLog, ReplayStep};
+
+use super::Editor;
+
+/// A replay in progress
+#[derive(Debug)]
+pub(crate) struct ReplayState {
+    steps: VecDeque<ReplayStep>,
+    started: Instant,
+    /// Ignore recorded timing
+    fast: bool,
+    total: usize,
+}
+
+impl Editor {
+    /// Stream inputs and buffer events to `path` (`--event-log`)
+    pub fn enable_event_streaming<P: AsRef<Path>>(&mut self, path: P) -> AnyhowResult<()> {
+        let recorder =
+            InputRecorder::create(path.as_ref(), self.terminal_width, self.terminal_height)?;
+        for event_log in self.event_logs.values_mut() {
+            event_log.stream_to(recorder.file_handle()?);
+        }
+        self.input_recorder = Some(recorder);
+        Ok(())
+    }
+
+    /// Append an input to the event log, if streaming, and keep it for a
+    /// crash report
+    pub fn record_input(&mut self, input: &ReplayInput) {
+        crate::services::crash_report::record_input(input);
+        if let Some(recorder) = self.input_recorder.as_mut() {
+            recorder.record(input);
+        }
+    }
+
+    /// Feed a recorded input to the editor as if it came from the terminal
+    pub fn apply_replay_input(&mut self, input: &ReplayInput) -> AnyhowResult<()> {
+        match input {
+            ReplayInput::Key(code, modifiers) => self.handle_key(*code, *modifiers)?,
+            ReplayInput::Mouse(event) => {
+                self.handle_mouse(*event)?;
+            }
+            ReplayInput::Paste(text) => self.paste_text(text.clone()),
+            ReplayInput::Resize(width, height) => self.resize(*width, *height),
+        }
+        Ok(())
+    }
+
+    /// Start replaying a recorded session
+    pub fn start_replay(&mut self, log: ReplayLog, fast: bool) {
+        let total = log.steps.len();
+        self.replay = Some(ReplayState {
+            steps: log.steps.into(),
+            started: self.time_source.now(),
+            fast,
+            total,
+        });
+        self.set_status_message(t!("replay.started", count = total).to_string());
+    }
+
+    /// Whether a replay is in progress
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// How long until the next replayed input is due
+    pub fn replay_wait(&self) -> Option<Duration> {
+        let replay = self.replay.as_ref()?;
+        let step = replay.steps.front()?;
+        if replay.fast {
+            return Some(Duration::ZERO);
+        }
+        let elapsed = self.time_source.elapsed_since(replay.started);
+        Some(step.at.saturating_sub(elapsed))
+    }
+
+    /// Apply the next recorded input if it is due
+    ///
+    /// Returns true if an input was applied (the caller should render).
+    pub fn poll_replay(&mut self) -> AnyhowResult<bool> {
+        if self.replay_wait() != Some(Duration::ZERO) {
+            return Ok(false);
+        }
+        let Some(step) = self.replay.as_mut().and_then(|r| r.steps.pop_front()) else {
+            return Ok(false);
+        };
+        self.apply_replay_input(&step.input)?;
+
+        if let Some(replay) = self.replay.as_ref().filter(|r| r.steps.is_empty()) {
+            let total = replay.total;
+            self.replay = None;
+            if !self.should_quit() {
+                self.set_status_message(t!("replay.finished", count = total).to_string());
+            }
+        }
+        Ok(true)
+    }
+}
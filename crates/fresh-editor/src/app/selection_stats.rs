@@ -0,0 +1,132 @@
+//! Selection statistics shown in the status bar.
+//!
+//! Counting is spread over frames, a limited number of bytes per frame, so
+//! that selecting a large file doesn't stall rendering. Until counting is
+//! done, the status bar shows the counts so far.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::selection_stats::SelectionStats;
+
+/// Bytes of the selection counted per frame
+const BYTES_PER_FRAME: usize = 1024 * 1024;
+
+/// Counts for the current selections, and the part still to count
+#[derive(Debug, Default)]
+pub(super) struct SelectionStatsState {
+    /// Buffer, buffer version and selections the counts are for
+    key: Option<(BufferId, u64, Vec<Range<usize>>)>,
+    stats: SelectionStats,
+    /// Selections still to count; the first may be partly counted
+    pending: VecDeque<Range<usize>>,
+    /// Whether counting of the first pending selection has started
+    started: bool,
+}
+
+impl Editor {
+    /// Text for the selection statistics in the status bar, counting more of
+    /// a large selection each time
+    pub(super) fn selection_stats_text(&mut self) -> Option<String> {
+        if !self.config.editor.show_selection_stats {
+            return None;
+        }
+        let mut ranges: Vec<Range<usize>> = self
+            .active_cursors()
+            .iter()
+            .filter_map(|(_, cursor)| cursor.selection_range())
+            .filter(|range| !range.is_empty())
+            .collect();
+        if ranges.is_empty() {
+            self.selection_stats = SelectionStatsState::default();
+            return None;
+        }
+        ranges.sort_by_key(|range| range.start);
+
+        let key = (
+            self.active_buffer(),
+            self.active_state().buffer.version(),
+            ranges.clone(),
+        );
+        if self.selection_stats.key.as_ref() != Some(&key) {
+            self.selection_stats = SelectionStatsState {
+                key: Some(key),
+                pending: ranges.into(),
+                ..Default::default()
+            };
+        }
+        self.count_selection_stats();
+
+        let state = &self.selection_stats;
+        let mut text = t!(
+            "status.selection_stats",
+            chars = state.stats.chars,
+            words = state.stats.words,
+            lines = state.stats.lines()
+        )
+        .to_string();
+        if self.config.editor.selection_stats_sum {
+            if let Some(sum) = state.stats.sum() {
+                text.push_str(", ");
+                text.push_str(&t!("status.selection_sum", sum = sum));
+            }
+        }
+        if !state.pending.is_empty() {
+            text.push('…');
+        }
+        Some(text)
+    }
+
+    /// Whether the selection statistics are still being counted, so another
+    /// frame is needed
+    pub fn check_selection_stats(&self) -> bool {
+        !self.selection_stats.pending.is_empty()
+    }
+
+    /// Count up to a frame's worth of the pending selections
+    fn count_selection_stats(&mut self) {
+        let with_sum = self.config.editor.selection_stats_sum;
+        let mut budget = BYTES_PER_FRAME;
+        while budget > 0 {
+            let Some(range) = self.selection_stats.pending.front().cloned() else {
+                break;
+            };
+            if !self.selection_stats.started {
+                self.selection_stats.stats.start_piece();
+                self.selection_stats.started = true;
+            }
+
+            let len = range.len().min(budget);
+            let Ok(mut bytes) = self
+                .active_state_mut()
+                .buffer
+                .get_text_range_mut(range.start, len)
+            else {
+                self.selection_stats.pending.clear();
+                break;
+            };
+            // Stop after whitespace, so no word or character is split
+            if len < range.len() {
+                if let Some(cut) = bytes.iter().rposition(u8::is_ascii_whitespace) {
+                    bytes.truncate(cut + 1);
+                }
+            }
+            let counted = bytes.len().max(1);
+            self.selection_stats
+                .stats
+                .add(&String::from_utf8_lossy(&bytes), with_sum);
+
+            budget = budget.saturating_sub(counted);
+            if counted >= range.len() {
+                self.selection_stats.pending.pop_front();
+                self.selection_stats.started = false;
+            } else if let Some(front) = self.selection_stats.pending.front_mut() {
+                front.start += counted;
+            }
+        }
+    }
+}
@@ -9,7 +9,6 @@
 
 use crate::config::Config;
 use crate::config_io::{ConfigLayer, ConfigResolver};
-use crate::input::keybindings::KeybindingResolver;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
@@ -65,6 +64,7 @@ impl Editor {
     pub fn save_settings(&mut self) {
         let old_theme = self.config.theme.clone();
         let old_locale = self.config.locale.clone();
+        let old_color_mode = self.config.editor.color_mode;
         let old_plugins = self.config.plugins.clone();
 
         // Get target layer, new config, and the actual changes made
@@ -109,6 +109,11 @@ impl Editor {
             }
         }
 
+        if old_color_mode != self.config.editor.color_mode {
+            self.color_capability =
+                crate::view::color_support::ColorCapability::resolve(self.config.editor.color_mode);
+        }
+
         // Apply locale change at runtime
         if old_locale != self.config.locale {
             if let Some(locale) = self.config.locale.as_option() {
@@ -128,11 +133,15 @@ impl Editor {
             }
         }
 
+        // Re-render inline diagnostics with the new settings and colors
+        self.refresh_diagnostic_lens();
+
         // Handle plugin enable/disable changes
         self.apply_plugin_config_changes(&old_plugins);
 
         // Update keybindings
-        self.keybindings = KeybindingResolver::new(&self.config);
+        self.keybindings.reset_from_config(&self.config);
+        self.refresh_user_commands();
 
         // Update LSP configs
         if let Some(ref mut lsp) = self.lsp {
@@ -561,7 +570,7 @@ impl Editor {
     }
 
     /// Apply plugin configuration changes by loading/unloading plugins as needed
-    fn apply_plugin_config_changes(
+    pub(super) fn apply_plugin_config_changes(
         &mut self,
         old_plugins: &std::collections::HashMap<String, crate::config::PluginConfig>,
     ) {
@@ -31,48 +31,7 @@ impl Editor {
         // Get the input text (selection or entire buffer)
         let input = self.get_shell_input();
 
-        // Detect the shell to use
-        let shell = detect_shell();
-
-        // Execute the command
-        let mut child = Command::new(&shell)
-            .args(["-c", command])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to spawn shell: {}", e))?;
-
-        // Write input to stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(input.as_bytes())
-                .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-        }
-
-        // Wait for the command to complete
-        let output = child
-            .wait_with_output()
-            .map_err(|e| format!("Failed to wait for command: {}", e))?;
-
-        if output.status.success() {
-            String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8 in output: {}", e))
-        } else {
-            // Include stderr in error message
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if !stderr.is_empty() {
-                Err(format!("Command failed: {}", stderr.trim()))
-            } else if !stdout.is_empty() {
-                // Some commands output errors to stdout
-                Err(format!("Command failed: {}", stdout.trim()))
-            } else {
-                Err(format!(
-                    "Command failed with exit code: {:?}",
-                    output.status.code()
-                ))
-            }
-        }
+        run_shell_filter(command, &input)
     }
 
     /// Get the input for shell command (selection or entire buffer).
@@ -94,7 +53,8 @@ impl Editor {
     /// Handle shell command execution after prompt confirmation.
     /// If `replace` is true, replaces the selection/buffer with output.
     /// If `replace` is false, creates a new buffer with the output.
-    pub fn handle_shell_command(&mut self, command: &str, replace: bool) {
+    /// Returns whether the command succeeded.
+    pub fn handle_shell_command(&mut self, command: &str, replace: bool) -> bool {
         // Capture selection range first
         let selection_range = {
             let primary = self.active_cursors().primary();
@@ -121,9 +81,11 @@ impl Editor {
                 } else {
                     self.create_shell_output_buffer(command, &output);
                 }
+                true
             }
             Err(err) => {
                 self.set_status_message(err);
+                false
             }
         }
     }
@@ -252,7 +214,8 @@ impl Editor {
         use crossterm::ExecutableCommand;
         use std::io::stdout;
 
-        // Suspend TUI
+        // Suspend TUI, leaving the terminal's input to the command
+        let _input_hold = crate::services::event_bus::hold_terminal_input();
         let _ = disable_raw_mode();
         let _ = stdout().execute(LeaveAlternateScreen);
 
@@ -281,6 +244,56 @@ impl Editor {
     }
 }
 
+/// Run a shell command with `input` as stdin, returning its output.
+/// Returns Err(error_message) on failure.
+pub(super) fn run_shell_filter(command: &str, input: &str) -> Result<String, String> {
+    // Detect the shell to use
+    let shell = detect_shell();
+
+    // Execute the command
+    let mut child = Command::new(&shell)
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+
+    // Write input to stdin. A command that doesn't read its input may exit
+    // before it's written, which isn't an error.
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(e) = stdin.write_all(input.as_bytes()) {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(format!("Failed to write to stdin: {}", e));
+            }
+        }
+    }
+
+    // Wait for the command to complete
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for command: {}", e))?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8 in output: {}", e))
+    } else {
+        // Include stderr in error message
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !stderr.is_empty() {
+            Err(format!("Command failed: {}", stderr.trim()))
+        } else if !stdout.is_empty() {
+            // Some commands output errors to stdout
+            Err(format!("Command failed: {}", stdout.trim()))
+        } else {
+            Err(format!(
+                "Command failed with exit code: {:?}",
+                output.status.code()
+            ))
+        }
+    }
+}
+
 /// Detect the shell to use for executing commands.
 fn detect_shell() -> String {
     // Try SHELL environment variable first
@@ -10,7 +10,7 @@
 use rust_i18n::t;
 
 use crate::model::event::{BufferId, Event, SplitDirection, SplitId};
-use crate::view::split::SplitViewState;
+use crate::view::split::{FocusDirection, SplitViewState};
 
 use super::Editor;
 
@@ -133,7 +133,23 @@ impl Editor {
         } else {
             self.split_manager.prev_split();
         }
+        self.on_split_focus_changed();
+    }
+
+    /// Move focus to the nearest split in a screen direction
+    pub fn focus_split_direction(&mut self, direction: FocusDirection) {
+        let editor_area = self.cached_layout.editor_content_area.unwrap_or_else(|| {
+            ratatui::layout::Rect::new(0, 0, self.terminal_width, self.terminal_height)
+        });
+        if self.split_manager.focus_direction(editor_area, direction) {
+            self.on_split_focus_changed();
+        } else {
+            self.set_status_message(t!("split.no_split_in_direction").to_string());
+        }
+    }
 
+    /// Bookkeeping after the active split changes via keyboard navigation
+    fn on_split_focus_changed(&mut self) {
         // Ensure the active tab is visible in the newly active split
         let split_id = self.split_manager.active_split();
         self.ensure_active_tab_visible(split_id, self.active_buffer(), self.effective_tabs_width());
@@ -241,7 +257,7 @@ impl Editor {
     /// Adjust the size of the active split
     pub fn adjust_split_size(&mut self, delta: f32) {
         let active_split = self.split_manager.active_split();
-        if let Err(e) = self.split_manager.adjust_ratio(active_split, delta) {
+        if let Err(e) = self.split_manager.adjust_leaf_size(active_split, delta) {
             self.set_status_message(t!("split.cannot_adjust", error = e).to_string());
         } else {
             let percent = (delta * 100.0) as i32;
@@ -267,6 +283,132 @@ impl Editor {
         }
     }
 
+    /// Swap the active split with its sibling
+    pub fn rotate_splits(&mut self) {
+        match self.split_manager.rotate_active() {
+            Ok(()) => {
+                self.set_status_message(t!("split.rotated").to_string());
+                self.resize_visible_terminals();
+            }
+            Err(e) => self.set_status_message(e),
+        }
+    }
+
+    /// Close every split except the active one, moving their tabs into it
+    pub fn close_other_splits(&mut self) {
+        // The terminal panel is not one of the "other" splits; it stays open
+        let panel_visible = self.detach_terminal_panel_for_layout();
+        match self.split_manager.close_other_splits() {
+            Ok(removed) => {
+                let active_split = self.split_manager.active_split();
+                let mut moved_tabs = Vec::new();
+                for split_id in removed {
+                    if let Some(view_state) = self.split_view_states.remove(&split_id) {
+                        moved_tabs.extend(view_state.open_buffers);
+                    }
+                }
+                if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+                    for buffer_id in moved_tabs {
+                        if !view_state.open_buffers.contains(&buffer_id) {
+                            view_state.open_buffers.push(buffer_id);
+                        }
+                    }
+                }
+                self.set_status_message(t!("split.closed_others").to_string());
+            }
+            Err(e) => self.set_status_message(e),
+        }
+        self.restore_terminal_panel_after_layout(panel_visible);
+        self.resize_visible_terminals();
+    }
+
+    /// Give every visible split an equal share of the screen
+    pub fn equalize_splits(&mut self) {
+        self.split_manager.distribute_splits_evenly();
+        self.set_status_message(t!("split.equalized").to_string());
+        self.resize_visible_terminals();
+    }
+
+    /// Prompt for a name to save the current split layout under
+    pub fn start_save_layout_prompt(&mut self) {
+        self.prompt = Some(crate::view::prompt::Prompt::new(
+            t!("layout.save_prompt").to_string(),
+            crate::view::prompt::PromptType::SaveLayout,
+        ));
+    }
+
+    /// Prompt to pick one of the saved layouts (for restore or delete)
+    pub fn start_layout_prompt(&mut self, prompt_type: crate::view::prompt::PromptType) {
+        if self.named_layouts.is_empty() {
+            self.set_status_message(t!("layout.none_saved").to_string());
+            return;
+        }
+
+        let mut names: Vec<&String> = self.named_layouts.keys().collect();
+        names.sort();
+        let suggestions: Vec<crate::input::commands::Suggestion> = names
+            .into_iter()
+            .map(|name| crate::input::commands::Suggestion {
+                text: name.clone(),
+                description: None,
+                value: Some(name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        let message = match prompt_type {
+            crate::view::prompt::PromptType::DeleteLayout => t!("layout.delete_prompt"),
+            _ => t!("layout.restore_prompt"),
+        };
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            message.to_string(),
+            prompt_type,
+            suggestions,
+        ));
+    }
+
+    /// Save the current split layout under `name`, replacing any existing one
+    pub fn save_named_layout(&mut self, name: &str) {
+        if name.is_empty() {
+            return;
+        }
+        let layout = self.capture_layout();
+        self.named_layouts.insert(name.to_string(), layout);
+        self.set_status_message(t!("layout.saved", name = name).to_string());
+    }
+
+    /// Replace the current split layout with the one saved under `name`
+    pub fn restore_named_layout(&mut self, name: &str) {
+        let Some(layout) = self.named_layouts.get(name).cloned() else {
+            self.set_status_message(t!("layout.not_found", name = name).to_string());
+            return;
+        };
+        let panel_visible = self.detach_terminal_panel_for_layout();
+        self.apply_layout(&layout);
+        // Keep the terminal panel unless the restored layout brings its own
+        self.restore_terminal_panel_after_layout(panel_visible);
+        self.on_split_focus_changed();
+        self.set_status_message(t!("layout.restored", name = name).to_string());
+    }
+
+    /// Forget the layout saved under `name`
+    pub fn delete_named_layout(&mut self, name: &str) {
+        if self.named_layouts.remove(name).is_some() {
+            self.set_status_message(t!("layout.deleted", name = name).to_string());
+        } else {
+            self.set_status_message(t!("layout.not_found", name = name).to_string());
+        }
+    }
+
+    /// Names of all saved layouts, sorted (for testing)
+    pub fn named_layout_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.named_layouts.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
     /// Get cached separator areas for testing
     /// Returns (split_id, direction, x, y, length) tuples
     pub fn get_separator_areas(&self) -> &[(SplitId, SplitDirection, u16, u16, u16)] {
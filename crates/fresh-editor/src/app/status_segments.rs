@@ -0,0 +1,111 @@
+//! Plugin status bar segments (`registerStatusSegment`).
+//!
+//! Segments sit at the right of the status bar, before the git branch, in
+//! descending priority. Registering an id again updates the segment in place.
+
+use std::cmp::Reverse;
+
+use fresh_core::api::StatusSegmentOptions;
+
+use crate::input::keybindings::Action;
+
+use super::Editor;
+
+/// A status bar segment registered by a plugin
+#[derive(Debug, Clone)]
+pub(crate) struct StatusSegment {
+    plugin_name: String,
+    id: String,
+    pub options: StatusSegmentOptions,
+}
+
+/// Add `segment`, or update the one with its plugin and id, keeping
+/// `segments` in display order
+fn set_segment(segments: &mut Vec<StatusSegment>, segment: StatusSegment) {
+    if let Some(existing) = segments
+        .iter_mut()
+        .find(|s| s.plugin_name == segment.plugin_name && s.id == segment.id)
+    {
+        existing.options = segment.options;
+    } else {
+        segments.push(segment);
+    }
+    // Stable, so equal priorities keep the order they were registered in
+    segments.sort_by_key(|s| Reverse(s.options.priority.unwrap_or(0)));
+}
+
+impl Editor {
+    pub(super) fn handle_set_status_segment(
+        &mut self,
+        plugin_name: String,
+        segment_id: String,
+        options: StatusSegmentOptions,
+    ) {
+        set_segment(
+            &mut self.status_segments,
+            StatusSegment {
+                plugin_name,
+                id: segment_id,
+                options,
+            },
+        );
+    }
+
+    pub(super) fn handle_remove_status_segment(&mut self, plugin_name: &str, segment_id: &str) {
+        self.status_segments
+            .retain(|s| !(s.plugin_name == plugin_name && s.id == segment_id));
+    }
+
+    /// Drop the status segments of an unloaded plugin
+    pub(super) fn clear_plugin_status_segments(&mut self, plugin_name: &str) {
+        self.status_segments
+            .retain(|s| s.plugin_name != plugin_name);
+    }
+
+    /// Run the command of the status segment at `index`, if it has one
+    pub(super) fn click_status_segment(&mut self, index: usize) -> anyhow::Result<()> {
+        let Some(command) = self
+            .status_segments
+            .get(index)
+            .and_then(|s| s.options.command.clone())
+        else {
+            return Ok(());
+        };
+        let action = Action::from_str(&command, &std::collections::HashMap::new())
+            .unwrap_or(Action::PluginAction(command));
+        self.handle_action(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(plugin: &str, id: &str, text: &str, priority: Option<i32>) -> StatusSegment {
+        StatusSegment {
+            plugin_name: plugin.to_string(),
+            id: id.to_string(),
+            options: StatusSegmentOptions {
+                text: text.to_string(),
+                fg: None,
+                bg: None,
+                priority,
+                command: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_segments_ordered_by_priority_and_updated_in_place() {
+        let mut segments = Vec::new();
+        set_segment(&mut segments, segment("a", "tests", "✓ 3", None));
+        set_segment(&mut segments, segment("b", "cov", "81%", Some(5)));
+        set_segment(&mut segments, segment("a", "lint", "ok", None));
+        // Same id from another plugin is a separate segment
+        set_segment(&mut segments, segment("b", "tests", "…", Some(-1)));
+        set_segment(&mut segments, segment("a", "tests", "✗ 1", None));
+
+        let texts: Vec<&str> = segments.iter().map(|s| s.options.text.as_str()).collect();
+        assert_eq!(texts, vec!["81%", "✗ 1", "ok", "…"]);
+    }
+}
@@ -31,6 +31,42 @@ use rust_i18n::t;
 impl Editor {
     /// Open a new terminal in the current split
     pub fn open_terminal(&mut self) {
+        match self.spawn_terminal() {
+            Ok(terminal_id) => {
+                // Create a buffer for this terminal
+                let buffer_id = self.create_terminal_buffer_attached(
+                    terminal_id,
+                    self.split_manager.active_split(),
+                );
+
+                // Switch to the terminal buffer
+                self.set_active_buffer(buffer_id);
+
+                // Enable terminal mode
+                self.terminal_mode = true;
+                self.key_context = crate::input::keybindings::KeyContext::Terminal;
+
+                // Resize terminal to match actual split content area
+                self.resize_visible_terminals();
+
+                self.set_terminal_opened_status(terminal_id);
+                tracing::info!(
+                    "Opened terminal {:?} with buffer {:?}",
+                    terminal_id,
+                    buffer_id
+                );
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("terminal.failed_to_open", error = e.to_string()).to_string(),
+                );
+                tracing::error!("Failed to open terminal: {}", e);
+            }
+        }
+    }
+
+    /// Spawn a terminal session in the working directory, without a buffer
+    pub(crate) fn spawn_terminal(&mut self) -> Result<TerminalId, String> {
         // Get the current split dimensions for the terminal size
         let (cols, rows) = self.get_terminal_dimensions();
 
@@ -57,67 +93,39 @@ impl Editor {
             .terminal_backing_files
             .get(&predicted_terminal_id)
             .cloned();
-        match self.terminal_manager.spawn(
+        let terminal_id = self.terminal_manager.spawn(
             cols,
             rows,
             Some(self.working_dir.clone()),
             Some(log_path.clone()),
             backing_path_for_spawn,
-        ) {
-            Ok(terminal_id) => {
-                // Track log file path (use actual ID in case it differs)
-                let actual_log_path = log_path.clone();
-                self.terminal_log_files
-                    .insert(terminal_id, actual_log_path.clone());
-                // If predicted differs, move backing path entry
-                if terminal_id != predicted_terminal_id {
-                    self.terminal_backing_files.remove(&predicted_terminal_id);
-                    let backing_path =
-                        terminal_root.join(format!("fresh-terminal-{}.txt", terminal_id.0));
-                    self.terminal_backing_files
-                        .insert(terminal_id, backing_path);
-                }
-
-                // Create a buffer for this terminal
-                let buffer_id = self.create_terminal_buffer_attached(
-                    terminal_id,
-                    self.split_manager.active_split(),
-                );
-
-                // Switch to the terminal buffer
-                self.set_active_buffer(buffer_id);
-
-                // Enable terminal mode
-                self.terminal_mode = true;
-                self.key_context = crate::input::keybindings::KeyContext::Terminal;
-
-                // Resize terminal to match actual split content area
-                self.resize_visible_terminals();
-
-                // Get the terminal escape keybinding dynamically
-                let exit_key = self
-                    .keybindings
-                    .find_keybinding_for_action(
-                        "terminal_escape",
-                        crate::input::keybindings::KeyContext::Terminal,
-                    )
-                    .unwrap_or_else(|| "Ctrl+Space".to_string());
-                self.set_status_message(
-                    t!("terminal.opened", id = terminal_id.0, exit_key = exit_key).to_string(),
-                );
-                tracing::info!(
-                    "Opened terminal {:?} with buffer {:?}",
-                    terminal_id,
-                    buffer_id
-                );
-            }
-            Err(e) => {
-                self.set_status_message(
-                    t!("terminal.failed_to_open", error = e.to_string()).to_string(),
-                );
-                tracing::error!("Failed to open terminal: {}", e);
-            }
+        )?;
+
+        // Track log file path (use actual ID in case it differs)
+        self.terminal_log_files.insert(terminal_id, log_path);
+        // If predicted differs, move backing path entry
+        if terminal_id != predicted_terminal_id {
+            self.terminal_backing_files.remove(&predicted_terminal_id);
+            let backing_path = terminal_root.join(format!("fresh-terminal-{}.txt", terminal_id.0));
+            self.terminal_backing_files
+                .insert(terminal_id, backing_path);
         }
+        Ok(terminal_id)
+    }
+
+    /// Tell the user a terminal opened and how to leave terminal mode
+    pub(crate) fn set_terminal_opened_status(&mut self, terminal_id: TerminalId) {
+        // Get the terminal escape keybinding dynamically
+        let exit_key = self
+            .keybindings
+            .find_keybinding_for_action(
+                "terminal_escape",
+                crate::input::keybindings::KeyContext::Terminal,
+            )
+            .unwrap_or_else(|| "Ctrl+Space".to_string());
+        self.set_status_message(
+            t!("terminal.opened", id = terminal_id.0, exit_key = exit_key).to_string(),
+        );
     }
 
     /// Create a buffer for a terminal session
@@ -538,6 +546,7 @@ impl Editor {
                 state.editing_disabled = true;
                 state.margins.configure_for_line_numbers(false);
             }
+            self.apply_terminal_command_decorations(buffer_id);
 
             // In read-only view, keep line wrapping disabled for terminal buffers
             // Also scroll viewport to show the end of the buffer where the cursor is
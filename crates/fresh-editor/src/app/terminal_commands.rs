@@ -0,0 +1,257 @@
+//! Shell integration features of terminal buffers
+//!
+//! With OSC 133 marks from the shell, the scrollback view can jump between
+//! command prompts and shows each finished command's exit status at the end
+//! of its prompt line. "Open File from Terminal Output" resolves a relative
+//! path against the directory the command that printed it ran in, as
+//! reported by OSC 7, falling back to the terminal's starting directory.
+
+use super::{BufferId, Editor};
+use crate::primitives::ansi::AnsiParser;
+use crate::primitives::file_reference::file_reference_at;
+use crate::services::terminal::shell_integration::ShellCommand;
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+/// Virtual text namespace for the exit status shown on prompt lines
+fn command_status_namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string("terminal-command-status".to_string())
+}
+
+impl Editor {
+    /// Move the cursor to the prompt of the previous command in the active terminal
+    pub fn terminal_previous_command(&mut self) {
+        self.jump_to_terminal_command(false);
+    }
+
+    /// Move the cursor to the prompt of the next command in the active terminal
+    pub fn terminal_next_command(&mut self) {
+        self.jump_to_terminal_command(true);
+    }
+
+    fn jump_to_terminal_command(&mut self, forward: bool) {
+        let buffer_id = self.active_buffer();
+        if !self.is_terminal_buffer(buffer_id) {
+            self.set_status_message(t!("status.not_viewing_terminal").to_string());
+            return;
+        }
+        // Prompts are lines of the scrollback buffer, so leave the live view
+        if self.terminal_mode {
+            self.exit_terminal_mode_to_scrollback();
+        }
+
+        let Some(cursor_line) = self.buffers.get(&buffer_id).map(|state| {
+            state
+                .buffer
+                .get_line_number(self.active_cursors().primary().position)
+        }) else {
+            return;
+        };
+        let target = self.with_shell_integration(buffer_id, |shell| {
+            if !shell.is_active() {
+                return Err(t!("terminal.no_shell_integration").to_string());
+            }
+            let prompt = if forward {
+                shell.next_prompt(cursor_line)
+            } else {
+                shell.previous_prompt(cursor_line)
+            };
+            prompt.ok_or_else(|| {
+                if forward {
+                    t!("terminal.no_next_command").to_string()
+                } else {
+                    t!("terminal.no_previous_command").to_string()
+                }
+            })
+        });
+
+        match target {
+            Some(Ok(line)) => {
+                self.goto_line_col(line + 1, None);
+                let status = self
+                    .with_shell_integration(buffer_id, |shell| {
+                        shell.command_at(line).map(command_status_text)
+                    })
+                    .flatten();
+                if let Some(status) = status {
+                    self.set_status_message(status);
+                }
+            }
+            Some(Err(message)) => self.set_status_message(message),
+            None => {}
+        }
+    }
+
+    /// Open the file path under the cursor in a terminal's scrollback
+    pub fn open_terminal_output_path(&mut self) {
+        let buffer_id = self.active_buffer();
+        if !self.is_terminal_buffer(buffer_id) {
+            self.set_status_message(t!("status.not_viewing_terminal").to_string());
+            return;
+        }
+        if self.terminal_mode {
+            self.set_status_message(t!("terminal.open_path_in_scrollback").to_string());
+            return;
+        }
+
+        let position = self.active_cursors().primary().position;
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let line = state.buffer.get_line_number(position);
+        let Some(line_start) = state.buffer.line_start_offset(line) else {
+            return;
+        };
+        let raw = state
+            .buffer
+            .get_line(line)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        let (text, offset) = strip_ansi_with_offset(&raw, position.saturating_sub(line_start));
+        let Some(reference) = file_reference_at(text.trim_end_matches(['\r', '\n']), offset) else {
+            self.set_status_message(t!("terminal.no_path_at_cursor").to_string());
+            return;
+        };
+
+        let base = self
+            .with_shell_integration(buffer_id, |shell| shell.cwd_at(line).cloned())
+            .flatten()
+            .or_else(|| {
+                let terminal_id = self.get_terminal_id(buffer_id)?;
+                self.terminal_manager.get(terminal_id)?.cwd()
+            })
+            .unwrap_or_else(|| self.working_dir.clone());
+        let path = crate::primitives::path_utils::expand_tilde(&reference.path);
+        let path = if path.is_absolute() {
+            path
+        } else {
+            base.join(path)
+        };
+        if !self.filesystem.is_file(&path).unwrap_or(false) {
+            self.set_status_message(
+                t!("terminal.path_not_found", path = path.display().to_string()).to_string(),
+            );
+            return;
+        }
+
+        self.focus_editor_from_terminal_panel();
+        match self.open_file(&path) {
+            Ok(_) => {
+                if let Some(line) = reference.line {
+                    self.goto_line_col(line, reference.column);
+                }
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Show each finished command's exit status at the end of its prompt line
+    pub(crate) fn apply_terminal_command_decorations(&mut self, buffer_id: BufferId) {
+        let commands: Vec<ShellCommand> = self
+            .with_shell_integration(buffer_id, |shell| {
+                shell
+                    .commands()
+                    .iter()
+                    .filter(|command| command.finished)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let success = Style::default()
+            .fg(self.theme.line_number_fg)
+            .add_modifier(Modifier::DIM);
+        let failure = Style::default().fg(self.theme.diagnostic_error_fg);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let namespace = command_status_namespace();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &namespace);
+
+        let buffer_len = state.buffer.len();
+        for command in &commands {
+            let line = command.prompt_line;
+            let Some(line_start) = state.buffer.line_start_offset(line) else {
+                continue;
+            };
+            // Anchor on the newline, or the last character of a final line
+            let anchor = match state.buffer.line_start_offset(line + 1) {
+                Some(next_start) if next_start > line_start => next_start - 1,
+                _ if buffer_len > line_start => buffer_len - 1,
+                _ => continue,
+            };
+            let (text, style) = if command.failed() {
+                (
+                    format!("  ✗ {}", command.exit_code.unwrap_or_default()),
+                    failure,
+                )
+            } else {
+                ("  ✓".to_string(), success)
+            };
+            state.virtual_texts.add_inline(
+                &mut state.marker_list,
+                anchor,
+                text,
+                style,
+                VirtualTextPosition::EndOfLine,
+                namespace.clone(),
+                0,
+            );
+        }
+    }
+
+    /// Leave terminal mode for the scrollback view, as if the user pressed
+    /// the terminal escape key
+    fn exit_terminal_mode_to_scrollback(&mut self) {
+        let buffer_id = self.active_buffer();
+        self.terminal_mode = false;
+        self.key_context = crate::input::keybindings::KeyContext::Normal;
+        self.terminal_mode_resume.remove(&buffer_id);
+        self.sync_terminal_to_buffer(buffer_id);
+    }
+
+    /// Run `f` with the shell integration state of a terminal buffer
+    fn with_shell_integration<R>(
+        &self,
+        buffer_id: BufferId,
+        f: impl FnOnce(&crate::services::terminal::shell_integration::ShellIntegration) -> R,
+    ) -> Option<R> {
+        let terminal_id = self.get_terminal_id(buffer_id)?;
+        let handle = self.terminal_manager.get(terminal_id)?;
+        let state = handle.state.lock().ok()?;
+        Some(f(state.shell_integration()))
+    }
+}
+
+/// Status bar text describing how a command finished
+fn command_status_text(command: &ShellCommand) -> String {
+    match (command.finished, command.exit_code) {
+        (false, _) => t!("terminal.command_running").to_string(),
+        (true, Some(code)) => t!("terminal.command_exit_code", code = code).to_string(),
+        (true, None) => t!("terminal.command_finished").to_string(),
+    }
+}
+
+/// Strip ANSI escapes from a scrollback line, mapping byte offset `offset`
+/// of the raw line to the matching offset in the stripped text
+fn strip_ansi_with_offset(raw: &str, offset: usize) -> (String, usize) {
+    let mut parser = AnsiParser::new();
+    let mut text = String::with_capacity(raw.len());
+    let mut mapped = None;
+    for (i, ch) in raw.char_indices() {
+        if mapped.is_none() && i >= offset {
+            mapped = Some(text.len());
+        }
+        if parser.parse_char(ch).is_some() {
+            text.push(ch);
+        }
+    }
+    let mapped = mapped.unwrap_or(text.len());
+    (text, mapped)
+}
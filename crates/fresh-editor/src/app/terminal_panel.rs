@@ -0,0 +1,375 @@
+//! Bottom terminal panel
+//!
+//! The panel is a labeled split spanning the full width below every other
+//! split. Its tabs are terminal buffers, so several terminals can live in it
+//! side by side. Hiding the panel closes the split but keeps the terminals
+//! running; showing it again restores the same tabs at the same height.
+
+use super::{BufferId, Editor};
+use crate::model::event::{SplitDirection, SplitId};
+use crate::view::split::SplitViewState;
+use rust_i18n::t;
+
+/// Split label that marks the terminal panel
+pub(crate) const TERMINAL_PANEL_LABEL: &str = "terminal-panel";
+
+/// Share of the height given to the editor splits above the panel by default
+const DEFAULT_EDITOR_RATIO: f32 = 0.7;
+
+/// Terminal panel state kept while the panel is hidden
+#[derive(Debug, Clone)]
+pub(crate) struct TerminalPanelState {
+    /// Terminal buffers shown as tabs in the panel
+    tabs: Vec<BufferId>,
+    /// Tab that was active when the panel was hidden
+    active: Option<BufferId>,
+    /// Share of the height given to the splits above the panel
+    ratio: f32,
+    /// Split that had focus before the panel was focused
+    return_split: Option<SplitId>,
+}
+
+impl Default for TerminalPanelState {
+    fn default() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: None,
+            ratio: DEFAULT_EDITOR_RATIO,
+            return_split: None,
+        }
+    }
+}
+
+impl Editor {
+    /// The split hosting the terminal panel, if the panel is visible
+    pub fn terminal_panel_split(&self) -> Option<SplitId> {
+        self.split_manager.find_split_by_label(TERMINAL_PANEL_LABEL)
+    }
+
+    /// Show and focus the terminal panel, or hide it when it already has focus
+    pub fn toggle_terminal_panel(&mut self) {
+        match self.terminal_panel_split() {
+            Some(split_id) if split_id == self.split_manager.active_split() => {
+                self.hide_terminal_panel();
+            }
+            Some(split_id) => self.focus_terminal_panel(split_id),
+            None => {
+                self.show_terminal_panel(true);
+            }
+        }
+    }
+
+    /// Open a new terminal tab in the panel, showing the panel if needed
+    pub fn new_panel_terminal(&mut self) {
+        let split_id = match self.terminal_panel_split() {
+            Some(split_id) => split_id,
+            None => {
+                // Showing an empty panel already starts a fresh terminal
+                let had_tabs = self.live_panel_tabs().is_some();
+                match self.show_terminal_panel(true) {
+                    Some(split_id) if had_tabs => split_id,
+                    _ => return,
+                }
+            }
+        };
+        self.focus_terminal_panel(split_id);
+
+        match self.spawn_terminal() {
+            Ok(terminal_id) => {
+                let buffer_id = self.create_terminal_buffer_attached(terminal_id, split_id);
+                self.set_active_buffer(buffer_id);
+                self.enter_terminal_mode();
+                self.set_terminal_opened_status(terminal_id);
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("terminal.failed_to_open", error = e.to_string()).to_string(),
+                );
+                tracing::error!("Failed to open terminal: {}", e);
+            }
+        }
+    }
+
+    /// Open a new terminal tab in the panel without moving focus to it
+    pub(crate) fn add_panel_terminal(&mut self) -> Option<BufferId> {
+        let terminal_id = match self.spawn_terminal() {
+            Ok(terminal_id) => terminal_id,
+            Err(e) => {
+                self.set_status_message(
+                    t!("terminal.failed_to_open", error = e.to_string()).to_string(),
+                );
+                tracing::error!("Failed to open terminal: {}", e);
+                return None;
+            }
+        };
+        let buffer_id = self.create_terminal_buffer_detached(terminal_id);
+
+        match self.terminal_panel_split() {
+            Some(split_id) => {
+                let _ = self.split_manager.set_split_buffer(split_id, buffer_id);
+                if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                    view_state.add_buffer(buffer_id);
+                    view_state.switch_buffer(buffer_id);
+                }
+                self.resize_visible_terminals();
+            }
+            None => {
+                let mut tabs = self.live_panel_tabs().unwrap_or_default();
+                tabs.push(buffer_id);
+                self.terminal_panel.tabs = tabs;
+                self.terminal_panel.active = Some(buffer_id);
+                self.show_terminal_panel(false)?;
+            }
+        }
+        Some(buffer_id)
+    }
+
+    /// Show the panel with `buffer_id` as its active tab, without moving focus
+    pub(crate) fn reveal_panel_terminal(&mut self, buffer_id: BufferId) {
+        match self.terminal_panel_split() {
+            Some(split_id) => {
+                let in_panel = self
+                    .split_view_states
+                    .get(&split_id)
+                    .is_some_and(|vs| vs.has_buffer(buffer_id));
+                if in_panel && split_id != self.split_manager.active_split() {
+                    let _ = self.split_manager.set_split_buffer(split_id, buffer_id);
+                    if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                        view_state.switch_buffer(buffer_id);
+                    }
+                }
+            }
+            None => {
+                if self.terminal_panel.tabs.contains(&buffer_id) {
+                    self.terminal_panel.active = Some(buffer_id);
+                    self.show_terminal_panel(false);
+                }
+            }
+        }
+    }
+
+    /// The terminal shown in the panel, if the panel has one
+    pub(crate) fn panel_active_terminal(&self) -> Option<BufferId> {
+        let buffer_id = match self.terminal_panel_split() {
+            Some(split_id) => self.split_manager.get_buffer_id(split_id),
+            None => self.terminal_panel.active,
+        }?;
+        self.is_terminal_buffer(buffer_id).then_some(buffer_id)
+    }
+
+    /// Move focus from the panel back to the split it was entered from, so
+    /// files opened from a panel terminal don't replace it
+    pub(crate) fn focus_editor_from_terminal_panel(&mut self) {
+        if self.terminal_panel_split() != Some(self.split_manager.active_split()) {
+            return;
+        }
+        let Some(target) = self
+            .terminal_panel
+            .return_split
+            .filter(|id| self.split_manager.root().find(*id).is_some())
+        else {
+            return;
+        };
+        if let Some(buffer_id) = self.split_manager.get_buffer_id(target) {
+            self.focus_split(target, buffer_id);
+        }
+    }
+
+    /// Prompt for a new name for the active terminal's tab
+    pub fn start_rename_terminal_prompt(&mut self) {
+        let buffer_id = self.active_buffer();
+        if !self.is_terminal_buffer(buffer_id) {
+            self.set_status_message(t!("status.not_viewing_terminal").to_string());
+            return;
+        }
+        let current = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .map(|m| m.display_name.trim_matches('*').to_string())
+            .unwrap_or_default();
+        self.prompt = Some(crate::view::prompt::Prompt::with_initial_text(
+            t!("terminal.rename_prompt").to_string(),
+            crate::view::prompt::PromptType::RenameTerminal { buffer_id },
+            current,
+        ));
+    }
+
+    /// Rename a terminal's tab
+    pub fn rename_terminal(&mut self, buffer_id: BufferId, name: &str) {
+        let name = name.trim();
+        if name.is_empty() || !self.is_terminal_buffer(buffer_id) {
+            return;
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.display_name = format!("*{}*", name);
+        }
+        self.set_status_message(t!("terminal.renamed", name = name).to_string());
+    }
+
+    /// Hide the panel for a layout change; returns whether it was visible.
+    ///
+    /// Call [`Editor::restore_terminal_panel_after_layout`] afterwards so the
+    /// panel survives operations that rebuild the split tree.
+    pub(crate) fn detach_terminal_panel_for_layout(&mut self) -> bool {
+        match self.terminal_panel_split() {
+            // The panel is the split being kept; it stops being the panel
+            Some(split_id) if split_id == self.split_manager.active_split() => {
+                self.split_manager.clear_label(split_id);
+                false
+            }
+            Some(_) => self.close_terminal_panel_split(),
+            None => false,
+        }
+    }
+
+    /// Bring the panel back after a layout change if it was visible before
+    pub(crate) fn restore_terminal_panel_after_layout(&mut self, was_visible: bool) {
+        if was_visible && self.terminal_panel_split().is_none() {
+            self.show_terminal_panel(false);
+        }
+    }
+
+    /// Remembered panel tabs whose terminals are still alive, if any
+    fn live_panel_tabs(&self) -> Option<Vec<BufferId>> {
+        let tabs: Vec<BufferId> = self
+            .terminal_panel
+            .tabs
+            .iter()
+            .copied()
+            .filter(|id| self.terminal_buffers.contains_key(id) && self.buffers.contains_key(id))
+            .collect();
+        (!tabs.is_empty()).then_some(tabs)
+    }
+
+    /// Create the panel split with the remembered tabs (or a new terminal)
+    fn show_terminal_panel(&mut self, focus: bool) -> Option<SplitId> {
+        let tabs = match self.live_panel_tabs() {
+            Some(tabs) => tabs,
+            None => match self.spawn_terminal() {
+                Ok(terminal_id) => {
+                    self.set_terminal_opened_status(terminal_id);
+                    vec![self.create_terminal_buffer_detached(terminal_id)]
+                }
+                Err(e) => {
+                    self.set_status_message(
+                        t!("terminal.failed_to_open", error = e.to_string()).to_string(),
+                    );
+                    tracing::error!("Failed to open terminal: {}", e);
+                    return None;
+                }
+            },
+        };
+        let active = self
+            .terminal_panel
+            .active
+            .filter(|id| tabs.contains(id))
+            .unwrap_or(tabs[tabs.len() - 1]);
+
+        let split_id = match self.split_manager.split_root(
+            SplitDirection::Horizontal,
+            active,
+            self.terminal_panel.ratio,
+        ) {
+            Ok(split_id) => split_id,
+            Err(e) => {
+                tracing::error!("Failed to create terminal panel split: {}", e);
+                return None;
+            }
+        };
+        self.split_manager
+            .set_label(split_id, TERMINAL_PANEL_LABEL.to_string());
+
+        let mut view_state =
+            SplitViewState::with_buffer(self.terminal_width, self.terminal_height, active);
+        view_state.open_buffers = tabs;
+        view_state.viewport.line_wrap_enabled = false;
+        self.split_view_states.insert(split_id, view_state);
+
+        if focus {
+            self.focus_terminal_panel(split_id);
+        }
+        self.resize_visible_terminals();
+        Some(split_id)
+    }
+
+    /// Focus the panel, remembering where to return when it is hidden
+    fn focus_terminal_panel(&mut self, split_id: SplitId) {
+        let current = self.split_manager.active_split();
+        if current != split_id {
+            self.terminal_panel.return_split = Some(current);
+        }
+        let buffer_id = self
+            .split_manager
+            .get_buffer_id(split_id)
+            .unwrap_or_else(|| self.active_buffer());
+        self.focus_split(split_id, buffer_id);
+        self.enter_terminal_mode();
+    }
+
+    /// Hide the panel, keeping its terminals running
+    fn hide_terminal_panel(&mut self) {
+        if !self.close_terminal_panel_split() {
+            self.set_status_message(t!("terminal.panel_only_split").to_string());
+            return;
+        }
+
+        let target = self
+            .terminal_panel
+            .return_split
+            .take()
+            .filter(|id| self.split_manager.root().find(*id).is_some())
+            .unwrap_or_else(|| self.split_manager.active_split());
+        if let Some(buffer_id) = self.split_manager.get_buffer_id(target) {
+            self.focus_split(target, buffer_id);
+        }
+        self.resize_visible_terminals();
+    }
+
+    /// Remember the panel's tabs and height, then close its split.
+    /// Returns false if the panel is the only split and cannot be closed.
+    fn close_terminal_panel_split(&mut self) -> bool {
+        let Some(split_id) = self.terminal_panel_split() else {
+            return false;
+        };
+        if self.split_manager.root().count_leaves() <= 1 {
+            return false;
+        }
+
+        let tabs = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.open_buffers.clone())
+            .unwrap_or_default();
+        let (terminal_tabs, other_tabs): (Vec<BufferId>, Vec<BufferId>) = tabs
+            .into_iter()
+            .partition(|id| self.terminal_buffers.contains_key(id));
+        self.terminal_panel.active = self.split_manager.get_buffer_id(split_id);
+        self.terminal_panel.tabs = terminal_tabs;
+        // The panel is the second child, so the ratio is the editor's share
+        if let Some((parent_id, false)) = self.split_manager.root().find_parent(split_id) {
+            if let Some(ratio) = self.split_manager.get_ratio(parent_id) {
+                self.terminal_panel.ratio = ratio;
+            }
+        }
+
+        if self.terminal_mode {
+            self.terminal_mode = false;
+            self.key_context = crate::input::keybindings::KeyContext::Normal;
+        }
+        if self.split_manager.close_split(split_id).is_err() {
+            return false;
+        }
+        self.split_view_states.remove(&split_id);
+
+        // Files dragged into the panel move to the split that takes over
+        let active_split = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            for buffer_id in other_tabs {
+                if !view_state.open_buffers.contains(&buffer_id) {
+                    view_state.open_buffers.push(buffer_id);
+                }
+            }
+        }
+        true
+    }
+}
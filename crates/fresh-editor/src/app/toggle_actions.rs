@@ -10,7 +10,6 @@ use rust_i18n::t;
 
 use crate::config::Config;
 use crate::config_io::{ConfigLayer, ConfigResolver};
-use crate::input::keybindings::KeybindingResolver;
 
 use super::Editor;
 
@@ -43,6 +42,25 @@ impl Editor {
         }
     }
 
+    /// Toggle read-only mode for the active buffer
+    ///
+    /// Also the override for files that were opened read-only because they
+    /// are not writable.
+    pub fn toggle_read_only(&mut self) {
+        let state = self.active_state_mut();
+        if state.editing_disabled {
+            // Virtual and binary buffers stay non-editable
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+        state.read_only = !state.read_only;
+        if state.read_only {
+            self.set_status_message(t!("toggle.read_only_enabled").to_string());
+        } else {
+            self.set_status_message(t!("toggle.read_only_disabled").to_string());
+        }
+    }
+
     /// Toggle debug highlight mode for the active buffer
     /// When enabled, shows byte positions and highlight span info for debugging
     pub fn toggle_debug_highlights(&mut self) {
@@ -191,6 +209,11 @@ impl Editor {
         self.gpm_active = active;
     }
 
+    /// Open files in read-only mode from now on (`--readonly`)
+    pub fn set_open_read_only(&mut self, read_only: bool) {
+        self.open_read_only = read_only;
+    }
+
     /// Toggle inlay hints visibility
     pub fn toggle_inlay_hints(&mut self) {
         self.config.editor.enable_inlay_hints = !self.config.editor.enable_inlay_hints;
@@ -276,6 +299,7 @@ impl Editor {
     /// Uses the layered config system to properly merge with defaults.
     pub fn reload_config(&mut self) {
         let old_theme = self.config.theme.clone();
+        let old_color_mode = self.config.editor.color_mode;
         self.config = Config::load_with_layers(&self.dir_context, &self.working_dir);
 
         // Refresh cached raw user config for plugins
@@ -291,8 +315,17 @@ impl Editor {
             }
         }
 
+        if old_color_mode != self.config.editor.color_mode {
+            self.color_capability =
+                crate::view::color_support::ColorCapability::resolve(self.config.editor.color_mode);
+        }
+
+        // Re-render inline diagnostics with the new settings and colors
+        self.refresh_diagnostic_lens();
+
         // Always reload keybindings (complex types don't implement PartialEq)
-        self.keybindings = KeybindingResolver::new(&self.config);
+        self.keybindings.reset_from_config(&self.config);
+        self.refresh_user_commands();
 
         // Update clipboard configuration
         self.clipboard.apply_config(&self.config.clipboard);
@@ -323,6 +356,7 @@ impl Editor {
 
         let theme_loader = ThemeLoader::new(self.dir_context.themes_dir());
         self.theme_registry = theme_loader.load_all();
+        self.theme_file_stamps = theme_loader.file_stamps();
 
         // Re-apply current theme if it still exists, otherwise it might have been updated
         if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
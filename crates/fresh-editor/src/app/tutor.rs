@@ -0,0 +1,394 @@
+//! Interactive tutorial (`Tutorial` command and `fresh --tutor`)
+//!
+//! The tutorial is an editable scratch copy of `docs/tutor.txt` with the keys
+//! of the active keymap filled in. Each lesson ends with an exercise on lines
+//! marked `-->`; the editor checks the exercises as the user works through
+//! them and remembers finished lessons in the data directory, so the next
+//! run ticks them off and starts at the first unfinished one.
+
+use super::Editor;
+use crate::input::keybindings::{KeyContext, KeybindingResolver};
+use crate::model::event::BufferId;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// The tutorial text; `{key:<action>}` is replaced with the action's key
+pub const TUTOR_CONTENT: &str = include_str!("../../docs/tutor.txt");
+
+/// Name of the tutorial buffer
+pub const TUTOR_BUFFER_NAME: &str = "*Tutor*";
+
+/// Prefix of the lines an exercise works on
+const EXERCISE_MARKER: &str = "--> ";
+
+/// Suffix added to the heading of a finished lesson
+const DONE_MARK: &str = "  [done]";
+
+/// What finishes a lesson
+#[derive(Debug, Clone, Copy)]
+enum Exercise {
+    /// The exercise lines read `expected`
+    Text(&'static [&'static str]),
+    /// The cursor is on `word` in an exercise line
+    CursorOn(&'static str),
+    /// The selection is exactly `text`
+    Selection(&'static str),
+    /// Nothing to do
+    None,
+}
+
+/// Exercise of each lesson, in the order of the lessons in the text
+const EXERCISES: &[Exercise] = &[
+    Exercise::CursorOn("treasure"),
+    Exercise::Text(&["The quick brown fox jumps over the lazy dog."]),
+    Exercise::Selection("elephant"),
+    Exercise::Text(&["let sum = 0;", "sum = sum + 1;", "print(sum);"]),
+    Exercise::CursorOn("needle"),
+    Exercise::Text(&["apple", "banana", "cherry", "date"]),
+    Exercise::None,
+];
+
+/// Number of lessons with an exercise
+fn exercise_count() -> usize {
+    EXERCISES
+        .iter()
+        .filter(|exercise| !matches!(exercise, Exercise::None))
+        .count()
+}
+
+/// Finished lessons, saved in the data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TutorProgress {
+    /// Numbers of the finished lessons, from 1
+    completed: BTreeSet<usize>,
+}
+
+/// Buffer version and cursors (position, anchor) at a check
+type TutorSnapshot = (u64, Vec<(usize, Option<usize>)>);
+
+/// The open tutorial and what it last checked
+#[derive(Debug, Default)]
+pub(crate) struct TutorState {
+    buffer_id: Option<BufferId>,
+    progress: TutorProgress,
+    last_checked: Option<TutorSnapshot>,
+}
+
+/// A lesson found in the buffer text
+#[derive(Debug)]
+struct Lesson {
+    /// Number from the heading, from 1
+    number: usize,
+    /// Byte range of the lesson, from its heading to the next one
+    range: Range<usize>,
+    /// Byte ranges of its exercise lines, without the marker
+    exercise_lines: Vec<Range<usize>>,
+}
+
+/// Number of the lesson a `Lesson N: ...` heading starts
+fn lesson_heading(line: &str) -> Option<usize> {
+    let rest = line.strip_prefix("Lesson ")?;
+    let (number, _) = rest.split_once(':')?;
+    number.trim().parse().ok()
+}
+
+/// The lessons of the tutorial text
+fn find_lessons(text: &str) -> Vec<Lesson> {
+    let mut lessons: Vec<Lesson> = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if let Some(number) = lesson_heading(content) {
+            if let Some(last) = lessons.last_mut() {
+                last.range.end = offset;
+            }
+            lessons.push(Lesson {
+                number,
+                range: offset..text.len(),
+                exercise_lines: Vec::new(),
+            });
+        } else if let (Some(lesson), true) =
+            (lessons.last_mut(), content.starts_with(EXERCISE_MARKER))
+        {
+            let start = offset + EXERCISE_MARKER.len();
+            lesson.exercise_lines.push(start..offset + content.len());
+        }
+        offset += line.len();
+    }
+    lessons
+}
+
+impl Exercise {
+    /// Whether the exercise of `lesson` is done
+    fn is_done(
+        &self,
+        text: &str,
+        lesson: &Lesson,
+        cursor: usize,
+        selection: Option<Range<usize>>,
+    ) -> bool {
+        let line_containing = |pos: usize| {
+            lesson
+                .exercise_lines
+                .iter()
+                .find(|line| line.start <= pos && pos <= line.end)
+        };
+        match *self {
+            Exercise::Text(expected) => {
+                let lines: Vec<&str> = lesson
+                    .exercise_lines
+                    .iter()
+                    .map(|line| text[line.clone()].trim_end())
+                    .collect();
+                lines == expected
+            }
+            Exercise::CursorOn(word) => {
+                let Some(line) = line_containing(cursor) else {
+                    return false;
+                };
+                text[line.clone()].match_indices(word).any(|(index, _)| {
+                    let start = line.start + index;
+                    start <= cursor && cursor <= start + word.len()
+                })
+            }
+            Exercise::Selection(expected) => selection.is_some_and(|range| {
+                line_containing(range.start).is_some() && &text[range] == expected
+            }),
+            Exercise::None => false,
+        }
+    }
+}
+
+/// The tutorial text with the keys of `keybindings` filled in and finished
+/// lessons marked
+fn render_tutor(keybindings: &KeybindingResolver, completed: &BTreeSet<usize>) -> String {
+    let mut out = String::with_capacity(TUTOR_CONTENT.len());
+    let mut rest = TUTOR_CONTENT;
+    while let Some(start) = rest.find("{key:") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + "{key:".len()..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let action = &after[..end];
+        match keybindings.find_keybinding_for_action(action, KeyContext::Normal) {
+            Some(key) => out.push_str(&key),
+            None => out.push_str(&format!(
+                "\"{}\" in the command palette",
+                KeybindingResolver::format_action_from_str(action)
+            )),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+
+    out.split_inclusive('\n')
+        .map(|line| match lesson_heading(line.trim_end()) {
+            Some(number) if completed.contains(&number) => {
+                format!("{}{}\n", line.trim_end(), DONE_MARK)
+            }
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+impl Editor {
+    fn tutor_progress_path(&self) -> std::path::PathBuf {
+        self.dir_context.data_dir.join("tutor.json")
+    }
+
+    /// Open the tutorial at the first unfinished lesson
+    pub fn show_tutor(&mut self) {
+        if let Some(buffer_id) = self
+            .tutor
+            .buffer_id
+            .filter(|id| self.buffers.contains_key(id))
+        {
+            self.set_active_buffer(buffer_id);
+            return;
+        }
+
+        self.tutor.progress = std::fs::read_to_string(self.tutor_progress_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let text = render_tutor(&self.keybindings, &self.tutor.progress.completed);
+        let resume_at = find_lessons(&text)
+            .iter()
+            .find(|lesson| !self.tutor.progress.completed.contains(&lesson.number))
+            .map_or(0, |lesson| lesson.range.start);
+
+        let buffer_id =
+            self.create_virtual_buffer(TUTOR_BUFFER_NAME.to_string(), String::new(), false);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &text);
+            state.buffer.clear_modified();
+        }
+        self.tutor.buffer_id = Some(buffer_id);
+        self.tutor.last_checked = None;
+        self.set_active_buffer(buffer_id);
+        if !self.tutor.progress.completed.is_empty() {
+            let cursor = self.active_cursors_mut().primary_mut();
+            cursor.position = resume_at;
+            cursor.anchor = None;
+            self.set_status_message(
+                t!(
+                    "tutor.resumed",
+                    done = self.tutor.progress.completed.len(),
+                    total = exercise_count()
+                )
+                .to_string(),
+            );
+        }
+    }
+
+    /// Check the exercises of the tutorial if it is the active buffer (called
+    /// from the main loop)
+    ///
+    /// Returns true if a lesson was finished (requires re-render).
+    pub fn check_tutor(&mut self) -> bool {
+        let Some(buffer_id) = self.tutor.buffer_id else {
+            return false;
+        };
+        if self.active_buffer() != buffer_id {
+            return false;
+        }
+        let Some(version) = self.buffers.get(&buffer_id).map(|s| s.buffer.version()) else {
+            self.tutor.buffer_id = None;
+            return false;
+        };
+        let cursors = self.active_cursors();
+        let snapshot: TutorSnapshot = (
+            version,
+            cursors
+                .iter()
+                .map(|(_, cursor)| (cursor.position, cursor.anchor))
+                .collect(),
+        );
+        let cursor = cursors.primary().position;
+        let selection = cursors.primary().selection_range();
+        if self.tutor.last_checked.as_ref() == Some(&snapshot) {
+            return false;
+        }
+        self.tutor.last_checked = Some(snapshot);
+
+        let Some(text) = self.buffers.get_mut(&buffer_id).and_then(|state| {
+            // It is a scratch copy, so closing it doesn't ask to save
+            state.buffer.clear_modified();
+            state.buffer.to_string()
+        }) else {
+            return false;
+        };
+
+        let mut finished = Vec::new();
+        for lesson in find_lessons(&text) {
+            if self.tutor.progress.completed.contains(&lesson.number) {
+                continue;
+            }
+            let Some(exercise) = EXERCISES.get(lesson.number.wrapping_sub(1)) else {
+                continue;
+            };
+            if exercise.is_done(&text, &lesson, cursor, selection.clone()) {
+                finished.push(lesson.number);
+            }
+        }
+        if finished.is_empty() {
+            return false;
+        }
+
+        self.tutor
+            .progress
+            .completed
+            .extend(finished.iter().copied());
+        self.save_tutor_progress();
+        let done = self.tutor.progress.completed.len();
+        let total = exercise_count();
+        let message = if done >= total {
+            t!("tutor.all_done")
+        } else {
+            t!(
+                "tutor.lesson_done",
+                lesson = finished[0],
+                done = done,
+                total = total
+            )
+        };
+        self.set_status_message(message.to_string());
+        true
+    }
+
+    fn save_tutor_progress(&self) {
+        let path = self.tutor_progress_path();
+        let result = serde_json::to_string(&self.tutor.progress)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, json)
+            });
+        if let Err(e) = result {
+            tracing::warn!("Failed to save tutorial progress to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_every_lesson_has_an_exercise() {
+        let lessons = find_lessons(TUTOR_CONTENT);
+        assert_eq!(lessons.len(), EXERCISES.len());
+        for (index, (lesson, exercise)) in lessons.iter().zip(EXERCISES).enumerate() {
+            assert_eq!(lesson.number, index + 1);
+            if let Exercise::Text(expected) = exercise {
+                assert_eq!(lesson.exercise_lines.len(), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_placeholders_are_actions() {
+        let mut rest = TUTOR_CONTENT;
+        while let Some(start) = rest.find("{key:") {
+            let after = &rest[start + 5..];
+            let end = after.find('}').unwrap();
+            let action = &after[..end];
+            assert!(
+                crate::input::keybindings::Action::from_str(action, &Default::default()).is_some(),
+                "unknown action in tutorial: {}",
+                action
+            );
+            rest = &after[end..];
+        }
+    }
+
+    #[test]
+    fn test_render_fills_keys_and_marks_done() {
+        let keybindings = KeybindingResolver::new(&Config::default());
+        let completed = BTreeSet::from([2]);
+        let text = render_tutor(&keybindings, &completed);
+        assert!(!text.contains("{key:"));
+        assert!(text.contains("Lesson 2: Typing and deleting  [done]\n"));
+        assert!(text.contains("Lesson 3: Selecting\n"));
+    }
+
+    #[test]
+    fn test_exercises_are_checked() {
+        let text = "Lesson 1: Words\n--> one two\nLesson 2: Fix\n--> a b \n";
+        let lessons = find_lessons(text);
+        let two = text.find("two").unwrap();
+
+        assert!(Exercise::CursorOn("two").is_done(text, &lessons[0], two + 1, None));
+        assert!(!Exercise::CursorOn("two").is_done(text, &lessons[0], 0, None));
+        assert!(Exercise::Selection("two").is_done(text, &lessons[0], 0, Some(two..two + 3)));
+        assert!(Exercise::Text(&["a b"]).is_done(text, &lessons[1], 0, None));
+        assert!(!Exercise::Text(&["a c"]).is_done(text, &lessons[1], 0, None));
+    }
+}
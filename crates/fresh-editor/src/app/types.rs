@@ -120,6 +120,10 @@ pub struct BufferMetadata {
     /// For file-backed buffers, recovery ID is computed from the path hash.
     /// For unnamed buffers, this is generated once and reused across auto-saves.
     pub recovery_id: Option<String>,
+
+    /// Whether the file is still being read on a background thread.
+    /// The buffer is an empty, read-only placeholder until loading finishes.
+    pub loading: bool,
 }
 
 impl BufferMetadata {
@@ -175,6 +179,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         }
     }
 
@@ -194,6 +199,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         }
     }
 
@@ -232,6 +238,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         }
     }
 
@@ -332,6 +339,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            loading: false,
         }
     }
 
@@ -349,6 +357,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: true,
             recovery_id: None,
+            loading: false,
         }
     }
 
@@ -368,6 +377,18 @@ pub(super) struct MacroRecordingState {
     pub actions: Vec<Action>,
 }
 
+/// The last edit, kept for repeating it
+#[derive(Debug, Clone, Default)]
+pub(super) struct LastEdit {
+    /// Actions that made the edit
+    pub actions: Vec<Action>,
+    /// Whether more typing still extends the edit
+    pub open: bool,
+    /// Selection action handled just before, which an edit replacing the
+    /// selection starts with
+    pub selection: Option<Action>,
+}
+
 /// LSP progress information
 #[derive(Debug, Clone)]
 pub(super) struct LspProgressInfo {
@@ -375,6 +396,8 @@ pub(super) struct LspProgressInfo {
     pub title: String,
     pub message: Option<String>,
     pub percentage: Option<u32>,
+    /// When the operation began (drives the spinner and picks the newest operation)
+    pub started: std::time::Instant,
 }
 
 /// LSP message entry (for window messages and logs)
@@ -442,6 +465,8 @@ pub enum HoverTarget {
     StatusBarEncodingIndicator,
     /// Hovering over the status bar language indicator
     StatusBarLanguageIndicator,
+    /// Hovering over a plugin's status bar segment (index in display order)
+    StatusBarPluginSegment(usize),
     /// Hovering over the search options "Case Sensitive" checkbox
     SearchOptionCaseSensitive,
     /// Hovering over the search options "Whole Word" checkbox
@@ -780,6 +805,8 @@ pub(crate) struct CachedLayout {
     pub status_bar_language_area: Option<(u16, u16, u16)>,
     /// Status bar message area (row, start_col, end_col) - clickable to show status log
     pub status_bar_message_area: Option<(u16, u16, u16)>,
+    /// Status bar plugin segment areas (row, start_col, end_col), in display order
+    pub status_bar_plugin_segment_areas: Vec<(u16, u16, u16)>,
     /// Search options layout for checkbox hit testing
     pub search_options_layout: Option<crate::view::ui::status_bar::SearchOptionsLayout>,
     /// Menu bar layout for hit testing
@@ -1,13 +1,18 @@
 //! Undo and redo action handlers.
 
 use super::Editor;
-use rust_i18n::t;
 
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
+            return;
+        }
+
+        // A shared buffer only undoes this participant's own edits
+        if self.collab_owns_undo() {
+            self.collab_undo();
             return;
         }
 
@@ -38,7 +43,12 @@ impl Editor {
     /// Handle Redo action - reapply an undone edit operation.
     pub fn handle_redo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
+            return;
+        }
+
+        if self.collab_owns_undo() {
+            self.collab_redo();
             return;
         }
 
@@ -0,0 +1,118 @@
+//! Unicode character picker and inspector
+//!
+//! The picker searches characters and emoji by name, block, emoji group or
+//! shortcode and inserts the chosen one at every cursor. The inspector shows
+//! the code points, UTF-8 bytes and names making up the character under the
+//! cursor.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::primitives::unicode_chars::{self, CharEntry};
+use crate::view::popup::{Popup, PopupPosition};
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+/// Most characters listed in the picker at once
+const MAX_PICKER_RESULTS: usize = 200;
+
+fn picker_suggestion(entry: CharEntry) -> Suggestion {
+    Suggestion {
+        text: format!("{}  {}", entry.display(), entry.name),
+        description: Some(format!("{}  {}", entry.codepoints(), entry.category)),
+        value: Some(entry.text),
+        disabled: false,
+        keybinding: None,
+        source: None,
+    }
+}
+
+/// Suggestions for a picker query
+pub(super) fn unicode_picker_suggestions(query: &str) -> Vec<Suggestion> {
+    unicode_chars::search(query, MAX_PICKER_RESULTS)
+        .into_iter()
+        .map(picker_suggestion)
+        .collect()
+}
+
+impl Editor {
+    /// Open the prompt for picking a character to insert
+    pub(super) fn start_unicode_picker(&mut self) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("unicode.picker_prompt").to_string(),
+            PromptType::InsertUnicodeCharacter,
+            Vec::new(),
+        ));
+        self.update_prompt_suggestions();
+    }
+
+    /// Insert the character picked in the prompt at every cursor
+    pub(super) fn insert_picked_character(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        self.insert_text_at_cursors(text, "Insert Character");
+    }
+
+    /// Show the code points of the character under the cursor in a popup
+    pub(super) fn inspect_character(&mut self) {
+        let position = self.active_cursors().primary().position;
+        let state = self.active_state_mut();
+        let end = state.buffer.next_grapheme_boundary(position);
+        let grapheme = state.get_text_range(position, end);
+        if grapheme.is_empty() {
+            self.set_status_message(t!("unicode.no_character").to_string());
+            return;
+        }
+
+        let codepoints = unicode_chars::inspect(&grapheme);
+        let byte_count: usize = codepoints.iter().map(|info| info.utf8.len()).sum();
+        let shown = unicode_chars::display_text(&grapheme);
+        let mut lines = vec![match unicode_chars::emoji_name(&grapheme) {
+            Some(name) => format!("{}  {}", shown, name),
+            None => shown,
+        }];
+        lines.push(
+            t!(
+                "unicode.inspect_summary",
+                codepoints = codepoints.len(),
+                bytes = byte_count
+            )
+            .to_string(),
+        );
+        lines.push(String::new());
+
+        let bytes_width = codepoints
+            .iter()
+            .map(|info| unicode_chars::hex_bytes(&info.utf8).len())
+            .max()
+            .unwrap_or(0);
+        for info in &codepoints {
+            let name = info
+                .name
+                .clone()
+                .unwrap_or_else(|| t!("unicode.unnamed").to_string());
+            let mut line = format!(
+                "{:<8} {:<width$}  {}",
+                unicode_chars::codepoint(info.ch),
+                unicode_chars::hex_bytes(&info.utf8),
+                name,
+                width = bytes_width
+            );
+            if let Some(block) = info.block {
+                line.push_str(&format!(" ({})", block));
+            }
+            lines.push(line);
+        }
+
+        let mut popup = Popup::text(lines, &self.theme);
+        popup.title = Some(t!("unicode.inspect_title").to_string());
+        popup.transient = true;
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 80;
+        self.active_state_mut().popups.show(popup);
+    }
+}
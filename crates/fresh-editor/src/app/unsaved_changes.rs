@@ -0,0 +1,333 @@
+//! Viewing and reverting the unsaved changes of a buffer
+//!
+//! The buffer is compared with its file on disk, either side by side (the
+//! disk diff session, with the buffer editable on the right) or as a unified
+//! diff in a read-only buffer. In both views the change under the cursor can
+//! be reverted to its version on disk. Local history views compare the
+//! buffer with a snapshot instead of the file on disk.
+
+use super::Editor;
+use crate::input::buffer_mode::UNSAVED_CHANGES_MODE;
+use crate::model::composite_buffer::DiffHunk;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, Event, OverlayFace};
+use crate::model::line_diff::{diff_hunks, revert_hunk_edit};
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::local_history::Snapshot;
+use crate::view::overlay::OverlayNamespace;
+use fresh_core::api::{OverlayColorSpec, OverlayOptions};
+use rust_i18n::t;
+
+/// Unchanged lines shown around each change in the unified view
+const CONTEXT_LINES: usize = 3;
+
+/// Overlay namespace of the unified view's line colors
+const UNIFIED_NAMESPACE: &str = "unsaved-changes";
+
+/// An open view of a buffer's unsaved changes
+#[derive(Debug, Clone)]
+pub(super) struct UnsavedChangesView {
+    /// Buffer whose changes are shown
+    pub source: BufferId,
+    /// For the unified view, the hunk shown on each of its lines
+    line_hunks: Option<Vec<Option<usize>>>,
+    /// Local history snapshot the buffer is compared with, instead of the
+    /// file on disk
+    pub snapshot: Option<Snapshot>,
+}
+
+impl UnsavedChangesView {
+    /// A side-by-side view of `source` compared with `snapshot`, or with the
+    /// file on disk
+    pub fn side_by_side(source: BufferId, snapshot: Option<Snapshot>) -> Self {
+        Self {
+            source,
+            line_hunks: None,
+            snapshot,
+        }
+    }
+}
+
+/// Lines of a unified diff of `old` and `new`, each with the hunk it shows
+fn unified_diff_lines(old: &str, new: &str, hunks: &[DiffHunk]) -> Vec<(String, Option<usize>)> {
+    let old_lines: Vec<&str> = old.split('\n').map(|l| l.trim_end_matches('\r')).collect();
+    let new_lines: Vec<&str> = new.split('\n').map(|l| l.trim_end_matches('\r')).collect();
+    // The empty piece after a final newline isn't a line of its own
+    let old_len = old_lines.len() - usize::from(old.ends_with('\n'));
+
+    let mut lines = Vec::new();
+    let mut index = 0;
+    while index < hunks.len() {
+        // Hunks whose context would touch are shown in one block
+        let first = index;
+        let mut last = index;
+        while let Some(next) = hunks.get(last + 1) {
+            let end = hunks[last].old_start + hunks[last].old_count;
+            if next.old_start > end + 2 * CONTEXT_LINES {
+                break;
+            }
+            last += 1;
+        }
+        index = last + 1;
+
+        let old_start = hunks[first].old_start.saturating_sub(CONTEXT_LINES);
+        let old_end = (hunks[last].old_start + hunks[last].old_count + CONTEXT_LINES)
+            .min(old_len)
+            .max(hunks[last].old_start + hunks[last].old_count);
+        let new_start = hunks[first].new_start - (hunks[first].old_start - old_start);
+        let added: usize = hunks[first..=last].iter().map(|h| h.new_count).sum();
+        let removed: usize = hunks[first..=last].iter().map(|h| h.old_count).sum();
+        let old_count = old_end - old_start;
+        lines.push((
+            format!(
+                "@@ -{},{} +{},{} @@",
+                old_start + 1,
+                old_count,
+                new_start + 1,
+                old_count + added - removed
+            ),
+            None,
+        ));
+
+        let mut old_line = old_start;
+        for (i, hunk) in hunks.iter().enumerate().take(last + 1).skip(first) {
+            for line in &old_lines[old_line..hunk.old_start] {
+                lines.push((format!(" {line}"), None));
+            }
+            for line in &old_lines[hunk.old_start..hunk.old_start + hunk.old_count] {
+                lines.push((format!("-{line}"), Some(i)));
+            }
+            for line in &new_lines[hunk.new_start..hunk.new_start + hunk.new_count] {
+                lines.push((format!("+{line}"), Some(i)));
+            }
+            old_line = hunk.old_start + hunk.old_count;
+        }
+        for line in &old_lines[old_line..old_end] {
+            lines.push((format!(" {line}"), None));
+        }
+    }
+    lines
+}
+
+impl Editor {
+    /// The file on disk, or `snapshot`, and the content of `buffer_id`, or
+    /// why they can't be compared
+    fn unsaved_changes_texts(
+        &self,
+        buffer_id: BufferId,
+        snapshot: Option<&Snapshot>,
+    ) -> Result<(String, String), String> {
+        let path = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .ok_or_else(|| t!("unsaved_changes.not_a_file").to_string())?;
+        let text = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.to_string())
+            .ok_or_else(|| t!("unsaved_changes.not_loaded").to_string())?;
+        let disk = match snapshot {
+            Some(snapshot) => self.local_history.read(snapshot),
+            None => self.filesystem.read_file(path),
+        }
+        .map_err(|e| t!("unsaved_changes.read_failed", error = e.to_string()).to_string())?;
+        Ok((String::from_utf8_lossy(&disk).into_owned(), text))
+    }
+
+    /// Show the unsaved changes of the active buffer, side by side or as a
+    /// unified diff
+    pub fn show_unsaved_changes(&mut self, unified: bool) {
+        let active = self.active_buffer();
+        let source = self
+            .unsaved_changes_views
+            .get(&active)
+            .map_or(active, |view| view.source);
+        let (disk, text) = match self.unsaved_changes_texts(source, None) {
+            Ok(texts) => texts,
+            Err(message) => {
+                self.set_status_message(message);
+                return;
+            }
+        };
+        let count = diff_hunks(disk.as_bytes(), text.as_bytes()).len();
+        if count == 0 {
+            self.set_status_message(t!("unsaved_changes.none").to_string());
+            return;
+        }
+
+        if unified {
+            let name = self
+                .buffer_metadata
+                .get(&source)
+                .map(|m| m.display_name.clone())
+                .unwrap_or_default();
+            let view = self.create_virtual_buffer(
+                format!("*{}: {}*", t!("unsaved_changes.title"), name),
+                UNSAVED_CHANGES_MODE.to_string(),
+                true,
+            );
+            if let Some(state) = self.buffers.get_mut(&view) {
+                state.editing_disabled = true;
+            }
+            self.unsaved_changes_views.insert(
+                view,
+                UnsavedChangesView {
+                    source,
+                    line_hunks: Some(Vec::new()),
+                    snapshot: None,
+                },
+            );
+            self.fill_unified_view(view, &disk, &text);
+            self.set_active_buffer(view);
+        } else {
+            match self.open_disk_diff(source) {
+                Ok(view) => {
+                    self.unsaved_changes_views
+                        .insert(view, UnsavedChangesView::side_by_side(source, None));
+                }
+                Err(e) => {
+                    self.set_status_message(e.to_string());
+                    return;
+                }
+            }
+        }
+        self.set_status_message(t!("unsaved_changes.opened", count = count).to_string());
+    }
+
+    /// Replace the content of a unified view with the diff of `disk` and `text`
+    fn fill_unified_view(&mut self, view: BufferId, disk: &str, text: &str) {
+        let hunks = diff_hunks(disk.as_bytes(), text.as_bytes());
+        let lines = unified_diff_lines(disk, text, &hunks);
+
+        let mut content = String::new();
+        let mut colored = Vec::new();
+        for (line, _) in &lines {
+            let bg = match line.as_bytes().first() {
+                Some(b'+') => Some("editor.diff_add_bg"),
+                Some(b'-') => Some("editor.diff_remove_bg"),
+                _ => None,
+            };
+            if let Some(bg) = bg {
+                colored.push((content.len()..content.len() + line.len(), bg));
+            }
+            content.push_str(line);
+            content.push('\n');
+        }
+        if let Err(e) =
+            self.set_virtual_buffer_content(view, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to fill unsaved changes view: {}", e);
+            return;
+        }
+        if let Some(entry) = self.unsaved_changes_views.get_mut(&view) {
+            entry.line_hunks = Some(lines.into_iter().map(|(_, hunk)| hunk).collect());
+        }
+
+        let Some(state) = self.buffers.get_mut(&view) else {
+            return;
+        };
+        let namespace = OverlayNamespace::from_string(UNIFIED_NAMESPACE.to_string());
+        state.apply(
+            &mut Cursors::default(),
+            &Event::ClearNamespace {
+                namespace: namespace.clone(),
+            },
+        );
+        for (range, bg) in colored {
+            let options = OverlayOptions {
+                bg: Some(OverlayColorSpec::ThemeKey(bg.to_string())),
+                extend_to_line_end: true,
+                ..Default::default()
+            };
+            state.apply(
+                &mut Cursors::default(),
+                &Event::AddOverlay {
+                    namespace: Some(namespace.clone()),
+                    range,
+                    face: OverlayFace::from_options(options),
+                    priority: 10,
+                    message: None,
+                    extend_to_line_end: true,
+                    url: None,
+                },
+            );
+        }
+    }
+
+    /// Revert the change under the cursor of an unsaved changes view to its
+    /// version on disk, or in the snapshot of a local history view
+    pub fn revert_hunk(&mut self) {
+        let view_id = self.active_buffer();
+        let Some(view) = self.unsaved_changes_views.get(&view_id).cloned() else {
+            self.set_status_message(t!("unsaved_changes.not_a_view").to_string());
+            return;
+        };
+        let (disk, text) = match self.unsaved_changes_texts(view.source, view.snapshot.as_ref()) {
+            Ok(texts) => texts,
+            Err(message) => {
+                self.set_status_message(message);
+                return;
+            }
+        };
+        let hunks = diff_hunks(disk.as_bytes(), text.as_bytes());
+
+        let hunk = match &view.line_hunks {
+            Some(line_hunks) => {
+                let state = self.active_state();
+                let line = state
+                    .buffer
+                    .get_line_number(self.active_cursors().primary().position);
+                line_hunks.get(line).copied().flatten()
+            }
+            None => self.hunk_at_diff_cursor(view_id, &hunks),
+        };
+        let Some(hunk) = hunk.and_then(|i| hunks.get(i)) else {
+            self.set_status_message(t!("unsaved_changes.no_change_here").to_string());
+            return;
+        };
+
+        let (range, replacement) = revert_hunk_edit(&disk, &text, hunk);
+        if !range.is_empty() {
+            self.handle_delete_range(view.source, range.clone());
+        }
+        if !replacement.is_empty() {
+            self.handle_insert_text(view.source, range.start, replacement);
+        }
+
+        let text = self
+            .buffers
+            .get(&view.source)
+            .and_then(|s| s.buffer.to_string())
+            .unwrap_or_default();
+        if view.line_hunks.is_some() {
+            self.fill_unified_view(view_id, &disk, &text);
+        } else {
+            self.realign_diff_session(view_id);
+        }
+        let left = diff_hunks(disk.as_bytes(), text.as_bytes()).len();
+        self.set_status_message(t!("unsaved_changes.reverted", count = left).to_string());
+    }
+
+    /// Index in `hunks` of the change at the cursor of a side-by-side view
+    fn hunk_at_diff_cursor(&mut self, composite: BufferId, hunks: &[DiffHunk]) -> Option<usize> {
+        let split_id = self.split_manager.active_split();
+        let row = self
+            .get_composite_view_state(split_id, composite)?
+            .cursor_row;
+        let rows = &self.composite_buffers.get(&composite)?.alignment.rows;
+        // A hunk header belongs to the change below it
+        let row = rows
+            .iter()
+            .skip(row)
+            .find(|r| r.get_pane_line(0).is_some() || r.get_pane_line(1).is_some())?;
+        let disk_line = row.get_pane_line(0).map(|l| l.line);
+        let buffer_line = row.get_pane_line(1).map(|l| l.line);
+        hunks.iter().position(|hunk| {
+            disk_line
+                .is_some_and(|l| (hunk.old_start..hunk.old_start + hunk.old_count).contains(&l))
+                || buffer_line
+                    .is_some_and(|l| (hunk.new_start..hunk.new_start + hunk.new_count).contains(&l))
+        })
+    }
+}
@@ -0,0 +1,229 @@
+//! User-defined commands from the `commands` config section
+//!
+//! A user command runs a sequence of built-in actions, shell commands and
+//! plugin actions. Each one is listed in the command palette and can be bound
+//! to a key by its name, like a built-in action.
+
+use std::collections::HashMap;
+
+use super::Editor;
+use crate::config::{CommandStep, ShellOutput, UserCommand};
+use crate::input::commands::{Command, CommandSource};
+use crate::input::keybindings::Action;
+use rust_i18n::t;
+
+/// How deeply user commands may run other user commands, which stops
+/// commands that run themselves
+const MAX_NESTING: usize = 8;
+
+/// A step of a user command, ready to run
+enum ResolvedStep {
+    Action(Action),
+    Shell {
+        command: String,
+        output: ShellOutput,
+    },
+}
+
+/// Command palette entries for the user commands, sorted by name
+pub(super) fn user_palette_commands(commands: &HashMap<String, UserCommand>) -> Vec<Command> {
+    let mut palette: Vec<Command> = commands
+        .iter()
+        .map(|(name, command)| Command {
+            name: name.clone(),
+            description: command.description.clone(),
+            action: Action::UserCommand(name.clone()),
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Config,
+        })
+        .collect();
+    palette.sort_by(|a, b| a.name.cmp(&b.name));
+    palette
+}
+
+impl Editor {
+    /// List the user commands of the current config in the command palette
+    pub(super) fn refresh_user_commands(&mut self) {
+        let palette = user_palette_commands(&self.config.commands);
+        if let Ok(mut registry) = self.command_registry.write() {
+            registry.set_user_commands(palette);
+        }
+    }
+
+    /// Run the user command `name`
+    pub(super) fn run_user_command(&mut self, name: &str) {
+        self.run_nested_user_command(name, 0);
+    }
+
+    /// Run the user command `name`, run by `depth` other user commands.
+    /// Returns whether all of its steps succeeded.
+    fn run_nested_user_command(&mut self, name: &str, depth: usize) -> bool {
+        if depth > MAX_NESTING {
+            self.set_status_message(t!("user_command.too_deep", name = name).to_string());
+            return false;
+        }
+        let Some(command) = self.config.commands.get(name) else {
+            self.set_status_message(t!("user_command.unknown", name = name).to_string());
+            return false;
+        };
+        // Nothing runs unless every step is valid
+        let steps: Result<Vec<_>, _> = command
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| self.resolve_step(name, index, step))
+            .collect();
+        let steps = match steps {
+            Ok(steps) => steps,
+            Err(message) => {
+                self.set_status_message(message);
+                return false;
+            }
+        };
+
+        for step in steps {
+            let succeeded = match step {
+                ResolvedStep::Action(Action::UserCommand(nested)) => {
+                    self.run_nested_user_command(&nested, depth + 1)
+                }
+                ResolvedStep::Action(action) => match self.handle_action(action) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        self.set_status_message(
+                            t!("user_command.failed", name = name, error = e.to_string())
+                                .to_string(),
+                        );
+                        false
+                    }
+                },
+                ResolvedStep::Shell { command, output } => self.run_shell_step(&command, output),
+            };
+            if !succeeded {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Check step `index` of the user command `name`
+    fn resolve_step(
+        &self,
+        name: &str,
+        index: usize,
+        step: &CommandStep,
+    ) -> Result<ResolvedStep, String> {
+        let step_number = index + 1;
+        match (&step.action, &step.shell, &step.plugin) {
+            (Some(action), None, None) => Action::from_str(action, &step.args)
+                .or_else(|| {
+                    self.config
+                        .commands
+                        .contains_key(action)
+                        .then(|| Action::UserCommand(action.clone()))
+                })
+                .map(ResolvedStep::Action)
+                .ok_or_else(|| {
+                    t!(
+                        "user_command.unknown_action",
+                        name = name,
+                        step = step_number,
+                        action = action
+                    )
+                    .to_string()
+                }),
+            (None, Some(command), None) => Ok(ResolvedStep::Shell {
+                command: command.clone(),
+                output: step.output,
+            }),
+            (None, None, Some(plugin_action)) => Ok(ResolvedStep::Action(Action::PluginAction(
+                plugin_action.clone(),
+            ))),
+            _ => Err(t!("user_command.invalid_step", name = name, step = step_number).to_string()),
+        }
+    }
+
+    /// Run a shell step with its variables substituted. Returns whether the
+    /// command succeeded.
+    fn run_shell_step(&mut self, command: &str, output: ShellOutput) -> bool {
+        let command = self.substitute_shell_variables(command);
+        match output {
+            ShellOutput::None => match self.execute_shell_command(&command) {
+                Ok(_) => true,
+                Err(error) => {
+                    self.set_status_message(error);
+                    false
+                }
+            },
+            ShellOutput::Buffer => self.handle_shell_command(&command, false),
+            ShellOutput::Replace => self.handle_shell_command(&command, true),
+        }
+    }
+
+    /// `command` with `${file}`, `${dir}`, `${filename}` and `${line}`
+    /// replaced by the active buffer's values, quoted for the shell. The
+    /// command is scanned once, so a value is never substituted into again.
+    fn substitute_shell_variables(&self, command: &str) -> String {
+        let path = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|m| m.file_path())
+            .cloned()
+            .unwrap_or_default();
+        let dir = path
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_default();
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let position = self.active_cursors().primary().position;
+        let line = self.active_state().buffer.get_line_number(position) + 1;
+
+        let variables = [
+            ("file", shell_quote(&path.to_string_lossy())),
+            ("dir", shell_quote(&dir.to_string_lossy())),
+            ("filename", shell_quote(&filename)),
+            ("line", line.to_string()),
+        ];
+
+        let mut substituted = String::with_capacity(command.len());
+        let mut rest = command;
+        while let Some(start) = rest.find("${") {
+            substituted.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let variable = rest.find('}').and_then(|end| {
+                variables
+                    .iter()
+                    .find(|(name, _)| rest[2..end] == **name)
+                    .map(|(_, value)| (end, value))
+            });
+            match variable {
+                Some((end, value)) => {
+                    substituted.push_str(value);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    substituted.push_str("${");
+                    rest = &rest[2..];
+                }
+            }
+        }
+        substituted.push_str(rest);
+        substituted
+    }
+}
+
+/// `value` as a single word for the shell that runs user command steps
+#[cfg(not(windows))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// `value` as a single word for the shell that runs user command steps
+/// (Windows paths can't contain `"`)
+#[cfg(windows)]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value)
+}
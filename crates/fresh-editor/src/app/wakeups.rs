@@ -0,0 +1,85 @@
+//! Waking the main loop
+//!
+//! The main loop sleeps on the event bus until terminal input, an async
+//! message or a plugin command arrives, or a timer is due. Timers with a
+//! deadline wake it exactly; the remaining periodic work (auto-save, recovery
+//! journal, file and git polling, the warning log) runs at least every
+//! `HOUSEKEEPING_INTERVAL`.
+
+use std::time::{Duration, Instant};
+
+use super::Editor;
+use crate::services::event_bus::Waker;
+
+/// Longest the main loop sleeps with nothing due
+const HOUSEKEEPING_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often growth of a file streamed from stdin is checked
+const STDIN_STREAMING_POLL: Duration = Duration::from_millis(50);
+
+impl Editor {
+    /// Wake the main loop through `waker` when async messages or plugin
+    /// commands arrive
+    pub fn set_loop_waker(&mut self, waker: Waker) {
+        if let Some(bridge) = &self.async_bridge {
+            let waker = waker.clone();
+            bridge.wake_on_message(move || waker.wake());
+        }
+        self.plugin_manager.wake_on_command(move || waker.wake());
+    }
+
+    /// Block until an async message arrives, or `timeout` passes
+    ///
+    /// Returns true if a message is waiting for `process_async_messages`.
+    pub fn wait_for_async_message(&self, timeout: Duration) -> bool {
+        self.async_bridge
+            .as_ref()
+            .is_some_and(|bridge| bridge.wait(timeout))
+    }
+
+    /// How long the main loop may sleep if no input or message arrives
+    pub fn next_wakeup(&self) -> Duration {
+        if self.check_selection_stats() {
+            return Duration::ZERO;
+        }
+
+        let hover = self
+            .mouse_state
+            .lsp_hover_state
+            .filter(|_| {
+                self.config.editor.mouse_hover_enabled && !self.mouse_state.lsp_hover_request_sent
+            })
+            .map(|(_, started, _, _)| {
+                Duration::from_millis(self.config.editor.mouse_hover_delay_ms)
+                    .saturating_sub(started.elapsed())
+            });
+        let semantic_highlight = self
+            .buffers
+            .values()
+            .filter_map(|state| state.reference_highlight_overlay.needs_redraw())
+            .min();
+        let completion = self
+            .scheduled_completion_trigger
+            .map(|due| due.saturating_duration_since(Instant::now()));
+        let stdin_streaming = self
+            .stdin_streaming
+            .as_ref()
+            .filter(|stream| !stream.complete)
+            .map(|_| STDIN_STREAMING_POLL);
+
+        [
+            hover,
+            semantic_highlight,
+            completion,
+            stdin_streaming,
+            self.plugin_timer_wait(),
+            self.replay_wait(),
+            self.before_save_wait(),
+            self.lint_wait(),
+            self.lsp_progress_spinner_wait(),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(HOUSEKEEPING_INTERVAL, Duration::min)
+    }
+}
@@ -293,21 +293,7 @@ impl WarningDomain for LspWarningDomain {
 impl LspWarningDomain {
     /// Get install hint for common LSP servers
     fn get_install_hint(&self) -> String {
-        let cmd = self.server_command.as_deref().unwrap_or("");
-
-        match cmd {
-            "pylsp" => t!("lsp.install_hint.pylsp").to_string(),
-            "rust-analyzer" => t!("lsp.install_hint.rust_analyzer").to_string(),
-            "typescript-language-server" => t!("lsp.install_hint.typescript").to_string(),
-            "gopls" => t!("lsp.install_hint.gopls").to_string(),
-            "clangd" => t!("lsp.install_hint.clangd").to_string(),
-            "bash-language-server" => t!("lsp.install_hint.bash").to_string(),
-            "vscode-html-language-server"
-            | "vscode-css-language-server"
-            | "vscode-json-language-server" => t!("lsp.install_hint.vscode").to_string(),
-            "csharp-ls" => t!("lsp.install_hint.csharp").to_string(),
-            _ => t!("lsp.install_hint.generic", command = cmd).to_string(),
-        }
+        lsp_install_hint(self.server_command.as_deref().unwrap_or(""))
     }
 
     /// Get install command for clipboard
@@ -334,6 +320,23 @@ impl LspWarningDomain {
     }
 }
 
+/// How to install the LSP server run as `command`
+pub(crate) fn lsp_install_hint(command: &str) -> String {
+    match command {
+        "pylsp" => t!("lsp.install_hint.pylsp").to_string(),
+        "rust-analyzer" => t!("lsp.install_hint.rust_analyzer").to_string(),
+        "typescript-language-server" => t!("lsp.install_hint.typescript").to_string(),
+        "gopls" => t!("lsp.install_hint.gopls").to_string(),
+        "clangd" => t!("lsp.install_hint.clangd").to_string(),
+        "bash-language-server" => t!("lsp.install_hint.bash").to_string(),
+        "vscode-html-language-server"
+        | "vscode-css-language-server"
+        | "vscode-json-language-server" => t!("lsp.install_hint.vscode").to_string(),
+        "csharp-ls" => t!("lsp.install_hint.csharp").to_string(),
+        _ => t!("lsp.install_hint.generic", command = command).to_string(),
+    }
+}
+
 /// Registry of all warning domains
 #[derive(Default)]
 pub struct WarningDomainRegistry {
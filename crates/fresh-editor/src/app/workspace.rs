@@ -34,9 +34,10 @@ use crate::state::ViewMode;
 use crate::view::split::{SplitNode, SplitViewState};
 use crate::workspace::{
     FileExplorerState, PersistedFileWorkspace, SearchOptions, SerializedBookmark, SerializedCursor,
-    SerializedFileState, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
-    SerializedSplitViewState, SerializedTabRef, SerializedTerminalWorkspace, SerializedViewMode,
-    Workspace, WorkspaceConfigOverrides, WorkspaceError, WorkspaceHistories, WORKSPACE_VERSION,
+    SerializedFileState, SerializedLayout, SerializedScroll, SerializedSplitDirection,
+    SerializedSplitNode, SerializedSplitViewState, SerializedTabRef, SerializedTerminalWorkspace,
+    SerializedViewMode, Workspace, WorkspaceConfigOverrides, WorkspaceError, WorkspaceHistories,
+    WORKSPACE_VERSION,
 };
 
 use super::types::Bookmark;
@@ -289,6 +290,7 @@ impl Editor {
             bookmarks,
             terminals,
             external_files,
+            layouts: self.named_layouts.clone(),
             saved_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -600,6 +602,9 @@ impl Editor {
             self.split_manager.set_active_split(new_active_split);
         }
 
+        // Named layouts are only restored on demand
+        self.named_layouts = workspace.layouts.clone();
+
         // 7. Restore bookmarks
         for (key, bookmark) in &workspace.bookmarks {
             if let Some(&buffer_id) = path_to_buffer.get(&bookmark.file_path) {
@@ -644,6 +649,142 @@ impl Editor {
         Ok(())
     }
 
+    /// Capture the current split tree and per-split view states as a layout
+    pub fn capture_layout(&self) -> SerializedLayout {
+        let terminal_indices = self.layout_terminal_indices();
+
+        let split_layout = serialize_split_node(
+            self.split_manager.root(),
+            &self.buffer_metadata,
+            &self.working_dir,
+            &self.terminal_buffers,
+            &terminal_indices,
+            self.split_manager.labels(),
+        );
+
+        let mut split_states = HashMap::new();
+        for split_id in self.split_manager.root().leaf_split_ids() {
+            let Some(view_state) = self.split_view_states.get(&split_id) else {
+                continue;
+            };
+            let serialized = serialize_split_view_state(
+                view_state,
+                &self.buffer_metadata,
+                &self.working_dir,
+                self.split_manager.get_buffer_id(split_id),
+                &self.terminal_buffers,
+                &terminal_indices,
+            );
+            split_states.insert(split_id.0, serialized);
+        }
+
+        SerializedLayout {
+            split_layout,
+            active_split_id: self.split_manager.active_split().0,
+            split_states,
+        }
+    }
+
+    /// Replace the current split tree with a previously captured layout
+    ///
+    /// Files referenced by the layout are opened if needed. Tabs that were
+    /// open before but are not part of the layout move to the active split so
+    /// no buffer becomes unreachable.
+    pub fn apply_layout(&mut self, layout: &SerializedLayout) {
+        let mut previous_tabs: Vec<BufferId> = Vec::new();
+        for split_id in self.split_manager.root().leaf_split_ids() {
+            if let Some(view_state) = self.split_view_states.get(&split_id) {
+                for buffer_id in &view_state.open_buffers {
+                    if !previous_tabs.contains(buffer_id) {
+                        previous_tabs.push(*buffer_id);
+                    }
+                }
+            }
+        }
+
+        // Collapse to a single split so the saved tree can be rebuilt from scratch
+        if let Ok(removed) = self.split_manager.close_other_splits() {
+            for split_id in removed {
+                self.split_view_states.remove(&split_id);
+            }
+        }
+        let base_split = self.split_manager.active_split();
+        self.split_manager.clear_label(base_split);
+        let mut view_state = SplitViewState::with_buffer(
+            self.terminal_width,
+            self.terminal_height,
+            self.active_buffer(),
+        );
+        view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+        view_state.rulers = self.config.editor.rulers.clone();
+        view_state.show_line_numbers = self.config.editor.line_numbers;
+        self.split_view_states.insert(base_split, view_state);
+
+        let mut path_to_buffer: HashMap<PathBuf, BufferId> = HashMap::new();
+        for rel_path in collect_file_paths_from_states(&layout.split_states) {
+            let abs_path = self.working_dir.join(&rel_path);
+            if !abs_path.exists() {
+                continue;
+            }
+            match self.open_file_internal(&abs_path) {
+                Ok(buffer_id) => {
+                    path_to_buffer.insert(rel_path, buffer_id);
+                }
+                Err(e) => tracing::warn!("Failed to open {:?} for layout: {}", abs_path, e),
+            }
+        }
+
+        let terminal_buffer_map: HashMap<usize, BufferId> = {
+            let indices = self.layout_terminal_indices();
+            self.terminal_buffers
+                .iter()
+                .filter_map(|(buffer_id, terminal_id)| {
+                    indices.get(terminal_id).map(|idx| (*idx, *buffer_id))
+                })
+                .collect()
+        };
+
+        let mut split_id_map: HashMap<usize, SplitId> = HashMap::new();
+        self.restore_split_node(
+            &layout.split_layout,
+            &path_to_buffer,
+            &terminal_buffer_map,
+            &layout.split_states,
+            &mut split_id_map,
+            true,
+        );
+        if let Some(&active) = split_id_map.get(&layout.active_split_id) {
+            self.split_manager.set_active_split(active);
+        }
+
+        let shown: HashSet<BufferId> = self
+            .split_view_states
+            .values()
+            .flat_map(|vs| vs.open_buffers.iter().copied())
+            .collect();
+        let active_split = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            for buffer_id in previous_tabs {
+                if !shown.contains(&buffer_id) && self.buffers.contains_key(&buffer_id) {
+                    view_state.open_buffers.push(buffer_id);
+                }
+            }
+        }
+
+        self.resize_visible_terminals();
+    }
+
+    /// Stable terminal numbering used by named layouts (ordered by terminal ID)
+    fn layout_terminal_indices(&self) -> HashMap<TerminalId, usize> {
+        let mut ids: Vec<TerminalId> = self.terminal_buffers.values().copied().collect();
+        ids.sort_by_key(|id| id.0);
+        ids.dedup();
+        ids.into_iter()
+            .enumerate()
+            .map(|(idx, id)| (id, idx))
+            .collect()
+    }
+
     /// Restore a terminal from serialized workspace metadata.
     ///
     /// Uses the incremental streaming architecture for fast restore:
@@ -0,0 +1,383 @@
+//! Workspace edits requested by language servers (`workspace/applyEdit`)
+//!
+//! Servers send these to run code actions and, for some servers, renames.
+//! Files with an open buffer are edited in the buffer (which sends didChange
+//! like any other edit) and left unsaved. Files that aren't loaded are edited
+//! on disk, and the servers for their language are told about it with
+//! didOpen/didChange/didSave so they don't work from stale contents.
+
+use super::{uri_to_path, Editor};
+use crate::model::event::BufferId;
+use lsp_types::{
+    ApplyWorkspaceEditResponse, DocumentChangeOperation, DocumentChanges, OneOf, Position,
+    TextDocumentContentChangeEvent, TextEdit, Uri, WorkspaceEdit,
+};
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+
+/// Most file names listed in the status message after an edit
+const MAX_LISTED_FILES: usize = 3;
+
+/// The text edits of a workspace edit, grouped by document in order of appearance
+///
+/// File operations (create, rename, delete) are left out.
+pub(crate) fn text_document_edits(edit: WorkspaceEdit) -> Vec<(Uri, Vec<TextEdit>)> {
+    let mut files: Vec<(Uri, Vec<TextEdit>)> = Vec::new();
+    let mut add = |uri: Uri, edits: Vec<TextEdit>| match files
+        .iter_mut()
+        .find(|(existing, _)| *existing == uri)
+    {
+        Some((_, existing)) => existing.extend(edits),
+        None => files.push((uri, edits)),
+    };
+
+    if let Some(changes) = edit.changes {
+        for (uri, edits) in changes {
+            add(uri, edits);
+        }
+    }
+
+    let document_edits = match edit.document_changes {
+        Some(DocumentChanges::Edits(edits)) => edits,
+        Some(DocumentChanges::Operations(ops)) => ops
+            .into_iter()
+            .filter_map(|op| match op {
+                DocumentChangeOperation::Edit(edit) => Some(edit),
+                DocumentChangeOperation::Op(_) => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    for document_edit in document_edits {
+        let edits = document_edit
+            .edits
+            .into_iter()
+            .map(|edit| match edit {
+                OneOf::Left(edit) => edit,
+                OneOf::Right(annotated) => annotated.text_edit,
+            })
+            .collect();
+        add(document_edit.text_document.uri, edits);
+    }
+
+    files
+}
+
+/// Whether a workspace edit creates, renames or deletes files
+fn has_file_operations(edit: &WorkspaceEdit) -> bool {
+    matches!(
+        &edit.document_changes,
+        Some(DocumentChanges::Operations(ops))
+            if ops.iter().any(|op| matches!(op, DocumentChangeOperation::Op(_)))
+    )
+}
+
+/// Byte offset of an LSP position (UTF-16 columns) in `text`
+///
+/// Positions past the end of a line fall back to the end of that line, and
+/// lines past the end of the text to the end of the text.
+fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return text.len(),
+        }
+    }
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let line = line.strip_suffix('\r').unwrap_or(line);
+
+    let mut utf16 = 0;
+    for (offset, ch) in line.char_indices() {
+        if utf16 >= position.character as usize {
+            return line_start + offset;
+        }
+        utf16 += ch.len_utf16();
+    }
+    line_start + line.len()
+}
+
+/// Apply LSP text edits to `text`
+///
+/// All positions refer to the original text, as the protocol requires.
+pub(crate) fn apply_text_edits(text: &str, edits: &[TextEdit]) -> String {
+    let mut ranges: Vec<(usize, usize, usize, &str)> = edits
+        .iter()
+        .enumerate()
+        .map(|(index, edit)| {
+            let start = position_to_offset(text, edit.range.start);
+            let end = position_to_offset(text, edit.range.end).max(start);
+            (start, end, index, edit.new_text.as_str())
+        })
+        .collect();
+    // Later edits first, so earlier offsets stay valid; of two inserts at the
+    // same position the one listed first ends up first
+    ranges.sort_by_key(|(start, end, index, _)| std::cmp::Reverse((*start, *end, *index)));
+
+    let mut result = text.to_string();
+    for (start, end, _, new_text) in ranges {
+        result.replace_range(start..end, new_text);
+    }
+    result
+}
+
+/// Where the edits of one file go
+enum EditTarget {
+    Buffer(BufferId, Vec<TextEdit>),
+    Disk {
+        path: PathBuf,
+        uri: Uri,
+        old_text: String,
+        new_text: String,
+    },
+}
+
+impl Editor {
+    /// Apply a workspace edit sent by a server and report the outcome to it
+    pub(crate) fn handle_lsp_apply_edit(
+        &mut self,
+        language: &str,
+        label: Option<String>,
+        edit: WorkspaceEdit,
+    ) -> ApplyWorkspaceEditResponse {
+        tracing::debug!("Applying workspace edit from {} ({:?})", language, label);
+        match self.apply_workspace_edit(edit) {
+            Ok(files) => {
+                if !files.is_empty() {
+                    let mut names: Vec<String> = files
+                        .iter()
+                        .take(MAX_LISTED_FILES)
+                        .map(|path| {
+                            path.file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_else(|| path.display().to_string())
+                        })
+                        .collect();
+                    if files.len() > MAX_LISTED_FILES {
+                        names.push("…".to_string());
+                    }
+                    let label = label.unwrap_or_else(|| t!("lsp.edit_label").to_string());
+                    self.set_status_message(
+                        t!(
+                            "lsp.edit_applied",
+                            label = label,
+                            count = files.len(),
+                            files = names.join(", ")
+                        )
+                        .to_string(),
+                    );
+                }
+                ApplyWorkspaceEditResponse {
+                    applied: true,
+                    failure_reason: None,
+                    failed_change: None,
+                }
+            }
+            Err(error) => {
+                self.set_status_message(t!("lsp.edit_failed", error = &error).to_string());
+                ApplyWorkspaceEditResponse {
+                    applied: false,
+                    failure_reason: Some(error),
+                    failed_change: None,
+                }
+            }
+        }
+    }
+
+    /// Apply a workspace edit to open buffers and to files on disk
+    ///
+    /// Every file is read and edited before anything is written, so a file
+    /// that can't be read leaves all of them untouched. Returns the changed
+    /// files.
+    fn apply_workspace_edit(&mut self, edit: WorkspaceEdit) -> Result<Vec<PathBuf>, String> {
+        if has_file_operations(&edit) {
+            return Err("creating, renaming and deleting files is not supported".to_string());
+        }
+
+        let mut targets = Vec::new();
+        for (uri, edits) in text_document_edits(edit) {
+            if edits.is_empty() {
+                continue;
+            }
+            let path = uri_to_path(&uri)?;
+            let path = self.filesystem.canonicalize(&path).unwrap_or(path);
+            let open_buffer = self
+                .buffers
+                .iter()
+                .find(|(_, state)| state.buffer.file_path() == Some(path.as_path()))
+                .map(|(id, _)| *id);
+            let target = match open_buffer {
+                Some(buffer_id) => EditTarget::Buffer(buffer_id, edits),
+                None => {
+                    let bytes = self
+                        .filesystem
+                        .read_file(&path)
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                    let old_text = String::from_utf8(bytes)
+                        .map_err(|_| format!("{}: not a UTF-8 file", path.display()))?;
+                    let new_text = apply_text_edits(&old_text, &edits);
+                    EditTarget::Disk {
+                        path,
+                        uri,
+                        old_text,
+                        new_text,
+                    }
+                }
+            };
+            targets.push(target);
+        }
+
+        let mut changed = Vec::new();
+        for target in targets {
+            match target {
+                EditTarget::Buffer(buffer_id, edits) => {
+                    self.apply_lsp_text_edits(buffer_id, edits)
+                        .map_err(|e| e.to_string())?;
+                    if let Some(path) = self
+                        .buffers
+                        .get(&buffer_id)
+                        .and_then(|state| state.buffer.file_path())
+                    {
+                        changed.push(path.to_path_buf());
+                    }
+                }
+                EditTarget::Disk {
+                    path,
+                    uri,
+                    old_text,
+                    new_text,
+                } => {
+                    if new_text == old_text {
+                        continue;
+                    }
+                    self.filesystem
+                        .write_file(&path, new_text.as_bytes())
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                    self.notify_lsp_disk_edit(&path, uri, old_text, new_text);
+                    changed.push(path);
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Tell the running servers for a file's language that it changed on disk
+    ///
+    /// didOpen is skipped by servers that already have the document open;
+    /// the full-text didChange then brings them up to date either way.
+    fn notify_lsp_disk_edit(&mut self, path: &Path, uri: Uri, old_text: String, new_text: String) {
+        let Some(language) =
+            crate::services::lsp::manager::detect_language(path, &self.config.languages)
+        else {
+            return;
+        };
+        let Some(lsp) = &self.lsp else {
+            return;
+        };
+
+        let mut handles: Vec<_> = lsp.get_handle(&language).into_iter().collect();
+        handles.extend(
+            lsp.additional_handles(&language)
+                .into_iter()
+                .map(|(_, h)| h),
+        );
+        for handle in handles {
+            let change = TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: new_text.clone(),
+            };
+            let result = handle
+                .did_open(uri.clone(), old_text.clone(), language.clone())
+                .and_then(|_| handle.did_change(uri.clone(), vec![change]))
+                .and_then(|_| handle.did_save(uri.clone(), Some(new_text.clone())));
+            if let Err(e) = result {
+                tracing::warn!("Failed to notify LSP about edited {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{OptionalVersionedTextDocumentIdentifier, Range, ResourceOp, TextDocumentEdit};
+    use std::collections::HashMap;
+
+    fn edit(start: (u32, u32), end: (u32, u32), text: &str) -> TextEdit {
+        TextEdit {
+            range: Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1)),
+            new_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_text_edits() {
+        let text = "fn old() {}\nlet x = old();\n";
+        let edits = [
+            edit((0, 3), (0, 6), "new"),
+            edit((1, 8), (1, 11), "new"),
+            edit((2, 0), (2, 0), "// end"),
+            edit((2, 0), (2, 0), "\n"),
+        ];
+        assert_eq!(
+            apply_text_edits(text, &edits),
+            "fn new() {}\nlet x = new();\n// end\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_text_edits_utf16_columns_and_crlf() {
+        // "é" is one UTF-16 unit but two bytes; "😀" is two units and four bytes
+        let text = "é😀ab\r\nsecond\r\n";
+        assert_eq!(
+            apply_text_edits(text, &[edit((0, 3), (0, 4), "X")]),
+            "é😀Xb\r\nsecond\r\n"
+        );
+        // Columns past the end of a line stop before the line ending
+        assert_eq!(
+            apply_text_edits(text, &[edit((1, 6), (1, 99), "!")]),
+            "é😀ab\r\nsecond!\r\n"
+        );
+    }
+
+    #[test]
+    fn test_text_document_edits_groups_by_document() {
+        let a: Uri = "file:///tmp/a.rs".parse().unwrap();
+        let b: Uri = "file:///tmp/b.rs".parse().unwrap();
+        let document_edit = |uri: &Uri, text: &str| TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier {
+                uri: uri.clone(),
+                version: None,
+            },
+            edits: vec![OneOf::Left(edit((0, 0), (0, 0), text))],
+        };
+        let workspace_edit = WorkspaceEdit {
+            changes: None,
+            document_changes: Some(DocumentChanges::Edits(vec![
+                document_edit(&a, "1"),
+                document_edit(&b, "2"),
+                document_edit(&a, "3"),
+            ])),
+            change_annotations: None,
+        };
+        let files = text_document_edits(workspace_edit);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, a);
+        assert_eq!(files[0].1.len(), 2);
+        assert_eq!(files[1].0, b);
+
+        let with_ops = WorkspaceEdit {
+            changes: Some(HashMap::new()),
+            document_changes: Some(DocumentChanges::Operations(vec![
+                DocumentChangeOperation::Op(ResourceOp::Delete(lsp_types::DeleteFile {
+                    uri: b,
+                    options: None,
+                })),
+            ])),
+            change_annotations: None,
+        };
+        assert!(has_file_operations(&with_ops));
+    }
+}
@@ -1,16 +1,20 @@
-//! Generate JSON Schemas for Fresh configuration and themes
+//! Generate JSON Schemas for Fresh configuration, themes and package manifests
 //!
 //! This binary generates JSON Schemas from Rust structs using schemars.
 //!
 //! Usage:
 //!   cargo run --features dev-bins --bin generate_schema config > plugins/config-schema.json
 //!   cargo run --features dev-bins --bin generate_schema theme > plugins/schemas/theme.schema.json
+//!   cargo run --features dev-bins --bin generate_schema package > plugins/schemas/package.schema.json
 
 use fresh::config::Config;
 use fresh::view::theme::ThemeFile;
+use fresh_core::package::PackageManifest;
 use schemars::schema_for;
 use std::env;
 
+const PACKAGE_SCHEMA_URL: &str = "https://raw.githubusercontent.com/sinelaw/fresh/main/crates/fresh-editor/plugins/schemas/package.schema.json";
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     let schema_type = args.get(1).map(|s| s.as_str()).unwrap_or("config");
@@ -35,8 +39,30 @@ fn main() {
             let schema = schema_for!(ThemeFile);
             serde_json::to_value(&schema).expect("Failed to serialize schema")
         }
+        "package" => {
+            let schema = schema_for!(PackageManifest);
+            let json = serde_json::to_value(&schema).expect("Failed to serialize schema");
+
+            // Manifests point at the published schema through "$schema"; give it
+            // its URL as "$id", right after "$schema"
+            let mut with_id = serde_json::Map::new();
+            for (key, value) in json.as_object().cloned().unwrap_or_default() {
+                let is_schema = key == "$schema";
+                with_id.insert(key, value);
+                if is_schema {
+                    with_id.insert(
+                        "$id".to_string(),
+                        serde_json::Value::String(PACKAGE_SCHEMA_URL.to_string()),
+                    );
+                }
+            }
+            serde_json::Value::Object(with_id)
+        }
         other => {
-            eprintln!("Unknown schema type: {}. Use 'config' or 'theme'.", other);
+            eprintln!(
+                "Unknown schema type: {}. Use 'config', 'theme' or 'package'.",
+                other
+            );
             std::process::exit(1);
         }
     };
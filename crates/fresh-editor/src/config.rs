@@ -225,6 +225,95 @@ impl JsonSchema for CursorStyle {
     }
 }
 
+/// Terminal color mode override
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ColorMode {
+    /// Detect from COLORTERM, TERM and terminfo
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 24-bit RGB colors
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// xterm 256-color palette
+    #[serde(rename = "256")]
+    Color256,
+    /// Basic 16 ANSI colors
+    #[serde(rename = "16")]
+    Color16,
+}
+
+impl JsonSchema for ColorMode {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ColorMode")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Terminal color mode",
+            "type": "string",
+            "enum": ["auto", "truecolor", "256", "16"],
+            "default": "auto"
+        })
+    }
+}
+
+/// How image files are previewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImagePreviewMode {
+    /// Detect the terminal's graphics protocol, falling back to ASCII art
+    #[default]
+    Auto,
+    /// Kitty graphics protocol
+    Kitty,
+    /// Sixel graphics
+    Sixel,
+    /// ASCII art only
+    Ascii,
+}
+
+impl JsonSchema for ImagePreviewMode {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ImagePreviewMode")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How image files are previewed",
+            "type": "string",
+            "enum": ["auto", "kitty", "sixel", "ascii"],
+            "default": "auto"
+        })
+    }
+}
+
+/// How wide East Asian Ambiguous characters are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AmbiguousWidth {
+    /// One column, as in most terminals
+    #[default]
+    Narrow,
+    /// Two columns, as in terminals set up for CJK locales
+    Wide,
+}
+
+impl JsonSchema for AmbiguousWidth {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("AmbiguousWidth")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How wide East Asian Ambiguous characters are drawn",
+            "type": "string",
+            "enum": ["narrow", "wide"],
+            "default": "narrow"
+        })
+    }
+}
+
 /// Newtype for keybinding map name that generates proper JSON Schema with enum options
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -373,6 +462,11 @@ pub struct Config {
     #[serde(default)]
     pub editor: EditorConfig,
 
+    /// Files left out of the file explorer, Quick Open, project search and
+    /// file watching
+    #[serde(default)]
+    pub files: FilesConfig,
+
     /// File explorer panel settings
     #[serde(default)]
     pub file_explorer: FileExplorerConfig,
@@ -402,6 +496,12 @@ pub struct Config {
     #[serde(default = "default_keybinding_map_name")]
     pub active_keybinding_map: KeybindingMapName,
 
+    /// User-defined commands by name. Each runs a sequence of actions, shell
+    /// commands and plugin actions, appears in the command palette and can be
+    /// bound to keys by its name.
+    #[serde(default)]
+    pub commands: HashMap<String, UserCommand>,
+
     /// Per-language configuration overrides (tab size, formatters, etc.)
     #[serde(default)]
     pub languages: HashMap<String, LanguageConfig>,
@@ -414,6 +514,18 @@ pub struct Config {
     #[serde(default)]
     pub warnings: WarningsConfig,
 
+    /// Inline diagnostic display settings
+    #[serde(default)]
+    pub diagnostics: DiagnosticsConfig,
+
+    /// TODO comment aggregation settings
+    #[serde(default)]
+    pub todo: TodoConfig,
+
+    /// CSV/TSV table view settings
+    #[serde(default)]
+    pub csv: CsvConfig,
+
     /// Plugin configurations by plugin name
     /// Plugins are auto-discovered from the plugins directory.
     /// Use this to enable/disable specific plugins.
@@ -464,6 +576,21 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub syntax_highlighting: bool,
 
+    /// Highlight spaces and tabs at the end of lines, except on the line
+    /// with the cursor. The color is the theme's `trailing_whitespace_bg`.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub highlight_trailing_whitespace: bool,
+
+    /// Underline URLs in the text. In terminals that support OSC 8
+    /// hyperlinks, URLs and markdown links become clickable; Ctrl+Click or
+    /// "Open Link" opens them in any terminal.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub hyperlinks: bool,
+
     /// Whether the menu bar is visible by default.
     /// The menu bar provides access to menus (File, Edit, View, etc.) at the top of the screen.
     /// Can be toggled at runtime via command palette or keybinding.
@@ -495,6 +622,20 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Whether the status bar shows the number of selected characters,
+    /// words and lines while there is a selection.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_selection_stats: bool,
+
+    /// Whether the selection statistics in the status bar include the sum
+    /// of the numbers in the selection.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub selection_stats_sum: bool,
+
     /// Use the terminal's default background color instead of the theme's editor background.
     /// When enabled, the editor background inherits from the terminal emulator,
     /// allowing transparency or custom terminal backgrounds to show through.
@@ -510,6 +651,31 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub cursor_style: CursorStyle,
 
+    /// Colors the terminal can display. Theme colors are downgraded to the
+    /// nearest match when the terminal supports fewer colors.
+    /// Options: auto, truecolor, 256, 16
+    /// Default: auto (detected from COLORTERM, TERM and terminfo)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub color_mode: ColorMode,
+
+    /// How image files are shown when opened or previewed.
+    /// Options: auto, kitty, sixel, ascii
+    /// Default: auto (Kitty graphics or sixel when the terminal supports
+    /// them, ASCII art otherwise)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub image_preview: ImagePreviewMode,
+
+    /// Columns taken by East Asian Ambiguous characters such as `±`, `→`,
+    /// `①` and box drawing. Set to match the terminal, or the cursor and
+    /// text drift apart on lines containing them.
+    /// Options: narrow, wide
+    /// Default: narrow (one column)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub ambiguous_width: AmbiguousWidth,
+
     /// Vertical ruler lines at specific column positions.
     /// Draws subtle vertical lines to help with line length conventions.
     /// Example: [80, 120] draws rulers at columns 80 and 120.
@@ -524,11 +690,59 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub tab_size: usize,
 
+    /// Detect whether an opened file is indented with tabs or spaces, and
+    /// its indent width, and use that for the buffer instead of the
+    /// language's settings
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub detect_indentation: bool,
+
+    /// Read `.editorconfig` files in the directories above an opened file.
+    /// Their `indent_style`, `indent_size`, `tab_width`,
+    /// `trim_trailing_whitespace` and `insert_final_newline` properties
+    /// override detected indentation, the language's settings and the
+    /// save options below.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub read_editorconfig: bool,
+
     /// Automatically indent new lines based on the previous line
     #[serde(default = "default_true")]
     #[schemars(extend("x-section" = "Editing"))]
     pub auto_indent: bool,
 
+    /// Words that increment and decrement step through, one cycle per
+    /// entry with its words separated by spaces. Case follows the word
+    /// being replaced.
+    /// Default: ["true false", "yes no", "on off"]
+    #[serde(default = "default_increment_cycles")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub increment_cycles: Vec<String>,
+
+    /// Directories searched for the path under the cursor by
+    /// "Open File Under Cursor", after the buffer's directory and the
+    /// project root. Relative entries are relative to the project root.
+    /// Example: ["include", "/usr/include"]
+    /// Default: []
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub include_paths: Vec<String>,
+
+    /// Shift pasted multi-line text to the indentation of the line it is
+    /// pasted into, keeping its lines' indentation relative to each other.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub reindent_on_paste: bool,
+
+    /// Make word motions (Ctrl+Left/Right and their selecting variants) stop
+    /// at camelCase humps, underscores and digits, like the sub-word motions.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub subword_movement: bool,
+
     /// Minimum lines to keep visible above/below cursor when scrolling
     #[serde(default = "default_scroll_offset")]
     #[schemars(extend("x-section" = "Editing"))]
@@ -542,18 +756,66 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub default_line_ending: LineEndingOption,
 
-    /// Remove trailing whitespace from lines when saving.
+    /// Remove trailing whitespace from lines when saving. Auto-save leaves
+    /// the lines with a cursor alone.
     /// Default: false
     #[serde(default = "default_false")]
     #[schemars(extend("x-section" = "Editing"))]
     pub trim_trailing_whitespace_on_save: bool,
 
-    /// Ensure files end with a newline when saving.
+    /// Ensure files end with a newline when saving. Auto-save skips this
+    /// while a cursor is on the last line.
     /// Default: false
     #[serde(default = "default_false")]
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    /// Save by writing a temporary file and renaming it over the original,
+    /// so a crash mid-save never leaves a truncated file. Permissions and
+    /// ownership are carried over, and on Linux extended attributes too.
+    /// Turn off to write files in place, keeping their inode for tools
+    /// that watch it (some file watchers, hard links).
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub atomic_save: bool,
+
+    /// Number of numbered backups (`file.~1~`, `file.~2~`, ...) to keep next
+    /// to a file when saving over it. `file.~1~` is the most recent.
+    /// Default: 0 (no backups)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub save_backup_count: usize,
+
+    /// How long a save waits for plugin `before_file_save` handlers, in
+    /// milliseconds. Handlers that haven't answered by then are skipped and
+    /// the file is saved without their edits.
+    /// Default: 2000ms
+    #[serde(default = "default_before_save_timeout")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub before_save_timeout_ms: u64,
+
+    /// Fill new files from the templates in the `templates` directory of the
+    /// config directory: opening a file that doesn't exist yet, or saving an
+    /// empty buffer under a name, inserts the template for its file name or
+    /// extension.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub file_templates: bool,
+
+    /// Author substituted for `${author}` in file templates.
+    /// Default: "" (empty)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub template_author: String,
+
+    /// License substituted for `${license}` in file templates, e.g. "MIT".
+    /// Default: "" (empty)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub template_license: String,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -568,6 +830,14 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Bracket Matching"))]
     pub rainbow_brackets: bool,
 
+    /// Color every bracket in view by its nesting depth, using the theme's rainbow palette.
+    /// Brackets are found in the tree-sitter parse tree, so brackets inside strings and
+    /// comments are left alone. Has no effect for languages without a tree-sitter grammar.
+    /// Default: false
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Bracket Matching"))]
+    pub rainbow_delimiters: bool,
+
     // ===== Completion =====
     /// Enable quick suggestions (VS Code-like behavior).
     /// When enabled, completion suggestions appear automatically while typing,
@@ -669,6 +939,34 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_recovery_save_interval_secs: u32,
 
+    /// Whether to keep local history: snapshots of each file, taken on every
+    /// save and periodically while it has unsaved edits, that can be browsed
+    /// and restored with the Local History command.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_enabled: bool,
+
+    /// Interval in seconds between local history snapshots of buffers with
+    /// unsaved edits. Saving always takes a snapshot.
+    /// Default: 300 seconds
+    #[serde(default = "default_local_history_interval")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_interval_secs: u32,
+
+    /// Most local history snapshots kept per file; older ones are deleted.
+    /// Default: 50
+    #[serde(default = "default_local_history_max_snapshots")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_max_snapshots: usize,
+
+    /// Local history snapshots older than this many days are deleted.
+    /// 0 keeps them regardless of age.
+    /// Default: 30 days
+    #[serde(default = "default_local_history_max_age_days")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub local_history_max_age_days: u32,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -732,7 +1030,8 @@ pub struct EditorConfig {
 
     /// File size threshold in bytes for "large file" behavior
     /// Files larger than this will:
-    /// - Skip LSP features
+    /// - Load lazily, reading chunks from disk as they scroll into view
+    /// - Skip syntax highlighting and LSP features
     /// - Use constant-size scrollbar thumb (1 char)
     ///
     /// Files smaller will count actual lines for accurate scrollbar rendering
@@ -754,6 +1053,23 @@ pub struct EditorConfig {
     #[serde(default = "default_file_tree_poll_interval")]
     #[schemars(extend("x-section" = "Performance"))]
     pub file_tree_poll_interval_ms: u64,
+
+    /// Poll interval in milliseconds for refreshing the git branch and dirty state
+    /// shown in the status bar. Git runs in the background, so polling never blocks input.
+    /// Set to 0 to disable the git status indicator.
+    /// Default: 5000ms (5 seconds)
+    #[serde(default = "default_git_status_poll_interval")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub git_status_poll_interval_ms: u64,
+
+    /// Memory in megabytes the content of open files may use. Above it, the
+    /// content of unmodified files that were not shown recently is dropped
+    /// from memory and read back from disk when needed.
+    /// Set to 0 to keep every file in memory.
+    /// Default: 1024 (1 GB)
+    #[serde(default = "default_memory_budget")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub memory_budget_mb: u64,
 }
 
 fn default_tab_size() -> usize {
@@ -789,6 +1105,14 @@ fn default_scroll_offset() -> usize {
     3
 }
 
+fn default_increment_cycles() -> Vec<String> {
+    vec![
+        "true false".to_string(),
+        "yes no".to_string(),
+        "on off".to_string(),
+    ]
+}
+
 fn default_highlight_timeout() -> u64 {
     5
 }
@@ -809,10 +1133,26 @@ fn default_auto_recovery_save_interval() -> u32 {
     2 // 2 seconds between recovery saves
 }
 
+fn default_local_history_interval() -> u32 {
+    300
+}
+
+fn default_local_history_max_snapshots() -> usize {
+    50
+}
+
+fn default_local_history_max_age_days() -> u32 {
+    30
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
 
+fn default_before_save_timeout() -> u64 {
+    2000
+}
+
 fn default_mouse_hover_delay() -> u64 {
     500 // 500ms delay before showing hover info
 }
@@ -829,15 +1169,31 @@ fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
 
+fn default_git_status_poll_interval() -> u64 {
+    5000 // 5 seconds between `git status` runs
+}
+
+fn default_memory_budget() -> u64 {
+    1024 // 1 GB of file content
+}
+
 impl Default for EditorConfig {
     fn default() -> Self {
         Self {
             tab_size: default_tab_size(),
+            detect_indentation: true,
+            read_editorconfig: true,
             auto_indent: true,
+            increment_cycles: default_increment_cycles(),
+            include_paths: Vec::new(),
+            reindent_on_paste: false,
+            subword_movement: false,
             line_numbers: true,
             relative_line_numbers: false,
             scroll_offset: default_scroll_offset(),
             syntax_highlighting: true,
+            highlight_trailing_whitespace: false,
+            hyperlinks: true,
             line_wrap: true,
             highlight_timeout_ms: default_highlight_timeout(),
             snapshot_interval: default_snapshot_interval(),
@@ -849,18 +1205,34 @@ impl Default for EditorConfig {
             auto_save_interval_secs: default_auto_save_interval(),
             recovery_enabled: true,
             auto_recovery_save_interval_secs: default_auto_recovery_save_interval(),
+            local_history_enabled: false,
+            local_history_interval_secs: default_local_history_interval(),
+            local_history_max_snapshots: default_local_history_max_snapshots(),
+            local_history_max_age_days: default_local_history_max_age_days(),
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: true,
             mouse_hover_delay_ms: default_mouse_hover_delay(),
             double_click_time_ms: default_double_click_time(),
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
+            git_status_poll_interval_ms: default_git_status_poll_interval(),
+            memory_budget_mb: default_memory_budget(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            atomic_save: true,
+            save_backup_count: 0,
+            file_templates: true,
+            template_author: String::new(),
+            template_license: String::new(),
+            before_save_timeout_ms: default_before_save_timeout(),
             highlight_matching_brackets: true,
             rainbow_brackets: true,
+            rainbow_delimiters: false,
             cursor_style: CursorStyle::default(),
+            color_mode: ColorMode::default(),
+            image_preview: ImagePreviewMode::default(),
+            ambiguous_width: AmbiguousWidth::default(),
             keyboard_disambiguate_escape_codes: true,
             keyboard_report_event_types: false,
             keyboard_report_alternate_keys: true,
@@ -873,12 +1245,24 @@ impl Default for EditorConfig {
             show_tab_bar: true,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_selection_stats: true,
+            selection_stats_sum: false,
             use_terminal_bg: false,
             rulers: Vec::new(),
         }
     }
 }
 
+/// Project files configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FilesConfig {
+    /// Glob patterns (gitignore syntax) of files and directories to leave out
+    /// everywhere, on top of .gitignore and .ignore files
+    /// Example: ["*.min.js", "dist/", "vendor/"]
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
 /// File explorer configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileExplorerConfig {
@@ -973,6 +1357,106 @@ impl Default for WarningsConfig {
     }
 }
 
+/// Inline diagnostic ("error lens") configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiagnosticsConfig {
+    /// Show the first diagnostic message of each line as dimmed text after
+    /// the end of the line (default: false)
+    #[serde(default = "default_false")]
+    pub inline_text: bool,
+
+    /// Tint the background of lines that have a diagnostic, colored by
+    /// severity (default: false)
+    #[serde(default = "default_false")]
+    pub line_background: bool,
+
+    /// Include errors in inline diagnostics (default: true)
+    #[serde(default = "default_true")]
+    pub show_errors: bool,
+
+    /// Include warnings in inline diagnostics (default: true)
+    #[serde(default = "default_true")]
+    pub show_warnings: bool,
+
+    /// Include information messages in inline diagnostics (default: true)
+    #[serde(default = "default_true")]
+    pub show_info: bool,
+
+    /// Include hints in inline diagnostics (default: false)
+    #[serde(default = "default_false")]
+    pub show_hints: bool,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            inline_text: false,
+            line_background: false,
+            show_errors: true,
+            show_warnings: true,
+            show_info: true,
+            show_hints: false,
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    /// Whether any inline rendering is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.inline_text || self.line_background
+    }
+}
+
+/// TODO comment aggregation configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TodoConfig {
+    /// Comment tags collected by the TODO panel and marked in the gutter
+    /// Default: ["TODO", "FIXME", "HACK"]
+    #[serde(default = "default_todo_tags")]
+    pub tags: Vec<String>,
+}
+
+fn default_todo_tags() -> Vec<String> {
+    vec!["TODO".to_string(), "FIXME".to_string(), "HACK".to_string()]
+}
+
+impl Default for TodoConfig {
+    fn default() -> Self {
+        Self {
+            tags: default_todo_tags(),
+        }
+    }
+}
+
+/// CSV/TSV table view configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsvConfig {
+    /// Pad cells so the columns of CSV and TSV files line up
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub align_columns: bool,
+
+    /// Color each column differently
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub rainbow_columns: bool,
+
+    /// Keep the header row visible at the top when scrolled past it
+    /// Default: false
+    #[serde(default)]
+    pub pin_header: bool,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            align_columns: true,
+            rainbow_columns: true,
+            pin_header: false,
+        }
+    }
+}
+
 /// Package manager configuration for plugins and themes
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PackagesConfig {
@@ -1069,6 +1553,61 @@ pub struct KeymapConfig {
     pub bindings: Vec<Keybinding>,
 }
 
+/// A user-defined command: a named sequence of steps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/description"))]
+pub struct UserCommand {
+    /// Description shown in the command palette
+    #[serde(default)]
+    pub description: String,
+
+    /// Steps run in order. A step that fails stops the command.
+    #[serde(default)]
+    pub steps: Vec<CommandStep>,
+}
+
+/// One step of a user-defined command. Exactly one of `action`, `shell` and
+/// `plugin` is set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CommandStep {
+    /// Action to perform (e.g., "save", "sort_lines"), or the name of another
+    /// user-defined command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+
+    /// Arguments for the action
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub args: HashMap<String, serde_json::Value>,
+
+    /// Shell command to run with the selection, or the whole buffer, as stdin.
+    /// `${file}`, `${dir}`, `${filename}` and `${line}` are replaced with the
+    /// active file's path, directory, name (each quoted as one shell word) and
+    /// cursor line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Where the output of `shell` goes
+    #[serde(default)]
+    pub output: ShellOutput,
+
+    /// Action registered by a plugin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+}
+
+/// Where the output of a shell step goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellOutput {
+    /// Discard the output
+    #[default]
+    None,
+    /// Show the output in a new buffer
+    Buffer,
+    /// Replace the selection, or the whole buffer, with the output
+    Replace,
+}
+
 /// Formatter configuration for a language
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(extend("x-display-field" = "/command"))]
@@ -1126,6 +1665,117 @@ fn default_on_save_timeout() -> u64 {
     10000
 }
 
+/// A linter run by the editor for languages or tools without a language server
+///
+/// Its findings are shown like language server diagnostics.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct LinterConfig {
+    /// The linter command (e.g., "shellcheck", "yamllint")
+    pub command: String,
+
+    /// Arguments to pass to the linter
+    /// Use "$FILE" to include the file path; without it the path is appended
+    /// (unless the buffer is passed on stdin)
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Whether to pass the buffer content via stdin instead of having the
+    /// linter read the saved file. Needed to lint while typing.
+    #[serde(default)]
+    pub stdin: bool,
+
+    /// When to lint: "save" (when the file is opened or saved) or "idle"
+    /// (also after a pause in typing; needs `stdin`)
+    #[serde(default)]
+    pub run_on: LintTrigger,
+
+    /// How to read the linter's output: "regex" (one finding per matching
+    /// line, see `pattern`) or "json" (see `json`)
+    #[serde(default)]
+    pub format: LinterFormat,
+
+    /// For the "regex" format: a pattern with the named groups `line`, and
+    /// optionally `column`, `end_line`, `end_column`, `severity`, `code` and
+    /// `message`. Lines and columns are 1-based.
+    #[serde(default)]
+    pub pattern: String,
+
+    /// For the "json" format: where the findings are in the output
+    #[serde(default)]
+    pub json: LinterJsonFields,
+
+    /// Timeout in milliseconds (default: 10000)
+    #[serde(default = "default_on_save_timeout")]
+    pub timeout_ms: u64,
+
+    /// Whether this linter is enabled (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// When a linter runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LintTrigger {
+    /// When the file is opened or saved
+    #[default]
+    Save,
+    /// Also after a pause in typing
+    Idle,
+}
+
+/// Output format of a linter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LinterFormat {
+    /// One finding per output line matching `pattern`
+    #[default]
+    Regex,
+    /// A JSON document listing the findings
+    Json,
+}
+
+/// Fields of a linter's JSON output
+///
+/// Each entry is a dot-separated path (e.g. "location.row") into a finding.
+/// Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LinterJsonFields {
+    /// Path to the list of findings (empty when the output is the list itself)
+    pub items: String,
+    /// Line of a finding
+    pub line: String,
+    /// Column of a finding
+    pub column: String,
+    /// Line where a finding ends
+    pub end_line: String,
+    /// Column where a finding ends
+    pub end_column: String,
+    /// Severity of a finding ("error", "warning", "info", "hint", ...)
+    pub severity: String,
+    /// Rule or error code of a finding
+    pub code: String,
+    /// Message of a finding
+    pub message: String,
+}
+
+impl Default for LinterJsonFields {
+    fn default() -> Self {
+        Self {
+            items: String::new(),
+            line: "line".to_string(),
+            column: "column".to_string(),
+            end_line: "endLine".to_string(),
+            end_column: "endColumn".to_string(),
+            severity: "severity".to_string(),
+            code: "code".to_string(),
+            message: "message".to_string(),
+        }
+    }
+}
+
 /// Language-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(extend("x-display-field" = "/grammar"))]
@@ -1179,7 +1829,8 @@ pub struct LanguageConfig {
     #[serde(default)]
     pub formatter: Option<FormatterConfig>,
 
-    /// Whether to automatically format on save (uses the formatter above)
+    /// Whether to automatically format on save (uses the formatter above, or the
+    /// language server when no formatter is configured)
     #[serde(default)]
     pub format_on_save: bool,
 
@@ -1188,6 +1839,15 @@ pub struct LanguageConfig {
     /// Note: Use `formatter` + `format_on_save` for formatting, not on_save
     #[serde(default)]
     pub on_save: Vec<OnSaveAction>,
+
+    /// Command that starts an interactive REPL for this language (e.g. "python3", "ghci").
+    /// Used by "Send to REPL" to start a REPL terminal when none is attached.
+    #[serde(default)]
+    pub repl: Option<String>,
+
+    /// Linters to run for this language, next to any language server
+    #[serde(default)]
+    pub linters: Vec<LinterConfig>,
 }
 
 /// Resolved editor configuration for a specific buffer.
@@ -1420,15 +2080,20 @@ impl Default for Config {
             check_for_updates: true,
             editor: EditorConfig::default(),
             file_explorer: FileExplorerConfig::default(),
+            files: FilesConfig::default(),
             file_browser: FileBrowserConfig::default(),
             clipboard: ClipboardConfig::default(),
             terminal: TerminalConfig::default(),
             keybindings: vec![], // User customizations only; defaults come from active_keybinding_map
             keybinding_maps: HashMap::new(), // User-defined maps go here
             active_keybinding_map: default_keybinding_map_name(),
+            commands: HashMap::new(),
             languages: Self::default_languages(),
             lsp: Self::default_lsp_config(),
             warnings: WarningsConfig::default(),
+            diagnostics: DiagnosticsConfig::default(),
+            todo: TodoConfig::default(),
+            csv: CsvConfig::default(),
             plugins: HashMap::new(), // Populated when scanning for plugins
             packages: PackagesConfig::default(),
         }
@@ -1618,6 +2283,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.edit.find_in_project").to_string(),
+                        action: "project_search".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.edit.delete_line").to_string(),
@@ -1633,6 +2305,13 @@ impl MenuConfig {
                         when: Some(context_keys::FORMATTER_AVAILABLE.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.edit.history_scrubber").to_string(),
+                        action: "history_scrubber".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.edit.settings").to_string(),
@@ -1820,6 +2499,65 @@ impl MenuConfig {
                                 checkbox: None,
                             },
                             MenuItem::Separator { separator: true },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.toggle_panel").to_string(),
+                                action: "toggle_terminal_panel".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.new_panel_terminal").to_string(),
+                                action: "new_panel_terminal".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.rename").to_string(),
+                                action: "rename_terminal".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Separator { separator: true },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.previous_command").to_string(),
+                                action: "terminal_previous_command".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.next_command").to_string(),
+                                action: "terminal_next_command".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.open_output_path").to_string(),
+                                action: "open_terminal_output_path".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Separator { separator: true },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.send_to_repl").to_string(),
+                                action: "send_to_repl".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.terminal.send_block_to_repl").to_string(),
+                                action: "send_block_to_repl".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Separator { separator: true },
                             MenuItem::Action {
                                 label: t!("menu.terminal.toggle_keyboard_capture").to_string(),
                                 action: "toggle_keyboard_capture".to_string(),
@@ -1955,6 +2693,20 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.go.goto_type_definition").to_string(),
+                        action: "lsp_goto_type_definition".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.go.goto_implementation").to_string(),
+                        action: "lsp_goto_implementation".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.go.find_references").to_string(),
                         action: "lsp_references".to_string(),
@@ -1978,6 +2730,69 @@ impl MenuConfig {
                         checkbox: None,
                     },
                     MenuItem::Separator { separator: true },
+                    MenuItem::Submenu {
+                        label: t!("menu.debug").to_string(),
+                        items: vec![
+                            MenuItem::Action {
+                                label: t!("menu.debug.start").to_string(),
+                                action: "debug_start".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.stop").to_string(),
+                                action: "debug_stop".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.step_over").to_string(),
+                                action: "debug_step_over".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.step_into").to_string(),
+                                action: "debug_step_into".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.step_out").to_string(),
+                                action: "debug_step_out".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Separator { separator: true },
+                            MenuItem::Action {
+                                label: t!("menu.debug.toggle_breakpoint").to_string(),
+                                action: "debug_toggle_breakpoint".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.variables").to_string(),
+                                action: "show_debug_variables".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                            MenuItem::Action {
+                                label: t!("menu.debug.console").to_string(),
+                                action: "show_debug_console".to_string(),
+                                args: HashMap::new(),
+                                when: None,
+                                checkbox: None,
+                            },
+                        ],
+                    },
+                    MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.go.command_palette").to_string(),
                         action: "command_palette".to_string(),
@@ -2043,6 +2858,20 @@ impl MenuConfig {
                         when: Some(context_keys::LSP_AVAILABLE.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.format_document").to_string(),
+                        action: "lsp_format_document".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.format_selection").to_string(),
+                        action: "lsp_format_range".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.lsp.toggle_inlay_hints").to_string(),
@@ -2073,6 +2902,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.show_info").to_string(),
+                        action: "show_lsp_info".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.lsp.toggle_for_buffer").to_string(),
@@ -2118,6 +2954,20 @@ impl MenuConfig {
                         when: Some(context_keys::FILE_EXPLORER_FOCUSED.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.explorer.move").to_string(),
+                        action: "file_explorer_move".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::FILE_EXPLORER_FOCUSED.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.explorer.copy").to_string(),
+                        action: "file_explorer_copy".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::FILE_EXPLORER_FOCUSED.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.explorer.delete").to_string(),
                         action: "file_explorer_delete".to_string(),
@@ -2133,6 +2983,20 @@ impl MenuConfig {
                         when: Some(context_keys::FILE_EXPLORER_FOCUSED.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.explorer.filter").to_string(),
+                        action: "file_explorer_filter".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::FILE_EXPLORER_FOCUSED.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.explorer.reveal_active_file").to_string(),
+                        action: "reveal_in_file_explorer".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.explorer.show_hidden").to_string(),
@@ -2148,6 +3012,13 @@ impl MenuConfig {
                         when: Some(context_keys::FILE_EXPLORER.to_string()),
                         checkbox: Some(context_keys::FILE_EXPLORER_SHOW_GITIGNORED.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.explorer.show_changed_only").to_string(),
+                        action: "file_explorer_toggle_changed_only".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::FILE_EXPLORER.to_string()),
+                        checkbox: Some(context_keys::FILE_EXPLORER_SHOW_CHANGED_ONLY.to_string()),
+                    },
                 ],
             },
             // Help menu
@@ -2226,7 +3097,7 @@ impl Config {
         match serde_json::from_str(json_content) {
             Ok(config) => Some(config),
             Err(e) => {
-                eprintln!("Failed to parse builtin keymap '{}': {}", name, e);
+                tracing::error!("Failed to parse builtin keymap '{}': {}", name, e);
                 None
             }
         }
@@ -2247,10 +3118,7 @@ impl Config {
     ) -> Vec<Keybinding> {
         // Prevent infinite loops
         if visited.contains(map_name) {
-            eprintln!(
-                "Warning: Circular inheritance detected in keymap '{}'",
-                map_name
-            );
+            tracing::warn!("Circular inheritance detected in keymap '{}'", map_name);
             return Vec::new();
         }
         visited.insert(map_name.to_string());
@@ -2303,6 +3171,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2327,6 +3197,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: Some("node".to_string()),
+                linters: Vec::new(),
             },
         );
 
@@ -2351,6 +3223,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2379,6 +3253,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: Some("python3".to_string()),
+                linters: Vec::new(),
             },
         );
 
@@ -2403,6 +3279,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2434,6 +3312,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2453,6 +3333,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2487,6 +3369,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: Some("bash".to_string()),
+                linters: Vec::new(),
             },
         );
 
@@ -2510,6 +3394,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2529,6 +3415,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2553,6 +3441,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2572,6 +3462,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2596,6 +3488,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2615,6 +3509,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2640,6 +3536,8 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2659,6 +3557,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2678,6 +3578,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2697,6 +3599,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2723,6 +3627,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2742,6 +3648,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2762,6 +3670,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2786,6 +3696,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2810,6 +3722,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2829,6 +3743,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2848,6 +3764,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2867,6 +3785,8 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -2926,6 +3846,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: Some(ra_init_options),
+                additional_servers: vec![],
             },
         );
 
@@ -2939,6 +3860,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -2951,6 +3873,7 @@ impl Config {
             auto_start: false,
             process_limits: ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         };
         lsp.insert("javascript".to_string(), ts_lsp.clone());
         lsp.insert("typescript".to_string(), ts_lsp);
@@ -2965,6 +3888,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -2978,6 +3902,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -2991,6 +3916,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
         lsp.insert(
@@ -3002,6 +3928,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3015,6 +3942,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3028,6 +3956,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3041,6 +3970,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3055,6 +3985,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3069,6 +4000,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3083,6 +4015,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3097,6 +4030,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3111,6 +4045,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3125,6 +4060,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3139,6 +4075,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3152,6 +4089,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3166,6 +4104,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3179,6 +4118,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3193,6 +4133,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3206,6 +4147,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -3220,6 +4162,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
     }
@@ -3547,6 +4490,8 @@ mod tests {
                 }),
                 format_on_save: true,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -977,6 +977,11 @@ impl DirectoryContext {
         self.data_dir.join("recovery")
     }
 
+    /// Get the local history directory path
+    pub fn local_history_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("local_history")
+    }
+
     /// Get the workspaces directory path
     pub fn workspaces_dir(&self) -> std::path::PathBuf {
         self.data_dir.join("workspaces")
@@ -991,6 +996,14 @@ impl DirectoryContext {
         self.data_dir.join(format!("{}_history.json", safe_name))
     }
 
+    /// Get the frecency file path for a picker
+    /// frecency_name can be: "commands", "files", "theme", "plugin:custom_name", etc.
+    pub fn frecency_path(&self, frecency_name: &str) -> std::path::PathBuf {
+        // Sanitize the name for filesystem safety (replace : with _)
+        let safe_name = frecency_name.replace(':', "_");
+        self.data_dir.join(format!("{}_frecency.json", safe_name))
+    }
+
     /// Get the search history file path (legacy, calls generic method)
     pub fn search_history_path(&self) -> std::path::PathBuf {
         self.prompt_history_path("search")
@@ -1027,6 +1040,11 @@ impl DirectoryContext {
         self.config_dir.join("themes")
     }
 
+    /// Get the file templates directory path
+    pub fn templates_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("templates")
+    }
+
     /// Get the grammars directory path
     pub fn grammars_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("grammars")
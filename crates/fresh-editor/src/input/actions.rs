@@ -6,9 +6,10 @@ use crate::model::buffer::{Buffer, LineEnding};
 use crate::model::cursor::{Cursors, Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
 use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
+use crate::primitives::markup_tags::{self, Markup};
 use crate::primitives::word_navigation::{
-    find_word_end, find_word_end_right, find_word_start, find_word_start_left,
-    find_word_start_right,
+    find_subword_start_left, find_subword_start_right, find_word_end, find_word_end_right,
+    find_word_start, find_word_start_left, find_word_start_right,
 };
 use crate::state::EditorState;
 use std::ops::Range;
@@ -76,6 +77,25 @@ fn content_len_without_line_ending(content: &str) -> usize {
     content.trim_end_matches(LINE_ENDING_CHARS).len()
 }
 
+/// Where smart Home moves a cursor: the first non-blank character of its line,
+/// or the line start when it is already there or the line is blank
+pub(crate) fn smart_home_position(
+    buffer: &mut Buffer,
+    position: usize,
+    estimated_line_length: usize,
+) -> Option<usize> {
+    let mut iter = buffer.line_iterator(position, estimated_line_length);
+    let (line_start, line_content) = iter.next_line()?;
+    let content = line_content.trim_end_matches(LINE_ENDING_CHARS);
+    let indent = content.len() - content.trim_start_matches([' ', '\t']).len();
+    let first_non_blank = line_start + indent;
+    if position == first_non_blank {
+        Some(line_start)
+    } else {
+        Some(first_non_blank)
+    }
+}
+
 /// Adjust position after moving left in CRLF mode.
 /// If we land on \n that's preceded by \r, skip back to the \r.
 /// This ensures the cursor never sits between \r and \n.
@@ -585,6 +605,127 @@ fn handle_auto_close(
     });
 }
 
+/// Markup flavor of the buffer, when tags should be closed and kept in sync
+fn buffer_markup(state: &EditorState) -> Option<Markup> {
+    if state.buffer.is_large_file() {
+        return None;
+    }
+    Markup::detect(&state.language, state.highlighter.syntax_name())
+}
+
+/// Handle tag auto-close in markup: `>` ending an opening tag adds its closing
+/// tag after the cursor, and `</` is completed to close the innermost open
+/// element. Returns true if handled.
+fn handle_tag_auto_close(
+    state: &mut EditorState,
+    events: &mut Vec<Event>,
+    cursor_id: CursorId,
+    ch: char,
+    insert_position: usize,
+    markup: Markup,
+) -> bool {
+    let before = state.get_text_range(0, insert_position);
+    match ch {
+        '>' => {
+            let Some(name) = markup_tags::tag_finished_by_gt(&before, markup) else {
+                return false;
+            };
+            let closing = format!("</{}>", name);
+            // Retyping the `>` of a tag that is already closed
+            let end = (insert_position + closing.len()).min(state.buffer.len());
+            if state.get_text_range(insert_position, end) == closing {
+                return false;
+            }
+            events.push(Event::Insert {
+                position: insert_position,
+                text: format!(">{}", closing),
+                cursor_id,
+            });
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: insert_position + 1 + closing.len(),
+                new_position: insert_position + 1,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            });
+            true
+        }
+        '/' => {
+            let Some(open_before) = before.strip_suffix('<') else {
+                return false;
+            };
+            let Some(name) = markup_tags::unclosed_tag(open_before, markup) else {
+                return false;
+            };
+            events.push(Event::Insert {
+                position: insert_position,
+                text: format!("/{}>", name),
+                cursor_id,
+            });
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Keep an element's closing tag name in step with an edit of its opening tag
+/// name. `events` must hold just that edit, not yet applied.
+fn sync_closing_tag(state: &mut EditorState, cursors: &Cursors, events: &mut Vec<Event>) {
+    if cursors.count() != 1 {
+        return;
+    }
+    let Some(markup) = buffer_markup(state) else {
+        return;
+    };
+    let (edited, replacement, cursor_id) = match events.as_slice() {
+        [Event::Insert {
+            position,
+            text,
+            cursor_id,
+        }] => (*position..*position, text.clone(), *cursor_id),
+        [Event::Delete {
+            range, cursor_id, ..
+        }] => (range.clone(), String::new(), *cursor_id),
+        _ => return,
+    };
+    let text = state.get_text_range(0, state.buffer.len());
+    let language = state.highlighter.language().cloned();
+    let Some((close_name, new_name)) = markup_tags::closing_tag_rename(
+        &text,
+        edited.clone(),
+        &replacement,
+        markup,
+        language.as_ref(),
+    ) else {
+        return;
+    };
+
+    // Like the edit itself, positions are in the text before any of the events
+    let cursor_after_edit = edited.start + replacement.len();
+    let shifted_end = close_name.start + new_name.len() + replacement.len() - edited.len();
+    events.push(Event::Delete {
+        range: close_name.clone(),
+        deleted_text: text[close_name.clone()].to_string(),
+        cursor_id,
+    });
+    events.push(Event::Insert {
+        position: close_name.start,
+        text: new_name,
+        cursor_id,
+    });
+    events.push(Event::MoveCursor {
+        cursor_id,
+        old_position: shifted_end,
+        new_position: cursor_after_edit,
+        old_anchor: None,
+        new_anchor: None,
+        old_sticky_column: 0,
+        new_sticky_column: 0,
+    });
+}
+
 /// Cursor context data collected before processing insertions.
 struct InsertCursorData {
     cursor_id: CursorId,
@@ -676,6 +817,11 @@ fn insert_char_events(
 ) {
     let is_closing_delimiter = matches!(ch, '}' | ')' | ']');
     let auto_close_char = get_auto_close_char(ch, auto_indent, &state.language);
+    let markup = if auto_indent && matches!(ch, '>' | '/') {
+        buffer_markup(state)
+    } else {
+        None
+    };
     let cursor_data = collect_insert_cursor_data(state, cursors);
 
     for data in cursor_data {
@@ -733,6 +879,20 @@ fn insert_char_events(
             continue;
         }
 
+        // Try closing a markup tag
+        if let Some(markup) = markup {
+            if handle_tag_auto_close(
+                state,
+                events,
+                data.cursor_id,
+                ch,
+                data.insert_position,
+                markup,
+            ) {
+                continue;
+            }
+        }
+
         // Try auto-close
         if let Some(close_char) = auto_close_char {
             if should_auto_close(data.char_after) {
@@ -830,6 +990,13 @@ pub fn action_to_events(
         return None;
     }
 
+    // Editing an opening tag name also renames the element's closing tag
+    let syncs_tags = auto_indent
+        && matches!(
+            action,
+            Action::InsertChar(_) | Action::DeleteBackward | Action::DeleteForward
+        );
+
     let mut events = Vec::new();
 
     // Convert block selection to multi-cursor before processing editing actions
@@ -1508,6 +1675,56 @@ pub fn action_to_events(
             }
         }
 
+        Action::MoveSubWordLeft | Action::MoveSubWordRight => {
+            for (cursor_id, cursor) in cursors.iter() {
+                let new_pos = if matches!(action, Action::MoveSubWordLeft) {
+                    find_subword_start_left(&state.buffer, cursor.position)
+                } else {
+                    find_subword_start_right(&state.buffer, cursor.position)
+                };
+                // Preserve anchor if deselect_on_move is false (Emacs mark mode)
+                let new_anchor = if cursor.deselect_on_move {
+                    None
+                } else {
+                    cursor.anchor
+                };
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
+        Action::SmartHome => {
+            for (cursor_id, cursor) in cursors.iter() {
+                let Some(new_pos) =
+                    smart_home_position(&mut state.buffer, cursor.position, estimated_line_length)
+                else {
+                    continue;
+                };
+                // Preserve anchor if deselect_on_move is false (Emacs mark mode)
+                let new_anchor = if cursor.deselect_on_move {
+                    None
+                } else {
+                    cursor.anchor
+                };
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor,
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
         Action::MoveDocumentStart => {
             for (cursor_id, cursor) in cursors.iter() {
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
@@ -1916,6 +2133,46 @@ pub fn action_to_events(
             }
         }
 
+        Action::SelectSubWordLeft | Action::SelectSubWordRight => {
+            for (cursor_id, cursor) in cursors.iter() {
+                let new_pos = if matches!(action, Action::SelectSubWordLeft) {
+                    find_subword_start_left(&state.buffer, cursor.position)
+                } else {
+                    find_subword_start_right(&state.buffer, cursor.position)
+                };
+                let anchor = cursor.anchor.unwrap_or(cursor.position);
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor: Some(anchor),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
+        Action::SelectSmartHome => {
+            for (cursor_id, cursor) in cursors.iter() {
+                let Some(new_pos) =
+                    smart_home_position(&mut state.buffer, cursor.position, estimated_line_length)
+                else {
+                    continue;
+                };
+                let anchor = cursor.anchor.unwrap_or(cursor.position);
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: new_pos,
+                    old_anchor: cursor.anchor,
+                    new_anchor: Some(anchor),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
         Action::SelectDocumentStart => {
             for (cursor_id, cursor) in cursors.iter() {
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
@@ -2521,6 +2778,9 @@ pub fn action_to_events(
         | Action::Save
         | Action::SaveAs
         | Action::Open
+        | Action::OpenFileUnderCursor
+        | Action::OpenFileUnderCursorInSplit
+        | Action::OpenLink
         | Action::SwitchProject
         | Action::New
         | Action::Close
@@ -2530,6 +2790,8 @@ pub fn action_to_events(
         | Action::PrevBuffer
         | Action::SwitchToPreviousTab
         | Action::SwitchToTabByName
+        | Action::InsertUnicodeCharacter
+        | Action::InspectCharacter
         | Action::NavigateBack
         | Action::NavigateForward
         | Action::SplitHorizontal
@@ -2551,24 +2813,59 @@ pub fn action_to_events(
         | Action::CommandPalette
         | Action::QuickOpen
         | Action::ShowHelp
+        | Action::ShowTutor
         | Action::ToggleLineWrap
         | Action::ToggleComposeMode
         | Action::SetComposeWidth
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
+        | Action::FocusSplitLeft
+        | Action::FocusSplitRight
+        | Action::FocusSplitUp
+        | Action::FocusSplitDown
+        | Action::RotateSplits
+        | Action::CloseOtherSplits
+        | Action::EqualizeSplits
+        | Action::SaveLayout
+        | Action::RestoreLayout
+        | Action::DeleteLayout
+        | Action::GitDiffHead
+        | Action::GitDiffIndex
+        | Action::GitDiffRevision
+        | Action::GitCheckoutBranch
+        | Action::GitFetch
+        | Action::GitPull
+        | Action::GitPush
+        | Action::GitLog
+        | Action::DiffNextHunk
+        | Action::DiffPrevHunk
+        | Action::ShowUnsavedChanges
+        | Action::ShowUnsavedChangesUnified
+        | Action::RevertHunk
+        | Action::LocalHistory
+        | Action::ActionConsole
+        | Action::RestoreSnapshot
         | Action::Undo
         | Action::Redo
+        | Action::HistoryScrubber
         | Action::GoToMatchingBracket
+        | Action::GoToMatchingTag
+        | Action::SelectElement
         | Action::JumpToNextError
         | Action::JumpToPreviousError
+        | Action::NextDiagnostic
+        | Action::PrevDiagnostic
         | Action::ShowKeyboardShortcuts
         | Action::ShowWarnings
         | Action::ShowStatusLog
         | Action::ShowLspStatus
+        | Action::ShowLspInfo
+        | Action::ShowBuffers
         | Action::ClearWarnings
-        | Action::SmartHome
         | Action::ToggleComment
+        | Action::Increment(_)
+        | Action::IncrementSequence(_)
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
         | Action::ClearBookmark(_)
@@ -2586,6 +2883,7 @@ pub fn action_to_events(
         | Action::PromptRecordMacro
         | Action::PromptPlayMacro
         | Action::PlayLastMacro
+        | Action::RepeatLastEdit
         | Action::PromptSetBookmark
         | Action::PromptJumpToBookmark
         | Action::PromptConfirm
@@ -2646,23 +2944,70 @@ pub fn action_to_events(
         | Action::FileExplorerNewDirectory
         | Action::FileExplorerDelete
         | Action::FileExplorerRename
+        | Action::FileExplorerToggleMark
+        | Action::FileExplorerMarkUp
+        | Action::FileExplorerMarkDown
+        | Action::FileExplorerMove
+        | Action::FileExplorerCopy
         | Action::FileExplorerToggleHidden
         | Action::FileExplorerToggleGitignored
+        | Action::FileExplorerToggleChangedOnly
+        | Action::FileExplorerFilter
+        | Action::RevealInFileExplorer
         | Action::FileExplorerSearchClear
         | Action::FileExplorerSearchBackspace
         | Action::LspCompletion
         | Action::LspGotoDefinition
+        | Action::LspGotoTypeDefinition
+        | Action::LspGotoImplementation
         | Action::LspReferences
         | Action::LspRename
         | Action::LspHover
         | Action::LspSignatureHelp
         | Action::LspCodeActions
+        | Action::LspFormatDocument
+        | Action::LspFormatRange
         | Action::LspRestart
         | Action::LspStop
+        | Action::LspInfoRestart
+        | Action::LspInfoStop
+        | Action::LspInfoOpenLog
+        | Action::LspLogCycleLevel
+        | Action::BuffersPanelOpen
+        | Action::BuffersPanelUnload
+        | Action::FilterKeyboardShortcuts
+        | Action::DebugStart
+        | Action::DebugStop
+        | Action::DebugContinue
+        | Action::DebugStepOver
+        | Action::DebugStepInto
+        | Action::DebugStepOut
+        | Action::DebugToggleBreakpoint
+        | Action::ShowDebugVariables
+        | Action::ShowDebugConsole
+        | Action::ShowPluginConsole
+        | Action::PluginConsoleCycleLevel
+        | Action::PluginConsoleCyclePlugin
+        | Action::ShowLog
+        | Action::LogCycleLevel
+        | Action::LogCycleSource
+        | Action::CheckHealth
+        | Action::ShowKeybindingConflicts
+        | Action::KeybindingConflictsRebind
+        | Action::KeybindingConflictsDisable
+        | Action::DebugAddWatch
+        | Action::DebugRemoveWatch
+        | Action::DebugEvaluate
+        | Action::DebugToggleVariable
         | Action::LspToggleForBuffer
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
         | Action::ToggleLineNumbers
+        | Action::ToggleReadOnly
+        | Action::TogglePagerFollow
+        | Action::CollabHost
+        | Action::CollabJoin
+        | Action::CollabLeave
         | Action::ToggleScrollSync
         | Action::ToggleMouseCapture
         | Action::DumpConfig
@@ -2674,6 +3019,9 @@ pub fn action_to_events(
         | Action::FindSelectionPrevious
         | Action::Replace
         | Action::QueryReplace
+        | Action::ProjectSearch
+        | Action::ProjectSearchOpen
+        | Action::ProjectSearchRerun
         | Action::MenuActivate
         | Action::MenuClose
         | Action::MenuLeft
@@ -2684,6 +3032,7 @@ pub fn action_to_events(
         | Action::MenuOpen(_)
         | Action::SwitchKeybindingMap(_)
         | Action::PluginAction(_)
+        | Action::UserCommand(_)
         | Action::None
         | Action::ScrollTabsLeft
         | Action::ScrollTabsRight
@@ -2702,6 +3051,14 @@ pub fn action_to_events(
         | Action::TerminalEscape
         | Action::ToggleKeyboardCapture
         | Action::TerminalPaste
+        | Action::ToggleTerminalPanel
+        | Action::NewPanelTerminal
+        | Action::RenameTerminal
+        | Action::SendToRepl
+        | Action::SendBlockToRepl
+        | Action::TerminalPreviousCommand
+        | Action::TerminalNextCommand
+        | Action::OpenTerminalOutputPath
         | Action::OpenSettings
         | Action::CloseSettings
         | Action::SettingsSave
@@ -2718,11 +3075,15 @@ pub fn action_to_events(
         | Action::ReloadWithEncoding
         | Action::SetLanguage
         | Action::ToggleIndentationStyle
+        | Action::ConvertIndentationToSpaces
+        | Action::ConvertIndentationToTabs
         | Action::ToggleTabIndicators
         | Action::ToggleDebugHighlights
+        | Action::TogglePerfHud
         | Action::ResetBufferSettings
         | Action::ShellCommand
         | Action::ShellCommandReplace
+        | Action::GlobalCommand
         | Action::CalibrateInput
         | Action::EventDebug
         | Action::OpenKeybindingEditor
@@ -2770,6 +3131,35 @@ pub fn action_to_events(
             }
         }
 
+        Action::SelectInsideWord
+        | Action::SelectAroundWord
+        | Action::SelectInsideQuotes
+        | Action::SelectAroundQuotes
+        | Action::SelectInsideBrackets
+        | Action::SelectAroundBrackets
+        | Action::SelectInsideParagraph
+        | Action::SelectAroundParagraph
+        | Action::SelectInsideFunction
+        | Action::SelectAroundFunction
+        | Action::SelectInsideArgument
+        | Action::SelectAroundArgument => {
+            let (object, around) = action.text_object()?;
+            for (cursor_id, cursor) in cursors.iter() {
+                let Some(range) = state.text_object_range(cursor.position, object, around) else {
+                    continue;
+                };
+                events.push(Event::MoveCursor {
+                    cursor_id,
+                    old_position: cursor.position,
+                    new_position: range.end,
+                    old_anchor: cursor.anchor,
+                    new_anchor: Some(range.start),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0, // Reset sticky column
+                });
+            }
+        }
+
         Action::ExpandSelection => {
             // Expand selection for each cursor
             for (cursor_id, cursor) in cursors.iter() {
@@ -2820,6 +3210,10 @@ pub fn action_to_events(
         }
     }
 
+    if syncs_tags {
+        sync_closing_tag(state, cursors, &mut events);
+    }
+
     Some(events)
 }
 
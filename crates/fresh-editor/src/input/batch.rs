@@ -0,0 +1,317 @@
+//! Batch scripts (`fresh --batch script.fs file...`)
+//!
+//! A script is a list of editor commands, one per line, run against each
+//! file in turn:
+//!
+//! ```text
+//! # Comments and blank lines are ignored
+//! s/colou?r/hue/i          # regex replace-all; flags: i (ignore case), w (whole word)
+//! replace "old" "new"      # literal replace-all
+//! goto 10:4                # 1-based line and optional column
+//! insert "// header\n"     # insert text at the cursor
+//! select_all               # any action name from the keybinding config
+//! sort_lines
+//! ```
+//!
+//! Quoted arguments accept `\n`, `\t`, `\"` and `\\` escapes.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::input::keybindings::Action;
+
+/// One command of a batch script
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchCommand {
+    /// Replace every match of `search` with `replacement`
+    Replace {
+        search: String,
+        replacement: String,
+        regex: bool,
+        case_sensitive: bool,
+        whole_word: bool,
+    },
+    /// Move the cursor to a 1-based line and optional column
+    Goto { line: usize, column: Option<usize> },
+    /// Insert text at the cursor
+    Insert(String),
+    /// Run an editor action
+    Action(Action),
+}
+
+/// A command and the script line it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchStep {
+    /// 1-based line number in the script
+    pub line: usize,
+    pub command: BatchCommand,
+}
+
+/// A parsed batch script
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchScript {
+    pub steps: Vec<BatchStep>,
+}
+
+/// A script line that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for BatchParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for BatchParseError {}
+
+impl BatchScript {
+    /// Parse a script, reporting the first invalid line
+    pub fn parse(text: &str) -> Result<Self, BatchParseError> {
+        let mut steps = Vec::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let command = parse_line(raw).map_err(|message| BatchParseError { line, message })?;
+            if let Some(command) = command {
+                steps.push(BatchStep { line, command });
+            }
+        }
+        Ok(Self { steps })
+    }
+}
+
+fn parse_line(raw: &str) -> Result<Option<BatchCommand>, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+
+    // s/pattern/replacement/flags with any punctuation as the delimiter
+    let mut chars = trimmed.chars();
+    if let (Some('s'), Some(delimiter)) = (chars.next(), chars.next()) {
+        if delimiter.is_ascii_punctuation() && delimiter != '_' {
+            return parse_substitute(&trimmed[1 + delimiter.len_utf8()..], delimiter).map(Some);
+        }
+    }
+
+    let words = split_words(trimmed)?;
+    let (name, args) = words.split_first().ok_or("empty command")?;
+    let command = match name.as_str() {
+        "replace" => match args {
+            [search, replacement] => BatchCommand::Replace {
+                search: search.clone(),
+                replacement: replacement.clone(),
+                regex: false,
+                case_sensitive: true,
+                whole_word: false,
+            },
+            _ => return Err("usage: replace \"search\" \"replacement\"".to_string()),
+        },
+        "goto" => match args {
+            [target] => parse_goto(target)?,
+            _ => return Err("usage: goto LINE[:COLUMN]".to_string()),
+        },
+        "insert" => match args {
+            [text] => BatchCommand::Insert(text.clone()),
+            _ => return Err("usage: insert \"text\"".to_string()),
+        },
+        _ => {
+            if !args.is_empty() {
+                return Err(format!("action '{}' takes no arguments", name));
+            }
+            let action = Action::from_str(name, &HashMap::new())
+                .ok_or_else(|| format!("unknown command '{}'", name))?;
+            BatchCommand::Action(action)
+        }
+    };
+    Ok(Some(command))
+}
+
+/// Parse the rest of `s/pattern/replacement/flags` after the opening delimiter
+fn parse_substitute(rest: &str, delimiter: char) -> Result<BatchCommand, String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                // An escaped delimiter is literal; other escapes are kept for the regex
+                Some(next) if next == delimiter => current.push(next),
+                Some(next) => {
+                    current.push('\\');
+                    current.push(next);
+                }
+                None => current.push('\\'),
+            }
+        } else if c == delimiter && parts.len() < 2 {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    if parts.len() < 2 {
+        return Err(format!(
+            "unterminated substitution; expected s{d}pattern{d}replacement{d}",
+            d = delimiter
+        ));
+    }
+    let flags = current.trim();
+    let mut case_sensitive = true;
+    let mut whole_word = false;
+    for flag in flags.chars() {
+        match flag {
+            'i' => case_sensitive = false,
+            'w' => whole_word = true,
+            // Every match is replaced; accept sed's g for familiarity
+            'g' => {}
+            _ => return Err(format!("unknown substitution flag '{}'", flag)),
+        }
+    }
+    let replacement = parts.pop().unwrap_or_default();
+    let search = parts.pop().unwrap_or_default();
+    if search.is_empty() {
+        return Err("empty search pattern".to_string());
+    }
+    regex::Regex::new(&search).map_err(|e| format!("invalid regex: {}", e))?;
+    Ok(BatchCommand::Replace {
+        search,
+        replacement,
+        regex: true,
+        case_sensitive,
+        whole_word,
+    })
+}
+
+fn parse_goto(target: &str) -> Result<BatchCommand, String> {
+    let invalid = || format!("invalid position '{}'", target);
+    let (line, column) = match target.split_once(':') {
+        Some((line, column)) => (line, Some(column.parse().map_err(|_| invalid())?)),
+        None => (target, None),
+    };
+    let line: usize = line.parse().map_err(|_| invalid())?;
+    if line == 0 || column == Some(0) {
+        return Err(invalid());
+    }
+    Ok(BatchCommand::Goto { line, column })
+}
+
+/// Split a line into whitespace-separated words, honouring double quotes
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut word = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => word.push('\n'),
+                        Some('t') => word.push('\t'),
+                        Some(other) => word.push(other),
+                        None => return Err("unterminated string".to_string()),
+                    },
+                    Some(other) => word.push(other),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            words.push(word);
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let script = BatchScript::parse(
+            "# tidy up\n\
+             s/colou?r/hue/i\n\
+             s|a\\|b|c|gw\n\
+             replace \"x y\" \"z\\n\"\n\
+             \n\
+             goto 3:2\n\
+             insert \"hi\" # trailing comment\n\
+             sort_lines\n",
+        )
+        .unwrap();
+        let commands: Vec<_> = script.steps.iter().map(|s| (s.line, &s.command)).collect();
+        assert_eq!(
+            commands,
+            vec![
+                (
+                    2,
+                    &BatchCommand::Replace {
+                        search: "colou?r".into(),
+                        replacement: "hue".into(),
+                        regex: true,
+                        case_sensitive: false,
+                        whole_word: false,
+                    }
+                ),
+                (
+                    3,
+                    &BatchCommand::Replace {
+                        search: "a|b".into(),
+                        replacement: "c".into(),
+                        regex: true,
+                        case_sensitive: true,
+                        whole_word: true,
+                    }
+                ),
+                (
+                    4,
+                    &BatchCommand::Replace {
+                        search: "x y".into(),
+                        replacement: "z\n".into(),
+                        regex: false,
+                        case_sensitive: true,
+                        whole_word: false,
+                    }
+                ),
+                (
+                    6,
+                    &BatchCommand::Goto {
+                        line: 3,
+                        column: Some(2)
+                    }
+                ),
+                (7, &BatchCommand::Insert("hi".into())),
+                (8, &BatchCommand::Action(Action::SortLines)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let error = BatchScript::parse("select_all\nfrobnicate\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(error.message.contains("frobnicate"));
+
+        assert!(BatchScript::parse("s/a(/b/").is_err());
+        assert!(BatchScript::parse("s/a/b").is_err());
+        assert!(BatchScript::parse("goto 0").is_err());
+        assert!(BatchScript::parse("insert \"open").is_err());
+    }
+}
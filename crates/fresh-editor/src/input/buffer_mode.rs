@@ -7,6 +7,42 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 use std::collections::HashMap;
 
+/// Mode of the LSP info buffer
+pub const LSP_INFO_MODE: &str = "lsp-info";
+
+/// Mode of the LSP server log buffer
+pub const LSP_LOG_MODE: &str = "lsp-log";
+
+/// Mode of the keyboard shortcuts page
+pub const KEYBOARD_SHORTCUTS_MODE: &str = "keyboard-shortcuts";
+
+/// Mode of the debugger variables and watch panel
+pub const DEBUG_VARIABLES_MODE: &str = "debug-variables";
+
+/// Mode of the debug console buffer
+pub const DEBUG_CONSOLE_MODE: &str = "debug-console";
+
+/// Mode of the plugin console buffer
+pub const PLUGIN_CONSOLE_MODE: &str = "plugin-console";
+
+/// Mode of the `*Log*` buffer
+pub const LOG_MODE: &str = "log";
+
+/// Mode of the `*Health*` buffer
+pub const HEALTH_MODE: &str = "health";
+
+/// Mode of the `*Keybinding Conflicts*` buffer
+pub const KEYBINDING_CONFLICTS_MODE: &str = "keybinding-conflicts";
+
+/// Mode of the `*Search Results*` buffer
+pub const PROJECT_SEARCH_MODE: &str = "project-search";
+
+/// Mode of the unified view of unsaved changes
+pub const UNSAVED_CHANGES_MODE: &str = "unsaved-changes";
+
+/// Mode of the panel listing the open buffers
+pub const BUFFERS_MODE: &str = "buffers";
+
 /// A buffer mode that defines keybindings and behavior for a type of buffer
 #[derive(Debug, Clone)]
 pub struct BufferMode {
@@ -87,6 +123,9 @@ impl BufferMode {
 pub struct ModeRegistry {
     /// All registered modes
     modes: HashMap<String, BufferMode>,
+
+    /// Bumped whenever a mode is registered
+    generation: u64,
 }
 
 impl ModeRegistry {
@@ -94,6 +133,7 @@ impl ModeRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             modes: HashMap::new(),
+            generation: 0,
         };
 
         // Register built-in "special" mode (base for all special buffers)
@@ -106,12 +146,148 @@ impl ModeRegistry {
 
         registry.register(special_mode);
 
+        // LSP info view: manage the server under the cursor
+        let lsp_info_mode = BufferMode::new(LSP_INFO_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('r'), KeyModifiers::NONE, "lsp_info_restart")
+            .with_binding(KeyCode::Char('s'), KeyModifiers::NONE, "lsp_info_stop")
+            .with_binding(KeyCode::Char('l'), KeyModifiers::NONE, "lsp_info_open_log")
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "lsp_info_open_log");
+        registry.register(lsp_info_mode);
+
+        // LSP server log view: cycle the minimum level shown
+        let lsp_log_mode = BufferMode::new(LSP_LOG_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('f'),
+                KeyModifiers::NONE,
+                "lsp_log_cycle_level",
+            );
+        registry.register(lsp_log_mode);
+
+        // Keyboard shortcuts page: filter the list
+        let keyboard_shortcuts_mode = BufferMode::new(KEYBOARD_SHORTCUTS_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('/'),
+                KeyModifiers::NONE,
+                "filter_keyboard_shortcuts",
+            );
+        registry.register(keyboard_shortcuts_mode);
+
+        // Debugger panels: expand variables, manage watches, evaluate
+        let debug_variables_mode = BufferMode::new(DEBUG_VARIABLES_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "debug_toggle_variable")
+            .with_binding(KeyCode::Char('a'), KeyModifiers::NONE, "debug_add_watch")
+            .with_binding(KeyCode::Char('d'), KeyModifiers::NONE, "debug_remove_watch");
+        registry.register(debug_variables_mode);
+
+        let debug_console_mode = BufferMode::new(DEBUG_CONSOLE_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('e'), KeyModifiers::NONE, "debug_evaluate");
+        registry.register(debug_console_mode);
+
+        // Plugin console: filter by level and plugin
+        let plugin_console_mode = BufferMode::new(PLUGIN_CONSOLE_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('f'),
+                KeyModifiers::NONE,
+                "plugin_console_cycle_level",
+            )
+            .with_binding(
+                KeyCode::Char('p'),
+                KeyModifiers::NONE,
+                "plugin_console_cycle_plugin",
+            );
+        registry.register(plugin_console_mode);
+
+        // Log: filter by level and source
+        let log_mode = BufferMode::new(LOG_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('f'), KeyModifiers::NONE, "log_cycle_level")
+            .with_binding(KeyCode::Char('s'), KeyModifiers::NONE, "log_cycle_source");
+        registry.register(log_mode);
+
+        // Health check: run the checks again
+        let health_mode = BufferMode::new(HEALTH_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('r'), KeyModifiers::NONE, "check_health");
+        registry.register(health_mode);
+
+        // Keybinding conflicts: rebind or disable the conflict under the cursor
+        let keybinding_conflicts_mode = BufferMode::new(KEYBINDING_CONFLICTS_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('r'),
+                KeyModifiers::NONE,
+                "keybinding_conflicts_rebind",
+            )
+            .with_binding(
+                KeyCode::Char('d'),
+                KeyModifiers::NONE,
+                "keybinding_conflicts_disable",
+            )
+            .with_binding(
+                KeyCode::Char('g'),
+                KeyModifiers::NONE,
+                "show_keybinding_conflicts",
+            );
+        registry.register(keybinding_conflicts_mode);
+
+        // Project search results: open the result under the cursor
+        let project_search_mode = BufferMode::new(PROJECT_SEARCH_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "project_search_open")
+            .with_binding(
+                KeyCode::Char('g'),
+                KeyModifiers::NONE,
+                "project_search_rerun",
+            );
+        registry.register(project_search_mode);
+
+        // Unified view of unsaved changes: revert the change under the cursor
+        let unsaved_changes_mode = BufferMode::new(UNSAVED_CHANGES_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('r'), KeyModifiers::NONE, "revert_hunk");
+        registry.register(unsaved_changes_mode);
+
+        // Buffers panel: open or unload the buffer under the cursor
+        let buffers_mode = BufferMode::new(BUFFERS_MODE)
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "buffers_panel_open")
+            .with_binding(
+                KeyCode::Char('u'),
+                KeyModifiers::NONE,
+                "buffers_panel_unload",
+            );
+        registry.register(buffers_mode);
+
         registry
     }
 
     /// Register a new mode
     pub fn register(&mut self, mode: BufferMode) {
         self.modes.insert(mode.name.clone(), mode);
+        self.generation += 1;
+    }
+
+    /// Counter that changes whenever a mode is registered
+    pub fn generation(&self) -> u64 {
+        self.generation
     }
 
     /// Get a mode by name
@@ -350,6 +526,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lsp_info_mode_keybindings() {
+        let registry = ModeRegistry::new();
+
+        assert_eq!(
+            registry.resolve_keybinding(LSP_INFO_MODE, KeyCode::Char('r'), KeyModifiers::NONE),
+            Some("lsp_info_restart".to_string())
+        );
+        // Closing comes from the parent special mode
+        assert_eq!(
+            registry.resolve_keybinding(LSP_LOG_MODE, KeyCode::Char('q'), KeyModifiers::NONE),
+            Some("close".to_string())
+        );
+        assert!(registry.is_read_only(LSP_LOG_MODE));
+    }
+
     #[test]
     fn test_mode_inheritance() {
         let mut registry = ModeRegistry::new();
@@ -4,6 +4,7 @@
 //! while maintaining the built-in command set.
 
 use crate::input::commands::{get_all_commands, Command, Suggestion};
+use crate::input::frecency::Frecency;
 use crate::input::fuzzy::fuzzy_match;
 use crate::input::keybindings::Action;
 use crate::input::keybindings::KeyContext;
@@ -17,55 +18,53 @@ pub struct CommandRegistry {
     /// Built-in commands (loaded once at startup)
     builtin_commands: Vec<Command>,
 
+    /// User-defined commands from the config (replaced when the config changes)
+    user_commands: Vec<Command>,
+
     /// Plugin-registered commands (dynamically added/removed)
     plugin_commands: Arc<RwLock<Vec<Command>>>,
 
-    /// Command usage history (most recent first)
-    /// Used to sort command palette suggestions by recency
-    command_history: Vec<String>,
+    /// How often and how recently each command was run
+    /// Used to rank command palette suggestions by frecency
+    usage: Frecency,
 }
 
 impl CommandRegistry {
-    /// Maximum number of commands to keep in history
-    const MAX_HISTORY_SIZE: usize = 50;
-
     /// Create a new command registry with built-in commands
     pub fn new() -> Self {
         Self {
             builtin_commands: get_all_commands(),
+            user_commands: Vec::new(),
             plugin_commands: Arc::new(RwLock::new(Vec::new())),
-            command_history: Vec::new(),
+            usage: Frecency::new(),
         }
     }
 
+    /// Replace the command usage data (e.g. loaded from a previous session)
+    pub fn set_usage(&mut self, usage: Frecency) {
+        self.usage = usage;
+    }
+
+    /// Command usage data, for saving across sessions
+    pub fn usage(&self) -> &Frecency {
+        &self.usage
+    }
+
     /// Refresh built-in commands (e.g. after locale change)
     pub fn refresh_builtin_commands(&mut self) {
         self.builtin_commands = get_all_commands();
     }
 
-    /// Record that a command was used (for history/sorting)
-    ///
-    /// This moves the command to the front of the history list.
-    /// Recently used commands appear first in suggestions.
-    pub fn record_usage(&mut self, command_name: &str) {
-        // Remove existing entry if present
-        self.command_history.retain(|name| name != command_name);
-
-        // Add to front (most recent)
-        self.command_history.insert(0, command_name.to_string());
-
-        // Trim to max size
-        if self.command_history.len() > Self::MAX_HISTORY_SIZE {
-            self.command_history.truncate(Self::MAX_HISTORY_SIZE);
-        }
+    /// Replace the user-defined commands
+    pub fn set_user_commands(&mut self, commands: Vec<Command>) {
+        self.user_commands = commands;
     }
 
-    /// Get the position of a command in history (0 = most recent)
-    /// Returns None if command is not in history
-    fn history_position(&self, command_name: &str) -> Option<usize> {
-        self.command_history
-            .iter()
-            .position(|name| name == command_name)
+    /// Record that a command was used (for frecency ranking)
+    ///
+    /// Frequently and recently used commands appear first in suggestions.
+    pub fn record_usage(&mut self, command_name: &str) {
+        self.usage.record(command_name);
     }
 
     /// Register a new command (typically from a plugin)
@@ -124,9 +123,10 @@ impl CommandRegistry {
         }
     }
 
-    /// Get all commands (built-in + plugin)
+    /// Get all commands (built-in + user-defined + plugin)
     pub fn get_all(&self) -> Vec<Command> {
         let mut all_commands = self.builtin_commands.clone();
+        all_commands.extend(self.user_commands.iter().cloned());
 
         let plugin_commands = self.plugin_commands.read().unwrap();
         let plugin_count = plugin_commands.len();
@@ -205,7 +205,7 @@ impl CommandRegistry {
                 }
                 let keybinding =
                     keybinding_resolver.get_keybinding_for_action(&cmd.action, current_context);
-                let history_pos = self.history_position(&cmd.name);
+                let score = score + self.usage.bonus(&localized_name);
 
                 let suggestion = Suggestion::with_source(
                     localized_name,
@@ -214,12 +214,12 @@ impl CommandRegistry {
                     keybinding,
                     Some(cmd.source.clone()),
                 );
-                (suggestion, history_pos, score)
+                (suggestion, score)
             };
 
         // First, try to match by name only
         // Commands with unmet custom contexts are completely hidden
-        let mut suggestions: Vec<(Suggestion, Option<usize>, i32)> = commands
+        let mut suggestions: Vec<(Suggestion, i32)> = commands
             .iter()
             .filter(|cmd| is_visible(cmd))
             .filter_map(|cmd| {
@@ -265,17 +265,17 @@ impl CommandRegistry {
 
         // Sort by:
         // 1. Disabled status (enabled first)
-        // 2. Fuzzy match score (higher is better) - only when query is not empty
-        // 3. History position (recent first, then never-used alphabetically)
+        // 2. Fuzzy match score plus frecency bonus (higher is better) - only when query is not empty
+        // 3. Frecency (frequent and recent first, then never-used alphabetically)
         let has_query = !query.is_empty();
-        suggestions.sort_by(|(a, a_hist, a_score), (b, b_hist, b_score)| {
+        suggestions.sort_by(|(a, a_score), (b, b_score)| {
             // First sort by disabled status
             match a.disabled.cmp(&b.disabled) {
                 std::cmp::Ordering::Equal => {}
                 other => return other,
             }
 
-            // When there's a query, sort by fuzzy score (higher is better)
+            // When there's a query, sort by ranked score (higher is better)
             if has_query {
                 match b_score.cmp(a_score) {
                     std::cmp::Ordering::Equal => {}
@@ -283,17 +283,14 @@ impl CommandRegistry {
                 }
             }
 
-            // Then sort by history position (lower = more recent = better)
-            match (a_hist, b_hist) {
-                (Some(a_pos), Some(b_pos)) => a_pos.cmp(b_pos),
-                (Some(_), None) => std::cmp::Ordering::Less, // In history beats not in history
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => a.text.cmp(&b.text), // Alphabetical for never-used commands
-            }
+            // Then by frecency, alphabetical for never-used commands
+            self.usage
+                .compare(&a.text, &b.text)
+                .then_with(|| a.text.cmp(&b.text))
         });
 
         // Extract just the suggestions
-        suggestions.into_iter().map(|(s, _, _)| s).collect()
+        suggestions.into_iter().map(|(s, _)| s).collect()
     }
 
     /// Get count of registered plugin commands
@@ -301,9 +298,9 @@ impl CommandRegistry {
         self.plugin_commands.read().unwrap().len()
     }
 
-    /// Get count of total commands (built-in + plugin)
+    /// Get count of total commands (built-in + user-defined + plugin)
     pub fn total_command_count(&self) -> usize {
-        self.builtin_commands.len() + self.plugin_command_count()
+        self.builtin_commands.len() + self.user_commands.len() + self.plugin_command_count()
     }
 
     /// Find a command by exact name match
@@ -316,9 +313,10 @@ impl CommandRegistry {
             }
         }
 
-        // Then check built-in commands
-        self.builtin_commands
+        // Then check user-defined and built-in commands
+        self.user_commands
             .iter()
+            .chain(&self.builtin_commands)
             .find(|c| c.name == name)
             .cloned()
     }
@@ -602,16 +600,21 @@ mod tests {
         let mut registry = CommandRegistry::new();
 
         registry.record_usage("Save File");
-        assert_eq!(registry.history_position("Save File"), Some(0));
+        assert!(registry.usage().score("Save File") > 0.0);
+        assert_eq!(registry.usage().score("Open File"), 0.0);
 
         registry.record_usage("Open File");
-        assert_eq!(registry.history_position("Open File"), Some(0));
-        assert_eq!(registry.history_position("Save File"), Some(1));
+        assert_eq!(
+            registry.usage().compare("Open File", "Save File"),
+            std::cmp::Ordering::Less
+        );
 
-        // Using Save File again should move it to front
+        // Using Save File again makes it the most frecent
         registry.record_usage("Save File");
-        assert_eq!(registry.history_position("Save File"), Some(0));
-        assert_eq!(registry.history_position("Open File"), Some(1));
+        assert_eq!(
+            registry.usage().compare("Save File", "Open File"),
+            std::cmp::Ordering::Less
+        );
     }
 
     #[test]
@@ -653,25 +656,33 @@ mod tests {
     }
 
     #[test]
-    fn test_history_max_size() {
-        let mut registry = CommandRegistry::new();
-
-        // Add more than MAX_HISTORY_SIZE commands
-        for i in 0..60 {
-            registry.record_usage(&format!("Command {}", i));
-        }
+    fn test_frequent_command_ranks_first_for_query() {
+        use crate::config::Config;
+        use crate::input::keybindings::KeybindingResolver;
 
-        // Should be trimmed to MAX_HISTORY_SIZE
-        assert_eq!(
-            registry.command_history.len(),
-            CommandRegistry::MAX_HISTORY_SIZE
-        );
+        crate::i18n::set_locale("en");
+        let mut registry = CommandRegistry::new();
+        let config = Config::default();
+        let keybindings = KeybindingResolver::new(&config);
+        let empty_contexts = std::collections::HashSet::new();
 
-        // Most recent should still be at front
-        assert_eq!(registry.history_position("Command 59"), Some(0));
+        let filter = |registry: &CommandRegistry| {
+            registry.filter(
+                "save",
+                KeyContext::Normal,
+                &keybindings,
+                false,
+                &empty_contexts,
+                None,
+            )
+        };
+        assert_ne!(filter(&registry)[0].text, "Save File As");
 
-        // Oldest should be trimmed
-        assert_eq!(registry.history_position("Command 0"), None);
+        // A command used often outranks equally good matches
+        for _ in 0..5 {
+            registry.record_usage("Save File As");
+        }
+        assert_eq!(filter(&registry)[0].text, "Save File As");
     }
 
     #[test]
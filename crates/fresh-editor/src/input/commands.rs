@@ -11,6 +11,8 @@ pub enum CommandSource {
     Builtin,
     /// Command registered by a plugin (contains plugin filename without extension)
     Plugin(String),
+    /// User-defined command from the `commands` config section
+    Config,
 }
 
 /// A command that can be executed from the command palette
@@ -176,6 +178,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.open_file_under_cursor",
+        desc_key: "cmd.open_file_under_cursor_desc",
+        action: || Action::OpenFileUnderCursor,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_file_under_cursor_in_split",
+        desc_key: "cmd.open_file_under_cursor_in_split_desc",
+        action: || Action::OpenFileUnderCursorInSplit,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_link",
+        desc_key: "cmd.open_link_desc",
+        action: || Action::OpenLink,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.switch_project",
         desc_key: "cmd.switch_project_desc",
@@ -282,6 +305,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.history_scrubber",
+        desc_key: "cmd.history_scrubber_desc",
+        action: || Action::HistoryScrubber,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.copy",
         desc_key: "cmd.copy_desc",
@@ -366,6 +396,34 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.increment",
+        desc_key: "cmd.increment_desc",
+        action: || Action::Increment(1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.decrement",
+        desc_key: "cmd.decrement_desc",
+        action: || Action::Increment(-1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.increment_sequence",
+        desc_key: "cmd.increment_sequence_desc",
+        action: || Action::IncrementSequence(1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.decrement_sequence",
+        desc_key: "cmd.decrement_sequence_desc",
+        action: || Action::IncrementSequence(-1),
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.open_line",
         desc_key: "cmd.open_line_desc",
@@ -423,6 +481,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.select_element",
+        desc_key: "cmd.select_element_desc",
+        action: || Action::SelectElement,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.insert_unicode_character",
+        desc_key: "cmd.insert_unicode_character_desc",
+        action: || Action::InsertUnicodeCharacter,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.inspect_character",
+        desc_key: "cmd.inspect_character_desc",
+        action: || Action::InspectCharacter,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Multi-cursor
     CommandDef {
         name_key: "cmd.add_cursor_above",
@@ -538,6 +617,181 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.focus_split_left",
+        desc_key: "cmd.focus_split_left_desc",
+        action: || Action::FocusSplitLeft,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.focus_split_right",
+        desc_key: "cmd.focus_split_right_desc",
+        action: || Action::FocusSplitRight,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.focus_split_up",
+        desc_key: "cmd.focus_split_up_desc",
+        action: || Action::FocusSplitUp,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.focus_split_down",
+        desc_key: "cmd.focus_split_down_desc",
+        action: || Action::FocusSplitDown,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rotate_splits",
+        desc_key: "cmd.rotate_splits_desc",
+        action: || Action::RotateSplits,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.close_other_splits",
+        desc_key: "cmd.close_other_splits_desc",
+        action: || Action::CloseOtherSplits,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.equalize_splits",
+        desc_key: "cmd.equalize_splits_desc",
+        action: || Action::EqualizeSplits,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.save_layout",
+        desc_key: "cmd.save_layout_desc",
+        action: || Action::SaveLayout,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.restore_layout",
+        desc_key: "cmd.restore_layout_desc",
+        action: || Action::RestoreLayout,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.delete_layout",
+        desc_key: "cmd.delete_layout_desc",
+        action: || Action::DeleteLayout,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_diff_head",
+        desc_key: "cmd.git_diff_head_desc",
+        action: || Action::GitDiffHead,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_diff_index",
+        desc_key: "cmd.git_diff_index_desc",
+        action: || Action::GitDiffIndex,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_diff_revision",
+        desc_key: "cmd.git_diff_revision_desc",
+        action: || Action::GitDiffRevision,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_checkout_branch",
+        desc_key: "cmd.git_checkout_branch_desc",
+        action: || Action::GitCheckoutBranch,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_fetch",
+        desc_key: "cmd.git_fetch_desc",
+        action: || Action::GitFetch,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_pull",
+        desc_key: "cmd.git_pull_desc",
+        action: || Action::GitPull,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_push",
+        desc_key: "cmd.git_push_desc",
+        action: || Action::GitPush,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.git_log",
+        desc_key: "cmd.git_log_desc",
+        action: || Action::GitLog,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_next_hunk",
+        desc_key: "cmd.diff_next_hunk_desc",
+        action: || Action::DiffNextHunk,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.diff_prev_hunk",
+        desc_key: "cmd.diff_prev_hunk_desc",
+        action: || Action::DiffPrevHunk,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_unsaved_changes",
+        desc_key: "cmd.show_unsaved_changes_desc",
+        action: || Action::ShowUnsavedChanges,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_unsaved_changes_unified",
+        desc_key: "cmd.show_unsaved_changes_unified_desc",
+        action: || Action::ShowUnsavedChangesUnified,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.revert_hunk",
+        desc_key: "cmd.revert_hunk_desc",
+        action: || Action::RevertHunk,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.local_history",
+        desc_key: "cmd.local_history_desc",
+        action: || Action::LocalHistory,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.restore_snapshot",
+        desc_key: "cmd.restore_snapshot_desc",
+        action: || Action::RestoreSnapshot,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // View toggles
     CommandDef {
         name_key: "cmd.toggle_line_numbers",
@@ -546,6 +800,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_read_only",
+        desc_key: "cmd.toggle_read_only_desc",
+        action: || Action::ToggleReadOnly,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_scroll_sync",
         desc_key: "cmd.toggle_scroll_sync_desc",
@@ -560,6 +821,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.debug_toggle_perf_hud",
+        desc_key: "cmd.debug_toggle_perf_hud_desc",
+        action: || Action::TogglePerfHud,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Rulers
     CommandDef {
         name_key: "cmd.add_ruler",
@@ -618,6 +886,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.convert_indentation_to_spaces",
+        desc_key: "cmd.convert_indentation_to_spaces_desc",
+        action: || Action::ConvertIndentationToSpaces,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.convert_indentation_to_tabs",
+        desc_key: "cmd.convert_indentation_to_tabs_desc",
+        action: || Action::ConvertIndentationToTabs,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_tab_indicators",
         desc_key: "cmd.toggle_tab_indicators_desc",
@@ -752,6 +1034,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[FileExplorer],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.explorer_move",
+        desc_key: "cmd.explorer_move_desc",
+        action: || Action::FileExplorerMove,
+        contexts: &[FileExplorer],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.explorer_copy",
+        desc_key: "cmd.explorer_copy_desc",
+        action: || Action::FileExplorerCopy,
+        contexts: &[FileExplorer],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.explorer_toggle_mark",
+        desc_key: "cmd.explorer_toggle_mark_desc",
+        action: || Action::FileExplorerToggleMark,
+        contexts: &[FileExplorer],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.toggle_hidden_files",
         desc_key: "cmd.toggle_hidden_files_desc",
@@ -766,6 +1069,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[FileExplorer],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_changed_files_only",
+        desc_key: "cmd.toggle_changed_files_only_desc",
+        action: || Action::FileExplorerToggleChangedOnly,
+        contexts: &[FileExplorer],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.explorer_filter",
+        desc_key: "cmd.explorer_filter_desc",
+        action: || Action::FileExplorerFilter,
+        contexts: &[FileExplorer],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.reveal_in_file_explorer",
+        desc_key: "cmd.reveal_in_file_explorer_desc",
+        action: || Action::RevealInFileExplorer,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
     // View
     CommandDef {
         name_key: "cmd.toggle_line_wrap",
@@ -845,6 +1169,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.project_search",
+        desc_key: "cmd.project_search_desc",
+        action: || Action::ProjectSearch,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Navigation
     CommandDef {
         name_key: "cmd.goto_line",
@@ -874,6 +1205,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.goto_type_definition",
+        desc_key: "cmd.goto_type_definition_desc",
+        action: || Action::LspGotoTypeDefinition,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_implementation",
+        desc_key: "cmd.goto_implementation_desc",
+        action: || Action::LspGotoImplementation,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_hover_info",
         desc_key: "cmd.show_hover_info_desc",
@@ -902,6 +1247,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.format_document",
+        desc_key: "cmd.format_document_desc",
+        action: || Action::LspFormatDocument,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.format_selection",
+        desc_key: "cmd.format_selection_desc",
+        action: || Action::LspFormatRange,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.start_restart_lsp",
         desc_key: "cmd.start_restart_lsp_desc",
@@ -966,6 +1325,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.goto_matching_tag",
+        desc_key: "cmd.goto_matching_tag_desc",
+        action: || Action::GoToMatchingTag,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Error navigation
     CommandDef {
         name_key: "cmd.jump_to_next_error",
@@ -981,6 +1347,20 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.next_diagnostic",
+        desc_key: "cmd.next_diagnostic_desc",
+        action: || Action::NextDiagnostic,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.prev_diagnostic",
+        desc_key: "cmd.prev_diagnostic_desc",
+        action: || Action::PrevDiagnostic,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // LSP
     CommandDef {
         name_key: "cmd.rename_symbol",
@@ -1032,6 +1412,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.repeat_last_edit",
+        desc_key: "cmd.repeat_last_edit_desc",
+        action: || Action::RepeatLastEdit,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_bookmark",
         desc_key: "cmd.set_bookmark_desc",
@@ -1054,6 +1441,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.action_console",
+        desc_key: "cmd.action_console_desc",
+        action: || Action::ActionConsole,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_keyboard_shortcuts",
         desc_key: "cmd.show_keyboard_shortcuts_desc",
@@ -1061,6 +1455,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_tutor",
+        desc_key: "cmd.show_tutor_desc",
+        action: || Action::ShowTutor,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_warnings",
         desc_key: "cmd.show_warnings_desc",
@@ -1075,6 +1476,146 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_lsp_info",
+        desc_key: "cmd.show_lsp_info_desc",
+        action: || Action::ShowLspInfo,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_buffers",
+        desc_key: "cmd.show_buffers_desc",
+        action: || Action::ShowBuffers,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_start",
+        desc_key: "cmd.debug_start_desc",
+        action: || Action::DebugStart,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_stop",
+        desc_key: "cmd.debug_stop_desc",
+        action: || Action::DebugStop,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_continue",
+        desc_key: "cmd.debug_continue_desc",
+        action: || Action::DebugContinue,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_over",
+        desc_key: "cmd.debug_step_over_desc",
+        action: || Action::DebugStepOver,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_into",
+        desc_key: "cmd.debug_step_into_desc",
+        action: || Action::DebugStepInto,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_out",
+        desc_key: "cmd.debug_step_out_desc",
+        action: || Action::DebugStepOut,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_toggle_breakpoint",
+        desc_key: "cmd.debug_toggle_breakpoint_desc",
+        action: || Action::DebugToggleBreakpoint,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_debug_variables",
+        desc_key: "cmd.show_debug_variables_desc",
+        action: || Action::ShowDebugVariables,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_debug_console",
+        desc_key: "cmd.show_debug_console_desc",
+        action: || Action::ShowDebugConsole,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_plugin_console",
+        desc_key: "cmd.show_plugin_console_desc",
+        action: || Action::ShowPluginConsole,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_log",
+        desc_key: "cmd.show_log_desc",
+        action: || Action::ShowLog,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.check_health",
+        desc_key: "cmd.check_health_desc",
+        action: || Action::CheckHealth,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_keybinding_conflicts",
+        desc_key: "cmd.show_keybinding_conflicts_desc",
+        action: || Action::ShowKeybindingConflicts,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_add_watch",
+        desc_key: "cmd.debug_add_watch_desc",
+        action: || Action::DebugAddWatch,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_evaluate",
+        desc_key: "cmd.debug_evaluate_desc",
+        action: || Action::DebugEvaluate,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.collab_host",
+        desc_key: "cmd.collab_host_desc",
+        action: || Action::CollabHost,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.collab_join",
+        desc_key: "cmd.collab_join_desc",
+        action: || Action::CollabJoin,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.collab_leave",
+        desc_key: "cmd.collab_leave_desc",
+        action: || Action::CollabLeave,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.clear_warnings",
         desc_key: "cmd.clear_warnings_desc",
@@ -1182,6 +1723,62 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_terminal_panel",
+        desc_key: "cmd.toggle_terminal_panel_desc",
+        action: || Action::ToggleTerminalPanel,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.new_panel_terminal",
+        desc_key: "cmd.new_panel_terminal_desc",
+        action: || Action::NewPanelTerminal,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.rename_terminal",
+        desc_key: "cmd.rename_terminal_desc",
+        action: || Action::RenameTerminal,
+        contexts: &[Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_previous_command",
+        desc_key: "cmd.terminal_previous_command_desc",
+        action: || Action::TerminalPreviousCommand,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_next_command",
+        desc_key: "cmd.terminal_next_command_desc",
+        action: || Action::TerminalNextCommand,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.open_terminal_output_path",
+        desc_key: "cmd.open_terminal_output_path_desc",
+        action: || Action::OpenTerminalOutputPath,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.send_to_repl",
+        desc_key: "cmd.send_to_repl_desc",
+        action: || Action::SendToRepl,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.send_block_to_repl",
+        desc_key: "cmd.send_block_to_repl_desc",
+        action: || Action::SendBlockToRepl,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Shell command operations
     CommandDef {
         name_key: "cmd.shell_command",
@@ -1197,6 +1794,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.global_command",
+        desc_key: "cmd.global_command_desc",
+        action: || Action::GlobalCommand,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
 ];
 
 /// Get all available commands for the command palette
@@ -0,0 +1,245 @@
+//! Frecency tracking for pickers
+//!
+//! Remembers how often and how recently entries (commands, files, picker
+//! values) were chosen, so that frequently used entries rank first. The data
+//! is saved in Fresh's data directory and kept across sessions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Usage of a single entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FrecencyEntry {
+    /// How many times the entry was chosen
+    count: u32,
+    /// When it was last chosen, in milliseconds since the Unix epoch
+    last_used: u64,
+}
+
+/// How often and how recently entries were chosen
+#[derive(Debug, Clone, Default)]
+pub struct Frecency {
+    entries: HashMap<String, FrecencyEntry>,
+}
+
+impl Frecency {
+    /// Maximum number of entries kept; the lowest-ranked ones are dropped
+    const MAX_ENTRIES: usize = 1000;
+
+    /// Frecency score giving the largest match bonus
+    const MAX_BONUS_SCORE: f64 = 1000.0;
+
+    /// Largest bonus added to a fuzzy match score
+    const MAX_BONUS: i32 = 50;
+
+    /// Create an empty frecency store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that an entry was chosen
+    pub fn record(&mut self, key: &str) {
+        self.record_at(key, now_millis());
+    }
+
+    fn record_at(&mut self, key: &str, now: u64) {
+        // Keep recording times strictly increasing, so entries chosen within the
+        // same millisecond still rank by the order they were chosen in
+        let latest = self.entries.values().map(|e| e.last_used).max();
+        let now = match latest {
+            Some(latest) if latest >= now => latest + 1,
+            _ => now,
+        };
+        let entry = self
+            .entries
+            .entry(key.to_string())
+            .or_insert(FrecencyEntry {
+                count: 0,
+                last_used: now,
+            });
+        entry.count = entry.count.saturating_add(1);
+        entry.last_used = now;
+        self.trim(now);
+    }
+
+    /// Drop the lowest-ranked entries beyond `MAX_ENTRIES`
+    fn trim(&mut self, now: u64) {
+        if self.entries.len() <= Self::MAX_ENTRIES {
+            return;
+        }
+        let mut ranked: Vec<(String, f64, u64)> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry_score(entry, now), entry.last_used))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| b.2.cmp(&a.2)));
+        for (key, _, _) in ranked.into_iter().skip(Self::MAX_ENTRIES) {
+            self.entries.remove(&key);
+        }
+    }
+
+    /// Frecency score of an entry: its use count weighted by how recently it
+    /// was last used. Entries never chosen score 0.
+    pub fn score(&self, key: &str) -> f64 {
+        self.score_at(key, now_millis())
+    }
+
+    fn score_at(&self, key: &str, now: u64) -> f64 {
+        self.entries
+            .get(key)
+            .map(|entry| entry_score(entry, now))
+            .unwrap_or(0.0)
+    }
+
+    /// Bonus added to the fuzzy match score of an entry
+    pub fn bonus(&self, key: &str) -> i32 {
+        let score = self.score(key).min(Self::MAX_BONUS_SCORE);
+        (score / Self::MAX_BONUS_SCORE * Self::MAX_BONUS as f64).ceil() as i32
+    }
+
+    /// When an entry was last chosen (milliseconds since the Unix epoch)
+    pub fn last_used(&self, key: &str) -> Option<u64> {
+        self.entries.get(key).map(|entry| entry.last_used)
+    }
+
+    /// Order two entries best first: higher frecency, then more recently used
+    pub fn compare(&self, a: &str, b: &str) -> std::cmp::Ordering {
+        let now = now_millis();
+        self.score_at(b, now)
+            .total_cmp(&self.score_at(a, now))
+            .then_with(|| self.last_used(b).cmp(&self.last_used(a)))
+    }
+
+    /// Whether no entry was ever chosen
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Save the usage data to a file
+    pub fn save_to_file(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.entries).map_err(std::io::Error::other)?;
+
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load usage data from a file
+    pub fn load_from_file(path: &std::path::Path) -> std::io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let json = std::fs::read_to_string(path)?;
+        let entries: HashMap<String, FrecencyEntry> =
+            serde_json::from_str(&json).map_err(std::io::Error::other)?;
+
+        let mut frecency = Self { entries };
+        frecency.trim(now_millis());
+        Ok(frecency)
+    }
+}
+
+/// Use count weighted by recency, in the style of Firefox's frecency buckets
+fn entry_score(entry: &FrecencyEntry, now: u64) -> f64 {
+    let hours_since_use = now.saturating_sub(entry.last_used) as f64 / 3_600_000.0;
+    let recency_weight = if hours_since_use < 4.0 {
+        100.0
+    } else if hours_since_use < 24.0 {
+        70.0
+    } else if hours_since_use < 24.0 * 7.0 {
+        50.0
+    } else if hours_since_use < 24.0 * 30.0 {
+        30.0
+    } else if hours_since_use < 24.0 * 90.0 {
+        10.0
+    } else {
+        1.0
+    };
+    entry.count as f64 * recency_weight
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    #[test]
+    fn test_frequent_entries_score_higher() {
+        let mut frecency = Frecency::new();
+        frecency.record_at("once", 10 * HOUR);
+        frecency.record_at("twice", 10 * HOUR);
+        frecency.record_at("twice", 10 * HOUR);
+
+        let now = 11 * HOUR;
+        assert!(frecency.score_at("twice", now) > frecency.score_at("once", now));
+        assert_eq!(frecency.score_at("never", now), 0.0);
+    }
+
+    #[test]
+    fn test_old_uses_decay() {
+        let mut frecency = Frecency::new();
+        frecency.record_at("old", 0);
+        frecency.record_at("old", 0);
+        frecency.record_at("recent", 100 * 24 * HOUR);
+
+        let now = 100 * 24 * HOUR + HOUR;
+        assert!(frecency.score_at("recent", now) > frecency.score_at("old", now));
+    }
+
+    #[test]
+    fn test_same_time_uses_keep_their_order() {
+        let mut frecency = Frecency::new();
+        frecency.record_at("first", HOUR);
+        frecency.record_at("second", HOUR);
+
+        assert!(frecency.last_used("second") > frecency.last_used("first"));
+        assert_eq!(
+            frecency.compare("second", "first"),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_entries_are_capped() {
+        let mut frecency = Frecency::new();
+        for i in 0..Frecency::MAX_ENTRIES + 10 {
+            frecency.record_at(&format!("entry {}", i), HOUR);
+        }
+
+        // The oldest entries are dropped
+        assert_eq!(frecency.entries.len(), Frecency::MAX_ENTRIES);
+        assert!(frecency
+            .last_used(&format!("entry {}", Frecency::MAX_ENTRIES + 9))
+            .is_some());
+        assert!(frecency.last_used("entry 0").is_none());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("frecency.json");
+
+        let mut frecency = Frecency::new();
+        frecency.record("Save File");
+        frecency.record("Save File");
+        frecency.save_to_file(&path).unwrap();
+
+        let loaded = Frecency::load_from_file(&path).unwrap();
+        assert_eq!(loaded.entries, frecency.entries);
+        assert!(loaded.bonus("Save File") > 0);
+        assert_eq!(loaded.bonus("Open File"), 0);
+    }
+}
@@ -4,6 +4,8 @@
 //! in order in the target string, but not necessarily consecutively.
 //! Matching is case-insensitive.
 
+use crate::input::frecency::Frecency;
+
 /// Score bonus constants for match quality ranking
 mod score {
     /// Bonus for consecutive character matches
@@ -377,6 +379,41 @@ where
     results
 }
 
+/// Filter a list of items using fuzzy matching, ranking by match quality plus frecency
+///
+/// `match_item` fuzzy-matches an item against the query and `get_key` names it in
+/// `frecency`. Frequently and recently chosen items get a bonus on top of their
+/// match score; with an empty query every item matches and frecency alone decides
+/// the order. Ties keep the items' original order.
+pub fn fuzzy_filter_frecent<T, M, K, S>(
+    items: &[T],
+    match_item: M,
+    get_key: K,
+    frecency: &Frecency,
+) -> Vec<(usize, FuzzyMatch)>
+where
+    M: Fn(&T) -> FuzzyMatch,
+    K: Fn(&T) -> S,
+    S: AsRef<str>,
+{
+    let mut results: Vec<(usize, FuzzyMatch, i32, Option<u64>)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| {
+            let m = match_item(item);
+            m.matched.then(|| {
+                let key = get_key(item);
+                let score = m.score + frecency.bonus(key.as_ref());
+                (idx, m, score, frecency.last_used(key.as_ref()))
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| b.3.cmp(&a.3)));
+
+    results.into_iter().map(|(idx, m, _, _)| (idx, m)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -654,4 +691,31 @@ mod tests {
             partial.score
         );
     }
+
+    #[test]
+    fn test_fuzzy_filter_frecent_boosts_used_items() {
+        let items = ["Open File", "Open Folder", "Open Recent"];
+        let mut frecency = Frecency::new();
+        frecency.record("Open Recent");
+
+        let results = fuzzy_filter_frecent(&items, |s| fuzzy_match("open", s), |s| *s, &frecency);
+        assert_eq!(results.len(), 3);
+        assert_eq!(items[results[0].0], "Open Recent");
+    }
+
+    #[test]
+    fn test_fuzzy_filter_frecent_empty_query_orders_by_use() {
+        let items = ["alpha", "beta", "gamma"];
+        let mut frecency = Frecency::new();
+        frecency.record("gamma");
+        frecency.record("beta");
+
+        let order: Vec<&str> =
+            fuzzy_filter_frecent(&items, |s| fuzzy_match("", s), |s| *s, &frecency)
+                .into_iter()
+                .map(|(idx, _)| items[idx])
+                .collect();
+        // Most recently used first, unused items keep their order
+        assert_eq!(order, vec!["beta", "gamma", "alpha"]);
+    }
 }
@@ -141,6 +141,8 @@ pub enum DeferredAction {
     UpdatePromptSuggestions,
     PromptHistoryPrev,
     PromptHistoryNext,
+    PromptHistorySearchPrev,
+    PromptHistorySearchNext,
     /// Preview theme from the current prompt input (for SelectTheme)
     PreviewThemeFromPrompt,
     /// Notify plugin that prompt selection changed (for live preview in Live Grep, etc.)
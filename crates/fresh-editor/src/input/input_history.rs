@@ -248,6 +248,70 @@ impl InputHistory {
         }
     }
 
+    /// Search backwards for an item containing the original input (Ctrl+P)
+    ///
+    /// Like `navigate_prev`, but only stops at items that contain the text
+    /// that was in the prompt when navigation started (case-insensitive).
+    /// An empty input matches every item.
+    ///
+    /// # Returns
+    /// * `Some(String)` - The previous matching item
+    /// * `None` - No older item matches
+    ///
+    /// # Example
+    /// ```
+    /// # use fresh::input_history::InputHistory;
+    /// let mut history = InputHistory::new();
+    /// history.push("cargo build".to_string());
+    /// history.push("git status".to_string());
+    /// history.push("cargo test".to_string());
+    ///
+    /// assert_eq!(history.search_prev("cargo"), Some("cargo test".to_string()));
+    /// assert_eq!(history.search_prev("cargo test"), Some("cargo build".to_string()));
+    /// assert_eq!(history.search_prev("cargo build"), None);
+    /// ```
+    pub fn search_prev(&mut self, current_input: &str) -> Option<String> {
+        let query = match (&self.position, &self.temp_input) {
+            (None, _) => current_input.to_lowercase(),
+            (Some(_), Some(original)) => original.to_lowercase(),
+            (Some(_), None) => String::new(),
+        };
+        let end = self.position.unwrap_or(self.items.len());
+        let index = (0..end)
+            .rev()
+            .find(|&i| self.items[i].to_lowercase().contains(&query))?;
+
+        if self.position.is_none() {
+            self.temp_input = Some(current_input.to_string());
+        }
+        self.position = Some(index);
+        Some(self.items[index].clone())
+    }
+
+    /// Search forwards for an item containing the original input (Ctrl+N)
+    ///
+    /// Counterpart of `search_prev`. When no newer item matches, returns the
+    /// original input and ends navigation, like `navigate_next`.
+    pub fn search_next(&mut self) -> Option<String> {
+        let pos = self.position?;
+        let query = self
+            .temp_input
+            .as_deref()
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        match (pos + 1..self.items.len()).find(|&i| self.items[i].to_lowercase().contains(&query)) {
+            Some(index) => {
+                self.position = Some(index);
+                Some(self.items[index].clone())
+            }
+            None => {
+                let original = self.temp_input.clone();
+                self.reset_navigation();
+                original
+            }
+        }
+    }
+
     /// Reset navigation state
     ///
     /// Call this when:
@@ -618,6 +682,50 @@ mod tests {
         assert_eq!(history.navigate_next(), None); // Not navigating anymore
     }
 
+    #[test]
+    fn test_search_filters_by_original_input() {
+        let mut history = InputHistory::new();
+        history.push("fn main".to_string());
+        history.push("TODO".to_string());
+        history.push("fn helper".to_string());
+        history.push("struct Foo".to_string());
+
+        // The filter is the text typed before searching, not the recalled entry
+        assert_eq!(history.search_prev("FN"), Some("fn helper".to_string()));
+        assert_eq!(
+            history.search_prev("fn helper"),
+            Some("fn main".to_string())
+        );
+        assert_eq!(history.search_prev("fn main"), None);
+
+        assert_eq!(history.search_next(), Some("fn helper".to_string()));
+        assert_eq!(history.search_next(), Some("FN".to_string())); // Back to original
+        assert_eq!(history.search_next(), None);
+    }
+
+    #[test]
+    fn test_search_without_match_keeps_input() {
+        let mut history = InputHistory::new();
+        history.push("alpha".to_string());
+
+        assert_eq!(history.search_prev("zzz"), None);
+        assert!(history.position.is_none());
+        assert!(history.temp_input.is_none());
+
+        // Empty input matches everything
+        assert_eq!(history.search_prev(""), Some("alpha".to_string()));
+    }
+
+    #[test]
+    fn test_search_after_init_at_last_matches_all() {
+        let mut history = InputHistory::new();
+        history.push("first".to_string());
+        history.push("second".to_string());
+        history.init_at_last();
+
+        assert_eq!(history.search_prev("second"), Some("first".to_string()));
+    }
+
     #[test]
     #[should_panic(expected = "History max_size must be greater than 0")]
     fn test_zero_capacity_panics() {
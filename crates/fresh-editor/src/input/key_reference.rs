@@ -0,0 +1,465 @@
+//! Key reference generated from the live keybinding tables
+//!
+//! Used by the keyboard shortcuts page and `fresh --dump-keys`. The reference
+//! is built from the resolver (keymap plus user remaps, chords included) and
+//! the buffer mode registry (built-in and plugin-defined modes), so it shows
+//! what a key actually does rather than a hardcoded list.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use rust_i18n::t;
+
+use crate::input::buffer_mode::ModeRegistry;
+use crate::input::keybindings::{format_keybinding, Action, KeyContext, KeybindingResolver};
+
+/// One binding in the reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEntry {
+    /// Formatted key sequence, e.g. "Ctrl+K Ctrl+C"
+    pub keys: String,
+    /// Action name as used in the config (empty if the action has none)
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+}
+
+/// A titled group of bindings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySection {
+    pub title: String,
+    pub entries: Vec<KeyEntry>,
+}
+
+/// The complete key reference, in display order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyReference {
+    pub sections: Vec<KeySection>,
+}
+
+/// Categories of editor-wide bindings, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Category {
+    Files,
+    Editing,
+    Navigation,
+    Selection,
+    Search,
+    Code,
+    View,
+    Plugins,
+}
+
+impl Category {
+    const ALL: [Category; 8] = [
+        Category::Files,
+        Category::Editing,
+        Category::Navigation,
+        Category::Selection,
+        Category::Search,
+        Category::Code,
+        Category::View,
+        Category::Plugins,
+    ];
+
+    /// Categorize an action by its config name
+    fn of(name: &str) -> Self {
+        let starts = |prefixes: &[&str]| prefixes.iter().any(|p| name.starts_with(p));
+        if starts(&["select_theme", "select_keybinding_map", "select_locale"]) {
+            Category::View
+        } else if starts(&[
+            "lsp_",
+            "debug_",
+            "git_",
+            "diff_",
+            "format_buffer",
+            "toggle_comment",
+        ]) || name.ends_with("_diagnostic")
+            || name.ends_with("_to_repl")
+            || name.ends_with("_error")
+        {
+            Category::Code
+        } else if starts(&[
+            "toggle_search_",
+            "search",
+            "find_",
+            "replace",
+            "query_replace",
+        ]) {
+            Category::Search
+        } else if starts(&[
+            "select_",
+            "block_select_",
+            "expand_selection",
+            "add_cursor_",
+            "remove_secondary_cursors",
+            "set_mark",
+        ]) {
+            Category::Selection
+        } else if starts(&[
+            "move_",
+            "goto_",
+            "jump_",
+            "scroll_",
+            "navigate_",
+            "smart_home",
+            "recenter",
+            "terminal_previous_command",
+            "terminal_next_command",
+        ]) {
+            Category::Navigation
+        } else if starts(&[
+            "save",
+            "open",
+            "new",
+            "close",
+            "quit",
+            "force_quit",
+            "detach",
+            "revert",
+            "switch_project",
+            "quick_open",
+            "toggle_auto_revert",
+        ]) && !starts(&[
+            "open_line",
+            "open_terminal",
+            "new_panel_terminal",
+            "open_settings",
+            "open_keybinding",
+        ]) {
+            Category::Files
+        } else if starts(&[
+            "toggle_",
+            "split_",
+            "focus_",
+            "next_",
+            "prev_",
+            "show_",
+            "menu_",
+            "command_palette",
+            "keyboard_shortcuts",
+            "increase_split",
+            "decrease_split",
+            "rotate_splits",
+            "equalize_splits",
+            "open_terminal",
+            "new_panel_terminal",
+            "rename_terminal",
+            "open_settings",
+            "open_keybinding",
+        ]) || name.ends_with("_layout")
+            || name.ends_with("_split")
+            || name.ends_with("_splits")
+        {
+            Category::View
+        } else {
+            Category::Editing
+        }
+    }
+
+    fn title(self) -> String {
+        match self {
+            Category::Files => t!("keys.category.files"),
+            Category::Editing => t!("keys.category.editing"),
+            Category::Navigation => t!("keys.category.navigation"),
+            Category::Selection => t!("keys.category.selection"),
+            Category::Search => t!("keys.category.search"),
+            Category::Code => t!("keys.category.code"),
+            Category::View => t!("keys.category.view"),
+            Category::Plugins => t!("keys.category.plugins"),
+        }
+        .to_string()
+    }
+}
+
+/// Contexts listed after the editor-wide categories, with their titles
+fn context_sections() -> [(KeyContext, String); 6] {
+    [
+        (KeyContext::Prompt, t!("keys.context.prompt").to_string()),
+        (KeyContext::Popup, t!("keys.context.popup").to_string()),
+        (
+            KeyContext::FileExplorer,
+            t!("keys.context.file_explorer").to_string(),
+        ),
+        (KeyContext::Menu, t!("keys.context.menu").to_string()),
+        (
+            KeyContext::Terminal,
+            t!("keys.context.terminal").to_string(),
+        ),
+        (
+            KeyContext::Settings,
+            t!("keys.context.settings").to_string(),
+        ),
+    ]
+}
+
+/// Format a key sequence, e.g. "Ctrl+K Ctrl+C"
+pub fn format_keys(keys: &[(KeyCode, KeyModifiers)]) -> String {
+    keys.iter()
+        .map(|(code, modifiers)| format_keybinding(code, modifiers))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn action_entry(keys: &[(KeyCode, KeyModifiers)], action: &Action) -> KeyEntry {
+    let name = match action {
+        Action::PluginAction(name) => name.as_str(),
+        _ => action.name().unwrap_or_default(),
+    };
+    KeyEntry {
+        keys: format_keys(keys),
+        name: name.to_string(),
+        description: KeybindingResolver::format_action(action),
+    }
+}
+
+fn command_entry(keys: &[(KeyCode, KeyModifiers)], command: &str) -> KeyEntry {
+    KeyEntry {
+        keys: format_keys(keys),
+        name: command.to_string(),
+        description: KeybindingResolver::format_action_from_str(command),
+    }
+}
+
+/// Add a section, sorted by description; keys that cannot be displayed
+/// are left out and empty sections are skipped
+fn push_section(sections: &mut Vec<KeySection>, title: String, mut entries: Vec<KeyEntry>) {
+    entries.retain(|e| !e.keys.is_empty());
+    if entries.is_empty() {
+        return;
+    }
+    entries.sort_by(|a, b| {
+        a.description
+            .cmp(&b.description)
+            .then_with(|| a.keys.cmp(&b.keys))
+    });
+    sections.push(KeySection { title, entries });
+}
+
+impl KeyReference {
+    /// Build the reference from the current bindings
+    pub fn build(resolver: &KeybindingResolver, modes: &ModeRegistry) -> Self {
+        let mut sections = Vec::new();
+
+        // Editor-wide bindings, grouped by what they do
+        let mut categories: HashMap<Category, Vec<KeyEntry>> = HashMap::new();
+        for context in [KeyContext::Global, KeyContext::Normal] {
+            for (keys, action) in resolver.effective_bindings(context) {
+                let category = match &action {
+                    Action::None => continue,
+                    Action::PluginAction(_) => Category::Plugins,
+                    _ => Category::of(action.name().unwrap_or_default()),
+                };
+                categories
+                    .entry(category)
+                    .or_default()
+                    .push(action_entry(&keys, &action));
+            }
+        }
+        for category in Category::ALL {
+            if let Some(entries) = categories.remove(&category) {
+                push_section(&mut sections, category.title(), entries);
+            }
+        }
+
+        for (context, title) in context_sections() {
+            let entries: Vec<KeyEntry> = resolver
+                .effective_bindings(context)
+                .iter()
+                .filter(|(_, action)| *action != Action::None)
+                .map(|(keys, action)| action_entry(keys, action))
+                .collect();
+            push_section(&mut sections, title, entries);
+        }
+
+        // Plugin bindings limited to a plugin-defined context
+        let mut custom: BTreeMap<&str, Vec<KeyEntry>> = BTreeMap::new();
+        for binding in resolver.plugin_bindings() {
+            if let Some(context) = &binding.custom_context {
+                custom
+                    .entry(context)
+                    .or_default()
+                    .push(action_entry(&binding.keys, &binding.action));
+            }
+        }
+        for (context, entries) in custom {
+            push_section(
+                &mut sections,
+                t!("keys.custom_context", context = context).to_string(),
+                entries,
+            );
+        }
+
+        // Buffer modes, built-in and plugin-defined; inherited keys are listed
+        // under the parent mode
+        let mut mode_names = modes.list_modes();
+        mode_names.sort();
+        for mode_name in mode_names {
+            let Some(mode) = modes.get(&mode_name) else {
+                continue;
+            };
+            let entries: Vec<KeyEntry> = mode
+                .keybindings
+                .iter()
+                .map(|(key, command)| command_entry(&[*key], command))
+                .chain(
+                    mode.chord_keybindings
+                        .iter()
+                        .map(|(keys, command)| command_entry(keys, command)),
+                )
+                .collect();
+            push_section(
+                &mut sections,
+                t!("keys.mode", mode = mode_name).to_string(),
+                entries,
+            );
+        }
+
+        Self { sections }
+    }
+
+    /// Keep only bindings whose keys, name or description contain `query`
+    /// (case-insensitive). A matching section title keeps the whole section.
+    pub fn filter(&self, query: &str) -> Self {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return self.clone();
+        }
+        let matches = |text: &str| text.to_lowercase().contains(&query);
+        let sections = self
+            .sections
+            .iter()
+            .filter_map(|section| {
+                let entries: Vec<KeyEntry> = if matches(&section.title) {
+                    section.entries.clone()
+                } else {
+                    section
+                        .entries
+                        .iter()
+                        .filter(|e| matches(&e.keys) || matches(&e.name) || matches(&e.description))
+                        .cloned()
+                        .collect()
+                };
+                (!entries.is_empty()).then(|| KeySection {
+                    title: section.title.clone(),
+                    entries,
+                })
+            })
+            .collect();
+        Self { sections }
+    }
+
+    /// Whether the reference has no bindings
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Plain text layout used by the shortcuts buffer
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for section in &self.sections {
+            out.push_str(&format!("── {} ──\n\n", section.title));
+            for entry in &section.entries {
+                out.push_str(&format!("  {:20} {}\n", entry.keys, entry.description));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Markdown layout used by `fresh --dump-keys markdown`
+    pub fn to_markdown(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|");
+        let mut out = String::from("# Keyboard Shortcuts\n");
+        for section in &self.sections {
+            out.push_str(&format!("\n## {}\n\n", section.title));
+            out.push_str("| Keys | Action | Description |\n");
+            out.push_str("| --- | --- | --- |\n");
+            for entry in &section.entries {
+                let name = if entry.name.is_empty() {
+                    String::new()
+                } else {
+                    format!("`{}`", entry.name)
+                };
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    escape(&entry.keys),
+                    name,
+                    escape(&entry.description)
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Keybinding};
+    use crate::input::buffer_mode::BufferMode;
+
+    fn find<'a>(reference: &'a KeyReference, name: &str) -> Vec<(&'a str, &'a KeyEntry)> {
+        reference
+            .sections
+            .iter()
+            .flat_map(|s| s.entries.iter().map(move |e| (s.title.as_str(), e)))
+            .filter(|(_, e)| e.name == name)
+            .collect()
+    }
+
+    #[test]
+    fn test_reference_reflects_remaps_and_modes() {
+        let mut config = Config::default();
+        config.keybindings.push(Keybinding {
+            key: "F9".to_string(),
+            modifiers: vec![],
+            keys: vec![],
+            action: "sort_lines".to_string(),
+            args: HashMap::new(),
+            when: None,
+        });
+        let resolver = KeybindingResolver::new(&config);
+        let mut modes = ModeRegistry::new();
+        modes.register(
+            BufferMode::new("my-plugin-mode")
+                .with_parent("special")
+                .with_binding(KeyCode::Char('x'), KeyModifiers::NONE, "my_plugin_command"),
+        );
+        let reference = KeyReference::build(&resolver, &modes);
+
+        let sort = find(&reference, "sort_lines");
+        assert!(sort.iter().any(|(_, e)| e.keys == "F9"));
+
+        let save = find(&reference, "save");
+        assert!(save
+            .iter()
+            .any(|(title, _)| *title == Category::Files.title()));
+
+        let plugin = find(&reference, "my_plugin_command");
+        assert_eq!(plugin.len(), 1);
+        assert_eq!(plugin[0].1.keys, "X");
+        assert!(plugin[0].0.contains("my-plugin-mode"));
+    }
+
+    #[test]
+    fn test_filter_and_markdown() {
+        let resolver = KeybindingResolver::new(&Config::default());
+        let reference = KeyReference::build(&resolver, &ModeRegistry::new());
+        assert!(!reference.is_empty());
+
+        let filtered = reference.filter("QUICK_OPEN");
+        assert!(!filtered.is_empty());
+        assert!(filtered
+            .sections
+            .iter()
+            .flat_map(|s| &s.entries)
+            .all(|e| e.name.contains("quick_open")
+                || e.description.to_lowercase().contains("quick_open")));
+        assert!(reference.filter("no binding matches this").is_empty());
+
+        let markdown = reference.to_markdown();
+        assert!(markdown.starts_with("# Keyboard Shortcuts\n"));
+        assert!(markdown.contains("| `save` |"));
+    }
+}
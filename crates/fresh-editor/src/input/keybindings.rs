@@ -1,4 +1,6 @@
 use crate::config::Config;
+use crate::input::buffer_mode::ModeRegistry;
+use crate::primitives::text_objects::TextObject;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rust_i18n::t;
 use std::collections::HashMap;
@@ -247,6 +249,8 @@ pub enum Action {
     InsertChar(char),
     InsertNewline,
     InsertTab,
+    InsertUnicodeCharacter,
+    InspectCharacter,
 
     // Basic movement
     MoveLeft,
@@ -256,6 +260,8 @@ pub enum Action {
     MoveWordLeft,
     MoveWordRight,
     MoveWordEnd, // Move to end of current word
+    MoveSubWordLeft,
+    MoveSubWordRight,
     MoveLineStart,
     MoveLineEnd,
     MoveLineUp,
@@ -275,6 +281,8 @@ pub enum Action {
     SelectWordLeft,
     SelectWordRight,
     SelectWordEnd, // Select to end of current word
+    SelectSubWordLeft,
+    SelectSubWordRight,
     SelectLineStart,
     SelectLineEnd,
     SelectDocumentStart,
@@ -284,8 +292,23 @@ pub enum Action {
     SelectAll,
     SelectWord,
     SelectLine,
+    SelectElement,
     ExpandSelection,
 
+    // Text objects
+    SelectInsideWord,
+    SelectAroundWord,
+    SelectInsideQuotes,
+    SelectAroundQuotes,
+    SelectInsideBrackets,
+    SelectAroundBrackets,
+    SelectInsideParagraph,
+    SelectAroundParagraph,
+    SelectInsideFunction,
+    SelectAroundFunction,
+    SelectInsideArgument,
+    SelectAroundArgument,
+
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
     BlockSelectRight,
@@ -332,6 +355,9 @@ pub enum Action {
     Save,
     SaveAs,
     Open,
+    OpenFileUnderCursor,        // Open the file path under the cursor
+    OpenFileUnderCursorInSplit, // Open the file path under the cursor in a new split
+    OpenLink,                   // Open the link under the cursor in the system browser
     SwitchProject,
     New,
     Close,
@@ -348,11 +374,15 @@ pub enum Action {
     // Navigation
     GotoLine,
     GoToMatchingBracket,
+    GoToMatchingTag,
     JumpToNextError,
     JumpToPreviousError,
+    NextDiagnostic,
+    PrevDiagnostic,
 
     // Smart editing
     SmartHome,
+    SelectSmartHome,
     DedentSelection,
     ToggleComment,
 
@@ -378,6 +408,7 @@ pub enum Action {
     PromptRecordMacro,
     PromptPlayMacro,
     PlayLastMacro,
+    RepeatLastEdit,
 
     // Bookmarks (prompt-based)
     PromptSetBookmark,
@@ -386,17 +417,22 @@ pub enum Action {
     // Undo/redo
     Undo,
     Redo,
+    HistoryScrubber,
 
     // View
     ScrollUp,
     ScrollDown,
     ShowHelp,
     ShowKeyboardShortcuts,
+    ShowTutor,
     ShowWarnings,
     ShowStatusLog,
     ShowLspStatus,
+    ShowLspInfo,
+    ShowBuffers,
     ClearWarnings,
     CommandPalette, // TODO: Consider dropping this now that we have QuickOpen
+    ActionConsole,  // Run any action by name, with arguments
     /// Quick Open - unified prompt with prefix-based provider routing
     QuickOpen,
     ToggleLineWrap,
@@ -430,6 +466,33 @@ pub enum Action {
     IncreaseSplitSize,
     DecreaseSplitSize,
     ToggleMaximizeSplit,
+    FocusSplitLeft,
+    FocusSplitRight,
+    FocusSplitUp,
+    FocusSplitDown,
+    RotateSplits,
+    CloseOtherSplits,
+    EqualizeSplits,
+    SaveLayout,
+    RestoreLayout,
+    DeleteLayout,
+
+    // Git
+    GitDiffHead,
+    GitDiffIndex,
+    GitDiffRevision,
+    GitCheckoutBranch,
+    GitFetch,
+    GitPull,
+    GitPush,
+    GitLog,
+    DiffNextHunk,
+    DiffPrevHunk,
+    ShowUnsavedChanges,
+    ShowUnsavedChangesUnified,
+    RevertHunk,
+    LocalHistory,
+    RestoreSnapshot,
 
     // Prompt mode actions
     PromptConfirm,
@@ -500,30 +563,78 @@ pub enum Action {
     FileExplorerNewDirectory,
     FileExplorerDelete,
     FileExplorerRename,
+    FileExplorerToggleMark,
+    FileExplorerMarkUp,
+    FileExplorerMarkDown,
+    FileExplorerMove,
+    FileExplorerCopy,
     FileExplorerToggleHidden,
     FileExplorerToggleGitignored,
+    FileExplorerToggleChangedOnly,
+    FileExplorerFilter,
+    RevealInFileExplorer,
     FileExplorerSearchClear,
     FileExplorerSearchBackspace,
 
     // LSP operations
     LspCompletion,
     LspGotoDefinition,
+    LspGotoTypeDefinition,
+    LspGotoImplementation,
     LspReferences,
     LspRename,
     LspHover,
     LspSignatureHelp,
     LspCodeActions,
+    LspFormatDocument,
+    LspFormatRange,
     LspRestart,
     LspStop,
+    LspInfoRestart,
+    LspInfoStop,
+    LspInfoOpenLog,
+    LspLogCycleLevel,
+    BuffersPanelOpen,
+    BuffersPanelUnload,
+    FilterKeyboardShortcuts,
+    DebugStart,
+    DebugStop,
+    DebugContinue,
+    DebugStepOver,
+    DebugStepInto,
+    DebugStepOut,
+    DebugToggleBreakpoint,
+    ShowDebugVariables,
+    ShowDebugConsole,
+    ShowPluginConsole,
+    PluginConsoleCycleLevel,
+    PluginConsoleCyclePlugin,
+    ShowLog,
+    LogCycleLevel,
+    LogCycleSource,
+    CheckHealth,
+    ShowKeybindingConflicts,
+    KeybindingConflictsRebind,
+    KeybindingConflictsDisable,
+    DebugAddWatch,
+    DebugRemoveWatch,
+    DebugEvaluate,
+    DebugToggleVariable,
     LspToggleForBuffer,
     ToggleInlayHints,
     ToggleMouseHover,
 
     // View toggles
     ToggleLineNumbers,
+    ToggleReadOnly,
+    TogglePagerFollow,
+    CollabHost,
+    CollabJoin,
+    CollabLeave,
     ToggleScrollSync,
     ToggleMouseCapture,
     ToggleDebugHighlights, // Debug mode: show highlight/overlay byte ranges
+    TogglePerfHud,         // Overlay with frame timings and counters
     SetBackground,
     SetBackgroundBlend,
 
@@ -534,6 +645,8 @@ pub enum Action {
     ReloadWithEncoding,
     SetLanguage,
     ToggleIndentationStyle,
+    ConvertIndentationToSpaces,
+    ConvertIndentationToTabs,
     ToggleTabIndicators,
     ResetBufferSettings,
     AddRuler,
@@ -550,7 +663,10 @@ pub enum Action {
     FindSelectionNext,     // Quick find next occurrence of selection (Ctrl+F3)
     FindSelectionPrevious, // Quick find previous occurrence of selection (Ctrl+Shift+F3)
     Replace,
-    QueryReplace, // Interactive replace (y/n/!/q for each match)
+    QueryReplace,       // Interactive replace (y/n/!/q for each match)
+    ProjectSearch,      // Search the files of the project
+    ProjectSearchOpen,  // Open the project search result under the cursor
+    ProjectSearchRerun, // Run the project search again
 
     // Menu navigation
     MenuActivate,     // Open menu bar (Alt or F10)
@@ -568,6 +684,9 @@ pub enum Action {
     // Plugin custom actions
     PluginAction(String),
 
+    // User-defined commands from the `commands` config section
+    UserCommand(String),
+
     // Settings operations
     OpenSettings,        // Open the settings modal
     CloseSettings,       // Close the settings modal
@@ -581,22 +700,35 @@ pub enum Action {
     SettingsDecrement,   // Decrement number value or previous dropdown option
 
     // Terminal operations
-    OpenTerminal,          // Open a new terminal in the current split
-    CloseTerminal,         // Close the current terminal
-    FocusTerminal,         // Focus the terminal buffer (if viewing terminal, focus input)
-    TerminalEscape,        // Escape from terminal mode back to editor
-    ToggleKeyboardCapture, // Toggle keyboard capture mode (all keys go to terminal)
-    TerminalPaste,         // Paste clipboard contents into terminal as a single batch
+    OpenTerminal,            // Open a new terminal in the current split
+    CloseTerminal,           // Close the current terminal
+    FocusTerminal,           // Focus the terminal buffer (if viewing terminal, focus input)
+    TerminalEscape,          // Escape from terminal mode back to editor
+    ToggleKeyboardCapture,   // Toggle keyboard capture mode (all keys go to terminal)
+    TerminalPaste,           // Paste clipboard contents into terminal as a single batch
+    ToggleTerminalPanel,     // Show/focus or hide the bottom terminal panel
+    NewPanelTerminal,        // Open a new terminal tab in the bottom panel
+    RenameTerminal,          // Rename the active terminal's tab
+    SendToRepl,              // Send the selection or current line to the language's REPL
+    SendBlockToRepl,         // Send the selection or the cell/block under the cursor to the REPL
+    TerminalPreviousCommand, // Jump to the previous shell prompt in terminal scrollback
+    TerminalNextCommand,     // Jump to the next shell prompt in terminal scrollback
+    OpenTerminalOutputPath,  // Open the file path under the cursor in terminal scrollback
 
     // Shell command operations
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
+    GlobalCommand,       // Apply a command to the lines matching a pattern
 
     // Case conversion
     ToUpperCase, // Convert selection to uppercase
     ToLowerCase, // Convert selection to lowercase
     SortLines,   // Sort selected lines alphabetically
 
+    // Increment/decrement
+    Increment(i64),         // Add to the number or cycle word under each cursor
+    IncrementSequence(i64), // Same, adding 1x, 2x, 3x... for successive cursors
+
     // Input calibration
     CalibrateInput, // Open the input calibration wizard
 
@@ -638,6 +770,21 @@ macro_rules! define_action_str_mapping {
             })
         }
 
+        /// Config name of this action (the inverse of `from_str`), if it has one.
+        /// Actions that take arguments other than a character have no single name.
+        pub fn name(&self) -> Option<&'static str> {
+            Some(match self {
+                $(Self::$s_variant => $s_name,)*
+                $(Self::$c_variant(_) => $c_name,)*
+                _ => return None,
+            })
+        }
+
+        /// Whether the action named `name` takes a `char` argument
+        pub fn takes_char(name: &str) -> bool {
+            matches!(name, $($c_name)|*)
+        }
+
         /// All valid action name strings, sorted alphabetically.
         /// Generated from the same macro as `from_str`, guaranteeing compile-time completeness.
         pub fn all_action_names() -> Vec<String> {
@@ -653,6 +800,14 @@ macro_rules! define_action_str_mapping {
 }
 
 impl Action {
+    /// Positive `count` argument, 1 when missing
+    fn count(args: &HashMap<String, serde_json::Value>) -> i64 {
+        args.get("count")
+            .and_then(|v| v.as_i64())
+            .filter(|&n| n > 0)
+            .unwrap_or(1)
+    }
+
     fn with_char(
         args: &HashMap<String, serde_json::Value>,
         make_action: impl FnOnce(char) -> Self,
@@ -669,6 +824,8 @@ impl Action {
         simple {
             "insert_newline" => InsertNewline,
             "insert_tab" => InsertTab,
+            "insert_unicode_character" => InsertUnicodeCharacter,
+            "inspect_character" => InspectCharacter,
 
             "move_left" => MoveLeft,
             "move_right" => MoveRight,
@@ -677,6 +834,8 @@ impl Action {
             "move_word_left" => MoveWordLeft,
             "move_word_right" => MoveWordRight,
             "move_word_end" => MoveWordEnd,
+            "move_subword_left" => MoveSubWordLeft,
+            "move_subword_right" => MoveSubWordRight,
             "move_line_start" => MoveLineStart,
             "move_line_end" => MoveLineEnd,
             "move_line_up" => MoveLineUp,
@@ -695,6 +854,8 @@ impl Action {
             "select_word_left" => SelectWordLeft,
             "select_word_right" => SelectWordRight,
             "select_word_end" => SelectWordEnd,
+            "select_subword_left" => SelectSubWordLeft,
+            "select_subword_right" => SelectSubWordRight,
             "select_line_start" => SelectLineStart,
             "select_line_end" => SelectLineEnd,
             "select_document_start" => SelectDocumentStart,
@@ -705,6 +866,19 @@ impl Action {
             "select_word" => SelectWord,
             "select_line" => SelectLine,
             "expand_selection" => ExpandSelection,
+            "select_element" => SelectElement,
+            "select_inside_word" => SelectInsideWord,
+            "select_around_word" => SelectAroundWord,
+            "select_inside_quotes" => SelectInsideQuotes,
+            "select_around_quotes" => SelectAroundQuotes,
+            "select_inside_brackets" => SelectInsideBrackets,
+            "select_around_brackets" => SelectAroundBrackets,
+            "select_inside_paragraph" => SelectInsideParagraph,
+            "select_around_paragraph" => SelectAroundParagraph,
+            "select_inside_function" => SelectInsideFunction,
+            "select_around_function" => SelectAroundFunction,
+            "select_inside_argument" => SelectInsideArgument,
+            "select_around_argument" => SelectAroundArgument,
 
             "block_select_left" => BlockSelectLeft,
             "block_select_right" => BlockSelectRight,
@@ -741,6 +915,9 @@ impl Action {
             "save" => Save,
             "save_as" => SaveAs,
             "open" => Open,
+            "open_file_under_cursor" => OpenFileUnderCursor,
+            "open_file_under_cursor_in_split" => OpenFileUnderCursorInSplit,
+            "open_link" => OpenLink,
             "switch_project" => SwitchProject,
             "new" => New,
             "close" => Close,
@@ -751,12 +928,18 @@ impl Action {
             "revert" => Revert,
             "toggle_auto_revert" => ToggleAutoRevert,
             "format_buffer" => FormatBuffer,
+            "trim_trailing_whitespace" => TrimTrailingWhitespace,
+            "ensure_final_newline" => EnsureFinalNewline,
             "goto_line" => GotoLine,
             "goto_matching_bracket" => GoToMatchingBracket,
+            "goto_matching_tag" => GoToMatchingTag,
             "jump_to_next_error" => JumpToNextError,
             "jump_to_previous_error" => JumpToPreviousError,
+            "next_diagnostic" => NextDiagnostic,
+            "prev_diagnostic" => PrevDiagnostic,
 
             "smart_home" => SmartHome,
+            "select_smart_home" => SelectSmartHome,
             "dedent_selection" => DedentSelection,
             "toggle_comment" => ToggleComment,
 
@@ -774,21 +957,27 @@ impl Action {
             "prompt_record_macro" => PromptRecordMacro,
             "prompt_play_macro" => PromptPlayMacro,
             "play_last_macro" => PlayLastMacro,
+            "repeat_last_edit" => RepeatLastEdit,
             "prompt_set_bookmark" => PromptSetBookmark,
             "prompt_jump_to_bookmark" => PromptJumpToBookmark,
 
             "undo" => Undo,
             "redo" => Redo,
+            "history_scrubber" => HistoryScrubber,
 
             "scroll_up" => ScrollUp,
             "scroll_down" => ScrollDown,
             "show_help" => ShowHelp,
             "keyboard_shortcuts" => ShowKeyboardShortcuts,
+            "show_tutor" => ShowTutor,
             "show_warnings" => ShowWarnings,
             "show_status_log" => ShowStatusLog,
             "show_lsp_status" => ShowLspStatus,
+            "show_lsp_info" => ShowLspInfo,
+            "show_buffers" => ShowBuffers,
             "clear_warnings" => ClearWarnings,
             "command_palette" => CommandPalette,
+            "action_console" => ActionConsole,
             "quick_open" => QuickOpen,
             "toggle_line_wrap" => ToggleLineWrap,
             "toggle_compose_mode" => ToggleComposeMode,
@@ -808,6 +997,32 @@ impl Action {
             "increase_split_size" => IncreaseSplitSize,
             "decrease_split_size" => DecreaseSplitSize,
             "toggle_maximize_split" => ToggleMaximizeSplit,
+            "focus_split_left" => FocusSplitLeft,
+            "focus_split_right" => FocusSplitRight,
+            "focus_split_up" => FocusSplitUp,
+            "focus_split_down" => FocusSplitDown,
+            "rotate_splits" => RotateSplits,
+            "close_other_splits" => CloseOtherSplits,
+            "equalize_splits" => EqualizeSplits,
+            "save_layout" => SaveLayout,
+            "restore_layout" => RestoreLayout,
+            "delete_layout" => DeleteLayout,
+
+            "git_diff_head" => GitDiffHead,
+            "git_diff_index" => GitDiffIndex,
+            "git_diff_revision" => GitDiffRevision,
+            "git_checkout_branch" => GitCheckoutBranch,
+            "git_fetch" => GitFetch,
+            "git_pull" => GitPull,
+            "git_push" => GitPush,
+            "git_log" => GitLog,
+            "diff_next_hunk" => DiffNextHunk,
+            "diff_prev_hunk" => DiffPrevHunk,
+            "show_unsaved_changes" => ShowUnsavedChanges,
+            "show_unsaved_changes_unified" => ShowUnsavedChangesUnified,
+            "revert_hunk" => RevertHunk,
+            "local_history" => LocalHistory,
+            "restore_snapshot" => RestoreSnapshot,
 
             "prompt_confirm" => PromptConfirm,
             "prompt_cancel" => PromptCancel,
@@ -866,28 +1081,76 @@ impl Action {
             "file_explorer_new_directory" => FileExplorerNewDirectory,
             "file_explorer_delete" => FileExplorerDelete,
             "file_explorer_rename" => FileExplorerRename,
+            "file_explorer_toggle_mark" => FileExplorerToggleMark,
+            "file_explorer_mark_up" => FileExplorerMarkUp,
+            "file_explorer_mark_down" => FileExplorerMarkDown,
+            "file_explorer_move" => FileExplorerMove,
+            "file_explorer_copy" => FileExplorerCopy,
             "file_explorer_toggle_hidden" => FileExplorerToggleHidden,
             "file_explorer_toggle_gitignored" => FileExplorerToggleGitignored,
+            "file_explorer_toggle_changed_only" => FileExplorerToggleChangedOnly,
+            "file_explorer_filter" => FileExplorerFilter,
+            "reveal_in_file_explorer" => RevealInFileExplorer,
             "file_explorer_search_clear" => FileExplorerSearchClear,
             "file_explorer_search_backspace" => FileExplorerSearchBackspace,
 
             "lsp_completion" => LspCompletion,
             "lsp_goto_definition" => LspGotoDefinition,
+            "lsp_goto_type_definition" => LspGotoTypeDefinition,
+            "lsp_goto_implementation" => LspGotoImplementation,
             "lsp_references" => LspReferences,
             "lsp_rename" => LspRename,
             "lsp_hover" => LspHover,
             "lsp_signature_help" => LspSignatureHelp,
             "lsp_code_actions" => LspCodeActions,
+            "lsp_format_document" => LspFormatDocument,
+            "lsp_format_range" => LspFormatRange,
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
+            "lsp_info_restart" => LspInfoRestart,
+            "lsp_info_stop" => LspInfoStop,
+            "lsp_info_open_log" => LspInfoOpenLog,
+            "lsp_log_cycle_level" => LspLogCycleLevel,
+            "buffers_panel_open" => BuffersPanelOpen,
+            "buffers_panel_unload" => BuffersPanelUnload,
+            "filter_keyboard_shortcuts" => FilterKeyboardShortcuts,
+            "debug_start" => DebugStart,
+            "debug_stop" => DebugStop,
+            "debug_continue" => DebugContinue,
+            "debug_step_over" => DebugStepOver,
+            "debug_step_into" => DebugStepInto,
+            "debug_step_out" => DebugStepOut,
+            "debug_toggle_breakpoint" => DebugToggleBreakpoint,
+            "show_debug_variables" => ShowDebugVariables,
+            "show_debug_console" => ShowDebugConsole,
+            "show_plugin_console" => ShowPluginConsole,
+            "plugin_console_cycle_level" => PluginConsoleCycleLevel,
+            "plugin_console_cycle_plugin" => PluginConsoleCyclePlugin,
+            "show_log" => ShowLog,
+            "log_cycle_level" => LogCycleLevel,
+            "log_cycle_source" => LogCycleSource,
+            "check_health" => CheckHealth,
+            "show_keybinding_conflicts" => ShowKeybindingConflicts,
+            "keybinding_conflicts_rebind" => KeybindingConflictsRebind,
+            "keybinding_conflicts_disable" => KeybindingConflictsDisable,
+            "debug_add_watch" => DebugAddWatch,
+            "debug_remove_watch" => DebugRemoveWatch,
+            "debug_evaluate" => DebugEvaluate,
+            "debug_toggle_variable" => DebugToggleVariable,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
             "toggle_inlay_hints" => ToggleInlayHints,
             "toggle_mouse_hover" => ToggleMouseHover,
 
             "toggle_line_numbers" => ToggleLineNumbers,
+            "toggle_read_only" => ToggleReadOnly,
+            "toggle_pager_follow" => TogglePagerFollow,
+            "collab_host" => CollabHost,
+            "collab_join" => CollabJoin,
+            "collab_leave" => CollabLeave,
             "toggle_scroll_sync" => ToggleScrollSync,
             "toggle_mouse_capture" => ToggleMouseCapture,
             "toggle_debug_highlights" => ToggleDebugHighlights,
+            "toggle_perf_hud" => TogglePerfHud,
             "set_background" => SetBackground,
             "set_background_blend" => SetBackgroundBlend,
             "select_theme" => SelectTheme,
@@ -899,6 +1162,8 @@ impl Action {
             "set_encoding" => SetEncoding,
             "reload_with_encoding" => ReloadWithEncoding,
             "toggle_indentation_style" => ToggleIndentationStyle,
+            "convert_indentation_to_spaces" => ConvertIndentationToSpaces,
+            "convert_indentation_to_tabs" => ConvertIndentationToTabs,
             "toggle_tab_indicators" => ToggleTabIndicators,
             "reset_buffer_settings" => ResetBufferSettings,
 
@@ -912,6 +1177,9 @@ impl Action {
             "find_selection_previous" => FindSelectionPrevious,
             "replace" => Replace,
             "query_replace" => QueryReplace,
+            "project_search" => ProjectSearch,
+            "project_search_open" => ProjectSearchOpen,
+            "project_search_rerun" => ProjectSearchRerun,
 
             "menu_activate" => MenuActivate,
             "menu_close" => MenuClose,
@@ -927,9 +1195,18 @@ impl Action {
             "terminal_escape" => TerminalEscape,
             "toggle_keyboard_capture" => ToggleKeyboardCapture,
             "terminal_paste" => TerminalPaste,
+            "toggle_terminal_panel" => ToggleTerminalPanel,
+            "new_panel_terminal" => NewPanelTerminal,
+            "rename_terminal" => RenameTerminal,
+            "send_to_repl" => SendToRepl,
+            "send_block_to_repl" => SendBlockToRepl,
+            "terminal_previous_command" => TerminalPreviousCommand,
+            "terminal_next_command" => TerminalNextCommand,
+            "open_terminal_output_path" => OpenTerminalOutputPath,
 
             "shell_command" => ShellCommand,
             "shell_command_replace" => ShellCommandReplace,
+            "global_command" => GlobalCommand,
 
             "to_upper_case" => ToUpperCase,
             "to_lower_case" => ToLowerCase,
@@ -975,6 +1252,14 @@ impl Action {
                 let map_name = args.get("map")?.as_str()?;
                 Self::SwitchKeybindingMap(map_name.to_string())
             },
+            "user_command" => {
+                let name = args.get("name")?.as_str()?;
+                Self::UserCommand(name.to_string())
+            },
+            "increment" => Self::Increment(Self::count(args)),
+            "decrement" => Self::Increment(-Self::count(args)),
+            "increment_sequence" => Self::IncrementSequence(Self::count(args)),
+            "decrement_sequence" => Self::IncrementSequence(-Self::count(args)),
         }
     }
 
@@ -991,12 +1276,15 @@ impl Action {
                 | Action::MoveWordLeft
                 | Action::MoveWordRight
                 | Action::MoveWordEnd
+                | Action::MoveSubWordLeft
+                | Action::MoveSubWordRight
                 | Action::MoveLineStart
                 | Action::MoveLineEnd
                 | Action::MovePageUp
                 | Action::MovePageDown
                 | Action::MoveDocumentStart
                 | Action::MoveDocumentEnd
+                | Action::SmartHome
                 // Selection actions
                 | Action::SelectLeft
                 | Action::SelectRight
@@ -1007,8 +1295,11 @@ impl Action {
                 | Action::SelectWordLeft
                 | Action::SelectWordRight
                 | Action::SelectWordEnd
+                | Action::SelectSubWordLeft
+                | Action::SelectSubWordRight
                 | Action::SelectLineStart
                 | Action::SelectLineEnd
+                | Action::SelectSmartHome
                 | Action::SelectDocumentStart
                 | Action::SelectDocumentEnd
                 | Action::SelectPageUp
@@ -1016,6 +1307,18 @@ impl Action {
                 | Action::SelectAll
                 | Action::SelectWord
                 | Action::SelectLine
+                | Action::SelectInsideWord
+                | Action::SelectAroundWord
+                | Action::SelectInsideQuotes
+                | Action::SelectAroundQuotes
+                | Action::SelectInsideBrackets
+                | Action::SelectAroundBrackets
+                | Action::SelectInsideParagraph
+                | Action::SelectAroundParagraph
+                | Action::SelectInsideFunction
+                | Action::SelectAroundFunction
+                | Action::SelectInsideArgument
+                | Action::SelectAroundArgument
                 | Action::ExpandSelection
                 // Block selection
                 | Action::BlockSelectLeft
@@ -1038,6 +1341,8 @@ impl Action {
                 | Action::DuplicateLine
                 | Action::MoveLineUp
                 | Action::MoveLineDown
+                | Action::Increment(_)
+                | Action::IncrementSequence(_)
                 // Clipboard editing (but not Copy)
                 | Action::Cut
                 | Action::Paste
@@ -1047,6 +1352,26 @@ impl Action {
         )
     }
 
+    /// Text object selected by a select-inside/around action, and whether
+    /// its surroundings are included
+    pub fn text_object(&self) -> Option<(TextObject, bool)> {
+        Some(match self {
+            Action::SelectInsideWord => (TextObject::Word, false),
+            Action::SelectAroundWord => (TextObject::Word, true),
+            Action::SelectInsideQuotes => (TextObject::Quotes, false),
+            Action::SelectAroundQuotes => (TextObject::Quotes, true),
+            Action::SelectInsideBrackets => (TextObject::Brackets, false),
+            Action::SelectAroundBrackets => (TextObject::Brackets, true),
+            Action::SelectInsideParagraph => (TextObject::Paragraph, false),
+            Action::SelectAroundParagraph => (TextObject::Paragraph, true),
+            Action::SelectInsideFunction => (TextObject::Function, false),
+            Action::SelectAroundFunction => (TextObject::Function, true),
+            Action::SelectInsideArgument => (TextObject::Argument, false),
+            Action::SelectAroundArgument => (TextObject::Argument, true),
+            _ => return None,
+        })
+    }
+
     /// Check if this action modifies buffer content (for block selection conversion).
     /// Block selections should be converted to multi-cursor before these actions.
     pub fn is_editing(&self) -> bool {
@@ -1067,6 +1392,8 @@ impl Action {
                 | Action::DuplicateLine
                 | Action::MoveLineUp
                 | Action::MoveLineDown
+                | Action::Increment(_)
+                | Action::IncrementSequence(_)
                 | Action::Cut
                 | Action::Paste
         )
@@ -1084,6 +1411,61 @@ pub enum ChordResolution {
     NoMatch,
 }
 
+/// A key binding registered by a plugin at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginKeybinding {
+    /// Plugin that registered the binding
+    pub plugin: String,
+    /// Key sequence (one element for a single key)
+    pub keys: Vec<(KeyCode, KeyModifiers)>,
+    pub action: Action,
+    pub context: KeyContext,
+    /// Custom context (set by plugins) the binding is limited to; the binding
+    /// then applies in the normal context while that custom context is active
+    pub custom_context: Option<String>,
+}
+
+/// Where a key binding comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingOrigin {
+    /// The active keymap
+    Keymap,
+    /// The `keybindings` of the user's config
+    User,
+    /// Registered by the named plugin
+    Plugin(String),
+    /// Bound by the named buffer mode, in buffers with that mode
+    Mode(String),
+}
+
+/// One of the bindings of a conflict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompetingBinding {
+    pub origin: BindingOrigin,
+    /// Context the binding is made in; global bindings compete with those of
+    /// every context
+    pub context: KeyContext,
+    pub action: Action,
+}
+
+/// Keys bound to different actions in the same context by more than one of
+/// the user's config, plugins and buffer modes. Overriding the keymap in the
+/// user's config is deliberate and not a conflict on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeybindingConflict {
+    pub keys: Vec<(KeyCode, KeyModifiers)>,
+    pub context: KeyContext,
+    /// The competing bindings, the one that wins first
+    pub bindings: Vec<CompetingBinding>,
+}
+
+impl KeybindingConflict {
+    /// The binding that wins
+    pub fn winner(&self) -> &CompetingBinding {
+        &self.bindings[0]
+    }
+}
+
 /// Resolves key events to actions based on configuration
 #[derive(Clone)]
 pub struct KeybindingResolver {
@@ -1100,6 +1482,21 @@ pub struct KeybindingResolver {
 
     /// Default chord bindings for each context
     default_chord_bindings: HashMap<KeyContext, HashMap<Vec<(KeyCode, KeyModifiers)>, Action>>,
+
+    /// Bindings registered by plugins, in registration order. They override the
+    /// keymap but not the user's custom bindings
+    plugin_bindings: Vec<PluginKeybinding>,
+
+    /// Unconditional plugin bindings indexed like the maps above
+    plugin_key_bindings: HashMap<KeyContext, HashMap<(KeyCode, KeyModifiers), Action>>,
+    plugin_chord_bindings: HashMap<KeyContext, HashMap<Vec<(KeyCode, KeyModifiers)>, Action>>,
+
+    /// Plugin bindings replaced by another plugin's binding of the same keys,
+    /// kept to report the conflict
+    displaced_plugin_bindings: Vec<PluginKeybinding>,
+
+    /// Bumped whenever the bindings change
+    generation: u64,
 }
 
 impl KeybindingResolver {
@@ -1110,20 +1507,342 @@ impl KeybindingResolver {
             default_bindings: HashMap::new(),
             chord_bindings: HashMap::new(),
             default_chord_bindings: HashMap::new(),
+            plugin_bindings: Vec::new(),
+            plugin_key_bindings: HashMap::new(),
+            plugin_chord_bindings: HashMap::new(),
+            displaced_plugin_bindings: Vec::new(),
+            generation: 0,
         };
 
         // Load bindings from the active keymap (with inheritance resolution) into default_bindings
         let map_bindings = config.resolve_keymap(&config.active_keybinding_map);
-        resolver.load_default_bindings_from_vec(&map_bindings);
+        resolver.load_default_bindings_from_vec(&map_bindings, &config.commands);
 
         // Then, load custom keybindings (these override the default map bindings)
-        resolver.load_bindings_from_vec(&config.keybindings);
+        resolver.load_bindings_from_vec(&config.keybindings, &config.commands);
 
         resolver
     }
 
+    /// Rebuild from configuration, keeping the bindings registered by plugins
+    pub fn reset_from_config(&mut self, config: &Config) {
+        let plugin_bindings = std::mem::take(&mut self.plugin_bindings);
+        let displaced = std::mem::take(&mut self.displaced_plugin_bindings);
+        let generation = self.generation;
+        *self = Self::new(config);
+        self.plugin_bindings = plugin_bindings;
+        self.displaced_plugin_bindings = displaced;
+        self.generation = generation;
+        self.index_plugin_bindings();
+    }
+
+    /// Counter that changes whenever the bindings change
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Register a plugin binding
+    ///
+    /// Returns the plugin binding it replaced (same keys and context), if any.
+    pub fn add_plugin_binding(&mut self, binding: PluginKeybinding) -> Option<PluginKeybinding> {
+        let replaced = self
+            .plugin_bindings
+            .iter()
+            .position(|b| {
+                b.keys == binding.keys
+                    && b.context == binding.context
+                    && b.custom_context == binding.custom_context
+            })
+            .map(|index| self.plugin_bindings.remove(index));
+        self.displaced_plugin_bindings
+            .retain(|b| !(b.plugin == binding.plugin && Self::same_keys(b, &binding)));
+        if let Some(old) = replaced
+            .as_ref()
+            .filter(|old| old.plugin != binding.plugin && old.action != binding.action)
+        {
+            self.displaced_plugin_bindings.push(old.clone());
+        }
+        self.plugin_bindings.push(binding);
+        self.index_plugin_bindings();
+        replaced
+    }
+
+    fn same_keys(a: &PluginKeybinding, b: &PluginKeybinding) -> bool {
+        a.keys == b.keys && a.context == b.context && a.custom_context == b.custom_context
+    }
+
+    /// Remove one binding a plugin registered; returns whether it existed
+    pub fn remove_plugin_binding(
+        &mut self,
+        plugin: &str,
+        keys: &[(KeyCode, KeyModifiers)],
+        context: KeyContext,
+        custom_context: Option<&str>,
+    ) -> bool {
+        let before = self.plugin_bindings.len();
+        let matches = |b: &PluginKeybinding| {
+            b.plugin == plugin
+                && b.keys == keys
+                && b.context == context
+                && b.custom_context.as_deref() == custom_context
+        };
+        self.plugin_bindings.retain(|b| !matches(b));
+        self.displaced_plugin_bindings.retain(|b| !matches(b));
+        self.index_plugin_bindings();
+        self.plugin_bindings.len() != before
+    }
+
+    /// Remove every binding a plugin registered; returns how many were removed
+    pub fn remove_plugin_bindings(&mut self, plugin: &str) -> usize {
+        let before = self.plugin_bindings.len();
+        self.plugin_bindings.retain(|b| b.plugin != plugin);
+        self.displaced_plugin_bindings
+            .retain(|b| b.plugin != plugin);
+        self.index_plugin_bindings();
+        before - self.plugin_bindings.len()
+    }
+
+    /// Bindings registered by plugins, in registration order
+    pub fn plugin_bindings(&self) -> &[PluginKeybinding] {
+        &self.plugin_bindings
+    }
+
+    /// Binding from the keymap or the user's config for exact keys in a context.
+    /// The flag is true for the user's custom bindings.
+    pub fn config_binding(
+        &self,
+        context: KeyContext,
+        keys: &[(KeyCode, KeyModifiers)],
+    ) -> Option<(&Action, bool)> {
+        let (custom, default) = match keys {
+            [key] => (
+                self.bindings.get(&context).and_then(|map| map.get(key)),
+                self.default_bindings
+                    .get(&context)
+                    .and_then(|map| map.get(key)),
+            ),
+            _ => (
+                self.chord_bindings
+                    .get(&context)
+                    .and_then(|map| map.get(keys)),
+                self.default_chord_bindings
+                    .get(&context)
+                    .and_then(|map| map.get(keys)),
+            ),
+        };
+        custom
+            .map(|action| (action, true))
+            .or(default.map(|action| (action, false)))
+    }
+
+    /// Keys bound to different actions by more than one of the user's
+    /// config, plugins and the buffer modes in `modes`
+    ///
+    /// Bindings limited to a plugin's custom context are left out, as they
+    /// only apply while that context is active. Mode bindings are compared
+    /// with the user's and plugins' bindings of the normal context, which they
+    /// shadow in buffers with that mode.
+    pub fn conflicts(&self, modes: &ModeRegistry) -> Vec<KeybindingConflict> {
+        let plugin_binding = |context: KeyContext, keys: &[(KeyCode, KeyModifiers)]| {
+            self.plugin_bindings
+                .iter()
+                .rev()
+                .find(|b| b.custom_context.is_none() && b.context == context && b.keys == keys)
+        };
+        let user_binding = |context: KeyContext, keys: &[(KeyCode, KeyModifiers)]| {
+            self.config_binding(context, keys)
+                .filter(|(_, custom)| *custom)
+                .map(|(action, _)| action)
+        };
+
+        // Bindings of one context in the order they are tried
+        let layer = |context: KeyContext, keys: &[(KeyCode, KeyModifiers)]| {
+            let mut bindings = Vec::new();
+            let mut push = |origin, action: &Action| {
+                bindings.push(CompetingBinding {
+                    origin,
+                    context,
+                    action: action.clone(),
+                })
+            };
+            if let Some(action) = user_binding(context, keys) {
+                push(BindingOrigin::User, action);
+            }
+            for plugin in plugin_binding(context, keys).into_iter().chain(
+                self.displaced_plugin_bindings.iter().rev().filter(|b| {
+                    b.custom_context.is_none() && b.context == context && b.keys == keys
+                }),
+            ) {
+                push(BindingOrigin::Plugin(plugin.plugin.clone()), &plugin.action);
+            }
+            if let Some(action) = match keys {
+                [key] => self
+                    .default_bindings
+                    .get(&context)
+                    .and_then(|map| map.get(key)),
+                _ => self
+                    .default_chord_bindings
+                    .get(&context)
+                    .and_then(|map| map.get(keys)),
+            } {
+                push(BindingOrigin::Keymap, action);
+            }
+            bindings
+        };
+
+        let mut conflicts = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for binding in self
+            .plugin_bindings
+            .iter()
+            .chain(&self.displaced_plugin_bindings)
+            .filter(|b| b.custom_context.is_none())
+        {
+            if !seen.insert((binding.context, binding.keys.clone())) {
+                continue;
+            }
+            let (context, keys) = (binding.context, binding.keys.as_slice());
+            // Global bindings are tried before those of any other context
+            let mut bindings = if context == KeyContext::Global {
+                Vec::new()
+            } else {
+                layer(KeyContext::Global, keys)
+            };
+            bindings.extend(layer(context, keys));
+            if bindings.iter().any(|b| b.action != bindings[0].action) {
+                conflicts.push(KeybindingConflict {
+                    keys: keys.to_vec(),
+                    context,
+                    bindings,
+                });
+            }
+        }
+
+        let mut mode_names = modes.list_modes();
+        mode_names.sort();
+        for name in mode_names {
+            let Some(mode) = modes.get(&name) else {
+                continue;
+            };
+            let mode_bindings = mode
+                .keybindings
+                .iter()
+                .map(|(key, command)| (vec![*key], command))
+                .chain(
+                    mode.chord_keybindings
+                        .iter()
+                        .map(|(keys, command)| (keys.clone(), command)),
+                );
+            for (keys, command) in mode_bindings {
+                let shadowed = user_binding(KeyContext::Normal, &keys)
+                    .map(|action| (BindingOrigin::User, action))
+                    .or_else(|| {
+                        plugin_binding(KeyContext::Normal, &keys)
+                            .map(|b| (BindingOrigin::Plugin(b.plugin.clone()), &b.action))
+                    });
+                let action = Action::from_str(command, &HashMap::new())
+                    .unwrap_or_else(|| Action::PluginAction(command.clone()));
+                if let Some((origin, other)) = shadowed.filter(|(_, other)| **other != action) {
+                    conflicts.push(KeybindingConflict {
+                        keys,
+                        context: KeyContext::Normal,
+                        bindings: vec![
+                            CompetingBinding {
+                                origin: BindingOrigin::Mode(name.clone()),
+                                context: KeyContext::Normal,
+                                action,
+                            },
+                            CompetingBinding {
+                                origin,
+                                context: KeyContext::Normal,
+                                action: other.clone(),
+                            },
+                        ],
+                    });
+                }
+            }
+        }
+
+        conflicts.sort_by_cached_key(|conflict| {
+            (
+                conflict.context.to_when_clause(),
+                crate::input::key_reference::format_keys(&conflict.keys),
+                format!("{:?}", conflict.winner().origin),
+            )
+        });
+        conflicts
+    }
+
+    /// Resolve plugin bindings limited to custom contexts
+    ///
+    /// Only bindings whose custom context is in `active` are considered; these
+    /// take precedence over the regular tables while their context is active.
+    pub fn resolve_custom_context_chord(
+        &self,
+        chord_state: &[(KeyCode, KeyModifiers)],
+        event: &KeyEvent,
+        active: &std::collections::HashSet<String>,
+    ) -> ChordResolution {
+        let mut sequence = chord_state.to_vec();
+        sequence.push((event.code, event.modifiers));
+        let mut partial = false;
+        for binding in self.plugin_bindings.iter().rev() {
+            if !binding
+                .custom_context
+                .as_ref()
+                .is_some_and(|name| active.contains(name))
+            {
+                continue;
+            }
+            if binding.keys == sequence {
+                return ChordResolution::Complete(binding.action.clone());
+            }
+            if binding.keys.len() > sequence.len() && binding.keys.starts_with(&sequence) {
+                partial = true;
+            }
+        }
+        if partial {
+            ChordResolution::Partial
+        } else {
+            ChordResolution::NoMatch
+        }
+    }
+
+    /// Rebuild the lookup maps of unconditional plugin bindings
+    fn index_plugin_bindings(&mut self) {
+        self.generation += 1;
+        self.plugin_key_bindings.clear();
+        self.plugin_chord_bindings.clear();
+        // Later registrations win
+        for binding in self
+            .plugin_bindings
+            .iter()
+            .filter(|b| b.custom_context.is_none())
+        {
+            match binding.keys.as_slice() {
+                [key] => {
+                    self.plugin_key_bindings
+                        .entry(binding.context)
+                        .or_default()
+                        .insert(*key, binding.action.clone());
+                }
+                [] => {}
+                keys => {
+                    self.plugin_chord_bindings
+                        .entry(binding.context)
+                        .or_default()
+                        .insert(keys.to_vec(), binding.action.clone());
+                }
+            }
+        }
+    }
+
     /// Load default bindings from a vector of keybinding definitions (into default_bindings/default_chord_bindings)
-    fn load_default_bindings_from_vec(&mut self, bindings: &[crate::config::Keybinding]) {
+    fn load_default_bindings_from_vec(
+        &mut self,
+        bindings: &[crate::config::Keybinding],
+        commands: &HashMap<String, crate::config::UserCommand>,
+    ) {
         for binding in bindings {
             // Determine context from "when" clause
             let context = if let Some(ref when) = binding.when {
@@ -1132,7 +1851,7 @@ impl KeybindingResolver {
                 KeyContext::Normal
             };
 
-            if let Some(action) = Action::from_str(&binding.action, &binding.args) {
+            if let Some(action) = Self::binding_action(binding, commands) {
                 // Check if this is a chord binding (has keys field)
                 if !binding.keys.is_empty() {
                     // Parse the chord sequence
@@ -1171,6 +1890,19 @@ impl KeybindingResolver {
         }
     }
 
+    /// The action of `binding`: a built-in action, or a user-defined command
+    /// bound by its name
+    fn binding_action(
+        binding: &crate::config::Keybinding,
+        commands: &HashMap<String, crate::config::UserCommand>,
+    ) -> Option<Action> {
+        Action::from_str(&binding.action, &binding.args).or_else(|| {
+            commands
+                .contains_key(&binding.action)
+                .then(|| Action::UserCommand(binding.action.clone()))
+        })
+    }
+
     /// Insert a binding and automatically add terminal key equivalents.
     /// Logs a warning if an equivalent key is already bound to a different action.
     fn insert_binding_with_equivalents(
@@ -1215,7 +1947,11 @@ impl KeybindingResolver {
     }
 
     /// Load custom bindings from a vector of keybinding definitions (into bindings/chord_bindings)
-    fn load_bindings_from_vec(&mut self, bindings: &[crate::config::Keybinding]) {
+    fn load_bindings_from_vec(
+        &mut self,
+        bindings: &[crate::config::Keybinding],
+        commands: &HashMap<String, crate::config::UserCommand>,
+    ) {
         for binding in bindings {
             // Determine context from "when" clause
             let context = if let Some(ref when) = binding.when {
@@ -1224,7 +1960,7 @@ impl KeybindingResolver {
                 KeyContext::Normal
             };
 
-            if let Some(action) = Action::from_str(&binding.action, &binding.args) {
+            if let Some(action) = Self::binding_action(binding, commands) {
                 // Check if this is a chord binding (has keys field)
                 if !binding.keys.is_empty() {
                     // Parse the chord sequence
@@ -1296,6 +2032,10 @@ impl KeybindingResolver {
                 | Action::SplitVertical
                 | Action::CloseSplit
                 | Action::ToggleMaximizeSplit
+                | Action::FocusSplitLeft
+                | Action::FocusSplitRight
+                | Action::FocusSplitUp
+                | Action::FocusSplitDown
                 // Tab/buffer navigation
                 | Action::NextBuffer
                 | Action::PrevBuffer
@@ -1308,6 +2048,11 @@ impl KeybindingResolver {
                 | Action::OpenTerminal
                 | Action::CloseTerminal
                 | Action::TerminalPaste
+                | Action::ToggleTerminalPanel
+                | Action::NewPanelTerminal
+                | Action::RenameTerminal
+                | Action::TerminalPreviousCommand
+                | Action::TerminalNextCommand
                 // File explorer
                 | Action::ToggleFileExplorer
                 // Menu bar
@@ -1339,12 +2084,18 @@ impl KeybindingResolver {
         // Check all chord binding sources in priority order
         let search_order = vec![
             (&self.chord_bindings, &KeyContext::Global, "custom global"),
+            (
+                &self.plugin_chord_bindings,
+                &KeyContext::Global,
+                "plugin global",
+            ),
             (
                 &self.default_chord_bindings,
                 &KeyContext::Global,
                 "default global",
             ),
             (&self.chord_bindings, &context, "custom context"),
+            (&self.plugin_chord_bindings, &context, "plugin context"),
             (&self.default_chord_bindings, &context, "default context"),
         ];
 
@@ -1396,6 +2147,13 @@ impl KeybindingResolver {
             }
         }
 
+        if let Some(global_bindings) = self.plugin_key_bindings.get(&KeyContext::Global) {
+            if let Some(action) = global_bindings.get(&(event.code, event.modifiers)) {
+                tracing::trace!("  -> Found in plugin global bindings: {:?}", action);
+                return action.clone();
+            }
+        }
+
         if let Some(global_bindings) = self.default_bindings.get(&KeyContext::Global) {
             if let Some(action) = global_bindings.get(&(event.code, event.modifiers)) {
                 tracing::trace!("  -> Found in default global bindings: {:?}", action);
@@ -1415,6 +2173,18 @@ impl KeybindingResolver {
             }
         }
 
+        // Try context-specific plugin bindings
+        if let Some(context_bindings) = self.plugin_key_bindings.get(&context) {
+            if let Some(action) = context_bindings.get(&(event.code, event.modifiers)) {
+                tracing::trace!(
+                    "  -> Found in plugin {} bindings: {:?}",
+                    context.to_when_clause(),
+                    action
+                );
+                return action.clone();
+            }
+        }
+
         // Try context-specific default bindings
         if let Some(context_bindings) = self.default_bindings.get(&context) {
             if let Some(action) = context_bindings.get(&(event.code, event.modifiers)) {
@@ -1479,6 +2249,13 @@ impl KeybindingResolver {
             }
         }
 
+        // Try plugin bindings for this context
+        if let Some(context_bindings) = self.plugin_key_bindings.get(&context) {
+            if let Some(action) = context_bindings.get(&(event.code, event.modifiers)) {
+                return Some(action.clone());
+            }
+        }
+
         // Try default bindings for this context
         if let Some(context_bindings) = self.default_bindings.get(&context) {
             if let Some(action) = context_bindings.get(&(event.code, event.modifiers)) {
@@ -1739,17 +2516,58 @@ impl KeybindingResolver {
         bindings
     }
 
+    /// Effective bindings of one context: the keymap's defaults overridden by
+    /// plugin bindings and then by custom bindings. Single keys are returned as
+    /// one-element sequences. Plugin bindings limited to a custom context are
+    /// not included (see `plugin_bindings`).
+    pub fn effective_bindings(
+        &self,
+        context: KeyContext,
+    ) -> Vec<(Vec<(KeyCode, KeyModifiers)>, Action)> {
+        let mut keys: HashMap<(KeyCode, KeyModifiers), Action> = HashMap::new();
+        for map in [
+            self.default_bindings.get(&context),
+            self.plugin_key_bindings.get(&context),
+            self.bindings.get(&context),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            keys.extend(map.iter().map(|(key, action)| (*key, action.clone())));
+        }
+        let mut chords: HashMap<Vec<(KeyCode, KeyModifiers)>, Action> = HashMap::new();
+        for map in [
+            self.default_chord_bindings.get(&context),
+            self.plugin_chord_bindings.get(&context),
+            self.chord_bindings.get(&context),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            chords.extend(
+                map.iter()
+                    .map(|(keys, action)| (keys.clone(), action.clone())),
+            );
+        }
+        keys.into_iter()
+            .map(|(key, action)| (vec![key], action))
+            .chain(chords)
+            .collect()
+    }
+
     /// Format a key combination as a readable string
     fn format_key(key_code: KeyCode, modifiers: KeyModifiers) -> String {
         format_keybinding(&key_code, &modifiers)
     }
 
     /// Format an action as a readable description
-    fn format_action(action: &Action) -> String {
+    pub fn format_action(action: &Action) -> String {
         match action {
             Action::InsertChar(c) => t!("action.insert_char", char = c),
             Action::InsertNewline => t!("action.insert_newline"),
             Action::InsertTab => t!("action.insert_tab"),
+            Action::InsertUnicodeCharacter => t!("action.insert_unicode_character"),
+            Action::InspectCharacter => t!("action.inspect_character"),
             Action::MoveLeft => t!("action.move_left"),
             Action::MoveRight => t!("action.move_right"),
             Action::MoveUp => t!("action.move_up"),
@@ -1757,6 +2575,8 @@ impl KeybindingResolver {
             Action::MoveWordLeft => t!("action.move_word_left"),
             Action::MoveWordRight => t!("action.move_word_right"),
             Action::MoveWordEnd => t!("action.move_word_end"),
+            Action::MoveSubWordLeft => t!("action.move_subword_left"),
+            Action::MoveSubWordRight => t!("action.move_subword_right"),
             Action::MoveLineStart => t!("action.move_line_start"),
             Action::MoveLineEnd => t!("action.move_line_end"),
             Action::MoveLineUp => t!("action.move_line_up"),
@@ -1774,6 +2594,8 @@ impl KeybindingResolver {
             Action::SelectWordLeft => t!("action.select_word_left"),
             Action::SelectWordRight => t!("action.select_word_right"),
             Action::SelectWordEnd => t!("action.select_word_end"),
+            Action::SelectSubWordLeft => t!("action.select_subword_left"),
+            Action::SelectSubWordRight => t!("action.select_subword_right"),
             Action::SelectLineStart => t!("action.select_line_start"),
             Action::SelectLineEnd => t!("action.select_line_end"),
             Action::SelectDocumentStart => t!("action.select_document_start"),
@@ -1784,6 +2606,19 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::SelectInsideWord => t!("action.select_inside_word"),
+            Action::SelectAroundWord => t!("action.select_around_word"),
+            Action::SelectInsideQuotes => t!("action.select_inside_quotes"),
+            Action::SelectAroundQuotes => t!("action.select_around_quotes"),
+            Action::SelectInsideBrackets => t!("action.select_inside_brackets"),
+            Action::SelectAroundBrackets => t!("action.select_around_brackets"),
+            Action::SelectInsideParagraph => t!("action.select_inside_paragraph"),
+            Action::SelectAroundParagraph => t!("action.select_around_paragraph"),
+            Action::SelectInsideFunction => t!("action.select_inside_function"),
+            Action::SelectAroundFunction => t!("action.select_around_function"),
+            Action::SelectInsideArgument => t!("action.select_inside_argument"),
+            Action::SelectAroundArgument => t!("action.select_around_argument"),
+            Action::SelectElement => t!("action.select_element"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -1816,6 +2651,9 @@ impl KeybindingResolver {
             Action::Save => t!("action.save"),
             Action::SaveAs => t!("action.save_as"),
             Action::Open => t!("action.open"),
+            Action::OpenFileUnderCursor => t!("action.open_file_under_cursor"),
+            Action::OpenFileUnderCursorInSplit => t!("action.open_file_under_cursor_in_split"),
+            Action::OpenLink => t!("action.open_link"),
             Action::SwitchProject => t!("action.switch_project"),
             Action::New => t!("action.new"),
             Action::Close => t!("action.close"),
@@ -1830,9 +2668,13 @@ impl KeybindingResolver {
             Action::EnsureFinalNewline => t!("action.ensure_final_newline"),
             Action::GotoLine => t!("action.goto_line"),
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
+            Action::GoToMatchingTag => t!("action.goto_matching_tag"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
             Action::JumpToPreviousError => t!("action.jump_to_previous_error"),
+            Action::NextDiagnostic => t!("action.next_diagnostic"),
+            Action::PrevDiagnostic => t!("action.prev_diagnostic"),
             Action::SmartHome => t!("action.smart_home"),
+            Action::SelectSmartHome => t!("action.select_smart_home"),
             Action::DedentSelection => t!("action.dedent_selection"),
             Action::ToggleComment => t!("action.toggle_comment"),
             Action::SetBookmark(c) => t!("action.set_bookmark", key = c),
@@ -1852,19 +2694,25 @@ impl KeybindingResolver {
             Action::PromptRecordMacro => t!("action.prompt_record_macro"),
             Action::PromptPlayMacro => t!("action.prompt_play_macro"),
             Action::PlayLastMacro => t!("action.play_last_macro"),
+            Action::RepeatLastEdit => t!("action.repeat_last_edit"),
             Action::PromptSetBookmark => t!("action.prompt_set_bookmark"),
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
+            Action::HistoryScrubber => t!("action.history_scrubber"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
             Action::ShowKeyboardShortcuts => t!("action.show_keyboard_shortcuts"),
+            Action::ShowTutor => t!("action.show_tutor"),
             Action::ShowWarnings => t!("action.show_warnings"),
             Action::ShowStatusLog => t!("action.show_status_log"),
             Action::ShowLspStatus => t!("action.show_lsp_status"),
+            Action::ShowLspInfo => t!("action.show_lsp_info"),
+            Action::ShowBuffers => t!("action.show_buffers"),
             Action::ClearWarnings => t!("action.clear_warnings"),
             Action::CommandPalette => t!("action.command_palette"),
+            Action::ActionConsole => t!("action.action_console"),
             Action::QuickOpen => t!("action.quick_open"),
             Action::ToggleLineWrap => t!("action.toggle_line_wrap"),
             Action::ToggleComposeMode => t!("action.toggle_compose_mode"),
@@ -1881,6 +2729,31 @@ impl KeybindingResolver {
             Action::IncreaseSplitSize => t!("action.increase_split_size"),
             Action::DecreaseSplitSize => t!("action.decrease_split_size"),
             Action::ToggleMaximizeSplit => t!("action.toggle_maximize_split"),
+            Action::FocusSplitLeft => t!("action.focus_split_left"),
+            Action::FocusSplitRight => t!("action.focus_split_right"),
+            Action::FocusSplitUp => t!("action.focus_split_up"),
+            Action::FocusSplitDown => t!("action.focus_split_down"),
+            Action::RotateSplits => t!("action.rotate_splits"),
+            Action::CloseOtherSplits => t!("action.close_other_splits"),
+            Action::EqualizeSplits => t!("action.equalize_splits"),
+            Action::SaveLayout => t!("action.save_layout"),
+            Action::RestoreLayout => t!("action.restore_layout"),
+            Action::DeleteLayout => t!("action.delete_layout"),
+            Action::GitDiffHead => t!("action.git_diff_head"),
+            Action::GitDiffIndex => t!("action.git_diff_index"),
+            Action::GitDiffRevision => t!("action.git_diff_revision"),
+            Action::GitCheckoutBranch => t!("action.git_checkout_branch"),
+            Action::GitFetch => t!("action.git_fetch"),
+            Action::GitPull => t!("action.git_pull"),
+            Action::GitPush => t!("action.git_push"),
+            Action::GitLog => t!("action.git_log"),
+            Action::DiffNextHunk => t!("action.diff_next_hunk"),
+            Action::DiffPrevHunk => t!("action.diff_prev_hunk"),
+            Action::ShowUnsavedChanges => t!("action.show_unsaved_changes"),
+            Action::ShowUnsavedChangesUnified => t!("action.show_unsaved_changes_unified"),
+            Action::RevertHunk => t!("action.revert_hunk"),
+            Action::LocalHistory => t!("action.local_history"),
+            Action::RestoreSnapshot => t!("action.restore_snapshot"),
             Action::PromptConfirm => t!("action.prompt_confirm"),
             Action::PromptConfirmWithText(ref text) => {
                 format!("{} ({})", t!("action.prompt_confirm"), text).into()
@@ -1941,26 +2814,76 @@ impl KeybindingResolver {
             Action::FileExplorerNewDirectory => t!("action.file_explorer_new_directory"),
             Action::FileExplorerDelete => t!("action.file_explorer_delete"),
             Action::FileExplorerRename => t!("action.file_explorer_rename"),
+            Action::FileExplorerToggleMark => t!("action.file_explorer_toggle_mark"),
+            Action::FileExplorerMarkUp => t!("action.file_explorer_mark_up"),
+            Action::FileExplorerMarkDown => t!("action.file_explorer_mark_down"),
+            Action::FileExplorerMove => t!("action.file_explorer_move"),
+            Action::FileExplorerCopy => t!("action.file_explorer_copy"),
             Action::FileExplorerToggleHidden => t!("action.file_explorer_toggle_hidden"),
             Action::FileExplorerToggleGitignored => t!("action.file_explorer_toggle_gitignored"),
+            Action::FileExplorerToggleChangedOnly => {
+                t!("action.file_explorer_toggle_changed_only")
+            }
+            Action::FileExplorerFilter => t!("action.file_explorer_filter"),
+            Action::RevealInFileExplorer => t!("action.reveal_in_file_explorer"),
             Action::FileExplorerSearchClear => t!("action.file_explorer_search_clear"),
             Action::FileExplorerSearchBackspace => t!("action.file_explorer_search_backspace"),
             Action::LspCompletion => t!("action.lsp_completion"),
             Action::LspGotoDefinition => t!("action.lsp_goto_definition"),
+            Action::LspGotoTypeDefinition => t!("action.lsp_goto_type_definition"),
+            Action::LspGotoImplementation => t!("action.lsp_goto_implementation"),
             Action::LspReferences => t!("action.lsp_references"),
             Action::LspRename => t!("action.lsp_rename"),
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
             Action::LspCodeActions => t!("action.lsp_code_actions"),
+            Action::LspFormatDocument => t!("action.lsp_format_document"),
+            Action::LspFormatRange => t!("action.lsp_format_range"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
+            Action::LspInfoRestart => t!("action.lsp_info_restart"),
+            Action::LspInfoStop => t!("action.lsp_info_stop"),
+            Action::LspInfoOpenLog => t!("action.lsp_info_open_log"),
+            Action::LspLogCycleLevel => t!("action.lsp_log_cycle_level"),
+            Action::BuffersPanelOpen => t!("action.buffers_panel_open"),
+            Action::BuffersPanelUnload => t!("action.buffers_panel_unload"),
+            Action::FilterKeyboardShortcuts => t!("action.filter_keyboard_shortcuts"),
+            Action::DebugStart => t!("action.debug_start"),
+            Action::DebugStop => t!("action.debug_stop"),
+            Action::DebugContinue => t!("action.debug_continue"),
+            Action::DebugStepOver => t!("action.debug_step_over"),
+            Action::DebugStepInto => t!("action.debug_step_into"),
+            Action::DebugStepOut => t!("action.debug_step_out"),
+            Action::DebugToggleBreakpoint => t!("action.debug_toggle_breakpoint"),
+            Action::ShowDebugVariables => t!("action.show_debug_variables"),
+            Action::ShowDebugConsole => t!("action.show_debug_console"),
+            Action::ShowPluginConsole => t!("action.show_plugin_console"),
+            Action::PluginConsoleCycleLevel => t!("action.plugin_console_cycle_level"),
+            Action::PluginConsoleCyclePlugin => t!("action.plugin_console_cycle_plugin"),
+            Action::ShowLog => t!("action.show_log"),
+            Action::LogCycleLevel => t!("action.log_cycle_level"),
+            Action::LogCycleSource => t!("action.log_cycle_source"),
+            Action::CheckHealth => t!("action.check_health"),
+            Action::ShowKeybindingConflicts => t!("action.show_keybinding_conflicts"),
+            Action::KeybindingConflictsRebind => t!("action.keybinding_conflicts_rebind"),
+            Action::KeybindingConflictsDisable => t!("action.keybinding_conflicts_disable"),
+            Action::DebugAddWatch => t!("action.debug_add_watch"),
+            Action::DebugRemoveWatch => t!("action.debug_remove_watch"),
+            Action::DebugEvaluate => t!("action.debug_evaluate"),
+            Action::DebugToggleVariable => t!("action.debug_toggle_variable"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
+            Action::ToggleReadOnly => t!("action.toggle_read_only"),
+            Action::TogglePagerFollow => t!("action.toggle_pager_follow"),
+            Action::CollabHost => t!("action.collab_host"),
+            Action::CollabJoin => t!("action.collab_join"),
+            Action::CollabLeave => t!("action.collab_leave"),
             Action::ToggleScrollSync => t!("action.toggle_scroll_sync"),
             Action::ToggleMouseCapture => t!("action.toggle_mouse_capture"),
             Action::ToggleDebugHighlights => t!("action.toggle_debug_highlights"),
+            Action::TogglePerfHud => t!("action.toggle_perf_hud"),
             Action::SetBackground => t!("action.set_background"),
             Action::SetBackgroundBlend => t!("action.set_background_blend"),
             Action::AddRuler => t!("action.add_ruler"),
@@ -1971,6 +2894,8 @@ impl KeybindingResolver {
             Action::ReloadWithEncoding => t!("action.reload_with_encoding"),
             Action::SetLanguage => t!("action.set_language"),
             Action::ToggleIndentationStyle => t!("action.toggle_indentation_style"),
+            Action::ConvertIndentationToSpaces => t!("action.convert_indentation_to_spaces"),
+            Action::ConvertIndentationToTabs => t!("action.convert_indentation_to_tabs"),
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
@@ -1982,6 +2907,9 @@ impl KeybindingResolver {
             Action::FindSelectionPrevious => t!("action.find_selection_previous"),
             Action::Replace => t!("action.replace"),
             Action::QueryReplace => t!("action.query_replace"),
+            Action::ProjectSearch => t!("action.project_search"),
+            Action::ProjectSearchOpen => t!("action.project_search_open"),
+            Action::ProjectSearchRerun => t!("action.project_search_rerun"),
             Action::MenuActivate => t!("action.menu_activate"),
             Action::MenuClose => t!("action.menu_close"),
             Action::MenuLeft => t!("action.menu_left"),
@@ -1992,6 +2920,7 @@ impl KeybindingResolver {
             Action::MenuOpen(name) => t!("action.menu_open", name = name),
             Action::SwitchKeybindingMap(map) => t!("action.switch_keybinding_map", map = map),
             Action::PluginAction(name) => t!("action.plugin_action", name = name),
+            Action::UserCommand(name) => t!("action.user_command", name = name),
             Action::ScrollTabsLeft => t!("action.scroll_tabs_left"),
             Action::ScrollTabsRight => t!("action.scroll_tabs_right"),
             Action::SelectTheme => t!("action.select_theme"),
@@ -2006,6 +2935,14 @@ impl KeybindingResolver {
             Action::TerminalEscape => t!("action.terminal_escape"),
             Action::ToggleKeyboardCapture => t!("action.toggle_keyboard_capture"),
             Action::TerminalPaste => t!("action.terminal_paste"),
+            Action::ToggleTerminalPanel => t!("action.toggle_terminal_panel"),
+            Action::NewPanelTerminal => t!("action.new_panel_terminal"),
+            Action::RenameTerminal => t!("action.rename_terminal"),
+            Action::SendToRepl => t!("action.send_to_repl"),
+            Action::SendBlockToRepl => t!("action.send_block_to_repl"),
+            Action::TerminalPreviousCommand => t!("action.terminal_previous_command"),
+            Action::TerminalNextCommand => t!("action.terminal_next_command"),
+            Action::OpenTerminalOutputPath => t!("action.open_terminal_output_path"),
             Action::OpenSettings => t!("action.open_settings"),
             Action::CloseSettings => t!("action.close_settings"),
             Action::SettingsSave => t!("action.settings_save"),
@@ -2018,9 +2955,14 @@ impl KeybindingResolver {
             Action::SettingsDecrement => t!("action.settings_decrement"),
             Action::ShellCommand => t!("action.shell_command"),
             Action::ShellCommandReplace => t!("action.shell_command_replace"),
+            Action::GlobalCommand => t!("action.global_command"),
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
             Action::SortLines => t!("action.sort_lines"),
+            Action::Increment(step) if *step < 0 => t!("action.decrement"),
+            Action::Increment(_) => t!("action.increment"),
+            Action::IncrementSequence(step) if *step < 0 => t!("action.decrement_sequence"),
+            Action::IncrementSequence(_) => t!("action.increment_sequence"),
             Action::CalibrateInput => t!("action.calibrate_input"),
             Action::EventDebug => t!("action.event_debug"),
             Action::OpenKeybindingEditor => "Keybinding Editor".into(),
@@ -2179,6 +3121,195 @@ impl KeybindingResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::buffer_mode::BufferMode;
+
+    fn plugin_binding(keys: Vec<(KeyCode, KeyModifiers)>, action: &str) -> PluginKeybinding {
+        PluginKeybinding {
+            plugin: "grep".to_string(),
+            keys,
+            action: Action::PluginAction(action.to_string()),
+            context: KeyContext::Normal,
+            custom_context: None,
+        }
+    }
+
+    #[test]
+    fn test_plugin_bindings_precedence_and_unload() {
+        let ctrl_f = (KeyCode::Char('f'), KeyModifiers::CONTROL);
+        let event = KeyEvent::new(ctrl_f.0, ctrl_f.1);
+        let mut resolver = KeybindingResolver::new(&Config::default());
+        let default = resolver.resolve(&event, KeyContext::Normal);
+
+        // Plugin bindings override the keymap defaults
+        assert!(resolver
+            .add_plugin_binding(plugin_binding(vec![ctrl_f], "live_grep"))
+            .is_none());
+        assert_eq!(
+            resolver.resolve(&event, KeyContext::Normal),
+            Action::PluginAction("live_grep".to_string())
+        );
+
+        // Config reloads keep plugin bindings
+        resolver.reset_from_config(&Config::default());
+        assert_eq!(
+            resolver.resolve(&event, KeyContext::Normal),
+            Action::PluginAction("live_grep".to_string())
+        );
+
+        // Unloading the plugin restores the default
+        assert_eq!(resolver.remove_plugin_bindings("grep"), 1);
+        assert_eq!(resolver.resolve(&event, KeyContext::Normal), default);
+
+        // User bindings take precedence over plugin bindings
+        let mut config = Config::default();
+        config.keybindings.push(crate::config::Keybinding {
+            key: "f".to_string(),
+            modifiers: vec!["ctrl".to_string()],
+            keys: vec![],
+            action: "command_palette".to_string(),
+            args: HashMap::new(),
+            when: None,
+        });
+        resolver.reset_from_config(&config);
+        resolver.add_plugin_binding(plugin_binding(vec![ctrl_f], "live_grep"));
+        assert_eq!(
+            resolver.resolve(&event, KeyContext::Normal),
+            Action::CommandPalette
+        );
+        assert_eq!(
+            resolver.config_binding(KeyContext::Normal, &[ctrl_f]),
+            Some((&Action::CommandPalette, true))
+        );
+    }
+
+    #[test]
+    fn test_plugin_bindings_chords_and_custom_contexts() {
+        let ctrl_k = (KeyCode::Char('k'), KeyModifiers::CONTROL);
+        let g = (KeyCode::Char('g'), KeyModifiers::NONE);
+        let mut resolver = KeybindingResolver::new(&Config::default());
+        resolver.add_plugin_binding(plugin_binding(vec![ctrl_k, g], "grep_word"));
+        assert_eq!(
+            resolver.resolve_chord(&[], &KeyEvent::new(ctrl_k.0, ctrl_k.1), KeyContext::Normal),
+            ChordResolution::Partial
+        );
+        assert_eq!(
+            resolver.resolve_chord(&[ctrl_k], &KeyEvent::new(g.0, g.1), KeyContext::Normal),
+            ChordResolution::Complete(Action::PluginAction("grep_word".to_string()))
+        );
+
+        // Custom-context bindings only apply while the context is active
+        let mut binding = plugin_binding(vec![g], "next_match");
+        binding.custom_context = Some("grep-results".to_string());
+        resolver.add_plugin_binding(binding);
+        let event = KeyEvent::new(g.0, g.1);
+        let mut active = std::collections::HashSet::new();
+        assert_eq!(
+            resolver.resolve_custom_context_chord(&[], &event, &active),
+            ChordResolution::NoMatch
+        );
+        assert_eq!(
+            resolver.resolve(&event, KeyContext::Normal),
+            Action::InsertChar('g')
+        );
+        active.insert("grep-results".to_string());
+        assert_eq!(
+            resolver.resolve_custom_context_chord(&[], &event, &active),
+            ChordResolution::Complete(Action::PluginAction("next_match".to_string()))
+        );
+
+        assert!(resolver.remove_plugin_binding(
+            "grep",
+            &[g],
+            KeyContext::Normal,
+            Some("grep-results")
+        ));
+        assert_eq!(
+            resolver.resolve_custom_context_chord(&[], &event, &active),
+            ChordResolution::NoMatch
+        );
+    }
+
+    fn competing(origin: BindingOrigin, action: Action) -> CompetingBinding {
+        CompetingBinding {
+            origin,
+            context: KeyContext::Normal,
+            action,
+        }
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let ctrl_f = (KeyCode::Char('f'), KeyModifiers::CONTROL);
+        let mut modes = ModeRegistry::new();
+        let mut resolver = KeybindingResolver::new(&Config::default());
+        let keymap = resolver.resolve(&KeyEvent::new(ctrl_f.0, ctrl_f.1), KeyContext::Normal);
+        assert!(resolver.conflicts(&modes).is_empty());
+
+        // A plugin overriding the keymap
+        let generation = resolver.generation();
+        resolver.add_plugin_binding(plugin_binding(vec![ctrl_f], "live_grep"));
+        assert_ne!(resolver.generation(), generation);
+        let live_grep = Action::PluginAction("live_grep".to_string());
+        assert_eq!(
+            resolver.conflicts(&modes),
+            vec![KeybindingConflict {
+                keys: vec![ctrl_f],
+                context: KeyContext::Normal,
+                bindings: vec![
+                    competing(BindingOrigin::Plugin("grep".to_string()), live_grep.clone()),
+                    competing(BindingOrigin::Keymap, keymap.clone()),
+                ],
+            }]
+        );
+
+        // Another plugin taking the same key, and the user's config on top
+        let mut search = plugin_binding(vec![ctrl_f], "search");
+        search.plugin = "search".to_string();
+        resolver.add_plugin_binding(search);
+        let mut config = Config::default();
+        config.keybindings.push(crate::config::Keybinding {
+            key: "f".to_string(),
+            modifiers: vec!["ctrl".to_string()],
+            keys: vec![],
+            action: "command_palette".to_string(),
+            args: HashMap::new(),
+            when: None,
+        });
+        resolver.reset_from_config(&config);
+        let conflicts = resolver.conflicts(&modes);
+        assert_eq!(conflicts.len(), 1);
+        let origins: Vec<_> = conflicts[0].bindings.iter().map(|b| &b.origin).collect();
+        assert_eq!(
+            origins,
+            vec![
+                &BindingOrigin::User,
+                &BindingOrigin::Plugin("search".to_string()),
+                &BindingOrigin::Plugin("grep".to_string()),
+                &BindingOrigin::Keymap,
+            ]
+        );
+        assert_eq!(conflicts[0].winner().action, Action::CommandPalette);
+
+        // A buffer mode shadowing the user's binding
+        resolver.remove_plugin_bindings("search");
+        resolver.remove_plugin_bindings("grep");
+        assert!(resolver.conflicts(&modes).is_empty());
+        modes.register(BufferMode::new("finder").with_binding(ctrl_f.0, ctrl_f.1, "finder_find"));
+        assert_eq!(
+            resolver.conflicts(&modes),
+            vec![KeybindingConflict {
+                keys: vec![ctrl_f],
+                context: KeyContext::Normal,
+                bindings: vec![
+                    competing(
+                        BindingOrigin::Mode("finder".to_string()),
+                        Action::PluginAction("finder_find".to_string())
+                    ),
+                    competing(BindingOrigin::User, Action::CommandPalette),
+                ],
+            }]
+        );
+    }
 
     #[test]
     fn test_parse_key() {
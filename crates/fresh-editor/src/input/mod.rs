@@ -3,19 +3,23 @@
 //! This module handles the input-to-action-to-event translation.
 
 pub mod actions;
+pub mod batch;
 pub mod buffer_mode;
 pub mod command_registry;
 pub mod commands;
 pub mod composite_router;
+pub mod frecency;
 pub mod fuzzy;
 pub mod handler;
 pub mod input_history;
+pub mod key_reference;
 pub mod key_translator;
 pub mod keybindings;
 mod line_move;
 pub mod multi_cursor;
 pub mod position_history;
 pub mod quick_open;
+pub mod replay;
 
 #[cfg(test)]
 pub mod tests_language_features;
@@ -2,7 +2,7 @@
 ///
 /// This module tracks the user's position history across buffers,
 /// allowing navigation back and forward through editing locations.
-/// Similar to VS Code's Alt+Left/Alt+Right navigation.
+/// Similar to VS Code's Go Back/Go Forward navigation.
 ///
 /// ## Architecture
 ///
@@ -45,6 +45,8 @@ pub enum QuickOpenResult {
 pub struct QuickOpenContext {
     /// Current working directory
     pub cwd: String,
+    /// Glob patterns of files left out of file listings (`files.exclude`)
+    pub file_exclude: Vec<String>,
     /// List of open buffer paths
     pub open_buffers: Vec<BufferInfo>,
     /// Active buffer ID
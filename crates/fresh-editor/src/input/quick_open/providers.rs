@@ -8,7 +8,9 @@
 
 use super::{QuickOpenContext, QuickOpenProvider, QuickOpenResult};
 use crate::input::commands::Suggestion;
-use crate::input::fuzzy::fuzzy_match;
+use crate::input::frecency::Frecency;
+use crate::input::fuzzy::{fuzzy_filter_frecent, fuzzy_match};
+use crate::services::ignore_rules::IgnoreRules;
 use rust_i18n::t;
 
 // ============================================================================
@@ -102,7 +104,7 @@ impl QuickOpenProvider for CommandProvider {
                     drop(keybindings);
                     drop(registry);
                     if let Ok(mut reg) = self.command_registry.write() {
-                        reg.record_usage(&cmd.name);
+                        reg.record_usage(&cmd.get_localized_name());
                     }
                     return QuickOpenResult::ExecuteAction(cmd.action.clone());
                 }
@@ -319,34 +321,35 @@ impl QuickOpenProvider for GotoLineProvider {
 // File Provider (default, no prefix)
 // ============================================================================
 
+/// Most files listed by the file provider
+const MAX_FILES: usize = 50_000;
+
 /// Provider for finding files in the project
 ///
 /// This is the default provider (empty prefix) that provides file suggestions
-/// using git ls-files, fd, find, or directory traversal.
+/// from a walk of the project that follows its ignore rules.
 pub struct FileProvider {
     /// Cached file list (populated lazily)
     file_cache: std::sync::Arc<std::sync::RwLock<Option<Vec<FileEntry>>>>,
-    /// Frecency data for ranking
-    frecency: std::sync::Arc<std::sync::RwLock<std::collections::HashMap<String, FrecencyData>>>,
+    /// Frecency data for ranking, keyed by absolute path
+    frecency: std::sync::Arc<std::sync::RwLock<Frecency>>,
 }
 
 #[derive(Clone)]
 struct FileEntry {
     relative_path: String,
-    frecency_score: f64,
-}
-
-#[derive(Clone)]
-struct FrecencyData {
-    access_count: u32,
-    last_access: std::time::Instant,
 }
 
 impl FileProvider {
     pub fn new() -> Self {
+        Self::with_frecency(Frecency::new())
+    }
+
+    /// Create a provider ranking files by previously saved frecency data
+    pub fn with_frecency(frecency: Frecency) -> Self {
         Self {
             file_cache: std::sync::Arc::new(std::sync::RwLock::new(None)),
-            frecency: std::sync::Arc::new(std::sync::RwLock::new(std::collections::HashMap::new())),
+            frecency: std::sync::Arc::new(std::sync::RwLock::new(frecency)),
         }
     }
 
@@ -358,45 +361,24 @@ impl FileProvider {
     }
 
     /// Record file access for frecency ranking
-    pub fn record_access(&self, path: &str) {
+    pub fn record_access(&self, path: &std::path::Path) {
         if let Ok(mut frecency) = self.frecency.write() {
-            let entry = frecency.entry(path.to_string()).or_insert(FrecencyData {
-                access_count: 0,
-                last_access: std::time::Instant::now(),
-            });
-            entry.access_count += 1;
-            entry.last_access = std::time::Instant::now();
+            frecency.record(&path.to_string_lossy());
         }
     }
 
-    fn get_frecency_score(&self, path: &str) -> f64 {
-        if let Ok(frecency) = self.frecency.read() {
-            if let Some(data) = frecency.get(path) {
-                let hours_since_access = data.last_access.elapsed().as_secs_f64() / 3600.0;
-
-                // Mozilla-style frecency weighting
-                let recency_weight = if hours_since_access < 4.0 {
-                    100.0
-                } else if hours_since_access < 24.0 {
-                    70.0
-                } else if hours_since_access < 24.0 * 7.0 {
-                    50.0
-                } else if hours_since_access < 24.0 * 30.0 {
-                    30.0
-                } else if hours_since_access < 24.0 * 90.0 {
-                    10.0
-                } else {
-                    1.0
-                };
-
-                return data.access_count as f64 * recency_weight;
-            }
+    /// Save the frecency data to a file
+    pub fn save_frecency(&self, path: &std::path::Path) -> std::io::Result<()> {
+        match self.frecency.read() {
+            Ok(frecency) => frecency.save_to_file(path),
+            Err(_) => Ok(()),
         }
-        0.0
     }
 
     /// Load files from the project directory
-    fn load_files(&self, cwd: &str) -> Vec<FileEntry> {
+    ///
+    /// Lists hidden files too, but nothing the project's ignore rules leave out.
+    fn load_files(&self, cwd: &str, exclude: &[String]) -> Vec<FileEntry> {
         // Check cache first
         if let Ok(cache) = self.file_cache.read() {
             if let Some(files) = cache.as_ref() {
@@ -404,20 +386,21 @@ impl FileProvider {
             }
         }
 
-        // Try different file discovery methods
-        let files = self
-            .try_git_files(cwd)
-            .or_else(|| self.try_fd_files(cwd))
-            .or_else(|| self.try_find_files(cwd))
-            .unwrap_or_else(Vec::new);
-
-        // Add frecency scores
-        let files: Vec<FileEntry> = files
-            .into_iter()
-            .map(|path| FileEntry {
-                frecency_score: self.get_frecency_score(&path),
-                relative_path: path,
+        let root = std::path::Path::new(cwd);
+        let files: Vec<FileEntry> = IgnoreRules::new(root, exclude)
+            .walker()
+            .hidden(false)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(root).ok()?;
+                Some(FileEntry {
+                    relative_path: relative.to_string_lossy().into_owned(),
+                })
             })
+            .take(MAX_FILES)
             .collect();
 
         // Update cache
@@ -427,91 +410,6 @@ impl FileProvider {
 
         files
     }
-
-    fn try_git_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("git")
-            .args(["ls-files", "--cached", "--others", "--exclude-standard"])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
-        }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty() && !line.starts_with(".git/"))
-            .map(|s| s.to_string())
-            .collect();
-
-        Some(files)
-    }
-
-    fn try_fd_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("fd")
-            .args([
-                "--type",
-                "f",
-                "--hidden",
-                "--exclude",
-                ".git",
-                "--max-results",
-                "50000",
-            ])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
-        }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(|s| s.to_string())
-            .collect();
-
-        Some(files)
-    }
-
-    fn try_find_files(&self, cwd: &str) -> Option<Vec<String>> {
-        let output = std::process::Command::new("find")
-            .args([
-                ".",
-                "-type",
-                "f",
-                "-not",
-                "-path",
-                "*/.git/*",
-                "-not",
-                "-path",
-                "*/node_modules/*",
-                "-not",
-                "-path",
-                "*/target/*",
-                "-not",
-                "-path",
-                "*/__pycache__/*",
-            ])
-            .current_dir(cwd)
-            .output()
-            .ok()?;
-
-        if !output.status.success() {
-            return None;
-        }
-
-        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(|s| s.trim_start_matches("./").to_string())
-            .take(50000)
-            .collect();
-
-        Some(files)
-    }
 }
 
 impl Default for FileProvider {
@@ -534,7 +432,7 @@ impl QuickOpenProvider for FileProvider {
     }
 
     fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
-        let files = self.load_files(&context.cwd);
+        let files = self.load_files(&context.cwd, &context.file_exclude);
 
         if files.is_empty() {
             return vec![Suggestion {
@@ -549,46 +447,24 @@ impl QuickOpenProvider for FileProvider {
 
         let max_results = 100;
 
-        let mut scored_files: Vec<(FileEntry, i32)> = if query.is_empty() {
-            // Sort by frecency when no query
-            let mut files = files;
-            files.sort_by(|a, b| {
-                b.frecency_score
-                    .partial_cmp(&a.frecency_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-            files
-                .into_iter()
-                .take(max_results)
-                .map(|f| (f, 0))
-                .collect()
-        } else {
-            // Filter and score by fuzzy match
-            files
-                .into_iter()
-                .filter_map(|file| {
-                    let match_result = fuzzy_match(query, &file.relative_path);
-                    if match_result.matched {
-                        // Boost score by frecency (normalized)
-                        let frecency_boost = (file.frecency_score / 100.0).min(20.0) as i32;
-                        Some((file, match_result.score + frecency_boost))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        };
-
-        // Sort by score
-        scored_files.sort_by(|a, b| b.1.cmp(&a.1));
+        // Rank by fuzzy match plus frecency; with no query frecency alone decides
+        let cwd = std::path::Path::new(&context.cwd);
+        let unranked = Frecency::new();
+        let frecency = self.frecency.read().ok();
+        let mut scored_files = fuzzy_filter_frecent(
+            &files,
+            |file| fuzzy_match(query, &file.relative_path),
+            |file| cwd.join(&file.relative_path).to_string_lossy().into_owned(),
+            frecency.as_deref().unwrap_or(&unranked),
+        );
         scored_files.truncate(max_results);
 
         scored_files
             .into_iter()
-            .map(|(file, _)| Suggestion {
-                text: file.relative_path.clone(),
+            .map(|(idx, _)| Suggestion {
+                text: files[idx].relative_path.clone(),
                 description: None,
-                value: Some(file.relative_path),
+                value: Some(files[idx].relative_path.clone()),
                 disabled: false,
                 keybinding: None,
                 source: None,
@@ -608,7 +484,7 @@ impl QuickOpenProvider for FileProvider {
             if let Some(suggestion) = suggestions.get(idx) {
                 if let Some(path) = &suggestion.value {
                     // Record access for frecency
-                    self.record_access(path);
+                    self.record_access(&std::path::Path::new(&context.cwd).join(path));
 
                     return QuickOpenResult::OpenFile {
                         path: path.clone(),
@@ -642,6 +518,7 @@ mod tests {
     fn make_test_context() -> QuickOpenContext {
         QuickOpenContext {
             cwd: "/tmp".to_string(),
+            file_exclude: Vec::new(),
             open_buffers: vec![
                 BufferInfo {
                     id: 1,
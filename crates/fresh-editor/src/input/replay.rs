@@ -0,0 +1,400 @@
+//! Recorded input for deterministic session replay
+//!
+//! `--event-log` writes every key press, mouse event, paste and resize to the
+//! log as a JSON line with the time since recording started. `--replay`
+//! (and `EditorTestHarness::replay_log`) feed those inputs back to an editor
+//! in the same order, reconstructing the session. Lines that are not inputs
+//! (the buffer events streamed alongside them, comments) are skipped.
+
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Version written in the session header
+const FORMAT_VERSION: u32 = 1;
+
+/// An input the editor can be fed again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayInput {
+    Key(KeyCode, KeyModifiers),
+    Mouse(MouseEvent),
+    Paste(String),
+    Resize(u16, u16),
+}
+
+/// An input and when it happened, relative to the start of the recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayStep {
+    pub at: Duration,
+    pub input: ReplayInput,
+}
+
+/// A recorded session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayLog {
+    /// Terminal size when recording started
+    pub size: Option<(u16, u16)>,
+    pub steps: Vec<ReplayStep>,
+}
+
+/// One line of the log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Session {
+        version: u32,
+        width: u16,
+        height: u16,
+    },
+    Keystroke {
+        elapsed_ms: u64,
+        key: String,
+        modifiers: Vec<String>,
+    },
+    Mouse {
+        elapsed_ms: u64,
+        kind: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        button: Option<String>,
+        column: u16,
+        row: u16,
+        modifiers: Vec<String>,
+    },
+    Paste {
+        elapsed_ms: u64,
+        text: String,
+    },
+    Resize {
+        elapsed_ms: u64,
+        width: u16,
+        height: u16,
+    },
+}
+
+impl ReplayLog {
+    /// Read a log written with `--event-log`
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// Parse log text, keeping only the input lines
+    pub fn parse(text: &str) -> Self {
+        let mut log = Self::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Ok(record) = serde_json::from_str::<Record>(line) else {
+                continue;
+            };
+            let (elapsed_ms, input) = match record {
+                Record::Session { width, height, .. } => {
+                    log.size = Some((width, height));
+                    continue;
+                }
+                Record::Keystroke {
+                    elapsed_ms,
+                    key,
+                    modifiers,
+                } => match decode_key(&key) {
+                    Some(code) => (
+                        elapsed_ms,
+                        ReplayInput::Key(code, decode_modifiers(&modifiers)),
+                    ),
+                    None => continue,
+                },
+                Record::Mouse {
+                    elapsed_ms,
+                    kind,
+                    button,
+                    column,
+                    row,
+                    modifiers,
+                } => match decode_mouse_kind(&kind, button.as_deref()) {
+                    Some(kind) => (
+                        elapsed_ms,
+                        ReplayInput::Mouse(MouseEvent {
+                            kind,
+                            column,
+                            row,
+                            modifiers: decode_modifiers(&modifiers),
+                        }),
+                    ),
+                    None => continue,
+                },
+                Record::Paste { elapsed_ms, text } => (elapsed_ms, ReplayInput::Paste(text)),
+                Record::Resize {
+                    elapsed_ms,
+                    width,
+                    height,
+                } => (elapsed_ms, ReplayInput::Resize(width, height)),
+            };
+            log.steps.push(ReplayStep {
+                at: Duration::from_millis(elapsed_ms),
+                input,
+            });
+        }
+        log
+    }
+}
+
+/// Writes inputs to an event log as they happen
+#[derive(Debug)]
+pub struct InputRecorder {
+    file: std::fs::File,
+    started: Instant,
+}
+
+impl InputRecorder {
+    /// Create (or truncate) the log and write its header
+    pub fn create(path: &Path, width: u16, height: u16) -> std::io::Result<Self> {
+        let mut file = std::fs::File::create(path)?;
+        writeln!(file, "# Event Log Stream")?;
+        writeln!(file, "# Started at: {}", chrono::Local::now())?;
+        writeln!(file, "# Format: JSON Lines (one event per line)")?;
+        writeln!(file, "# Replay with: fresh --replay {}", path.display())?;
+        writeln!(file, "#")?;
+        writeln!(file, "{}", session_line(width, height))?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+        })
+    }
+
+    /// Another handle to the log file, for buffer event streams
+    ///
+    /// Handles share the file offset, so lines from all of them interleave.
+    pub fn file_handle(&self) -> std::io::Result<std::fs::File> {
+        self.file.try_clone()
+    }
+
+    /// Append an input
+    pub fn record(&mut self, input: &ReplayInput) {
+        let Some(line) = encode_line(input, self.started.elapsed()) else {
+            return;
+        };
+        if let Err(e) = writeln!(self.file, "{line}").and_then(|_| self.file.flush()) {
+            tracing::trace!("Warning: Failed to write input to event log: {e}");
+        }
+    }
+}
+
+/// The log line for an input made `elapsed` after recording started
+///
+/// Inputs that can't be replayed (keys with no name) have none.
+pub fn encode_line(input: &ReplayInput, elapsed: Duration) -> Option<String> {
+    let record = encode(input, elapsed.as_millis() as u64)?;
+    serde_json::to_string(&record).ok()
+}
+
+/// The log line that starts a session recorded at the given terminal size
+pub fn session_line(width: u16, height: u16) -> String {
+    let header = Record::Session {
+        version: FORMAT_VERSION,
+        width,
+        height,
+    };
+    serde_json::to_string(&header).unwrap_or_default()
+}
+
+fn encode(input: &ReplayInput, elapsed_ms: u64) -> Option<Record> {
+    Some(match input {
+        ReplayInput::Key(code, modifiers) => Record::Keystroke {
+            elapsed_ms,
+            key: encode_key(code)?,
+            modifiers: encode_modifiers(*modifiers),
+        },
+        ReplayInput::Mouse(event) => {
+            let (kind, button) = encode_mouse_kind(event.kind);
+            Record::Mouse {
+                elapsed_ms,
+                kind: kind.to_string(),
+                button: button.map(str::to_string),
+                column: event.column,
+                row: event.row,
+                modifiers: encode_modifiers(event.modifiers),
+            }
+        }
+        ReplayInput::Paste(text) => Record::Paste {
+            elapsed_ms,
+            text: text.clone(),
+        },
+        ReplayInput::Resize(width, height) => Record::Resize {
+            elapsed_ms,
+            width: *width,
+            height: *height,
+        },
+    })
+}
+
+/// Key names; any other single character is that character
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("enter", KeyCode::Enter),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("esc", KeyCode::Esc),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+fn encode_key(code: &KeyCode) -> Option<String> {
+    match code {
+        KeyCode::Char(c) => Some(c.to_string()),
+        KeyCode::F(n) => Some(format!("f{}", n)),
+        _ => NAMED_KEYS
+            .iter()
+            .find(|(_, named)| named == code)
+            .map(|(name, _)| name.to_string()),
+    }
+}
+
+fn decode_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    if let Some(n) = key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+        return Some(KeyCode::F(n));
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, code)| *code)
+}
+
+const MODIFIER_NAMES: &[(&str, KeyModifiers)] = &[
+    ("ctrl", KeyModifiers::CONTROL),
+    ("alt", KeyModifiers::ALT),
+    ("shift", KeyModifiers::SHIFT),
+    ("super", KeyModifiers::SUPER),
+];
+
+fn encode_modifiers(modifiers: KeyModifiers) -> Vec<String> {
+    MODIFIER_NAMES
+        .iter()
+        .filter(|(_, m)| modifiers.contains(*m))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+fn decode_modifiers(names: &[String]) -> KeyModifiers {
+    names
+        .iter()
+        .filter_map(|name| MODIFIER_NAMES.iter().find(|(n, _)| n == name))
+        .fold(KeyModifiers::NONE, |acc, (_, m)| acc | *m)
+}
+
+const BUTTON_NAMES: &[(&str, MouseButton)] = &[
+    ("left", MouseButton::Left),
+    ("right", MouseButton::Right),
+    ("middle", MouseButton::Middle),
+];
+
+fn encode_mouse_kind(kind: MouseEventKind) -> (&'static str, Option<&'static str>) {
+    let button_name = |button: MouseButton| {
+        BUTTON_NAMES
+            .iter()
+            .find(|(_, b)| *b == button)
+            .map(|(name, _)| *name)
+    };
+    match kind {
+        MouseEventKind::Down(b) => ("down", button_name(b)),
+        MouseEventKind::Up(b) => ("up", button_name(b)),
+        MouseEventKind::Drag(b) => ("drag", button_name(b)),
+        MouseEventKind::Moved => ("moved", None),
+        MouseEventKind::ScrollDown => ("scroll_down", None),
+        MouseEventKind::ScrollUp => ("scroll_up", None),
+        MouseEventKind::ScrollLeft => ("scroll_left", None),
+        MouseEventKind::ScrollRight => ("scroll_right", None),
+    }
+}
+
+fn decode_mouse_kind(kind: &str, button: Option<&str>) -> Option<MouseEventKind> {
+    let button = || {
+        BUTTON_NAMES
+            .iter()
+            .find(|(name, _)| Some(*name) == button)
+            .map(|(_, b)| *b)
+    };
+    Some(match kind {
+        "down" => MouseEventKind::Down(button()?),
+        "up" => MouseEventKind::Up(button()?),
+        "drag" => MouseEventKind::Drag(button()?),
+        "moved" => MouseEventKind::Moved,
+        "scroll_down" => MouseEventKind::ScrollDown,
+        "scroll_up" => MouseEventKind::ScrollUp,
+        "scroll_left" => MouseEventKind::ScrollLeft,
+        "scroll_right" => MouseEventKind::ScrollRight,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_parse_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.log");
+        let inputs = vec![
+            ReplayInput::Key(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            ReplayInput::Key(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            ReplayInput::Key(KeyCode::F(5), KeyModifiers::NONE),
+            ReplayInput::Key(KeyCode::PageDown, KeyModifiers::ALT),
+            ReplayInput::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 12,
+                row: 3,
+                modifiers: KeyModifiers::NONE,
+            }),
+            ReplayInput::Paste("line one\nline two".to_string()),
+            ReplayInput::Resize(100, 30),
+        ];
+
+        let mut recorder = InputRecorder::create(&path, 80, 24).unwrap();
+        for input in &inputs {
+            recorder.record(input);
+        }
+        // Buffer events share the file and are skipped by the parser
+        writeln!(
+            recorder.file_handle().unwrap(),
+            r#"{{"index":0,"event":{{"Insert":{{"position":0,"text":"x","cursor_id":0}}}}}}"#
+        )
+        .unwrap();
+
+        let log = ReplayLog::load(&path).unwrap();
+        assert_eq!(log.size, Some((80, 24)));
+        let replayed: Vec<_> = log.steps.into_iter().map(|s| s.input).collect();
+        assert_eq!(replayed, inputs);
+    }
+
+    #[test]
+    fn test_parse_skips_unknown_lines() {
+        let log = ReplayLog::parse(
+            "# comment\n\
+             {\"type\":\"keystroke\",\"key\":\"Char('a')\",\"modifiers\":\"KeyModifiers(0x0)\"}\n\
+             {\"type\":\"render\",\"cursor_position\":3}\n\
+             {\"type\":\"keystroke\",\"elapsed_ms\":40,\"key\":\"enter\",\"modifiers\":[]}\n",
+        );
+        assert_eq!(
+            log.steps,
+            vec![ReplayStep {
+                at: Duration::from_millis(40),
+                input: ReplayInput::Key(KeyCode::Enter, KeyModifiers::NONE),
+            }]
+        );
+    }
+}
@@ -5,14 +5,19 @@ use crossterm::event::{
     MouseEvent,
 };
 use fresh::input::key_translator::KeyTranslator;
+use fresh::input::replay::{ReplayInput, ReplayLog};
+use fresh::services::crash_report;
+use fresh::services::event_bus::{self, EventBus, LoopEvent};
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
 use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
 use fresh::services::tracing_setup;
+use fresh::view::hyperlinks::{terminal_supports_hyperlinks, HyperlinkBackend};
 use fresh::{
-    app::Editor,
+    app::{Damage, Editor},
     client, config,
     config_io::DirectoryContext,
+    model::event::SplitDirection,
     model::filesystem::{FileSystem, StdFileSystem},
     server::SocketPaths,
     services::release_checker,
@@ -25,6 +30,7 @@ use ratatui::Terminal;
 use std::{
     io::{self, stdout},
     path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
     time::Duration,
 };
 
@@ -42,6 +48,14 @@ const EXIT_NEW_SESSION: i32 = 2;
     "  config paths              Show directories used by Fresh\n",
     "  init                      Initialize a new plugin/theme/language\n",
     "\n",
+    "Plugin commands:\n",
+    "  plugin install URL|NAME   Install a package from a git URL or the registry\n",
+    "  plugin list               List installed packages\n",
+    "  plugin outdated           Check installed packages for updates\n",
+    "  plugin update [NAME]      Update one or all installed packages\n",
+    "  plugin remove NAME        Remove an installed package\n",
+    "  plugin enable|disable NAME   Enable or disable a package's plugins\n",
+    "\n",
     "Session commands:\n",
     "  session list              List active sessions\n",
     "  session attach [NAME]     Attach to a session (NAME or current dir)\n",
@@ -56,12 +70,21 @@ const EXIT_NEW_SESSION: i32 = 2;
     "  fresh --cmd session new proj                 Start session named 'proj'\n",
     "  fresh --cmd session open-file . main.rs     Open file in current dir session\n",
     "  fresh --cmd session open-file proj a.rs     Open file in 'proj' session\n",
+    "  fresh --cmd plugin install https://github.com/user/fresh-plugin   Install a plugin\n",
+    "  fresh --diff old.rs new.rs                   Compare two files side by side\n",
+    "  fresh --merge LOCAL BASE REMOTE MERGED       Resolve a merge (git mergetool)\n",
+    "  fresh --batch tidy.fs src/*.rs               Run an editor script on files\n",
+    "  fresh --remote-send listBuffers              List buffers of an editor run with --listen\n",
+    "  fresh --dump-keys markdown > keys.md         Export the effective key bindings\n",
+    "  fresh --health                               Check the setup and suggest fixes\n",
+    "  fresh --tutor                                Learn the basics in an interactive tutorial\n",
     "\n",
     "Documentation: https://getfresh.dev/docs"
 ))]
 struct Cli {
     /// Run a command instead of opening files
-    /// Commands: session (list|attach|new|kill|open-file), config (show|paths), init
+    /// Commands: session (list|attach|new|kill|open-file), config (show|paths), init,
+    /// plugin (install|list|outdated|update|remove|enable|disable)
     #[arg(long, num_args = 1.., value_name = "COMMAND")]
     cmd: Vec<String>,
 
@@ -69,7 +92,8 @@ struct Cli {
     #[arg(value_name = "FILES")]
     files: Vec<String>,
 
-    /// Attach to session. Use -a for current dir, -a NAME for named session
+    /// Attach to session. Use -a for current dir, -a NAME for named session,
+    /// -a PATH for a server started with --server PATH
     #[arg(short = 'a', long, value_name = "NAME", num_args = 0..=1, default_missing_value = "")]
     attach: Option<String>,
 
@@ -81,6 +105,26 @@ struct Cli {
     #[arg(long)]
     no_plugins: bool,
 
+    /// Open files read-only (use Toggle Read-Only to edit)
+    #[arg(long)]
+    readonly: bool,
+
+    /// Run as a pager: read-only with less-like keys (q, /, G, F to follow)
+    #[arg(long)]
+    pager: bool,
+
+    /// Open each file in its own horizontal split (stacked)
+    #[arg(short = 'o', conflicts_with = "vsplit")]
+    split: bool,
+
+    /// Open each file in its own vertical split (side by side)
+    #[arg(short = 'O')]
+    vsplit: bool,
+
+    /// Exit once the given files are closed (for use as $GIT_EDITOR)
+    #[arg(long)]
+    wait: bool,
+
     /// Path to configuration file
     #[arg(long, value_name = "PATH")]
     config: Option<PathBuf>,
@@ -89,10 +133,22 @@ struct Cli {
     #[arg(long, value_name = "PATH")]
     log_file: Option<PathBuf>,
 
+    /// Write a flamegraph-compatible profile of startup (folded stacks) to the file
+    #[arg(long, value_name = "PATH")]
+    profile_startup: Option<PathBuf>,
+
     /// Enable event logging to the specified file
     #[arg(long, value_name = "LOG_FILE")]
     event_log: Option<PathBuf>,
 
+    /// Replay the inputs recorded with --event-log
+    #[arg(long, value_name = "LOG_FILE")]
+    replay: Option<PathBuf>,
+
+    /// With --replay, ignore the recorded timing and replay as fast as possible
+    #[arg(long, requires = "replay")]
+    replay_fast: bool,
+
     /// Don't restore previous workspace
     #[arg(long, alias = "no-session")]
     no_restore: bool,
@@ -105,11 +161,45 @@ struct Cli {
     #[arg(long, value_name = "LOCALE")]
     locale: Option<String>,
 
-    // === Hidden internal flags ===
-    /// Start as a daemon server (internal)
-    #[arg(long, hide = true)]
-    server: bool,
+    /// Compare two files side by side (usable as `git difftool`)
+    #[arg(long, num_args = 2, value_names = ["LEFT", "RIGHT"], conflicts_with_all = ["files", "merge"])]
+    diff: Option<Vec<PathBuf>>,
+
+    /// Three-way merge into MERGED; exits with 1 while conflicts remain (usable as `git mergetool`)
+    #[arg(long, num_args = 4, value_names = ["LOCAL", "BASE", "REMOTE", "MERGED"], conflicts_with = "files")]
+    merge: Option<Vec<PathBuf>>,
+
+    /// Run the editor commands in SCRIPT against each file and save the results
+    #[arg(long, value_name = "SCRIPT", conflicts_with_all = ["diff", "merge"])]
+    batch: Option<PathBuf>,
 
+    /// Print the effective key bindings (including remaps) and exit
+    #[arg(long, value_name = "FORMAT")]
+    dump_keys: Option<KeysFormat>,
+
+    /// Check the terminal, config, language servers, formatters and plugins, and exit
+    #[arg(long)]
+    health: bool,
+
+    /// Open the interactive tutorial
+    #[arg(long)]
+    tutor: bool,
+
+    /// Run the editor headless as a server (on SOCKET if given); attach with -a
+    #[arg(long, value_name = "SOCKET", num_args = 0..=1)]
+    server: Option<Option<PathBuf>>,
+
+    /// Accept JSON-RPC requests from other programs on a unix socket (SOCKET,
+    /// or one for the working directory)
+    #[arg(long, value_name = "SOCKET", num_args = 0..=1)]
+    listen: Option<Option<PathBuf>>,
+
+    /// Send a JSON-RPC request to an editor started with --listen and print
+    /// the result (use --listen SOCKET to pick the editor)
+    #[arg(long, num_args = 1..=2, value_names = ["METHOD", "PARAMS"])]
+    remote_send: Option<Vec<String>>,
+
+    // === Hidden internal flags ===
     /// Session name for server mode (internal, used by spawn_server_detached)
     #[arg(long, hide = true, value_name = "NAME")]
     session_name: Option<String>,
@@ -139,9 +229,21 @@ struct Args {
     files: Vec<String>,
     stdin: bool,
     no_plugins: bool,
+    readonly: bool,
+    pager: bool,
+    /// Open files in splits (`-o`/`-O`)
+    split: Option<SplitDirection>,
+    wait: bool,
     config: Option<PathBuf>,
     log_file: Option<PathBuf>,
+    profile_startup: Option<PathBuf>,
     event_log: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    replay_fast: bool,
+    batch: Option<PathBuf>,
+    dump_keys: Option<KeysFormat>,
+    health: bool,
+    tutor: bool,
     no_session: bool,
     no_upgrade_check: bool,
     dump_config: bool,
@@ -149,7 +251,15 @@ struct Args {
     locale: Option<String>,
     check_plugin: Option<PathBuf>,
     init: Option<Option<String>>,
+    /// Arguments of `--cmd plugin`
+    plugin_cmd: Option<Vec<String>>,
     server: bool,
+    /// Explicit server socket (`--server PATH` or `-a PATH`)
+    socket_path: Option<PathBuf>,
+    /// Control socket (`--listen [SOCKET]`)
+    listen: Option<Option<PathBuf>>,
+    /// Request to send to a running editor (`--remote-send METHOD [PARAMS]`)
+    remote_send: Option<Vec<String>>,
     // Session-related fields (set via subcommands or -a shortcut)
     attach: bool,
     list_sessions: bool,
@@ -157,10 +267,62 @@ struct Args {
     kill: Option<Option<String>>,
     /// Open files in a session without attaching (session_name, files)
     open_files_in_session: Option<(Option<String>, Vec<String>)>,
+    /// Standalone comparison requested with --diff or --merge
+    diff_mode: Option<DiffMode>,
+}
+
+/// Output format of `--dump-keys`
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum KeysFormat {
+    Markdown,
+    Text,
+}
+
+/// Files to compare in `--diff`/`--merge` mode
+#[derive(Debug)]
+enum DiffMode {
+    Diff {
+        left: PathBuf,
+        right: PathBuf,
+    },
+    Merge {
+        local: PathBuf,
+        base: PathBuf,
+        remote: PathBuf,
+        merged: PathBuf,
+    },
+}
+
+impl DiffMode {
+    fn from_cli(diff: Option<Vec<PathBuf>>, merge: Option<Vec<PathBuf>>) -> Option<Self> {
+        if let Some([left, right]) = diff.and_then(|paths| <[PathBuf; 2]>::try_from(paths).ok()) {
+            return Some(DiffMode::Diff { left, right });
+        }
+        let [local, base, remote, merged] = <[PathBuf; 4]>::try_from(merge?).ok()?;
+        Some(DiffMode::Merge {
+            local,
+            base,
+            remote,
+            merged,
+        })
+    }
+
+    /// Inputs that must exist (BASE and MERGED may be missing, as with git)
+    fn required_inputs(&self) -> Vec<&PathBuf> {
+        match self {
+            DiffMode::Diff { left, right } => vec![left, right],
+            DiffMode::Merge { local, remote, .. } => vec![local, remote],
+        }
+    }
 }
 
 impl From<Cli> for Args {
     fn from(cli: Cli) -> Self {
+        let plugin_cmd = match cli.cmd.split_first() {
+            Some((first, rest)) if first == "plugin" => Some(rest.to_vec()),
+            _ => None,
+        };
+
         // Parse --cmd arguments to determine command
         let (
             list_sessions,
@@ -313,10 +475,14 @@ impl From<Cli> for Args {
                     cli.files,
                     None,
                 ),
+                // Plugin commands, handled by plugin_command
+                ["plugin", ..] => (
+                    false, None, false, None, false, false, None, cli.files, None,
+                ),
                 // Unknown command
                 _ => {
                     eprintln!("Unknown command: {}", cli.cmd.join(" "));
-                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file), config (show|paths), init");
+                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file), config (show|paths), init, plugin (install|list|outdated|update|remove|enable|disable)");
                     std::process::exit(1);
                 }
             }
@@ -324,8 +490,8 @@ impl From<Cli> for Args {
             // No --cmd - check for -a shortcut and internal flags
             let attach = cli.attach.is_some();
             let session_name = if attach {
-                let name = cli.attach.unwrap();
-                if name.is_empty() || name == "." {
+                let name = cli.attach.clone().unwrap();
+                if name.is_empty() || name == "." || is_socket_path(&name) {
                     cli.session_name
                 } else {
                     Some(name)
@@ -352,9 +518,26 @@ impl From<Cli> for Args {
             files,
             stdin: cli.stdin,
             no_plugins: cli.no_plugins,
+            readonly: cli.readonly,
+            pager: cli.pager,
+            split: if cli.split {
+                Some(SplitDirection::Horizontal)
+            } else if cli.vsplit {
+                Some(SplitDirection::Vertical)
+            } else {
+                None
+            },
+            wait: cli.wait,
             config: cli.config,
             log_file: cli.log_file,
+            profile_startup: cli.profile_startup,
             event_log: cli.event_log,
+            replay: cli.replay,
+            replay_fast: cli.replay_fast,
+            batch: cli.batch,
+            dump_keys: cli.dump_keys,
+            health: cli.health,
+            tutor: cli.tutor,
             no_session: cli.no_restore,
             no_upgrade_check: cli.no_upgrade_check,
             dump_config,
@@ -362,16 +545,30 @@ impl From<Cli> for Args {
             locale: cli.locale,
             check_plugin: cli.check_plugin,
             init,
-            server: cli.server,
+            plugin_cmd,
+            server: cli.server.is_some(),
+            socket_path: cli.server.flatten().or_else(|| {
+                cli.attach
+                    .filter(|name| is_socket_path(name))
+                    .map(PathBuf::from)
+            }),
+            listen: cli.listen,
+            remote_send: cli.remote_send,
             attach,
             list_sessions,
             session_name,
             kill,
             open_files_in_session,
+            diff_mode: DiffMode::from_cli(cli.diff, cli.merge),
         }
     }
 }
 
+/// Whether an `-a` argument names a socket path rather than a session
+fn is_socket_path(name: &str) -> bool {
+    name.contains(std::path::MAIN_SEPARATOR) || name.contains('/')
+}
+
 /// Parsed file location from CLI argument in file:line:col format
 #[derive(Debug)]
 struct FileLocation {
@@ -401,12 +598,13 @@ struct IterationOutcome {
     loop_result: AnyhowResult<()>,
     update_result: Option<release_checker::ReleaseCheckResult>,
     restart_dir: Option<PathBuf>,
+    exit_code: i32,
 }
 
 struct SetupState {
     config: config::Config,
     tracing_handles: Option<TracingHandles>,
-    terminal: Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
     terminal_size: (u16, u16),
     file_locations: Vec<FileLocation>,
     show_file_explorer: bool,
@@ -683,6 +881,9 @@ fn handle_first_run_setup(
     if let Some(handles) = tracing_handles.take() {
         editor.set_warning_log(handles.warning.receiver, handles.warning.path);
         editor.set_status_log_path(handles.status.path);
+        if let Some(profile) = handles.startup_profile {
+            editor.set_startup_profile(profile);
+        }
     }
 
     if workspace_enabled {
@@ -709,31 +910,65 @@ fn handle_first_run_setup(
     // Queue CLI files to be opened after the TUI starts
     // This ensures they go through the same code path as interactive file opens,
     // with consistent error handling (e.g., encoding confirmation prompts in the UI)
+    let mut opened_first = false;
     for loc in file_locations {
         if loc.path.is_dir() {
             continue;
         }
         tracing::info!("[SYNTAX DEBUG] Queueing CLI file for open: {:?}", loc.path);
-        editor.queue_file_open(loc.path.clone(), loc.line, loc.column);
+        match args.split {
+            // -o/-O: the first file takes the existing pane, the rest get a split each
+            Some(direction) if opened_first => {
+                editor.queue_file_open_in_split(loc.path.clone(), loc.line, loc.column, direction)
+            }
+            _ => editor.queue_file_open(loc.path.clone(), loc.line, loc.column),
+        }
+        opened_first = true;
+    }
+
+    if args.wait {
+        let wait_paths: Vec<PathBuf> = file_locations
+            .iter()
+            .filter(|loc| !loc.path.is_dir())
+            .map(|loc| loc.path.clone())
+            .collect();
+        editor.set_wait_for_files(&wait_paths);
+    }
+
+    match &args.diff_mode {
+        Some(DiffMode::Diff { left, right }) => {
+            editor
+                .open_diff_files(left, right)
+                .context("Failed to open diff")?;
+        }
+        Some(DiffMode::Merge {
+            local,
+            base,
+            remote,
+            merged,
+        }) => {
+            editor
+                .open_merge_files(local, base, remote, merged)
+                .context("Failed to open merge")?;
+        }
+        None => {}
     }
 
     if show_file_explorer {
         editor.show_file_explorer();
     }
 
-    if editor.has_recovery_files().unwrap_or(false) {
-        tracing::info!("Recovery files found from previous session, recovering...");
-        match editor.recover_all_buffers() {
-            Ok(count) if count > 0 => {
-                tracing::info!("Recovered {} buffer(s)", count);
-            }
-            Ok(_) => {
-                tracing::info!("No buffers to recover");
-            }
-            Err(e) => {
-                tracing::warn!("Failed to recover buffers: {}", e);
-            }
-        }
+    if args.tutor {
+        editor.show_tutor();
+    }
+
+    editor.report_previous_crash();
+    editor.offer_crash_recovery();
+
+    if let Some(replay_path) = &args.replay {
+        let log = ReplayLog::load(replay_path)
+            .with_context(|| format!("Failed to read replay log {}", replay_path.display()))?;
+        editor.start_replay(log, args.replay_fast);
     }
 
     Ok(())
@@ -979,7 +1214,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         .log_file
         .clone()
         .unwrap_or_else(fresh::services::log_dirs::main_log_path);
-    let tracing_handles = tracing_setup::init_global(&log_file);
+    let tracing_handles = tracing_setup::init_global(&log_file, args.profile_startup.as_deref());
 
     // Clean up stale log files from dead processes on startup
     fresh::services::log_dirs::cleanup_stale_logs();
@@ -990,14 +1225,28 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     tracing::info!("Signal handlers installed");
 
     let original_hook = std::panic::take_hook();
+    let crash_log_file = log_file.clone();
     std::panic::set_hook(Box::new(move |panic| {
         terminal_modes::emergency_cleanup();
+        let report =
+            crash_report::write_report(&crash_report::Panic::capture(panic), &crash_log_file);
         original_hook(panic);
+        match report {
+            Ok(dir) => eprintln!(
+                "\nFresh crashed. A crash report was written to {}\n\
+                 Unsaved changes will be offered for recovery on the next start.",
+                dir.display()
+            ),
+            Err(e) => eprintln!("\nFresh crashed. Failed to write a crash report: {}", e),
+        }
     }));
 
     // Check if we should read from stdin
-    // This can be triggered by --stdin flag or by using "-" as a file argument
-    let stdin_requested = args.stdin || args.files.iter().any(|f| f == "-");
+    // This can be triggered by --stdin flag or by using "-" as a file argument,
+    // and the pager reads piped input without either
+    let stdin_requested = args.stdin
+        || args.files.iter().any(|f| f == "-")
+        || (args.pager && args.files.is_empty() && stdin_has_data());
 
     // Start stdin streaming in background BEFORE entering raw mode
     // This is critical - once raw mode is enabled, stdin is used for terminal events
@@ -1127,6 +1376,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     let dir_context = fresh::config_io::DirectoryContext::from_system()?;
 
+    let load_config_span = tracing::info_span!("load_config").entered();
     let mut config = if let Some(config_path) = &args.config {
         // Explicit config file overrides layered system
         match config::Config::load_from_file(config_path) {
@@ -1143,6 +1393,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     } else {
         config::Config::load_with_layers(&dir_context, &effective_working_dir)
     };
+    load_config_span.exit();
 
     // CLI flag overrides config
     if args.no_upgrade_check {
@@ -1162,7 +1413,8 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         report_alternate_keys: config.editor.keyboard_report_alternate_keys,
         report_all_keys_as_escape_codes: config.editor.keyboard_report_all_keys_as_escape_codes,
     };
-    let terminal_modes = TerminalModes::enable(Some(&keyboard_config))?;
+    let terminal_modes = tracing::info_span!("enable_terminal")
+        .in_scope(|| TerminalModes::enable(Some(&keyboard_config)))?;
 
     #[cfg(target_os = "linux")]
     let gpm_client = match GpmClient::connect() {
@@ -1184,7 +1436,10 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     let _ = stdout().execute(config.editor.cursor_style.to_crossterm_style());
     tracing::info!("Set cursor style to {:?}", config.editor.cursor_style);
 
-    let backend = ratatui::backend::CrosstermBackend::new(stdout());
+    let backend = HyperlinkBackend::new(
+        ratatui::backend::CrosstermBackend::new(stdout()),
+        terminal_supports_hyperlinks(),
+    );
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
@@ -1226,20 +1481,34 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 fn run_editor_iteration(
     editor: &mut Editor,
     workspace_enabled: bool,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
     key_translator: &KeyTranslator,
     #[cfg(target_os = "linux")] gpm_client: &Option<GpmClient>,
 ) -> AnyhowResult<IterationOutcome> {
-    #[cfg(target_os = "linux")]
-    let loop_result = run_event_loop(
-        editor,
-        terminal,
-        workspace_enabled,
-        key_translator,
-        gpm_client,
-    );
-    #[cfg(not(target_os = "linux"))]
-    let loop_result = run_event_loop(editor, terminal, workspace_enabled, key_translator);
+    let loop_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        #[cfg(target_os = "linux")]
+        let loop_result = run_event_loop(
+            editor,
+            terminal,
+            workspace_enabled,
+            key_translator,
+            gpm_client,
+        );
+        #[cfg(not(target_os = "linux"))]
+        let loop_result = run_event_loop(editor, terminal, workspace_enabled, key_translator);
+        loop_result
+    }));
+    let loop_result = match loop_result {
+        Ok(loop_result) => loop_result,
+        Err(panic) => {
+            // The recovery session stays open, so the next start offers
+            // the unsaved changes
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                editor.save_recovery_after_panic();
+            }));
+            std::panic::resume_unwind(panic);
+        }
+    };
 
     if let Err(e) = editor.end_recovery_session() {
         tracing::warn!("Failed to end recovery session: {}", e);
@@ -1247,11 +1516,13 @@ fn run_editor_iteration(
 
     let update_result = editor.get_update_result().cloned();
     let restart_dir = editor.take_restart_dir();
+    let exit_code = editor.diff_mode_exit_code();
 
     Ok(IterationOutcome {
         loop_result,
         update_result,
         restart_dir,
+        exit_code,
     })
 }
 
@@ -2055,7 +2326,12 @@ fn run_server_command(args: &Args) -> AnyhowResult<()> {
     let config = EditorServerConfig {
         working_dir: working_dir.clone(),
         session_name: args.session_name.clone(),
-        idle_timeout: Some(std::time::Duration::from_secs(3600)), // 1 hour default
+        socket_path: args.socket_path.clone(),
+        // 1 hour default; a server the user started on an explicit socket stays up
+        idle_timeout: args
+            .socket_path
+            .is_none()
+            .then(|| std::time::Duration::from_secs(3600)),
         editor_config,
         dir_context,
         plugins_enabled: !args.no_plugins,
@@ -2084,6 +2360,294 @@ fn run_server_command(args: &Args) -> AnyhowResult<()> {
     Ok(())
 }
 
+/// Run a batch script against each file (`--batch`)
+///
+/// Files that fail are left untouched; the exit code is 1 if any failed.
+fn run_batch_command(script_path: &std::path::Path, args: &Args) -> AnyhowResult<()> {
+    use fresh::input::batch::BatchScript;
+
+    let text = std::fs::read_to_string(script_path)
+        .with_context(|| format!("Failed to read batch script {}", script_path.display()))?;
+    let script = BatchScript::parse(&text)
+        .with_context(|| format!("Invalid batch script {}", script_path.display()))?;
+    if args.files.is_empty() {
+        anyhow::bail!("--batch needs at least one file to run on");
+    }
+
+    let working_dir = std::env::current_dir()?;
+    let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+    let config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file(config_path)?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+    let mut editor = Editor::with_working_dir(
+        config,
+        80,
+        24,
+        Some(working_dir),
+        dir_context,
+        false,
+        fresh::view::color_support::ColorCapability::TrueColor,
+        std::sync::Arc::new(StdFileSystem),
+    )?;
+
+    let mut failed = 0;
+    for file in &args.files {
+        let path = std::path::Path::new(file);
+        if !path.is_file() {
+            eprintln!("{}: no such file", path.display());
+            failed += 1;
+            continue;
+        }
+        match editor.run_batch_on_file(path, &script) {
+            Ok(outcome) => tracing::info!("Batch {}: {:?}", path.display(), outcome),
+            Err(e) => {
+                eprintln!("{}: {:#}", path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Send a request to an editor listening on a control socket (`--remote-send`)
+///
+/// Prints the result as JSON; exits with 1 if the editor reports an error.
+fn remote_send_command(request: &[String], args: &Args) -> AnyhowResult<()> {
+    use fresh::services::control_socket;
+
+    let method = &request[0];
+    let params = match request.get(1) {
+        Some(params) => serde_json::from_str(params)
+            .with_context(|| format!("PARAMS is not valid JSON: {}", params))?,
+        None => serde_json::Value::Null,
+    };
+    let socket = match args.listen.clone().flatten() {
+        Some(socket) => socket,
+        None => control_socket::default_socket_path(&std::env::current_dir()?)?,
+    };
+    match control_socket::send_request(&socket, method, params) {
+        Ok(serde_json::Value::Null) => Ok(()),
+        Ok(result) => {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Manage installed packages (`--cmd plugin ...`)
+///
+/// Uses the same directories and lockfile as the package manager plugin, so
+/// packages installed here show up in the editor's package list.
+fn plugin_command(plugin_args: &[String], args: &Args) -> AnyhowResult<()> {
+    use fresh::services::packages::PackageManager;
+
+    let working_dir = std::env::current_dir()?;
+    let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+    let config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file(config_path)?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+    let manager = PackageManager::new(
+        dir_context.config_dir.clone(),
+        config.packages.sources.clone(),
+    );
+
+    let plugin_args: Vec<&str> = plugin_args.iter().map(|s| s.as_str()).collect();
+    match plugin_args.as_slice() {
+        ["install", spec] => {
+            let package = manager.install(spec)?;
+            manager.write_lockfile()?;
+            println!(
+                "Installed {} {} v{} to {}",
+                package.kind.as_str(),
+                package.name,
+                package.version,
+                package.path.display()
+            );
+        }
+        ["list"] | ["ls"] => {
+            let packages = manager.installed();
+            if packages.is_empty() {
+                println!("No packages installed");
+            }
+            for package in packages {
+                let names = package.plugin_names();
+                let disabled = !names.is_empty()
+                    && names
+                        .iter()
+                        .all(|name| config.plugins.get(name).is_some_and(|p| !p.enabled));
+                println!(
+                    "{:<24} {:<9} {:<10} {}{}",
+                    package.name,
+                    package.kind.as_str(),
+                    package.version,
+                    package.source,
+                    if disabled { " (disabled)" } else { "" }
+                );
+            }
+        }
+        ["outdated"] => {
+            let mut outdated = 0;
+            for package in manager.installed() {
+                match manager.commits_behind(&package) {
+                    Ok(Some(behind)) if behind > 0 => {
+                        println!("{:<24} {} commit(s) behind", package.name, behind);
+                        outdated += 1;
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("{}: {:#}", package.name, e),
+                }
+            }
+            if outdated == 0 {
+                println!("All packages are up to date");
+            }
+        }
+        ["update", names @ ..] => {
+            let packages = if names.is_empty() {
+                manager.installed()
+            } else {
+                names
+                    .iter()
+                    .map(|name| manager.find(name))
+                    .collect::<AnyhowResult<Vec<_>>>()?
+            };
+            let mut failed = 0;
+            for package in &packages {
+                match manager.update(package) {
+                    Ok(true) => println!("Updated {}", package.name),
+                    Ok(false) => println!("{} is up to date", package.name),
+                    Err(e) => {
+                        eprintln!("{}: {:#}", package.name, e);
+                        failed += 1;
+                    }
+                }
+            }
+            manager.write_lockfile()?;
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+        ["remove", name] | ["rm", name] => {
+            let package = manager.find(name)?;
+            manager.remove(&package)?;
+            manager.write_lockfile()?;
+            println!("Removed {}", package.name);
+        }
+        [action @ ("enable" | "disable"), name] => {
+            let package = manager.find(name)?;
+            let enabled = *action == "enable";
+            let changes: std::collections::HashMap<String, serde_json::Value> = package
+                .plugin_names()
+                .into_iter()
+                .map(|plugin| {
+                    (
+                        format!("/plugins/{}/enabled", plugin),
+                        serde_json::Value::Bool(enabled),
+                    )
+                })
+                .collect();
+            if changes.is_empty() {
+                anyhow::bail!("'{}' has no plugins to {}", package.name, action);
+            }
+            fresh::config_io::ConfigResolver::new(dir_context, working_dir).save_changes_to_layer(
+                &changes,
+                &std::collections::HashSet::new(),
+                fresh::config_io::ConfigLayer::User,
+            )?;
+            let verb = if enabled { "Enabled" } else { "Disabled" };
+            println!("{} {} (takes effect on restart)", verb, package.name);
+        }
+        _ => {
+            eprintln!("Unknown plugin command: {}", plugin_args.join(" "));
+            eprintln!("Usage: fresh --cmd plugin (install URL|NAME | list | outdated | update [NAME...] | remove NAME | enable NAME | disable NAME)");
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Print the key bindings of the loaded config (`--dump-keys`)
+///
+/// Covers the active keymap, custom bindings and the built-in buffer modes.
+/// Modes that plugins define at runtime are only listed by the in-editor page.
+fn dump_keys_command(format: KeysFormat, args: &Args) -> AnyhowResult<()> {
+    use fresh::input::buffer_mode::ModeRegistry;
+    use fresh::input::key_reference::KeyReference;
+    use fresh::input::keybindings::KeybindingResolver;
+
+    let working_dir = std::env::current_dir()?;
+    let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+    let config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file(config_path)?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+    fresh::i18n::init_with_config(args.locale.as_deref().or(config.locale.as_option()));
+
+    let reference = KeyReference::build(&KeybindingResolver::new(&config), &ModeRegistry::new());
+    match format {
+        KeysFormat::Markdown => print!("{}", reference.to_markdown()),
+        KeysFormat::Text => print!("{}", reference.to_text()),
+    }
+    Ok(())
+}
+
+/// Print the health check report (`--health`); exits with 1 if a check failed
+///
+/// Runs the checks of the `Check Health` command in a headless editor, after
+/// waiting for the plugins to load.
+fn health_command(args: &Args) -> AnyhowResult<()> {
+    use std::io::IsTerminal;
+
+    let working_dir = std::env::current_dir()?;
+    let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+    let config = if let Some(config_path) = &args.config {
+        config::Config::load_from_file(config_path)?
+    } else {
+        config::Config::load_with_layers(&dir_context, &working_dir)
+    };
+    fresh::i18n::init_with_config(args.locale.as_deref().or(config.locale.as_option()));
+
+    let color_capability =
+        fresh::view::color_support::ColorCapability::resolve(config.editor.color_mode);
+    let mut editor = Editor::with_working_dir(
+        config,
+        80,
+        24,
+        Some(working_dir),
+        dir_context,
+        !args.no_plugins,
+        color_capability,
+        std::sync::Arc::new(StdFileSystem),
+    )?;
+    if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        editor.set_keyboard_enhancement(crossterm::terminal::supports_keyboard_enhancement().ok());
+    }
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    while editor.plugins_loading() && std::time::Instant::now() < deadline {
+        editor.process_async_messages();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    let report = editor.health_report();
+    print!("{}", report.to_text());
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 /// Open files in a running session without attaching
 fn run_open_files_command(session_name: Option<&str>, files: &[String]) -> AnyhowResult<()> {
     use fresh::server::daemon::is_process_running;
@@ -2241,8 +2805,10 @@ fn run_attach_command(args: &Args) -> AnyhowResult<()> {
 
     let working_dir = std::env::current_dir()?;
 
-    // Determine socket paths based on session name or working directory
-    let socket_paths = if let Some(ref name) = args.session_name {
+    // Determine socket paths based on socket path, session name or working directory
+    let socket_paths = if let Some(ref path) = args.socket_path {
+        SocketPaths::for_socket_path(path)
+    } else if let Some(ref name) = args.session_name {
         SocketPaths::for_session_name(name)?
     } else {
         SocketPaths::for_working_dir(&working_dir)?
@@ -2253,6 +2819,17 @@ fn run_attach_command(args: &Args) -> AnyhowResult<()> {
         eprintln!("Cleaned up stale session.");
     }
 
+    // A server on an explicit socket is started by the user, not on demand
+    if let Some(ref path) = args.socket_path {
+        if !socket_paths.is_server_alive() {
+            anyhow::bail!(
+                "No server listening on {} (start one with `fresh --server {}`)",
+                path.display(),
+                path.display()
+            );
+        }
+    }
+
     // Check if a server is running, if not start one
     let server_was_started = if !socket_paths.is_server_alive() {
         eprintln!("Starting server...");
@@ -2467,6 +3044,31 @@ fn real_main() -> AnyhowResult<()> {
         }
     }
 
+    // Handle --dump-keys early (no terminal setup needed)
+    if let Some(format) = args.dump_keys {
+        return dump_keys_command(format, &args);
+    }
+
+    // Handle --health early (no terminal setup needed)
+    if args.health {
+        return health_command(&args);
+    }
+
+    // Handle --cmd plugin early (no terminal setup needed)
+    if let Some(plugin_args) = &args.plugin_cmd {
+        return plugin_command(plugin_args, &args);
+    }
+
+    // Handle --remote-send early (no terminal setup needed)
+    if let Some(request) = &args.remote_send {
+        return remote_send_command(request, &args);
+    }
+
+    // Handle --batch early (no terminal setup needed)
+    if let Some(script_path) = &args.batch {
+        return run_batch_command(script_path, &args);
+    }
+
     // Handle --check-plugin early (no terminal setup needed)
     #[cfg(feature = "plugins")]
     if let Some(plugin_path) = &args.check_plugin {
@@ -2503,6 +3105,18 @@ fn real_main() -> AnyhowResult<()> {
         return run_attach_command(&args);
     }
 
+    // Check --diff/--merge inputs before taking over the terminal
+    if let Some(diff_mode) = &args.diff_mode {
+        if let Some(missing) = diff_mode
+            .required_inputs()
+            .into_iter()
+            .find(|p| !p.exists())
+        {
+            eprintln!("Error: {} does not exist", missing.display());
+            std::process::exit(2);
+        }
+    }
+
     let SetupState {
         config,
         mut tracing_handles,
@@ -2526,6 +3140,10 @@ fn real_main() -> AnyhowResult<()> {
 
     let mut current_working_dir = initial_working_dir;
     let (terminal_width, terminal_height) = terminal_size;
+    // Kept for the editors created when switching projects
+    let log_buffer = tracing_handles
+        .as_ref()
+        .map(|handles| handles.log_buffer.clone());
 
     // Track whether this is the first run (for session restore, file open, etc.)
     let mut is_first_run = true;
@@ -2534,17 +3152,20 @@ fn real_main() -> AnyhowResult<()> {
     let mut restore_workspace_on_restart = false;
 
     // Main editor loop - supports restarting with a new working directory
-    // Returns (loop_result, last_update_result) tuple
-    let (result, last_update_result) = loop {
+    // Returns (loop_result, last_update_result, exit_code) tuple
+    let (result, last_update_result, exit_code) = loop {
         let first_run = is_first_run;
-        let workspace_enabled = !args.no_session && file_locations.is_empty();
+        let workspace_enabled =
+            !args.no_session && file_locations.is_empty() && args.diff_mode.is_none();
 
         // Detect terminal color capability
-        let color_capability = fresh::view::color_support::ColorCapability::detect();
+        let color_capability =
+            fresh::view::color_support::ColorCapability::resolve(config.editor.color_mode);
 
         // Use the filesystem created during initialization (supports both local and remote)
         let fs = filesystem.clone();
 
+        let create_editor_span = tracing::info_span!("create_editor").entered();
         let mut editor = Editor::with_working_dir(
             config.clone(),
             terminal_width,
@@ -2556,9 +3177,17 @@ fn real_main() -> AnyhowResult<()> {
             fs,
         )
         .context("Failed to create editor instance")?;
+        create_editor_span.exit();
 
         // Set the process spawner (LocalProcessSpawner for local, RemoteProcessSpawner for remote)
         editor.set_process_spawner(process_spawner.clone());
+        editor.set_screen_links(terminal.backend().links().clone());
+        editor.set_open_read_only(args.readonly);
+        editor.set_pager_mode(args.pager);
+        if let Some(log_buffer) = &log_buffer {
+            editor.set_log_buffer(log_buffer.clone());
+        }
+        editor.set_keyboard_enhancement(Some(terminal_modes.keyboard_enhancement_enabled()));
 
         #[cfg(target_os = "linux")]
         if gpm_client.is_some() {
@@ -2566,6 +3195,7 @@ fn real_main() -> AnyhowResult<()> {
         }
 
         if first_run {
+            let _span = tracing::info_span!("first_run_setup").entered();
             handle_first_run_setup(
                 &mut editor,
                 &args,
@@ -2599,6 +3229,10 @@ fn real_main() -> AnyhowResult<()> {
             editor.set_status_message(fresh::i18n::switched_to_project_message(&path));
         }
 
+        if let Some(socket) = &args.listen {
+            editor.start_control_socket(socket.as_deref());
+        }
+
         if let Err(e) = editor.start_recovery_session() {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
@@ -2616,6 +3250,7 @@ fn real_main() -> AnyhowResult<()> {
         let update_result = iteration.update_result;
         let restart_dir = iteration.restart_dir;
         let loop_result = iteration.loop_result;
+        let exit_code = iteration.exit_code;
 
         drop(editor);
 
@@ -2633,7 +3268,7 @@ fn real_main() -> AnyhowResult<()> {
             continue;
         }
 
-        break (loop_result, update_result);
+        break (loop_result, update_result, exit_code);
     };
 
     // Restore terminal state
@@ -2660,14 +3295,20 @@ fn real_main() -> AnyhowResult<()> {
         }
     }
 
-    result.context("Editor loop returned an error")
+    result.context("Editor loop returned an error")?;
+
+    // --merge reports unresolved conflicts to git mergetool
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
 }
 
 /// Main event loop
 #[cfg(target_os = "linux")]
 fn run_event_loop(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
     workspace_enabled: bool,
     key_translator: &KeyTranslator,
     gpm_client: &Option<GpmClient>,
@@ -2685,7 +3326,7 @@ fn run_event_loop(
 #[cfg(not(target_os = "linux"))]
 fn run_event_loop(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
     workspace_enabled: bool,
     key_translator: &KeyTranslator,
 ) -> AnyhowResult<()> {
@@ -2704,73 +3345,210 @@ fn run_event_loop(
     )
 }
 
+/// Draw the parts of the screen in `damage`
+///
+/// `last_frame` holds the last frame drawn; when only the status bar is
+/// damaged, it is drawn over a copy of that frame.
+fn draw_damage(
+    editor: &mut Editor,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
+    damage: Damage,
+    last_frame: &mut Option<ratatui::buffer::Buffer>,
+) -> AnyhowResult<()> {
+    if damage == Damage::StatusBar {
+        if let Some(previous) = last_frame.as_ref() {
+            let mut frame = terminal.get_frame();
+            if editor.render_status_bar_only(&mut frame, previous) {
+                // Drawing cells moves the terminal cursor, so put it back where
+                // the last full frame left it
+                crossterm::queue!(terminal.backend_mut(), crossterm::cursor::SavePosition)?;
+                terminal.flush()?;
+                crossterm::queue!(terminal.backend_mut(), crossterm::cursor::RestorePosition)?;
+                io::Write::flush(terminal.backend_mut())?;
+                *last_frame = Some(terminal.current_buffer_mut().clone());
+                terminal.swap_buffers();
+                return Ok(());
+            }
+        }
+    }
+    draw_frame(editor, terminal, last_frame)
+}
+
+/// Render and draw the whole screen, keeping it in `last_frame`
+fn draw_frame(
+    editor: &mut Editor,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
+    last_frame: &mut Option<ratatui::buffer::Buffer>,
+) -> AnyhowResult<()> {
+    use std::io::Write;
+
+    let started = std::time::Instant::now();
+    tracing::info_span!("draw_frame").in_scope(|| -> AnyhowResult<()> {
+        let completed = terminal.draw(|frame| editor.render(frame))?;
+        *last_frame = Some(completed.buffer.clone());
+        let graphics = editor.take_graphics_output();
+        if !graphics.is_empty() {
+            let mut out = stdout();
+            out.write_all(&graphics)?;
+            out.flush()?;
+        }
+        Ok(())
+    })?;
+    editor.record_frame_time(started.elapsed());
+    editor.finish_startup_profile();
+    Ok(())
+}
+
+/// Run the main loop, with terminal input read by `poll_event` on its own
+/// thread and sent through the event bus
 fn run_event_loop_common<F>(
     editor: &mut Editor,
-    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
     workspace_enabled: bool,
     _key_translator: &KeyTranslator,
-    mut poll_event: F,
+    poll_event: F,
 ) -> AnyhowResult<()>
 where
-    F: FnMut(Duration) -> AnyhowResult<Option<CrosstermEvent>>,
+    F: FnMut(Duration) -> AnyhowResult<Option<CrosstermEvent>> + Send,
 {
+    let bus = EventBus::new();
+    editor.set_loop_waker(bus.waker());
+    let stop_input = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        let sender = bus.input_sender();
+        let stop = &stop_input;
+        std::thread::Builder::new()
+            .name("terminal-input".to_string())
+            .spawn_scoped(scope, move || {
+                event_bus::read_terminal_input(poll_event, sender, stop)
+            })
+            .context("Failed to start terminal input thread")?;
+
+        let result = handle_loop_events(editor, terminal, workspace_enabled, &bus);
+        stop_input.store(true, Ordering::Release);
+        result
+    })
+}
+
+/// Handle events from the bus, rendering as needed, until the editor quits
+fn handle_loop_events(
+    editor: &mut Editor,
+    terminal: &mut Terminal<HyperlinkBackend<ratatui::backend::CrosstermBackend<io::Stdout>>>,
+    workspace_enabled: bool,
+    bus: &EventBus,
+) -> AnyhowResult<()> {
     use std::time::Instant;
 
     const FRAME_DURATION: Duration = Duration::from_millis(16); // 60fps
     let mut last_render = Instant::now();
-    let mut needs_render = true;
-    let mut pending_event: Option<CrosstermEvent> = None;
+    // How long the last frame took to draw
+    let mut draw_time = Duration::ZERO;
+    let mut damage = Damage::Full;
+    let mut last_frame = None;
+    let mut pending_event: Option<LoopEvent> = None;
 
     loop {
         // Process async messages and poll for file changes (auto-revert, file tree)
         if editor.process_async_messages() {
-            needs_render = true;
+            damage.add(editor.take_async_damage());
         }
 
         // Process pending file opens from CLI arguments
         // This runs after the first render, ensuring files go through the same
         // code path as interactive file opens (with proper UI prompts for errors)
         if editor.process_pending_file_opens() {
-            needs_render = true;
+            damage.add(Damage::Full);
         }
 
         // Check mouse hover timer for LSP hover requests
         if editor.check_mouse_hover_timer() {
-            needs_render = true;
+            damage.add(Damage::Full);
         }
 
         // Check semantic highlight debounce timer
         if editor.check_semantic_highlight_timer() {
-            needs_render = true;
+            damage.add(Damage::Full);
         }
 
         // Check completion trigger timer (debounced quick suggestions)
         if editor.check_completion_trigger_timer() {
-            needs_render = true;
+            damage.add(Damage::Full);
+        }
+
+        // Fire due plugin timers (setTimeout/setInterval)
+        if editor.check_plugin_timers() {
+            damage.add(Damage::Full);
+        }
+
+        // Save anyway if before_file_save handlers are taking too long
+        if editor.check_before_save_timeout() {
+            damage.add(Damage::Full);
+        }
+
+        // Lint buffers after a pause in typing
+        editor.check_lint_timer();
+
+        // Keep counting a large selection for the status bar
+        if editor.check_selection_stats() {
+            damage.add(Damage::StatusBar);
+        }
+
+        // Unload least recently shown buffers when over the memory budget
+        if editor.check_memory_budget() {
+            damage.add(Damage::Full);
         }
 
         // Check for warnings and open warning log if any occurred
         if editor.check_warning_log() {
-            needs_render = true;
+            damage.add(Damage::Full);
+        }
+
+        // Show new log events in the *Log* buffer if it is open
+        if editor.check_log_view() {
+            damage.add(Damage::Full);
+        }
+
+        // Check the tutorial exercises if the tutorial is open
+        if editor.check_tutor() {
+            damage.add(Damage::Full);
+        }
+
+        // Report keybinding conflicts when the bindings change
+        if editor.check_keybinding_conflicts() {
+            damage.add(Damage::Full);
         }
 
         // Poll stdin streaming progress (if active)
         if editor.poll_stdin_streaming() {
-            needs_render = true;
+            damage.add(Damage::Full);
+        }
+
+        // Replayed mouse input must hit the layout of the previous input
+        if editor.poll_replay()? {
+            draw_frame(editor, terminal, &mut last_frame)?;
+            last_render = Instant::now();
+            damage = Damage::None;
         }
 
         if let Err(e) = editor.auto_recovery_save_dirty_buffers() {
             tracing::debug!("Auto-recovery-save error: {}", e);
         }
 
+        if let Err(e) = editor.journal_unsaved_edits() {
+            tracing::debug!("Recovery journal error: {}", e);
+        }
+
         if let Err(e) = editor.auto_save_persistent_buffers() {
             tracing::debug!("Auto-save (disk) error: {}", e);
         }
 
+        editor.local_history_snapshot_buffers();
+
         // Handle hard redraw requests (e.g. after returning from sudo)
         if editor.take_full_redraw_request() {
             terminal.clear()?;
-            needs_render = true;
+            damage.add(Damage::Full);
         }
 
         if editor.should_quit() {
@@ -2784,27 +3562,32 @@ where
             break;
         }
 
-        if needs_render && last_render.elapsed() >= FRAME_DURATION {
-            terminal.draw(|frame| editor.render(frame))?;
+        if damage.is_damaged() && last_render.elapsed() >= FRAME_DURATION {
+            editor.update_crash_context();
+            let draw_started = Instant::now();
+            draw_damage(editor, terminal, damage, &mut last_frame)?;
+            draw_time = draw_started.elapsed();
             last_render = Instant::now();
-            needs_render = false;
+            damage = Damage::None;
         }
 
-        let event = if let Some(e) = pending_event.take() {
-            Some(e)
-        } else {
-            let timeout = if needs_render {
-                FRAME_DURATION.saturating_sub(last_render.elapsed())
-            } else {
-                Duration::from_millis(50)
-            };
+        // Sleep until input, an async message or plugin command, the next
+        // frame (if one is due) or the next timer
+        let loop_event = pending_event.take().or_else(|| {
+            let mut timeout = editor.next_wakeup();
+            if damage.is_damaged() {
+                timeout = timeout.min(FRAME_DURATION.saturating_sub(last_render.elapsed()));
+            }
+            bus.recv_timeout(timeout)
+        });
 
-            poll_event(timeout)?
+        let event = match loop_event {
+            Some(LoopEvent::Input(event)) => event,
+            Some(LoopEvent::InputFailed(e)) => return Err(e),
+            Some(LoopEvent::Wake) | None => continue,
         };
 
-        let Some(event) = event else { continue };
-
-        let (event, next) = coalesce_mouse_moves(event)?;
+        let (event, next) = coalesce_mouse_moves(event, bus);
         pending_event = next;
 
         // Event debug dialog receives ALL RAW events (before any translation or processing)
@@ -2813,7 +3596,7 @@ where
             if let CrosstermEvent::Key(key_event) = event {
                 if key_event.kind == KeyEventKind::Press {
                     editor.handle_event_debug_input(&key_event);
-                    needs_render = true;
+                    damage.add(Damage::Full);
                 }
             }
             // Consume all events while event debug is active
@@ -2827,22 +3610,39 @@ where
                     // Use editor's translator so calibration changes take effect immediately
                     let translated_event = editor.key_translator().translate(key_event);
                     handle_key_event(editor, translated_event)?;
-                    needs_render = true;
+                    damage.add(Damage::Full);
+
+                    // Apply keys that are already queued (fast typing, key
+                    // repeat, a paste without bracketed paste mode) before
+                    // drawing. Spending as long on them as on drawing keeps
+                    // up with input when drawing is slow.
+                    let deadline = Instant::now() + FRAME_DURATION.max(draw_time);
+                    let next = handle_queued_keys(bus, deadline, |key_event| {
+                        if editor.should_quit() || editor.is_event_debug_active() {
+                            return Ok(false);
+                        }
+                        let translated_event = editor.key_translator().translate(key_event);
+                        handle_key_event(editor, translated_event)?;
+                        Ok(true)
+                    })?;
+                    pending_event = pending_event.or(next);
                 }
             }
             CrosstermEvent::Mouse(mouse_event) => {
                 if handle_mouse_event(editor, mouse_event)? {
-                    needs_render = true;
+                    damage.add(Damage::Full);
                 }
             }
             CrosstermEvent::Resize(w, h) => {
+                editor.record_input(&ReplayInput::Resize(w, h));
                 editor.resize(w, h);
-                needs_render = true;
+                damage.add(Damage::Full);
             }
             CrosstermEvent::Paste(text) => {
                 // External paste from terminal (bracketed paste mode)
+                editor.record_input(&ReplayInput::Paste(text.clone()));
                 editor.paste_text(text);
-                needs_render = true;
+                damage.add(Damage::Full);
             }
             _ => {}
         }
@@ -2951,10 +3751,7 @@ fn handle_key_event(editor: &mut Editor, key_event: KeyEvent) -> AnyhowResult<()
         key_event.state
     );
 
-    // Log the keystroke
-    let key_code = format!("{:?}", key_event.code);
-    let modifiers = format!("{:?}", key_event.modifiers);
-    editor.log_keystroke(&key_code, &modifiers);
+    editor.record_input(&ReplayInput::Key(key_event.code, key_event.modifiers));
 
     // Delegate to the editor's handle_key method
     editor.handle_key(key_event.code, key_event.modifiers)?;
@@ -2973,6 +3770,8 @@ fn handle_mouse_event(editor: &mut Editor, mouse_event: MouseEvent) -> AnyhowRes
         mouse_event.modifiers
     );
 
+    editor.record_input(&ReplayInput::Mouse(mouse_event));
+
     // Delegate to the editor's handle_mouse method
     editor
         .handle_mouse(mouse_event)
@@ -2980,32 +3779,103 @@ fn handle_mouse_event(editor: &mut Editor, mouse_event: MouseEvent) -> AnyhowRes
 }
 
 /// Skip stale mouse move events, return the latest one.
-/// If we read a non-move event while draining, return it as pending.
+/// If the next queued event is anything else, return it as pending.
 fn coalesce_mouse_moves(
     event: CrosstermEvent,
-) -> AnyhowResult<(CrosstermEvent, Option<CrosstermEvent>)> {
+    bus: &EventBus,
+) -> (CrosstermEvent, Option<LoopEvent>) {
     use crossterm::event::MouseEventKind;
 
     // Only coalesce mouse moves
     if !matches!(&event, CrosstermEvent::Mouse(m) if m.kind == MouseEventKind::Moved) {
-        return Ok((event, None));
+        return (event, None);
     }
 
     let mut latest = event;
-    while event_poll(Duration::ZERO)? {
-        let next = event_read()?;
-        if matches!(&next, CrosstermEvent::Mouse(m) if m.kind == MouseEventKind::Moved) {
-            latest = next; // Newer move, skip the old one
-        } else {
-            return Ok((latest, Some(next))); // Hit a click/key, save it
+    while let Some(next) = bus.try_recv() {
+        match next {
+            LoopEvent::Input(CrosstermEvent::Mouse(m)) if m.kind == MouseEventKind::Moved => {
+                latest = CrosstermEvent::Mouse(m); // Newer move, skip the old one
+            }
+            other => return (latest, Some(other)), // Hit a click/key, save it
         }
     }
-    Ok((latest, None))
+    (latest, None)
+}
+
+/// Pass key presses already queued on `bus` to `handle`, until `deadline`,
+/// an event that is not a key, or `handle` returns false.
+///
+/// Mouse events are not batched: they hit the layout of the last frame drawn.
+/// Returns the event that stopped the batch, to be handled next.
+fn handle_queued_keys(
+    bus: &EventBus,
+    deadline: std::time::Instant,
+    mut handle: impl FnMut(KeyEvent) -> AnyhowResult<bool>,
+) -> AnyhowResult<Option<LoopEvent>> {
+    while std::time::Instant::now() < deadline {
+        match bus.try_recv() {
+            Some(LoopEvent::Input(CrosstermEvent::Key(key_event))) => {
+                if key_event.kind == KeyEventKind::Press && !handle(key_event)? {
+                    return Ok(None);
+                }
+            }
+            // Async messages are processed before the next frame anyway
+            Some(LoopEvent::Wake) => {}
+            Some(other) => return Ok(Some(other)),
+            None => break,
+        }
+    }
+    Ok(None)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(c: char) -> CrosstermEvent {
+        CrosstermEvent::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_queued_keys_are_handled_until_other_input() {
+        let bus = EventBus::new();
+        let input = bus.input_sender();
+        for event in [key('a'), key('b'), CrosstermEvent::FocusLost, key('c')] {
+            input.send(LoopEvent::Input(event)).unwrap();
+        }
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(60);
+        let mut handled = Vec::new();
+        let next = handle_queued_keys(&bus, deadline, |key_event| {
+            handled.push(key_event.code);
+            Ok(true)
+        })
+        .unwrap();
+
+        assert_eq!(handled, vec![KeyCode::Char('a'), KeyCode::Char('b')]);
+        assert!(matches!(
+            next,
+            Some(LoopEvent::Input(CrosstermEvent::FocusLost))
+        ));
+        // The key after the focus change is still queued
+        assert!(matches!(bus.try_recv(), Some(LoopEvent::Input(_))));
+    }
+
+    #[test]
+    fn test_queued_keys_stop_at_deadline() {
+        let bus = EventBus::new();
+        bus.input_sender().send(LoopEvent::Input(key('a'))).unwrap();
+
+        let next = handle_queued_keys(&bus, std::time::Instant::now(), |_| {
+            panic!("no key should be handled after the deadline")
+        })
+        .unwrap();
+
+        assert!(next.is_none());
+        assert!(bus.try_recv().is_some());
+    }
 
     #[test]
     fn test_parse_file_location_simple_path() {
@@ -216,6 +216,61 @@ impl LineNumber {
     }
 }
 
+/// How [`TextBuffer::save_to_file`] writes files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Write a temporary file and rename it over the destination; when false
+    /// existing files are overwritten in place, keeping their inode
+    pub atomic: bool,
+    /// Number of numbered backups (`name.~1~`, `name.~2~`, ...) to keep
+    pub backup_count: usize,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            atomic: true,
+            backup_count: 0,
+        }
+    }
+}
+
+/// Path of the `n`th numbered backup of `path` (`name.~n~`)
+pub fn numbered_backup_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".~{}~", n));
+    path.with_file_name(name)
+}
+
+/// A content change recorded for the crash recovery journal
+///
+/// Offsets are in bytes and apply in order to the content the buffer had when
+/// journaling started.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum JournalEdit {
+    Insert { offset: usize, text: String },
+    Delete { offset: usize, len: usize },
+}
+
+/// Edits waiting to be written to the recovery journal
+#[derive(Debug, Default)]
+struct EditJournal {
+    edits: Vec<JournalEdit>,
+    /// An edit that can't be expressed as inserts and deletes (undo of a
+    /// bulk edit, whole-content replacement) invalidated the journal
+    broken: bool,
+}
+
+/// Edits taken from a buffer with [`TextBuffer::take_journal_edits`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum JournalEdits {
+    /// Edits to append to the journal
+    Edits(Vec<JournalEdit>),
+    /// The journal can't describe the changes; write a new snapshot instead
+    NeedsSnapshot,
+}
+
 /// A text buffer that manages document content using a piece table
 /// with integrated line tracking
 pub struct TextBuffer {
@@ -277,6 +332,15 @@ pub struct TextBuffer {
 
     /// Monotonic version counter for change tracking.
     version: u64,
+
+    /// How saves write the file
+    save_options: SaveOptions,
+
+    /// Recovery journal of edits, recorded while enabled
+    journal: Option<EditJournal>,
+
+    /// Was the content dropped from memory by `unload`?
+    unloaded: bool,
 }
 
 /// Snapshot of a TextBuffer's piece tree and associated string buffers.
@@ -291,6 +355,16 @@ pub struct BufferSnapshot {
     pub next_buffer_id: usize,
 }
 
+impl std::fmt::Debug for TextBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextBuffer")
+            .field("file_path", &self.file_path)
+            .field("len", &self.len())
+            .field("large_file", &self.large_file)
+            .finish_non_exhaustive()
+    }
+}
+
 impl TextBuffer {
     /// Create a new text buffer with the given filesystem implementation.
     /// Note: large_file_threshold is ignored in the new implementation
@@ -315,6 +389,9 @@ impl TextBuffer {
             original_encoding: encoding,
             saved_file_size: None,
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         }
     }
 
@@ -382,6 +459,9 @@ impl TextBuffer {
             is_binary: true,
             saved_file_size: Some(bytes),
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         }
     }
 
@@ -424,6 +504,9 @@ impl TextBuffer {
             is_binary: false,
             saved_file_size: Some(bytes), // Treat initial content as "saved" state
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         }
     }
 
@@ -470,6 +553,9 @@ impl TextBuffer {
             is_binary: false,
             saved_file_size: Some(bytes),
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         }
     }
 
@@ -505,6 +591,9 @@ impl TextBuffer {
             original_encoding: encoding,
             saved_file_size: None,
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         }
     }
 
@@ -749,6 +838,9 @@ impl TextBuffer {
             original_encoding: encoding,
             saved_file_size: Some(file_size),
             version: 0,
+            save_options: SaveOptions::default(),
+            journal: None,
+            unloaded: false,
         })
     }
 
@@ -1028,18 +1120,36 @@ impl TextBuffer {
     /// the file server-side, avoiding transfer of unchanged content.
     ///
     /// For local filesystems with ownership concerns (file owned by another user),
-    /// uses in-place writing to preserve ownership. Otherwise uses atomic writes.
+    /// or when atomic saves are turned off in [`SaveOptions`], uses in-place
+    /// writing to preserve ownership and the inode. Otherwise uses atomic writes.
+    ///
+    /// If `path` is a symlink its target is written and the link is kept.
     ///
     /// If the line ending format has been changed (via set_line_ending), all content
     /// will be converted to the new format during save.
     pub fn save_to_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
-        let dest_path = path.as_ref();
+        let path = path.as_ref();
+        // Write through symlinks to their target rather than replacing the link
+        let target = if self.fs.exists(path) {
+            self.fs
+                .canonicalize(path)
+                .unwrap_or_else(|_| path.to_path_buf())
+        } else {
+            path.to_path_buf()
+        };
+        let dest_path = target.as_path();
         let total = self.total_bytes();
 
+        self.write_numbered_backup(dest_path);
+
         // Handle empty files
         if total == 0 {
-            self.fs.write_file(dest_path, &[])?;
-            self.finalize_save(dest_path)?;
+            if self.save_options.atomic || !self.fs.exists(dest_path) {
+                self.fs.write_file(dest_path, &[])?;
+            } else {
+                self.write_data_inplace(dest_path, &[], self.fs.metadata_if_exists(dest_path))?;
+            }
+            self.finalize_save(path)?;
             return Ok(());
         }
 
@@ -1050,7 +1160,9 @@ impl TextBuffer {
         // Check if we need in-place writing to preserve file ownership (local only)
         // Remote filesystems handle this differently
         let is_local = self.fs.remote_connection_info().is_none();
-        let use_inplace = is_local && self.should_use_inplace_write(dest_path);
+        let use_inplace = is_local
+            && (self.should_use_inplace_write(dest_path)
+                || (!self.save_options.atomic && self.fs.exists(dest_path)));
 
         if use_inplace {
             // In-place write: write directly to preserve ownership
@@ -1087,10 +1199,38 @@ impl TextBuffer {
             self.fs.write_patched(src_for_patch, dest_path, &ops)?;
         }
 
-        self.finalize_save(dest_path)?;
+        self.finalize_save(path)?;
         Ok(())
     }
 
+    /// Set how [`Self::save_to_file`] writes files
+    pub fn set_save_options(&mut self, options: SaveOptions) {
+        self.save_options = options;
+    }
+
+    /// Copy the file about to be overwritten to `name.~1~`, shifting older
+    /// backups up and dropping those beyond the configured count
+    ///
+    /// Best effort: a failed backup is logged and does not stop the save.
+    fn write_numbered_backup(&self, dest_path: &Path) {
+        let count = self.save_options.backup_count;
+        if count == 0 || !self.fs.is_file(dest_path).unwrap_or(false) {
+            return;
+        }
+        let _ = self.fs.remove_file(&numbered_backup_path(dest_path, count));
+        for n in (1..count).rev() {
+            let from = numbered_backup_path(dest_path, n);
+            if self.fs.exists(&from) {
+                let _ = self
+                    .fs
+                    .rename(&from, &numbered_backup_path(dest_path, n + 1));
+            }
+        }
+        if let Err(e) = self.fs.copy(dest_path, &numbered_backup_path(dest_path, 1)) {
+            tracing::warn!("Failed to back up {}: {}", dest_path.display(), e);
+        }
+    }
+
     /// Write using in-place mode to preserve file ownership.
     ///
     /// This is used when the file is owned by a different user and we need
@@ -1617,9 +1757,11 @@ impl TextBuffer {
         if text.is_empty() {
             return self.piece_tree.cursor_at_offset(offset);
         }
+        self.reload_before_edit();
 
         // Mark as modified (updates version)
         self.mark_content_modified();
+        self.journal_insert(offset, &text);
 
         // Count line feeds in the text to insert
         let line_feed_cnt = Some(text.iter().filter(|&&b| b == b'\n').count());
@@ -1706,8 +1848,13 @@ impl TextBuffer {
             let offset = self.position_to_offset(position);
             return self.piece_tree.cursor_at_offset(offset);
         }
+        self.reload_before_edit();
 
         self.mark_content_modified();
+        if self.journal.is_some() {
+            let offset = self.position_to_offset(position);
+            self.journal_insert(offset, &text);
+        }
 
         // Count line feeds in the text to insert
         let line_feed_cnt = text.iter().filter(|&&b| b == b'\n').count();
@@ -1735,11 +1882,13 @@ impl TextBuffer {
         if bytes == 0 || offset >= self.total_bytes() {
             return;
         }
+        self.reload_before_edit();
 
         // Update piece tree
         self.piece_tree.delete(offset, bytes, &self.buffers);
 
         self.mark_content_modified();
+        self.journal_delete(offset, bytes);
     }
 
     /// Delete text in a range
@@ -1752,6 +1901,12 @@ impl TextBuffer {
     /// Delete text in a line/column range
     /// This now uses the optimized piece_tree.delete_position_range() for a single traversal
     pub fn delete_range(&mut self, start: Position, end: Position) {
+        self.reload_before_edit();
+        if self.journal.is_some() {
+            let start = self.position_to_offset(start);
+            let end = self.position_to_offset(end);
+            self.journal_delete(start, end.saturating_sub(start));
+        }
         // Use the optimized position-based deletion
         self.piece_tree.delete_position_range(
             start.line,
@@ -1770,6 +1925,7 @@ impl TextBuffer {
     /// This is used for bulk operations like "replace all" where applying
     /// individual edits would be prohibitively slow.
     pub fn replace_content(&mut self, new_content: &str) {
+        self.reload_before_edit();
         let bytes = new_content.len();
         let content_bytes = new_content.as_bytes().to_vec();
 
@@ -1795,6 +1951,7 @@ impl TextBuffer {
         }
 
         self.mark_content_modified();
+        self.break_journal();
     }
 
     /// Restore a previously saved buffer state (for undo/redo of BulkEdit).
@@ -1806,7 +1963,10 @@ impl TextBuffer {
         self.piece_tree = snapshot.piece_tree.clone();
         self.buffers = snapshot.buffers.clone();
         self.next_buffer_id = snapshot.next_buffer_id;
+        // The snapshot may have been taken while the content was unloaded
+        self.unloaded = !self.large_file && self.buffers.iter().any(|b| !b.is_loaded());
         self.mark_content_modified();
+        self.break_journal();
     }
 
     /// Snapshot the current buffer state (piece tree + buffers) for BulkEdit undo/redo.
@@ -1825,6 +1985,7 @@ impl TextBuffer {
     /// Apply bulk edits efficiently in a single pass
     /// Returns the net change in bytes
     pub fn apply_bulk_edits(&mut self, edits: &[(usize, usize, &str)]) -> isize {
+        self.reload_before_edit();
         // Pre-allocate buffers for all insert texts (only non-empty texts)
         // This avoids the borrow conflict in the closure
         // IMPORTANT: Only add entries for non-empty texts because the closure
@@ -1856,6 +2017,12 @@ impl TextBuffer {
             });
 
         self.mark_content_modified();
+        // Edits are sorted by descending position, so applying them in order
+        // never shifts a later one
+        for (pos, del_len, text) in edits {
+            self.journal_delete(*pos, *del_len);
+            self.journal_insert(*pos, text.as_bytes());
+        }
         delta
     }
 
@@ -1921,6 +2088,9 @@ impl TextBuffer {
         if bytes == 0 {
             return Ok(Vec::new());
         }
+        if self.unloaded {
+            self.reload()?;
+        }
 
         let mut result = Vec::with_capacity(bytes);
         // Clamp end_offset to buffer length to handle reads beyond EOF
@@ -2228,11 +2398,155 @@ impl TextBuffer {
         self.recovery_pending = pending;
     }
 
+    /// Start or stop recording edits for the recovery journal
+    ///
+    /// Enabling discards anything recorded before, so the journal starts from
+    /// the current content.
+    pub fn set_edit_journal(&mut self, enabled: bool) {
+        self.journal = enabled.then(EditJournal::default);
+    }
+
+    /// Whether edits are being recorded for the recovery journal
+    pub fn is_edit_journal_enabled(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Take the edits recorded since the last call
+    ///
+    /// Returns `None` when journaling is disabled.
+    pub fn take_journal_edits(&mut self) -> Option<JournalEdits> {
+        let journal = std::mem::take(self.journal.as_mut()?);
+        Some(if journal.broken {
+            JournalEdits::NeedsSnapshot
+        } else {
+            JournalEdits::Edits(journal.edits)
+        })
+    }
+
+    fn journal_insert(&mut self, offset: usize, text: &[u8]) {
+        if let Some(journal) = &mut self.journal {
+            if !text.is_empty() {
+                journal.edits.push(JournalEdit::Insert {
+                    offset,
+                    text: String::from_utf8_lossy(text).into_owned(),
+                });
+            }
+        }
+    }
+
+    fn journal_delete(&mut self, offset: usize, len: usize) {
+        if let Some(journal) = &mut self.journal {
+            if len > 0 {
+                journal.edits.push(JournalEdit::Delete { offset, len });
+            }
+        }
+    }
+
+    fn break_journal(&mut self) {
+        if let Some(journal) = &mut self.journal {
+            journal.edits.clear();
+            journal.broken = true;
+        }
+    }
+
     /// Check if this is a large file with lazy loading enabled
     pub fn is_large_file(&self) -> bool {
         self.large_file
     }
 
+    /// Bytes of content and line index held in memory
+    pub fn memory_usage(&self) -> usize {
+        self.buffers.iter().map(StringBuffer::memory_usage).sum()
+    }
+
+    /// Whether the content can be dropped from memory and read back from the
+    /// file later: it is unchanged since it was loaded or saved, and it is
+    /// stored exactly as the file's bytes
+    pub fn can_unload(&self) -> bool {
+        self.file_path.is_some()
+            && !self.unloaded
+            && !self.modified
+            && !self.large_file
+            && !self.is_binary
+            && matches!(self.encoding, Encoding::Utf8 | Encoding::Ascii)
+            && self.encoding == self.original_encoding
+            && self.line_ending == self.original_line_ending
+            && self.total_bytes() > 0
+            && self.saved_file_size == Some(self.total_bytes())
+            && self.buffers.len() == 1
+            && self.buffers[0].is_loaded()
+            && Arc::ptr_eq(&self.piece_tree.root(), &self.saved_root)
+    }
+
+    /// Drop the content from memory, keeping the piece tree and its line
+    /// counts. The content is read back from the file when it is next needed.
+    /// Returns false, keeping the content, if it can't be unloaded.
+    pub fn unload(&mut self) -> bool {
+        if !self.can_unload() {
+            return false;
+        }
+        let Some(path) = self.file_path.clone() else {
+            return false;
+        };
+        self.buffers[0] = StringBuffer::new_unloaded(0, path, 0, self.total_bytes());
+        self.unloaded = true;
+        true
+    }
+
+    /// Whether the content was dropped from memory by `unload`
+    pub fn is_unloaded(&self) -> bool {
+        self.unloaded
+    }
+
+    /// Read the content dropped by `unload` back from the file
+    ///
+    /// Returns false if the file no longer has the content that was dropped;
+    /// the buffer then has the file's new content, as if it had been loaded
+    /// again.
+    pub fn reload(&mut self) -> anyhow::Result<bool> {
+        if !self.unloaded {
+            return Ok(true);
+        }
+        let path = self
+            .file_path
+            .clone()
+            .context("Unloaded buffer has no file")?;
+        let buffer = StringBuffer::new(0, self.fs.read_file(&path)?);
+        let bytes = buffer.get_data().map_or(0, <[u8]>::len);
+        let line_feed_cnt = buffer.line_feed_count();
+        self.unloaded = false;
+
+        if bytes == self.total_bytes() && line_feed_cnt.map(|count| count + 1) == self.line_count()
+        {
+            self.buffers[0] = buffer;
+            return Ok(true);
+        }
+
+        tracing::info!(
+            "Buffer::reload: {} changed on disk while unloaded",
+            path.display()
+        );
+        self.piece_tree = if bytes > 0 {
+            PieceTree::new(BufferLocation::Stored(0), 0, bytes, line_feed_cnt)
+        } else {
+            PieceTree::empty()
+        };
+        self.saved_root = self.piece_tree.root();
+        self.buffers = vec![buffer];
+        self.next_buffer_id = 1;
+        self.saved_file_size = Some(bytes);
+        self.bump_version();
+        Ok(false)
+    }
+
+    /// Read unloaded content back before an edit, whose piece tree update
+    /// needs the line index of the text around it
+    fn reload_before_edit(&mut self) {
+        if let Err(e) = self.reload() {
+            tracing::error!("Failed to read unloaded buffer back before editing: {}", e);
+        }
+    }
+
     /// Get the saved file size (size of the file on disk after last load/save)
     /// For large files, this is used during recovery to know the expected original file size.
     /// Returns None for new unsaved buffers.
@@ -3730,6 +4044,55 @@ mod tests {
         assert_eq!(buffer.get_all_text().unwrap(), b"ba");
     }
 
+    #[test]
+    fn test_edit_journal() {
+        let mut buffer = TextBuffer::from_bytes(b"hello\nworld".to_vec(), test_fs());
+        buffer.insert_bytes(0, b"ignored ".to_vec());
+        assert_eq!(buffer.take_journal_edits(), None);
+
+        buffer.set_edit_journal(true);
+        buffer.insert_bytes(0, b">".to_vec());
+        buffer.delete_range(
+            Position { line: 1, column: 0 },
+            Position { line: 1, column: 5 },
+        );
+        buffer.apply_bulk_edits(&[(5, 1, "!"), (1, 0, "<")]);
+        let Some(JournalEdits::Edits(edits)) = buffer.take_journal_edits() else {
+            panic!("expected journaled edits");
+        };
+        assert_eq!(
+            edits,
+            vec![
+                JournalEdit::Insert {
+                    offset: 0,
+                    text: ">".to_string()
+                },
+                JournalEdit::Delete { offset: 15, len: 5 },
+                JournalEdit::Delete { offset: 5, len: 1 },
+                JournalEdit::Insert {
+                    offset: 5,
+                    text: "!".to_string()
+                },
+                JournalEdit::Insert {
+                    offset: 1,
+                    text: "<".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            buffer.take_journal_edits(),
+            Some(JournalEdits::Edits(vec![]))
+        );
+
+        // Whole-content replacement can only be recovered from a snapshot
+        buffer.insert_bytes(0, b"x".to_vec());
+        buffer.replace_content("new");
+        assert_eq!(
+            buffer.take_journal_edits(),
+            Some(JournalEdits::NeedsSnapshot)
+        );
+    }
+
     // ===== Phase 1-3: Large File Support Tests =====
 
     mod large_file_support {
@@ -3879,6 +4242,59 @@ mod tests {
             assert!(buffer.buffers[0].is_loaded());
         }
 
+        #[test]
+        fn test_unload_and_reload_small_file() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("small.txt");
+            std::fs::write(&file_path, b"hello\nworld\n").unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 0, test_fs()).unwrap();
+            assert!(buffer.memory_usage() > 0);
+            assert!(buffer.unload());
+            assert!(buffer.is_unloaded());
+            assert_eq!(buffer.memory_usage(), 0);
+            // Line counts are kept without the content
+            assert_eq!(buffer.line_count(), Some(3));
+            assert_eq!(buffer.to_string(), None);
+
+            // Reading reloads the content from the file
+            assert_eq!(buffer.get_text_range_mut(0, 5).unwrap(), b"hello");
+            assert!(!buffer.is_unloaded());
+            assert_eq!(buffer.to_string().unwrap(), "hello\nworld\n");
+            assert!(!buffer.is_modified());
+        }
+
+        #[test]
+        fn test_reload_after_file_changed() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("small.txt");
+            std::fs::write(&file_path, b"hello\n").unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 0, test_fs()).unwrap();
+            assert!(buffer.unload());
+            std::fs::write(&file_path, b"changed\non disk\n").unwrap();
+
+            assert!(!buffer.reload().unwrap());
+            assert_eq!(buffer.to_string().unwrap(), "changed\non disk\n");
+            assert_eq!(buffer.line_count(), Some(3));
+        }
+
+        #[test]
+        fn test_modified_buffer_is_not_unloaded() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("small.txt");
+            std::fs::write(&file_path, b"hello\n").unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 0, test_fs()).unwrap();
+            buffer.insert(0, "x");
+            assert!(!buffer.unload());
+            assert_eq!(buffer.to_string().unwrap(), "xhello\n");
+
+            // Buffers without a file have nowhere to read back from
+            let mut scratch = TextBuffer::from_str_test("scratch");
+            assert!(!scratch.unload());
+        }
+
         #[test]
         fn test_load_large_file_lazy_loading() {
             let temp_dir = TempDir::new().unwrap();
@@ -4918,6 +5334,81 @@ mod tests {
 
             Ok(())
         }
+
+        #[test]
+        fn test_save_keeps_numbered_backups() -> anyhow::Result<()> {
+            let temp_dir = tempfile::TempDir::new()?;
+            let file_path = temp_dir.path().join("notes.txt");
+            std::fs::write(&file_path, "v0")?;
+
+            for version in ["v1", "v2", "v3"] {
+                let mut buffer = TextBuffer::from_bytes(version.as_bytes().to_vec(), test_fs());
+                buffer.set_save_options(SaveOptions {
+                    atomic: true,
+                    backup_count: 2,
+                });
+                buffer.save_to_file(&file_path)?;
+            }
+
+            assert_eq!(std::fs::read_to_string(&file_path)?, "v3");
+            let backup = |n| numbered_backup_path(&file_path, n);
+            assert_eq!(backup(1), temp_dir.path().join("notes.txt.~1~"));
+            assert_eq!(std::fs::read_to_string(backup(1))?, "v2");
+            assert_eq!(std::fs::read_to_string(backup(2))?, "v1");
+            assert!(!backup(3).exists());
+            Ok(())
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn test_save_through_symlink_keeps_link() -> anyhow::Result<()> {
+            let temp_dir = tempfile::TempDir::new()?;
+            let target = temp_dir.path().join("target.txt");
+            let link = temp_dir.path().join("link.txt");
+            std::fs::write(&target, "old")?;
+            std::os::unix::fs::symlink(&target, &link)?;
+
+            let mut buffer = TextBuffer::from_bytes(b"new".to_vec(), test_fs());
+            buffer.save_to_file(&link)?;
+
+            assert!(std::fs::symlink_metadata(&link)?.file_type().is_symlink());
+            assert_eq!(std::fs::read_to_string(&target)?, "new");
+            assert_eq!(buffer.file_path(), Some(link.as_path()));
+            Ok(())
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn test_save_preserves_permissions_and_inode_in_place() -> anyhow::Result<()> {
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+            let temp_dir = tempfile::TempDir::new()?;
+            let file_path = temp_dir.path().join("script.sh");
+            std::fs::write(&file_path, "echo old")?;
+            std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o750))?;
+            let inode = std::fs::metadata(&file_path)?.ino();
+
+            // Atomic saves replace the inode but keep the mode
+            let mut buffer = TextBuffer::from_bytes(b"echo atomic".to_vec(), test_fs());
+            buffer.save_to_file(&file_path)?;
+            let meta = std::fs::metadata(&file_path)?;
+            assert_eq!(meta.permissions().mode() & 0o777, 0o750);
+            assert_ne!(meta.ino(), inode);
+
+            // In-place saves keep the inode
+            let inode = meta.ino();
+            let mut buffer = TextBuffer::from_bytes(b"echo in place".to_vec(), test_fs());
+            buffer.set_save_options(SaveOptions {
+                atomic: false,
+                backup_count: 0,
+            });
+            buffer.save_to_file(&file_path)?;
+            let meta = std::fs::metadata(&file_path)?;
+            assert_eq!(std::fs::read_to_string(&file_path)?, "echo in place");
+            assert_eq!(meta.ino(), inode);
+            assert_eq!(meta.permissions().mode() & 0o777, 0o750);
+            Ok(())
+        }
     }
 
     mod large_file_encoding_tests {
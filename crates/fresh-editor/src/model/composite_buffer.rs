@@ -354,6 +354,106 @@ impl LineAlignment {
         Self { rows }
     }
 
+    /// Create alignment for any number of panes from diffs against one pivot pane
+    ///
+    /// `hunks[i]` is the diff from the pivot pane (old) to pane `i` (new); the
+    /// entry for the pivot itself is ignored. Changes that overlap on the pivot
+    /// are grouped under a single hunk header, so e.g. a three-way merge view
+    /// shows both sides of a conflict on the same rows.
+    pub fn from_pivot_hunks(pivot: usize, line_counts: &[usize], hunks: &[Vec<DiffHunk>]) -> Self {
+        let pane_count = line_counts.len();
+
+        // Pivot ranges touched by any pane's hunks, merged where they overlap or touch
+        let mut regions: Vec<(usize, usize)> = hunks
+            .iter()
+            .enumerate()
+            .filter(|(pane, _)| *pane != pivot)
+            .flat_map(|(_, pane_hunks)| pane_hunks.iter())
+            .map(|h| (h.old_start, h.old_start + h.old_count))
+            .collect();
+        regions.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in regions {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        let line_ref = |line: usize| SourceLineRef {
+            line,
+            byte_range: 0..0,
+        };
+        // Offset of each pane's line numbers relative to the pivot, outside of hunks
+        let mut deltas = vec![0isize; pane_count];
+        let mut next_hunk = vec![0usize; pane_count];
+        let mut rows = Vec::new();
+        let mut pivot_line = 0usize;
+
+        let push_context =
+            |rows: &mut Vec<AlignedRow>, from: usize, to: usize, deltas: &[isize]| {
+                for line in from..to {
+                    rows.push(AlignedRow {
+                        pane_lines: deltas
+                            .iter()
+                            .map(|d| Some(line_ref((line as isize + d) as usize)))
+                            .collect(),
+                        row_type: RowType::Context,
+                    });
+                }
+            };
+
+        for (start, end) in merged {
+            push_context(&mut rows, pivot_line, start, &deltas);
+
+            // Line range of each pane covered by this region
+            let ranges: Vec<(usize, usize)> = (0..pane_count)
+                .map(|pane| {
+                    let first = (start as isize + deltas[pane]) as usize;
+                    if pane != pivot {
+                        let pane_hunks = hunks.get(pane).map(Vec::as_slice).unwrap_or(&[]);
+                        while let Some(h) = pane_hunks.get(next_hunk[pane]) {
+                            if h.old_start > end {
+                                break;
+                            }
+                            deltas[pane] += h.new_count as isize - h.old_count as isize;
+                            next_hunk[pane] += 1;
+                        }
+                    }
+                    (first, (end as isize + deltas[pane]) as usize)
+                })
+                .collect();
+
+            rows.push(AlignedRow {
+                pane_lines: vec![None; pane_count],
+                row_type: RowType::HunkHeader,
+            });
+            let height = ranges.iter().map(|(a, b)| b - a).max().unwrap_or(0);
+            for offset in 0..height {
+                let pane_lines: Vec<Option<SourceLineRef>> = ranges
+                    .iter()
+                    .map(|&(a, b)| (a + offset < b).then(|| line_ref(a + offset)))
+                    .collect();
+                let row_type = if pane_lines.iter().all(Option::is_some) {
+                    RowType::Modification
+                } else if pane_lines[pivot].is_none() {
+                    RowType::Addition
+                } else {
+                    RowType::Deletion
+                };
+                rows.push(AlignedRow {
+                    pane_lines,
+                    row_type,
+                });
+            }
+            pivot_line = end;
+        }
+        let pivot_count = line_counts.get(pivot).copied().unwrap_or(0);
+        push_context(&mut rows, pivot_line, pivot_count, &deltas);
+
+        Self { rows }
+    }
+
     /// Get the aligned row at the given display index
     pub fn get_row(&self, display_row: usize) -> Option<&AlignedRow> {
         self.rows.get(display_row)
@@ -518,6 +618,64 @@ mod tests {
         assert_eq!(alignment.rows[2].row_type, RowType::HunkHeader);
     }
 
+    #[test]
+    fn test_line_alignment_from_pivot_hunks() {
+        // local | merged (pivot) | remote
+        // merged: a b c      local: a X c      remote: a b c d
+        let hunks = vec![
+            vec![DiffHunk::new(1, 1, 1, 1)],
+            vec![],
+            vec![DiffHunk::new(3, 0, 3, 1)],
+        ];
+        let alignment = LineAlignment::from_pivot_hunks(1, &[3, 3, 4], &hunks);
+        let lines = |row: &AlignedRow| -> Vec<Option<usize>> {
+            (0..3)
+                .map(|pane| row.get_pane_line(pane).map(|l| l.line))
+                .collect()
+        };
+        let types: Vec<RowType> = alignment.rows.iter().map(|r| r.row_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                RowType::Context,
+                RowType::HunkHeader,
+                RowType::Modification,
+                RowType::Context,
+                RowType::HunkHeader,
+                RowType::Addition,
+            ]
+        );
+        assert_eq!(lines(&alignment.rows[2]), vec![Some(1), Some(1), Some(1)]);
+        assert_eq!(lines(&alignment.rows[3]), vec![Some(2), Some(2), Some(2)]);
+        assert_eq!(lines(&alignment.rows[5]), vec![None, None, Some(3)]);
+    }
+
+    #[test]
+    fn test_line_alignment_from_pivot_hunks_groups_overlapping_changes() {
+        // Both sides change pivot line 1, one of them also line 2
+        let hunks = vec![
+            vec![DiffHunk::new(1, 1, 1, 2)],
+            vec![],
+            vec![DiffHunk::new(1, 2, 1, 1)],
+        ];
+        let alignment = LineAlignment::from_pivot_hunks(1, &[5, 4, 3], &hunks);
+        let headers = alignment
+            .rows
+            .iter()
+            .filter(|r| r.row_type == RowType::HunkHeader)
+            .count();
+        assert_eq!(headers, 1);
+        // Trailing context line is aligned after the combined hunk
+        let last = alignment.rows.last().unwrap();
+        assert_eq!(last.row_type, RowType::Context);
+        assert_eq!(
+            (0..3)
+                .map(|p| last.get_pane_line(p).unwrap().line)
+                .collect::<Vec<_>>(),
+            vec![4, 3, 2]
+        );
+    }
+
     #[test]
     fn test_composite_buffer_focus() {
         let sources = vec![
@@ -600,6 +600,11 @@ impl EventLog {
         self.saved_at_index = Some(self.current_index);
     }
 
+    /// Forget the saved point, for a buffer whose text differs from disk
+    pub fn clear_saved(&mut self) {
+        self.saved_at_index = None;
+    }
+
     /// Check if the buffer is at the saved position (not modified)
     /// Returns true if we're at the saved position OR if all events between
     /// saved_at_index and current_index are readonly (don't modify buffer content)
@@ -645,6 +650,12 @@ impl EventLog {
         Ok(())
     }
 
+    /// Stream events to an already open file (runtime only)
+    #[cfg(feature = "runtime")]
+    pub fn stream_to(&mut self, file: std::fs::File) {
+        self.stream_file = Some(file);
+    }
+
     /// Disable streaming (runtime only)
     #[cfg(feature = "runtime")]
     pub fn disable_streaming(&mut self) {
@@ -759,6 +770,20 @@ impl EventLog {
         self.current_index - 1
     }
 
+    /// Replace the events logged from `index` on with `event`, which has
+    /// the same effect, so that they are undone and redone as one
+    pub fn replace_since(&mut self, index: usize, event: Event) {
+        self.entries.truncate(index);
+        self.current_index = self.entries.len();
+        if self
+            .saved_at_index
+            .is_some_and(|saved| saved > self.current_index)
+        {
+            self.saved_at_index = None;
+        }
+        self.append(event);
+    }
+
     /// Get the current event index
     pub fn current_index(&self) -> usize {
         self.current_index
@@ -676,16 +676,39 @@ impl FileSystem for StdFileSystem {
 
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
         let original_metadata = self.metadata_if_exists(path);
+        let permissions = original_metadata
+            .as_ref()
+            .and_then(|meta| meta.permissions.as_ref());
         let temp_path = self.temp_path_for(path);
         {
-            let mut file = self.create_file(&temp_path)?;
+            // The temp file gets the original's permissions before any data
+            // is written, so a private file is never readable by others
+            #[cfg(unix)]
+            let mut file = {
+                use std::os::unix::fs::OpenOptionsExt;
+                std::fs::OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .mode(permissions.map_or(0o666, |perms| perms.mode() & 0o777))
+                    .open(&temp_path)?
+            };
+            #[cfg(not(unix))]
+            let mut file = std::fs::File::create(&temp_path)?;
+            // Also sets the bits the umask cleared, and the mode of a
+            // leftover temp file, which opening doesn't change
+            if let Some(perms) = permissions {
+                let _ = file.set_permissions(perms.to_std());
+            }
             file.write_all(data)?;
             file.sync_all()?;
         }
         if let Some(ref meta) = original_metadata {
-            if let Some(ref perms) = meta.permissions {
-                let _ = self.set_permissions(&temp_path, perms);
-            }
+            // Best effort: only root can give a file to another owner
+            #[cfg(unix)]
+            let _ = std::os::unix::fs::chown(&temp_path, meta.uid, meta.gid);
+            #[cfg(all(target_os = "linux", feature = "runtime"))]
+            copy_xattrs(path, &temp_path);
         }
         self.rename(&temp_path, path)?;
         Ok(())
@@ -878,6 +901,64 @@ impl FileSystem for StdFileSystem {
     }
 }
 
+/// Copy extended attributes (SELinux labels, ACLs, user tags) from `from` to `to`
+///
+/// Best effort: attributes the process may not read or set are skipped.
+#[cfg(all(target_os = "linux", feature = "runtime"))]
+fn copy_xattrs(from: &Path, to: &Path) {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let (Ok(from), Ok(to)) = (
+        CString::new(from.as_os_str().as_bytes()),
+        CString::new(to.as_os_str().as_bytes()),
+    ) else {
+        return;
+    };
+
+    // SAFETY: every call gets valid NUL-terminated paths and buffers of the
+    // length passed alongside them
+    unsafe {
+        let len = libc::listxattr(from.as_ptr(), std::ptr::null_mut(), 0);
+        if len <= 0 {
+            return;
+        }
+        let mut names = vec![0u8; len as usize];
+        let len = libc::listxattr(from.as_ptr(), names.as_mut_ptr().cast(), names.len());
+        if len <= 0 {
+            return;
+        }
+        names.truncate(len as usize);
+
+        for name in names.split(|&b| b == 0).filter(|n| !n.is_empty()) {
+            let Ok(name) = CString::new(name) else {
+                continue;
+            };
+            let len = libc::getxattr(from.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0);
+            if len < 0 {
+                continue;
+            }
+            let mut value = vec![0u8; len as usize];
+            let len = libc::getxattr(
+                from.as_ptr(),
+                name.as_ptr(),
+                value.as_mut_ptr().cast(),
+                value.len(),
+            );
+            if len < 0 {
+                continue;
+            }
+            libc::setxattr(
+                to.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr().cast(),
+                len as usize,
+                0,
+            );
+        }
+    }
+}
+
 // ============================================================================
 // NoopFileSystem Implementation
 // ============================================================================
@@ -1117,6 +1198,27 @@ mod tests {
         assert_eq!(fs.read_file(&path).unwrap(), b"updated");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_atomic_write_keeps_private_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("secret.txt");
+        std::fs::write(&path, b"initial").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        // A temp file left behind by an earlier save, readable by everyone
+        let temp_path = fs.temp_path_for(&path);
+        std::fs::write(&temp_path, b"stale").unwrap();
+        std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        fs.write_file(&path, b"updated").unwrap();
+        assert_eq!(fs.read_file(&path).unwrap(), b"updated");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
     #[test]
     fn test_write_patched_default_impl() {
         // Test that the default write_patched implementation works correctly
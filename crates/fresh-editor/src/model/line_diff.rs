@@ -4,6 +4,7 @@
 //! insertions, deletions, and modifications. It uses a longest common subsequence (LCS)
 //! approach to identify which lines are unchanged, then marks the ranges that differ.
 
+use crate::model::composite_buffer::DiffHunk;
 use std::ops::Range;
 
 /// Type of change detected for a line range
@@ -82,6 +83,181 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// Compute the changed regions between two texts as aligned hunks.
+///
+/// Each hunk pairs a run of lines in `old` with the run of lines in `new` that
+/// replaced it (either side may be empty). Unchanged lines are not included,
+/// so the result can be fed directly to `LineAlignment::from_hunks`.
+pub fn diff_hunks(old: &[u8], new: &[u8]) -> Vec<DiffHunk> {
+    if old == new {
+        return Vec::new();
+    }
+
+    let old_lines: Vec<&[u8]> = old.split(|&b| b == b'\n').collect();
+    let new_lines: Vec<&[u8]> = new.split(|&b| b == b'\n').collect();
+    let lcs = longest_common_subsequence(&old_lines, &new_lines);
+
+    let mut hunks = Vec::new();
+    let mut old_idx = 0;
+    let mut new_idx = 0;
+    // A sentinel match past the end flushes the trailing hunk
+    let end = LineMatch {
+        saved_idx: old_lines.len(),
+        current_idx: new_lines.len(),
+    };
+    for m in lcs.iter().copied().chain(std::iter::once(end)) {
+        if m.saved_idx > old_idx || m.current_idx > new_idx {
+            hunks.push(DiffHunk::new(
+                old_idx,
+                m.saved_idx - old_idx,
+                new_idx,
+                m.current_idx - new_idx,
+            ));
+        }
+        old_idx = m.saved_idx + 1;
+        new_idx = m.current_idx + 1;
+    }
+    hunks
+}
+
+/// Diffs larger than this many LCS table cells are replaced as one block
+const MAX_TEXT_EDIT_DIFF_CELLS: usize = 1 << 22;
+
+/// Compute the edits that turn `old` into `new`, as `(range in old, replacement)`
+/// pairs in ascending order.
+///
+/// Unchanged lines are left out and each changed run of lines is narrowed to the
+/// bytes that differ, so positions outside the edits (cursors, markers) keep
+/// pointing at the same text after the edits are applied.
+pub fn text_edits(old: &str, new: &str) -> Vec<(Range<usize>, String)> {
+    if old == new {
+        return Vec::new();
+    }
+
+    // Only diff the region between the common leading and trailing lines
+    let prefix = old[..common_prefix_len(old, new)]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    let suffix_len = common_suffix_len(&old[prefix..], &new[prefix..]);
+    let suffix = old[old.len() - suffix_len..]
+        .find('\n')
+        .map_or(0, |i| suffix_len - i - 1);
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let old_starts = line_starts(old_mid);
+    let new_starts = line_starts(new_mid);
+    let hunks = if old_starts.len().saturating_mul(new_starts.len()) > MAX_TEXT_EDIT_DIFF_CELLS {
+        vec![DiffHunk::new(0, old_starts.len(), 0, new_starts.len())]
+    } else {
+        diff_hunks(old_mid.as_bytes(), new_mid.as_bytes())
+    };
+
+    // Pair up the old and new byte ranges that replace each other
+    let mut replacements = Vec::new();
+    for hunk in hunks {
+        if hunk.old_count == hunk.new_count {
+            // Line-for-line changes (re-indentation, spacing) are narrowed per
+            // line so that unchanged text between them is not rewritten
+            for i in 0..hunk.old_count {
+                let (old_line, new_line) = (hunk.old_start + i, hunk.new_start + i);
+                replacements.push((
+                    line_start(&old_starts, old_mid.len(), old_line)
+                        ..line_start(&old_starts, old_mid.len(), old_line + 1).min(old_mid.len()),
+                    line_start(&new_starts, new_mid.len(), new_line)
+                        ..line_start(&new_starts, new_mid.len(), new_line + 1).min(new_mid.len()),
+                ));
+            }
+            continue;
+        }
+
+        let mut old_range = line_start(&old_starts, old_mid.len(), hunk.old_start)
+            ..line_start(&old_starts, old_mid.len(), hunk.old_start + hunk.old_count);
+        let mut new_range = line_start(&new_starts, new_mid.len(), hunk.new_start)
+            ..line_start(&new_starts, new_mid.len(), hunk.new_start + hunk.new_count);
+        if hunk.old_start + hunk.old_count == old_starts.len() {
+            // The last line has no newline of its own, so a hunk touching it
+            // takes the newline before it instead
+            old_range.end = old_mid.len();
+            new_range.end = new_mid.len();
+            if hunk.old_start > 0 && hunk.new_start > 0 {
+                old_range.start -= 1;
+                new_range.start -= 1;
+            }
+        }
+        replacements.push((old_range, new_range));
+    }
+
+    let mut edits = Vec::new();
+    for (old_range, new_range) in replacements {
+        // Narrow each replacement to the bytes that actually differ
+        let old_text = &old_mid[old_range.clone()];
+        let new_text = &new_mid[new_range.clone()];
+        let head = common_prefix_len(old_text, new_text);
+        let tail = common_suffix_len(&old_text[head..], &new_text[head..]);
+        if head == old_text.len() && head == new_text.len() {
+            continue;
+        }
+        edits.push((
+            prefix + old_range.start + head..prefix + old_range.end - tail,
+            new_text[head..new_text.len() - tail].to_string(),
+        ));
+    }
+    edits
+}
+
+/// The edit to `new` that undoes `hunk`, one of the hunks of
+/// `diff_hunks(old, new)`: the range of `new` to replace and the text from
+/// `old` that replaces it
+pub fn revert_hunk_edit(old: &str, new: &str, hunk: &DiffHunk) -> (Range<usize>, String) {
+    let old_starts = line_starts(old);
+    let new_starts = line_starts(new);
+    let mut old_range = line_start(&old_starts, old.len(), hunk.old_start)
+        ..line_start(&old_starts, old.len(), hunk.old_start + hunk.old_count);
+    let mut new_range = line_start(&new_starts, new.len(), hunk.new_start)
+        ..line_start(&new_starts, new.len(), hunk.new_start + hunk.new_count);
+    if hunk.old_start + hunk.old_count == old_starts.len() {
+        // As in `text_edits`, a hunk at the end takes the newline before it
+        old_range.end = old.len();
+        new_range.end = new.len();
+        if hunk.old_start > 0 && hunk.new_start > 0 {
+            old_range.start -= 1;
+            new_range.start -= 1;
+        }
+    }
+    (new_range, old[old_range].to_string())
+}
+
+/// Byte offset of the start of each line (split on `\n`, like `diff_hunks`)
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Start of line `line`, or one past the end for the line after the last
+fn line_start(starts: &[usize], len: usize, line: usize) -> usize {
+    starts.get(line).copied().unwrap_or(len + 1)
+}
+
+/// Length in bytes of the common prefix of `a` and `b`, on a char boundary
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, ca), cb)| ca != cb)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
+
+/// Length in bytes of the common suffix of `a` and `b`, on a char boundary
+fn common_suffix_len(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(ca, cb)| ca == cb)
+        .map(|(c, _)| c.len_utf8())
+        .sum()
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -486,6 +662,79 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    fn hunk_ranges(old: &[u8], new: &[u8]) -> Vec<(Range<usize>, Range<usize>)> {
+        diff_hunks(old, new)
+            .into_iter()
+            .map(|h| {
+                (
+                    h.old_start..h.old_start + h.old_count,
+                    h.new_start..h.new_start + h.new_count,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_hunks_identical() {
+        assert!(diff_hunks(b"a\nb\n", b"a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_diff_hunks_mixed_changes() {
+        let old = b"a\nb\nc\nd\ne\n";
+        let new = b"a\nB\nc\ne\nf\n";
+        // b -> B is a modification, d is deleted, f is inserted before the final empty line
+        assert_eq!(
+            hunk_ranges(old, new),
+            vec![(1..2, 1..2), (3..4, 3..3), (5..5, 4..5)]
+        );
+    }
+
+    #[test]
+    fn test_diff_hunks_against_empty() {
+        assert_eq!(hunk_ranges(b"", b"x\ny"), vec![(0..1, 0..2)]);
+    }
+
+    #[test]
+    fn test_text_edits_are_narrowed_to_changed_bytes() {
+        let old = "fn main() {\nlet x = 1;\n}\n";
+        let new = "fn main() {\n    let x = 1;\n}\n";
+        assert_eq!(text_edits(old, new), vec![(12..12, "    ".to_string())]);
+
+        // Separate changes stay separate, leaving the text between them alone
+        let old = "a = 1\nb = 2\nc = 3\n";
+        let new = "a = 10\nb = 2\nc = 30\n";
+        assert_eq!(
+            text_edits(old, new),
+            vec![(5..5, "0".to_string()), (17..17, "0".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_text_edits_trailing_lines() {
+        assert_eq!(text_edits("a\nb", "a"), vec![(1..3, String::new())]);
+        assert_eq!(text_edits("a", "a\nb"), vec![(1..1, "\nb".to_string())]);
+        assert_eq!(text_edits("a\n", "a"), vec![(1..2, String::new())]);
+    }
+
+    #[test]
+    fn test_revert_hunk_edit() {
+        let revert = |old: &str, new: &str, index: usize| {
+            let hunk = &diff_hunks(old.as_bytes(), new.as_bytes())[index];
+            let (range, text) = revert_hunk_edit(old, new, hunk);
+            let mut result = new.to_string();
+            result.replace_range(range, &text);
+            result
+        };
+        assert_eq!(revert("a\nb\nc\n", "a\nB\nc\n", 0), "a\nb\nc\n");
+        assert_eq!(revert("a\nc\n", "a\nb\nc\n", 0), "a\nc\n");
+        assert_eq!(revert("a\nb\nc\n", "a\nc\n", 0), "a\nb\nc\n");
+        assert_eq!(revert("a\nb", "a", 0), "a\nb");
+        assert_eq!(revert("a", "a\nb", 0), "a");
+        // Only the chosen hunk is reverted
+        assert_eq!(revert("a\nb\nc\nd\n", "A\nb\nc\nD\n", 1), "A\nb\nc\nd\n");
+    }
 }
 
 #[cfg(test)]
@@ -502,6 +751,21 @@ mod proptests {
     }
 
     proptest! {
+        /// Applying the edits from text_edits to the old text yields the new text
+        #[test]
+        fn text_edits_reproduce_new_text(
+            old in multiline_string(),
+            new in multiline_string()
+        ) {
+            let old = String::from_utf8(old).unwrap();
+            let new = String::from_utf8(new).unwrap();
+            let mut result = old.clone();
+            for (range, text) in text_edits(&old, &new).into_iter().rev() {
+                result.replace_range(range, &text);
+            }
+            prop_assert_eq!(result, new);
+        }
+
         /// Identical content should always produce equal=true
         #[test]
         fn identical_content_is_equal(content in multiline_string()) {
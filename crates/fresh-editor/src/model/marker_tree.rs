@@ -237,7 +237,7 @@ impl IntervalTree {
             return false;
         }
 
-        self.root = Self::delete_recursive(self.root.take(), start, id);
+        self.root = Self::delete_recursive(self.root.take(), start, id, &mut self.marker_map);
 
         self.marker_map.remove(&id).is_some()
     }
@@ -327,7 +327,12 @@ impl IntervalTree {
     }
 
     /// Recursive helper for delete
-    fn delete_recursive(root: NodePtr, start: u64, id: MarkerId) -> NodePtr {
+    fn delete_recursive(
+        root: NodePtr,
+        start: u64,
+        id: MarkerId,
+        marker_map: &mut HashMap<MarkerId, Rc<RefCell<Node>>>,
+    ) -> NodePtr {
         // Remove unnecessary 'mut'
         let root = root?;
 
@@ -338,20 +343,23 @@ impl IntervalTree {
 
         match start.cmp(&root_start) {
             Ordering::Less => {
-                root_mut.left = Self::delete_recursive(root_mut.left.take(), start, id);
+                root_mut.left = Self::delete_recursive(root_mut.left.take(), start, id, marker_map);
             }
             Ordering::Greater => {
-                root_mut.right = Self::delete_recursive(root_mut.right.take(), start, id);
+                root_mut.right =
+                    Self::delete_recursive(root_mut.right.take(), start, id, marker_map);
             }
             Ordering::Equal => match id.cmp(&root_id) {
                 Ordering::Less => {
-                    root_mut.left = Self::delete_recursive(root_mut.left.take(), start, id);
+                    root_mut.left =
+                        Self::delete_recursive(root_mut.left.take(), start, id, marker_map);
                 }
                 Ordering::Greater => {
-                    root_mut.right = Self::delete_recursive(root_mut.right.take(), start, id);
+                    root_mut.right =
+                        Self::delete_recursive(root_mut.right.take(), start, id, marker_map);
                 }
                 Ordering::Equal => {
-                    return Self::perform_node_deletion(root_mut, Rc::clone(&root));
+                    return Self::perform_node_deletion(root_mut, Rc::clone(&root), marker_map);
                 }
             },
         }
@@ -362,7 +370,11 @@ impl IntervalTree {
     }
 
     /// Handles the actual structural changes for deletion.
-    fn perform_node_deletion(mut node: RefMut<Node>, node_rc: Rc<RefCell<Node>>) -> NodePtr {
+    fn perform_node_deletion(
+        mut node: RefMut<Node>,
+        node_rc: Rc<RefCell<Node>>,
+        marker_map: &mut HashMap<MarkerId, Rc<RefCell<Node>>>,
+    ) -> NodePtr {
         if node.left.is_none() {
             let right = node.right.take();
             if let Some(ref r) = right {
@@ -378,14 +390,18 @@ impl IntervalTree {
         } else {
             let successor_rc = Self::min_node(node.right.as_ref().unwrap());
 
-            let (successor_start, successor_id) = {
+            // The successor's marker moves into this node, so its id must
+            // point here; the marker being deleted takes the successor's place
+            // (still the minimum of the right subtree) and is removed there
+            mem::swap(&mut node.marker, &mut successor_rc.borrow_mut().marker);
+            marker_map.insert(node.marker.id, Rc::clone(&node_rc));
+            let (deleted_start, deleted_id) = {
                 let s = successor_rc.borrow();
                 (s.marker.interval.start, s.marker.id)
             };
 
-            mem::swap(&mut node.marker, &mut successor_rc.borrow_mut().marker);
-
-            node.right = Self::delete_recursive(node.right.take(), successor_start, successor_id);
+            node.right =
+                Self::delete_recursive(node.right.take(), deleted_start, deleted_id, marker_map);
 
             drop(node);
             Node::update_stats(&node_rc);
@@ -802,6 +818,24 @@ mod tests {
         assert_eq!(get_pos(&tree, id1), (0, 5), "Engulfing deletion at pos 0.");
     }
 
+    #[test]
+    fn test_delete_node_with_two_children() {
+        let mut tree = IntervalTree::new();
+        let id1 = insert_marker(&mut tree, 10, 10);
+        let id2 = insert_marker(&mut tree, 20, 20);
+        let id3 = insert_marker(&mut tree, 30, 30);
+
+        // The middle marker is the root, with a child on each side
+        assert!(tree.delete(id2));
+        tree.adjust_for_edit(0, 5);
+
+        assert_eq!(get_pos(&tree, id1), (15, 15));
+        assert_eq!(get_pos(&tree, id3), (35, 35));
+        let mut ids: Vec<MarkerId> = tree.query(0, 100).into_iter().map(|m| m.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![id1, id3]);
+    }
+
     #[test]
     fn test_deletion_preserves_marker_ordering() {
         // This test reproduces the bug found in prop_marker_ordering_preserved
@@ -79,6 +79,19 @@ impl StringBuffer {
         matches!(self.data, BufferData::Loaded { .. })
     }
 
+    /// Bytes of data and line starts held in memory
+    pub fn memory_usage(&self) -> usize {
+        match &self.data {
+            BufferData::Loaded { data, line_starts } => {
+                data.len()
+                    + line_starts
+                        .as_ref()
+                        .map_or(0, |starts| starts.len() * std::mem::size_of::<usize>())
+            }
+            BufferData::Unloaded { .. } => 0,
+        }
+    }
+
     /// Get data reference if loaded, None if unloaded
     ///
     /// NOTE: This is a low-level API. External code should use TextBuffer::get_text_range_mut()
@@ -4,10 +4,11 @@
 //! enabling a 4-level overlay architecture (System → User → Project → Session).
 
 use crate::config::{
-    AcceptSuggestionOnEnter, ClipboardConfig, CursorStyle, FileBrowserConfig, FileExplorerConfig,
-    FormatterConfig, HighlighterPreference, Keybinding, KeybindingMapName, KeymapConfig,
-    LanguageConfig, LineEndingOption, OnSaveAction, PluginConfig, TerminalConfig, ThemeName,
-    WarningsConfig,
+    AcceptSuggestionOnEnter, AmbiguousWidth, ClipboardConfig, ColorMode, CsvConfig, CursorStyle,
+    DiagnosticsConfig, FileBrowserConfig, FileExplorerConfig, FilesConfig, FormatterConfig,
+    HighlighterPreference, ImagePreviewMode, Keybinding, KeybindingMapName, KeymapConfig,
+    LanguageConfig, LineEndingOption, LinterConfig, OnSaveAction, PluginConfig, TerminalConfig,
+    ThemeName, TodoConfig, UserCommand, WarningsConfig,
 };
 use crate::types::LspServerConfig;
 use serde::{Deserialize, Serialize};
@@ -79,6 +80,7 @@ pub struct PartialConfig {
     pub locale: Option<String>,
     pub check_for_updates: Option<bool>,
     pub editor: Option<PartialEditorConfig>,
+    pub files: Option<PartialFilesConfig>,
     pub file_explorer: Option<PartialFileExplorerConfig>,
     pub file_browser: Option<PartialFileBrowserConfig>,
     pub clipboard: Option<PartialClipboardConfig>,
@@ -86,9 +88,13 @@ pub struct PartialConfig {
     pub keybindings: Option<Vec<Keybinding>>,
     pub keybinding_maps: Option<HashMap<String, KeymapConfig>>,
     pub active_keybinding_map: Option<KeybindingMapName>,
+    pub commands: Option<HashMap<String, UserCommand>>,
     pub languages: Option<HashMap<String, PartialLanguageConfig>>,
     pub lsp: Option<HashMap<String, LspServerConfig>>,
     pub warnings: Option<PartialWarningsConfig>,
+    pub diagnostics: Option<PartialDiagnosticsConfig>,
+    pub todo: Option<PartialTodoConfig>,
+    pub csv: Option<PartialCsvConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
 }
@@ -102,11 +108,15 @@ impl Merge for PartialConfig {
 
         // Nested structs: merge recursively
         merge_partial(&mut self.editor, &other.editor);
+        merge_partial(&mut self.files, &other.files);
         merge_partial(&mut self.file_explorer, &other.file_explorer);
         merge_partial(&mut self.file_browser, &other.file_browser);
         merge_partial(&mut self.clipboard, &other.clipboard);
         merge_partial(&mut self.terminal, &other.terminal);
         merge_partial(&mut self.warnings, &other.warnings);
+        merge_partial(&mut self.diagnostics, &other.diagnostics);
+        merge_partial(&mut self.todo, &other.todo);
+        merge_partial(&mut self.csv, &other.csv);
         merge_partial(&mut self.packages, &other.packages);
 
         // Lists: higher precedence replaces (per design doc)
@@ -114,6 +124,7 @@ impl Merge for PartialConfig {
 
         // HashMaps: merge entries, higher precedence wins on key collision
         merge_hashmap(&mut self.keybinding_maps, &other.keybinding_maps);
+        merge_hashmap(&mut self.commands, &other.commands);
         merge_hashmap_recursive(&mut self.languages, &other.languages);
         merge_hashmap_recursive(&mut self.lsp, &other.lsp);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
@@ -137,11 +148,19 @@ fn merge_partial<T: Merge + Clone>(target: &mut Option<T>, other: &Option<T>) {
 #[serde(default)]
 pub struct PartialEditorConfig {
     pub tab_size: Option<usize>,
+    pub detect_indentation: Option<bool>,
+    pub read_editorconfig: Option<bool>,
     pub auto_indent: Option<bool>,
+    pub increment_cycles: Option<Vec<String>>,
+    pub include_paths: Option<Vec<String>>,
+    pub reindent_on_paste: Option<bool>,
+    pub subword_movement: Option<bool>,
     pub line_numbers: Option<bool>,
     pub relative_line_numbers: Option<bool>,
     pub scroll_offset: Option<usize>,
     pub syntax_highlighting: Option<bool>,
+    pub highlight_trailing_whitespace: Option<bool>,
+    pub hyperlinks: Option<bool>,
     pub line_wrap: Option<bool>,
     pub highlight_timeout_ms: Option<u64>,
     pub snapshot_interval: Option<usize>,
@@ -151,6 +170,10 @@ pub struct PartialEditorConfig {
     pub enable_semantic_tokens_full: Option<bool>,
     pub recovery_enabled: Option<bool>,
     pub auto_recovery_save_interval_secs: Option<u32>,
+    pub local_history_enabled: Option<bool>,
+    pub local_history_interval_secs: Option<u32>,
+    pub local_history_max_snapshots: Option<usize>,
+    pub local_history_max_age_days: Option<u32>,
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub highlight_context_bytes: Option<usize>,
@@ -159,12 +182,24 @@ pub struct PartialEditorConfig {
     pub double_click_time_ms: Option<u64>,
     pub auto_revert_poll_interval_ms: Option<u64>,
     pub file_tree_poll_interval_ms: Option<u64>,
+    pub git_status_poll_interval_ms: Option<u64>,
+    pub memory_budget_mb: Option<u64>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub atomic_save: Option<bool>,
+    pub save_backup_count: Option<usize>,
+    pub before_save_timeout_ms: Option<u64>,
+    pub file_templates: Option<bool>,
+    pub template_author: Option<String>,
+    pub template_license: Option<String>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
+    pub rainbow_delimiters: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
+    pub color_mode: Option<ColorMode>,
+    pub image_preview: Option<ImagePreviewMode>,
+    pub ambiguous_width: Option<AmbiguousWidth>,
     pub keyboard_disambiguate_escape_codes: Option<bool>,
     pub keyboard_report_event_types: Option<bool>,
     pub keyboard_report_alternate_keys: Option<bool>,
@@ -177,6 +212,8 @@ pub struct PartialEditorConfig {
     pub show_tab_bar: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_selection_stats: Option<bool>,
+    pub selection_stats_sum: Option<bool>,
     pub use_terminal_bg: Option<bool>,
     pub rulers: Option<Vec<usize>>,
 }
@@ -184,13 +221,23 @@ pub struct PartialEditorConfig {
 impl Merge for PartialEditorConfig {
     fn merge_from(&mut self, other: &Self) {
         self.tab_size.merge_from(&other.tab_size);
+        self.detect_indentation
+            .merge_from(&other.detect_indentation);
+        self.read_editorconfig.merge_from(&other.read_editorconfig);
         self.auto_indent.merge_from(&other.auto_indent);
+        self.increment_cycles.merge_from(&other.increment_cycles);
+        self.include_paths.merge_from(&other.include_paths);
+        self.reindent_on_paste.merge_from(&other.reindent_on_paste);
+        self.subword_movement.merge_from(&other.subword_movement);
         self.line_numbers.merge_from(&other.line_numbers);
         self.relative_line_numbers
             .merge_from(&other.relative_line_numbers);
         self.scroll_offset.merge_from(&other.scroll_offset);
         self.syntax_highlighting
             .merge_from(&other.syntax_highlighting);
+        self.highlight_trailing_whitespace
+            .merge_from(&other.highlight_trailing_whitespace);
+        self.hyperlinks.merge_from(&other.hyperlinks);
         self.line_wrap.merge_from(&other.line_wrap);
         self.highlight_timeout_ms
             .merge_from(&other.highlight_timeout_ms);
@@ -206,6 +253,14 @@ impl Merge for PartialEditorConfig {
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_recovery_save_interval_secs
             .merge_from(&other.auto_recovery_save_interval_secs);
+        self.local_history_enabled
+            .merge_from(&other.local_history_enabled);
+        self.local_history_interval_secs
+            .merge_from(&other.local_history_interval_secs);
+        self.local_history_max_snapshots
+            .merge_from(&other.local_history_max_snapshots);
+        self.local_history_max_age_days
+            .merge_from(&other.local_history_max_age_days);
         self.auto_save_enabled.merge_from(&other.auto_save_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
@@ -221,16 +276,31 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.auto_revert_poll_interval_ms);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
+        self.git_status_poll_interval_ms
+            .merge_from(&other.git_status_poll_interval_ms);
+        self.memory_budget_mb.merge_from(&other.memory_budget_mb);
         self.default_line_ending
             .merge_from(&other.default_line_ending);
         self.trim_trailing_whitespace_on_save
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        self.atomic_save.merge_from(&other.atomic_save);
+        self.save_backup_count.merge_from(&other.save_backup_count);
+        self.before_save_timeout_ms
+            .merge_from(&other.before_save_timeout_ms);
+        self.file_templates.merge_from(&other.file_templates);
+        self.template_author.merge_from(&other.template_author);
+        self.template_license.merge_from(&other.template_license);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
+        self.rainbow_delimiters
+            .merge_from(&other.rainbow_delimiters);
         self.cursor_style.merge_from(&other.cursor_style);
+        self.color_mode.merge_from(&other.color_mode);
+        self.image_preview.merge_from(&other.image_preview);
+        self.ambiguous_width.merge_from(&other.ambiguous_width);
         self.keyboard_disambiguate_escape_codes
             .merge_from(&other.keyboard_disambiguate_escape_codes);
         self.keyboard_report_event_types
@@ -252,11 +322,28 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_selection_stats
+            .merge_from(&other.show_selection_stats);
+        self.selection_stats_sum
+            .merge_from(&other.selection_stats_sum);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
         self.rulers.merge_from(&other.rulers);
     }
 }
 
+/// Partial project files configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialFilesConfig {
+    pub exclude: Option<Vec<String>>,
+}
+
+impl Merge for PartialFilesConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.exclude.merge_from(&other.exclude);
+    }
+}
+
 /// Partial file explorer configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -336,6 +423,59 @@ impl Merge for PartialWarningsConfig {
     }
 }
 
+/// Partial inline diagnostics configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialDiagnosticsConfig {
+    pub inline_text: Option<bool>,
+    pub line_background: Option<bool>,
+    pub show_errors: Option<bool>,
+    pub show_warnings: Option<bool>,
+    pub show_info: Option<bool>,
+    pub show_hints: Option<bool>,
+}
+
+impl Merge for PartialDiagnosticsConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.inline_text.merge_from(&other.inline_text);
+        self.line_background.merge_from(&other.line_background);
+        self.show_errors.merge_from(&other.show_errors);
+        self.show_warnings.merge_from(&other.show_warnings);
+        self.show_info.merge_from(&other.show_info);
+        self.show_hints.merge_from(&other.show_hints);
+    }
+}
+
+/// Partial TODO comment configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialTodoConfig {
+    pub tags: Option<Vec<String>>,
+}
+
+impl Merge for PartialTodoConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.tags.merge_from(&other.tags);
+    }
+}
+
+/// Partial CSV/TSV table view configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialCsvConfig {
+    pub align_columns: Option<bool>,
+    pub rainbow_columns: Option<bool>,
+    pub pin_header: Option<bool>,
+}
+
+impl Merge for PartialCsvConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.align_columns.merge_from(&other.align_columns);
+        self.rainbow_columns.merge_from(&other.rainbow_columns);
+        self.pin_header.merge_from(&other.pin_header);
+    }
+}
+
 /// Partial packages configuration for plugin/theme package management.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -383,6 +523,8 @@ pub struct PartialLanguageConfig {
     pub formatter: Option<FormatterConfig>,
     pub format_on_save: Option<bool>,
     pub on_save: Option<Vec<OnSaveAction>>,
+    pub repl: Option<String>,
+    pub linters: Option<Vec<LinterConfig>>,
 }
 
 impl Merge for PartialLanguageConfig {
@@ -401,6 +543,8 @@ impl Merge for PartialLanguageConfig {
         self.formatter.merge_from(&other.formatter);
         self.format_on_save.merge_from(&other.format_on_save);
         self.on_save.merge_from(&other.on_save);
+        self.repl.merge_from(&other.repl);
+        self.linters.merge_from(&other.linters);
     }
 }
 
@@ -420,6 +564,9 @@ impl Merge for LspServerConfig {
         if self.initialization_options.is_none() {
             self.initialization_options = other.initialization_options.clone();
         }
+        if self.additional_servers.is_empty() {
+            self.additional_servers = other.additional_servers.clone();
+        }
     }
 }
 
@@ -429,11 +576,19 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
     fn from(cfg: &crate::config::EditorConfig) -> Self {
         Self {
             tab_size: Some(cfg.tab_size),
+            detect_indentation: Some(cfg.detect_indentation),
+            read_editorconfig: Some(cfg.read_editorconfig),
             auto_indent: Some(cfg.auto_indent),
+            increment_cycles: Some(cfg.increment_cycles.clone()),
+            include_paths: Some(cfg.include_paths.clone()),
+            reindent_on_paste: Some(cfg.reindent_on_paste),
+            subword_movement: Some(cfg.subword_movement),
             line_numbers: Some(cfg.line_numbers),
             relative_line_numbers: Some(cfg.relative_line_numbers),
             scroll_offset: Some(cfg.scroll_offset),
             syntax_highlighting: Some(cfg.syntax_highlighting),
+            highlight_trailing_whitespace: Some(cfg.highlight_trailing_whitespace),
+            hyperlinks: Some(cfg.hyperlinks),
             line_wrap: Some(cfg.line_wrap),
             highlight_timeout_ms: Some(cfg.highlight_timeout_ms),
             snapshot_interval: Some(cfg.snapshot_interval),
@@ -443,6 +598,10 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             enable_semantic_tokens_full: Some(cfg.enable_semantic_tokens_full),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_recovery_save_interval_secs: Some(cfg.auto_recovery_save_interval_secs),
+            local_history_enabled: Some(cfg.local_history_enabled),
+            local_history_interval_secs: Some(cfg.local_history_interval_secs),
+            local_history_max_snapshots: Some(cfg.local_history_max_snapshots),
+            local_history_max_age_days: Some(cfg.local_history_max_age_days),
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             highlight_context_bytes: Some(cfg.highlight_context_bytes),
@@ -451,12 +610,24 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             double_click_time_ms: Some(cfg.double_click_time_ms),
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
+            git_status_poll_interval_ms: Some(cfg.git_status_poll_interval_ms),
+            memory_budget_mb: Some(cfg.memory_budget_mb),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            atomic_save: Some(cfg.atomic_save),
+            save_backup_count: Some(cfg.save_backup_count),
+            before_save_timeout_ms: Some(cfg.before_save_timeout_ms),
+            file_templates: Some(cfg.file_templates),
+            template_author: Some(cfg.template_author.clone()),
+            template_license: Some(cfg.template_license.clone()),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
+            rainbow_delimiters: Some(cfg.rainbow_delimiters),
             cursor_style: Some(cfg.cursor_style),
+            color_mode: Some(cfg.color_mode),
+            image_preview: Some(cfg.image_preview),
+            ambiguous_width: Some(cfg.ambiguous_width),
             keyboard_disambiguate_escape_codes: Some(cfg.keyboard_disambiguate_escape_codes),
             keyboard_report_event_types: Some(cfg.keyboard_report_event_types),
             keyboard_report_alternate_keys: Some(cfg.keyboard_report_alternate_keys),
@@ -471,6 +642,8 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_tab_bar: Some(cfg.show_tab_bar),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_selection_stats: Some(cfg.show_selection_stats),
+            selection_stats_sum: Some(cfg.selection_stats_sum),
             use_terminal_bg: Some(cfg.use_terminal_bg),
             rulers: Some(cfg.rulers.clone()),
         }
@@ -482,7 +655,19 @@ impl PartialEditorConfig {
     pub fn resolve(self, defaults: &crate::config::EditorConfig) -> crate::config::EditorConfig {
         crate::config::EditorConfig {
             tab_size: self.tab_size.unwrap_or(defaults.tab_size),
+            detect_indentation: self
+                .detect_indentation
+                .unwrap_or(defaults.detect_indentation),
+            read_editorconfig: self.read_editorconfig.unwrap_or(defaults.read_editorconfig),
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
+            increment_cycles: self
+                .increment_cycles
+                .unwrap_or_else(|| defaults.increment_cycles.clone()),
+            include_paths: self
+                .include_paths
+                .unwrap_or_else(|| defaults.include_paths.clone()),
+            reindent_on_paste: self.reindent_on_paste.unwrap_or(defaults.reindent_on_paste),
+            subword_movement: self.subword_movement.unwrap_or(defaults.subword_movement),
             line_numbers: self.line_numbers.unwrap_or(defaults.line_numbers),
             relative_line_numbers: self
                 .relative_line_numbers
@@ -491,6 +676,10 @@ impl PartialEditorConfig {
             syntax_highlighting: self
                 .syntax_highlighting
                 .unwrap_or(defaults.syntax_highlighting),
+            highlight_trailing_whitespace: self
+                .highlight_trailing_whitespace
+                .unwrap_or(defaults.highlight_trailing_whitespace),
+            hyperlinks: self.hyperlinks.unwrap_or(defaults.hyperlinks),
             line_wrap: self.line_wrap.unwrap_or(defaults.line_wrap),
             highlight_timeout_ms: self
                 .highlight_timeout_ms
@@ -512,6 +701,18 @@ impl PartialEditorConfig {
             auto_recovery_save_interval_secs: self
                 .auto_recovery_save_interval_secs
                 .unwrap_or(defaults.auto_recovery_save_interval_secs),
+            local_history_enabled: self
+                .local_history_enabled
+                .unwrap_or(defaults.local_history_enabled),
+            local_history_interval_secs: self
+                .local_history_interval_secs
+                .unwrap_or(defaults.local_history_interval_secs),
+            local_history_max_snapshots: self
+                .local_history_max_snapshots
+                .unwrap_or(defaults.local_history_max_snapshots),
+            local_history_max_age_days: self
+                .local_history_max_age_days
+                .unwrap_or(defaults.local_history_max_age_days),
             auto_save_enabled: self.auto_save_enabled.unwrap_or(defaults.auto_save_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
@@ -534,6 +735,10 @@ impl PartialEditorConfig {
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
+            git_status_poll_interval_ms: self
+                .git_status_poll_interval_ms
+                .unwrap_or(defaults.git_status_poll_interval_ms),
+            memory_budget_mb: self.memory_budget_mb.unwrap_or(defaults.memory_budget_mb),
             default_line_ending: self
                 .default_line_ending
                 .unwrap_or(defaults.default_line_ending.clone()),
@@ -543,11 +748,29 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            atomic_save: self.atomic_save.unwrap_or(defaults.atomic_save),
+            save_backup_count: self.save_backup_count.unwrap_or(defaults.save_backup_count),
+            before_save_timeout_ms: self
+                .before_save_timeout_ms
+                .unwrap_or(defaults.before_save_timeout_ms),
+            file_templates: self.file_templates.unwrap_or(defaults.file_templates),
+            template_author: self
+                .template_author
+                .unwrap_or(defaults.template_author.clone()),
+            template_license: self
+                .template_license
+                .unwrap_or(defaults.template_license.clone()),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
             rainbow_brackets: self.rainbow_brackets.unwrap_or(defaults.rainbow_brackets),
+            rainbow_delimiters: self
+                .rainbow_delimiters
+                .unwrap_or(defaults.rainbow_delimiters),
             cursor_style: self.cursor_style.unwrap_or(defaults.cursor_style),
+            color_mode: self.color_mode.unwrap_or(defaults.color_mode),
+            image_preview: self.image_preview.unwrap_or(defaults.image_preview),
+            ambiguous_width: self.ambiguous_width.unwrap_or(defaults.ambiguous_width),
             keyboard_disambiguate_escape_codes: self
                 .keyboard_disambiguate_escape_codes
                 .unwrap_or(defaults.keyboard_disambiguate_escape_codes),
@@ -578,12 +801,34 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_selection_stats: self
+                .show_selection_stats
+                .unwrap_or(defaults.show_selection_stats),
+            selection_stats_sum: self
+                .selection_stats_sum
+                .unwrap_or(defaults.selection_stats_sum),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
             rulers: self.rulers.unwrap_or_else(|| defaults.rulers.clone()),
         }
     }
 }
 
+impl From<&FilesConfig> for PartialFilesConfig {
+    fn from(cfg: &FilesConfig) -> Self {
+        Self {
+            exclude: Some(cfg.exclude.clone()),
+        }
+    }
+}
+
+impl PartialFilesConfig {
+    pub fn resolve(self, defaults: &FilesConfig) -> FilesConfig {
+        FilesConfig {
+            exclude: self.exclude.unwrap_or_else(|| defaults.exclude.clone()),
+        }
+    }
+}
+
 impl From<&FileExplorerConfig> for PartialFileExplorerConfig {
     fn from(cfg: &FileExplorerConfig) -> Self {
         Self {
@@ -664,6 +909,68 @@ impl PartialTerminalConfig {
     }
 }
 
+impl From<&DiagnosticsConfig> for PartialDiagnosticsConfig {
+    fn from(cfg: &DiagnosticsConfig) -> Self {
+        Self {
+            inline_text: Some(cfg.inline_text),
+            line_background: Some(cfg.line_background),
+            show_errors: Some(cfg.show_errors),
+            show_warnings: Some(cfg.show_warnings),
+            show_info: Some(cfg.show_info),
+            show_hints: Some(cfg.show_hints),
+        }
+    }
+}
+
+impl PartialDiagnosticsConfig {
+    pub fn resolve(self, defaults: &DiagnosticsConfig) -> DiagnosticsConfig {
+        DiagnosticsConfig {
+            inline_text: self.inline_text.unwrap_or(defaults.inline_text),
+            line_background: self.line_background.unwrap_or(defaults.line_background),
+            show_errors: self.show_errors.unwrap_or(defaults.show_errors),
+            show_warnings: self.show_warnings.unwrap_or(defaults.show_warnings),
+            show_info: self.show_info.unwrap_or(defaults.show_info),
+            show_hints: self.show_hints.unwrap_or(defaults.show_hints),
+        }
+    }
+}
+
+impl From<&TodoConfig> for PartialTodoConfig {
+    fn from(cfg: &TodoConfig) -> Self {
+        Self {
+            tags: Some(cfg.tags.clone()),
+        }
+    }
+}
+
+impl PartialTodoConfig {
+    pub fn resolve(self, defaults: &TodoConfig) -> TodoConfig {
+        TodoConfig {
+            tags: self.tags.unwrap_or_else(|| defaults.tags.clone()),
+        }
+    }
+}
+
+impl From<&CsvConfig> for PartialCsvConfig {
+    fn from(cfg: &CsvConfig) -> Self {
+        Self {
+            align_columns: Some(cfg.align_columns),
+            rainbow_columns: Some(cfg.rainbow_columns),
+            pin_header: Some(cfg.pin_header),
+        }
+    }
+}
+
+impl PartialCsvConfig {
+    pub fn resolve(self, defaults: &CsvConfig) -> CsvConfig {
+        CsvConfig {
+            align_columns: self.align_columns.unwrap_or(defaults.align_columns),
+            rainbow_columns: self.rainbow_columns.unwrap_or(defaults.rainbow_columns),
+            pin_header: self.pin_header.unwrap_or(defaults.pin_header),
+        }
+    }
+}
+
 impl From<&WarningsConfig> for PartialWarningsConfig {
     fn from(cfg: &WarningsConfig) -> Self {
         Self {
@@ -735,6 +1042,8 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             formatter: cfg.formatter.clone(),
             format_on_save: Some(cfg.format_on_save),
             on_save: Some(cfg.on_save.clone()),
+            repl: cfg.repl.clone(),
+            linters: Some(cfg.linters.clone()),
         }
     }
 }
@@ -763,6 +1072,8 @@ impl PartialLanguageConfig {
             formatter: self.formatter.or_else(|| defaults.formatter.clone()),
             format_on_save: self.format_on_save.unwrap_or(defaults.format_on_save),
             on_save: self.on_save.unwrap_or_else(|| defaults.on_save.clone()),
+            repl: self.repl.or_else(|| defaults.repl.clone()),
+            linters: self.linters.unwrap_or_else(|| defaults.linters.clone()),
         }
     }
 }
@@ -775,6 +1086,7 @@ impl From<&crate::config::Config> for PartialConfig {
             locale: cfg.locale.0.clone(),
             check_for_updates: Some(cfg.check_for_updates),
             editor: Some(PartialEditorConfig::from(&cfg.editor)),
+            files: Some(PartialFilesConfig::from(&cfg.files)),
             file_explorer: Some(PartialFileExplorerConfig::from(&cfg.file_explorer)),
             file_browser: Some(PartialFileBrowserConfig::from(&cfg.file_browser)),
             clipboard: Some(PartialClipboardConfig::from(&cfg.clipboard)),
@@ -782,6 +1094,7 @@ impl From<&crate::config::Config> for PartialConfig {
             keybindings: Some(cfg.keybindings.clone()),
             keybinding_maps: Some(cfg.keybinding_maps.clone()),
             active_keybinding_map: Some(cfg.active_keybinding_map.clone()),
+            commands: Some(cfg.commands.clone()),
             languages: Some(
                 cfg.languages
                     .iter()
@@ -790,6 +1103,9 @@ impl From<&crate::config::Config> for PartialConfig {
             ),
             lsp: Some(cfg.lsp.clone()),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
+            diagnostics: Some(PartialDiagnosticsConfig::from(&cfg.diagnostics)),
+            todo: Some(PartialTodoConfig::from(&cfg.todo)),
+            csv: Some(PartialCsvConfig::from(&cfg.csv)),
             // Only include plugins that differ from defaults
             // Path is auto-discovered at runtime and should never be saved
             plugins: {
@@ -867,6 +1183,15 @@ impl PartialConfig {
             result
         };
 
+        // Resolve commands HashMap - merge with defaults
+        let commands = {
+            let mut result = defaults.commands.clone();
+            if let Some(partial_commands) = self.commands {
+                result.extend(partial_commands);
+            }
+            result
+        };
+
         // Resolve plugins HashMap - merge with defaults
         let plugins = {
             let mut result = defaults.plugins.clone();
@@ -890,6 +1215,10 @@ impl PartialConfig {
                 .editor
                 .map(|e| e.resolve(&defaults.editor))
                 .unwrap_or_else(|| defaults.editor.clone()),
+            files: self
+                .files
+                .map(|e| e.resolve(&defaults.files))
+                .unwrap_or_else(|| defaults.files.clone()),
             file_explorer: self
                 .file_explorer
                 .map(|e| e.resolve(&defaults.file_explorer))
@@ -913,12 +1242,25 @@ impl PartialConfig {
             active_keybinding_map: self
                 .active_keybinding_map
                 .unwrap_or_else(|| defaults.active_keybinding_map.clone()),
+            commands,
             languages,
             lsp,
             warnings: self
                 .warnings
                 .map(|e| e.resolve(&defaults.warnings))
                 .unwrap_or_else(|| defaults.warnings.clone()),
+            diagnostics: self
+                .diagnostics
+                .map(|e| e.resolve(&defaults.diagnostics))
+                .unwrap_or_else(|| defaults.diagnostics.clone()),
+            todo: self
+                .todo
+                .map(|e| e.resolve(&defaults.todo))
+                .unwrap_or_else(|| defaults.todo.clone()),
+            csv: self
+                .csv
+                .map(|e| e.resolve(&defaults.csv))
+                .unwrap_or_else(|| defaults.csv.clone()),
             plugins,
             packages: self
                 .packages
@@ -945,6 +1287,8 @@ impl Default for LanguageConfig {
             formatter: None,
             format_on_save: false,
             on_save: Vec::new(),
+            repl: None,
+            linters: Vec::new(),
         }
     }
 }
@@ -0,0 +1,202 @@
+//! Code block detection for sending code to a REPL
+//!
+//! Finds the chunk of source around a position that can be evaluated on its
+//! own: a cell delimited by `%%` marker comments (`# %%`, `// %%`), the
+//! top-level statement from the tree-sitter syntax tree, or the paragraph of
+//! non-blank lines around the cursor.
+
+use fresh_languages::tree_sitter::Parser;
+use fresh_languages::Language;
+use std::ops::Range;
+
+/// Lines of `text` with their byte ranges, excluding line terminators
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches('\n').trim_end_matches('\r');
+        ranges.push(start..start + content.len());
+        start += line.len();
+    }
+    if text.is_empty() || text.ends_with('\n') {
+        ranges.push(start..start);
+    }
+    ranges
+}
+
+/// Index of the line containing `offset`
+fn line_index(lines: &[Range<usize>], offset: usize) -> usize {
+    lines
+        .iter()
+        .position(|line| offset <= line.end)
+        .unwrap_or(lines.len().saturating_sub(1))
+}
+
+/// Byte range of the line containing `offset`, without its line terminator
+pub fn line_at(text: &str, offset: usize) -> Range<usize> {
+    let lines = line_ranges(text);
+    lines[line_index(&lines, offset)].clone()
+}
+
+/// Byte range of the block of code around `offset`.
+///
+/// Cells win when the file has `%%` markers after `comment_prefix`; otherwise
+/// the top-level syntax node under the cursor is used when `language` has a
+/// grammar, falling back to the paragraph of non-blank lines.
+pub fn block_at(
+    text: &str,
+    offset: usize,
+    language: Option<&Language>,
+    comment_prefix: Option<&str>,
+) -> Range<usize> {
+    let lines = line_ranges(text);
+    let row = line_index(&lines, offset);
+    comment_prefix
+        .and_then(|prefix| cell_at(text, &lines, row, prefix))
+        .or_else(|| language.and_then(|lang| top_level_node_at(text, &lines, row, lang)))
+        .unwrap_or_else(|| paragraph_at(text, &lines, row))
+}
+
+/// Whether a line is a `%%` cell marker comment
+fn is_cell_marker(line: &str, comment_prefix: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(comment_prefix.trim_end())
+        .is_some_and(|rest| rest.trim_start().starts_with("%%"))
+}
+
+/// The cell containing `row`, if the text has cell markers
+fn cell_at(
+    text: &str,
+    lines: &[Range<usize>],
+    row: usize,
+    comment_prefix: &str,
+) -> Option<Range<usize>> {
+    let markers: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_cell_marker(&text[(*line).clone()], comment_prefix))
+        .map(|(i, _)| i)
+        .collect();
+    if markers.is_empty() {
+        return None;
+    }
+    // A cursor on a marker line belongs to the cell the marker starts
+    let first = markers
+        .iter()
+        .rev()
+        .find(|&&m| m <= row)
+        .map_or(0, |&m| m + 1);
+    let last = markers
+        .iter()
+        .find(|&&m| m > row)
+        .map_or(lines.len(), |&m| m);
+    if first >= last {
+        return None;
+    }
+    Some(lines[first].start..lines[last - 1].end)
+}
+
+/// The top-level syntax node spanning `row`, expanded to whole lines
+fn top_level_node_at(
+    text: &str,
+    lines: &[Range<usize>],
+    row: usize,
+    language: &Language,
+) -> Option<Range<usize>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()?).ok()?;
+    let tree = parser.parse(text, None)?;
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let node = root.named_children(&mut cursor).find(|node| {
+        node.start_position().row <= row && row <= node.end_position().row && !node.is_error()
+    })?;
+    let start = lines.get(node.start_position().row)?.start;
+    let end = lines.get(node.end_position().row)?.end.max(node.end_byte());
+    Some(start..end.min(text.len()))
+}
+
+/// The run of non-blank lines around `row`
+fn paragraph_at(text: &str, lines: &[Range<usize>], row: usize) -> Range<usize> {
+    let is_blank = |i: usize| text[lines[i].clone()].trim().is_empty();
+    if is_blank(row) {
+        return lines[row].clone();
+    }
+    let mut first = row;
+    while first > 0 && !is_blank(first - 1) {
+        first -= 1;
+    }
+    let mut last = row;
+    while last + 1 < lines.len() && !is_blank(last + 1) {
+        last += 1;
+    }
+    lines[first].start..lines[last].end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block<'a>(
+        text: &'a str,
+        offset: usize,
+        language: Option<Language>,
+        prefix: Option<&str>,
+    ) -> &'a str {
+        &text[block_at(text, offset, language.as_ref(), prefix)]
+    }
+
+    #[test]
+    fn test_line_at() {
+        let text = "a = 1\r\nb = 2\n";
+        assert_eq!(&text[line_at(text, 0)], "a = 1");
+        assert_eq!(&text[line_at(text, 8)], "b = 2");
+        assert_eq!(line_at(text, text.len()), text.len()..text.len());
+    }
+
+    #[test]
+    fn test_block_uses_top_level_node() {
+        let text = "import os\n\ndef f(x):\n    y = x\n\n    return y\n\nprint(f(1))\n";
+        let inside = text.find("return").unwrap();
+        assert_eq!(
+            block(text, inside, Some(Language::Python), Some("#")),
+            "def f(x):\n    y = x\n\n    return y"
+        );
+        assert_eq!(
+            block(text, 0, Some(Language::Python), Some("#")),
+            "import os"
+        );
+    }
+
+    #[test]
+    fn test_block_prefers_cells() {
+        let text = "# %% setup\nx = 1\ny = 2\n# %%\nprint(x)\n";
+        assert_eq!(
+            block(
+                text,
+                text.find("y =").unwrap(),
+                Some(Language::Python),
+                Some("#")
+            ),
+            "x = 1\ny = 2"
+        );
+        // A cursor on a marker sends the cell it starts
+        assert_eq!(
+            block(
+                text,
+                text.find("# %%\n").unwrap(),
+                Some(Language::Python),
+                Some("#")
+            ),
+            "print(x)\n"
+        );
+    }
+
+    #[test]
+    fn test_block_falls_back_to_paragraph() {
+        let text = "let a = 1\nlet b = 2\n\nlet c = 3\n";
+        assert_eq!(block(text, 12, None, None), "let a = 1\nlet b = 2");
+        assert_eq!(block(text, text.len() - 2, None, None), "let c = 3");
+        assert_eq!(block(text, 20, None, None), "");
+    }
+}
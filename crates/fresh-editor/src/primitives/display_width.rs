@@ -5,17 +5,50 @@
 //! cursor positioning, line wrapping, and UI layout with CJK characters,
 //! emoji, and other double-width or zero-width characters.
 
+use std::borrow::Cow;
+use std::cell::Cell;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+thread_local! {
+    /// Whether East Asian Ambiguous characters are two columns wide. Kept per
+    /// thread: the editor sets it from its config on the thread that renders
+    /// and handles input, so editors in parallel tests don't affect each other.
+    static AMBIGUOUS_WIDE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Draw East Asian Ambiguous characters (`±`, `→`, `①`, box drawing...) two
+/// columns wide, as terminals configured for CJK locales do, or one column
+/// wide (the default)
+pub fn set_ambiguous_wide(wide: bool) {
+    AMBIGUOUS_WIDE.with(|cell| cell.set(wide));
+}
+
+/// Whether East Asian Ambiguous characters are two columns wide
+#[inline]
+pub fn ambiguous_is_wide() -> bool {
+    AMBIGUOUS_WIDE.with(Cell::get)
+}
+
+/// Whether a character is one column wide or two depending on the terminal
+#[inline]
+pub fn is_ambiguous(c: char) -> bool {
+    c.width() == Some(1) && c.width_cjk() == Some(2)
+}
+
 /// Calculate the display width of a single character.
 ///
 /// Returns 0 for control characters and zero-width characters,
 /// 2 for CJK/fullwidth characters and emoji,
-/// 1 for most other characters.
+/// 1 for most other characters. Ambiguous characters are 2 columns when
+/// [`ambiguous_is_wide`].
 #[inline]
 pub fn char_width(c: char) -> usize {
     // unicode_width returns None for control characters
-    c.width().unwrap_or(0)
+    if ambiguous_is_wide() {
+        c.width_cjk().unwrap_or(0)
+    } else {
+        c.width().unwrap_or(0)
+    }
 }
 
 /// Calculate the display width of a string.
@@ -24,7 +57,60 @@ pub fn char_width(c: char) -> usize {
 /// Use this instead of `.chars().count()` when calculating visual layout.
 #[inline]
 pub fn str_width(s: &str) -> usize {
-    s.width()
+    if ambiguous_is_wide() {
+        s.width_cjk()
+    } else {
+        s.width()
+    }
+}
+
+/// Text to hand to ratatui, which measures ambiguous characters as one
+/// column: when they are wide, each is followed by a space that holds its
+/// second column. [`skip_ambiguous_padding`] then keeps the terminal from
+/// drawing over it.
+pub fn pad_ambiguous(text: &str) -> Cow<'_, str> {
+    if !ambiguous_is_wide() || !text.chars().any(is_ambiguous) {
+        return Cow::Borrowed(text);
+    }
+    let mut padded = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        push_padded(&mut padded, c);
+    }
+    Cow::Owned(padded)
+}
+
+/// Append a character to span text, padded as by [`pad_ambiguous`]
+#[inline]
+pub fn push_padded(text: &mut String, c: char) {
+    text.push(c);
+    if ambiguous_is_wide() && is_ambiguous(c) {
+        text.push(' ');
+    }
+}
+
+/// When ambiguous characters are wide, mark the blank cell after each one
+/// as skipped, so the terminal's second column of the character isn't
+/// overwritten and the cells after it stay aligned
+#[cfg(any(feature = "runtime", feature = "wasm"))]
+pub fn skip_ambiguous_padding(buffer: &mut ratatui::buffer::Buffer) {
+    if !ambiguous_is_wide() {
+        return;
+    }
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x + 1 < area.right() {
+            let mut chars = buffer[(x, y)].symbol().chars();
+            let ambiguous = matches!(chars.next(), Some(c) if is_ambiguous(c))
+                && chars.all(|c| c.width() == Some(0));
+            if ambiguous && buffer[(x + 1, y)].symbol() == " " {
+                buffer[(x + 1, y)].set_skip(true);
+                x += 2;
+            } else {
+                x += 1;
+            }
+        }
+    }
 }
 
 /// Extension trait for convenient width calculation on string types.
@@ -132,6 +218,46 @@ mod tests {
         assert_eq!(char_width('\u{200B}'), 0);
     }
 
+    #[test]
+    fn test_ambiguous_width() {
+        assert!(is_ambiguous('→'));
+        assert!(is_ambiguous('±'));
+        assert!(is_ambiguous('①'));
+        assert!(!is_ambiguous('a'));
+        assert!(!is_ambiguous('你'));
+        assert_eq!(str_width("±→b"), 3);
+        assert_eq!(pad_ambiguous("±→b"), "±→b");
+
+        set_ambiguous_wide(true);
+        assert_eq!(char_width('→'), 2);
+        assert_eq!(char_width('你'), 2);
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(str_width("±→b"), 5);
+        assert_eq!(visual_column_at_byte("±→b", "±→".len()), 4);
+        assert_eq!(byte_offset_at_visual_column("±→b", 2), "±".len());
+        assert_eq!(pad_ambiguous("±→b"), "± → b");
+        set_ambiguous_wide(false);
+    }
+
+    #[test]
+    #[cfg(any(feature = "runtime", feature = "wasm"))]
+    fn test_skip_ambiguous_padding() {
+        use ratatui::buffer::Buffer;
+        use ratatui::layout::Rect;
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(0, 0, "± b→c", ratatui::style::Style::default());
+        skip_ambiguous_padding(&mut buffer);
+        assert!(!buffer[(1, 0)].skip);
+
+        set_ambiguous_wide(true);
+        skip_ambiguous_padding(&mut buffer);
+        set_ambiguous_wide(false);
+        let skipped: Vec<bool> = (0..6).map(|x| buffer[(x, 0)].skip).collect();
+        // Only padding spaces are skipped; `→` is followed by text
+        assert_eq!(skipped, [false, true, false, false, false, false]);
+    }
+
     #[test]
     fn test_display_width_trait() {
         let s = "你好";
@@ -0,0 +1,346 @@
+//! EditorConfig (`.editorconfig`) parsing and section matching.
+//!
+//! Only the properties the editor acts on are read: `indent_style`,
+//! `indent_size`, `tab_width`, `trim_trailing_whitespace` and
+//! `insert_final_newline`. Finding and reading the files is left to the
+//! caller; this module turns their text into settings for one path.
+
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Name of EditorConfig files
+pub const FILE_NAME: &str = ".editorconfig";
+
+/// A parsed `.editorconfig` file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorConfigFile {
+    /// `root = true` in the preamble: files further up are not read
+    pub root: bool,
+    /// Section globs with their properties, keys lower-cased, in file order
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// What the `.editorconfig` files above a path say about it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EditorConfigSettings {
+    /// `indent_style`: tabs (`true`) or spaces (`false`)
+    pub use_tabs: Option<bool>,
+    /// `indent_size`, with `tab` resolved to the tab width
+    pub indent_size: Option<usize>,
+    /// `tab_width`, defaulting to a numeric `indent_size`
+    pub tab_width: Option<usize>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub insert_final_newline: Option<bool>,
+}
+
+impl EditorConfigSettings {
+    /// Columns per indent level, as the editor's single tab size
+    pub fn tab_size(&self) -> Option<usize> {
+        if self.use_tabs == Some(true) {
+            self.tab_width.or(self.indent_size)
+        } else {
+            self.indent_size.or(self.tab_width)
+        }
+    }
+
+    /// Whether any property was set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Parse the text of an `.editorconfig` file, skipping lines it can't read
+pub fn parse(text: &str) -> EditorConfigFile {
+    let mut file = EditorConfigFile::default();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            file.sections.push((glob.to_string(), Vec::new()));
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim().to_string();
+        match file.sections.last_mut() {
+            Some((_, properties)) => properties.push((key, value)),
+            None if key == "root" => file.root = value.eq_ignore_ascii_case("true"),
+            None => {}
+        }
+    }
+    file
+}
+
+impl EditorConfigFile {
+    /// Set the properties of the sections matching `path` in `properties`;
+    /// `path` is relative to the file's directory, with `/` separators
+    fn apply(&self, path: &str, properties: &mut HashMap<String, String>) {
+        for (glob, section) in &self.sections {
+            if !glob_matches(glob, path) {
+                continue;
+            }
+            for (key, value) in section {
+                if value.eq_ignore_ascii_case("unset") {
+                    properties.remove(key);
+                } else {
+                    properties.insert(key.clone(), value.to_lowercase());
+                }
+            }
+        }
+    }
+}
+
+/// Settings from `files`, outermost first, each with the path it applies
+/// to relative to its directory; inner files win
+pub fn settings_for(files: &[(&EditorConfigFile, &str)]) -> EditorConfigSettings {
+    let mut properties = HashMap::new();
+    for (file, path) in files {
+        file.apply(path, &mut properties);
+    }
+
+    let flag = |key: &str| match properties.get(key).map(String::as_str) {
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        _ => None,
+    };
+    let number = |key: &str| {
+        properties
+            .get(key)
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|&n| n > 0)
+    };
+
+    let tab_width = number("tab_width").or_else(|| number("indent_size"));
+    let indent_size = match properties.get("indent_size").map(String::as_str) {
+        Some("tab") => tab_width,
+        _ => number("indent_size"),
+    };
+    EditorConfigSettings {
+        use_tabs: match properties.get("indent_style").map(String::as_str) {
+            Some("tab") => Some(true),
+            Some("space") => Some(false),
+            _ => None,
+        },
+        indent_size,
+        tab_width,
+        trim_trailing_whitespace: flag("trim_trailing_whitespace"),
+        insert_final_newline: flag("insert_final_newline"),
+    }
+}
+
+/// Whether a section glob matches `path` (relative to the `.editorconfig`
+/// directory, with `/` separators)
+///
+/// Globs without a `/` match file names in any subdirectory; others match
+/// from the directory itself. `*` stops at `/` and `**` doesn't, `?`, `[a-z]`,
+/// `[!abc]`, `{a,b}` and `{1..10}` work as in shells.
+pub fn glob_matches(glob: &str, path: &str) -> bool {
+    let mut ranges = Vec::new();
+    let mut pattern = String::from("^");
+    let chars: Vec<char> = glob.chars().collect();
+    if glob.contains('/') {
+        let start = usize::from(chars.first() == Some(&'/'));
+        translate(&chars[start..], &mut pattern, &mut ranges);
+    } else {
+        pattern.push_str("(?:.*/)?");
+        translate(&chars, &mut pattern, &mut ranges);
+    }
+    pattern.push('$');
+
+    let Ok(regex) = Regex::new(&pattern) else {
+        return false;
+    };
+    let Some(captures) = regex.captures(path) else {
+        return false;
+    };
+    // Numeric ranges match any number, then the numbers are checked
+    ranges.iter().enumerate().all(|(i, &(low, high))| {
+        captures
+            .get(i + 1)
+            .and_then(|m| m.as_str().parse::<i64>().ok())
+            .is_some_and(|n| (low..=high).contains(&n))
+    })
+}
+
+/// Append the regex for `glob` to `out`, recording each `{n..m}` range
+fn translate(glob: &[char], out: &mut String, ranges: &mut Vec<(i64, i64)>) {
+    let mut i = 0;
+    while i < glob.len() {
+        match glob[i] {
+            '\\' if i + 1 < glob.len() => {
+                out.push_str(&regex::escape(&glob[i + 1].to_string()));
+                i += 1;
+            }
+            // `a/**/b` also matches `a/b`
+            '/' if glob[i + 1..].starts_with(&['*', '*', '/']) => {
+                out.push_str("(?:/|/.*/)");
+                i += 3;
+            }
+            '*' if glob.get(i + 1) == Some(&'*') => {
+                out.push_str(".*");
+                i += 1;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => match glob[i..].iter().position(|&c| c == ']') {
+                Some(len) if len > 1 => {
+                    let class: String = glob[i + 1..i + len].iter().collect();
+                    out.push('[');
+                    match class.strip_prefix('!') {
+                        Some(rest) => {
+                            out.push('^');
+                            out.push_str(&rest.replace('\\', "\\\\").replace('[', "\\["));
+                        }
+                        None => out.push_str(&class.replace('\\', "\\\\").replace('[', "\\[")),
+                    }
+                    out.push(']');
+                    i += len;
+                }
+                _ => out.push_str("\\["),
+            },
+            '{' => match matching_brace(&glob[i..]) {
+                Some(len) => {
+                    let inner = &glob[i + 1..i + len];
+                    translate_braces(inner, out, ranges);
+                    i += len;
+                }
+                None => out.push_str("\\{"),
+            },
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+}
+
+/// Append the regex for the inside of `{...}`
+fn translate_braces(inner: &[char], out: &mut String, ranges: &mut Vec<(i64, i64)>) {
+    let text: String = inner.iter().collect();
+    if let Some((low, high)) = text.split_once("..") {
+        if let (Ok(low), Ok(high)) = (low.parse::<i64>(), high.parse::<i64>()) {
+            ranges.push((low.min(high), low.max(high)));
+            out.push_str("([+-]?[0-9]+)");
+            return;
+        }
+    }
+
+    let mut alternatives = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, &c) in inner.iter().enumerate() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    alternatives.push(&inner[start..]);
+
+    // `{word}` without alternatives is taken literally
+    if alternatives.len() == 1 {
+        out.push_str("\\{");
+        translate(inner, out, ranges);
+        out.push_str("\\}");
+        return;
+    }
+    out.push_str("(?:");
+    for (i, alternative) in alternatives.iter().enumerate() {
+        if i > 0 {
+            out.push('|');
+        }
+        translate(alternative, out, ranges);
+    }
+    out.push(')');
+}
+
+/// Offset of the `}` closing the `{` that `glob` starts with
+fn matching_brace(glob: &[char]) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < glob.len() {
+        match glob[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*", "a.rs"));
+        assert!(glob_matches("*", "src/a.rs"));
+        assert!(glob_matches("*.rs", "src/deep/a.rs"));
+        assert!(!glob_matches("*.rs", "a.rs.bak"));
+        assert!(glob_matches("*.{js,ts}", "web/app.ts"));
+        assert!(!glob_matches("*.{js,ts}", "web/app.rs"));
+        assert!(glob_matches("Makefile", "sub/Makefile"));
+        assert!(glob_matches("[Mm]akefile", "makefile"));
+        assert!(!glob_matches("[!M]akefile", "Makefile"));
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(glob_matches("{single}.txt", "{single}.txt"));
+    }
+
+    #[test]
+    fn test_glob_paths_and_ranges() {
+        // Globs with a slash match from the `.editorconfig` directory
+        assert!(glob_matches("src/*.rs", "src/a.rs"));
+        assert!(!glob_matches("src/*.rs", "lib/src/a.rs"));
+        assert!(!glob_matches("src/*.rs", "src/deep/a.rs"));
+        assert!(glob_matches("/docs/**.md", "docs/a/b.md"));
+        assert!(glob_matches("a/**/b.txt", "a/b.txt"));
+        assert!(glob_matches("a/**/b.txt", "a/x/y/b.txt"));
+        assert!(glob_matches("part{1..3}.txt", "part2.txt"));
+        assert!(!glob_matches("part{1..3}.txt", "part4.txt"));
+    }
+
+    #[test]
+    fn test_settings_for() {
+        let outer = parse(
+            "root = true\n\n[*]\nindent_style = space\nindent_size = 4\n\
+             trim_trailing_whitespace = true\ninsert_final_newline = true\n\n\
+             [Makefile]\nindent_style = tab\n",
+        );
+        assert!(outer.root);
+        let inner = parse("# Generated files\n[*.md]\ntrim_trailing_whitespace = unset\n");
+
+        let settings = settings_for(&[(&outer, "docs/readme.md"), (&inner, "readme.md")]);
+        assert_eq!(settings.use_tabs, Some(false));
+        assert_eq!(settings.tab_size(), Some(4));
+        assert_eq!(settings.trim_trailing_whitespace, None);
+        assert_eq!(settings.insert_final_newline, Some(true));
+
+        let settings = settings_for(&[(&outer, "Makefile")]);
+        assert_eq!(settings.use_tabs, Some(true));
+        assert_eq!(settings.tab_width, Some(4));
+    }
+
+    #[test]
+    fn test_indent_size_tab() {
+        let file = parse("[*.go]\nindent_style = TAB\nindent_size = tab\ntab_width = 8\n");
+        let settings = settings_for(&[(&file, "main.go")]);
+        assert_eq!(settings.use_tabs, Some(true));
+        assert_eq!(settings.indent_size, Some(8));
+        assert_eq!(settings.tab_size(), Some(8));
+        assert!(settings_for(&[(&file, "main.rs")]).is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+//! File references in plain text, such as `src/main.rs:12:5` in compiler output.
+
+/// A path-like token with an optional `:line` or `:line:col` suffix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReference {
+    /// The path as written, without the position suffix
+    pub path: String,
+    /// 1-based line, if given
+    pub line: Option<usize>,
+    /// 1-based column, if given
+    pub column: Option<usize>,
+}
+
+/// Characters that end a path-like token
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '"' | '\'' | '`' | '<' | '>' | '|' | '(' | ')' | '[' | ']'
+        )
+}
+
+/// Find the file reference containing byte offset `offset` of `line`
+///
+/// Trailing punctuation from the surrounding prose (`.`, `,`, `;` and a
+/// dangling `:`) is dropped, and a `path:line[:col]` suffix is parsed.
+pub fn file_reference_at(line: &str, offset: usize) -> Option<FileReference> {
+    let offset = offset.min(line.len());
+    if !line.is_char_boundary(offset) {
+        return None;
+    }
+    let start = line[..offset]
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| is_delimiter(c))
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    let end = line[offset..]
+        .char_indices()
+        .find(|&(_, c)| is_delimiter(c))
+        .map(|(i, _)| offset + i)
+        .unwrap_or(line.len());
+    parse_file_reference(&line[start..end])
+}
+
+/// Parse a single token as `path[:line[:col]]`
+pub fn parse_file_reference(token: &str) -> Option<FileReference> {
+    let token = token.trim_end_matches(['.', ',', ';', ':']);
+    let mut parts: Vec<&str> = token.rsplitn(3, ':').collect();
+    parts.reverse();
+
+    let (path, line, column) = match parts.as_slice() {
+        [path, middle, last] => match (middle.parse().ok(), last.parse().ok()) {
+            (Some(line), Some(col)) => (path.to_string(), Some(line), Some(col)),
+            // A non-numeric middle part belongs to the path, e.g. the drive
+            // letter in `C:\src\main.rs:3`
+            (None, Some(line)) => (format!("{}:{}", path, middle), Some(line), None),
+            _ => (token.to_string(), None, None),
+        },
+        [path, line] => match line.parse().ok() {
+            Some(line) => (path.to_string(), Some(line), None),
+            None => (token.to_string(), None, None),
+        },
+        _ => (token.to_string(), None, None),
+    };
+
+    if path.is_empty() || !path.chars().any(|c| c.is_alphanumeric()) {
+        return None;
+    }
+    Some(FileReference { path, line, column })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(path: &str, line: Option<usize>, column: Option<usize>) -> FileReference {
+        FileReference {
+            path: path.to_string(),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn test_plain_path() {
+        assert_eq!(
+            file_reference_at("edit src/main.rs now", 8),
+            Some(reference("src/main.rs", None, None))
+        );
+    }
+
+    #[test]
+    fn test_line_and_column_suffix() {
+        let line = "  --> src/app/mod.rs:120:9";
+        assert_eq!(
+            file_reference_at(line, 10),
+            Some(reference("src/app/mod.rs", Some(120), Some(9)))
+        );
+        assert_eq!(
+            file_reference_at("lib.py:7: error", 0),
+            Some(reference("lib.py", Some(7), None))
+        );
+    }
+
+    #[test]
+    fn test_trailing_punctuation_and_quotes() {
+        assert_eq!(
+            file_reference_at("see 'docs/guide.md'.", 8),
+            Some(reference("docs/guide.md", None, None))
+        );
+        assert_eq!(
+            file_reference_at("in tests/a.rs, line 3", 6),
+            Some(reference("tests/a.rs", None, None))
+        );
+    }
+
+    #[test]
+    fn test_windows_drive_letter() {
+        assert_eq!(
+            parse_file_reference(r"C:\src\main.rs:3"),
+            Some(reference(r"C:\src\main.rs", Some(3), None))
+        );
+    }
+
+    #[test]
+    fn test_no_reference_on_whitespace_or_punctuation() {
+        assert_eq!(file_reference_at("a   b", 2), None);
+        assert_eq!(file_reference_at("--", 1), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! File templates: the initial content of new files.
+//!
+//! A template is a file in the templates directory. A template named exactly
+//! like the new file (such as `Makefile`) is used first, otherwise one with
+//! the same extension (such as `module.rs` for `main.rs`).
+//!
+//! Templates can use these variables:
+//!
+//! ```text
+//! ${filename}  file name, e.g. main.rs
+//! ${basename}  file name without its extension, e.g. main
+//! ${date}      today's date, e.g. 2024-05-01
+//! ${year}      this year
+//! ${author}    editor.template_author
+//! ${license}   editor.template_license
+//! ${cursor}    where the cursor goes; removed from the text
+//! ```
+//!
+//! `$${` stands for a literal `${`. Other `${...}` are left as they are.
+
+use std::collections::HashMap;
+
+/// Text of a template with its variables substituted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedTemplate {
+    pub text: String,
+    /// Byte offset of `${cursor}` in the text
+    pub cursor_offset: Option<usize>,
+}
+
+/// Of the template file names `names`, the one for a new file named
+/// `file_name`
+pub fn template_for<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    file_name: &str,
+) -> Option<&'a str> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext)
+        .filter(|ext| !ext.is_empty());
+    let mut by_extension: Option<&str> = None;
+    for name in names {
+        if name == file_name {
+            return Some(name);
+        }
+        let same_extension =
+            extension.is_some() && name.rsplit_once('.').map(|(_, ext)| ext) == extension;
+        if same_extension && by_extension.is_none_or(|best| name < best) {
+            by_extension = Some(name);
+        }
+    }
+    by_extension
+}
+
+/// Substitute `variables` in `template`
+pub fn expand_template(template: &str, variables: &HashMap<&str, String>) -> ExpandedTemplate {
+    let mut text = String::with_capacity(template.len());
+    let mut cursor_offset = None;
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            // `$${` is an escaped `${`
+            text.push_str(&rest[..start - 1]);
+            text.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        if name == "cursor" {
+            cursor_offset.get_or_insert(text.len());
+        } else if let Some(value) = variables.get(name) {
+            text.push_str(value);
+        } else {
+            text.push_str(&rest[start..start + 2 + end + 1]);
+        }
+        rest = &after[end + 1..];
+    }
+    text.push_str(rest);
+    ExpandedTemplate {
+        text,
+        cursor_offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_for() {
+        let names = ["module.rs", "Makefile", "a.rs", "script.py"];
+        assert_eq!(template_for(names, "main.rs"), Some("a.rs"));
+        assert_eq!(template_for(names, "Makefile"), Some("Makefile"));
+        assert_eq!(template_for(names, "tool.py"), Some("script.py"));
+        assert_eq!(template_for(names, "notes.txt"), None);
+        assert_eq!(template_for(names, "README"), None);
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let variables = HashMap::from([
+            ("filename", "main.rs".to_string()),
+            ("author", "Ada".to_string()),
+        ]);
+        let expanded = expand_template(
+            "// ${filename} by ${author}\n${cursor}\n// ${unknown} $${author}",
+            &variables,
+        );
+        assert_eq!(
+            expanded.text,
+            "// main.rs by Ada\n\n// ${unknown} ${author}"
+        );
+        assert_eq!(expanded.cursor_offset, Some(18));
+
+        let expanded = expand_template("no variables ${", &variables);
+        assert_eq!(expanded.text, "no variables ${");
+        assert_eq!(expanded.cursor_offset, None);
+    }
+}
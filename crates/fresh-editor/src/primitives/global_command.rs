@@ -0,0 +1,291 @@
+//! Parsing of global commands, which apply a command to every line matching
+//! a pattern.
+//!
+//! The syntax follows `:g` in vi editors:
+//!
+//! ```text
+//! [start,end]g/pattern/command
+//! [start,end]v/pattern/command
+//! ```
+//!
+//! `v` applies the command to the lines that don't match. Any punctuation
+//! character can delimit the pattern instead of `/`. The optional range
+//! gives 1-based line numbers. Commands are `d` (delete the line), `>` and
+//! `<` (indent and dedent), `!cmd` (pipe the line through a shell command)
+//! and `normal` followed by action names and quoted text to type, run with
+//! the cursor at the start of each line.
+
+use regex::Regex;
+use std::ops::RangeInclusive;
+
+/// A parsed global command
+#[derive(Debug, Clone)]
+pub struct GlobalCommand {
+    /// 1-based lines the command is limited to
+    pub lines: Option<RangeInclusive<usize>>,
+    pub pattern: Regex,
+    /// Apply the command to the lines that don't match
+    pub invert: bool,
+    pub command: LineCommand,
+}
+
+/// What is done to each matching line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineCommand {
+    Delete,
+    Indent,
+    Dedent,
+    /// Replace the line with the output of a shell command given the line
+    Pipe(String),
+    /// Run actions with the cursor at the start of the line
+    Normal(Vec<NormalStep>),
+}
+
+/// Step of a `normal` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NormalStep {
+    /// Action name, as used in keybindings
+    Action(String),
+    /// Text to type
+    Text(String),
+}
+
+/// Why a global command couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Not of the form `g/pattern/command`
+    Syntax,
+    InvalidRange,
+    /// The pattern isn't a valid regex; holds the regex error
+    InvalidPattern(String),
+    /// Holds the command as given
+    UnknownCommand(String),
+}
+
+/// Parse a global command
+pub fn parse_global_command(input: &str) -> Result<GlobalCommand, ParseError> {
+    let input = input.trim_start().trim_start_matches(':');
+
+    let range_end = input
+        .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == ' '))
+        .unwrap_or(input.len());
+    let (range, rest) = input.split_at(range_end);
+    let lines = parse_range(range.trim())?;
+
+    let mut chars = rest.chars();
+    let invert = match chars.next() {
+        Some('g') => false,
+        Some('v') => true,
+        _ => return Err(ParseError::Syntax),
+    };
+    let delimiter = chars
+        .next()
+        .filter(|c| c.is_ascii_punctuation())
+        .ok_or(ParseError::Syntax)?;
+    let rest = chars.as_str();
+    let (pattern, command) = split_pattern(rest, delimiter).ok_or(ParseError::Syntax)?;
+    if pattern.is_empty() {
+        return Err(ParseError::Syntax);
+    }
+    let pattern = Regex::new(&pattern).map_err(|e| ParseError::InvalidPattern(e.to_string()))?;
+
+    Ok(GlobalCommand {
+        lines,
+        pattern,
+        invert,
+        command: parse_line_command(command.trim())?,
+    })
+}
+
+/// Parse `start,end` or a single line number
+fn parse_range(range: &str) -> Result<Option<RangeInclusive<usize>>, ParseError> {
+    if range.is_empty() {
+        return Ok(None);
+    }
+    let number = |s: &str| {
+        s.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or(ParseError::InvalidRange)
+    };
+    let (start, end) = match range.split_once(',') {
+        Some((start, end)) => (number(start)?, number(end)?),
+        None => (number(range)?, number(range)?),
+    };
+    if start > end {
+        return Err(ParseError::InvalidRange);
+    }
+    Ok(Some(start..=end))
+}
+
+/// Split `pattern/command` at the first unescaped delimiter; an escaped
+/// delimiter stands for itself in the pattern
+fn split_pattern(text: &str, delimiter: char) -> Option<(String, &str)> {
+    let mut pattern = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == delimiter {
+            return Some((pattern, &text[i + c.len_utf8()..]));
+        }
+        if c == '\\' {
+            match chars.next() {
+                Some((_, next)) if next == delimiter => pattern.push(next),
+                Some((_, next)) => {
+                    pattern.push('\\');
+                    pattern.push(next);
+                }
+                None => pattern.push('\\'),
+            }
+        } else {
+            pattern.push(c);
+        }
+    }
+    None
+}
+
+fn parse_line_command(command: &str) -> Result<LineCommand, ParseError> {
+    match command {
+        "d" | "delete" => return Ok(LineCommand::Delete),
+        ">" => return Ok(LineCommand::Indent),
+        "<" => return Ok(LineCommand::Dedent),
+        _ => {}
+    }
+    if let Some(shell) = command.strip_prefix('!') {
+        if !shell.trim().is_empty() {
+            return Ok(LineCommand::Pipe(shell.trim().to_string()));
+        }
+    } else if let Some(steps) = command.strip_prefix("normal ") {
+        if let Some(steps) = parse_normal_steps(steps).filter(|steps| !steps.is_empty()) {
+            return Ok(LineCommand::Normal(steps));
+        }
+    }
+    Err(ParseError::UnknownCommand(command.to_string()))
+}
+
+/// Split `move_line_end "text" ...` into steps; `None` for an unclosed quote
+fn parse_normal_steps(text: &str) -> Option<Vec<NormalStep>> {
+    let mut steps = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut quoted = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => match chars.next()? {
+                        'n' => quoted.push('\n'),
+                        't' => quoted.push('\t'),
+                        other => quoted.push(other),
+                    },
+                    other => quoted.push(other),
+                }
+            }
+            steps.push(NormalStep::Text(quoted));
+        } else {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            steps.push(NormalStep::Action(name));
+        }
+    }
+    Some(steps)
+}
+
+impl GlobalCommand {
+    /// Whether the command applies to `line` (without its line ending)
+    /// with 0-based index `index`
+    pub fn applies_to(&self, index: usize, line: &str) -> bool {
+        let in_range = self
+            .lines
+            .as_ref()
+            .is_none_or(|lines| lines.contains(&(index + 1)));
+        in_range && self.pattern.is_match(line) != self.invert
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let command = parse_global_command("g/TODO/d").unwrap();
+        assert_eq!(command.pattern.as_str(), "TODO");
+        assert!(!command.invert);
+        assert_eq!(command.lines, None);
+        assert_eq!(command.command, LineCommand::Delete);
+
+        let command = parse_global_command(":2,5v#a/b#>").unwrap();
+        assert_eq!(command.pattern.as_str(), "a/b");
+        assert!(command.invert);
+        assert_eq!(command.lines, Some(2..=5));
+        assert_eq!(command.command, LineCommand::Indent);
+
+        let command = parse_global_command(r"g/a\/b/!tr a-z A-Z").unwrap();
+        assert_eq!(command.pattern.as_str(), "a/b");
+        assert_eq!(command.command, LineCommand::Pipe("tr a-z A-Z".into()));
+
+        let command = parse_global_command(r#"g/^fn/normal move_line_end " // \"x\"""#).unwrap();
+        assert_eq!(
+            command.command,
+            LineCommand::Normal(vec![
+                NormalStep::Action("move_line_end".into()),
+                NormalStep::Text(" // \"x\"".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_global_command("x/a/d").unwrap_err(),
+            ParseError::Syntax
+        );
+        assert_eq!(parse_global_command("g/a").unwrap_err(), ParseError::Syntax);
+        assert_eq!(
+            parse_global_command("g//d").unwrap_err(),
+            ParseError::Syntax
+        );
+        assert_eq!(
+            parse_global_command("5,2g/a/d").unwrap_err(),
+            ParseError::InvalidRange
+        );
+        assert!(matches!(
+            parse_global_command("g/(/d").unwrap_err(),
+            ParseError::InvalidPattern(_)
+        ));
+        assert_eq!(
+            parse_global_command("g/a/x").unwrap_err(),
+            ParseError::UnknownCommand("x".into())
+        );
+        assert_eq!(
+            parse_global_command(r#"g/a/normal "open"#).unwrap_err(),
+            ParseError::UnknownCommand(r#"normal "open"#.into())
+        );
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let command = parse_global_command("2,3g/x/d").unwrap();
+        let lines = ["x", "x", "y", "x"];
+        let applies: Vec<bool> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| command.applies_to(i, line))
+            .collect();
+        assert_eq!(applies, [false, true, false, false]);
+
+        let command = parse_global_command("v/x/d").unwrap();
+        assert!(command.applies_to(2, "y"));
+        assert!(!command.applies_to(0, "x"));
+    }
+}
@@ -529,6 +529,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
 
@@ -50,6 +50,17 @@ pub struct GrammarRegistry {
     filename_scopes: HashMap<String, String>,
     /// Paths to dynamically loaded grammar files (for reloading when adding more)
     loaded_grammar_paths: Vec<(String, PathBuf, Vec<String>)>,
+    /// Stands in for the grammars while they are loaded in the background
+    loading: bool,
+}
+
+impl std::fmt::Debug for GrammarRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrammarRegistry")
+            .field("syntaxes", &self.syntax_set.syntaxes().len())
+            .field("loading", &self.loading)
+            .finish_non_exhaustive()
+    }
 }
 
 impl GrammarRegistry {
@@ -67,19 +78,41 @@ impl GrammarRegistry {
             user_extensions,
             filename_scopes,
             loaded_grammar_paths: Vec::new(),
+            loading: false,
         }
     }
 
     /// Create an empty grammar registry (fast, for tests that don't need syntax highlighting)
     pub fn empty() -> Arc<Self> {
+        Arc::new(Self::plain_text())
+    }
+
+    /// Create an empty registry standing in for the grammars while they load.
+    ///
+    /// Files opened with it are not highlighted until the real registry
+    /// replaces it.
+    pub fn loading() -> Arc<Self> {
+        Arc::new(Self {
+            loading: true,
+            ..Self::plain_text()
+        })
+    }
+
+    fn plain_text() -> Self {
         let mut builder = SyntaxSetBuilder::new();
         builder.add_plain_text_syntax();
-        Arc::new(Self {
+        Self {
             syntax_set: Arc::new(builder.build()),
             user_extensions: HashMap::new(),
             filename_scopes: HashMap::new(),
             loaded_grammar_paths: Vec::new(),
-        })
+            loading: false,
+        }
+    }
+
+    /// Whether this registry stands in for grammars still loading
+    pub fn is_loading(&self) -> bool {
+        self.loading
     }
 
     /// Build the default filename -> scope mappings for dotfiles and special files.
@@ -584,6 +617,7 @@ impl GrammarRegistry {
             user_extensions,
             filename_scopes: base.filename_scopes.clone(),
             loaded_grammar_paths,
+            loading: false,
         })
     }
 
@@ -19,7 +19,8 @@
 use crate::model::buffer::Buffer;
 use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlighter::{
-    highlight_color, HighlightCategory, HighlightSpan, Highlighter, Language,
+    highlight_color, overlay_highlight_spans, HighlightCategory, HighlightSpan, Highlighter,
+    Language,
 };
 use crate::view::theme::Theme;
 use std::ops::Range;
@@ -206,6 +207,8 @@ pub struct TextMateEngine {
     /// Tree-sitter language for non-highlighting features (indentation, semantic highlighting)
     /// Even when using syntect for highlighting, we track the language for other features
     ts_language: Option<Language>,
+    /// Tree-sitter highlighter producing rainbow delimiters, when they are enabled
+    delimiters: Option<Box<Highlighter>>,
 }
 
 #[derive(Debug, Clone)]
@@ -232,6 +235,7 @@ impl TextMateEngine {
             cache: None,
             last_buffer_len: 0,
             ts_language: None,
+            delimiters: None,
         }
     }
 
@@ -247,6 +251,7 @@ impl TextMateEngine {
             cache: None,
             last_buffer_len: 0,
             ts_language,
+            delimiters: None,
         }
     }
 
@@ -255,6 +260,21 @@ impl TextMateEngine {
         self.ts_language.as_ref()
     }
 
+    /// Enable or disable rainbow delimiters
+    ///
+    /// TextMate grammars have no parse tree, so brackets are found with a
+    /// delimiters-only tree-sitter highlighter when the language has a grammar.
+    pub fn set_rainbow_delimiters(&mut self, enabled: bool) {
+        if !enabled {
+            self.delimiters = None;
+        } else if self.delimiters.is_none() {
+            self.delimiters = self
+                .ts_language
+                .and_then(|lang| Highlighter::delimiters_only(lang).ok())
+                .map(Box::new);
+        }
+    }
+
     /// Highlight the visible viewport range
     ///
     /// `context_bytes` controls how far before/after the viewport to parse for accurate
@@ -266,6 +286,32 @@ impl TextMateEngine {
         viewport_end: usize,
         theme: &Theme,
         context_bytes: usize,
+    ) -> Vec<HighlightSpan> {
+        let spans =
+            self.highlight_syntax(buffer, viewport_start, viewport_end, theme, context_bytes);
+        match &mut self.delimiters {
+            Some(delimiters) => overlay_highlight_spans(
+                spans,
+                delimiters.highlight_viewport(
+                    buffer,
+                    viewport_start,
+                    viewport_end,
+                    theme,
+                    context_bytes,
+                ),
+            ),
+            None => spans,
+        }
+    }
+
+    /// Highlight the viewport with the TextMate grammar
+    fn highlight_syntax(
+        &mut self,
+        buffer: &Buffer,
+        viewport_start: usize,
+        viewport_end: usize,
+        theme: &Theme,
+        context_bytes: usize,
     ) -> Vec<HighlightSpan> {
         use syntect::parsing::{ParseState, ScopeStack};
 
@@ -465,8 +511,32 @@ impl TextMateEngine {
         spans.truncate(write_idx + 1);
     }
 
+    /// Update caches for text inserted into the buffer
+    pub fn adjust_for_insert(&mut self, position: usize, text: &str) {
+        self.invalidate_syntax_range(position..position + text.len());
+        if let Some(delimiters) = &mut self.delimiters {
+            delimiters.adjust_for_insert(position, text);
+        }
+    }
+
+    /// Update caches for a range deleted from the buffer
+    pub fn adjust_for_delete(&mut self, range: Range<usize>) {
+        self.invalidate_syntax_range(range.clone());
+        if let Some(delimiters) = &mut self.delimiters {
+            delimiters.adjust_for_delete(range);
+        }
+    }
+
     /// Invalidate cache for edited range
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
+        self.invalidate_syntax_range(edit_range.clone());
+        if let Some(delimiters) = &mut self.delimiters {
+            delimiters.invalidate_range(edit_range);
+        }
+    }
+
+    /// Invalidate the TextMate span cache if the edit intersects it
+    fn invalidate_syntax_range(&mut self, edit_range: Range<usize>) {
         if let Some(cache) = &self.cache {
             if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
                 self.cache = None;
@@ -477,6 +547,9 @@ impl TextMateEngine {
     /// Invalidate all cache
     pub fn invalidate_all(&mut self) {
         self.cache = None;
+        if let Some(delimiters) = &mut self.delimiters {
+            delimiters.invalidate_all();
+        }
     }
 
     /// Get syntax name
@@ -583,6 +656,12 @@ impl HighlightEngine {
             }
         }
 
+        // Grammars still loading: highlight once they are ready rather than
+        // build a tree-sitter highlighter that would be replaced
+        if registry.is_loading() {
+            return Self::None;
+        }
+
         // No TextMate grammar found - fall back to tree-sitter if available
         // This handles languages like TypeScript that syntect doesn't include by default
         if let Some(lang) = ts_language {
@@ -625,6 +704,12 @@ impl HighlightEngine {
             }
         }
 
+        // Grammars still loading: highlight once they are ready rather than
+        // build a tree-sitter highlighter that would be replaced
+        if registry.is_loading() {
+            return Self::None;
+        }
+
         // No TextMate grammar found - fall back to tree-sitter if available
         // This handles languages like TypeScript that syntect doesn't include by default
         if let Some(lang) = ts_language {
@@ -697,6 +782,7 @@ impl HighlightEngine {
         theme: &Theme,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
+        let _timer = crate::services::perf::timer(crate::services::perf::Phase::Highlight);
         match self {
             Self::TreeSitter(h) => {
                 h.highlight_viewport(buffer, viewport_start, viewport_end, theme, context_bytes)
@@ -708,6 +794,38 @@ impl HighlightEngine {
         }
     }
 
+    /// Notify the engine of text inserted into the buffer
+    ///
+    /// Tree-sitter applies the edit to its parse tree and re-highlights only the
+    /// damaged ranges; TextMate invalidates its cache for the inserted range.
+    pub fn adjust_for_insert(&mut self, position: usize, text: &str) {
+        match self {
+            Self::TreeSitter(h) => h.adjust_for_insert(position, text),
+            Self::TextMate(h) => h.adjust_for_insert(position, text),
+            Self::None => {}
+        }
+    }
+
+    /// Notify the engine of a range deleted from the buffer
+    pub fn adjust_for_delete(&mut self, range: Range<usize>) {
+        match self {
+            Self::TreeSitter(h) => h.adjust_for_delete(range),
+            Self::TextMate(h) => h.adjust_for_delete(range),
+            Self::None => {}
+        }
+    }
+
+    /// Enable or disable rainbow delimiters (bracket coloring by nesting depth)
+    ///
+    /// Requires a tree-sitter grammar for the buffer's language.
+    pub fn set_rainbow_delimiters(&mut self, enabled: bool) {
+        match self {
+            Self::TreeSitter(h) => h.set_rainbow_delimiters(enabled),
+            Self::TextMate(h) => h.set_rainbow_delimiters(enabled),
+            Self::None => {}
+        }
+    }
+
     /// Invalidate cache for an edited range
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
         match self {
@@ -936,6 +1054,39 @@ mod tests {
         assert!(engine.language().is_some());
     }
 
+    #[test]
+    fn test_textmate_rainbow_delimiters() {
+        let registry =
+            GrammarRegistry::load(&crate::primitives::grammar::LocalGrammarLoader::embedded_only());
+        let mut engine = HighlightEngine::for_file(Path::new("test.rs"), &registry);
+        assert_eq!(engine.backend_name(), "textmate");
+        let buffer = Buffer::from_str("fn f() { g([1]); }", 0, test_fs());
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+
+        engine.set_rainbow_delimiters(true);
+        let spans = engine.highlight_viewport(&buffer, 0, buffer.len(), &theme, 1000);
+        let color_at = |offset: usize| {
+            spans
+                .iter()
+                .find(|s| s.range.contains(&offset))
+                .map(|s| s.color)
+        };
+        assert_eq!(color_at(7), Some(theme.syntax_rainbow[0]));
+        assert_eq!(color_at(10), Some(theme.syntax_rainbow[1]));
+        assert_eq!(color_at(11), Some(theme.syntax_rainbow[2]));
+        // Spans stay sorted and non-overlapping after layering
+        assert!(spans.windows(2).all(|w| w[0].range.end <= w[1].range.start));
+
+        // Editing keeps the delimiter layer in sync with the buffer
+        let mut buffer = buffer;
+        buffer.insert(9, "h");
+        engine.adjust_for_insert(9, "h");
+        let spans = engine.highlight_viewport(&buffer, 0, buffer.len(), &theme, 1000);
+        assert!(spans
+            .iter()
+            .any(|s| s.range == (11..12) && s.color == theme.syntax_rainbow[1]));
+    }
+
     #[test]
     fn test_tree_sitter_explicit_preference() {
         let registry =
@@ -2,7 +2,14 @@
 //!
 //! # Design
 //! - **Viewport-only parsing**: Only highlights visible lines for instant performance with large files
-//! - **Incremental updates**: Re-parses only edited regions
+//! - **Incremental updates**: The parse tree of the highlighted window is kept between frames.
+//!   Buffer edits are applied to it as `InputEdit`s, tree-sitter reparses incrementally, and only
+//!   the damaged ranges (the edited lines plus whatever `changed_ranges` reports) are re-queried
+//! - **Injections**: Regions matched by the language's injections query (HTML `<script>`, Rust
+//!   macro bodies, JS tagged templates, PHP heredocs, ...) are highlighted with the injected
+//!   language when Fresh has a grammar for it; unknown languages are left to the host highlights
+//! - **Rainbow delimiters**: Optionally colors bracket tokens by nesting depth, computed from the
+//!   same parse tree so brackets inside strings and comments are left alone
 //! - **Lazy initialization**: Parsing happens on first render
 //!
 //! # Performance
@@ -12,9 +19,10 @@
 use crate::config::LARGE_FILE_THRESHOLD_BYTES;
 use crate::model::buffer::Buffer;
 use crate::view::theme::Theme;
-use fresh_languages::tree_sitter_highlight::{
-    HighlightConfiguration, HighlightEvent, Highlighter as TSHighlighter,
+use fresh_languages::tree_sitter::{
+    InputEdit, Node, Parser, Point, Query, QueryCursor, StreamingIterator, Tree,
 };
+use fresh_languages::tree_sitter_highlight::HighlightConfiguration;
 pub use fresh_languages::{HighlightCategory, Language};
 use ratatui::style::Color;
 use std::ops::Range;
@@ -48,51 +56,221 @@ pub struct HighlightSpan {
     pub color: Color,
 }
 
+/// What a cached span is colored by (resolved against the theme at render time)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpanKind {
+    /// Syntax category from the highlights query
+    Category(HighlightCategory),
+    /// Bracket at the given nesting depth (rainbow delimiters)
+    Delimiter(usize),
+}
+
+impl SpanKind {
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Category(category) => highlight_color(category, theme),
+            Self::Delimiter(depth) => theme.syntax_rainbow[depth % theme.syntax_rainbow.len()],
+        }
+    }
+}
+
 /// Internal span used for caching (stores category instead of color)
 #[derive(Debug, Clone)]
 struct CachedSpan {
-    /// Byte range in the buffer
+    /// Byte range relative to the start of the parsed window
     range: Range<usize>,
-    /// Highlight category for this span
-    category: HighlightCategory,
+    /// What colors this span
+    kind: SpanKind,
 }
 
-/// Cache of highlighted spans for a specific byte range
-#[derive(Debug, Clone)]
+/// Bracket tokens colored by rainbow delimiters
+const OPEN_DELIMITERS: &[&str] = &["(", "[", "{"];
+const CLOSE_DELIMITERS: &[&str] = &[")", "]", "}"];
+
+/// Parse state and highlighted spans for the parsed window of the buffer
 struct HighlightCache {
-    /// Byte range this cache covers
-    range: Range<usize>,
-    /// Highlighted spans within this range (stores categories for theme-independent caching)
+    /// Buffer offset where the parsed window starts
+    start: usize,
+    /// Source of the parsed window, kept in sync with buffer edits
+    source: Vec<u8>,
+    /// Parse tree for `source`, edited in place as the buffer changes
+    tree: Tree,
+    /// Highlighted spans sorted by start (stores categories for theme-independent caching)
     spans: Vec<CachedSpan>,
+    /// Window-relative ranges edited since the last highlight pass
+    damaged: Vec<Range<usize>>,
 }
 
-/// Syntax highlighter with incremental viewport-based parsing
-pub struct Highlighter {
-    /// Tree-sitter highlighter instance
-    ts_highlighter: TSHighlighter,
-    /// Language being highlighted
+impl HighlightCache {
+    /// Byte range of the buffer this cache covers
+    fn range(&self) -> Range<usize> {
+        self.start..self.start + self.source.len()
+    }
+}
+
+/// Parser and queries for one language (the host language or an injected one)
+struct LanguageLayer {
     language: Language,
-    /// Highlight configuration for the language
+    parser: Parser,
     config: HighlightConfiguration,
-    /// Cache of highlighted spans (only for visible viewport)
+    /// Highlight category for each capture of the highlights query
+    categories: Vec<Option<HighlightCategory>>,
+    /// Compiled injections query, if the language has one
+    injections: Option<Query>,
+}
+
+impl LanguageLayer {
+    fn new(language: Language) -> Result<Self, String> {
+        let config = language.highlight_config()?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&config.language)
+            .map_err(|e| format!("Failed to create {language} parser: {e}"))?;
+        let categories = config
+            .query
+            .capture_names()
+            .iter()
+            .map(|name| {
+                recognized_index(language.highlight_names(), name)
+                    .and_then(|index| language.highlight_category(index))
+            })
+            .collect();
+        let injections = language.injections_query().and_then(|source| {
+            match Query::new(&config.language, source) {
+                Ok(query) => Some(query),
+                Err(e) => {
+                    tracing::debug!("Ignoring {} injections query: {}", language, e);
+                    None
+                }
+            }
+        });
+        Ok(Self {
+            language,
+            parser,
+            config,
+            categories,
+            injections,
+        })
+    }
+
+    /// Highlight `range` of a parsed tree, returning non-overlapping spans clipped to it
+    fn highlight(
+        &self,
+        cursor: &mut QueryCursor,
+        root: Node<'_>,
+        source: &[u8],
+        range: Range<usize>,
+    ) -> Vec<CachedSpan> {
+        let mut captures = Vec::new();
+        cursor.set_byte_range(range.clone());
+        let mut matches = cursor.captures(&self.config.query, root, source);
+        while let Some((query_match, index)) = matches.next() {
+            let capture = query_match.captures[*index];
+            if let Some(category) = self.categories[capture.index as usize] {
+                captures.push((
+                    capture.node.byte_range(),
+                    query_match.pattern_index,
+                    category,
+                ));
+            }
+        }
+        flatten_captures(captures, range)
+    }
+
+    /// Find injected regions within `range` and the languages they should use
+    fn injected_regions(
+        &self,
+        cursor: &mut QueryCursor,
+        root: Node<'_>,
+        source: &[u8],
+        range: Range<usize>,
+    ) -> Vec<(Range<usize>, Language)> {
+        let Some(query) = &self.injections else {
+            return Vec::new();
+        };
+        let content_index = query.capture_index_for_name("injection.content");
+        let language_index = query.capture_index_for_name("injection.language");
+
+        let mut regions = Vec::new();
+        cursor.set_byte_range(range);
+        let mut matches = cursor.matches(query, root, source);
+        while let Some(query_match) = matches.next() {
+            let mut content = None;
+            let mut name = None;
+            for capture in query_match.captures {
+                if Some(capture.index) == content_index {
+                    content = Some(capture.node.byte_range());
+                } else if Some(capture.index) == language_index {
+                    name = capture.node.utf8_text(source).ok();
+                }
+            }
+            let name = name.or_else(|| {
+                query
+                    .property_settings(query_match.pattern_index)
+                    .iter()
+                    .find(|property| &*property.key == "injection.language")
+                    .and_then(|property| property.value.as_deref())
+            });
+            let language =
+                name.and_then(|name| Language::from_id(name).or_else(|| Language::from_name(name)));
+            if let (Some(content), Some(language)) = (content, language) {
+                regions.push((content, language));
+            }
+        }
+        regions
+    }
+}
+
+/// Syntax highlighter with incremental viewport-based parsing
+pub struct Highlighter {
+    /// Parser and queries for the buffer's language
+    layer: LanguageLayer,
+    /// Layers for injected languages, created on first use (`None` if unavailable)
+    injected: Vec<(Language, Option<LanguageLayer>)>,
+    /// Query cursor reused across highlight passes
+    cursor: QueryCursor,
+    /// Parse tree and highlighted spans for the parsed window
     cache: Option<HighlightCache>,
     /// Last known buffer length (for detecting complete buffer changes)
     last_buffer_len: usize,
+    /// Whether syntax categories are highlighted (false for a delimiters-only highlighter)
+    syntax: bool,
+    /// Whether brackets are colored by nesting depth
+    rainbow_delimiters: bool,
 }
 
 impl Highlighter {
     /// Create a new highlighter for the given language
     pub fn new(language: Language) -> Result<Self, String> {
-        let config = language.highlight_config()?;
         Ok(Self {
-            ts_highlighter: TSHighlighter::new(),
-            language,
-            config,
+            layer: LanguageLayer::new(language)?,
+            injected: Vec::new(),
+            cursor: QueryCursor::new(),
             cache: None,
             last_buffer_len: 0,
+            syntax: true,
+            rainbow_delimiters: false,
         })
     }
 
+    /// Create a highlighter that only produces rainbow delimiter spans
+    ///
+    /// Used alongside TextMate highlighting, which has no parse tree of its own.
+    pub fn delimiters_only(language: Language) -> Result<Self, String> {
+        let mut highlighter = Self::new(language)?;
+        highlighter.syntax = false;
+        highlighter.rainbow_delimiters = true;
+        Ok(highlighter)
+    }
+
+    /// Enable or disable rainbow delimiters
+    pub fn set_rainbow_delimiters(&mut self, enabled: bool) {
+        if self.rainbow_delimiters != enabled {
+            self.rainbow_delimiters = enabled;
+            self.cache = None;
+        }
+    }
+
     /// Highlight the visible viewport range
     ///
     /// This only parses the visible lines for instant performance with large files.
@@ -108,31 +286,54 @@ impl Highlighter {
         theme: &Theme,
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
-        // Check if cache is valid for this range
-        if let Some(cache) = &self.cache {
-            if cache.range.start <= viewport_start
-                && cache.range.end >= viewport_end
-                && self.last_buffer_len == buffer.len()
-            {
-                // Cache hit! Filter spans to the requested range and resolve colors from theme
-                return cache
-                    .spans
-                    .iter()
-                    .filter(|span| {
-                        span.range.start < viewport_end && span.range.end > viewport_start
-                    })
-                    .map(|span| HighlightSpan {
-                        range: span.range.clone(),
-                        color: highlight_color(span.category, theme),
-                    })
-                    .collect();
-            }
+        let covers_viewport = self.cache.as_ref().is_some_and(|cache| {
+            let range = cache.range();
+            range.start <= viewport_start && range.end >= viewport_end.min(buffer.len())
+        });
+
+        if covers_viewport && self.last_buffer_len == buffer.len() {
+            // Cache hit - bring the tree up to date with any edits since the last pass
+            self.rehighlight_damaged();
+        } else if !self.parse_window(buffer, viewport_start, viewport_end, context_bytes) {
+            return Vec::new();
         }
 
-        // Cache miss - need to parse
+        let Some(cache) = &self.cache else {
+            return Vec::new();
+        };
+
+        // Filter spans to the requested range and resolve colors from theme
+        cache
+            .spans
+            .iter()
+            .filter_map(|span| {
+                let range = cache.start + span.range.start..cache.start + span.range.end;
+                (range.start < viewport_end && range.end > viewport_start).then(|| HighlightSpan {
+                    range,
+                    color: span.kind.color(theme),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse the viewport plus context from scratch and highlight all of it
+    ///
+    /// Returns false if the window is too large to parse.
+    fn parse_window(
+        &mut self,
+        buffer: &Buffer,
+        viewport_start: usize,
+        viewport_end: usize,
+        context_bytes: usize,
+    ) -> bool {
+        self.cache = None;
+
         // Extend range for context (helps with multi-line constructs like strings, comments, nested blocks)
         let parse_start = viewport_start.saturating_sub(context_bytes);
         let parse_end = (viewport_end + context_bytes).min(buffer.len());
+        if parse_end < parse_start {
+            return false;
+        }
         let parse_range = parse_start..parse_end;
 
         // Limit parse size for safety
@@ -143,74 +344,161 @@ impl Highlighter {
                 MAX_PARSE_BYTES
             );
             // Just return empty spans if the range is too large
-            return Vec::new();
+            return false;
         }
 
         // Extract source bytes from buffer
-        let source = buffer.slice_bytes(parse_range.clone());
-
-        // Highlight the source - store categories for theme-independent caching
-        let mut cached_spans = Vec::new();
-        match self.ts_highlighter.highlight(
-            &self.config,
-            &source,
-            None,     // cancellation flag
-            |_| None, // injection callback
-        ) {
-            Ok(highlights) => {
-                let mut current_highlight: Option<usize> = None;
-
-                for event in highlights {
-                    match event {
-                        Ok(HighlightEvent::Source { start, end }) => {
-                            let span_start = parse_start + start;
-                            let span_end = parse_start + end;
-
-                            if let Some(highlight_idx) = current_highlight {
-                                if let Some(category) =
-                                    self.language.highlight_category(highlight_idx)
-                                {
-                                    cached_spans.push(CachedSpan {
-                                        range: span_start..span_end,
-                                        category,
-                                    });
-                                }
-                            }
-                        }
-                        Ok(HighlightEvent::HighlightStart(s)) => {
-                            current_highlight = Some(s.0);
-                        }
-                        Ok(HighlightEvent::HighlightEnd) => {
-                            current_highlight = None;
-                        }
-                        Err(e) => {
-                            tracing::warn!("Highlight error: {}", e);
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::error!("Failed to highlight: {}", e);
-            }
-        }
+        let source = buffer.slice_bytes(parse_range);
+        let Some(tree) = self.layer.parser.parse(&source, None) else {
+            tracing::error!("Failed to parse {} source", self.layer.language);
+            return false;
+        };
 
-        // Update cache
+        let spans = self.highlight_range(&tree, &source, 0..source.len());
         self.cache = Some(HighlightCache {
-            range: parse_range,
-            spans: cached_spans.clone(),
+            start: parse_start,
+            source,
+            tree,
+            spans,
+            damaged: Vec::new(),
         });
         self.last_buffer_len = buffer.len();
+        true
+    }
+
+    /// Reparse the cached tree after edits and re-query only the damaged ranges
+    fn rehighlight_damaged(&mut self) {
+        let Some(mut cache) = self.cache.take() else {
+            return;
+        };
+        if cache.damaged.is_empty() {
+            self.cache = Some(cache);
+            return;
+        }
 
-        // Filter to requested viewport and resolve colors from theme
-        cached_spans
+        let Some(tree) = self.layer.parser.parse(&cache.source, Some(&cache.tree)) else {
+            tracing::error!("Failed to reparse {} source", self.layer.language);
+            return;
+        };
+
+        let mut damaged = std::mem::take(&mut cache.damaged);
+        damaged.extend(
+            cache
+                .tree
+                .changed_ranges(&tree)
+                .map(|range| range.start_byte..range.end_byte),
+        );
+        cache.tree = tree;
+
+        let damaged = damaged
             .into_iter()
-            .filter(|span| span.range.start < viewport_end && span.range.end > viewport_start)
-            .map(|span| HighlightSpan {
-                range: span.range,
-                color: highlight_color(span.category, theme),
-            })
-            .collect()
+            .map(|range| line_bounds(&cache.source, range))
+            .collect();
+        for range in merge_ranges(damaged) {
+            let spans = self.highlight_range(&cache.tree, &cache.source, range.clone());
+            remove_range(&mut cache.spans, &range);
+            cache.spans.extend(spans);
+        }
+        cache.spans.sort_by_key(|span| span.range.start);
+        self.cache = Some(cache);
+    }
+
+    /// Highlight `range` of the window: host spans, then injected languages, then
+    /// rainbow delimiters, each layered over the previous
+    fn highlight_range(
+        &mut self,
+        tree: &Tree,
+        source: &[u8],
+        range: Range<usize>,
+    ) -> Vec<CachedSpan> {
+        let root = tree.root_node();
+        let mut spans = Vec::new();
+
+        if self.syntax {
+            spans = self
+                .layer
+                .highlight(&mut self.cursor, root, source, range.clone());
+
+            let mut injected_spans = Vec::new();
+            let regions =
+                self.layer
+                    .injected_regions(&mut self.cursor, root, source, range.clone());
+            for (content, language) in regions {
+                let Some(layer) = injected_layer(&mut self.injected, language) else {
+                    continue;
+                };
+
+                // Injected regions are small (a script body, a macro call), so they are
+                // parsed from scratch rather than tracked incrementally.
+                let text = &source[content.clone()];
+                let Some(injected_tree) = layer.parser.parse(text, None) else {
+                    continue;
+                };
+                let local_start = range.start.max(content.start) - content.start;
+                let local_end = range.end.min(content.end).saturating_sub(content.start);
+                if local_start >= local_end {
+                    continue;
+                }
+                injected_spans.extend(
+                    layer
+                        .highlight(
+                            &mut self.cursor,
+                            injected_tree.root_node(),
+                            text,
+                            local_start..local_end,
+                        )
+                        .into_iter()
+                        .map(|span| CachedSpan {
+                            range: content.start + span.range.start..content.start + span.range.end,
+                            kind: span.kind,
+                        }),
+                );
+            }
+            injected_spans.sort_by_key(|span| span.range.start);
+            spans = overlay_spans(spans, injected_spans);
+        }
+
+        if self.rainbow_delimiters {
+            spans = overlay_spans(spans, delimiter_spans(root, range));
+        }
+        spans
+    }
+
+    /// Update the parse tree and cached spans for text inserted into the buffer
+    ///
+    /// Call this after the buffer has been edited. Edits inside the parsed window are
+    /// applied to the tree so the next highlight pass only reparses what changed.
+    pub fn adjust_for_insert(&mut self, position: usize, text: &str) {
+        self.apply_edit(position, 0, text.as_bytes());
+    }
+
+    /// Update the parse tree and cached spans for a range deleted from the buffer
+    pub fn adjust_for_delete(&mut self, range: Range<usize>) {
+        self.apply_edit(range.start, range.len(), &[]);
+    }
+
+    fn apply_edit(&mut self, position: usize, old_len: usize, new_text: &[u8]) {
+        let new_len = new_text.len();
+        if old_len == 0 && new_len == 0 {
+            return;
+        }
+        self.last_buffer_len = (self.last_buffer_len + new_len).saturating_sub(old_len);
+
+        let Some(cache) = self.cache.as_mut() else {
+            return;
+        };
+        let window = cache.range();
+        let old_end = position + old_len;
+
+        if position >= window.start && old_end <= window.end {
+            cache.edit(position - window.start, old_len, new_text);
+        } else if old_end <= window.start {
+            // Edit before the window: the parsed text is unchanged, it just moves
+            cache.start = cache.start + new_len - old_len;
+        } else if position < window.end {
+            // Edit straddles a window boundary - start over on the next pass
+            self.cache = None;
+        }
     }
 
     /// Invalidate cache for an edited range
@@ -218,8 +506,9 @@ impl Highlighter {
     /// Call this when the buffer is edited to mark the cache as stale.
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
         if let Some(cache) = &self.cache {
+            let range = cache.range();
             // If edit intersects cache, invalidate it
-            if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
+            if edit_range.start < range.end && edit_range.end > range.start {
                 self.cache = None;
             }
         }
@@ -232,7 +521,316 @@ impl Highlighter {
 
     /// Get the current language
     pub fn language(&self) -> &Language {
-        &self.language
+        &self.layer.language
+    }
+}
+
+impl HighlightCache {
+    /// Apply a window-relative edit to the source, tree, and cached spans
+    fn edit(&mut self, start: usize, old_len: usize, new_text: &[u8]) {
+        let old_end = start + old_len;
+        let new_len = new_text.len();
+        let new_end = start + new_len;
+
+        let start_position = point_at(&self.source, start);
+        let old_end_position = advance_point(start_position, &self.source[start..old_end]);
+        let new_end_position = advance_point(start_position, new_text);
+        self.source.splice(start..old_end, new_text.iter().copied());
+        self.tree.edit(&InputEdit {
+            start_byte: start,
+            old_end_byte: old_end,
+            new_end_byte: new_end,
+            start_position,
+            old_end_position,
+            new_end_position,
+        });
+
+        // Shift what follows the edit; whatever the edit touched becomes damaged
+        let mut damaged = start..new_end;
+        let mut shift = |range: &mut Range<usize>| -> bool {
+            if range.end <= start {
+                true
+            } else if range.start >= old_end {
+                range.start = range.start + new_len - old_len;
+                range.end = range.end + new_len - old_len;
+                true
+            } else {
+                let end = if range.end > old_end {
+                    range.end + new_len - old_len
+                } else {
+                    new_end
+                };
+                damaged.start = damaged.start.min(range.start);
+                damaged.end = damaged.end.max(end);
+                false
+            }
+        };
+        self.spans.retain_mut(|span| shift(&mut span.range));
+        self.damaged.retain_mut(|range| shift(range));
+        self.damaged.push(damaged);
+    }
+}
+
+/// Get (creating on first use) the layer for an injected language
+fn injected_layer(
+    injected: &mut Vec<(Language, Option<LanguageLayer>)>,
+    language: Language,
+) -> Option<&mut LanguageLayer> {
+    let index = match injected.iter().position(|(lang, _)| *lang == language) {
+        Some(index) => index,
+        None => {
+            injected.push((language, LanguageLayer::new(language).ok()));
+            injected.len() - 1
+        }
+    };
+    injected[index].1.as_mut()
+}
+
+/// Find bracket tokens within `range` and color them by nesting depth.
+///
+/// Depth counts the enclosing nodes that are themselves delimited (have an opening
+/// bracket token as a child). Brackets inside strings and comments are not tokens of
+/// their own in the tree, so they are never colored.
+fn delimiter_spans(root: Node<'_>, range: Range<usize>) -> Vec<CachedSpan> {
+    let mut spans = Vec::new();
+    let mut cursor = root.walk();
+    let mut stack = vec![(root, 0usize)];
+    while let Some((node, depth)) = stack.pop() {
+        let children: Vec<Node<'_>> = node.children(&mut cursor).collect();
+        let delimited = children
+            .iter()
+            .any(|child| !child.is_named() && OPEN_DELIMITERS.contains(&child.kind()));
+        for child in children {
+            if child.end_byte() <= range.start || child.start_byte() >= range.end {
+                continue;
+            }
+            if child.child_count() > 0 {
+                stack.push((child, depth + usize::from(delimited)));
+            } else if delimited
+                && !child.is_named()
+                && child.start_byte() < child.end_byte()
+                && (OPEN_DELIMITERS.contains(&child.kind())
+                    || CLOSE_DELIMITERS.contains(&child.kind()))
+            {
+                spans.push(CachedSpan {
+                    range: child.byte_range(),
+                    kind: SpanKind::Delimiter(depth),
+                });
+            }
+        }
+    }
+    spans.sort_by_key(|span| span.range.start);
+    spans
+}
+
+/// A span that can be trimmed when another span is layered over it
+trait LayeredSpan: Sized {
+    fn range(&self) -> &Range<usize>;
+    fn with_range(&self, range: Range<usize>) -> Self;
+}
+
+impl LayeredSpan for CachedSpan {
+    fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+    fn with_range(&self, range: Range<usize>) -> Self {
+        Self {
+            range,
+            kind: self.kind,
+        }
+    }
+}
+
+impl LayeredSpan for HighlightSpan {
+    fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+    fn with_range(&self, range: Range<usize>) -> Self {
+        Self {
+            range,
+            color: self.color,
+        }
+    }
+}
+
+/// Layer `top` over `base`: wherever a `top` span exists it replaces `base`.
+///
+/// Both lists must be sorted by start.
+fn overlay_spans<S: LayeredSpan>(base: Vec<S>, top: Vec<S>) -> Vec<S> {
+    if top.is_empty() {
+        return base;
+    }
+    let mut spans = Vec::with_capacity(base.len() + top.len());
+    let mut first = 0;
+    for span in base {
+        let end = span.range().end;
+        let mut start = span.range().start;
+        while first < top.len() && top[first].range().end <= start {
+            first += 1;
+        }
+        for cover in top[first..]
+            .iter()
+            .take_while(|cover| cover.range().start < end)
+        {
+            if cover.range().start > start {
+                spans.push(span.with_range(start..cover.range().start));
+            }
+            start = start.max(cover.range().end);
+        }
+        if start < end {
+            spans.push(span.with_range(start..end));
+        }
+    }
+    spans.extend(top);
+    spans.sort_by_key(|span| span.range().start);
+    spans
+}
+
+/// Layer rendered `top` spans (e.g. rainbow delimiters) over `base` highlight spans
+pub fn overlay_highlight_spans(
+    base: Vec<HighlightSpan>,
+    top: Vec<HighlightSpan>,
+) -> Vec<HighlightSpan> {
+    overlay_spans(base, top)
+}
+
+/// Match a capture name against the configured highlight names the same way
+/// `tree_sitter_highlight` does: the longest recognized name whose parts all
+/// appear in the capture name wins (`function.method` highlights as `function`).
+fn recognized_index(recognized: &[&str], capture_name: &str) -> Option<usize> {
+    let parts: Vec<&str> = capture_name.split('.').collect();
+    let mut best = None;
+    let mut best_len = 0;
+    for (index, name) in recognized.iter().enumerate() {
+        let len = name.split('.').count();
+        if len > best_len && name.split('.').all(|part| parts.contains(&part)) {
+            best = Some(index);
+            best_len = len;
+        }
+    }
+    best
+}
+
+/// Flatten nested captures into non-overlapping spans clipped to `clip`.
+///
+/// The innermost capture wins; when several patterns capture the same node the
+/// earliest pattern wins, matching `tree_sitter_highlight`.
+fn flatten_captures(
+    mut captures: Vec<(Range<usize>, usize, HighlightCategory)>,
+    clip: Range<usize>,
+) -> Vec<CachedSpan> {
+    captures.sort_by(|a, b| {
+        a.0.start
+            .cmp(&b.0.start)
+            .then(b.0.end.cmp(&a.0.end))
+            .then(a.1.cmp(&b.1))
+    });
+    captures.dedup_by(|later, earlier| later.0 == earlier.0);
+
+    let mut spans = Vec::new();
+    let mut push = |start: usize, end: usize, category: HighlightCategory| {
+        let start = start.max(clip.start);
+        let end = end.min(clip.end);
+        if start < end {
+            spans.push(CachedSpan {
+                range: start..end,
+                kind: SpanKind::Category(category),
+            });
+        }
+    };
+
+    // Stack of open captures (end, category); the top one colors the text
+    let mut stack: Vec<(usize, HighlightCategory)> = Vec::new();
+    let mut pos = 0;
+    for (range, _, category) in captures {
+        while let Some(&(end, outer)) = stack.last() {
+            if end > range.start {
+                break;
+            }
+            push(pos, end, outer);
+            pos = pos.max(end);
+            stack.pop();
+        }
+        let mut end = range.end;
+        if let Some(&(outer_end, outer)) = stack.last() {
+            push(pos, range.start, outer);
+            end = end.min(outer_end);
+        }
+        pos = range.start;
+        stack.push((end, category));
+    }
+    while let Some((end, category)) = stack.pop() {
+        push(pos, end, category);
+        pos = pos.max(end);
+    }
+    spans
+}
+
+/// Remove `range` from the spans, trimming spans that straddle its edges
+fn remove_range(spans: &mut Vec<CachedSpan>, range: &Range<usize>) {
+    let mut kept = Vec::with_capacity(spans.len());
+    for span in spans.drain(..) {
+        if span.range.end <= range.start || span.range.start >= range.end {
+            kept.push(span);
+            continue;
+        }
+        if span.range.start < range.start {
+            kept.push(CachedSpan {
+                range: span.range.start..range.start,
+                kind: span.kind,
+            });
+        }
+        if span.range.end > range.end {
+            kept.push(CachedSpan {
+                range: range.end..span.range.end,
+                kind: span.kind,
+            });
+        }
+    }
+    *spans = kept;
+}
+
+/// Extend a range to cover the whole lines it touches
+fn line_bounds(source: &[u8], range: Range<usize>) -> Range<usize> {
+    let start = range.start.min(source.len());
+    let end = range.end.clamp(start, source.len());
+    let line_start = source[..start]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line_end = source[end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(source.len(), |i| end + i + 1);
+    line_start..line_end
+}
+
+/// Sort ranges and merge the ones that overlap or touch
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Row/column of a byte offset in the source
+fn point_at(source: &[u8], offset: usize) -> Point {
+    advance_point(Point::new(0, 0), &source[..offset])
+}
+
+/// The point reached after `text`, starting at `point`
+fn advance_point(point: Point, text: &[u8]) -> Point {
+    match text.iter().rposition(|&b| b == b'\n') {
+        Some(last_newline) => Point::new(
+            point.row + text.iter().filter(|&&b| b == b'\n').count(),
+            text.len() - last_newline - 1,
+        ),
+        None => Point::new(point.row, point.column + text.len()),
     }
 }
 
@@ -417,6 +1015,123 @@ mod tests {
         assert!(highlighter.cache.is_none());
     }
 
+    fn span_list(spans: &[HighlightSpan]) -> Vec<(Range<usize>, Color)> {
+        spans.iter().map(|s| (s.range.clone(), s.color)).collect()
+    }
+
+    #[test]
+    fn test_incremental_edits_match_full_parse() {
+        let mut buffer = Buffer::from_str_test("fn main() {\n    let x = 1;\n}\n");
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+
+        // Comment out the `let` line, then open a string that runs to the end
+        let edits: [(usize, &str); 2] = [(16, "// "), (4, "\"")];
+        for (position, text) in edits {
+            buffer.insert(position, text);
+            highlighter.adjust_for_insert(position, text);
+            assert!(
+                highlighter.cache.is_some(),
+                "edit should keep the parse tree"
+            );
+
+            let incremental =
+                highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+            let mut fresh = Highlighter::new(Language::Rust).unwrap();
+            let full = fresh.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+            assert_eq!(span_list(&incremental), span_list(&full));
+        }
+
+        // Delete both insertions again
+        for range in [4..5, 16..19] {
+            buffer.delete(range.clone());
+            highlighter.adjust_for_delete(range);
+            let incremental =
+                highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+            let mut fresh = Highlighter::new(Language::Rust).unwrap();
+            let full = fresh.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+            assert_eq!(span_list(&incremental), span_list(&full));
+        }
+    }
+
+    #[test]
+    fn test_edit_before_window_shifts_cache() {
+        let mut content = String::new();
+        for i in 0..200 {
+            content.push_str(&format!("fn function_{i}() {{}}\n"));
+        }
+        let mut buffer = Buffer::from_str_test(&content);
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        highlighter.highlight_viewport(&buffer, 3000, 3500, &theme, 500);
+        let window_start = highlighter.cache.as_ref().unwrap().start;
+
+        buffer.insert(0, "// header\n");
+        highlighter.adjust_for_insert(0, "// header\n");
+        assert_eq!(highlighter.cache.as_ref().unwrap().start, window_start + 10);
+
+        let shifted = highlighter.highlight_viewport(&buffer, 3010, 3510, &theme, 500);
+        let mut fresh = Highlighter::new(Language::Rust).unwrap();
+        let full = fresh.highlight_viewport(&buffer, 3010, 3510, &theme, 500);
+        let keyword_starts = |spans: &[HighlightSpan]| -> Vec<usize> {
+            spans
+                .iter()
+                .filter(|s| s.color == theme.syntax_keyword)
+                .map(|s| s.range.start)
+                .collect()
+        };
+        assert_eq!(keyword_starts(&shifted), keyword_starts(&full));
+    }
+
+    #[test]
+    fn test_html_script_injection() {
+        let buffer = Buffer::from_str_test("<p>let</p>\n<script>let x = 1;</script>\n");
+        let mut highlighter = Highlighter::new(Language::HTML).unwrap();
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+
+        // `let` inside <script> is highlighted as a JavaScript keyword...
+        let script_let = 19..22;
+        assert!(spans
+            .iter()
+            .any(|s| s.range == script_let && s.color == theme.syntax_keyword));
+        // ...while the same word in HTML text is not
+        assert!(!spans
+            .iter()
+            .any(|s| s.range == (3..6) && s.color == theme.syntax_keyword));
+    }
+
+    #[test]
+    fn test_rainbow_delimiters_follow_nesting() {
+        let buffer = Buffer::from_str_test("fn f() { g(\"(\", [1]); }");
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let color_at = |spans: &[HighlightSpan], offset: usize| {
+            spans
+                .iter()
+                .find(|s| s.range.start == offset && s.range.len() == 1)
+                .map(|s| s.color)
+        };
+
+        // Disabled by default
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+        assert_eq!(color_at(&spans, 7), None);
+
+        highlighter.set_rainbow_delimiters(true);
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+        // `(` of `f()` and `{` share the outermost level
+        assert_eq!(color_at(&spans, 4), Some(theme.syntax_rainbow[0]));
+        assert_eq!(color_at(&spans, 7), Some(theme.syntax_rainbow[0]));
+        // `(` of the call, then `[` inside it
+        assert_eq!(color_at(&spans, 10), Some(theme.syntax_rainbow[1]));
+        assert_eq!(color_at(&spans, 16), Some(theme.syntax_rainbow[2]));
+        assert_eq!(color_at(&spans, 18), Some(theme.syntax_rainbow[2]));
+        // The paren inside the string literal stays a string
+        let in_string = spans.iter().find(|s| s.range.contains(&12)).unwrap();
+        assert_eq!(in_string.color, theme.syntax_string);
+    }
+
     #[test]
     fn test_theme_affects_colors() {
         let buffer = Buffer::from_str_test("fn main() {\n    println!(\"Hello\");\n}");
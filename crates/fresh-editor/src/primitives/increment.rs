@@ -0,0 +1,270 @@
+//! Incrementing and decrementing the value under the cursor.
+//!
+//! Works on one line at a time. A word under the cursor that belongs to a
+//! cycle (such as `true false`) is replaced by the next or previous word of
+//! the cycle; otherwise the first number under or after the cursor is
+//! changed. Decimal integers keep their zero padding, hex numbers keep their
+//! width and letter case, and decimals keep their number of fraction digits.
+
+use std::ops::Range;
+
+/// Replacement for part of a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncrementEdit {
+    /// Byte range in the line that is replaced
+    pub range: Range<usize>,
+    /// New text for the range
+    pub text: String,
+}
+
+/// Add `delta` to the value under or after byte `column` of `line`
+///
+/// `cycles` are lists of words to step through, each given as one string
+/// with the words separated by whitespace. Returns `None` when there is no
+/// value to change.
+pub fn increment_at(
+    line: &str,
+    column: usize,
+    delta: i64,
+    cycles: &[String],
+) -> Option<IncrementEdit> {
+    let column = column.min(line.len());
+    cycle_word(line, column, delta, cycles).or_else(|| increment_number(line, column, delta))
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Replace the word under the cursor with its neighbour in a cycle
+fn cycle_word(line: &str, column: usize, delta: i64, cycles: &[String]) -> Option<IncrementEdit> {
+    let bytes = line.as_bytes();
+    let mut start = column;
+    while start > 0 && is_word_byte(bytes[start - 1]) {
+        start -= 1;
+    }
+    let mut end = column;
+    while end < bytes.len() && is_word_byte(bytes[end]) {
+        end += 1;
+    }
+    if start == end {
+        return None;
+    }
+    let word = &line[start..end];
+
+    for cycle in cycles {
+        let words: Vec<&str> = cycle.split_whitespace().collect();
+        let Some(index) = words.iter().position(|w| w.eq_ignore_ascii_case(word)) else {
+            continue;
+        };
+        let next = (index as i64 + delta).rem_euclid(words.len() as i64) as usize;
+        return Some(IncrementEdit {
+            range: start..end,
+            text: match_case(words[next], word),
+        });
+    }
+    None
+}
+
+/// `word` written in the case of `like`: all upper, capitalized, or as is
+fn match_case(word: &str, like: &str) -> String {
+    let has_letters = like.chars().any(|c| c.is_ascii_alphabetic());
+    if has_letters && !like.chars().any(|c| c.is_ascii_lowercase()) {
+        return word.to_ascii_uppercase();
+    }
+    if like.starts_with(|c: char| c.is_ascii_uppercase()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            return first.to_ascii_uppercase().to_string() + chars.as_str();
+        }
+    }
+    word.to_string()
+}
+
+/// A number found in a line
+struct NumberToken {
+    range: Range<usize>,
+    kind: NumberKind,
+}
+
+enum NumberKind {
+    Hex,
+    /// Decimal, with the number of digits after the point
+    Decimal(usize),
+}
+
+/// Numbers in `line`, in order
+fn number_tokens(line: &str) -> Vec<NumberToken> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        // Digits inside an identifier such as `utf8` or `x86_64` aren't numbers
+        let in_word = i > 0 && (bytes[i - 1].is_ascii_alphabetic() || bytes[i - 1] == b'_');
+
+        let is_hex = bytes[i] == b'0'
+            && matches!(bytes.get(i + 1), Some(b'x' | b'X'))
+            && bytes.get(i + 2).is_some_and(|b| b.is_ascii_hexdigit());
+        if is_hex {
+            let mut end = i + 2;
+            while end < bytes.len() && bytes[end].is_ascii_hexdigit() {
+                end += 1;
+            }
+            if !in_word {
+                tokens.push(NumberToken {
+                    range: i..end,
+                    kind: NumberKind::Hex,
+                });
+            }
+            i = end;
+            continue;
+        }
+
+        let mut end = i;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        let mut fraction = 0;
+        if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+            let mut fraction_end = end + 1;
+            while fraction_end < bytes.len() && bytes[fraction_end].is_ascii_digit() {
+                fraction_end += 1;
+            }
+            // `1.9.0` is a version, whose parts are numbers of their own
+            let is_version = bytes.get(fraction_end) == Some(&b'.')
+                && bytes.get(fraction_end + 1).is_some_and(u8::is_ascii_digit);
+            if !is_version {
+                fraction = fraction_end - end - 1;
+                end = fraction_end;
+            }
+        }
+        if !in_word {
+            // A minus sign belongs to the number unless it follows a word,
+            // as in `a-1`
+            let negative = i > 0
+                && bytes[i - 1] == b'-'
+                && (i < 2 || !(is_word_byte(bytes[i - 2]) || bytes[i - 2] == b')'));
+            tokens.push(NumberToken {
+                range: if negative { i - 1 } else { i }..end,
+                kind: NumberKind::Decimal(fraction),
+            });
+        }
+        i = end;
+    }
+    tokens
+}
+
+/// Change the first number that ends after the cursor
+fn increment_number(line: &str, column: usize, delta: i64) -> Option<IncrementEdit> {
+    let token = number_tokens(line)
+        .into_iter()
+        .find(|token| token.range.end > column)?;
+    let text = &line[token.range.clone()];
+    let new_text = match token.kind {
+        NumberKind::Hex => increment_hex(&text[2..], delta).map(|digits| {
+            let prefix = &text[..2];
+            format!("{prefix}{digits}")
+        }),
+        NumberKind::Decimal(fraction) => increment_decimal(text, fraction, delta),
+    }?;
+    Some(IncrementEdit {
+        range: token.range,
+        text: new_text,
+    })
+}
+
+/// Hex digits plus `delta`, wrapping around and keeping width and case
+fn increment_hex(digits: &str, delta: i64) -> Option<String> {
+    let value = u64::from_str_radix(digits, 16).ok()?;
+    let value = value.wrapping_add(delta as u64);
+    let width = digits.len();
+    if digits.chars().any(|c| c.is_ascii_uppercase()) {
+        Some(format!("{value:0width$X}"))
+    } else {
+        Some(format!("{value:0width$x}"))
+    }
+}
+
+/// Decimal number plus `delta`, keeping zero padding and fraction digits
+fn increment_decimal(text: &str, fraction: usize, delta: i64) -> Option<String> {
+    let negative = text.starts_with('-');
+    let unsigned = text.trim_start_matches('-');
+    let integer_digits = unsigned.split('.').next().unwrap_or_default();
+    let padded_width = if integer_digits.len() > 1 && integer_digits.starts_with('0') {
+        integer_digits.len()
+    } else {
+        0
+    };
+
+    // Work on the value scaled to an integer so fractions stay exact
+    let scale = 10i128.checked_pow(fraction as u32)?;
+    let magnitude: i128 = unsigned.replace('.', "").parse().ok()?;
+    let value = if negative { -magnitude } else { magnitude };
+    let value = value.checked_add((delta as i128).checked_mul(scale)?)?;
+
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    let scale = scale as u128;
+    let integer = format!("{:0padded_width$}", magnitude / scale);
+    if fraction == 0 {
+        Some(format!("{sign}{integer}"))
+    } else {
+        Some(format!("{sign}{integer}.{:0fraction$}", magnitude % scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(line: &str, column: usize, delta: i64) -> String {
+        let cycles = vec!["true false".to_string(), "yes no".to_string()];
+        match increment_at(line, column, delta, &cycles) {
+            Some(edit) => {
+                let mut result = line.to_string();
+                result.replace_range(edit.range, &edit.text);
+                result
+            }
+            None => line.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(apply("x = 41;", 0, 1), "x = 42;");
+        assert_eq!(apply("x = 41;", 5, 1), "x = 42;");
+        assert_eq!(apply("x = 41;", 6, 1), "x = 41;");
+        assert_eq!(apply("a 1 b 2", 3, 5), "a 1 b 7");
+        assert_eq!(apply("x = 0;", 4, -1), "x = -1;");
+        assert_eq!(apply("x = -3;", 4, 5), "x = 2;");
+        assert_eq!(apply("a-1", 0, 1), "a-2");
+        assert_eq!(apply("007", 0, 1), "008");
+        assert_eq!(apply("099", 0, 1), "100");
+        assert_eq!(apply("utf8 and 9", 0, 1), "utf8 and 10");
+    }
+
+    #[test]
+    fn test_hex_and_decimals() {
+        assert_eq!(apply("0x0f", 0, 1), "0x10");
+        assert_eq!(apply("0xFF", 0, 1), "0x100");
+        assert_eq!(apply("0x00", 0, -1), "0xffffffffffffffff");
+        assert_eq!(apply("1.25", 0, 1), "2.25");
+        assert_eq!(apply("0.50", 0, -1), "-0.50");
+        assert_eq!(apply("v1.9.0", 0, 1), "v1.10.0");
+        assert_eq!(apply("1.9.0", 2, 1), "1.10.0");
+    }
+
+    #[test]
+    fn test_cycles() {
+        assert_eq!(apply("let on = true;", 10, 1), "let on = false;");
+        assert_eq!(apply("False", 0, 1), "True");
+        assert_eq!(apply("YES", 3, 1), "NO");
+        assert_eq!(apply("yes", 0, 3), "no");
+        assert_eq!(apply("truth 1", 0, 1), "truth 2");
+        assert_eq!(apply("nothing here", 0, 1), "nothing here");
+    }
+}
@@ -0,0 +1,252 @@
+//! Indentation detection and conversion.
+//!
+//! Detection looks at how indentation changes from one line to the next:
+//! lines indented with tabs are counted against lines indented with spaces,
+//! and the most common step between space-indented lines is the indent
+//! width. Steps are used rather than absolute indents so that deeply nested
+//! code doesn't look like wide indentation.
+
+/// Lines examined when detecting indentation
+const MAX_LINES: usize = 10_000;
+
+/// Widest indent step that counts as one level
+const MAX_INDENT_WIDTH: usize = 8;
+
+/// Indentation found in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedIndent {
+    /// Lines are indented with tabs
+    pub use_tabs: bool,
+    /// Spaces per indent level, when lines are indented with spaces and
+    /// their indentation changes by a consistent step
+    pub width: Option<usize>,
+}
+
+/// Detect the indentation of `text`, or `None` if no line is indented
+pub fn detect_indentation(text: &str) -> Option<DetectedIndent> {
+    let mut tab_lines = 0usize;
+    let mut space_lines = 0usize;
+    let mut steps = [0usize; MAX_INDENT_WIDTH + 1];
+    let mut previous_spaces = 0usize;
+
+    for line in text.lines().take(MAX_LINES) {
+        let rest = line.trim_start_matches([' ', '\t']);
+        if rest.is_empty() {
+            continue;
+        }
+        let leading = &line[..line.len() - rest.len()];
+        if leading.starts_with('\t') {
+            tab_lines += 1;
+            previous_spaces = 0;
+            continue;
+        }
+        let spaces = leading.len() - leading.trim_start_matches(' ').len();
+        // ` * text` continues a block comment rather than indenting
+        if spaces % 2 == 1 && rest.starts_with('*') {
+            continue;
+        }
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        let step = spaces.abs_diff(previous_spaces);
+        if (2..=MAX_INDENT_WIDTH).contains(&step) {
+            steps[step] += 1;
+        }
+        previous_spaces = spaces;
+    }
+
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+    if tab_lines > space_lines {
+        return Some(DetectedIndent {
+            use_tabs: true,
+            width: None,
+        });
+    }
+    // The most common step wins; on a tie the smaller one, since two levels
+    // of a narrow indent look like one level of a wide one
+    let width = (2..=MAX_INDENT_WIDTH)
+        .filter(|&step| steps[step] > 0)
+        .max_by_key(|&step| (steps[step], std::cmp::Reverse(step)));
+    Some(DetectedIndent {
+        use_tabs: false,
+        width,
+    })
+}
+
+/// Rewrite the leading whitespace of every line of `text` as tabs (with
+/// spaces for any part narrower than a tab) or as spaces only, keeping its
+/// width with tabs `tab_size` columns wide
+pub fn convert_indentation(text: &str, use_tabs: bool, tab_size: usize) -> String {
+    let mut converted = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let rest = line.trim_start_matches([' ', '\t']);
+        let leading = &line[..line.len() - rest.len()];
+        let width = indent_width(leading, tab_size);
+        converted.push_str(&render_indent(width, use_tabs, tab_size));
+        converted.push_str(rest);
+    }
+    converted
+}
+
+/// Shift the lines of a multi-line `block` being inserted after `prefix`
+/// (the text before the insert position on its line) so that they keep
+/// their indentation relative to each other under the line's indentation
+///
+/// The first line is taken to start where it was copied from: when it has
+/// no leading whitespace, only the other lines set the block's base
+/// indentation. It is left alone unless `prefix` is all whitespace. Blank
+/// lines are kept as pasted.
+pub fn reindent_block(block: &str, prefix: &str, use_tabs: bool, tab_size: usize) -> String {
+    let lines: Vec<&str> = block.split('\n').collect();
+    let leading = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let is_blank = |line: &str| line.trim_start_matches([' ', '\t']).is_empty();
+
+    let first = lines[0];
+    let first_indented = leading(first) > 0 && !is_blank(first);
+    let Some(base) = lines
+        .iter()
+        .enumerate()
+        .filter(|&(i, line)| (i > 0 || first_indented) && !is_blank(line))
+        .map(|(_, line)| indent_width(&line[..leading(line)], tab_size))
+        .min()
+    else {
+        return block.to_string();
+    };
+
+    let at_indent = is_blank(prefix);
+    let target = indent_width(&prefix[..leading(prefix)], tab_size);
+    let mut result = String::with_capacity(block.len());
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        let width = indent_width(&line[..leading(line)], tab_size);
+        let rest = &line[leading(line)..];
+        if i == 0 {
+            if at_indent && !is_blank(line) {
+                // The prefix already indents the first line to the target
+                let extra = width.saturating_sub(base);
+                result.push_str(&render_indent(extra, use_tabs, tab_size));
+                result.push_str(rest);
+            } else {
+                result.push_str(line);
+            }
+        } else if !is_blank(line) {
+            let width = target + width.saturating_sub(base);
+            result.push_str(&render_indent(width, use_tabs, tab_size));
+            result.push_str(rest);
+        } else {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
+/// Columns taken by `leading` whitespace, with tabs stopping at the next
+/// multiple of `tab_size`
+fn indent_width(leading: &str, tab_size: usize) -> usize {
+    let tab_size = tab_size.max(1);
+    leading.chars().fold(0, |column, c| match c {
+        '\t' => (column / tab_size + 1) * tab_size,
+        _ => column + 1,
+    })
+}
+
+/// Whitespace `width` columns wide
+fn render_indent(width: usize, use_tabs: bool, tab_size: usize) -> String {
+    let tab_size = tab_size.max(1);
+    if use_tabs {
+        "\t".repeat(width / tab_size) + &" ".repeat(width % tab_size)
+    } else {
+        " ".repeat(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_spaces_width() {
+        let two = "fn a() {\n  if x {\n    y();\n  }\n}\n";
+        assert_eq!(
+            detect_indentation(two),
+            Some(DetectedIndent {
+                use_tabs: false,
+                width: Some(2)
+            })
+        );
+
+        // Deep nesting and two-level dedents don't make it look wider
+        let four = "a:\n    b:\n        c:\n            d\ne\n    f\n";
+        assert_eq!(detect_indentation(four).unwrap().width, Some(4));
+    }
+
+    #[test]
+    fn test_detect_tabs_and_nothing() {
+        let tabs = "func a() {\n\tif x {\n\t\ty()\n\t}\n}\n";
+        assert_eq!(
+            detect_indentation(tabs),
+            Some(DetectedIndent {
+                use_tabs: true,
+                width: None
+            })
+        );
+        assert_eq!(detect_indentation("a\nb\n\n   \nc"), None);
+    }
+
+    #[test]
+    fn test_detect_ignores_block_comment_continuations() {
+        let text = "/**\n * Docs\n * more\n */\nfn a() {\n    b();\n}\n";
+        assert_eq!(detect_indentation(text).unwrap().width, Some(4));
+    }
+
+    #[test]
+    fn test_convert_indentation() {
+        let text = "a\n    b\n      c\r\n\t d\n";
+        assert_eq!(
+            convert_indentation(text, true, 4),
+            "a\n\tb\n\t  c\r\n\t d\n"
+        );
+        assert_eq!(
+            convert_indentation("\tx\n\t\ty  \n", false, 2),
+            "  x\n    y  \n"
+        );
+        // Tabs stop at the next multiple of the tab size
+        assert_eq!(convert_indentation("  \tx", false, 4), "    x");
+    }
+
+    #[test]
+    fn test_reindent_block() {
+        // Copied from the start of an indented line, pasted deeper
+        let block = "    if x {\n        y();\n    }";
+        assert_eq!(
+            reindent_block(block, "        ", false, 4),
+            "if x {\n            y();\n        }"
+        );
+        // Copied from mid-line: the other lines set the base
+        let block = "if x {\n        y();\n\n    }";
+        assert_eq!(
+            reindent_block(block, "  let a = ", false, 4),
+            "if x {\n      y();\n\n  }"
+        );
+        // Converted to the buffer's tabs
+        assert_eq!(reindent_block("a\n  b\n  c", "\t", true, 4), "a\n\tb\n\tc");
+        // Nothing indented to go by
+        assert_eq!(reindent_block("a\n\n", "    ", false, 4), "a\n\n");
+    }
+
+    #[test]
+    fn test_reindent_block_at_same_indent_is_unchanged() {
+        // Whitespace-only lines stay, as in Python bodies and YAML block scalars
+        let python = "    def run(self):\n        x = 1\n        \n        return x\n";
+        assert_eq!(
+            reindent_block(python, "    ", false, 4),
+            python.trim_start()
+        );
+        let yaml = "  script: |\n    make\n      \n    make test";
+        assert_eq!(reindent_block(yaml, "  ", false, 2), yaml.trim_start());
+    }
+}
@@ -0,0 +1,472 @@
+//! Tag matching for markup languages (HTML, XML and friends)
+//!
+//! Pairs opening and closing tags into elements, using the tree-sitter syntax
+//! tree when the language has a grammar and a small tag scanner otherwise.
+//! Also answers the questions asked while typing: which tag a `>` just
+//! finished, and which element a `</` should close.
+
+use fresh_languages::tree_sitter::{Node, Parser};
+use fresh_languages::Language;
+use std::ops::Range;
+
+/// Flavor of markup, deciding void elements and name case sensitivity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Html,
+    Xml,
+}
+
+impl Markup {
+    /// Markup flavor for a buffer's language id or TextMate syntax name
+    pub fn detect(language: &str, syntax_name: Option<&str>) -> Option<Self> {
+        match language {
+            "html" => return Some(Self::Html),
+            "xml" | "svg" | "vue" | "svelte" => return Some(Self::Xml),
+            _ => {}
+        }
+        match syntax_name?.to_ascii_lowercase().as_str() {
+            "html" | "html (erb)" | "php" => Some(Self::Html),
+            "xml" | "svg" | "xsl" | "vue" | "svelte" | "plist" => Some(Self::Xml),
+            _ => None,
+        }
+    }
+
+    /// Whether the element never has a closing tag (`<br>`, `<img>`, ...)
+    fn is_void(self, name: &str) -> bool {
+        self == Self::Html && VOID_ELEMENTS.contains(&name.to_ascii_lowercase().as_str())
+    }
+
+    fn same_name(self, a: &str, b: &str) -> bool {
+        match self {
+            Self::Html => a.eq_ignore_ascii_case(b),
+            Self::Xml => a == b,
+        }
+    }
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// An element: its opening tag and, unless it was never closed, its closing tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    /// Whole opening tag, `<` through `>`
+    pub open: Range<usize>,
+    /// Name inside the opening tag
+    pub open_name: Range<usize>,
+    /// Whole closing tag
+    pub close: Option<Range<usize>>,
+    /// Name inside the closing tag
+    pub close_name: Option<Range<usize>>,
+}
+
+impl Element {
+    /// Byte range from the start of the opening tag to the end of the element
+    pub fn range(&self) -> Range<usize> {
+        self.open.start..self.close.as_ref().map_or(self.open.end, |close| close.end)
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.')
+}
+
+/// Whether `name` is a valid tag name
+pub fn is_tag_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_name_start) && chars.all(is_name_char)
+}
+
+/// Length of the tag name starting at `text[start..]`
+fn name_len(text: &str, start: usize) -> usize {
+    let rest = &text[start..];
+    match rest.chars().next() {
+        Some(c) if is_name_start(c) => rest.find(|c| !is_name_char(c)).unwrap_or(rest.len()),
+        _ => 0,
+    }
+}
+
+/// A tag found by the scanner
+struct Tag {
+    range: Range<usize>,
+    name: Range<usize>,
+    closing: bool,
+    self_closing: bool,
+}
+
+/// End of the tag whose attributes start at `from`, skipping quoted values
+fn tag_end(text: &str, from: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate().skip(from) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return Some(i + 1),
+            None => {}
+        }
+    }
+    None
+}
+
+/// All tags in `text`, skipping comments, CDATA, declarations and the
+/// contents of raw text elements
+fn scan_tags(text: &str, markup: Markup) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut pos = 0;
+    while let Some(found) = text[pos..].find('<') {
+        let start = pos + found;
+        let rest = &text[start..];
+        let skip_to = |end: &str| rest.find(end).map_or(text.len(), |i| start + i + end.len());
+        if rest.starts_with("<!--") {
+            pos = skip_to("-->");
+            continue;
+        }
+        if rest.starts_with("<![CDATA[") {
+            pos = skip_to("]]>");
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            pos = skip_to(">");
+            continue;
+        }
+        let closing = rest.starts_with("</");
+        let name_start = start + if closing { 2 } else { 1 };
+        let len = name_len(text, name_start);
+        if len == 0 {
+            pos = start + 1;
+            continue;
+        }
+        let name = name_start..name_start + len;
+        let Some(end) = tag_end(text, name.end) else {
+            break;
+        };
+        let self_closing = !closing && text[..end - 1].ends_with('/');
+        tags.push(Tag {
+            range: start..end,
+            name: name.clone(),
+            closing,
+            self_closing,
+        });
+        pos = end;
+        // Script and style bodies are not markup
+        if markup == Markup::Html && !closing && !self_closing {
+            let tag_name = text[name].to_ascii_lowercase();
+            if tag_name == "script" || tag_name == "style" {
+                let close = format!("</{}", tag_name);
+                pos = text[end..]
+                    .to_ascii_lowercase()
+                    .find(&close)
+                    .map_or(text.len(), |i| end + i);
+            }
+        }
+    }
+    tags
+}
+
+/// Pair scanned tags into elements. An unmatched closing tag is ignored, and
+/// elements it skips over stay unclosed.
+fn pair_tags(text: &str, tags: &[Tag], markup: Markup) -> (Vec<Element>, Vec<Element>) {
+    let mut elements = Vec::new();
+    let mut open: Vec<Element> = Vec::new();
+    for tag in tags {
+        let name = &text[tag.name.clone()];
+        if tag.closing {
+            let Some(index) = open
+                .iter()
+                .rposition(|e| markup.same_name(&text[e.open_name.clone()], name))
+            else {
+                continue;
+            };
+            let mut element = open.remove(index);
+            elements.extend(open.drain(index..));
+            element.close = Some(tag.range.clone());
+            element.close_name = Some(tag.name.clone());
+            elements.push(element);
+        } else {
+            let element = Element {
+                open: tag.range.clone(),
+                open_name: tag.name.clone(),
+                close: None,
+                close_name: None,
+            };
+            if tag.self_closing || markup.is_void(name) {
+                elements.push(element);
+            } else {
+                open.push(element);
+            }
+        }
+    }
+    (elements, open)
+}
+
+/// Elements from the tree-sitter syntax tree
+fn tree_elements(text: &str, language: &Language) -> Option<Vec<Element>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()?).ok()?;
+    let tree = parser.parse(text, None)?;
+    let mut elements = Vec::new();
+    collect_tree_elements(tree.root_node(), &mut elements);
+    Some(elements)
+}
+
+fn collect_tree_elements(node: Node, elements: &mut Vec<Element>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if let Some(element) = tree_element(child) {
+            elements.push(element);
+        }
+        collect_tree_elements(child, elements);
+    }
+}
+
+fn tree_element(node: Node) -> Option<Element> {
+    let tag_name = |tag: Node| {
+        let mut cursor = tag.walk();
+        let name = tag
+            .children(&mut cursor)
+            .find(|c| c.kind() == "tag_name")
+            .map(|c| c.byte_range());
+        name
+    };
+    if node.kind() == "self_closing_tag" {
+        return Some(Element {
+            open: node.byte_range(),
+            open_name: tag_name(node)?,
+            close: None,
+            close_name: None,
+        });
+    }
+    if !node.kind().ends_with("element") {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    let start = children.iter().find(|c| c.kind() == "start_tag")?;
+    let end = children
+        .iter()
+        .find(|c| c.kind() == "end_tag" && !c.is_missing() && !c.byte_range().is_empty());
+    Some(Element {
+        open: start.byte_range(),
+        open_name: tag_name(*start)?,
+        close: end.map(|e| e.byte_range()),
+        close_name: end.and_then(|e| tag_name(*e)),
+    })
+}
+
+/// All elements in `text`, ordered by where they start
+pub fn elements(text: &str, markup: Markup, language: Option<&Language>) -> Vec<Element> {
+    let mut elements = language
+        .and_then(|language| tree_elements(text, language))
+        .unwrap_or_else(|| {
+            let tags = scan_tags(text, markup);
+            let (mut closed, unclosed) = pair_tags(text, &tags, markup);
+            closed.extend(unclosed);
+            closed
+        });
+    elements.sort_by_key(|e| e.open.start);
+    elements
+}
+
+/// The innermost element whose tags or content contain `offset`
+pub fn element_at(
+    text: &str,
+    offset: usize,
+    markup: Markup,
+    language: Option<&Language>,
+) -> Option<Element> {
+    elements(text, markup, language)
+        .into_iter()
+        .filter(|e| {
+            let range = e.range();
+            range.start <= offset && offset < range.end
+        })
+        .min_by_key(|e| e.range().len())
+}
+
+/// Name of the tag that typing `>` at the end of `before` would finish, when
+/// it should get a closing tag: an opening, non-void, non-self-closing tag
+pub fn tag_finished_by_gt(before: &str, markup: Markup) -> Option<String> {
+    let start = before.rfind('<')?;
+    let tag = &before[start + 1..];
+    let len = name_len(before, start + 1);
+    if len == 0 || tag.ends_with('/') {
+        return None;
+    }
+    // Inside a quoted attribute value the `>` doesn't end the tag, and an
+    // unquoted `>` means the tag already ended
+    let mut quote = None;
+    for c in tag.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return None,
+            None => {}
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    let name = &tag[..len];
+    (!markup.is_void(name)).then(|| name.to_string())
+}
+
+/// Name of the innermost element still open at the end of `before`, which
+/// typing `</` there would close
+pub fn unclosed_tag(before: &str, markup: Markup) -> Option<String> {
+    let tags = scan_tags(before, markup);
+    let (_, open) = pair_tags(before, &tags, markup);
+    open.last().map(|e| before[e.open_name.clone()].to_string())
+}
+
+/// The closing tag edit that keeps an element's tags in sync after an edit
+/// of its opening tag name.
+///
+/// `edited` is the byte range of `text` being replaced by `replacement`.
+/// Returns the closing tag name range in `text` and the new name.
+pub fn closing_tag_rename(
+    text: &str,
+    edited: Range<usize>,
+    replacement: &str,
+    markup: Markup,
+    language: Option<&Language>,
+) -> Option<(Range<usize>, String)> {
+    // Cheap check before parsing: the edit must touch a name right after `<`
+    let line_start = text[..edited.start].rfind('\n').map_or(0, |i| i + 1);
+    let name_start = text[line_start..edited.start]
+        .rfind(|c: char| !is_name_char(c))
+        .map(|i| line_start + i)?;
+    if !text[name_start..].starts_with('<') {
+        return None;
+    }
+
+    let element = elements(text, markup, language)
+        .into_iter()
+        .find(|e| e.open_name.start == name_start + 1)?;
+    let name = element.open_name.clone();
+    let close_name = element.close_name?;
+    if edited.start < name.start || edited.end > name.end {
+        return None;
+    }
+    if text[close_name.clone()] != text[name.clone()] {
+        return None;
+    }
+    let new_name = format!(
+        "{}{}{}",
+        &text[name.start..edited.start],
+        replacement,
+        &text[edited.end..name.end]
+    );
+    is_tag_name(&new_name).then_some((close_name, new_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str, markup: Markup, language: Option<Language>) -> Vec<(String, bool)> {
+        elements(text, markup, language.as_ref())
+            .iter()
+            .map(|e| (text[e.open_name.clone()].to_string(), e.close.is_some()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scanner_pairs_tags() {
+        let text = "<?xml version=\"1.0\"?>\n<a x=\"1>2\"><!-- <b> --><b/><c>t</c><d></a>";
+        assert_eq!(
+            names(text, Markup::Xml, None),
+            vec![
+                ("a".to_string(), true),
+                ("b".to_string(), false),
+                ("c".to_string(), true),
+                ("d".to_string(), false),
+            ]
+        );
+        let a = element_at(text, text.find("x=").unwrap(), Markup::Xml, None).unwrap();
+        assert_eq!(&text[a.close.unwrap()], "</a>");
+    }
+
+    #[test]
+    fn test_tree_sitter_elements() {
+        let text = "<div><p>one<br>two</p><img src=x></div>";
+        let p = element_at(
+            text,
+            text.find("one").unwrap(),
+            Markup::Html,
+            Some(&Language::HTML),
+        )
+        .unwrap();
+        assert_eq!(&text[p.open.clone()], "<p>");
+        assert_eq!(&text[p.close.unwrap()], "</p>");
+        let div = element_at(
+            text,
+            text.find("</div>").unwrap(),
+            Markup::Html,
+            Some(&Language::HTML),
+        )
+        .unwrap();
+        assert_eq!(&text[div.open_name], "div");
+    }
+
+    #[test]
+    fn test_tag_finished_by_gt() {
+        assert_eq!(
+            tag_finished_by_gt("<ul>\n  <li class=\"a\"", Markup::Html),
+            Some("li".to_string())
+        );
+        assert_eq!(tag_finished_by_gt("<br", Markup::Html), None);
+        assert_eq!(
+            tag_finished_by_gt("<br", Markup::Xml),
+            Some("br".to_string())
+        );
+        assert_eq!(tag_finished_by_gt("<a/", Markup::Xml), None);
+        assert_eq!(tag_finished_by_gt("</a", Markup::Xml), None);
+        assert_eq!(tag_finished_by_gt("<a title=\"x", Markup::Xml), None);
+        assert_eq!(tag_finished_by_gt("a < b", Markup::Xml), None);
+        assert_eq!(
+            tag_finished_by_gt("<a title=\"x>y\"", Markup::Xml),
+            Some("a".to_string())
+        );
+        assert_eq!(tag_finished_by_gt("<a>b", Markup::Xml), None);
+    }
+
+    #[test]
+    fn test_unclosed_tag() {
+        assert_eq!(
+            unclosed_tag("<a><b><c/></b><d>text", Markup::Xml),
+            Some("d".to_string())
+        );
+        assert_eq!(unclosed_tag("<p><br>", Markup::Html), Some("p".to_string()));
+        assert_eq!(unclosed_tag("<a></a>", Markup::Xml), None);
+    }
+
+    #[test]
+    fn test_closing_tag_rename() {
+        let text = "<div class=\"x\">\n  <span>hi</span>\n</div>\n";
+        // Typing a character at the end of the opening name
+        let (range, name) = closing_tag_rename(text, 4..4, "x", Markup::Xml, None).unwrap();
+        assert_eq!(&text[range], "div");
+        assert_eq!(name, "divx");
+        // Deleting a character with the tree-sitter grammar
+        let (range, name) =
+            closing_tag_rename(text, 20..21, "", Markup::Html, Some(&Language::HTML)).unwrap();
+        assert_eq!(&text[range], "span");
+        assert_eq!(name, "san");
+        // Typing a space starts the attributes, so the names stay as they are
+        assert_eq!(closing_tag_rename(text, 4..4, " ", Markup::Xml, None), None);
+        // Edits outside a tag name are left alone
+        assert_eq!(
+            closing_tag_rename(text, 24..24, "x", Markup::Xml, None),
+            None
+        );
+    }
+}
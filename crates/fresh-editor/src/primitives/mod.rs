@@ -16,9 +16,16 @@
 
 // Pure modules - available for both runtime and WASM
 pub mod display_width;
+pub mod editorconfig;
+pub mod file_reference;
+pub mod file_template;
+pub mod global_command;
 pub mod grapheme;
+pub mod increment;
+pub mod indent_detection;
 pub mod line_wrapping;
 pub mod path_utils;
+pub mod selection_stats;
 pub mod snippet;
 pub mod text_property;
 
@@ -57,10 +64,18 @@ pub mod textmate_engine;
 // Runtime-only modules (depend on tree-sitter)
 // These provide enhanced features using AST analysis
 #[cfg(feature = "runtime")]
+pub mod code_block;
+#[cfg(feature = "runtime")]
 pub mod highlight_engine;
 #[cfg(feature = "runtime")]
 pub mod highlighter;
 #[cfg(feature = "runtime")]
 pub mod indent;
 #[cfg(feature = "runtime")]
+pub mod markup_tags;
+#[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod text_objects;
+#[cfg(feature = "runtime")]
+pub mod unicode_chars;
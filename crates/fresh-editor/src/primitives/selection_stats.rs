@@ -0,0 +1,175 @@
+//! Character, word and line counts of selected text.
+//!
+//! Text is added a piece at a time, so that counting a large selection can
+//! be spread over several frames. Pieces must start and end at word
+//! boundaries, such as whole lines.
+
+/// Counts over the text added so far
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectionStats {
+    pub chars: usize,
+    pub words: usize,
+    /// Line breaks seen; see [`SelectionStats::lines`]
+    newlines: usize,
+    /// Pieces of text added, each counted as at least one line
+    pieces: usize,
+    ends_with_newline: bool,
+    /// Sum of the numbers, as an integer scaled by `10^sum_scale`
+    sum: i128,
+    sum_scale: u32,
+    has_numbers: bool,
+}
+
+impl SelectionStats {
+    /// Count `text`; `with_sum` also adds up the numbers in it
+    pub fn add(&mut self, text: &str, with_sum: bool) {
+        self.chars += text.chars().count();
+        self.words += text.split_whitespace().count();
+        self.newlines += text.matches('\n').count();
+        if !text.is_empty() {
+            self.ends_with_newline = text.ends_with('\n');
+        }
+        if with_sum {
+            for number in numbers(text) {
+                self.add_number(number);
+            }
+        }
+    }
+
+    /// Start a new selection; lines are counted per selection
+    pub fn start_piece(&mut self) {
+        if self.pieces > 0 && !self.ends_with_newline {
+            self.newlines += 1;
+        }
+        self.pieces += 1;
+        self.ends_with_newline = false;
+    }
+
+    /// Lines touched by the selections; a line break at the end of a
+    /// selection doesn't start another line
+    pub fn lines(&self) -> usize {
+        if self.pieces == 0 || self.chars == 0 {
+            return 0;
+        }
+        self.newlines + usize::from(!self.ends_with_newline)
+    }
+
+    /// Sum of the numbers counted, if there were any
+    pub fn sum(&self) -> Option<String> {
+        if !self.has_numbers {
+            return None;
+        }
+        let scale = 10i128.pow(self.sum_scale);
+        let sign = if self.sum < 0 { "-" } else { "" };
+        let magnitude = self.sum.unsigned_abs();
+        let scale = scale as u128;
+        let digits = self.sum_scale as usize;
+        if digits == 0 {
+            Some(format!("{sign}{magnitude}"))
+        } else {
+            Some(format!(
+                "{sign}{}.{:0digits$}",
+                magnitude / scale,
+                magnitude % scale
+            ))
+        }
+    }
+
+    fn add_number(&mut self, number: &str) {
+        let fraction = number.split_once('.').map_or(0, |(_, f)| f.len() as u32);
+        let Ok(value) = number.replace('.', "").parse::<i128>() else {
+            return;
+        };
+        // Bring the sum and the number to the same number of decimals
+        if fraction > self.sum_scale {
+            let Some(sum) = 10i128
+                .checked_pow(fraction - self.sum_scale)
+                .and_then(|factor| self.sum.checked_mul(factor))
+            else {
+                return;
+            };
+            self.sum = sum;
+            self.sum_scale = fraction;
+        }
+        let value = 10i128
+            .checked_pow(self.sum_scale - fraction)
+            .and_then(|factor| value.checked_mul(factor));
+        if let Some(sum) = value.and_then(|value| self.sum.checked_add(value)) {
+            self.sum = sum;
+            self.has_numbers = true;
+        }
+    }
+}
+
+/// Decimal numbers in `text`, with their sign, skipping digits that are
+/// part of words such as `utf8`
+fn numbers(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let in_word = i > 0 && (bytes[i - 1].is_ascii_alphabetic() || bytes[i - 1] == b'_');
+        let mut end = i;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+        }
+        let followed_by_word = bytes
+            .get(end)
+            .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_');
+        if !in_word && !followed_by_word {
+            let negative = i > 0 && bytes[i - 1] == b'-';
+            numbers.push(&text[if negative { i - 1 } else { i }..end]);
+        }
+        i = end;
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(pieces: &[&str], with_sum: bool) -> SelectionStats {
+        let mut stats = SelectionStats::default();
+        for piece in pieces {
+            stats.start_piece();
+            stats.add(piece, with_sum);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_counts() {
+        let s = stats(&["hello wörld\nfoo"], false);
+        assert_eq!((s.chars, s.words, s.lines()), (15, 3, 2));
+
+        let s = stats(&["one line\n"], false);
+        assert_eq!(s.lines(), 1);
+
+        let s = stats(&["a", "b c"], false);
+        assert_eq!((s.chars, s.words, s.lines()), (4, 3, 2));
+
+        assert_eq!(stats(&[""], false).lines(), 0);
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(stats(&["1 2\n3"], true).sum().as_deref(), Some("6"));
+        assert_eq!(
+            stats(&["price 1.5, tax 0.25, -1"], true).sum().as_deref(),
+            Some("0.75")
+        );
+        assert_eq!(stats(&["utf8 x86_64 3d"], true).sum(), None);
+        assert_eq!(stats(&["1 2"], false).sum(), None);
+    }
+}
@@ -0,0 +1,466 @@
+//! Text objects: the ranges that select-inside/around act on.
+//!
+//! Each object has an "inside" range and an "around" range that also takes
+//! in its delimiters or surrounding whitespace. Words, quotes, brackets and
+//! paragraphs are found in the text itself; functions and arguments come from
+//! the tree-sitter syntax tree, so they need a language with a grammar.
+
+use fresh_languages::tree_sitter::{Node, Parser};
+use fresh_languages::Language;
+use std::ops::Range;
+
+/// A kind of text object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextObject {
+    /// A run of word characters; around adds the whitespace after it
+    Word,
+    /// A quoted string on the current line (`"`, `'` or `` ` ``)
+    Quotes,
+    /// The innermost `()`, `[]` or `{}` pair around the cursor
+    Brackets,
+    /// A run of non-blank lines; around adds the blank lines after it
+    Paragraph,
+    /// A function, method or closure; inside is its body
+    Function,
+    /// An item of an argument or parameter list; around adds its separator
+    Argument,
+}
+
+/// Range of `object` at byte `offset` of `text`, or `None` if there is none
+/// there. Functions and arguments need `language` to have a grammar.
+pub fn text_object_range(
+    text: &str,
+    offset: usize,
+    object: TextObject,
+    around: bool,
+    language: Option<&Language>,
+) -> Option<Range<usize>> {
+    let offset = offset.min(text.len());
+    let range = match object {
+        TextObject::Word => word_range(text, offset, around),
+        TextObject::Quotes => quotes_range(text, offset, around),
+        TextObject::Brackets => brackets_range(text, offset, around),
+        TextObject::Paragraph => paragraph_range(text, offset, around),
+        TextObject::Function => syntax_range(text, offset, around, language?, function_range),
+        TextObject::Argument => syntax_range(text, offset, around, language?, argument_range),
+    }?;
+    (!range.is_empty()).then_some(range)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// End of the run of characters matching `pred` starting at `from`
+fn run_end(text: &str, from: usize, pred: impl Fn(char) -> bool) -> usize {
+    text[from..]
+        .char_indices()
+        .find(|&(_, c)| !pred(c))
+        .map_or(text.len(), |(i, _)| from + i)
+}
+
+/// Start of the run of characters matching `pred` ending at `to`
+fn run_start(text: &str, to: usize, pred: impl Fn(char) -> bool) -> usize {
+    text[..to]
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| !pred(c))
+        .map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+/// Byte range of the line containing `offset`, without its line terminator
+fn line_bounds(text: &str, offset: usize) -> Range<usize> {
+    let start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+    let end = if text[start..end].ends_with('\r') {
+        end - 1
+    } else {
+        end
+    };
+    start..end
+}
+
+fn word_range(text: &str, offset: usize, around: bool) -> Option<Range<usize>> {
+    let at = text[offset..].chars().next();
+    let before = text[..offset].chars().next_back();
+    let (start, end) = if at.is_some_and(is_word_char) || before.is_some_and(is_word_char) {
+        // Right after a word counts as on it
+        let anchor = if at.is_some_and(is_word_char) {
+            offset
+        } else {
+            offset - before.map_or(0, char::len_utf8)
+        };
+        (
+            run_start(text, anchor, is_word_char),
+            run_end(text, anchor, is_word_char),
+        )
+    } else if at.is_some_and(is_blank) {
+        // Inside whitespace selects the whitespace
+        return Some(run_start(text, offset, is_blank)..run_end(text, offset, is_blank));
+    } else {
+        return None;
+    };
+
+    if !around {
+        return Some(start..end);
+    }
+    let trailing = run_end(text, end, is_blank);
+    if trailing > end {
+        return Some(start..trailing);
+    }
+    // At the end of a line, take the whitespace before the word instead
+    Some(run_start(text, start, is_blank)..end)
+}
+
+fn quotes_range(text: &str, offset: usize, around: bool) -> Option<Range<usize>> {
+    let line = line_bounds(text, offset);
+    let mut pairs = Vec::new();
+    let mut open: Option<(usize, char)> = None;
+    let mut escaped = false;
+    for (i, c) in text[line.clone()].char_indices() {
+        let pos = line.start + i;
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match open {
+            _ if c == '\\' => escaped = true,
+            Some((start, quote)) if c == quote => {
+                pairs.push(start..pos + 1);
+                open = None;
+            }
+            None if matches!(c, '"' | '\'' | '`') => open = Some((pos, c)),
+            _ => {}
+        }
+    }
+
+    // The pair around the cursor, or else the next one on the line
+    let pair = pairs
+        .iter()
+        .find(|pair| pair.start <= offset && offset < pair.end)
+        .or_else(|| pairs.iter().find(|pair| pair.start > offset))?;
+    if around {
+        Some(pair.clone())
+    } else {
+        Some(pair.start + 1..pair.end - 1)
+    }
+}
+
+fn closing_bracket(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn brackets_range(text: &str, offset: usize, around: bool) -> Option<Range<usize>> {
+    // A cursor on an opening bracket selects that pair; otherwise the first
+    // unmatched opening bracket before it, which also pairs with a closing
+    // bracket under the cursor
+    let mut open = None;
+    if text[offset..]
+        .chars()
+        .next()
+        .and_then(closing_bracket)
+        .is_some()
+    {
+        open = Some(offset);
+    } else {
+        let mut pending = 0usize;
+        for (i, c) in text[..offset].char_indices().rev() {
+            match c {
+                ')' | ']' | '}' => pending += 1,
+                '(' | '[' | '{' if pending == 0 => {
+                    open = Some(i);
+                    break;
+                }
+                '(' | '[' | '{' => pending -= 1,
+                _ => {}
+            }
+        }
+    }
+    let open = open?;
+
+    let mut depth = Vec::new();
+    let mut close = None;
+    for (i, c) in text[open..].char_indices() {
+        if let Some(closing) = closing_bracket(c) {
+            depth.push(closing);
+        } else if matches!(c, ')' | ']' | '}') {
+            if depth.pop() != Some(c) {
+                return None;
+            }
+            if depth.is_empty() {
+                close = Some(open + i);
+                break;
+            }
+        }
+    }
+    let close = close?;
+    if around {
+        Some(open..close + 1)
+    } else {
+        Some(open + 1..close)
+    }
+}
+
+/// Lines of `text` with their byte ranges, including line terminators
+fn lines_with_endings(text: &str) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push(start..start + line.len());
+        start += line.len();
+    }
+    lines
+}
+
+fn paragraph_range(text: &str, offset: usize, around: bool) -> Option<Range<usize>> {
+    let lines = lines_with_endings(text);
+    let row = lines
+        .iter()
+        .position(|line| offset < line.end)
+        .or_else(|| lines.len().checked_sub(1))?;
+    let blank = |i: usize| text[lines[i].clone()].trim().is_empty();
+
+    // On a blank line the paragraph is the run of blank lines
+    let kind = blank(row);
+    let mut first = row;
+    while first > 0 && blank(first - 1) == kind {
+        first -= 1;
+    }
+    let mut last = row;
+    while last + 1 < lines.len() && blank(last + 1) == kind {
+        last += 1;
+    }
+
+    if around {
+        if last + 1 < lines.len() {
+            // Take in the following run as well
+            last += 1;
+            while last + 1 < lines.len() && blank(last + 1) != kind {
+                last += 1;
+            }
+        } else {
+            // The last paragraph takes the run before it instead
+            while first > 0 && blank(first - 1) != kind {
+                first -= 1;
+            }
+        }
+    }
+    Some(lines[first].start..lines[last].end)
+}
+
+/// Parse `text` and find a range with `find` in the deepest node at `offset`
+fn syntax_range(
+    text: &str,
+    offset: usize,
+    around: bool,
+    language: &Language,
+    find: fn(&str, Node, usize, bool) -> Option<Range<usize>>,
+) -> Option<Range<usize>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()?).ok()?;
+    let tree = parser.parse(text, None)?;
+    let node = tree.root_node().descendant_for_byte_range(offset, offset)?;
+    find(text, node, offset, around)
+}
+
+fn is_function_kind(kind: &str) -> bool {
+    let function_like = ["function", "method", "lambda", "closure", "func_literal"]
+        .iter()
+        .any(|word| kind.contains(word));
+    let part_of_one = ["call", "type", "modifier", "parameter", "argument", "name"]
+        .iter()
+        .any(|word| kind.contains(word));
+    function_like && !part_of_one
+}
+
+fn function_range(text: &str, node: Node, _offset: usize, around: bool) -> Option<Range<usize>> {
+    let mut current = Some(node);
+    while let Some(node) = current {
+        if node.is_named() && is_function_kind(node.kind()) {
+            if around {
+                return Some(whole_lines(text, node.byte_range()));
+            }
+            let body = node.child_by_field_name("body")?;
+            return Some(block_contents(text, body));
+        }
+        current = node.parent();
+    }
+    None
+}
+
+/// Inside of a body: between its braces when it has them, and without the
+/// line breaks next to them so whole lines are selected
+fn block_contents(text: &str, body: Node) -> Range<usize> {
+    let count = body.child_count();
+    let first = body.child(0);
+    let last = count.checked_sub(1).and_then(|i| body.child(i as u32));
+    let mut range = match (first, last) {
+        (Some(first), Some(last))
+            if count >= 2 && matches!(first.kind(), "{" | "(" | "[" | "do") =>
+        {
+            first.end_byte()..last.start_byte()
+        }
+        _ => return body.byte_range(),
+    };
+    let contents = &text[range.clone()];
+    if let Some(newline) = contents.find('\n') {
+        if contents[..newline].trim().is_empty() {
+            range.start += newline + 1;
+        }
+    }
+    let contents = &text[range.clone()];
+    if let Some(newline) = contents.rfind('\n') {
+        if contents[newline + 1..].trim().is_empty() {
+            range.end = range.start + newline + 1;
+        }
+    }
+    range
+}
+
+/// `range` widened to whole lines, with the line break, when only
+/// whitespace shares its first and last lines
+fn whole_lines(text: &str, range: Range<usize>) -> Range<usize> {
+    let start = run_start(text, range.start, is_blank);
+    let end = run_end(text, range.end, |c| is_blank(c) || c == '\r');
+    let starts_line = start == 0 || text[..start].ends_with('\n');
+    let ends_line = end == text.len() || text[end..].starts_with('\n');
+    if starts_line && ends_line {
+        start..(end + 1).min(text.len())
+    } else {
+        range
+    }
+}
+
+fn is_list_kind(kind: &str) -> bool {
+    ["arguments", "argument_list", "parameters", "parameter_list"]
+        .iter()
+        .any(|suffix| kind.ends_with(suffix))
+}
+
+fn argument_range(text: &str, node: Node, offset: usize, around: bool) -> Option<Range<usize>> {
+    // Climb to the item whose parent is an argument or parameter list
+    let mut current = node;
+    let item = loop {
+        if is_list_kind(current.kind()) && current.named_child_count() > 0 {
+            // On a separator or bracket of the list itself: the nearest item
+            let mut cursor = current.walk();
+            let items: Vec<Node> = current.named_children(&mut cursor).collect();
+            break *items
+                .iter()
+                .find(|item| offset <= item.end_byte())
+                .unwrap_or(items.last()?);
+        }
+        let parent = current.parent()?;
+        if is_list_kind(parent.kind()) && current.is_named() {
+            break current;
+        }
+        current = parent;
+    };
+
+    let range = item.byte_range();
+    if !around {
+        return Some(range);
+    }
+    // The separator after the item and the space up to the next one, or for
+    // the last item the separator before it
+    if let Some(next) = item.next_named_sibling() {
+        return Some(range.start..next.start_byte());
+    }
+    match item.prev_named_sibling() {
+        Some(prev) => Some(prev.end_byte()..range.end),
+        None => Some(whole_lines(text, range)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select<'a>(text: &'a str, cursor: &str, object: TextObject, around: bool) -> &'a str {
+        let offset = text.find(cursor).unwrap();
+        let range = text_object_range(text, offset, object, around, Some(&Language::Rust))
+            .expect("no text object");
+        &text[range]
+    }
+
+    #[test]
+    fn test_word() {
+        let text = "let fooBar = baz;";
+        assert_eq!(select(text, "Bar", TextObject::Word, false), "fooBar");
+        assert_eq!(select(text, "Bar", TextObject::Word, true), "fooBar ");
+        // Right after a word, and at the end of a line
+        assert_eq!(select(text, " =", TextObject::Word, false), "fooBar");
+        assert_eq!(select("a b", "b", TextObject::Word, true), " b");
+        assert_eq!(
+            text_object_range(text, 11, TextObject::Word, false, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_quotes() {
+        let text = r#"call("a \"b\"", 'c')"#;
+        assert_eq!(select(text, "a ", TextObject::Quotes, false), r#"a \"b\""#);
+        assert_eq!(select(text, "c'", TextObject::Quotes, true), "'c'");
+        // Before any quote, the next pair on the line
+        assert_eq!(
+            select(text, "call", TextObject::Quotes, false),
+            r#"a \"b\""#
+        );
+    }
+
+    #[test]
+    fn test_brackets() {
+        let text = "f(a, [b, c], {d})";
+        assert_eq!(select(text, "b,", TextObject::Brackets, false), "b, c");
+        assert_eq!(select(text, "a,", TextObject::Brackets, true), &text[1..]);
+        assert_eq!(select(text, "{d", TextObject::Brackets, true), "{d}");
+        assert_eq!(select(text, "})", TextObject::Brackets, false), "d");
+    }
+
+    #[test]
+    fn test_paragraph() {
+        let text = "a\nb\n\n\nc\nd\n";
+        assert_eq!(select(text, "b", TextObject::Paragraph, false), "a\nb\n");
+        assert_eq!(select(text, "b", TextObject::Paragraph, true), "a\nb\n\n\n");
+        assert_eq!(select(text, "d", TextObject::Paragraph, true), "\n\nc\nd\n");
+    }
+
+    #[test]
+    fn test_function_and_argument() {
+        let text = "fn first() {}\n\nfn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        assert_eq!(
+            select(text, "a + b", TextObject::Function, false),
+            "    a + b\n"
+        );
+        assert_eq!(
+            select(text, "a + b", TextObject::Function, true),
+            "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n"
+        );
+        assert_eq!(
+            select(text, "a: i32", TextObject::Argument, false),
+            "a: i32"
+        );
+        assert_eq!(
+            select(text, "a: i32", TextObject::Argument, true),
+            "a: i32, "
+        );
+        assert_eq!(
+            select(text, "b: i32", TextObject::Argument, true),
+            ", b: i32"
+        );
+        assert_eq!(
+            text_object_range(text, 0, TextObject::Function, false, None),
+            None
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Unicode character names, for the character picker and inspector.
+//!
+//! The catalog holds every emoji (with its CLDR name, group and GitHub
+//! shortcodes) followed by every named character with its Unicode block.
+//! Ideographs whose names are just their code point (`CJK UNIFIED
+//! IDEOGRAPH-4E00`) are left out; they can still be found as `U+4E00`.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Name prefixes of characters named after their code point
+const NUMBERED_NAME_PREFIXES: &[&str] = &[
+    "CJK UNIFIED IDEOGRAPH-",
+    "CJK COMPATIBILITY IDEOGRAPH-",
+    "TANGUT IDEOGRAPH-",
+    "TANGUT COMPONENT-",
+    "KHITAN SMALL SCRIPT CHARACTER-",
+    "NUSHU CHARACTER-",
+];
+
+/// A character, or an emoji sequence, that can be inserted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharEntry {
+    /// The text inserted
+    pub text: String,
+    /// Unicode or CLDR name
+    pub name: String,
+    /// Unicode block, or emoji group
+    pub category: &'static str,
+    /// GitHub shortcodes, for emoji
+    pub shortcodes: Vec<&'static str>,
+    /// Upper-cased name, category and shortcodes, for searching
+    key: String,
+}
+
+impl CharEntry {
+    fn new(
+        text: String,
+        name: String,
+        category: &'static str,
+        shortcodes: Vec<&'static str>,
+    ) -> Self {
+        let key = format!("{} {} {}", name, shortcodes.join(" "), category).to_uppercase();
+        Self {
+            text,
+            name,
+            category,
+            shortcodes,
+            key,
+        }
+    }
+
+    /// The text as shown in a list, see [`display_text`]
+    pub fn display(&self) -> String {
+        display_text(&self.text)
+    }
+
+    /// Code points of the text, as `U+1F600 U+FE0F`
+    pub fn codepoints(&self) -> String {
+        self.text
+            .chars()
+            .map(codepoint)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// One code point of a character, as shown by the inspector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodepointInfo {
+    pub ch: char,
+    /// Unicode name, if the code point has one
+    pub name: Option<String>,
+    /// Unicode block, if the code point is in one
+    pub block: Option<&'static str>,
+    /// UTF-8 encoding
+    pub utf8: Vec<u8>,
+}
+
+/// A character as shown on its own: combining marks get a dotted circle to
+/// sit on, and invisible characters are replaced by their code point
+pub fn display_text(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if is_combining(c) => format!("\u{25CC}{}", c),
+        (Some(c), None) if c.is_whitespace() || c.is_control() || is_format(c) => codepoint(c),
+        _ => text.to_string(),
+    }
+}
+
+/// `U+XXXX` for a code point
+pub fn codepoint(c: char) -> String {
+    format!("U+{:04X}", c as u32)
+}
+
+/// Upper-case hex bytes separated by spaces
+pub fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Unicode name of a code point (controls and unassigned code points have none)
+pub fn char_name(c: char) -> Option<String> {
+    unicode_names2::name(c).map(|name| name.to_string())
+}
+
+/// Unicode block containing a code point
+pub fn block_name(c: char) -> Option<&'static str> {
+    unicode_blocks::find_unicode_block(c).map(|block| block.name())
+}
+
+/// Each code point of `text` with its name, block and UTF-8 bytes
+pub fn inspect(text: &str) -> Vec<CodepointInfo> {
+    text.chars()
+        .map(|ch| {
+            let mut buf = [0; 4];
+            CodepointInfo {
+                ch,
+                name: char_name(ch),
+                block: block_name(ch),
+                utf8: ch.encode_utf8(&mut buf).as_bytes().to_vec(),
+            }
+        })
+        .collect()
+}
+
+/// The emoji `text` is, if any
+pub fn emoji_name(text: &str) -> Option<&'static str> {
+    emojis::get(text).map(|emoji| emoji.name())
+}
+
+fn is_combining(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+fn is_format(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00AD | 0x200B..=0x200F | 0x2028..=0x202E | 0x2060..=0x206F | 0xFE00..=0xFE0F | 0xFEFF
+    )
+}
+
+fn group_name(group: emojis::Group) -> &'static str {
+    use emojis::Group;
+    match group {
+        Group::SmileysAndEmotion => "Smileys & Emotion",
+        Group::PeopleAndBody => "People & Body",
+        Group::AnimalsAndNature => "Animals & Nature",
+        Group::FoodAndDrink => "Food & Drink",
+        Group::TravelAndPlaces => "Travel & Places",
+        Group::Activities => "Activities",
+        Group::Objects => "Objects",
+        Group::Symbols => "Symbols",
+        Group::Flags => "Flags",
+    }
+}
+
+/// Every emoji, then every named character, built on first use
+pub fn catalog() -> &'static [CharEntry] {
+    static CATALOG: OnceLock<Vec<CharEntry>> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let mut entries: Vec<CharEntry> = emojis::iter()
+            .map(|emoji| {
+                CharEntry::new(
+                    emoji.as_str().to_string(),
+                    emoji.name().to_string(),
+                    group_name(emoji.group()),
+                    emoji.shortcodes().collect(),
+                )
+            })
+            .collect();
+        // Characters that are emoji on their own are listed as emoji only
+        let emoji_chars: HashSet<char> = entries
+            .iter()
+            .filter_map(|entry| {
+                let mut chars = entry.text.chars().filter(|&c| c != '\u{FE0F}');
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            })
+            .collect();
+        for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+            if emoji_chars.contains(&c) {
+                continue;
+            }
+            let Some(name) = char_name(c) else {
+                continue;
+            };
+            if NUMBERED_NAME_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                continue;
+            }
+            entries.push(CharEntry::new(
+                c.to_string(),
+                name,
+                block_name(c).unwrap_or(""),
+                Vec::new(),
+            ));
+        }
+        entries
+    })
+}
+
+/// A `U+XXXX` or `0xXXXX` query, as a character
+fn parse_codepoint(query: &str) -> Option<char> {
+    let hex = query
+        .strip_prefix("U+")
+        .or_else(|| query.strip_prefix("u+"))
+        .or_else(|| query.strip_prefix("0x"))
+        .or_else(|| query.strip_prefix("0X"))?;
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
+
+/// Characters matching `query`, best first, at most `limit`
+///
+/// Every word of the query must appear in the name, block, emoji group or
+/// a shortcode. Exact names and shortcodes come first, then names starting
+/// with the query, then names containing each word at a word start, and
+/// shorter names first within each of those. A `U+XXXX` query finds that
+/// code point, and an empty query lists the catalog from the start.
+pub fn search(query: &str, limit: usize) -> Vec<CharEntry> {
+    let query = query.trim();
+    if let Some(c) = parse_codepoint(query) {
+        let text = c.to_string();
+        return vec![catalog()
+            .iter()
+            .find(|entry| entry.text == text)
+            .cloned()
+            .unwrap_or_else(|| {
+                CharEntry::new(
+                    text,
+                    char_name(c).unwrap_or_else(|| codepoint(c)),
+                    block_name(c).unwrap_or(""),
+                    Vec::new(),
+                )
+            })];
+    }
+
+    let upper = query.to_uppercase();
+    let words: Vec<&str> = upper.split_whitespace().collect();
+    if words.is_empty() {
+        return catalog().iter().take(limit).cloned().collect();
+    }
+    let mut ranked: Vec<(u8, &CharEntry)> = catalog()
+        .iter()
+        .filter(|entry| words.iter().all(|word| entry.key.contains(word)))
+        .map(|entry| {
+            let name = entry.name.to_uppercase();
+            let rank = if name == upper
+                || entry
+                    .shortcodes
+                    .iter()
+                    .any(|code| code.eq_ignore_ascii_case(query))
+            {
+                0
+            } else if name.starts_with(&upper) {
+                1
+            } else if words
+                .iter()
+                .all(|word| name.split([' ', '-']).any(|part| part.starts_with(word)))
+            {
+                2
+            } else {
+                3
+            };
+            (rank, entry)
+        })
+        .collect();
+    ranked.sort_by_key(|(rank, entry)| (*rank, entry.name.len()));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, entry)| entry.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_by_name_and_shortcode() {
+        let results = search("grinning face", 5);
+        assert_eq!(results[0].text, "😀");
+        assert_eq!(results[0].category, "Smileys & Emotion");
+
+        let results = search("tada", 5);
+        assert_eq!(results[0].text, "🎉");
+
+        let results = search("greek small alpha", 5);
+        assert_eq!(results[0].text, "α");
+        assert_eq!(results[0].category, "Greek and Coptic");
+    }
+
+    #[test]
+    fn test_search_by_block_and_codepoint() {
+        let results = search("arrows rightwards", 50);
+        assert!(results.iter().any(|entry| entry.text == "→"));
+        assert!(results.iter().all(|entry| entry.key.contains("ARROWS")));
+
+        assert_eq!(
+            search("U+00E9", 5)[0].name,
+            "LATIN SMALL LETTER E WITH ACUTE"
+        );
+        assert_eq!(search("u+4e00", 5)[0].text, "一");
+        assert!(search("no such character anywhere", 5).is_empty());
+    }
+
+    #[test]
+    fn test_inspect_grapheme() {
+        let info = inspect("e\u{301}");
+        assert_eq!(info.len(), 2);
+        assert_eq!(info[0].name.as_deref(), Some("LATIN SMALL LETTER E"));
+        assert_eq!(info[1].name.as_deref(), Some("COMBINING ACUTE ACCENT"));
+        assert_eq!(hex_bytes(&info[1].utf8), "CC 81");
+        assert_eq!(info[1].block, Some("Combining Diacritical Marks"));
+        assert_eq!(emoji_name("👍"), Some("thumbs up"));
+    }
+
+    #[test]
+    fn test_display() {
+        let acute = CharEntry::new("\u{301}".into(), String::new(), "", Vec::new());
+        assert_eq!(acute.display(), "\u{25CC}\u{301}");
+        let space = CharEntry::new("\u{200B}".into(), String::new(), "", Vec::new());
+        assert_eq!(space.display(), "U+200B");
+        assert_eq!(space.codepoints(), "U+200B");
+    }
+}
@@ -423,6 +423,172 @@ pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
     start + current_idx
 }
 
+// ============================================================================
+// Sub-word navigation
+// ============================================================================
+//
+// Sub-word motions work like the word motions above, but also stop inside
+// identifiers: at camelCase humps, between snake_case parts and where digits
+// start or end. Acronyms stay together, so `parseHTTPRequest` splits into
+// `parse`, `HTTP` and `Request`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubwordClass {
+    Upper,
+    Lower,
+    Digit,
+    Underscore,
+    Whitespace,
+    Punctuation,
+}
+
+fn get_subword_class(g: &str) -> SubwordClass {
+    match get_grapheme_class(g) {
+        CharClass::Whitespace => SubwordClass::Whitespace,
+        CharClass::Punctuation => SubwordClass::Punctuation,
+        CharClass::Word => match g.chars().find(|c| c.is_alphanumeric()) {
+            None => SubwordClass::Underscore,
+            Some(c) if c.is_uppercase() => SubwordClass::Upper,
+            Some(c) if c.is_numeric() => SubwordClass::Digit,
+            // Caseless scripts count as lower case
+            Some(_) => SubwordClass::Lower,
+        },
+    }
+}
+
+/// Byte offset and sub-word class of each grapheme in `text`
+fn subword_classes(text: &str) -> Vec<(usize, SubwordClass)> {
+    let mut graphemes = Vec::new();
+    let mut idx = 0;
+    while idx < text.len() {
+        let next = next_grapheme_boundary(text, idx);
+        graphemes.push((idx, get_subword_class(&text[idx..next])));
+        idx = next;
+    }
+    graphemes
+}
+
+fn is_subword_part(class: SubwordClass) -> bool {
+    matches!(
+        class,
+        SubwordClass::Upper | SubwordClass::Lower | SubwordClass::Digit
+    )
+}
+
+/// Find the start of the sub-word to the left of the given position
+///
+/// Like [`find_word_start_left`], but a run of letters and digits is split
+/// into its parts, and underscores before the cursor are skipped like
+/// whitespace.
+pub fn find_subword_start_left(buffer: &Buffer, pos: usize) -> usize {
+    if pos == 0 {
+        return 0;
+    }
+
+    let actual_pos = pos.min(buffer.len());
+    let start = actual_pos.saturating_sub(1000);
+    let bytes = buffer.slice_bytes(start..actual_pos);
+    let text = String::from_utf8_lossy(&bytes);
+    let graphemes = subword_classes(&text);
+    let class = |i: usize| graphemes[i - 1].1;
+
+    // `i` is the index of the grapheme after the cursor
+    let mut i = graphemes.len();
+    while i > 0 && class(i) == SubwordClass::Whitespace {
+        i -= 1;
+    }
+    let skipped_to = i;
+    while i > 0 && class(i) == SubwordClass::Underscore {
+        i -= 1;
+    }
+
+    if i > 0 && is_subword_part(class(i)) {
+        match class(i) {
+            SubwordClass::Lower => {
+                while i > 0 && class(i) == SubwordClass::Lower {
+                    i -= 1;
+                }
+                // The capital starting a hump belongs to it
+                if i > 0 && class(i) == SubwordClass::Upper {
+                    i -= 1;
+                }
+            }
+            part => {
+                while i > 0 && class(i) == part {
+                    i -= 1;
+                }
+            }
+        }
+    } else if i == skipped_to && i > 0 {
+        // Punctuation is consumed as a whole, as by word motions
+        let target = class(i);
+        while i > 0 && class(i) == target {
+            i -= 1;
+        }
+    }
+    // Otherwise the underscores don't follow a word part (`__init`), so
+    // they are the token
+
+    let offset = graphemes.get(i).map_or(text.len(), |&(offset, _)| offset);
+    actual_pos.saturating_sub(text.len() - offset)
+}
+
+/// Find the start of the sub-word to the right of the given position
+///
+/// Like [`find_word_start_right`], but stops at the start of each part of a
+/// run of letters and digits, skipping the underscores between parts.
+pub fn find_subword_start_right(buffer: &Buffer, pos: usize) -> usize {
+    let buf_len = buffer.len();
+    if pos >= buf_len {
+        return buf_len;
+    }
+
+    let start = pos;
+    let end = (pos + 1000).min(buf_len);
+    let bytes = buffer.slice_bytes(start..end);
+    let text = String::from_utf8_lossy(&bytes);
+    let graphemes = subword_classes(&text);
+    let len = graphemes.len();
+    let class = |i: usize| graphemes.get(i).map(|&(_, class)| class);
+    let consume = |mut i: usize, target: SubwordClass| {
+        while class(i) == Some(target) {
+            i += 1;
+        }
+        i
+    };
+
+    let mut i = 0;
+    match graphemes[0].1 {
+        // Whitespace and separators are consumed on their own
+        SubwordClass::Whitespace | SubwordClass::Underscore => {
+            i = consume(0, graphemes[0].1);
+            return start + graphemes.get(i).map_or(text.len(), |&(offset, _)| offset);
+        }
+        SubwordClass::Upper => {
+            i = 1;
+            if class(i) == Some(SubwordClass::Lower) {
+                i = consume(i, SubwordClass::Lower);
+            } else {
+                // An acronym, up to the capital starting the next hump
+                while class(i) == Some(SubwordClass::Upper)
+                    && class(i + 1) != Some(SubwordClass::Lower)
+                {
+                    i += 1;
+                }
+            }
+        }
+        part => i = consume(i, part),
+    }
+
+    // Skip separators to land at the start of the next token
+    if i < len && is_subword_part(graphemes[0].1) {
+        i = consume(i, SubwordClass::Underscore);
+    }
+    i = consume(i, SubwordClass::Whitespace);
+
+    start + graphemes.get(i).map_or(text.len(), |&(offset, _)| offset)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -474,6 +640,35 @@ mod tests {
     // Tests for byte-level word navigation (shared by Buffer and String)
     // ========================================================================
 
+    #[test]
+    fn test_find_subword_start_right() {
+        let buffer = Buffer::from_str_test("parseHTTPRequest snake_case utf8Decode x");
+        let stops: Vec<usize> = std::iter::successors(Some(0), |&pos| {
+            let next = find_subword_start_right(&buffer, pos);
+            (next > pos).then_some(next)
+        })
+        .collect();
+        // parse|HTTP|Request |snake_|case |utf|8|Decode |x|
+        assert_eq!(stops, vec![0, 5, 9, 17, 23, 28, 31, 32, 39, 40]);
+    }
+
+    #[test]
+    fn test_find_subword_start_left() {
+        let buffer = Buffer::from_str_test("parseHTTPRequest snake_case utf8Decode");
+        let stops: Vec<usize> = std::iter::successors(Some(buffer.len()), |&pos| {
+            let next = find_subword_start_left(&buffer, pos);
+            (next < pos).then_some(next)
+        })
+        .collect();
+        assert_eq!(stops, vec![38, 32, 31, 28, 23, 17, 9, 5, 0]);
+
+        // Punctuation and leading underscores are tokens of their own
+        let buffer = Buffer::from_str_test("a.__init");
+        assert_eq!(find_subword_start_left(&buffer, 8), 4);
+        assert_eq!(find_subword_start_left(&buffer, 4), 2);
+        assert_eq!(find_subword_start_left(&buffer, 2), 1);
+    }
+
     #[test]
     fn test_find_word_start_bytes_basic() {
         let s = "hello world test";
@@ -36,6 +36,8 @@ pub struct EditorServerConfig {
     pub working_dir: PathBuf,
     /// Optional session name
     pub session_name: Option<String>,
+    /// Explicit socket path (`fresh --server PATH`); takes precedence over the session name
+    pub socket_path: Option<PathBuf>,
     /// Idle timeout before auto-shutdown
     pub idle_timeout: Option<Duration>,
     /// Editor configuration
@@ -133,7 +135,11 @@ struct ConnectedClient {
 impl EditorServer {
     /// Create a new editor server
     pub fn new(config: EditorServerConfig) -> io::Result<Self> {
-        let socket_paths = if let Some(ref name) = config.session_name {
+        let socket_paths = if let Some(ref path) = config.socket_path {
+            let paths = SocketPaths::for_socket_path(path);
+            paths.check_replaceable()?;
+            paths
+        } else if let Some(ref name) = config.session_name {
             SocketPaths::for_session_name(name)?
         } else {
             SocketPaths::for_working_dir(&config.working_dir)?
@@ -319,6 +325,15 @@ impl EditorServer {
                 if editor.check_mouse_hover_timer() {
                     needs_render = true;
                 }
+                if editor.check_plugin_timers() {
+                    needs_render = true;
+                }
+                if editor.check_before_save_timeout() {
+                    needs_render = true;
+                }
+                if editor.check_selection_stats() {
+                    needs_render = true;
+                }
             }
 
             // Render and broadcast if needed
@@ -584,12 +599,11 @@ impl EditorServer {
         }
 
         // Process control messages
-        eprintln!(
-            "[server] Processing {} control messages",
-            control_messages.len()
-        );
+        if !control_messages.is_empty() {
+            tracing::debug!("Processing {} control messages", control_messages.len());
+        }
         for (idx, msg) in control_messages {
-            eprintln!("[server] Control message from client {}: {:?}", idx, msg);
+            tracing::debug!("Control message from client {}: {:?}", idx, msg);
             // Always process Quit, even from disconnected clients
             if let ClientControl::Quit = msg {
                 tracing::info!("Client requested quit, shutting down");
@@ -752,8 +766,9 @@ impl EditorServer {
             .draw(|frame| editor.render(frame))
             .map_err(|e| io::Error::other(e.to_string()))?;
 
-        // Get the captured output
-        let output = terminal.backend_mut().take_buffer();
+        // Get the captured output, followed by any images drawn over it
+        let mut output = terminal.backend_mut().take_buffer();
+        output.extend(editor.take_graphics_output());
 
         if output.is_empty() && pending_sequences.is_empty() {
             return Ok(());
@@ -72,6 +72,23 @@ impl SocketPaths {
         }
     }
 
+    /// Get socket paths for an explicit socket path (`fresh --server PATH`)
+    ///
+    /// The control socket lives at `path`; the data socket and PID file sit
+    /// next to it with `.data` and `.pid` appended.
+    pub fn for_socket_path(path: &Path) -> Self {
+        let with_suffix = |suffix: &str| {
+            let mut name = path.as_os_str().to_os_string();
+            name.push(suffix);
+            PathBuf::from(name)
+        };
+        Self {
+            data: with_suffix(".data"),
+            control: path.to_path_buf(),
+            pid: with_suffix(".pid"),
+        }
+    }
+
     /// Check if the sockets exist (server might be running)
     pub fn exists(&self) -> bool {
         self.data.exists() && self.control.exists()
@@ -110,10 +127,42 @@ impl SocketPaths {
         false
     }
 
+    /// First of the session paths taken by something other than a socket or
+    /// a PID file, which cleaning up would destroy
+    fn foreign_file(&self) -> Option<&Path> {
+        [&self.data, &self.control]
+            .into_iter()
+            .find(|path| path.symlink_metadata().is_ok() && !platform::is_socket_file(path))
+            .or_else(|| {
+                let is_pid_file = self.pid.symlink_metadata().is_ok_and(|m| m.is_file())
+                    && matches!(self.read_pid(), Ok(Some(_)));
+                (self.pid.symlink_metadata().is_ok() && !is_pid_file).then_some(&self.pid)
+            })
+            .map(PathBuf::as_path)
+    }
+
+    /// Check that binding these paths won't take over a running server or
+    /// delete files that aren't a session's (`fresh --server PATH`)
+    pub fn check_replaceable(&self) -> io::Result<()> {
+        if self.is_server_alive() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a server is already running on {}", self.control.display()),
+            ));
+        }
+        if let Some(path) = self.foreign_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a server socket", path.display()),
+            ));
+        }
+        Ok(())
+    }
+
     /// Clean up stale session files if server is not running
     /// Returns true if files were cleaned up
     pub fn cleanup_if_stale(&self) -> bool {
-        if self.exists() && !self.is_server_alive() {
+        if self.exists() && !self.is_server_alive() && self.foreign_file().is_none() {
             let _ = self.cleanup();
             true
         } else {
@@ -505,6 +554,36 @@ mod tests {
             .contains("my-session.ctrl.sock"));
     }
 
+    #[test]
+    fn test_explicit_socket_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("fresh.sock");
+        let paths = SocketPaths::for_socket_path(&socket);
+        assert_eq!(paths.control, socket);
+        assert_eq!(paths.data, temp_dir.path().join("fresh.sock.data"));
+        assert_eq!(paths.pid, temp_dir.path().join("fresh.sock.pid"));
+    }
+
+    #[test]
+    fn test_explicit_socket_path_keeps_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        std::fs::write(&file, "keep me").unwrap();
+        let paths = SocketPaths::for_socket_path(&file);
+        assert_eq!(
+            paths.check_replaceable().unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+
+        std::fs::remove_file(&file).unwrap();
+        std::fs::write(&paths.pid, "keep me").unwrap();
+        assert_eq!(
+            paths.check_replaceable().unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        assert_eq!(std::fs::read_to_string(&paths.pid).unwrap(), "keep me");
+    }
+
     #[test]
     fn test_exists_returns_false_for_missing_sockets() {
         let temp_dir = TempDir::new().unwrap();
@@ -35,6 +35,13 @@ pub fn socket_name_for_path(path: &Path) -> io::Result<interprocess::local_socke
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
 }
 
+/// Whether `path` is a unix socket, as a server leaves behind
+pub fn is_socket_file(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    path.symlink_metadata()
+        .is_ok_and(|metadata| metadata.file_type().is_socket())
+}
+
 /// Try to read without blocking on Unix
 ///
 /// Sets nonblocking mode temporarily to attempt a read.
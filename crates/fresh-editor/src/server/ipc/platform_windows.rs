@@ -34,6 +34,11 @@ pub fn socket_name_for_path(path: &Path) -> io::Result<interprocess::local_socke
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
 }
 
+/// Whether `path` is a marker file written for a named pipe
+pub fn is_socket_file(path: &Path) -> bool {
+    std::fs::read(path).is_ok_and(|content| content == b"socket")
+}
+
 /// Try to read without blocking on Windows
 ///
 /// Uses PeekNamedPipe to check if data is available before reading.
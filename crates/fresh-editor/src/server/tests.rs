@@ -590,6 +590,7 @@ mod integration_tests {
         let server_config = EditorServerConfig {
             working_dir: temp_dir.clone(),
             session_name: Some(session_name.clone()),
+            socket_path: None,
             idle_timeout: Some(Duration::from_secs(30)),
             editor_config: config,
             dir_context,
@@ -755,6 +756,7 @@ mod integration_tests {
         let server_config = EditorServerConfig {
             working_dir: temp_dir.clone(),
             session_name: Some(session_name.clone()),
+            socket_path: None,
             idle_timeout: Some(Duration::from_secs(30)),
             editor_config: config,
             dir_context,
@@ -889,4 +891,67 @@ mod integration_tests {
         std::fs::remove_dir_all(&temp_dir).ok();
         eprintln!("[multi] === END test_second_client_gets_full_screen ===");
     }
+
+    /// `fresh --server PATH`: the server listens on the given socket and
+    /// clients attach through the same path
+    #[test]
+    fn test_editor_server_explicit_socket_path() {
+        use crate::config::Config;
+        use crate::config_io::DirectoryContext;
+        use crate::server::editor_server::{EditorServer, EditorServerConfig};
+        use std::sync::mpsc;
+
+        let temp_dir = std::env::temp_dir().join(format!("fresh-sock-{}", std::process::id()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let socket = temp_dir.join("fresh.sock");
+
+        let server_config = EditorServerConfig {
+            working_dir: temp_dir.clone(),
+            session_name: None,
+            socket_path: Some(socket.clone()),
+            idle_timeout: None,
+            editor_config: Config::default(),
+            dir_context: DirectoryContext::for_testing(&temp_dir),
+            plugins_enabled: false,
+        };
+
+        let (shutdown_tx, shutdown_rx) = mpsc::channel();
+        // EditorServer must be created in the thread because Editor is not Send
+        let server_handle = thread::spawn(move || {
+            let mut server = EditorServer::new(server_config).unwrap();
+            shutdown_tx.send(server.shutdown_handle()).unwrap();
+            server.run()
+        });
+        let shutdown_handle = shutdown_rx.recv().unwrap();
+
+        let socket_paths = SocketPaths::for_socket_path(&socket);
+        while socket_paths.read_pid().ok().flatten().is_none() {
+            thread::yield_now();
+        }
+        assert!(socket_paths.is_server_alive());
+        // A second server on the same path must not take it over
+        assert_eq!(
+            socket_paths.check_replaceable().unwrap_err().kind(),
+            std::io::ErrorKind::AddrInUse
+        );
+
+        let conn = ClientConnection::connect(&socket_paths).expect("Failed to connect to server");
+        let hello = ClientHello::new(TermSize::new(80, 24));
+        conn.write_control(&serde_json::to_string(&ClientControl::Hello(hello)).unwrap())
+            .unwrap();
+        let response = conn.read_control().unwrap().unwrap();
+        assert!(matches!(
+            serde_json::from_str::<ServerControl>(&response).unwrap(),
+            ServerControl::Hello(_)
+        ));
+
+        let mut output = Vec::new();
+        conn.write_data(b"headless").unwrap();
+        read_until_contains(&conn, &mut output, "headless");
+
+        shutdown_handle.store(true, Ordering::SeqCst);
+        let _ = server_handle.join();
+        let _ = socket_paths.cleanup();
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
 }
@@ -10,12 +10,13 @@
 //! - Computation should be sync (editing, rendering)
 //! - Main loop remains responsive and simple
 
+use crate::services::git::GitEvent;
 use crate::services::terminal::TerminalId;
 use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{
-    CodeActionOrCommand, CompletionItem, Diagnostic, InlayHint, Location,
-    SemanticTokensFullDeltaResult, SemanticTokensLegend, SemanticTokensRangeResult,
-    SemanticTokensResult, SignatureHelp,
+    ApplyWorkspaceEditResponse, CodeActionOrCommand, CompletionItem, Diagnostic, DocumentHighlight,
+    InlayHint, Location, SemanticTokensFullDeltaResult, SemanticTokensLegend,
+    SemanticTokensRangeResult, SemanticTokensResult, SignatureHelp, WorkspaceEdit,
 };
 use serde_json::Value;
 use std::sync::mpsc;
@@ -33,6 +34,8 @@ pub enum LspSemanticTokensResponse {
 pub enum AsyncMessage {
     /// LSP diagnostics received for a file
     LspDiagnostics {
+        /// Server that published the diagnostics
+        language: String,
         uri: String,
         diagnostics: Vec<Diagnostic>,
     },
@@ -42,6 +45,9 @@ pub enum AsyncMessage {
         language: String,
         /// Completion trigger characters from server capabilities
         completion_trigger_characters: Vec<String>,
+        /// Signature help trigger characters from server capabilities
+        /// (empty when the server does not provide signature help)
+        signature_help_trigger_characters: Vec<String>,
         /// Legend describing semantic token types supported by the server
         semantic_tokens_legend: Option<SemanticTokensLegend>,
         /// Whether the server supports full document semantic tokens
@@ -50,6 +56,8 @@ pub enum AsyncMessage {
         semantic_tokens_full_delta: bool,
         /// Whether the server supports range semantic tokens
         semantic_tokens_range: bool,
+        /// Names of the features the server supports (e.g. "hover", "rename")
+        features: Vec<String>,
     },
 
     /// LSP server crashed or failed
@@ -96,6 +104,18 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP document highlight response (occurrences of the symbol under the cursor)
+    LspDocumentHighlight {
+        request_id: u64,
+        highlights: Vec<DocumentHighlight>,
+    },
+
+    /// LSP document or range formatting response
+    LspFormatting {
+        request_id: u64,
+        result: Result<Vec<lsp_types::TextEdit>, String>,
+    },
+
     /// LSP signature help response
     LspSignatureHelp {
         request_id: u64,
@@ -111,6 +131,8 @@ pub enum AsyncMessage {
     /// LSP pulled diagnostics response (textDocument/diagnostic)
     LspPulledDiagnostics {
         request_id: u64,
+        /// Server that produced the diagnostics
+        language: String,
         uri: String,
         /// New result_id for incremental updates (None if server doesn't support)
         result_id: Option<String>,
@@ -167,6 +189,52 @@ pub enum AsyncMessage {
     /// File open dialog: async shortcuts (Windows drive letters) loaded
     FileOpenShortcutsLoaded(Vec<crate::app::file_open::NavigationShortcut>),
 
+    /// A file opened with `open_file_in_background` finished loading
+    FileLoaded {
+        buffer_id: crate::model::event::BufferId,
+        path: std::path::PathBuf,
+        result: anyhow::Result<crate::model::buffer::Buffer>,
+    },
+
+    /// The grammars loaded in the background at startup are ready
+    GrammarsLoaded(std::sync::Arc<crate::primitives::grammar::GrammarRegistry>),
+
+    /// Plugins loaded in the background finished loading
+    PluginsLoaded {
+        errors: Vec<String>,
+        /// Plugins found in the loaded directories, with their config
+        discovered: std::collections::HashMap<String, fresh_core::config::PluginConfig>,
+    },
+
+    /// A search too slow to wait for finished in the background
+    SearchFinished {
+        buffer_id: crate::model::event::BufferId,
+        /// Length of the buffer when it was searched
+        buffer_len: usize,
+        query: String,
+        search_range: Option<std::ops::Range<usize>>,
+        /// Position and length of each match
+        matches: Vec<(usize, usize)>,
+    },
+
+    /// Files with matches found by a project search that is still going on
+    ProjectSearchMatches {
+        search_id: u64,
+        files: Vec<crate::services::project_search::FileMatches>,
+    },
+
+    /// A project search finished, or stopped at its match limit
+    ProjectSearchFinished {
+        search_id: u64,
+        summary: crate::services::project_search::SearchSummary,
+    },
+
+    /// A project search started by a plugin finished
+    PluginGrepFinished {
+        callback_id: u64,
+        files: Vec<crate::services::project_search::FileMatches>,
+    },
+
     /// Terminal output received (triggers redraw)
     TerminalOutput { terminal_id: TerminalId },
 
@@ -203,6 +271,33 @@ pub enum AsyncMessage {
         params: Option<Value>,
     },
 
+    /// Server asks the editor to apply a workspace edit (workspace/applyEdit)
+    ///
+    /// The edit may touch files that aren't open; the outcome goes back to
+    /// the server through `response`.
+    LspApplyEdit {
+        language: String,
+        label: Option<String>,
+        edit: WorkspaceEdit,
+        response: tokio::sync::oneshot::Sender<ApplyWorkspaceEditResponse>,
+    },
+
+    /// Request from a client of the control socket (`fresh --listen`)
+    RemoteRequest {
+        request: crate::services::control_socket::RemoteRequest,
+        response: mpsc::Sender<crate::services::control_socket::RemoteResponse>,
+    },
+
+    /// A linter run on a buffer finished
+    LinterFinished {
+        buffer_id: crate::model::event::BufferId,
+        /// Run number, to drop the results of superseded runs
+        run: u64,
+        /// Key the linter's diagnostics are stored under
+        linter: String,
+        result: Result<Vec<Diagnostic>, crate::services::linter::LintError>,
+    },
+
     /// Response for a plugin-initiated LSP request
     PluginLspResponse {
         language: String,
@@ -228,6 +323,23 @@ pub enum AsyncMessage {
         status: LspServerStatus,
         message: Option<String>,
     },
+
+    /// Result or progress from a background git command
+    Git(GitEvent),
+
+    /// Response, event or output from a debug adapter
+    Dap {
+        /// Debug session the adapter belongs to
+        session_id: u64,
+        message: crate::services::dap::DapMessage,
+    },
+
+    /// Connection or message of a collaborative editing session
+    Collab {
+        /// Session the connection belongs to
+        session_id: u64,
+        event: crate::services::collab::CollabEvent,
+    },
 }
 
 /// LSP progress value types
@@ -248,7 +360,9 @@ pub enum LspProgressValue {
 }
 
 /// LSP message type (corresponds to MessageType in LSP spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Ordered from most to least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LspMessageType {
     Error = 1,
     Warning = 2,
@@ -270,20 +384,24 @@ pub enum LspServerStatus {
 ///
 /// Design:
 /// - Lightweight, cloneable sender that can be passed to async tasks
-/// - Non-blocking receiver checked each frame in main loop
+/// - Non-blocking receiver drained by the main loop, which is woken up when
+///   a message arrives (see `wake_on_message`)
 /// - No locks needed in main loop (channel handles synchronization)
 #[derive(Clone)]
 pub struct AsyncBridge {
     sender: mpsc::Sender<AsyncMessage>,
     // Receiver wrapped in Arc<Mutex<>> to allow cloning
     receiver: std::sync::Arc<std::sync::Mutex<mpsc::Receiver<AsyncMessage>>>,
+    // Messages taken off the channel by `wait` or `has_messages`, delivered
+    // before the rest
+    received: std::sync::Arc<std::sync::Mutex<Vec<AsyncMessage>>>,
 }
 
 impl AsyncBridge {
     /// Create a new async bridge with an unbounded channel
     ///
     /// Unbounded is appropriate here because:
-    /// 1. Main loop processes messages every 16ms (60fps)
+    /// 1. Main loop drains all messages each time it wakes up
     /// 2. LSP messages are infrequent (< 100/sec typically)
     /// 3. Memory usage is bounded by message rate × frame time
     pub fn new() -> Self {
@@ -291,6 +409,7 @@ impl AsyncBridge {
         Self {
             sender,
             receiver: std::sync::Arc::new(std::sync::Mutex::new(receiver)),
+            received: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
@@ -306,10 +425,13 @@ impl AsyncBridge {
 
     /// Try to receive pending messages (non-blocking)
     ///
-    /// Called each frame in the main loop to process async messages.
+    /// Called by the main loop each time it wakes up to process async messages.
     /// Returns all pending messages without blocking.
     pub fn try_recv_all(&self) -> Vec<AsyncMessage> {
         let mut messages = Vec::new();
+        if let Ok(mut received) = self.received.lock() {
+            messages.append(&mut received);
+        }
 
         // Lock the receiver and drain all pending messages
         if let Ok(receiver) = self.receiver.lock() {
@@ -323,11 +445,36 @@ impl AsyncBridge {
 
     /// Check if there are pending messages (non-blocking)
     pub fn has_messages(&self) -> bool {
-        // Note: This is racy but safe - only used for optimization
-        if let Ok(receiver) = self.receiver.lock() {
-            receiver.try_recv().is_ok()
-        } else {
-            false
+        self.wait(std::time::Duration::ZERO)
+    }
+
+    /// Block until a message is pending, or `timeout` passes
+    ///
+    /// Returns true if a message is pending; it is left for `try_recv_all`.
+    pub fn wait(&self, timeout: std::time::Duration) -> bool {
+        let Ok(mut received) = self.received.lock() else {
+            return false;
+        };
+        if !received.is_empty() {
+            return true;
+        }
+        let Ok(receiver) = self.receiver.lock() else {
+            return false;
+        };
+        match receiver.recv_timeout(timeout) {
+            Ok(msg) => {
+                received.push(msg);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Call `wake` whenever a message arrives, so a loop waiting on other
+    /// events can come back to drain the bridge
+    pub fn wake_on_message(&self, wake: impl Fn() + Send + 'static) {
+        if let Ok(mut receiver) = self.receiver.lock() {
+            crate::services::event_bus::wake_on_receive(&mut receiver, wake);
         }
     }
 }
@@ -352,10 +499,12 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![".".to_string()],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -386,20 +535,24 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
         sender
             .send(AsyncMessage::LspInitialized {
                 language: "typescript".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -428,20 +581,24 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
         sender2
             .send(AsyncMessage::LspInitialized {
                 language: "typescript".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -478,6 +635,7 @@ mod tests {
 
         sender
             .send(AsyncMessage::LspDiagnostics {
+                language: "rust".to_string(),
                 uri: "file:///test.rs".to_string(),
                 diagnostics: diagnostics.clone(),
             })
@@ -490,6 +648,7 @@ mod tests {
             AsyncMessage::LspDiagnostics {
                 uri,
                 diagnostics: diags,
+                ..
             } => {
                 assert_eq!(uri, "file:///test.rs");
                 assert_eq!(diags.len(), 1);
@@ -540,10 +699,12 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -561,10 +722,12 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -587,30 +750,36 @@ mod tests {
             .send(AsyncMessage::LspInitialized {
                 language: "rust".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
         sender
             .send(AsyncMessage::LspInitialized {
                 language: "typescript".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
         sender
             .send(AsyncMessage::LspInitialized {
                 language: "python".to_string(),
                 completion_trigger_characters: vec![],
+                signature_help_trigger_characters: vec![],
                 semantic_tokens_legend: None,
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                features: vec![],
             })
             .unwrap();
 
@@ -631,4 +800,45 @@ mod tests {
             _ => panic!("Expected ordered LspInitialized messages"),
         }
     }
+
+    #[test]
+    fn test_async_bridge_wait_keeps_message() {
+        let bridge = AsyncBridge::new();
+        assert!(!bridge.wait(std::time::Duration::ZERO));
+
+        bridge
+            .sender()
+            .send(AsyncMessage::LspError {
+                language: "rust".to_string(),
+                error: "first".to_string(),
+                stderr_log_path: None,
+            })
+            .unwrap();
+        assert!(bridge.wait(std::time::Duration::from_secs(1)));
+        assert!(bridge.has_messages());
+        assert_eq!(bridge.try_recv_all().len(), 1);
+        assert!(!bridge.has_messages());
+    }
+
+    #[test]
+    fn test_async_bridge_wake_on_message() {
+        let bridge = AsyncBridge::new();
+        let (woken_tx, woken_rx) = mpsc::channel();
+        bridge.wake_on_message(move || {
+            let _ = woken_tx.send(());
+        });
+
+        bridge
+            .sender()
+            .send(AsyncMessage::LspError {
+                language: "rust".to_string(),
+                error: "first".to_string(),
+                stderr_log_path: None,
+            })
+            .unwrap();
+        woken_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(bridge.try_recv_all().len(), 1);
+    }
 }
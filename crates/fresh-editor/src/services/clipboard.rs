@@ -240,6 +240,18 @@ impl Clipboard {
     }
 }
 
+/// Connect to the system clipboard (X11/Wayland/macOS/Windows), keeping the
+/// connection for later copies; the error says why it can't be reached
+pub fn connect_system_clipboard() -> Result<(), String> {
+    let mut guard = SYSTEM_CLIPBOARD
+        .lock()
+        .map_err(|_| "clipboard lock poisoned".to_string())?;
+    if guard.is_none() {
+        *guard = Some(arboard::Clipboard::new().map_err(|e| e.to_string())?);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
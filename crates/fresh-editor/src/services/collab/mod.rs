@@ -0,0 +1,27 @@
+//! Collaborative editing
+//!
+//! Peers share one buffer by exchanging the same `Insert`/`Delete` events
+//! the editor records in its event log.
+//!
+//! # Module Structure
+//!
+//! - **`protocol`**: [`CollabMessage`] - JSON-lines messages between the host
+//!   and its guests.
+//! - **`ot`**: Operational transformation. The host orders all edits; guests
+//!   keep their unacknowledged edits and transform incoming ones against them
+//!   (the Jupiter scheme), so every peer converges on the same text.
+//! - **`transport`**: TCP listener and connections. Incoming messages reach
+//!   the main loop as `AsyncMessage::Collab`.
+//!
+//! The session itself (capturing local edits, remote cursors and per-peer
+//! undo) is driven by the editor in `app::collab`.
+
+pub mod ot;
+pub mod protocol;
+pub mod transport;
+
+pub use protocol::{
+    session_token, split_join_address, tokens_match, CollabMessage, Participant, PeerId,
+    HOST_PEER_ID,
+};
+pub use transport::{CollabEvent, CollabListener, ConnectionId, PeerLink};
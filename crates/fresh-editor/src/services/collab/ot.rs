@@ -0,0 +1,432 @@
+//! Operational transformation of buffer edits
+//!
+//! An operation is a sequence of `Event::Insert` / `Event::Delete` applied in
+//! order. [`transform`] takes two operations made concurrently on the same
+//! text and returns versions of each that apply on top of the other, so both
+//! peers converge on the same text:
+//!
+//! ```text
+//! apply(apply(text, a), b') == apply(apply(text, b), a')
+//! ```
+//!
+//! Inserts at the same position are ordered by `a_first`; the host passes
+//! `false` for guest operations and guests pass `true` for host operations,
+//! so the host's ordering always wins.
+//!
+//! Operations received over the network must pass [`is_valid`] against the
+//! text they were made on before they are transformed or applied; both index
+//! the text with their offsets and panic on bad ones.
+
+use crate::model::event::{CursorId, Event};
+
+/// Transform two concurrent operations against each other
+///
+/// Returns `(a', b')` where `a'` applies after `b` and `b'` applies after `a`.
+pub fn transform(a: &[Event], b: &[Event], a_first: bool) -> (Vec<Event>, Vec<Event>) {
+    if a.is_empty() || b.is_empty() {
+        return (a.to_vec(), b.to_vec());
+    }
+    if a.len() == 1 && b.len() == 1 {
+        return (
+            transform_event(&a[0], &b[0], a_first),
+            transform_event(&b[0], &a[0], !a_first),
+        );
+    }
+    if a.len() > 1 {
+        let (head, tail) = a.split_at(1);
+        let (head, b) = transform(head, b, a_first);
+        let (tail, b) = transform(tail, &b, a_first);
+        return ([head, tail].concat(), b);
+    }
+    let (head, tail) = b.split_at(1);
+    let (a, head) = transform(a, head, a_first);
+    let (a, tail) = transform(&a, tail, a_first);
+    (a, [head, tail].concat())
+}
+
+/// Transform a single edit `a` so that it applies after `b`
+fn transform_event(a: &Event, b: &Event, a_first: bool) -> Vec<Event> {
+    match (a, b) {
+        (
+            Event::Insert { position, text, .. },
+            Event::Insert {
+                position: other,
+                text: other_text,
+                ..
+            },
+        ) => {
+            let position = if *other < *position || (*other == *position && !a_first) {
+                position + other_text.len()
+            } else {
+                *position
+            };
+            vec![insert(position, text.clone())]
+        }
+        (Event::Insert { position, text, .. }, Event::Delete { range, .. }) => {
+            let position = if *position <= range.start {
+                *position
+            } else if *position >= range.end {
+                position - range.len()
+            } else {
+                range.start
+            };
+            vec![insert(position, text.clone())]
+        }
+        (
+            Event::Delete {
+                range,
+                deleted_text,
+                ..
+            },
+            Event::Insert { position, text, .. },
+        ) => {
+            let len = text.len();
+            if *position <= range.start {
+                vec![delete(range.start + len, deleted_text.clone())]
+            } else if *position >= range.end {
+                vec![delete(range.start, deleted_text.clone())]
+            } else {
+                // The insert landed inside the deleted range: keep it and delete
+                // the text on either side, right part first so the left part's
+                // offsets stay valid
+                let split = position - range.start;
+                vec![
+                    delete(position + len, deleted_text[split..].to_string()),
+                    delete(range.start, deleted_text[..split].to_string()),
+                ]
+            }
+        }
+        (
+            Event::Delete {
+                range,
+                deleted_text,
+                ..
+            },
+            Event::Delete { range: other, .. },
+        ) => {
+            if range.end <= other.start {
+                return vec![delete(range.start, deleted_text.clone())];
+            }
+            if range.start >= other.end {
+                return vec![delete(range.start - other.len(), deleted_text.clone())];
+            }
+            // Overlap: the other side already deleted the shared part
+            let overlap_start = range.start.max(other.start) - range.start;
+            let overlap_end = range.end.min(other.end) - range.start;
+            let remaining = format!(
+                "{}{}",
+                &deleted_text[..overlap_start],
+                &deleted_text[overlap_end..]
+            );
+            if remaining.is_empty() {
+                Vec::new()
+            } else {
+                vec![delete(range.start.min(other.start), remaining)]
+            }
+        }
+        // `b` is not an edit
+        (Event::Insert { .. } | Event::Delete { .. }, _) => vec![a.clone()],
+        // Only inserts and deletes are shared
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `events` only insert and delete at char boundaries of `text`,
+/// each delete naming the text it removes
+pub fn is_valid(text: &str, events: &[Event]) -> bool {
+    let mut text = text.to_string();
+    for event in events {
+        match event {
+            Event::Insert {
+                position,
+                text: inserted,
+                ..
+            } => {
+                if !text.is_char_boundary(*position) {
+                    return false;
+                }
+                text.insert_str(*position, inserted);
+            }
+            Event::Delete {
+                range,
+                deleted_text,
+                ..
+            } => {
+                if text.get(range.clone()) != Some(deleted_text.as_str()) {
+                    return false;
+                }
+                text.replace_range(range.clone(), "");
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Apply an operation to a string (used by tests and to check convergence)
+pub fn apply_to_string(text: &mut String, events: &[Event]) {
+    for event in events {
+        match event {
+            Event::Insert {
+                position, text: t, ..
+            } => text.insert_str(*position, t),
+            Event::Delete { range, .. } => {
+                text.replace_range(range.clone(), "");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The operation that reverts `events`
+pub fn invert(events: &[Event]) -> Vec<Event> {
+    events.iter().rev().filter_map(Event::inverse).collect()
+}
+
+/// Map a position through an operation
+pub fn transform_position(position: usize, events: &[Event]) -> usize {
+    events.iter().fold(position, |pos, event| match event {
+        Event::Insert {
+            position: at, text, ..
+        } if *at <= pos => pos + text.len(),
+        Event::Delete { range, .. } if range.start < pos => {
+            pos - (pos.min(range.end) - range.start)
+        }
+        _ => pos,
+    })
+}
+
+/// Keep only the edits of an event, flattening batches
+pub fn edits_of(event: &Event, out: &mut Vec<Event>) {
+    match event {
+        Event::Insert { position, text, .. } => {
+            if !text.is_empty() {
+                out.push(insert(*position, text.clone()));
+            }
+        }
+        Event::Delete {
+            range,
+            deleted_text,
+            ..
+        } => {
+            if !range.is_empty() {
+                out.push(delete(range.start, deleted_text.clone()));
+            }
+        }
+        Event::Batch { events, .. } => {
+            for event in events {
+                edits_of(event, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The smallest edit turning `old` into `new` (common prefix and suffix kept)
+pub fn diff(old: &str, new: &str) -> Vec<Event> {
+    let prefix = old
+        .char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or_else(|| old.len().min(new.len()));
+    let suffix = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum::<usize>();
+
+    let mut events = Vec::new();
+    let old_end = old.len() - suffix;
+    if old_end > prefix {
+        events.push(delete(prefix, old[prefix..old_end].to_string()));
+    }
+    let new_end = new.len() - suffix;
+    if new_end > prefix {
+        events.push(insert(prefix, new[prefix..new_end].to_string()));
+    }
+    events
+}
+
+fn insert(position: usize, text: String) -> Event {
+    Event::Insert {
+        position,
+        text,
+        cursor_id: CursorId::UNDO_SENTINEL,
+    }
+}
+
+fn delete(start: usize, deleted_text: String) -> Event {
+    Event::Delete {
+        range: start..start + deleted_text.len(),
+        deleted_text,
+        cursor_id: CursorId::UNDO_SENTINEL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converges(text: &str, a: Vec<Event>, b: Vec<Event>, a_first: bool) -> String {
+        let (a_prime, b_prime) = transform(&a, &b, a_first);
+        let mut left = text.to_string();
+        apply_to_string(&mut left, &a);
+        apply_to_string(&mut left, &b_prime);
+        let mut right = text.to_string();
+        apply_to_string(&mut right, &b);
+        apply_to_string(&mut right, &a_prime);
+        assert_eq!(left, right);
+        left
+    }
+
+    #[test]
+    fn test_concurrent_inserts() {
+        assert_eq!(
+            converges(
+                "ac",
+                vec![insert(1, "b".into())],
+                vec![insert(2, "d".into())],
+                true
+            ),
+            "abcd"
+        );
+        // Same position: a_first decides the order
+        assert_eq!(
+            converges(
+                "",
+                vec![insert(0, "a".into())],
+                vec![insert(0, "b".into())],
+                true
+            ),
+            "ab"
+        );
+        assert_eq!(
+            converges(
+                "",
+                vec![insert(0, "a".into())],
+                vec![insert(0, "b".into())],
+                false
+            ),
+            "ba"
+        );
+    }
+
+    #[test]
+    fn test_insert_inside_delete_survives() {
+        let result = converges(
+            "hello world",
+            vec![delete(2, "llo wo".into())],
+            vec![insert(5, "XY".into())],
+            true,
+        );
+        assert_eq!(result, "heXYrld");
+    }
+
+    #[test]
+    fn test_overlapping_deletes() {
+        let result = converges(
+            "abcdefgh",
+            vec![delete(1, "bcde".into())],
+            vec![delete(3, "defg".into())],
+            true,
+        );
+        assert_eq!(result, "ah");
+
+        let result = converges(
+            "abcdef",
+            vec![delete(1, "bc".into())],
+            vec![delete(0, "abcdef".into())],
+            false,
+        );
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_multi_event_operations() {
+        let result = converges(
+            "one two three",
+            vec![insert(3, ",".into()), delete(9, "three".into())],
+            vec![insert(0, ">> ".into()), insert(16, "!".into())],
+            true,
+        );
+        assert_eq!(result, ">> one, two !");
+    }
+
+    #[test]
+    fn test_invert_restores_text() {
+        let mut text = "abc".to_string();
+        let op = vec![delete(1, "b".into()), insert(1, "XYZ".into())];
+        apply_to_string(&mut text, &op);
+        assert_eq!(text, "aXYZc");
+        apply_to_string(&mut text, &invert(&op));
+        assert_eq!(text, "abc");
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut text = "fn main() {}".to_string();
+        let new = "fn main() { run(); }";
+        let op = diff(&text, new);
+        apply_to_string(&mut text, &op);
+        assert_eq!(text, new);
+        assert!(diff("same", "same").is_empty());
+
+        let mut text = "héllo".to_string();
+        let op = diff(&text, "hällo");
+        apply_to_string(&mut text, &op);
+        assert_eq!(text, "hällo");
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid(
+            "héllo",
+            &[delete(1, "é".into()), insert(1, "e".into())]
+        ));
+        // Past the end, inside a character, a reversed range
+        assert!(!is_valid("héllo", &[insert(7, "x".into())]));
+        assert!(!is_valid("héllo", &[insert(2, "x".into())]));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..1;
+        assert!(!is_valid(
+            "héllo",
+            &[Event::Delete {
+                range: reversed,
+                deleted_text: String::new(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }]
+        ));
+        // A delete must name the text it removes, as of the edits before it
+        assert!(!is_valid(
+            "abc",
+            &[delete(0, "ab".into()), delete(1, "c".into())]
+        ));
+        assert!(is_valid(
+            "abc",
+            &[delete(0, "ab".into()), delete(0, "c".into())]
+        ));
+        // Anything but inserts and deletes
+        assert!(!is_valid(
+            "abc",
+            &[Event::MoveCursor {
+                cursor_id: CursorId::UNDO_SENTINEL,
+                old_position: 0,
+                new_position: 1,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_transform_position() {
+        let op = vec![insert(0, "ab".into()), delete(4, "xy".into())];
+        assert_eq!(transform_position(3, &op), 4);
+        assert_eq!(transform_position(7, &op), 7);
+    }
+}
@@ -0,0 +1,164 @@
+//! Wire format of collaborative sessions
+//!
+//! Messages are JSON objects, one per line. The host is the single sequencer:
+//! every operation it accepts gets the next revision number, and guests tag
+//! the operations they send with the last revision they have seen.
+//!
+//! Every session has a random token that guests must present in their
+//! `Hello`; it is part of the address shared with them, `token@host:port`.
+
+use crate::model::event::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Participant identifier; the host is always [`HOST_PEER_ID`]
+pub type PeerId = u32;
+
+/// Peer id of the session host
+pub const HOST_PEER_ID: PeerId = 0;
+
+/// A participant of a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Participant {
+    pub peer_id: PeerId,
+    pub name: String,
+}
+
+/// Message exchanged between host and guests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabMessage {
+    /// Guest -> host: first message after connecting
+    Hello { name: String, token: String },
+    /// Host -> guest: the shared text at `revision` and who is already there
+    Welcome {
+        peer_id: PeerId,
+        revision: u64,
+        text: String,
+        peers: Vec<Participant>,
+    },
+    /// An edit made of `Insert`/`Delete` events
+    ///
+    /// From a guest, `revision` is the revision the edit was made against.
+    /// From the host, it is the revision the edit created.
+    Op {
+        peer_id: PeerId,
+        revision: u64,
+        events: Vec<Event>,
+    },
+    /// Host -> guest: the guest's outstanding edit was accepted as `revision`
+    Ack { revision: u64 },
+    /// A participant's cursor and selection anchor
+    Cursor {
+        peer_id: PeerId,
+        position: usize,
+        anchor: Option<usize>,
+    },
+    /// Host -> guests: someone joined
+    PeerJoined { peer: Participant },
+    /// Host -> guests: someone left
+    PeerLeft { peer_id: PeerId },
+}
+
+impl CollabMessage {
+    /// Encode as a single line (without the trailing newline)
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Decode a line received from a peer
+    pub fn from_line(line: &str) -> Result<Self, String> {
+        serde_json::from_str(line).map_err(|e| e.to_string())
+    }
+}
+
+/// A new random session token (128 bits, hex)
+pub fn session_token() -> String {
+    let half = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0),
+        );
+        hasher.finish()
+    };
+    format!("{:016x}{:016x}", half(), half())
+}
+
+/// Compare session tokens in time that doesn't depend on where they differ
+pub fn tokens_match(token: &str, expected: &str) -> bool {
+    token.len() == expected.len()
+        && token
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Split a join address `token@host:port` into the token and the address
+///
+/// An address without a token gets an empty one, which no host accepts.
+pub fn split_join_address(address: &str) -> (&str, &str) {
+    match address.rsplit_once('@') {
+        Some((token, address)) => (token, address),
+        None => ("", address),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::event::CursorId;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("0123abcd", "0123abcd"));
+        assert!(!tokens_match("0123abce", "0123abcd"));
+        assert!(!tokens_match("0123abc", "0123abcd"));
+        assert!(!tokens_match("", "0123abcd"));
+    }
+
+    #[test]
+    fn test_op_round_trip() {
+        let message = CollabMessage::Op {
+            peer_id: 2,
+            revision: 7,
+            events: vec![Event::Insert {
+                position: 3,
+                text: "hi\n".to_string(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            }],
+        };
+        let line = message.to_line();
+        assert!(!line.contains('\n'));
+        match CollabMessage::from_line(&line).unwrap() {
+            CollabMessage::Op {
+                peer_id,
+                revision,
+                events,
+            } => {
+                assert_eq!((peer_id, revision), (2, 7));
+                assert!(matches!(
+                    &events[..],
+                    [Event::Insert { position: 3, text, .. }] if text == "hi\n"
+                ));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_join_address() {
+        let token = session_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, session_token());
+        assert_eq!(
+            split_join_address("abc@127.0.0.1:7878"),
+            ("abc", "127.0.0.1:7878")
+        );
+        assert_eq!(split_join_address("localhost:7878"), ("", "localhost:7878"));
+    }
+}
@@ -0,0 +1,287 @@
+//! TCP transport for collaborative sessions
+//!
+//! Each connection gets a reader thread that forwards decoded messages to the
+//! main loop as `AsyncMessage::Collab` and a writer thread fed by a channel,
+//! so the editor never blocks on the network.
+
+use super::protocol::CollabMessage;
+use crate::services::async_bridge::AsyncMessage;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// How long joining waits for the host to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a guest has to introduce itself before it is dropped
+const HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest line accepted before the guest introduced itself
+const MAX_HELLO_BYTES: u64 = 4 * 1024;
+
+/// Longest message line accepted (the host's welcome carries the whole text)
+const MAX_MESSAGE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Identifies one connection within a session
+pub type ConnectionId = u64;
+
+/// Network event forwarded to the main loop
+#[derive(Debug)]
+pub enum CollabEvent {
+    /// A guest connected to the host
+    Connected {
+        connection: ConnectionId,
+        link: PeerLink,
+    },
+    /// A message arrived on a connection
+    Message {
+        connection: ConnectionId,
+        message: CollabMessage,
+    },
+    /// The connection closed (`error` is set if it failed)
+    Disconnected {
+        connection: ConnectionId,
+        error: Option<String>,
+    },
+}
+
+/// Sending half of a connection
+///
+/// Dropping the link closes the connection.
+#[derive(Debug)]
+pub struct PeerLink {
+    tx: mpsc::Sender<CollabMessage>,
+    stream: TcpStream,
+}
+
+impl PeerLink {
+    /// Queue a message; returns false if the connection is gone
+    pub fn send(&self, message: CollabMessage) -> bool {
+        self.tx.send(message).is_ok()
+    }
+}
+
+impl Drop for PeerLink {
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Listening socket of a hosted session
+///
+/// Dropping it stops accepting new guests.
+#[derive(Debug)]
+pub struct CollabListener {
+    local_addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl CollabListener {
+    /// Address guests connect to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for CollabListener {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake the blocking accept so the thread sees the flag
+        let _ = TcpStream::connect_timeout(&self.local_addr, Duration::from_millis(100));
+    }
+}
+
+/// Accept guests on `addr`
+///
+/// Every accepted connection is reported as [`CollabEvent::Connected`]. It is
+/// closed unless its first message is a `Hello` that arrives within
+/// [`HELLO_TIMEOUT`].
+pub fn listen(
+    addr: &str,
+    session_id: u64,
+    async_tx: mpsc::Sender<AsyncMessage>,
+) -> std::io::Result<CollabListener> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    let stopped = Arc::new(AtomicBool::new(false));
+    let next_connection = AtomicU64::new(1);
+
+    let stop_flag = stopped.clone();
+    thread::Builder::new()
+        .name("collab-accept".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        tracing::warn!("Collab accept failed: {}", e);
+                        continue;
+                    }
+                };
+                let connection = next_connection.fetch_add(1, Ordering::SeqCst);
+                match spawn_connection(stream, session_id, connection, true, async_tx.clone()) {
+                    Ok(link) => {
+                        let event = CollabEvent::Connected { connection, link };
+                        if async_tx
+                            .send(AsyncMessage::Collab { session_id, event })
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Collab connection setup failed: {}", e),
+                }
+            }
+        })?;
+
+    Ok(CollabListener {
+        local_addr,
+        stopped,
+    })
+}
+
+/// Connect to a host at `addr`
+///
+/// Messages from the host are reported with connection id 0.
+pub fn connect(
+    addr: &str,
+    session_id: u64,
+    async_tx: mpsc::Sender<AsyncMessage>,
+) -> std::io::Result<PeerLink> {
+    let mut last_error = None;
+    for socket_addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return spawn_connection(stream, session_id, 0, false, async_tx),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "address did not resolve")
+    }))
+}
+
+/// Read one line of at most `limit` bytes, without its newline
+///
+/// Returns `Ok(None)` at the end of the stream.
+fn read_line_limited(reader: &mut impl BufRead, limit: u64) -> std::io::Result<Option<String>> {
+    let mut line = Vec::new();
+    reader.take(limit + 1).read_until(b'\n', &mut line)?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    } else if line.len() as u64 > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "collab message too long",
+        ));
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn spawn_connection(
+    stream: TcpStream,
+    session_id: u64,
+    connection: ConnectionId,
+    expect_hello: bool,
+    async_tx: mpsc::Sender<AsyncMessage>,
+) -> std::io::Result<PeerLink> {
+    stream.set_nodelay(true)?;
+    let reader = stream.try_clone()?;
+    let mut writer = stream.try_clone()?;
+    let (tx, rx) = mpsc::channel::<CollabMessage>();
+
+    let greeted = Arc::new(AtomicBool::new(!expect_hello));
+    if expect_hello {
+        let watched = stream.try_clone()?;
+        let greeted = greeted.clone();
+        thread::Builder::new()
+            .name("collab-hello".to_string())
+            .spawn(move || {
+                thread::sleep(HELLO_TIMEOUT);
+                if !greeted.load(Ordering::SeqCst) {
+                    let _ = watched.shutdown(Shutdown::Both);
+                }
+            })?;
+    }
+
+    thread::Builder::new()
+        .name("collab-write".to_string())
+        .spawn(move || {
+            for message in rx {
+                let mut line = message.to_line();
+                line.push('\n');
+                if writer.write_all(line.as_bytes()).is_err() {
+                    break;
+                }
+            }
+            let _ = writer.shutdown(Shutdown::Write);
+        })?;
+
+    thread::Builder::new()
+        .name("collab-read".to_string())
+        .spawn(move || {
+            let mut reader = BufReader::new(reader);
+            let mut error = None;
+            loop {
+                let greeting = !greeted.load(Ordering::SeqCst);
+                let limit = if greeting {
+                    MAX_HELLO_BYTES
+                } else {
+                    MAX_MESSAGE_BYTES
+                };
+                let line = match read_line_limited(&mut reader, limit) {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                };
+                if line.trim().is_empty() && !greeting {
+                    continue;
+                }
+                let message = match CollabMessage::from_line(&line) {
+                    Ok(message) => message,
+                    Err(e) if !greeting => {
+                        tracing::warn!("Ignoring malformed collab message: {}", e);
+                        continue;
+                    }
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                };
+                if greeting {
+                    if !matches!(message, CollabMessage::Hello { .. }) {
+                        error = Some("expected a hello".to_string());
+                        break;
+                    }
+                    greeted.store(true, Ordering::SeqCst);
+                }
+                let event = CollabEvent::Message {
+                    connection,
+                    message,
+                };
+                if async_tx
+                    .send(AsyncMessage::Collab { session_id, event })
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            let event = CollabEvent::Disconnected { connection, error };
+            let _ = async_tx.send(AsyncMessage::Collab { session_id, event });
+        })?;
+
+    Ok(PeerLink { tx, stream })
+}
@@ -0,0 +1,452 @@
+//! JSON-RPC control socket (`fresh --listen`)
+//!
+//! External tools drive a running editor by sending JSON-RPC 2.0 requests,
+//! one per line, to a unix socket. Each connection gets a thread that parses
+//! the requests and hands them to the main loop as
+//! `AsyncMessage::RemoteRequest`; the reply is written back once the editor
+//! has handled the request. Only a small set of methods is offered, mirroring
+//! the plugin API: open a file, go to a position, insert text, list buffers
+//! and run a command.
+
+use crate::services::async_bridge::AsyncMessage;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// How long a request waits for the editor to handle it
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC error codes
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// A valid request the editor couldn't carry out
+pub const REQUEST_FAILED: i64 = -32000;
+
+/// Methods clients may call
+pub const METHODS: &[&str] = &[
+    "openFile",
+    "gotoPosition",
+    "insertText",
+    "listBuffers",
+    "runCommand",
+];
+
+/// What a client asks the editor to do
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(
+    tag = "method",
+    content = "params",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    deny_unknown_fields
+)]
+pub enum RemoteRequest {
+    /// Open a file (relative to the editor's working directory), optionally
+    /// at a 1-based line and column
+    OpenFile {
+        path: PathBuf,
+        #[serde(default)]
+        line: Option<usize>,
+        #[serde(default)]
+        column: Option<usize>,
+    },
+    /// Move the cursor of the active buffer to a 1-based line and column
+    GotoPosition {
+        line: usize,
+        #[serde(default)]
+        column: Option<usize>,
+    },
+    /// Insert text at the cursor, or at a byte offset of a buffer
+    InsertText {
+        text: String,
+        #[serde(default)]
+        buffer_id: Option<usize>,
+        #[serde(default)]
+        position: Option<usize>,
+    },
+    /// List the open buffers
+    ListBuffers,
+    /// Run an action (`save`, `move_line_up`) or command palette command by name
+    RunCommand { name: String },
+}
+
+impl RemoteRequest {
+    /// Read the request for `method` from its `params`
+    pub fn parse(method: &str, params: Value) -> Result<Self, RemoteError> {
+        if !METHODS.contains(&method) {
+            return Err(RemoteError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{}'", method),
+            ));
+        }
+        let mut message = json!({ "method": method });
+        if !params.is_null() {
+            message["params"] = params;
+        }
+        serde_json::from_value(message).map_err(|e| RemoteError::invalid_params(e.to_string()))
+    }
+}
+
+/// A JSON-RPC error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RemoteError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self::new(REQUEST_FAILED, message)
+    }
+}
+
+impl std::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+/// Reply to a request, sent back by the main loop
+pub type RemoteResponse = Result<Value, RemoteError>;
+
+/// Socket used when `--listen` isn't given a path: one per working directory,
+/// next to the session sockets
+pub fn default_socket_path(working_dir: &Path) -> io::Result<PathBuf> {
+    let working_dir = working_dir
+        .canonicalize()
+        .unwrap_or_else(|_| working_dir.to_path_buf());
+    Ok(
+        crate::server::SocketPaths::socket_directory()?.join(format!(
+            "{}.rpc.sock",
+            crate::workspace::encode_path_for_filename(&working_dir)
+        )),
+    )
+}
+
+/// Listening control socket
+///
+/// Dropping it stops accepting connections and removes the socket file.
+#[derive(Debug)]
+pub struct ControlSocket {
+    path: PathBuf,
+    stopped: Arc<AtomicBool>,
+}
+
+impl ControlSocket {
+    /// Path clients connect to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        #[cfg(unix)]
+        {
+            // Wake the blocking accept so the thread sees the flag
+            let _ = std::os::unix::net::UnixStream::connect(&self.path);
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Accept control connections on a unix socket at `path`
+///
+/// A socket file left behind by an editor that is gone is replaced; one that
+/// still answers is an error, and so is anything at `path` that isn't a
+/// socket. The socket is only accessible to its owner.
+#[cfg(unix)]
+pub fn listen(path: &Path, async_tx: mpsc::Sender<AsyncMessage>) -> io::Result<ControlSocket> {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_socket() => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Ok(_) => {
+            if UnixStream::connect(path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("another editor is listening on {}", path.display()),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)?;
+
+    // Bind in a directory only we can enter and make the socket private
+    // there, so nobody can connect before it is moved into place
+    let staging = parent.join(format!(
+        ".{}.{}",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id()
+    ));
+    std::fs::DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join("socket");
+    let bound = UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+        // Unlike a rename, a link fails if something appeared at `path`
+        std::fs::hard_link(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&staging);
+    let listener = bound?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let stop_flag = stopped.clone();
+    std::thread::Builder::new()
+        .name("control-accept".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        tracing::warn!("Control socket accept failed: {}", e);
+                        continue;
+                    }
+                };
+                let async_tx = async_tx.clone();
+                let spawned = std::thread::Builder::new()
+                    .name("control-connection".to_string())
+                    .spawn(move || match stream.try_clone() {
+                        Ok(writer) => serve(BufReader::new(stream), writer, &async_tx),
+                        Err(e) => tracing::warn!("Control connection setup failed: {}", e),
+                    });
+                if let Err(e) = spawned {
+                    tracing::warn!("Control connection setup failed: {}", e);
+                }
+            }
+        })?;
+
+    Ok(ControlSocket {
+        path: path.to_path_buf(),
+        stopped,
+    })
+}
+
+/// Unix sockets aren't available here
+#[cfg(not(unix))]
+pub fn listen(path: &Path, _async_tx: mpsc::Sender<AsyncMessage>) -> io::Result<ControlSocket> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "cannot listen on {}: the control socket needs unix sockets",
+            path.display()
+        ),
+    ))
+}
+
+/// Answer the requests of one connection until it closes
+fn serve(reader: impl BufRead, mut writer: impl Write, async_tx: &mpsc::Sender<AsyncMessage>) {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(reply) = handle_line(&line, async_tx) else {
+            continue;
+        };
+        if writeln!(writer, "{}", reply).is_err() {
+            break;
+        }
+    }
+}
+
+/// Handle one JSON-RPC message; notifications (no `id`) get no reply
+fn handle_line(line: &str, async_tx: &mpsc::Sender<AsyncMessage>) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_reply(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        let id = id.unwrap_or(Value::Null);
+        return Some(error_reply(id, INVALID_REQUEST, "missing method"));
+    };
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = RemoteRequest::parse(method, params).and_then(|request| {
+        let (response, reply) = mpsc::channel();
+        async_tx
+            .send(AsyncMessage::RemoteRequest { request, response })
+            .map_err(|_| RemoteError::failed("the editor is shutting down"))?;
+        reply
+            .recv_timeout(REQUEST_TIMEOUT)
+            .map_err(|_| RemoteError::failed("the editor did not answer"))?
+    });
+
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_reply(id, e.code, &e.message),
+    })
+}
+
+fn error_reply(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Send one request to the editor listening on `path` and return its result
+#[cfg(unix)]
+pub fn send_request(path: &Path, method: &str, params: Value) -> anyhow::Result<Value> {
+    use anyhow::Context;
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(path)
+        .with_context(|| format!("no editor is listening on {}", path.display()))?;
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    writeln!(stream, "{}", request)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    let reply: Value = serde_json::from_str(&line).context("invalid reply from the editor")?;
+    if let Some(error) = reply.get("error") {
+        return Err(RemoteError::new(
+            error["code"].as_i64().unwrap_or(REQUEST_FAILED),
+            error["message"].as_str().unwrap_or_default(),
+        )
+        .into());
+    }
+    Ok(reply.get("result").cloned().unwrap_or(Value::Null))
+}
+
+/// Unix sockets aren't available here
+#[cfg(not(unix))]
+pub fn send_request(_path: &Path, _method: &str, _params: Value) -> anyhow::Result<Value> {
+    anyhow::bail!("the control socket needs unix sockets")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requests() {
+        assert_eq!(
+            RemoteRequest::parse("openFile", json!({ "path": "src/main.rs", "line": 3 })),
+            Ok(RemoteRequest::OpenFile {
+                path: PathBuf::from("src/main.rs"),
+                line: Some(3),
+                column: None,
+            })
+        );
+        assert_eq!(
+            RemoteRequest::parse("insertText", json!({ "text": "x", "bufferId": 2 })),
+            Ok(RemoteRequest::InsertText {
+                text: "x".to_string(),
+                buffer_id: Some(2),
+                position: None,
+            })
+        );
+        assert_eq!(
+            RemoteRequest::parse("listBuffers", Value::Null),
+            Ok(RemoteRequest::ListBuffers)
+        );
+        assert_eq!(
+            RemoteRequest::parse("quit", Value::Null).unwrap_err().code,
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            RemoteRequest::parse("gotoPosition", json!({ "line": "x" }))
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            RemoteRequest::parse("runCommand", json!({ "name": "save", "extra": 1 }))
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_serve_replies_to_requests_but_not_notifications() {
+        let (async_tx, async_rx) = mpsc::channel();
+        // Stand-in for the main loop
+        let editor = std::thread::spawn(move || {
+            while let Ok(AsyncMessage::RemoteRequest { request, response }) = async_rx.recv() {
+                let _ = response.send(match request {
+                    RemoteRequest::ListBuffers => Ok(json!([])),
+                    _ => Err(RemoteError::failed("nope")),
+                });
+            }
+        });
+
+        let input = concat!(
+            "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"listBuffers\"}\n",
+            "{\"jsonrpc\":\"2.0\",\"method\":\"gotoPosition\",\"params\":{\"line\":1}}\n",
+            "not json\n",
+            "{\"jsonrpc\":\"2.0\",\"id\":\"b\",\"method\":\"runCommand\",\"params\":{\"name\":\"save\"}}\n",
+        );
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, &async_tx);
+        drop(async_tx);
+        editor.join().unwrap();
+
+        let replies: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(replies.len(), 3);
+        assert_eq!(replies[0]["result"], json!([]));
+        assert_eq!(replies[1]["error"]["code"], json!(PARSE_ERROR));
+        assert_eq!(replies[2]["id"], json!("b"));
+        assert_eq!(replies[2]["error"]["message"], json!("nope"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_listen_leaves_other_files_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "keep me").unwrap();
+
+        let (async_tx, _async_rx) = mpsc::channel();
+        let err = listen(&path, async_tx).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+    }
+}
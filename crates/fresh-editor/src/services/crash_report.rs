@@ -0,0 +1,371 @@
+//! Crash reports
+//!
+//! When the editor panics, the panic hook writes a report to
+//! `{log_dir}/crashes/crash-{time}-{pid}/` and prints where it is:
+//!
+//! - `report.txt`: the panic message, where it happened and the backtrace
+//! - `events.log`: the last inputs, in the `--event-log` format, so
+//!   `fresh --replay` can play them again
+//! - `buffers.json`: the open buffers (names, sizes, state), without content
+//! - `log.txt`: the end of the editor's log
+//!
+//! The panic hook can't reach the editor, so the editor keeps its recent
+//! inputs and open buffers here as it runs. Pasted text is left out of the
+//! inputs. The next start tells the user where the report is.
+
+use crate::input::replay::{self, ReplayInput};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock, TryLockError};
+use std::time::{Duration, Instant};
+
+/// Number of recent inputs kept for a crash report
+const MAX_EVENTS: usize = 500;
+
+/// How much of the end of the log goes into a crash report
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+/// Number of crash reports kept; older ones are removed
+const MAX_REPORTS: usize = 10;
+
+/// File in the crash directory naming the report the next start mentions
+const LAST_CRASH_FILE: &str = "last-crash";
+
+/// An open buffer, as described in a crash report
+#[derive(Debug, Clone, Serialize)]
+pub struct BufferSummary {
+    pub id: usize,
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub language: String,
+    /// Size in bytes
+    pub size: usize,
+    /// Line count, if known
+    pub lines: Option<usize>,
+    pub encoding: String,
+    pub line_ending: String,
+    pub modified: bool,
+    /// Content dropped from memory by the memory budget
+    pub unloaded: bool,
+    pub read_only: bool,
+    pub active: bool,
+}
+
+/// What the editor was doing, kept up to date for the panic hook
+#[derive(Debug, Clone, Default)]
+struct CrashContext {
+    /// Terminal size
+    size: (u16, u16),
+    /// Recent inputs as event log lines, oldest first
+    events: VecDeque<String>,
+    buffers: Vec<BufferSummary>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    size: (0, 0),
+    events: VecDeque::new(),
+    buffers: Vec::new(),
+});
+
+/// When the first input was recorded; event times count from here
+static FIRST_INPUT: OnceLock<Instant> = OnceLock::new();
+
+fn lock_context() -> MutexGuard<'static, CrashContext> {
+    CONTEXT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Note an input for the next crash report
+pub fn record_input(input: &ReplayInput) {
+    let first = *FIRST_INPUT.get_or_init(Instant::now);
+    let Some(line) = event_line(input, first.elapsed()) else {
+        return;
+    };
+    let mut context = lock_context();
+    if context.events.len() == MAX_EVENTS {
+        context.events.pop_front();
+    }
+    context.events.push_back(line);
+}
+
+/// Note the terminal size and open buffers for the next crash report
+pub fn set_editor_state(size: (u16, u16), buffers: Vec<BufferSummary>) {
+    let mut context = lock_context();
+    context.size = size;
+    context.buffers = buffers;
+}
+
+/// The event log line for an input; pastes only give their length
+fn event_line(input: &ReplayInput, elapsed: Duration) -> Option<String> {
+    match input {
+        ReplayInput::Paste(text) => Some(format!(
+            "# paste of {} characters left out",
+            text.chars().count()
+        )),
+        _ => replay::encode_line(input, elapsed),
+    }
+}
+
+/// A panic, as described in a crash report
+#[derive(Debug, Clone)]
+pub struct Panic {
+    pub message: String,
+    /// File, line and column of the panic
+    pub location: Option<String>,
+    pub thread: String,
+    pub backtrace: String,
+}
+
+impl Panic {
+    /// Describe the panic being handled by the panic hook
+    pub fn capture(info: &std::panic::PanicHookInfo<'_>) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "(no message)".to_string());
+        Self {
+            message,
+            location: info.location().map(|l| l.to_string()),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("(unnamed)")
+                .to_string(),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        }
+    }
+}
+
+/// Directory crash reports are written to
+pub fn crash_dir() -> PathBuf {
+    super::log_dirs::log_dir().join("crashes")
+}
+
+/// Write a crash report for `panic`, with the end of `log_file`, and return
+/// its directory
+///
+/// Called from the panic hook. The next start mentions the report.
+pub fn write_report(panic: &Panic, log_file: &Path) -> io::Result<PathBuf> {
+    // The panic may have happened while the context was locked
+    let context = match CONTEXT.try_lock() {
+        Ok(context) => context.clone(),
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().clone(),
+        Err(TryLockError::WouldBlock) => CrashContext::default(),
+    };
+    let dir = crash_dir();
+    let report = write_report_in(&dir, panic, &context, log_file)?;
+    fs::write(
+        dir.join(LAST_CRASH_FILE),
+        report.to_string_lossy().as_bytes(),
+    )?;
+    remove_old_reports(&dir, MAX_REPORTS);
+    Ok(report)
+}
+
+/// The report of a crash since the last start that hasn't been mentioned yet
+pub fn take_last_crash() -> Option<PathBuf> {
+    let marker = crash_dir().join(LAST_CRASH_FILE);
+    let path = PathBuf::from(fs::read_to_string(&marker).ok()?.trim());
+    let _ = fs::remove_file(&marker);
+    path.is_dir().then_some(path)
+}
+
+fn write_report_in(
+    dir: &Path,
+    panic: &Panic,
+    context: &CrashContext,
+    log_file: &Path,
+) -> io::Result<PathBuf> {
+    let now = chrono::Local::now();
+    let report = dir.join(format!(
+        "crash-{}-{}",
+        now.format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    ));
+    fs::create_dir_all(&report)?;
+
+    fs::write(
+        report.join("report.txt"),
+        format!(
+            "Fresh {} crashed\n\
+             Time: {}\n\
+             OS: {} {}\n\
+             Thread: {}\n\
+             Location: {}\n\n\
+             {}\n\n\
+             Backtrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            now.to_rfc3339(),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            panic.thread,
+            panic.location.as_deref().unwrap_or("unknown"),
+            panic.message,
+            panic.backtrace
+        ),
+    )?;
+
+    let mut events = String::from("# Inputs before the crash, oldest first\n");
+    if context.size != (0, 0) {
+        events.push_str(&replay::session_line(context.size.0, context.size.1));
+        events.push('\n');
+    }
+    for line in &context.events {
+        events.push_str(line);
+        events.push('\n');
+    }
+    fs::write(report.join("events.log"), events)?;
+
+    let mut buffers = serde_json::to_string_pretty(&context.buffers).map_err(io::Error::other)?;
+    buffers.push('\n');
+    fs::write(report.join("buffers.json"), buffers)?;
+
+    // The log may be missing, e.g. when it couldn't be created
+    if let Ok(tail) = read_tail(log_file, LOG_TAIL_BYTES) {
+        fs::write(report.join("log.txt"), tail)?;
+    }
+    Ok(report)
+}
+
+/// The last `max_bytes` of a file, starting at a line
+fn read_tail(path: &Path, max_bytes: u64) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    if start == 0 {
+        return Ok(text.into_owned());
+    }
+    // Drop the partial first line
+    Ok(text
+        .split_once('\n')
+        .map_or(String::new(), |(_, rest)| rest.to_string()))
+}
+
+/// Remove all but the `keep` newest reports in `dir`
+fn remove_old_reports(dir: &Path, keep: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    // Names start with the time, so they sort oldest first
+    let mut reports: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("crash-"))
+        })
+        .collect();
+    reports.sort();
+    let excess = reports.len().saturating_sub(keep);
+    for old in &reports[..excess] {
+        let _ = fs::remove_dir_all(old);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::replay::ReplayLog;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn panic() -> Panic {
+        Panic {
+            message: "index out of bounds".to_string(),
+            location: Some("src/app/mod.rs:10:5".to_string()),
+            thread: "main".to_string(),
+            backtrace: "0: fresh::main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_describes_state_without_content() {
+        let temp = tempfile::tempdir().unwrap();
+        let log_file = temp.path().join("fresh.log");
+        fs::write(&log_file, "INFO Editor starting\n").unwrap();
+        let key = ReplayInput::Key(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        let context = CrashContext {
+            size: (80, 24),
+            events: [
+                event_line(&key, Duration::ZERO).unwrap(),
+                event_line(
+                    &ReplayInput::Paste("secret".to_string()),
+                    Duration::from_millis(5),
+                )
+                .unwrap(),
+            ]
+            .into(),
+            buffers: vec![BufferSummary {
+                id: 1,
+                name: "notes.txt".to_string(),
+                path: Some(PathBuf::from("/tmp/notes.txt")),
+                language: "text".to_string(),
+                size: 6,
+                lines: Some(1),
+                encoding: "UTF-8".to_string(),
+                line_ending: "LF".to_string(),
+                modified: true,
+                unloaded: false,
+                read_only: false,
+                active: true,
+            }],
+        };
+
+        let report =
+            write_report_in(&temp.path().join("crashes"), &panic(), &context, &log_file).unwrap();
+
+        let text = fs::read_to_string(report.join("report.txt")).unwrap();
+        assert!(text.contains("index out of bounds"));
+        assert!(text.contains("src/app/mod.rs:10:5"));
+        let events = fs::read_to_string(report.join("events.log")).unwrap();
+        assert!(!events.contains("secret"));
+        let replayed = ReplayLog::parse(&events);
+        assert_eq!(replayed.size, Some((80, 24)));
+        assert_eq!(replayed.steps.len(), 1);
+        assert_eq!(replayed.steps[0].input, key);
+        let buffers = fs::read_to_string(report.join("buffers.json")).unwrap();
+        assert!(buffers.contains("notes.txt"));
+        assert_eq!(
+            fs::read_to_string(report.join("log.txt")).unwrap(),
+            "INFO Editor starting\n"
+        );
+    }
+
+    #[test]
+    fn test_log_tail_starts_at_a_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let log_file = temp.path().join("fresh.log");
+        fs::write(&log_file, "first line\nsecond line\nthird\n").unwrap();
+        assert_eq!(read_tail(&log_file, 15).unwrap(), "third\n");
+        assert_eq!(
+            read_tail(&log_file, 1000).unwrap(),
+            "first line\nsecond line\nthird\n"
+        );
+    }
+
+    #[test]
+    fn test_old_reports_are_removed() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in ["crash-1", "crash-2", "crash-3"] {
+            fs::create_dir(temp.path().join(name)).unwrap();
+        }
+        fs::write(temp.path().join(LAST_CRASH_FILE), "crash-3").unwrap();
+
+        remove_old_reports(temp.path(), 2);
+
+        assert!(!temp.path().join("crash-1").exists());
+        assert!(temp.path().join("crash-2").exists());
+        assert!(temp.path().join("crash-3").exists());
+        assert!(temp.path().join(LAST_CRASH_FILE).exists());
+    }
+}
@@ -0,0 +1,323 @@
+//! Debug adapter process and message transport
+
+use crate::services::async_bridge::AsyncMessage;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc};
+use std::time::Duration;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::mpsc;
+
+/// How long an adapter gets to exit on its own after `disconnect`
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+
+/// Message from a debug adapter, forwarded to the main loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum DapMessage {
+    /// Response to a request sent with [`DapClient::request`]
+    Response {
+        request_seq: i64,
+        command: String,
+        success: bool,
+        /// Error message when `success` is false
+        message: Option<String>,
+        body: Value,
+    },
+    /// Event such as `stopped`, `output` or `terminated`
+    Event { event: String, body: Value },
+    /// A line the adapter wrote to stderr
+    Stderr(String),
+    /// The adapter exited (`error` is set if its output could not be read)
+    Exited { error: Option<String> },
+}
+
+enum Outgoing {
+    Message(Value),
+    Shutdown,
+}
+
+/// Handle to a running debug adapter
+///
+/// Dropping the handle stops the adapter.
+#[derive(Debug)]
+pub struct DapClient {
+    next_seq: Arc<AtomicI64>,
+    tx: mpsc::UnboundedSender<Outgoing>,
+}
+
+impl DapClient {
+    /// Start a debug adapter
+    ///
+    /// Messages from the adapter are sent as `AsyncMessage::Dap` tagged with
+    /// `session_id`.
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        session_id: u64,
+        command: &str,
+        args: &[String],
+        cwd: &Path,
+        async_tx: std_mpsc::Sender<AsyncMessage>,
+    ) -> Result<Self, String> {
+        // Spawning needs the runtime's reactor for the child's pipes
+        let _guard = runtime.enter();
+        let mut child = Command::new(command)
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start '{}': {}", command, e))?;
+
+        let stdin = child.stdin.take().ok_or("Failed to open adapter stdin")?;
+        let stdout = child.stdout.take().ok_or("Failed to open adapter stdout")?;
+        let stderr = child.stderr.take().ok_or("Failed to open adapter stderr")?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let next_seq = Arc::new(AtomicI64::new(1));
+
+        runtime.spawn(write_loop(child, stdin, rx));
+        runtime.spawn(read_loop(
+            BufReader::new(stdout),
+            session_id,
+            async_tx.clone(),
+            tx.clone(),
+            next_seq.clone(),
+        ));
+        runtime.spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let _ = async_tx.send(AsyncMessage::Dap {
+                    session_id,
+                    message: DapMessage::Stderr(line),
+                });
+            }
+        });
+
+        Ok(Self { next_seq, tx })
+    }
+
+    /// Send a request, returning its sequence number
+    ///
+    /// The matching [`DapMessage::Response`] carries it as `request_seq`.
+    pub fn request(&self, command: &str, arguments: Value) -> i64 {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        tracing::debug!("DAP request {} ({})", command, seq);
+        let _ = self.tx.send(Outgoing::Message(json!({
+            "seq": seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        })));
+        seq
+    }
+
+    /// Stop the adapter after the requests already sent have been written
+    pub fn shutdown(&self) {
+        let _ = self.tx.send(Outgoing::Shutdown);
+    }
+}
+
+impl Drop for DapClient {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+async fn write_loop(
+    mut child: Child,
+    mut stdin: ChildStdin,
+    mut rx: mpsc::UnboundedReceiver<Outgoing>,
+) {
+    while let Some(Outgoing::Message(message)) = rx.recv().await {
+        if let Err(e) = write_message(&mut stdin, &message).await {
+            tracing::warn!("Failed to write to debug adapter: {}", e);
+            break;
+        }
+    }
+    drop(stdin);
+    if tokio::time::timeout(SHUTDOWN_GRACE, child.wait())
+        .await
+        .is_err()
+    {
+        let _ = child.kill().await;
+    }
+}
+
+async fn read_loop<R: AsyncBufRead + Unpin>(
+    mut stdout: R,
+    session_id: u64,
+    async_tx: std_mpsc::Sender<AsyncMessage>,
+    tx: mpsc::UnboundedSender<Outgoing>,
+    next_seq: Arc<AtomicI64>,
+) {
+    loop {
+        let value = match read_message(&mut stdout).await {
+            Ok(Some(value)) => value,
+            Ok(None) => {
+                let _ = async_tx.send(AsyncMessage::Dap {
+                    session_id,
+                    message: DapMessage::Exited { error: None },
+                });
+                break;
+            }
+            Err(e) => {
+                let _ = async_tx.send(AsyncMessage::Dap {
+                    session_id,
+                    message: DapMessage::Exited { error: Some(e) },
+                });
+                break;
+            }
+        };
+
+        if value.get("type").and_then(Value::as_str) == Some("request") {
+            // Reverse requests (runInTerminal, startDebugging) are not supported
+            let command = value.get("command").cloned().unwrap_or(Value::Null);
+            tracing::debug!("Declining debug adapter request {}", command);
+            let _ = tx.send(Outgoing::Message(json!({
+                "seq": next_seq.fetch_add(1, Ordering::SeqCst),
+                "type": "response",
+                "request_seq": value.get("seq").cloned().unwrap_or(Value::Null),
+                "command": command,
+                "success": false,
+                "message": "Not supported",
+            })));
+            continue;
+        }
+
+        match parse_message(value) {
+            Some(message) => {
+                let _ = async_tx.send(AsyncMessage::Dap {
+                    session_id,
+                    message,
+                });
+            }
+            None => tracing::debug!("Ignoring malformed debug adapter message"),
+        }
+    }
+}
+
+/// Convert a protocol message into a [`DapMessage`]
+fn parse_message(value: Value) -> Option<DapMessage> {
+    let body = value.get("body").cloned().unwrap_or(Value::Null);
+    match value.get("type")?.as_str()? {
+        "response" => Some(DapMessage::Response {
+            request_seq: value.get("request_seq")?.as_i64()?,
+            command: value.get("command")?.as_str()?.to_string(),
+            success: value.get("success").and_then(Value::as_bool)?,
+            message: value
+                .get("message")
+                .and_then(Value::as_str)
+                .map(String::from),
+            body,
+        }),
+        "event" => Some(DapMessage::Event {
+            event: value.get("event")?.as_str()?.to_string(),
+            body,
+        }),
+        _ => None,
+    }
+}
+
+/// Read one message; `None` at end of output
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Value>, String> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read from debug adapter: {}", e))?;
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+        if line == "\r\n" {
+            break;
+        }
+        if let Some(len) = line.strip_prefix("Content-Length: ") {
+            content_length = Some(
+                len.trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
+            );
+        }
+    }
+
+    let content_length = content_length.ok_or("Missing Content-Length header")?;
+    let mut content = vec![0u8; content_length];
+    reader
+        .read_exact(&mut content)
+        .await
+        .map_err(|e| format!("Failed to read debug adapter message: {}", e))?;
+    tracing::trace!("DAP message: {}", String::from_utf8_lossy(&content));
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| format!("Invalid debug adapter message: {}", e))
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Value,
+) -> std::io::Result<()> {
+    let json = message.to_string();
+    let frame = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
+    writer.write_all(frame.as_bytes()).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_message_round_trip() {
+        let mut buffer = Vec::new();
+        let request = json!({"seq": 1, "type": "request", "command": "threads"});
+        write_message(&mut buffer, &request).await.unwrap();
+        write_message(&mut buffer, &json!({"seq": 2}))
+            .await
+            .unwrap();
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        assert_eq!(read_message(&mut reader).await.unwrap(), Some(request));
+        assert_eq!(
+            read_message(&mut reader).await.unwrap(),
+            Some(json!({"seq": 2}))
+        );
+        assert_eq!(read_message(&mut reader).await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_message() {
+        assert_eq!(
+            parse_message(json!({
+                "seq": 5, "type": "response", "request_seq": 2,
+                "command": "launch", "success": false, "message": "no program"
+            })),
+            Some(DapMessage::Response {
+                request_seq: 2,
+                command: "launch".to_string(),
+                success: false,
+                message: Some("no program".to_string()),
+                body: Value::Null,
+            })
+        );
+        assert_eq!(
+            parse_message(json!({
+                "seq": 6, "type": "event", "event": "stopped",
+                "body": {"reason": "breakpoint", "threadId": 1}
+            })),
+            Some(DapMessage::Event {
+                event: "stopped".to_string(),
+                body: json!({"reason": "breakpoint", "threadId": 1}),
+            })
+        );
+        assert_eq!(parse_message(json!({"seq": 7, "type": "event"})), None);
+    }
+}
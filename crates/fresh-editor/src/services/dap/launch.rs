@@ -0,0 +1,214 @@
+//! Debug configurations from `.fresh/launch.toml`
+//!
+//! ```toml
+//! [[configurations]]
+//! name = "Debug app"
+//! request = "launch"            # or "attach"
+//! adapter = { command = "lldb-dap", args = [] }
+//!
+//! # Passed to the adapter as the launch/attach arguments
+//! [configurations.arguments]
+//! program = "${workspaceFolder}/target/debug/app"
+//! cwd = "${workspaceFolder}"
+//! ```
+//!
+//! `${workspaceFolder}`, `${file}` and `${env:NAME}` are replaced in the
+//! adapter command, its arguments and every string in `arguments`.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Location of the launch configurations in a project
+pub fn launch_file_path(working_dir: &Path) -> PathBuf {
+    working_dir.join(".fresh").join("launch.toml")
+}
+
+/// Whether to start the program or attach to a running one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchRequest {
+    #[default]
+    Launch,
+    Attach,
+}
+
+impl LaunchRequest {
+    /// DAP request name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LaunchRequest::Launch => "launch",
+            LaunchRequest::Attach => "attach",
+        }
+    }
+}
+
+/// Debug adapter executable
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AdapterConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// One entry of `launch.toml`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LaunchConfig {
+    pub name: String,
+    #[serde(default)]
+    pub request: LaunchRequest,
+    pub adapter: AdapterConfig,
+    /// Adapter-specific launch/attach arguments
+    #[serde(default = "empty_arguments")]
+    pub arguments: Value,
+}
+
+fn empty_arguments() -> Value {
+    Value::Object(Default::default())
+}
+
+#[derive(Deserialize)]
+struct LaunchFile {
+    #[serde(default)]
+    configurations: Vec<LaunchConfig>,
+}
+
+/// Parse the contents of `launch.toml`
+pub fn parse_launch_configs(text: &str) -> Result<Vec<LaunchConfig>, String> {
+    toml::from_str::<LaunchFile>(text)
+        .map(|file| file.configurations)
+        .map_err(|e| e.to_string())
+}
+
+impl LaunchConfig {
+    /// Copy of this configuration with variables replaced
+    ///
+    /// `file` is the path of the active buffer, if any.
+    pub fn resolve(&self, working_dir: &Path, file: Option<&Path>) -> LaunchConfig {
+        let substitute = |text: &str| substitute_variables(text, working_dir, file);
+        LaunchConfig {
+            name: self.name.clone(),
+            request: self.request,
+            adapter: AdapterConfig {
+                command: substitute(&self.adapter.command),
+                args: self.adapter.args.iter().map(|a| substitute(a)).collect(),
+            },
+            arguments: substitute_in_value(&self.arguments, &substitute),
+        }
+    }
+}
+
+fn substitute_in_value(value: &Value, substitute: &dyn Fn(&str) -> String) -> Value {
+    match value {
+        Value::String(s) => Value::String(substitute(s)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute_in_value(item, substitute))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute_in_value(v, substitute)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn substitute_variables(text: &str, working_dir: &Path, file: Option<&Path>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &rest[start + 2..start + len];
+        match name {
+            "workspaceFolder" => result.push_str(&working_dir.to_string_lossy()),
+            "file" => result.push_str(&file.map(|f| f.to_string_lossy()).unwrap_or_default()),
+            _ => match name.strip_prefix("env:") {
+                Some(var) => result.push_str(&std::env::var(var).unwrap_or_default()),
+                // Leave unknown variables for the adapter
+                None => result.push_str(&rest[start..=start + len]),
+            },
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_launch_configs() {
+        let configs = parse_launch_configs(
+            r#"
+            [[configurations]]
+            name = "Debug app"
+            adapter = { command = "lldb-dap" }
+
+            [configurations.arguments]
+            program = "${workspaceFolder}/target/debug/app"
+            args = ["--verbose"]
+            stopOnEntry = true
+
+            [[configurations]]
+            name = "Attach"
+            request = "attach"
+            adapter = { command = "python3", args = ["-m", "debugpy.adapter"] }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].request, LaunchRequest::Launch);
+        assert_eq!(
+            configs[0].arguments,
+            json!({
+                "program": "${workspaceFolder}/target/debug/app",
+                "args": ["--verbose"],
+                "stopOnEntry": true,
+            })
+        );
+        assert_eq!(configs[1].request, LaunchRequest::Attach);
+        assert_eq!(configs[1].adapter.args, vec!["-m", "debugpy.adapter"]);
+        assert_eq!(configs[1].arguments, json!({}));
+
+        assert!(parse_launch_configs("[[configurations]]\nname = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_resolve_variables() {
+        let config = LaunchConfig {
+            name: "test".to_string(),
+            request: LaunchRequest::Launch,
+            adapter: AdapterConfig {
+                command: "${workspaceFolder}/adapter".to_string(),
+                args: vec![],
+            },
+            arguments: json!({
+                "program": "${file}",
+                "args": ["${workspaceFolder}", "${unknown}", "${unclosed"],
+                "port": 5678,
+            }),
+        };
+
+        let resolved = config.resolve(Path::new("/project"), Some(Path::new("/project/main.py")));
+        assert_eq!(resolved.adapter.command, "/project/adapter");
+        assert_eq!(
+            resolved.arguments,
+            json!({
+                "program": "/project/main.py",
+                "args": ["/project", "${unknown}", "${unclosed"],
+                "port": 5678,
+            })
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! DAP (Debug Adapter Protocol) Client
+//!
+//! Debug adapters (lldb-dap, debugpy, delve, ...) run as subprocesses and
+//! exchange `Content-Length` framed JSON messages with the editor over
+//! stdin/stdout, the same transport the LSP client uses.
+//!
+//! # Module Structure
+//!
+//! - **`client`**: [`DapClient`] - Handle to a running adapter. Requests are
+//!   written by a tokio task; responses, events and stderr output are sent to
+//!   the main loop as `AsyncMessage::Dap`. Requests from the adapter
+//!   (`runInTerminal`, `startDebugging`) are answered as unsupported.
+//!
+//! - **`launch`**: [`LaunchConfig`] - Launch and attach configurations read
+//!   from `.fresh/launch.toml` in the project root.
+//!
+//! The session itself (breakpoints, stepping, the variables panel and the
+//! debug console) is driven by the editor in `app::debugger`.
+
+pub mod client;
+pub mod launch;
+
+pub use client::{DapClient, DapMessage};
+pub use launch::{AdapterConfig, LaunchConfig, LaunchRequest};
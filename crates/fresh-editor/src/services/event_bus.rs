@@ -0,0 +1,284 @@
+//! Event bus for the main loop
+//!
+//! The main loop sleeps until something happens: a terminal event, an async
+//! message (LSP, file watcher, plugins, git, background jobs), or its next
+//! timer. Terminal input is read on its own thread and the async bridge wakes
+//! the loop when a message arrives, so both come in through one channel and
+//! nothing waits for a polling interval.
+//!
+//! Code that hands the terminal to another program (e.g. `sudo` asking for a
+//! password) must hold the input with [`hold_terminal_input`] first, so the
+//! input thread does not read the keys meant for it.
+
+use crossterm::event::Event;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// How long the input thread waits for a terminal event before checking
+/// whether it should stop or pause
+const INPUT_POLL: Duration = Duration::from_millis(20);
+
+/// Something the main loop has to handle
+#[derive(Debug)]
+pub enum LoopEvent {
+    /// An event read from the terminal
+    Input(Event),
+    /// Reading the terminal failed
+    InputFailed(anyhow::Error),
+    /// Async messages are waiting in the async bridge
+    Wake,
+}
+
+/// Channel the main loop waits on
+pub struct EventBus {
+    sender: Sender<LoopEvent>,
+    receiver: Receiver<LoopEvent>,
+    /// Whether a `Wake` is already queued; bursts of async messages only
+    /// queue one
+    wake_queued: Arc<AtomicBool>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            wake_queued: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Sender for the terminal input thread
+    pub fn input_sender(&self) -> Sender<LoopEvent> {
+        self.sender.clone()
+    }
+
+    /// Handle for waking the main loop when async messages arrive
+    pub fn waker(&self) -> Waker {
+        Waker {
+            sender: self.sender.clone(),
+            wake_queued: Arc::clone(&self.wake_queued),
+        }
+    }
+
+    /// Wait up to `timeout` for the next event
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<LoopEvent> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => Some(self.received(event)),
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// The next event, if one is already queued
+    pub fn try_recv(&self) -> Option<LoopEvent> {
+        self.receiver
+            .try_recv()
+            .ok()
+            .map(|event| self.received(event))
+    }
+
+    fn received(&self, event: LoopEvent) -> LoopEvent {
+        if matches!(event, LoopEvent::Wake) {
+            self.wake_queued.store(false, Ordering::Release);
+        }
+        event
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wakes the main loop so it drains the async bridge and plugin commands
+#[derive(Clone)]
+pub struct Waker {
+    sender: Sender<LoopEvent>,
+    wake_queued: Arc<AtomicBool>,
+}
+
+impl Waker {
+    pub fn wake(&self) {
+        if !self.wake_queued.swap(true, Ordering::AcqRel) {
+            let _ = self.sender.send(LoopEvent::Wake);
+        }
+    }
+}
+
+/// Have a thread move everything sent to `receiver` to a new channel that
+/// replaces it, calling `wake` after each item
+///
+/// This lets a loop that waits on other events be woken when something is
+/// sent, without changing the senders.
+pub fn wake_on_receive<T: Send + 'static>(
+    receiver: &mut Receiver<T>,
+    wake: impl Fn() + Send + 'static,
+) {
+    let (forward, forwarded) = mpsc::channel();
+    // The thread gets the incoming channel only once it is running, so
+    // nothing is lost if it can't be started
+    let (hand_over, incoming) = mpsc::channel::<Receiver<T>>();
+    let spawned = std::thread::Builder::new()
+        .name("event-bus-wake".to_string())
+        .spawn(move || {
+            let Ok(incoming) = incoming.recv() else {
+                return;
+            };
+            while let Ok(item) = incoming.recv() {
+                if forward.send(item).is_err() {
+                    break;
+                }
+                wake();
+            }
+        });
+    match spawned {
+        Ok(_) => {
+            let _ = hand_over.send(std::mem::replace(receiver, forwarded));
+        }
+        Err(e) => tracing::error!("Failed to start event bus wake thread: {}", e),
+    }
+}
+
+/// Read terminal events with `poll` and send them to the main loop, until
+/// `stop` is set or the main loop is gone
+///
+/// `poll` waits up to the given time for an event. Reading pauses while the
+/// input is held.
+pub fn read_terminal_input<F>(mut poll: F, sender: Sender<LoopEvent>, stop: &AtomicBool)
+where
+    F: FnMut(Duration) -> anyhow::Result<Option<Event>>,
+{
+    while !stop.load(Ordering::Acquire) {
+        {
+            let mut gate = lock_gate();
+            if gate.holds > 0 {
+                // Check `stop` again now and then while held
+                drop(GATE_CHANGED.wait_timeout(gate, INPUT_POLL));
+                continue;
+            }
+            gate.reading = true;
+        }
+        let result = poll(INPUT_POLL);
+        lock_gate().reading = false;
+        GATE_CHANGED.notify_all();
+
+        let event = match result {
+            Ok(Some(event)) => LoopEvent::Input(event),
+            Ok(None) => continue,
+            Err(e) => LoopEvent::InputFailed(e),
+        };
+        let failed = matches!(event, LoopEvent::InputFailed(_));
+        if sender.send(event).is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Whether terminal input may be read
+struct InputGate {
+    /// Number of live `InputHold`s
+    holds: usize,
+    /// Whether the input thread is waiting for an event right now
+    reading: bool,
+}
+
+static GATE: Mutex<InputGate> = Mutex::new(InputGate {
+    holds: 0,
+    reading: false,
+});
+static GATE_CHANGED: Condvar = Condvar::new();
+
+fn lock_gate() -> std::sync::MutexGuard<'static, InputGate> {
+    GATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Stops the input thread from reading the terminal while it lives
+#[must_use = "input is only held while the hold is alive"]
+pub struct InputHold(());
+
+/// Stop reading terminal input, e.g. while another program reads from the
+/// terminal. Waits for a read in progress to finish.
+pub fn hold_terminal_input() -> InputHold {
+    let mut gate = lock_gate();
+    gate.holds += 1;
+    while gate.reading {
+        gate = GATE_CHANGED
+            .wait(gate)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+    InputHold(())
+}
+
+impl Drop for InputHold {
+    fn drop(&mut self) {
+        lock_gate().holds -= 1;
+        GATE_CHANGED.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_wakes_are_coalesced() {
+        let bus = EventBus::new();
+        let waker = bus.waker();
+        waker.wake();
+        waker.clone().wake();
+        assert!(matches!(bus.try_recv(), Some(LoopEvent::Wake)));
+        assert!(bus.try_recv().is_none());
+
+        // Once the loop has seen the wake, the next message wakes it again
+        waker.wake();
+        assert!(matches!(bus.try_recv(), Some(LoopEvent::Wake)));
+    }
+
+    #[test]
+    fn test_input_is_forwarded_until_stopped() {
+        let bus = EventBus::new();
+        let stop = AtomicBool::new(false);
+        let mut keys = vec![key('a'), key('b')].into_iter();
+        std::thread::scope(|s| {
+            let sender = bus.input_sender();
+            let stop = &stop;
+            s.spawn(move || read_terminal_input(|_| Ok(keys.next()), sender, stop));
+
+            for expected in ['a', 'b'] {
+                match bus.recv_timeout(Duration::from_secs(5)) {
+                    Some(LoopEvent::Input(event)) => assert_eq!(event, key(expected)),
+                    other => panic!("expected a key, got {:?}", other),
+                }
+            }
+            stop.store(true, Ordering::Release);
+        });
+    }
+
+    #[test]
+    fn test_held_input_is_not_read() {
+        let bus = EventBus::new();
+        let stop = AtomicBool::new(false);
+        let hold = hold_terminal_input();
+        std::thread::scope(|s| {
+            let sender = bus.input_sender();
+            let stop = &stop;
+            s.spawn(move || read_terminal_input(|_| Ok(Some(key('x'))), sender, stop));
+
+            assert!(bus.recv_timeout(Duration::from_millis(100)).is_none());
+            drop(hold);
+            assert!(matches!(
+                bus.recv_timeout(Duration::from_secs(5)),
+                Some(LoopEvent::Input(_))
+            ));
+            stop.store(true, Ordering::Release);
+        });
+    }
+}
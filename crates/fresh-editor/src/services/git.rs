@@ -0,0 +1,422 @@
+//! Background git integration
+//!
+//! All git invocations run as child processes in background jobs and report
+//! back through the async bridge, so the UI thread never waits on git. The
+//! pool caps how many git processes run at once, and status refreshes are
+//! coalesced so a slow repository cannot queue up a backlog of polls.
+
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::jobs::JobPool;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+/// Maximum number of git processes running at the same time
+pub const DEFAULT_MAX_GIT_PROCESSES: usize = 4;
+
+/// Number of commits shown in the log viewer
+const LOG_LIMIT: usize = 500;
+
+/// Branch and working tree state of a repository
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GitStatus {
+    /// Current branch, or the short commit hash when HEAD is detached
+    pub branch: String,
+    /// Whether there are staged, unstaged or untracked changes
+    pub dirty: bool,
+    /// Commits the branch is ahead of its upstream
+    pub ahead: usize,
+    /// Commits the branch is behind its upstream
+    pub behind: usize,
+}
+
+impl GitStatus {
+    /// Compact form for the status bar, e.g. `main* ↑1 ↓2`
+    pub fn summary(&self) -> String {
+        let mut text = self.branch.clone();
+        if self.dirty {
+            text.push('*');
+        }
+        if self.ahead > 0 {
+            text.push_str(&format!(" ↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            text.push_str(&format!(" ↓{}", self.behind));
+        }
+        text
+    }
+}
+
+/// A branch offered by the checkout picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitBranch {
+    /// Short name, e.g. `main` or `origin/main`
+    pub name: String,
+    /// Whether this is a remote-tracking branch
+    pub remote: bool,
+}
+
+impl GitBranch {
+    /// Name to pass to `git checkout`. Remote branches use the name without
+    /// the remote, so git creates (or switches to) a local tracking branch
+    /// instead of detaching HEAD.
+    pub fn checkout_name(&self) -> &str {
+        if self.remote {
+            self.name
+                .split_once('/')
+                .map_or(self.name.as_str(), |(_, b)| b)
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// A long-running git command started by the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitOperation {
+    Fetch,
+    Pull,
+    Push,
+    Checkout(String),
+}
+
+impl GitOperation {
+    /// Short description for status messages, e.g. `pull` or `checkout main`
+    pub fn label(&self) -> String {
+        match self {
+            GitOperation::Fetch => "fetch".to_string(),
+            GitOperation::Pull => "pull".to_string(),
+            GitOperation::Push => "push".to_string(),
+            GitOperation::Checkout(branch) => format!("checkout {}", branch),
+        }
+    }
+
+    fn args(&self) -> Vec<String> {
+        match self {
+            GitOperation::Fetch => vec!["fetch".into(), "--progress".into()],
+            GitOperation::Pull => vec!["pull".into(), "--progress".into()],
+            GitOperation::Push => vec!["push".into(), "--progress".into()],
+            GitOperation::Checkout(branch) => vec!["checkout".into(), branch.clone()],
+        }
+    }
+}
+
+/// Results reported back to the editor
+#[derive(Debug)]
+pub enum GitEvent {
+    /// Fresh status (None when the directory is not inside a repository)
+    Status(Option<GitStatus>),
+    /// Local and remote-tracking branches, most recently committed first
+    Branches(Result<Vec<GitBranch>, String>),
+    /// A progress line from a running operation
+    Progress {
+        operation: GitOperation,
+        message: String,
+    },
+    /// A running operation completed
+    Finished {
+        operation: GitOperation,
+        result: Result<(), String>,
+    },
+    /// Formatted history for the log viewer
+    Log(Result<String, String>),
+}
+
+/// Runs git commands in the background with bounded concurrency
+pub struct GitPool {
+    permits: Arc<Semaphore>,
+    status_in_flight: Arc<AtomicBool>,
+}
+
+impl GitPool {
+    pub fn new(max_processes: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_processes.max(1))),
+            status_in_flight: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Refresh branch and dirty state; skipped if a refresh is still running
+    pub fn refresh_status(
+        &self,
+        jobs: &mut JobPool,
+        sender: mpsc::Sender<AsyncMessage>,
+        dir: PathBuf,
+    ) {
+        if self.status_in_flight.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let permits = Arc::clone(&self.permits);
+        let in_flight = Arc::clone(&self.status_in_flight);
+        jobs.spawn(None, |_| async move {
+            let _permit = permits.acquire_owned().await;
+            let status = run_git(&dir, &["status", "--porcelain=v2", "--branch"])
+                .await
+                .ok()
+                .and_then(|out| parse_status(&out));
+            in_flight.store(false, Ordering::Release);
+            let _ = sender.send(AsyncMessage::Git(GitEvent::Status(status)));
+        });
+    }
+
+    /// List local and remote-tracking branches
+    pub fn list_branches(
+        &self,
+        jobs: &mut JobPool,
+        sender: mpsc::Sender<AsyncMessage>,
+        dir: PathBuf,
+    ) {
+        let permits = Arc::clone(&self.permits);
+        jobs.spawn(None, |_| async move {
+            let _permit = permits.acquire_owned().await;
+            let result = run_git(
+                &dir,
+                &[
+                    "for-each-ref",
+                    "--sort=-committerdate",
+                    "--format=%(refname)",
+                    "refs/heads",
+                    "refs/remotes",
+                ],
+            )
+            .await
+            .map(|out| parse_branches(&out));
+            let _ = sender.send(AsyncMessage::Git(GitEvent::Branches(result)));
+        });
+    }
+
+    /// Load recent history for the log viewer
+    pub fn log(&self, jobs: &mut JobPool, sender: mpsc::Sender<AsyncMessage>, dir: PathBuf) {
+        let permits = Arc::clone(&self.permits);
+        jobs.spawn(None, |_| async move {
+            let _permit = permits.acquire_owned().await;
+            let limit = format!("-n{}", LOG_LIMIT);
+            let result = run_git(
+                &dir,
+                &[
+                    "log",
+                    "--graph",
+                    "--decorate",
+                    "--date=short",
+                    "--format=%h %ad %an%d %s",
+                    &limit,
+                ],
+            )
+            .await;
+            let _ = sender.send(AsyncMessage::Git(GitEvent::Log(result)));
+        });
+    }
+
+    /// Run a fetch/pull/push/checkout, streaming its progress output
+    pub fn run(
+        &self,
+        jobs: &mut JobPool,
+        sender: mpsc::Sender<AsyncMessage>,
+        dir: PathBuf,
+        operation: GitOperation,
+    ) {
+        let permits = Arc::clone(&self.permits);
+        jobs.spawn(None, |_| async move {
+            let _permit = permits.acquire_owned().await;
+            let result = run_with_progress(&dir, &operation, &sender).await;
+            let _ = sender.send(AsyncMessage::Git(GitEvent::Finished { operation, result }));
+        });
+    }
+}
+
+impl Default for GitPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_GIT_PROCESSES)
+    }
+}
+
+/// Build a git command that can never stop to ask for input
+fn git_command(dir: &PathBuf) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    cmd
+}
+
+/// Run git to completion and return stdout, or the first line of stderr on failure
+async fn run_git(dir: &PathBuf, args: &[&str]) -> Result<String, String> {
+    let output = git_command(dir)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(first_line(&String::from_utf8_lossy(&output.stderr)))
+    }
+}
+
+async fn run_with_progress(
+    dir: &PathBuf,
+    operation: &GitOperation,
+    sender: &mpsc::Sender<AsyncMessage>,
+) -> Result<(), String> {
+    let mut child = git_command(dir)
+        .args(operation.args())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // git writes progress to stderr, redrawing lines with '\r'
+    let mut stderr = child.stderr.take().ok_or("no stderr")?;
+    let mut buf = [0u8; 1024];
+    let mut pending = String::new();
+    let mut last_line = String::new();
+    loop {
+        let n = stderr.read(&mut buf).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        pending.push_str(&String::from_utf8_lossy(&buf[..n]));
+        while let Some(pos) = pending.find(['\r', '\n']) {
+            let line = pending[..pos].trim().to_string();
+            pending.drain(..=pos);
+            if !line.is_empty() {
+                let _ = sender.send(AsyncMessage::Git(GitEvent::Progress {
+                    operation: operation.clone(),
+                    message: line.clone(),
+                }));
+                last_line = line;
+            }
+        }
+    }
+    if !pending.trim().is_empty() {
+        last_line = pending.trim().to_string();
+    }
+
+    let status = child.wait().await.map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(last_line)
+    }
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Parse `git status --porcelain=v2 --branch` output
+pub fn parse_status(output: &str) -> Option<GitStatus> {
+    let mut status = GitStatus::default();
+    let mut oid = None;
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let mut parts = header.split_whitespace();
+            match parts.next() {
+                Some("branch.oid") => oid = parts.next().map(str::to_string),
+                Some("branch.head") => status.branch = parts.next()?.to_string(),
+                Some("branch.ab") => {
+                    for part in parts {
+                        if let Some(n) = part.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if !line.is_empty() {
+            // Changed ("1", "2"), unmerged ("u") and untracked ("?") entries
+            status.dirty = true;
+        }
+    }
+
+    if status.branch == "(detached)" {
+        status.branch = oid
+            .filter(|oid| oid != "(initial)")
+            .map(|oid| oid.chars().take(7).collect())
+            .unwrap_or_else(|| "HEAD".to_string());
+    }
+    (!status.branch.is_empty()).then_some(status)
+}
+
+/// Parse `git for-each-ref --format=%(refname)` output
+fn parse_branches(output: &str) -> Vec<GitBranch> {
+    output
+        .lines()
+        .map(str::trim)
+        // refs/remotes/<remote>/HEAD is a symbolic ref, not a branch to check out
+        .filter(|refname| !refname.ends_with("/HEAD"))
+        .filter_map(|refname| {
+            if let Some(name) = refname.strip_prefix("refs/heads/") {
+                Some(GitBranch {
+                    name: name.to_string(),
+                    remote: false,
+                })
+            } else {
+                refname.strip_prefix("refs/remotes/").map(|name| GitBranch {
+                    name: name.to_string(),
+                    remote: true,
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_clean_branch() {
+        let output = "# branch.oid 1234567890abcdef\n# branch.head main\n";
+        let status = parse_status(output).unwrap();
+        assert_eq!(status.branch, "main");
+        assert!(!status.dirty);
+        assert_eq!(status.summary(), "main");
+    }
+
+    #[test]
+    fn test_parse_status_dirty_with_upstream() {
+        let output = "# branch.oid 1234567890abcdef\n\
+                      # branch.head feature\n\
+                      # branch.upstream origin/feature\n\
+                      # branch.ab +2 -1\n\
+                      1 .M N... 100644 100644 100644 abc abc src/lib.rs\n\
+                      ? notes.txt\n";
+        let status = parse_status(output).unwrap();
+        assert_eq!(status.branch, "feature");
+        assert!(status.dirty);
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert_eq!(status.summary(), "feature* ↑2 ↓1");
+    }
+
+    #[test]
+    fn test_parse_status_detached_head() {
+        let output = "# branch.oid 1234567890abcdef\n# branch.head (detached)\n";
+        assert_eq!(parse_status(output).unwrap().branch, "1234567");
+    }
+
+    #[test]
+    fn test_parse_status_not_a_repository() {
+        assert_eq!(parse_status(""), None);
+    }
+
+    #[test]
+    fn test_parse_branches_skips_remote_head() {
+        let output = "refs/heads/main\n\
+                      refs/heads/feature/x\n\
+                      refs/remotes/origin/HEAD\n\
+                      refs/remotes/origin/feature/y\n";
+        let branches = parse_branches(output);
+        let names: Vec<_> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "feature/x", "origin/feature/y"]);
+        assert_eq!(branches[1].checkout_name(), "feature/x");
+        assert_eq!(branches[2].checkout_name(), "feature/y");
+    }
+}
@@ -0,0 +1,222 @@
+//! Health check report
+//!
+//! The editor's `Check Health` command and `fresh --health` run the same
+//! checks (see `app::health`): terminal capabilities, config files, the
+//! language servers and formatters the config refers to, and plugins. This
+//! module holds the report, its plain text layout, and the lookups shared by
+//! the checks.
+
+use std::path::{Path, PathBuf};
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthStatus {
+    Ok,
+    /// Worth knowing, nothing to fix (e.g. a server that isn't started)
+    Info,
+    /// Something doesn't work as configured
+    Warning,
+    /// Something is broken
+    Error,
+}
+
+impl HealthStatus {
+    fn marker(self) -> &'static str {
+        match self {
+            HealthStatus::Ok => "[ok]",
+            HealthStatus::Info => "[info]",
+            HealthStatus::Warning => "[warn]",
+            HealthStatus::Error => "[error]",
+        }
+    }
+}
+
+/// One line of the report
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub status: HealthStatus,
+    /// What was checked, e.g. `Colors` or `rust-analyzer`
+    pub label: String,
+    /// What was found
+    pub detail: String,
+    /// How to fix it, for anything not ok
+    pub fix: Option<String>,
+}
+
+impl HealthCheck {
+    pub fn new(status: HealthStatus, label: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            label: label.into(),
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    pub fn ok(label: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(HealthStatus::Ok, label, detail)
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Checks of one area, e.g. the terminal
+#[derive(Debug, Clone)]
+pub struct HealthSection {
+    pub title: String,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthSection {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            checks: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, check: HealthCheck) {
+        self.checks.push(check);
+    }
+}
+
+/// All checks, by area
+#[derive(Debug, Clone, Default)]
+pub struct HealthReport {
+    pub sections: Vec<HealthSection>,
+}
+
+impl HealthReport {
+    /// Number of checks with `status`
+    pub fn count(&self, status: HealthStatus) -> usize {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.checks)
+            .filter(|check| check.status == status)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(HealthStatus::Error) > 0
+    }
+
+    /// Plain text layout used by the `*Health*` buffer and `fresh --health`
+    pub fn to_text(&self) -> String {
+        let errors = self.count(HealthStatus::Error);
+        let warnings = self.count(HealthStatus::Warning);
+        let mut out = format!(
+            "Health check: {} error{}, {} warning{}\n\n",
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        );
+        let label_width = self
+            .sections
+            .iter()
+            .flat_map(|section| &section.checks)
+            .map(|check| check.label.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(32);
+        for section in &self.sections {
+            out.push_str(&format!("── {} ──\n\n", section.title));
+            for check in &section.checks {
+                out.push_str(&format!(
+                    "  {:7} {:width$}  {}\n",
+                    check.status.marker(),
+                    check.label,
+                    check.detail,
+                    width = label_width
+                ));
+                if let Some(fix) = &check.fix {
+                    out.push_str(&format!(
+                        "  {:7} {:width$}  fix: {}\n",
+                        "",
+                        "",
+                        fix,
+                        width = label_width
+                    ));
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Where `command` would be run from: a path if it contains a separator,
+/// otherwise the first match on `PATH`
+pub fn find_on_path(command: &str) -> Option<PathBuf> {
+    if command.is_empty() {
+        return None;
+    }
+    if command.contains('/') || command.contains('\\') {
+        let path = Path::new(command);
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let path_var = std::env::var_os("PATH")?;
+    for dir in std::env::split_paths(&path_var) {
+        let candidate = dir.join(command);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        #[cfg(windows)]
+        {
+            let with_exe = dir.join(format!("{}.exe", command));
+            if with_exe.is_file() {
+                return Some(with_exe);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_counts_and_text() {
+        let mut terminal = HealthSection::new("Terminal");
+        terminal.push(HealthCheck::ok("Colors", "truecolor"));
+        terminal.push(
+            HealthCheck::new(HealthStatus::Warning, "Clipboard", "no system clipboard")
+                .with_fix("Enable clipboard.use_osc52"),
+        );
+        let mut config = HealthSection::new("Configuration");
+        config.push(HealthCheck::new(
+            HealthStatus::Error,
+            "User config",
+            "parse error",
+        ));
+        let report = HealthReport {
+            sections: vec![terminal, config],
+        };
+
+        assert_eq!(report.count(HealthStatus::Warning), 1);
+        assert!(report.has_errors());
+        let text = report.to_text();
+        assert!(text.starts_with("Health check: 1 error, 1 warning\n"));
+        assert!(text.contains("── Terminal ──"));
+        assert!(text.contains("  [warn]  Clipboard    no system clipboard\n"));
+        assert!(text.contains("fix: Enable clipboard.use_osc52\n"));
+    }
+
+    #[test]
+    fn test_find_on_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = dir.path().join("some-tool");
+        std::fs::write(&tool, "").unwrap();
+        assert_eq!(find_on_path(tool.to_str().unwrap()), Some(tool));
+        assert_eq!(
+            find_on_path(dir.path().join("missing").to_str().unwrap()),
+            None
+        );
+        assert_eq!(find_on_path("fresh-no-such-command-on-path"), None);
+        assert_eq!(find_on_path(""), None);
+    }
+}
@@ -0,0 +1,233 @@
+//! What the editor leaves out of a project's file listings
+//!
+//! One set of rules decides which files the file explorer hides, which files
+//! Quick Open lists, which files project search looks at and which
+//! directories are watched for changes:
+//!
+//! - `.gitignore` and `.ignore` files (an `.ignore` rule wins over a
+//!   `.gitignore` rule in the same directory, and deeper directories win over
+//!   shallower ones), honored outside git repositories too
+//! - the repository's `.git/info/exclude` and the global git excludes file
+//!   (`core.excludesFile`, by default `~/.config/git/ignore`)
+//! - the `files.exclude` globs of the config, in gitignore syntax
+//!
+//! The gitignore-style rules can be shown anyway in the file explorer;
+//! `files.exclude` always applies.
+
+use ignore::gitignore::{gitconfig_excludes_path, Gitignore, GitignoreBuilder};
+use ignore::WalkBuilder;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Ignore files read in every directory, in increasing precedence
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Ignore rules of a project directory
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    root: PathBuf,
+    /// `files.exclude` globs
+    exclude: Gitignore,
+    /// Global git excludes and the repository's `.git/info/exclude`
+    global: Gitignore,
+    /// `.gitignore` and `.ignore` rules of the directories loaded so far
+    dirs: HashMap<PathBuf, Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Rules for the project at `root`, leaving out the `exclude` globs
+    ///
+    /// Only the root directory's ignore files are loaded; see `load_dir`.
+    pub fn new(root: &Path, exclude: &[String]) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+        for glob in exclude {
+            if let Err(e) = builder.add_line(None, glob) {
+                tracing::warn!("Invalid files.exclude pattern {:?}: {}", glob, e);
+            }
+        }
+        let exclude = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to build files.exclude patterns: {}", e);
+            Gitignore::empty()
+        });
+
+        let mut builder = GitignoreBuilder::new(root);
+        if let Some(path) = gitconfig_excludes_path() {
+            Self::add_file(&mut builder, &path);
+        }
+        Self::add_file(&mut builder, &root.join(".git/info/exclude"));
+        let global = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load global git excludes: {}", e);
+            Gitignore::empty()
+        });
+
+        let mut rules = Self {
+            root: root.to_path_buf(),
+            exclude,
+            global,
+            dirs: HashMap::new(),
+        };
+        rules.load_dir(root);
+        rules
+    }
+
+    fn add_file(builder: &mut GitignoreBuilder, path: &Path) {
+        if path.is_file() {
+            if let Some(e) = builder.add(path) {
+                tracing::warn!("Failed to read ignore file {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Directory the rules apply to
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Load (or reload) the ignore files of `dir`
+    ///
+    /// Rules of a directory only apply once it has been loaded, so load each
+    /// directory before asking about its entries.
+    pub fn load_dir(&mut self, dir: &Path) {
+        let mut builder = GitignoreBuilder::new(dir);
+        for name in IGNORE_FILES {
+            Self::add_file(&mut builder, &dir.join(name));
+        }
+        match builder.build() {
+            Ok(rules) if !rules.is_empty() => {
+                self.dirs.insert(dir.to_path_buf(), rules);
+            }
+            Ok(_) => {
+                self.dirs.remove(dir);
+            }
+            Err(e) => tracing::warn!("Failed to load ignore files of {:?}: {}", dir, e),
+        }
+    }
+
+    /// Number of directories with ignore files loaded
+    pub fn loaded_dir_count(&self) -> usize {
+        self.dirs.len()
+    }
+
+    /// Whether `path` (or a directory it is in) matches a `files.exclude` glob
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        if path.starts_with(&self.root) {
+            self.exclude
+                .matched_path_or_any_parents(path, is_dir)
+                .is_ignore()
+        } else {
+            self.exclude.matched(path, is_dir).is_ignore()
+        }
+    }
+
+    /// Whether `path` is left out by `.gitignore`, `.ignore` or the git excludes
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        // The deepest directory with a rule about the path decides
+        let mut dirs: Vec<(&PathBuf, &Gitignore)> = self
+            .dirs
+            .iter()
+            .filter(|(dir, _)| path.starts_with(dir) && path != dir.as_path())
+            .collect();
+        dirs.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.components().count()));
+        for (_, rules) in dirs {
+            let matched = rules.matched(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        path.starts_with(&self.root) && self.global.matched(path, is_dir).is_ignore()
+    }
+
+    /// A walker over the files under the root that follows these rules
+    ///
+    /// Hidden files are skipped; call `hidden(false)` on the walker to list
+    /// them too (`.git` directories stay out).
+    pub fn walker(&self) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(&self.root);
+        let exclude = self.exclude.clone();
+        builder
+            // Honor .gitignore files in directories that aren't git repositories too
+            .require_git(false)
+            .git_global(true)
+            .git_exclude(true)
+            .filter_entry(move |entry| {
+                if entry.file_name() == ".git" {
+                    return false;
+                }
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                !exclude.matched(entry.path(), is_dir).is_ignore()
+            });
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn walk(rules: &IgnoreRules) -> Vec<String> {
+        let mut files: Vec<String> = rules
+            .walker()
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+            .map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(rules.root())
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_ignore_files_and_excludes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+        fs::write(root.join(".ignore"), "!keep.log\n").unwrap();
+        fs::create_dir_all(root.join("build")).unwrap();
+        fs::create_dir_all(root.join("src/generated")).unwrap();
+        fs::write(root.join("build/out.txt"), "").unwrap();
+        fs::write(root.join("debug.log"), "").unwrap();
+        fs::write(root.join("keep.log"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+        fs::write(root.join("src/generated/api.rs"), "").unwrap();
+
+        let rules = IgnoreRules::new(root, &["generated/".to_string()]);
+        assert!(rules.is_ignored(&root.join("debug.log"), false));
+        assert!(!rules.is_ignored(&root.join("keep.log"), false));
+        assert!(rules.is_ignored(&root.join("build"), true));
+        assert!(!rules.is_ignored(&root.join("src/main.rs"), false));
+        assert!(rules.is_excluded(&root.join("src/generated"), true));
+        assert!(rules.is_excluded(&root.join("src/generated/api.rs"), false));
+        assert!(!rules.is_excluded(&root.join("debug.log"), false));
+
+        // Walking agrees with the checks above
+        assert_eq!(walk(&rules), vec!["keep.log", "src/main.rs"]);
+    }
+
+    #[test]
+    fn test_deeper_directory_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let sub = root.join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(root.join(".gitignore"), "*.tmp\n").unwrap();
+        fs::write(sub.join(".gitignore"), "!wanted.tmp\n").unwrap();
+
+        let mut rules = IgnoreRules::new(root, &[]);
+        assert!(rules.is_ignored(&sub.join("wanted.tmp"), false));
+        rules.load_dir(&sub);
+        assert_eq!(rules.loaded_dir_count(), 2);
+        assert!(!rules.is_ignored(&sub.join("wanted.tmp"), false));
+        assert!(rules.is_ignored(&sub.join("other.tmp"), false));
+    }
+}
@@ -0,0 +1,264 @@
+//! Background jobs with cancellation
+//!
+//! Work that may take longer than a frame (git commands, searching a large
+//! buffer, loading a file, plugin processes) runs as a job on the tokio
+//! runtime and reports back through the async bridge, so the UI thread never
+//! waits on it.
+//!
+//! Every job gets a [`CancellationToken`], and may belong to a buffer: closing
+//! the buffer cancels its jobs. Async jobs are aborted at their next await
+//! point; blocking jobs can't be interrupted, so they are expected to check
+//! the token as they go. At most a few blocking jobs run at once, so a burst
+//! of searches cannot take every thread of the runtime.
+
+use crate::model::event::BufferId;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+
+/// Maximum number of blocking jobs running at the same time
+pub const DEFAULT_MAX_BLOCKING_JOBS: usize = 4;
+
+/// Identifies a job started by a [`JobPool`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JobId(u64);
+
+/// Tells a job that its result is no longer wanted
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+struct Job {
+    /// Buffer whose closing cancels the job
+    owner: Option<BufferId>,
+    token: CancellationToken,
+    abort: AbortHandle,
+}
+
+impl Job {
+    fn cancel(&self) {
+        self.token.cancel();
+        self.abort.abort();
+    }
+}
+
+/// Runs background jobs on the tokio runtime and keeps track of them
+pub struct JobPool {
+    /// None when there is no async runtime; jobs are not started then
+    runtime: Option<Handle>,
+    blocking_permits: Arc<Semaphore>,
+    jobs: HashMap<JobId, Job>,
+    next_id: u64,
+}
+
+impl JobPool {
+    pub fn new(runtime: Option<Handle>, max_blocking_jobs: usize) -> Self {
+        Self {
+            runtime,
+            blocking_permits: Arc::new(Semaphore::new(max_blocking_jobs.max(1))),
+            jobs: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Start an async job. Returns None when there is no runtime to run it.
+    pub fn spawn<F, Fut>(&mut self, owner: Option<BufferId>, job: F) -> Option<JobId>
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let runtime = self.runtime.as_ref()?;
+        let token = CancellationToken::new();
+        let task = runtime.spawn(job(token.clone()));
+        Some(self.track(owner, token, task.abort_handle()))
+    }
+
+    /// Start a job that blocks its thread (CPU-bound work or blocking I/O).
+    /// It should return early once `token` is cancelled.
+    pub fn spawn_blocking<F>(&mut self, owner: Option<BufferId>, job: F) -> Option<JobId>
+    where
+        F: FnOnce(&CancellationToken) + Send + 'static,
+    {
+        let runtime = self.runtime.as_ref()?;
+        let token = CancellationToken::new();
+        let permits = Arc::clone(&self.blocking_permits);
+        let job_token = token.clone();
+        let task = runtime.spawn(async move {
+            let Ok(permit) = permits.acquire_owned().await else {
+                return;
+            };
+            if job_token.is_cancelled() {
+                return;
+            }
+            // The permit moves into the blocking task, so it is held until the
+            // job returns even if this task is aborted
+            let _ = tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                job(&job_token);
+            })
+            .await;
+        });
+        Some(self.track(owner, token, task.abort_handle()))
+    }
+
+    fn track(
+        &mut self,
+        owner: Option<BufferId>,
+        token: CancellationToken,
+        abort: AbortHandle,
+    ) -> JobId {
+        self.jobs.retain(|_, job| !job.abort.is_finished());
+        self.next_id += 1;
+        let id = JobId(self.next_id);
+        self.jobs.insert(
+            id,
+            Job {
+                owner,
+                token,
+                abort,
+            },
+        );
+        id
+    }
+
+    /// Cancel a job; does nothing if it already finished
+    pub fn cancel(&mut self, id: JobId) {
+        if let Some(job) = self.jobs.remove(&id) {
+            job.cancel();
+        }
+    }
+
+    /// Cancel every job belonging to `buffer`, returning how many were running
+    pub fn cancel_owned_by(&mut self, buffer: BufferId) -> usize {
+        let mut cancelled = 0;
+        self.jobs.retain(|_, job| {
+            if job.abort.is_finished() {
+                return false;
+            }
+            if job.owner == Some(buffer) {
+                job.cancel();
+                cancelled += 1;
+                return false;
+            }
+            true
+        });
+        cancelled
+    }
+
+    /// Cancel every job
+    pub fn cancel_all(&mut self) {
+        for (_, job) in self.jobs.drain() {
+            job.cancel();
+        }
+    }
+
+    /// Number of jobs that have not finished yet
+    pub fn running(&mut self) -> usize {
+        self.jobs.retain(|_, job| !job.abort.is_finished());
+        self.jobs.len()
+    }
+}
+
+impl Drop for JobPool {
+    /// Dropping the runtime waits for blocking jobs, so ask them to stop first
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_no_runtime_starts_nothing() {
+        let mut pool = JobPool::new(None, 1);
+        assert_eq!(pool.spawn(None, |_| async {}), None);
+        assert_eq!(pool.spawn_blocking(None, |_| {}), None);
+    }
+
+    #[test]
+    fn test_closing_owner_cancels_blocking_job() {
+        let rt = runtime();
+        let mut pool = JobPool::new(Some(rt.handle().clone()), 1);
+        let (started_tx, started_rx) = mpsc::channel();
+        let (done_tx, done_rx) = mpsc::channel();
+        pool.spawn_blocking(Some(BufferId(1)), move |token| {
+            started_tx.send(()).unwrap();
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            done_tx.send(()).unwrap();
+        });
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        assert_eq!(pool.cancel_owned_by(BufferId(2)), 0);
+        assert_eq!(pool.cancel_owned_by(BufferId(1)), 1);
+        done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    #[test]
+    fn test_cancel_aborts_async_job() {
+        let rt = runtime();
+        let mut pool = JobPool::new(Some(rt.handle().clone()), 1);
+        let (tx, rx) = mpsc::channel::<()>();
+        let id = pool
+            .spawn(None, |_| async move {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                let _ = tx.send(());
+            })
+            .unwrap();
+        pool.cancel(id);
+        // The sender is dropped with the aborted task, without sending
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_err());
+        assert_eq!(pool.running(), 0);
+    }
+
+    #[test]
+    fn test_blocking_jobs_are_bounded() {
+        let rt = runtime();
+        let mut pool = JobPool::new(Some(rt.handle().clone()), 1);
+        let (tx, rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let first_tx = tx.clone();
+        pool.spawn_blocking(None, move |_| {
+            first_tx.send(1).unwrap();
+            let _ = release_rx.recv();
+        });
+        pool.spawn_blocking(None, move |_| {
+            tx.send(2).unwrap();
+        });
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 1);
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        release_tx.send(()).unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 2);
+    }
+}
@@ -0,0 +1,320 @@
+//! Linters run outside of language servers
+//!
+//! A linter (configured per language, see `config::LinterConfig`) is a command
+//! that reads a file, or the buffer on stdin, and prints its findings. The
+//! findings are read from its output with a regex or as JSON and turned into
+//! LSP diagnostics, so they are shown like the ones language servers publish.
+
+use crate::config::{LinterConfig, LinterFormat, LinterJsonFields};
+use crate::services::jobs::CancellationToken;
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use regex::Regex;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Why a linter produced no diagnostics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintError {
+    /// The command isn't installed
+    NotFound(String),
+    /// The command couldn't run, timed out, or printed something unreadable
+    Failed(String),
+}
+
+/// Run a linter on `file` and read its findings
+///
+/// `stdin` is the buffer content when the linter reads it from stdin. The
+/// exit code is ignored, since linters usually fail when they find something.
+pub fn run_linter(
+    linter: &LinterConfig,
+    file: &Path,
+    stdin: Option<&str>,
+    working_dir: &Path,
+    token: &CancellationToken,
+) -> Result<Vec<Diagnostic>, LintError> {
+    let file_str = file.display().to_string();
+    let mut args: Vec<String> = linter
+        .args
+        .iter()
+        .map(|arg| arg.replace("$FILE", &file_str))
+        .collect();
+    if stdin.is_none() && !linter.args.iter().any(|arg| arg.contains("$FILE")) {
+        args.push(file_str);
+    }
+
+    let mut child = Command::new(&linter.command)
+        .args(&args)
+        .current_dir(working_dir)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => LintError::NotFound(linter.command.clone()),
+            _ => LintError::Failed(format!("{}: {}", linter.command, e)),
+        })?;
+
+    // Feed and drain the pipes on other threads, so a linter with a lot to
+    // say can't fill a pipe and stall
+    let writer = stdin.zip(child.stdin.take()).map(|(text, mut pipe)| {
+        let text = text.to_string();
+        std::thread::spawn(move || {
+            let _ = pipe.write_all(text.as_bytes());
+        })
+    });
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut output = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut output);
+            }
+            String::from_utf8_lossy(&output).into_owned()
+        })
+    };
+    let stdout_reader = read(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr_reader = read(child.stderr.take().map(|p| Box::new(p) as _));
+
+    let timeout = Duration::from_millis(linter.timeout_ms);
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if token.is_cancelled() => {
+                let _ = child.kill();
+                return Ok(Vec::new());
+            }
+            Ok(None) if started.elapsed() > timeout => {
+                let _ = child.kill();
+                return Err(LintError::Failed(format!(
+                    "{} timed out after {}ms",
+                    linter.command, linter.timeout_ms
+                )));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+            Err(e) => return Err(LintError::Failed(format!("{}: {}", linter.command, e))),
+        }
+    }
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    let mut diagnostics = match linter.format {
+        LinterFormat::Regex => {
+            let regex = Regex::new(&linter.pattern)
+                .map_err(|e| LintError::Failed(format!("{}: {}", linter.command, e)))?;
+            // Some linters report on stderr
+            let mut diagnostics = parse_regex_output(&regex, &stdout);
+            diagnostics.extend(parse_regex_output(&regex, &stderr));
+            diagnostics
+        }
+        LinterFormat::Json if stdout.trim().is_empty() => Vec::new(),
+        LinterFormat::Json => parse_json_output(&linter.json, &stdout)
+            .map_err(|e| LintError::Failed(format!("{}: {}", linter.command, e)))?,
+    };
+    for diagnostic in &mut diagnostics {
+        diagnostic.source = Some(linter.command.clone());
+    }
+    Ok(diagnostics)
+}
+
+/// One diagnostic per line of `output` that `regex` matches
+pub fn parse_regex_output(regex: &Regex, output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let captures = regex.captures(line)?;
+            let group = |name: &str| captures.name(name).map(|m| m.as_str());
+            let number = |name: &str| group(name).and_then(|s| s.trim().parse::<u32>().ok());
+            Some(finding(
+                number("line")?,
+                number("column"),
+                number("end_line"),
+                number("end_column"),
+                group("severity"),
+                group("code").map(str::to_string),
+                group("message").unwrap_or(line).trim().to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// The diagnostics listed in a linter's JSON `output`
+pub fn parse_json_output(
+    fields: &LinterJsonFields,
+    output: &str,
+) -> Result<Vec<Diagnostic>, String> {
+    let json: Value = serde_json::from_str(output).map_err(|e| e.to_string())?;
+    let items = lookup(&json, &fields.items)
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("no list of findings at {:?}", fields.items))?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let number = |path: &str| {
+                lookup(item, path).and_then(|v| {
+                    v.as_u64()
+                        .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                        .and_then(|n| u32::try_from(n).ok())
+                })
+            };
+            let text = |path: &str| {
+                lookup(item, path).and_then(|v| match v {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Null => None,
+                    other => Some(other.to_string()),
+                })
+            };
+            Some(finding(
+                number(&fields.line)?,
+                number(&fields.column),
+                number(&fields.end_line),
+                number(&fields.end_column),
+                text(&fields.severity).as_deref(),
+                text(&fields.code),
+                text(&fields.message).unwrap_or_default(),
+            ))
+        })
+        .collect())
+}
+
+/// The value at a dot-separated `path` in `json` (the value itself for "")
+fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(json);
+    }
+    path.split('.').try_fold(json, |value, key| match value {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+/// A diagnostic from 1-based line and column numbers
+///
+/// Without a column the whole line is marked; without an end, one character.
+fn finding(
+    line: u32,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<&str>,
+    code: Option<String>,
+    message: String,
+) -> Diagnostic {
+    let line = line.saturating_sub(1);
+    let (start, end) = match column {
+        Some(column) => {
+            let start = Position::new(line, column.saturating_sub(1));
+            let end = match (end_line, end_column) {
+                (end_line, Some(end_column)) => Position::new(
+                    end_line.map_or(line, |l| l.saturating_sub(1)),
+                    end_column.saturating_sub(1),
+                ),
+                _ => Position::new(line, start.character + 1),
+            };
+            (start, end.max(start))
+        }
+        None => (Position::new(line, 0), Position::new(line + 1, 0)),
+    };
+    Diagnostic {
+        range: Range::new(start, end),
+        severity: Some(severity.map_or(DiagnosticSeverity::WARNING, parse_severity)),
+        code: code.map(NumberOrString::String),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Severity from the words linters use for it
+fn parse_severity(severity: &str) -> DiagnosticSeverity {
+    let severity = severity.trim().to_ascii_lowercase();
+    if severity.starts_with('e') || severity.starts_with('f') {
+        // error, fatal
+        DiagnosticSeverity::ERROR
+    } else if severity.starts_with('i') || severity.starts_with('n') {
+        // info, information, note
+        DiagnosticSeverity::INFORMATION
+    } else if severity.starts_with('h') || severity.starts_with('s') {
+        // hint, style, suggestion
+        DiagnosticSeverity::HINT
+    } else {
+        DiagnosticSeverity::WARNING
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_regex_output() {
+        // yamllint's "parsable" format
+        let regex = Regex::new(
+            r"^[^:]+:(?P<line>\d+):(?P<column>\d+): \[(?P<severity>\w+)\] (?P<message>.*) \((?P<code>[^)]+)\)$",
+        )
+        .unwrap();
+        let output = "a.yaml:3:1: [warning] too many blank lines (1 > 0) (empty-lines)\n\
+                      some other output\n\
+                      a.yaml:1:5: [error] syntax error (syntax)\n";
+        let diagnostics = parse_regex_output(&regex, output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].range.start, Position::new(2, 0));
+        assert_eq!(diagnostics[0].range.end, Position::new(2, 1));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].message, "too many blank lines (1 > 0)");
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("empty-lines".to_string()))
+        );
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_parse_json_output() {
+        // shellcheck --format=json1
+        let output = r#"{"comments":[
+            {"file":"-","line":2,"endLine":2,"column":6,"endColumn":10,"level":"info",
+             "code":2086,"message":"Double quote to prevent globbing"},
+            {"file":"-","line":4,"column":1,"level":"style","code":2034,"message":"x appears unused"}
+        ]}"#;
+        let fields = LinterJsonFields {
+            items: "comments".to_string(),
+            severity: "level".to_string(),
+            ..Default::default()
+        };
+        let diagnostics = parse_json_output(&fields, output).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].range,
+            Range::new(Position::new(1, 5), Position::new(1, 9))
+        );
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("2086".to_string()))
+        );
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::HINT));
+
+        assert!(parse_json_output(&LinterJsonFields::default(), output).is_err());
+    }
+
+    #[test]
+    fn test_lookup_nested_paths() {
+        let json: Value = serde_json::json!({"location": {"row": 3}, "items": [{"a": 1}]});
+        assert_eq!(lookup(&json, "location.row"), Some(&Value::from(3)));
+        assert_eq!(lookup(&json, "items.0.a"), Some(&Value::from(1)));
+        assert_eq!(lookup(&json, "missing.row"), None);
+    }
+}
@@ -0,0 +1,311 @@
+//! Local history: snapshots of file contents kept outside the project
+//!
+//! Each file has a directory of its own under the local history directory,
+//! named after the file's path, with one file per snapshot. A snapshot's file
+//! name holds when it was taken, in milliseconds since the Unix epoch, and
+//! whether a save or the periodic snapshot took it.
+//!
+//! Snapshots may hold anything the files did, so on Unix their directories
+//! are only accessible to the owner and each snapshot gets its file's mode.
+
+use crate::workspace::encode_path_for_filename;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What took a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotKind {
+    /// The file was saved
+    Save,
+    /// The periodic snapshot of unsaved edits
+    Auto,
+}
+
+impl SnapshotKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            SnapshotKind::Save => "save",
+            SnapshotKind::Auto => "auto",
+        }
+    }
+}
+
+/// A stored snapshot of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Where the snapshot's content is stored
+    pub path: PathBuf,
+    pub time: SystemTime,
+    pub kind: SnapshotKind,
+}
+
+/// Which snapshots are kept when a new one is recorded
+#[derive(Debug, Clone, Copy)]
+pub struct Retention {
+    /// Most snapshots kept per file
+    pub max_snapshots: usize,
+    /// Snapshots older than this are deleted
+    pub max_age: Option<Duration>,
+}
+
+/// Local history storage
+#[derive(Debug, Clone)]
+pub struct LocalHistory {
+    dir: PathBuf,
+}
+
+impl LocalHistory {
+    /// File extension of snapshot files
+    const SNAPSHOT_EXT: &'static str = "snap";
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Directory holding the snapshots of `file`
+    fn file_dir(&self, file: &Path) -> PathBuf {
+        self.dir.join(encode_path_for_filename(file))
+    }
+
+    /// Snapshots of `file`, newest first
+    pub fn snapshots(&self, file: &Path) -> io::Result<Vec<Snapshot>> {
+        let entries = match fs::read_dir(self.file_dir(file)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut snapshots: Vec<Snapshot> = entries
+            .filter_map(|entry| parse_snapshot(entry.ok()?.path()))
+            .collect();
+        snapshots.sort_by(|a, b| b.time.cmp(&a.time).then_with(|| b.path.cmp(&a.path)));
+        Ok(snapshots)
+    }
+
+    /// Content of a snapshot
+    pub fn read(&self, snapshot: &Snapshot) -> io::Result<Vec<u8>> {
+        fs::read(&snapshot.path)
+    }
+
+    /// Store `content` as a snapshot of `file` taken at `now`, then delete the
+    /// snapshots `retention` doesn't keep. Nothing is stored when the content
+    /// is that of the newest snapshot; returns whether a snapshot was stored.
+    pub fn record(
+        &self,
+        file: &Path,
+        content: &[u8],
+        kind: SnapshotKind,
+        now: SystemTime,
+        retention: Retention,
+    ) -> io::Result<bool> {
+        let snapshots = self.snapshots(file)?;
+        if let Some(newest) = snapshots.first() {
+            if fs::read(&newest.path).is_ok_and(|newest| newest == content) {
+                return Ok(false);
+            }
+        }
+
+        let dir = self.file_dir(file);
+        create_private_dir(&dir)?;
+        let millis = now
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let target = dir.join(format!(
+            "{}-{}.{}",
+            millis,
+            kind.as_str(),
+            Self::SNAPSHOT_EXT
+        ));
+        let temp_path = target.with_extension("tmp");
+        let mut temp = create_snapshot_file(&temp_path, file)?;
+        temp.write_all(content)?;
+        drop(temp);
+        fs::rename(&temp_path, &target)?;
+
+        self.prune(file, now, retention)?;
+        Ok(true)
+    }
+
+    /// Delete the snapshots of `file` that `retention` doesn't keep
+    fn prune(&self, file: &Path, now: SystemTime, retention: Retention) -> io::Result<()> {
+        for (index, snapshot) in self.snapshots(file)?.iter().enumerate() {
+            let too_old = retention.max_age.is_some_and(|max_age| {
+                now.duration_since(snapshot.time)
+                    .is_ok_and(|age| age > max_age)
+            });
+            if index >= retention.max_snapshots.max(1) || too_old {
+                fs::remove_file(&snapshot.path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Create `dir` and its missing parents, accessible only to the owner
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
+
+/// Create a new file at `path` with the permissions of `file`, or readable
+/// only by the owner if `file` is gone
+fn create_snapshot_file(path: &Path, file: &Path) -> io::Result<File> {
+    // A leftover from an interrupted snapshot would keep its old mode
+    if let Err(e) = fs::remove_file(path) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e);
+        }
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        let mode = fs::metadata(file)
+            .map(|metadata| metadata.permissions().mode() & 0o777)
+            .unwrap_or(0o600);
+        options.mode(mode);
+    }
+    #[cfg(not(unix))]
+    let _ = file;
+    options.open(path)
+}
+
+/// The snapshot stored at `path`, if it is a snapshot file
+fn parse_snapshot(path: PathBuf) -> Option<Snapshot> {
+    if path.extension()? != LocalHistory::SNAPSHOT_EXT {
+        return None;
+    }
+    let (millis, kind) = path.file_stem()?.to_str()?.split_once('-')?;
+    let kind = match kind {
+        "save" => SnapshotKind::Save,
+        "auto" => SnapshotKind::Auto,
+        _ => return None,
+    };
+    let time = UNIX_EPOCH + Duration::from_millis(millis.parse().ok()?);
+    Some(Snapshot { path, time, kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const KEEP_ALL: Retention = Retention {
+        max_snapshots: 100,
+        max_age: None,
+    };
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_record_and_read() {
+        let temp = TempDir::new().unwrap();
+        let history = LocalHistory::new(temp.path().to_path_buf());
+        let file = Path::new("/project/src/main.rs");
+
+        assert!(history.snapshots(file).unwrap().is_empty());
+        assert!(history
+            .record(file, b"one", SnapshotKind::Save, at(10), KEEP_ALL)
+            .unwrap());
+        assert!(history
+            .record(file, b"two", SnapshotKind::Auto, at(20), KEEP_ALL)
+            .unwrap());
+        // Unchanged content isn't stored again
+        assert!(!history
+            .record(file, b"two", SnapshotKind::Save, at(30), KEEP_ALL)
+            .unwrap());
+
+        let snapshots = history.snapshots(file).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].time, at(20));
+        assert_eq!(snapshots[0].kind, SnapshotKind::Auto);
+        assert_eq!(history.read(&snapshots[0]).unwrap(), b"two");
+        assert_eq!(history.read(&snapshots[1]).unwrap(), b"one");
+
+        // Other files have their own history
+        assert!(history
+            .snapshots(Path::new("/project/src/lib.rs"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_pruning() {
+        let temp = TempDir::new().unwrap();
+        let history = LocalHistory::new(temp.path().to_path_buf());
+        let file = Path::new("/project/notes.txt");
+        let retention = Retention {
+            max_snapshots: 3,
+            max_age: Some(Duration::from_secs(100)),
+        };
+
+        for (i, secs) in [10, 20, 30, 40].into_iter().enumerate() {
+            let content = format!("version {i}");
+            history
+                .record(
+                    file,
+                    content.as_bytes(),
+                    SnapshotKind::Save,
+                    at(secs),
+                    retention,
+                )
+                .unwrap();
+        }
+        let times: Vec<_> = history
+            .snapshots(file)
+            .unwrap()
+            .iter()
+            .map(|s| s.time)
+            .collect();
+        assert_eq!(times, [at(40), at(30), at(20)]);
+
+        history
+            .record(file, b"late", SnapshotKind::Auto, at(125), retention)
+            .unwrap();
+        let times: Vec<_> = history
+            .snapshots(file)
+            .unwrap()
+            .iter()
+            .map(|s| s.time)
+            .collect();
+        assert_eq!(times, [at(125), at(40), at(30)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_snapshot_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let temp = TempDir::new().unwrap();
+        let history = LocalHistory::new(temp.path().join("history"));
+        let file = temp.path().join("secret.env");
+        fs::write(&file, "KEY=1").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+
+        history
+            .record(&file, b"KEY=1", SnapshotKind::Save, at(10), KEEP_ALL)
+            .unwrap();
+        let snapshot = &history.snapshots(&file).unwrap()[0];
+        assert_eq!(mode(&snapshot.path), 0o640);
+        assert_eq!(mode(snapshot.path.parent().unwrap()), 0o700);
+
+        // Without a file to copy the mode from, only the owner can read it
+        let gone = Path::new("/project/gone.txt");
+        history
+            .record(gone, b"gone", SnapshotKind::Auto, at(20), KEEP_ALL)
+            .unwrap();
+        assert_eq!(mode(&history.snapshots(gone).unwrap()[0].path), 0o600);
+    }
+}
@@ -0,0 +1,237 @@
+//! In-memory log for the `*Log*` buffer
+//!
+//! This module provides a tracing layer that keeps the most recent log events
+//! in a ring buffer, so the editor can show them without leaving the TUI. It
+//! runs next to the log file, and keeps working when the log file can't be
+//! created.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::Level;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Number of log events kept
+const MAX_ENTRIES: usize = 10_000;
+
+/// A log event
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Number of the event; later events have higher numbers
+    pub seq: u64,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub level: Level,
+    /// Module that logged the event, e.g. `fresh::services::lsp::manager`
+    pub target: String,
+    /// The message followed by the other fields of the event
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Part of the editor the event comes from, e.g. `lsp` for
+    /// `fresh::services::lsp::manager`
+    pub fn source(&self) -> &str {
+        let target = self.target.as_str();
+        let target = target.strip_prefix("fresh::").unwrap_or(target);
+        let target = target.strip_prefix("services::").unwrap_or(target);
+        target.split("::").next().unwrap_or(target)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Ring {
+    entries: VecDeque<LogEntry>,
+    next_seq: u64,
+}
+
+/// Shared handle to the recent log events
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    ring: Arc<Mutex<Ring>>,
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Ring> {
+        self.ring
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Add an event, dropping the oldest when full
+    pub fn push(&self, level: Level, target: &str, message: String) {
+        let mut ring = self.lock();
+        let seq = ring.next_seq;
+        ring.next_seq += 1;
+        if ring.entries.len() == MAX_ENTRIES {
+            ring.entries.pop_front();
+        }
+        ring.entries.push_back(LogEntry {
+            seq,
+            timestamp: chrono::Local::now(),
+            level,
+            target: target.to_string(),
+            message,
+        });
+    }
+
+    /// Number the next event will get
+    pub fn next_seq(&self) -> u64 {
+        self.lock().next_seq
+    }
+
+    /// Copies of the kept events at `min_level` or more severe, oldest first
+    pub fn entries(&self, min_level: Level) -> Vec<LogEntry> {
+        self.lock()
+            .entries
+            .iter()
+            .filter(|entry| entry.level <= min_level)
+            .cloned()
+            .collect()
+    }
+
+    /// Whether an event at `min_level` or more severe was added since event
+    /// number `seq`
+    pub fn has_new(&self, seq: u64, min_level: Level) -> bool {
+        self.lock()
+            .entries
+            .iter()
+            .rev()
+            .take_while(|entry| entry.seq >= seq)
+            .any(|entry| entry.level <= min_level)
+    }
+}
+
+/// A tracing layer that adds events to a [`LogBuffer`]
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+/// Create a log buffer layer and the handle to pass to the editor
+pub fn create() -> (LogBufferLayer, LogBuffer) {
+    let buffer = LogBuffer::new();
+    (
+        LogBufferLayer {
+            buffer: buffer.clone(),
+        },
+        buffer,
+    )
+}
+
+impl<S> Layer<S> for LogBufferLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer
+            .push(*metadata.level(), metadata.target(), visitor.finish());
+    }
+}
+
+/// Collects the message and the other fields of an event
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl FieldVisitor {
+    fn finish(mut self) -> String {
+        if self.message.is_empty() {
+            return self.fields.trim_start().to_string();
+        }
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl tracing::field::Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_layer_keeps_message_and_fields() {
+        let (layer, buffer) = create();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(server = "rust-analyzer", "Server exited");
+            tracing::debug!("Polling");
+        });
+
+        let entries = buffer.entries(Level::TRACE);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, Level::WARN);
+        assert_eq!(entries[0].message, "Server exited server=rust-analyzer");
+        assert_eq!(entries[1].message, "Polling");
+        assert_eq!(buffer.entries(Level::INFO).len(), 1);
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let buffer = LogBuffer::new();
+        for i in 0..MAX_ENTRIES + 5 {
+            buffer.push(Level::INFO, "fresh", i.to_string());
+        }
+        let entries = buffer.entries(Level::TRACE);
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].message, "5");
+        assert_eq!(buffer.next_seq(), (MAX_ENTRIES + 5) as u64);
+    }
+
+    #[test]
+    fn test_has_new_respects_level() {
+        let buffer = LogBuffer::new();
+        buffer.push(Level::INFO, "fresh", "started".to_string());
+        let seen = buffer.next_seq();
+        assert!(!buffer.has_new(seen, Level::TRACE));
+
+        buffer.push(Level::DEBUG, "fresh", "noise".to_string());
+        assert!(!buffer.has_new(seen, Level::INFO));
+        assert!(buffer.has_new(seen, Level::DEBUG));
+    }
+
+    #[test]
+    fn test_source_of_target() {
+        let entry = |target: &str| LogEntry {
+            seq: 0,
+            timestamp: chrono::Local::now(),
+            level: Level::INFO,
+            target: target.to_string(),
+            message: String::new(),
+        };
+        assert_eq!(entry("fresh::services::lsp::manager").source(), "lsp");
+        assert_eq!(entry("fresh::app::file_open").source(), "app");
+        assert_eq!(
+            entry("fresh_plugin_runtime::backend").source(),
+            "fresh_plugin_runtime"
+        );
+        assert_eq!(entry("status").source(), "status");
+    }
+}
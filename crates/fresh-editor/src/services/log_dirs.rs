@@ -106,9 +106,11 @@ pub fn lsp_log_dir() -> PathBuf {
 
 /// Get the path for an LSP server's log file for this process.
 ///
-/// Returns `{log_dir}/lsp/{language}-{PID}.log`
+/// Returns `{log_dir}/lsp/{language}-{PID}.log`. Path separators in the
+/// name (additional servers are named `{language}/{server}`) become `_`.
 pub fn lsp_log_path(language: &str) -> PathBuf {
-    lsp_log_dir().join(format!("{}-{}.log", language, std::process::id()))
+    let name = language.replace(['/', '\\'], "_");
+    lsp_log_dir().join(format!("{}-{}.log", name, std::process::id()))
 }
 
 /// Clean up stale log files from dead processes.
@@ -390,6 +392,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lsp_log_path_for_additional_server() {
+        let path = lsp_log_path("python/ruff");
+        assert_eq!(path.parent().unwrap(), lsp_log_dir());
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("python_ruff-"), "Got {}", name);
+    }
+
     #[test]
     fn test_extract_pid_from_filename() {
         assert_eq!(extract_pid_from_filename("fresh-12345.log"), Some(12345));
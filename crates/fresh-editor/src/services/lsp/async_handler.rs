@@ -22,14 +22,14 @@ use lsp_types::{
         PublishDiagnostics,
     },
     request::{Initialize, Request},
-    ClientCapabilities, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializeParams, InitializeResult, InitializedParams,
-    PublishDiagnosticsParams, SemanticTokenModifier, SemanticTokenType,
-    SemanticTokensClientCapabilities, SemanticTokensClientCapabilitiesRequests,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult,
-    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentContentChangeEvent,
-    TextDocumentIdentifier, TextDocumentItem, TokenFormat, Uri, VersionedTextDocumentIdentifier,
-    WindowClientCapabilities, WorkspaceFolder,
+    ApplyWorkspaceEditParams, ApplyWorkspaceEditResponse, ClientCapabilities,
+    DidChangeTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    InitializeParams, InitializeResult, InitializedParams, PublishDiagnosticsParams,
+    SemanticTokenModifier, SemanticTokenType, SemanticTokensClientCapabilities,
+    SemanticTokensClientCapabilitiesRequests, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
+    TokenFormat, Uri, VersionedTextDocumentIdentifier, WindowClientCapabilities, WorkspaceFolder,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -46,6 +46,9 @@ use tokio::sync::{mpsc, oneshot};
 /// This gives the LSP server time to process didOpen before receiving changes
 const DID_OPEN_GRACE_PERIOD_MS: u64 = 200;
 
+/// How long a workspace/applyEdit request waits for the editor to apply the edit
+const APPLY_EDIT_TIMEOUT_SECS: u64 = 30;
+
 /// Check if a document is already open and should skip didOpen.
 /// Returns true if the document is already open (should skip), false if it should proceed.
 fn should_skip_did_open(
@@ -200,8 +203,10 @@ impl LspClientState {
 /// Create common LSP client capabilities with workDoneProgress support
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
-        GeneralClientCapabilities, RenameClientCapabilities, TextDocumentClientCapabilities,
-        WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        DocumentFormattingClientCapabilities, DocumentHighlightClientCapabilities,
+        DocumentRangeFormattingClientCapabilities, GeneralClientCapabilities,
+        RenameClientCapabilities, TextDocumentClientCapabilities, WorkspaceClientCapabilities,
+        WorkspaceEditClientCapabilities,
     };
 
     ClientCapabilities {
@@ -218,6 +223,15 @@ fn create_client_capabilities() -> ClientCapabilities {
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
+            document_highlight: Some(DocumentHighlightClientCapabilities {
+                dynamic_registration: Some(false),
+            }),
+            formatting: Some(DocumentFormattingClientCapabilities {
+                dynamic_registration: Some(false),
+            }),
+            range_formatting: Some(DocumentRangeFormattingClientCapabilities {
+                dynamic_registration: Some(false),
+            }),
             rename: Some(RenameClientCapabilities {
                 dynamic_registration: Some(true),
                 prepare_support: Some(true),
@@ -311,6 +325,91 @@ fn extract_semantic_token_capability(
         .unwrap_or((None, false, false, false))
 }
 
+/// Names of the LSP features a server advertises, for display in the LSP info view
+fn supported_features(capabilities: &ServerCapabilities) -> Vec<String> {
+    use lsp_types::{
+        CodeActionProviderCapability, HoverProviderCapability, ImplementationProviderCapability,
+        OneOf, TypeDefinitionProviderCapability,
+    };
+
+    fn one_of<T>(provider: &Option<OneOf<bool, T>>) -> bool {
+        !matches!(provider, None | Some(OneOf::Left(false)))
+    }
+
+    let features = [
+        ("completion", capabilities.completion_provider.is_some()),
+        (
+            "hover",
+            !matches!(
+                capabilities.hover_provider,
+                None | Some(HoverProviderCapability::Simple(false))
+            ),
+        ),
+        (
+            "signatureHelp",
+            capabilities.signature_help_provider.is_some(),
+        ),
+        ("definition", one_of(&capabilities.definition_provider)),
+        (
+            "typeDefinition",
+            !matches!(
+                capabilities.type_definition_provider,
+                None | Some(TypeDefinitionProviderCapability::Simple(false))
+            ),
+        ),
+        (
+            "implementation",
+            !matches!(
+                capabilities.implementation_provider,
+                None | Some(ImplementationProviderCapability::Simple(false))
+            ),
+        ),
+        ("references", one_of(&capabilities.references_provider)),
+        (
+            "documentHighlight",
+            one_of(&capabilities.document_highlight_provider),
+        ),
+        (
+            "documentSymbol",
+            one_of(&capabilities.document_symbol_provider),
+        ),
+        (
+            "workspaceSymbol",
+            one_of(&capabilities.workspace_symbol_provider),
+        ),
+        (
+            "codeAction",
+            !matches!(
+                capabilities.code_action_provider,
+                None | Some(CodeActionProviderCapability::Simple(false))
+            ),
+        ),
+        (
+            "formatting",
+            one_of(&capabilities.document_formatting_provider),
+        ),
+        (
+            "rangeFormatting",
+            one_of(&capabilities.document_range_formatting_provider),
+        ),
+        ("rename", one_of(&capabilities.rename_provider)),
+        ("inlayHint", one_of(&capabilities.inlay_hint_provider)),
+        (
+            "semanticTokens",
+            capabilities.semantic_tokens_provider.is_some(),
+        ),
+        (
+            "pullDiagnostics",
+            capabilities.diagnostic_provider.is_some(),
+        ),
+    ];
+    features
+        .into_iter()
+        .filter(|(_, supported)| *supported)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
 fn semantic_tokens_full_supported(full: &Option<SemanticTokensFullOptions>) -> bool {
     match full {
         Some(SemanticTokensFullOptions::Bool(v)) => *v,
@@ -326,6 +425,25 @@ fn semantic_tokens_full_delta_supported(full: &Option<SemanticTokensFullOptions>
     }
 }
 
+/// Which location request a "go to" command sends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoTarget {
+    Definition,
+    TypeDefinition,
+    Implementation,
+}
+
+impl GotoTarget {
+    /// LSP method name for this request
+    fn method(self) -> &'static str {
+        match self {
+            GotoTarget::Definition => "textDocument/definition",
+            GotoTarget::TypeDefinition => "textDocument/typeDefinition",
+            GotoTarget::Implementation => "textDocument/implementation",
+        }
+    }
+}
+
 /// Commands sent from the main loop to the LSP task
 #[derive(Debug)]
 enum LspCommand {
@@ -360,9 +478,10 @@ enum LspCommand {
         character: u32,
     },
 
-    /// Request go-to-definition
+    /// Request go-to-definition (or type definition / implementation)
     GotoDefinition {
         request_id: u64,
+        target: GotoTarget,
         uri: Uri,
         line: u32,
         character: u32,
@@ -393,6 +512,23 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Request occurrences of the symbol under the cursor
+    DocumentHighlight {
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
+    /// Request formatting edits for the whole document or a range
+    Formatting {
+        request_id: u64,
+        uri: Uri,
+        /// Range to format, or None for the whole document
+        range: Option<lsp_types::Range>,
+        options: lsp_types::FormattingOptions,
+    },
+
     /// Request signature help
     SignatureHelp {
         request_id: u64,
@@ -744,6 +880,14 @@ impl LspState {
             .and_then(|cp| cp.trigger_characters.clone())
             .unwrap_or_default();
 
+        // Extract signature help trigger characters from server capabilities
+        let signature_help_trigger_characters = result
+            .capabilities
+            .signature_help_provider
+            .as_ref()
+            .and_then(|sp| sp.trigger_characters.clone())
+            .unwrap_or_default();
+
         let (
             semantic_tokens_legend,
             semantic_tokens_full,
@@ -755,10 +899,12 @@ impl LspState {
         let _ = self.async_tx.send(AsyncMessage::LspInitialized {
             language: self.language.clone(),
             completion_trigger_characters,
+            signature_help_trigger_characters,
             semantic_tokens_legend,
             semantic_tokens_full,
             semantic_tokens_full_delta,
             semantic_tokens_range,
+            features: supported_features(&result.capabilities),
         });
 
         // Send running status
@@ -890,6 +1036,20 @@ impl LspState {
             TextDocumentPositionParams, WorkDoneProgressParams,
         };
 
+        // Servers without completion support answer with no items (a buffer
+        // can have several servers, and only some of them offer completion)
+        if self
+            .capabilities
+            .as_ref()
+            .is_some_and(|caps| caps.completion_provider.is_none())
+        {
+            let _ = self.async_tx.send(AsyncMessage::LspCompletion {
+                request_id,
+                items: vec![],
+            });
+            return Ok(());
+        }
+
         tracing::trace!(
             "LSP: completion request at {}:{}:{}",
             uri.as_str(),
@@ -946,11 +1106,15 @@ impl LspState {
         }
     }
 
-    /// Handle go-to-definition request
+    /// Handle go-to-definition, type definition and implementation requests
+    ///
+    /// All three share the same parameters and response shape.
     #[allow(clippy::type_complexity)]
+    #[allow(clippy::too_many_arguments)]
     async fn handle_goto_definition(
         &mut self,
         request_id: u64,
+        target: GotoTarget,
         uri: Uri,
         line: u32,
         character: u32,
@@ -962,7 +1126,8 @@ impl LspState {
         };
 
         tracing::trace!(
-            "LSP: go-to-definition request at {}:{}:{}",
+            "LSP: {} request at {}:{}:{}",
+            target.method(),
             uri.as_str(),
             line,
             character
@@ -979,7 +1144,7 @@ impl LspState {
 
         // Send request and get response
         match self
-            .send_request_sequential::<_, Value>("textDocument/definition", Some(params), pending)
+            .send_request_sequential::<_, Value>(target.method(), Some(params), pending)
             .await
         {
             Ok(result) => {
@@ -1286,6 +1451,190 @@ impl LspState {
         }
     }
 
+    /// Handle document highlight request
+    #[allow(clippy::type_complexity)]
+    async fn handle_document_highlight(
+        &mut self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            DocumentHighlightParams, PartialResultParams, Position, TextDocumentIdentifier,
+            WorkDoneProgressParams,
+        };
+
+        tracing::trace!(
+            "LSP: document highlight request at {}:{}:{}",
+            uri.as_str(),
+            line,
+            character
+        );
+
+        // Don't queue a request the server never advertised; an unanswered
+        // request would stall every sequential request behind it.
+        let supported = self.capabilities.as_ref().is_some_and(|caps| {
+            !matches!(
+                caps.document_highlight_provider,
+                None | Some(lsp_types::OneOf::Left(false))
+            )
+        });
+        if !supported {
+            let _ = self.async_tx.send(AsyncMessage::LspDocumentHighlight {
+                request_id,
+                highlights: Vec::new(),
+            });
+            return Ok(());
+        }
+
+        let params = DocumentHighlightParams {
+            text_document_position_params: lsp_types::TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position { line, character },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Value>(
+                "textDocument/documentHighlight",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(result) => {
+                // Parse the response (Vec<DocumentHighlight> or null)
+                let highlights = if result.is_null() {
+                    Vec::new()
+                } else {
+                    serde_json::from_value::<Vec<lsp_types::DocumentHighlight>>(result)
+                        .unwrap_or_default()
+                };
+
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentHighlight {
+                    request_id,
+                    highlights,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                // Servers without documentHighlight support end up here; the
+                // editor keeps its word-match highlights.
+                tracing::debug!("Document highlight request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentHighlight {
+                    request_id,
+                    highlights: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle document or range formatting request
+    #[allow(clippy::type_complexity)]
+    async fn handle_formatting(
+        &mut self,
+        request_id: u64,
+        uri: Uri,
+        range: Option<lsp_types::Range>,
+        options: lsp_types::FormattingOptions,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            DocumentFormattingParams, DocumentRangeFormattingParams, TextDocumentIdentifier,
+            WorkDoneProgressParams,
+        };
+
+        tracing::trace!(
+            "LSP: formatting request for {} (range: {:?})",
+            uri.as_str(),
+            range
+        );
+
+        // Don't queue a request the server never advertised; an unanswered
+        // request would stall every sequential request behind it.
+        let supported = self.capabilities.as_ref().is_some_and(|caps| {
+            if range.is_some() {
+                !matches!(
+                    caps.document_range_formatting_provider,
+                    None | Some(lsp_types::OneOf::Left(false))
+                )
+            } else {
+                !matches!(
+                    caps.document_formatting_provider,
+                    None | Some(lsp_types::OneOf::Left(false))
+                )
+            }
+        });
+        if !supported {
+            let _ = self.async_tx.send(AsyncMessage::LspFormatting {
+                request_id,
+                result: Err("unsupported".to_string()),
+            });
+            return Ok(());
+        }
+
+        let text_document = TextDocumentIdentifier { uri };
+        let work_done_progress_params = WorkDoneProgressParams::default();
+        // Format-on-save waits for this response, so don't let a stuck server
+        // block saving forever
+        let timeout = std::time::Duration::from_secs(5);
+        let response = match range {
+            Some(range) => {
+                let params = DocumentRangeFormattingParams {
+                    text_document,
+                    range,
+                    options,
+                    work_done_progress_params,
+                };
+                tokio::time::timeout(
+                    timeout,
+                    self.send_request_sequential::<_, Value>(
+                        "textDocument/rangeFormatting",
+                        Some(params),
+                        pending,
+                    ),
+                )
+                .await
+            }
+            None => {
+                let params = DocumentFormattingParams {
+                    text_document,
+                    options,
+                    work_done_progress_params,
+                };
+                tokio::time::timeout(
+                    timeout,
+                    self.send_request_sequential::<_, Value>(
+                        "textDocument/formatting",
+                        Some(params),
+                        pending,
+                    ),
+                )
+                .await
+            }
+        };
+
+        let result = match response {
+            Ok(Ok(value)) if value.is_null() => Ok(Vec::new()),
+            Ok(Ok(value)) => serde_json::from_value::<Vec<lsp_types::TextEdit>>(value)
+                .map_err(|e| format!("Invalid formatting response: {}", e)),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err("timed out".to_string()),
+        };
+        if let Err(e) = &result {
+            tracing::debug!("Formatting request failed: {}", e);
+        }
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspFormatting { request_id, result });
+        Ok(())
+    }
+
     /// Handle signature help request
     #[allow(clippy::type_complexity)]
     async fn handle_signature_help(
@@ -1514,6 +1863,7 @@ impl LspState {
 
                     let _ = self.async_tx.send(AsyncMessage::LspPulledDiagnostics {
                         request_id,
+                        language: self.language.clone(),
                         uri: uri_string,
                         result_id,
                         diagnostics,
@@ -1535,6 +1885,7 @@ impl LspState {
 
                     let _ = self.async_tx.send(AsyncMessage::LspPulledDiagnostics {
                         request_id,
+                        language: self.language.clone(),
                         uri: uri_string,
                         result_id: Some(result_id),
                         diagnostics: Vec::new(),
@@ -1548,6 +1899,7 @@ impl LspState {
                     );
                     let _ = self.async_tx.send(AsyncMessage::LspPulledDiagnostics {
                         request_id,
+                        language: self.language.clone(),
                         uri: uri_string,
                         result_id: None,
                         diagnostics: Vec::new(),
@@ -1562,6 +1914,7 @@ impl LspState {
                 // Send empty result on error
                 let _ = self.async_tx.send(AsyncMessage::LspPulledDiagnostics {
                     request_id,
+                    language: self.language.clone(),
                     uri: uri.as_str().to_string(),
                     result_id: None,
                     diagnostics: Vec::new(),
@@ -2267,6 +2620,7 @@ impl LspTask {
                         }
                         LspCommand::GotoDefinition {
                             request_id,
+                            target,
                             uri,
                             line,
                             character,
@@ -2278,7 +2632,7 @@ impl LspTask {
                                 );
                                 let _ = state
                                     .handle_goto_definition(
-                                        request_id, uri, line, character, &pending,
+                                        request_id, target, uri, line, character, &pending,
                                     )
                                     .await;
                             } else {
@@ -2351,6 +2705,46 @@ impl LspTask {
                                 });
                             }
                         }
+                        LspCommand::DocumentHighlight {
+                            request_id,
+                            uri,
+                            line,
+                            character,
+                        } => {
+                            if state.initialized {
+                                let _ = state
+                                    .handle_document_highlight(
+                                        request_id, uri, line, character, &pending,
+                                    )
+                                    .await;
+                            } else {
+                                tracing::trace!(
+                                    "LSP not initialized, cannot get document highlights"
+                                );
+                                let _ = state.async_tx.send(AsyncMessage::LspDocumentHighlight {
+                                    request_id,
+                                    highlights: Vec::new(),
+                                });
+                            }
+                        }
+                        LspCommand::Formatting {
+                            request_id,
+                            uri,
+                            range,
+                            options,
+                        } => {
+                            if state.initialized {
+                                let _ = state
+                                    .handle_formatting(request_id, uri, range, options, &pending)
+                                    .await;
+                            } else {
+                                tracing::trace!("LSP not initialized, cannot format");
+                                let _ = state.async_tx.send(AsyncMessage::LspFormatting {
+                                    request_id,
+                                    result: Err("LSP not initialized".to_string()),
+                                });
+                            }
+                        }
                         LspCommand::SignatureHelp {
                             request_id,
                             uri,
@@ -2425,6 +2819,7 @@ impl LspTask {
                                 );
                                 let _ = state.async_tx.send(AsyncMessage::LspPulledDiagnostics {
                                     request_id,
+                                    language: state.language.clone(),
                                     uri: uri.as_str().to_string(),
                                     result_id: None,
                                     diagnostics: Vec::new(),
@@ -2768,6 +3163,66 @@ async fn handle_message_dispatch(
                         error: None,
                     }
                 }
+                "workspace/applyEdit" => {
+                    let params = request
+                        .params
+                        .clone()
+                        .and_then(|p| serde_json::from_value::<ApplyWorkspaceEditParams>(p).ok());
+                    match params {
+                        Some(params) => {
+                            // The main loop applies the edit; answer once it has, without
+                            // holding up this reader task
+                            let (tx, rx) = oneshot::channel();
+                            let _ = async_tx.send(AsyncMessage::LspApplyEdit {
+                                language: language.to_string(),
+                                label: params.label,
+                                edit: params.edit,
+                                response: tx,
+                            });
+                            let stdin_writer = stdin_writer.clone();
+                            let id = request.id;
+                            tokio::spawn(async move {
+                                let result = match tokio::time::timeout(
+                                    std::time::Duration::from_secs(APPLY_EDIT_TIMEOUT_SECS),
+                                    rx,
+                                )
+                                .await
+                                {
+                                    Ok(Ok(result)) => result,
+                                    _ => ApplyWorkspaceEditResponse {
+                                        applied: false,
+                                        failure_reason: Some(
+                                            "Editor did not apply the edit".to_string(),
+                                        ),
+                                        failed_change: None,
+                                    },
+                                };
+                                let response = JsonRpcResponse {
+                                    jsonrpc: "2.0".to_string(),
+                                    id,
+                                    result: serde_json::to_value(result).ok(),
+                                    error: None,
+                                };
+                                if let Err(e) =
+                                    write_server_response(&stdin_writer, &response).await
+                                {
+                                    tracing::error!("{}", e);
+                                }
+                            });
+                            return Ok(());
+                        }
+                        None => JsonRpcResponse {
+                            jsonrpc: "2.0".to_string(),
+                            id: request.id,
+                            result: None,
+                            error: Some(JsonRpcError {
+                                code: -32602,
+                                message: "Invalid workspace/applyEdit params".to_string(),
+                                data: None,
+                            }),
+                        },
+                    }
+                }
                 _ => {
                     // For unknown methods, notify plugins and return null to acknowledge receipt
                     tracing::debug!("Server request for plugins: {}", request.method);
@@ -2786,25 +3241,34 @@ async fn handle_message_dispatch(
                 }
             };
 
-            // Write response directly to stdin (avoids deadlock when main loop is waiting for LSP response)
-            let json = serde_json::to_string(&response)
-                .map_err(|e| format!("Failed to serialize response: {}", e))?;
-            let message = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
-
-            let mut stdin = stdin_writer.lock().await;
-            use tokio::io::AsyncWriteExt;
-            if let Err(e) = stdin.write_all(message.as_bytes()).await {
-                tracing::error!("Failed to write server response: {}", e);
-            }
-            if let Err(e) = stdin.flush().await {
-                tracing::error!("Failed to flush server response: {}", e);
-            }
-            tracing::trace!("Sent response to server request id={}", response.id);
+            write_server_response(stdin_writer, &response).await?;
         }
     }
     Ok(())
 }
 
+/// Answer a server-to-client request
+///
+/// Writes directly to stdin (avoids deadlock when main loop is waiting for LSP response)
+async fn write_server_response(
+    stdin_writer: &Arc<tokio::sync::Mutex<ChildStdin>>,
+    response: &JsonRpcResponse,
+) -> Result<(), String> {
+    let json = serde_json::to_string(response)
+        .map_err(|e| format!("Failed to serialize response: {}", e))?;
+    let message = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
+
+    let mut stdin = stdin_writer.lock().await;
+    if let Err(e) = stdin.write_all(message.as_bytes()).await {
+        tracing::error!("Failed to write server response: {}", e);
+    }
+    if let Err(e) = stdin.flush().await {
+        tracing::error!("Failed to flush server response: {}", e);
+    }
+    tracing::trace!("Sent response to server request id={}", response.id);
+    Ok(())
+}
+
 /// Standalone function to handle notifications (for reader task)
 async fn handle_notification_dispatch(
     notification: JsonRpcNotification,
@@ -2825,6 +3289,7 @@ async fn handle_notification_dispatch(
 
                 // Send to main loop
                 let _ = async_tx.send(AsyncMessage::LspDiagnostics {
+                    language: language.to_string(),
                     uri: params.uri.to_string(),
                     diagnostics: params.diagnostics,
                 });
@@ -3223,7 +3688,9 @@ impl LspHandle {
     /// will receive it with the specified language_id).
     pub fn did_open(&self, uri: Uri, text: String, language_id: String) -> Result<(), String> {
         // Verify the document language matches this handle's language
-        if language_id != self.language {
+        // (additional servers are keyed `{language}/{server}`)
+        let handle_language = self.language.split('/').next().unwrap_or_default();
+        if language_id != handle_language {
             tracing::warn!(
                 "did_open: document language '{}' does not match LSP handle language '{}' for {}",
                 language_id,
@@ -3287,10 +3754,11 @@ impl LspHandle {
             .map_err(|_| "Failed to send completion command".to_string())
     }
 
-    /// Request go-to-definition
+    /// Request go-to-definition, type definition or implementation
     pub fn goto_definition(
         &self,
         request_id: u64,
+        target: GotoTarget,
         uri: Uri,
         line: u32,
         character: u32,
@@ -3298,6 +3766,7 @@ impl LspHandle {
         self.command_tx
             .try_send(LspCommand::GotoDefinition {
                 request_id,
+                target,
                 uri,
                 line,
                 character,
@@ -3361,6 +3830,42 @@ impl LspHandle {
             .map_err(|_| "Failed to send references command".to_string())
     }
 
+    /// Request occurrences of the symbol under the cursor
+    pub fn document_highlight(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentHighlight {
+                request_id,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send document highlight command".to_string())
+    }
+
+    /// Request formatting edits for the whole document (`range` None) or a range
+    pub fn formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        range: Option<lsp_types::Range>,
+        options: lsp_types::FormattingOptions,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::Formatting {
+                request_id,
+                uri,
+                range,
+                options,
+            })
+            .map_err(|_| "Failed to send formatting command".to_string())
+    }
+
     /// Request signature help
     pub fn signature_help(
         &self,
@@ -2,9 +2,12 @@
 //!
 //! This module handles converting LSP diagnostics to visual overlays in the editor.
 //! Diagnostics are displayed as colored underlines (red for errors, yellow for warnings, etc.)
+//! and, when enabled in `[diagnostics]`, as end-of-line virtual text ("error lens").
+use crate::config::DiagnosticsConfig;
 use crate::model::buffer::Buffer;
 use crate::state::EditorState;
 use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
 use lsp_types::{Diagnostic, DiagnosticSeverity};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
@@ -17,6 +20,16 @@ pub fn lsp_diagnostic_namespace() -> OverlayNamespace {
     OverlayNamespace::from_string("lsp-diagnostic".to_string())
 }
 
+/// Namespace for the end-of-line diagnostic messages
+pub fn lsp_diagnostic_lens_namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string("lsp-diagnostic-lens".to_string())
+}
+
+/// Namespace for the per-line diagnostic background tint
+pub fn lsp_diagnostic_line_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("lsp-diagnostic-line".to_string())
+}
+
 /// Cache for diagnostic hash to avoid redundant updates, keyed by file path.
 /// This prevents diagnostics from one buffer from invalidating another buffer's cache.
 static DIAGNOSTIC_CACHE: LazyLock<Mutex<HashMap<String, u64>>> =
@@ -199,6 +212,145 @@ pub fn apply_diagnostics_to_state(
     }
 }
 
+/// Rank a severity for the lens (lower is more severe), or None if the
+/// config hides it
+fn lens_rank(severity: Option<DiagnosticSeverity>, config: &DiagnosticsConfig) -> Option<u8> {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => config.show_errors.then_some(0),
+        Some(DiagnosticSeverity::WARNING) => config.show_warnings.then_some(1),
+        Some(DiagnosticSeverity::INFORMATION) => config.show_info.then_some(2),
+        Some(DiagnosticSeverity::HINT) | None => config.show_hints.then_some(3),
+        _ => None,
+    }
+}
+
+/// Blend a diagnostic background halfway toward the editor background so the
+/// line tint stays weaker than the diagnostic range itself
+fn line_tint(
+    color: ratatui::style::Color,
+    theme: &crate::view::theme::Theme,
+) -> ratatui::style::Color {
+    use crate::view::theme::color_to_rgb;
+    match (color_to_rgb(color), color_to_rgb(theme.editor_bg)) {
+        (Some((r, g, b)), Some((br, bg, bb))) => ratatui::style::Color::Rgb(
+            ((r as u16 + br as u16) / 2) as u8,
+            ((g as u16 + bg as u16) / 2) as u8,
+            ((b as u16 + bb as u16) / 2) as u8,
+        ),
+        _ => color,
+    }
+}
+
+/// Apply inline diagnostic messages and line tinting to editor state
+///
+/// For each line, the most severe diagnostic enabled in `config` (earliest on
+/// the line for ties) is shown as dimmed text after the line end, and the line
+/// background is optionally tinted by its severity.
+pub fn apply_diagnostic_lens(
+    state: &mut EditorState,
+    diagnostics: &[Diagnostic],
+    config: &DiagnosticsConfig,
+    theme: &crate::view::theme::Theme,
+) {
+    let vtext_ns = lsp_diagnostic_lens_namespace();
+    let line_ns = lsp_diagnostic_line_namespace();
+    state
+        .virtual_texts
+        .clear_namespace(&mut state.marker_list, &vtext_ns);
+    state
+        .overlays
+        .clear_namespace(&line_ns, &mut state.marker_list);
+
+    if !config.is_enabled() {
+        return;
+    }
+
+    // line -> (rank, start character, diagnostic)
+    let mut per_line: HashMap<usize, (u8, u32, &Diagnostic)> = HashMap::new();
+    for diagnostic in diagnostics {
+        let Some(rank) = lens_rank(diagnostic.severity, config) else {
+            continue;
+        };
+        let line = diagnostic.range.start.line as usize;
+        let key = (rank, diagnostic.range.start.character);
+        match per_line.get(&line) {
+            Some(&(r, c, _)) if (r, c) <= key => {}
+            _ => {
+                per_line.insert(line, (key.0, key.1, diagnostic));
+            }
+        }
+    }
+
+    let buffer_len = state.buffer.len();
+    for (line, (_, _, diagnostic)) in per_line {
+        let Some(line_start) = state.buffer.line_start_offset(line) else {
+            continue;
+        };
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .filter(|&next| next > line_start)
+            .map(|next| next - 1)
+            .unwrap_or(buffer_len);
+
+        let (fg, bg) = match diagnostic.severity {
+            Some(DiagnosticSeverity::ERROR) => {
+                (theme.diagnostic_error_fg, theme.diagnostic_error_bg)
+            }
+            Some(DiagnosticSeverity::WARNING) => {
+                (theme.diagnostic_warning_fg, theme.diagnostic_warning_bg)
+            }
+            Some(DiagnosticSeverity::INFORMATION) => {
+                (theme.diagnostic_info_fg, theme.diagnostic_info_bg)
+            }
+            _ => (theme.diagnostic_hint_fg, theme.diagnostic_hint_bg),
+        };
+
+        let tint = line_tint(bg, theme);
+        if config.line_background {
+            let overlay = Overlay::with_namespace(
+                &mut state.marker_list,
+                line_start..line_end,
+                OverlayFace::Background { color: tint },
+                line_ns.clone(),
+            )
+            .with_priority_value(0)
+            .with_extend_to_line_end(true);
+            state.overlays.add(overlay);
+        }
+
+        if config.inline_text {
+            // A final line without a newline anchors on its last character
+            let anchor = if line_end < buffer_len {
+                line_end
+            } else if buffer_len > line_start {
+                buffer_len - 1
+            } else {
+                continue;
+            };
+            let message = diagnostic.message.lines().next().unwrap_or("").trim();
+            if message.is_empty() {
+                continue;
+            }
+            let mut style = ratatui::style::Style::default()
+                .fg(fg)
+                .add_modifier(ratatui::style::Modifier::DIM);
+            if config.line_background {
+                style = style.bg(tint);
+            }
+            state.virtual_texts.add_inline(
+                &mut state.marker_list,
+                anchor,
+                message.to_string(),
+                style,
+                VirtualTextPosition::EndOfLine,
+                vtext_ns.clone(),
+                0,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,4 +499,80 @@ mod tests {
         assert_eq!(range.start, 3);
         assert_eq!(range.end, 8);
     }
+
+    fn lens_diagnostic(
+        line: u32,
+        character: u32,
+        severity: DiagnosticSeverity,
+        msg: &str,
+    ) -> Diagnostic {
+        Diagnostic {
+            range: Range {
+                start: Position { line, character },
+                end: Position {
+                    line,
+                    character: character + 1,
+                },
+            },
+            severity: Some(severity),
+            message: msg.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_lens_picks_most_severe_enabled_per_line() {
+        use crate::model::cursor::Cursors;
+        use crate::model::event::{CursorId, Event};
+
+        let mut state = EditorState::new(
+            80,
+            24,
+            crate::config::LARGE_FILE_THRESHOLD_BYTES as usize,
+            std::sync::Arc::new(crate::model::filesystem::StdFileSystem),
+        );
+        state.apply(
+            &mut Cursors::new(),
+            &Event::Insert {
+                position: 0,
+                text: "one\ntwo\nthree".to_string(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            },
+        );
+
+        let diagnostics = vec![
+            lens_diagnostic(0, 0, DiagnosticSeverity::WARNING, "warn"),
+            lens_diagnostic(0, 2, DiagnosticSeverity::ERROR, "err\ndetail"),
+            lens_diagnostic(1, 0, DiagnosticSeverity::HINT, "hint"),
+            lens_diagnostic(2, 1, DiagnosticSeverity::INFORMATION, "info"),
+        ];
+        let config = DiagnosticsConfig {
+            inline_text: true,
+            ..Default::default()
+        };
+        let theme = crate::view::theme::Theme::load_builtin(theme::THEME_DARK).unwrap();
+        apply_diagnostic_lens(&mut state, &diagnostics, &config, &theme);
+
+        let texts: Vec<(usize, String)> = state
+            .virtual_texts
+            .query_inline_in_range(&state.marker_list, 0, state.buffer.len())
+            .into_iter()
+            .map(|(pos, vtext)| (pos, vtext.text.clone()))
+            .collect();
+        // Line 1 anchors on its newline, the hint is hidden, and the last
+        // line (no newline) anchors on its final character
+        assert_eq!(
+            texts,
+            vec![(3, "err".to_string()), (12, "info".to_string())]
+        );
+
+        // Disabling everything clears the previous lens
+        apply_diagnostic_lens(
+            &mut state,
+            &diagnostics,
+            &DiagnosticsConfig::default(),
+            &theme,
+        );
+        assert!(state.virtual_texts.is_empty());
+    }
 }
@@ -1,7 +1,8 @@
 //! LSP Manager - manages multiple language servers using async I/O
 //!
 //! This module provides a manager for multiple LSP servers that:
-//! - Spawns one server per language
+//! - Spawns one main server per language, plus any additional servers
+//!   configured for it
 //! - Uses async LspHandle for non-blocking I/O
 //! - Routes requests to appropriate servers
 //! - Configured via config.json
@@ -38,6 +39,10 @@ pub struct LspManager {
     /// Configuration for each language
     config: HashMap<String, LspServerConfig>,
 
+    /// Keys of the additional servers attached to each language.
+    /// Additional servers are stored in `handles` and `config` under these keys.
+    additional_servers: HashMap<String, Vec<String>>,
+
     /// Default root URI for workspace (used if no per-language root is set)
     root_uri: Option<Uri>,
 
@@ -70,6 +75,9 @@ pub struct LspManager {
     /// Completion trigger characters per language (from server capabilities)
     completion_trigger_characters: HashMap<String, Vec<String>>,
 
+    /// Signature help trigger characters per language (from server capabilities)
+    signature_help_trigger_characters: HashMap<String, Vec<String>>,
+
     /// Semantic token legends per language (from server capabilities)
     semantic_token_legends: HashMap<String, SemanticTokensLegend>,
 
@@ -81,6 +89,9 @@ pub struct LspManager {
 
     /// Whether a language supports range semantic tokens
     semantic_tokens_range_support: HashMap<String, bool>,
+
+    /// Names of the features each server supports (from server capabilities)
+    server_features: HashMap<String, Vec<String>>,
 }
 
 impl LspManager {
@@ -89,6 +100,7 @@ impl LspManager {
         Self {
             handles: HashMap::new(),
             config: HashMap::new(),
+            additional_servers: HashMap::new(),
             root_uri,
             per_language_root_uris: HashMap::new(),
             runtime: None,
@@ -99,10 +111,12 @@ impl LspManager {
             allowed_languages: HashSet::new(),
             disabled_languages: HashSet::new(),
             completion_trigger_characters: HashMap::new(),
+            signature_help_trigger_characters: HashMap::new(),
             semantic_token_legends: HashMap::new(),
             semantic_tokens_full_support: HashMap::new(),
             semantic_tokens_full_delta_support: HashMap::new(),
             semantic_tokens_range_support: HashMap::new(),
+            server_features: HashMap::new(),
         }
     }
 
@@ -138,6 +152,12 @@ impl LspManager {
         self.completion_trigger_characters.get(language)
     }
 
+    /// Set signature help trigger characters for a language
+    pub fn set_signature_help_trigger_characters(&mut self, language: &str, chars: Vec<String>) {
+        self.signature_help_trigger_characters
+            .insert(language.to_string(), chars);
+    }
+
     /// Store semantic token capability information for a language
     pub fn set_semantic_tokens_capabilities(
         &mut self,
@@ -190,10 +210,39 @@ impl LspManager {
             .unwrap_or(&false)
     }
 
+    /// Set the names of the features a server supports
+    pub fn set_server_features(&mut self, language: &str, features: Vec<String>) {
+        self.server_features.insert(language.to_string(), features);
+    }
+
+    /// Get the names of the features a server supports (empty until it is initialized)
+    pub fn server_features(&self, language: &str) -> &[String] {
+        self.server_features
+            .get(language)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// Check if a character is a completion trigger for any running language server
     pub fn is_completion_trigger_char(&self, ch: char, language: &str) -> bool {
         let ch_str = ch.to_string();
-        self.completion_trigger_characters
+        std::iter::once(language)
+            .chain(
+                self.additional_server_keys(language)
+                    .iter()
+                    .map(String::as_str),
+            )
+            .any(|server| {
+                self.completion_trigger_characters
+                    .get(server)
+                    .is_some_and(|chars| chars.contains(&ch_str))
+            })
+    }
+
+    /// Check if a character triggers signature help for the language's server
+    pub fn is_signature_help_trigger_char(&self, ch: char, language: &str) -> bool {
+        let ch_str = ch.to_string();
+        self.signature_help_trigger_characters
             .get(language)
             .map(|chars| chars.contains(&ch_str))
             .unwrap_or(false)
@@ -250,9 +299,60 @@ impl LspManager {
 
     /// Set configuration for a language
     pub fn set_language_config(&mut self, language: String, config: LspServerConfig) {
+        for key in self
+            .additional_servers
+            .remove(&language)
+            .unwrap_or_default()
+        {
+            self.config.remove(&key);
+        }
+        let keys: Vec<String> = config
+            .additional_servers
+            .iter()
+            .map(|server| {
+                let key = format!("{}/{}", language, server.name);
+                self.config
+                    .insert(key.clone(), server.to_server_config(config.auto_start));
+                key
+            })
+            .collect();
+        if !keys.is_empty() {
+            self.additional_servers.insert(language.clone(), keys);
+        }
         self.config.insert(language, config);
     }
 
+    /// Keys of the additional servers configured for a language
+    ///
+    /// Each key (`<language>/<name>`) can be used wherever a language is
+    /// expected to address that server directly.
+    pub fn additional_server_keys(&self, language: &str) -> &[String] {
+        self.additional_servers
+            .get(language)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the running additional servers for a language, with their keys
+    pub fn additional_handles(&self, language: &str) -> Vec<(&str, &LspHandle)> {
+        self.additional_server_keys(language)
+            .iter()
+            .filter_map(|key| self.handles.get(key).map(|h| (key.as_str(), h)))
+            .collect()
+    }
+
+    /// Get the language a server serves
+    ///
+    /// Returns the language itself for main servers and the owning language
+    /// for additional servers.
+    pub fn server_language<'a>(&'a self, server: &'a str) -> &'a str {
+        self.additional_servers
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|key| key == server))
+            .map(|(language, _)| language.as_str())
+            .unwrap_or(server)
+    }
+
     /// Set a new root URI for the workspace
     ///
     /// This should be called after shutting down all servers when switching projects.
@@ -290,7 +390,7 @@ impl LspManager {
     /// Returns the language-specific root if set, otherwise the default root.
     pub fn get_effective_root_uri(&self, language: &str) -> Option<Uri> {
         self.per_language_root_uris
-            .get(language)
+            .get(self.server_language(language))
             .cloned()
             .or_else(|| self.root_uri.clone())
     }
@@ -433,6 +533,13 @@ impl LspManager {
                     language
                 );
                 self.handles.insert(language.to_string(), handle);
+
+                // Additional servers run alongside the main server
+                for key in self.additional_server_keys(language).to_vec() {
+                    if self.force_spawn(&key).is_none() {
+                        tracing::warn!("Failed to start additional LSP server {}", key);
+                    }
+                }
                 self.handles.get_mut(language)
             }
             Err(e) => {
@@ -593,9 +700,18 @@ impl LspManager {
         // Add to allowed languages so it stays active even if auto_start=false
         self.allowed_languages.insert(language.to_string());
 
-        // Remove existing handle
-        if let Some(handle) = self.handles.remove(language) {
-            let _ = handle.shutdown();
+        // Remove existing handles, including the additional servers which are
+        // started again together with the main server
+        for key in std::iter::once(language.to_string())
+            .chain(self.additional_server_keys(language).to_vec())
+        {
+            if key != language {
+                self.clear_cooldown(&key);
+                self.disabled_languages.remove(&key);
+            }
+            if let Some(handle) = self.handles.remove(&key) {
+                let _ = handle.shutdown();
+            }
         }
 
         // Spawn new server (bypassing auto_start for user-initiated restart)
@@ -660,6 +776,14 @@ impl LspManager {
             // Also remove from allowed languages so it will require confirmation again
             // if user tries to start it later
             self.allowed_languages.remove(language);
+            // Stop the additional servers along with the main server
+            for key in self.additional_server_keys(language).to_vec() {
+                if let Some(handle) = self.handles.remove(&key) {
+                    let _ = handle.shutdown();
+                }
+                self.disabled_languages.insert(key.clone());
+                self.pending_restarts.remove(&key);
+            }
             true
         } else {
             tracing::warn!("No running LSP server found for {}", language);
@@ -742,6 +866,7 @@ mod tests {
             process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
             auto_start: false,
             initialization_options: None,
+            additional_servers: vec![],
         };
 
         manager.set_language_config("rust".to_string(), config);
@@ -751,6 +876,41 @@ mod tests {
         assert!(manager.config.get("rust").unwrap().enabled);
     }
 
+    #[test]
+    fn test_lsp_manager_registers_additional_servers() {
+        let mut manager = LspManager::new(None);
+
+        let config = LspServerConfig {
+            enabled: true,
+            command: "pylsp".to_string(),
+            args: vec![],
+            process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
+            auto_start: true,
+            initialization_options: None,
+            additional_servers: vec![crate::types::AdditionalLspServerConfig {
+                name: "ruff".to_string(),
+                command: "ruff".to_string(),
+                args: vec!["server".to_string()],
+                enabled: true,
+                process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
+                initialization_options: None,
+            }],
+        };
+
+        manager.set_language_config("python".to_string(), config);
+
+        assert_eq!(manager.additional_server_keys("python"), ["python/ruff"]);
+        assert!(manager.additional_server_keys("rust").is_empty());
+        let extra = manager.config.get("python/ruff").unwrap();
+        assert_eq!(extra.command, "ruff");
+        assert!(
+            extra.auto_start,
+            "Extras start together with the main server"
+        );
+        assert_eq!(manager.server_language("python/ruff"), "python");
+        assert_eq!(manager.server_language("python"), "python");
+    }
+
     #[test]
     fn test_lsp_manager_force_spawn_no_runtime() {
         let mut manager = LspManager::new(None);
@@ -765,6 +925,7 @@ mod tests {
                 process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
                 auto_start: false,
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -804,6 +965,7 @@ mod tests {
                 process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
                 auto_start: false,
                 initialization_options: None,
+                additional_servers: vec![],
             },
         );
 
@@ -839,6 +1001,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
         languages.insert(
@@ -857,6 +1021,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
         languages.insert(
@@ -875,6 +1041,8 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                repl: None,
+                linters: Vec::new(),
             },
         );
         languages
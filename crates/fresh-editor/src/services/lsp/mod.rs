@@ -30,7 +30,7 @@
 //! │                         LspManager                                      │
 //! │                                                                         │
 //! │  - One manager per editor instance                                     │
-//! │  - Manages multiple language servers (one per language)                │
+//! │  - Manages multiple language servers (one or more per language)        │
 //! │  - Handles server lifecycle (spawn, restart, shutdown)                 │
 //! │  - Restart throttling with exponential backoff                         │
 //! │  - Manual start/stop support via command palette                       │
@@ -106,6 +106,14 @@
 //! - If not, we send `didOpen` first, then add the ID to the set
 //! - This handles: multiple servers per buffer, server restarts (new ID)
 //!
+//! # Multiple Servers per Language
+//!
+//! A language can list `additional_servers` next to its main server. They
+//! start and stop with the main server and are keyed `<language>/<name>` in
+//! the manager. Buffers are synced with all of them; completions and
+//! diagnostics are merged, formatting goes to the first server that supports
+//! it, and every other request goes to the main server.
+//!
 //! # Error Handling
 //!
 //! - **Server crashes**: Automatic restart with exponential backoff
@@ -120,4 +128,4 @@ pub mod manager;
 pub mod semantic_tokens;
 
 // Re-export for public API (used by tests)
-pub use crate::types::LspServerConfig;
+pub use crate::types::{AdditionalLspServerConfig, LspServerConfig};
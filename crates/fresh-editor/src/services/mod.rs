@@ -5,17 +5,33 @@
 
 pub mod async_bridge;
 pub mod clipboard;
+pub mod collab;
+pub mod control_socket;
+pub mod crash_report;
+pub mod dap;
+pub mod event_bus;
 pub mod fs;
+pub mod git;
 #[cfg(target_os = "linux")]
 pub mod gpm;
+pub mod health;
+pub mod ignore_rules;
+pub mod jobs;
+pub mod linter;
+pub mod local_history;
+pub mod log_buffer;
 pub mod log_dirs;
 pub mod lsp;
+pub mod packages;
+pub mod perf;
 pub mod plugins;
 pub mod process_limits;
+pub mod project_search;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
 pub mod signal_handler;
+pub mod startup_profile;
 pub mod status_log;
 pub mod styled_html;
 pub mod telemetry;
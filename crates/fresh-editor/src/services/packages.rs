@@ -0,0 +1,721 @@
+//! Package management for `fresh --cmd plugin`.
+//!
+//! Works on the same layout as the package manager plugin (`plugins/pkg.ts`):
+//! packages are git checkouts under `<config>/<kind>/packages/<name>`,
+//! registries are cloned to `plugins/packages/.index/<source hash>`, and
+//! `<config>/fresh.lock` records the commit each package is at.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Kind of package, from the `type` field of its `package.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Plugin,
+    Theme,
+    Language,
+    Bundle,
+}
+
+impl PackageKind {
+    const ALL: [PackageKind; 4] = [
+        PackageKind::Plugin,
+        PackageKind::Theme,
+        PackageKind::Language,
+        PackageKind::Bundle,
+    ];
+
+    fn from_manifest_type(kind: &str) -> Option<Self> {
+        match kind {
+            "plugin" => Some(PackageKind::Plugin),
+            "theme" => Some(PackageKind::Theme),
+            "language" => Some(PackageKind::Language),
+            "bundle" => Some(PackageKind::Bundle),
+            _ => None,
+        }
+    }
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            PackageKind::Plugin => "plugins",
+            PackageKind::Theme => "themes",
+            PackageKind::Language => "languages",
+            PackageKind::Bundle => "bundles",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PackageKind::Plugin => "plugin",
+            PackageKind::Theme => "theme",
+            PackageKind::Language => "language",
+            PackageKind::Bundle => "bundle",
+        }
+    }
+}
+
+/// What `fresh --cmd plugin install` was given, split into its parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageUrl {
+    /// Repository URL or path, without the `#subpath` fragment
+    pub repo_url: String,
+    /// Directory of the package inside the repository
+    pub subpath: Option<String>,
+    /// Package name derived from the URL, without a `fresh-` prefix
+    pub name: String,
+}
+
+impl PackageUrl {
+    pub fn parse(url: &str) -> Self {
+        let (repo_url, subpath) = match url.split_once('#') {
+            Some((repo, sub)) => {
+                let sub = sub.trim_matches('/');
+                (repo, (!sub.is_empty()).then(|| sub.to_string()))
+            }
+            None => (url, None),
+        };
+        let last = match &subpath {
+            Some(sub) => sub.rsplit('/').next().unwrap_or(sub),
+            None => {
+                let repo = repo_url.trim_end_matches('/');
+                let last = repo.rsplit(['/', ':']).next().unwrap_or(repo);
+                last.strip_suffix(".git").unwrap_or(last)
+            }
+        };
+        let name = match last.strip_prefix("fresh-").unwrap_or(last) {
+            "" => "unknown".to_string(),
+            name => name.to_string(),
+        };
+        PackageUrl {
+            repo_url: repo_url.to_string(),
+            subpath,
+            name,
+        }
+    }
+}
+
+/// Whether `spec` names a registry package rather than a URL or path
+pub fn is_registry_name(spec: &str) -> bool {
+    !spec.is_empty() && !spec.starts_with(['.', '~']) && !spec.contains(['/', '\\', ':', '@', '#'])
+}
+
+/// Directory name of a registry source's clone (djb2, as in `pkg.ts`)
+///
+/// Follows the JavaScript arithmetic exactly so both sides find the same
+/// clone: the shift wraps to 32 bits while the running sum does not.
+pub fn source_hash(source: &str) -> String {
+    let mut hash: i64 = 5381;
+    for unit in source.encode_utf16() {
+        hash = i64::from((hash as i32).wrapping_shl(5)) + hash + i64::from(unit);
+    }
+    let hex = format!("{:x}", hash.unsigned_abs());
+    hex.chars().take(8).collect()
+}
+
+/// An installed package
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: PackageKind,
+    /// URL it was installed from, empty if unknown
+    pub source: String,
+    pub version: String,
+}
+
+impl InstalledPackage {
+    /// Names the plugins of this package are loaded under
+    ///
+    /// Like any plugin directory, each `.ts`/`.js` file at the top of the
+    /// package is a plugin named after the file.
+    pub fn plugin_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(&self.path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                matches!(path.extension().and_then(|e| e.to_str()), Some("ts" | "js"))
+                    && !path.to_string_lossy().contains(".i18n.")
+            })
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn is_git_checkout(&self) -> bool {
+        self.path.join(".git").exists()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    name: Option<String>,
+    version: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    /// Package contents (`entry`, `grammar`, `languages`, ...), checked
+    /// loosely like the package manager plugin does
+    fresh: Option<serde_json::Value>,
+}
+
+/// Check a package the way `validatePackage` in `pkg.ts` does, returning
+/// its name and kind
+fn validate_package(dir: &Path, manifest: &Manifest) -> Result<(String, PackageKind)> {
+    let name = manifest
+        .name
+        .as_deref()
+        .context("invalid package.json - missing 'name' field")?;
+    // The name becomes a directory under the packages dir
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        bail!(
+            "invalid package.json - '{}' is not a valid package name",
+            name
+        );
+    }
+    let kind = manifest.kind.as_deref().context(
+        "invalid package.json - missing 'type' field (should be 'plugin', 'theme', 'language', or 'bundle')",
+    )?;
+    let kind = PackageKind::from_manifest_type(kind).with_context(|| {
+        format!(
+            "invalid package.json - 'type' must be 'plugin', 'theme', 'language', or 'bundle', got '{}'",
+            kind
+        )
+    })?;
+
+    let fresh = manifest.fresh.clone().unwrap_or_default();
+    let field = |value: &serde_json::Value, key: &str| -> Option<String> {
+        Some(value.get(key)?.as_str()?.to_string())
+    };
+    let list = |key: &str| {
+        fresh
+            .get(key)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    // Entry files may be given as `.ts` and shipped as `.js`
+    let entry_exists = |entry: &str| {
+        dir.join(entry).exists()
+            || entry
+                .strip_suffix(".ts")
+                .is_some_and(|stem| dir.join(format!("{}.js", stem)).exists())
+    };
+    let check_grammar = |grammar: Option<&serde_json::Value>, what: &str| -> Result<()> {
+        if let Some(file) = grammar.and_then(|g| field(g, "file")) {
+            if !dir.join(&file).exists() {
+                bail!("grammar file not found{}: {}", what, file);
+            }
+        }
+        Ok(())
+    };
+
+    match kind {
+        PackageKind::Plugin => {
+            let entry = field(&fresh, "entry").unwrap_or_else(|| format!("{}.ts", name));
+            if !entry_exists(&entry) {
+                bail!(
+                    "missing entry file '{}' - check fresh.entry in package.json",
+                    entry
+                );
+            }
+        }
+        PackageKind::Language => {
+            if ["grammar", "language", "lsp"]
+                .iter()
+                .all(|key| fresh.get(key).is_none_or(|v| v.is_null()))
+            {
+                bail!("language package must define at least one of: grammar, language, or lsp");
+            }
+            check_grammar(fresh.get("grammar"), "")?;
+        }
+        PackageKind::Bundle => {
+            let (languages, plugins) = (list("languages"), list("plugins"));
+            if languages.is_empty() && plugins.is_empty() && list("themes").is_empty() {
+                bail!("bundle package must define at least one language, plugin, or theme");
+            }
+            for language in &languages {
+                let id = field(language, "id")
+                    .context("bundle language entry missing required 'id' field")?;
+                check_grammar(language.get("grammar"), &format!(" for language '{}'", id))?;
+            }
+            for plugin in &plugins {
+                let entry = field(plugin, "entry")
+                    .context("bundle plugin entry missing required 'entry' field")?;
+                if !entry_exists(&entry) {
+                    bail!("plugin entry file not found: {}", entry);
+                }
+            }
+        }
+        PackageKind::Theme => {}
+    }
+    Ok((name.to_string(), kind))
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest> {
+    let path = dir.join("package.json");
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("missing {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("invalid {}", path.display()))
+}
+
+/// Source recorded for packages copied out of a monorepo
+#[derive(Debug, Deserialize)]
+struct SourceInfo {
+    installed_from: Option<String>,
+    original_url: Option<String>,
+}
+
+/// `fresh.lock`, in the format the package manager plugin reads
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub lockfile_version: u32,
+    pub generated: String,
+    pub packages: BTreeMap<String, LockfileEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockfileEntry {
+    pub source: String,
+    pub commit: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RegistryData {
+    #[serde(default)]
+    packages: BTreeMap<String, RegistryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryEntry {
+    repository: String,
+}
+
+/// Run git without prompting for credentials
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-c", "credential.helper=", "-c", "core.askPass="])
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{}", stderr.lines().next().unwrap_or("git failed").trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Packages installed under one config directory
+pub struct PackageManager {
+    config_dir: PathBuf,
+    /// Registry sources (`packages.sources` in the config)
+    sources: Vec<String>,
+}
+
+impl PackageManager {
+    pub fn new(config_dir: PathBuf, sources: Vec<String>) -> Self {
+        Self {
+            config_dir,
+            sources,
+        }
+    }
+
+    fn packages_dir(&self, kind: PackageKind) -> PathBuf {
+        self.config_dir.join(kind.dir_name()).join("packages")
+    }
+
+    fn index_dir(&self) -> PathBuf {
+        self.packages_dir(PackageKind::Plugin).join(".index")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.packages_dir(PackageKind::Plugin).join(".cache")
+    }
+
+    pub fn lockfile_path(&self) -> PathBuf {
+        self.config_dir.join("fresh.lock")
+    }
+
+    /// Clone or pull every registry source
+    pub fn sync_registry(&self) -> Result<()> {
+        std::fs::create_dir_all(self.index_dir())?;
+        for source in &self.sources {
+            let dir = self.index_dir().join(source_hash(source));
+            let dir = dir.to_string_lossy();
+            let result = if Path::new(dir.as_ref()).exists() {
+                git(&["-C", &dir, "pull", "--ff-only"])
+            } else {
+                git(&["clone", "--depth", "1", "--", source, &dir])
+            };
+            result.with_context(|| format!("failed to sync registry {}", source))?;
+        }
+        Ok(())
+    }
+
+    /// Repository of a registry package, from the synced or cached indices
+    fn find_in_registry(&self, name: &str) -> Option<String> {
+        self.sources.iter().find_map(|source| {
+            let hash = source_hash(source);
+            ["plugins", "themes", "languages"].iter().find_map(|kind| {
+                [
+                    self.index_dir().join(&hash).join(format!("{}.json", kind)),
+                    self.cache_dir().join(format!("{}_{}.json", hash, kind)),
+                ]
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok())
+                .filter_map(|content| serde_json::from_str::<RegistryData>(&content).ok())
+                .find_map(|mut data| data.packages.remove(name))
+                .map(|entry| entry.repository)
+            })
+        })
+    }
+
+    /// Install a package from a git URL (with an optional `#subpath`) or a
+    /// registry name
+    pub fn install(&self, spec: &str) -> Result<InstalledPackage> {
+        let url = if is_registry_name(spec) {
+            match self.find_in_registry(spec) {
+                Some(repository) => repository,
+                None => {
+                    self.sync_registry()?;
+                    self.find_in_registry(spec)
+                        .with_context(|| format!("no package named '{}' in the registry", spec))?
+                }
+            }
+        } else {
+            spec.to_string()
+        };
+        let parsed = PackageUrl::parse(&url);
+
+        let staging_root = self.packages_dir(PackageKind::Plugin);
+        std::fs::create_dir_all(&staging_root)?;
+        // Hidden, so the plugin loader skips it if we're interrupted
+        let staging = staging_root.join(format!(".install-{}", source_hash(&url)));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        let result = self.install_from_staging(&parsed, &url, &staging);
+        if staging.exists() {
+            let _ = std::fs::remove_dir_all(&staging);
+        }
+        result
+    }
+
+    fn install_from_staging(
+        &self,
+        parsed: &PackageUrl,
+        url: &str,
+        staging: &Path,
+    ) -> Result<InstalledPackage> {
+        git(&[
+            "clone",
+            "--depth",
+            "1",
+            "--",
+            &parsed.repo_url,
+            &staging.to_string_lossy(),
+        ])
+        .with_context(|| format!("failed to clone {}", parsed.repo_url))?;
+
+        let package_dir = match &parsed.subpath {
+            Some(sub) => {
+                let dir = staging.join(sub);
+                if !dir.is_dir() {
+                    bail!("'{}' not found in {}", sub, parsed.repo_url);
+                }
+                dir
+            }
+            None => staging.to_path_buf(),
+        };
+        let manifest = read_manifest(&package_dir)?;
+        let (name, kind) = validate_package(&package_dir, &manifest)
+            .with_context(|| format!("invalid package {}", url))?;
+
+        let target_root = self.packages_dir(kind);
+        let target = target_root.join(&name);
+        if target.exists() {
+            bail!("'{}' is already installed", name);
+        }
+        std::fs::create_dir_all(&target_root)?;
+        std::fs::rename(&package_dir, &target)
+            .with_context(|| format!("failed to move package to {}", target.display()))?;
+        if parsed.subpath.is_some() {
+            let info = serde_json::json!({
+                "repository": parsed.repo_url,
+                "subpath": parsed.subpath,
+                "installed_from": url,
+                "installed_at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            });
+            std::fs::write(
+                target.join(".fresh-source.json"),
+                serde_json::to_string_pretty(&info)?,
+            )?;
+        }
+
+        Ok(InstalledPackage {
+            name,
+            path: target,
+            kind,
+            source: url.to_string(),
+            version: manifest.version.unwrap_or_else(|| "unknown".to_string()),
+        })
+    }
+
+    /// Installed packages of every kind, sorted by name
+    pub fn installed(&self) -> Vec<InstalledPackage> {
+        let mut packages: Vec<InstalledPackage> = PackageKind::ALL
+            .iter()
+            .flat_map(|&kind| {
+                std::fs::read_dir(self.packages_dir(kind))
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(move |entry| {
+                        let name = entry.file_name().to_str()?.to_string();
+                        if name.starts_with('.') {
+                            return None;
+                        }
+                        let path = entry.path();
+                        let manifest = read_manifest(&path).unwrap_or_default();
+                        Some(InstalledPackage {
+                            source: package_source(&path),
+                            version: manifest.version.unwrap_or_else(|| "unknown".to_string()),
+                            name,
+                            path,
+                            kind,
+                        })
+                    })
+            })
+            .collect();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        packages
+    }
+
+    pub fn find(&self, name: &str) -> Result<InstalledPackage> {
+        self.installed()
+            .into_iter()
+            .find(|p| p.name == name)
+            .with_context(|| format!("'{}' is not installed", name))
+    }
+
+    /// How many commits a package is behind its remote, after fetching
+    ///
+    /// `None` for packages that aren't git checkouts (monorepo installs).
+    pub fn commits_behind(&self, package: &InstalledPackage) -> Result<Option<usize>> {
+        if !package.is_git_checkout() {
+            return Ok(None);
+        }
+        let path = package.path.to_string_lossy();
+        git(&["-C", &path, "fetch", "--quiet"])?;
+        let count = git(&["-C", &path, "rev-list", "--count", "HEAD..@{upstream}"])?;
+        Ok(count.parse().ok())
+    }
+
+    /// Fast-forward a package to its remote; returns whether it changed
+    pub fn update(&self, package: &InstalledPackage) -> Result<bool> {
+        if !package.is_git_checkout() {
+            bail!(
+                "'{}' is not a git checkout; reinstall it to update",
+                package.name
+            );
+        }
+        let path = package.path.to_string_lossy();
+        let before = git(&["-C", &path, "rev-parse", "HEAD"])?;
+        git(&["-C", &path, "pull", "--ff-only"])?;
+        let after = git(&["-C", &path, "rev-parse", "HEAD"])?;
+        Ok(before != after)
+    }
+
+    pub fn remove(&self, package: &InstalledPackage) -> Result<()> {
+        std::fs::remove_dir_all(&package.path)
+            .with_context(|| format!("failed to remove {}", package.path.display()))
+    }
+
+    /// Record the commit of every installed plugin and theme in `fresh.lock`
+    pub fn write_lockfile(&self) -> Result<()> {
+        let packages = self
+            .installed()
+            .into_iter()
+            .filter(|p| matches!(p.kind, PackageKind::Plugin | PackageKind::Theme))
+            .map(|p| {
+                let commit = if p.is_git_checkout() {
+                    git(&["-C", &p.path.to_string_lossy(), "rev-parse", "HEAD"]).unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                let entry = LockfileEntry {
+                    source: p.source,
+                    commit,
+                    version: p.version,
+                    integrity: None,
+                };
+                (p.name, entry)
+            })
+            .collect();
+        let lockfile = Lockfile {
+            lockfile_version: 1,
+            generated: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            packages,
+        };
+        std::fs::write(
+            self.lockfile_path(),
+            serde_json::to_string_pretty(&lockfile)?,
+        )
+        .context("failed to write fresh.lock")
+    }
+}
+
+/// URL a package was installed from: its git remote, or the source recorded
+/// when it was copied out of a monorepo
+fn package_source(path: &Path) -> String {
+    if path.join(".git").exists() {
+        if let Ok(url) = git(&["-C", &path.to_string_lossy(), "remote", "get-url", "origin"]) {
+            return url;
+        }
+    }
+    std::fs::read_to_string(path.join(".fresh-source.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SourceInfo>(&content).ok())
+        .and_then(|info| info.installed_from.or(info.original_url))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_url_names() {
+        let parsed = PackageUrl::parse("https://github.com/user/fresh-rainbow.git");
+        assert_eq!(parsed.repo_url, "https://github.com/user/fresh-rainbow.git");
+        assert_eq!(parsed.subpath, None);
+        assert_eq!(parsed.name, "rainbow");
+
+        let parsed = PackageUrl::parse("https://github.com/user/plugins#packages/fresh-todo/");
+        assert_eq!(parsed.repo_url, "https://github.com/user/plugins");
+        assert_eq!(parsed.subpath.as_deref(), Some("packages/fresh-todo"));
+        assert_eq!(parsed.name, "todo");
+
+        assert_eq!(
+            PackageUrl::parse("git@github.com:user/lint.git").name,
+            "lint"
+        );
+
+        assert!(is_registry_name("rainbow-brackets"));
+        assert!(!is_registry_name("git@github.com:user/lint.git"));
+        assert!(!is_registry_name("./local-plugin"));
+    }
+
+    #[test]
+    fn test_source_hash_matches_pkg_plugin() {
+        // Values computed by `hashString` in plugins/pkg.ts
+        assert_eq!(
+            source_hash("https://github.com/sinelaw/fresh-plugins-registry"),
+            "193934da"
+        );
+        assert_eq!(source_hash("a"), "2b606");
+        assert_eq!(source_hash("é☃x"), "b8f78e9");
+    }
+
+    #[test]
+    fn test_validate_package() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let validate = |manifest: &str| {
+            validate_package(dir, &serde_json::from_str(manifest).unwrap()).map(|(_, k)| k)
+        };
+
+        assert!(validate(r#"{"type": "plugin"}"#).is_err());
+        assert!(validate(r#"{"name": "hello"}"#).is_err());
+        assert!(validate(r#"{"name": "hello", "type": "widget"}"#).is_err());
+        assert!(validate(r#"{"name": "../../hello", "type": "theme"}"#).is_err());
+        assert!(validate(r#"{"name": "..", "type": "theme"}"#).is_err());
+        // The entry file is missing
+        assert!(validate(r#"{"name": "hello", "type": "plugin"}"#).is_err());
+        assert!(validate(r#"{"name": "hello", "type": "language"}"#).is_err());
+        assert!(validate(r#"{"name": "hello", "type": "bundle", "fresh": {}}"#).is_err());
+
+        std::fs::write(dir.join("hello.js"), "").unwrap();
+        assert_eq!(
+            validate(r#"{"name": "hello", "type": "plugin"}"#).unwrap(),
+            PackageKind::Plugin
+        );
+        assert_eq!(
+            validate(r#"{"name": "hello", "type": "theme"}"#).unwrap(),
+            PackageKind::Theme
+        );
+        assert!(validate(
+            r#"{"name": "hello", "type": "bundle", "fresh": {"plugins": [{"entry": "other.ts"}]}}"#
+        )
+        .is_err());
+        assert_eq!(
+            validate(
+                r#"{"name": "hello", "type": "bundle", "fresh": {"plugins": [{"entry": "hello.ts"}]}}"#
+            )
+            .unwrap(),
+            PackageKind::Bundle
+        );
+    }
+
+    #[test]
+    fn test_install_writes_lockfile_and_remove() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("fresh-hello");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(
+            repo.join("package.json"),
+            r#"{"name": "hello", "version": "1.2.0", "type": "plugin"}"#,
+        )
+        .unwrap();
+        std::fs::write(repo.join("hello.ts"), "").unwrap();
+        let repo_path = repo.to_string_lossy().to_string();
+        for args in [
+            vec!["init", "--quiet", &repo_path],
+            vec!["-C", &repo_path, "add", "."],
+            vec![
+                "-C",
+                &repo_path,
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "init",
+            ],
+        ] {
+            git(&args).unwrap();
+        }
+
+        let config_dir = temp.path().join("config");
+        let manager = PackageManager::new(config_dir.clone(), Vec::new());
+        let package = manager.install(&format!("file://{}", repo_path)).unwrap();
+        assert_eq!(package.name, "hello");
+        assert_eq!(package.plugin_names(), vec!["hello".to_string()]);
+        assert!(config_dir.join("plugins/packages/hello/hello.ts").exists());
+        assert!(manager.install(&repo_path).is_err());
+        manager.write_lockfile().unwrap();
+
+        let lockfile: Lockfile =
+            serde_json::from_str(&std::fs::read_to_string(manager.lockfile_path()).unwrap())
+                .unwrap();
+        let entry = &lockfile.packages["hello"];
+        assert_eq!(entry.version, "1.2.0");
+        assert_eq!(entry.commit.len(), 40);
+        assert_eq!(manager.commits_behind(&package).unwrap(), Some(0));
+
+        manager.remove(&manager.find("hello").unwrap()).unwrap();
+        assert!(manager.installed().is_empty());
+    }
+}
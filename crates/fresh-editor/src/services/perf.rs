@@ -0,0 +1,98 @@
+//! Timings and counters for the performance HUD
+//!
+//! Subsystems deep in the call tree, such as syntax highlighting, add to
+//! process-wide accumulators, which the HUD drains once per frame. Nothing is
+//! recorded while the HUD is hidden.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMES: [AtomicU64; Phase::ALL.len()] = [const { AtomicU64::new(0) }; Phase::ALL.len()];
+static COUNTS: [AtomicU64; Counter::ALL.len()] = [const { AtomicU64::new(0) }; Counter::ALL.len()];
+
+/// Work whose time is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Applying edit events to buffers
+    EventApply,
+    /// Computing syntax highlighting
+    Highlight,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 2] = [Phase::EventApply, Phase::Highlight];
+}
+
+/// Work whose occurrences are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    /// Key presses handled
+    Keys,
+    /// Edit events applied
+    Events,
+    /// Messages received from LSP servers, plugins and other background tasks
+    AsyncMessages,
+    /// Plugin hooks run
+    PluginHooks,
+}
+
+impl Counter {
+    pub const ALL: [Counter; 4] = [
+        Counter::Keys,
+        Counter::Events,
+        Counter::AsyncMessages,
+        Counter::PluginHooks,
+    ];
+}
+
+/// Start or stop recording
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    for time in &TIMES {
+        time.store(0, Ordering::Relaxed);
+    }
+    for count in &COUNTS {
+        count.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Measure `phase` until the returned timer is dropped
+pub fn timer(phase: Phase) -> Timer {
+    Timer {
+        phase,
+        started: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+    }
+}
+
+/// Count `n` occurrences of `counter`
+pub fn count(counter: Counter, n: u64) {
+    if ENABLED.load(Ordering::Relaxed) {
+        COUNTS[counter as usize].fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+/// Time spent in `phase` since the last call
+pub fn take_time(phase: Phase) -> Duration {
+    Duration::from_nanos(TIMES[phase as usize].swap(0, Ordering::Relaxed))
+}
+
+/// Occurrences of `counter` since the last call
+pub fn take_count(counter: Counter) -> u64 {
+    COUNTS[counter as usize].swap(0, Ordering::Relaxed)
+}
+
+/// Adds the time since it was created to its phase when dropped
+pub struct Timer {
+    phase: Phase,
+    started: Option<Instant>,
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some(started) = self.started {
+            let nanos = started.elapsed().as_nanos() as u64;
+            TIMES[self.phase as usize].fetch_add(nanos, Ordering::Relaxed);
+        }
+    }
+}
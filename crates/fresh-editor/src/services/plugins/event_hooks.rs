@@ -4,7 +4,7 @@
 //! This ensures hooks are triggered consistently whenever state changes occur.
 
 use crate::model::event::Event;
-use crate::services::plugins::hooks::{HookArgs, HookRegistry};
+use crate::services::plugins::hooks::{HookArgs, HookRegistry, TextChange};
 use fresh_core::BufferId;
 use std::sync::RwLock;
 
@@ -87,6 +87,37 @@ impl EventHooks for Event {
     }
 }
 
+/// The buffer edits an event makes, in the order they are applied
+///
+/// BulkEdit events swap whole buffer snapshots and carry no positions; the
+/// caller reports those as a replacement of the entire buffer.
+pub fn text_changes(event: &Event) -> Vec<TextChange> {
+    let mut changes = Vec::new();
+    collect_text_changes(event, &mut changes);
+    changes
+}
+
+fn collect_text_changes(event: &Event, changes: &mut Vec<TextChange>) {
+    match event {
+        Event::Insert { position, text, .. } => changes.push(TextChange {
+            start: *position,
+            removed_len: 0,
+            text: text.clone(),
+        }),
+        Event::Delete { range, .. } => changes.push(TextChange {
+            start: range.start,
+            removed_len: range.len(),
+            text: String::new(),
+        }),
+        Event::Batch { events, .. } => {
+            for event in events {
+                collect_text_changes(event, changes);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Apply an event with automatic hook invocations
 pub fn apply_event_with_hooks(
     state: &mut crate::state::EditorState,
@@ -275,4 +306,48 @@ mod tests {
         assert!(was_applied);
         assert_eq!(state.buffer.to_string().unwrap(), "test");
     }
+
+    #[test]
+    fn test_text_changes_follow_batch_order() {
+        let event = Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: 4..7,
+                    deleted_text: "abc".to_string(),
+                    cursor_id: CursorId(0),
+                },
+                Event::MoveCursor {
+                    cursor_id: CursorId(0),
+                    old_position: 7,
+                    new_position: 4,
+                    old_anchor: None,
+                    new_anchor: None,
+                    old_sticky_column: 0,
+                    new_sticky_column: 0,
+                },
+                Event::Insert {
+                    position: 4,
+                    text: "xy".to_string(),
+                    cursor_id: CursorId(0),
+                },
+            ],
+            description: "replace".to_string(),
+        };
+
+        assert_eq!(
+            text_changes(&event),
+            vec![
+                TextChange {
+                    start: 4,
+                    removed_len: 3,
+                    text: String::new(),
+                },
+                TextChange {
+                    start: 4,
+                    removed_len: 0,
+                    text: "xy".to_string(),
+                },
+            ]
+        );
+    }
 }
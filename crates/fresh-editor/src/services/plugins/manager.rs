@@ -9,7 +9,7 @@ use crate::config_io::DirectoryContext;
 use crate::input::command_registry::CommandRegistry;
 use fresh_core::config::PluginConfig;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 #[cfg(feature = "plugins")]
@@ -26,6 +26,9 @@ pub struct PluginManager {
     inner: Option<PluginThreadHandle>,
     #[cfg(not(feature = "plugins"))]
     _phantom: std::marker::PhantomData<()>,
+    /// Dynamically loaded native plugins (independent of the TypeScript runtime).
+    #[cfg(feature = "runtime")]
+    native: super::native::NativePluginHost,
 }
 
 impl PluginManager {
@@ -49,6 +52,8 @@ impl PluginManager {
                     Ok(handle) => {
                         return Self {
                             inner: Some(handle),
+                            #[cfg(feature = "runtime")]
+                            native: Default::default(),
                         }
                     }
                     Err(e) => {
@@ -60,7 +65,11 @@ impl PluginManager {
             } else {
                 tracing::info!("Plugins disabled via --no-plugins flag");
             }
-            Self { inner: None }
+            Self {
+                inner: None,
+                #[cfg(feature = "runtime")]
+                native: Default::default(),
+            }
         }
 
         #[cfg(not(feature = "plugins"))]
@@ -72,6 +81,8 @@ impl PluginManager {
             }
             Self {
                 _phantom: std::marker::PhantomData,
+                #[cfg(feature = "runtime")]
+                native: Default::default(),
             }
         }
     }
@@ -154,6 +165,62 @@ impl PluginManager {
         (Vec::new(), HashMap::new())
     }
 
+    /// Load plugins from directories without waiting for them, then call
+    /// `done` with the load errors and the plugins discovered in all of them.
+    ///
+    /// Hooks and actions sent after this run once these plugins are loaded,
+    /// so the plugins don't miss any.
+    #[cfg(feature = "plugins")]
+    pub fn load_plugins_in_background(
+        &self,
+        dirs: &[PathBuf],
+        plugin_configs: &HashMap<String, PluginConfig>,
+        done: impl FnOnce(Vec<String>, HashMap<String, PluginConfig>) + Send + 'static,
+    ) {
+        let Some(ref manager) = self.inner else {
+            done(Vec::new(), HashMap::new());
+            return;
+        };
+        let mut errors = Vec::new();
+        let mut receivers = Vec::new();
+        for dir in dirs {
+            match manager.start_loading_plugins_from_dir_with_config(dir, plugin_configs) {
+                Ok(receiver) => receivers.push(receiver),
+                Err(e) => errors.push(e),
+            }
+        }
+        let spawned = std::thread::Builder::new()
+            .name("plugin-load-wait".to_string())
+            .spawn(move || {
+                let mut discovered = HashMap::new();
+                for receiver in receivers {
+                    match receiver.recv() {
+                        Ok((dir_errors, dir_plugins)) => {
+                            errors.extend(dir_errors);
+                            discovered.extend(dir_plugins);
+                        }
+                        Err(_) => errors.push("Plugin thread closed".to_string()),
+                    }
+                }
+                done(errors, discovered);
+            });
+        if let Err(e) = spawned {
+            tracing::error!("Failed to start plugin load thread: {}", e);
+        }
+    }
+
+    /// Load plugins from directories (no-op when plugins disabled).
+    #[cfg(not(feature = "plugins"))]
+    pub fn load_plugins_in_background(
+        &self,
+        dirs: &[PathBuf],
+        plugin_configs: &HashMap<String, PluginConfig>,
+        done: impl FnOnce(Vec<String>, HashMap<String, PluginConfig>) + Send + 'static,
+    ) {
+        let _ = (dirs, plugin_configs);
+        done(Vec::new(), HashMap::new());
+    }
+
     /// Unload a plugin by name.
     pub fn unload_plugin(&self, name: &str) -> anyhow::Result<()> {
         #[cfg(feature = "plugins")]
@@ -186,8 +253,41 @@ impl PluginManager {
         }
     }
 
+    /// Load native plugin libraries from a directory, respecting config.
+    #[cfg(feature = "runtime")]
+    pub fn load_native_plugins(
+        &mut self,
+        dir: &Path,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) {
+        self.native.load_dir(dir, plugin_configs);
+    }
+
+    /// Run an action registered by a native plugin. Returns false if no
+    /// native plugin owns the action.
+    pub fn run_native_action(&self, action_name: &str) -> bool {
+        #[cfg(feature = "runtime")]
+        {
+            self.native.run_action(action_name)
+        }
+        #[cfg(not(feature = "runtime"))]
+        {
+            let _ = action_name;
+            false
+        }
+    }
+
+    /// Take native plugin load errors and crashes to report to the user.
+    #[cfg(feature = "runtime")]
+    pub fn take_native_failures(&mut self) -> Vec<super::native::NativePluginFailure> {
+        self.native.take_failures()
+    }
+
     /// Run a hook (fire-and-forget).
     pub fn run_hook(&self, hook_name: &str, args: super::hooks::HookArgs) {
+        crate::services::perf::count(crate::services::perf::Counter::PluginHooks, 1);
+        #[cfg(feature = "runtime")]
+        self.native.run_hook(hook_name, &args);
         #[cfg(feature = "plugins")]
         {
             if let Some(ref manager) = self.inner {
@@ -216,17 +316,26 @@ impl PluginManager {
 
     /// Process pending plugin commands (non-blocking).
     pub fn process_commands(&mut self) -> Vec<super::api::PluginCommand> {
+        #[allow(unused_mut)]
+        let mut commands = Vec::new();
         #[cfg(feature = "plugins")]
-        {
-            if let Some(ref mut manager) = self.inner {
-                return manager.process_commands();
-            }
-            Vec::new()
+        if let Some(ref mut manager) = self.inner {
+            commands = manager.process_commands();
         }
-        #[cfg(not(feature = "plugins"))]
-        {
-            Vec::new()
+        #[cfg(feature = "runtime")]
+        commands.extend(self.native.process_commands());
+        commands
+    }
+
+    /// Call `wake` whenever a plugin sends a command, so the main loop comes
+    /// back to process it
+    pub fn wake_on_command(&mut self, wake: impl Fn() + Clone + Send + 'static) {
+        #[cfg(feature = "plugins")]
+        if let Some(ref mut manager) = self.inner {
+            manager.wake_on_command(wake.clone());
         }
+        #[cfg(feature = "runtime")]
+        self.native.wake_on_output(wake);
     }
 
     /// Process commands, blocking until `HookCompleted` for the given hook arrives.
@@ -293,6 +402,24 @@ impl PluginManager {
             .reload_plugin(name)
     }
 
+    /// Start the `before_file_save` handlers for a held save.
+    ///
+    /// Returns the plugins that will answer with a `BeforeSaveResponse`.
+    pub fn run_before_save(&self, request_id: u64, args: super::hooks::HookArgs) -> Vec<String> {
+        #[cfg(feature = "plugins")]
+        {
+            self.inner
+                .as_ref()
+                .map(|m| m.run_before_save(request_id, args))
+                .unwrap_or_default()
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (request_id, args);
+            Vec::new()
+        }
+    }
+
     /// Check if any handlers are registered for a hook.
     pub fn has_hook_handlers(&self, hook_name: &str) -> bool {
         #[cfg(feature = "plugins")]
@@ -327,6 +454,46 @@ impl PluginManager {
         let _ = (callback_id, result_json);
     }
 
+    /// Run the callback of a due plugin timer
+    #[cfg(feature = "plugins")]
+    pub fn fire_timer(&self, plugin_name: String, timer_id: u64) {
+        if let Some(inner) = &self.inner {
+            inner.fire_timer(plugin_name, timer_id);
+        }
+    }
+
+    /// Run the callback of a due plugin timer (no-op when plugins disabled)
+    #[cfg(not(feature = "plugins"))]
+    pub fn fire_timer(&self, plugin_name: String, timer_id: u64) {
+        let _ = (plugin_name, timer_id);
+    }
+
+    /// Ask a plugin completion provider for items
+    #[cfg(feature = "plugins")]
+    pub fn provide_completions(
+        &self,
+        plugin_name: String,
+        provider_id: u64,
+        request_id: u64,
+        context: fresh_core::api::CompletionContext,
+    ) {
+        if let Some(inner) = &self.inner {
+            inner.provide_completions(plugin_name, provider_id, request_id, context);
+        }
+    }
+
+    /// Ask a plugin completion provider for items (no-op when plugins disabled)
+    #[cfg(not(feature = "plugins"))]
+    pub fn provide_completions(
+        &self,
+        plugin_name: String,
+        provider_id: u64,
+        request_id: u64,
+        context: fresh_core::api::CompletionContext,
+    ) {
+        let _ = (plugin_name, provider_id, request_id, context);
+    }
+
     /// Reject an async callback in the plugin runtime
     #[cfg(feature = "plugins")]
     pub fn reject_callback(&self, callback_id: super::api::JsCallbackId, error: String) {
@@ -13,6 +13,8 @@ pub mod bridge;
 pub mod event_hooks;
 pub mod hooks;
 pub mod manager;
+#[cfg(feature = "runtime")]
+pub mod native;
 
 #[cfg(feature = "embed-plugins")]
 pub mod embedded;
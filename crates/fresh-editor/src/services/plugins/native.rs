@@ -0,0 +1,650 @@
+//! Native plugin host
+//!
+//! Loads native plugin libraries (`.so`/`.dylib`/`.dll`) from
+//! `~/.config/fresh/plugins/native/` using the ABI in [`fresh_core::native`].
+//!
+//! Each plugin runs on its own thread, so a slow plugin never blocks the
+//! editor. Commands sent by a plugin are queued and drained together with the
+//! TypeScript plugin commands in `PluginManager::process_commands`.
+//!
+//! Crash containment:
+//! - A panic inside a plugin is caught on the plugin side and reported as
+//!   `NATIVE_PANIC`; the host then unloads the plugin and reports it.
+//! - A hard crash (segfault, abort) can't be caught in-process. Before every
+//!   call into a plugin the host writes a `<library>.crash` marker naming the
+//!   call, and removes it afterwards. A marker left over at startup means the
+//!   plugin took the editor down: the plugin is skipped and reported until
+//!   the marker is deleted.
+
+use super::api::PluginCommand;
+use super::hooks::HookArgs;
+use fresh_core::config::PluginConfig;
+use fresh_core::native::{
+    NativeHost, NativePluginDescriptor, DESCRIPTOR_SYMBOL, LOG_DEBUG, LOG_ERROR, LOG_INFO,
+    LOG_WARN, NATIVE_ABI_VERSION, NATIVE_OK, NATIVE_PANIC, NATIVE_UNHANDLED,
+};
+use libloading::Library;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Problems reported by the native plugin host, shown to the user by the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NativePluginFailure {
+    /// The library could not be loaded or initialized.
+    LoadFailed { path: PathBuf, error: String },
+    /// The library was built against another ABI version.
+    AbiMismatch { path: PathBuf, found: u32 },
+    /// A crash marker from a previous session was found; the plugin was skipped.
+    CrashedLastRun {
+        name: String,
+        call: String,
+        marker: PathBuf,
+    },
+    /// The plugin panicked and was unloaded.
+    Crashed { name: String, reason: String },
+    /// A call returned an error; the plugin stays loaded.
+    Error { name: String, message: String },
+}
+
+enum Call {
+    Hook { name: String, payload: String },
+    Action(String),
+    Shutdown,
+}
+
+enum Output {
+    Command(PluginCommand),
+    Failure(NativePluginFailure),
+}
+
+/// State shared with the plugin through `NativeHost::ctx`.
+struct HostContext {
+    name: String,
+    output: Mutex<Sender<Output>>,
+    /// Action name -> plugin name, filled from `RegisterCommand`.
+    actions: Arc<Mutex<HashMap<String, String>>>,
+    /// Last error logged by the plugin, used to explain a failing call.
+    last_error: Mutex<Option<String>>,
+}
+
+struct LoadedPlugin {
+    name: String,
+    hooks: Vec<String>,
+    calls: Sender<Call>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LoadedPlugin {
+    fn wants_hook(&self, hook: &str) -> bool {
+        self.hooks.iter().any(|h| h == "*" || h == hook)
+    }
+}
+
+/// Owns all loaded native plugins.
+pub struct NativePluginHost {
+    plugins: Vec<LoadedPlugin>,
+    actions: Arc<Mutex<HashMap<String, String>>>,
+    output_tx: Sender<Output>,
+    output_rx: Receiver<Output>,
+    pending_failures: Vec<NativePluginFailure>,
+}
+
+impl Default for NativePluginHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativePluginHost {
+    pub fn new() -> Self {
+        let (output_tx, output_rx) = mpsc::channel();
+        Self {
+            plugins: Vec::new(),
+            actions: Arc::new(Mutex::new(HashMap::new())),
+            output_tx,
+            output_rx,
+            pending_failures: Vec::new(),
+        }
+    }
+
+    /// Call `wake` whenever a plugin sends a command or fails
+    pub fn wake_on_output(&mut self, wake: impl Fn() + Send + 'static) {
+        crate::services::event_bus::wake_on_receive(&mut self.output_rx, wake);
+    }
+
+    /// Names of the loaded plugins.
+    pub fn plugin_names(&self) -> Vec<String> {
+        self.plugins.iter().map(|p| p.name.clone()).collect()
+    }
+
+    /// Load every native library in `dir`, skipping plugins disabled in `configs`.
+    pub fn load_dir(&mut self, dir: &Path, configs: &HashMap<String, PluginConfig>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+        for path in paths {
+            self.load_library(&path, configs);
+        }
+    }
+
+    fn load_library(&mut self, path: &Path, configs: &HashMap<String, PluginConfig>) {
+        let marker = crash_marker(path);
+        if marker.exists() {
+            let call = std::fs::read_to_string(&marker).unwrap_or_default();
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            tracing::warn!(
+                "Skipping native plugin {:?}: it crashed during '{}' last session",
+                path,
+                call
+            );
+            self.pending_failures
+                .push(NativePluginFailure::CrashedLastRun { name, call, marker });
+            return;
+        }
+
+        let load_failed = |error: String| NativePluginFailure::LoadFailed {
+            path: path.to_path_buf(),
+            error,
+        };
+
+        // SAFETY: loading a library runs its initializers. Native plugins are
+        // trusted code installed by the user, like TypeScript plugins.
+        let library = match unsafe { Library::new(path) } {
+            Ok(lib) => lib,
+            Err(e) => {
+                self.pending_failures.push(load_failed(e.to_string()));
+                return;
+            }
+        };
+        let descriptor = unsafe {
+            match library
+                .get::<unsafe extern "C" fn() -> *const NativePluginDescriptor>(DESCRIPTOR_SYMBOL)
+            {
+                Ok(entry) => entry(),
+                Err(e) => {
+                    self.pending_failures.push(load_failed(e.to_string()));
+                    return;
+                }
+            }
+        };
+        let Some(descriptor) = (unsafe { descriptor.as_ref() }) else {
+            self.pending_failures
+                .push(load_failed("null plugin descriptor".to_string()));
+            return;
+        };
+        if descriptor.abi_version != NATIVE_ABI_VERSION {
+            self.pending_failures
+                .push(NativePluginFailure::AbiMismatch {
+                    path: path.to_path_buf(),
+                    found: descriptor.abi_version,
+                });
+            return;
+        }
+        let Some(name) = (unsafe { descriptor.name() }).map(str::to_string) else {
+            self.pending_failures
+                .push(load_failed("plugin name is not valid UTF-8".to_string()));
+            return;
+        };
+        if configs.get(&name).is_some_and(|c| !c.enabled) {
+            tracing::info!("Native plugin '{}' is disabled in config", name);
+            return;
+        }
+
+        let descriptor: &'static NativePluginDescriptor =
+            unsafe { &*(descriptor as *const NativePluginDescriptor) };
+        self.start(name, descriptor, Some(library), path.to_path_buf());
+    }
+
+    /// Start a plugin thread for `descriptor`.
+    ///
+    /// `library` is kept alive by the thread (and leaked if the plugin crashes,
+    /// since unmapping code that may still be referenced is not safe).
+    fn start(
+        &mut self,
+        name: String,
+        descriptor: &'static NativePluginDescriptor,
+        library: Option<Library>,
+        path: PathBuf,
+    ) {
+        let hooks = unsafe { descriptor.hooks() };
+
+        let context = Arc::new(HostContext {
+            name: name.clone(),
+            output: Mutex::new(self.output_tx.clone()),
+            actions: Arc::clone(&self.actions),
+            last_error: Mutex::new(None),
+        });
+        let (calls_tx, calls_rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name(format!("native-plugin-{}", name))
+            .spawn(move || run_plugin(descriptor, library, context, calls_rx, path));
+
+        match thread {
+            Ok(thread) => {
+                tracing::info!("Loaded native plugin '{}'", name);
+                self.plugins.push(LoadedPlugin {
+                    name,
+                    hooks,
+                    calls: calls_tx,
+                    thread: Some(thread),
+                });
+            }
+            Err(e) => self.pending_failures.push(NativePluginFailure::Crashed {
+                name,
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    /// Forward a hook to every plugin subscribed to it.
+    pub fn run_hook(&self, hook_name: &str, args: &HookArgs) {
+        let mut payload = None;
+        for plugin in self.plugins.iter().filter(|p| p.wants_hook(hook_name)) {
+            let payload = payload
+                .get_or_insert_with(|| serde_json::to_string(args).unwrap_or_default())
+                .clone();
+            let _ = plugin.calls.send(Call::Hook {
+                name: hook_name.to_string(),
+                payload,
+            });
+        }
+    }
+
+    /// Run `action` if a native plugin registered it. Returns false otherwise.
+    pub fn run_action(&self, action: &str) -> bool {
+        let owner = self.actions.lock().unwrap().get(action).cloned();
+        let Some(plugin) = owner.and_then(|o| self.plugins.iter().find(|p| p.name == o)) else {
+            return false;
+        };
+        plugin.calls.send(Call::Action(action.to_string())).is_ok()
+    }
+
+    /// Drain commands sent by plugins, unloading plugins that crashed.
+    pub fn process_commands(&mut self) -> Vec<PluginCommand> {
+        let mut commands = Vec::new();
+        while let Ok(output) = self.output_rx.try_recv() {
+            match output {
+                Output::Command(command) => commands.push(command),
+                Output::Failure(failure) => {
+                    if let NativePluginFailure::Crashed { name, .. } = &failure {
+                        self.unload(name);
+                    }
+                    self.pending_failures.push(failure);
+                }
+            }
+        }
+        commands
+    }
+
+    /// Take failures that should be shown to the user.
+    pub fn take_failures(&mut self) -> Vec<NativePluginFailure> {
+        std::mem::take(&mut self.pending_failures)
+    }
+
+    /// Unload a plugin by name.
+    pub fn unload(&mut self, name: &str) {
+        self.actions
+            .lock()
+            .unwrap()
+            .retain(|_, owner| owner != name);
+        if let Some(index) = self.plugins.iter().position(|p| p.name == name) {
+            let plugin = self.plugins.remove(index);
+            let _ = plugin.calls.send(Call::Shutdown);
+        }
+    }
+}
+
+impl Drop for NativePluginHost {
+    fn drop(&mut self) {
+        for plugin in &self.plugins {
+            let _ = plugin.calls.send(Call::Shutdown);
+        }
+        // Give plugins a moment to shut down cleanly, but never hang on exit
+        // because of a stuck plugin.
+        let deadline = Instant::now() + Duration::from_millis(500);
+        for plugin in &mut self.plugins {
+            while plugin.thread.as_ref().is_some_and(|t| !t.is_finished()) {
+                if Instant::now() >= deadline {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            if let Some(thread) = plugin.thread.take().filter(|t| t.is_finished()) {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+fn crash_marker(library: &Path) -> PathBuf {
+    let mut marker = library.as_os_str().to_owned();
+    marker.push(".crash");
+    PathBuf::from(marker)
+}
+
+/// Run a call into the plugin with the crash marker naming it in place.
+fn with_marker<T>(marker: &Path, call: &str, f: impl FnOnce() -> T) -> T {
+    let _ = std::fs::write(marker, call);
+    let result = f();
+    let _ = std::fs::remove_file(marker);
+    result
+}
+
+/// Body of a plugin thread: init, then serve calls until shutdown or a crash.
+fn run_plugin(
+    descriptor: &'static NativePluginDescriptor,
+    library: Option<Library>,
+    context: Arc<HostContext>,
+    calls: Receiver<Call>,
+    path: PathBuf,
+) {
+    let marker = crash_marker(&path);
+    let host = NativeHost {
+        abi_version: NATIVE_ABI_VERSION,
+        ctx: Arc::as_ptr(&context) as *mut c_void,
+        send_command: host_send_command,
+        log: host_log,
+    };
+
+    let instance = with_marker(&marker, "init", || unsafe { (descriptor.init)(&host) });
+    if instance.is_null() {
+        let error = context
+            .last_error
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| "init failed".to_string());
+        context.report(Output::Failure(NativePluginFailure::LoadFailed {
+            path,
+            error,
+        }));
+        return;
+    }
+
+    let mut crashed = false;
+    while let Ok(call) = calls.recv() {
+        let (label, code) = match &call {
+            Call::Hook { name, payload } => {
+                let label = format!("hook {}", name);
+                let code = with_marker(&marker, &label, || unsafe {
+                    (descriptor.handle_hook)(
+                        instance,
+                        name.as_ptr(),
+                        name.len(),
+                        payload.as_ptr(),
+                        payload.len(),
+                    )
+                });
+                (label, code)
+            }
+            Call::Action(action) => {
+                let label = format!("action {}", action);
+                let code = with_marker(&marker, &label, || unsafe {
+                    (descriptor.run_action)(instance, action.as_ptr(), action.len())
+                });
+                (label, code)
+            }
+            Call::Shutdown => break,
+        };
+        let last_error = context.last_error.lock().unwrap().take();
+        match code {
+            NATIVE_OK | NATIVE_UNHANDLED => {}
+            NATIVE_PANIC => {
+                context.report(Output::Failure(NativePluginFailure::Crashed {
+                    name: context.name.clone(),
+                    reason: last_error.unwrap_or_else(|| format!("panic in {}", label)),
+                }));
+                crashed = true;
+                break;
+            }
+            _ => context.report(Output::Failure(NativePluginFailure::Error {
+                name: context.name.clone(),
+                message: last_error.unwrap_or_else(|| format!("{} failed", label)),
+            })),
+        }
+    }
+
+    if crashed {
+        // The plugin's state may be corrupt: don't call back into it, and keep
+        // its code mapped in case it left threads or callbacks behind.
+        std::mem::forget(library);
+        return;
+    }
+    with_marker(&marker, "shutdown", || unsafe {
+        (descriptor.shutdown)(instance)
+    });
+    drop(library);
+}
+
+impl HostContext {
+    fn report(&self, output: Output) {
+        let _ = self.output.lock().unwrap().send(output);
+    }
+}
+
+unsafe extern "C" fn host_send_command(ctx: *mut c_void, json: *const u8, len: usize) -> i32 {
+    let Some(context) = (ctx as *const HostContext).as_ref() else {
+        return -1;
+    };
+    if json.is_null() {
+        return -1;
+    }
+    let bytes = std::slice::from_raw_parts(json, len);
+    match serde_json::from_slice::<PluginCommand>(bytes) {
+        Ok(mut command) => {
+            if let PluginCommand::RegisterCommand { command } = &mut command {
+                command.plugin_name = context.name.clone();
+                context
+                    .actions
+                    .lock()
+                    .unwrap()
+                    .insert(command.action_name.clone(), context.name.clone());
+            }
+            context.report(Output::Command(command));
+            0
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Native plugin '{}' sent an invalid command: {}",
+                context.name,
+                e
+            );
+            -1
+        }
+    }
+}
+
+unsafe extern "C" fn host_log(ctx: *mut c_void, level: u32, msg: *const u8, len: usize) {
+    let Some(context) = (ctx as *const HostContext).as_ref() else {
+        return;
+    };
+    if msg.is_null() {
+        return;
+    }
+    let msg = String::from_utf8_lossy(std::slice::from_raw_parts(msg, len));
+    let name = &context.name;
+    match level {
+        LOG_ERROR => {
+            tracing::error!("[native:{}] {}", name, msg);
+            *context.last_error.lock().unwrap() = Some(msg.into_owned());
+        }
+        LOG_WARN => tracing::warn!("[native:{}] {}", name, msg),
+        LOG_INFO => tracing::info!("[native:{}] {}", name, msg),
+        LOG_DEBUG => tracing::debug!("[native:{}] {}", name, msg),
+        _ => tracing::trace!("[native:{}] {}", name, msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fresh_core::native::{descriptor_for, HostHandle, NativePlugin};
+    use std::sync::OnceLock;
+
+    struct Echo {
+        host: HostHandle,
+    }
+
+    impl NativePlugin for Echo {
+        const NAME: &'static str = "echo";
+        const HOOKS: &'static str = "after_file_save";
+
+        fn new(host: HostHandle) -> Self {
+            for action in ["echo_ping", "echo_fail", "echo_boom"] {
+                host.send(&PluginCommand::RegisterCommand {
+                    command: fresh_core::command::Command {
+                        name: action.to_string(),
+                        description: String::new(),
+                        action_name: action.to_string(),
+                        plugin_name: String::new(),
+                        custom_contexts: Vec::new(),
+                    },
+                });
+            }
+            Self { host }
+        }
+
+        fn on_hook(&mut self, hook: &str, _payload: &str) -> Result<(), String> {
+            self.host.send(&PluginCommand::SetStatus {
+                message: format!("hook {}", hook),
+            });
+            Ok(())
+        }
+
+        fn run_action(&mut self, action: &str) -> Option<Result<(), String>> {
+            match action {
+                "echo_ping" => {
+                    self.host.send(&PluginCommand::SetStatus {
+                        message: "pong".to_string(),
+                    });
+                    Some(Ok(()))
+                }
+                "echo_fail" => Some(Err("nope".to_string())),
+                "echo_boom" => panic!("boom"),
+                _ => None,
+            }
+        }
+    }
+
+    fn echo_descriptor() -> &'static NativePluginDescriptor {
+        static DESCRIPTOR: OnceLock<NativePluginDescriptor> = OnceLock::new();
+        DESCRIPTOR.get_or_init(descriptor_for::<Echo>)
+    }
+
+    fn wait_for<T>(
+        host: &mut NativePluginHost,
+        mut f: impl FnMut(&mut NativePluginHost) -> Option<T>,
+    ) -> T {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(value) = f(host) {
+                return value;
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for plugin");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    fn statuses(commands: Vec<PluginCommand>) -> Vec<String> {
+        commands
+            .into_iter()
+            .filter_map(|c| match c {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_native_plugin_hooks_actions_and_crash_containment() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("libecho.so");
+        let mut host = NativePluginHost::new();
+        host.start("echo".to_string(), echo_descriptor(), None, library.clone());
+
+        // init registers the command, and the action becomes routable.
+        wait_for(&mut host, |h| {
+            h.process_commands()
+                .into_iter()
+                .find(|c| matches!(c, PluginCommand::RegisterCommand { .. }))
+        });
+        assert!(host.run_action("echo_ping"));
+        assert!(!host.run_action("unknown"));
+        assert_eq!(
+            wait_for(&mut host, |h| statuses(h.process_commands()).pop()),
+            "pong"
+        );
+
+        // Only subscribed hooks are delivered.
+        let args = HookArgs::AfterFileSave {
+            buffer_id: fresh_core::BufferId(1),
+            path: PathBuf::from("a.txt"),
+        };
+        host.run_hook("after_file_open", &args);
+        host.run_hook("after_file_save", &args);
+        assert_eq!(
+            wait_for(&mut host, |h| statuses(h.process_commands()).pop()),
+            "hook after_file_save"
+        );
+
+        // Errors are reported but keep the plugin loaded.
+        assert!(host.run_action("echo_fail"));
+        wait_for(&mut host, |h| {
+            h.process_commands();
+            h.take_failures().pop()
+        });
+        assert_eq!(host.plugin_names(), vec!["echo".to_string()]);
+
+        // A panic unloads the plugin instead of taking the editor down.
+        assert!(host.run_action("echo_boom"));
+        let failure = wait_for(&mut host, |h| {
+            h.process_commands();
+            h.take_failures().pop()
+        });
+        assert!(matches!(
+            failure,
+            NativePluginFailure::Crashed { ref name, ref reason } if name == "echo" && reason == "boom"
+        ));
+        assert!(host.plugin_names().is_empty());
+        assert!(!host.run_action("echo_ping"));
+        assert!(!crash_marker(&library).exists());
+    }
+
+    #[test]
+    fn test_leftover_crash_marker_skips_plugin() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir
+            .path()
+            .join(format!("libbad.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&library, b"not a library").unwrap();
+        std::fs::write(crash_marker(&library), "hook render_start").unwrap();
+
+        let mut host = NativePluginHost::new();
+        host.load_dir(dir.path(), &HashMap::new());
+        assert!(host.plugin_names().is_empty());
+        assert_eq!(
+            host.take_failures(),
+            vec![NativePluginFailure::CrashedLastRun {
+                name: "libbad".to_string(),
+                call: "hook render_start".to_string(),
+                marker: crash_marker(&library),
+            }]
+        );
+    }
+}
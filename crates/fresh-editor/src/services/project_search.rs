@@ -0,0 +1,320 @@
+//! Project-wide text search
+//!
+//! Searches the files under a directory for a pattern the way ripgrep does:
+//! the directory is walked on several threads, hidden files and whatever the
+//! project's ignore rules (see `services::ignore_rules`) leave out are
+//! skipped, and so are binary files. Each file's matching lines (with context lines
+//! around them) are reported as soon as the file has been searched, so results
+//! can be shown while the search goes on.
+
+use crate::services::ignore_rules::IgnoreRules;
+use crate::services::jobs::CancellationToken;
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Bytes looked at to decide whether a file is binary
+const BINARY_CHECK_LEN: usize = 8192;
+
+/// How to search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Treat the pattern as a regular expression instead of literal text
+    pub regex: bool,
+    /// Match case; None matches case only when the pattern has an uppercase letter
+    pub case_sensitive: Option<bool>,
+    /// Only match whole words
+    pub whole_word: bool,
+    /// Lines shown before and after each matching line
+    pub context_lines: usize,
+    /// Stop searching after this many matching lines
+    pub max_matches: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            regex: true,
+            case_sensitive: None,
+            whole_word: false,
+            context_lines: 0,
+            max_matches: 10_000,
+        }
+    }
+}
+
+/// A line of a search result: a matching line or a context line around one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultLine {
+    /// Line number (1-indexed)
+    pub number: usize,
+    /// Text of the line, without its line ending
+    pub text: String,
+    /// Byte ranges of the matches in `text`; empty for context lines
+    pub matches: Vec<Range<usize>>,
+}
+
+impl ResultLine {
+    pub fn is_match(&self) -> bool {
+        !self.matches.is_empty()
+    }
+}
+
+/// Matching lines of one file, in line order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMatches {
+    pub path: PathBuf,
+    pub lines: Vec<ResultLine>,
+}
+
+impl FileMatches {
+    /// Number of matching lines (context lines not counted)
+    pub fn match_count(&self) -> usize {
+        self.lines.iter().filter(|line| line.is_match()).count()
+    }
+}
+
+/// How a finished search went
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchSummary {
+    /// Files searched (binary files not counted)
+    pub files_searched: usize,
+    /// Matching lines found
+    pub matches: usize,
+    /// The search stopped at `SearchOptions::max_matches`
+    pub truncated: bool,
+}
+
+/// Build the regex matching `pattern` with `options`
+pub fn build_regex(pattern: &str, options: &SearchOptions) -> Result<Regex, regex::Error> {
+    let mut source = if options.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    if options.whole_word {
+        source = format!(r"\b(?:{})\b", source);
+    }
+    let case_sensitive = options
+        .case_sensitive
+        .unwrap_or_else(|| pattern.chars().any(char::is_uppercase));
+    RegexBuilder::new(&source)
+        .case_insensitive(!case_sensitive)
+        .build()
+}
+
+/// Matching lines of `text`, with up to `context_lines` lines around each
+///
+/// Context lines shared by nearby matches are only listed once.
+pub fn search_text(text: &str, regex: &Regex, context_lines: usize) -> Vec<ResultLine> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    let context = |range: Range<usize>| {
+        range.map(|index| ResultLine {
+            number: index + 1,
+            text: lines[index].to_string(),
+            matches: Vec::new(),
+        })
+    };
+    let mut result: Vec<ResultLine> = Vec::new();
+    // Index of the first line not added to `result` yet
+    let mut next = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let matches: Vec<Range<usize>> = regex
+            .find_iter(line)
+            .filter(|m| !m.is_empty())
+            .map(|m| m.range())
+            .collect();
+        if matches.is_empty() {
+            continue;
+        }
+        result.extend(context(
+            index.saturating_sub(context_lines).max(next)..index,
+        ));
+        if index < next {
+            // Already added as context of the previous match
+            if let Some(existing) = result.iter_mut().rev().find(|l| l.number == index + 1) {
+                existing.matches = matches;
+            }
+        } else {
+            result.push(ResultLine {
+                number: index + 1,
+                text: line.to_string(),
+                matches,
+            });
+        }
+        let after_end = (index + 1 + context_lines).min(lines.len());
+        result.extend(context(next.max(index + 1)..after_end));
+        next = next.max(after_end);
+    }
+    result
+}
+
+/// Search one file; None if it can't be read or is binary
+pub fn search_file(path: &Path, regex: &Regex, context_lines: usize) -> Option<Vec<ResultLine>> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes[..bytes.len().min(BINARY_CHECK_LEN)].contains(&0) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    Some(search_text(&text, regex, context_lines))
+}
+
+/// Search the files under the root of `rules`, calling `on_file` (from the
+/// walker's threads) for each file with matches
+///
+/// Returns early once `token` is cancelled or `max_matches` matching lines
+/// were found.
+pub fn search_project<F>(
+    rules: &IgnoreRules,
+    regex: &Regex,
+    options: &SearchOptions,
+    token: &CancellationToken,
+    on_file: F,
+) -> SearchSummary
+where
+    F: Fn(FileMatches) + Sync,
+{
+    let files_searched = AtomicUsize::new(0);
+    let matches = AtomicUsize::new(0);
+    let truncated = AtomicBool::new(false);
+
+    let walker = rules.walker().build_parallel();
+    walker.run(|| {
+        let on_file = &on_file;
+        let files_searched = &files_searched;
+        let matches = &matches;
+        let truncated = &truncated;
+        Box::new(move |entry| {
+            if token.is_cancelled() || truncated.load(Ordering::Relaxed) {
+                return ignore::WalkState::Quit;
+            }
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                return ignore::WalkState::Continue;
+            }
+            let Some(lines) = search_file(entry.path(), regex, options.context_lines) else {
+                return ignore::WalkState::Continue;
+            };
+            files_searched.fetch_add(1, Ordering::Relaxed);
+            let file = FileMatches {
+                path: entry.into_path(),
+                lines,
+            };
+            let count = file.match_count();
+            if count == 0 {
+                return ignore::WalkState::Continue;
+            }
+            let total = matches.fetch_add(count, Ordering::Relaxed) + count;
+            on_file(file);
+            if total >= options.max_matches {
+                truncated.store(true, Ordering::Relaxed);
+                return ignore::WalkState::Quit;
+            }
+            ignore::WalkState::Continue
+        })
+    });
+
+    SearchSummary {
+        files_searched: files_searched.into_inner(),
+        matches: matches.into_inner(),
+        truncated: truncated.into_inner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn numbers(lines: &[ResultLine]) -> Vec<(usize, bool)> {
+        lines.iter().map(|l| (l.number, l.is_match())).collect()
+    }
+
+    #[test]
+    fn test_smart_case_and_literal_patterns() {
+        let options = SearchOptions {
+            regex: false,
+            ..Default::default()
+        };
+        let regex = build_regex("a.b", &options).unwrap();
+        assert!(regex.is_match("A.B"));
+        assert!(!regex.is_match("axb"));
+
+        // An uppercase letter makes the search case-sensitive
+        let regex = build_regex("Foo", &SearchOptions::default()).unwrap();
+        assert!(!regex.is_match("foo"));
+
+        let whole_word = SearchOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let regex = build_regex("foo", &whole_word).unwrap();
+        assert!(regex.is_match("a foo b"));
+        assert!(!regex.is_match("foobar"));
+    }
+
+    #[test]
+    fn test_context_lines_are_merged() {
+        let text = "one\ntwo\nmatch\nfour\nmatch\nsix\nseven\neight\nnine\nmatch\n";
+        let regex = build_regex("match", &SearchOptions::default()).unwrap();
+        let lines = search_text(text, &regex, 1);
+        assert_eq!(
+            numbers(&lines),
+            vec![
+                (2, false),
+                (3, true),
+                (4, false),
+                (5, true),
+                (6, false),
+                (9, false),
+                (10, true),
+            ]
+        );
+        assert_eq!(lines[1].matches.len(), 1);
+        assert_eq!(lines[1].matches[0], 0..5);
+    }
+
+    #[test]
+    fn test_match_within_previous_context() {
+        let text = "match\nmatch\nother\n";
+        let regex = build_regex("match", &SearchOptions::default()).unwrap();
+        let lines = search_text(text, &regex, 2);
+        assert_eq!(numbers(&lines), vec![(1, true), (2, true), (3, false)]);
+    }
+
+    #[test]
+    fn test_search_project_skips_ignored_excluded_and_binary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "ignored.txt\n").unwrap();
+        std::fs::write(root.join("bundle.min.js"), "needle\n").unwrap();
+        std::fs::write(root.join("ignored.txt"), "needle\n").unwrap();
+        std::fs::write(root.join("binary.bin"), b"needle\0").unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "fn needle() {}\nneedle\n").unwrap();
+
+        let options = SearchOptions::default();
+        let regex = build_regex("needle", &options).unwrap();
+        let found = Mutex::new(Vec::new());
+        let rules = IgnoreRules::new(root, &["*.min.js".to_string()]);
+        let summary = search_project(
+            &rules,
+            &regex,
+            &options,
+            &CancellationToken::new(),
+            |file| found.lock().unwrap().push(file),
+        );
+
+        let found = found.into_inner().unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, root.join("src/lib.rs"));
+        assert_eq!(summary.matches, 2);
+        assert!(!summary.truncated);
+    }
+}
@@ -7,7 +7,8 @@
 //! ## How it works
 //!
 //! 1. **Session Lock**: On startup, creates a lock file with the process ID
-//! 2. **Auto-Save**: Periodically saves modified buffers to recovery directory
+//! 2. **Auto-Save**: Periodically saves modified buffers to recovery directory,
+//!    and journals every edit made in between
 //! 3. **Crash Detection**: On startup, checks if lock file exists without running process
 //! 4. **Recovery**: If crash detected, offers to recover unsaved changes
 //!
@@ -19,6 +20,7 @@
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
+//! ├── {hash}.journal         # Edits made since the chunks were written
 //! └── ...
 //! ```
 //!
@@ -54,9 +56,9 @@ pub mod types;
 
 pub use storage::RecoveryStorage;
 pub use types::{
-    generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData, ChunkedRecoveryIndex,
-    InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
-    SessionInfo, MAX_CHUNK_SIZE,
+    apply_journal, generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData,
+    ChunkedRecoveryIndex, InplaceWriteRecovery, JournalEdit, RecoveryChunk, RecoveryEntry,
+    RecoveryMetadata, RecoveryResult, SessionInfo, MAX_CHUNK_SIZE,
 };
 
 use std::collections::HashMap;
@@ -285,6 +287,21 @@ impl RecoveryService {
         Ok(())
     }
 
+    /// Whether a buffer's content has been saved for recovery this session
+    ///
+    /// Journaled edits only make sense on top of a saved snapshot.
+    pub fn has_snapshot(&self, buffer_id: &str) -> bool {
+        self.last_save_times.contains_key(buffer_id)
+    }
+
+    /// Append edits made since the last snapshot to a buffer's journal
+    pub fn append_journal(&mut self, buffer_id: &str, edits: &[JournalEdit]) -> io::Result<()> {
+        if !self.config.enabled || edits.is_empty() {
+            return Ok(());
+        }
+        self.storage.append_journal(buffer_id, edits)
+    }
+
     /// Delete recovery for a buffer (call when buffer is saved normally or closed)
     pub fn delete_buffer_recovery(&mut self, buffer_id: &str) -> io::Result<()> {
         if !self.config.enabled {
@@ -342,6 +359,7 @@ impl RecoveryService {
                 return Ok(RecoveryResult::RecoveredChunks {
                     original_path: original_path.clone(),
                     chunks: chunked_data.chunks,
+                    journal: self.storage.read_journal(&entry.id)?,
                 });
             } else {
                 return Ok(RecoveryResult::Corrupted {
@@ -360,9 +378,11 @@ impl RecoveryService {
 
         // For original_file_size == 0, we expect exactly one chunk with offset=0
         if chunked_data.chunks.len() == 1 && chunked_data.chunks[0].offset == 0 {
+            let mut content = chunked_data.chunks[0].content.clone();
+            apply_journal(&mut content, &self.storage.read_journal(&entry.id)?);
             Ok(RecoveryResult::Recovered {
                 original_path: entry.metadata.original_path.clone(),
-                content: chunked_data.chunks[0].content.clone(),
+                content,
             })
         } else {
             Ok(RecoveryResult::Corrupted {
@@ -518,6 +538,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_journal_replayed_on_recover() {
+        let (mut service, temp) = create_test_service();
+        let id = "unsaved_journal";
+        assert!(!service.has_snapshot(id));
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"hello world".to_vec())];
+        service
+            .save_buffer(id, chunks, None, None, Some(1), 0, 11)
+            .unwrap();
+        assert!(service.has_snapshot(id));
+
+        service
+            .append_journal(
+                id,
+                &[
+                    JournalEdit::Delete { offset: 0, len: 5 },
+                    JournalEdit::Insert {
+                        offset: 0,
+                        text: "goodbye".to_string(),
+                    },
+                ],
+            )
+            .unwrap();
+        // A crash mid-append leaves a partial line, which is ignored
+        let journal = temp.path().join(format!("{id}.journal"));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&journal)
+            .unwrap();
+        std::io::Write::write_all(&mut file, b"{\"op\":\"ins").unwrap();
+
+        let entries = service.list_recoverable().unwrap();
+        match service.load_recovery(&entries[0]).unwrap() {
+            RecoveryResult::Recovered { content, .. } => assert_eq!(content, b"goodbye world"),
+            result => panic!("Expected Recovered result, got {:?}", result),
+        }
+
+        // A new snapshot supersedes the journal
+        let chunks = vec![RecoveryChunk::new(0, 0, b"fresh".to_vec())];
+        service
+            .save_buffer(id, chunks, None, None, Some(1), 0, 5)
+            .unwrap();
+        assert!(!journal.exists());
+    }
+
     #[test]
     fn test_needs_auto_recovery_save() {
         let (service, _temp) = create_test_service();
@@ -586,7 +652,9 @@ mod tests {
             RecoveryResult::RecoveredChunks {
                 original_path: path,
                 chunks,
+                journal,
             } => {
+                assert!(journal.is_empty());
                 assert_eq!(path, original_path);
                 assert_eq!(chunks.len(), 1);
                 assert_eq!(chunks[0].offset, 0);
@@ -4,8 +4,8 @@
 //! to ensure data integrity even during crashes.
 
 use super::types::{
-    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, RecoveryChunk,
-    RecoveryEntry, RecoveryMetadata, SessionInfo,
+    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, JournalEdit,
+    RecoveryChunk, RecoveryEntry, RecoveryMetadata, SessionInfo,
 };
 use crate::input::input_history::get_data_dir;
 use std::fs::{self, File};
@@ -27,6 +27,8 @@ impl RecoveryStorage {
     const META_EXT: &'static str = "meta.json";
     /// File extension for content files
     const CONTENT_EXT: &'static str = "content";
+    /// File extension for edit journals
+    const JOURNAL_EXT: &'static str = "journal";
     /// Session lock file name
     const SESSION_LOCK: &'static str = "session.lock";
 
@@ -146,6 +148,46 @@ impl RecoveryStorage {
             .join(format!("{id}.chunk.{}", chunk_index))
     }
 
+    /// Get path for the edit journal
+    fn journal_path(&self, id: &str) -> PathBuf {
+        self.recovery_dir
+            .join(format!("{id}.{}", Self::JOURNAL_EXT))
+    }
+
+    /// Append edits to a buffer's journal
+    ///
+    /// The edits apply on top of the last saved chunks; saving new chunks
+    /// starts a fresh journal.
+    pub fn append_journal(&self, id: &str, edits: &[JournalEdit]) -> io::Result<()> {
+        let mut lines = Vec::new();
+        for edit in edits {
+            serde_json::to_writer(&mut lines, edit).map_err(io::Error::other)?;
+            lines.push(b'\n');
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path(id))?;
+        file.write_all(&lines)?;
+        file.sync_data()
+    }
+
+    /// Read a buffer's journal
+    ///
+    /// Reading stops at the first incomplete line, which a crash in the middle
+    /// of an append can leave behind.
+    pub fn read_journal(&self, id: &str) -> io::Result<Vec<JournalEdit>> {
+        let content = match fs::read_to_string(self.journal_path(id)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        Ok(content
+            .lines()
+            .map_while(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
     /// List all chunk files for a given ID
     fn list_chunk_paths(&self, id: &str) -> io::Result<Vec<PathBuf>> {
         let mut paths = Vec::new();
@@ -202,6 +244,8 @@ impl RecoveryStorage {
 
         // Delete any existing chunk files first
         self.delete_chunk_files(id)?;
+        // The new chunks include everything journaled so far
+        let _ = fs::remove_file(self.journal_path(id));
 
         // Create chunked data structure
         let chunked_data = ChunkedRecoveryData::new(original_file_size, final_size, chunks);
@@ -453,6 +497,7 @@ impl RecoveryStorage {
 
         // Delete chunk files (for Chunked format)
         self.delete_chunk_files(id)?;
+        let _ = fs::remove_file(self.journal_path(id));
 
         // Delete metadata file
         if meta_path.exists() {
@@ -518,6 +563,9 @@ impl RecoveryStorage {
                 } else if name.ends_with(&format!(".{}", Self::CONTENT_EXT)) {
                     name.trim_end_matches(&format!(".{}", Self::CONTENT_EXT))
                         .to_string()
+                } else if name.ends_with(&format!(".{}", Self::JOURNAL_EXT)) {
+                    name.trim_end_matches(&format!(".{}", Self::JOURNAL_EXT))
+                        .to_string()
                 } else if name.contains(".chunk.") {
                     // Handle chunk files like "id.chunk.0"
                     name.split(".chunk.").next().unwrap_or("").to_string()
@@ -540,6 +588,7 @@ impl RecoveryStorage {
                 if !is_valid {
                     let _ = fs::remove_file(&meta_path);
                     let _ = self.delete_chunk_files(&id);
+                    let _ = fs::remove_file(self.journal_path(&id));
                     cleaned += 1;
                 }
             }
@@ -10,7 +10,11 @@
 //!
 //! For small files or new buffers, there's typically a single chunk containing
 //! the full content. For large files, only modified regions are stored as chunks.
+//!
+//! Edits made after the chunks were written are appended to `{id}.journal`,
+//! one JSON [`JournalEdit`] per line, and replayed on top of them.
 
+pub use crate::model::buffer::JournalEdit;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -331,6 +335,8 @@ pub enum RecoveryResult {
     RecoveredChunks {
         original_path: PathBuf,
         chunks: Vec<RecoveryChunk>,
+        /// Journaled edits to apply after the chunks
+        journal: Vec<JournalEdit>,
     },
     /// Original file was modified since recovery was saved
     OriginalFileModified { id: String, original_path: PathBuf },
@@ -381,6 +387,25 @@ fn is_process_running(_pid: u32) -> bool {
     false
 }
 
+/// Replay journaled edits on top of recovered content
+///
+/// Out-of-range offsets are clamped so a truncated journal can't panic.
+pub fn apply_journal(content: &mut Vec<u8>, journal: &[JournalEdit]) {
+    for edit in journal {
+        match edit {
+            JournalEdit::Insert { offset, text } => {
+                let offset = (*offset).min(content.len());
+                content.splice(offset..offset, text.bytes());
+            }
+            JournalEdit::Delete { offset, len } => {
+                let start = (*offset).min(content.len());
+                let end = offset.saturating_add(*len).min(content.len());
+                content.drain(start..end);
+            }
+        }
+    }
+}
+
 /// Generate a stable hash for a file path (used as recovery file ID)
 pub fn path_hash(path: &std::path::Path) -> String {
     use sha2::{Digest, Sha256};
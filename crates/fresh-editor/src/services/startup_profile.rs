@@ -0,0 +1,187 @@
+//! Startup profiling layer for tracing
+//!
+//! With `--profile-startup`, this layer records how long each span is entered
+//! while the editor starts. When the first frame has been drawn, the totals
+//! are written as folded stacks (`parent;child microseconds`), the format
+//! read by flamegraph tools such as inferno and speedscope.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::span;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Time spent in each stack of span names, stopped when written
+struct Profile {
+    recording: AtomicBool,
+    stacks: Mutex<HashMap<String, Duration>>,
+}
+
+/// A tracing layer that times spans for the startup profile
+pub struct StartupProfileLayer {
+    profile: Arc<Profile>,
+}
+
+/// Handle returned from setup, used to write the profile
+pub struct StartupProfileHandle {
+    profile: Arc<Profile>,
+    /// Path the profile is written to
+    pub path: PathBuf,
+}
+
+/// When a span was entered
+struct EnteredAt(Instant);
+
+/// Create a startup profile layer and handle
+///
+/// Returns the layer (to add to tracing subscriber) and a handle (to pass to editor)
+pub fn create(path: PathBuf) -> (StartupProfileLayer, StartupProfileHandle) {
+    let profile = Arc::new(Profile {
+        recording: AtomicBool::new(true),
+        stacks: Mutex::new(HashMap::new()),
+    });
+    let layer = StartupProfileLayer {
+        profile: profile.clone(),
+    };
+    (layer, StartupProfileHandle { profile, path })
+}
+
+impl StartupProfileHandle {
+    /// Stop recording and write the profile. Later calls do nothing.
+    pub fn finish(&self) -> std::io::Result<()> {
+        if !self.profile.recording.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let stacks = match self.profile.stacks.lock() {
+            Ok(mut stacks) => std::mem::take(&mut *stacks),
+            Err(_) => return Ok(()),
+        };
+        let mut out = BufWriter::new(File::create(&self.path)?);
+        for (stack, micros) in fold_stacks(&stacks) {
+            writeln!(out, "{} {}", stack, micros)?;
+        }
+        out.flush()
+    }
+}
+
+/// The time each stack spent outside of its child spans, in microseconds,
+/// sorted by stack. `totals` holds the time each stack was entered, children
+/// included.
+fn fold_stacks(totals: &HashMap<String, Duration>) -> Vec<(String, u64)> {
+    let mut folded: Vec<(String, u64)> = totals
+        .iter()
+        .map(|(stack, total)| {
+            let children: Duration = totals
+                .iter()
+                .filter(|(other, _)| {
+                    other
+                        .strip_prefix(stack.as_str())
+                        .and_then(|rest| rest.strip_prefix(';'))
+                        .is_some_and(|child| !child.contains(';'))
+                })
+                .map(|(_, time)| *time)
+                .sum();
+            (
+                stack.clone(),
+                total.saturating_sub(children).as_micros() as u64,
+            )
+        })
+        .filter(|(_, micros)| *micros > 0)
+        .collect();
+    folded.sort();
+    folded
+}
+
+impl<S> Layer<S> for StartupProfileLayer
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if !self.profile.recording.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(EnteredAt(Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let Some(EnteredAt(entered)) = span.extensions_mut().remove::<EnteredAt>() else {
+            return;
+        };
+        if !self.profile.recording.load(Ordering::Relaxed) {
+            return;
+        }
+        let stack = span
+            .scope()
+            .from_root()
+            .map(|span| span.name())
+            .collect::<Vec<_>>()
+            .join(";");
+        if let Ok(mut stacks) = self.profile.stacks.lock() {
+            *stacks.entry(stack).or_default() += entered.elapsed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_fold_stacks_subtracts_children() {
+        let totals = HashMap::from([
+            ("startup".to_string(), Duration::from_micros(100)),
+            ("startup;config".to_string(), Duration::from_micros(30)),
+            ("startup;plugins".to_string(), Duration::from_micros(50)),
+            (
+                "startup;plugins;load".to_string(),
+                Duration::from_micros(50),
+            ),
+        ]);
+        assert_eq!(
+            fold_stacks(&totals),
+            vec![
+                ("startup".to_string(), 20),
+                ("startup;config".to_string(), 30),
+                ("startup;plugins;load".to_string(), 50),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_written_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("startup.folded");
+        let (layer, handle) = create(path.clone());
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _outer = tracing::info_span!("outer").entered();
+            let _inner = tracing::info_span!("inner").entered();
+            std::thread::sleep(Duration::from_millis(2));
+        });
+        handle.finish().unwrap();
+
+        let profile = std::fs::read_to_string(&path).unwrap();
+        let inner = profile
+            .lines()
+            .find_map(|line| line.strip_prefix("outer;inner "))
+            .unwrap();
+        assert!(inner.parse::<u64>().unwrap() >= 2000);
+
+        std::fs::write(&path, "").unwrap();
+        handle.finish().unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+    }
+}
@@ -216,8 +216,13 @@ impl TerminalManager {
             if let Some(ref p) = backing_path {
                 if let Ok(metadata) = std::fs::metadata(p) {
                     if metadata.len() > 0 {
+                        // Shell integration lines continue after the restored scrollback
+                        let restored_lines = std::fs::read(p)
+                            .map(|bytes| bytes.iter().filter(|&&b| b == b'\n').count())
+                            .unwrap_or(0);
                         if let Ok(mut s) = state.lock() {
                             s.set_backing_file_history_end(metadata.len());
+                            s.shell_integration_mut().set_line_offset(restored_lines);
                         }
                     }
                 }
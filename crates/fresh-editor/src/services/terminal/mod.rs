@@ -43,12 +43,14 @@
 //! ## Module Responsibilities
 //!
 //! - `term.rs`: Terminal state and incremental streaming methods
+//! - `shell_integration.rs`: OSC 133/OSC 7 command boundaries and working directory
 //! - `manager.rs`: PTY lifecycle and read loop with streaming
 //! - `../app/terminal.rs`: Mode switching logic
 //! - `../app/session.rs`: Session save/restore integration
 
 mod manager;
 pub mod pty;
+pub mod shell_integration;
 pub mod term;
 
 pub use manager::{detect_shell, TerminalId, TerminalManager};
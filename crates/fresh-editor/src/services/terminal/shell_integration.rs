@@ -0,0 +1,405 @@
+//! Shell integration through OSC 133 and OSC 7
+//!
+//! Shells configured for integration mark the parts of each command with
+//! FinalTerm-style OSC 133 sequences and report their working directory with
+//! OSC 7:
+//!
+//! - `OSC 133;A` - the prompt starts
+//! - `OSC 133;B` - the prompt ends and the command line starts
+//! - `OSC 133;C` - the command runs and its output starts
+//! - `OSC 133;D[;exit]` - the command finished, with its exit code
+//! - `OSC 7;file://host/path` - the shell's current directory
+//!
+//! `alacritty_terminal` ignores these sequences, so `OscScanner` picks them
+//! out of the raw PTY stream and `TerminalState` records each one at the
+//! cursor's line. Lines are counted from the first line of the terminal's
+//! backing file, so they are also line numbers in the scrollback buffer.
+
+use std::path::PathBuf;
+
+/// Most commands kept per terminal; the oldest are dropped first
+const MAX_COMMANDS: usize = 10_000;
+
+/// Longest OSC payload collected, so a stray unterminated sequence can't
+/// grow without bound
+const MAX_OSC_LEN: usize = 4096;
+
+/// A shell integration event parsed from an OSC sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellEvent {
+    /// OSC 133;A
+    PromptStart,
+    /// OSC 133;B
+    CommandStart,
+    /// OSC 133;C
+    OutputStart,
+    /// OSC 133;D with the exit code, if the shell reported one
+    CommandFinished(Option<i32>),
+    /// OSC 7 with the decoded directory
+    WorkingDirectory(PathBuf),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// Incremental scanner for OSC sequences, resumable across PTY reads
+#[derive(Debug)]
+pub struct OscScanner {
+    state: ScanState,
+    payload: Vec<u8>,
+}
+
+impl Default for OscScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OscScanner {
+    pub fn new() -> Self {
+        Self {
+            state: ScanState::Ground,
+            payload: Vec::new(),
+        }
+    }
+
+    /// Scan a chunk of PTY output for shell integration events
+    ///
+    /// Returns each event with the offset just past its terminator, so the
+    /// caller can feed the emulator up to that point before recording it.
+    pub fn scan(&mut self, data: &[u8]) -> Vec<(usize, ShellEvent)> {
+        let mut events = Vec::new();
+        for (i, &byte) in data.iter().enumerate() {
+            self.state = match (self.state, byte) {
+                (ScanState::Ground, 0x1b) => ScanState::Escape,
+                (ScanState::Ground, _) => ScanState::Ground,
+                (ScanState::Escape, b']') => {
+                    self.payload.clear();
+                    ScanState::Osc
+                }
+                (ScanState::Escape, 0x1b) => ScanState::Escape,
+                (ScanState::Escape, _) => ScanState::Ground,
+                // BEL terminator
+                (ScanState::Osc, 0x07) => {
+                    if let Some(event) = parse_osc(&self.payload) {
+                        events.push((i + 1, event));
+                    }
+                    ScanState::Ground
+                }
+                (ScanState::Osc, 0x1b) => ScanState::OscEscape,
+                (ScanState::Osc, _) => {
+                    if self.payload.len() < MAX_OSC_LEN {
+                        self.payload.push(byte);
+                    }
+                    ScanState::Osc
+                }
+                // ST terminator (ESC \)
+                (ScanState::OscEscape, b'\\') => {
+                    if let Some(event) = parse_osc(&self.payload) {
+                        events.push((i + 1, event));
+                    }
+                    ScanState::Ground
+                }
+                // Any other escape aborts the OSC and may start a new one
+                (ScanState::OscEscape, b']') => {
+                    self.payload.clear();
+                    ScanState::Osc
+                }
+                (ScanState::OscEscape, _) => ScanState::Ground,
+            };
+        }
+        events
+    }
+}
+
+/// Parse the payload of an OSC sequence (between `ESC ]` and the terminator)
+fn parse_osc(payload: &[u8]) -> Option<ShellEvent> {
+    let payload = std::str::from_utf8(payload).ok()?;
+    let (code, rest) = payload.split_once(';')?;
+    match code {
+        "133" => {
+            let mut params = rest.split(';');
+            match params.next()? {
+                "A" => Some(ShellEvent::PromptStart),
+                "B" => Some(ShellEvent::CommandStart),
+                "C" => Some(ShellEvent::OutputStart),
+                "D" => Some(ShellEvent::CommandFinished(
+                    params.next().and_then(|code| code.parse().ok()),
+                )),
+                _ => None,
+            }
+        }
+        "7" => parse_osc7_path(rest).map(ShellEvent::WorkingDirectory),
+        _ => None,
+    }
+}
+
+/// Decode the directory of an OSC 7 `file://host/path` URL
+pub fn parse_osc7_path(url: &str) -> Option<PathBuf> {
+    let (_, rest) = url.split_once("://")?;
+    // Skip the host name; the path starts at the first slash after it
+    let path = &rest[rest.find('/')?..];
+    let path = percent_decode(path)?;
+    // file:///C:/Users -> C:/Users
+    #[cfg(windows)]
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Decode `%XX` escapes in a URL path
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// One command run at a shell prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommand {
+    /// Line where the prompt starts
+    pub prompt_line: usize,
+    /// Line where the command's output starts, once it runs
+    pub output_line: Option<usize>,
+    /// Whether the shell reported the command as finished
+    pub finished: bool,
+    /// Exit code reported when the command finished
+    pub exit_code: Option<i32>,
+    /// Working directory reported before the prompt
+    pub cwd: Option<PathBuf>,
+}
+
+impl ShellCommand {
+    /// Whether the command finished with a non-zero exit code
+    pub fn failed(&self) -> bool {
+        self.finished && self.exit_code.is_some_and(|code| code != 0)
+    }
+}
+
+/// Commands and working directory reported by a terminal's shell
+#[derive(Debug, Default)]
+pub struct ShellIntegration {
+    commands: Vec<ShellCommand>,
+    cwd: Option<PathBuf>,
+    /// Lines in the backing file before this terminal started writing to it
+    line_offset: usize,
+}
+
+impl ShellIntegration {
+    /// Record an event that arrived while the cursor was on `line`
+    /// (counted from the start of this terminal's own output)
+    pub fn record(&mut self, event: ShellEvent, line: usize) {
+        let line = line + self.line_offset;
+        match event {
+            ShellEvent::PromptStart => {
+                // A prompt redrawn before anything ran replaces the previous one
+                if self
+                    .commands
+                    .last()
+                    .is_some_and(|last| !last.finished && last.output_line.is_none())
+                {
+                    self.commands.pop();
+                }
+                if self.commands.len() >= MAX_COMMANDS {
+                    self.commands.remove(0);
+                }
+                self.commands.push(ShellCommand {
+                    prompt_line: line,
+                    output_line: None,
+                    finished: false,
+                    exit_code: None,
+                    cwd: self.cwd.clone(),
+                });
+            }
+            ShellEvent::CommandStart => {}
+            ShellEvent::OutputStart => {
+                if let Some(last) = self.commands.last_mut().filter(|last| !last.finished) {
+                    last.output_line = Some(line);
+                }
+            }
+            ShellEvent::CommandFinished(exit_code) => {
+                // Shells also send D before their first prompt, when nothing ran
+                if let Some(last) = self
+                    .commands
+                    .last_mut()
+                    .filter(|last| !last.finished && last.output_line.is_some())
+                {
+                    last.finished = true;
+                    last.exit_code = exit_code;
+                }
+            }
+            ShellEvent::WorkingDirectory(path) => {
+                self.cwd = Some(path);
+            }
+        }
+    }
+
+    /// Set how many lines of earlier sessions precede this terminal's output
+    /// in its backing file
+    pub fn set_line_offset(&mut self, lines: usize) {
+        self.line_offset = lines;
+    }
+
+    /// Whether the shell has reported any prompt
+    pub fn is_active(&self) -> bool {
+        !self.commands.is_empty()
+    }
+
+    /// The shell's current working directory, if it reported one
+    pub fn cwd(&self) -> Option<&PathBuf> {
+        self.cwd.as_ref()
+    }
+
+    /// All recorded commands, oldest first
+    pub fn commands(&self) -> &[ShellCommand] {
+        &self.commands
+    }
+
+    /// Prompt line of the last command that starts above `line`
+    pub fn previous_prompt(&self, line: usize) -> Option<usize> {
+        self.commands
+            .iter()
+            .rev()
+            .map(|command| command.prompt_line)
+            .find(|&prompt| prompt < line)
+    }
+
+    /// Prompt line of the first command that starts below `line`
+    pub fn next_prompt(&self, line: usize) -> Option<usize> {
+        self.commands
+            .iter()
+            .map(|command| command.prompt_line)
+            .find(|&prompt| prompt > line)
+    }
+
+    /// The command whose prompt or output contains `line`
+    pub fn command_at(&self, line: usize) -> Option<&ShellCommand> {
+        self.commands
+            .iter()
+            .rev()
+            .find(|command| command.prompt_line <= line)
+    }
+
+    /// Directory that relative paths printed on `line` are relative to:
+    /// the directory the command on that line ran in, or the current one
+    pub fn cwd_at(&self, line: usize) -> Option<&PathBuf> {
+        self.command_at(line)
+            .and_then(|command| command.cwd.as_ref())
+            .or(self.cwd.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_all(data: &[u8]) -> Vec<ShellEvent> {
+        OscScanner::new()
+            .scan(data)
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_osc133_with_both_terminators() {
+        let events =
+            scan_all(b"\x1b]133;A\x07$ \x1b]133;B\x1b\\ls\r\n\x1b]133;C\x07out\x1b]133;D;2\x07");
+        assert_eq!(
+            events,
+            vec![
+                ShellEvent::PromptStart,
+                ShellEvent::CommandStart,
+                ShellEvent::OutputStart,
+                ShellEvent::CommandFinished(Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_resumes_across_chunks() {
+        let mut scanner = OscScanner::new();
+        assert!(scanner.scan(b"text \x1b]13").is_empty());
+        let events = scanner.scan(b"3;D;0\x07more");
+        assert_eq!(events, vec![(6, ShellEvent::CommandFinished(Some(0)))]);
+    }
+
+    #[test]
+    fn test_scan_ignores_other_sequences() {
+        assert!(scan_all(b"\x1b]0;title\x07\x1b[31mred\x1b[0m").is_empty());
+    }
+
+    #[test]
+    fn test_parse_osc7_path() {
+        assert_eq!(
+            parse_osc7_path("file://myhost/home/user/my%20project"),
+            Some(PathBuf::from("/home/user/my project"))
+        );
+        assert_eq!(parse_osc7_path("file:///tmp"), Some(PathBuf::from("/tmp")));
+        assert_eq!(parse_osc7_path("not a url"), None);
+    }
+
+    #[test]
+    fn test_commands_track_lines_exit_codes_and_cwd() {
+        let mut shell = ShellIntegration::default();
+        shell.record(ShellEvent::CommandFinished(Some(0)), 0);
+        shell.record(ShellEvent::WorkingDirectory(PathBuf::from("/a")), 0);
+        shell.record(ShellEvent::PromptStart, 0);
+        shell.record(ShellEvent::OutputStart, 1);
+        shell.record(ShellEvent::CommandFinished(Some(1)), 3);
+        shell.record(ShellEvent::WorkingDirectory(PathBuf::from("/b")), 3);
+        shell.record(ShellEvent::PromptStart, 3);
+        shell.record(ShellEvent::OutputStart, 4);
+        shell.record(ShellEvent::CommandFinished(Some(0)), 5);
+        shell.record(ShellEvent::PromptStart, 5);
+
+        let commands = shell.commands();
+        assert_eq!(commands.len(), 3);
+        assert!(commands[0].failed());
+        assert!(!commands[1].failed());
+        assert!(!commands[2].finished);
+
+        assert_eq!(shell.previous_prompt(4), Some(3));
+        assert_eq!(shell.previous_prompt(3), Some(0));
+        assert_eq!(shell.next_prompt(3), Some(5));
+        assert_eq!(shell.next_prompt(5), None);
+
+        assert_eq!(shell.cwd_at(2), Some(&PathBuf::from("/a")));
+        assert_eq!(shell.cwd_at(4), Some(&PathBuf::from("/b")));
+    }
+
+    #[test]
+    fn test_redrawn_prompt_replaces_empty_command() {
+        let mut shell = ShellIntegration::default();
+        shell.record(ShellEvent::PromptStart, 0);
+        shell.record(ShellEvent::PromptStart, 1);
+        assert_eq!(shell.commands().len(), 1);
+        assert_eq!(shell.commands()[0].prompt_line, 1);
+    }
+
+    #[test]
+    fn test_line_offset_shifts_recorded_lines() {
+        let mut shell = ShellIntegration::default();
+        shell.set_line_offset(10);
+        shell.record(ShellEvent::PromptStart, 2);
+        assert_eq!(shell.commands()[0].prompt_line, 12);
+    }
+}
@@ -26,7 +26,13 @@
 //!
 //! `backing_file_history_end` tracks the byte offset where scrollback ends in the
 //! backing file, used for truncation when re-entering terminal mode.
+//!
+//! ## Shell Integration
+//!
+//! `process_output` also watches for OSC 133/OSC 7 sequences and records
+//! command boundaries and the working directory in `ShellIntegration`.
 
+use super::shell_integration::{OscScanner, ShellIntegration};
 use alacritty_terminal::event::{Event, EventListener};
 use alacritty_terminal::grid::Scroll;
 use alacritty_terminal::index::{Column, Line};
@@ -88,6 +94,10 @@ pub struct TerminalState {
     backing_file_history_end: u64,
     /// Queue of data to write back to the PTY (for DSR responses, etc.)
     pty_write_queue: Arc<Mutex<Vec<String>>>,
+    /// Scanner for shell integration sequences in the PTY output
+    osc_scanner: OscScanner,
+    /// Command boundaries and working directory reported by the shell
+    shell_integration: ShellIntegration,
 }
 
 impl TerminalState {
@@ -112,6 +122,8 @@ impl TerminalState {
             synced_history_lines: 0,
             backing_file_history_end: 0,
             pty_write_queue,
+            osc_scanner: OscScanner::new(),
+            shell_integration: ShellIntegration::default(),
         }
     }
 
@@ -129,10 +141,34 @@ impl TerminalState {
 
     /// Process output from the PTY
     pub fn process_output(&mut self, data: &[u8]) {
-        self.parser.advance(&mut self.term, data);
+        // Feed the emulator up to each shell integration sequence, so the
+        // event is recorded on the line the cursor is on at that point
+        let mut start = 0;
+        for (end, event) in self.osc_scanner.scan(data) {
+            self.parser.advance(&mut self.term, &data[start..end]);
+            start = end;
+            let line = self.cursor_history_line();
+            self.shell_integration.record(event, line);
+        }
+        self.parser.advance(&mut self.term, &data[start..]);
         self.dirty = true;
     }
 
+    /// Line of the cursor counted from the oldest scrollback line
+    fn cursor_history_line(&self) -> usize {
+        self.history_size() + self.term.grid().cursor.point.line.0.max(0) as usize
+    }
+
+    /// Command boundaries and working directory reported by the shell
+    pub fn shell_integration(&self) -> &ShellIntegration {
+        &self.shell_integration
+    }
+
+    /// Mutable access to the shell integration state
+    pub fn shell_integration_mut(&mut self) -> &mut ShellIntegration {
+        &mut self.shell_integration
+    }
+
     /// Resize the terminal
     pub fn resize(&mut self, cols: u16, rows: u16) {
         if cols != self.cols || rows != self.rows {
@@ -335,6 +371,12 @@ impl TerminalState {
         self.term.mode().contains(TermMode::ALTERNATE_SCROLL)
     }
 
+    /// Check if bracketed paste mode is enabled.
+    /// When enabled, pasted text should be wrapped in `ESC [200~` / `ESC [201~`.
+    pub fn uses_bracketed_paste(&self) -> bool {
+        self.term.mode().contains(TermMode::BRACKETED_PASTE)
+    }
+
     // =========================================================================
     // Incremental scrollback streaming
     // =========================================================================
@@ -902,6 +944,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shell_integration_records_command_lines() {
+        let mut state = TerminalState::new(80, 5);
+        state.process_output(b"\x1b]7;file://host/work\x07");
+        for i in 0..3 {
+            state.process_output(b"\x1b]133;A\x07$ make\r\n\x1b]133;C\x07");
+            state.process_output(format!("output {}\r\n", i).as_bytes());
+            state.process_output(format!("\x1b]133;D;{}\x07", i).as_bytes());
+        }
+
+        let shell = state.shell_integration();
+        let prompts: Vec<usize> = shell.commands().iter().map(|c| c.prompt_line).collect();
+        // Each command takes two lines, and the early ones have scrolled into history
+        assert_eq!(prompts, vec![0, 2, 4]);
+        assert_eq!(shell.commands()[1].output_line, Some(3));
+        assert_eq!(shell.commands()[2].exit_code, Some(2));
+        assert_eq!(shell.cwd_at(3), Some(&std::path::PathBuf::from("/work")));
+    }
+
     #[test]
     fn test_dsr_cursor_position_response() {
         // Test that sending a DSR (Device Status Report) query generates a response
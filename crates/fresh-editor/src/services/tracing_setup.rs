@@ -9,6 +9,8 @@ use std::sync::Arc;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
+use super::log_buffer::{LogBuffer, LogBufferLayer};
+use super::startup_profile::{StartupProfileHandle, StartupProfileLayer};
 use super::status_log::{StatusLogHandle, StatusLogLayer};
 use super::warning_log::{WarningLogHandle, WarningLogLayer};
 
@@ -16,38 +18,62 @@ use super::warning_log::{WarningLogHandle, WarningLogLayer};
 pub struct TracingHandles {
     pub warning: WarningLogHandle,
     pub status: StatusLogHandle,
+    /// Recent log events, for the `*Log*` buffer
+    pub log_buffer: LogBuffer,
+    /// Present when started with `--profile-startup`
+    pub startup_profile: Option<StartupProfileHandle>,
 }
 
 /// Initialize the global tracing subscriber with file logging and warning/status capture.
 ///
 /// This sets up:
-/// - File-based logging with the given log file
+/// - File-based logging with the given log file, if it can be created
+/// - An in-memory ring buffer of recent events for the `*Log*` buffer
 /// - Environment-based filtering (RUST_LOG) with DEBUG default
 /// - Warning log layer that captures WARN+ to a separate file
 /// - Status log layer that captures status messages to a separate file
+/// - Startup profile layer that times spans, if `profile_path` is given
 ///
 /// Returns the tracing handles if successful, None if setup failed.
-pub fn init_global(log_file_path: &Path) -> Option<TracingHandles> {
+pub fn init_global(log_file_path: &Path, profile_path: Option<&Path>) -> Option<TracingHandles> {
     let (warning_layer, warning_handle) = super::warning_log::create().ok()?;
     let (status_layer, status_handle) = super::status_log::create().ok()?;
-    let log_file = File::create(log_file_path).ok()?;
-
-    let subscriber = build_subscriber(log_file, Some(warning_layer), Some(status_layer));
+    let (profile_layer, profile_handle) =
+        match profile_path.map(|path| super::startup_profile::create(path.to_path_buf())) {
+            Some((layer, handle)) => (Some(layer), Some(handle)),
+            None => (None, None),
+        };
+    let (log_buffer_layer, log_buffer) = super::log_buffer::create();
+    // Without a log file, events still reach the log buffer
+    let log_file = File::create(log_file_path).ok();
+
+    let subscriber = build_subscriber(
+        log_file,
+        Some(log_buffer_layer),
+        Some(warning_layer),
+        Some(status_layer),
+        profile_layer,
+    );
     subscriber.init();
 
     Some(TracingHandles {
         warning: warning_handle,
         status: status_handle,
+        log_buffer,
+        startup_profile: profile_handle,
     })
 }
 
-/// Build a subscriber with file logging and optional warning/status layers.
+/// Build a subscriber with optional file logging and optional log buffer,
+/// warning, status and profile layers.
 ///
 /// This is the core subscriber configuration shared between production and tests.
 pub fn build_subscriber(
-    log_file: File,
+    log_file: Option<File>,
+    log_buffer_layer: Option<LogBufferLayer>,
     warning_layer: Option<WarningLogLayer>,
     status_layer: Option<StatusLogLayer>,
+    profile_layer: Option<StartupProfileLayer>,
 ) -> impl tracing::Subscriber + Send + Sync {
     let env_filter = EnvFilter::from_default_env()
         .add_directive(tracing::Level::DEBUG.into())
@@ -55,13 +81,15 @@ pub fn build_subscriber(
         .add_directive("swc_ecma_transforms_base=info".parse().unwrap())
         .add_directive("swc_common=info".parse().unwrap());
 
-    let fmt_layer = fmt::layer().with_writer(Arc::new(log_file));
+    let fmt_layer = log_file.map(|file| fmt::layer().with_writer(Arc::new(file)));
 
     tracing_subscriber::registry()
         .with(fmt_layer)
         .with(env_filter)
+        .with(log_buffer_layer)
         .with(warning_layer)
         .with(status_layer)
+        .with(profile_layer)
 }
 
 #[cfg(test)]
@@ -92,9 +120,11 @@ mod tests {
             super::super::status_log::create_with_path(status_log_path.to_path_buf()).unwrap();
 
         let subscriber = build_subscriber(
-            log_file.reopen().unwrap(),
+            Some(log_file.reopen().unwrap()),
+            None,
             Some(warning_layer),
             Some(status_layer),
+            None,
         );
 
         TestSubscriber {
@@ -14,9 +14,11 @@ use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::highlighter::Language;
 use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::text_objects::{self, TextObject};
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
 use crate::view::conceal::ConcealManager;
+use crate::view::link_overlay::LinkOverlay;
 use crate::view::margin::{MarginAnnotation, MarginContent, MarginManager, MarginPosition};
 use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, UnderlineStyle};
 use crate::view::popup::{
@@ -24,6 +26,7 @@ use crate::view::popup::{
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
 use crate::view::soft_break::SoftBreakManager;
+use crate::view::trailing_whitespace_overlay::TrailingWhitespaceOverlay;
 use crate::view::virtual_text::VirtualTextManager;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
@@ -64,6 +67,14 @@ pub struct BufferSettings {
     /// Used for visual display of tab characters and indent calculations.
     /// Set based on language config; can be changed per-buffer by user
     pub tab_size: usize,
+
+    /// Whether saving removes trailing whitespace, when an `.editorconfig`
+    /// file says so; `None` follows the editor config
+    pub trim_trailing_whitespace: Option<bool>,
+
+    /// Whether saving adds a missing final newline, when an `.editorconfig`
+    /// file says so; `None` follows the editor config
+    pub insert_final_newline: Option<bool>,
 }
 
 impl Default for BufferSettings {
@@ -72,6 +83,8 @@ impl Default for BufferSettings {
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4,
+            trim_trailing_whitespace: None,
+            insert_final_newline: None,
         }
     }
 }
@@ -103,6 +116,9 @@ pub struct EditorState {
     /// Conceal ranges for hiding/replacing byte ranges during rendering
     pub conceals: ConcealManager,
 
+    /// Folded ranges, hidden in every view mode and shown as a placeholder
+    pub folds: ConcealManager,
+
     /// Soft break points for marker-based line wrapping during rendering
     pub soft_breaks: SoftBreakManager,
 
@@ -132,6 +148,11 @@ pub struct EditorState {
     /// but navigation, selection, and copy are still allowed
     pub editing_disabled: bool,
 
+    /// Whether the buffer is in read-only mode (default false)
+    /// Set for unwritable files and `--readonly`; `apply` ignores events
+    /// that would modify the buffer until the user toggles it off
+    pub read_only: bool,
+
     /// Per-buffer user settings (tab size, indentation style, etc.)
     /// These settings are preserved across file reloads (auto-revert)
     pub buffer_settings: BufferSettings,
@@ -151,6 +172,12 @@ pub struct EditorState {
     /// Bracket matching highlight overlay
     pub bracket_highlight_overlay: BracketHighlightOverlay,
 
+    /// Trailing whitespace highlight overlay
+    pub trailing_whitespace_overlay: TrailingWhitespaceOverlay,
+
+    /// URL link overlay
+    pub link_overlay: LinkOverlay,
+
     /// Cached LSP semantic tokens (converted to buffer byte ranges)
     pub semantic_tokens: Option<SemanticTokenStore>,
 
@@ -177,6 +204,7 @@ impl EditorState {
             marker_list: MarkerList::new(),
             virtual_texts: VirtualTextManager::new(),
             conceals: ConcealManager::new(),
+            folds: ConcealManager::new(),
             soft_breaks: SoftBreakManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
@@ -185,12 +213,15 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            read_only: false,
             buffer_settings: BufferSettings::default(),
             reference_highlighter: ReferenceHighlighter::new(),
             is_composite_buffer: false,
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            link_overlay: LinkOverlay::new(),
             semantic_tokens: None,
             language: "text".to_string(), // Default to plain text
         }
@@ -241,7 +272,13 @@ impl EditorState {
     ) -> anyhow::Result<Self> {
         let buffer = Buffer::load_from_file(path, large_file_threshold, fs)?;
 
-        let highlighter = HighlightEngine::for_file(path, registry);
+        // Large files are read in chunks as they scroll into view; like LSP,
+        // highlighting is skipped so rendering never reads beyond the viewport
+        let highlighter = if buffer.is_large_file() {
+            HighlightEngine::None
+        } else {
+            HighlightEngine::for_file(path, registry)
+        };
         let language = Language::from_path(path);
         let mut reference_highlighter = ReferenceHighlighter::new();
         let language_name = if let Some(lang) = &language {
@@ -264,6 +301,7 @@ impl EditorState {
             marker_list,
             virtual_texts: VirtualTextManager::new(),
             conceals: ConcealManager::new(),
+            folds: ConcealManager::new(),
             soft_breaks: SoftBreakManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
@@ -272,12 +310,15 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            read_only: false,
             buffer_settings: BufferSettings::default(),
             reference_highlighter,
             is_composite_buffer: false,
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            link_overlay: LinkOverlay::new(),
             semantic_tokens: None,
             language: language_name,
         })
@@ -300,8 +341,25 @@ impl EditorState {
         fs: Arc<dyn FileSystem + Send + Sync>,
     ) -> anyhow::Result<Self> {
         let buffer = Buffer::load_from_file(path, large_file_threshold, fs)?;
+        Ok(Self::from_loaded_file(buffer, path, registry, languages))
+    }
 
-        let highlighter = HighlightEngine::for_file_with_languages(path, registry, languages);
+    /// Create an editor state for a file that has already been read
+    ///
+    /// Used by [`Self::from_file_with_languages`] and for files loaded on a
+    /// background thread.
+    pub fn from_loaded_file(
+        buffer: Buffer,
+        path: &std::path::Path,
+        registry: &GrammarRegistry,
+        languages: &std::collections::HashMap<String, crate::config::LanguageConfig>,
+    ) -> Self {
+        // Large files are not highlighted (see from_file)
+        let highlighter = if buffer.is_large_file() {
+            HighlightEngine::None
+        } else {
+            HighlightEngine::for_file_with_languages(path, registry, languages)
+        };
 
         let language = Language::from_path(path);
         let mut reference_highlighter = ReferenceHighlighter::new();
@@ -318,7 +376,7 @@ impl EditorState {
             marker_list.adjust_for_insert(0, buffer.len());
         }
 
-        Ok(Self {
+        Self {
             buffer,
             highlighter,
             indent_calculator: RefCell::new(IndentCalculator::new()),
@@ -326,6 +384,7 @@ impl EditorState {
             marker_list,
             virtual_texts: VirtualTextManager::new(),
             conceals: ConcealManager::new(),
+            folds: ConcealManager::new(),
             soft_breaks: SoftBreakManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
@@ -334,15 +393,18 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            read_only: false,
             buffer_settings: BufferSettings::default(),
             reference_highlighter,
             is_composite_buffer: false,
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            link_overlay: LinkOverlay::new(),
             semantic_tokens: None,
             language: language_name,
-        })
+        }
     }
 
     /// Create an editor state from a buffer and highlighter
@@ -373,6 +435,7 @@ impl EditorState {
             marker_list,
             virtual_texts: VirtualTextManager::new(),
             conceals: ConcealManager::new(),
+            folds: ConcealManager::new(),
             soft_breaks: SoftBreakManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
@@ -381,12 +444,15 @@ impl EditorState {
             text_properties: TextPropertyManager::new(),
             show_cursors: true,
             editing_disabled: false,
+            read_only: false,
             buffer_settings: BufferSettings::default(),
             reference_highlighter,
             is_composite_buffer: false,
             debug_highlight_mode: false,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            link_overlay: LinkOverlay::new(),
             semantic_tokens: None,
             language: language_name,
         }
@@ -409,9 +475,8 @@ impl EditorState {
         // Insert text into buffer
         self.buffer.insert(position, text);
 
-        // Invalidate highlight cache for edited range
-        self.highlighter
-            .invalidate_range(position..position + text.len());
+        // Apply the edit to the highlighter's parse tree / cache
+        self.highlighter.adjust_for_insert(position, text);
 
         // Note: reference_highlight_overlay uses markers that auto-adjust,
         // so no manual invalidation needed
@@ -458,8 +523,8 @@ impl EditorState {
         // Delete from buffer
         self.buffer.delete(range.clone());
 
-        // Invalidate highlight cache for edited range
-        self.highlighter.invalidate_range(range.clone());
+        // Apply the edit to the highlighter's parse tree / cache
+        self.highlighter.adjust_for_delete(range.clone());
 
         // Note: reference_highlight_overlay uses markers that auto-adjust,
         // so no manual invalidation needed
@@ -493,6 +558,10 @@ impl EditorState {
     /// Apply an event to the state - THE ONLY WAY TO MODIFY STATE
     /// This is the heart of the event-driven architecture
     pub fn apply(&mut self, cursors: &mut Cursors, event: &Event) {
+        if self.read_only && event.modifies_buffer() {
+            tracing::debug!("Ignoring edit to read-only buffer");
+            return;
+        }
         match event {
             Event::Insert {
                 position,
@@ -933,6 +1002,8 @@ fn convert_popup_data_to_popup(data: &PopupData) -> Popup {
         background_style: Style::default().bg(Color::Rgb(30, 30, 30)),
         scroll_offset: 0,
         text_selection: None,
+        id: None,
+        z_index: 0,
     }
 }
 
@@ -1004,6 +1075,24 @@ impl EditorState {
         }
     }
 
+    /// Range of a text object around `position`, or `None` if there is none
+    /// there or the buffer isn't fully loaded
+    pub fn text_object_range(
+        &self,
+        position: usize,
+        object: TextObject,
+        around: bool,
+    ) -> Option<Range<usize>> {
+        let text = self.buffer.to_string()?;
+        text_objects::text_object_range(
+            &text,
+            position,
+            object,
+            around,
+            self.highlighter.language(),
+        )
+    }
+
     /// Get the content of a line by its byte offset
     ///
     /// Returns the line containing the given offset, along with its start position.
@@ -1382,6 +1471,54 @@ mod tests {
         assert_eq!(cursors.primary().position, 5);
     }
 
+    #[test]
+    fn test_apply_read_only() {
+        let mut state = EditorState::new(
+            80,
+            24,
+            crate::config::LARGE_FILE_THRESHOLD_BYTES as usize,
+            test_fs(),
+        );
+        let mut cursors = Cursors::new();
+        let cursor_id = cursors.primary_id();
+
+        state.apply(
+            &mut cursors,
+            &Event::Insert {
+                position: 0,
+                text: "hello".to_string(),
+                cursor_id,
+            },
+        );
+        state.read_only = true;
+
+        state.apply(
+            &mut cursors,
+            &Event::Delete {
+                range: 0..5,
+                deleted_text: "hello".to_string(),
+                cursor_id,
+            },
+        );
+        assert_eq!(state.buffer.to_string().unwrap(), "hello");
+        assert_eq!(cursors.primary().position, 5);
+
+        // Cursor movement is still allowed
+        state.apply(
+            &mut cursors,
+            &Event::MoveCursor {
+                cursor_id,
+                old_position: 5,
+                new_position: 2,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            },
+        );
+        assert_eq!(cursors.primary().position, 2);
+    }
+
     #[test]
     fn test_apply_move_cursor() {
         let mut state = EditorState::new(
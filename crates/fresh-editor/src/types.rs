@@ -20,6 +20,7 @@ pub mod context_keys {
     pub const LSP_AVAILABLE: &str = "lsp_available";
     pub const FILE_EXPLORER_SHOW_HIDDEN: &str = "file_explorer_show_hidden";
     pub const FILE_EXPLORER_SHOW_GITIGNORED: &str = "file_explorer_show_gitignored";
+    pub const FILE_EXPLORER_SHOW_CHANGED_ONLY: &str = "file_explorer_show_changed_only";
     pub const HAS_SELECTION: &str = "has_selection";
     pub const FORMATTER_AVAILABLE: &str = "formatter_available";
     pub const INLAY_HINTS: &str = "inlay_hints";
@@ -108,6 +109,12 @@ pub struct LspServerConfig {
     /// These are passed in the `initializationOptions` field of the LSP Initialize request
     #[serde(default)]
     pub initialization_options: Option<serde_json::Value>,
+
+    /// Extra servers attached to the same buffers (e.g. a linter next to the main server).
+    /// Completions and diagnostics from all servers are merged; formatting uses the
+    /// first server that supports it, and other requests go to the main server.
+    #[serde(default)]
+    pub additional_servers: Vec<AdditionalLspServerConfig>,
 }
 
 impl LspServerConfig {
@@ -133,6 +140,54 @@ impl LspServerConfig {
             initialization_options: self
                 .initialization_options
                 .or_else(|| defaults.initialization_options.clone()),
+            additional_servers: if self.additional_servers.is_empty() {
+                defaults.additional_servers.clone()
+            } else {
+                self.additional_servers
+            },
+        }
+    }
+}
+
+/// A language server that runs alongside the main server for a language
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/name"))]
+pub struct AdditionalLspServerConfig {
+    /// Name shown in status messages (e.g. "tailwindcss")
+    pub name: String,
+
+    /// Command to spawn the server
+    pub command: String,
+
+    /// Arguments to pass to the server
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Whether the server is enabled
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Process resource limits (memory and CPU)
+    #[serde(default)]
+    pub process_limits: ProcessLimits,
+
+    /// Custom initialization options to send to the server
+    #[serde(default)]
+    pub initialization_options: Option<serde_json::Value>,
+}
+
+impl AdditionalLspServerConfig {
+    /// Build the server config used to spawn this server. It starts together
+    /// with the main server, so it shares its `auto_start` setting.
+    pub fn to_server_config(&self, auto_start: bool) -> LspServerConfig {
+        LspServerConfig {
+            command: self.command.clone(),
+            args: self.args.clone(),
+            enabled: self.enabled,
+            auto_start,
+            process_limits: self.process_limits.clone(),
+            initialization_options: self.initialization_options.clone(),
+            additional_servers: Vec::new(),
         }
     }
 }
@@ -31,13 +31,8 @@ impl ColorCapability {
     /// Can be overridden with FRESH_COLOR_MODE env var: "truecolor", "256", or "16"
     pub fn detect() -> Self {
         // Check for manual override first
-        if let Ok(mode) = std::env::var("FRESH_COLOR_MODE") {
-            match mode.to_lowercase().as_str() {
-                "truecolor" | "24bit" | "true" => return ColorCapability::TrueColor,
-                "256" | "256color" => return ColorCapability::Color256,
-                "16" | "basic" | "ansi" => return ColorCapability::Color16,
-                _ => {} // Fall through to auto-detection
-            }
+        if let Some(capability) = Self::env_override() {
+            return capability;
         }
 
         // Check TERM first for multiplexers that don't support truecolor
@@ -105,6 +100,11 @@ impl ColorCapability {
                 return ColorCapability::TrueColor;
             }
 
+            // The terminfo entry knows the real palette size for everything else
+            if let Some(colors) = terminfo_colors(&term) {
+                return Self::from_color_count(colors);
+            }
+
             // xterm usually supports 256
             if t.starts_with("xterm") {
                 return ColorCapability::Color256;
@@ -119,155 +119,230 @@ impl ColorCapability {
         // Default to 256 as safe middle ground
         ColorCapability::Color256
     }
+
+    /// Resolve the capability to use, honoring the `editor.color_mode` config
+    /// override. `auto` (the default) falls back to [`ColorCapability::detect`].
+    /// The FRESH_COLOR_MODE env var still wins so a single run can be forced.
+    pub fn resolve(mode: crate::config::ColorMode) -> Self {
+        use crate::config::ColorMode;
+        if let Some(capability) = Self::env_override() {
+            return capability;
+        }
+        match mode {
+            ColorMode::Auto => Self::detect(),
+            ColorMode::TrueColor => ColorCapability::TrueColor,
+            ColorMode::Color256 => ColorCapability::Color256,
+            ColorMode::Color16 => ColorCapability::Color16,
+        }
+    }
+
+    /// Capability forced with the FRESH_COLOR_MODE env var, if set to a known value
+    fn env_override() -> Option<Self> {
+        let mode = std::env::var("FRESH_COLOR_MODE").ok()?;
+        match mode.to_lowercase().as_str() {
+            "truecolor" | "24bit" | "true" => Some(ColorCapability::TrueColor),
+            "256" | "256color" => Some(ColorCapability::Color256),
+            "16" | "basic" | "ansi" => Some(ColorCapability::Color16),
+            _ => None, // Fall through to auto-detection
+        }
+    }
+
+    /// Map a terminfo `colors` value to a capability level
+    fn from_color_count(colors: u32) -> Self {
+        if colors >= 1 << 24 {
+            ColorCapability::TrueColor
+        } else if colors >= 256 {
+            ColorCapability::Color256
+        } else {
+            ColorCapability::Color16
+        }
+    }
 }
 
-/// Convert an RGB color to the nearest 256-color palette index
+/// Look up the `colors` capability of a terminal in the compiled terminfo database.
 ///
-/// The 256-color palette consists of:
-/// - 0-15: Standard ANSI colors (basic 16 colors)
-/// - 16-231: 6x6x6 color cube (216 colors)
-/// - 232-255: Grayscale ramp (24 shades)
-fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
-    // Check if it's close to grayscale
-    let gray_threshold = 8;
-    if r.abs_diff(g) < gray_threshold && g.abs_diff(b) < gray_threshold {
-        // Use grayscale ramp (232-255)
-        let gray = (r as u16 + g as u16 + b as u16) / 3;
-        if gray < 8 {
-            return 16; // Use black from color cube
-        }
-        if gray > 248 {
-            return 231; // Use white from color cube
+/// Searches the same directories as ncurses ($TERMINFO, ~/.terminfo, $TERMINFO_DIRS
+/// and the system locations). Returns None if no entry is found or it has no
+/// color count.
+fn terminfo_colors(term: &str) -> Option<u32> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<std::path::PathBuf> = Vec::new();
+    if let Ok(dir) = std::env::var("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".terminfo"));
+    }
+    if let Ok(list) = std::env::var("TERMINFO_DIRS") {
+        for dir in list.split(':') {
+            // An empty entry stands for the default system directory
+            let dir = if dir.is_empty() {
+                "/usr/share/terminfo"
+            } else {
+                dir
+            };
+            dirs.push(dir.into());
         }
-        // Map to grayscale ramp (232-255, 24 shades = indices 0-23)
-        // Formula maps gray 8-248 to offset 0-23, avoiding u8 overflow
-        return 232 + ((gray - 8) * 23 / 240) as u8;
+    }
+    for dir in [
+        "/etc/terminfo",
+        "/lib/terminfo",
+        "/usr/share/terminfo",
+        "/usr/lib/terminfo",
+    ] {
+        dirs.push(dir.into());
     }
 
-    // Map to 6x6x6 color cube (indices 16-231)
-    // Each RGB component maps to 0-5
-    let r_idx = if r < 48 {
-        0
-    } else {
-        ((r as u16 - 35) * 5 / 220).min(5) as u8
+    dirs.iter().find_map(|dir| {
+        // Linux uses the first letter as subdirectory, macOS its hex code
+        [first.to_string(), format!("{:x}", first as u32)]
+            .iter()
+            .find_map(|sub| std::fs::read(dir.join(sub).join(term)).ok())
+            .and_then(|data| parse_terminfo_colors(&data))
+    })
+}
+
+/// Extract the `colors` numeric capability from a compiled terminfo entry.
+///
+/// Supports the legacy format (16-bit numbers) and the extended-number format
+/// used by entries such as `xterm-direct` whose color count exceeds 32767.
+fn parse_terminfo_colors(data: &[u8]) -> Option<u32> {
+    /// Index of `colors` in the terminfo numeric capabilities
+    const COLORS_INDEX: usize = 13;
+
+    let read_i16 = |offset: usize| -> Option<i16> {
+        Some(i16::from_le_bytes([
+            *data.get(offset)?,
+            *data.get(offset + 1)?,
+        ]))
     };
-    let g_idx = if g < 48 {
-        0
-    } else {
-        ((g as u16 - 35) * 5 / 220).min(5) as u8
+    let number_size = match read_i16(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
     };
-    let b_idx = if b < 48 {
-        0
+    let names_size = read_i16(2)?.max(0) as usize;
+    let bool_count = read_i16(4)?.max(0) as usize;
+    let num_count = read_i16(6)?.max(0) as usize;
+    if COLORS_INDEX >= num_count {
+        return None;
+    }
+
+    // Numbers start on an even offset after the names and booleans
+    let mut offset = 12 + names_size + bool_count;
+    offset += offset % 2;
+    offset += COLORS_INDEX * number_size;
+
+    let value = if number_size == 2 {
+        read_i16(offset)? as i32
     } else {
-        ((b as u16 - 35) * 5 / 220).min(5) as u8
+        i32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?)
     };
-
-    16 + 36 * r_idx + 6 * g_idx + b_idx
+    (value > 0).then_some(value as u32)
 }
 
-/// Convert an RGB color to the nearest basic 16 ANSI color
-fn rgb_to_16(r: u8, g: u8, b: u8) -> Color {
-    // Calculate luminance and saturation to help with color matching
-    let max = r.max(g).max(b);
-    let min = r.min(g).min(b);
-    let lum = (max as u16 + min as u16) / 2;
-    let is_bright = lum > 127;
-
-    // Check for grayscale
-    let gray_threshold = 30;
-    if max - min < gray_threshold {
-        return if lum < 64 {
-            Color::Black
-        } else if lum < 128 {
-            Color::DarkGray
-        } else if lum < 192 {
-            Color::Gray
-        } else {
-            Color::White
-        };
-    }
+/// Perceptual distance between two RGB colors.
+///
+/// Uses the "redmean" weighted Euclidean distance, a cheap approximation of
+/// how different two colors look that is much closer to human perception than
+/// plain RGB distance (green differences count more, blue less).
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let rmean = (a.0 as i32 + b.0 as i32) / 2;
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (((512 + rmean) * dr * dr) / 256 + 4 * dg * dg + ((767 - rmean) * db * db) / 256) as u32
+}
 
-    // Determine dominant color(s)
-    let r_dom = r >= g && r >= b;
-    let g_dom = g >= r && g >= b;
-    let b_dom = b >= r && b >= g;
-
-    // Determine if secondary colors are significant
-    let threshold = max / 2;
-    let r_sig = r > threshold;
-    let g_sig = g > threshold;
-    let b_sig = b > threshold;
-
-    // Map to ANSI colors
-    match (r_dom, g_dom, b_dom, r_sig, g_sig, b_sig) {
-        // Pure colors
-        (true, false, false, true, false, false) => {
-            if is_bright {
-                Color::LightRed
-            } else {
-                Color::Red
-            }
-        }
-        (false, true, false, false, true, false) => {
-            if is_bright {
-                Color::LightGreen
-            } else {
-                Color::Green
-            }
+/// Channel levels of the 6x6x6 color cube (indices 16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The basic 16 ANSI colors with their xterm default RGB values
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// RGB value of a 256-color palette entry (xterm defaults)
+fn indexed_to_rgb(idx: u8) -> (u8, u8, u8) {
+    match idx {
+        0..=15 => ANSI_16[idx as usize].1,
+        16..=231 => {
+            let i = idx - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[((i % 36) / 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
         }
-        (false, false, true, false, false, true) => {
-            if is_bright {
-                Color::LightBlue
-            } else {
-                Color::Blue
-            }
+        232..=255 => {
+            let gray = 8 + (idx - 232) * 10;
+            (gray, gray, gray)
         }
+    }
+}
 
-        // Yellow (red + green)
-        (_, _, false, true, true, false) => {
-            if is_bright {
-                Color::LightYellow
-            } else {
-                Color::Yellow
-            }
-        }
+/// Index into `CUBE_LEVELS` of the level closest to a channel value
+fn nearest_cube_level(value: u8) -> u8 {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, level)| level.abs_diff(value))
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
 
-        // Cyan (green + blue)
-        (false, _, _, false, true, true) => {
-            if is_bright {
-                Color::LightCyan
-            } else {
-                Color::Cyan
-            }
-        }
+/// Convert an RGB color to the nearest 256-color palette index
+///
+/// The 256-color palette consists of:
+/// - 0-15: Standard ANSI colors (basic 16 colors)
+/// - 16-231: 6x6x6 color cube (216 colors)
+/// - 232-255: Grayscale ramp (24 shades)
+///
+/// The 16 ANSI colors are skipped because terminals commonly remap them.
+/// Near-gray colors only match neutral entries so they don't pick up a tint.
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    let target = (r, g, b);
+    let gray = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+    let ramp_idx = 232 + (gray.saturating_sub(3) / 10).min(23);
 
-        // Magenta (red + blue)
-        (_, false, _, true, false, true) => {
-            if is_bright {
-                Color::LightMagenta
-            } else {
-                Color::Magenta
-            }
-        }
+    let gray_threshold = 8;
+    let candidates: Vec<u8> = if r.abs_diff(g) < gray_threshold && g.abs_diff(b) < gray_threshold {
+        // Black and white from the cube extend the ramp at both ends
+        vec![16, 231, ramp_idx]
+    } else {
+        let cube_idx =
+            16 + 36 * nearest_cube_level(r) + 6 * nearest_cube_level(g) + nearest_cube_level(b);
+        vec![cube_idx, ramp_idx]
+    };
 
-        // White-ish (all colors significant)
-        (_, _, _, true, true, true) => {
-            if is_bright {
-                Color::White
-            } else {
-                Color::Gray
-            }
-        }
+    candidates
+        .into_iter()
+        .min_by_key(|&idx| color_distance(target, indexed_to_rgb(idx)))
+        .unwrap_or(16)
+}
 
-        // Fallback
-        _ => {
-            if is_bright {
-                Color::White
-            } else {
-                Color::DarkGray
-            }
-        }
-    }
+/// Convert an RGB color to the perceptually nearest basic 16 ANSI color
+fn rgb_to_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| color_distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
 }
 
 /// Convert a Color to the appropriate format for the terminal's capability
@@ -288,47 +363,11 @@ pub fn convert_color(color: Color, capability: ColorCapability) -> Color {
 
 /// Convert a 256-color index to the nearest 16 color
 fn indexed_to_16(idx: u8) -> Color {
-    match idx {
-        // Standard colors (0-7)
-        0 => Color::Black,
-        1 => Color::Red,
-        2 => Color::Green,
-        3 => Color::Yellow,
-        4 => Color::Blue,
-        5 => Color::Magenta,
-        6 => Color::Cyan,
-        7 => Color::Gray,
-        // Bright colors (8-15)
-        8 => Color::DarkGray,
-        9 => Color::LightRed,
-        10 => Color::LightGreen,
-        11 => Color::LightYellow,
-        12 => Color::LightBlue,
-        13 => Color::LightMagenta,
-        14 => Color::LightCyan,
-        15 => Color::White,
-        // Color cube (16-231) - convert back to RGB then to 16
-        16..=231 => {
-            let idx = idx - 16;
-            let r = (idx / 36) * 51;
-            let g = ((idx % 36) / 6) * 51;
-            let b = (idx % 6) * 51;
-            rgb_to_16(r, g, b)
-        }
-        // Grayscale (232-255)
-        232..=255 => {
-            let gray = (idx - 232) * 10 + 8;
-            if gray < 64 {
-                Color::Black
-            } else if gray < 128 {
-                Color::DarkGray
-            } else if gray < 192 {
-                Color::Gray
-            } else {
-                Color::White
-            }
-        }
+    if idx < 16 {
+        return ANSI_16[idx as usize].0;
     }
+    let (r, g, b) = indexed_to_rgb(idx);
+    rgb_to_16(r, g, b)
 }
 
 /// Convert all colors in a ratatui Buffer for the given color capability
@@ -436,6 +475,72 @@ mod tests {
         assert_eq!(rgb_to_16(255, 255, 255), Color::White);
     }
 
+    /// Build a minimal compiled terminfo entry with the given `colors` value
+    fn terminfo_entry(colors: i32, extended: bool) -> Vec<u8> {
+        let names = b"test|test terminal\0";
+        let num_count: i16 = 14;
+        let magic: i16 = if extended { 0o1036 } else { 0o432 };
+        let mut data = Vec::new();
+        for value in [magic, names.len() as i16, 1, num_count, 0, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(names);
+        data.push(0); // one boolean
+        if data.len() % 2 == 1 {
+            data.push(0);
+        }
+        for i in 0..num_count as i32 {
+            let value = if i == 13 { colors } else { -1 };
+            if extended {
+                data.extend_from_slice(&value.to_le_bytes());
+            } else {
+                data.extend_from_slice(&(value as i16).to_le_bytes());
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_terminfo_colors() {
+        assert_eq!(
+            parse_terminfo_colors(&terminfo_entry(256, false)),
+            Some(256)
+        );
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(8, false)), Some(8));
+        assert_eq!(
+            parse_terminfo_colors(&terminfo_entry(1 << 24, true)),
+            Some(1 << 24)
+        );
+        assert_eq!(parse_terminfo_colors(&terminfo_entry(-1, false)), None);
+        assert_eq!(parse_terminfo_colors(b"garbage"), None);
+
+        assert_eq!(
+            ColorCapability::from_color_count(1 << 24),
+            ColorCapability::TrueColor
+        );
+        assert_eq!(
+            ColorCapability::from_color_count(256),
+            ColorCapability::Color256
+        );
+        assert_eq!(
+            ColorCapability::from_color_count(8),
+            ColorCapability::Color16
+        );
+    }
+
+    #[test]
+    fn test_downgrade_picks_perceptually_nearest() {
+        // A muted teal should stay teal-ish, not collapse to gray
+        assert_eq!(rgb_to_16(0, 150, 150), Color::Cyan);
+        // Dark orange is closer to red than yellow
+        assert_eq!(rgb_to_16(200, 40, 0), Color::Red);
+        // Cube levels are not evenly spaced: 95 is the first non-zero level
+        assert_eq!(rgb_to_256(95, 0, 0), 52);
+        // Index round-trips through the 16-color downgrade using real cube RGB
+        assert_eq!(indexed_to_16(196), Color::LightRed);
+        assert_eq!(indexed_to_16(4), Color::Blue);
+    }
+
     #[test]
     fn test_convert_color_truecolor() {
         let color = Color::Rgb(100, 150, 200);
@@ -18,6 +18,12 @@
 //! - Plugin transforms see the original (unconcealed) tokens
 //! - Concealment happens transparently at the editor level
 //! - Wrapping operates on the concealed (shorter) lines
+//!
+//! ## Folds
+//!
+//! `EditorState::folds` uses the same manager for folded regions. Folds apply
+//! in every view mode and are skipped while building the base token stream,
+//! so a fold hiding many lines still leaves the viewport filled.
 
 use crate::model::marker::{MarkerId, MarkerList};
 use fresh_core::overlay::OverlayNamespace;
@@ -138,21 +144,7 @@ impl ConcealManager {
         end: usize,
         marker_list: &MarkerList,
     ) -> Vec<(Range<usize>, Option<&str>)> {
-        let mut results: Vec<(Range<usize>, Option<&str>)> = self
-            .ranges
-            .iter()
-            .filter_map(|r| {
-                let range = r.range(marker_list);
-                if range.start < end && start < range.end {
-                    Some((range, r.replacement.as_deref()))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Sort by start position for sequential processing
-        results.sort_by_key(|(range, _)| range.start);
+        let results = self.query_range(start, end, marker_list);
 
         // Debug: log conceal ranges being applied during render
         if !results.is_empty() {
@@ -170,6 +162,29 @@ impl ConcealManager {
         results
     }
 
+    /// Ranges overlapping `start..end`, sorted by start position
+    pub fn query_range(
+        &self,
+        start: usize,
+        end: usize,
+        marker_list: &MarkerList,
+    ) -> Vec<(Range<usize>, Option<&str>)> {
+        let mut results: Vec<(Range<usize>, Option<&str>)> = self
+            .ranges
+            .iter()
+            .filter_map(|r| {
+                let range = r.range(marker_list);
+                if range.start < end && start < range.end {
+                    Some((range, r.replacement.as_deref()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        results.sort_by_key(|(range, _)| range.start);
+        results
+    }
+
     /// Check if a byte position is inside any conceal range.
     /// Returns the conceal info if concealed.
     pub fn is_concealed(
@@ -159,6 +159,15 @@ impl<'a> InputHandler for FileBrowserInputHandler<'a> {
                         ctx.defer(DeferredAction::FileBrowserUpdateFilter);
                         InputResult::Consumed
                     }
+                    'p' => {
+                        // Search previously opened paths
+                        ctx.defer(DeferredAction::PromptHistorySearchPrev);
+                        InputResult::Consumed
+                    }
+                    'n' => {
+                        ctx.defer(DeferredAction::PromptHistorySearchNext);
+                        InputResult::Consumed
+                    }
                     _ => InputResult::Consumed,
                 }
             }
@@ -76,6 +76,11 @@ impl FileExplorerDecorationCache {
         self.direct.get(path)
     }
 
+    /// Paths that have a decoration of their own.
+    pub fn decorated_paths(&self) -> impl Iterator<Item = &Path> {
+        self.direct.keys().map(PathBuf::as_path)
+    }
+
     /// Lookup a bubbled decoration for a path (direct or descendant).
     pub fn bubbled_for_path(&self, path: &Path) -> Option<&FileExplorerDecoration> {
         self.bubbled.get(path)
@@ -1,35 +1,33 @@
 //! Ignore pattern matching for file tree filtering
 //!
 //! This module provides functionality to filter files and directories based on:
-//! - .gitignore patterns
+//! - the project's ignore rules (.gitignore, .ignore, git excludes and the
+//!   `files.exclude` config, see `services::ignore_rules`), shared with Quick
+//!   Open and project search
 //! - Custom glob patterns
 //! - Hidden file detection
-//!
-//! Uses the `ignore` crate which provides robust .gitignore parsing
-//! compatible with git's ignore rules.
 
-use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use std::path::{Path, PathBuf};
+use crate::services::ignore_rules::IgnoreRules;
+use std::path::Path;
 
 /// Status of a file/directory with respect to ignore patterns
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IgnoreStatus {
     /// File is visible and not ignored
     Visible,
-    /// File is ignored by .gitignore
+    /// File is ignored by .gitignore, .ignore or the git excludes
     GitIgnored,
     /// File is hidden (starts with .)
     Hidden,
-    /// File is ignored by custom pattern
+    /// File is ignored by custom pattern or `files.exclude`
     CustomIgnored,
 }
 
 /// Manages ignore patterns for file filtering
 #[derive(Debug)]
 pub struct IgnorePatterns {
-    /// Gitignore matchers per directory
-    /// Key: directory path, Value: gitignore rules for that directory
-    gitignores: Vec<(PathBuf, Gitignore)>,
+    /// Project ignore rules, with the ignore files of each expanded directory
+    rules: Option<IgnoreRules>,
 
     /// Custom glob patterns to ignore
     custom_patterns: Vec<String>,
@@ -48,7 +46,7 @@ impl IgnorePatterns {
     /// Create a new ignore pattern matcher
     pub fn new() -> Self {
         Self {
-            gitignores: Vec::new(),
+            rules: None,
             custom_patterns: Vec::new(),
             show_hidden: false,
             show_gitignored: false,
@@ -56,32 +54,22 @@ impl IgnorePatterns {
         }
     }
 
-    /// Load .gitignore file from a directory
+    /// Use the project ignore rules `rules`
+    pub fn set_rules(&mut self, rules: IgnoreRules) {
+        self.rules = Some(rules);
+    }
+
+    /// Load the .gitignore and .ignore files of a directory
     ///
-    /// This should be called when expanding a directory to load its .gitignore
+    /// This should be called when expanding a directory to load its ignore
+    /// files. Without project rules set, the first directory loaded is taken
+    /// as the project root.
     pub fn load_gitignore(&mut self, dir: &Path) -> std::io::Result<()> {
-        let gitignore_path = dir.join(".gitignore");
-
-        if !gitignore_path.exists() {
-            return Ok(()); // No .gitignore, nothing to load
-        }
-
-        let mut builder = GitignoreBuilder::new(dir);
-        builder.add(&gitignore_path);
-
-        match builder.build() {
-            Ok(gitignore) => {
-                // Remove any existing gitignore for this directory
-                self.gitignores.retain(|(path, _)| path != dir);
-                // Add new gitignore
-                self.gitignores.push((dir.to_path_buf(), gitignore));
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load .gitignore from {:?}: {}", gitignore_path, e);
-                Ok(()) // Don't fail if .gitignore is malformed
-            }
+        match &mut self.rules {
+            Some(rules) => rules.load_dir(dir),
+            None => self.rules = Some(IgnoreRules::new(dir, &[])),
         }
+        Ok(())
     }
 
     /// Add a custom glob pattern to ignore
@@ -126,7 +114,7 @@ impl IgnorePatterns {
         }
 
         // Check custom patterns
-        if self.matches_custom_pattern(path) {
+        if self.matches_custom_pattern(path) || self.is_excluded(path, is_dir) {
             return IgnoreStatus::CustomIgnored;
         }
 
@@ -138,22 +126,20 @@ impl IgnorePatterns {
         IgnoreStatus::Visible
     }
 
-    /// Check if path matches any .gitignore rules
+    /// Check if path matches any .gitignore or .ignore rules
     fn matches_gitignore(&self, path: &Path, is_dir: bool) -> bool {
-        // Find the most specific .gitignore (deepest directory)
-        // that could apply to this path
-        for (gitignore_dir, gitignore) in &self.gitignores {
-            if path.starts_with(gitignore_dir) {
-                let relative_path = path.strip_prefix(gitignore_dir).unwrap_or(path);
-                let matched = gitignore.matched(relative_path, is_dir);
-
-                if matched.is_ignore() {
-                    return true;
-                }
-            }
-        }
+        self.rules
+            .as_ref()
+            .is_some_and(|rules| rules.is_ignored(path, is_dir))
+    }
 
-        false
+    /// Check if path matches a `files.exclude` glob of the project
+    ///
+    /// Excluded directories are not watched for changes either.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        self.rules
+            .as_ref()
+            .is_some_and(|rules| rules.is_excluded(path, is_dir))
     }
 
     /// Check if path matches any custom patterns
@@ -221,7 +207,7 @@ impl IgnorePatterns {
 
     /// Clear all gitignore rules
     pub fn clear_gitignores(&mut self) {
-        self.gitignores.clear();
+        self.rules = None;
     }
 
     /// Clear all custom patterns
@@ -229,9 +215,9 @@ impl IgnorePatterns {
         self.custom_patterns.clear();
     }
 
-    /// Get number of loaded .gitignore files
+    /// Get number of directories with ignore files loaded
     pub fn gitignore_count(&self) -> usize {
-        self.gitignores.len()
+        self.rules.as_ref().map_or(0, IgnoreRules::loaded_dir_count)
     }
 }
 
@@ -315,6 +301,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_project_excludes_are_custom_ignored() -> std::io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::write(root.join(".gitignore"), "*.log\n")?;
+
+        let mut patterns = IgnorePatterns::new();
+        patterns.set_rules(IgnoreRules::new(root, &["dist/".to_string()]));
+
+        assert_eq!(
+            patterns.get_status(&root.join("dist"), true),
+            IgnoreStatus::CustomIgnored
+        );
+        assert_eq!(
+            patterns.get_status(&root.join("app.log"), false),
+            IgnoreStatus::GitIgnored
+        );
+        // Showing gitignored files doesn't bring excluded ones back
+        patterns.set_show_gitignored(true);
+        assert!(!patterns.is_ignored(&root.join("app.log"), false));
+        assert!(patterns.is_ignored(&root.join("dist"), true));
+
+        Ok(())
+    }
+
     #[test]
     fn test_show_hidden_toggle() {
         let mut patterns = IgnorePatterns::new();
@@ -1,11 +1,11 @@
-use super::ignore::IgnorePatterns;
+use super::ignore::{IgnorePatterns, IgnoreStatus};
 use super::node::NodeId;
 use super::search::FileExplorerSearch;
 use super::tree::FileTree;
 use crate::input::fuzzy::FuzzyMatch;
 use crate::model::filesystem::DirEntry;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// View state for file tree navigation and filtering
 #[derive(Debug)]
@@ -24,6 +24,14 @@ pub struct FileTreeView {
     pub(crate) viewport_height: usize,
     /// Search state for quick navigation
     search: FileExplorerSearch,
+    /// Paths marked for multi-item operations (move, copy, delete)
+    marked: BTreeSet<PathBuf>,
+    /// Only show changed files (and the directories containing them)
+    show_only_changed: bool,
+    /// Changed files and their ancestor directories
+    changed_paths: HashSet<PathBuf>,
+    /// Filter box narrowing the tree to matching entries (None when closed)
+    filter: Option<FileExplorerSearch>,
 }
 
 /// Sort mode for file tree entries
@@ -49,6 +57,10 @@ impl FileTreeView {
             ignore_patterns: IgnorePatterns::new(),
             viewport_height: 10, // Default, will be updated during rendering
             search: FileExplorerSearch::new(),
+            marked: BTreeSet::new(),
+            show_only_changed: false,
+            changed_paths: HashSet::new(),
+            filter: None,
         }
     }
 
@@ -67,15 +79,136 @@ impl FileTreeView {
         &mut self.tree
     }
 
+    /// Get the nodes shown in the explorer, in display order
+    ///
+    /// These are the tree's expanded nodes minus those filtered out by ignore
+    /// rules or the changed-files filter. A filtered directory hides its
+    /// whole subtree. The root is always shown.
+    pub fn visible_nodes(&self) -> Vec<NodeId> {
+        let mut visible = Vec::new();
+        self.collect_visible(self.tree.root_id(), &mut visible);
+        if let Some(filter) = self.filter.as_ref().filter(|f| f.is_active()) {
+            // Keep the root, then the matches, best first
+            let mut matches: Vec<(i32, NodeId)> = visible
+                .split_off(1)
+                .into_iter()
+                .filter_map(|id| {
+                    let result = filter.match_name(&self.relative_path(id))?;
+                    Some((result.score, id))
+                })
+                .collect();
+            matches.sort_by(|a, b| b.0.cmp(&a.0));
+            visible.extend(matches.into_iter().map(|(_, id)| id));
+        }
+        visible
+    }
+
+    fn collect_visible(&self, id: NodeId, visible: &mut Vec<NodeId>) {
+        let Some(node) = self.tree.get_node(id) else {
+            return;
+        };
+        if id != self.tree.root_id() && self.is_filtered(&node.entry.path, node.is_dir()) {
+            return;
+        }
+
+        visible.push(id);
+        if node.is_expanded() {
+            for &child_id in &node.children {
+                self.collect_visible(child_id, visible);
+            }
+        }
+    }
+
+    /// Whether a path is hidden by the ignore rules or the changed-files filter
+    ///
+    /// Hidden (dot) files are not filtered here; they are only dimmed.
+    fn is_filtered(&self, path: &Path, is_dir: bool) -> bool {
+        if self.show_only_changed && !self.changed_paths.contains(path) {
+            return true;
+        }
+        match self.ignore_patterns.get_status(path, is_dir) {
+            IgnoreStatus::GitIgnored | IgnoreStatus::CustomIgnored => {
+                self.ignore_patterns.is_ignored(path, is_dir)
+            }
+            IgnoreStatus::Visible | IgnoreStatus::Hidden => false,
+        }
+    }
+
+    /// Whether a path is ignored by .gitignore or custom patterns
+    pub fn is_path_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        matches!(
+            self.ignore_patterns.get_status(path, is_dir),
+            IgnoreStatus::GitIgnored | IgnoreStatus::CustomIgnored
+        )
+    }
+
+    /// Whether only changed files are shown
+    pub fn show_only_changed(&self) -> bool {
+        self.show_only_changed
+    }
+
+    /// Show only changed files, or everything again
+    pub fn set_show_only_changed(&mut self, show: bool) {
+        self.show_only_changed = show;
+        self.ensure_selection_visible();
+    }
+
+    /// Set the changed files used by the changed-files filter
+    ///
+    /// Directories leading to each file are included so the files stay
+    /// reachable in the tree.
+    pub fn set_changed_paths<I>(&mut self, paths: I)
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        let root = self.tree.root_path().to_path_buf();
+        let mut changed = HashSet::new();
+        for path in paths {
+            for ancestor in path.ancestors() {
+                if !ancestor.starts_with(&root) || !changed.insert(ancestor.to_path_buf()) {
+                    break;
+                }
+            }
+        }
+        if changed != self.changed_paths {
+            self.changed_paths = changed;
+            if self.show_only_changed {
+                self.ensure_selection_visible();
+            }
+        }
+    }
+
+    /// Move the selection to the nearest shown node if filtering hid it
+    pub fn ensure_selection_visible(&mut self) {
+        let visible = self.visible_nodes();
+        if self
+            .selected_node
+            .is_some_and(|selected| visible.contains(&selected))
+        {
+            return;
+        }
+
+        // Walk up to the closest shown ancestor, falling back to the root
+        let ancestor = self.selected_node.and_then(|selected| {
+            self.tree
+                .get_ancestors(selected)
+                .into_iter()
+                .rev()
+                .find(|id| visible.contains(id))
+        });
+        self.selected_node = ancestor.or(Some(self.tree.root_id()));
+    }
+
     /// Get currently visible nodes with their indent levels
     ///
     /// Returns a list of (NodeId, indent_level) tuples for rendering.
     pub fn get_display_nodes(&self) -> Vec<(NodeId, usize)> {
-        let visible = self.tree.get_visible_nodes();
+        let flatten = self.is_filtering();
+        let visible = self.visible_nodes();
         visible
             .into_iter()
             .map(|id| {
-                let depth = self.tree.get_depth(id);
+                let depth = if flatten { 0 } else { self.tree.get_depth(id) };
                 (id, depth)
             })
             .collect()
@@ -93,7 +226,7 @@ impl FileTreeView {
 
     /// Select the next visible node
     pub fn select_next(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if visible.is_empty() {
             return;
         }
@@ -111,7 +244,7 @@ impl FileTreeView {
 
     /// Select the previous visible node
     pub fn select_prev(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if visible.is_empty() {
             return;
         }
@@ -133,7 +266,7 @@ impl FileTreeView {
             return;
         }
 
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if visible.is_empty() {
             return;
         }
@@ -154,7 +287,7 @@ impl FileTreeView {
             return;
         }
 
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if visible.is_empty() {
             return;
         }
@@ -182,7 +315,7 @@ impl FileTreeView {
         }
 
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.visible_nodes();
             if let Some(pos) = visible.iter().position(|&id| id == selected) {
                 // Only scroll if cursor goes PAST the viewport edges
                 // This implements symmetric scrolling behavior
@@ -202,7 +335,7 @@ impl FileTreeView {
 
     /// Select the first visible node
     pub fn select_first(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if !visible.is_empty() {
             self.selected_node = Some(visible[0]);
         }
@@ -210,7 +343,7 @@ impl FileTreeView {
 
     /// Select the last visible node
     pub fn select_last(&mut self) {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         if !visible.is_empty() {
             self.selected_node = Some(*visible.last().unwrap());
         }
@@ -250,7 +383,7 @@ impl FileTreeView {
         }
 
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.visible_nodes();
             if let Some(pos) = visible.iter().position(|&id| id == selected) {
                 // If selection is above viewport, scroll up
                 if pos < self.scroll_offset {
@@ -289,10 +422,80 @@ impl FileTreeView {
         }
     }
 
+    /// Toggle the mark on the selected node (the root can't be marked)
+    pub fn toggle_mark_selected(&mut self) {
+        let Some(selected) = self.selected_node else {
+            return;
+        };
+        if selected == self.tree.root_id() {
+            return;
+        }
+        if let Some(node) = self.tree.get_node(selected) {
+            let path = node.entry.path.clone();
+            if !self.marked.remove(&path) {
+                self.marked.insert(path);
+            }
+        }
+    }
+
+    /// Mark the selected node (no-op for the root)
+    pub fn mark_selected(&mut self) {
+        if let Some(node) = self
+            .selected_node
+            .filter(|&id| id != self.tree.root_id())
+            .and_then(|id| self.tree.get_node(id))
+        {
+            self.marked.insert(node.entry.path.clone());
+        }
+    }
+
+    /// Whether a path is marked
+    pub fn is_marked(&self, path: &Path) -> bool {
+        self.marked.contains(path)
+    }
+
+    /// Number of marked paths
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Clear all marks, returning whether there were any
+    pub fn clear_marks(&mut self) -> bool {
+        let had_marks = !self.marked.is_empty();
+        self.marked.clear();
+        had_marks
+    }
+
+    /// Paths a file operation applies to: the marked paths, or else the
+    /// selected node (never the root)
+    ///
+    /// Marked paths inside a marked directory are dropped, since they move
+    /// along with it.
+    pub fn operation_targets(&self) -> Vec<PathBuf> {
+        if self.marked.is_empty() {
+            return self
+                .selected_node
+                .filter(|&id| id != self.tree.root_id())
+                .and_then(|id| self.tree.get_node(id))
+                .map(|node| vec![node.entry.path.clone()])
+                .unwrap_or_default();
+        }
+        self.marked
+            .iter()
+            .filter(|path| {
+                !self
+                    .marked
+                    .iter()
+                    .any(|other| other != *path && path.starts_with(other))
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Get the index of the selected node in the visible list
     pub fn get_selected_index(&self) -> Option<usize> {
         if let Some(selected) = self.selected_node {
-            let visible = self.tree.get_visible_nodes();
+            let visible = self.visible_nodes();
             visible.iter().position(|&id| id == selected)
         } else {
             None
@@ -301,13 +504,13 @@ impl FileTreeView {
 
     /// Get visible node at index (accounting for scroll offset)
     pub fn get_node_at_index(&self, index: usize) -> Option<NodeId> {
-        let visible = self.tree.get_visible_nodes();
+        let visible = self.visible_nodes();
         visible.get(index).copied()
     }
 
     /// Get the number of visible nodes
     pub fn visible_count(&self) -> usize {
-        self.tree.get_visible_nodes().len()
+        self.visible_nodes().len()
     }
 
     /// Get reference to ignore patterns
@@ -328,6 +531,7 @@ impl FileTreeView {
     /// Toggle showing gitignored files
     pub fn toggle_show_gitignored(&mut self) {
         self.ignore_patterns.toggle_show_gitignored();
+        self.ensure_selection_visible();
     }
 
     /// Check if a node should be visible (not filtered by ignore patterns)
@@ -382,7 +586,7 @@ impl FileTreeView {
     pub fn collect_symlink_mappings(&self) -> HashMap<PathBuf, PathBuf> {
         let mut mappings = HashMap::new();
 
-        for node_id in self.tree.get_visible_nodes() {
+        for node_id in self.visible_nodes() {
             if let Some(node) = self.tree.get_node(node_id) {
                 // Only process expanded symlink directories
                 if node.entry.is_symlink() && node.is_dir() && node.is_expanded() {
@@ -433,11 +637,10 @@ impl FileTreeView {
     /// Get nodes that match the current search query
     fn get_matching_nodes(&self) -> Vec<NodeId> {
         if !self.search.is_active() {
-            return self.tree.get_visible_nodes();
+            return self.visible_nodes();
         }
 
-        self.tree
-            .get_visible_nodes()
+        self.visible_nodes()
             .into_iter()
             .filter(|&id| {
                 if let Some(node) = self.tree.get_node(id) {
@@ -514,8 +717,79 @@ impl FileTreeView {
         }
     }
 
+    // ==================== Filter Methods ====================
+
+    /// Open the filter box, replacing any type-to-search query
+    pub fn open_filter(&mut self) {
+        self.search.clear();
+        self.filter = Some(FileExplorerSearch::new());
+    }
+
+    /// Close the filter box, keeping the selected entry selected.
+    /// Returns whether it was open.
+    pub fn close_filter(&mut self) -> bool {
+        let was_open = self.filter.take().is_some();
+        if was_open {
+            self.update_scroll_for_selection();
+        }
+        was_open
+    }
+
+    /// Whether the filter box is open
+    pub fn is_filter_open(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    /// Whether the filter box is open with a query, so entries are flattened
+    pub fn is_filtering(&self) -> bool {
+        self.filter.as_ref().is_some_and(|f| f.is_active())
+    }
+
+    /// The filter box text, if it is open
+    pub fn filter_query(&self) -> Option<&str> {
+        self.filter.as_ref().map(|f| f.query())
+    }
+
+    /// Add a character to the filter and select the best match
+    pub fn filter_push_char(&mut self, c: char) {
+        if let Some(filter) = &mut self.filter {
+            filter.push_char(c);
+            self.select_first_filter_match();
+        }
+    }
+
+    /// Remove the last character from the filter
+    pub fn filter_pop_char(&mut self) {
+        if let Some(filter) = &mut self.filter {
+            filter.pop_char();
+            self.select_first_filter_match();
+        }
+    }
+
+    fn select_first_filter_match(&mut self) {
+        let visible = self.visible_nodes();
+        self.selected_node = visible.get(1).or(visible.first()).copied();
+        self.scroll_offset = 0;
+        self.update_scroll_for_selection();
+    }
+
+    /// A node's path relative to the tree root, as shown in the filter results
+    pub fn relative_path(&self, node_id: NodeId) -> String {
+        let Some(node) = self.tree.get_node(node_id) else {
+            return String::new();
+        };
+        node.entry
+            .path
+            .strip_prefix(self.tree.root_path())
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| node.entry.name.clone())
+    }
+
     /// Get match result for a node's name (for highlighting)
     pub fn get_match_for_node(&self, node_id: NodeId) -> Option<FuzzyMatch> {
+        if let Some(filter) = self.filter.as_ref().filter(|f| f.is_active()) {
+            return filter.match_name(&self.relative_path(node_id));
+        }
         if !self.search.is_active() {
             return None;
         }
@@ -739,4 +1013,114 @@ mod tests {
         view.set_sort_mode(SortMode::Modified);
         assert_eq!(view.get_sort_mode(), SortMode::Modified);
     }
+
+    #[tokio::test]
+    async fn test_marks_drive_operation_targets() {
+        let (temp_dir, mut view) = create_test_view().await;
+        let root_id = view.tree().root_id();
+        view.tree_mut().expand_node(root_id).await.unwrap();
+        let dir1 = temp_dir.path().join("dir1");
+        let file3 = temp_dir.path().join("file3.txt");
+
+        // The root is never a target
+        assert!(view.operation_targets().is_empty());
+        view.toggle_mark_selected();
+        assert_eq!(view.marked_count(), 0);
+
+        // Without marks the selection is the target
+        view.navigate_to_path(&file3);
+        assert_eq!(view.operation_targets(), vec![file3.clone()]);
+
+        view.toggle_mark_selected();
+        view.navigate_to_path(&dir1);
+        view.mark_selected();
+        assert!(view.is_marked(&file3));
+        assert_eq!(view.operation_targets(), vec![dir1.clone(), file3.clone()]);
+
+        // Children of a marked directory go along with it
+        let dir1_id = view.get_selected().unwrap();
+        view.tree_mut().expand_node(dir1_id).await.unwrap();
+        view.navigate_to_path(&dir1.join("file1.txt"));
+        view.mark_selected();
+        assert_eq!(view.operation_targets(), vec![dir1, file3.clone()]);
+
+        view.navigate_to_path(&file3);
+        view.toggle_mark_selected();
+        assert!(!view.is_marked(&file3));
+        assert!(view.clear_marks());
+        assert!(!view.clear_marks());
+    }
+
+    #[tokio::test]
+    async fn test_ignore_and_changed_filters() {
+        let (temp_dir, mut view) = create_test_view().await;
+        let root = temp_dir.path().to_path_buf();
+        std_fs::write(root.join(".gitignore"), "dir2/\n").unwrap();
+        view.load_gitignore_for_dir(&root).unwrap();
+
+        let root_id = view.tree().root_id();
+        view.tree_mut().expand_node(root_id).await.unwrap();
+        let names = |view: &FileTreeView| -> Vec<String> {
+            view.visible_nodes()
+                .into_iter()
+                .skip(1)
+                .map(|id| view.tree().get_node(id).unwrap().entry.name.clone())
+                .collect()
+        };
+
+        // Ignored directories are hidden until gitignored files are shown;
+        // dotfiles are not filtered
+        assert_eq!(names(&view), vec!["dir1", ".gitignore", "file3.txt"]);
+        assert!(view.is_path_ignored(&root.join("dir2"), true));
+        view.toggle_show_gitignored();
+        assert_eq!(
+            names(&view),
+            vec!["dir1", "dir2", ".gitignore", "file3.txt"]
+        );
+
+        // Changed-only keeps changed files and the directories leading to them
+        let dir1_id = view.tree().get_node_by_path(&root.join("dir1")).unwrap().id;
+        view.tree_mut().expand_node(dir1_id).await.unwrap();
+        view.navigate_to_path(&root.join("file3.txt"));
+        view.set_changed_paths([root.join("dir1/file2.txt")]);
+        view.set_show_only_changed(true);
+        assert_eq!(names(&view), vec!["dir1", "file2.txt"]);
+
+        // The selection moved off the hidden file
+        assert_eq!(view.get_selected(), Some(root_id));
+
+        view.set_show_only_changed(false);
+        assert_eq!(names(&view).len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_filter_flattens_matches() {
+        let (temp_dir, mut view) = create_test_view().await;
+        let root = temp_dir.path().to_path_buf();
+        let root_id = view.tree().root_id();
+        view.tree_mut().expand_node(root_id).await.unwrap();
+        let dir1_id = view.tree().get_node_by_path(&root.join("dir1")).unwrap().id;
+        view.tree_mut().expand_node(dir1_id).await.unwrap();
+
+        view.open_filter();
+        assert!(view.is_filter_open());
+        assert!(!view.is_filtering());
+        for c in "file2".chars() {
+            view.filter_push_char(c);
+        }
+
+        // Matches are shown flat with their relative paths, after the root
+        let display = view.get_display_nodes();
+        assert_eq!(display.len(), 2);
+        assert_eq!(display[1].1, 0);
+        assert_eq!(view.relative_path(display[1].0), "dir1/file2.txt");
+        assert_eq!(view.get_selected(), Some(display[1].0));
+
+        // Closing keeps the match selected in the full tree
+        let selected = view.get_selected();
+        assert!(view.close_filter());
+        assert!(!view.close_filter());
+        assert_eq!(view.get_selected(), selected);
+        assert_eq!(view.get_display_nodes().len(), 6);
+    }
 }
@@ -0,0 +1,233 @@
+//! Terminal hyperlinks (OSC 8)
+//!
+//! Rendering records which screen cells show a link in [`ScreenLinks`], and
+//! [`HyperlinkBackend`] wraps those cells in OSC 8 escapes as it writes them.
+//! The escapes can't go into the cells themselves: ratatui takes the width
+//! of a cell's symbol as the number of cells it covers, and would stop
+//! redrawing the cells after a link.
+
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::{Position, Size};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// A run of cells on one screen row that links to a URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenLink {
+    pub y: u16,
+    pub x: Range<u16>,
+    pub url: String,
+    /// Symbols of the cells when the link was rendered
+    text: String,
+}
+
+impl ScreenLink {
+    /// Link the cells `x` of row `y` in `buffer`
+    pub fn new(buffer: &Buffer, y: u16, x: Range<u16>, url: String) -> Self {
+        let text = Self::symbols(buffer, y, x.clone());
+        Self { y, x, url, text }
+    }
+
+    fn symbols(buffer: &Buffer, y: u16, x: Range<u16>) -> String {
+        x.filter_map(|x| buffer.cell((x, y)).map(|cell| cell.symbol()))
+            .collect()
+    }
+
+    /// Whether `buffer` still shows the link, rather than something drawn
+    /// over it
+    pub fn is_shown(&self, buffer: &Buffer) -> bool {
+        Self::symbols(buffer, self.y, self.x.clone()) == self.text
+    }
+
+    fn contains(&self, x: u16, y: u16) -> bool {
+        self.y == y && self.x.contains(&x)
+    }
+}
+
+/// Links on the screen, shared between the editor, which renders them, and
+/// the backend, which writes them
+#[derive(Debug, Clone, Default)]
+pub struct ScreenLinks(Arc<Mutex<Vec<ScreenLink>>>);
+
+impl ScreenLinks {
+    /// Replace the links with those of a new frame
+    pub fn set(&self, links: Vec<ScreenLink>) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = links;
+        }
+    }
+
+    /// URL of the link at a screen position
+    pub fn url_at(&self, x: u16, y: u16) -> Option<String> {
+        let links = self.0.lock().ok()?;
+        links
+            .iter()
+            .find(|link| link.contains(x, y))
+            .map(|link| link.url.clone())
+    }
+}
+
+/// Whether the terminal is likely to understand OSC 8 hyperlinks
+///
+/// Most terminals either support them or ignore them; the Linux console and
+/// dumb terminals print them, as does the old Windows console.
+pub fn terminal_supports_hyperlinks() -> bool {
+    match std::env::var("TERM").ok().as_deref() {
+        Some("dumb" | "linux") => false,
+        Some(_) => true,
+        None => std::env::var_os("WT_SESSION").is_some(),
+    }
+}
+
+/// A cell as handed to [`Backend::draw`]
+type DrawnCell<'a> = (u16, u16, &'a Cell);
+
+/// Backend that writes the cells of [`ScreenLinks`] as OSC 8 hyperlinks
+pub struct HyperlinkBackend<B> {
+    inner: B,
+    links: ScreenLinks,
+    enabled: bool,
+}
+
+impl<B> HyperlinkBackend<B> {
+    /// Wrap `inner`; without `enabled`, cells are written as they are
+    pub fn new(inner: B, enabled: bool) -> Self {
+        Self {
+            inner,
+            links: ScreenLinks::default(),
+            enabled,
+        }
+    }
+
+    /// The links this backend writes, to hand to the editor
+    pub fn links(&self) -> &ScreenLinks {
+        &self.links
+    }
+}
+
+impl<B: Backend<Error = io::Error> + Write> Backend for HyperlinkBackend<B> {
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let Ok(links) = self.links.0.lock() else {
+            return self.inner.draw(content);
+        };
+        if !self.enabled || links.is_empty() {
+            return self.inner.draw(content);
+        }
+
+        let mut plain = Vec::new();
+        let mut linked: Vec<(&ScreenLink, Vec<DrawnCell>)> = Vec::new();
+        for (x, y, cell) in content {
+            match links.iter().find(|link| link.contains(x, y)) {
+                None => plain.push((x, y, cell)),
+                Some(link) => match linked.last_mut() {
+                    Some((last, cells)) if std::ptr::eq(*last, link) => cells.push((x, y, cell)),
+                    _ => linked.push((link, vec![(x, y, cell)])),
+                },
+            }
+        }
+
+        self.inner.draw(plain.into_iter())?;
+        for (link, cells) in linked {
+            write!(self.inner, "\x1B]8;;{}\x07", link.url)?;
+            self.inner.draw(cells.into_iter())?;
+            write!(self.inner, "\x1B]8;;\x07")?;
+        }
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+impl<B: Write> Write for HyperlinkBackend<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_links_wrap_their_cells() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buffer.set_string(0, 0, "go x.org now", Style::default());
+        let mut output = Vec::new();
+        let mut backend = HyperlinkBackend::new(CrosstermBackend::new(&mut output), true);
+        let link = ScreenLink::new(&buffer, 0, 3..8, "https://x.org".to_string());
+        backend.links().set(vec![link]);
+
+        let cells: Vec<_> = buffer
+            .content()
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (i as u16, 0, cell))
+            .collect();
+        backend.draw(cells.into_iter()).unwrap();
+        assert_eq!(
+            backend.links().url_at(4, 0).as_deref(),
+            Some("https://x.org")
+        );
+        assert_eq!(backend.links().url_at(8, 0), None);
+        drop(backend);
+        let output = String::from_utf8(output).unwrap();
+
+        let open = output.find("\x1B]8;;https://x.org\x07").unwrap();
+        let close = output.rfind("\x1B]8;;\x07").unwrap();
+        assert!(output[..open].contains("go "));
+        assert!(output[open..close].contains("x.org"));
+        assert!(!output[open..close].contains("now"));
+    }
+}
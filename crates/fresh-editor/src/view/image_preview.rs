@@ -0,0 +1,733 @@
+//! Image previews
+//!
+//! Reads the format and dimensions of common image files from their headers,
+//! decodes the pixels of PNG and uncompressed BMP files, and turns them into
+//! something a terminal can show: Kitty graphics protocol or sixel escape
+//! sequences, or ASCII art for terminals that support neither.
+
+use base64::Engine;
+use std::io::Read;
+
+/// Image file formats recognized from their leading bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    WebP,
+}
+
+impl ImageFormat {
+    /// Format of an image file, from its first bytes
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if bytes.starts_with(b"BM") && bytes.len() >= 26 {
+            Some(Self::Bmp)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::WebP)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+            Self::Bmp => "BMP",
+            Self::WebP => "WebP",
+        }
+    }
+}
+
+/// Terminal graphics protocols images can be drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+impl GraphicsProtocol {
+    /// Protocol the terminal described by `var` (an environment lookup)
+    /// supports, if any
+    pub fn detect(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || matches!(program.as_str(), "WezTerm" | "ghostty")
+        {
+            return Some(Self::Kitty);
+        }
+        if term.contains("sixel")
+            || term.starts_with("foot")
+            || term.starts_with("mlterm")
+            || matches!(program.as_str(), "iTerm.app" | "mintty")
+            || var("WT_SESSION").is_some()
+        {
+            return Some(Self::Sixel);
+        }
+        None
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Kitty => "Kitty",
+            Self::Sixel => "sixel",
+        }
+    }
+}
+
+/// Decoded image, 8-bit RGBA rows from the top
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pixels {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Pixels {
+    fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.rgba[i],
+            self.rgba[i + 1],
+            self.rgba[i + 2],
+            self.rgba[i + 3],
+        ]
+    }
+
+    /// Average color of the pixels in the given box, as RGBA
+    fn average(&self, x0: u32, y0: u32, x1: u32, y1: u32) -> [u8; 4] {
+        let (x1, y1) = (x1.max(x0 + 1), y1.max(y0 + 1));
+        let mut sum = [0u64; 4];
+        for y in y0..y1.min(self.height) {
+            for x in x0..x1.min(self.width) {
+                let p = self.pixel(x, y);
+                let alpha = p[3] as u64;
+                for c in 0..3 {
+                    sum[c] += p[c] as u64 * alpha;
+                }
+                sum[3] += alpha;
+            }
+        }
+        let count = ((x1.min(self.width) - x0) * (y1.min(self.height) - y0)).max(1) as u64;
+        if sum[3] == 0 {
+            return [0, 0, 0, 0];
+        }
+        [
+            (sum[0] / sum[3]) as u8,
+            (sum[1] / sum[3]) as u8,
+            (sum[2] / sum[3]) as u8,
+            (sum[3] / count) as u8,
+        ]
+    }
+
+    /// The image scaled to `width` x `height`, averaging the pixels each new
+    /// pixel covers
+    pub fn resize(&self, width: u32, height: u32) -> Pixels {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            let y0 = y * self.height / height;
+            let y1 = (y + 1) * self.height / height;
+            for x in 0..width {
+                let x0 = x * self.width / width;
+                let x1 = (x + 1) * self.width / width;
+                rgba.extend_from_slice(&self.average(x0, y0, x1, y1));
+            }
+        }
+        Pixels {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+/// An image file ready to be previewed
+#[derive(Debug, Clone)]
+pub struct ImagePreview {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub file_size: u64,
+    /// Decoded pixels, for the formats that can be decoded
+    pub pixels: Option<Pixels>,
+    /// The file itself when it is a PNG, which Kitty terminals take as-is
+    png: Option<Vec<u8>>,
+}
+
+impl ImagePreview {
+    /// Preview for the contents of an image file, or None if `bytes` isn't
+    /// an image in a recognized format
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        let format = ImageFormat::sniff(&bytes)?;
+        let (width, height) = dimensions(format, &bytes)?;
+        let pixels = match format {
+            ImageFormat::Png => decode_png(&bytes),
+            ImageFormat::Bmp => decode_bmp(&bytes),
+            _ => None,
+        };
+        Some(Self {
+            format,
+            width,
+            height,
+            file_size: bytes.len() as u64,
+            png: (format == ImageFormat::Png && pixels.is_some()).then_some(bytes),
+            pixels,
+        })
+    }
+
+    /// Preview that only describes an image, from the start of its file
+    pub fn describe(head: Vec<u8>) -> Option<Self> {
+        let format = ImageFormat::sniff(&head)?;
+        let (width, height) = dimensions(format, &head)?;
+        Some(Self {
+            format,
+            width,
+            height,
+            file_size: head.len() as u64,
+            pixels: None,
+            png: None,
+        })
+    }
+
+    /// Whether the image can be drawn, as opposed to only described
+    pub fn can_draw(&self) -> bool {
+        self.pixels.is_some()
+    }
+
+    /// Size in cells of the image fitted into `cols` x `rows` cells of
+    /// `cell` pixels, keeping its aspect ratio. Images smaller than the area
+    /// keep their size unless `upscale` is set.
+    pub fn fit(&self, cols: u16, rows: u16, cell: (u16, u16), upscale: bool) -> (u16, u16) {
+        if cols == 0 || rows == 0 || self.width == 0 || self.height == 0 {
+            return (0, 0);
+        }
+        let (cell_w, cell_h) = (cell.0.max(1) as f64, cell.1.max(1) as f64);
+        let natural_cols = self.width as f64 / cell_w;
+        let natural_rows = self.height as f64 / cell_h;
+        let mut scale = (cols as f64 / natural_cols).min(rows as f64 / natural_rows);
+        if !upscale {
+            scale = scale.min(1.0);
+        }
+        let fitted_cols = (natural_cols * scale).round().clamp(1.0, cols as f64) as u16;
+        let fitted_rows = (natural_rows * scale).round().clamp(1.0, rows as f64) as u16;
+        (fitted_cols, fitted_rows)
+    }
+
+    /// Lines of ASCII art, `cols` x `rows` characters, shading each cell by
+    /// the brightness of the pixels under it
+    pub fn ascii_art(&self, cols: u16, rows: u16) -> Vec<String> {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+        let Some(pixels) = &self.pixels else {
+            return Vec::new();
+        };
+        let small = pixels.resize(cols as u32, rows as u32);
+        (0..small.height)
+            .map(|y| {
+                (0..small.width)
+                    .map(|x| {
+                        let [r, g, b, a] = small.pixel(x, y);
+                        let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+                        let level = luma * a as u32 / 255;
+                        RAMP[(level as usize * (RAMP.len() - 1) + 127) / 255] as char
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// Escape sequences drawing the image over `cols` x `rows` cells with
+    /// their top-left corner at the (0-based) `col`, `row`. The cursor is
+    /// left where it was.
+    pub fn encode(
+        &self,
+        protocol: GraphicsProtocol,
+        col: u16,
+        row: u16,
+        cols: u16,
+        rows: u16,
+        cell: (u16, u16),
+    ) -> Vec<u8> {
+        let Some(pixels) = &self.pixels else {
+            return Vec::new();
+        };
+        let mut out = format!("\x1b7\x1b[{};{}H", row + 1, col + 1).into_bytes();
+        let (width, height) = (cols as u32 * cell.0 as u32, rows as u32 * cell.1 as u32);
+        match protocol {
+            GraphicsProtocol::Kitty => match &self.png {
+                Some(png) => kitty(&mut out, "f=100", png, cols, rows),
+                None => {
+                    let scaled = pixels.resize(width.min(pixels.width), height.min(pixels.height));
+                    let format = format!("f=32,s={},v={}", scaled.width, scaled.height);
+                    kitty(&mut out, &format, &scaled.rgba, cols, rows);
+                }
+            },
+            GraphicsProtocol::Sixel => sixel(&mut out, &pixels.resize(width, height)),
+        }
+        out.extend_from_slice(b"\x1b8");
+        out
+    }
+}
+
+/// Escape sequence removing every image drawn with the Kitty protocol
+pub const KITTY_DELETE_ALL: &[u8] = b"\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Transmit and display `data` in one Kitty graphics command, split into
+/// chunks of at most 4096 base64 bytes
+fn kitty(out: &mut Vec<u8>, format: &str, data: &[u8], cols: u16, rows: u16) {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.extend_from_slice(
+                format!("\x1b_Ga=T,{format},q=2,C=1,c={cols},r={rows},m={more};").as_bytes(),
+            );
+        } else {
+            out.extend_from_slice(format!("\x1b_Gm={more};").as_bytes());
+        }
+        out.extend_from_slice(chunk);
+        out.extend_from_slice(b"\x1b\\");
+    }
+}
+
+/// Sixel image of `pixels` using a 6x6x6 color cube, leaving transparent
+/// pixels untouched
+fn sixel(out: &mut Vec<u8>, pixels: &Pixels) {
+    let level = |v: u8| (v as u32 * 5 + 127) / 255;
+    out.extend_from_slice(
+        format!("\x1bP0;1;0q\"1;1;{};{}", pixels.width, pixels.height).as_bytes(),
+    );
+    for i in 0..216u32 {
+        let (r, g, b) = (i / 36, i / 6 % 6, i % 6);
+        out.extend_from_slice(format!("#{};2;{};{};{}", i, r * 20, g * 20, b * 20).as_bytes());
+    }
+    let width = pixels.width as usize;
+    for band in (0..pixels.height).step_by(6) {
+        // Sixel bits per column for each color used in this band
+        let mut colors: Vec<(u32, Vec<u8>)> = Vec::new();
+        for dy in 0..6.min(pixels.height - band) {
+            for x in 0..pixels.width {
+                let [r, g, b, a] = pixels.pixel(x, band + dy);
+                if a < 128 {
+                    continue;
+                }
+                let color = level(r) * 36 + level(g) * 6 + level(b);
+                let index = match colors.iter().position(|(c, _)| *c == color) {
+                    Some(index) => index,
+                    None => {
+                        colors.push((color, vec![0; width]));
+                        colors.len() - 1
+                    }
+                };
+                colors[index].1[x as usize] |= 1 << dy;
+            }
+        }
+        for (color, bits) in &colors {
+            out.extend_from_slice(format!("#{}", color).as_bytes());
+            let mut x = 0;
+            while x < bits.len() {
+                let run = bits[x..].iter().take_while(|&&b| b == bits[x]).count();
+                let ch = 63 + bits[x];
+                if run > 3 {
+                    out.extend_from_slice(format!("!{}", run).as_bytes());
+                    out.push(ch);
+                } else {
+                    out.extend(std::iter::repeat_n(ch, run));
+                }
+                x += run;
+            }
+            out.push(b'$');
+        }
+        out.push(b'-');
+    }
+    out.extend_from_slice(b"\x1b\\");
+}
+
+fn be16(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn le16(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn be32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le24(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16)
+}
+
+/// Width and height of an image, read from its header
+fn dimensions(format: ImageFormat, bytes: &[u8]) -> Option<(u32, u32)> {
+    match format {
+        ImageFormat::Png => {
+            if bytes.get(12..16)? != b"IHDR" {
+                return None;
+            }
+            Some((be32(bytes, 16)?, be32(bytes, 20)?))
+        }
+        ImageFormat::Gif => Some((le16(bytes, 6)?, le16(bytes, 8)?)),
+        ImageFormat::Bmp => Some((le32(bytes, 18)?, (le32(bytes, 22)? as i32).unsigned_abs())),
+        ImageFormat::Jpeg => {
+            // Walk the segments up to a start-of-frame marker
+            let mut pos = 2;
+            while pos + 9 < bytes.len() {
+                if bytes[pos] != 0xFF {
+                    return None;
+                }
+                let marker = bytes[pos + 1];
+                if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                    return Some((be16(bytes, pos + 7)?, be16(bytes, pos + 5)?));
+                }
+                pos += 2 + be16(bytes, pos + 2)? as usize;
+            }
+            None
+        }
+        ImageFormat::WebP => match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(bytes, 26)? & 0x3FFF, le16(bytes, 28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = le32(bytes, 21)?;
+                Some(((bits & 0x3FFF) + 1, (bits >> 14 & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(bytes, 24)? + 1, le24(bytes, 27)? + 1)),
+            _ => None,
+        },
+    }
+}
+
+/// Decode a non-interlaced PNG of any color type and bit depth
+fn decode_png(bytes: &[u8]) -> Option<Pixels> {
+    let mut pos = 8;
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut data = Vec::new();
+    while pos + 8 <= bytes.len() {
+        let len = be32(bytes, pos)? as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let body = bytes.get(pos + 8..pos + 8 + len)?;
+        match kind {
+            b"IHDR" => header = Some(body),
+            b"PLTE" => palette = body,
+            b"tRNS" => transparency = body,
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        pos += 12 + len;
+    }
+    let header = header?;
+    let (width, height) = (be32(header, 0)?, be32(header, 4)?);
+    let (depth, color_type, interlace) = (*header.get(8)?, *header.get(9)?, *header.get(12)?);
+    if interlace != 0 || width == 0 || height == 0 || width > 16384 || height > 16384 {
+        return None;
+    }
+    let channels = match color_type {
+        0 | 3 => 1,
+        2 => 3,
+        4 => 2,
+        6 => 4,
+        _ => return None,
+    };
+    let bits_per_pixel = channels * depth as usize;
+    let stride = (width as usize * bits_per_pixel).div_ceil(8);
+    let bpp = bits_per_pixel.div_ceil(8);
+
+    let mut raw = Vec::new();
+    flate2::read::ZlibDecoder::new(data.as_slice())
+        .read_to_end(&mut raw)
+        .ok()?;
+    if raw.len() < (stride + 1) * height as usize {
+        return None;
+    }
+
+    // Undo the per-row filters
+    let mut rows = vec![0u8; stride * height as usize];
+    for y in 0..height as usize {
+        let filter = raw[y * (stride + 1)];
+        let line = &raw[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        let (done, rest) = rows.split_at_mut(y * stride);
+        let prev = if y > 0 {
+            &done[(y - 1) * stride..]
+        } else {
+            &[][..]
+        };
+        let cur = &mut rest[..stride];
+        for i in 0..stride {
+            let a = if i >= bpp { cur[i - bpp] } else { 0 };
+            let b = prev.get(i).copied().unwrap_or(0);
+            let c = if i >= bpp {
+                prev.get(i - bpp).copied().unwrap_or(0)
+            } else {
+                0
+            };
+            cur[i] = line[i].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return None,
+            });
+        }
+    }
+
+    // Sample `index` of a row, scaled to 8 bits
+    let sample = |row: &[u8], index: usize| -> u8 {
+        match depth {
+            8 => row[index],
+            16 => row[index * 2],
+            _ => {
+                let bit = index * depth as usize;
+                let value = row[bit / 8] >> (8 - depth as usize - bit % 8) & ((1 << depth) - 1);
+                if color_type == 3 {
+                    value
+                } else {
+                    (value as u32 * 255 / ((1 << depth) - 1)) as u8
+                }
+            }
+        }
+    };
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height as usize {
+        let row = &rows[y * stride..(y + 1) * stride];
+        for x in 0..width as usize {
+            let pixel = match color_type {
+                0 => {
+                    let v = sample(row, x);
+                    [v, v, v, 255]
+                }
+                2 => [
+                    sample(row, x * 3),
+                    sample(row, x * 3 + 1),
+                    sample(row, x * 3 + 2),
+                    255,
+                ],
+                3 => {
+                    let i = sample(row, x) as usize;
+                    let rgb = palette.get(i * 3..i * 3 + 3).unwrap_or(&[0, 0, 0]);
+                    let alpha = transparency.get(i).copied().unwrap_or(255);
+                    [rgb[0], rgb[1], rgb[2], alpha]
+                }
+                4 => {
+                    let v = sample(row, x * 2);
+                    [v, v, v, sample(row, x * 2 + 1)]
+                }
+                _ => [
+                    sample(row, x * 4),
+                    sample(row, x * 4 + 1),
+                    sample(row, x * 4 + 2),
+                    sample(row, x * 4 + 3),
+                ],
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    Some(Pixels {
+        width,
+        height,
+        rgba,
+    })
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Decode an uncompressed 24- or 32-bit BMP
+fn decode_bmp(bytes: &[u8]) -> Option<Pixels> {
+    let offset = le32(bytes, 10)? as usize;
+    let width = le32(bytes, 18)?;
+    let raw_height = le32(bytes, 22)? as i32;
+    let bits = le16(bytes, 28)?;
+    let compression = le32(bytes, 30)?;
+    let height = raw_height.unsigned_abs();
+    if !matches!(bits, 24 | 32) || !matches!(compression, 0 | 3) || width > 16384 || height > 16384
+    {
+        return None;
+    }
+    let bytes_per_pixel = bits as usize / 8;
+    let stride = (width as usize * bytes_per_pixel).div_ceil(4) * 4;
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height as usize {
+        // Rows are stored bottom-up unless the height is negative
+        let row = if raw_height > 0 {
+            height as usize - 1 - y
+        } else {
+            y
+        };
+        let start = offset + row * stride;
+        let line = bytes.get(start..start + width as usize * bytes_per_pixel)?;
+        for px in line.chunks(bytes_per_pixel) {
+            let alpha = if bits == 32 && compression == 3 {
+                px[3]
+            } else {
+                255
+            };
+            rgba.extend_from_slice(&[px[2], px[1], px[0], alpha]);
+        }
+    }
+    Some(Pixels {
+        width,
+        height,
+        rgba,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// A PNG built chunk by chunk, with the given filter byte on each row
+    fn png(width: u32, height: u32, color_type: u8, rows: &[Vec<u8>]) -> Vec<u8> {
+        let chunk = |kind: &[u8], body: &[u8]| {
+            let mut out = (body.len() as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            out.extend_from_slice(&[0, 0, 0, 0]); // CRC isn't checked
+            out
+        };
+        let mut header = width.to_be_bytes().to_vec();
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        for row in rows {
+            encoder.write_all(row).unwrap();
+        }
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        out.extend(chunk(b"IHDR", &header));
+        out.extend(chunk(b"IDAT", &encoder.finish().unwrap()));
+        out.extend(chunk(b"IEND", &[]));
+        out
+    }
+
+    #[test]
+    fn test_decode_png_with_filters() {
+        // Row 0 unfiltered, row 1 "up" filtered: both end up white then black
+        let bytes = png(
+            2,
+            2,
+            2,
+            &[
+                vec![0, 255, 255, 255, 0, 0, 0],
+                vec![2, 1, 1, 1, 255, 255, 255],
+            ],
+        );
+        let preview = ImagePreview::from_bytes(bytes).unwrap();
+        assert_eq!(preview.format, ImageFormat::Png);
+        assert_eq!((preview.width, preview.height), (2, 2));
+        let pixels = preview.pixels.unwrap();
+        assert_eq!(pixels.pixel(0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixels.pixel(1, 0), [0, 0, 0, 255]);
+        assert_eq!(pixels.pixel(0, 1), [0, 0, 0, 255]);
+        assert_eq!(pixels.pixel(1, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_dimensions_of_undecoded_formats() {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&[0x40, 0x01, 0xF0, 0x00]);
+        let preview = ImagePreview::from_bytes(gif).unwrap();
+        assert_eq!(
+            (preview.format, preview.width, preview.height),
+            (ImageFormat::Gif, 320, 240)
+        );
+        assert!(!preview.can_draw());
+
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01,
+            0xE0, 0x02, 0x80, 0x03,
+        ];
+        let preview = ImagePreview::from_bytes(jpeg.to_vec()).unwrap();
+        assert_eq!((preview.width, preview.height), (640, 480));
+
+        assert!(ImagePreview::from_bytes(b"plain text".to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_fit_keeps_aspect_ratio() {
+        let bytes = png(200, 100, 2, &vec![vec![0; 601]; 100]);
+        let preview = ImagePreview::from_bytes(bytes).unwrap();
+        // 200x100 pixels is 20x5 cells of 10x20 pixels
+        assert_eq!(preview.fit(80, 24, (10, 20), false), (20, 5));
+        assert_eq!(preview.fit(10, 24, (10, 20), false), (10, 3));
+        assert_eq!(preview.fit(80, 10, (10, 20), true), (40, 10));
+    }
+
+    #[test]
+    fn test_ascii_art_and_encodings() {
+        let bytes = png(2, 1, 6, &[vec![0, 255, 255, 255, 255, 0, 0, 0, 255]]);
+        let preview = ImagePreview::from_bytes(bytes).unwrap();
+        assert_eq!(preview.ascii_art(2, 1), vec!["@".to_string()]);
+
+        let kitty = preview.encode(GraphicsProtocol::Kitty, 4, 2, 2, 1, (10, 20));
+        let kitty = String::from_utf8(kitty).unwrap();
+        assert!(kitty.starts_with("\x1b7\x1b[3;5H\x1b_Ga=T,f=100,q=2,C=1,c=2,r=1,m=0;"));
+        assert!(kitty.ends_with("\x1b\\\x1b8"));
+
+        let sixel = preview.encode(GraphicsProtocol::Sixel, 0, 0, 1, 1, (2, 6));
+        let sixel = String::from_utf8(sixel).unwrap();
+        assert!(sixel.contains("\x1bP0;1;0q\"1;1;2;6"));
+        // White on the left column, black on the right, six rows each
+        assert!(sixel.contains("#215~?$#0?~$-"));
+    }
+
+    #[test]
+    fn test_detect_protocol() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            GraphicsProtocol::detect(env(&[("TERM", "xterm-kitty")])),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect(env(&[("TERM", "foot")])),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(
+            GraphicsProtocol::detect(env(&[("TERM", "xterm-256color")])),
+            None
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! URL links using the overlay system
+//!
+//! URLs in the visible text are underlined and carry their address in the
+//! overlay's `url`, like the links the markdown compose plugin adds. Rendering
+//! turns overlays with a URL into terminal hyperlinks, and "Open Link" and
+//! Ctrl+Click open them.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, OverlayNamespace};
+use ratatui::style::{Modifier, Style};
+use std::ops::Range;
+
+/// Schemes that start a URL in plain text
+const SCHEMES: &[&str] = &["https://", "http://", "ftp://", "file://", "mailto:"];
+
+/// Schemes "Open Link" passes to the system opener. Others, such as `file:`,
+/// can point the opener at a local program.
+const OPENABLE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Namespace for URL overlays
+pub fn link_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("links".to_string())
+}
+
+/// Whether `url` is an absolute URL a terminal or browser can open
+pub fn is_absolute_url(url: &str) -> bool {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+    !scheme.is_empty()
+        && !rest.is_empty()
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !url.chars().any(char::is_control)
+}
+
+/// Whether `url` is safe to hand to the system opener
+pub fn is_openable_url(url: &str) -> bool {
+    is_absolute_url(url)
+        && url.split_once(':').is_some_and(|(scheme, _)| {
+            OPENABLE_SCHEMES
+                .iter()
+                .any(|openable| scheme.eq_ignore_ascii_case(openable))
+        })
+}
+
+/// Whether a byte can be part of a URL in plain text
+fn is_url_byte(byte: u8) -> bool {
+    !byte.is_ascii_whitespace()
+        && !byte.is_ascii_control()
+        && !matches!(byte, b'<' | b'>' | b'"' | b'\'' | b'`')
+}
+
+/// Length of `url` without trailing punctuation and unbalanced closing brackets
+fn trim_url(url: &[u8]) -> usize {
+    let count = |byte: u8, end: usize| url[..end].iter().filter(|&&b| b == byte).count();
+    let mut end = url.len();
+    while end > 0 {
+        let unbalanced = match url[end - 1] {
+            b'.' | b',' | b':' | b';' | b'!' | b'?' => true,
+            b')' => count(b'(', end) < count(b')', end),
+            b']' => count(b'[', end) < count(b']', end),
+            _ => false,
+        };
+        if !unbalanced {
+            break;
+        }
+        end -= 1;
+    }
+    end
+}
+
+/// Byte ranges of the URLs in `text`, which starts at byte `offset` of the
+/// buffer
+pub fn url_ranges(text: &[u8], offset: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        let at_word_start = i == 0 || !text[i - 1].is_ascii_alphanumeric();
+        let scheme = SCHEMES
+            .iter()
+            .find(|scheme| at_word_start && text[i..].starts_with(scheme.as_bytes()));
+        let Some(scheme) = scheme else {
+            i += 1;
+            continue;
+        };
+        let mut end = i + scheme.len();
+        while end < text.len() && is_url_byte(text[end]) {
+            end += 1;
+        }
+        let end = i + trim_url(&text[i..end]);
+        if end > i + scheme.len() {
+            ranges.push(offset + i..offset + end);
+        }
+        i = end.max(i + scheme.len());
+    }
+    ranges
+}
+
+/// Manager for URL overlays
+pub struct LinkOverlay {
+    /// Whether URLs are linked
+    pub enabled: bool,
+    /// Buffer version and viewport the current overlays were made for
+    last_state: Option<(u64, Range<usize>)>,
+}
+
+impl LinkOverlay {
+    /// Create a new URL overlay manager
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            last_state: None,
+        }
+    }
+
+    /// Update the links for the visible part of the buffer
+    ///
+    /// Returns true if overlays were updated
+    pub fn update(
+        &mut self,
+        buffer: &Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        viewport: Range<usize>,
+    ) -> bool {
+        if !self.enabled {
+            if self.last_state.is_some() {
+                self.clear(overlays, marker_list);
+                return true;
+            }
+            return false;
+        }
+
+        let state = (buffer.version(), viewport.clone());
+        if self.last_state.as_ref() == Some(&state) {
+            return false;
+        }
+        self.last_state = Some(state);
+
+        let ns = link_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+
+        let end = viewport.end.min(buffer.len());
+        let start = viewport.start.min(end);
+        let text = buffer.slice_bytes(start..end);
+        for range in url_ranges(&text, start) {
+            let url = String::from_utf8_lossy(&text[range.start - start..range.end - start]);
+            let mut overlay = Overlay::with_namespace(
+                marker_list,
+                range,
+                OverlayFace::Style {
+                    style: Style::default().add_modifier(Modifier::UNDERLINED),
+                },
+                ns.clone(),
+            )
+            .with_priority_value(1); // Below every other highlight
+            overlay.url = Some(url.into_owned());
+            overlays.add(overlay);
+        }
+        true
+    }
+
+    /// Remove all links
+    pub fn clear(&mut self, overlays: &mut OverlayManager, marker_list: &mut MarkerList) {
+        let ns = link_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+        self.last_state = None;
+    }
+}
+
+impl Default for LinkOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_ranges() {
+        let text = b"see https://example.com/a_(b). or <http://x.org/?q=1>, mailto:me@x.org";
+        let urls: Vec<&[u8]> = url_ranges(text, 0)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                &b"https://example.com/a_(b)"[..],
+                b"http://x.org/?q=1",
+                b"mailto:me@x.org",
+            ]
+        );
+        // Offsets are buffer positions; a scheme alone or inside a word isn't a URL
+        assert_eq!(
+            url_ranges(b"(https://a.b) https:// xhttp://c", 10),
+            vec![11..22]
+        );
+    }
+
+    #[test]
+    fn test_is_absolute_url() {
+        assert!(is_absolute_url("https://example.com"));
+        assert!(is_absolute_url("mailto:me@example.com"));
+        assert!(!is_absolute_url("#installation"));
+        assert!(!is_absolute_url("docs/guide.md"));
+        assert!(!is_absolute_url("https://example.com/\x1b]"));
+    }
+
+    #[test]
+    fn test_is_openable_url() {
+        assert!(is_openable_url("HTTPS://example.com"));
+        assert!(is_openable_url("mailto:me@example.com"));
+        assert!(!is_openable_url("file:///usr/bin/xterm"));
+        assert!(!is_openable_url("ftp://example.com"));
+        assert!(!is_openable_url("https://example.com/\x1b]"));
+    }
+}
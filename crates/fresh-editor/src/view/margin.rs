@@ -1,4 +1,5 @@
 use crate::model::marker::{MarkerId, MarkerList};
+use crate::view::theme::Theme;
 use ratatui::style::{Color, Style};
 use std::collections::BTreeMap;
 
@@ -22,6 +23,8 @@ pub struct LineIndicator {
     pub symbol: String,
     /// The color of the indicator
     pub color: Color,
+    /// Theme key to take the color from instead, resolved at render time
+    pub theme_color: Option<String>,
     /// Priority for display when multiple indicators exist (higher wins)
     pub priority: i32,
     /// Marker ID anchoring this indicator to a byte position
@@ -35,11 +38,27 @@ impl LineIndicator {
         Self {
             symbol: symbol.into(),
             color,
+            theme_color: None,
             priority,
             marker_id: MarkerId(0), // Placeholder, set by MarginManager
         }
     }
 
+    /// Take the color from a theme key (e.g. "diagnostic.error_fg"), falling
+    /// back to `color` if the theme doesn't define it
+    pub fn with_theme_color(mut self, key: impl Into<String>) -> Self {
+        self.theme_color = Some(key.into());
+        self
+    }
+
+    /// The color to draw the indicator in with `theme`
+    pub fn display_color(&self, theme: &Theme) -> Color {
+        self.theme_color
+            .as_deref()
+            .and_then(|key| theme.resolve_theme_key(key))
+            .unwrap_or(self.color)
+    }
+
     /// Create a line indicator with a specific marker ID
     pub fn with_marker(
         symbol: impl Into<String>,
@@ -50,6 +69,7 @@ impl LineIndicator {
         Self {
             symbol: symbol.into(),
             color,
+            theme_color: None,
             priority,
             marker_id,
         }
@@ -425,6 +445,15 @@ impl MarginManager {
         }
     }
 
+    /// Byte positions of the line indicators in a namespace
+    pub fn line_indicator_positions(&self, namespace: &str) -> Vec<usize> {
+        self.line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| self.indicator_markers.get_position(MarkerId(marker_id)))
+            .collect()
+    }
+
     /// Get the line indicator for a specific line number
     ///
     /// This looks up all indicators whose markers resolve to the given line.
@@ -1003,4 +1032,26 @@ mod tests {
         assert!(manager.get_line_indicator(7, byte_to_line).is_some());
         assert!(manager.get_line_indicator(9, byte_to_line).is_some());
     }
+
+    #[test]
+    fn test_line_indicator_positions_follow_edits() {
+        let mut manager = MarginManager::new();
+        manager.set_line_indicator(
+            line_to_byte(2),
+            "breakpoint".to_string(),
+            LineIndicator::new("●", Color::Red, 30),
+        );
+        manager.set_line_indicator(
+            line_to_byte(4),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+
+        assert_eq!(manager.line_indicator_positions("breakpoint"), vec![20]);
+
+        // Two lines inserted before the breakpoint move it down
+        manager.adjust_for_insert(line_to_byte(1), 20);
+        assert_eq!(manager.line_indicator_positions("breakpoint"), vec![40]);
+        assert!(manager.line_indicator_positions("bookmarks").is_empty());
+    }
 }
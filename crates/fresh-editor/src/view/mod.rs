@@ -53,10 +53,18 @@ pub mod file_browser_input;
 #[cfg(feature = "runtime")]
 pub mod file_tree;
 #[cfg(feature = "runtime")]
+pub mod hyperlinks;
+#[cfg(feature = "runtime")]
+pub mod image_preview;
+#[cfg(feature = "runtime")]
 pub mod keybinding_editor;
 #[cfg(feature = "runtime")]
+pub mod link_overlay;
+#[cfg(feature = "runtime")]
 pub mod markdown;
 #[cfg(feature = "runtime")]
+pub mod perf_hud;
+#[cfg(feature = "runtime")]
 pub mod popup;
 #[cfg(feature = "runtime")]
 pub mod popup_input;
@@ -74,3 +82,5 @@ pub mod reference_highlight_overlay;
 pub mod split;
 #[cfg(feature = "runtime")]
 pub mod stream;
+#[cfg(feature = "runtime")]
+pub mod trailing_whitespace_overlay;
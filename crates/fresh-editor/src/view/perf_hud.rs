@@ -0,0 +1,105 @@
+//! Performance HUD rendering
+//!
+//! Renders the performance HUD in the top right corner, over the editor.
+
+use std::time::Duration;
+
+use crate::app::perf_hud::PerfHud;
+use crate::services::perf::Counter;
+use crate::view::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use rust_i18n::t;
+
+/// Width of the HUD in characters
+const HUD_WIDTH: u16 = 38;
+
+/// Render the performance HUD overlay
+pub fn render_perf_hud(frame: &mut Frame, area: Rect, hud: &PerfHud, theme: &Theme) {
+    let last = hud.last();
+    let timings = [
+        (t!("perf_hud.frame"), last.frame, hud.max(|f| f.frame)),
+        (t!("perf_hud.render"), last.render, hud.max(|f| f.render)),
+        (
+            t!("perf_hud.event_apply"),
+            last.event_apply,
+            hud.max(|f| f.event_apply),
+        ),
+        (
+            t!("perf_hud.highlight"),
+            last.highlight,
+            hud.max(|f| f.highlight),
+        ),
+    ];
+    let counters = Counter::ALL.map(|counter| {
+        let label = match counter {
+            Counter::Keys => t!("perf_hud.keys"),
+            Counter::Events => t!("perf_hud.events"),
+            Counter::AsyncMessages => t!("perf_hud.async_messages"),
+            Counter::PluginHooks => t!("perf_hud.plugin_hooks"),
+        };
+        (label, hud.counts[counter as usize])
+    });
+
+    let label_style = Style::default().fg(theme.line_number_fg);
+    let value_style = Style::default().fg(theme.editor_fg);
+    let label_width = timings
+        .iter()
+        .map(|(label, _, _)| label.chars().count())
+        .chain(counters.iter().map(|(label, _)| label.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:label_width$} {:>9} {:>9}",
+            "",
+            t!("perf_hud.last"),
+            t!("perf_hud.max")
+        ),
+        label_style.add_modifier(Modifier::BOLD),
+    ))];
+    for (label, last, max) in &timings {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:label_width$} ", label), label_style),
+            Span::styled(
+                format!("{:>9} {:>9}", format_duration(*last), format_duration(*max)),
+                value_style,
+            ),
+        ]));
+    }
+    lines.push(Line::default());
+    for (label, count) in &counters {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:label_width$} ", label), label_style),
+            Span::styled(format!("{:>9}", count), value_style),
+        ]));
+    }
+
+    let hud_width = HUD_WIDTH.min(area.width);
+    let hud_height = (lines.len() as u16 + 2).min(area.height);
+    let hud_area = Rect {
+        x: area.x + area.width.saturating_sub(hud_width),
+        y: area.y,
+        width: hud_width,
+        height: hud_height,
+    };
+
+    frame.render_widget(Clear, hud_area);
+    let block = Block::default()
+        .title(t!("perf_hud.title").to_string())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.editor_fg))
+        .style(Style::default().bg(theme.editor_bg).fg(theme.editor_fg));
+    frame.render_widget(Paragraph::new(lines).block(block), hud_area);
+}
+
+/// Format a duration in milliseconds, with microsecond precision
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
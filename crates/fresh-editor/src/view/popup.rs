@@ -56,6 +56,8 @@ pub enum PopupKind {
     Completion,
     /// Hover/documentation popup - read-only, scroll, dismiss on keypress
     Hover,
+    /// LSP signature help popup - read-only, re-requested while typing arguments
+    SignatureHelp,
     /// Action popup with selectable actions - navigate and execute
     Action,
     /// Generic list popup
@@ -169,6 +171,12 @@ pub struct Popup {
     /// Kind of popup - determines input handling behavior
     pub kind: PopupKind,
 
+    /// Identifier used to replace or hide a specific popup (e.g. plugin floating panels)
+    pub id: Option<String>,
+
+    /// Stacking order: popups with a higher z-index are drawn above lower ones
+    pub z_index: i32,
+
     /// Title of the popup (optional)
     pub title: Option<String>,
 
@@ -223,6 +231,8 @@ impl Popup {
             background_style: Style::default().bg(theme.popup_bg),
             scroll_offset: 0,
             text_selection: None,
+            id: None,
+            z_index: 0,
         }
     }
 
@@ -250,6 +260,8 @@ impl Popup {
             background_style: Style::default().bg(theme.popup_bg),
             scroll_offset: 0,
             text_selection: None,
+            id: None,
+            z_index: 0,
         }
     }
 
@@ -269,6 +281,8 @@ impl Popup {
             background_style: Style::default().bg(theme.popup_bg),
             scroll_offset: 0,
             text_selection: None,
+            id: None,
+            z_index: 0,
         }
     }
 
@@ -290,6 +304,18 @@ impl Popup {
         self
     }
 
+    /// Set the identifier used by `PopupManager::hide_by_id`
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Set the stacking order relative to other popups
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
     /// Set the position
     pub fn with_position(mut self, position: PopupPosition) -> Self {
         self.position = position;
@@ -1102,9 +1128,30 @@ impl PopupManager {
         Self { popups: Vec::new() }
     }
 
-    /// Show a popup (adds to top of stack)
+    /// Show a popup.
+    ///
+    /// The popup goes above every popup with the same or lower z-index, so the
+    /// stack stays ordered by z-index. A popup with an id replaces any existing
+    /// popup with the same id.
     pub fn show(&mut self, popup: Popup) {
-        self.popups.push(popup);
+        if let Some(id) = &popup.id {
+            self.popups.retain(|p| p.id.as_ref() != Some(id));
+        }
+        let index = self
+            .popups
+            .iter()
+            .rposition(|p| p.z_index <= popup.z_index)
+            .map_or(0, |i| i + 1);
+        self.popups.insert(index, popup);
+    }
+
+    /// Hide the popup with the given id, wherever it is in the stack
+    pub fn hide_by_id(&mut self, id: &str) -> Option<Popup> {
+        let index = self
+            .popups
+            .iter()
+            .position(|p| p.id.as_deref() == Some(id))?;
+        Some(self.popups.remove(index))
     }
 
     /// Hide the topmost popup
@@ -1156,6 +1203,13 @@ impl PopupManager {
             .unwrap_or(false)
     }
 
+    /// Check if the topmost popup is a signature help popup
+    pub fn is_signature_help_popup(&self) -> bool {
+        self.top()
+            .map(|p| p.kind == PopupKind::SignatureHelp)
+            .unwrap_or(false)
+    }
+
     /// Check if the topmost popup is an action popup
     pub fn is_action_popup(&self) -> bool {
         self.top()
@@ -1539,4 +1593,39 @@ mod tests {
         assert_eq!((s_line, s_col), (1, 5));
         assert_eq!((e_line, e_col), (2, 10));
     }
+
+    #[test]
+    fn test_popup_manager_z_order_and_ids() {
+        let theme = crate::view::theme::Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let mut manager = PopupManager::new();
+        manager.show(
+            Popup::text(vec!["panel".to_string()], &theme)
+                .with_id("panel".to_string())
+                .with_z_index(10),
+        );
+        manager.show(Popup::text(vec!["hover".to_string()], &theme));
+
+        // A lower z-index popup shown later still stays below the panel
+        assert_eq!(manager.top().unwrap().id.as_deref(), Some("panel"));
+        assert_eq!(
+            manager.all()[0].content,
+            PopupContent::Text(vec!["hover".to_string()])
+        );
+
+        // Showing the same id again replaces the existing popup
+        manager.show(
+            Popup::text(vec!["updated".to_string()], &theme)
+                .with_id("panel".to_string())
+                .with_z_index(10),
+        );
+        assert_eq!(manager.all().len(), 2);
+        assert_eq!(
+            manager.top().unwrap().content,
+            PopupContent::Text(vec!["updated".to_string()])
+        );
+
+        assert!(manager.hide_by_id("panel").is_some());
+        assert!(manager.hide_by_id("panel").is_none());
+        assert_eq!(manager.all().len(), 1);
+    }
 }
@@ -30,7 +30,7 @@ pub fn handle_popup_input(
 ) -> InputResult {
     match popup.kind {
         PopupKind::Completion => handle_completion_input_with_popup(event, popup, ctx),
-        PopupKind::Hover => handle_hover_input(event, popup, ctx),
+        PopupKind::Hover | PopupKind::SignatureHelp => handle_hover_input(event, popup, ctx),
         PopupKind::Action => handle_action_input(event, popup, ctx),
         PopupKind::List | PopupKind::Text => {
             // Generic list/text popups use the default action-like behavior
@@ -1,6 +1,7 @@
 //! Prompt/minibuffer system for user input
 
 use crate::input::commands::Suggestion;
+use crate::input::frecency::Frecency;
 use crate::primitives::grapheme;
 use crate::primitives::word_navigation::{
     find_word_end_bytes, find_word_start_bytes, is_word_char,
@@ -65,6 +66,8 @@ pub enum PromptType {
     JumpToBookmark,
     /// Set compose width (empty clears to viewport)
     SetComposeWidth,
+    /// Filter the keyboard shortcuts page
+    FilterKeyboardShortcuts,
     /// Add a vertical ruler at a column position
     AddRuler,
     /// Remove a vertical ruler (select from list)
@@ -79,6 +82,16 @@ pub enum PromptType {
     SetLanguage,
     /// Stop a running LSP server (select from list)
     StopLspServer,
+    /// Select a debug configuration from `.fresh/launch.toml`
+    SelectDebugConfiguration,
+    /// Add a debugger watch expression
+    DebugAddWatch,
+    /// Evaluate an expression in the debug session
+    DebugEvaluate,
+    /// Address to host a collaborative session on
+    CollabHost,
+    /// Address of a collaborative session to join
+    CollabJoin,
     /// Select a theme (select from list)
     /// Stores the original theme name for restoration on cancel
     SelectTheme { original_theme: String },
@@ -94,6 +107,16 @@ pub enum PromptType {
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
     ConfirmSaveConflict,
+    /// A modified buffer's file changed on disk (reload/keep/diff)
+    ConfirmExternalChange {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Select a local history snapshot of a buffer's file to compare with it
+    LocalHistory {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Recover unsaved changes left by a crashed session (recover/discard/compare)
+    ConfirmCrashRecovery,
     /// Confirm saving with sudo after permission denied
     ConfirmSudoSave {
         info: crate::model::buffer::SudoSaveRequired,
@@ -121,15 +144,45 @@ pub enum PromptType {
         path: std::path::PathBuf,
         is_dir: bool,
     },
+    /// Confirm deleting the marked files and directories in the file explorer
+    ConfirmDeleteFiles { paths: Vec<std::path::PathBuf> },
+    /// Move the marked (or selected) file explorer items to a destination
+    FileExplorerMove { paths: Vec<std::path::PathBuf> },
+    /// Copy the marked (or selected) file explorer items to a destination
+    FileExplorerCopy { paths: Vec<std::path::PathBuf> },
     /// Confirm loading a large file with non-resynchronizable encoding
     /// (like GB18030, GBK, Shift-JIS, EUC-KR) that requires full file loading
     ConfirmLargeFileEncoding { path: std::path::PathBuf },
     /// Switch to a tab by name (from the current split's open buffers)
     SwitchToTab,
+    /// Save the current split layout under a name
+    SaveLayout,
+    /// Restore a named split layout (select from list)
+    RestoreLayout,
+    /// Delete a named split layout (select from list)
+    DeleteLayout,
+    /// New name for a terminal's tab
+    RenameTerminal {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Revision to diff the active buffer against
+    GitDiffRevision,
+    /// Branch to check out (select from list)
+    GitCheckoutBranch,
+    /// Pick one of several LSP locations (definitions, implementations, references)
+    GotoLocation,
+    /// Pick a Unicode character or emoji to insert at each cursor
+    InsertUnicodeCharacter,
+    /// Action to run by name, with arguments
+    ActionConsole,
     /// Run shell command on buffer/selection
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
     ShellCommand { replace: bool },
+    /// Global command applying a command to the lines matching a pattern
+    GlobalCommand,
+    /// Pattern to search the project's files for
+    ProjectSearch,
     /// Async prompt from plugin (for editor.prompt() API)
     /// The result is returned via callback resolution
     AsyncPrompt,
@@ -161,6 +214,12 @@ pub struct Prompt {
     /// When true, navigating suggestions updates the input text (selected) to match.
     /// Used by plugin prompts that want picker-like behavior (e.g. compose width).
     pub sync_input_on_navigate: bool,
+    /// When true, Tab marks/unmarks the selected suggestion instead of accepting it,
+    /// and confirming acts on every marked suggestion.
+    pub multi_select: bool,
+    /// Values of the marked suggestions, in the order they were marked.
+    /// Stored by value so marks survive re-filtering of the suggestion list.
+    pub marked: Vec<String>,
 }
 
 impl Prompt {
@@ -177,6 +236,8 @@ impl Prompt {
             selection_anchor: None,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            multi_select: false,
+            marked: Vec::new(),
         }
     }
 
@@ -205,6 +266,8 @@ impl Prompt {
             selection_anchor: None,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            multi_select: false,
+            marked: Vec::new(),
         }
     }
 
@@ -232,6 +295,8 @@ impl Prompt {
             selection_anchor,
             suggestions_set_for_input: None,
             sync_input_on_navigate: false,
+            multi_select: false,
+            marked: Vec::new(),
         }
     }
 
@@ -351,6 +416,51 @@ impl Prompt {
             .map(|s| s.get_value().to_string())
     }
 
+    /// Whether Tab currently marks suggestions.
+    ///
+    /// Quick Open only marks entries in file mode; its command, buffer and
+    /// goto-line modes act on a single entry.
+    pub fn marking_enabled(&self) -> bool {
+        if !self.multi_select {
+            return false;
+        }
+        if self.prompt_type == PromptType::QuickOpen {
+            return !self.input.starts_with(['>', '#', ':']);
+        }
+        true
+    }
+
+    /// Whether a suggestion is marked
+    pub fn is_marked(&self, suggestion: &Suggestion) -> bool {
+        !self.marked.is_empty() && self.marked.iter().any(|v| v == suggestion.get_value())
+    }
+
+    /// Mark or unmark the selected suggestion, then move the selection down
+    ///
+    /// Disabled suggestions cannot be marked. Returns true if a mark changed.
+    pub fn toggle_mark_selected(&mut self) -> bool {
+        let Some(idx) = self.selected_suggestion else {
+            return false;
+        };
+        let Some(suggestion) = self.suggestions.get(idx) else {
+            return false;
+        };
+        if suggestion.disabled {
+            return false;
+        }
+        let value = suggestion.get_value().to_string();
+        if let Some(pos) = self.marked.iter().position(|v| *v == value) {
+            self.marked.remove(pos);
+        } else {
+            self.marked.push(value);
+        }
+        // Don't wrap around, like Down
+        if idx + 1 < self.suggestions.len() {
+            self.selected_suggestion = Some(idx + 1);
+        }
+        true
+    }
+
     /// Get the final input (use selected suggestion if available, otherwise raw input)
     pub fn get_final_input(&self) -> String {
         self.selected_value().unwrap_or_else(|| self.input.clone())
@@ -359,9 +469,11 @@ impl Prompt {
     /// Apply fuzzy filtering to suggestions based on current input
     ///
     /// If `match_description` is true, also matches against suggestion descriptions.
+    /// Matches are ranked by match quality plus how often and how recently their
+    /// values were chosen (`frecency`).
     /// Updates `suggestions` with filtered and sorted results.
-    pub fn filter_suggestions(&mut self, match_description: bool) {
-        use crate::input::fuzzy::{fuzzy_match, FuzzyMatch};
+    pub fn filter_suggestions(&mut self, match_description: bool, frecency: &Frecency) {
+        use crate::input::fuzzy::{fuzzy_filter_frecent, fuzzy_match, FuzzyMatch};
 
         // Skip filtering if the plugin has already set suggestions for this exact input.
         // This handles the race condition where run_hook("prompt_changed") is async:
@@ -377,9 +489,9 @@ impl Prompt {
         };
 
         let input = &self.input;
-        let mut filtered: Vec<(crate::input::commands::Suggestion, i32)> = original
-            .iter()
-            .filter_map(|s| {
+        let ranked = fuzzy_filter_frecent(
+            original,
+            |s| {
                 let text_result = fuzzy_match(input, &s.text);
                 let desc_result = if match_description {
                     s.description
@@ -389,16 +501,21 @@ impl Prompt {
                 } else {
                     FuzzyMatch::no_match()
                 };
-                if text_result.matched || desc_result.matched {
-                    Some((s.clone(), text_result.score.max(desc_result.score)))
+                if desc_result.matched && desc_result.score > text_result.score {
+                    desc_result
+                } else if text_result.matched {
+                    text_result
                 } else {
-                    None
+                    desc_result
                 }
-            })
+            },
+            |s| s.get_value().to_string(),
+            frecency,
+        );
+        self.suggestions = ranked
+            .into_iter()
+            .map(|(idx, _)| original[idx].clone())
             .collect();
-
-        filtered.sort_by(|a, b| b.1.cmp(&a.1));
-        self.suggestions = filtered.into_iter().map(|(s, _)| s).collect();
         self.selected_suggestion = if self.suggestions.is_empty() {
             None
         } else {
@@ -1159,6 +1276,86 @@ mod tests {
         assert_eq!(prompt.selected_text(), Some("two three".to_string()));
     }
 
+    #[test]
+    fn test_toggle_mark_selected() {
+        let mut prompt = Prompt::with_suggestions(
+            "Open: ".to_string(),
+            PromptType::Plugin {
+                custom_type: "files".to_string(),
+            },
+            vec![
+                Suggestion::new("a.rs".to_string()),
+                Suggestion::new("b.rs".to_string()),
+            ],
+        );
+        prompt.multi_select = true;
+
+        assert!(prompt.toggle_mark_selected());
+        assert_eq!(prompt.marked, vec!["a.rs".to_string()]);
+        assert_eq!(prompt.selected_suggestion, Some(1));
+
+        // The last entry stays selected after marking
+        assert!(prompt.toggle_mark_selected());
+        assert_eq!(prompt.selected_suggestion, Some(1));
+        assert!(prompt.is_marked(&prompt.suggestions[1].clone()));
+
+        // Toggling again unmarks
+        assert!(prompt.toggle_mark_selected());
+        assert_eq!(prompt.marked, vec!["a.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_marking_disabled_suggestion() {
+        let mut prompt = Prompt::with_suggestions(
+            "Open: ".to_string(),
+            PromptType::Plugin {
+                custom_type: "files".to_string(),
+            },
+            vec![Suggestion::with_description_and_disabled(
+                "a.rs".to_string(),
+                String::new(),
+                true,
+            )],
+        );
+        prompt.multi_select = true;
+
+        assert!(!prompt.toggle_mark_selected());
+        assert!(prompt.marked.is_empty());
+    }
+
+    #[test]
+    fn test_quick_open_marks_only_in_file_mode() {
+        let mut prompt = Prompt::new(String::new(), PromptType::QuickOpen);
+        prompt.multi_select = true;
+
+        prompt.input = ">save".to_string();
+        assert!(!prompt.marking_enabled());
+        prompt.input = "main.rs".to_string();
+        assert!(prompt.marking_enabled());
+    }
+
+    #[test]
+    fn test_filter_suggestions_ranks_frecent_values_first() {
+        let mut prompt = Prompt::with_suggestions(
+            "Language: ".to_string(),
+            PromptType::SetLanguage,
+            vec![
+                Suggestion::new("rust".to_string()),
+                Suggestion::new("ruby".to_string()),
+            ],
+        );
+        let mut frecency = Frecency::new();
+
+        prompt.input = "ru".to_string();
+        prompt.filter_suggestions(false, &frecency);
+        assert_eq!(prompt.suggestions[0].text, "rust");
+
+        frecency.record("ruby");
+        prompt.filter_suggestions(false, &frecency);
+        assert_eq!(prompt.suggestions[0].text, "ruby");
+        assert_eq!(prompt.selected_suggestion, Some(0));
+    }
+
     // Property-based tests for Prompt operations
     #[cfg(test)]
     mod property_tests {
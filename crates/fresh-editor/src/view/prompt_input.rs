@@ -235,6 +235,21 @@ impl InputHandler for Prompt {
                 InputResult::Consumed
             }
 
+            // Tab marks the suggestion in multi-select prompts
+            KeyCode::Tab if self.marking_enabled() => {
+                if self.toggle_mark_selected()
+                    && matches!(
+                        self.prompt_type,
+                        crate::view::prompt::PromptType::Plugin { .. }
+                    )
+                {
+                    if let Some(selected_index) = self.selected_suggestion {
+                        ctx.defer(DeferredAction::PromptSelectionChanged { selected_index });
+                    }
+                }
+                InputResult::Consumed
+            }
+
             // Tab accepts suggestion
             KeyCode::Tab => {
                 if let Some(selected) = self.selected_suggestion {
@@ -313,6 +328,16 @@ impl Prompt {
                 ctx.defer(DeferredAction::UpdatePromptSuggestions);
                 InputResult::Consumed
             }
+            // Ctrl+P/N search history for entries containing the typed text.
+            // Quick Open keeps Ctrl+P so that it can toggle itself closed.
+            'p' | 'n' if self.prompt_type != crate::view::prompt::PromptType::QuickOpen => {
+                if c == 'p' {
+                    ctx.defer(DeferredAction::PromptHistorySearchPrev);
+                } else {
+                    ctx.defer(DeferredAction::PromptHistorySearchNext);
+                }
+                InputResult::Consumed
+            }
             // Pass through other Ctrl+key combinations to global keybindings (e.g., Ctrl+P to toggle Quick Open)
             _ => InputResult::Ignored,
         }
@@ -440,17 +465,17 @@ mod tests {
 
     #[test]
     fn test_prompt_ctrl_p_returns_ignored() {
-        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::Search);
+        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::QuickOpen);
         let mut ctx = InputContext::new();
 
-        // Ctrl+P should return Ignored so it can be handled by global keybindings
+        // Ctrl+P should return Ignored in Quick Open so the global binding can toggle it closed
         let result = prompt.handle_key_event(&key_with_ctrl('p'), &mut ctx);
         assert_eq!(result, InputResult::Ignored, "Ctrl+P should return Ignored");
     }
 
     #[test]
     fn test_prompt_ctrl_p_dispatch_returns_ignored() {
-        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::Search);
+        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::QuickOpen);
         let mut ctx = InputContext::new();
 
         // dispatch_input should also return Ignored for Ctrl+P (not Consumed by modal behavior)
@@ -461,4 +486,22 @@ mod tests {
             "dispatch_input should return Ignored for Ctrl+P"
         );
     }
+
+    #[test]
+    fn test_prompt_ctrl_p_n_search_history() {
+        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::Search);
+        let mut ctx = InputContext::new();
+
+        let result = prompt.handle_key_event(&key_with_ctrl('p'), &mut ctx);
+        assert_eq!(result, InputResult::Consumed);
+        let result = prompt.handle_key_event(&key_with_ctrl('n'), &mut ctx);
+        assert_eq!(result, InputResult::Consumed);
+        assert!(matches!(
+            ctx.deferred_actions.as_slice(),
+            [
+                DeferredAction::PromptHistorySearchPrev,
+                DeferredAction::PromptHistorySearchNext
+            ]
+        ));
+    }
 }
@@ -9,6 +9,7 @@ use crate::model::marker::MarkerList;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
 use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, OverlayNamespace};
 use ratatui::style::Color;
+use std::ops::Range;
 use std::time::{Duration, Instant};
 
 /// Default debounce delay for reference highlighting (150ms)
@@ -32,6 +33,10 @@ pub struct ReferenceHighlightOverlay {
     word_changed_at: Option<Instant>,
     /// Debounce delay before updating highlights
     debounce_delay: Duration,
+    /// Cursor position awaiting an LSP documentHighlight request
+    lsp_request: Option<usize>,
+    /// Color used for the most recently applied highlights
+    highlight_color: Color,
     /// Whether highlighting is enabled
     pub enabled: bool,
 }
@@ -44,6 +49,8 @@ impl ReferenceHighlightOverlay {
             pending_word: None,
             word_changed_at: None,
             debounce_delay: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+            lsp_request: None,
+            highlight_color: Color::Reset,
             enabled: true,
         }
     }
@@ -103,6 +110,10 @@ impl ReferenceHighlightOverlay {
                 // Debounce period elapsed - update overlays
                 self.current_word = self.pending_word.clone();
                 self.word_changed_at = None;
+                self.highlight_color = highlight_color;
+                // Word matches are shown immediately; the LSP result (if any)
+                // replaces them once it arrives.
+                self.lsp_request = self.current_word.as_ref().map(|_| cursor_position);
 
                 self.apply_highlights(
                     buffer,
@@ -166,6 +177,39 @@ impl ReferenceHighlightOverlay {
         }
     }
 
+    /// Take the cursor position for which an LSP documentHighlight request
+    /// should be sent, if highlights were just refreshed
+    pub fn take_lsp_request(&mut self) -> Option<usize> {
+        self.lsp_request.take()
+    }
+
+    /// Replace the word-match highlights with ranges reported by the LSP server
+    ///
+    /// Ignored if the cursor has since left the identifier. An empty result
+    /// keeps the word-match fallback.
+    pub fn apply_lsp_highlights(
+        &mut self,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        ranges: Vec<Range<usize>>,
+    ) {
+        if !self.enabled || self.current_word.is_none() || ranges.is_empty() {
+            return;
+        }
+
+        let ns = reference_highlight_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+
+        for range in ranges {
+            let face = OverlayFace::Background {
+                color: self.highlight_color,
+            };
+            let overlay = Overlay::with_namespace(marker_list, range, face, ns.clone())
+                .with_priority_value(5);
+            overlays.add(overlay);
+        }
+    }
+
     /// Check if a redraw is needed (debounce timer pending)
     pub fn needs_redraw(&self) -> Option<Duration> {
         self.word_changed_at.map(|changed_at| {
@@ -185,6 +229,7 @@ impl ReferenceHighlightOverlay {
         self.current_word = None;
         self.pending_word = None;
         self.word_changed_at = None;
+        self.lsp_request = None;
     }
 
     /// Check if currently debouncing
@@ -261,4 +306,81 @@ mod tests {
         let word = get_word_at_position(&buffer, 6);
         assert_eq!(word, Some("world".to_string()));
     }
+
+    fn overlay_ranges(overlays: &OverlayManager, marker_list: &MarkerList) -> Vec<Range<usize>> {
+        let mut ranges: Vec<_> = overlays
+            .all()
+            .iter()
+            .map(|o| o.range(marker_list))
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        ranges
+    }
+
+    #[test]
+    fn test_lsp_highlights_replace_word_matches() {
+        let buffer = Buffer::from_str_test("foo bar foo baz foo");
+        let mut overlays = OverlayManager::new();
+        let mut marker_list = MarkerList::new();
+        let mut highlighter = ReferenceHighlighter::new();
+        let mut overlay = ReferenceHighlightOverlay::with_debounce(0);
+
+        // First call records the word, second applies it once the debounce elapses
+        for _ in 0..2 {
+            overlay.update(
+                &buffer,
+                &mut overlays,
+                &mut marker_list,
+                &mut highlighter,
+                1,
+                0,
+                buffer.len(),
+                1000,
+                Color::Yellow,
+            );
+        }
+        assert_eq!(
+            overlay_ranges(&overlays, &marker_list),
+            vec![0..3, 8..11, 16..19]
+        );
+        assert_eq!(overlay.take_lsp_request(), Some(1));
+        assert_eq!(overlay.take_lsp_request(), None);
+
+        // The server knows the last "foo" is a different symbol
+        overlay.apply_lsp_highlights(&mut overlays, &mut marker_list, vec![0..3, 8..11]);
+        assert_eq!(overlay_ranges(&overlays, &marker_list), vec![0..3, 8..11]);
+
+        // An empty answer keeps what is shown
+        overlay.apply_lsp_highlights(&mut overlays, &mut marker_list, Vec::new());
+        assert_eq!(overlay_ranges(&overlays, &marker_list), vec![0..3, 8..11]);
+    }
+
+    #[test]
+    fn test_lsp_highlights_ignored_off_identifier() {
+        let buffer = Buffer::from_str_test("foo bar foo");
+        let mut overlays = OverlayManager::new();
+        let mut marker_list = MarkerList::new();
+        let mut highlighter = ReferenceHighlighter::new();
+        let mut overlay = ReferenceHighlightOverlay::with_debounce(0);
+
+        // Cursor on whitespace: nothing to highlight and nothing to ask the server
+        for _ in 0..2 {
+            overlay.update(
+                &buffer,
+                &mut overlays,
+                &mut marker_list,
+                &mut highlighter,
+                3,
+                0,
+                buffer.len(),
+                1000,
+                Color::Yellow,
+            );
+        }
+        assert_eq!(overlay.take_lsp_request(), None);
+
+        let ranges: Vec<Range<usize>> = vec![Range { start: 0, end: 3 }];
+        overlay.apply_lsp_highlights(&mut overlays, &mut marker_list, ranges);
+        assert!(overlays.is_empty());
+    }
 }
@@ -4,7 +4,7 @@
 
 use rust_i18n::t;
 
-use crate::primitives::display_width::str_width;
+use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
 
 use super::items::SettingControl;
 use super::layout::{SettingsHit, SettingsLayout};
@@ -2421,8 +2421,9 @@ fn render_search_result_item(
     // Third line: Description (if any)
     if let Some(ref desc) = result.item.description {
         let desc_style = Style::default().fg(theme.line_number_fg);
-        let truncated_desc = if desc.len() > area.width as usize - 2 {
-            format!("  {}...", &desc[..area.width as usize - 5])
+        let truncated_desc = if str_width(desc) > area.width as usize - 2 {
+            let end = byte_offset_at_visual_column(desc, area.width as usize - 5);
+            format!("  {}...", &desc[..end])
         } else {
             format!("  {}", desc)
         };
@@ -58,6 +58,15 @@ pub enum SplitNode {
     },
 }
 
+/// Screen direction used for spatial navigation between splits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
 /// Per-buffer view state within a split.
 ///
 /// Each buffer opened in a split gets its own `BufferViewState` stored in the
@@ -530,6 +539,30 @@ impl SplitNode {
         }
     }
 
+    /// Find the parent container of a split.
+    /// Returns the parent's ID and whether the target is its first child.
+    pub fn find_parent(&self, target_id: SplitId) -> Option<(SplitId, bool)> {
+        match self {
+            Self::Leaf { .. } => None,
+            Self::Split {
+                first,
+                second,
+                split_id,
+                ..
+            } => {
+                if first.id() == target_id {
+                    Some((*split_id, true))
+                } else if second.id() == target_id {
+                    Some((*split_id, false))
+                } else {
+                    first
+                        .find_parent(target_id)
+                        .or_else(|| second.find_parent(target_id))
+                }
+            }
+        }
+    }
+
     /// Count the number of leaf nodes (visible buffers)
     pub fn count_leaves(&self) -> usize {
         match self {
@@ -724,6 +757,8 @@ impl SplitManager {
             self.replace_split_with_split(active_id, direction, new_buffer_id, ratio, before);
 
         if let Ok(new_split_id) = result {
+            // A new split would be hidden behind a maximized one, so restore first
+            self.maximized_split = None;
             // Set the new split as active
             self.active_split = new_split_id;
             Ok(new_split_id)
@@ -732,6 +767,22 @@ impl SplitManager {
         }
     }
 
+    /// Split the whole layout, placing the new buffer after (below/right of)
+    /// every existing split. The active split is unchanged.
+    pub fn split_root(
+        &mut self,
+        direction: SplitDirection,
+        new_buffer_id: BufferId,
+        ratio: f32,
+    ) -> Result<SplitId, String> {
+        let root_id = self.root.id();
+        let new_split_id =
+            self.replace_split_with_split(root_id, direction, new_buffer_id, ratio, false)?;
+        // A new split would be hidden behind a maximized one, so restore first
+        self.maximized_split = None;
+        Ok(new_split_id)
+    }
+
     /// Replace a split with a new split container.
     /// When `before` is true, the new buffer is placed as the first child (left/top).
     fn replace_split_with_split(
@@ -882,6 +933,17 @@ impl SplitManager {
         }
     }
 
+    /// Grow (positive delta) or shrink (negative delta) a leaf split by
+    /// adjusting the ratio of its parent container
+    pub fn adjust_leaf_size(&mut self, split_id: SplitId, delta: f32) -> Result<(), String> {
+        let (parent_id, is_first) = self
+            .root
+            .find_parent(split_id)
+            .ok_or_else(|| "Split has no parent container".to_string())?;
+        let delta = if is_first { delta } else { -delta };
+        self.adjust_ratio(parent_id, delta)
+    }
+
     /// Get all visible buffer views with their rectangles
     pub fn get_visible_buffers(&self, viewport_rect: Rect) -> Vec<(SplitId, BufferId, Rect)> {
         // If a split is maximized, only show that split taking up the full viewport
@@ -990,6 +1052,108 @@ impl SplitManager {
         }
     }
 
+    /// Find the nearest visible leaf split in the given screen direction
+    /// from the active split.
+    ///
+    /// Candidates must lie entirely on the requested side of the active split
+    /// and overlap it on the perpendicular axis. Among those, the closest one
+    /// wins; ties are broken by the largest overlap.
+    pub fn split_in_direction(
+        &self,
+        viewport_rect: Rect,
+        direction: FocusDirection,
+    ) -> Option<SplitId> {
+        let leaves = self.get_visible_buffers(viewport_rect);
+        let (_, _, current) = leaves
+            .iter()
+            .find(|(id, _, _)| *id == self.active_split)
+            .copied()?;
+
+        let overlap = |a_start: u16, a_len: u16, b_start: u16, b_len: u16| -> u16 {
+            let start = a_start.max(b_start);
+            let end = (a_start + a_len).min(b_start + b_len);
+            end.saturating_sub(start)
+        };
+
+        leaves
+            .iter()
+            .filter(|(id, _, _)| *id != self.active_split)
+            .filter_map(|(id, _, rect)| {
+                let (distance, shared) = match direction {
+                    FocusDirection::Left if rect.x + rect.width <= current.x => (
+                        current.x - (rect.x + rect.width),
+                        overlap(rect.y, rect.height, current.y, current.height),
+                    ),
+                    FocusDirection::Right if rect.x >= current.x + current.width => (
+                        rect.x - (current.x + current.width),
+                        overlap(rect.y, rect.height, current.y, current.height),
+                    ),
+                    FocusDirection::Up if rect.y + rect.height <= current.y => (
+                        current.y - (rect.y + rect.height),
+                        overlap(rect.x, rect.width, current.x, current.width),
+                    ),
+                    FocusDirection::Down if rect.y >= current.y + current.height => (
+                        rect.y - (current.y + current.height),
+                        overlap(rect.x, rect.width, current.x, current.width),
+                    ),
+                    _ => return None,
+                };
+                (shared > 0).then_some((*id, distance, shared))
+            })
+            .min_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)))
+            .map(|(id, _, _)| id)
+    }
+
+    /// Move focus to the nearest split in the given direction.
+    /// Returns true if the active split changed.
+    pub fn focus_direction(&mut self, viewport_rect: Rect, direction: FocusDirection) -> bool {
+        match self.split_in_direction(viewport_rect, direction) {
+            Some(target) => {
+                self.active_split = target;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swap the two children of the container holding the active split,
+    /// so the panes trade places while keeping their on-screen sizes
+    pub fn rotate_active(&mut self) -> Result<(), String> {
+        let (parent_id, _) = self
+            .root
+            .find_parent(self.active_split)
+            .ok_or_else(|| "Cannot rotate: only one split exists".to_string())?;
+        if let Some(SplitNode::Split { first, second, .. }) = self.root.find_mut(parent_id) {
+            std::mem::swap(first, second);
+        }
+        Ok(())
+    }
+
+    /// Close every split except the active one.
+    /// Returns the IDs of the leaf splits that were removed.
+    pub fn close_other_splits(&mut self) -> Result<Vec<SplitId>, String> {
+        if self.root.count_leaves() <= 1 {
+            return Err("No other splits to close".to_string());
+        }
+        let buffer_id = self
+            .active_buffer_id()
+            .ok_or_else(|| "Active split is not a leaf".to_string())?;
+
+        let removed: Vec<SplitId> = self
+            .root
+            .leaf_split_ids()
+            .into_iter()
+            .filter(|id| *id != self.active_split)
+            .collect();
+
+        self.root = SplitNode::leaf(buffer_id, self.active_split);
+        self.maximized_split = None;
+        let active = self.active_split;
+        self.labels.retain(|id, _| *id == active);
+
+        Ok(removed)
+    }
+
     /// Get all split IDs that display a specific buffer
     pub fn splits_for_buffer(&self, target_buffer_id: BufferId) -> Vec<SplitId> {
         self.root
@@ -1357,6 +1521,154 @@ mod tests {
         assert_eq!(manager.find_unlabeled_leaf(), Some(manager.active_split()));
     }
 
+    // === Navigation and layout tests ===
+
+    fn viewport() -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 40,
+        }
+    }
+
+    #[test]
+    fn test_focus_direction_vertical_split() {
+        let mut manager = SplitManager::new(BufferId(0));
+        let left = manager.active_split();
+        let right = manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+
+        assert!(!manager.focus_direction(viewport(), FocusDirection::Right));
+        assert!(!manager.focus_direction(viewport(), FocusDirection::Up));
+        assert!(manager.focus_direction(viewport(), FocusDirection::Left));
+        assert_eq!(manager.active_split(), left);
+        assert!(manager.focus_direction(viewport(), FocusDirection::Right));
+        assert_eq!(manager.active_split(), right);
+    }
+
+    #[test]
+    fn test_focus_direction_prefers_overlapping_neighbor() {
+        // ┌──────┬──────┐
+        // │      │  B   │
+        // │  A   ├──────┤
+        // │      │  C   │
+        // └──────┴──────┘
+        let mut manager = SplitManager::new(BufferId(0));
+        let a = manager.active_split();
+        let b = manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+        let c = manager
+            .split_active(SplitDirection::Horizontal, BufferId(2), 0.5)
+            .unwrap();
+
+        assert!(manager.focus_direction(viewport(), FocusDirection::Up));
+        assert_eq!(manager.active_split(), b);
+        assert!(manager.focus_direction(viewport(), FocusDirection::Down));
+        assert_eq!(manager.active_split(), c);
+        assert!(manager.focus_direction(viewport(), FocusDirection::Left));
+        assert_eq!(manager.active_split(), a);
+    }
+
+    #[test]
+    fn test_adjust_leaf_size_resizes_parent() {
+        let mut manager = SplitManager::new(BufferId(0));
+        let first = manager.active_split();
+        let second = manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+        let (parent, _) = manager.root().find_parent(second).unwrap();
+
+        manager.adjust_leaf_size(second, 0.1).unwrap();
+        assert!((manager.get_ratio(parent).unwrap() - 0.4).abs() < 0.001);
+
+        manager.adjust_leaf_size(first, 0.2).unwrap();
+        assert!((manager.get_ratio(parent).unwrap() - 0.6).abs() < 0.001);
+
+        // A lone leaf has no container to resize
+        let mut single = SplitManager::new(BufferId(0));
+        let only = single.active_split();
+        assert!(single.adjust_leaf_size(only, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_rotate_active_swaps_siblings() {
+        let mut manager = SplitManager::new(BufferId(0));
+        let first = manager.active_split();
+        let second = manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+
+        manager.rotate_active().unwrap();
+        assert_eq!(manager.root().leaf_split_ids(), vec![second, first]);
+        assert_eq!(manager.active_split(), second);
+    }
+
+    #[test]
+    fn test_close_other_splits() {
+        let mut manager = SplitManager::new(BufferId(0));
+        let first = manager.active_split();
+        manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+        let third = manager
+            .split_active(SplitDirection::Horizontal, BufferId(2), 0.5)
+            .unwrap();
+        manager.set_label(first, "sidebar".to_string());
+
+        let removed = manager.close_other_splits().unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(removed.contains(&first));
+        assert_eq!(manager.root().count_leaves(), 1);
+        assert_eq!(manager.active_split(), third);
+        assert_eq!(manager.active_buffer_id(), Some(BufferId(2)));
+        assert_eq!(manager.find_split_by_label("sidebar"), None);
+
+        assert!(manager.close_other_splits().is_err());
+    }
+
+    #[test]
+    fn test_split_while_maximized_restores_layout() {
+        let mut manager = SplitManager::new(BufferId(0));
+        manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+        manager.maximize_split().unwrap();
+        assert!(manager.is_maximized());
+
+        manager
+            .split_active(SplitDirection::Horizontal, BufferId(2), 0.5)
+            .unwrap();
+        assert!(!manager.is_maximized());
+        assert_eq!(manager.root().count_leaves(), 3);
+    }
+
+    #[test]
+    fn test_split_root_spans_whole_layout() {
+        let mut manager = SplitManager::new(BufferId(0));
+        let first = manager.active_split();
+        let second = manager
+            .split_active(SplitDirection::Vertical, BufferId(1), 0.5)
+            .unwrap();
+
+        let bottom = manager
+            .split_root(SplitDirection::Horizontal, BufferId(2), 0.7)
+            .unwrap();
+        assert_eq!(manager.active_split(), second);
+        assert_eq!(manager.root().leaf_split_ids(), vec![first, second, bottom]);
+
+        let area = Rect::new(0, 0, 80, 21);
+        let leaves = manager.get_visible_buffers(area);
+        let (_, _, bottom_rect) = leaves.iter().find(|(id, _, _)| *id == bottom).unwrap();
+        assert_eq!(bottom_rect.width, 80);
+        assert!(leaves
+            .iter()
+            .filter(|(id, _, _)| *id != bottom)
+            .all(|(_, _, rect)| rect.y + rect.height <= bottom_rect.y));
+    }
+
     #[test]
     fn test_find_unlabeled_leaf_single_split_labeled() {
         let mut manager = SplitManager::new(BufferId(0));
@@ -6,6 +6,7 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use super::types::{Theme, ThemeFile, ThemeInfo, BUILTIN_THEMES};
 
@@ -18,6 +19,29 @@ pub fn normalize_theme_name(name: &str) -> String {
     name.to_lowercase().replace('_', "-").replace(' ', "-")
 }
 
+/// Check whether a path has a theme file extension (`.json` or `.toml`).
+fn is_theme_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "json" || ext == "toml")
+}
+
+/// Parse a theme file, choosing JSON or TOML by its extension.
+fn read_theme_file(path: &Path) -> Option<ThemeFile> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let parsed = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str::<ThemeFile>(&content).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str::<ThemeFile>(&content).map_err(|e| e.to_string())
+    };
+    match parsed {
+        Ok(theme_file) => Some(theme_file),
+        Err(e) => {
+            tracing::warn!("Failed to parse theme {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
 /// A registry holding all loaded themes.
 ///
 /// This is a pure data structure - no I/O operations.
@@ -92,6 +116,36 @@ impl ThemeLoader {
         self.user_themes_dir.as_deref()
     }
 
+    /// Modification times of every theme file under the user themes directory,
+    /// sorted by path.
+    ///
+    /// Comparing two stamps detects added, removed and edited theme files,
+    /// which is how the editor hot-reloads themes.
+    pub fn file_stamps(&self) -> Vec<(PathBuf, SystemTime)> {
+        let mut stamps = Vec::new();
+        if let Some(ref user_dir) = self.user_themes_dir {
+            Self::collect_stamps(user_dir, &mut stamps);
+        }
+        stamps.sort();
+        stamps
+    }
+
+    fn collect_stamps(dir: &Path, stamps: &mut Vec<(PathBuf, SystemTime)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                Self::collect_stamps(&path, stamps);
+            } else if is_theme_file(&path) {
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    stamps.push((path, modified));
+                }
+            }
+        }
+    }
+
     /// Load all themes (embedded + user + packages) into a registry.
     pub fn load_all(&self) -> ThemeRegistry {
         let mut themes = HashMap::new();
@@ -113,7 +167,7 @@ impl ThemeLoader {
         }
 
         // Load theme packages from ~/.config/fresh/themes/packages/*/
-        // Each package directory may contain multiple theme JSON files
+        // Each package directory may contain multiple theme files (JSON or TOML)
         if let Some(ref user_dir) = self.user_themes_dir {
             let packages_dir = user_dir.join("packages");
             if packages_dir.exists() {
@@ -134,7 +188,7 @@ impl ThemeLoader {
                                             &mut theme_list,
                                         );
                                     } else {
-                                        // Fallback: scan directory for JSON files
+                                        // Fallback: scan directory for theme files
                                         let pack_name = format!("pkg/{}", name);
                                         self.scan_directory(
                                             &path,
@@ -184,18 +238,14 @@ impl ThemeLoader {
                     ) {
                         let theme_path = pkg_dir.join(file);
                         if theme_path.exists() {
-                            if let Ok(content) = std::fs::read_to_string(&theme_path) {
-                                if let Ok(theme_file) = serde_json::from_str::<ThemeFile>(&content)
-                                {
-                                    let theme: Theme = theme_file.into();
-                                    let normalized_name = normalize_theme_name(name);
-                                    // Don't overwrite existing themes
-                                    if !themes.contains_key(&normalized_name) {
-                                        themes.insert(normalized_name.clone(), theme);
-                                        let pack_name = format!("pkg/{}", pkg_name);
-                                        theme_list
-                                            .push(ThemeInfo::new(normalized_name, &pack_name));
-                                    }
+                            if let Some(theme_file) = read_theme_file(&theme_path) {
+                                let theme: Theme = theme_file.into();
+                                let normalized_name = normalize_theme_name(name);
+                                // Don't overwrite existing themes
+                                if !themes.contains_key(&normalized_name) {
+                                    themes.insert(normalized_name.clone(), theme);
+                                    let pack_name = format!("pkg/{}", pkg_name);
+                                    theme_list.push(ThemeInfo::new(normalized_name, &pack_name));
                                 }
                             }
                         }
@@ -205,7 +255,7 @@ impl ThemeLoader {
             }
         }
 
-        // Fallback: if no fresh.themes, scan for JSON files
+        // Fallback: if no fresh.themes, scan for theme files
         let pack_name = format!("pkg/{}", pkg_name);
         self.scan_directory(pkg_dir, &pack_name, themes, theme_list);
     }
@@ -242,7 +292,7 @@ impl ThemeLoader {
                     format!("{}/{}", pack, subdir_name)
                 };
                 self.scan_directory(&path, &new_pack, themes, theme_list);
-            } else if path.extension().is_some_and(|ext| ext == "json") {
+            } else if is_theme_file(&path) {
                 // Load theme file
                 let raw_name = path.file_stem().unwrap().to_string_lossy().to_string();
                 let name = normalize_theme_name(&raw_name);
@@ -252,12 +302,10 @@ impl ThemeLoader {
                     continue;
                 }
 
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(theme_file) = serde_json::from_str::<ThemeFile>(&content) {
-                        let theme: Theme = theme_file.into();
-                        themes.insert(name.clone(), theme);
-                        theme_list.push(ThemeInfo::new(name, pack));
-                    }
+                if let Some(theme_file) = read_theme_file(&path) {
+                    let theme: Theme = theme_file.into();
+                    themes.insert(name.clone(), theme);
+                    theme_list.push(ThemeInfo::new(name, pack));
                 }
             }
         }
@@ -268,9 +316,13 @@ impl ThemeLoader {
 impl Theme {
     /// Set the terminal cursor color using OSC 12 escape sequence.
     /// This makes the hardware cursor visible on any background.
+    /// Nothing is written unless stdout is a terminal (e.g. `--batch`).
     pub fn set_terminal_cursor_color(&self) {
         use super::types::color_to_rgb;
-        use std::io::Write;
+        use std::io::{IsTerminal, Write};
+        if !std::io::stdout().is_terminal() {
+            return;
+        }
         if let Some((r, g, b)) = color_to_rgb(self.cursor) {
             // OSC 12 sets cursor color: \x1b]12;#RRGGBB\x07
             let _ = write!(
@@ -427,6 +479,59 @@ mod tests {
         }
     }
 
+    /// Test that user themes written in TOML are loaded alongside JSON ones.
+    #[test]
+    fn test_custom_toml_theme_loading() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let themes_dir = temp_dir.path().to_path_buf();
+
+        let theme_toml = r#"
+            name = "my-toml-theme"
+
+            [editor]
+            bg = [10, 20, 30]
+            fg = "White"
+
+            [ui]
+            [search]
+            [diagnostic]
+
+            [syntax]
+            keyword = [200, 100, 50]
+        "#;
+        std::fs::write(themes_dir.join("my-toml-theme.toml"), theme_toml)
+            .expect("Failed to write theme file");
+
+        let registry = ThemeLoader::new(themes_dir).load_all();
+        let theme = registry
+            .get("my-toml-theme")
+            .expect("TOML theme should be loaded");
+        assert_eq!(theme.editor_bg, ratatui::style::Color::Rgb(10, 20, 30));
+        assert_eq!(theme.editor_fg, ratatui::style::Color::White);
+        assert_eq!(
+            theme.syntax_keyword,
+            ratatui::style::Color::Rgb(200, 100, 50)
+        );
+    }
+
+    /// Test that file stamps change when a theme file is added or edited.
+    #[test]
+    fn test_theme_file_stamps_detect_changes() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let themes_dir = temp_dir.path().to_path_buf();
+        let loader = ThemeLoader::new(themes_dir.clone());
+        assert!(loader.file_stamps().is_empty());
+
+        let path = themes_dir.join("mine.toml");
+        std::fs::write(&path, "name = \"mine\"").unwrap();
+        std::fs::write(themes_dir.join("notes.txt"), "not a theme").unwrap();
+        let stamps = loader.file_stamps();
+        assert_eq!(stamps.len(), 1);
+        assert_eq!(stamps[0].0, path);
+
+        assert!(ThemeLoader::embedded_only().file_stamps().is_empty());
+    }
+
     /// Test that custom themes in a package directory (with package.json) are loaded.
     #[test]
     fn test_custom_theme_package_loading() {
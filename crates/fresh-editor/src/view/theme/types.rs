@@ -221,6 +221,9 @@ pub struct EditorColors {
     /// Vertical ruler background color
     #[serde(default = "default_ruler_bg")]
     pub ruler_bg: ColorDef,
+    /// Background of whitespace at the end of lines
+    #[serde(default = "default_trailing_whitespace_bg")]
+    pub trailing_whitespace_bg: ColorDef,
 }
 
 // Default editor colors (for minimal themes)
@@ -260,6 +263,9 @@ fn default_diff_modify_bg() -> ColorDef {
 fn default_ruler_bg() -> ColorDef {
     ColorDef::Rgb(50, 50, 50) // Subtle dark gray, slightly lighter than default editor bg
 }
+fn default_trailing_whitespace_bg() -> ColorDef {
+    ColorDef::Rgb(100, 40, 40) // Muted red
+}
 
 /// UI element colors (tabs, menus, status bar, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -762,6 +768,24 @@ pub struct SyntaxColors {
     /// Operators (+, -, =, etc.)
     #[serde(default = "default_syntax_operator")]
     pub operator: ColorDef,
+    /// Rainbow delimiters: outermost bracket level
+    #[serde(default = "default_syntax_rainbow_1")]
+    pub rainbow_1: ColorDef,
+    /// Rainbow delimiters: second bracket level
+    #[serde(default = "default_syntax_rainbow_2")]
+    pub rainbow_2: ColorDef,
+    /// Rainbow delimiters: third bracket level
+    #[serde(default = "default_syntax_rainbow_3")]
+    pub rainbow_3: ColorDef,
+    /// Rainbow delimiters: fourth bracket level
+    #[serde(default = "default_syntax_rainbow_4")]
+    pub rainbow_4: ColorDef,
+    /// Rainbow delimiters: fifth bracket level
+    #[serde(default = "default_syntax_rainbow_5")]
+    pub rainbow_5: ColorDef,
+    /// Rainbow delimiters: sixth bracket level (deeper levels cycle back to the first)
+    #[serde(default = "default_syntax_rainbow_6")]
+    pub rainbow_6: ColorDef,
 }
 
 // Default syntax colors (VSCode Dark+ inspired)
@@ -789,6 +813,24 @@ fn default_syntax_constant() -> ColorDef {
 fn default_syntax_operator() -> ColorDef {
     ColorDef::Rgb(212, 212, 212)
 }
+fn default_syntax_rainbow_1() -> ColorDef {
+    ColorDef::Rgb(255, 215, 0)
+}
+fn default_syntax_rainbow_2() -> ColorDef {
+    ColorDef::Rgb(218, 112, 214)
+}
+fn default_syntax_rainbow_3() -> ColorDef {
+    ColorDef::Rgb(23, 159, 255)
+}
+fn default_syntax_rainbow_4() -> ColorDef {
+    ColorDef::Rgb(50, 205, 50)
+}
+fn default_syntax_rainbow_5() -> ColorDef {
+    ColorDef::Rgb(255, 127, 80)
+}
+fn default_syntax_rainbow_6() -> ColorDef {
+    ColorDef::Rgb(147, 112, 219)
+}
 
 /// Comprehensive theme structure with all UI colors
 #[derive(Debug, Clone)]
@@ -809,6 +851,9 @@ pub struct Theme {
     // Vertical ruler color
     pub ruler_bg: Color,
 
+    // Trailing whitespace highlight
+    pub trailing_whitespace_bg: Color,
+
     // Diff highlighting colors
     pub diff_add_bg: Color,
     pub diff_remove_bg: Color,
@@ -930,6 +975,8 @@ pub struct Theme {
     pub syntax_variable: Color,
     pub syntax_constant: Color,
     pub syntax_operator: Color,
+    /// Rainbow delimiter palette, indexed by bracket nesting depth
+    pub syntax_rainbow: [Color; 6],
 }
 
 impl From<ThemeFile> for Theme {
@@ -945,6 +992,7 @@ impl From<ThemeFile> for Theme {
             line_number_fg: file.editor.line_number_fg.into(),
             line_number_bg: file.editor.line_number_bg.into(),
             ruler_bg: file.editor.ruler_bg.into(),
+            trailing_whitespace_bg: file.editor.trailing_whitespace_bg.into(),
             diff_add_bg: file.editor.diff_add_bg.clone().into(),
             diff_remove_bg: file.editor.diff_remove_bg.clone().into(),
             diff_modify_bg: file.editor.diff_modify_bg.into(),
@@ -1032,6 +1080,14 @@ impl From<ThemeFile> for Theme {
             syntax_variable: file.syntax.variable.into(),
             syntax_constant: file.syntax.constant.into(),
             syntax_operator: file.syntax.operator.into(),
+            syntax_rainbow: [
+                file.syntax.rainbow_1.into(),
+                file.syntax.rainbow_2.into(),
+                file.syntax.rainbow_3.into(),
+                file.syntax.rainbow_4.into(),
+                file.syntax.rainbow_5.into(),
+                file.syntax.rainbow_6.into(),
+            ],
         }
     }
 }
@@ -1053,6 +1109,7 @@ impl From<Theme> for ThemeFile {
                 diff_remove_bg: theme.diff_remove_bg.into(),
                 diff_modify_bg: theme.diff_modify_bg.into(),
                 ruler_bg: theme.ruler_bg.into(),
+                trailing_whitespace_bg: theme.trailing_whitespace_bg.into(),
             },
             ui: UiColors {
                 tab_active_fg: theme.tab_active_fg.into(),
@@ -1142,6 +1199,12 @@ impl From<Theme> for ThemeFile {
                 variable: theme.syntax_variable.into(),
                 constant: theme.syntax_constant.into(),
                 operator: theme.syntax_operator.into(),
+                rainbow_1: theme.syntax_rainbow[0].into(),
+                rainbow_2: theme.syntax_rainbow[1].into(),
+                rainbow_3: theme.syntax_rainbow[2].into(),
+                rainbow_4: theme.syntax_rainbow[3].into(),
+                rainbow_5: theme.syntax_rainbow[4].into(),
+                rainbow_6: theme.syntax_rainbow[5].into(),
             },
         }
     }
@@ -1197,6 +1260,7 @@ impl Theme {
                 "diff_remove_bg" => Some(self.diff_remove_bg),
                 "diff_modify_bg" => Some(self.diff_modify_bg),
                 "ruler_bg" => Some(self.ruler_bg),
+                "trailing_whitespace_bg" => Some(self.trailing_whitespace_bg),
                 _ => None,
             },
             "ui" => match field {
@@ -1236,6 +1300,12 @@ impl Theme {
                 "variable" => Some(self.syntax_variable),
                 "constant" => Some(self.syntax_constant),
                 "operator" => Some(self.syntax_operator),
+                "rainbow_1" => Some(self.syntax_rainbow[0]),
+                "rainbow_2" => Some(self.syntax_rainbow[1]),
+                "rainbow_3" => Some(self.syntax_rainbow[2]),
+                "rainbow_4" => Some(self.syntax_rainbow[3]),
+                "rainbow_5" => Some(self.syntax_rainbow[4]),
+                "rainbow_6" => Some(self.syntax_rainbow[5]),
                 _ => None,
             },
             "diagnostic" => match field {
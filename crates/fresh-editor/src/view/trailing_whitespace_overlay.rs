@@ -0,0 +1,164 @@
+//! Trailing whitespace highlight using the overlay system
+//!
+//! Spaces and tabs at the end of visible lines get a background color. The
+//! line holding the cursor is left alone, so whitespace doesn't flash up
+//! while it is being typed.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, OverlayNamespace};
+use ratatui::style::Color;
+use std::ops::Range;
+
+/// Namespace for trailing whitespace overlays
+pub fn trailing_whitespace_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("trailing-whitespace".to_string())
+}
+
+/// Byte ranges of trailing whitespace in `text`, which starts at byte
+/// `offset` of the buffer
+///
+/// Lines containing `skip_position` are left out, as is a last line that
+/// isn't complete unless `ends_buffer` says the buffer ends there.
+pub fn trailing_whitespace_ranges(
+    text: &[u8],
+    offset: usize,
+    ends_buffer: bool,
+    skip_position: Option<usize>,
+) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut line_start = 0;
+    while line_start < text.len() {
+        let newline = text[line_start..].iter().position(|&b| b == b'\n');
+        let line_end = match newline {
+            Some(i) => line_start + i,
+            None if ends_buffer => text.len(),
+            None => break,
+        };
+        let mut content_end = line_end;
+        if content_end > line_start && text[content_end - 1] == b'\r' {
+            content_end -= 1;
+        }
+        let mut trailing_start = content_end;
+        while trailing_start > line_start && matches!(text[trailing_start - 1], b' ' | b'\t') {
+            trailing_start -= 1;
+        }
+        let on_line = skip_position
+            .is_some_and(|pos| (offset + line_start..=offset + line_end).contains(&pos));
+        if trailing_start < content_end && !on_line {
+            ranges.push(offset + trailing_start..offset + content_end);
+        }
+        line_start = line_end + 1;
+    }
+    ranges
+}
+
+/// Manager for trailing whitespace overlays
+pub struct TrailingWhitespaceOverlay {
+    /// Whether trailing whitespace is highlighted
+    pub enabled: bool,
+    /// Buffer version, viewport, cursor position and color the current
+    /// overlays were made for
+    last_state: Option<(u64, Range<usize>, usize, Color)>,
+}
+
+impl TrailingWhitespaceOverlay {
+    /// Create a new trailing whitespace overlay manager
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            last_state: None,
+        }
+    }
+
+    /// Update the highlights for the visible part of the buffer
+    ///
+    /// Returns true if overlays were updated
+    pub fn update(
+        &mut self,
+        buffer: &Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        viewport: Range<usize>,
+        cursor_position: usize,
+        color: Color,
+    ) -> bool {
+        if !self.enabled {
+            if self.last_state.is_some() {
+                self.clear(overlays, marker_list);
+                return true;
+            }
+            return false;
+        }
+
+        let state = (buffer.version(), viewport.clone(), cursor_position, color);
+        if self.last_state.as_ref() == Some(&state) {
+            return false;
+        }
+        self.last_state = Some(state);
+
+        let ns = trailing_whitespace_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+
+        let end = viewport.end.min(buffer.len());
+        let start = viewport.start.min(end);
+        let text = buffer.slice_bytes(start..end);
+        let ranges =
+            trailing_whitespace_ranges(&text, start, end == buffer.len(), Some(cursor_position));
+        for range in ranges {
+            let overlay = Overlay::with_namespace(
+                marker_list,
+                range,
+                OverlayFace::Background { color },
+                ns.clone(),
+            )
+            .with_priority_value(1); // Below every other highlight
+            overlays.add(overlay);
+        }
+        true
+    }
+
+    /// Remove all highlights
+    pub fn clear(&mut self, overlays: &mut OverlayManager, marker_list: &mut MarkerList) {
+        let ns = trailing_whitespace_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+        self.last_state = None;
+    }
+}
+
+impl Default for TrailingWhitespaceOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_whitespace_ranges() {
+        let text = b"a  \nb\t\r\n   \nc";
+        assert_eq!(
+            trailing_whitespace_ranges(text, 10, true, None),
+            vec![11..13, 15..16, 18..21]
+        );
+        // A cursor anywhere on the line, including its end, hides it
+        assert_eq!(
+            trailing_whitespace_ranges(text, 10, true, Some(13)),
+            vec![15..16, 18..21]
+        );
+    }
+
+    #[test]
+    fn test_incomplete_last_line() {
+        assert_eq!(
+            trailing_whitespace_ranges(b"a \nb  ", 0, false, None),
+            vec![1..2]
+        );
+        assert_eq!(
+            trailing_whitespace_ranges(b"a \nb  ", 0, true, None),
+            vec![1..2, 4..6]
+        );
+    }
+}
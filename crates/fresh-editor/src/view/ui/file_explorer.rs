@@ -9,6 +9,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState},
     Frame,
 };
+use rust_i18n::t;
 
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -45,6 +46,7 @@ impl FileExplorerRenderer {
         remote_connection: Option<&str>,
     ) {
         let search_active = view.is_search_active();
+        let filtering = view.is_filtering();
 
         // Update viewport height for scrolling calculations
         // Account for borders (top + bottom = 2)
@@ -77,7 +79,7 @@ impl FileExplorerRenderer {
                 let actual_idx = scroll_offset + viewport_idx;
                 let is_selected = selected_index == Some(actual_idx);
                 // Get match positions for highlighting
-                let fuzzy_match = if search_active {
+                let fuzzy_match = if search_active || filtering {
                     view.get_match_for_node(node_id)
                 } else {
                     None
@@ -107,7 +109,14 @@ impl FileExplorerRenderer {
             .unwrap_or_default();
 
         // Show search query in title when search is active
-        let title = if search_active {
+        let filter_suffix = if view.show_only_changed() {
+            format!(" [{}]", t!("explorer.filter_changed"))
+        } else {
+            String::new()
+        };
+        let title = if let Some(query) = view.filter_query() {
+            format!(" {} ", t!("explorer.filter_title", query = query))
+        } else if search_active {
             format!(" /{} ", view.search_query())
         } else if let Some(host) = remote_connection {
             // Extract just the hostname from "user@host" or "user@host:port"
@@ -118,9 +127,9 @@ impl FileExplorerRenderer {
                 .split(':')
                 .next()
                 .unwrap_or(host);
-            format!(" [{}]{} ", hostname, keybinding_suffix)
+            format!(" [{}]{}{} ", hostname, keybinding_suffix, filter_suffix)
         } else {
-            format!(" File Explorer{} ", keybinding_suffix)
+            format!(" File Explorer{}{} ", keybinding_suffix, filter_suffix)
         };
 
         // Title style: inverted colors (dark on light) when focused using theme colors
@@ -217,6 +226,12 @@ impl FileExplorerRenderer {
         fuzzy_match: Option<&FuzzyMatch>,
     ) -> ListItem<'static> {
         let node = view.tree().get_node(node_id).expect("Node should exist");
+        // Filter results are flattened, so show where each one lives
+        let name = if view.is_filtering() && node_id != view.tree().root_id() {
+            view.relative_path(node_id)
+        } else {
+            node.entry.name.clone()
+        };
 
         // Build the line with indentation and tree structure
         let mut spans = Vec::new();
@@ -224,7 +239,7 @@ impl FileExplorerRenderer {
         // Calculate the left side width for padding calculation
         let indent_width = indent * 2;
         let indicator_width = if node.is_dir() { 2 } else { 2 }; // "▼ " or "  "
-        let name_width = str_width(&node.entry.name);
+        let name_width = str_width(&name);
         let left_side_width = indent_width + indicator_width + name_width;
 
         // Indentation
@@ -252,16 +267,28 @@ impl FileExplorerRenderer {
             spans.push(Span::raw("  "));
         }
 
-        // Name styling using theme colors
-        let base_fg = if is_selected && is_focused {
-            theme.editor_fg
-        } else if node
+        let direct_decoration = decorations.direct_for_path(&node.entry.path);
+        let bubbled_decoration = if node.is_dir() {
+            decorations
+                .bubbled_for_path(&node.entry.path)
+                .filter(|_| direct_decoration.is_none())
+        } else {
+            None
+        };
+
+        // Name styling: git status color, then dimmed for hidden/ignored, then theme colors
+        let is_dimmed = node
             .entry
             .metadata
             .as_ref()
             .map(|m| m.is_hidden)
             .unwrap_or(false)
-        {
+            || view.is_path_ignored(&node.entry.path, node.is_dir());
+        let base_fg = if is_selected && is_focused {
+            theme.editor_fg
+        } else if let Some(decoration) = direct_decoration.or(bubbled_decoration) {
+            Self::decoration_color(decoration)
+        } else if is_dimmed {
             theme.line_number_fg
         } else if node.entry.is_symlink() {
             // Symlinks use a distinct color (type color, typically cyan)
@@ -275,17 +302,14 @@ impl FileExplorerRenderer {
         // Render name with match highlighting
         if let Some(fm) = fuzzy_match {
             Self::render_name_with_highlights(
-                &node.entry.name,
+                &name,
                 &fm.match_positions,
                 base_fg,
                 theme,
                 &mut spans,
             );
         } else {
-            spans.push(Span::styled(
-                node.entry.name.clone(),
-                Style::default().fg(base_fg),
-            ));
+            spans.push(Span::styled(name, Style::default().fg(base_fg)));
         }
 
         // Determine the right-side indicator (status symbol)
@@ -296,15 +320,6 @@ impl FileExplorerRenderer {
             files_with_unsaved_changes.contains(&node.entry.path)
         };
 
-        let direct_decoration = decorations.direct_for_path(&node.entry.path);
-        let bubbled_decoration = if node.is_dir() {
-            decorations
-                .bubbled_for_path(&node.entry.path)
-                .filter(|_| direct_decoration.is_none())
-        } else {
-            None
-        };
-
         let right_indicator: Option<(String, Color)> = if has_unsaved {
             Some(("●".to_string(), theme.diagnostic_warning_fg))
         } else if let Some(decoration) = direct_decoration {
@@ -350,7 +365,15 @@ impl FileExplorerRenderer {
             ));
         }
 
-        ListItem::new(Line::from(spans)).style(Style::default().bg(theme.editor_bg))
+        // Marked items (for multi-item operations) keep a selection background
+        let row_style = if view.is_marked(&node.entry.path) {
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().bg(theme.editor_bg)
+        };
+        ListItem::new(Line::from(spans)).style(row_style)
     }
 
     fn decoration_symbol(symbol: &str) -> String {
@@ -9,8 +9,10 @@ use crate::model::cursor::SelectionMode;
 use crate::model::event::{BufferId, EventLog, SplitDirection};
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::ansi_background::AnsiBackground;
-use crate::primitives::display_width::char_width;
+use crate::primitives::display_width::{char_width, pad_ambiguous, push_padded, str_width};
 use crate::state::{EditorState, ViewMode};
+use crate::view::hyperlinks::ScreenLink;
+use crate::view::link_overlay::is_absolute_url;
 use crate::view::split::SplitManager;
 use crate::view::ui::tabs::TabsRenderer;
 use crate::view::ui::view_pipeline::{
@@ -96,6 +98,7 @@ fn push_span_with_map(
             map.push(source);
         }
     }
+    let text = pad_ambiguous(&text).into_owned();
     spans.push(Span::styled(text, style));
 }
 
@@ -186,7 +189,7 @@ impl SpanAccumulator {
             self.first_source = source;
         }
 
-        self.text.push(ch);
+        push_padded(&mut self.text, ch);
 
         // Update map for this character's visual width
         let width = char_width(ch);
@@ -216,6 +219,7 @@ fn push_debug_tag(spans: &mut Vec<Span<'static>>, map: &mut Vec<Option<usize>>,
             map.push(None);
         }
     }
+    let text = pad_ambiguous(&text).into_owned();
     spans.push(Span::styled(text, debug_tag_style()));
 }
 
@@ -356,6 +360,8 @@ struct BufferLayoutOutput {
     gutter_width: usize,
     buffer_ends_with_newline: bool,
     selection: SelectionContext,
+    /// Byte ranges of the visible overlays that link to a URL
+    links: Vec<(Range<usize>, String)>,
 }
 
 struct SplitLayout {
@@ -476,7 +482,7 @@ fn render_left_margin(
             line_spans,
             line_view_map,
             indicator.symbol.clone(),
-            Style::default().fg(indicator.color),
+            Style::default().fg(indicator.display_color(ctx.theme)),
             None,
         );
     } else {
@@ -800,6 +806,7 @@ impl SplitRenderer {
             usize,
             usize,
         )>, // horizontal scrollbar areas (rect + max_content_width + thumb_start + thumb_end)
+        Vec<ScreenLink>,                                             // hyperlinked cells for OSC 8
     ) {
         let _span = tracing::trace_span!("render_content").entered();
 
@@ -826,6 +833,7 @@ impl SplitRenderer {
         let mut maximize_split_areas = Vec::new();
         let mut view_line_mappings: HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>> =
             HashMap::new();
+        let mut hyperlinks = Vec::new();
 
         // Render each split
         for (split_id, buffer_id, split_area) in visible_buffers {
@@ -1033,7 +1041,7 @@ impl SplitRenderer {
                 let view_prefs =
                     Self::resolve_view_preferences(state, split_view_states.as_deref(), split_id);
 
-                let split_view_mappings = Self::render_buffer_in_split(
+                let (split_view_mappings, split_links) = Self::render_buffer_in_split(
                     frame,
                     state,
                     split_cursors,
@@ -1062,6 +1070,7 @@ impl SplitRenderer {
 
                 // Store view line mappings for mouse click handling
                 view_line_mappings.insert(split_id, split_view_mappings);
+                hyperlinks.extend(split_links);
 
                 // For small files, count actual lines for accurate scrollbar
                 // For large files, we'll use a constant thumb size
@@ -1168,6 +1177,7 @@ impl SplitRenderer {
             maximize_split_areas,
             view_line_mappings,
             horizontal_scrollbar_areas,
+            hyperlinks,
         )
     }
 
@@ -1828,7 +1838,7 @@ impl SplitRenderer {
             }
 
             current_style = Some(final_style);
-            current_span_text.push(*ch);
+            push_padded(&mut current_span_text, *ch);
             col += char_width;
             rendered += char_width;
         }
@@ -2410,15 +2420,32 @@ impl SplitRenderer {
         let is_binary = state.buffer.is_binary();
         let line_ending = state.buffer.line_ending();
 
-        // Build base token stream from source
-        let base_tokens = Self::build_base_tokens(
-            &mut state.buffer,
-            viewport.top_byte,
-            estimated_line_length,
-            visible_count,
-            is_binary,
-            line_ending,
-        );
+        // Build base token stream from source, skipping folded ranges
+        let folds: Vec<(std::ops::Range<usize>, Option<String>)> = state
+            .folds
+            .query_range(viewport.top_byte, usize::MAX, &state.marker_list)
+            .into_iter()
+            .map(|(range, placeholder)| (range, placeholder.map(str::to_string)))
+            .collect();
+        let base_tokens = if folds.is_empty() || is_binary {
+            Self::build_base_tokens(
+                &mut state.buffer,
+                viewport.top_byte,
+                estimated_line_length,
+                visible_count,
+                is_binary,
+                line_ending,
+            )
+        } else {
+            Self::build_folded_base_tokens(
+                &mut state.buffer,
+                viewport.top_byte,
+                estimated_line_length,
+                visible_count,
+                line_ending,
+                &folds,
+            )
+        };
 
         // Use plugin transform if available, otherwise use base tokens
         let mut tokens = view_transform.map(|vt| vt.tokens).unwrap_or(base_tokens);
@@ -2982,6 +3009,94 @@ impl SplitRenderer {
         tokens
     }
 
+    /// Build base tokens with each fold replaced by its placeholder
+    ///
+    /// Building resumes at the end of every fold, so the folded lines don't
+    /// count against `visible_count`.
+    fn build_folded_base_tokens(
+        buffer: &mut Buffer,
+        top_byte: usize,
+        estimated_line_length: usize,
+        visible_count: usize,
+        line_ending: crate::model::buffer::LineEnding,
+        folds: &[(std::ops::Range<usize>, Option<String>)],
+    ) -> Vec<fresh_core::api::ViewTokenWire> {
+        use fresh_core::api::{ViewTokenWire, ViewTokenWireKind};
+
+        let buffer_len = buffer.len();
+        let mut tokens = Vec::new();
+        let mut start = top_byte;
+        let mut lines = 0usize;
+        let mut folds = folds.iter().peekable();
+
+        loop {
+            // Folds already passed are skipped; one containing `start` moves it past
+            while let Some((range, _)) = folds.peek() {
+                if range.end <= start {
+                    folds.next();
+                } else if range.start < start {
+                    start = range.end;
+                    folds.next();
+                } else {
+                    break;
+                }
+            }
+            let next_fold = folds.next();
+            let fold_start = next_fold.map_or(usize::MAX, |(range, _)| range.start);
+
+            let segment = Self::build_base_tokens(
+                buffer,
+                start,
+                estimated_line_length,
+                visible_count.saturating_sub(lines),
+                false,
+                line_ending,
+            );
+            let mut reached_fold = false;
+            for token in segment {
+                match token.source_offset {
+                    // The line iterator starts at the beginning of the line
+                    Some(offset) if offset < start => continue,
+                    Some(offset) if offset >= fold_start => {
+                        reached_fold = true;
+                        break;
+                    }
+                    _ => {}
+                }
+                if matches!(token.kind, ViewTokenWireKind::Newline) {
+                    lines += 1;
+                }
+                tokens.push(token);
+            }
+
+            let Some((range, placeholder)) = next_fold else {
+                break;
+            };
+            // A segment stopping short of the fold already filled the viewport
+            if !reached_fold {
+                break;
+            }
+            tokens.push(ViewTokenWire {
+                source_offset: Some(range.start),
+                kind: ViewTokenWireKind::Text(placeholder.clone().unwrap_or_default()),
+                style: None,
+            });
+            if lines >= visible_count || range.end >= buffer_len {
+                break;
+            }
+            start = range.end;
+        }
+
+        if tokens.is_empty() {
+            tokens.push(ViewTokenWire {
+                source_offset: Some(top_byte),
+                kind: ViewTokenWireKind::Text(String::new()),
+                style: None,
+            });
+        }
+        tokens
+    }
+
     /// Build tokens for binary files by reading raw bytes directly
     /// This preserves byte values >= 0x80 that would be lost by String::from_utf8_lossy
     fn build_base_tokens_binary(
@@ -3576,6 +3691,24 @@ impl SplitRenderer {
             primary_cursor_position,
         );
 
+        // Update trailing whitespace overlays
+        state.trailing_whitespace_overlay.update(
+            &state.buffer,
+            &mut state.overlays,
+            &mut state.marker_list,
+            viewport_start..viewport_end,
+            primary_cursor_position,
+            theme.trailing_whitespace_bg,
+        );
+
+        // Update URL link overlays
+        state.link_overlay.update(
+            &state.buffer,
+            &mut state.overlays,
+            &mut state.marker_list,
+            viewport_start..viewport_end,
+        );
+
         // Semantic tokens are stored as overlays so their ranges track edits.
         // Convert them into highlight spans for the render pipeline.
         let is_compose = matches!(view_mode, ViewMode::Compose);
@@ -3779,6 +3912,14 @@ impl SplitRenderer {
             if show_line_number && prev_was_source_line {
                 current_source_line_num += 1;
             }
+            // Lines hidden by a fold make the numbering jump
+            if show_line_number && !state.folds.is_empty() {
+                if let Some(&first_byte) =
+                    current_view_line.char_source_bytes.iter().flatten().next()
+                {
+                    current_source_line_num = state.buffer.get_line_number(first_byte);
+                }
+            }
             // Only update the flag when we see a source line - virtual lines
             // between source lines shouldn't reset the tracking
             if show_line_number {
@@ -3865,6 +4006,9 @@ impl SplitRenderer {
             let mut first_line_byte_pos: Option<usize> = None;
             let mut last_line_byte_pos: Option<usize> = None;
 
+            // End-of-line virtual text is emitted after the line content
+            let mut end_of_line_vtexts: Vec<&crate::view::virtual_text::VirtualText> = Vec::new();
+
             let chars_iterator = line_content.chars().peekable();
             for ch in chars_iterator {
                 // Get source byte for this character using character index
@@ -3919,6 +4063,14 @@ impl SplitRenderer {
                     break;
                 }
 
+                if let Some(vtexts) = byte_pos.and_then(|bp| virtual_text_lookup.get(&bp)) {
+                    end_of_line_vtexts.extend(
+                        vtexts
+                            .iter()
+                            .filter(|v| v.position == VirtualTextPosition::EndOfLine),
+                    );
+                }
+
                 // Skip characters before left_column
                 if col_offset >= left_col {
                     // Check if this view position is the START of a tab expansion
@@ -4235,6 +4387,18 @@ impl SplitRenderer {
                 }
             }
 
+            for vtext in end_of_line_vtexts {
+                let text_with_space = format!("  {}", vtext.text);
+                visible_char_count += str_width(&text_with_space);
+                push_span_with_map(
+                    &mut line_spans,
+                    &mut line_view_map,
+                    text_with_space,
+                    vtext.style,
+                    None,
+                );
+            }
+
             // ViewLines are already wrapped (Break tokens became newlines in ViewLineIterator)
             // so each line is one visual line - no need to wrap again
             let current_y = lines.len() as u16;
@@ -4280,66 +4444,62 @@ impl SplitRenderer {
             }
 
             // Fill remaining width for overlays with extend_to_line_end
-            // Only when line wrapping is disabled (side-by-side diff typically disables wrapping)
-            if !line_wrap {
-                // Calculate the content area width (total width minus gutter)
-                let content_width = render_area.width.saturating_sub(gutter_width as u16) as usize;
-                let remaining_cols = content_width.saturating_sub(visible_char_count);
-
-                if remaining_cols > 0 {
-                    // Find the highest priority background color from overlays with extend_to_line_end
-                    // that overlap with this line's byte range
-                    let fill_style: Option<Style> = if let (Some(start), Some(end)) =
-                        (first_line_byte_pos, last_line_byte_pos)
-                    {
-                        viewport_overlays
-                            .iter()
-                            .filter(|(overlay, range)| {
-                                overlay.extend_to_line_end
-                                    && range.start <= end
-                                    && range.end >= start
-                            })
-                            .max_by_key(|(o, _)| o.priority)
-                            .and_then(|(overlay, _)| {
-                                match &overlay.face {
-                                    crate::view::overlay::OverlayFace::Background { color } => {
-                                        // Set both fg and bg to ensure ANSI codes are output
-                                        Some(Style::default().fg(*color).bg(*color))
-                                    }
-                                    crate::view::overlay::OverlayFace::Style { style } => {
-                                        // Extract background from style if present
-                                        // Set fg to same as bg for invisible text
-                                        style.bg.map(|bg| Style::default().fg(bg).bg(bg))
-                                    }
-                                    crate::view::overlay::OverlayFace::ThemedStyle {
-                                        fallback_style,
-                                        bg_theme,
-                                        ..
-                                    } => {
-                                        // Try theme key first, fall back to style's bg
-                                        let bg = bg_theme
-                                            .as_ref()
-                                            .and_then(|key| theme.resolve_theme_key(key))
-                                            .or(fallback_style.bg);
-                                        bg.map(|bg| Style::default().fg(bg).bg(bg))
-                                    }
-                                    _ => None,
+            // Wrapped lines are already split into visual lines, so each segment fills its own row
+            // Calculate the content area width (total width minus gutter)
+            let content_width = render_area.width.saturating_sub(gutter_width as u16) as usize;
+            let remaining_cols = content_width.saturating_sub(visible_char_count);
+
+            if remaining_cols > 0 {
+                // Find the highest priority background color from overlays with extend_to_line_end
+                // that overlap with this line's byte range
+                let fill_style: Option<Style> = if let (Some(start), Some(end)) =
+                    (first_line_byte_pos, last_line_byte_pos)
+                {
+                    viewport_overlays
+                        .iter()
+                        .filter(|(overlay, range)| {
+                            overlay.extend_to_line_end && range.start <= end && range.end >= start
+                        })
+                        .max_by_key(|(o, _)| o.priority)
+                        .and_then(|(overlay, _)| {
+                            match &overlay.face {
+                                crate::view::overlay::OverlayFace::Background { color } => {
+                                    // Set both fg and bg to ensure ANSI codes are output
+                                    Some(Style::default().fg(*color).bg(*color))
                                 }
-                            })
-                    } else {
-                        None
-                    };
+                                crate::view::overlay::OverlayFace::Style { style } => {
+                                    // Extract background from style if present
+                                    // Set fg to same as bg for invisible text
+                                    style.bg.map(|bg| Style::default().fg(bg).bg(bg))
+                                }
+                                crate::view::overlay::OverlayFace::ThemedStyle {
+                                    fallback_style,
+                                    bg_theme,
+                                    ..
+                                } => {
+                                    // Try theme key first, fall back to style's bg
+                                    let bg = bg_theme
+                                        .as_ref()
+                                        .and_then(|key| theme.resolve_theme_key(key))
+                                        .or(fallback_style.bg);
+                                    bg.map(|bg| Style::default().fg(bg).bg(bg))
+                                }
+                                _ => None,
+                            }
+                        })
+                } else {
+                    None
+                };
 
-                    if let Some(fill_bg) = fill_style {
-                        let fill_text = " ".repeat(remaining_cols);
-                        push_span_with_map(
-                            &mut line_spans,
-                            &mut line_view_map,
-                            fill_text,
-                            fill_bg,
-                            None,
-                        );
-                    }
+                if let Some(fill_bg) = fill_style {
+                    let fill_text = " ".repeat(remaining_cols);
+                    push_span_with_map(
+                        &mut line_spans,
+                        &mut line_view_map,
+                        fill_text,
+                        fill_bg,
+                        None,
+                    );
                 }
             }
 
@@ -4724,12 +4884,23 @@ impl SplitRenderer {
             .populate_line_cache(viewport.top_byte, visible_count);
 
         let viewport_start = viewport.top_byte;
-        let viewport_end = Self::calculate_viewport_end(
+        let mut viewport_end = Self::calculate_viewport_end(
             state,
             viewport_start,
             estimated_line_length,
             visible_count,
         );
+        // Lines after a fold lie further down the buffer than the line count suggests
+        if !state.folds.is_empty() {
+            let last_shown = view_data
+                .lines
+                .iter()
+                .flat_map(|line| line.char_source_bytes.iter().flatten())
+                .max();
+            if let Some(&last_shown) = last_shown {
+                viewport_end = viewport_end.max(last_shown + 1);
+            }
+        }
 
         let decorations = Self::decoration_context(
             state,
@@ -4791,6 +4962,12 @@ impl SplitRenderer {
 
         let view_line_mappings = render_output.view_line_mappings.clone();
 
+        let links = decorations
+            .viewport_overlays
+            .iter()
+            .filter_map(|(overlay, range)| Some((range.clone(), overlay.url.clone()?)))
+            .collect();
+
         let buffer_ends_with_newline = if !state.buffer.is_empty() {
             let last_char = state.get_text_range(state.buffer.len() - 1, state.buffer.len());
             last_char == "\n"
@@ -4809,6 +4986,7 @@ impl SplitRenderer {
             gutter_width,
             buffer_ends_with_newline,
             selection,
+            links,
         }
     }
 
@@ -4828,7 +5006,7 @@ impl SplitRenderer {
         hide_cursor: bool,
         rulers: &[usize],
         compose_column_guides: Option<Vec<u16>>,
-    ) {
+    ) -> Vec<ScreenLink> {
         let render_area = layout_output.render_area;
         let effective_editor_bg = layout_output.effective_editor_bg;
         let gutter_width = layout_output.gutter_width;
@@ -4865,6 +5043,14 @@ impl SplitRenderer {
             .style(Style::default().bg(effective_editor_bg));
         frame.render_widget(Paragraph::new(lines).block(editor_block), render_area);
 
+        let links = Self::hyperlink_cells(
+            frame.buffer_mut(),
+            &layout_output.links,
+            &layout_output.view_line_mappings,
+            render_area,
+            gutter_width,
+        );
+
         let cursor = Self::resolve_cursor_fallback(
             layout_output.render_output.cursor,
             layout_output.selection.primary_cursor_position,
@@ -4925,11 +5111,14 @@ impl SplitRenderer {
                 event_log.log_render_state(cursor_pos, screen_x, screen_y, buffer_len);
             }
         }
+
+        links
     }
 
     /// Render a single buffer in a split pane (convenience wrapper).
     /// Calls compute_buffer_layout then draw_buffer_in_split.
-    /// Returns the view line mappings for mouse click handling.
+    /// Returns the view line mappings for mouse click handling and the
+    /// hyperlinked cells.
     #[allow(clippy::too_many_arguments)]
     fn render_buffer_in_split(
         frame: &mut Frame,
@@ -4956,7 +5145,7 @@ impl SplitRenderer {
         session_mode: bool,
         rulers: &[usize],
         show_line_numbers: bool,
-    ) -> Vec<ViewLineMapping> {
+    ) -> (Vec<ViewLineMapping>, Vec<ScreenLink>) {
         let layout_output = Self::compute_buffer_layout(
             state,
             cursors,
@@ -4978,7 +5167,7 @@ impl SplitRenderer {
 
         let view_line_mappings = layout_output.view_line_mappings.clone();
 
-        Self::draw_buffer_in_split(
+        let links = Self::draw_buffer_in_split(
             frame,
             state,
             cursors,
@@ -4994,7 +5183,7 @@ impl SplitRenderer {
             compose_column_guides,
         );
 
-        view_line_mappings
+        (view_line_mappings, links)
     }
 
     /// Render vertical column guide lines in the editor content area.
@@ -5057,123 +5246,53 @@ impl SplitRenderer {
         }
     }
 
-    /// Post-process the rendered frame to apply OSC 8 hyperlink escape sequences
-    /// for any overlays that have a URL set.
+    /// Screen cells of the overlays that link to a URL
     ///
     /// Uses view_line_mappings to translate overlay byte ranges into screen
-    /// positions, then wraps the corresponding cells with OSC 8 sequences so
-    /// they become clickable in terminals that support the protocol.
-    fn apply_hyperlink_overlays(
-        frame: &mut Frame,
-        viewport_overlays: &[(crate::view::overlay::Overlay, Range<usize>)],
+    /// positions. The terminal backend turns the cells into OSC 8 hyperlinks
+    /// when it writes them, so they become clickable in terminals that
+    /// support the protocol.
+    fn hyperlink_cells(
+        buf: &ratatui::buffer::Buffer,
+        links: &[(Range<usize>, String)],
         view_line_mappings: &[ViewLineMapping],
         render_area: Rect,
         gutter_width: usize,
-        cursor_screen_pos: Option<(u16, u16)>,
-    ) {
-        let hyperlink_overlays: Vec<_> = viewport_overlays
+    ) -> Vec<ScreenLink> {
+        let links: Vec<_> = links
             .iter()
-            .filter(|(overlay, _)| overlay.url.is_some())
+            .filter(|(_, url)| is_absolute_url(url))
             .collect();
-
-        if hyperlink_overlays.is_empty() {
-            return;
+        if links.is_empty() {
+            return Vec::new();
         }
 
-        let buf = frame.buffer_mut();
+        let content_x = render_area.x + gutter_width as u16;
+        let max_x = render_area.x + render_area.width;
+        let mut cells = Vec::new();
         for (screen_row, mapping) in view_line_mappings.iter().enumerate() {
             let y = render_area.y + screen_row as u16;
             if y >= render_area.y + render_area.height {
                 break;
             }
-            for (overlay, range) in &hyperlink_overlays {
-                let url = overlay.url.as_ref().unwrap();
+            for (range, url) in &links {
                 // Find screen columns in this row whose source byte falls in range
                 let mut run_start: Option<u16> = None;
-                let content_x_offset = render_area.x + gutter_width as u16;
-                for (char_idx, maybe_byte) in mapping.char_source_bytes.iter().enumerate() {
-                    let in_range = maybe_byte
-                        .map(|b| b >= range.start && b < range.end)
-                        .unwrap_or(false);
-                    let screen_x = content_x_offset + char_idx as u16;
-                    if in_range && screen_x < render_area.x + render_area.width {
-                        if run_start.is_none() {
-                            run_start = Some(screen_x);
-                        }
+                for visual_col in 0..=mapping.visual_to_char.len() {
+                    let x = content_x.saturating_add(visual_col as u16);
+                    let in_range = x < max_x
+                        && mapping
+                            .source_byte_at_visual_col(visual_col)
+                            .is_some_and(|byte| range.contains(&byte));
+                    if in_range {
+                        run_start.get_or_insert(x);
                     } else if let Some(start_x) = run_start.take() {
-                        Self::apply_osc8_to_cells(
-                            buf,
-                            start_x,
-                            screen_x,
-                            y,
-                            url,
-                            cursor_screen_pos,
-                        );
+                        cells.push(ScreenLink::new(buf, y, start_x..x, url.clone()));
                     }
                 }
-                // Flush trailing run
-                if let Some(start_x) = run_start {
-                    let end_x = content_x_offset + mapping.char_source_bytes.len() as u16;
-                    let end_x = end_x.min(render_area.x + render_area.width);
-                    Self::apply_osc8_to_cells(buf, start_x, end_x, y, url, cursor_screen_pos);
-                }
-            }
-        }
-    }
-
-    /// Apply OSC 8 hyperlink escape sequences to a run of buffer cells.
-    ///
-    /// Uses 2-character chunking to work around Crossterm width accounting
-    /// issues with OSC sequences (same approach as popup hyperlinks).
-    /// When the cursor falls on the second character of a 2-char chunk, the
-    /// chunk is split into two 1-char chunks so the terminal cursor remains
-    /// visible on the correct cell.
-    fn apply_osc8_to_cells(
-        buf: &mut ratatui::buffer::Buffer,
-        start_x: u16,
-        end_x: u16,
-        y: u16,
-        url: &str,
-        cursor_pos: Option<(u16, u16)>,
-    ) {
-        let area = *buf.area();
-        if y < area.y || y >= area.y + area.height {
-            return;
-        }
-        let max_x = area.x + area.width;
-        // If the cursor is on this row, note its x so we can avoid
-        // swallowing it into the second half of a 2-char chunk.
-        let cursor_x = cursor_pos.and_then(|(cx, cy)| if cy == y { Some(cx) } else { None });
-        let mut x = start_x;
-        while x < end_x {
-            if x >= max_x {
-                break;
-            }
-            // Determine chunk size: normally 2, but use 1 if the cursor
-            // sits on the second cell of the pair so it stays addressable.
-            let chunk_size = if cursor_x == Some(x + 1) { 1 } else { 2 };
-
-            let mut chunk = String::new();
-            let chunk_start = x;
-            for _ in 0..chunk_size {
-                if x >= end_x || x >= max_x {
-                    break;
-                }
-                let sym = buf[(x, y)].symbol().to_string();
-                chunk.push_str(&sym);
-                x += 1;
-            }
-            if !chunk.is_empty() {
-                let actual_chunk_len = x - chunk_start;
-                let hyperlink = format!("\x1B]8;;{}\x07{}\x1B]8;;\x07", url, chunk);
-                buf[(chunk_start, y)].set_symbol(&hyperlink);
-                // Clear trailing cells that were folded into this chunk so
-                // ratatui's diff picks up the change when chunking shifts.
-                for cx in (chunk_start + 1)..chunk_start + actual_chunk_len {
-                    buf[(cx, y)].set_symbol("");
-                }
             }
         }
+        cells
     }
 
     /// Apply styles from original line_spans to a wrapped segment
@@ -6546,204 +6665,32 @@ mod tests {
         }
     }
 
-    /// Helper: strip OSC 8 escape sequences from a string, returning plain text.
-    fn strip_osc8(s: &str) -> String {
-        let mut result = String::with_capacity(s.len());
-        let bytes = s.as_bytes();
-        let mut i = 0;
-        while i < bytes.len() {
-            if i + 3 < bytes.len()
-                && bytes[i] == 0x1b
-                && bytes[i + 1] == b']'
-                && bytes[i + 2] == b'8'
-                && bytes[i + 3] == b';'
-            {
-                i += 4;
-                while i < bytes.len() && bytes[i] != 0x07 {
-                    i += 1;
-                }
-                if i < bytes.len() {
-                    i += 1;
-                }
-            } else {
-                result.push(bytes[i] as char);
-                i += 1;
-            }
-        }
-        result
-    }
-
-    /// Read a row from a ratatui buffer, skipping the second cell of 2-char
-    /// OSC 8 chunks so we get clean text.
-    fn read_row(buf: &ratatui::buffer::Buffer, y: u16) -> String {
-        let width = buf.area().width;
-        let mut s = String::new();
-        let mut col = 0u16;
-        while col < width {
-            let cell = &buf[(col, y)];
-            let stripped = strip_osc8(cell.symbol());
-            let chars = stripped.chars().count();
-            if chars > 1 {
-                s.push_str(&stripped);
-                col += chars as u16;
-            } else {
-                s.push_str(&stripped);
-                col += 1;
-            }
-        }
-        s.trim_end().to_string()
-    }
-
     #[test]
-    fn test_apply_osc8_to_cells_preserves_adjacent_cells() {
+    fn test_hyperlink_cells_follow_the_mapping() {
         use ratatui::buffer::Buffer;
-        use ratatui::layout::Rect;
 
-        // Simulate: "[Quick Install](#installation)" in a 40-wide buffer row 0
-        let text = "[Quick Install](#installation)";
-        let area = Rect::new(0, 0, 40, 1);
+        // "see https://x.org now" starting at byte 100, after a 2-column gutter
+        let text = "see https://x.org now";
+        let area = Rect::new(0, 0, 30, 2);
         let mut buf = Buffer::empty(area);
-        for (i, ch) in text.chars().enumerate() {
-            if (i as u16) < 40 {
-                buf[(i as u16, 0)].set_symbol(&ch.to_string());
-            }
-        }
-
-        // Overlay covers "Quick Install" = cols 1..14 (bytes 9..22 mapped to screen)
-        let url = "https://example.com";
-
-        // Apply with cursor at col 0 (not inside the overlay range)
-        SplitRenderer::apply_osc8_to_cells(&mut buf, 1, 14, 0, url, Some((0, 0)));
-
-        let row = read_row(&buf, 0);
-        assert_eq!(
-            row, text,
-            "After OSC 8 application, reading the row should reproduce the original text"
-        );
-
-        // Cell 14 = ']' must not be touched
-        let cell14 = strip_osc8(buf[(14, 0)].symbol());
-        assert_eq!(cell14, "]", "Cell 14 (']') must not be modified by OSC 8");
-
-        // Cell 0 = '[' must not be touched
-        let cell0 = strip_osc8(buf[(0, 0)].symbol());
-        assert_eq!(cell0, "[", "Cell 0 ('[') must not be modified by OSC 8");
-    }
-
-    #[test]
-    fn test_apply_osc8_stable_across_reapply() {
-        use ratatui::buffer::Buffer;
-        use ratatui::layout::Rect;
-
-        let text = "[Quick Install](#installation)";
-        let area = Rect::new(0, 0, 40, 1);
-
-        // First render: apply OSC 8 with cursor at col 0
-        let mut buf1 = Buffer::empty(area);
-        for (i, ch) in text.chars().enumerate() {
-            if (i as u16) < 40 {
-                buf1[(i as u16, 0)].set_symbol(&ch.to_string());
-            }
-        }
-        SplitRenderer::apply_osc8_to_cells(
-            &mut buf1,
-            1,
-            14,
-            0,
-            "https://example.com",
-            Some((0, 0)),
-        );
-        let row1 = read_row(&buf1, 0);
-
-        // Second render: fresh buffer, same text, apply OSC 8 with cursor at col 5
-        let mut buf2 = Buffer::empty(area);
-        for (i, ch) in text.chars().enumerate() {
-            if (i as u16) < 40 {
-                buf2[(i as u16, 0)].set_symbol(&ch.to_string());
-            }
-        }
-        SplitRenderer::apply_osc8_to_cells(
-            &mut buf2,
-            1,
-            14,
-            0,
-            "https://example.com",
-            Some((5, 0)),
-        );
-        let row2 = read_row(&buf2, 0);
-
-        assert_eq!(row1, text);
-        assert_eq!(row2, text);
-    }
-
-    #[test]
-    #[ignore = "OSC 8 hyperlinks disabled pending ratatui diff fix"]
-    fn test_apply_osc8_diff_between_renders() {
-        use ratatui::buffer::Buffer;
-        use ratatui::layout::Rect;
-
-        // Simulate ratatui's diff-based update: a "concealed" render followed
-        // by an "unconcealed" render. The backend buffer accumulates diffs.
-        let area = Rect::new(0, 0, 40, 1);
-
-        // --- Render 1: concealed text "Quick Install" at cols 0..12, rest is space ---
-        let concealed = "Quick Install";
-        let mut frame1 = Buffer::empty(area);
-        for (i, ch) in concealed.chars().enumerate() {
-            frame1[(i as u16, 0)].set_symbol(&ch.to_string());
-        }
-        // OSC 8 covers cols 0..13 (concealed mapping)
-        SplitRenderer::apply_osc8_to_cells(
-            &mut frame1,
-            0,
-            13,
-            0,
-            "https://example.com",
-            Some((0, 5)),
-        );
-
-        // Simulate backend: starts empty, apply diff from frame1
-        let mut prev = Buffer::empty(area);
-        let mut backend = Buffer::empty(area);
-        let diff1 = prev.diff(&frame1);
-        for (x, y, cell) in &diff1 {
-            backend[(*x, *y)] = (*cell).clone();
-        }
-
-        // --- Render 2: unconcealed "[Quick Install](#installation)" ---
-        let full = "[Quick Install](#installation)";
-        let mut frame2 = Buffer::empty(area);
-        for (i, ch) in full.chars().enumerate() {
-            if (i as u16) < 40 {
-                frame2[(i as u16, 0)].set_symbol(&ch.to_string());
-            }
-        }
-        // OSC 8 covers cols 1..14 (unconcealed mapping)
-        SplitRenderer::apply_osc8_to_cells(
-            &mut frame2,
-            1,
-            14,
-            0,
-            "https://example.com",
-            Some((0, 0)),
-        );
-
-        // Apply diff from frame1→frame2 to backend
-        let diff2 = frame1.diff(&frame2);
-        for (x, y, cell) in &diff2 {
-            backend[(*x, *y)] = (*cell).clone();
-        }
+        buf.set_string(2, 0, text, Style::default());
+        let mapping = ViewLineMapping {
+            char_source_bytes: (0..text.len()).map(|i| Some(100 + i)).collect(),
+            visual_to_char: (0..text.len()).collect(),
+            line_end_byte: 100 + text.len(),
+        };
+        let links = vec![
+            (104..117, "https://x.org".to_string()),
+            (100..103, "#anchor".to_string()),
+        ];
 
-        // Backend should now show the full text when read
-        let row = read_row(&backend, 0);
-        assert_eq!(
-            row, full,
-            "After diff-based update from concealed to unconcealed, \
-             backend should show full text"
-        );
+        let cells = SplitRenderer::hyperlink_cells(&buf, &links, &[mapping], area, 2);
+        assert_eq!(cells.len(), 1, "relative links aren't terminal hyperlinks");
+        assert_eq!((cells[0].y, cells[0].x.clone()), (0, 6..19));
+        assert_eq!(cells[0].url, "https://x.org");
+        assert!(cells[0].is_shown(&buf));
 
-        // Specifically, cell 14 must be ']'
-        let cell14 = strip_osc8(backend[(14, 0)].symbol());
-        assert_eq!(cell14, "]", "Cell 14 must be ']' after unconcealed render");
+        buf.set_string(8, 0, "popup", Style::default());
+        assert!(!cells[0].is_shown(&buf));
     }
 }
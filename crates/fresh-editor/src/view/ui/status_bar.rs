@@ -4,10 +4,12 @@ use std::path::Path;
 
 use crate::app::WarningLevel;
 use crate::primitives::display_width::{char_width, str_width};
+use crate::services::git::GitStatus;
 use crate::state::EditorState;
 use crate::view::prompt::Prompt;
+use fresh_core::api::{OverlayColorSpec, StatusSegmentOptions};
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
@@ -28,6 +30,8 @@ pub struct StatusBarLayout {
     pub language_indicator: Option<(u16, u16, u16)>,
     /// Status message area (row, start_col, end_col) - clickable to show full history
     pub message_area: Option<(u16, u16, u16)>,
+    /// Plugin segment areas (row, start_col, end_col), in segment order
+    pub plugin_segments: Vec<(u16, u16, u16)>,
 }
 
 /// Status bar hover state for styling clickable indicators
@@ -47,6 +51,8 @@ pub enum StatusBarHover {
     LanguageIndicator,
     /// Mouse is over the status message area
     MessageArea,
+    /// Mouse is over a plugin segment (index in segment order)
+    PluginSegment(usize),
 }
 
 /// Which search option checkbox is being hovered
@@ -242,6 +248,19 @@ pub fn truncate_path(path: &Path, max_len: usize) -> TruncatedPath {
     }
 }
 
+/// Resolve a plugin segment's color, or `default` if unset or not a theme key
+fn resolve_segment_color(
+    spec: Option<&OverlayColorSpec>,
+    theme: &crate::view::theme::Theme,
+    default: Color,
+) -> Color {
+    match spec {
+        Some(OverlayColorSpec::Rgb(r, g, b)) => Color::Rgb(*r, *g, *b),
+        Some(OverlayColorSpec::ThemeKey(key)) => theme.resolve_theme_key(key).unwrap_or(default),
+        None => default,
+    }
+}
+
 /// Renders the status bar and prompt/minibuffer
 pub struct StatusBarRenderer;
 
@@ -262,6 +281,9 @@ impl StatusBarRenderer {
     /// * `general_warning_count` - Number of general warnings (for badge display)
     /// * `remote_connection` - Optional remote connection info (e.g., "user@host")
     /// * `session_name` - Optional session name (for session persistence mode)
+    /// * `git_status` - Optional git branch/dirty state of the working directory
+    /// * `plugin_segments` - Segments added by plugins, in display order
+    /// * `selection_stats` - Counts for the selection, when there is one
     ///
     /// # Returns
     /// Layout information with positions of clickable indicators
@@ -284,6 +306,9 @@ impl StatusBarRenderer {
         hover: StatusBarHover,
         remote_connection: Option<&str>,
         session_name: Option<&str>,
+        git_status: Option<&GitStatus>,
+        plugin_segments: &[StatusSegmentOptions],
+        selection_stats: Option<&str>,
     ) -> StatusBarLayout {
         Self::render_status(
             frame,
@@ -303,6 +328,9 @@ impl StatusBarRenderer {
             hover,
             remote_connection,
             session_name,
+            git_status,
+            plugin_segments,
+            selection_stats,
         )
     }
 
@@ -488,16 +516,20 @@ impl StatusBarRenderer {
         hover: StatusBarHover,
         remote_connection: Option<&str>,
         session_name: Option<&str>,
+        git_status: Option<&GitStatus>,
+        plugin_segments: &[StatusSegmentOptions],
+        selection_stats: Option<&str>,
     ) -> StatusBarLayout {
         // Initialize layout tracking
         let mut layout = StatusBarLayout::default();
         // Use the pre-computed display name from buffer metadata
         let filename = display_name;
 
-        let modified = if state.buffer.is_modified() {
-            " [+]"
-        } else {
-            ""
+        let modified = match (state.buffer.is_modified(), state.read_only) {
+            (true, true) => " [+] [RO]",
+            (true, false) => " [+]",
+            (false, true) => " [RO]",
+            (false, false) => "",
         };
 
         // Format chord state if present
@@ -575,6 +607,12 @@ impl StatusBarRenderer {
             String::new()
         };
 
+        // Selection statistics (only while there is a selection)
+        let selection_stats_indicator = selection_stats
+            .filter(|_| state.show_cursors)
+            .map(|stats| format!(" | {stats}"))
+            .unwrap_or_default();
+
         // Build status message parts
         let mut message_parts: Vec<&str> = Vec::new();
         if let Some(msg) = status_message {
@@ -616,15 +654,40 @@ impl StatusBarRenderer {
         };
 
         // Track where the message starts for click detection
-        let base_and_chord_width = str_width(&base_status) + str_width(&chord_display);
+        let mut base_and_chord_width = str_width(&base_status)
+            + str_width(&selection_stats_indicator)
+            + str_width(&chord_display);
         let message_width = str_width(&message_suffix);
 
-        let left_status = format!("{base_status}{chord_display}{message_suffix}");
+        let mut left_status =
+            format!("{base_status}{selection_stats_indicator}{chord_display}{message_suffix}");
 
         // Build right-side indicators (these stay fixed on the right)
-        // Order: [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Order: [Plugin segments] [Git branch] [Indentation] [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
         // Note: Remote indicator is now on the left side, before the filename
 
+        // Plugin segments (clickable when they have a command)
+        let segment_texts: Vec<String> = plugin_segments
+            .iter()
+            .map(|segment| format!(" {} ", segment.text))
+            .collect();
+        let segments_width: usize = segment_texts.iter().map(|text| str_width(text)).sum();
+
+        // Git branch indicator (branch name, '*' when dirty, ahead/behind counts)
+        let git_text = git_status
+            .map(|status| format!(" {} ", status.summary()))
+            .unwrap_or_default();
+        let git_width = str_width(&git_text);
+
+        // Indentation indicator (tabs or spaces, and the indent width)
+        let indent_size = state.buffer_settings.tab_size;
+        let mut indent_text = if state.buffer_settings.use_tabs {
+            format!(" {} ", t!("status.indent_tabs", size = indent_size))
+        } else {
+            format!(" {} ", t!("status.indent_spaces", size = indent_size))
+        };
+        let indent_width = str_width(&indent_text);
+
         // Line ending indicator (clickable to change format)
         let line_ending_text = format!(" {} ", state.buffer.line_ending().display_name());
         let line_ending_width = str_width(&line_ending_text);
@@ -671,16 +734,31 @@ impl StatusBarRenderer {
         let padded_cmd_palette = format!(" {} ", cmd_palette_indicator);
 
         // Calculate available width and right side width
-        // Right side: [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Right side: [Plugin segments] [Git branch] [Indentation] [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
         let available_width = area.width as usize;
         let cmd_palette_width = str_width(&padded_cmd_palette);
-        let right_side_width = line_ending_width
+        let mut right_side_width = segments_width
+            + git_width
+            + line_ending_width
             + encoding_width
             + language_width
             + lsp_indicator_width
             + warning_badge_width
             + update_width
             + cmd_palette_width;
+        // The selection statistics give way to messages when space is tight
+        if !selection_stats_indicator.is_empty()
+            && str_width(&left_status) + right_side_width >= available_width
+        {
+            base_and_chord_width -= str_width(&selection_stats_indicator);
+            left_status = format!("{base_status}{chord_display}{message_suffix}");
+        }
+        // The indentation indicator gives way to the left status when space is tight
+        if str_width(&left_status) + right_side_width + indent_width < available_width {
+            right_side_width += indent_width;
+        } else {
+            indent_text.clear();
+        }
 
         // Only show command palette indicator if there's enough space (at least 15 chars for minimal display)
         let spans = if available_width >= 15 {
@@ -765,6 +843,58 @@ impl StatusBarRenderer {
                 current_col = area.x + (available_width - right_side_width) as u16;
             }
 
+            // Add plugin segments
+            for (index, (segment, text)) in plugin_segments.iter().zip(&segment_texts).enumerate() {
+                let width = str_width(text) as u16;
+                layout
+                    .plugin_segments
+                    .push((area.y, current_col, current_col + width));
+                let is_hovering =
+                    hover == StatusBarHover::PluginSegment(index) && segment.command.is_some();
+                let style = if is_hovering {
+                    Style::default()
+                        .fg(theme.menu_hover_fg)
+                        .bg(theme.menu_hover_bg)
+                        .add_modifier(Modifier::UNDERLINED)
+                } else {
+                    Style::default()
+                        .fg(resolve_segment_color(
+                            segment.fg.as_ref(),
+                            theme,
+                            theme.status_bar_fg,
+                        ))
+                        .bg(resolve_segment_color(
+                            segment.bg.as_ref(),
+                            theme,
+                            theme.status_bar_bg,
+                        ))
+                };
+                spans.push(Span::styled(text.clone(), style));
+                current_col += width;
+            }
+
+            // Add git branch indicator
+            if !git_text.is_empty() {
+                spans.push(Span::styled(
+                    git_text.clone(),
+                    Style::default()
+                        .fg(theme.status_bar_fg)
+                        .bg(theme.status_bar_bg),
+                ));
+                current_col += git_width as u16;
+            }
+
+            // Add indentation indicator
+            if !indent_text.is_empty() {
+                spans.push(Span::styled(
+                    indent_text.clone(),
+                    Style::default()
+                        .fg(theme.status_bar_fg)
+                        .bg(theme.status_bar_bg),
+                ));
+                current_col += indent_width as u16;
+            }
+
             // Add line ending indicator (clickable to change format)
             {
                 let is_hovering = hover == StatusBarHover::LineEndingIndicator;
@@ -976,6 +1106,77 @@ impl StatusBarRenderer {
         layout
     }
 
+    /// Render the history scrubber in the prompt line
+    ///
+    /// Shows the previewed step, the time of its last edit, a gauge of the
+    /// position in the history and the scrubber's keys (if they fit).
+    pub fn render_history_scrubber(
+        frame: &mut Frame,
+        area: Rect,
+        view: &crate::app::history_scrubber::HistoryScrubberView,
+        theme: &crate::view::theme::Theme,
+    ) {
+        use ratatui::layout::{Constraint, Direction, Layout};
+        use ratatui::widgets::LineGauge;
+
+        let base_style = Style::default().fg(theme.prompt_fg).bg(theme.prompt_bg);
+        let time = view
+            .time
+            .clone()
+            .unwrap_or_else(|| t!("history_scrubber.start").to_string());
+        let label = format!(
+            " {} ",
+            t!(
+                "history_scrubber.label",
+                step = view.step,
+                total = view.total,
+                time = time
+            )
+        );
+        let mut hints = format!(" {} ", t!("history_scrubber.hints"));
+
+        let label_width = str_width(&label) as u16;
+        // Keep at least a short gauge; drop the key hints first
+        if label_width + str_width(&hints) as u16 + 10 > area.width {
+            hints.clear();
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(label_width),
+                Constraint::Min(0),
+                Constraint::Length(str_width(&hints) as u16),
+            ])
+            .split(area);
+
+        let ratio = if view.total == 0 {
+            0.0
+        } else {
+            view.step as f64 / view.total as f64
+        };
+        let gauge = LineGauge::default()
+            .ratio(ratio)
+            .label("")
+            .style(base_style)
+            .filled_style(
+                Style::default()
+                    .fg(theme.menu_highlight_fg)
+                    .bg(theme.prompt_bg),
+            )
+            .unfilled_style(
+                Style::default()
+                    .fg(theme.help_separator_fg)
+                    .bg(theme.prompt_bg),
+            );
+
+        frame.render_widget(Paragraph::new(label).style(base_style), chunks[0]);
+        frame.render_widget(gauge, chunks[1]);
+        frame.render_widget(
+            Paragraph::new(hints).style(base_style.fg(theme.help_separator_fg)),
+            chunks[2],
+        );
+    }
+
     /// Render the search options bar (shown when search prompt is active)
     ///
     /// Displays checkboxes for search options with their keyboard shortcuts:
@@ -8,6 +8,7 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use rust_i18n::t;
 
 /// Renders the autocomplete suggestions popup
 pub struct SuggestionsRenderer;
@@ -50,10 +51,16 @@ impl SuggestionsRenderer {
         }
 
         // Create a block with a border and background
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.popup_border_fg))
             .style(Style::default().bg(theme.suggestion_bg));
+        if !prompt.marked.is_empty() {
+            block = block.title(format!(
+                " {} ",
+                t!("prompt.marked_count", count = prompt.marked.len())
+            ));
+        }
 
         let inner_area = block.inner(area);
 
@@ -156,8 +163,15 @@ impl SuggestionsRenderer {
             // Build the line with three columns
             let mut spans = Vec::new();
 
-            // Left margin
-            spans.push(Span::styled(" ".repeat(left_margin), base_style));
+            // Left margin, with a check mark for marked entries of multi-select prompts
+            if prompt.is_marked(suggestion) {
+                spans.push(Span::styled(
+                    format!("✓{}", " ".repeat(left_margin - 1)),
+                    base_style.add_modifier(Modifier::BOLD),
+                ));
+            } else {
+                spans.push(Span::styled(" ".repeat(left_margin), base_style));
+            }
 
             // Column 1: Command name (fixed width, truncate if too long)
             let name = &suggestion.text;
@@ -366,6 +380,7 @@ impl SuggestionsRenderer {
                     let source_text = match source {
                         CommandSource::Builtin => "builtin".to_string(),
                         CommandSource::Plugin(name) => name.clone(),
+                        CommandSource::Config => "config".to_string(),
                     };
                     let source_visual_width = str_width(&source_text);
                     let source_display = if source_visual_width > source_column_width {
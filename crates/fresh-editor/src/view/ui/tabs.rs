@@ -219,14 +219,15 @@ pub fn calculate_tab_widths(
             ""
         };
 
-        let binary_indicator = if buffer_metadata.get(id).map(|m| m.binary).unwrap_or(false) {
-            " [BIN]"
-        } else {
-            ""
+        // Files still loading in the background are marked until their content arrives
+        let kind_indicator = match buffer_metadata.get(id) {
+            Some(m) if m.loading => " [...]",
+            Some(m) if m.binary => " [BIN]",
+            _ => "",
         };
 
-        // Same format as render_for_split: " {name}{modified}{binary_indicator} " + "× "
-        let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
+        // Same format as render_for_split: " {name}{modified}{kind_indicator} " + "× "
+        let tab_name_text = format!(" {name}{modified}{kind_indicator} ");
         let close_text = "× ";
         let tab_width = str_width(&tab_name_text) + str_width(close_text);
 
@@ -333,10 +334,11 @@ impl TabsRenderer {
             } else {
                 ""
             };
-            let binary_indicator = if buffer_metadata.get(id).map(|m| m.binary).unwrap_or(false) {
-                " [BIN]"
-            } else {
-                ""
+            // Files still loading in the background are marked until their content arrives
+            let kind_indicator = match buffer_metadata.get(id) {
+                Some(m) if m.loading => " [...]",
+                Some(m) if m.binary => " [BIN]",
+                _ => "",
             };
 
             let is_active = *id == active_buffer;
@@ -379,8 +381,8 @@ impl TabsRenderer {
                 base_style
             };
 
-            // Build tab content: " {name}{modified}{binary_indicator} "
-            let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
+            // Build tab content: " {name}{modified}{kind_indicator} "
+            let tab_name_text = format!(" {name}{modified}{kind_indicator} ");
             let tab_name_width = str_width(&tab_name_text);
 
             // Close button: "× "
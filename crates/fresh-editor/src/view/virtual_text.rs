@@ -32,6 +32,10 @@ pub enum VirtualTextPosition {
     BeforeChar,
     /// Render after the character (e.g., type hints: `x: i32`)
     AfterChar,
+    /// Render after the end of the line, following any other inline text
+    /// Anchored at the line's last byte (the newline, or the final character
+    /// of a line without one). Used for inline diagnostics.
+    EndOfLine,
 
     // ─── Line positions (full lines) ───
     /// Render as a full line ABOVE the line containing this position
@@ -50,9 +54,9 @@ impl VirtualTextPosition {
         matches!(self, Self::LineAbove | Self::LineBelow)
     }
 
-    /// Returns true if this is an inline position (BeforeChar/AfterChar)
+    /// Returns true if this is an inline position (BeforeChar/AfterChar/EndOfLine)
     pub fn is_inline(&self) -> bool {
-        matches!(self, Self::BeforeChar | Self::AfterChar)
+        matches!(self, Self::BeforeChar | Self::AfterChar | Self::EndOfLine)
     }
 }
 
@@ -244,6 +248,45 @@ impl VirtualTextManager {
         id
     }
 
+    /// Add inline virtual text (BeforeChar/AfterChar/EndOfLine) with namespace
+    /// for bulk removal
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_inline(
+        &mut self,
+        marker_list: &mut MarkerList,
+        position: usize,
+        text: String,
+        style: Style,
+        placement: VirtualTextPosition,
+        namespace: VirtualTextNamespace,
+        priority: i32,
+    ) -> VirtualTextId {
+        debug_assert!(
+            placement.is_inline(),
+            "add_inline requires BeforeChar, AfterChar or EndOfLine"
+        );
+
+        let marker_id = marker_list.create(position, false);
+
+        let id = VirtualTextId(self.next_id);
+        self.next_id += 1;
+
+        self.texts.insert(
+            id,
+            VirtualText {
+                marker_id,
+                text,
+                style,
+                position: placement,
+                priority,
+                string_id: None,
+                namespace: Some(namespace),
+            },
+        );
+
+        id
+    }
+
     /// Remove a virtual text entry by its string identifier
     pub fn remove_by_id(&mut self, marker_list: &mut MarkerList, string_id: &str) -> bool {
         // Find the entry with matching string_id
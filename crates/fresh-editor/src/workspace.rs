@@ -83,6 +83,10 @@ pub struct Workspace {
     #[serde(default)]
     pub external_files: Vec<PathBuf>,
 
+    /// Named split layouts saved by the user (name -> layout)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub layouts: HashMap<String, SerializedLayout>,
+
     /// Timestamp when workspace was saved (Unix epoch seconds)
     pub saved_at: u64,
 }
@@ -114,6 +118,23 @@ pub enum SerializedSplitNode {
     },
 }
 
+/// A named snapshot of the split tree and the per-split view states
+///
+/// Terminal panes are matched by their position among the open terminals
+/// when the layout is restored; files are matched by path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedLayout {
+    /// Split layout tree
+    pub split_layout: SerializedSplitNode,
+
+    /// Active split ID when the layout was saved
+    pub active_split_id: usize,
+
+    /// Per-split view states (keyed by split_id)
+    #[serde(default)]
+    pub split_states: HashMap<usize, SerializedSplitViewState>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SerializedSplitDirection {
     Horizontal,
@@ -746,6 +767,7 @@ impl Workspace {
             bookmarks: HashMap::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
+            layouts: HashMap::new(),
             saved_at: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -884,6 +906,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_named_layouts_serialization() {
+        let mut workspace = Workspace::new(PathBuf::from("/test"));
+
+        // No layouts: field is omitted entirely
+        let json = serde_json::to_string(&workspace).unwrap();
+        assert!(!json.contains("layouts"));
+
+        workspace.layouts.insert(
+            "review".to_string(),
+            SerializedLayout {
+                split_layout: SerializedSplitNode::Leaf {
+                    file_path: Some(PathBuf::from("src/main.rs")),
+                    split_id: 3,
+                    label: Some("left".to_string()),
+                },
+                active_split_id: 3,
+                split_states: HashMap::new(),
+            },
+        );
+
+        let json = serde_json::to_string(&workspace).unwrap();
+        let restored: Workspace = serde_json::from_str(&json).unwrap();
+        let layout = restored.layouts.get("review").unwrap();
+        assert_eq!(layout.active_split_id, 3);
+        assert!(matches!(
+            &layout.split_layout,
+            SerializedSplitNode::Leaf { label: Some(l), .. } if l == "left"
+        ));
+    }
+
     #[test]
     fn test_file_state_serialization() {
         let file_state = SerializedFileState {
@@ -1,5 +1,6 @@
 // Visual regression testing utilities
 
+use crate::common::harness::EditorTestHarness;
 use ratatui::buffer::Buffer;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
@@ -54,6 +55,19 @@ impl VisualFlow {
         }
     }
 
+    /// Render the harness and capture its screen as a step
+    pub fn capture(
+        &mut self,
+        harness: &mut EditorTestHarness,
+        step_name: &str,
+        description: &str,
+    ) -> anyhow::Result<()> {
+        harness.render()?;
+        let cursor_pos = harness.screen_cursor_position();
+        self.step(harness.buffer(), cursor_pos, step_name, description)?;
+        Ok(())
+    }
+
     /// Capture a step in the flow
     pub fn step(
         &mut self,
@@ -0,0 +1,51 @@
+//! E2E tests for the action console
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn run_in_console(harness: &mut EditorTestHarness, input: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Run Action").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(input).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// An action without a default binding runs with the typed arguments
+#[test]
+fn test_action_console_runs_action_with_arguments() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.type_text("width = 95").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    run_in_console(&mut harness, "increment count=10");
+    harness.assert_buffer_content("width = 105");
+
+    // A partial name runs the best matching action
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    run_in_console(&mut harness, "to_upper");
+    harness.assert_buffer_content("WIDTH = 105");
+}
+
+/// Unknown actions and missing arguments are reported
+#[test]
+fn test_action_console_reports_errors() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+
+    run_in_console(&mut harness, "menu_open");
+    harness.assert_screen_contains("menu_open needs arguments: name=<menu>");
+
+    run_in_console(&mut harness, "zzzqqq");
+    harness.assert_screen_contains("Unknown action: zzzqqq");
+}
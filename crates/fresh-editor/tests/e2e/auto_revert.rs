@@ -239,6 +239,84 @@ fn test_auto_revert_skipped_when_buffer_modified() {
     );
 }
 
+/// Open a file, edit it locally, then change it on disk and wait for the
+/// reload/keep/diff prompt
+fn open_with_conflicting_change(harness: &mut EditorTestHarness, file_path: &Path) {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    write_and_sync(file_path, "Original content\n");
+    harness.open_file(file_path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("local edit").unwrap();
+
+    harness.sleep(FILE_CHANGE_DELAY);
+    write_and_sync(file_path, "External change\n");
+    harness.wait_for_prompt().unwrap();
+    harness.assert_screen_contains("changed on disk. (r)eload, (k)eep changes, (d)iff?");
+}
+
+/// Reloading from the prompt replaces the local edits with the disk version
+#[test]
+fn test_external_change_prompt_reload() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("conflict.txt");
+    open_with_conflicting_change(&mut harness, &file_path);
+
+    harness.type_text("r").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("External change\n");
+}
+
+/// Keeping the buffer leaves the edits alone and does not ask again
+#[test]
+fn test_external_change_prompt_keep() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(160, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("conflict.txt");
+    open_with_conflicting_change(&mut harness, &file_path);
+
+    harness.type_text("k").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("Original content\nlocal edit");
+    harness.assert_screen_contains("Kept your changes to conflict.txt");
+
+    for _ in 0..10 {
+        harness.process_async_and_render().unwrap();
+        harness.sleep(Duration::from_millis(300));
+    }
+    assert!(!harness.editor().is_prompting());
+    harness.assert_buffer_content("Original content\nlocal edit");
+}
+
+/// The diff choice shows the disk version next to the buffer
+#[test]
+fn test_external_change_prompt_diff() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let file_path = harness.project_dir().unwrap().join("conflict.txt");
+    open_with_conflicting_change(&mut harness, &file_path);
+
+    harness.type_text("d").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("On disk");
+    harness.assert_screen_contains("Buffer");
+    harness.assert_screen_contains("External change");
+    harness.assert_screen_contains("local edit");
+}
+
 /// Test rapid consecutive file changes are handled correctly
 #[test]
 fn test_auto_revert_rapid_changes() {
@@ -0,0 +1,53 @@
+//! E2E tests for batch scripts (`fresh --batch`)
+
+use crate::common::harness::EditorTestHarness;
+use fresh::app::BatchOutcome;
+use fresh::input::batch::BatchScript;
+use tempfile::TempDir;
+
+fn run(content: &str, script: &str) -> (anyhow::Result<BatchOutcome>, String) {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("input.txt");
+    std::fs::write(&path, content).unwrap();
+    let script = BatchScript::parse(script).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let outcome = harness.editor_mut().run_batch_on_file(&path, &script);
+    (outcome, std::fs::read_to_string(&path).unwrap())
+}
+
+#[test]
+fn test_batch_replaces_sorts_and_saves() {
+    let (outcome, saved) = run(
+        "pear color  \napple Colour\nfig\n",
+        "s/colou?r/hue/i\nreplace \"fig\" \"banana\"\nselect_all\nsort_lines\ntrim_trailing_whitespace\n",
+    );
+    assert_eq!(outcome.unwrap(), BatchOutcome::Changed);
+    assert_eq!(saved, "apple hue\nbanana\npear hue\n");
+}
+
+#[test]
+fn test_batch_goto_and_insert() {
+    let (outcome, saved) = run(
+        "one\ntwo\n",
+        "goto 2\ninsert \"- \"\ngoto 1:4\ninsert \"!\"\n",
+    );
+    assert_eq!(outcome.unwrap(), BatchOutcome::Changed);
+    assert_eq!(saved, "one!\n- two\n");
+}
+
+#[test]
+fn test_batch_without_matches_leaves_file_unchanged() {
+    let (outcome, saved) = run("alpha\n", "s/beta/gamma/\n");
+    assert_eq!(outcome.unwrap(), BatchOutcome::Unchanged);
+    assert_eq!(saved, "alpha\n");
+}
+
+#[test]
+fn test_batch_failure_rolls_back() {
+    // goto_line opens a prompt, which a batch cannot answer
+    let (outcome, saved) = run("keep me\n", "s/keep/drop/\ngoto_line\n");
+    let error = outcome.unwrap_err().to_string();
+    assert!(error.contains("line 2"), "unexpected error: {}", error);
+    assert_eq!(saved, "keep me\n");
+}
@@ -169,7 +169,7 @@ fn test_block_select_all_directions() {
         );
     }
 
-    // Test Alt+Shift+Right
+    // Test Ctrl+Alt+Shift+Right
     {
         let mut harness = EditorTestHarness::new(80, 24).unwrap();
         harness.type_text("aaaa\nbbbb\ncccc").unwrap();
@@ -178,17 +178,20 @@ fn test_block_select_all_directions() {
             .unwrap();
 
         harness
-            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .send_key(
+                KeyCode::Right,
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+            )
             .unwrap();
         harness.render().unwrap();
 
         assert!(
             harness.has_selection(),
-            "Alt+Shift+Right should create selection"
+            "Ctrl+Alt+Shift+Right should create selection"
         );
     }
 
-    // Test Alt+Shift+Left
+    // Test Ctrl+Alt+Shift+Left
     {
         let mut harness = EditorTestHarness::new(80, 24).unwrap();
         harness.type_text("aaaa\nbbbb\ncccc").unwrap();
@@ -203,13 +206,16 @@ fn test_block_select_all_directions() {
             .unwrap();
 
         harness
-            .send_key(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .send_key(
+                KeyCode::Left,
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+            )
             .unwrap();
         harness.render().unwrap();
 
         assert!(
             harness.has_selection(),
-            "Alt+Shift+Left should create selection"
+            "Ctrl+Alt+Shift+Left should create selection"
         );
     }
 }
@@ -307,10 +313,16 @@ fn test_block_select_then_type() {
         .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
         .unwrap();
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness.render().unwrap();
 
@@ -396,10 +408,16 @@ fn test_block_selection_renders_rectangular() {
         .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
         .unwrap();
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness.render().unwrap();
 
@@ -493,7 +511,10 @@ fn test_block_selection_copy_copies_rectangular_region() {
         .unwrap();
     for _ in 0..4 {
         harness
-            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .send_key(
+                KeyCode::Right,
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+            )
             .unwrap();
     }
     harness.render().unwrap();
@@ -317,10 +317,13 @@ fn blog_showcase_editing_block_selection() {
         snap(&mut h, &mut s, Some("Alt+Shift+↓"), 100);
     }
     for _ in 0..2 {
-        h.send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
-            .unwrap();
+        h.send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
+        .unwrap();
         h.render().unwrap();
-        snap(&mut h, &mut s, Some("Alt+Shift+→"), 100);
+        snap(&mut h, &mut s, Some("Ctrl+Alt+Shift+→"), 100);
     }
     hold(&mut h, &mut s, 5, 100);
 
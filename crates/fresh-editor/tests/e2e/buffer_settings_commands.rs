@@ -4,6 +4,7 @@
 //! - Toggle Tab Indicators
 //! - Toggle Line Numbers
 //! - Reset Buffer Settings
+//! - Indentation detection and Convert Indentation to Tabs/Spaces
 
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -416,3 +417,69 @@ fn test_toggle_line_numbers_persists_across_file_changes() {
     // Verify the edited content is still visible
     harness.assert_screen_contains("Edited line 5");
 }
+
+/// Opened files keep the indentation they already use, shown in the status bar
+#[test]
+fn test_indentation_detected_on_open() {
+    let temp_dir = TempDir::new().unwrap();
+    let two_spaces = temp_dir.path().join("two.txt");
+    std::fs::write(&two_spaces, "a:\n  b:\n    c\n  d\n").unwrap();
+    let tabs = temp_dir.path().join("tabs.txt");
+    std::fs::write(&tabs, "a {\n\tb\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(120, 24, Config::default()).unwrap();
+    harness.open_file(&two_spaces).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 2");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert!(harness.get_buffer_content().unwrap().starts_with("  a:"));
+
+    harness.open_file(&tabs).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Tab Size: 4");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert!(harness.get_buffer_content().unwrap().starts_with("\ta {"));
+
+    // With detection off, the language's settings apply
+    let mut config = Config::default();
+    config.editor.detect_indentation = false;
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&tabs).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 4");
+}
+
+/// Converting indentation rewrites leading whitespace in one undo step
+#[test]
+fn test_convert_indentation_commands() {
+    let mut harness = EditorTestHarness::new(140, 24).unwrap();
+    let original = "a\n    b\n        c\n      e\n\td\n";
+    let _fixture = harness.load_buffer_from_text(original).unwrap();
+
+    run_command(&mut harness, "Convert Indentation to Tabs");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "a\n\tb\n\t\tc\n\t  e\n\td\n"
+    );
+    harness.assert_screen_contains("Converted indentation to tabs (4)");
+    harness.assert_screen_contains("Tab Size: 4");
+
+    run_command(&mut harness, "Convert Indentation to Spaces");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "a\n    b\n        c\n      e\n    d\n"
+    );
+    harness.assert_screen_contains("Spaces: 4");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "a\n\tb\n\t\tc\n\t  e\n\td\n"
+    );
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), original);
+}
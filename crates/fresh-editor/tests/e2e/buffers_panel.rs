@@ -0,0 +1,101 @@
+//! E2E tests for the memory budget and the Buffers panel
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Write a file of about 700 KB, so that two of them go over a 1 MB budget
+fn write_large_file(dir: &Path, name: &str) -> (PathBuf, String) {
+    let content: String = (0..70_000).map(|i| format!("line {:04}\n", i)).collect();
+    let path = dir.join(name);
+    fs::write(&path, &content).unwrap();
+    (path, content)
+}
+
+/// Open two files with a 1 MB budget; the first one gets unloaded
+fn over_budget_harness() -> (TempDir, EditorTestHarness, PathBuf, String) {
+    let dir = TempDir::new().unwrap();
+    let (first, first_content) = write_large_file(dir.path(), "first.txt");
+    let (second, _) = write_large_file(dir.path(), "second.txt");
+
+    let mut config = Config::default();
+    config.editor.memory_budget_mb = 1;
+    let mut harness = EditorTestHarness::with_config(100, 30, config).unwrap();
+    harness.open_file(&first).unwrap();
+    harness
+        .send_key(KeyCode::PageDown, KeyModifiers::NONE)
+        .unwrap();
+    harness.open_file(&second).unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.editor_mut().enforce_memory_budget(), 1);
+    (dir, harness, first, first_content)
+}
+
+/// Open the Buffers panel and put the cursor on the line of `name`
+fn select_in_panel(harness: &mut EditorTestHarness, name: &str) {
+    harness.editor_mut().show_buffers();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    let line = content
+        .lines()
+        .position(|line| line.ends_with(name))
+        .unwrap();
+    for _ in 0..line {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+}
+
+#[test]
+fn test_unloaded_buffer_is_read_back_when_shown() {
+    let (_dir, mut harness, _first, first_content) = over_budget_harness();
+    select_in_panel(&mut harness, "first.txt");
+    harness.assert_screen_contains("unloaded");
+    harness.assert_screen_contains("budget 1.0 MB");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content(&first_content);
+    // The cursor is where it was before the content was unloaded
+    assert!(harness.cursor_position() > 0);
+    harness.assert_screen_not_contains("line 0000");
+}
+
+#[test]
+fn test_unloaded_buffer_changed_on_disk_is_reloaded() {
+    let (_dir, mut harness, first, _) = over_budget_harness();
+    fs::write(&first, "changed on disk\n").unwrap();
+
+    select_in_panel(&mut harness, "first.txt");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("changed on disk\n");
+    let status = harness.editor().get_status_message().unwrap();
+    assert!(status.ends_with("changed on disk while unloaded and was reloaded"));
+}
+
+#[test]
+fn test_modified_buffer_is_not_unloaded() {
+    let dir = TempDir::new().unwrap();
+    let (path, _) = write_large_file(dir.path(), "edited.txt");
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.type_text("x").unwrap();
+
+    select_in_panel(&mut harness, "edited.txt");
+    harness.assert_screen_contains("modified");
+    harness
+        .send_key(KeyCode::Char('u'), KeyModifiers::NONE)
+        .unwrap();
+    let status = harness.editor().get_status_message().unwrap();
+    assert!(status.ends_with(
+        "edited.txt can't be unloaded: it is shown, modified, or not a file read as-is from disk"
+    ));
+}
@@ -0,0 +1,242 @@
+//! E2E tests for collaborative editing (host/guest over loopback TCP)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::event::{CursorId, Event};
+use fresh::services::collab::CollabMessage;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Process network messages on both editors until `condition` holds
+fn pump<F>(host: &mut EditorTestHarness, guest: &mut EditorTestHarness, condition: F)
+where
+    F: Fn(&EditorTestHarness, &EditorTestHarness) -> bool,
+{
+    for _ in 0..500 {
+        host.editor_mut().process_async_messages();
+        guest.editor_mut().process_async_messages();
+        if condition(host, guest) {
+            host.render().unwrap();
+            guest.render().unwrap();
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    panic!(
+        "collab peers did not converge: host={:?} guest={:?}",
+        host.get_buffer_content(),
+        guest.get_buffer_content()
+    );
+}
+
+fn same_text(host: &EditorTestHarness, guest: &EditorTestHarness) -> bool {
+    guest.editor().collab_buffer().is_some()
+        && host.get_buffer_content() == guest.get_buffer_content()
+}
+
+/// Host a new buffer containing `text` and connect a guest to it
+fn start_session(text: &str) -> (EditorTestHarness, EditorTestHarness) {
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.type_text(text).unwrap();
+    let address = host
+        .editor_mut()
+        .collab_host("127.0.0.1:0")
+        .expect("hosting on loopback");
+
+    let mut guest = EditorTestHarness::new(80, 24).unwrap();
+    guest.editor_mut().collab_join(&address);
+    pump(&mut host, &mut guest, |host, guest| {
+        same_text(host, guest) && !host.editor().collab_participants().is_empty()
+    });
+    (host, guest)
+}
+
+/// A guest receives the host's text and concurrent edits converge
+#[test]
+fn test_collab_concurrent_edits_converge() {
+    let (mut host, mut guest) = start_session("hello world");
+    assert_eq!(guest.get_buffer_content().unwrap(), "hello world");
+    assert_eq!(guest.editor().collab_participants().len(), 1);
+
+    // Both edit before either sees the other's change
+    host.send_key(KeyCode::Home, KeyModifiers::CONTROL).unwrap();
+    host.type_text(">> ").unwrap();
+    guest.send_key(KeyCode::End, KeyModifiers::CONTROL).unwrap();
+    guest.type_text("!").unwrap();
+
+    pump(&mut host, &mut guest, |host, guest| {
+        same_text(host, guest) && host.get_buffer_content().unwrap().ends_with('!')
+    });
+    assert_eq!(host.get_buffer_content().unwrap(), ">> hello world!");
+}
+
+/// Undo in a shared buffer only reverts the local participant's edits
+#[test]
+fn test_collab_undo_is_per_peer() {
+    let (mut host, mut guest) = start_session("a");
+
+    guest.send_key(KeyCode::End, KeyModifiers::CONTROL).unwrap();
+    guest.type_text(" guest").unwrap();
+    pump(&mut host, &mut guest, |host, _| {
+        host.get_buffer_content().unwrap() == "a guest"
+    });
+
+    host.send_key(KeyCode::Home, KeyModifiers::CONTROL).unwrap();
+    host.type_text("host ").unwrap();
+    pump(&mut host, &mut guest, |_, guest| {
+        guest.get_buffer_content().unwrap() == "host a guest"
+    });
+
+    // The guest's undo skips the host's later edit
+    guest
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    pump(&mut host, &mut guest, |host, guest| {
+        same_text(host, guest) && host.get_buffer_content().unwrap() == "host a"
+    });
+
+    guest
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    pump(&mut host, &mut guest, |host, guest| {
+        same_text(host, guest) && host.get_buffer_content().unwrap() == "host a guest"
+    });
+}
+
+/// Leaving ends the session for the guest but keeps its copy of the text
+#[test]
+fn test_collab_host_leaving_ends_session() {
+    let (mut host, mut guest) = start_session("shared");
+
+    host.editor_mut().collab_leave();
+    pump(&mut host, &mut guest, |_, guest| {
+        !guest.editor().is_collaborating()
+    });
+    assert_eq!(guest.get_buffer_content().unwrap(), "shared");
+    let status = guest
+        .editor()
+        .get_status_message()
+        .cloned()
+        .unwrap_or_default();
+    assert!(status.contains("ended"), "status: {}", status);
+}
+
+/// Ending a session keeps unsaved edits marked as modified
+#[test]
+fn test_collab_end_keeps_buffer_modified() {
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.type_text("draft").unwrap();
+    host.editor_mut()
+        .collab_host("127.0.0.1:0")
+        .expect("hosting on loopback");
+    host.editor_mut().collab_leave();
+
+    host.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(host.get_buffer_content().unwrap(), "draft");
+    assert!(host.editor().active_state().buffer.is_modified());
+}
+
+/// A guest without the session token is disconnected and never gets the text
+#[test]
+fn test_collab_join_with_wrong_token_is_refused() {
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.type_text("secret").unwrap();
+    let address = host
+        .editor_mut()
+        .collab_host("127.0.0.1:0")
+        .expect("hosting on loopback");
+    let (_, host_port) = address.split_once('@').unwrap();
+
+    let mut guest = EditorTestHarness::new(80, 24).unwrap();
+    guest
+        .editor_mut()
+        .collab_join(&format!("wrong@{}", host_port));
+    pump(&mut host, &mut guest, |_, guest| {
+        !guest.editor().is_collaborating()
+    });
+    assert!(guest.editor().collab_buffer().is_none());
+    assert!(host.editor().collab_participants().is_empty());
+}
+
+/// An edit outside the shared text disconnects the guest that sent it and
+/// leaves the host's buffer alone
+#[test]
+fn test_collab_invalid_edit_disconnects_guest() {
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.type_text("héllo").unwrap();
+    let address = host
+        .editor_mut()
+        .collab_host("127.0.0.1:0")
+        .expect("hosting on loopback");
+    let (token, host_port) = address.split_once('@').unwrap();
+
+    let mut stream = TcpStream::connect(host_port).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_millis(10)))
+        .unwrap();
+    let mut send = |message: CollabMessage| {
+        writeln!(stream, "{}", message.to_line()).unwrap();
+    };
+    send(CollabMessage::Hello {
+        name: "mallory".to_string(),
+        token: token.to_string(),
+    });
+    // Inside the two-byte `é`
+    send(CollabMessage::Op {
+        peer_id: 1,
+        revision: 0,
+        events: vec![Event::Insert {
+            position: 2,
+            text: "x".to_string(),
+            cursor_id: CursorId::UNDO_SENTINEL,
+        }],
+    });
+
+    assert!(
+        host_closes(&mut host, &stream),
+        "the host kept the connection open"
+    );
+    assert!(host.editor().collab_participants().is_empty());
+    assert_eq!(host.get_buffer_content().unwrap(), "héllo");
+}
+
+/// A connection that sends an overlong line before its hello is dropped
+#[test]
+fn test_collab_oversized_hello_is_dropped() {
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.type_text("text").unwrap();
+    let address = host
+        .editor_mut()
+        .collab_host("127.0.0.1:0")
+        .expect("hosting on loopback");
+    let (_, host_port) = address.split_once('@').unwrap();
+
+    let mut stream = TcpStream::connect(host_port).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_millis(10)))
+        .unwrap();
+    stream.write_all(&[b'x'; 8 * 1024]).unwrap();
+
+    assert!(
+        host_closes(&mut host, &stream),
+        "the host kept the connection open"
+    );
+}
+
+/// Process the host's messages until it closes `stream`
+fn host_closes(host: &mut EditorTestHarness, stream: &TcpStream) -> bool {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    for _ in 0..500 {
+        host.editor_mut().process_async_messages();
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) => return true,
+            Ok(_) => continue,
+            Err(_) => {}
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
@@ -567,7 +567,7 @@ fn test_command_palette_down_no_wraparound() {
         .unwrap();
 
     // Filter to get only two commands
-    harness.type_text("save f").unwrap();
+    harness.type_text("save file").unwrap();
     harness.render().unwrap();
 
     // Should match "Save File" and "Save File As"
@@ -972,3 +972,52 @@ fn test_command_palette_select_cursor_style() {
         .wait_for_screen_contains("Cursor style changed")
         .unwrap();
 }
+
+/// The shortcuts page is built from the live bindings (including user remaps)
+/// and can be narrowed down with '/'
+#[test]
+fn test_keyboard_shortcuts_show_remaps_and_filter() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use fresh::config::{Config, Keybinding};
+
+    let mut config = Config::default();
+    config.keybindings.push(Keybinding {
+        key: "F9".to_string(),
+        modifiers: vec![],
+        keys: vec![],
+        action: "sort_lines".to_string(),
+        args: Default::default(),
+        when: None,
+    });
+    let mut harness = EditorTestHarness::with_config(100, 30, config).unwrap();
+
+    harness.editor_mut().open_keyboard_shortcuts();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Keyboard Shortcuts");
+    harness.assert_screen_contains("── Files ──");
+
+    harness
+        .send_key(KeyCode::Char('/'), KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("sort").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("matching \"sort\"");
+    harness.assert_screen_contains("F9");
+    harness.assert_screen_contains("Sort lines");
+    harness.assert_screen_not_contains("── Files ──");
+
+    // A filter without matches says so
+    harness
+        .send_key(KeyCode::Char('/'), KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("zzzz").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No bindings match \"zzzz\"");
+}
@@ -0,0 +1,129 @@
+//! E2E tests for the JSON-RPC control socket (`fresh --listen`)
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::control_socket::{send_request, RemoteError, METHOD_NOT_FOUND};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+
+/// Send a request from another thread, keeping the editor running until the
+/// reply arrives
+fn request(
+    harness: &mut EditorTestHarness,
+    socket: &Path,
+    method: &str,
+    params: Value,
+) -> anyhow::Result<Value> {
+    let socket = socket.to_path_buf();
+    let method = method.to_string();
+    let client: JoinHandle<anyhow::Result<Value>> =
+        std::thread::spawn(move || send_request(&socket, &method, params));
+    harness.wait_until(|_| client.is_finished())?;
+    client.join().unwrap()
+}
+
+fn listening_harness(temp_dir: &Path) -> anyhow::Result<(EditorTestHarness, PathBuf)> {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24)?;
+    let socket = temp_dir.join("fresh.sock");
+    harness.editor_mut().start_control_socket(Some(&socket));
+    assert_eq!(
+        harness.editor().control_socket_path(),
+        Some(socket.as_path())
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&socket)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    Ok((harness, socket))
+}
+
+/// Files are opened at a position and edited through the socket
+#[test]
+#[cfg_attr(not(unix), ignore = "The control socket needs unix sockets")]
+fn test_control_socket_opens_and_edits_files() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "first\nsecond\n")?;
+    let (mut harness, socket) = listening_harness(temp_dir.path())?;
+
+    let buffer_id = request(
+        &mut harness,
+        &socket,
+        "openFile",
+        json!({ "path": file, "line": 2, "column": 3 }),
+    )?;
+    assert!(buffer_id.is_u64());
+    request(&mut harness, &socket, "insertText", json!({ "text": "<>" }))?;
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some("first\nse<>cond\n")
+    );
+
+    request(&mut harness, &socket, "gotoPosition", json!({ "line": 1 }))?;
+    request(
+        &mut harness,
+        &socket,
+        "runCommand",
+        json!({ "name": "move_line_end" }),
+    )?;
+    request(&mut harness, &socket, "insertText", json!({ "text": "!" }))?;
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some("first!\nse<>cond\n")
+    );
+
+    let buffers = request(&mut harness, &socket, "listBuffers", Value::Null)?;
+    let opened = buffers
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|buffer| buffer["id"] == buffer_id)
+        .unwrap();
+    assert_eq!(opened["path"], json!(file));
+    assert_eq!(opened["modified"], json!(true));
+    assert_eq!(opened["active"], json!(true));
+
+    Ok(())
+}
+
+/// Unknown methods and commands are reported as errors
+#[test]
+#[cfg_attr(not(unix), ignore = "The control socket needs unix sockets")]
+fn test_control_socket_reports_errors() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, socket) = listening_harness(temp_dir.path())?;
+
+    let error = request(&mut harness, &socket, "deleteFile", Value::Null).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RemoteError>().map(|e| e.code),
+        Some(METHOD_NOT_FOUND)
+    );
+    let error = request(
+        &mut harness,
+        &socket,
+        "runCommand",
+        json!({ "name": "no_such_command" }),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("unknown command"));
+
+    // Positions must fall between characters
+    harness.type_text("é")?;
+    let error = request(
+        &mut harness,
+        &socket,
+        "insertText",
+        json!({ "text": "x", "position": 1 }),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("inside a character"));
+    assert_eq!(harness.get_buffer_content().as_deref(), Some("é"));
+
+    // The socket is removed with the editor
+    drop(harness);
+    assert!(!socket.exists());
+
+    Ok(())
+}
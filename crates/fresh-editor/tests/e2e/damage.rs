@@ -0,0 +1,47 @@
+//! E2E tests for partial redraws of damaged screen regions
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+/// Drawing only the status bar over the last frame gives the same screen as
+/// rendering the whole frame
+#[test]
+fn test_status_bar_only_matches_full_render() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello world").unwrap();
+    harness.render().unwrap();
+    let previous = harness.buffer().clone();
+
+    harness
+        .editor_mut()
+        .set_status_message("Indexing".to_string());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let mut frame = terminal.get_frame();
+    assert!(harness
+        .editor_mut()
+        .render_status_bar_only(&mut frame, &previous));
+    let partial = frame.buffer_mut().clone();
+
+    harness.render().unwrap();
+    assert_eq!(&partial, harness.buffer());
+    harness.assert_screen_contains("Indexing");
+}
+
+/// The whole frame is rendered when something may be drawn over the status bar
+#[test]
+fn test_status_bar_only_refused_with_prompt() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    let previous = harness.buffer().clone();
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    let mut frame = terminal.get_frame();
+    assert!(!harness
+        .editor_mut()
+        .render_status_bar_only(&mut frame, &previous));
+}
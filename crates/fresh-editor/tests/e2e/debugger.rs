@@ -0,0 +1,237 @@
+//! E2E tests for the debugger (breakpoints, stepping, variables and console)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::path::{Path, PathBuf};
+
+/// Bash debug adapter that stops at line 2 of the file given as its first
+/// argument, moves one line per `next` and ends the program on `continue`
+const FAKE_ADAPTER: &str = r#"#!/bin/bash
+SOURCE="$1"
+LINE=2
+OUT_SEQ=1
+
+read_message() {
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+    if [ "$content_length" -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+send_message() {
+    local message="$1"
+    OUT_SEQ=$((OUT_SEQ + 1))
+    printf 'Content-Length: %d\r\n\r\n%s' "${#message}" "$message"
+}
+
+respond() {
+    send_message '{"seq":'$OUT_SEQ',"type":"response","request_seq":'$1',"success":true,"command":"'$2'","body":'"$3"'}'
+}
+
+event() {
+    send_message '{"seq":'$OUT_SEQ',"type":"event","event":"'$1'","body":'"$2"'}'
+}
+
+while true; do
+    msg=$(read_message)
+    if [ -z "$msg" ]; then
+        break
+    fi
+    seq=$(echo "$msg" | grep -o '"seq":[0-9]*' | head -1 | cut -d: -f2)
+    command=$(echo "$msg" | grep -o '"command":"[^"]*"' | head -1 | cut -d'"' -f4)
+    echo "request $command" >&2
+
+    case "$command" in
+        initialize)
+            respond $seq $command '{"supportsConfigurationDoneRequest":true}'
+            event initialized '{}'
+            ;;
+        configurationDone)
+            respond $seq $command '{}'
+            event output '{"category":"stdout","output":"hello from program\n"}'
+            event stopped '{"reason":"breakpoint","threadId":1}'
+            ;;
+        setBreakpoints)
+            respond $seq $command '{"breakpoints":[]}'
+            ;;
+        stackTrace)
+            respond $seq $command '{"stackFrames":[{"id":7,"name":"main","line":'$LINE',"column":1,"source":{"path":"'$SOURCE'"}}]}'
+            ;;
+        scopes)
+            respond $seq $command '{"scopes":[{"name":"Locals","variablesReference":10,"expensive":false}]}'
+            ;;
+        variables)
+            respond $seq $command '{"variables":[{"name":"count","value":"'$LINE'","variablesReference":0}]}'
+            ;;
+        evaluate)
+            respond $seq $command '{"result":"42","variablesReference":0}'
+            ;;
+        next)
+            respond $seq $command '{}'
+            LINE=$((LINE + 1))
+            event stopped '{"reason":"step","threadId":1}'
+            ;;
+        continue)
+            respond $seq $command '{"allThreadsContinued":true}'
+            event exited '{"exitCode":0}'
+            event terminated '{}'
+            ;;
+        disconnect)
+            respond $seq $command '{}'
+            break
+            ;;
+        *)
+            respond $seq $command '{}'
+            ;;
+    esac
+done
+"#;
+
+/// Project with `main.txt`, the fake adapter and a launch configuration
+fn setup_project(dir: &Path) -> anyhow::Result<PathBuf> {
+    let source = dir.join("main.txt");
+    std::fs::write(&source, "start\ncount = 2\ncount = 3\ndone\n")?;
+
+    let adapter = dir.join("fake_dap.sh");
+    std::fs::write(&adapter, FAKE_ADAPTER)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&adapter, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    std::fs::create_dir_all(dir.join(".fresh"))?;
+    std::fs::write(
+        dir.join(".fresh").join("launch.toml"),
+        r#"
+[[configurations]]
+name = "Fake"
+adapter = { command = "${workspaceFolder}/fake_dap.sh", args = ["${workspaceFolder}/main.txt"] }
+"#,
+    )?;
+    Ok(source)
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    Ok(())
+}
+
+/// F9 toggles a breakpoint, which follows the line when text is inserted above
+#[test]
+fn test_toggle_breakpoint_in_gutter() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = setup_project(temp_dir.path())?;
+    let mut harness = EditorTestHarness::with_working_dir(100, 24, temp_dir.path().to_path_buf())?;
+    harness.open_file(&source)?;
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_contains("●");
+    assert_eq!(harness.editor().breakpoint_lines(&source), vec![1]);
+
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE)?;
+    harness.type_text("new line\n")?;
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    // The cursor is on the breakpoint's new line, so this clears it
+    assert!(harness.editor().breakpoint_lines(&source).is_empty());
+    harness.assert_screen_not_contains("●");
+    Ok(())
+}
+
+/// A session stops at the breakpoint, shows variables, steps and ends
+#[test]
+fn test_debug_session_stop_step_and_continue() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = setup_project(temp_dir.path())?;
+    let mut harness = EditorTestHarness::with_working_dir(100, 30, temp_dir.path().to_path_buf())?;
+    harness.open_file(&source)?;
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.wait_for_screen_contains("Paused (breakpoint)")?;
+    harness.wait_for_screen_contains("▶")?;
+    assert!(harness.editor().is_debugging());
+
+    run_command(&mut harness, "Show Debug Variables")?;
+    harness.wait_for_screen_contains("count = 2")?;
+    harness.assert_screen_contains("Debugger: Fake - paused (breakpoint) in main (main.txt:2)");
+    harness.assert_screen_contains("Locals");
+
+    // Stepping follows the execution point in the source
+    run_command(&mut harness, "Debug: Step Over")?;
+    harness.wait_for_screen_contains("▶   3 │ count = 3")?;
+    run_command(&mut harness, "Show Debug Variables")?;
+    harness.wait_for_screen_contains("paused (step) in main (main.txt:3)")?;
+    harness.assert_screen_contains("count = 3");
+
+    run_command(&mut harness, "Debug: Add Watch")?;
+    harness.type_text("count * 2")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("count * 2 = 42")?;
+
+    run_command(&mut harness, "Debug: Continue")?;
+    harness.wait_until(|h| !h.editor().is_debugging())?;
+    harness.wait_for_screen_contains("Debugger: not running")?;
+
+    run_command(&mut harness, "Show Debug Console")?;
+    harness.wait_for_screen_contains("hello from program")?;
+    harness.assert_screen_contains("Program exited with code 0");
+    harness.assert_screen_contains("── session ended ──");
+    Ok(())
+}
+
+/// Expressions evaluated from the console print their result
+#[test]
+fn test_debug_console_evaluate() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let source = setup_project(temp_dir.path())?;
+    let mut harness = EditorTestHarness::with_working_dir(100, 30, temp_dir.path().to_path_buf())?;
+    harness.open_file(&source)?;
+
+    run_command(&mut harness, "Start Debugging")?;
+    harness.wait_for_screen_contains("Paused (breakpoint)")?;
+
+    run_command(&mut harness, "Debug: Evaluate Expression")?;
+    harness.type_text("answer")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("> answer")?;
+    harness.wait_for_screen_contains("42")?;
+
+    run_command(&mut harness, "Stop Debugging")?;
+    harness.wait_for_screen_contains("── session ended ──")?;
+    assert!(!harness.editor().is_debugging());
+    Ok(())
+}
+
+/// Starting without a launch.toml explains where configurations go
+#[test]
+fn test_debug_start_without_configuration() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let mut harness = EditorTestHarness::with_working_dir(100, 24, temp_dir.path().to_path_buf())?;
+
+    run_command(&mut harness, "Start Debugging")?;
+    harness.assert_screen_contains("No debug configurations in");
+    assert!(!harness.editor().is_debugging());
+    Ok(())
+}
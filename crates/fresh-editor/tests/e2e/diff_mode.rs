@@ -0,0 +1,135 @@
+//! E2E tests for the standalone `--diff` / `--merge` mode
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use tempfile::TempDir;
+
+fn diff_harness(left: &str, right: &str) -> (TempDir, EditorTestHarness) {
+    let dir = TempDir::new().unwrap();
+    let left_path = dir.path().join("left.txt");
+    let right_path = dir.path().join("right.txt");
+    fs::write(&left_path, left).unwrap();
+    fs::write(&right_path, right).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness
+        .editor_mut()
+        .open_diff_files(&left_path, &right_path)
+        .unwrap();
+    harness.render().unwrap();
+    (dir, harness)
+}
+
+#[test]
+fn test_diff_shows_both_files_and_navigates_changes() {
+    let (_dir, mut harness) = diff_harness(
+        "one\ntwo\nthree\nfour\nfive\n",
+        "one\nTWO\nthree\nfour\nFIVE\n",
+    );
+
+    harness.assert_screen_contains("*Diff: left.txt ↔ right.txt*");
+    harness.assert_screen_contains("2 change(s)");
+    harness.assert_screen_contains("TWO");
+    harness.assert_screen_contains("FIVE");
+
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No more changes");
+}
+
+#[test]
+fn test_diff_edit_and_save_either_side() {
+    let (dir, mut harness) = diff_harness("alpha\nbeta\n", "alpha\ngamma\n");
+
+    // Left pane is focused: type at the start of the first line
+    harness.type_text("x").unwrap();
+    // Switch to the right pane and edit it too
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("new").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("left.txt")).unwrap(),
+        "xalpha\nbeta\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("right.txt")).unwrap(),
+        "alph\nnew\ngamma\n"
+    );
+    harness.assert_screen_contains("Saved 2 file(s)");
+}
+
+#[test]
+fn test_merge_exit_code_tracks_resolution() {
+    let dir = TempDir::new().unwrap();
+    let local = dir.path().join("local.txt");
+    let base = dir.path().join("base.txt");
+    let remote = dir.path().join("remote.txt");
+    let merged = dir.path().join("merged.txt");
+    fs::write(&local, "a\nlocal\n").unwrap();
+    fs::write(&base, "a\nbase\n").unwrap();
+    fs::write(&remote, "a\nremote\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness
+        .editor_mut()
+        .open_merge_files(&local, &base, &remote, &merged)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Merge: merged.txt*");
+
+    // MERGED was seeded from BASE but not saved yet
+    assert_eq!(harness.editor().diff_mode_exit_code(), 1);
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(fs::read_to_string(&merged).unwrap(), "a\nbase\n");
+    assert_eq!(harness.editor().diff_mode_exit_code(), 0);
+
+    // LOCAL and REMOTE are read-only
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.type_text("z").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("This pane is read-only");
+    assert_eq!(fs::read_to_string(&remote).unwrap(), "a\nremote\n");
+}
+
+#[test]
+fn test_merge_with_conflict_markers_exits_nonzero() {
+    let dir = TempDir::new().unwrap();
+    let local = dir.path().join("local.txt");
+    let base = dir.path().join("base.txt");
+    let remote = dir.path().join("remote.txt");
+    let merged = dir.path().join("merged.txt");
+    fs::write(&local, "local\n").unwrap();
+    fs::write(&base, "base\n").unwrap();
+    fs::write(&remote, "remote\n").unwrap();
+    fs::write(
+        &merged,
+        "<<<<<<< HEAD\nlocal\n=======\nremote\n>>>>>>> topic\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness
+        .editor_mut()
+        .open_merge_files(&local, &base, &remote, &merged)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.editor().diff_mode_exit_code(), 1);
+}
@@ -91,8 +91,8 @@ fn test_explorer_menu_checkbox_updates_on_toggle() {
     harness.render().unwrap();
 
     // Navigate to Show Hidden Files and select it
-    // Menu items: New File, New Folder, Rename, Delete, Refresh, --separator--, Show Hidden Files
-    for _ in 0..6 {
+    // Menu items: New File, New Folder, Open, Rename, Move, Copy, Delete, Refresh, Filter, Reveal Active File, Show Hidden Files
+    for _ in 0..10 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
     harness
@@ -417,9 +417,9 @@ fn test_explorer_menu_toggle_hidden_via_menu() {
     harness.render().unwrap();
 
     // Navigate to "Show Hidden Files"
-    // Menu items (separators are auto-skipped): New File -> New Folder -> Open -> Rename -> Delete -> Refresh -> Show Hidden Files
-    // That's 6 Down presses from New File to Show Hidden Files
-    for _ in 0..6 {
+    // Menu items (separators are auto-skipped): New File -> New Folder -> Open -> Rename -> Move -> Copy -> Delete -> Refresh -> Filter -> Reveal Active File -> Show Hidden Files
+    // That's 10 Down presses from New File to Show Hidden Files
+    for _ in 0..10 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
     harness.render().unwrap();
@@ -2687,3 +2687,263 @@ fn test_file_explorer_escape_clears_search() {
         "Should remain in FileExplorer context after second Escape"
     );
 }
+
+/// Test moving marked files into a directory updates the open buffer's path
+#[test]
+fn test_file_explorer_move_marked_files_retargets_open_buffer() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::create_dir(project_root.join("sub")).unwrap();
+    fs::write(project_root.join("a.txt"), "alpha").unwrap();
+    fs::write(project_root.join("b.txt"), "beta").unwrap();
+
+    harness
+        .editor_mut()
+        .open_file(&project_root.join("a.txt"))
+        .unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("b.txt").unwrap();
+
+    // Root -> sub -> a.txt, then mark a.txt and b.txt
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().file_explorer().unwrap().marked_count(), 2);
+
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Move 2 items to:");
+
+    harness.type_text("sub/").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(project_root.join("sub/a.txt").exists());
+    assert!(project_root.join("sub/b.txt").exists());
+    assert!(!project_root.join("a.txt").exists());
+    assert!(!project_root.join("b.txt").exists());
+    assert_eq!(harness.editor().file_explorer().unwrap().marked_count(), 0);
+
+    // The open buffer now points at the moved file
+    assert_eq!(
+        harness.editor().active_state().buffer.file_path(),
+        Some(project_root.join("sub/a.txt").as_path())
+    );
+    assert!(matches!(
+        harness.editor().get_key_context(),
+        fresh::input::keybindings::KeyContext::FileExplorer
+    ));
+}
+
+/// Test copying a directory to a new name copies its contents
+#[test]
+fn test_file_explorer_copy_directory() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::create_dir_all(project_root.join("src/nested")).unwrap();
+    fs::write(project_root.join("src/main.txt"), "main").unwrap();
+    fs::write(project_root.join("src/nested/deep.txt"), "deep").unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("src").unwrap();
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::F(5), KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Copy 'src' to:");
+
+    harness.type_text("backup").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(project_root.join("backup/nested/deep.txt")).unwrap(),
+        "deep"
+    );
+    assert_eq!(
+        fs::read_to_string(project_root.join("backup/main.txt")).unwrap(),
+        "main"
+    );
+    assert!(project_root.join("src/main.txt").exists());
+    harness.wait_for_file_explorer_item("backup").unwrap();
+}
+
+/// Test that gitignored entries are hidden until gitignored files are shown
+#[test]
+fn test_file_explorer_hides_gitignored_entries() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::write(project_root.join(".gitignore"), "build/\n*.log\n").unwrap();
+    fs::create_dir(project_root.join("build")).unwrap();
+    fs::write(project_root.join("debug.log"), "log").unwrap();
+    fs::write(project_root.join("main.rs"), "fn main() {}").unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("main.rs").unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(!screen.contains("build"), "Screen:\n{}", screen);
+    assert!(!screen.contains("debug.log"), "Screen:\n{}", screen);
+
+    harness
+        .send_key(KeyCode::Char('i'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_file_explorer_item("debug.log").unwrap();
+    harness.assert_screen_contains("build");
+}
+
+/// Test that the changed-files filter shows only decorated files and their folders
+#[test]
+fn test_file_explorer_show_changed_files_only() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::create_dir_all(project_root.join("src/nested")).unwrap();
+    fs::write(project_root.join("src/nested/edited.rs"), "").unwrap();
+    fs::write(project_root.join("src/untouched.rs"), "").unwrap();
+    fs::write(project_root.join("readme.md"), "").unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("readme.md").unwrap();
+
+    harness.editor_mut().handle_set_file_explorer_decorations(
+        "test".to_string(),
+        vec![fresh::view::file_tree::FileExplorerDecoration {
+            path: project_root.join("src/nested/edited.rs"),
+            symbol: "M".to_string(),
+            color: [255, 184, 108],
+            priority: 50,
+        }],
+    );
+
+    harness
+        .send_key(
+            KeyCode::Char('g'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+
+    // Collapsed folders leading to the change are expanded
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("edited.rs"), "Screen:\n{}", screen);
+    assert!(screen.contains("nested"), "Screen:\n{}", screen);
+    assert!(!screen.contains("untouched.rs"), "Screen:\n{}", screen);
+    assert!(!screen.contains("readme.md"), "Screen:\n{}", screen);
+    assert!(screen.contains("[changed]"), "Screen:\n{}", screen);
+
+    harness
+        .send_key(
+            KeyCode::Char('g'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("readme.md");
+    harness.assert_screen_contains("untouched.rs");
+}
+
+/// Test that the filter box flattens matching entries and opening one drops the filter
+#[test]
+fn test_file_explorer_filter_flattens_matches() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::create_dir_all(project_root.join("src/parser")).unwrap();
+    fs::write(project_root.join("src/parser/lexer.rs"), "lexer").unwrap();
+    fs::write(project_root.join("src/main.rs"), "").unwrap();
+    fs::write(project_root.join("readme.md"), "").unwrap();
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_file_explorer_item("src").unwrap();
+
+    // Syncing to an open file expands src/parser so lexer.rs is loaded
+    harness
+        .editor_mut()
+        .open_file(&project_root.join("src/parser/lexer.rs"))
+        .unwrap();
+    harness.editor_mut().focus_file_explorer();
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .file_explorer()
+                .and_then(|e| e.get_selected_entry())
+                .is_some_and(|e| e.name == "lexer.rs")
+        })
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("lex").unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("Filter: lex"), "Screen:\n{}", screen);
+    assert!(
+        screen.contains("src/parser/lexer.rs"),
+        "Screen:\n{}",
+        screen
+    );
+    assert!(!screen.contains("readme.md"), "Screen:\n{}", screen);
+    assert!(!screen.contains("main.rs"), "Screen:\n{}", screen);
+
+    // Escape closes the filter and restores the tree
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("readme.md");
+    harness.assert_screen_not_contains("Filter:");
+    let selected = harness
+        .editor()
+        .file_explorer()
+        .and_then(|e| e.get_selected_entry())
+        .map(|e| e.name.clone());
+    assert_eq!(selected.as_deref(), Some("lexer.rs"));
+}
+
+/// Test that revealing the active file opens the explorer and selects it
+#[test]
+fn test_reveal_active_file_in_file_explorer() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40).unwrap();
+    let project_root = harness.project_dir().unwrap();
+
+    fs::create_dir_all(project_root.join("a/b/c")).unwrap();
+    fs::write(project_root.join("a/b/c/deep.txt"), "deep").unwrap();
+
+    harness
+        .editor_mut()
+        .open_file(&project_root.join("a/b/c/deep.txt"))
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.editor_mut().reveal_in_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .file_explorer()
+                .and_then(|e| e.get_selected_entry())
+                .is_some_and(|e| e.name == "deep.txt")
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("deep.txt");
+}
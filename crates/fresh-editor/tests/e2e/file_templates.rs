@@ -0,0 +1,70 @@
+//! E2E tests for filling new files from file templates
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::fs;
+use tempfile::TempDir;
+
+/// A harness whose templates directory holds `templates`, working in a
+/// project directory
+fn harness_with_templates(templates: &[(&str, &str)]) -> (TempDir, EditorTestHarness) {
+    let temp = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(&temp.path().join("context"));
+    fs::create_dir_all(dir_context.templates_dir()).unwrap();
+    for (name, content) in templates {
+        fs::write(dir_context.templates_dir().join(name), content).unwrap();
+    }
+    let project = temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+
+    let mut config = Config::default();
+    config.editor.template_author = "Ada".to_string();
+    let harness =
+        EditorTestHarness::with_shared_dir_context(100, 24, config, project, dir_context).unwrap();
+    (temp, harness)
+}
+
+#[test]
+fn test_new_file_starts_from_template() {
+    let (temp, mut harness) =
+        harness_with_templates(&[("module.rs", "// ${filename} by ${author}\n${cursor}\n")]);
+    let path = temp.path().join("project/lib.rs");
+    harness.open_file(&path).unwrap();
+    harness.assert_buffer_content("// lib.rs by Ada\n\n");
+
+    // The cursor is where the template put it
+    harness.type_text("fn main() {}").unwrap();
+    harness.assert_buffer_content("// lib.rs by Ada\nfn main() {}\n");
+
+    // Existing files and other extensions are left alone
+    let existing = temp.path().join("project/main.rs");
+    fs::write(&existing, "").unwrap();
+    harness.open_file(&existing).unwrap();
+    harness.assert_buffer_content("");
+    harness
+        .open_file(&temp.path().join("project/notes.txt"))
+        .unwrap();
+    harness.assert_buffer_content("");
+}
+
+#[test]
+fn test_save_as_fills_empty_buffer() {
+    let (temp, mut harness) = harness_with_templates(&[("readme.md", "# ${basename}\n")]);
+    harness.new_buffer().unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("notes.md").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("# notes\n");
+    assert_eq!(
+        fs::read_to_string(temp.path().join("project/notes.md")).unwrap(),
+        "# notes\n"
+    );
+}
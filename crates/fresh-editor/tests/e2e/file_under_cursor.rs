@@ -0,0 +1,105 @@
+//! E2E tests for opening the file path under the cursor
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::path::Path;
+
+/// Run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Put the cursor on column `column` (0-based) of the first line
+fn move_to_column(harness: &mut EditorTestHarness, column: usize) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..column {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+}
+
+fn active_path(harness: &EditorTestHarness) -> Option<std::path::PathBuf> {
+    harness
+        .editor()
+        .active_state()
+        .buffer
+        .file_path()
+        .map(Path::to_path_buf)
+}
+
+/// A path relative to the project root opens at its `:line:col` suffix; a
+/// path relative to the buffer's directory opens too
+#[test]
+fn test_open_file_under_cursor_at_line_and_column() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::create_dir_all(root.join("docs")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+    std::fs::write(root.join("docs/guide.md"), "see usage.md.\n").unwrap();
+    std::fs::write(root.join("docs/usage.md"), "usage\n").unwrap();
+    std::fs::write(
+        root.join("build.log"),
+        "error at src/lib.rs:3:4, see docs\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::with_working_dir(100, 24, root.clone()).unwrap();
+    harness.open_file(&root.join("build.log")).unwrap();
+    harness.render().unwrap();
+
+    move_to_column(&mut harness, 12);
+    run_command(&mut harness, "Open File Under Cursor");
+    assert_eq!(active_path(&harness), Some(root.join("src/lib.rs")));
+    // Line 3, column 4 is the `c` of `fn c()`
+    assert_eq!(harness.cursor_position(), 20 + 3);
+
+    harness.open_file(&root.join("docs/guide.md")).unwrap();
+    move_to_column(&mut harness, 6);
+    run_command(&mut harness, "Open File Under Cursor");
+    assert_eq!(active_path(&harness), Some(root.join("docs/usage.md")));
+}
+
+/// Include paths are searched, the split variant opens a new split, and a
+/// missing file or a cursor off any path only shows a message
+#[test]
+fn test_open_file_under_cursor_from_include_paths_in_split() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    std::fs::create_dir_all(root.join("include")).unwrap();
+    std::fs::write(root.join("include/util.h"), "int util(void);\n").unwrap();
+    std::fs::write(root.join("main.c"), "#include \"util.h\" // missing.h\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.include_paths = vec!["include".to_string()];
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, config, root.clone()).unwrap();
+    harness.open_file(&root.join("main.c")).unwrap();
+    harness.render().unwrap();
+
+    move_to_column(&mut harness, 18);
+    run_command(&mut harness, "Open File Under Cursor");
+    harness.assert_screen_contains("No file path under the cursor");
+    assert_eq!(active_path(&harness), Some(root.join("main.c")));
+
+    move_to_column(&mut harness, 24);
+    run_command(&mut harness, "Open File Under Cursor");
+    harness.assert_screen_contains("File not found: missing.h");
+    assert_eq!(active_path(&harness), Some(root.join("main.c")));
+
+    move_to_column(&mut harness, 12);
+    run_command(&mut harness, "Open File Under Cursor in Split");
+    assert_eq!(harness.editor().get_split_count(), 2);
+    assert_eq!(active_path(&harness), Some(root.join("include/util.h")));
+}
@@ -0,0 +1,54 @@
+//! E2E tests for plugin floating panels (showFloatingPanel / hideFloatingPanel)
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::plugins::api::{FloatingPanelOptions, PluginCommand};
+
+fn panel(id: &str, lines: &[&str]) -> FloatingPanelOptions {
+    FloatingPanelOptions {
+        id: id.to_string(),
+        lines: lines.iter().map(|l| l.to_string()).collect(),
+        title: Some("Keys".to_string()),
+        anchor: Some("center".to_string()),
+        x: None,
+        y: None,
+        width: None,
+        height: None,
+        border: None,
+        z_index: None,
+    }
+}
+
+#[test]
+fn test_floating_panel_show_update_hide() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::ShowFloatingPanel {
+            options: panel("keys", &["a  first entry"]),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Keys");
+    harness.assert_screen_contains("a  first entry");
+
+    // Same id replaces the panel rather than stacking a second one
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::ShowFloatingPanel {
+            options: panel("keys", &["b  second entry"]),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("b  second entry");
+    harness.assert_screen_not_contains("a  first entry");
+
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::HideFloatingPanel {
+            panel_id: "keys".to_string(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("b  second entry");
+}
@@ -0,0 +1,124 @@
+//! E2E tests for the built-in side-by-side git diff view
+
+use crate::common::git_test_helper::GitTestRepo;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn select_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    select_command(harness, name);
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+fn repo_with_committed_file() -> GitTestRepo {
+    let repo = GitTestRepo::new();
+    repo.create_file("notes.txt", "alpha\nbravo\ncharlie\n");
+    repo.git_add_all();
+    repo.git_commit("Initial commit");
+    repo
+}
+
+#[test]
+fn test_diff_against_head_opens_side_by_side() {
+    let repo = repo_with_committed_file();
+    repo.modify_file("notes.txt", "alpha\nBRAVO\ncharlie\ndelta\n");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+
+    run_command(&mut harness, "Git: Diff File Against HEAD");
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("*Diff: notes.txt (HEAD)*"), "{}", screen);
+    assert!(screen.contains("Working"), "{}", screen);
+    // Both sides of the modified line are visible on the same row
+    let row = screen
+        .lines()
+        .find(|l| l.contains("bravo"))
+        .unwrap_or_else(|| panic!("old line missing:\n{}", screen));
+    assert!(row.contains("BRAVO"), "{}", screen);
+    assert!(screen.contains("delta"), "{}", screen);
+
+    // The snapshot buffers are hidden from the tab bar
+    assert!(!screen.contains("*HEAD:notes.txt*"), "{}", screen);
+}
+
+#[test]
+fn test_diff_against_index_reports_no_changes() {
+    let repo = repo_with_committed_file();
+    repo.modify_file("notes.txt", "alpha\nbravo\ncharlie\necho\n");
+    repo.stage_file("notes.txt");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+
+    run_command(&mut harness, "Git: Diff File Against Index");
+    harness.render().unwrap();
+    harness.assert_screen_contains("No differences against Index");
+}
+
+#[test]
+fn test_diff_against_revision_prompt() {
+    let repo = repo_with_committed_file();
+    repo.modify_file("notes.txt", "alpha\ncharlie\n");
+    repo.git_add_all();
+    repo.git_commit("Drop bravo");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+
+    select_command(&mut harness, "Git: Diff File Against Revision");
+    harness
+        .wait_for_screen_contains("Diff against revision:")
+        .unwrap();
+    harness.type_text("HEAD~1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("*Diff: notes.txt (HEAD~1)*"), "{}", screen);
+    assert!(screen.contains("bravo"), "{}", screen);
+
+    // Closing the diff tab returns to the file, not to a leftover snapshot
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    let screen = harness.screen_to_string();
+    assert!(!screen.contains("*Diff:"), "{}", screen);
+    assert!(!screen.contains("HEAD~1:notes.txt"), "{}", screen);
+    harness.assert_screen_contains("notes.txt");
+}
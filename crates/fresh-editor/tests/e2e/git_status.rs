@@ -0,0 +1,118 @@
+//! E2E tests for the built-in git branch/status integration
+
+use crate::common::git_test_helper::GitTestRepo;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::process::Command;
+
+fn git(repo: &GitTestRepo, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn select_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+fn repo_on_branch(branch: &str) -> GitTestRepo {
+    let repo = GitTestRepo::new();
+    repo.create_file("notes.txt", "alpha\n");
+    repo.git_add_all();
+    repo.git_commit("Initial commit");
+    git(&repo, &["checkout", "-q", "-b", branch]);
+    repo
+}
+
+fn harness_for(repo: &GitTestRepo) -> EditorTestHarness {
+    EditorTestHarness::with_config_and_working_dir(120, 30, Config::default(), repo.path.clone())
+        .unwrap()
+}
+
+fn wait_for_branch(harness: &mut EditorTestHarness, branch: &str, dirty: bool) {
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .git_status()
+                .is_some_and(|s| s.branch == branch && s.dirty == dirty)
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_status_bar_shows_branch_and_dirty_state() {
+    let repo = repo_on_branch("topic");
+    let mut harness = harness_for(&repo);
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+
+    wait_for_branch(&mut harness, "topic", false);
+    harness.assert_screen_contains(" topic ");
+
+    // Saving an edit makes the working tree dirty and refreshes the indicator
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    wait_for_branch(&mut harness, "topic", true);
+    harness.render().unwrap();
+    harness.assert_screen_contains(" topic* ");
+}
+
+#[test]
+fn test_checkout_branch_from_picker() {
+    let repo = repo_on_branch("topic");
+    git(&repo, &["branch", "feature"]);
+    let mut harness = harness_for(&repo);
+
+    wait_for_branch(&mut harness, "topic", false);
+    select_command(&mut harness, "Git: Checkout Branch");
+    harness
+        .wait_for_screen_contains("Checkout branch:")
+        .unwrap();
+    harness.assert_screen_contains("current");
+    harness.type_text("feature").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    wait_for_branch(&mut harness, "feature", false);
+    assert_eq!(
+        git(&repo, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        "feature"
+    );
+    harness
+        .wait_for_screen_contains("git checkout feature finished")
+        .unwrap();
+}
+
+#[test]
+fn test_show_log_opens_history_buffer() {
+    let repo = repo_on_branch("topic");
+    repo.modify_file("notes.txt", "alpha\nbravo\n");
+    repo.git_add_all();
+    repo.git_commit("Add bravo");
+    let mut harness = harness_for(&repo);
+
+    select_command(&mut harness, "Git: Show Log");
+    harness.wait_for_screen_contains("*Git History*").unwrap();
+    harness.assert_screen_contains("Add bravo");
+    harness.assert_screen_contains("Initial commit");
+}
@@ -0,0 +1,69 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn start_global_command(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Global Command").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+}
+
+/// The prompt previews the matching lines, and deleting them is undone in
+/// one step
+#[test]
+fn test_global_delete_preview_and_undo() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let text = "keep 1\n// TODO one\nkeep 2\n// TODO two";
+    harness.type_text(text).unwrap();
+
+    start_global_command(&mut harness);
+    harness.type_text("g/TODO/d").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("2 lines");
+    harness.assert_screen_contains("// TODO two");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("keep 1\nkeep 2");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(text);
+}
+
+/// `normal` runs actions and types text on each line, and `v` picks the
+/// lines that don't match
+#[test]
+fn test_global_normal_and_inverted_indent() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let text = "let a = 1\n\nlet b = 2\nfn f() {}";
+    harness.type_text(text).unwrap();
+
+    start_global_command(&mut harness);
+    harness
+        .type_text("g/^let/normal move_line_end \";\"")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("let a = 1;\n\nlet b = 2;\nfn f() {}");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(text);
+
+    start_global_command(&mut harness);
+    harness.type_text("v/^let/>").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("let a = 1\n\nlet b = 2\n    fn f() {}");
+}
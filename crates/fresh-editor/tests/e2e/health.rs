@@ -0,0 +1,90 @@
+//! E2E tests for the health check
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, FormatterConfig};
+use fresh::services::health::HealthStatus;
+
+fn config_with_formatter(command: &str) -> Config {
+    let mut config = Config::default();
+    config.clipboard.use_system_clipboard = false;
+    let rust = config.languages.get_mut("rust").unwrap();
+    rust.formatter = Some(FormatterConfig {
+        command: command.to_string(),
+        args: Vec::new(),
+        stdin: true,
+        timeout_ms: 10000,
+    });
+    rust.format_on_save = true;
+    config
+}
+
+#[test]
+fn test_health_buffer_shows_sections() {
+    let mut harness =
+        EditorTestHarness::with_config(120, 40, config_with_formatter("rustfmt")).unwrap();
+    harness.editor_mut().show_health();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Health*");
+    harness.assert_screen_contains("── Terminal ──");
+    harness.assert_screen_contains("Keyboard protocol");
+    harness.assert_screen_contains("── Configuration ──");
+}
+
+#[test]
+fn test_missing_formatter_is_reported_with_fix() {
+    let mut harness = EditorTestHarness::with_config(
+        160,
+        40,
+        config_with_formatter("fresh-test-missing-formatter"),
+    )
+    .unwrap();
+
+    let report = harness.editor().health_report();
+    let formatters = report
+        .sections
+        .iter()
+        .find(|section| section.title == "Formatters")
+        .unwrap();
+    let rust = formatters
+        .checks
+        .iter()
+        .find(|check| check.label == "rust")
+        .unwrap();
+    // Formatting on save can't work, so this is more than a note
+    assert_eq!(rust.status, HealthStatus::Warning);
+    assert!(rust
+        .detail
+        .contains("fresh-test-missing-formatter not found"));
+    assert!(rust
+        .fix
+        .as_deref()
+        .unwrap()
+        .contains("languages.rust.formatter"));
+
+    // r runs the checks again in the health buffer
+    harness.editor_mut().show_health();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("problem(s) found");
+}
+
+#[test]
+fn test_unknown_keybinding_action_is_reported() {
+    let mut config = config_with_formatter("rustfmt");
+    config.keybindings.push(fresh::config::Keybinding {
+        key: "k".to_string(),
+        modifiers: vec!["ctrl".to_string()],
+        keys: Vec::new(),
+        action: "no_such_action".to_string(),
+        args: Default::default(),
+        when: None,
+    });
+    let harness = EditorTestHarness::with_config(120, 40, config).unwrap();
+
+    let text = harness.editor().health_report().to_text();
+    assert!(text.contains("ctrl+k: unknown action 'no_such_action'"));
+}
@@ -0,0 +1,75 @@
+//! E2E tests for the history scrubber
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn harness_with_edits(text: &str) -> EditorTestHarness {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text(text).unwrap();
+    harness
+}
+
+#[test]
+fn test_scrubber_previews_and_cancel_restores() {
+    let mut harness = harness_with_edits("abc");
+    harness.editor_mut().open_history_scrubber();
+    harness.render().unwrap();
+    assert!(harness.editor().is_history_scrubber_open());
+    harness.assert_screen_contains("History 3/3");
+
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "a");
+    harness.assert_screen_contains("History 1/3");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+    harness.assert_screen_contains("History 0/3 · original");
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "a");
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert!(!harness.editor().is_history_scrubber_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "abc");
+    harness.assert_screen_not_contains("History 1/3");
+}
+
+#[test]
+fn test_scrubber_keep_forks_history() {
+    let mut harness = harness_with_edits("abc");
+    harness.editor_mut().open_history_scrubber();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(!harness.editor().is_history_scrubber_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "ab");
+
+    // Editing from the kept point replaces the later history
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "abX");
+    harness
+        .send_key(KeyCode::Char('y'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "abX");
+
+    harness.editor_mut().open_history_scrubber();
+    harness.render().unwrap();
+    harness.assert_screen_contains("History 3/3");
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "abX");
+}
+
+#[test]
+fn test_scrubber_requires_history() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.editor_mut().open_history_scrubber();
+    assert!(!harness.editor().is_history_scrubber_open());
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("No edit history for this buffer")
+    );
+}
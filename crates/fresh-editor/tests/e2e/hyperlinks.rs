@@ -0,0 +1,138 @@
+//! E2E tests for URLs: terminal hyperlinks and opening links
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::style::Modifier;
+
+const URL: &str = "https://example.com/docs";
+
+/// Run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, command_name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(command_name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Screen cell of the first character of the URL
+fn url_cell(harness: &EditorTestHarness) -> (u16, u16) {
+    let (byte, y) = harness.find_text_on_screen(URL).unwrap();
+    let row = harness.get_row_text(y);
+    (row[..byte as usize].chars().count() as u16, y)
+}
+
+/// URLs are underlined and their cells are handed to the backend as links;
+/// "Open Link" opens the URL under the cursor
+#[test]
+fn test_url_is_linked_and_opened_under_cursor() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text(&format!("Docs at {} (online)\n", URL))
+        .unwrap();
+    harness.editor_mut().record_opened_links_for_test();
+    harness.render().unwrap();
+
+    let (x, y) = url_cell(&harness);
+    let links = harness.editor().screen_links();
+    assert_eq!(links.url_at(x, y).as_deref(), Some(URL));
+    assert_eq!(
+        links.url_at(x + URL.len() as u16 - 1, y).as_deref(),
+        Some(URL)
+    );
+    assert_eq!(links.url_at(x - 1, y), None);
+    assert_eq!(links.url_at(x + URL.len() as u16, y), None);
+    let style = harness.get_cell_style(x, y).unwrap();
+    assert!(style.add_modifier.contains(Modifier::UNDERLINED));
+
+    // Cursor at the start of the line: nothing to open
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Open Link");
+    harness.assert_screen_contains("No link under the cursor");
+    assert!(harness.editor().opened_links().is_empty());
+
+    // Inside the URL
+    for _ in 0..12 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    run_command(&mut harness, "Open Link");
+    assert_eq!(harness.editor().opened_links(), [URL]);
+}
+
+/// Ctrl+Click opens a link; a plain click just moves the cursor
+#[test]
+fn test_ctrl_click_opens_link() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text(&format!("see {}\n", URL))
+        .unwrap();
+    harness.editor_mut().record_opened_links_for_test();
+    harness.render().unwrap();
+    let (x, y) = url_cell(&harness);
+
+    harness.mouse_click(x + 3, y).unwrap();
+    assert!(harness.editor().opened_links().is_empty());
+    assert_eq!(harness.cursor_position(), 4 + 3);
+
+    // Another cell, so this isn't taken for a double click
+
+    for kind in [
+        MouseEventKind::Down(MouseButton::Left),
+        MouseEventKind::Up(MouseButton::Left),
+    ] {
+        harness
+            .send_mouse(MouseEvent {
+                kind,
+                column: x + 5,
+                row: y,
+                modifiers: KeyModifiers::CONTROL,
+            })
+            .unwrap();
+    }
+    assert_eq!(harness.editor().opened_links(), [URL]);
+}
+
+/// Only http, https and mailto links are opened, from the text or from an
+/// overlay a plugin added
+#[test]
+fn test_other_schemes_are_not_opened() {
+    use fresh::model::event::{Event, OverlayFace};
+    use fresh::view::overlay::OverlayNamespace;
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("file:///usr/bin/xterm\nplugin link\n")
+        .unwrap();
+    harness.editor_mut().record_opened_links_for_test();
+    harness
+        .apply_event(Event::AddOverlay {
+            namespace: Some(OverlayNamespace::from_string("plugin".to_string())),
+            range: 22..28,
+            face: OverlayFace::Foreground {
+                color: (100, 150, 255),
+            },
+            priority: 0,
+            message: None,
+            extend_to_line_end: false,
+            url: Some("file:///bin/sh".to_string()),
+        })
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Open Link");
+    harness.assert_screen_contains("Not opening file:///usr/bin/xterm");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    run_command(&mut harness, "Open Link");
+    harness.assert_screen_contains("Not opening file:///bin/sh");
+    assert!(harness.editor().opened_links().is_empty());
+}
@@ -0,0 +1,83 @@
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use fresh::config::{Config, ImagePreviewMode};
+use tempfile::TempDir;
+
+/// A 24-bit BMP, 4×2 pixels: the left half white, the right half black
+fn write_bmp(path: &std::path::Path) {
+    let (width, height) = (4u32, 2u32);
+    let row_size = width * 3;
+    let pixel_bytes = row_size * height;
+    let mut bmp = Vec::new();
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(54 + pixel_bytes).to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&54u32.to_le_bytes());
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&width.to_le_bytes());
+    bmp.extend_from_slice(&height.to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&24u16.to_le_bytes());
+    bmp.extend_from_slice(&[0; 24]);
+    for _ in 0..height {
+        for x in 0..width {
+            let value = if x < width / 2 { 255 } else { 0 };
+            bmp.extend_from_slice(&[value; 3]);
+        }
+    }
+    std::fs::write(path, bmp).unwrap();
+}
+
+fn harness_with_mode(mode: ImagePreviewMode) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.image_preview = mode;
+    EditorTestHarness::create(80, 24, HarnessOptions::new().with_config(config)).unwrap()
+}
+
+/// Without terminal graphics, an opened image is drawn as ASCII art under a
+/// header with its format and size
+#[test]
+fn test_image_opens_as_ascii_art() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("pixels.bmp");
+    write_bmp(&path);
+
+    let mut harness = harness_with_mode(ImagePreviewMode::Ascii);
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    let buffer_id = harness.editor().active_buffer();
+    assert!(harness.editor().is_image_preview(buffer_id));
+    harness.assert_screen_contains("pixels.bmp  BMP  4×2");
+    harness.assert_screen_contains("@@@@@@@@@@@@@@@@@@@@");
+    harness.assert_screen_not_contains("<00>");
+    assert!(harness.editor_mut().take_graphics_output().is_empty());
+}
+
+/// With the Kitty protocol the image is sent once after the frame, and
+/// deleted again when its buffer is closed
+#[test]
+fn test_image_drawn_with_kitty_graphics() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("pixels.bmp");
+    write_bmp(&path);
+
+    let mut harness = harness_with_mode(ImagePreviewMode::Kitty);
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    let output = String::from_utf8(harness.editor_mut().take_graphics_output()).unwrap();
+    assert!(output.starts_with("\x1b7"), "output: {output:?}");
+    assert!(output.contains("\x1b_Ga=T"), "output: {output:?}");
+    assert!(!harness.screen_to_string().contains("@@@@"));
+
+    // Unchanged frames don't resend the image
+    harness.render().unwrap();
+    assert!(harness.editor_mut().take_graphics_output().is_empty());
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(buffer_id).unwrap();
+    harness.render().unwrap();
+    let output = String::from_utf8(harness.editor_mut().take_graphics_output()).unwrap();
+    assert!(output.contains("\x1b_Ga=d"), "output: {output:?}");
+    assert!(!harness.editor().is_image_preview(buffer_id));
+}
@@ -0,0 +1,68 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, Keybinding};
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A bound key with a count steps the number after the cursor, and
+/// repeating it changes the same number again
+#[test]
+fn test_increment_with_count() {
+    let mut config = Config::default();
+    config.keybindings.push(Keybinding {
+        key: "a".to_string(),
+        modifiers: vec!["ctrl".to_string(), "alt".to_string()],
+        keys: vec![],
+        action: "increment".to_string(),
+        args: [("count".to_string(), serde_json::json!(10))].into(),
+        when: None,
+    });
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("width = 95; ok = true").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    let ctrl_alt = KeyModifiers::CONTROL | KeyModifiers::ALT;
+    harness.send_key(KeyCode::Char('a'), ctrl_alt).unwrap();
+    harness.assert_buffer_content("width = 105; ok = true");
+    harness.send_key(KeyCode::Char('a'), ctrl_alt).unwrap();
+    harness.assert_buffer_content("width = 115; ok = true");
+
+    // Words from the cycles toggle
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    run_command(&mut harness, "Decrement Number");
+    harness.assert_buffer_content("width = 115; ok = false");
+}
+
+/// With several cursors, the sequence command numbers the lines
+#[test]
+fn test_increment_sequence_multi_cursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("item 0\nitem 0\nitem 0").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    let ctrl_alt = KeyModifiers::CONTROL | KeyModifiers::ALT;
+    harness.send_key(KeyCode::Down, ctrl_alt).unwrap();
+    harness.send_key(KeyCode::Down, ctrl_alt).unwrap();
+
+    run_command(&mut harness, "Increment Numbers in Sequence");
+    harness.assert_buffer_content("item 1\nitem 2\nitem 3");
+
+    // One undo step reverts every cursor's edit
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("item 0\nitem 0\nitem 0");
+}
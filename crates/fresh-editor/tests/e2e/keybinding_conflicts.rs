@@ -0,0 +1,105 @@
+//! E2E tests for keybinding conflict detection and the conflicts panel
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, Keybinding};
+use fresh::input::keybindings::BindingOrigin;
+use fresh::services::plugins::api::PluginCommand;
+
+fn bind(harness: &mut EditorTestHarness, plugin: &str, keys: &str, command: &str) {
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::RegisterKeybinding {
+            plugin_name: plugin.to_string(),
+            keys: keys.to_string(),
+            command: command.to_string(),
+            context: None,
+        })
+        .unwrap();
+}
+
+/// Put the cursor on the first line of the panel mentioning `text`
+fn move_to_line(harness: &mut EditorTestHarness, text: &str) {
+    let content = harness.get_buffer_content().unwrap();
+    let offset = content.find(text).unwrap();
+    harness
+        .editor_mut()
+        .active_cursors_mut()
+        .primary_mut()
+        .position = offset;
+}
+
+#[test]
+fn test_plugin_conflict_is_reported_and_disabled() {
+    let mut harness = EditorTestHarness::new(160, 30).unwrap();
+    harness.editor_mut().check_keybinding_conflicts();
+    assert!(harness.editor().keybinding_conflicts().is_empty());
+
+    // A plugin taking Ctrl+F from the keymap is reported once
+    bind(&mut harness, "grep", "C-f", "live_grep");
+    assert!(harness.editor_mut().check_keybinding_conflicts());
+    assert!(!harness.editor_mut().check_keybinding_conflicts());
+    harness.render().unwrap();
+    harness.assert_screen_contains("1 keybinding conflict(s)");
+
+    harness.editor_mut().show_keybinding_conflicts();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Keybinding Conflicts*");
+    harness.assert_screen_contains("Ctrl+F  (normal)");
+    harness.assert_screen_contains("plugin grep");
+    harness.assert_screen_contains("keymap");
+
+    // d hands the key back to the keymap's action through the user's config
+    move_to_line(&mut harness, "wins");
+    harness
+        .send_key(KeyCode::Char('d'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("overriding plugin 'grep'");
+    let conflicts = harness.editor().keybinding_conflicts();
+    assert_eq!(conflicts[0].winner().origin, BindingOrigin::User);
+    assert!(harness
+        .editor()
+        .config()
+        .keybindings
+        .iter()
+        .any(|binding| binding.key == "f" && binding.modifiers == vec!["ctrl".to_string()]));
+}
+
+#[test]
+fn test_mode_shadowing_user_binding_and_rebind() {
+    let mut config = Config::default();
+    config.keybindings.push(Keybinding {
+        key: "g".to_string(),
+        modifiers: Vec::new(),
+        keys: Vec::new(),
+        action: "command_palette".to_string(),
+        args: Default::default(),
+        when: None,
+    });
+    let mut harness = EditorTestHarness::with_config(160, 30, config).unwrap();
+
+    // The conflicts panel's own g shadows the user's binding there
+    harness.editor_mut().show_keybinding_conflicts();
+    harness.render().unwrap();
+    harness.assert_screen_contains("G  (keybinding-conflicts buffers)");
+    harness.assert_screen_contains("mode keybinding-conflicts");
+    harness.assert_screen_contains("your config");
+
+    // Mode bindings can't be disabled
+    move_to_line(&mut harness, "G  (keybinding-conflicts");
+    harness
+        .send_key(KeyCode::Char('d'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("can't be disabled");
+
+    // r opens the keybinding editor on the user's binding of the key
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().is_keybinding_editor_active());
+    harness.assert_screen_contains("Edit Keybinding");
+    harness.assert_screen_contains("Action:  command_palette");
+}
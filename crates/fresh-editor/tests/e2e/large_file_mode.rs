@@ -671,3 +671,34 @@ fn test_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// Files over the threshold open without syntax highlighting or LSP, and the
+/// status bar says so
+#[test]
+fn test_large_file_disables_highlighting() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let small = temp_dir.path().join("small.rs");
+    let large = temp_dir.path().join("large.rs");
+    std::fs::write(&small, "fn main() {}\n")?;
+    std::fs::write(&large, "fn main() {}\n".repeat(200))?;
+
+    let mut config = fresh::config::Config::default();
+    config.editor.large_file_threshold_bytes = 1024;
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        140,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&small)?;
+    assert!(harness.has_highlighter());
+
+    harness.open_file(&large)?;
+    harness.render()?;
+    assert!(harness.editor().active_state().buffer.is_large_file());
+    assert!(!harness.has_highlighter());
+    harness.assert_screen_contains("Opened large.rs [large file, 2.5 KB");
+    harness.assert_screen_contains("fn main() {}");
+    Ok(())
+}
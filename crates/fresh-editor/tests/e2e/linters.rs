@@ -0,0 +1,110 @@
+//! E2E tests for linters run by the editor (languages without a server)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, LanguageConfig, LintTrigger, LinterConfig, LinterFormat};
+use tempfile::TempDir;
+
+/// Plain text files linted by `grep`, which reports each TODO as `line:text`
+fn config_with_linter(stdin: bool, run_on: LintTrigger) -> Config {
+    let linter = LinterConfig {
+        command: "grep".to_string(),
+        args: vec!["-n".to_string(), "TODO".to_string()],
+        stdin,
+        run_on,
+        format: LinterFormat::Regex,
+        pattern: r"^(?P<line>\d+):(?P<message>.*)$".to_string(),
+        json: Default::default(),
+        timeout_ms: 5000,
+        enabled: true,
+    };
+
+    let mut config = Config::default();
+    config.languages.insert(
+        "plaintext".to_string(),
+        LanguageConfig {
+            extensions: vec!["txt".to_string()],
+            filenames: vec![],
+            grammar: "plaintext".to_string(),
+            comment_prefix: None,
+            auto_indent: false,
+            highlighter: Default::default(),
+            textmate_grammar: None,
+            show_whitespace_tabs: true,
+            use_tabs: false,
+            tab_size: None,
+            formatter: None,
+            format_on_save: false,
+            on_save: vec![],
+            repl: None,
+            linters: vec![linter],
+        },
+    );
+    config
+}
+
+/// Messages of the diagnostics stored for all files
+fn diagnostic_messages(harness: &EditorTestHarness) -> Vec<String> {
+    harness
+        .editor()
+        .get_stored_diagnostics()
+        .values()
+        .flatten()
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect()
+}
+
+/// Findings show up when the file is opened and are replaced on save
+#[test]
+#[cfg_attr(not(unix), ignore = "Linters require Unix-like environment")]
+fn test_linter_runs_on_open_and_save() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "first\nTODO: second\n")?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config_with_linter(false, LintTrigger::Save),
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&file_path)?;
+    harness.wait_until(|h| diagnostic_messages(h) == ["TODO: second"])?;
+
+    let diagnostics = harness.editor().get_stored_diagnostics();
+    let diagnostic = &diagnostics.values().next().unwrap()[0];
+    assert_eq!(diagnostic.range.start.line, 1);
+    assert_eq!(diagnostic.source.as_deref(), Some("grep"));
+
+    // Saved changes replace the findings
+    harness.send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)?;
+    harness.type_text("TODO: first\nsecond\n")?;
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| diagnostic_messages(h) == ["TODO: first"])?;
+
+    Ok(())
+}
+
+/// Idle linters read the unsaved buffer after a pause in typing
+#[test]
+#[cfg_attr(not(unix), ignore = "Linters require Unix-like environment")]
+fn test_idle_linter_lints_unsaved_buffer() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "first\n")?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config_with_linter(true, LintTrigger::Idle),
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&file_path)?;
+    harness.type_text("TODO ")?;
+    harness.wait_until(|h| diagnostic_messages(h) == ["TODO first"])?;
+
+    // Nothing was saved
+    assert_eq!(std::fs::read_to_string(&file_path)?, "first\n");
+
+    Ok(())
+}
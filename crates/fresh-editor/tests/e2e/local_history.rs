@@ -0,0 +1,64 @@
+//! E2E tests for local history snapshots
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+use tempfile::TempDir;
+
+/// Open a file and save two versions of it
+fn saved_twice_harness() -> (TempDir, EditorTestHarness) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path, "one\ntwo\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.local_history_enabled = true;
+    let mut harness = EditorTestHarness::with_config(120, 30, config).unwrap();
+    harness.open_file(&path).unwrap();
+    for text in ["first ", "second "] {
+        harness.type_text(text).unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    harness.assert_buffer_content("first second one\ntwo\n");
+    (dir, harness)
+}
+
+#[test]
+fn test_saves_are_listed() {
+    let (_dir, mut harness) = saved_twice_harness();
+    harness.editor_mut().start_local_history_prompt();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Local history:");
+    assert_eq!(harness.screen_to_string().matches("saved").count(), 2);
+}
+
+#[test]
+fn test_restore_snapshot() {
+    let (dir, mut harness) = saved_twice_harness();
+    harness.editor_mut().start_local_history_prompt();
+    // The newest snapshot comes first; pick the first save
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Diff: file.txt*");
+    harness.assert_screen_contains("Snapshot ");
+
+    harness.editor_mut().restore_snapshot();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Snapshot restored");
+
+    let view = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(view).unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("first one\ntwo\n");
+    // Restoring doesn't save
+    assert_eq!(
+        fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+        "first second one\ntwo\n"
+    );
+}
@@ -0,0 +1,88 @@
+//! E2E tests for the `*Log*` buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tracing::Level;
+
+fn harness_with_log() -> EditorTestHarness {
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    let log = harness.editor().log_buffer().clone();
+    log.push(
+        Level::DEBUG,
+        "fresh::services::lsp::manager",
+        "Sending didOpen".to_string(),
+    );
+    log.push(
+        Level::ERROR,
+        "fresh::services::lsp::manager",
+        "rust-analyzer exited".to_string(),
+    );
+    log.push(
+        Level::WARN,
+        "fresh::services::plugins::thread",
+        "Slow plugin hook".to_string(),
+    );
+    harness.editor_mut().show_log();
+    harness.render().unwrap();
+    harness
+}
+
+#[test]
+fn test_log_shows_info_and_more_severe() {
+    let mut harness = harness_with_log();
+    harness.assert_screen_contains("*Log*");
+    harness.assert_screen_contains("rust-analyzer exited");
+    harness.assert_screen_contains("Slow plugin hook");
+    harness.assert_screen_not_contains("Sending didOpen");
+
+    // f shows debug messages too
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Char('f'), KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.render().unwrap();
+    harness.assert_screen_contains("Sending didOpen");
+}
+
+#[test]
+fn test_log_filters_by_source() {
+    let mut harness = harness_with_log();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Log: lsp");
+    harness.assert_screen_contains("rust-analyzer exited");
+    harness.assert_screen_not_contains("Slow plugin hook");
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Slow plugin hook");
+    harness.assert_screen_not_contains("rust-analyzer exited");
+}
+
+#[test]
+fn test_log_follows_new_messages() {
+    let mut harness = harness_with_log();
+    harness
+        .editor()
+        .log_buffer()
+        .push(Level::INFO, "fresh::app", "Opened a file".to_string());
+    harness
+        .editor()
+        .log_buffer()
+        .push(Level::DEBUG, "fresh::app", "Not shown at info".to_string());
+    assert!(harness.editor_mut().check_log_view());
+    assert!(!harness.editor_mut().check_log_view());
+    harness.render().unwrap();
+    harness.assert_screen_contains("Opened a file");
+    harness.assert_screen_not_contains("Not shown at info");
+    // The cursor stays at the end
+    assert_eq!(
+        harness.cursor_position(),
+        harness.get_buffer_content().unwrap().len()
+    );
+}
@@ -803,6 +803,7 @@ fn test_lsp_waiting_indicator() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -870,6 +871,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -965,6 +967,7 @@ fn test_semantic_tokens_range_preserves_overlays_on_edit() -> anyhow::Result<()>
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1058,6 +1061,7 @@ fn test_semantic_tokens_persist_on_enter_key() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1152,6 +1156,7 @@ fn test_semantic_tokens_overlays_shift_on_edit() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1254,6 +1259,7 @@ fn test_semantic_tokens_range_only_viewport_highlighting() -> anyhow::Result<()>
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1417,6 +1423,7 @@ fn test_lsp_completion_canceled_on_cursor_move() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1482,6 +1489,7 @@ fn test_lsp_cursor_animation() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -1550,6 +1558,7 @@ fn test_lsp_completion_canceled_on_text_edit() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -2134,6 +2143,7 @@ fn test_lsp_diagnostics_non_blocking() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -2291,6 +2301,7 @@ fn test_rust_analyzer_rename_real_scenario() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -2899,6 +2910,7 @@ fn test_lsp_progress_status_display() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -3059,6 +3071,7 @@ fn test_lsp_crash_detection_and_restart() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -3270,6 +3283,7 @@ fn test_pull_diagnostics_message_handling() -> anyhow::Result<()> {
     if let Some(bridge) = harness.editor().async_bridge() {
         let _ = bridge.sender().send(AsyncMessage::LspPulledDiagnostics {
             request_id: 1,
+            language: "rust".to_string(),
             uri: uri.as_str().to_string(),
             result_id: Some("test-result-id-123".to_string()),
             diagnostics: vec![diagnostic],
@@ -3316,6 +3330,7 @@ fn test_pull_diagnostics_unchanged_response() -> anyhow::Result<()> {
     if let Some(bridge) = harness.editor().async_bridge() {
         let _ = bridge.sender().send(AsyncMessage::LspPulledDiagnostics {
             request_id: 2,
+            language: "rust".to_string(),
             uri: uri.as_str().to_string(),
             result_id: Some("test-result-id-456".to_string()),
             diagnostics: Vec::new(), // Empty when unchanged
@@ -3355,6 +3370,7 @@ fn test_pull_diagnostics_auto_trigger_after_open() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -3433,6 +3449,7 @@ fn test_pull_diagnostics_result_id_tracking() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -3691,6 +3708,7 @@ fn test_stopped_lsp_does_not_auto_restart_on_edit() -> anyhow::Result<()> {
             auto_start: true, // Auto-start so it starts when we open the file
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -4014,6 +4032,7 @@ fn test_hover_popup_persists_within_symbol_and_popup() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -5654,6 +5673,7 @@ fn test_hover_does_not_autostart_lsp_when_disabled() -> anyhow::Result<()> {
             auto_start: false, // This is the key setting - LSP should NOT auto-start
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
     // Enable mouse hover in config
@@ -5739,6 +5759,7 @@ fn test_typing_does_not_autostart_lsp_when_disabled() -> anyhow::Result<()> {
             auto_start: false, // This is the key setting - LSP should NOT auto-start
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -5812,6 +5833,7 @@ fn test_completion_triggered_on_trigger_character() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -5892,6 +5914,7 @@ fn test_completion_triggered_on_word_char_with_quick_suggestions() -> anyhow::Re
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -5971,6 +5994,7 @@ fn test_completion_not_triggered_on_word_char_without_quick_suggestions() -> any
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -6052,6 +6076,7 @@ fn test_completion_not_triggered_on_non_word_char() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -6147,6 +6172,7 @@ fn test_hover_popup_follows_mouse_when_lsp_returns_no_range() -> anyhow::Result<
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -6370,6 +6396,7 @@ fn test_hover_does_not_trigger_past_end_of_line() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -6469,6 +6496,7 @@ fn test_hover_does_not_trigger_on_empty_line() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -6570,6 +6598,7 @@ fn test_hover_no_duplicate_popup_when_moving_within_symbol() -> anyhow::Result<(
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -7333,6 +7362,7 @@ log("STOPPED")
             auto_start: true,
             process_limits: fresh::types::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -7647,6 +7677,7 @@ log("STOPPED")
             auto_start: true,
             process_limits: fresh::types::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -7946,6 +7977,7 @@ log("STOPPED")
             auto_start: true,
             process_limits: fresh::types::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -8015,3 +8047,81 @@ log("STOPPED")
 
     Ok(())
 }
+
+/// Test that `$/progress` operations show a spinner with the title and
+/// percentage in the status bar and disappear when they end
+#[test]
+fn test_lsp_progress_spinner_in_status_bar() -> anyhow::Result<()> {
+    use fresh::services::async_bridge::{AsyncMessage, LspProgressValue};
+
+    let mut harness = EditorTestHarness::new(120, 24)?;
+    let send = |harness: &EditorTestHarness, token: &str, value: LspProgressValue| {
+        if let Some(bridge) = harness.editor().async_bridge() {
+            bridge
+                .sender()
+                .send(AsyncMessage::LspProgress {
+                    language: "rust".to_string(),
+                    token: token.to_string(),
+                    value,
+                })
+                .unwrap();
+        }
+    };
+
+    send(
+        &harness,
+        "rustAnalyzer/Indexing",
+        LspProgressValue::Begin {
+            title: "Indexing".to_string(),
+            message: None,
+            percentage: Some(0),
+        },
+    );
+    harness.wait_for_screen_contains("⠋ LSP (rust): Indexing (0%)")?;
+
+    send(
+        &harness,
+        "rustAnalyzer/Indexing",
+        LspProgressValue::Report {
+            message: Some("12/40".to_string()),
+            percentage: Some(30),
+        },
+    );
+    harness.wait_for_screen_contains("LSP (rust): Indexing - 12/40 (30%)")?;
+
+    // The spinner advances with time while the operation is running
+    harness.advance_time(std::time::Duration::from_millis(100));
+    harness.process_async_and_render()?;
+    harness.assert_screen_contains("⠙ LSP (rust): Indexing - 12/40 (30%)");
+
+    // The newest operation is shown, with a count of the others
+    harness.advance_time(std::time::Duration::from_millis(100));
+    send(
+        &harness,
+        "rustAnalyzer/Fetching",
+        LspProgressValue::Begin {
+            title: "Fetching".to_string(),
+            message: None,
+            percentage: None,
+        },
+    );
+    harness.wait_for_screen_contains("LSP (rust): Fetching +1")?;
+
+    send(
+        &harness,
+        "rustAnalyzer/Fetching",
+        LspProgressValue::End { message: None },
+    );
+    harness.wait_for_screen_contains("LSP (rust): Indexing - 12/40 (30%)")?;
+    harness.assert_screen_not_contains("+1");
+
+    send(
+        &harness,
+        "rustAnalyzer/Indexing",
+        LspProgressValue::End { message: None },
+    );
+    harness.wait_until(|h| !h.screen_to_string().contains("LSP (rust): Indexing"))?;
+    assert!(!harness.editor().has_active_lsp_progress());
+
+    Ok(())
+}
@@ -37,6 +37,7 @@ fn test_start_lsp_command_works_when_config_disabled() -> anyhow::Result<()> {
             auto_start: false,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -64,7 +65,7 @@ fn test_start_lsp_command_works_when_config_disabled() -> anyhow::Result<()> {
     harness.wait_for_prompt()?;
 
     // Type the command name and execute
-    harness.type_text("Start")?;
+    harness.type_text("Start/Restart LSP")?;
     harness.render()?;
 
     // Should find "Start/Restart LSP" command
@@ -123,6 +124,7 @@ fn test_settings_ui_lsp_enabled_change_takes_effect() -> anyhow::Result<()> {
             auto_start: true, // auto_start=true so it will start when enabled
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -247,6 +249,7 @@ fn test_lsp_manager_config_updated_via_set_lsp_config() -> anyhow::Result<()> {
             auto_start: false,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            additional_servers: vec![],
         },
     );
 
@@ -281,6 +284,7 @@ fn test_lsp_manager_config_updated_via_set_lsp_config() -> anyhow::Result<()> {
         auto_start: false,
         process_limits: fresh::services::process_limits::ProcessLimits::default(),
         initialization_options: None,
+        additional_servers: vec![],
     };
     harness
         .editor_mut()
@@ -289,7 +293,7 @@ fn test_lsp_manager_config_updated_via_set_lsp_config() -> anyhow::Result<()> {
     // Trigger LSP start via command
     harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
     harness.wait_for_prompt()?;
-    harness.type_text("Start")?;
+    harness.type_text("Start/Restart LSP")?;
     harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
     harness.render()?;
 
@@ -0,0 +1,110 @@
+//! E2E tests for LSP document/range formatting and format-on-save
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn formatting_harness(
+    content: &str,
+    format_on_save: bool,
+) -> anyhow::Result<(tempfile::TempDir, std::path::PathBuf, EditorTestHarness)> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, content)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::formatting_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            additional_servers: vec![],
+        },
+    );
+    let rust = config.languages.get_mut("rust").unwrap();
+    // No external formatter, so formatting goes through the language server
+    rust.formatter = None;
+    rust.format_on_save = format_on_save;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (rust) ready"))?;
+    Ok((temp_dir, test_file, harness))
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text(name)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    Ok(())
+}
+
+/// Format Document applies the server's edits as one undoable step
+#[test]
+fn test_format_document_applies_edits() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_formatting()?;
+    let (_temp_dir, _test_file, mut harness) = formatting_harness("fn main() {}\n", false)?;
+
+    run_command(&mut harness, "Format Document")?;
+    harness.wait_until(|h| {
+        h.get_buffer_content().as_deref() == Some("/* formatted */ fn main() {}\n")
+    })?;
+    harness.assert_screen_contains("Formatted (1 edits)");
+
+    harness.send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)?;
+    harness.assert_buffer_content("fn main() {}\n");
+    Ok(())
+}
+
+/// Format Selection sends only the selected range
+#[test]
+fn test_format_selection_formats_range() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_formatting()?;
+    let (_temp_dir, _test_file, mut harness) =
+        formatting_harness("fn main() {\nlet x = 1;\n}\n", false)?;
+
+    // Select the second line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT)?;
+    run_command(&mut harness, "Format Selection")?;
+    harness.wait_until(|h| {
+        h.get_buffer_content().as_deref() == Some("fn main() {\n    let x = 1;\n}\n")
+    })?;
+    Ok(())
+}
+
+/// With format_on_save and no external formatter, saving writes the
+/// server-formatted text
+#[test]
+fn test_format_on_save_uses_language_server() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_formatting()?;
+    let (_temp_dir, test_file, mut harness) = formatting_harness("fn main() {}\n", true)?;
+
+    harness.type_text("// x\n")?;
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)?;
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&test_file).is_ok_and(|s| s.starts_with("/* formatted */"))
+    })?;
+
+    assert_eq!(
+        std::fs::read_to_string(&test_file)?,
+        "/* formatted */ // x\nfn main() {}\n"
+    );
+    harness.assert_buffer_content("/* formatted */ // x\nfn main() {}\n");
+    harness.render()?;
+    harness.assert_screen_contains("Saved");
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! E2E tests for the LSP info view and server log
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::async_bridge::{AsyncMessage, LspMessageType};
+use fresh::services::lsp::LspServerConfig;
+use fresh::services::process_limits::ProcessLimits;
+
+fn harness_with_server() -> anyhow::Result<(tempfile::TempDir, EditorTestHarness)> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        LspServerConfig {
+            command: FakeLspServer::script_path().to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: ProcessLimits::default(),
+            initialization_options: None,
+            additional_servers: vec![],
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    harness.wait_until(|h| h.editor().is_lsp_server_ready("rust"))?;
+    Ok((temp_dir, harness))
+}
+
+/// Open the info view and put the cursor on the first server
+fn open_lsp_info(harness: &mut EditorTestHarness) -> anyhow::Result<()> {
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text("Show LSP Info")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("LSP Servers")?;
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    }
+    Ok(())
+}
+
+fn send_log_message(harness: &EditorTestHarness, message_type: LspMessageType, message: &str) {
+    if let Some(bridge) = harness.editor().async_bridge() {
+        bridge
+            .sender()
+            .send(AsyncMessage::LspLogMessage {
+                language: "rust".to_string(),
+                message_type,
+                message: message.to_string(),
+            })
+            .unwrap();
+    }
+}
+
+/// The info view shows the server's state and the features it supports
+#[test]
+fn test_lsp_info_shows_server_state_and_features() -> anyhow::Result<()> {
+    let _server = FakeLspServer::spawn()?;
+    let (_temp_dir, mut harness) = harness_with_server()?;
+
+    open_lsp_info(&mut harness)?;
+    harness.assert_screen_contains("rust (running)");
+    harness.assert_screen_contains("Command:");
+    harness.assert_screen_contains("Features:  completion, hover, definition");
+    harness.assert_screen_contains("Log:");
+    Ok(())
+}
+
+/// Keys in the info view stop and restart the server under the cursor
+#[test]
+fn test_lsp_info_stop_and_restart_server() -> anyhow::Result<()> {
+    let _server = FakeLspServer::spawn()?;
+    let (_temp_dir, mut harness) = harness_with_server()?;
+    open_lsp_info(&mut harness)?;
+
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("rust (not running)"))?;
+    assert!(harness.editor().running_lsp_servers().is_empty());
+
+    harness.send_key(KeyCode::Char('r'), KeyModifiers::NONE)?;
+    harness.wait_until(|h| h.screen_to_string().contains("rust (running)"))?;
+    assert_eq!(harness.editor().running_lsp_servers(), ["rust"]);
+    Ok(())
+}
+
+/// The log view lists the server's messages, filters them by level and
+/// picks up new ones while open
+#[test]
+fn test_lsp_log_view_filters_by_level() -> anyhow::Result<()> {
+    let _server = FakeLspServer::spawn()?;
+    let (_temp_dir, mut harness) = harness_with_server()?;
+    send_log_message(&harness, LspMessageType::Info, "indexing started");
+    send_log_message(&harness, LspMessageType::Error, "indexing failed");
+
+    open_lsp_info(&mut harness)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_for_screen_contains("LSP log: rust")?;
+    harness.assert_screen_contains("[info] indexing started");
+    harness.assert_screen_contains("[error] indexing failed");
+
+    // log -> info -> warning -> error
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Char('f'), KeyModifiers::NONE)?;
+    }
+    harness.assert_screen_contains("showing error and more severe");
+    harness.assert_screen_not_contains("indexing started");
+    harness.assert_screen_contains("[error] indexing failed");
+
+    send_log_message(&harness, LspMessageType::Error, "server panicked");
+    harness.wait_for_screen_contains("[error] server panicked")?;
+    Ok(())
+}
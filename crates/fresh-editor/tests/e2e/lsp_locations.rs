@@ -0,0 +1,146 @@
+//! E2E tests for LSP location navigation (definition, type definition,
+//! implementation and references)
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const SOURCE: &str = "struct Foo;\nfn make() -> Foo {\n    Foo\n}\nfn main() { make(); }\n";
+
+fn locations_harness() -> anyhow::Result<(tempfile::TempDir, EditorTestHarness)> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, SOURCE)?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::locations_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            additional_servers: vec![],
+        },
+    );
+
+    // No plugins: a references panel plugin would take over from the picker
+    let mut harness = EditorTestHarness::create(
+        120,
+        30,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf()),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    wait_for(&mut harness, |h| {
+        h.screen_to_string().contains("LSP (rust) ready")
+    })?;
+
+    // Put the cursor on `make` in the last line
+    harness.send_key(KeyCode::End, KeyModifiers::CONTROL)?;
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+    for _ in 0..13 {
+        harness.send_key(KeyCode::Right, KeyModifiers::NONE)?;
+    }
+    harness.render()?;
+    Ok((temp_dir, harness))
+}
+
+/// Wait for `condition`, failing instead of hanging when it never holds
+fn wait_for(
+    harness: &mut EditorTestHarness,
+    condition: impl FnMut(&EditorTestHarness) -> bool,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        harness.wait_for_async(condition, 10_000)?,
+        "timed out waiting for the language server"
+    );
+    Ok(())
+}
+
+fn status_contains(harness: &EditorTestHarness, text: &str) -> bool {
+    harness
+        .editor()
+        .get_status_message()
+        .is_some_and(|msg| msg.contains(text))
+}
+
+/// A single definition jumps directly and records the jump origin
+#[test]
+fn test_goto_definition_single_result_jumps() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_locations()?;
+    let (_temp_dir, mut harness) = locations_harness()?;
+    let origin = harness.cursor_position();
+
+    harness.send_key(KeyCode::F(12), KeyModifiers::NONE)?;
+    wait_for(&mut harness, |h| status_contains(h, "Jumped to definition"))?;
+
+    // Line 1, column 3 of SOURCE
+    assert_eq!(harness.cursor_position(), "struct Foo;\n".len() + 3);
+
+    // Navigating back returns to where the jump started
+    harness.send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)?;
+    harness.render()?;
+    assert_eq!(harness.cursor_position(), origin);
+    Ok(())
+}
+
+/// Several type definitions open a picker; choosing one jumps to it
+#[test]
+fn test_goto_type_definition_multiple_results_open_picker() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_locations()?;
+    let (_temp_dir, mut harness) = locations_harness()?;
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text("Go to Type Definition")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    wait_for(&mut harness, |h| {
+        h.screen_to_string().contains("Type definitions:")
+    })?;
+
+    harness.assert_screen_contains("test.rs:1");
+    harness.assert_screen_contains("test.rs:2");
+    harness.assert_screen_contains("fn make() -> Foo {");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(harness.cursor_position(), "struct Foo;\n".len() + 3);
+    Ok(())
+}
+
+/// An empty implementation result reports that nothing was found
+#[test]
+fn test_goto_implementation_no_results() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_locations()?;
+    let (_temp_dir, mut harness) = locations_harness()?;
+
+    harness.send_key(KeyCode::F(12), KeyModifiers::CONTROL)?;
+    wait_for(&mut harness, |h| {
+        status_contains(h, "No implementation found")
+    })?;
+    Ok(())
+}
+
+/// Multiple references without a references panel plugin open the picker
+#[test]
+fn test_find_references_opens_picker() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_locations()?;
+    let (_temp_dir, mut harness) = locations_harness()?;
+
+    harness.send_key(KeyCode::F(12), KeyModifiers::SHIFT)?;
+    wait_for(&mut harness, |h| {
+        h.screen_to_string().contains("References to 'make':")
+    })?;
+
+    harness.assert_screen_contains("test.rs:2");
+    harness.assert_screen_contains("test.rs:4");
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! E2E tests for attaching several language servers to one buffer
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::lsp::{AdditionalLspServerConfig, LspServerConfig};
+use fresh::services::process_limits::ProcessLimits;
+
+fn server_config(
+    script: std::path::PathBuf,
+    additional: Option<std::path::PathBuf>,
+) -> LspServerConfig {
+    LspServerConfig {
+        command: script.to_string_lossy().to_string(),
+        args: vec![],
+        enabled: true,
+        auto_start: true,
+        process_limits: ProcessLimits::default(),
+        initialization_options: None,
+        additional_servers: additional
+            .into_iter()
+            .map(|script| AdditionalLspServerConfig {
+                name: "second".to_string(),
+                command: script.to_string_lossy().to_string(),
+                args: vec![],
+                enabled: true,
+                process_limits: ProcessLimits::default(),
+                initialization_options: None,
+            })
+            .collect(),
+    }
+}
+
+fn multi_server_harness(
+    content: &str,
+    config: LspServerConfig,
+) -> anyhow::Result<(tempfile::TempDir, EditorTestHarness)> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, content)?;
+
+    let mut config_all = fresh::config::Config::default();
+    config_all.lsp.insert("rust".to_string(), config);
+    config_all.languages.get_mut("rust").unwrap().formatter = None;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config_all,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    harness.wait_until(|h| {
+        h.editor().is_lsp_server_ready("rust") && h.editor().is_lsp_server_ready("rust/second")
+    })?;
+    Ok((temp_dir, harness))
+}
+
+fn stored_messages(harness: &EditorTestHarness) -> Vec<String> {
+    let mut messages: Vec<String> = harness
+        .editor()
+        .get_stored_diagnostics()
+        .values()
+        .flatten()
+        .map(|d| d.message.clone())
+        .collect();
+    messages.sort();
+    messages
+}
+
+/// Diagnostics from each server are kept side by side instead of replacing
+/// each other
+#[test]
+fn test_diagnostics_from_all_servers_are_merged() -> anyhow::Result<()> {
+    let _main = FakeLspServer::spawn()?;
+    let _second = FakeLspServer::spawn_additional()?;
+    let (_temp_dir, mut harness) = multi_server_harness(
+        "fn main() {}\n",
+        server_config(
+            FakeLspServer::script_path(),
+            Some(FakeLspServer::additional_script_path()),
+        ),
+    )?;
+
+    harness.wait_until(|h| stored_messages(h) == ["Warning from second server"])?;
+
+    // The main server publishes its own diagnostics on save
+    harness.send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| {
+        stored_messages(h) == ["Test error from fake LSP", "Warning from second server"]
+    })?;
+    Ok(())
+}
+
+/// Completion asks every server and shows their items in one popup
+#[test]
+fn test_completions_from_all_servers_are_merged() -> anyhow::Result<()> {
+    let _main = FakeLspServer::spawn()?;
+    let _second = FakeLspServer::spawn_additional()?;
+    let (_temp_dir, mut harness) = multi_server_harness(
+        "fn main() {}\n",
+        server_config(
+            FakeLspServer::script_path(),
+            Some(FakeLspServer::additional_script_path()),
+        ),
+    )?;
+
+    harness.send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| h.screen_to_string().contains("second_server_item"))?;
+    harness.assert_screen_contains("test_variable");
+
+    // The item both servers offer is listed once, with the main server's detail
+    let screen = harness.screen_to_string();
+    assert_eq!(screen.matches("test_function").count(), 2, "{screen}");
+    assert!(screen.contains("fn test_function()"), "{screen}");
+    Ok(())
+}
+
+/// Formatting goes to the first server that supports it
+#[test]
+fn test_formatting_uses_server_that_supports_it() -> anyhow::Result<()> {
+    let _main = FakeLspServer::spawn_additional()?;
+    let _formatter = FakeLspServer::spawn_with_formatting()?;
+    let config = server_config(
+        FakeLspServer::additional_script_path(),
+        Some(FakeLspServer::formatting_script_path()),
+    );
+    let (_temp_dir, mut harness) = multi_server_harness("fn main() {}\n", config)?;
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_for_prompt()?;
+    harness.type_text("Format Document")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.wait_until(|h| {
+        h.get_buffer_content().as_deref() == Some("/* formatted */ fn main() {}\n")
+    })?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! E2E tests for LSP signature help while typing call arguments
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn signature_help_harness() -> anyhow::Result<(tempfile::TempDir, EditorTestHarness)> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::signature_help_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            additional_servers: vec![],
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (rust) ready"))?;
+    Ok((temp_dir, harness))
+}
+
+/// Typing `(` opens signature help, and it follows the active parameter
+/// until the call is closed
+#[test]
+fn test_signature_help_tracks_active_parameter() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_signature_help()?;
+    let (_temp_dir, mut harness) = signature_help_harness()?;
+
+    harness.type_text("add(")?;
+    harness.wait_until(|h| h.screen_to_string().contains("Signature 1/2"))?;
+    harness.assert_screen_contains("add(a: i32, b: i32) -> i32");
+    harness.assert_screen_contains("Adds two integers");
+
+    // The first parameter is highlighted
+    let (x, y) = harness.find_text_on_screen("a: i32").unwrap();
+    let style = harness.get_cell_style(x, y).unwrap();
+    assert!(style
+        .add_modifier
+        .contains(ratatui::style::Modifier::UNDERLINED));
+
+    // Typing the first argument keeps the popup open; `,` moves to `b`
+    harness.type_text("1, ")?;
+    harness.wait_until(|h| {
+        h.find_text_on_screen("b: i32").is_some_and(|(x, y)| {
+            h.get_cell_style(x, y).is_some_and(|s| {
+                s.add_modifier
+                    .contains(ratatui::style::Modifier::UNDERLINED)
+            })
+        })
+    })?;
+
+    // Leaving the call closes the popup
+    harness.type_text("2)")?;
+    harness.wait_until(|h| !h.screen_to_string().contains("Signature 1/2"))?;
+    Ok(())
+}
+
+/// Alt+Down and Alt+Up cycle through the overloads
+#[test]
+fn test_signature_help_cycles_overloads() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_signature_help()?;
+    let (_temp_dir, mut harness) = signature_help_harness()?;
+
+    harness.type_text("add(")?;
+    harness.wait_until(|h| h.screen_to_string().contains("Signature 1/2"))?;
+
+    harness.send_key(KeyCode::Down, KeyModifiers::ALT)?;
+    harness.render()?;
+    harness.assert_screen_contains("Signature 2/2");
+    harness.assert_screen_contains("add(x: f64, y: f64) -> f64");
+    harness.assert_screen_contains("Adds two floats");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::ALT)?;
+    harness.render()?;
+    harness.assert_screen_contains("Signature 1/2");
+
+    harness.send_key(KeyCode::Up, KeyModifiers::ALT)?;
+    harness.render()?;
+    harness.assert_screen_contains("Signature 2/2");
+
+    // Other keys still dismiss the popup
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_not_contains("Signature 2/2");
+    Ok(())
+}
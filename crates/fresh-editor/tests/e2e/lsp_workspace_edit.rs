@@ -0,0 +1,70 @@
+//! E2E tests for workspace edits requested by the server (workspace/applyEdit)
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+
+/// An edit spanning an open buffer and a file that isn't loaded changes the
+/// buffer, rewrites the other file on disk and tells the server about it
+#[test]
+fn test_apply_edit_updates_buffer_and_unopened_file() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_apply_edit()?;
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    let other_file = temp_dir.path().join("other.rs");
+    let log_file = temp_dir.path().join("lsp.log");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+    std::fs::write(&other_file, "fn other() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::apply_edit_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![log_file.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            additional_servers: vec![],
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.wait_until(|h| h.get_buffer_content().as_deref() == Some("/* a */ fn main() {}\n"))?;
+    harness.wait_until(|h| {
+        h.screen_to_string()
+            .contains("Add headers: changed 2 file(s)")
+    })?;
+
+    // The unopened file is edited on disk, the open one is left unsaved
+    assert_eq!(
+        std::fs::read_to_string(&other_file)?,
+        "/* b */ fn other() {}\n"
+    );
+    assert_eq!(std::fs::read_to_string(&test_file)?, "fn main() {}\n");
+
+    // The server hears about the file it changed and gets a positive answer
+    harness.wait_until(|_| {
+        std::fs::read_to_string(&log_file)
+            .is_ok_and(|log| log.contains("textDocument/didSave") && log.contains("response"))
+    })?;
+    let log = std::fs::read_to_string(&log_file)?;
+    for method in ["didOpen", "didChange", "didSave"] {
+        assert!(
+            log.lines()
+                .any(|line| line.starts_with(&format!("textDocument/{method} "))
+                    && line.ends_with("/other.rs")),
+            "no {method} for other.rs in:\n{log}"
+        );
+    }
+    assert!(log.contains(r#""applied":true"#), "{log}");
+    Ok(())
+}
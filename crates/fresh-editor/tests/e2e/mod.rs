@@ -1,17 +1,25 @@
+pub mod action_console;
 pub mod altgr_shift;
 pub mod ansi_cursor;
 pub mod auto_indent;
 pub mod auto_revert;
 pub mod basic;
+pub mod batch;
 pub mod binary_file;
 pub mod block_selection;
 pub mod blog_showcases;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
+pub mod buffers_panel;
 pub mod case_conversion;
+pub mod collab;
 pub mod command_palette;
+pub mod control_socket;
 pub mod crash_repro;
 pub mod crlf_rendering;
+pub mod damage;
+pub mod debugger;
+pub mod diff_mode;
 pub mod document_model;
 pub mod duplicate_line;
 pub mod emacs_actions;
@@ -20,8 +28,20 @@ pub mod explorer_menu;
 pub mod file_browser;
 pub mod file_explorer;
 pub mod file_permissions;
+pub mod file_templates;
+pub mod file_under_cursor;
+pub mod floating_panel;
+pub mod git_diff_view;
+pub mod git_status;
+pub mod global_command;
+pub mod health;
+pub mod history_scrubber;
 pub mod horizontal_scrollbar;
+pub mod hyperlinks;
+pub mod image_preview;
+pub mod increment;
 pub mod indent_dedent;
+pub mod keybinding_conflicts;
 pub mod keybinding_editor;
 pub mod language_features_e2e;
 pub mod large_file_inplace_write_bug;
@@ -29,13 +49,22 @@ pub mod large_file_mode;
 pub mod lifecycle;
 pub mod line_wrap_scroll_bugs;
 pub mod line_wrapping;
+pub mod linters;
 pub mod live_grep;
+pub mod local_history;
 pub mod locale;
+pub mod log_view;
 pub mod lsp;
 pub mod lsp_completion_french_locale;
 pub mod lsp_completion_popup_behavior;
 pub mod lsp_config;
+pub mod lsp_formatting;
+pub mod lsp_info;
+pub mod lsp_locations;
+pub mod lsp_multiple_servers;
 pub mod lsp_order;
+pub mod lsp_signature_help;
+pub mod lsp_workspace_edit;
 pub mod macros;
 pub mod margin;
 pub mod markdown_compose;
@@ -49,6 +78,8 @@ pub mod multicursor;
 pub mod on_save_actions;
 pub mod open_folder;
 pub mod paste;
+pub mod perf_hud;
+pub mod plugin_decorations;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod popup_selection;
@@ -56,11 +87,17 @@ pub mod position_history;
 pub mod position_history_bugs;
 pub mod position_history_debug;
 pub mod position_history_truncate_debug;
+pub mod project_search;
 pub mod prompt;
 pub mod prompt_editing;
+pub mod prompt_history;
+pub mod prompt_multi_select;
+pub mod read_only;
 pub mod recovery;
 pub mod remote_fs_test;
 pub mod rendering;
+pub mod repeat_edit;
+pub mod replay;
 pub mod save_as_language_detection;
 pub mod scroll_clearing;
 pub mod scrolling;
@@ -93,12 +130,17 @@ pub mod test_scrollbar_keybinds_cursor;
 pub mod theme;
 pub mod toggle_bars;
 pub mod toggle_comment;
+pub mod trailing_whitespace;
 pub mod triple_click;
+pub mod tutor;
 pub mod undo_bulk_edit_after_save;
 pub mod undo_redo;
 pub mod unicode_cursor;
+pub mod unicode_picker;
 pub mod unicode_prompt_bugs;
+pub mod unsaved_changes;
 pub mod update_notification;
+pub mod user_commands;
 pub mod vertical_rulers;
 #[cfg(feature = "plugins")]
 pub mod vi_mode;
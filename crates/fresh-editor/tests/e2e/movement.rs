@@ -670,3 +670,103 @@ fn test_movement_through_multiple_empty_lines() {
         "Should be somewhere on Line 6, got position {final_pos}"
     );
 }
+
+/// Test that Home toggles between the first non-blank character and column 0
+#[test]
+fn test_smart_home_toggles() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("    let value = 1;").unwrap();
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 4, "Home goes to the indentation");
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 0, "Home again goes to column 0");
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), 4);
+
+    // Shift+Home selects back to the indentation
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 4);
+    let selection = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range();
+    assert_eq!(selection, Some(4..18));
+}
+
+/// Test that Alt+Left/Right, and Alt+Shift to select, stop inside camelCase,
+/// snake_case and digits
+#[test]
+fn test_subword_movement() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("getHTTPResponse my_var2x").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    let mut stops = Vec::new();
+    for _ in 0..7 {
+        harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+        stops.push(harness.cursor_position());
+    }
+    // get|HTTP|Response |my_|var|2|x|
+    assert_eq!(stops, vec![3, 7, 16, 19, 22, 23, 24]);
+
+    let mut stops = Vec::new();
+    for _ in 0..7 {
+        harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+        stops.push(harness.cursor_position());
+    }
+    assert_eq!(stops, vec![23, 22, 19, 16, 7, 3, 0]);
+
+    // Alt+Shift selects by sub-word
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    let selection = harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range();
+    assert_eq!(selection, Some(0..3));
+}
+
+/// Ctrl+Left/Right move by whole words unless `editor.subword_movement` is on
+#[test]
+fn test_word_movement_with_and_without_subword_setting() {
+    for (subword_movement, expected, selected) in
+        [(false, vec![15, 24], 16..24), (true, vec![3, 7], 3..7)]
+    {
+        let mut config = fresh::config::Config::default();
+        config.editor.subword_movement = subword_movement;
+        let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+        harness.type_text("getHTTPResponse my_var2x").unwrap();
+        harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+        let mut stops = Vec::new();
+        for _ in 0..2 {
+            harness
+                .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+                .unwrap();
+            stops.push(harness.cursor_position());
+        }
+        assert_eq!(stops, expected, "subword_movement: {}", subword_movement);
+
+        harness
+            .send_key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+            .unwrap();
+        let selection = harness
+            .editor()
+            .active_cursors()
+            .primary()
+            .selection_range();
+        assert_eq!(selection, Some(selected));
+    }
+}
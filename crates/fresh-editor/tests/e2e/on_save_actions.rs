@@ -50,6 +50,8 @@ fn test_format_on_save() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -113,6 +115,8 @@ fn test_on_save_linter_style() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -176,6 +180,8 @@ fn test_on_save_action_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -248,6 +254,8 @@ fn test_on_save_file_placeholder() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -314,6 +322,8 @@ fn test_formatter_stdin_mode() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -387,6 +397,8 @@ fn test_on_save_stops_on_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action1, action2],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -481,6 +493,8 @@ fn test_formatter_not_found_shows_message() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            repl: None,
+            linters: Vec::new(),
         },
     );
 
@@ -647,3 +661,175 @@ fn test_whitespace_cleanup_no_change_needed() {
     // Content should remain the same
     harness.assert_buffer_content("line 1\nline 2\n");
 }
+
+/// `.editorconfig` files above a file set its save cleanup and indentation,
+/// overriding the editor config
+#[test]
+fn test_editorconfig_settings() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir_all(project_dir.join("src")).unwrap();
+    std::fs::write(
+        project_dir.join(".editorconfig"),
+        "root = true\n\n[*]\ntrim_trailing_whitespace = true\ninsert_final_newline = true\n\n\
+         [*.go]\nindent_style = tab\nindent_size = 8\n",
+    )
+    .unwrap();
+    // Nearer files win
+    std::fs::write(
+        project_dir.join("src/.editorconfig"),
+        "[*.md]\ntrim_trailing_whitespace = false\n",
+    )
+    .unwrap();
+
+    let text_path = project_dir.join("src/notes.txt");
+    std::fs::write(&text_path, "line 1  \nline 2").unwrap();
+    let markdown_path = project_dir.join("src/notes.md");
+    std::fs::write(&markdown_path, "hard break  \nline 2").unwrap();
+    let go_path = project_dir.join("main.go");
+    std::fs::write(&go_path, "package main\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Config::default(), project_dir)
+            .unwrap();
+
+    for path in [&text_path, &markdown_path] {
+        harness.open_file(path).unwrap();
+        harness.type_text("x").unwrap();
+        harness
+            .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+            .unwrap();
+        harness
+            .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    assert_eq!(
+        std::fs::read_to_string(&text_path).unwrap(),
+        "line 1\nline 2\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&markdown_path).unwrap(),
+        "hard break  \nline 2\n"
+    );
+
+    harness.open_file(&go_path).unwrap();
+    let settings = &harness.editor().active_state().buffer_settings;
+    assert!(settings.use_tabs);
+    assert_eq!(settings.tab_size, 8);
+}
+
+fn sed_formatter_config(format_on_save: bool) -> Config {
+    // Put spaces around `=` (stdin -> stdout)
+    let formatter = FormatterConfig {
+        command: "sed".to_string(),
+        args: vec!["'s/=/ = /'".to_string()],
+        stdin: true,
+        timeout_ms: 5000,
+    };
+
+    let mut config = Config::default();
+    config.languages.insert(
+        "plaintext".to_string(),
+        LanguageConfig {
+            extensions: vec!["txt".to_string()],
+            filenames: vec![],
+            grammar: "plaintext".to_string(),
+            comment_prefix: None,
+            auto_indent: false,
+            highlighter: Default::default(),
+            textmate_grammar: None,
+            show_whitespace_tabs: true,
+            use_tabs: false,
+            tab_size: None,
+            formatter: Some(formatter),
+            format_on_save,
+            on_save: vec![],
+            repl: None,
+            linters: Vec::new(),
+        },
+    );
+    config
+}
+
+/// Formatter output is applied as minimal edits, so the cursor stays on the
+/// same text instead of jumping to the end of the buffer
+#[test]
+#[cfg_attr(not(unix), ignore = "On-save actions require Unix-like environment")]
+fn test_format_on_save_keeps_cursor_position() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("assignments.txt");
+    std::fs::write(&file_path, "x=1\ny=2\nz=3\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        sed_formatter_config(true),
+        project_dir,
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Put the cursor at the end of the second line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("x = 1\ny = 2\nz = 3\n");
+
+    // Typing continues where the cursor was
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("x = 1\ny = 2!\nz = 3\n");
+
+    // The whole format is a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("x=1\ny=2\nz=3\n");
+}
+
+/// Format Document uses the configured formatter when no language server
+/// can format the buffer
+#[test]
+#[cfg_attr(not(unix), ignore = "On-save actions require Unix-like environment")]
+fn test_format_document_falls_back_to_formatter() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("assignments.txt");
+    std::fs::write(&file_path, "x=1\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        sed_formatter_config(false),
+        project_dir,
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Format Document").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("x = 1\n");
+    harness.assert_screen_contains("Formatted with sed");
+}
@@ -839,3 +839,49 @@ fn test_paste_crlf_into_prompt() {
     // Prompt should contain the text (newlines may be shown differently in prompt)
     harness.assert_screen_contains("line1");
 }
+
+/// Pasted lines are shifted to the indentation of the line they are pasted
+/// into, keeping their relative indentation
+#[test]
+fn test_paste_reindents_block() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_indent = false;
+    config.editor.reindent_on_paste = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("fn main() {\n    ").unwrap();
+
+    harness
+        .editor_mut()
+        .paste_text("if ok {\n\trun();\n}\n".to_string());
+    harness.render().unwrap();
+    harness.assert_buffer_content("fn main() {\n    if ok {\n        run();\n    }\n");
+
+    // A single undo step removes the whole paste
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("fn main() {\n    ");
+}
+
+/// With `reindent_on_paste` off, pasted text is inserted as is
+#[test]
+fn test_paste_without_reindent() {
+    let mut config = fresh::config::Config::default();
+    config.editor.reindent_on_paste = false;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("  ").unwrap();
+
+    harness.editor_mut().paste_text("a\nb".to_string());
+    harness.assert_buffer_content("  a\nb");
+}
+
+/// By default a pasted Python block keeps its indentation and blank lines
+#[test]
+fn test_paste_python_block_unchanged_by_default() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("class A:\n    ").unwrap();
+
+    let block = "def run(self):\n        x = 1\n        \n        return x\n";
+    harness.editor_mut().paste_text(block.to_string());
+    harness.assert_buffer_content(&format!("class A:\n    {}", block));
+}
@@ -0,0 +1,37 @@
+//! E2E tests for the performance HUD
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::Duration;
+
+fn toggle_perf_hud(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Toggle Performance HUD").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// The HUD shows the timings of drawn frames until it is hidden again
+#[test]
+fn test_perf_hud_shows_frame_times() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+
+    toggle_perf_hud(&mut harness);
+    harness.assert_screen_contains("Performance HUD shown");
+    harness.assert_screen_contains("Plugin hooks");
+
+    harness
+        .editor_mut()
+        .record_frame_time(Duration::from_micros(12_345));
+    harness.render().unwrap();
+    harness.assert_screen_contains("12.345ms");
+
+    toggle_perf_hud(&mut harness);
+    harness.assert_screen_contains("Performance HUD hidden");
+    harness.assert_screen_not_contains("Plugin hooks");
+}
@@ -0,0 +1,125 @@
+//! E2E tests for plugin status bar segments and gutter signs
+//! (registerStatusSegment / setGutterSigns)
+
+use crate::common::harness::{layout, EditorTestHarness};
+use fresh::services::plugins::api::{
+    GutterSign, OverlayColorSpec, PluginCommand, StatusSegmentOptions,
+};
+
+fn segment(text: &str, command: Option<&str>) -> StatusSegmentOptions {
+    StatusSegmentOptions {
+        text: text.to_string(),
+        fg: Some(OverlayColorSpec::ThemeKey("ui.status_bar_fg".to_string())),
+        bg: None,
+        priority: None,
+        command: command.map(|c| c.to_string()),
+    }
+}
+
+#[test]
+fn test_status_segment_update_click_and_remove() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetStatusSegment {
+            plugin_name: "tests".to_string(),
+            segment_id: "results".to_string(),
+            options: segment("PASS 12", Some("command_palette")),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness.get_status_bar().contains("PASS 12"));
+
+    // Registering the same id again updates the segment in place
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetStatusSegment {
+            plugin_name: "tests".to_string(),
+            segment_id: "results".to_string(),
+            options: segment("FAIL 1", Some("command_palette")),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    let status = harness.get_status_bar();
+    assert!(status.contains("FAIL 1"));
+    assert!(!status.contains("PASS 12"));
+
+    // Clicking the segment runs its command
+    let row = layout::status_bar_row(24) as u16;
+    let col = status.find("FAIL 1").unwrap();
+    let col = status[..col].chars().count() as u16;
+    harness.mouse_click(col, row).unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().is_prompting());
+    harness
+        .send_key(
+            crossterm::event::KeyCode::Esc,
+            crossterm::event::KeyModifiers::NONE,
+        )
+        .unwrap();
+
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::RemoveStatusSegment {
+            plugin_name: "tests".to_string(),
+            segment_id: "results".to_string(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.get_status_bar().contains("FAIL 1"));
+}
+
+#[test]
+fn test_gutter_signs_replace_previous_signs() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("one\ntwo\nthree\nfour\n")
+        .unwrap();
+    let buffer_id = harness.editor().active_buffer();
+
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetGutterSigns {
+            plugin_name: "coverage".to_string(),
+            buffer_id,
+            signs: vec![
+                GutterSign {
+                    line: 1,
+                    symbol: "✓".to_string(),
+                    color: Some(OverlayColorSpec::ThemeKey("diagnostic.info_fg".to_string())),
+                    priority: None,
+                },
+                // Past the end of the buffer: dropped
+                GutterSign {
+                    line: 40,
+                    symbol: "✗".to_string(),
+                    color: None,
+                    priority: None,
+                },
+            ],
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("✓");
+    assert!(harness.get_screen_row(3).contains('✓'));
+    harness.assert_screen_not_contains("✗");
+
+    // A new set replaces the plugin's previous signs
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetGutterSigns {
+            plugin_name: "coverage".to_string(),
+            buffer_id,
+            signs: vec![GutterSign {
+                line: 2,
+                symbol: "✗".to_string(),
+                color: Some(OverlayColorSpec::Rgb(255, 0, 0)),
+                priority: Some(5),
+            }],
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("✓");
+    assert!(harness.get_screen_row(4).contains('✗'));
+}
@@ -0,0 +1,112 @@
+//! E2E tests for the CSV mode plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+fn setup_csv_project(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().to_path_buf();
+    let plugins_dir = project.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "csv_mode");
+    let file = project.join("data.csv");
+    fs::write(&file, content).unwrap();
+    (temp_dir, file)
+}
+
+/// Cells are padded so every column starts at the same screen column
+#[test]
+fn test_csv_columns_are_aligned() {
+    let (temp_dir, file) =
+        setup_csv_project("id,name,city\n1,Alexandra,Oslo\n22,Bo,\"Rome, IT\"\n");
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        20,
+        Config::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("22,Bo       ,\"Rome, IT\""))
+        .unwrap();
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("id,name     ,city"), "Screen:\n{}", screen);
+    assert!(screen.contains("1 ,Alexandra,Oslo"), "Screen:\n{}", screen);
+}
+
+/// Tab and Shift+Tab move between cells, wrapping across rows
+#[test]
+fn test_csv_tab_moves_between_cells() {
+    let (temp_dir, file) = setup_csv_project("a,bb,c\nd,e,f\n");
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        20,
+        Config::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("a,bb,c"))
+        .unwrap();
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| h.cursor_position() == 2)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| h.cursor_position() == 5)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| h.cursor_position() == 7)
+        .unwrap();
+    harness
+        .send_key(KeyCode::BackTab, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .wait_until_stable(|h| h.cursor_position() == 5)
+        .unwrap();
+
+    // Tab moved the cursor instead of inserting anything
+    assert_eq!(harness.get_buffer_content().unwrap(), "a,bb,c\nd,e,f\n");
+}
+
+/// Deleting a column removes that cell from every row
+#[test]
+fn test_csv_delete_column() {
+    let (temp_dir, file) = setup_csv_project("a,b,c\n1,2,3\n");
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        20,
+        Config::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("a,b,c"))
+        .unwrap();
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until_stable(|h| h.cursor_position() == 2)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("CSV: Delete Column").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "a,c\n1,3\n")
+        .unwrap();
+}
@@ -0,0 +1,137 @@
+//! E2E tests for the JSON tools plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+fn setup_json_project(content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().to_path_buf();
+    let plugins_dir = project.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "json_tools");
+    let file = project.join("data.json");
+    fs::write(&file, content).unwrap();
+    (temp_dir, file)
+}
+
+fn open_harness(temp_dir: &tempfile::TempDir, file: &std::path::Path) -> EditorTestHarness {
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        20,
+        Config::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(file).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(name))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+/// Formatting pretty-prints a minified document and keeps comments
+#[test]
+fn test_json_format_document() {
+    let (temp_dir, file) = setup_json_project("{\"a\":1,// one\n\"b\":[true,null]}\n");
+    let mut harness = open_harness(&temp_dir, &file);
+
+    run_command(&mut harness, "JSON: Format Document");
+    harness
+        .wait_until(|h| {
+            h.get_buffer_content().unwrap()
+                == "{\n    \"a\": 1, // one\n    \"b\": [\n        true,\n        null\n    ]\n}\n"
+        })
+        .unwrap();
+}
+
+/// The status bar shows the jq-style path of the value under the cursor
+#[test]
+fn test_json_breadcrumb_follows_cursor() {
+    let (temp_dir, file) = setup_json_project("{\n  \"items\": [\n    {\"name\": \"x\"}\n  ]\n}\n");
+    let mut harness = open_harness(&temp_dir, &file);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(".items[0].name"))
+        .unwrap();
+}
+
+/// Folding to depth 1 hides the contents of every nested container
+#[test]
+fn test_json_fold_to_depth() {
+    let (temp_dir, file) = setup_json_project(
+        "{\n  \"a\": {\n    \"b\": 1\n  },\n  \"c\": [\n    2\n  ],\n  \"d\": 3\n}\n",
+    );
+    let mut harness = open_harness(&temp_dir, &file);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("\"b\": 1"))
+        .unwrap();
+
+    run_command(&mut harness, "JSON: Fold to Depth");
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Fold to depth:"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("\"a\": {…},"))
+        .unwrap();
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("\"c\": […],"), "Screen:\n{}", screen);
+    assert!(screen.contains("\"d\": 3"), "Screen:\n{}", screen);
+    assert!(!screen.contains("\"b\": 1"), "Screen:\n{}", screen);
+    // Folding only changes the view
+    assert!(harness.get_buffer_content().unwrap().contains("\"b\": 1"));
+
+    run_command(&mut harness, "JSON: Unfold All");
+    harness
+        .wait_until(|h| h.screen_to_string().contains("\"b\": 1"))
+        .unwrap();
+}
+
+/// A jq-style query opens its results in a scratch buffer
+#[test]
+fn test_json_query_results() {
+    let (temp_dir, file) = setup_json_project(
+        "{\"items\": [{\"name\": \"alpha\", \"n\": 1}, {\"name\": \"beta\", \"n\": 5}]}\n",
+    );
+    let mut harness = open_harness(&temp_dir, &file);
+
+    run_command(&mut harness, "JSON: Query");
+    harness
+        .wait_until(|h| h.screen_to_string().contains("JSON query:"))
+        .unwrap();
+    harness
+        .type_text(".items[] | select(.n > 2) | .name")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("*JSON Query*"))
+        .unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "\"beta\"\n")
+        .unwrap();
+}
@@ -2,12 +2,17 @@
 //! These tests are only compiled when the "plugins" feature is enabled.
 
 pub mod audit_mode;
+pub mod csv_mode;
 pub mod diff_cursor;
 pub mod find_file;
 pub mod git;
 pub mod gutter;
+pub mod json_tools;
 pub mod language_pack;
 pub mod lsp_find_references;
 pub mod package_manager;
 pub mod plugin;
+pub mod tasks;
+pub mod test_runner;
 pub mod theme_editor;
+pub mod todo_panel;
@@ -6,6 +6,7 @@ use crate::common::git_test_helper::{DirGuard, GitTestRepo};
 use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
 use crate::common::tracing::init_tracing_from_env;
 use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
 use std::fs;
 
 /// Test that plugins in the packages/ subdirectory are discovered and loaded.
@@ -1580,3 +1581,144 @@ globalThis.uninstall_test_hello = function() { editor.setStatus("Hello from unin
         screen
     );
 }
+
+/// Write a package in the installed packages directory whose plugin loads
+/// on `events`
+fn write_deferred_package(dir_context: &DirectoryContext, name: &str, events: &str, code: &str) {
+    let package_dir = dir_context
+        .config_dir
+        .join("plugins")
+        .join("packages")
+        .join(name);
+    fs::create_dir_all(&package_dir).unwrap();
+    fs::write(package_dir.join("main.ts"), code).unwrap();
+    fs::write(
+        package_dir.join("package.json"),
+        format!(
+            r#"{{
+    "name": "{name}",
+    "version": "1.0.0",
+    "description": "A plugin loaded when needed",
+    "type": "plugin",
+    "fresh": {{
+        "entry": "main.ts",
+        "activation_events": {events}
+    }}
+}}"#
+        ),
+    )
+    .unwrap();
+}
+
+/// A package with an onCommand activation event is loaded when its action
+/// is run from the command palette, and the action then runs.
+#[test]
+fn test_package_activated_by_command() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    let state_dir = tempfile::TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(state_dir.path());
+    write_deferred_package(
+        &dir_context,
+        "lazy-command",
+        r#"["onCommand:lazy_command_hello"]"#,
+        r#"
+const editor = getEditor();
+
+globalThis.lazy_command_hello = function(): void {
+    editor.setStatus("Hello from the lazy package");
+};
+editor.registerCommand("Lazy Command: Hello", "Say hello", "lazy_command_hello", null);
+editor.setStatus("Lazy package loaded");
+"#,
+    );
+
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        30,
+        Default::default(),
+        repo.path.clone(),
+        dir_context,
+    )
+    .unwrap();
+    harness.render().unwrap();
+    assert_ne!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("Lazy package loaded"),
+        "The package should not be loaded at startup"
+    );
+
+    // The action is listed under its own name until the plugin is loaded
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("lazy_command_hello").unwrap();
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .contains("Loads the lazy-command plugin")
+        })
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| {
+            h.editor().get_status_message().map(String::as_str)
+                == Some("Hello from the lazy package")
+        })
+        .unwrap();
+}
+
+/// A package with an onLanguage activation event is loaded when the first
+/// file of the language is opened, and sees that file being opened.
+#[test]
+fn test_package_activated_by_language() {
+    let repo = GitTestRepo::new();
+    repo.setup_typical_project();
+    let state_dir = tempfile::TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(state_dir.path());
+    write_deferred_package(
+        &dir_context,
+        "lazy-rust",
+        r#"["onLanguage:rust"]"#,
+        r#"
+const editor = getEditor();
+
+globalThis.onLazyRustFileOpen = function(data: { buffer_id: number; path: string }): void {
+    editor.setStatus("Lazy rust plugin saw " + editor.pathBasename(data.path));
+};
+editor.on("after_file_open", "onLazyRustFileOpen");
+"#,
+    );
+    fs::write(repo.path.join("notes.txt"), "plain text\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        30,
+        Default::default(),
+        repo.path.clone(),
+        dir_context,
+    )
+    .unwrap();
+
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+    harness.render().unwrap();
+    assert!(
+        !harness
+            .editor()
+            .get_status_message()
+            .is_some_and(|m| m.contains("Lazy rust plugin")),
+        "The package should not be loaded by a text file"
+    );
+
+    harness.open_file(&repo.path.join("src/main.rs")).unwrap();
+    harness
+        .wait_until(|h| {
+            h.editor().get_status_message().map(String::as_str)
+                == Some("Lazy rust plugin saw main.rs")
+        })
+        .unwrap();
+}
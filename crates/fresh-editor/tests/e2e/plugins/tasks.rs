@@ -0,0 +1,63 @@
+//! E2E tests for the task runner plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+/// The Run Task picker lists tasks from every detected source
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_run_task_lists_project_tasks() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().to_path_buf();
+
+    fs::write(
+        project.join("Makefile"),
+        ".PHONY: build\nbuild:\n\tcc main.c\n%.o: %.c\n\tcc -c $<\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"scripts": {"lint": "eslint ."}}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(project.join(".cargo")).unwrap();
+    fs::write(
+        project.join(".cargo/config.toml"),
+        "[alias]\nxtask = \"run --package xtask --\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("justfile"),
+        "deploy env:\n    ./deploy.sh {{env}}\n",
+    )
+    .unwrap();
+
+    let plugins_dir = project.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "tasks");
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 40, Config::default(), project)
+            .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Run Task").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| h.screen_to_string().contains("just: deploy"))
+        .unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(screen.contains("make: build"), "Screen:\n{}", screen);
+    assert!(screen.contains("npm: lint"), "Screen:\n{}", screen);
+    assert!(screen.contains("cargo: xtask"), "Screen:\n{}", screen);
+    assert!(!screen.contains("%.o"), "Screen:\n{}", screen);
+}
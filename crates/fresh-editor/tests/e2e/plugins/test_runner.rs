@@ -0,0 +1,44 @@
+//! E2E tests for the test runner plugin
+
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use fresh::config::Config;
+use std::fs;
+
+/// Tests in an opened file get a run marker in the gutter
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_test_runner_marks_tests_in_gutter() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().to_path_buf();
+
+    fs::create_dir_all(project.join("src")).unwrap();
+    let file = project.join("src/lib.rs");
+    fs::write(
+        &file,
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn adds_numbers() {\n        assert_eq!(super::add(1, 2), 3);\n    }\n}\n",
+    )
+    .unwrap();
+
+    let plugins_dir = project.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "test_runner");
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 40, Config::default(), project)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .lines()
+                .any(|line| line.starts_with('▶') && line.contains("fn adds_numbers()"))
+        })
+        .unwrap();
+
+    // Only the test function is marked, not its attribute or the code under test
+    let screen = harness.screen_to_string();
+    let marked = screen.lines().filter(|line| line.starts_with('▶')).count();
+    assert_eq!(marked, 1, "Screen:\n{}", screen);
+}
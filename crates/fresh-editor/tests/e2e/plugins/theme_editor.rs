@@ -2471,3 +2471,79 @@ editor.setStatus("Delete theme test plugin loaded");
         "Theme file should be deleted (moved to trash)"
     );
 }
+
+/// Number of cells in screen `rows` drawn with `color`
+fn cells_with_color(
+    harness: &EditorTestHarness,
+    rows: std::ops::Range<u16>,
+    color: Color,
+) -> usize {
+    let width = harness.buffer().area.width;
+    rows.flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter_map(|(x, y)| harness.get_cell_style(x, y))
+        .filter(|style| style.fg == Some(color) || style.bg == Some(color))
+        .count()
+}
+
+/// Test that the preview draws sample code with the theme's colors and
+/// follows color edits right away
+#[test]
+fn test_theme_editor_preview_follows_edits() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "theme_editor");
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 40, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+    open_theme_editor(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("fn greet(name: &str)"))
+        .unwrap();
+
+    // The keyword is drawn in the dark theme's keyword color on its background
+    let (_, y) = harness.find_text_on_screen("fn greet").unwrap();
+    let row = harness.screen_row_text(y);
+    let x = row[..row.find("fn greet").unwrap()].chars().count() as u16;
+    let style = harness.get_cell_style(x, y).unwrap();
+    assert_eq!(style.fg, Some(Color::Rgb(86, 156, 214)));
+    assert_eq!(style.bg, Some(Color::Rgb(30, 30, 30)));
+    let (_, last_y) = harness.find_text_on_screen("  }").unwrap();
+    let preview_rows = y - 1..last_y + 1;
+    let red = Color::Rgb(255, 0, 0);
+    assert_eq!(cells_with_color(&harness, preview_rows.clone(), red), 0);
+
+    // Change the editor background: down to the editor section, then to
+    // its first field
+    for _ in 0..2 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        harness.process_async_and_render().unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("#RRGGBB"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("#FF0000").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            !screen.contains("#RRGGBB") && screen.contains("#FF0000")
+        })
+        .unwrap();
+
+    // The preview is drawn with the new color
+    assert!(cells_with_color(&harness, preview_rows, red) > 0);
+}
@@ -0,0 +1,97 @@
+//! E2E tests for the TODO panel plugin
+
+use crate::common::git_test_helper::{DirGuard, GitTestRepo};
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+fn setup_todo_repo() -> GitTestRepo {
+    let repo = GitTestRepo::new();
+    repo.create_file(
+        "src/main.rs",
+        "fn main() {\n    // TODO: wire up config\n    run();\n}\n",
+    );
+    repo.create_file(
+        "src/lib.rs",
+        "pub fn run() {\n    // FIXME leaks handles\n}\n",
+    );
+    repo.create_file("README.md", "No tags here, TODOS is not a tag\n");
+    // Keep the copied plugins out of the workspace scan
+    repo.create_file(".gitignore", "plugins/\n");
+    repo.git_add_all();
+    repo.git_commit("Initial commit");
+
+    let plugins_dir = repo.path.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    copy_plugin(&plugins_dir, "todo_panel");
+    repo
+}
+
+/// Tagged lines in an open buffer get a gutter marker
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_todo_gutter_marker_on_open() {
+    let repo = setup_todo_repo();
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("src/main.rs")).unwrap();
+
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .lines()
+                .any(|line| line.starts_with('●') && line.contains("TODO: wire up config"))
+        })
+        .unwrap();
+}
+
+/// The panel lists tagged comments from the whole workspace, grouped by file
+#[test]
+#[cfg_attr(target_os = "windows", ignore)]
+fn test_todo_panel_lists_workspace_tags() {
+    let repo = setup_todo_repo();
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("src/main.rs")).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show TODO Panel").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("lib.rs:") && screen.contains("FIXME  leaks handles")
+        })
+        .unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("2: TODO  wire up config"),
+        "Screen:\n{}",
+        screen
+    );
+    assert!(!screen.contains("README.md:"), "Screen:\n{}", screen);
+}
@@ -26,7 +26,9 @@ fn test_navigate_back_forward_single_buffer() {
     assert!(end_pos > 0);
 
     // Navigate back - should go to beginning
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
 
     // Note: The current implementation saves position on buffer switch,
     // not on large cursor movements. So this test verifies the keybinding works
@@ -61,23 +63,31 @@ fn test_navigate_back_forward_across_buffers() {
     let buffer3_pos = harness.cursor_position();
     assert_eq!(harness.cursor_position(), buffer3_pos);
 
-    // Navigate back (Alt+Left) - should go to Buffer 2
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    // Navigate back (Ctrl+-) - should go to Buffer 2
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Buffer 2 content");
     assert_eq!(harness.cursor_position(), buffer2_pos);
 
     // Navigate back again - should go to Buffer 1
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Buffer 1 content");
     assert_eq!(harness.cursor_position(), buffer1_pos);
 
-    // Navigate forward (Alt+Right) - should go back to Buffer 2
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    // Navigate forward (Ctrl+=) - should go back to Buffer 2
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Buffer 2 content");
     assert_eq!(harness.cursor_position(), buffer2_pos);
 
     // Navigate forward again - should go to Buffer 3
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Buffer 3 content");
     assert_eq!(harness.cursor_position(), buffer3_pos);
 }
@@ -112,7 +122,9 @@ fn test_buffer_switching_creates_history() {
     harness.assert_buffer_content("First");
 
     // Navigate back should take us through the history
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Second");
     assert_eq!(harness.cursor_position(), second_pos);
 }
@@ -142,7 +154,9 @@ fn test_cursor_position_preserved() {
     harness.type_text("ABCDEFGHIJ").unwrap();
 
     // Navigate back - cursor should be at position 5
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("0123456789");
     assert_eq!(harness.cursor_position(), 5);
 }
@@ -156,7 +170,9 @@ fn test_navigate_back_at_beginning() {
     let pos = harness.cursor_position();
 
     // Try to navigate back when there's no history
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
 
     // Should still be in the same buffer at the same position
     harness.assert_buffer_content("Content");
@@ -176,7 +192,9 @@ fn test_navigate_forward_at_end() {
     harness.type_text("Second").unwrap();
 
     // Try to navigate forward when we're at the end of history
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
 
     // Should still be in the second buffer
     harness.assert_buffer_content("Second");
@@ -199,8 +217,12 @@ fn test_new_navigation_truncates_forward_history() {
     harness.type_text("Buffer 3").unwrap();
 
     // Navigate back twice
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Buffer 1");
 
     // Create a new buffer - this should truncate forward history
@@ -210,7 +232,9 @@ fn test_new_navigation_truncates_forward_history() {
     harness.type_text("Buffer 4").unwrap();
 
     // Try to navigate forward - should not be able to go to Buffer 2 or 3
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
 
     // Should still be in Buffer 4 (at the end of history)
     harness.assert_buffer_content("Buffer 4");
@@ -243,10 +267,14 @@ fn test_position_history_with_next_prev_buffer() {
         .unwrap();
     harness.assert_buffer_content("Second");
 
-    // Navigate back twice using Alt+Left
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    // Navigate back twice using Ctrl+-
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("First");
 
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.assert_buffer_content("Third");
 }
@@ -25,7 +25,9 @@ fn test_back_forward_updates_visible_content() {
     harness.assert_buffer_content("THIRD_BUFFER_CONTENT");
 
     // Navigate back to buffer 2
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap(); // Force render
 
     // Check that the VISIBLE CONTENT actually changed, not just the tab
@@ -40,7 +42,9 @@ fn test_back_forward_updates_visible_content() {
     );
 
     // Navigate back again to buffer 1
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
 
     let screen = harness.screen_to_string();
@@ -54,7 +58,9 @@ fn test_back_forward_updates_visible_content() {
     );
 
     // Navigate forward to buffer 2
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
 
     let screen = harness.screen_to_string();
@@ -98,7 +104,9 @@ fn test_cursor_movement_tracked_in_history() {
     harness.type_text("Other buffer").unwrap();
 
     // Navigate back - cursor should return to the end position in buffer 1
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
 
     // Verify we're in the first buffer AND cursor is at the saved position
@@ -136,7 +144,9 @@ fn test_small_cursor_movements_with_back_forward() {
     harness.type_text("Different content").unwrap();
 
     // Navigate back
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
 
     // Should be back at position 5 in first buffer
@@ -181,7 +191,9 @@ fn test_page_movements_tracked_in_history() {
     harness.type_text("Other").unwrap();
 
     // Navigate back
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
 
     // Should be at the middle position where we paged down to
@@ -232,7 +244,9 @@ fn test_cursor_movements_within_single_buffer_tracked() {
     // We should go: current(beginning) -> back to end -> back to beginning (before the jump to end)
 
     // First back: should go to position 2 (end)
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
@@ -243,7 +257,9 @@ fn test_cursor_movements_within_single_buffer_tracked() {
     );
 
     // Second back: should go to position 1 (beginning before jump to end)
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
@@ -254,7 +270,9 @@ fn test_cursor_movements_within_single_buffer_tracked() {
     );
 
     // Navigate forward: should go back to end
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
@@ -299,7 +317,9 @@ fn test_large_jumps_within_buffer_tracked() {
     // Now navigate back through history
     // Should go: current(page2) -> page1 -> start
 
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
@@ -309,7 +329,9 @@ fn test_large_jumps_within_buffer_tracked() {
         harness.cursor_position()
     );
 
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
@@ -320,7 +342,9 @@ fn test_large_jumps_within_buffer_tracked() {
     );
 
     // Navigate forward
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     harness.render().unwrap();
     assert_eq!(
         harness.cursor_position(),
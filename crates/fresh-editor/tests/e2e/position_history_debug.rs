@@ -41,9 +41,11 @@ fn test_debug_position_history() {
 
     // Navigate back
     println!("\nNavigating back...");
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     println!(
-        "After Alt+Left: cursor at {}, content: {:?}",
+        "After Ctrl+-: cursor at {}, content: {:?}",
         harness.cursor_position(),
         harness.get_buffer_content().unwrap()
     );
@@ -59,7 +59,9 @@ fn test_debug_truncate() {
 
     // Navigate back twice
     println!("\n=== Navigate back (first) ===");
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     println!(
         "After first back: content = {:?}",
         harness.get_buffer_content().unwrap()
@@ -74,7 +76,9 @@ fn test_debug_truncate() {
     );
 
     println!("\n=== Navigate back (second) ===");
-    harness.send_key(KeyCode::Left, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        .unwrap();
     println!(
         "After second back: content = {:?}",
         harness.get_buffer_content().unwrap()
@@ -110,7 +114,9 @@ fn test_debug_truncate() {
 
     // Try to navigate forward - should not be able to go to Buffer 2 or 3
     println!("\n=== Navigate forward (should stay in Buffer 4) ===");
-    harness.send_key(KeyCode::Right, KeyModifiers::ALT).unwrap();
+    harness
+        .send_key(KeyCode::Char('='), KeyModifiers::CONTROL)
+        .unwrap();
     println!(
         "After forward: content = {:?}",
         harness.get_buffer_content().unwrap()
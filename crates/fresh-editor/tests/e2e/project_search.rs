@@ -0,0 +1,103 @@
+//! E2E tests for the native project search and its `*Search Results*` buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Put the cursor on the first line of the results mentioning `text`
+fn move_to_line(harness: &mut EditorTestHarness, text: &str) {
+    let content = harness.get_buffer_content().unwrap();
+    let offset = content.find(text).unwrap();
+    harness
+        .editor_mut()
+        .active_cursors_mut()
+        .primary_mut()
+        .position = offset;
+}
+
+#[test]
+fn test_project_search_lists_results_and_opens_them() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project");
+    fs::create_dir_all(project_root.join("src")).unwrap();
+    fs::write(
+        project_root.join("src/main.rs"),
+        "fn main() {\n    let total = 1;\n    println!(\"{}\", total);\n}\n",
+    )
+    .unwrap();
+    fs::write(project_root.join("notes.txt"), "nothing here\n").unwrap();
+    fs::write(project_root.join(".gitignore"), "target/\n").unwrap();
+    fs::create_dir(project_root.join("target")).unwrap();
+    fs::write(project_root.join("target/out.txt"), "total\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project_root)
+            .unwrap();
+    harness.editor_mut().start_project_search("total");
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("2 matches in 1 files") && !screen.contains("searching...")
+        })
+        .unwrap();
+
+    harness.assert_screen_contains("*Search Results*");
+    harness.assert_screen_contains("src/main.rs");
+    harness.assert_screen_contains("1- fn main() {");
+    harness.assert_screen_contains("3:     println!");
+    // Ignored files are not searched
+    harness.assert_screen_not_contains("target/out.txt");
+
+    // Enter opens the file at the match under the cursor
+    move_to_line(&mut harness, "3:");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.starts_with("fn main()"));
+    let cursor = harness.cursor_position();
+    assert_eq!(&content[cursor..cursor + 5], "total");
+}
+
+#[test]
+fn test_project_search_without_matches() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("a.txt"), "alpha\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        Default::default(),
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.editor_mut().start_project_search("omega");
+    harness
+        .wait_until(|h| h.screen_to_string().contains("No matches for 'omega'"))
+        .unwrap();
+    harness.assert_screen_contains("0 matches in 0 files");
+}
+
+#[test]
+fn test_project_search_honors_files_exclude() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    fs::create_dir(root.join("dist")).unwrap();
+    fs::write(root.join("dist/bundle.js"), "const marker = 1;\n").unwrap();
+    fs::write(root.join("app.js"), "const marker = 2;\n").unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.files.exclude = vec!["dist/".to_string()];
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, config, root).unwrap();
+    harness.editor_mut().start_project_search("marker");
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("1 matches in 1 files") && !screen.contains("searching...")
+        })
+        .unwrap();
+    harness.assert_screen_contains("app.js");
+    harness.assert_screen_not_contains("bundle.js");
+}
@@ -0,0 +1,157 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use fresh::view::prompt::PromptType;
+use tempfile::TempDir;
+
+fn search_for(harness: &mut EditorTestHarness, text: &str) {
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+fn live_grep_prompt() -> PromptType {
+    PromptType::Plugin {
+        custom_type: "live_grep".to_string(),
+    }
+}
+
+/// Ctrl+P/Ctrl+N only visit history entries containing the typed text
+#[test]
+fn test_ctrl_p_searches_history_by_substring() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .load_buffer_from_text("fn main() {}\nfn helper() {}\n// TODO\n")
+        .unwrap();
+    harness.editor_mut().clear_search_history();
+
+    search_for(&mut harness, "fn main");
+    search_for(&mut harness, "TODO");
+    search_for(&mut harness, "fn helper");
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("FN").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some("fn helper"));
+
+    // "TODO" doesn't contain "fn" and is skipped
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some("fn main"));
+
+    // No older match: the prompt keeps the current entry
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some("fn main"));
+
+    harness
+        .send_key(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some("fn helper"));
+
+    // Past the newest match the typed text comes back
+    harness
+        .send_key(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some("FN"));
+    harness.assert_screen_contains("Search: FN");
+}
+
+/// Plugin prompts keep their history on disk, keyed by prompt type
+#[test]
+fn test_plugin_prompt_history_persists_across_sessions() {
+    let context_temp = TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(context_temp.path());
+    let project = TempDir::new().unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_shared_dir_context(
+            100,
+            24,
+            Config::default(),
+            project.path().to_path_buf(),
+            dir_context.clone(),
+        )
+        .unwrap();
+        harness
+            .editor_mut()
+            .start_prompt("Grep: ".to_string(), live_grep_prompt());
+        harness.type_text("needle").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.editor().save_histories();
+    }
+
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        24,
+        Config::default(),
+        project.path().to_path_buf(),
+        dir_context,
+    )
+    .unwrap();
+    harness
+        .editor_mut()
+        .start_prompt("Grep: ".to_string(), live_grep_prompt());
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Grep: needle");
+
+    // Other prompt types have their own history
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().start_prompt(
+        "Other: ".to_string(),
+        PromptType::Plugin {
+            custom_type: "other".to_string(),
+        },
+    );
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some(""));
+}
+
+/// Files opened from the Open File prompt can be recalled with Ctrl+P
+#[test]
+fn test_open_file_prompt_remembers_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "notes").unwrap();
+    let path_text = file_path.display().to_string();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&path_text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.editor().is_prompting());
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("notes").unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().prompt_input(), Some(path_text.as_str()));
+}
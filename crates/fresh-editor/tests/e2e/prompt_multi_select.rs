@@ -0,0 +1,83 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Tab marks files in Quick Open's file mode and Enter opens all of them
+#[test]
+fn test_quick_open_opens_marked_files() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::write(project_dir.join("alpha_notes.txt"), "alpha\n").unwrap();
+    std::fs::write(project_dir.join("beta_notes.txt"), "beta\n").unwrap();
+    std::fs::write(project_dir.join("gamma_notes.txt"), "gamma\n").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    // Drop the ">" command prefix to switch to file mode
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("notes").unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("alpha_notes.txt")
+                && screen.contains("beta_notes.txt")
+                && screen.contains("gamma_notes.txt")
+        })
+        .unwrap();
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("2 marked");
+    assert_eq!(harness.editor().prompt_input(), Some("notes"));
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| {
+            let tabs = h.get_tab_bar();
+            ["alpha_notes.txt", "beta_notes.txt", "gamma_notes.txt"]
+                .iter()
+                .filter(|name| tabs.contains(*name))
+                .count()
+                == 2
+        })
+        .unwrap();
+}
+
+/// Marking the same entry twice unmarks it, and with no marks Enter opens the selection
+#[test]
+fn test_quick_open_unmark_falls_back_to_selection() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::write(project_dir.join("only_file.txt"), "only\n").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("only_file").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("only_file.txt"))
+        .unwrap();
+
+    // A single entry stays selected, so the second Tab unmarks it
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("1 marked");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("marked");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.get_tab_bar().contains("only_file.txt"))
+        .unwrap();
+}
@@ -0,0 +1,96 @@
+//! E2E tests for read-only buffers (unwritable files and `--readonly`)
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn toggle_read_only(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Toggle Read-Only").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Files without write permission open read-only until toggled off
+#[test]
+#[cfg(unix)]
+fn test_unwritable_file_opens_read_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let file_path = temp_dir.path().join("locked.txt");
+    std::fs::write(&file_path, "hello\n").unwrap();
+    std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o444)).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.assert_screen_contains("[RO]");
+
+    harness.type_text("x").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('t'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('u'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello\n");
+    harness.assert_screen_contains("Buffer is read-only");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    toggle_read_only(&mut harness);
+    harness.assert_screen_contains("Read-only mode off");
+    harness.assert_screen_not_contains("[RO]");
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xhello\n");
+}
+
+/// `--readonly` opens writable files read-only too, and the mode survives a revert
+#[test]
+fn test_open_read_only_blocks_edits() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "notes\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.editor_mut().set_open_read_only(true);
+    harness.open_file(&file_path).unwrap();
+    harness.assert_screen_contains("[RO]");
+
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("abc").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "notes\n");
+
+    harness.editor_mut().revert_file().unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[RO]");
+    harness.type_text("abc").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "notes\n");
+
+    toggle_read_only(&mut harness);
+    toggle_read_only(&mut harness);
+    harness.assert_screen_contains("Read-only mode on");
+}
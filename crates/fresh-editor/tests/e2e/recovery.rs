@@ -1166,3 +1166,76 @@ fn test_recovery_insert_at_end_of_large_file() {
         println!("No chunked recovery entry found");
     }
 }
+
+/// Edits journaled after the last recovery snapshot survive a crash, and the
+/// next session offers to recover them next to the file on disk
+#[test]
+fn test_crash_recovery_replays_journal_and_compares_with_disk() {
+    use fresh::services::recovery::SessionInfo;
+    use tempfile::TempDir;
+
+    let project = TempDir::new().unwrap();
+    let file = project.path().join("notes.txt");
+    std::fs::write(&file, "original\n").unwrap();
+    let context_temp = TempDir::new().unwrap();
+    let dir_context = fresh::config_io::DirectoryContext::for_testing(context_temp.path());
+    let recovery_dir = dir_context.recovery_dir();
+
+    {
+        let mut harness = EditorTestHarness::with_shared_dir_context(
+            120,
+            24,
+            Default::default(),
+            project.path().to_path_buf(),
+            dir_context.clone(),
+        )
+        .unwrap();
+        harness.editor_mut().start_recovery_session().unwrap();
+        harness.open_file(&file).unwrap();
+
+        // The first edit writes a snapshot, later ones go to the journal
+        harness.type_text("first ").unwrap();
+        assert_eq!(harness.editor_mut().journal_unsaved_edits().unwrap(), 1);
+        harness.type_text("second ").unwrap();
+        assert_eq!(harness.editor_mut().journal_unsaved_edits().unwrap(), 1);
+        assert!(std::fs::read_dir(&recovery_dir)
+            .unwrap()
+            .flatten()
+            .any(|e| e.path().extension().is_some_and(|ext| ext == "journal")));
+    }
+
+    // Crash: the session lock is left behind by a process that is gone
+    let mut child = std::process::Command::new("true").spawn().unwrap();
+    child.wait().unwrap();
+    let session = SessionInfo {
+        pid: child.id(),
+        ..SessionInfo::new()
+    };
+    std::fs::write(
+        recovery_dir.join("session.lock"),
+        serde_json::to_string(&session).unwrap(),
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        120,
+        24,
+        Default::default(),
+        project.path().to_path_buf(),
+        dir_context,
+    )
+    .unwrap();
+    harness.editor_mut().offer_crash_recovery();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Unsaved changes to 1 file(s) survived a crash");
+
+    harness.type_text("c").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Recovered unsaved changes to 1 file(s)");
+    harness.assert_screen_contains("*Diff: notes.txt*");
+    harness.assert_screen_contains("first second original");
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "original\n");
+}
@@ -0,0 +1,83 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, Keybinding};
+
+fn bind(config: &mut Config, key: &str, action: &str) {
+    config.keybindings.push(Keybinding {
+        key: key.to_string(),
+        modifiers: vec!["alt".to_string()],
+        keys: vec![],
+        action: action.to_string(),
+        args: Default::default(),
+        when: None,
+    });
+}
+
+/// A run of typing, with a corrected typo, is repeated at the cursor
+#[test]
+fn test_repeat_typing() {
+    let mut config = Config::default();
+    bind(&mut config, "q", "repeat_last_edit");
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("a\nb\nc").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("TODO;").unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text(": ").unwrap();
+    harness.assert_buffer_content("TODO: a\nb\nc");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("TODO: a\nTODO: b\nc");
+
+    // With several cursors the edit is made at each of them
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    let ctrl_alt = KeyModifiers::CONTROL | KeyModifiers::ALT;
+    harness.send_key(KeyCode::Down, ctrl_alt).unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("TODO: aTODO: \nTODO: bTODO: \nc");
+}
+
+/// Replacing a word selected by a text object is repeated on the word at
+/// the cursor
+#[test]
+fn test_repeat_replace_selection() {
+    let mut config = Config::default();
+    bind(&mut config, "q", "repeat_last_edit");
+    bind(&mut config, "o", "select_inside_word");
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("let foo = bar + baz;").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::ALT)
+        .unwrap();
+    harness.type_text("qux").unwrap();
+    harness.assert_buffer_content("let qux = bar + baz;");
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("let qux = bar + qux;");
+}
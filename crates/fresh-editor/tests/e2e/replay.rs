@@ -0,0 +1,105 @@
+//! E2E tests for recording inputs with `--event-log` and replaying them
+
+use crate::common::harness::{layout, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use fresh::input::replay::ReplayInput;
+use tempfile::TempDir;
+
+/// Drive `harness` with `inputs` while streaming them to `log`
+fn record(harness: &mut EditorTestHarness, log: &std::path::Path, inputs: &[ReplayInput]) {
+    harness.editor_mut().enable_event_streaming(log).unwrap();
+    for input in inputs {
+        harness.editor_mut().record_input(input);
+        match input {
+            ReplayInput::Resize(width, height) => harness.resize(*width, *height).unwrap(),
+            input => {
+                harness.editor_mut().apply_replay_input(input).unwrap();
+                harness.render().unwrap();
+            }
+        }
+    }
+}
+
+fn key(code: KeyCode) -> ReplayInput {
+    ReplayInput::Key(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_replay_reconstructs_typing_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let log = temp_dir.path().join("session.log");
+
+    let mut inputs: Vec<ReplayInput> = "hello world"
+        .chars()
+        .map(|c| key(KeyCode::Char(c)))
+        .collect();
+    inputs.push(ReplayInput::Key(KeyCode::Left, KeyModifiers::CONTROL));
+    inputs.push(key(KeyCode::Backspace));
+    inputs.push(key(KeyCode::Enter));
+    inputs.push(ReplayInput::Paste("pasted ".to_string()));
+    inputs.push(key(KeyCode::End));
+    inputs.push(key(KeyCode::Char('!')));
+
+    let mut recorded = EditorTestHarness::new(80, 24).unwrap();
+    record(&mut recorded, &log, &inputs);
+    let expected = recorded.get_buffer_content().unwrap();
+    assert_eq!(expected, "hello\npasted world!");
+
+    let mut replayed = EditorTestHarness::new(80, 24).unwrap();
+    replayed.replay_log(&log).unwrap();
+    assert_eq!(replayed.get_buffer_content().unwrap(), expected);
+    assert_eq!(
+        replayed.editor().active_cursors().primary().position,
+        recorded.editor().active_cursors().primary().position
+    );
+}
+
+#[test]
+fn test_replay_applies_mouse_and_resize() {
+    let temp_dir = TempDir::new().unwrap();
+    let log = temp_dir.path().join("session.log");
+
+    let mut recorded = EditorTestHarness::new(80, 24).unwrap();
+    let inputs = vec![
+        key(KeyCode::Char('a')),
+        key(KeyCode::Char('b')),
+        key(KeyCode::Char('c')),
+        key(KeyCode::Enter),
+        key(KeyCode::Char('d')),
+        ReplayInput::Resize(100, 30),
+    ];
+    record(&mut recorded, &log, &inputs);
+
+    // Click between "a" and "b" on the first line
+    let row = layout::CONTENT_START_ROW;
+    let column = recorded
+        .get_screen_row(row)
+        .chars()
+        .position(|c| c == 'a')
+        .unwrap() as u16
+        + 1;
+    let click = |kind| {
+        ReplayInput::Mouse(MouseEvent {
+            kind,
+            column,
+            row: row as u16,
+            modifiers: KeyModifiers::NONE,
+        })
+    };
+    let tail = vec![
+        click(MouseEventKind::Down(MouseButton::Left)),
+        click(MouseEventKind::Up(MouseButton::Left)),
+        key(KeyCode::Char('X')),
+    ];
+    for input in &tail {
+        recorded.editor_mut().record_input(input);
+        recorded.editor_mut().apply_replay_input(input).unwrap();
+        recorded.render().unwrap();
+    }
+    assert_eq!(recorded.get_buffer_content().unwrap(), "aXbc\nd");
+
+    let mut replayed = EditorTestHarness::new(80, 24).unwrap();
+    replayed.replay_log(&log).unwrap();
+    assert_eq!(replayed.get_buffer_content().unwrap(), "aXbc\nd");
+    assert_eq!(replayed.terminal_height(), 30);
+}
@@ -2228,3 +2228,61 @@ fn test_ctrl_d_at_word_end() {
         "Ctrl+D at word end should select entire 'word'"
     );
 }
+
+/// Text object actions bound to keys select inside or around the object
+#[test]
+fn test_text_object_keybindings() {
+    use fresh::config::{Config, Keybinding};
+
+    let mut config = Config::default();
+    for (key, action) in [
+        ("q", "select_around_quotes"),
+        ("o", "select_inside_brackets"),
+    ] {
+        config.keybindings.push(Keybinding {
+            key: key.to_string(),
+            modifiers: vec!["alt".to_string()],
+            keys: vec![],
+            action: action.to_string(),
+            args: Default::default(),
+            when: None,
+        });
+    }
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("call(a, \"b c\", d)").unwrap();
+
+    // Inside the quotes, between "b" and " c"
+    for _ in 0..7 {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "\"b c\"");
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "a, \"b c\", d");
+}
+
+/// The status bar counts the selection, and adds up its numbers when asked
+#[test]
+fn test_selection_stats_in_status_bar() {
+    let mut config = fresh::config::Config::default();
+    config.editor.selection_stats_sum = true;
+    let mut harness = EditorTestHarness::with_config(140, 24, config).unwrap();
+    harness.type_text("price 1.5\ntax 0.25").unwrap();
+    harness.render().unwrap();
+    assert!(!harness.get_status_bar().contains("chars"));
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    let status = harness.get_status_bar();
+    assert!(
+        status.contains("18 chars, 4 words, 2 lines, Sum 1.75"),
+        "status bar: {status}"
+    );
+}
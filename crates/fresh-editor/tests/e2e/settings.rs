@@ -877,8 +877,10 @@ fn test_settings_from_terminal_mode_captures_input() {
     harness.assert_screen_contains("Settings");
 
     // Now try to use Settings navigation - press Down to navigate categories
-    // Categories: General, Clipboard, Editor, ...
+    // Categories: General, Clipboard, CSV, Diagnostics, Editor, ...
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Clipboard
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // CSV
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Diagnostics
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Editor
     harness.render().unwrap();
 
@@ -1078,8 +1080,10 @@ fn test_settings_descriptions_render_properly() {
     harness.open_settings().unwrap();
 
     // Navigate to Editor category which has settings with descriptions
-    // Categories: General, Clipboard, Editor, ...
+    // Categories: General, Clipboard, CSV, Diagnostics, Editor, ...
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Clipboard
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // CSV
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Diagnostics
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Editor
     harness.render().unwrap();
 
@@ -1282,9 +1286,11 @@ fn test_settings_percentage_value_saves_correctly() {
     // Open settings
     harness.open_settings().unwrap();
 
-    // Navigate to File Explorer category (down four times from General)
-    // Categories: General, Clipboard, Editor, File Browser, File Explorer, ...
+    // Navigate to File Explorer category (down six times from General)
+    // Categories: General, Clipboard, CSV, Diagnostics, Editor, File Browser, File Explorer, ...
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Clipboard
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // CSV
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Diagnostics
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // Editor
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // File Browser
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap(); // File Explorer
@@ -379,3 +379,64 @@ fn test_large_file_editing_with_slow_fs() {
         "Last line should be present"
     );
 }
+
+/// Slow config where only reading file contents is slow
+fn slow_reads(delay: Duration) -> SlowFsConfig {
+    SlowFsConfig {
+        read_file_delay: delay,
+        ..SlowFsConfig::none()
+    }
+}
+
+#[test]
+fn test_open_file_in_background_with_slow_fs() {
+    // A file that is slow to read opens in a loading tab, then shows its content
+    let mut harness =
+        EditorTestHarness::with_slow_fs(120, 24, slow_reads(Duration::from_millis(300))).unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("slow.txt");
+    std::fs::write(&path, "slow file content\n").unwrap();
+
+    // Keep the scratch buffer from being replaced by the file
+    harness.type_text("scratch").unwrap();
+
+    let buffer_id = harness.editor_mut().open_file_in_background(&path).unwrap();
+    assert!(harness.editor().is_buffer_loading(buffer_id));
+    harness.render().unwrap();
+    harness.assert_screen_contains("slow.txt [...]");
+    harness.assert_screen_contains("Loading");
+
+    // The placeholder is read-only
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+
+    harness
+        .wait_until(|h| !h.editor().is_buffer_loading(buffer_id))
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "slow file content\n");
+    harness.assert_screen_contains("slow file content");
+    harness.assert_screen_not_contains("[...]");
+}
+
+#[test]
+fn test_close_file_before_background_load_finishes() {
+    // Closing the loading tab discards the file when it arrives
+    let mut harness =
+        EditorTestHarness::with_slow_fs(120, 24, slow_reads(Duration::from_millis(300))).unwrap();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("slow.txt");
+    std::fs::write(&path, "slow file content\n").unwrap();
+    harness.type_text("scratch").unwrap();
+
+    let buffer_id = harness.editor_mut().open_file_in_background(&path).unwrap();
+    assert!(harness.editor().is_buffer_loading(buffer_id));
+    harness.editor_mut().close_buffer(buffer_id).unwrap();
+
+    std::thread::sleep(Duration::from_millis(500));
+    harness.process_async_and_render().unwrap();
+    assert!(!harness.editor().is_buffer_loading(buffer_id));
+    assert_eq!(harness.get_buffer_content().unwrap(), "scratch");
+    harness.assert_screen_not_contains("slow.txt [...]");
+    harness.assert_screen_not_contains("slow file content");
+}
@@ -189,7 +189,9 @@ fn test_auto_close_before_whitespace() {
     harness.open_file(&file_path).unwrap();
 
     // Position cursor at beginning (before space)
-    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
 
     // Type opening paren - should auto-close before whitespace
     harness.type_text("(").unwrap();
@@ -1115,11 +1117,281 @@ fn test_jump_through_multiple_errors() {
     );
 }
 
+/// Test that next/previous diagnostic shows the full diagnostic in a popup
+#[test]
+fn test_next_diagnostic_shows_detail_popup() {
+    use fresh::services::async_bridge::AsyncMessage;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "line1\nline2\nline3\nline4\nline5").unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+
+    let mut warning = create_diagnostic(1, 0, 1, 5, "mismatched types\nexpected `u32`");
+    warning.severity = Some(lsp_types::DiagnosticSeverity::WARNING);
+    warning.source = Some("rustc".to_string());
+    warning.code = Some(lsp_types::NumberOrString::String("E0308".to_string()));
+    let diagnostics = vec![warning, create_diagnostic(3, 0, 3, 5, "Error on line 4")];
+
+    // Deliver the diagnostics the way an LSP server would
+    let uri = url::Url::from_file_path(&file_path).unwrap().to_string();
+    if let Some(bridge) = harness.editor().async_bridge() {
+        bridge
+            .sender()
+            .send(AsyncMessage::LspDiagnostics {
+                language: "rust".to_string(),
+                uri,
+                diagnostics,
+            })
+            .unwrap();
+    }
+    harness
+        .wait_until(|h| h.editor().get_stored_diagnostics().len() == 1)
+        .unwrap();
+
+    // Alt+F8 jumps to the first diagnostic and shows its details
+    harness.send_key(KeyCode::F(8), KeyModifiers::ALT).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_cursors().primary().position, 6);
+    harness.assert_screen_contains("Diagnostic 1 of 2");
+    harness.assert_screen_contains("Warning (rustc) [E0308]");
+    harness.assert_screen_contains("expected `u32`");
+
+    // Alt+Shift+F8 wraps around to the last diagnostic
+    harness
+        .send_key(KeyCode::F(8), KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_cursors().primary().position, 18);
+    harness.assert_screen_contains("Diagnostic 2 of 2");
+    harness.assert_screen_contains("Error on line 4");
+    harness.assert_screen_not_contains("Warning (rustc)");
+}
+
+/// Test that inline diagnostics show the message after the line and tint the line
+#[test]
+fn test_inline_diagnostics_render_after_line_end() {
+    use fresh::services::async_bridge::AsyncMessage;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.rs");
+    std::fs::write(&file_path, "line1\nline2\nline3\n").unwrap();
+
+    let mut config = Config::default();
+    config.diagnostics.inline_text = true;
+    config.diagnostics.line_background = true;
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .without_empty_plugins_dir(),
+    )
+    .unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    let mut hint = create_diagnostic(2, 0, 2, 5, "Hint on line 3");
+    hint.severity = Some(lsp_types::DiagnosticSeverity::HINT);
+    let diagnostics = vec![
+        create_diagnostic(1, 2, 1, 4, "Error on line 2\nmore detail"),
+        hint,
+    ];
+
+    let uri = url::Url::from_file_path(&file_path).unwrap().to_string();
+    if let Some(bridge) = harness.editor().async_bridge() {
+        bridge
+            .sender()
+            .send(AsyncMessage::LspDiagnostics {
+                language: "rust".to_string(),
+                uri,
+                diagnostics,
+            })
+            .unwrap();
+    }
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Error on line 2"))
+        .unwrap();
+
+    // Only the first message line is shown, after the line content
+    harness.assert_screen_contains("line2  Error on line 2");
+    harness.assert_screen_not_contains("more detail");
+
+    // Hints are hidden by default
+    harness.assert_screen_not_contains("Hint on line 3");
+
+    // The line background is tinted all the way to the right edge
+    let (_, y) = harness.find_text_on_screen("Error on line 2").unwrap();
+    let (_, clean_y) = harness.find_text_on_screen("line1").unwrap();
+    let tinted = harness.get_cell_style(70, y).unwrap().bg;
+    let untinted = harness.get_cell_style(70, clean_y).unwrap().bg;
+    assert_ne!(tinted, untinted);
+}
+
+// =============================================================================
+// Markup Tag Tests
+// =============================================================================
+
+/// Run a command palette entry by name
+fn run_palette_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test that finishing an opening tag inserts its closing tag, except for void elements
+#[test]
+fn test_auto_close_html_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.html");
+    std::fs::write(&file_path, "").unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+
+    harness.type_text("<p class=\"a>b\">").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<p class=\"a>b\"></p>"
+    );
+    let cursor_pos = harness.editor().active_cursors().primary().position;
+    assert_eq!(cursor_pos, 15, "Cursor should be between the tags");
+
+    harness.type_text("x<br>y").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<p class=\"a>b\">x<br>y</p>"
+    );
+}
+
+/// Test that typing `</` closes the innermost open element in XML
+#[test]
+fn test_auto_close_xml_closing_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.xml");
+    std::fs::write(&file_path, "<root>\n  <item/>\n  <name>x").unwrap();
+
+    // XML is detected from its TextMate syntax, which needs the full registry
+    let mut config = Config::default();
+    config.editor.auto_indent = true;
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .without_empty_plugins_dir()
+            .with_full_grammar_registry(),
+    )
+    .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.type_text("</").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<root>\n  <item/>\n  <name>x</name>"
+    );
+    harness.type_text("\n</").unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.ends_with("</root>"), "Content: {content:?}");
+}
+
+/// Test that editing an opening tag name renames its closing tag
+#[test]
+fn test_rename_closing_tag_with_opening_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.html");
+    std::fs::write(&file_path, "<div>\n  <b>text</b>\n</div>\n").unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+
+    harness.type_text("x").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<divx>\n  <b>text</b>\n</divx>\n"
+    );
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<di>\n  <b>text</b>\n</di>\n"
+    );
+    let cursor_pos = harness.editor().active_cursors().primary().position;
+    assert_eq!(cursor_pos, 3, "Cursor should stay in the opening tag");
+
+    // A space starts the attributes and leaves the closing tag alone
+    harness.type_text(" id=\"x\"").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "<di id=\"x\">\n  <b>text</b>\n</di>\n"
+    );
+}
+
+/// Test jumping between matching tags and growing an element selection
+#[test]
+fn test_goto_matching_tag_and_select_element() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.html");
+    let content = "<ul>\n  <li>one</li>\n</ul>\n";
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = harness_with_auto_indent();
+    harness.open_file(&file_path).unwrap();
+
+    run_palette_command(&mut harness, "Go to Matching Tag");
+    let cursor_pos = harness.editor().active_cursors().primary().position;
+    assert_eq!(cursor_pos, content.find("</ul>").unwrap());
+    run_palette_command(&mut harness, "Go to Matching Tag");
+    assert_eq!(harness.editor().active_cursors().primary().position, 0);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    run_palette_command(&mut harness, "Select Element");
+    let li = content.find("<li>").unwrap()..content.find("\n</ul>").unwrap();
+    assert_eq!(
+        harness
+            .editor()
+            .active_cursors()
+            .primary()
+            .selection_range(),
+        Some(li)
+    );
+
+    run_palette_command(&mut harness, "Select Element");
+    assert_eq!(
+        harness
+            .editor()
+            .active_cursors()
+            .primary()
+            .selection_range(),
+        Some(0..content.len() - 1)
+    );
+}
+
 // =============================================================================
 // Block/Rectangular Selection Tests
 // =============================================================================
 
-/// Test that block selection starts with Alt+Shift+Right
+/// Test that block selection starts with Ctrl+Alt+Shift+Right
 #[test]
 fn test_block_selection_start() {
     let temp_dir = TempDir::new().unwrap();
@@ -1132,9 +1404,12 @@ fn test_block_selection_start() {
     // Cursor starts at position 0
     harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
 
-    // Start block selection with Alt+Shift+Right
+    // Start block selection with Ctrl+Alt+Shift+Right
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness.render().unwrap();
 
@@ -1214,10 +1489,16 @@ fn test_block_selection_rectangle() {
 
     // Extend block selection right twice (columns 1-3)
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness
-        .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Right,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
 
     // Extend block selection down once (lines 0-1)
@@ -1273,9 +1554,12 @@ fn test_block_selection_left() {
         .send_key(KeyCode::Right, KeyModifiers::NONE)
         .unwrap();
 
-    // Start block selection with Alt+Shift+Left
+    // Start block selection with Ctrl+Alt+Shift+Left
     harness
-        .send_key(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .send_key(
+            KeyCode::Left,
+            KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        )
         .unwrap();
     harness.render().unwrap();
 
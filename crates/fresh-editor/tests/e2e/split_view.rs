@@ -608,3 +608,186 @@ fn test_close_terminal_in_split_closes_split() {
     // Terminal should be gone
     harness.assert_screen_not_contains("Terminal");
 }
+
+/// Run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, query: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text(query).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Test directional split focus with Ctrl+Alt+h/l
+#[test]
+fn test_focus_split_by_direction() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let left_split = harness.editor().get_active_split();
+
+    run_command(&mut harness, "split vert");
+    let right_split = harness.editor().get_active_split();
+    assert_ne!(left_split, right_split);
+
+    harness
+        .send_key(
+            KeyCode::Char('h'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    assert_eq!(harness.editor().get_active_split(), left_split);
+
+    // Nothing further left: focus stays put
+    harness
+        .send_key(
+            KeyCode::Char('h'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().get_active_split(), left_split);
+    harness.assert_screen_contains("No split in that direction");
+
+    harness
+        .send_key(
+            KeyCode::Char('l'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    assert_eq!(harness.editor().get_active_split(), right_split);
+}
+
+/// Test that resizing the active split changes its parent's ratio
+#[test]
+fn test_increase_split_size() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    run_command(&mut harness, "split vert");
+
+    let separators = harness.editor().get_separator_areas().to_vec();
+    let (container, _, _, _, _) = separators[0];
+    let before = harness.editor().get_split_ratio(container).unwrap();
+
+    // The active split is the second (right) child, so growing it shrinks the ratio
+    run_command(&mut harness, "increase split size");
+    let after = harness.editor().get_split_ratio(container).unwrap();
+    assert!(after < before, "ratio {before} should shrink, got {after}");
+}
+
+/// Test closing every split except the active one
+#[test]
+fn test_close_other_splits() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    run_command(&mut harness, "split vert");
+    run_command(&mut harness, "split horiz");
+    assert_eq!(harness.editor().get_split_count(), 3);
+
+    let active = harness.editor().get_active_split();
+    run_command(&mut harness, "close other splits");
+
+    assert_eq!(harness.editor().get_split_count(), 1);
+    assert_eq!(harness.editor().get_active_split(), active);
+}
+
+/// Test saving a named layout and restoring it after collapsing the splits
+#[test]
+fn test_save_and_restore_named_layout() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    run_command(&mut harness, "split vert");
+    run_command(&mut harness, "split horiz");
+    assert_eq!(harness.editor().get_split_count(), 3);
+
+    run_command(&mut harness, "save layout");
+    harness.type_text("review").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().named_layout_names(), vec!["review"]);
+
+    run_command(&mut harness, "close other splits");
+    assert_eq!(harness.editor().get_split_count(), 1);
+
+    run_command(&mut harness, "restore layout");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().get_split_count(), 3);
+    harness.assert_screen_contains("Restored layout 'review'");
+
+    run_command(&mut harness, "delete layout");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.editor().named_layout_names().is_empty());
+}
+
+/// `-O` queues each file after the first into its own vertical split
+#[test]
+fn test_cli_files_open_in_splits() {
+    use fresh::model::event::SplitDirection;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let paths: Vec<_> = ["one.txt", "two.txt", "three.txt"]
+        .iter()
+        .map(|name| {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, format!("text in {name}")).unwrap();
+            path
+        })
+        .collect();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness
+        .editor_mut()
+        .queue_file_open(paths[0].clone(), None, None);
+    for path in &paths[1..] {
+        harness.editor_mut().queue_file_open_in_split(
+            path.clone(),
+            None,
+            None,
+            SplitDirection::Vertical,
+        );
+    }
+    harness.editor_mut().process_pending_file_opens();
+    harness.render().unwrap();
+
+    assert_eq!(harness.editor().get_split_count(), 3);
+    harness.assert_screen_contains("text in one.txt");
+    harness.assert_screen_contains("text in two.txt");
+    harness.assert_screen_contains("text in three.txt");
+
+    // Each split shows only its own file
+    let active_split = harness.editor().get_active_split();
+    assert_eq!(harness.editor().get_split_tabs(active_split).len(), 1);
+}
+
+/// `--wait` quits once every file given on the command line has been closed
+#[test]
+fn test_wait_quits_when_files_closed() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let message = temp_dir.path().join("COMMIT_EDITMSG");
+    let other = temp_dir.path().join("other.txt");
+    std::fs::write(&message, "fix bug\n").unwrap();
+    std::fs::write(&other, "other\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness
+        .editor_mut()
+        .queue_file_open(message.clone(), None, None);
+    harness
+        .editor_mut()
+        .set_wait_for_files(std::slice::from_ref(&message));
+    harness.editor_mut().process_pending_file_opens();
+    harness.open_file(&other).unwrap();
+
+    // Closing an unrelated file keeps the editor running
+    harness.editor_mut().close_tab();
+    assert!(!harness.should_quit());
+
+    harness.editor_mut().close_tab();
+    assert!(harness.should_quit());
+}
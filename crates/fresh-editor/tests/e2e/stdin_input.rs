@@ -255,3 +255,76 @@ fn test_stdin_large_file_lazy_loading() {
     harness.render().unwrap();
     harness.assert_screen_contains("[stdin]");
 }
+
+/// Test pager mode keys: read-only buffer, G jumps to the end, q quits
+#[test]
+fn test_pager_mode_keys() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_pager_mode(true);
+
+    let content = "first\nsecond\nthird\n";
+    let temp_file = create_stdin_temp_file(content);
+    harness
+        .editor_mut()
+        .open_stdin_buffer(temp_file.path(), None)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[RO]");
+
+    // Plain letters navigate instead of inserting text
+    harness
+        .send_key(KeyCode::Char('G'), KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), content.len());
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+    harness
+        .send_key(KeyCode::Char('j'), KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), "first\n".len());
+    harness.assert_buffer_content(content);
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.should_quit());
+}
+
+/// Test pager follow mode keeps the cursor at the end of growing input
+#[test]
+fn test_pager_follow_mode() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::sync::mpsc;
+    use std::thread;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_pager_mode(true);
+
+    let mut temp_file = create_stdin_temp_file("line 1\n");
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let thread_handle = thread::spawn(move || -> anyhow::Result<()> {
+        let _ = done_rx.recv();
+        Ok(())
+    });
+    harness
+        .editor_mut()
+        .open_stdin_buffer(temp_file.path(), Some(thread_handle))
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('F'), KeyModifiers::SHIFT)
+        .unwrap();
+    assert!(harness.editor().is_pager_following());
+    assert_eq!(harness.cursor_position(), "line 1\n".len());
+
+    temp_file.write_all(b"line 2\nline 3\n").unwrap();
+    temp_file.flush().unwrap();
+    harness.editor_mut().poll_stdin_streaming();
+    assert_eq!(harness.cursor_position(), "line 1\nline 2\nline 3\n".len());
+
+    done_tx.send(()).unwrap();
+}
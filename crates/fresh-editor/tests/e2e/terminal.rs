@@ -2954,3 +2954,177 @@ fn test_windows_terminal_shows_prompt_and_executes_command() {
         screen
     );
 }
+
+/// The terminal panel toggles open and closed, keeping its terminals alive
+#[test]
+fn test_terminal_panel_toggle_keeps_terminals() {
+    let mut harness = harness_or_return!(100, 30);
+    harness.render().unwrap();
+
+    // Ctrl+` opens the panel below the editor with a fresh terminal
+    harness
+        .send_key(KeyCode::Char('`'), KeyModifiers::CONTROL)
+        .unwrap();
+    let panel = harness
+        .editor()
+        .terminal_panel_split()
+        .expect("terminal panel should be open");
+    assert_eq!(harness.editor().get_split_count(), 2);
+    assert_eq!(harness.editor().get_active_split(), panel);
+    assert!(harness.editor().is_terminal_mode());
+
+    // A second terminal becomes another tab in the panel
+    harness.editor_mut().new_panel_terminal();
+    harness.render().unwrap();
+    let tabs = harness.editor().get_split_tabs(panel);
+    assert_eq!(tabs.len(), 2);
+    harness.assert_screen_contains("*Terminal 0*");
+    harness.assert_screen_contains("*Terminal 1*");
+
+    // Toggling while focused hides the panel; the terminals keep running
+    harness
+        .send_key(KeyCode::Char('`'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert!(harness.editor().terminal_panel_split().is_none());
+    assert_eq!(harness.editor().get_split_count(), 1);
+    assert!(!harness.editor().is_terminal_mode());
+    harness.assert_screen_not_contains("*Terminal 0*");
+    assert!(tabs
+        .iter()
+        .all(|id| harness.editor().is_terminal_buffer(*id)));
+
+    // Showing it again restores the same tabs
+    harness
+        .send_key(KeyCode::Char('`'), KeyModifiers::CONTROL)
+        .unwrap();
+    let panel = harness.editor().terminal_panel_split().unwrap();
+    assert_eq!(harness.editor().get_split_tabs(panel), tabs);
+}
+
+/// Closing the other splits keeps the terminal panel
+#[test]
+fn test_terminal_panel_survives_close_other_splits() {
+    let mut harness = harness_or_return!(100, 30);
+    harness.editor_mut().split_pane_vertical();
+    harness.editor_mut().toggle_terminal_panel();
+    assert_eq!(harness.editor().get_split_count(), 3);
+
+    // Return to an editor split and close the others
+    harness.editor_mut().next_split();
+    assert_ne!(
+        harness.editor().terminal_panel_split(),
+        Some(harness.editor().get_active_split())
+    );
+    harness.editor_mut().close_other_splits();
+
+    assert_eq!(harness.editor().get_split_count(), 2);
+    let panel = harness.editor().terminal_panel_split().unwrap();
+    assert_eq!(harness.editor().get_split_tabs(panel).len(), 1);
+}
+
+/// Terminals can be given a name shown on their tab
+#[test]
+fn test_rename_terminal() {
+    let mut harness = harness_or_return!(100, 30);
+    harness.editor_mut().toggle_terminal_panel();
+
+    // The current name is preselected, so typing replaces it
+    harness.editor_mut().start_rename_terminal_prompt();
+    harness.type_text("server").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*server*");
+    harness.assert_screen_not_contains("*Terminal 0*");
+}
+
+/// Send to REPL starts the language's REPL in the panel and steps through lines
+#[test]
+fn test_send_to_repl_starts_repl_and_advances() {
+    use fresh::config::Config;
+    use tempfile::TempDir;
+
+    if harness_or_skip(1, 1).is_none() {
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("script.py");
+    std::fs::write(&file, "print('hello_repl')\n\nprint('second')\n").unwrap();
+
+    // A filter that upper-cases its input stands in for a real REPL
+    let mut config = Config::default();
+    config.languages.get_mut("python").unwrap().repl =
+        Some("awk '{ print toupper($0); fflush() }'".to_string());
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    let file_buffer = harness.editor().active_buffer();
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::CONTROL)
+        .unwrap();
+
+    // The REPL opens in the panel while focus stays in the file
+    let panel = harness
+        .editor()
+        .terminal_panel_split()
+        .expect("terminal panel should be open");
+    assert_ne!(harness.editor().get_active_split(), panel);
+    assert_eq!(harness.editor().active_buffer(), file_buffer);
+    assert!(!harness.editor().is_terminal_mode());
+    harness
+        .wait_until(|h| h.screen_to_string().contains("PRINT('HELLO_REPL')"))
+        .unwrap();
+
+    // The cursor skips the blank line to the next statement
+    let second = "print('hello_repl')\n\n".len();
+    assert_eq!(harness.cursor_position(), second);
+
+    // Later sends reuse the same REPL terminal
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("PRINT('SECOND')"))
+        .unwrap();
+    assert_eq!(harness.editor().get_split_tabs(panel).len(), 1);
+}
+
+/// OSC 133 marks let scrollback jump to a command's prompt and show its exit status
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses printf from a Unix shell
+fn test_shell_integration_command_navigation() {
+    let mut harness = harness_or_return!(100, 30);
+    harness
+        .editor_mut()
+        .set_terminal_jump_to_end_on_output(false);
+    harness.editor_mut().open_terminal();
+
+    // Print what a shell with integration would around a failing command
+    harness.editor_mut().send_terminal_input(
+        b"printf '\\033]133;A\\007MARK$ \\033]133;B\\007\\n\\033]133;C\\007OSC_OUTPUT\\n\\033]133;D;3\\007'\n",
+    );
+    harness
+        .wait_until(|h| {
+            h.screen_to_string()
+                .lines()
+                .any(|line| line.trim_start().starts_with("OSC_OUTPUT"))
+        })
+        .unwrap();
+
+    // Jumping from terminal mode switches to scrollback at the prompt
+    harness
+        .send_key(KeyCode::PageUp, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.editor().is_terminal_mode());
+    harness.assert_screen_contains("Command exited with code 3");
+    harness.assert_screen_contains("✗ 3");
+}
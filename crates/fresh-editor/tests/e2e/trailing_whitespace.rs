@@ -0,0 +1,64 @@
+//! E2E tests for trailing whitespace highlighting
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+/// Gutter width for a small buffer: 1 (indicator) + 4 (digits) + 3 (" │ ")
+const GUTTER: u16 = 8;
+
+fn is_highlighted(harness: &EditorTestHarness, column: u16, line: u16) -> bool {
+    let (first_row, _) = harness.content_area_rows();
+    let color = harness.editor().theme().trailing_whitespace_bg;
+    harness
+        .get_cell_style(GUTTER + column, first_row as u16 + line)
+        .is_some_and(|style| style.bg == Some(color))
+}
+
+/// Whitespace at the end of lines is highlighted, except on the cursor line
+#[test]
+fn test_trailing_whitespace_highlighted_off_cursor_line() {
+    let mut config = Config::default();
+    config.editor.highlight_trailing_whitespace = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("one  \ntwo\t\nthree \n")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(!is_highlighted(&harness, 3, 0), "cursor line is left alone");
+    assert!(is_highlighted(&harness, 3, 1), "trailing tab");
+    assert!(is_highlighted(&harness, 5, 2), "trailing space");
+    assert!(!is_highlighted(&harness, 4, 2), "text before it");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(is_highlighted(&harness, 3, 0));
+    assert!(is_highlighted(&harness, 4, 0));
+    assert!(!is_highlighted(&harness, 5, 2));
+
+    // Removing the whitespace removes the highlight
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(!is_highlighted(&harness, 3, 1));
+}
+
+/// Highlighting is off unless configured
+#[test]
+fn test_trailing_whitespace_not_highlighted_by_default() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("one  \ntwo\n").unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    assert!(!is_highlighted(&harness, 3, 0));
+}
@@ -0,0 +1,106 @@
+//! E2E tests for the interactive tutorial
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::fs;
+use tempfile::TempDir;
+
+fn tutor_harness(temp: &TempDir) -> EditorTestHarness {
+    let dir_context = DirectoryContext::for_testing(&temp.path().join("context"));
+    let project = temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    EditorTestHarness::with_shared_dir_context(100, 30, Config::default(), project, dir_context)
+        .unwrap()
+}
+
+/// Put the cursor at `position`, selecting from `anchor` if given
+fn place_cursor(harness: &mut EditorTestHarness, anchor: Option<usize>, position: usize) {
+    let cursor = harness.editor_mut().active_cursors_mut().primary_mut();
+    cursor.position = position;
+    cursor.anchor = anchor;
+}
+
+#[test]
+fn test_tutor_shows_keys_of_keymap() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = tutor_harness(&temp);
+    harness.editor_mut().show_tutor();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Tutor*");
+    harness.assert_screen_contains("Welcome to the Fresh Tutorial");
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.contains("Lesson 1: Moving around\n"));
+    assert!(!content.contains("{key:"));
+}
+
+#[test]
+fn test_tutor_checks_exercises_and_keeps_progress() {
+    let temp = TempDir::new().unwrap();
+    {
+        let mut harness = tutor_harness(&temp);
+        harness.editor_mut().show_tutor();
+        let content = harness.get_buffer_content().unwrap();
+
+        // Fix the typos by replacing the exercise line
+        let line = "Thhe quick brown fox jumpss ovr the lazy dog.";
+        let start = content.find(line).unwrap();
+        place_cursor(&mut harness, Some(start), start + line.len());
+        harness
+            .type_text("The quick brown fox jumps over the lazy dog.")
+            .unwrap();
+        assert!(harness.editor_mut().check_tutor());
+        harness.render().unwrap();
+        assert!(harness.get_status_bar().contains("lesson 2 done (1/6)"));
+
+        // Nothing changed since, so nothing more to report
+        assert!(!harness.editor_mut().check_tutor());
+
+        // Closing the scratch copy doesn't ask to save it
+        harness
+            .send_key(KeyCode::Char('w'), KeyModifiers::ALT)
+            .unwrap();
+        harness.assert_screen_not_contains("*Tutor*");
+    }
+
+    // Opening the tutorial again ticks off the lesson and starts at the
+    // first unfinished one
+    let mut harness = tutor_harness(&temp);
+    harness.editor_mut().show_tutor();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.contains("Lesson 2: Typing and deleting  [done]\n"));
+    assert!(content.contains("--> Thhe quick brown fox"));
+    assert_eq!(
+        harness.cursor_position(),
+        content.find("Lesson 1: Moving around").unwrap()
+    );
+    assert!(harness.get_status_bar().contains("1/6 lessons done"));
+}
+
+#[test]
+fn test_tutor_selection_exercise() {
+    let temp = TempDir::new().unwrap();
+    let mut harness = tutor_harness(&temp);
+    harness.editor_mut().show_tutor();
+    let content = harness.get_buffer_content().unwrap();
+    let start = content.find("elephant never").unwrap();
+
+    place_cursor(&mut harness, None, start);
+    for _ in 0.."elephan".len() {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    assert!(!harness.editor_mut().check_tutor());
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "elephant");
+    assert!(harness.editor_mut().check_tutor());
+    harness.render().unwrap();
+    assert!(harness.get_status_bar().contains("lesson 3 done"));
+}
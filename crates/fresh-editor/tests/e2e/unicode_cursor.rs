@@ -27,7 +27,9 @@ fn test_cursor_sync_with_non_ascii_box_drawing_chars() {
         expected_buffer_pos, buffer_pos
     );
 
-    // Move cursor to the beginning of the line
+    // Move cursor to the beginning of the line; the first Home stops at
+    // the indentation
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
     harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
 
     // Cursor should now be at position 0
@@ -1118,3 +1120,59 @@ fn test_left_arrow_at_long_position_file_loaded() {
          If this is 51, the bug is present: left arrow fell back to code point movement."
     );
 }
+
+/// Move right `count` times and return the cursor's screen position
+fn cursor_after_right(harness: &mut EditorTestHarness, count: usize) -> (u16, u16) {
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    for _ in 0..count {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.render().unwrap();
+    harness.screen_cursor_position()
+}
+
+/// Ambiguous-width characters take one column unless configured otherwise,
+/// while CJK characters always take two
+#[test]
+fn test_cursor_columns_with_narrow_ambiguous_width() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("±→x").unwrap();
+    let (start_x, _) = cursor_after_right(&mut harness, 0);
+    assert_eq!(cursor_after_right(&mut harness, 2).0, start_x + 2);
+    harness.assert_screen_contains("±→x");
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("你好x").unwrap();
+    assert_eq!(cursor_after_right(&mut harness, 2).0, start_x + 4);
+}
+
+/// With `ambiguous_width: wide`, ambiguous characters take two columns for
+/// drawing, cursor placement and mouse clicks alike
+#[test]
+fn test_cursor_and_clicks_with_wide_ambiguous_width() {
+    let mut config = fresh::config::Config::default();
+    config.editor.ambiguous_width = fresh::config::AmbiguousWidth::Wide;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("±→x").unwrap();
+    let (start_x, row) = cursor_after_right(&mut harness, 0);
+
+    assert_eq!(cursor_after_right(&mut harness, 1).0, start_x + 2);
+    assert_eq!(cursor_after_right(&mut harness, 2).0, start_x + 4);
+    // The second column of each character is a blank the terminal draws over
+    harness.assert_screen_contains("± → x");
+
+    harness.mouse_click(start_x + 4, row).unwrap();
+    assert_eq!(harness.cursor_position(), "±→".len());
+    harness.mouse_click(start_x + 3, row).unwrap();
+    assert_eq!(harness.cursor_position(), "±".len());
+    harness.mouse_click(start_x + 2, row).unwrap();
+    assert_eq!(harness.cursor_position(), "±".len());
+
+    harness.type_text("y").unwrap();
+    harness.assert_buffer_content("±y→x");
+}
@@ -0,0 +1,91 @@
+//! Tests for the Unicode character picker and inspector
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A character picked by name is inserted at every cursor as one undo step
+#[test]
+fn test_picker_inserts_at_all_cursors() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("one\ntwo").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        .unwrap();
+
+    run_command(&mut harness, "Insert Unicode Character");
+    harness.type_text("greek small alpha").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("α  GREEK SMALL LETTER ALPHA");
+    harness.assert_screen_contains("U+03B1  Greek and Coptic");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "αone\nαtwo");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "one\ntwo");
+}
+
+/// Emoji are found by their shortcodes, and code points by `U+XXXX`
+#[test]
+fn test_picker_finds_emoji_and_codepoints() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("").unwrap();
+
+    run_command(&mut harness, "Insert Unicode Character");
+    harness.type_text("tada").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("party popper");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    run_command(&mut harness, "Insert Unicode Character");
+    harness.type_text("U+2192").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "🎉→");
+
+    // With no match, Enter inserts nothing
+    run_command(&mut harness, "Insert Unicode Character");
+    harness.type_text("zzzz no such character").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "🎉→");
+}
+
+/// The inspector breaks the character under the cursor into code points
+#[test]
+fn test_inspect_character_under_cursor() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    let _fixture = harness.load_buffer_from_text("e\u{301}x").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    run_command(&mut harness, "Inspect Character");
+    harness.assert_screen_contains("2 code points, 3 UTF-8 bytes");
+    harness.assert_screen_contains("U+0065   65     LATIN SMALL LETTER E (Basic Latin)");
+    harness.assert_screen_contains(
+        "U+0301   CC 81  COMBINING ACUTE ACCENT (Combining Diacritical Marks)",
+    );
+}
@@ -0,0 +1,68 @@
+//! E2E tests for viewing and reverting unsaved changes
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use tempfile::TempDir;
+
+/// Open a file and append "!" to its second line
+fn edited_harness() -> (TempDir, EditorTestHarness) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path, "one\ntwo\nthree\nfour\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("one\ntwo!\nthree\nfour\n");
+    (dir, harness)
+}
+
+#[test]
+fn test_unified_view_reverts_change() {
+    let (_dir, mut harness) = edited_harness();
+    harness.editor_mut().show_unsaved_changes(true);
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("@@ -1,4 +1,4 @@");
+    harness.assert_screen_contains("-two");
+    harness.assert_screen_contains("+two!");
+    harness.assert_screen_contains("1 unsaved change(s)");
+
+    // Header, context line, then the removed line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('r'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Change reverted, 0 left");
+    harness.assert_screen_not_contains("+two!");
+
+    harness
+        .send_key(KeyCode::Char('q'), KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("one\ntwo\nthree\nfour\n");
+}
+
+#[test]
+fn test_side_by_side_view_reverts_change() {
+    let (_dir, mut harness) = edited_harness();
+    harness.editor_mut().show_unsaved_changes(false);
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Diff: file.txt*");
+    harness.assert_screen_contains("1 unsaved change(s)");
+
+    harness.send_key(KeyCode::F(7), KeyModifiers::NONE).unwrap();
+    harness.editor_mut().revert_hunk();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Change reverted, 0 left");
+
+    // Closing the view keeps the file open
+    let view = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(view).unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("one\ntwo\nthree\nfour\n");
+}
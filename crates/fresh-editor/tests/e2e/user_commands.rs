@@ -0,0 +1,131 @@
+//! E2E tests for user-defined commands from the config
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, Keybinding};
+
+fn config_with_commands(commands: serde_json::Value) -> Config {
+    Config {
+        commands: serde_json::from_value(commands).unwrap(),
+        ..Default::default()
+    }
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A command bound by its name runs its actions and shell steps in order,
+/// including other user commands
+#[test]
+#[cfg_attr(not(unix), ignore = "Shell commands require Unix-like environment")]
+fn test_bound_user_command_runs_steps() {
+    let mut config = config_with_commands(serde_json::json!({
+        "shout": {
+            "description": "Uppercase the buffer",
+            "steps": [
+                { "action": "select_all" },
+                { "shell": "tr a-z A-Z", "output": "replace" }
+            ]
+        },
+        "shout_and_dup": {
+            "steps": [
+                { "action": "shout" },
+                { "action": "move_document_start" },
+                { "action": "duplicate_line" }
+            ]
+        }
+    }));
+    config.keybindings.push(Keybinding {
+        key: "F5".to_string(),
+        modifiers: vec![],
+        keys: vec![],
+        action: "shout_and_dup".to_string(),
+        args: Default::default(),
+        when: None,
+    });
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("hello\nworld").unwrap();
+
+    harness.send_key(KeyCode::F(5), KeyModifiers::NONE).unwrap();
+    harness.assert_buffer_content("HELLO\nHELLO\nWORLD");
+}
+
+/// Commands are listed in the palette, and one with an invalid step reports
+/// it without running any step
+#[test]
+fn test_palette_user_command_and_invalid_step() {
+    let config = config_with_commands(serde_json::json!({
+        "twice": { "steps": [{ "action": "duplicate_line" }] },
+        "broken": {
+            "steps": [
+                { "action": "select_all" },
+                { "action": "no_such_action" }
+            ]
+        }
+    }));
+    let mut harness = EditorTestHarness::with_config(140, 24, config).unwrap();
+    harness.type_text("line").unwrap();
+
+    run_command(&mut harness, "twice");
+    harness.assert_buffer_content("line\nline");
+
+    run_command(&mut harness, "broken");
+    harness.assert_screen_contains("step 2: unknown action no_such_action");
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("line\n!line");
+}
+
+/// File variables reach the shell as single words, so quotes, `$(…)` and
+/// `${…}` in a file name are passed through as text
+#[test]
+#[cfg(unix)]
+fn test_shell_variables_are_quoted() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let name = "it's ${line} $(echo hi).txt";
+    let file_path = temp_dir.path().join(name);
+    std::fs::write(&file_path, "text").unwrap();
+
+    let config = config_with_commands(serde_json::json!({
+        "name_line": {
+            "steps": [
+                { "action": "select_all" },
+                { "shell": "printf '%s:%s' ${filename} ${line}", "output": "replace" }
+            ]
+        }
+    }));
+    let mut harness = EditorTestHarness::with_config(140, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    run_command(&mut harness, "name_line");
+    harness.assert_buffer_content(&format!("{}:1", name));
+}
+
+/// A shell step that doesn't read its input succeeds even when the input
+/// is more than the pipe holds
+#[test]
+#[cfg(unix)]
+fn test_shell_step_ignoring_input() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("big.txt");
+    std::fs::write(&file_path, "x".repeat(128 * 1024)).unwrap();
+
+    let config = config_with_commands(serde_json::json!({
+        "stamp": {
+            "steps": [{ "shell": "printf stamped", "output": "replace" }]
+        }
+    }));
+    let mut harness = EditorTestHarness::with_config(140, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    run_command(&mut harness, "stamp");
+    harness.assert_buffer_content("stamped");
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
 use fresh::config::Config;
 use std::fs;
 use std::time::Duration;
@@ -129,3 +130,34 @@ fn test_auto_recovery_save_throttled_before_interval() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_persistent_auto_save_cleans_up_whitespace_off_cursor_lines() -> anyhow::Result<()> {
+    let mut config = auto_save_config(2);
+    config.editor.trim_trailing_whitespace_on_save = true;
+    config.editor.ensure_final_newline_on_save = true;
+
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config)?;
+    let temp_dir = harness.project_dir().unwrap();
+    let file_path = temp_dir.join("test_cleanup.txt");
+    fs::write(&file_path, "one  \ntwo")?;
+
+    harness.open_file(&file_path)?;
+    harness.send_key(KeyCode::End, KeyModifiers::CONTROL)?;
+    harness.type_text(" three ")?;
+
+    // The line being typed on keeps its whitespace, and no newline is added
+    // after it
+    harness.advance_time(Duration::from_millis(2100));
+    assert_eq!(harness.editor_mut().auto_save_persistent_buffers()?, 1);
+    assert_eq!(fs::read_to_string(&file_path)?, "one\ntwo three ");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::CONTROL)?;
+    harness.type_text("x")?;
+    harness.advance_time(Duration::from_millis(2100));
+    assert_eq!(harness.editor_mut().auto_save_persistent_buffers()?, 1);
+    assert_eq!(fs::read_to_string(&file_path)?, "xone\ntwo three\n");
+    assert_eq!(harness.cursor_position(), 1);
+
+    Ok(())
+}
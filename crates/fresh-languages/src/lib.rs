@@ -485,6 +485,83 @@ impl Language {
             _ => HighlightCategory::from_default_index(index),
         }
     }
+
+    /// Highlight names the configuration for this language recognizes.
+    ///
+    /// Indices into this list are what [`Self::highlight_category`] maps.
+    pub fn highlight_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::TypeScript => TYPESCRIPT_HIGHLIGHT_CAPTURES,
+            _ => DEFAULT_HIGHLIGHT_CAPTURES,
+        }
+    }
+
+    /// Get the tree-sitter injections query for this language, if its grammar ships one.
+    ///
+    /// Injections mark regions that are highlighted with another language, e.g. the
+    /// body of an HTML `<script>` element or the token tree of a Rust macro call.
+    pub fn injections_query(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => Some(tree_sitter_rust::INJECTIONS_QUERY),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::INJECTIONS_QUERY),
+            #[cfg(feature = "tree-sitter-html")]
+            Self::HTML => Some(tree_sitter_html::INJECTIONS_QUERY),
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => Some(tree_sitter_php::INJECTIONS_QUERY),
+            #[cfg(feature = "tree-sitter-lua")]
+            Self::Lua => Some(tree_sitter_lua::INJECTIONS_QUERY),
+            _ => None,
+        }
+    }
+
+    /// Get the tree-sitter grammar for this language, if its support is enabled.
+    ///
+    /// Use this to parse a syntax tree directly, e.g. to find the statement
+    /// under the cursor.
+    pub fn grammar(&self) -> Option<tree_sitter::Language> {
+        match self {
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-python")]
+            Self::Python => Some(tree_sitter_python::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            #[cfg(feature = "tree-sitter-html")]
+            Self::HTML => Some(tree_sitter_html::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-css")]
+            Self::CSS => Some(tree_sitter_css::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c")]
+            Self::C => Some(tree_sitter_c::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-cpp")]
+            Self::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => Some(tree_sitter_go::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-json")]
+            Self::Json => Some(tree_sitter_json::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-java")]
+            Self::Java => Some(tree_sitter_java::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c-sharp")]
+            Self::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+            #[cfg(feature = "tree-sitter-ruby")]
+            Self::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-bash")]
+            Self::Bash => Some(tree_sitter_bash::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-lua")]
+            Self::Lua => Some(tree_sitter_lua::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-pascal")]
+            Self::Pascal => Some(tree_sitter_pascal::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-odin")]
+            Self::Odin => Some(tree_sitter_odin::LANGUAGE.into()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
 }
 
 impl Language {
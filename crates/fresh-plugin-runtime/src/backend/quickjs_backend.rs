@@ -88,9 +88,9 @@
 
 use anyhow::{anyhow, Result};
 use fresh_core::api::{
-    ActionSpec, BufferInfo, CompositeHunk, CreateCompositeBufferOptions, EditorStateSnapshot,
-    JsCallbackId, LanguagePackConfig, LspServerPackConfig, OverlayOptions, PluginCommand,
-    PluginResponse,
+    ActionSpec, BeforeSaveResult, BufferInfo, CompletionContext, CompositeHunk,
+    CreateCompositeBufferOptions, EditorStateSnapshot, JsCallbackId, LanguagePackConfig,
+    LspServerPackConfig, OverlayOptions, PluginCommand, PluginLogLevel, PluginResponse,
 };
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
@@ -348,6 +348,7 @@ fn format_js_error(
 fn log_js_error(ctx: &rquickjs::Ctx<'_>, err: rquickjs::Error, context: &str) {
     let error = format_js_error(ctx, err, context);
     tracing::error!("{}", error);
+    report_to_plugin_console(ctx, &error.to_string());
 
     // When enabled, panic on JS errors to make them visible and fail fast
     if should_panic_on_js_errors() {
@@ -355,6 +356,28 @@ fn log_js_error(ctx: &rquickjs::Ctx<'_>, err: rquickjs::Error, context: &str) {
     }
 }
 
+/// Send an uncaught error to the plugin console of the context's plugin
+fn report_to_plugin_console(ctx: &rquickjs::Ctx<'_>, error: &str) {
+    let Ok(editor) = ctx
+        .globals()
+        .get::<_, rquickjs::Class<JsEditorApi>>("editor")
+    else {
+        return;
+    };
+    let Ok(editor) = editor.try_borrow() else {
+        return;
+    };
+    let (message, stack) = match error.split_once('\n') {
+        Some((message, stack)) => (message, Some(stack.trim_start_matches("Stack trace:\n"))),
+        None => (error, None),
+    };
+    editor.log_to_console(
+        PluginLogLevel::Error,
+        message.to_string(),
+        stack.map(str::to_string),
+    );
+}
+
 /// Global flag to panic on JS errors (enabled during testing)
 static PANIC_ON_JS_ERRORS: std::sync::atomic::AtomicBool =
     std::sync::atomic::AtomicBool::new(false);
@@ -424,6 +447,10 @@ fn run_pending_jobs_checked(ctx: &rquickjs::Ctx<'_>, context: &str) -> usize {
                 format!("{:?}", exc)
             };
             tracing::error!("Unhandled JS exception during {}: {}", context, error_msg);
+            report_to_plugin_console(
+                ctx,
+                &format!("Unhandled JS exception during {}: {}", context, error_msg),
+            );
             if should_panic_on_js_errors() {
                 panic!("Unhandled JS exception during {}: {}", context, error_msg);
             }
@@ -504,6 +531,11 @@ pub struct PluginHandler {
     pub handler_name: String,
 }
 
+/// Global name of the debouncing wrapper `on` installs for a handler
+fn debounced_handler_name(event_name: &str, handler_name: &str) -> String {
+    format!("_debounced:{}:{}", event_name, handler_name)
+}
+
 /// JavaScript-exposed Editor API using rquickjs class system
 /// This allows proper lifetime handling for methods returning JS values
 #[derive(rquickjs::class::Trace, rquickjs::JsLifetime)]
@@ -526,6 +558,35 @@ pub struct JsEditorApi {
     pub plugin_name: String,
 }
 
+impl JsEditorApi {
+    /// Send a message to the editor's plugin console
+    fn log_to_console(&self, level: PluginLogLevel, message: String, stack: Option<String>) {
+        let _ = self.command_sender.send(PluginCommand::PluginLog {
+            plugin_name: self.plugin_name.clone(),
+            level,
+            message,
+            stack,
+        });
+    }
+
+    /// Allocate a timer id and ask the editor to schedule it
+    fn start_timer(&self, delay_ms: u64, repeat: bool) -> u64 {
+        let timer_id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::StartTimer {
+            plugin_name: self.plugin_name.clone(),
+            timer_id,
+            delay_ms,
+            repeat,
+        });
+        timer_id
+    }
+}
+
 #[plugin_api_impl]
 #[rquickjs::methods(rename_all = "camelCase")]
 impl JsEditorApi {
@@ -569,18 +630,22 @@ impl JsEditorApi {
 
     pub fn debug(&self, msg: String) {
         tracing::info!("Plugin.debug: {}", msg);
+        self.log_to_console(PluginLogLevel::Debug, msg, None);
     }
 
     pub fn info(&self, msg: String) {
         tracing::info!("Plugin: {}", msg);
+        self.log_to_console(PluginLogLevel::Info, msg, None);
     }
 
     pub fn warn(&self, msg: String) {
         tracing::warn!("Plugin: {}", msg);
+        self.log_to_console(PluginLogLevel::Warn, msg, None);
     }
 
     pub fn error(&self, msg: String) {
         tracing::error!("Plugin: {}", msg);
+        self.log_to_console(PluginLogLevel::Error, msg, None);
     }
 
     // === Status ===
@@ -666,6 +731,61 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Bind keys to a command (a handler name or built-in action name)
+    /// keys use mode binding syntax ("C-k C-g"); context is optional - a key
+    /// context such as "normal" (the default) or "prompt", or a custom context
+    /// set with setContext. Bindings are removed when the plugin unloads
+    pub fn register_keybinding<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        keys: String,
+        command: String,
+        context: rquickjs::function::Opt<rquickjs::Value<'js>>,
+    ) -> bool {
+        let context = context
+            .0
+            .filter(|v| !v.is_null() && !v.is_undefined())
+            .and_then(|v| v.as_string().and_then(|s| s.to_string().ok()));
+
+        // Plugin handlers run in this plugin's context, as with defineMode
+        self.registered_actions.borrow_mut().insert(
+            command.clone(),
+            PluginHandler {
+                plugin_name: self.plugin_name.clone(),
+                handler_name: command.clone(),
+            },
+        );
+
+        self.command_sender
+            .send(PluginCommand::RegisterKeybinding {
+                plugin_name: self.plugin_name.clone(),
+                keys,
+                command,
+                context,
+            })
+            .is_ok()
+    }
+
+    /// Remove a key binding registered with registerKeybinding
+    pub fn unregister_keybinding<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        keys: String,
+        context: rquickjs::function::Opt<rquickjs::Value<'js>>,
+    ) -> bool {
+        let context = context
+            .0
+            .filter(|v| !v.is_null() && !v.is_undefined())
+            .and_then(|v| v.as_string().and_then(|s| s.to_string().ok()));
+        self.command_sender
+            .send(PluginCommand::UnregisterKeybinding {
+                plugin_name: self.plugin_name.clone(),
+                keys,
+                context,
+            })
+            .is_ok()
+    }
+
     /// Set a context (for keybinding conditions)
     pub fn set_context(&self, name: String, active: bool) -> bool {
         self.command_sender
@@ -1054,13 +1174,37 @@ impl JsEditorApi {
     // === Event Handling ===
 
     /// Subscribe to an editor event
-    pub fn on<'js>(&self, _ctx: rquickjs::Ctx<'js>, event_name: String, handler_name: String) {
+    /// With `debounceMs`, the handler runs once the event has been quiet that long
+    /// (per buffer); the `changes` of buffer_changed events in between are merged
+    /// before_file_save handlers may return (or resolve to) a BeforeSaveResult
+    pub fn on<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        event_name: String,
+        handler_name: String,
+        #[plugin_api(ts_type = "{ debounceMs?: number }")] options: rquickjs::function::Opt<
+            rquickjs::Object<'js>,
+        >,
+    ) -> rquickjs::Result<()> {
         // If registering for lines_changed, clear all seen_byte_ranges so lines
         // that were already marked "seen" (before this plugin initialized) get
         // re-sent via the hook.
         if event_name == "lines_changed" {
             let _ = self.command_sender.send(PluginCommand::RefreshAllLines);
         }
+        let debounce_ms = options
+            .0
+            .and_then(|options| options.get::<_, Option<u64>>("debounceMs").ok().flatten())
+            .filter(|ms| *ms > 0);
+        let handler_name = match debounce_ms {
+            Some(debounce_ms) => {
+                let wrapper_name = debounced_handler_name(&event_name, &handler_name);
+                let debounce: Function = ctx.globals().get("_debounceHandler")?;
+                debounce.call::<_, ()>((wrapper_name.clone(), handler_name, debounce_ms))?;
+                wrapper_name
+            }
+            None => handler_name,
+        };
         self.event_handlers
             .borrow_mut()
             .entry(event_name)
@@ -1069,12 +1213,14 @@ impl JsEditorApi {
                 plugin_name: self.plugin_name.clone(),
                 handler_name,
             });
+        Ok(())
     }
 
     /// Unsubscribe from an event
     pub fn off(&self, event_name: String, handler_name: String) {
+        let wrapper_name = debounced_handler_name(&event_name, &handler_name);
         if let Some(list) = self.event_handlers.borrow_mut().get_mut(&event_name) {
-            list.retain(|h| h.handler_name != handler_name);
+            list.retain(|h| h.handler_name != handler_name && h.handler_name != wrapper_name);
         }
     }
 
@@ -1679,6 +1825,38 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    // === Folds ===
+
+    /// Fold a byte range, showing `placeholder` in its place in every view mode
+    pub fn add_fold(
+        &self,
+        buffer_id: u32,
+        namespace: String,
+        start: u32,
+        end: u32,
+        placeholder: String,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::AddFold {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace: OverlayNamespace::from_string(namespace),
+                start: start as usize,
+                end: end as usize,
+                placeholder,
+            })
+            .is_ok()
+    }
+
+    /// Remove all folds in a namespace
+    pub fn clear_fold_namespace(&self, buffer_id: u32, namespace: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::ClearFoldNamespace {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace: OverlayNamespace::from_string(namespace),
+            })
+            .is_ok()
+    }
+
     // === Soft Breaks ===
 
     /// Add a soft break point for marker-based line wrapping
@@ -2004,9 +2182,26 @@ impl JsEditorApi {
     }
 
     /// Start an interactive prompt
-    pub fn start_prompt(&self, label: String, prompt_type: String) -> bool {
+    /// With `multiSelect`, Tab marks suggestions and prompt_confirmed reports
+    /// the marked values in `selected_values`
+    pub fn start_prompt<'js>(
+        &self,
+        label: String,
+        prompt_type: String,
+        #[plugin_api(ts_type = "{ multiSelect?: boolean }")] options: rquickjs::function::Opt<
+            rquickjs::Object<'js>,
+        >,
+    ) -> bool {
+        let multi_select = options
+            .0
+            .and_then(|options| options.get::<_, Option<bool>>("multiSelect").ok().flatten())
+            .unwrap_or(false);
         self.command_sender
-            .send(PluginCommand::StartPrompt { label, prompt_type })
+            .send(PluginCommand::StartPrompt {
+                label,
+                prompt_type,
+                multi_select,
+            })
             .is_ok()
     }
 
@@ -2253,6 +2448,22 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Replace this plugin's gutter signs in a buffer (an empty list clears them)
+    /// Signs follow their line through edits and take theme keys for colors
+    pub fn set_gutter_signs(
+        &self,
+        buffer_id: u32,
+        signs: Vec<fresh_core::api::GutterSign>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetGutterSigns {
+                plugin_name: self.plugin_name.clone(),
+                buffer_id: BufferId(buffer_id as usize),
+                signs,
+            })
+            .is_ok()
+    }
+
     /// Enable or disable line numbers for a buffer
     pub fn set_line_numbers(&self, buffer_id: u32, enabled: bool) -> bool {
         self.command_sender
@@ -2422,6 +2633,47 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Show a read-only floating panel (replaces any visible panel with the same id)
+    ///
+    /// Takes a typed FloatingPanelOptions struct - serde validates field names at runtime
+    pub fn show_floating_panel(&self, opts: fresh_core::api::FloatingPanelOptions) -> bool {
+        self.command_sender
+            .send(PluginCommand::ShowFloatingPanel { options: opts })
+            .is_ok()
+    }
+
+    /// Hide a floating panel by id
+    pub fn hide_floating_panel(&self, id: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::HideFloatingPanel { panel_id: id })
+            .is_ok()
+    }
+
+    /// Add a segment to the status bar, or update it if `id` is already registered
+    pub fn register_status_segment(
+        &self,
+        id: String,
+        options: fresh_core::api::StatusSegmentOptions,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetStatusSegment {
+                plugin_name: self.plugin_name.clone(),
+                segment_id: id,
+                options,
+            })
+            .is_ok()
+    }
+
+    /// Remove a status bar segment added with registerStatusSegment
+    pub fn remove_status_segment(&self, id: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::RemoveStatusSegment {
+                plugin_name: self.plugin_name.clone(),
+                segment_id: id,
+            })
+            .is_ok()
+    }
+
     /// Disable LSP for a specific language
     pub fn disable_lsp_for_language(&self, language: String) -> bool {
         self.command_sender
@@ -2677,6 +2929,17 @@ impl JsEditorApi {
             .is_ok())
     }
 
+    /// Show an image file in place of a buffer's contents (null shows the
+    /// contents again)
+    pub fn set_image_preview(&self, buffer_id: u32, path: Option<String>) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetImagePreview {
+                buffer_id: BufferId(buffer_id as usize),
+                path: path.map(PathBuf::from),
+            })
+            .is_ok()
+    }
+
     /// Get text properties at cursor position (returns JS array)
     pub fn get_text_properties_at_cursor(
         &self,
@@ -2802,6 +3065,206 @@ impl JsEditorApi {
         id
     }
 
+    /// Search the files of the working directory for a pattern, ripgrep style
+    /// (async). Skips hidden, gitignored and binary files. Options: `regex`
+    /// (default true), `caseSensitive` (default: only if the pattern has an
+    /// uppercase letter), `wholeWord`, `maxResults` (default 100).
+    /// Files are relative to the working directory; line and column are 1-indexed.
+    #[plugin_api(
+        async_promise,
+        js_name = "grepProject",
+        ts_return = "Array<{file: string, line: number, column: number, content: string}>"
+    )]
+    #[qjs(rename = "_grepProjectStart")]
+    pub fn grep_project_start<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        pattern: String,
+        #[plugin_api(
+            ts_type = "{ regex?: boolean, caseSensitive?: boolean, wholeWord?: boolean, maxResults?: number }"
+        )]
+        options: rquickjs::function::Opt<rquickjs::Object<'js>>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let options = options.0;
+        let get_bool = |key: &str| {
+            options
+                .as_ref()
+                .and_then(|options| options.get::<_, Option<bool>>(key).ok().flatten())
+        };
+        let max_results = options
+            .as_ref()
+            .and_then(|options| options.get::<_, Option<u32>>("maxResults").ok().flatten())
+            .unwrap_or(100);
+        let _ = self.command_sender.send(PluginCommand::GrepProject {
+            pattern,
+            regex: get_bool("regex").unwrap_or(true),
+            case_sensitive: get_bool("caseSensitive"),
+            whole_word: get_bool("wholeWord").unwrap_or(false),
+            max_results: max_results as usize,
+            callback_id: JsCallbackId::new(id),
+        });
+        id
+    }
+
+    /// Call `callback` once after `delay_ms` milliseconds; returns a timer id for clearTimer
+    /// Timers fire from the editor's main loop, so they run between frames
+    #[plugin_api(js_name = "setTimeout")]
+    #[qjs(rename = "_setTimeoutStart")]
+    pub fn set_timeout_start<'js>(
+        &self,
+        #[plugin_api(ts_type = "() => void")] _callback: rquickjs::Function<'js>,
+        delay_ms: u64,
+    ) -> u64 {
+        self.start_timer(delay_ms, false)
+    }
+
+    /// Call `callback` every `delay_ms` milliseconds until cleared; returns a timer id
+    #[plugin_api(js_name = "setInterval")]
+    #[qjs(rename = "_setIntervalStart")]
+    pub fn set_interval_start<'js>(
+        &self,
+        #[plugin_api(ts_type = "() => void")] _callback: rquickjs::Function<'js>,
+        delay_ms: u64,
+    ) -> u64 {
+        self.start_timer(delay_ms, true)
+    }
+
+    /// Cancel a timer started with setTimeout or setInterval
+    #[plugin_api(js_name = "clearTimer")]
+    #[qjs(rename = "_clearTimer")]
+    pub fn clear_timer(&self, timer_id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::ClearTimer {
+                plugin_name: self.plugin_name.clone(),
+                timer_id,
+            })
+            .is_ok()
+    }
+
+    /// Offer completions from `callback` in the completion popup; returns a provider id
+    /// An empty `languages` list means every language. Items are labelled with
+    /// `options.label` (default: the plugin name) and ranked by `options.priority`
+    /// (language servers rank 0, higher goes first)
+    #[plugin_api(js_name = "registerCompletionProvider")]
+    #[qjs(rename = "_registerCompletionProviderStart")]
+    pub fn register_completion_provider_start<'js>(
+        &self,
+        languages: Vec<String>,
+        trigger_chars: Vec<String>,
+        #[plugin_api(
+            ts_type = "(context: CompletionContext) => CompletionItem[] | Promise<CompletionItem[]>"
+        )]
+        _callback: rquickjs::Function<'js>,
+        #[plugin_api(ts_type = "{ label?: string; priority?: number }")]
+        options: rquickjs::function::Opt<rquickjs::Object<'js>>,
+    ) -> u64 {
+        let provider_id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            id
+        };
+        let options = options.0;
+        let label = options
+            .as_ref()
+            .and_then(|o| o.get::<_, Option<String>>("label").ok().flatten())
+            .unwrap_or_else(|| self.plugin_name.clone());
+        let priority = options
+            .as_ref()
+            .and_then(|o| o.get::<_, Option<i32>>("priority").ok().flatten())
+            .unwrap_or(0);
+        let _ = self
+            .command_sender
+            .send(PluginCommand::RegisterCompletionProvider {
+                plugin_name: self.plugin_name.clone(),
+                provider_id,
+                languages,
+                trigger_chars: trigger_chars
+                    .iter()
+                    .filter_map(|c| c.chars().next())
+                    .collect(),
+                label,
+                priority,
+            });
+        provider_id
+    }
+
+    /// Remove a completion provider registered with registerCompletionProvider
+    #[plugin_api(js_name = "unregisterCompletionProvider")]
+    #[qjs(rename = "_unregisterCompletionProvider")]
+    pub fn unregister_completion_provider(&self, provider_id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::UnregisterCompletionProvider {
+                plugin_name: self.plugin_name.clone(),
+                provider_id,
+            })
+            .is_ok()
+    }
+
+    /// Report a completion provider's items (called by `_provideCompletions`)
+    #[qjs(rename = "_completionsDone")]
+    pub fn completions_done<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        request_id: u64,
+        items: Value<'js>,
+    ) -> bool {
+        let items = if items.is_undefined() || items.is_null() {
+            Vec::new()
+        } else {
+            serde_json::from_value(js_to_json(&ctx, items)).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Plugin {} returned invalid completion items: {}",
+                    self.plugin_name,
+                    e
+                );
+                Vec::new()
+            })
+        };
+        self.command_sender
+            .send(PluginCommand::CompletionItems { request_id, items })
+            .is_ok()
+    }
+
+    /// Report what a `before_file_save` handler returned (called by `_runBeforeSave`)
+    #[qjs(rename = "_beforeSaveDone")]
+    pub fn before_save_done<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        request_id: u64,
+        result: Value<'js>,
+    ) -> bool {
+        let result = if result.is_undefined() || result.is_null() {
+            BeforeSaveResult::default()
+        } else {
+            serde_json::from_value(js_to_json(&ctx, result)).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Plugin {} returned an invalid before_file_save result: {}",
+                    self.plugin_name,
+                    e
+                );
+                BeforeSaveResult::default()
+            })
+        };
+        self.command_sender
+            .send(PluginCommand::BeforeSaveResponse {
+                plugin_name: self.plugin_name.clone(),
+                request_id,
+                result,
+            })
+            .is_ok()
+    }
+
     /// Send LSP request (async, returns request_id)
     #[plugin_api(async_promise, js_name = "sendLspRequest", ts_return = "unknown")]
     #[qjs(rename = "_sendLspRequestStart")]
@@ -3044,6 +3507,33 @@ impl JsEditorApi {
         });
         id
     }
+
+    /// Enable or disable a plugin by name and save it to the user config (async)
+    /// Disabling unloads the plugin, enabling loads it again
+    #[plugin_api(async_promise, js_name = "setPluginEnabled", ts_return = "boolean")]
+    #[qjs(rename = "_setPluginEnabledStart")]
+    pub fn set_plugin_enabled_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        name: String,
+        enabled: bool,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::SetPluginEnabled {
+            name,
+            enabled,
+            callback_id: JsCallbackId::new(id),
+        });
+        id
+    }
 }
 
 // =============================================================================
@@ -3362,21 +3852,34 @@ impl QuickJsBackend {
             // Define getEditor() globally
             ctx.eval::<(), _>("globalThis.getEditor = function() { return editor; };")?;
 
-            // Provide console.log for debugging
+            // Provide console.log for debugging; output also goes to the plugin console
             // Use Rest<T> to handle variadic arguments like console.log('a', 'b', obj)
             let console = Object::new(ctx.clone())?;
-            console.set("log", Function::new(ctx.clone(), |ctx: rquickjs::Ctx, args: rquickjs::function::Rest<rquickjs::Value>| {
-                let parts: Vec<String> = args.0.iter().map(|v| js_value_to_string(&ctx, v)).collect();
-                tracing::info!("console.log: {}", parts.join(" "));
-            })?)?;
-            console.set("warn", Function::new(ctx.clone(), |ctx: rquickjs::Ctx, args: rquickjs::function::Rest<rquickjs::Value>| {
-                let parts: Vec<String> = args.0.iter().map(|v| js_value_to_string(&ctx, v)).collect();
-                tracing::warn!("console.warn: {}", parts.join(" "));
-            })?)?;
-            console.set("error", Function::new(ctx.clone(), |ctx: rquickjs::Ctx, args: rquickjs::function::Rest<rquickjs::Value>| {
-                let parts: Vec<String> = args.0.iter().map(|v| js_value_to_string(&ctx, v)).collect();
-                tracing::error!("console.error: {}", parts.join(" "));
-            })?)?;
+            for (method, level) in [
+                ("log", PluginLogLevel::Info),
+                ("info", PluginLogLevel::Info),
+                ("debug", PluginLogLevel::Debug),
+                ("warn", PluginLogLevel::Warn),
+                ("error", PluginLogLevel::Error),
+            ] {
+                let sender = command_sender.clone();
+                let plugin_name = plugin_name.to_string();
+                console.set(method, Function::new(ctx.clone(), move |ctx: rquickjs::Ctx, args: rquickjs::function::Rest<rquickjs::Value>| {
+                    let parts: Vec<String> = args.0.iter().map(|v| js_value_to_string(&ctx, v)).collect();
+                    let message = parts.join(" ");
+                    match level {
+                        PluginLogLevel::Error => tracing::error!("console.{}: {}", method, message),
+                        PluginLogLevel::Warn => tracing::warn!("console.{}: {}", method, message),
+                        _ => tracing::info!("console.{}: {}", method, message),
+                    }
+                    let _ = sender.send(PluginCommand::PluginLog {
+                        plugin_name: plugin_name.clone(),
+                        level,
+                        message,
+                        stack: None,
+                    });
+                })?)?;
+            }
             globals.set("console", console)?;
 
             // Bootstrap: Promise infrastructure (getEditor is defined per-plugin in execute_js)
@@ -3386,15 +3889,12 @@ impl QuickJsBackend {
 
                 // Resolve a pending callback (called from Rust)
                 globalThis._resolveCallback = function(callbackId, result) {
-                    console.log('[JS] _resolveCallback called with callbackId=' + callbackId + ', pendingCallbacks.size=' + globalThis._pendingCallbacks.size);
                     const cb = globalThis._pendingCallbacks.get(callbackId);
                     if (cb) {
-                        console.log('[JS] _resolveCallback: found callback, calling resolve()');
                         globalThis._pendingCallbacks.delete(callbackId);
                         cb.resolve(result);
-                        console.log('[JS] _resolveCallback: resolve() called');
                     } else {
-                        console.log('[JS] _resolveCallback: NO callback found for id=' + callbackId);
+                        console.debug('_resolveCallback: no callback found for id=' + callbackId);
                     }
                 };
 
@@ -3467,6 +3967,7 @@ impl QuickJsBackend {
                 // Apply wrappers to async functions on editor
                 editor.spawnProcess = _wrapAsyncThenable("_spawnProcessStart", "spawnProcess");
                 editor.delay = _wrapAsync("_delayStart", "delay");
+                editor.grepProject = _wrapAsync("_grepProjectStart", "grepProject");
                 editor.createVirtualBuffer = _wrapAsync("_createVirtualBufferStart", "createVirtualBuffer");
                 editor.createVirtualBufferInSplit = _wrapAsync("_createVirtualBufferInSplitStart", "createVirtualBufferInSplit");
                 editor.createVirtualBufferInExistingSplit = _wrapAsync("_createVirtualBufferInExistingSplitStart", "createVirtualBufferInExistingSplit");
@@ -3480,25 +3981,141 @@ impl QuickJsBackend {
                 editor.unloadPlugin = _wrapAsync("_unloadPluginStart", "unloadPlugin");
                 editor.reloadPlugin = _wrapAsync("_reloadPluginStart", "reloadPlugin");
                 editor.listPlugins = _wrapAsync("_listPluginsStart", "listPlugins");
+                editor.setPluginEnabled = _wrapAsync("_setPluginEnabledStart", "setPluginEnabled");
                 editor.prompt = _wrapAsync("_promptStart", "prompt");
                 editor.getLineStartPosition = _wrapAsync("_getLineStartPositionStart", "getLineStartPosition");
                 editor.getLineEndPosition = _wrapAsync("_getLineEndPositionStart", "getLineEndPosition");
                 editor.createTerminal = _wrapAsync("_createTerminalStart", "createTerminal");
 
-                // Wrapper for deleteTheme - wraps sync function in Promise
-                editor.deleteTheme = function(name) {
-                    return new Promise(function(resolve, reject) {
-                        const success = editor._deleteThemeSync(name);
-                        if (success) {
-                            resolve();
-                        } else {
-                            reject(new Error("Failed to delete theme: " + name));
+                // Timers: callbacks stay here, the editor only tracks deadlines
+                // and calls _fireTimer(timerId) when one is due
+                globalThis._timers = new Map();
+                globalThis._fireTimer = function(timerId) {
+                    const timer = globalThis._timers.get(timerId);
+                    if (!timer) {
+                        return;
+                    }
+                    if (!timer.repeat) {
+                        globalThis._timers.delete(timerId);
+                    }
+                    timer.callback();
+                };
+                const _startTimer = function(methodName, repeat) {
+                    return function(callback, delayMs) {
+                        if (typeof callback !== 'function') {
+                            throw new TypeError('timer callback must be a function');
                         }
-                    });
+                        const timerId = editor[methodName](callback, Math.max(0, Math.floor(delayMs || 0)));
+                        globalThis._timers.set(timerId, { callback, repeat });
+                        return timerId;
+                    };
                 };
-            "#.as_bytes())?;
-
-            Ok::<_, rquickjs::Error>(())
+                editor.setTimeout = _startTimer("_setTimeoutStart", false);
+                editor.setInterval = _startTimer("_setIntervalStart", true);
+                editor.clearTimer = function(timerId) {
+                    globalThis._timers.delete(timerId);
+                    return editor._clearTimer(timerId);
+                };
+                globalThis.setTimeout = editor.setTimeout;
+                globalThis.setInterval = editor.setInterval;
+                globalThis.clearTimeout = editor.clearTimer;
+                globalThis.clearInterval = editor.clearTimer;
+
+                // Completion providers: callbacks stay here, the editor asks
+                // for items with _provideCompletions and waits for every answer
+                globalThis._completionProviders = new Map();
+                editor.registerCompletionProvider = function(languages, triggerChars, callback, options) {
+                    if (typeof callback !== 'function') {
+                        throw new TypeError('completion provider must be a function');
+                    }
+                    const providerId = editor._registerCompletionProviderStart(
+                        languages || [], triggerChars || [], callback, options);
+                    globalThis._completionProviders.set(providerId, callback);
+                    return providerId;
+                };
+                editor.unregisterCompletionProvider = function(providerId) {
+                    globalThis._completionProviders.delete(providerId);
+                    return editor._unregisterCompletionProvider(providerId);
+                };
+                globalThis._provideCompletions = function(providerId, requestId, context) {
+                    const done = function(items) {
+                        editor._completionsDone(requestId, Array.isArray(items) ? items : []);
+                    };
+                    const failed = function(e) {
+                        console.error('Completion provider ' + providerId + ' error:', e);
+                        done([]);
+                    };
+                    try {
+                        const provider = globalThis._completionProviders.get(providerId);
+                        Promise.resolve(provider ? provider(context) : []).then(done, failed);
+                    } catch (e) {
+                        failed(e);
+                    }
+                };
+
+                // before_file_save: the editor holds the save until every
+                // handler has answered, so a throwing handler still answers
+                globalThis._runBeforeSave = function(requestId, handlerName, data) {
+                    const done = function(result) {
+                        editor._beforeSaveDone(requestId, result);
+                    };
+                    const failed = function(e) {
+                        console.error('Handler ' + handlerName + ' error:', e);
+                        done(null);
+                    };
+                    try {
+                        const handler = globalThis[handlerName];
+                        const result = typeof handler === 'function' ? handler(data) : null;
+                        Promise.resolve(result).then(done, failed);
+                    } catch (e) {
+                        failed(e);
+                    }
+                };
+
+                // Debounced event handlers (editor.on with debounceMs): events
+                // are held per buffer until none arrived for debounceMs, then
+                // the handler gets the last one with all `changes` merged
+                globalThis._debounceHandler = function(wrapperName, handlerName, debounceMs) {
+                    const pending = new Map();
+                    globalThis[wrapperName] = function(data) {
+                        const key = data && data.buffer_id !== undefined ? data.buffer_id : null;
+                        const held = pending.get(key);
+                        if (held) {
+                            editor.clearTimer(held.timer);
+                            if (Array.isArray(held.data.changes) && Array.isArray(data.changes)) {
+                                data = Object.assign({}, data, { changes: held.data.changes.concat(data.changes) });
+                            }
+                        }
+                        const timer = editor.setTimeout(function() {
+                            pending.delete(key);
+                            const handler = globalThis[handlerName];
+                            if (typeof handler === 'function') {
+                                const result = handler(data);
+                                if (result && typeof result.then === 'function') {
+                                    result.catch(function(e) {
+                                        console.error('Handler ' + handlerName + ' async error:', e);
+                                    });
+                                }
+                            }
+                        }, debounceMs);
+                        pending.set(key, { data, timer });
+                    };
+                };
+
+                // Wrapper for deleteTheme - wraps sync function in Promise
+                editor.deleteTheme = function(name) {
+                    return new Promise(function(resolve, reject) {
+                        const success = editor._deleteThemeSync(name);
+                        if (success) {
+                            resolve();
+                        } else {
+                            reject(new Error("Failed to delete theme: " + name));
+                        }
+                    });
+                };
+            "#.as_bytes())?;
+
+            Ok::<_, rquickjs::Error>(())
         }).map_err(|e| anyhow!("Failed to set up global API: {}", e))?;
 
         Ok(())
@@ -3724,12 +4341,9 @@ impl QuickJsBackend {
         let code = format!(
             r#"
             (function() {{
-                console.log('[JS] start_action: calling {fn}');
                 try {{
                     if (typeof globalThis.{fn} === 'function') {{
-                        console.log('[JS] start_action: {fn} is a function, invoking...');
                         globalThis.{fn}();
-                        console.log('[JS] start_action: {fn} invoked (may be async)');
                     }} else {{
                         console.error('[JS] Action {action} is not defined as a global function');
                     }}
@@ -3865,6 +4479,26 @@ impl QuickJsBackend {
             .send(PluginCommand::SetStatus { message });
     }
 
+    /// Drop the event handlers and actions a plugin registered.
+    ///
+    /// The plugin's JS context is kept, so state it stored on `globalThis`
+    /// survives a reload while re-running its code registers everything anew.
+    pub fn remove_plugin_handlers(&self, plugin_name: &str) {
+        for handlers in self.event_handlers.borrow_mut().values_mut() {
+            handlers.retain(|h| h.plugin_name != plugin_name);
+        }
+        self.registered_actions
+            .borrow_mut()
+            .retain(|_, h| h.plugin_name != plugin_name);
+    }
+
+    /// Tell the editor a plugin was unloaded so it can drop its key bindings
+    pub fn send_plugin_unloaded(&self, plugin_name: String) {
+        let _ = self
+            .command_sender
+            .send(PluginCommand::PluginUnloaded { plugin_name });
+    }
+
     /// Send a hook-completed sentinel to the editor.
     /// This signals that all commands from the hook have been sent,
     /// allowing the render loop to wait deterministically.
@@ -3959,6 +4593,145 @@ impl QuickJsBackend {
         });
     }
 
+    /// Ask a plugin's completion provider for items
+    ///
+    /// The provider answers with a `CompletionItems` command once its callback
+    /// returns or its promise settles.
+    pub fn provide_completions(
+        &mut self,
+        plugin_name: &str,
+        provider_id: u64,
+        request_id: u64,
+        context: &CompletionContext,
+    ) {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(js_context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!("provide_completions: plugin {} is not loaded", plugin_name);
+            let _ = self.command_sender.send(PluginCommand::CompletionItems {
+                request_id,
+                items: Vec::new(),
+            });
+            return;
+        };
+        let context_json = match serde_json::to_value(context) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::error!("Failed to serialize completion context: {}", e);
+                return;
+            }
+        };
+        js_context.with(|ctx| {
+            let provide: rquickjs::Function = match ctx.globals().get("_provideCompletions") {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::error!(
+                        "provide_completions: _provideCompletions not found: {:?}",
+                        e
+                    );
+                    return;
+                }
+            };
+            let result = json_to_js_value(&ctx, &context_json)
+                .and_then(|context| provide.call::<_, ()>((provider_id, request_id, context)));
+            if let Err(e) = result {
+                log_js_error(
+                    &ctx,
+                    e,
+                    &format!(
+                        "completion provider {} of plugin {}",
+                        provider_id, plugin_name
+                    ),
+                );
+            }
+            run_pending_jobs_checked(&ctx, &format!("provide_completions {}", provider_id));
+        });
+    }
+
+    /// Call the `before_file_save` handlers for a save the editor is holding
+    ///
+    /// Each handler answers with a `BeforeSaveResponse` command once it
+    /// returns or its promise settles. Returns the plugins that were called.
+    pub fn run_before_save(
+        &mut self,
+        request_id: u64,
+        event_data: &serde_json::Value,
+    ) -> Vec<String> {
+        let handlers = self
+            .event_handlers
+            .borrow()
+            .get("before_file_save")
+            .cloned()
+            .unwrap_or_default();
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let mut called = Vec::new();
+        for handler in handlers {
+            let Some(context) = plugin_contexts.get(&handler.plugin_name) else {
+                continue;
+            };
+            let started = context.with(|ctx| {
+                let run: rquickjs::Function = match ctx.globals().get("_runBeforeSave") {
+                    Ok(f) => f,
+                    Err(e) => {
+                        tracing::error!("run_before_save: _runBeforeSave not found: {:?}", e);
+                        return false;
+                    }
+                };
+                let data = match json_to_js_value(&ctx, event_data) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        log_js_error(&ctx, e, "before_file_save data");
+                        return false;
+                    }
+                };
+                let result = run.call::<_, ()>((request_id, handler.handler_name.clone(), data));
+                let started = match result {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log_js_error(&ctx, e, &format!("handler {}", handler.handler_name));
+                        false
+                    }
+                };
+                run_pending_jobs_checked(
+                    &ctx,
+                    &format!("before_file_save {}", handler.handler_name),
+                );
+                started
+            });
+            if started {
+                called.push(handler.plugin_name);
+            }
+        }
+        called
+    }
+
+    /// Run the callback of a due timer (called from Rust by the editor's main loop)
+    pub fn fire_timer(&mut self, plugin_name: &str, timer_id: u64) {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!("fire_timer: plugin {} is not loaded", plugin_name);
+            return;
+        };
+
+        context.with(|ctx| {
+            let globals = ctx.globals();
+            let fire_fn: rquickjs::Function = match globals.get("_fireTimer") {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::error!("fire_timer: _fireTimer not found: {:?}", e);
+                    return;
+                }
+            };
+            if let Err(e) = fire_fn.call::<_, ()>((timer_id,)) {
+                log_js_error(
+                    &ctx,
+                    e,
+                    &format!("timer {} of plugin {}", timer_id, plugin_name),
+                );
+            }
+            run_pending_jobs_checked(&ctx, &format!("fire_timer {}", timer_id));
+        });
+    }
+
     /// Reject a pending async callback with an error (called from Rust when async op fails)
     pub fn reject_callback(&mut self, callback_id: fresh_core::api::JsCallbackId, error: &str) {
         let id = callback_id.as_u64();
@@ -4265,6 +5038,103 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_register_keybinding() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.registerKeybinding("C-k C-g", "grep_project", "normal");
+            editor.unregisterKeybinding("C-k C-g");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::RegisterKeybinding {
+                keys,
+                command,
+                context,
+                ..
+            } => {
+                assert_eq!(keys, "C-k C-g");
+                assert_eq!(command, "grep_project");
+                assert_eq!(context.as_deref(), Some("normal"));
+            }
+            cmd => panic!("Expected RegisterKeybinding, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::UnregisterKeybinding { keys, context, .. } => {
+                assert_eq!(keys, "C-k C-g");
+                assert_eq!(context, None);
+            }
+            cmd => panic!("Expected UnregisterKeybinding, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_timers() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._fired = [];
+            globalThis._once = setTimeout(() => _fired.push("once"), 20);
+            globalThis._every = editor.setInterval(() => _fired.push("every"), 5);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let mut timer_ids = Vec::new();
+        for expected_repeat in [false, true] {
+            match rx.try_recv().unwrap() {
+                PluginCommand::StartTimer {
+                    plugin_name,
+                    timer_id,
+                    delay_ms,
+                    repeat,
+                } => {
+                    assert_eq!(plugin_name, "test");
+                    assert_eq!(repeat, expected_repeat);
+                    assert_eq!(delay_ms, if repeat { 5 } else { 20 });
+                    timer_ids.push(timer_id);
+                }
+                cmd => panic!("Expected StartTimer, got {:?}", cmd),
+            }
+        }
+
+        // One-shot timers fire once, intervals until cleared
+        backend.fire_timer("test", timer_ids[0]);
+        backend.fire_timer("test", timer_ids[0]);
+        backend.fire_timer("test", timer_ids[1]);
+        backend.fire_timer("test", timer_ids[1]);
+        backend
+            .execute_js("editor.clearTimer(_every);", "test.js")
+            .unwrap();
+        backend.fire_timer("test", timer_ids[1]);
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::ClearTimer { timer_id, .. } => assert_eq!(timer_id, timer_ids[1]),
+            cmd => panic!("Expected ClearTimer, got {:?}", cmd),
+        }
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let fired: Vec<String> = ctx.globals().get("_fired").unwrap();
+                assert_eq!(fired, vec!["once", "every", "every"]);
+            });
+    }
+
     #[test]
     fn test_api_set_context() {
         let (mut backend, rx) = create_test_backend();
@@ -4485,6 +5355,183 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_emit_debounced_event_merges_changes() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.onChanged = function(data) {
+                editor.setStatus(JSON.stringify(data.changes.map(c => c.text)));
+            };
+            editor.on("buffer_changed", "onChanged", { debounceMs: 100 });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        for text in ["a", "b"] {
+            let event_data = serde_json::json!({
+                "buffer_id": 1,
+                "changes": [{ "start": 0, "removed_len": 0, "text": text }],
+            });
+            backend.emit("buffer_changed", &event_data).await.unwrap();
+        }
+
+        // The second event restarts the debounce timer
+        let mut timer_ids = Vec::new();
+        while let Ok(cmd) = rx.try_recv() {
+            match cmd {
+                PluginCommand::StartTimer { timer_id, .. } => timer_ids.push(timer_id),
+                PluginCommand::ClearTimer { .. } => {}
+                cmd => panic!("Unexpected command before the timer fired: {:?}", cmd),
+            }
+        }
+        assert_eq!(timer_ids.len(), 2);
+
+        backend.fire_timer("test", timer_ids[1]);
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetStatus { message } => assert_eq!(message, r#"["a","b"]"#),
+            cmd => panic!("Expected SetStatus from event handler, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_completion_provider_answers_requests() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._provider = editor.registerCompletionProvider(["rust"], ["!"], async (context) => {
+                return [{ label: context.prefix + "ln!", insertText: "println!()", kind: "function" }];
+            }, { label: "macros", priority: 5 });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let provider_id = match rx.try_recv().unwrap() {
+            PluginCommand::RegisterCompletionProvider {
+                plugin_name,
+                provider_id,
+                languages,
+                trigger_chars,
+                label,
+                priority,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(languages, vec!["rust"]);
+                assert_eq!(trigger_chars, vec!['!']);
+                assert_eq!(label, "macros");
+                assert_eq!(priority, 5);
+                provider_id
+            }
+            cmd => panic!("Expected RegisterCompletionProvider, got {:?}", cmd),
+        };
+
+        let context = CompletionContext {
+            buffer_id: BufferId(1),
+            language: "rust".to_string(),
+            position: 4,
+            prefix: "prin".to_string(),
+        };
+        backend.provide_completions("test", provider_id, 42, &context);
+        match rx.try_recv().unwrap() {
+            PluginCommand::CompletionItems { request_id, items } => {
+                assert_eq!(request_id, 42);
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].label, "prinln!");
+                assert_eq!(items[0].insert_text.as_deref(), Some("println!()"));
+                assert_eq!(items[0].kind.as_deref(), Some("function"));
+            }
+            cmd => panic!("Expected CompletionItems, got {:?}", cmd),
+        }
+
+        // An unregistered provider still answers, with nothing
+        backend
+            .execute_js("editor.unregisterCompletionProvider(_provider);", "test.js")
+            .unwrap();
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            PluginCommand::UnregisterCompletionProvider { .. }
+        ));
+        backend.provide_completions("test", provider_id, 43, &context);
+        match rx.try_recv().unwrap() {
+            PluginCommand::CompletionItems { request_id, items } => {
+                assert_eq!(request_id, 43);
+                assert!(items.is_empty());
+            }
+            cmd => panic!("Expected CompletionItems, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_run_before_save_collects_answers() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.addHeader = function(data) {
+                return { edits: [{ start: 0, end: 0, text: "// " + data.path + "\n" }] };
+            };
+            globalThis.refuse = async function() {
+                return { veto: "not today" };
+            };
+            globalThis.broken = function() {
+                throw new Error("boom");
+            };
+            editor.on("before_file_save", "addHeader");
+            editor.on("before_file_save", "refuse");
+            editor.on("before_file_save", "broken");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let data = serde_json::json!({ "buffer_id": 1, "path": "a.rs" });
+        let called = backend.run_before_save(7, &data);
+        assert_eq!(called, vec!["test", "test", "test"]);
+
+        let mut results = Vec::new();
+        while let Ok(cmd) = rx.try_recv() {
+            if let PluginCommand::BeforeSaveResponse {
+                plugin_name,
+                request_id,
+                result,
+            } = cmd
+            {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(request_id, 7);
+                results.push(result);
+            }
+        }
+        // A handler that throws still answers, with nothing to do
+        assert_eq!(
+            results,
+            vec![
+                BeforeSaveResult {
+                    edits: Some(vec![fresh_core::api::BeforeSaveEdit {
+                        start: 0,
+                        end: 0,
+                        text: "// a.rs\n".to_string(),
+                    }]),
+                    veto: None,
+                },
+                BeforeSaveResult {
+                    edits: None,
+                    veto: Some("not today".to_string()),
+                },
+                BeforeSaveResult::default(),
+            ]
+        );
+    }
+
     #[test]
     fn test_api_copy_to_clipboard() {
         let (mut backend, rx) = create_test_backend();
@@ -4758,6 +5805,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_grep_project_sends_command() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._grepPromise = editor.grepProject("fn main", { regex: false, maxResults: 5 });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::GrepProject {
+                pattern,
+                regex,
+                case_sensitive,
+                whole_word,
+                max_results,
+                ..
+            } => {
+                assert_eq!(pattern, "fn main");
+                assert!(!regex);
+                assert_eq!(case_sensitive, None);
+                assert!(!whole_word);
+                assert_eq!(max_results, 5);
+            }
+            _ => panic!("Expected GrepProject, got {:?}", cmd),
+        }
+    }
+
     #[test]
     fn test_api_get_buffer_text_resolves_callback() {
         let (mut backend, rx) = create_test_backend();
@@ -4959,6 +6040,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_set_gutter_signs() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setGutterSigns(1, [
+                { line: 3, symbol: "✓", color: "diagnostic.info_fg" },
+                { line: 7, symbol: "✗", color: [255, 0, 0], priority: 20 }
+            ]);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetGutterSigns {
+                plugin_name,
+                buffer_id,
+                signs,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(buffer_id.0, 1);
+                assert_eq!(signs.len(), 2);
+                assert_eq!(signs[0].line, 3);
+                assert_eq!(signs[0].priority, None);
+                assert_eq!(
+                    signs[0].color.as_ref().and_then(|c| c.as_theme_key()),
+                    Some("diagnostic.info_fg")
+                );
+                assert_eq!(
+                    signs[1].color.as_ref().and_then(|c| c.as_rgb()),
+                    Some((255, 0, 0))
+                );
+                assert_eq!(signs[1].priority, Some(20));
+            }
+            _ => panic!("Expected SetGutterSigns, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_status_segments() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.registerStatusSegment("tests", {
+                text: "✓ 12",
+                fg: "ui.status_bar_fg",
+                command: "run_tests"
+            });
+            editor.removeStatusSegment("tests");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetStatusSegment {
+                plugin_name,
+                segment_id,
+                options,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(segment_id, "tests");
+                assert_eq!(options.text, "✓ 12");
+                assert_eq!(options.command.as_deref(), Some("run_tests"));
+                assert_eq!(options.bg, None);
+            }
+            cmd => panic!("Expected SetStatusSegment, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::RemoveStatusSegment {
+                plugin_name,
+                segment_id,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(segment_id, "tests");
+            }
+            cmd => panic!("Expected RemoveStatusSegment, got {:?}", cmd),
+        }
+    }
+
     // ==================== Virtual Buffer Tests ====================
 
     #[test]
@@ -5378,14 +6547,40 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::StartPrompt { label, prompt_type } => {
+            PluginCommand::StartPrompt {
+                label,
+                prompt_type,
+                multi_select,
+            } => {
                 assert_eq!(label, "Enter value:");
                 assert_eq!(prompt_type, "test-prompt");
+                assert!(!multi_select);
             }
             _ => panic!("Expected StartPrompt, got {:?}", cmd),
         }
     }
 
+    #[test]
+    fn test_api_start_prompt_multi_select() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.startPrompt("Stage hunks:", "stage-hunks", { multiSelect: true });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::StartPrompt { multi_select, .. } => assert!(multi_select),
+            _ => panic!("Expected StartPrompt, got {:?}", cmd),
+        }
+    }
+
     #[test]
     fn test_api_start_prompt_with_initial() {
         let (mut backend, rx) = create_test_backend();
@@ -5728,7 +6923,7 @@ mod tests {
 
     #[test]
     fn test_api_debug() {
-        let (mut backend, _rx) = create_test_backend();
+        let (mut backend, rx) = create_test_backend();
 
         // debug() should not panic and should work with any input
         backend
@@ -5741,7 +6936,37 @@ mod tests {
                 "test.js",
             )
             .unwrap();
-        // If we get here without panic, the test passes
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::PluginLog { level, message, .. } => {
+                assert_eq!(level, PluginLogLevel::Debug);
+                assert_eq!(message, "Test debug message");
+            }
+            cmd => panic!("Expected PluginLog, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_console_output_goes_to_plugin_console() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(r#"console.warn("careful", 42);"#, "test.js")
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::PluginLog {
+                level,
+                message,
+                stack,
+                ..
+            } => {
+                assert_eq!(level, PluginLogLevel::Warn);
+                assert_eq!(message, "careful 42");
+                assert!(stack.is_none());
+            }
+            cmd => panic!("Expected PluginLog, got {:?}", cmd),
+        }
     }
 
     // ==================== TypeScript Definitions Test ====================
@@ -12,7 +12,7 @@
 use crate::backend::quickjs_backend::{PendingResponses, TsPluginInfo};
 use crate::backend::QuickJsBackend;
 use anyhow::{anyhow, Result};
-use fresh_core::api::{EditorStateSnapshot, PluginCommand};
+use fresh_core::api::{CompletionContext, EditorStateSnapshot, PluginCommand};
 use fresh_core::hooks::HookArgs;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -25,6 +25,9 @@ use std::time::Duration;
 // Re-export PluginConfig from fresh-core
 pub use fresh_core::config::PluginConfig;
 
+/// Errors from loading a plugin directory, and the plugins found in it
+pub type DirLoadResult = (Vec<String>, HashMap<String, PluginConfig>);
+
 /// Request messages sent to the plugin thread
 #[derive(Debug)]
 pub enum PluginRequest {
@@ -46,6 +49,17 @@ pub enum PluginRequest {
         error: String,
     },
 
+    /// Run the callback of a due plugin timer
+    FireTimer { plugin_name: String, timer_id: u64 },
+
+    /// Ask a plugin completion provider for items
+    ProvideCompletions {
+        plugin_name: String,
+        provider_id: u64,
+        request_id: u64,
+        context: CompletionContext,
+    },
+
     /// Load all plugins from a directory
     LoadPluginsFromDir {
         dir: PathBuf,
@@ -82,6 +96,14 @@ pub enum PluginRequest {
     /// Run a hook (fire-and-forget, no response needed)
     RunHook { hook_name: String, args: HookArgs },
 
+    /// Call the before_file_save handlers for a save the editor is holding
+    /// Responds with the plugins whose handlers were called
+    RunBeforeSave {
+        request_id: u64,
+        args: HookArgs,
+        response: oneshot::Sender<Vec<String>>,
+    },
+
     /// Check if any handlers are registered for a hook
     HasHookHandlers {
         hook_name: String,
@@ -432,26 +454,36 @@ impl PluginThreadHandle {
         dir: &Path,
         plugin_configs: &HashMap<String, PluginConfig>,
     ) -> (Vec<String>, HashMap<String, PluginConfig>) {
+        match self.start_loading_plugins_from_dir_with_config(dir, plugin_configs) {
+            Ok(rx) => rx
+                .recv()
+                .unwrap_or_else(|_| (vec!["Plugin thread closed".to_string()], HashMap::new())),
+            Err(e) => (vec![e], HashMap::new()),
+        }
+    }
+
+    /// Start loading all plugins from a directory with config support,
+    /// without waiting for them to load
+    ///
+    /// The result is sent to the returned receiver. Requests sent after this
+    /// one (hooks, actions) are handled once the plugins are loaded.
+    pub fn start_loading_plugins_from_dir_with_config(
+        &self,
+        dir: &Path,
+        plugin_configs: &HashMap<String, PluginConfig>,
+    ) -> std::result::Result<oneshot::Receiver<DirLoadResult>, String> {
         let (tx, rx) = oneshot::channel();
         let Some(sender) = self.request_sender.as_ref() else {
-            return (vec!["Plugin thread shut down".to_string()], HashMap::new());
+            return Err("Plugin thread shut down".to_string());
         };
-        if sender
+        sender
             .send(PluginRequest::LoadPluginsFromDirWithConfig {
                 dir: dir.to_path_buf(),
                 plugin_configs: plugin_configs.clone(),
                 response: tx,
             })
-            .is_err()
-        {
-            return (
-                vec!["Plugin thread not responding".to_string()],
-                HashMap::new(),
-            );
-        }
-
-        rx.recv()
-            .unwrap_or_else(|_| (vec!["Plugin thread closed".to_string()], HashMap::new()))
+            .map_err(|_| "Plugin thread not responding".to_string())?;
+        Ok(rx)
     }
 
     /// Unload a plugin (blocking)
@@ -537,6 +569,29 @@ impl PluginThreadHandle {
         rx.recv().unwrap_or(false)
     }
 
+    /// Start the before_file_save handlers for a held save (blocking)
+    ///
+    /// Returns the plugins whose handlers were called; each of them answers
+    /// with a `BeforeSaveResponse` command carrying `request_id`.
+    pub fn run_before_save(&self, request_id: u64, args: HookArgs) -> Vec<String> {
+        let (tx, rx) = oneshot::channel();
+        let Some(sender) = self.request_sender.as_ref() else {
+            return vec![];
+        };
+        if sender
+            .send(PluginRequest::RunBeforeSave {
+                request_id,
+                args,
+                response: tx,
+            })
+            .is_err()
+        {
+            return vec![];
+        }
+
+        rx.recv().unwrap_or_default()
+    }
+
     /// List all loaded plugins (blocking)
     pub fn list_plugins(&self) -> Vec<TsPluginInfo> {
         let (tx, rx) = oneshot::channel();
@@ -553,6 +608,35 @@ impl PluginThreadHandle {
         rx.recv().unwrap_or_default()
     }
 
+    /// Call `wake` whenever a plugin sends a command, so the editor's loop can
+    /// come back to process it instead of polling for it
+    pub fn wake_on_command(&mut self, wake: impl Fn() + Send + 'static) {
+        let (forward, forwarded) = std::sync::mpsc::channel();
+        // The thread gets the incoming channel only once it is running, so
+        // nothing is lost if it can't be started
+        let (hand_over, incoming) =
+            std::sync::mpsc::channel::<std::sync::mpsc::Receiver<PluginCommand>>();
+        let spawned = std::thread::Builder::new()
+            .name("plugin-command-wake".to_string())
+            .spawn(move || {
+                let Ok(incoming) = incoming.recv() else {
+                    return;
+                };
+                while let Ok(command) = incoming.recv() {
+                    if forward.send(command).is_err() {
+                        break;
+                    }
+                    wake();
+                }
+            });
+        match spawned {
+            Ok(_) => {
+                let _ = hand_over.send(std::mem::replace(&mut self.command_receiver, forwarded));
+            }
+            Err(e) => tracing::error!("Failed to start plugin command wake thread: {}", e),
+        }
+    }
+
     /// Process pending plugin commands (non-blocking)
     ///
     /// Returns immediately with any pending commands by polling the command queue directly.
@@ -680,6 +764,37 @@ impl PluginThreadHandle {
         }
     }
 
+    /// Fire a due plugin timer
+    /// Called by the app's main loop
+    pub fn fire_timer(&self, plugin_name: String, timer_id: u64) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::FireTimer {
+                plugin_name,
+                timer_id,
+            });
+        }
+    }
+
+    /// Ask a plugin completion provider for items (non-blocking)
+    ///
+    /// The provider answers with a `CompletionItems` command for `request_id`.
+    pub fn provide_completions(
+        &self,
+        plugin_name: String,
+        provider_id: u64,
+        request_id: u64,
+        context: CompletionContext,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::ProvideCompletions {
+                plugin_name,
+                provider_id,
+                request_id,
+                context,
+            });
+        }
+    }
+
     /// Reject an async callback in the plugin runtime
     /// Called by the app when async operations fail
     pub fn reject_callback(&self, callback_id: fresh_core::api::JsCallbackId, error: String) {
@@ -1001,6 +1116,23 @@ async fn handle_request(
             let _ = response.send(has_handlers);
         }
 
+        PluginRequest::RunBeforeSave {
+            request_id,
+            args,
+            response,
+        } => {
+            let data = fresh_core::hooks::hook_args_to_json(&args)
+                .and_then(|json| Ok(serde_json::from_str::<serde_json::Value>(&json)?));
+            let called = match data {
+                Ok(data) => runtime.borrow_mut().run_before_save(request_id, &data),
+                Err(e) => {
+                    tracing::error!("Failed to serialize before_file_save args: {}", e);
+                    vec![]
+                }
+            };
+            let _ = response.send(called);
+        }
+
         PluginRequest::ListPlugins { response } => {
             let plugin_list: Vec<TsPluginInfo> = plugins.values().cloned().collect();
             let _ = response.send(plugin_list);
@@ -1030,6 +1162,27 @@ async fn handle_request(
             // reject_callback now runs execute_pending_job() internally
         }
 
+        PluginRequest::FireTimer {
+            plugin_name,
+            timer_id,
+        } => {
+            runtime.borrow_mut().fire_timer(&plugin_name, timer_id);
+        }
+
+        PluginRequest::ProvideCompletions {
+            plugin_name,
+            provider_id,
+            request_id,
+            context,
+        } => {
+            runtime.borrow_mut().provide_completions(
+                &plugin_name,
+                provider_id,
+                request_id,
+                &context,
+            );
+        }
+
         PluginRequest::Shutdown => {
             tracing::info!("Plugin thread received shutdown request");
             return true;
@@ -1288,6 +1441,12 @@ fn unload_plugin_internal(
             .services
             .unregister_commands_by_plugin(name);
 
+        // Drop its event handlers and actions so a reload doesn't run them twice
+        runtime.borrow().remove_plugin_handlers(name);
+
+        // Let the editor drop the plugin's key bindings
+        runtime.borrow().send_plugin_unloaded(name.to_string());
+
         Ok(())
     } else {
         Err(anyhow!("Plugin '{}' not found", name))
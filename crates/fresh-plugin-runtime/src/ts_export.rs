@@ -4,171 +4,170 @@
 //! TypeScript declarations that are combined with the proc macro output.
 //! The generated TypeScript is validated and formatted using oxc.
 //!
-//! Types are automatically collected based on `JSEDITORAPI_REFERENCED_TYPES`
-//! from the proc macro, so when you add a new type to method signatures,
-//! it will automatically be included if it has `#[derive(TS)]`.
+//! Only the types plugins use directly are listed (in `api_types!`). Types
+//! they reference, such as option structs nested in other options, are found
+//! through ts-rs's dependency metadata, so they can't be forgotten. Types
+//! named in `JSEDITORAPI_REFERENCED_TYPES` by the proc macro but missing from
+//! the output make `write_fresh_dts` fail.
+
+use std::collections::HashSet;
 
 use oxc_allocator::Allocator;
 use oxc_codegen::Codegen;
 use oxc_parser::Parser;
 use oxc_span::SourceType;
-use ts_rs::TS;
+use ts_rs::{TypeVisitor, TS};
 
 use fresh_core::api::{
-    ActionPopupAction, ActionPopupOptions, ActionSpec, BackgroundProcessResult, BufferInfo,
-    BufferSavedDiff, CompositeHunk, CompositeLayoutConfig, CompositePaneStyle,
-    CompositeSourceConfig, CreateCompositeBufferOptions, CreateTerminalOptions,
-    CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
-    CreateVirtualBufferOptions, CursorInfo, DirEntry, FormatterPackConfig, JsDiagnostic,
-    JsPosition, JsRange, JsTextPropertyEntry, LanguagePackConfig, LayoutHints, LspServerPackConfig,
-    SpawnResult, TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle,
-    ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
+    ActionPopupOptions, ActionSpec, BackgroundProcessResult, BeforeSaveResult, BufferInfo,
+    BufferSavedDiff, CompletionContext, CompositeHunk, CreateCompositeBufferOptions,
+    CreateTerminalOptions, CreateVirtualBufferInExistingSplitOptions,
+    CreateVirtualBufferInSplitOptions, CreateVirtualBufferOptions, CursorInfo, DirEntry,
+    FloatingPanelOptions, GutterSign, JsDiagnostic, JsTextPropertyEntry, LanguagePackConfig,
+    LayoutHints, LspServerPackConfig, PluginCompletionItem, SpawnResult, StatusSegmentOptions,
+    TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenWire, ViewportInfo,
+    VirtualBufferResult,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
+use fresh_core::package::PackageManifest;
 
-/// Get the TypeScript declaration for a type by name
-///
-/// Returns None if the type is not known (not registered in this mapping).
-/// Add new types here when they're added to api.rs with `#[derive(TS)]`.
-fn get_type_decl(type_name: &str) -> Option<String> {
-    // Map TypeScript type names to their ts-rs declarations
-    // The type name should match either the Rust struct name or the ts(rename = "...") value
-    match type_name {
-        // Core types
-        "BufferInfo" => Some(BufferInfo::decl()),
-        "CursorInfo" => Some(CursorInfo::decl()),
-        "ViewportInfo" => Some(ViewportInfo::decl()),
-        "ActionSpec" => Some(ActionSpec::decl()),
-        "BufferSavedDiff" => Some(BufferSavedDiff::decl()),
-        "LayoutHints" => Some(LayoutHints::decl()),
-
-        // Process types
-        "SpawnResult" => Some(SpawnResult::decl()),
-        "BackgroundProcessResult" => Some(BackgroundProcessResult::decl()),
-
-        // Terminal types
-        "TerminalResult" => Some(TerminalResult::decl()),
-        "CreateTerminalOptions" => Some(CreateTerminalOptions::decl()),
-
-        // Composite buffer types (ts-rs renames these with Ts prefix)
-        "TsCompositeLayoutConfig" | "CompositeLayoutConfig" => Some(CompositeLayoutConfig::decl()),
-        "TsCompositeSourceConfig" | "CompositeSourceConfig" => Some(CompositeSourceConfig::decl()),
-        "TsCompositePaneStyle" | "CompositePaneStyle" => Some(CompositePaneStyle::decl()),
-        "TsCompositeHunk" | "CompositeHunk" => Some(CompositeHunk::decl()),
-        "TsCreateCompositeBufferOptions" | "CreateCompositeBufferOptions" => {
-            Some(CreateCompositeBufferOptions::decl())
-        }
-
-        // View transform types
-        "ViewTokenWireKind" => Some(ViewTokenWireKind::decl()),
-        "ViewTokenStyle" => Some(ViewTokenStyle::decl()),
-        "ViewTokenWire" => Some(ViewTokenWire::decl()),
-
-        // UI types (ts-rs renames these with Ts prefix)
-        "TsActionPopupAction" | "ActionPopupAction" => Some(ActionPopupAction::decl()),
-        "ActionPopupOptions" => Some(ActionPopupOptions::decl()),
-        "TsHighlightSpan" => Some(TsHighlightSpan::decl()),
-        "FileExplorerDecoration" => Some(FileExplorerDecoration::decl()),
-
-        // Virtual buffer option types
-        "TextPropertyEntry" | "JsTextPropertyEntry" => Some(JsTextPropertyEntry::decl()),
-        "CreateVirtualBufferOptions" => Some(CreateVirtualBufferOptions::decl()),
-        "CreateVirtualBufferInSplitOptions" => Some(CreateVirtualBufferInSplitOptions::decl()),
-        "CreateVirtualBufferInExistingSplitOptions" => {
-            Some(CreateVirtualBufferInExistingSplitOptions::decl())
+/// Define `visit_api_types`, which visits every listed type
+macro_rules! api_types {
+    ($($ty:ty),* $(,)?) => {
+        fn visit_api_types(v: &mut impl TypeVisitor) {
+            $(v.visit::<$ty>();)*
         }
+    };
+}
 
-        // Return types
-        "TextPropertiesAtCursor" => Some(TextPropertiesAtCursor::decl()),
-        "VirtualBufferResult" => Some(VirtualBufferResult::decl()),
-
-        // Prompt and directory types
-        "PromptSuggestion" | "Suggestion" => Some(Suggestion::decl()),
-        "DirEntry" => Some(DirEntry::decl()),
-
-        // Diagnostic types
-        "JsDiagnostic" => Some(JsDiagnostic::decl()),
-        "JsRange" => Some(JsRange::decl()),
-        "JsPosition" => Some(JsPosition::decl()),
+// Types named in `EditorAPI` signatures, plus the arguments and results of
+// plugin callbacks. Their dependencies are included automatically.
+api_types![
+    // Core types
+    BufferInfo,
+    CursorInfo,
+    ViewportInfo,
+    ActionSpec,
+    BufferSavedDiff,
+    LayoutHints,
+    // Process types
+    SpawnResult,
+    BackgroundProcessResult,
+    // Event handler and callback types
+    BeforeSaveResult,
+    CompletionContext,
+    PluginCompletionItem,
+    // Terminal types
+    TerminalResult,
+    CreateTerminalOptions,
+    // Composite buffer types
+    CompositeHunk,
+    CreateCompositeBufferOptions,
+    // View transform types
+    ViewTokenWire,
+    // UI types
+    ActionPopupOptions,
+    FloatingPanelOptions,
+    StatusSegmentOptions,
+    GutterSign,
+    TsHighlightSpan,
+    FileExplorerDecoration,
+    // Virtual buffer types
+    JsTextPropertyEntry,
+    CreateVirtualBufferOptions,
+    CreateVirtualBufferInSplitOptions,
+    CreateVirtualBufferInExistingSplitOptions,
+    TextPropertiesAtCursor,
+    VirtualBufferResult,
+    // Prompt and directory types
+    Suggestion,
+    DirEntry,
+    // Diagnostic types
+    JsDiagnostic,
+    // Language pack types
+    LanguagePackConfig,
+    LspServerPackConfig,
+    // Package manifest (package.json) types
+    PackageManifest,
+];
 
-        // Language pack types
-        "LanguagePackConfig" => Some(LanguagePackConfig::decl()),
-        "LspServerPackConfig" => Some(LspServerPackConfig::decl()),
-        "FormatterPackConfig" => Some(FormatterPackConfig::decl()),
+/// Collects the declarations of visited types and, transitively, of the
+/// types they reference
+struct DeclCollector {
+    /// TypeScript names already declared, including those from the preamble
+    declared: HashSet<String>,
+    /// `(name, declaration)` pairs, dependencies before their dependents
+    decls: Vec<(String, String)>,
+}
 
-        _ => None,
+impl TypeVisitor for DeclCollector {
+    fn visit<T: TS + 'static + ?Sized>(&mut self) {
+        // Only types deriving TS have a declaration (and an output path);
+        // primitives and containers don't, but visit their parameters
+        if T::output_path().is_none() || !self.declared.insert(T::ident()) {
+            return;
+        }
+        T::visit_dependencies(self);
+        self.decls.push((T::ident(), T::decl()));
     }
 }
 
-/// Types that are dependencies of other types and must always be included.
-/// These are types referenced inside option structs or other complex types
-/// that aren't directly in method signatures.
-const DEPENDENCY_TYPES: &[&str] = &[
-    "TextPropertyEntry",              // Used in CreateVirtualBuffer*Options.entries
-    "TsCompositeLayoutConfig",        // Used in createCompositeBuffer opts
-    "TsCompositeSourceConfig",        // Used in createCompositeBuffer opts.sources
-    "TsCompositePaneStyle",           // Used in TsCompositeSourceConfig.style
-    "TsCompositeHunk",                // Used in createCompositeBuffer opts.hunks
-    "TsCreateCompositeBufferOptions", // Options for createCompositeBuffer
-    "ViewportInfo",                   // Used by plugins for viewport queries
-    "LayoutHints",                    // Used by plugins for view transforms
-    "ViewTokenWire",                  // Used by plugins for view transforms
-    "ViewTokenWireKind",              // Used by ViewTokenWire
-    "ViewTokenStyle",                 // Used by ViewTokenWire
-    "PromptSuggestion",               // Used by plugins for prompt suggestions
-    "DirEntry",                       // Used by plugins for directory entries
-    "BufferInfo",                     // Used by listBuffers, getBufferInfo
-    "JsDiagnostic",                   // Used by getAllDiagnostics
-    "JsRange",                        // Used by JsDiagnostic
-    "JsPosition",                     // Used by JsRange
-    "ActionSpec",                     // Used by executeActions
-    "TsActionPopupAction",            // Used by ActionPopupOptions.actions
-    "ActionPopupOptions",             // Used by showActionPopup
-    "FileExplorerDecoration",         // Used by setFileExplorerDecorations
-    "FormatterPackConfig",            // Used by LanguagePackConfig.formatter
-    "TerminalResult",                 // Used by createTerminal return type
-    "CreateTerminalOptions",          // Used by createTerminal opts parameter
-    "CursorInfo",                     // Used by getPrimaryCursor, getAllCursors
-];
+/// Type names the preamble declares by hand (`BufferId`, `ProcessHandle`, ...)
+fn preamble_type_names() -> HashSet<String> {
+    use crate::backend::quickjs_backend::JSEDITORAPI_TS_PREAMBLE;
+
+    JSEDITORAPI_TS_PREAMBLE
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("type ")
+                .or_else(|| line.strip_prefix("interface "))
+        })
+        .filter_map(|rest| {
+            rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .filter(|name| !name.is_empty())
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Declarations of the API types and every type they reference, by name
+fn collect_api_types() -> Vec<(String, String)> {
+    let mut collector = DeclCollector {
+        declared: preamble_type_names(),
+        decls: Vec::new(),
+    };
+    visit_api_types(&mut collector);
+    collector.decls
+}
 
-/// Collect TypeScript type declarations based on referenced types from proc macro
+/// Types referenced by `EditorAPI` signatures that have no declaration
 ///
-/// Uses `JSEDITORAPI_REFERENCED_TYPES` to determine which types to include.
-/// Also includes dependency types that are referenced by other types.
-pub fn collect_ts_types() -> String {
+/// A type showing up here has to be added to `api_types!`.
+pub fn undeclared_api_types() -> Vec<&'static str> {
     use crate::backend::quickjs_backend::JSEDITORAPI_REFERENCED_TYPES;
 
-    let mut types = Vec::new();
-    // Track by declaration content to prevent duplicates from aliases
-    // (e.g., "CompositeHunk" and "TsCompositeHunk" both resolve to the same decl)
-    let mut included_decls = std::collections::HashSet::new();
-
-    // First, include dependency types (order matters - dependencies first)
-    for type_name in DEPENDENCY_TYPES {
-        if let Some(decl) = get_type_decl(type_name) {
-            if included_decls.insert(decl.clone()) {
-                types.push(decl);
-            }
-        }
-    }
-
-    // Collect types referenced by the API
-    for type_name in JSEDITORAPI_REFERENCED_TYPES {
-        if let Some(decl) = get_type_decl(type_name) {
-            if included_decls.insert(decl.clone()) {
-                types.push(decl);
-            }
-        } else {
-            // Log warning for unknown types (these need to be added to get_type_decl)
-            eprintln!(
-                "Warning: Type '{}' is referenced in API but not registered in get_type_decl()",
-                type_name
-            );
-        }
-    }
+    let mut declared = preamble_type_names();
+    declared.extend(collect_api_types().into_iter().map(|(name, _)| name));
+    JSEDITORAPI_REFERENCED_TYPES
+        .iter()
+        .copied()
+        .filter(|name| !declared.contains(*name))
+        .collect()
+}
 
-    types.join("\n\n")
+/// Collect TypeScript type declarations for the plugin API
+///
+/// Includes every type listed in `api_types!` and, transitively, the types
+/// they reference.
+pub fn collect_ts_types() -> String {
+    collect_api_types()
+        .into_iter()
+        .map(|(_, decl)| decl)
+        .collect::<Vec<_>>()
+        .join("\n\n")
 }
 
 /// Validate TypeScript syntax using oxc parser
@@ -218,6 +217,14 @@ pub fn format_typescript(source: &str) -> String {
 pub fn write_fresh_dts() -> Result<(), String> {
     use crate::backend::quickjs_backend::{JSEDITORAPI_TS_EDITOR_API, JSEDITORAPI_TS_PREAMBLE};
 
+    let undeclared = undeclared_api_types();
+    if !undeclared.is_empty() {
+        return Err(format!(
+            "Types referenced by EditorAPI have no declaration (add them to api_types!): {}",
+            undeclared.join(", ")
+        ));
+    }
+
     let ts_types = collect_ts_types();
 
     let content = format!(
@@ -329,8 +336,16 @@ mod tests {
     // Type declaration tests
     // ========================================================================
 
+    /// Declaration of an API type by its TypeScript name
+    fn api_type_decl(name: &str) -> Option<String> {
+        collect_api_types()
+            .into_iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, decl)| decl)
+    }
+
     #[test]
-    fn test_get_type_decl_returns_all_expected_types() {
+    fn test_collect_api_types_returns_all_expected_types() {
         let expected_types = vec![
             "BufferInfo",
             "CursorInfo",
@@ -340,6 +355,10 @@ mod tests {
             "LayoutHints",
             "SpawnResult",
             "BackgroundProcessResult",
+            "BeforeSaveResult",
+            "BeforeSaveEdit",
+            "CompletionContext",
+            "CompletionItem",
             "TerminalResult",
             "CreateTerminalOptions",
             "TsCompositeLayoutConfig",
@@ -352,6 +371,10 @@ mod tests {
             "ViewTokenWire",
             "TsActionPopupAction",
             "ActionPopupOptions",
+            "FloatingPanelOptions",
+            "OverlayColorSpec",
+            "StatusSegmentOptions",
+            "GutterSign",
             "TsHighlightSpan",
             "FileExplorerDecoration",
             "TextPropertyEntry",
@@ -368,53 +391,60 @@ mod tests {
             "LanguagePackConfig",
             "LspServerPackConfig",
             "FormatterPackConfig",
+            "PackageManifest",
+            "PackageKind",
+            "FreshPackageConfig",
+            "PackageTheme",
+            "ThemeVariant",
+            "GrammarPackConfig",
+            "BundleLanguage",
+            "BundlePlugin",
         ];
 
         for type_name in &expected_types {
             assert!(
-                get_type_decl(type_name).is_some(),
-                "get_type_decl should return a declaration for '{}'",
+                api_type_decl(type_name).is_some(),
+                "collect_api_types should return a declaration for '{}'",
                 type_name
             );
         }
     }
 
     #[test]
-    fn test_get_type_decl_aliases_resolve_same() {
-        // Rust name aliases should produce the same declaration as ts-rs name
-        let alias_pairs = vec![
-            ("CompositeHunk", "TsCompositeHunk"),
-            ("CompositeLayoutConfig", "TsCompositeLayoutConfig"),
-            ("CompositeSourceConfig", "TsCompositeSourceConfig"),
-            ("CompositePaneStyle", "TsCompositePaneStyle"),
-            (
-                "CreateCompositeBufferOptions",
-                "TsCreateCompositeBufferOptions",
-            ),
-            ("ActionPopupAction", "TsActionPopupAction"),
-            ("Suggestion", "PromptSuggestion"),
-            ("JsTextPropertyEntry", "TextPropertyEntry"),
-        ];
+    fn test_dependencies_come_before_dependents() {
+        let names: Vec<String> = collect_api_types().into_iter().map(|(n, _)| n).collect();
+        let index = |name: &str| names.iter().position(|n| n == name).unwrap();
+
+        // Reached only through other types, never listed in api_types!
+        assert!(index("JsPosition") < index("JsRange"));
+        assert!(index("JsRange") < index("JsDiagnostic"));
+        assert!(index("BeforeSaveEdit") < index("BeforeSaveResult"));
+        assert!(index("TsCompositePaneStyle") < index("TsCompositeSourceConfig"));
+        assert!(index("FormatterPackConfig") < index("LanguagePackConfig"));
+    }
 
-        for (rust_name, ts_name) in &alias_pairs {
-            let rust_decl = get_type_decl(rust_name);
-            let ts_decl = get_type_decl(ts_name);
+    #[test]
+    fn test_preamble_types_are_not_redeclared() {
+        let preamble = preamble_type_names();
+        assert!(preamble.contains("BufferId"));
+        assert!(preamble.contains("ProcessHandle"));
+        for (name, _) in collect_api_types() {
             assert!(
-                rust_decl.is_some(),
-                "get_type_decl should handle Rust name '{}'",
-                rust_name
-            );
-            assert_eq!(
-                rust_decl, ts_decl,
-                "Alias '{}' and '{}' should produce identical declarations",
-                rust_name, ts_name
+                !preamble.contains(&name),
+                "'{}' is declared by the preamble",
+                name
             );
         }
     }
 
+    #[test]
+    fn test_all_referenced_types_are_declared() {
+        assert_eq!(undeclared_api_types(), Vec::<&str>::new());
+    }
+
     #[test]
     fn test_terminal_types_exist() {
-        let terminal_result = get_type_decl("TerminalResult");
+        let terminal_result = api_type_decl("TerminalResult");
         assert!(
             terminal_result.is_some(),
             "TerminalResult should be defined"
@@ -433,7 +463,7 @@ mod tests {
             "TerminalResult should have splitId field"
         );
 
-        let terminal_opts = get_type_decl("CreateTerminalOptions");
+        let terminal_opts = api_type_decl("CreateTerminalOptions");
         assert!(
             terminal_opts.is_some(),
             "CreateTerminalOptions should be defined"
@@ -442,7 +472,7 @@ mod tests {
 
     #[test]
     fn test_cursor_info_type_exists() {
-        let cursor_info = get_type_decl("CursorInfo");
+        let cursor_info = api_type_decl("CursorInfo");
         assert!(cursor_info.is_some(), "CursorInfo should be defined");
         let decl = cursor_info.unwrap();
         assert!(
@@ -726,6 +756,8 @@ mod tests {
             "setClipboard",
             "registerCommand",
             "unregisterCommand",
+            "registerKeybinding",
+            "unregisterKeybinding",
             "setContext",
             "executeAction",
             "getCursorPosition",
@@ -796,6 +828,8 @@ mod tests {
             "addConceal",
             "clearConcealNamespace",
             "clearConcealsInRange",
+            "addFold",
+            "clearFoldNamespace",
             "addSoftBreak",
             "clearSoftBreakNamespace",
             "clearSoftBreaksInRange",
@@ -830,6 +864,7 @@ mod tests {
             "setBufferCursor",
             "setLineIndicator",
             "clearLineIndicators",
+            "setGutterSigns",
             "setLineNumbers",
             "setViewMode",
             "setViewState",
@@ -840,6 +875,10 @@ mod tests {
             "removeScrollSyncGroup",
             "executeActions",
             "showActionPopup",
+            "showFloatingPanel",
+            "hideFloatingPanel",
+            "registerStatusSegment",
+            "removeStatusSegment",
             "disableLspForLanguage",
             "setLspRootUri",
             "getAllDiagnostics",
@@ -848,11 +887,17 @@ mod tests {
             "createVirtualBufferInSplit",
             "createVirtualBufferInExistingSplit",
             "setVirtualBufferContent",
+            "setImagePreview",
             "getTextPropertiesAtCursor",
             "spawnProcess",
             "spawnProcessWait",
             "getBufferText",
             "delay",
+            "setTimeout",
+            "setInterval",
+            "clearTimer",
+            "registerCompletionProvider",
+            "unregisterCompletionProvider",
             "sendLspRequest",
             "spawnBackgroundProcess",
             "killBackgroundProcess",
@@ -865,6 +910,7 @@ mod tests {
             "unloadPlugin",
             "reloadPlugin",
             "listPlugins",
+            "setPluginEnabled",
         ];
 
         let mut missing = Vec::new();
@@ -0,0 +1,51 @@
+//! Test harness for Fresh and Fresh plugins
+//!
+//! [`EditorTestHarness`] runs a real editor against a virtual terminal, so tests
+//! can type, press keys, let plugins run and assert on what ends up on screen.
+//! This is the harness the editor's own end-to-end tests use.
+//!
+//! A plugin integration test copies the plugin into the `plugins` directory of
+//! a temporary project and starts the harness there:
+//!
+//! ```ignore
+//! use crossterm::event::{KeyCode, KeyModifiers};
+//! use fresh_test_harness::{install_plugin, EditorTestHarness};
+//!
+//! #[test]
+//! fn my_plugin_shows_results() {
+//!     let project = tempfile::TempDir::new().unwrap();
+//!     let plugins_dir = project.path().join("plugins");
+//!     std::fs::create_dir(&plugins_dir).unwrap();
+//!     install_plugin(&plugins_dir, "my_plugin.ts".as_ref());
+//!
+//!     let mut harness = EditorTestHarness::with_working_dir(100, 30, project.path().into()).unwrap();
+//!     harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL).unwrap();
+//!     harness.type_text("My Plugin: Search").unwrap();
+//!     harness.send_key(KeyCode::Enter, KeyModifiers::NONE).unwrap();
+//!     harness.wait_for_screen_contains("results").unwrap();
+//!     harness.assert_no_plugin_errors();
+//! }
+//! ```
+//!
+//! For plugin logic that doesn't need a running editor, [`MOCK_EDITOR_TS`] is
+//! a TypeScript mock of `EditorAPI` that records calls and lets tests fire
+//! events and commands.
+
+pub mod fixtures;
+pub mod harness;
+pub mod scrollbar;
+
+pub use fixtures::TestFixture;
+pub use harness::{install_plugin, layout, strip_osc8, EditorTestHarness, HarnessOptions};
+
+/// TypeScript mock of `EditorAPI` (`mock_editor.ts`)
+///
+/// It references `fresh.d.ts` from the same directory.
+pub const MOCK_EDITOR_TS: &str = include_str!("../ts/mock_editor.ts");
+
+/// Write `mock_editor.ts` into `dir`, returning its path
+pub fn write_mock_editor(dir: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+    let path = dir.join("mock_editor.ts");
+    std::fs::write(&path, MOCK_EDITOR_TS)?;
+    Ok(path)
+}
@@ -0,0 +1,135 @@
+/// <reference path="./fresh.d.ts" />
+
+/**
+ * Mock EditorAPI for unit-testing plugin logic outside the editor
+ *
+ * Install it before loading the plugin, then drive the plugin through the
+ * events and commands it registered and inspect the calls it made:
+ *
+ * ```ts
+ * import { installMockEditor } from "./mock_editor.ts";
+ *
+ * const mock = installMockEditor();
+ * mock.stub("spawnProcess", () => mock.process({ stdout: "a.rs:1:1:hit\n", stderr: "", exit_code: 0 }));
+ * await import("./my_plugin.ts");
+ *
+ * await mock.runCommand("My Plugin: Search");
+ * assert(mock.callsTo("setStatus").length === 1);
+ * ```
+ *
+ * Every EditorAPI method exists on the mock. Unstubbed methods record the call
+ * and return `undefined`, except the few with built-in behaviour below
+ * (event/command registration, `t`, process spawning).
+ */
+
+/** A recorded call to an EditorAPI method */
+export interface MockCall {
+  method: string;
+  args: unknown[];
+}
+
+type Stub = (...args: unknown[]) => unknown;
+
+export interface MockEditor {
+  /** The object plugins receive from `getEditor()` */
+  readonly editor: EditorAPI;
+  /** Every call made through `editor`, in order */
+  readonly calls: MockCall[];
+  /** Calls made to one method */
+  callsTo(method: string): MockCall[];
+  /** Replace a method's implementation */
+  stub(method: string, impl: Stub): void;
+  /** Invoke the handlers registered for an event with `editor.on` */
+  emit(eventName: string, data?: unknown): Promise<unknown[]>;
+  /** Invoke the handler of a command registered with `editor.registerCommand` */
+  runCommand(name: string): Promise<unknown>;
+  /** Build a ProcessHandle that resolves to `result` */
+  process<T>(result: T): ProcessHandle<T>;
+  /** Forget recorded calls and registrations, keeping stubs */
+  reset(): void;
+}
+
+/**
+ * Create a mock editor and make `getEditor()` return it
+ */
+export function installMockEditor(): MockEditor {
+  const calls: MockCall[] = [];
+  const stubs = new Map<string, Stub>();
+  const handlers = new Map<string, string[]>();
+  const commands = new Map<string, string>();
+
+  const callHandler = (handlerName: string, ...args: unknown[]): unknown => {
+    const handler = (globalThis as Record<string, unknown>)[handlerName];
+    if (typeof handler !== "function") {
+      throw new Error(`Handler '${handlerName}' is not defined on globalThis`);
+    }
+    return handler(...args);
+  };
+
+  const process = <T>(result: T): ProcessHandle<T> => {
+    const promise = Promise.resolve(result);
+    return {
+      result: promise,
+      kill: () => Promise.resolve(false),
+      then: (onFulfilled, onRejected) => promise.then(onFulfilled, onRejected),
+    };
+  };
+
+  const builtins: Record<string, Stub> = {
+    on: (eventName, handlerName) => {
+      const list = handlers.get(eventName as string) ?? [];
+      list.push(handlerName as string);
+      handlers.set(eventName as string, list);
+    },
+    off: (eventName, handlerName) => {
+      const list = handlers.get(eventName as string) ?? [];
+      handlers.set(eventName as string, list.filter((h) => h !== handlerName));
+    },
+    registerCommand: (name, _description, handlerName) => {
+      commands.set(name as string, handlerName as string);
+      return true;
+    },
+    unregisterCommand: (name) => commands.delete(name as string),
+    t: (key) => key,
+    spawnProcess: () => process<SpawnResult>({ stdout: "", stderr: "", exit_code: 0 }),
+  };
+
+  const editor = new Proxy({} as EditorAPI, {
+    get(_target, property) {
+      if (typeof property !== "string" || property === "then") {
+        return undefined;
+      }
+      return (...args: unknown[]) => {
+        calls.push({ method: property, args });
+        const impl = stubs.get(property) ?? builtins[property];
+        return impl ? impl(...args) : undefined;
+      };
+    },
+  });
+
+  (globalThis as Record<string, unknown>).getEditor = () => editor;
+
+  return {
+    editor,
+    calls,
+    callsTo: (method) => calls.filter((call) => call.method === method),
+    stub: (method, impl) => {
+      stubs.set(method, impl);
+    },
+    emit: (eventName, data) =>
+      Promise.all((handlers.get(eventName) ?? []).map((h) => callHandler(h, data))),
+    runCommand: async (name) => {
+      const handlerName = commands.get(name);
+      if (handlerName === undefined) {
+        throw new Error(`Command '${name}' is not registered`);
+      }
+      return await callHandler(handlerName);
+    },
+    process,
+    reset: () => {
+      calls.length = 0;
+      handlers.clear();
+      commands.clear();
+    },
+  };
+}
@@ -47,13 +47,19 @@ export default defineConfig({
           { text: "Navigation", link: "/features/navigation" },
           { text: "File Explorer", link: "/features/file-explorer" },
           { text: "Search and Replace", link: "/features/search-replace" },
+          { text: "Data Files", link: "/features/data-files" },
+          { text: "Batch Scripts", link: "/features/batch" },
+          { text: "Remote Control", link: "/features/remote-control" },
           { text: "Integrated Terminal", link: "/features/terminal" },
           { text: "LSP Integration", link: "/features/lsp" },
+          { text: "Debugging", link: "/features/debugging" },
           { text: "Themes", link: "/features/themes" },
           { text: "Encoding", link: "/features/encoding" },
           { text: "Remote Editing (SSH)", link: "/features/ssh" },
           { text: "Session Persistence", link: "/features/session-persistence" },
+          { text: "Collaborative Editing", link: "/features/collaboration" },
           { text: "Keybinding Editor", link: "/features/keybinding-editor" },
+          { text: "Git", link: "/features/git" },
         ],
       },
       {
@@ -84,6 +90,7 @@ export default defineConfig({
               { text: "Common Patterns", link: "/plugins/development/patterns" },
               { text: "Utilities Library", link: "/plugins/development/utilities" },
               { text: "Internationalization", link: "/plugins/development/i18n" },
+              { text: "Native Plugins", link: "/plugins/development/native-plugins" },
             ],
           },
           {